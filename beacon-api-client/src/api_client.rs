@@ -56,11 +56,12 @@ async fn api_error_or_value<T: serde::de::DeserializeOwned>(
 }
 
 pub trait ClientTypes: Clone {
-    type SignedContributionAndProof: serde::Serialize;
+    type SignedContributionAndProof: serde::Serialize + serde::de::DeserializeOwned;
     type SyncCommitteeContribution: serde::Serialize + serde::de::DeserializeOwned;
     type BlindedBeaconBlock: serde::Serialize + serde::de::DeserializeOwned;
     type SignedBlindedBeaconBlock: serde::Serialize + serde::de::DeserializeOwned;
     type Attestation: serde::Serialize + serde::de::DeserializeOwned;
+    type ElectraAttestation: serde::Serialize + serde::de::DeserializeOwned;
     type AttesterSlashing: serde::Serialize + serde::de::DeserializeOwned;
     type BeaconBlock: serde::Serialize + serde::de::DeserializeOwned;
     type BeaconState: serde::Serialize + serde::de::DeserializeOwned;
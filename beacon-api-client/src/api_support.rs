@@ -0,0 +1,513 @@
+//! Support for the block publishing endpoints: `POST /eth/v{1,2}/beacon/{blocks,blinded_blocks}`.
+//!
+//! The v2 endpoints take the fork of the block in the `Eth-Consensus-Version` header and accept
+//! SSZ bodies, so decoding picks the fork from the header rather than guessing it from the shape
+//! of the JSON. The v1 endpoints only take JSON, decoded as the first fork whose type matches.
+use crate::types::BroadcastValidation;
+use ethereum_consensus::{
+    altair, bellatrix, capella, deneb, electra, phase0,
+    ssz::prelude::*,
+    types::{SignedBeaconBlock, SignedBlindedBeaconBlock},
+    Fork as Version,
+};
+use serde::de::IntoDeserializer;
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+pub const SSZ_CONTENT_TYPE: &str = "application/octet-stream";
+
+#[derive(Debug, Error)]
+pub enum PublishError {
+    #[error("a {block} block can not be published to `{path}`")]
+    WrongEndpoint { block: &'static str, path: &'static str },
+    #[error("{version} {block} blocks are not supported")]
+    UnsupportedVersion { version: Version, block: &'static str },
+    #[error("unknown consensus version `{0}`")]
+    UnknownVersion(String),
+    #[error("unknown broadcast validation `{0}`")]
+    UnknownBroadcastValidation(String),
+    #[error("`{0}` does not accept SSZ")]
+    SszNotAccepted(&'static str),
+    #[error("`{0}` requires the consensus version")]
+    MissingVersion(&'static str),
+    #[error("unknown publishing endpoint `{0}`")]
+    UnknownEndpoint(String),
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+    #[error("{0}")]
+    Serialize(#[from] SerializeError),
+    #[error("{0}")]
+    Deserialize(#[from] DeserializeError),
+}
+
+impl FromStr for BroadcastValidation {
+    type Err = PublishError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gossip" => Ok(Self::Gossip),
+            "consensus" => Ok(Self::Consensus),
+            "consensus_and_equivocation" => Ok(Self::ConsensusAndEquivocation),
+            other => Err(PublishError::UnknownBroadcastValidation(other.to_string())),
+        }
+    }
+}
+
+/// Parse the value of the `Eth-Consensus-Version` header.
+pub fn parse_version(value: &str) -> Result<Version, PublishError> {
+    let deserializer: serde::de::value::StrDeserializer<'_, serde::de::value::Error> =
+        value.into_deserializer();
+    <Version as serde::Deserialize>::deserialize(deserializer)
+        .map_err(|_| PublishError::UnknownVersion(value.to_string()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Json,
+    Ssz,
+}
+
+impl Encoding {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json => JSON_CONTENT_TYPE,
+            Self::Ssz => SSZ_CONTENT_TYPE,
+        }
+    }
+}
+
+/// An endpoint publishing blocks, with the `broadcast_validation` query of the v2 endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublishEndpoint {
+    Blocks,
+    BlindedBlocks,
+    BlocksV2(BroadcastValidation),
+    BlindedBlocksV2(BroadcastValidation),
+}
+
+impl PublishEndpoint {
+    /// Select an endpoint from its path and the value of its `broadcast_validation` query, if
+    /// given.
+    pub fn parse(path: &str, broadcast_validation: Option<&str>) -> Result<Self, PublishError> {
+        let broadcast_validation =
+            broadcast_validation.map(BroadcastValidation::from_str).transpose()?;
+        let endpoint = match path.trim_start_matches('/') {
+            "eth/v1/beacon/blocks" => Self::Blocks,
+            "eth/v1/beacon/blinded_blocks" => Self::BlindedBlocks,
+            "eth/v2/beacon/blocks" => Self::BlocksV2(broadcast_validation.unwrap_or_default()),
+            "eth/v2/beacon/blinded_blocks" => {
+                Self::BlindedBlocksV2(broadcast_validation.unwrap_or_default())
+            }
+            other => return Err(PublishError::UnknownEndpoint(other.to_string())),
+        };
+        Ok(endpoint)
+    }
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            Self::Blocks => "eth/v1/beacon/blocks",
+            Self::BlindedBlocks => "eth/v1/beacon/blinded_blocks",
+            Self::BlocksV2(_) => "eth/v2/beacon/blocks",
+            Self::BlindedBlocksV2(_) => "eth/v2/beacon/blinded_blocks",
+        }
+    }
+
+    pub fn is_blinded(&self) -> bool {
+        matches!(self, Self::BlindedBlocks | Self::BlindedBlocksV2(_))
+    }
+
+    pub fn broadcast_validation(&self) -> Option<BroadcastValidation> {
+        match self {
+            Self::BlocksV2(validation) | Self::BlindedBlocksV2(validation) => Some(*validation),
+            _ => None,
+        }
+    }
+
+    // The v2 endpoints take the version header and SSZ bodies.
+    fn is_v2(&self) -> bool {
+        self.broadcast_validation().is_some()
+    }
+}
+
+/// A block encoded for a publishing endpoint, with the value of its `Eth-Consensus-Version`
+/// header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodedBlock {
+    pub body: Vec<u8>,
+    pub content_type: &'static str,
+    pub version: String,
+}
+
+/// A signed block to publish, either full or blinded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PublishableBlock<
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+> {
+    Full(
+        SignedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    ),
+    Blinded(
+        SignedBlindedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    ),
+}
+
+impl<
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+    >
+    PublishableBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >
+{
+    pub fn version(&self) -> Version {
+        match self {
+            Self::Full(block) => block.version(),
+            Self::Blinded(block) => block.version(),
+        }
+    }
+
+    pub fn is_blinded(&self) -> bool {
+        matches!(self, Self::Blinded(_))
+    }
+
+    fn kind(blinded: bool) -> &'static str {
+        if blinded {
+            "blinded"
+        } else {
+            "full"
+        }
+    }
+
+    /// Encode the block as the body of a request to `endpoint`.
+    pub fn encode(
+        &self,
+        endpoint: PublishEndpoint,
+        encoding: Encoding,
+    ) -> Result<EncodedBlock, PublishError> {
+        if self.is_blinded() != endpoint.is_blinded() {
+            return Err(PublishError::WrongEndpoint {
+                block: Self::kind(self.is_blinded()),
+                path: endpoint.path(),
+            })
+        }
+        let body = match (encoding, self) {
+            (Encoding::Ssz, _) if !endpoint.is_v2() => {
+                return Err(PublishError::SszNotAccepted(endpoint.path()))
+            }
+            (Encoding::Ssz, Self::Full(block)) => serialize(block)?,
+            (Encoding::Ssz, Self::Blinded(block)) => serialize(block)?,
+            (Encoding::Json, Self::Full(block)) => serde_json::to_vec(block)?,
+            (Encoding::Json, Self::Blinded(block)) => serde_json::to_vec(block)?,
+        };
+        Ok(EncodedBlock {
+            body,
+            content_type: encoding.content_type(),
+            version: self.version().to_string(),
+        })
+    }
+
+    /// Decode the body of a request to `endpoint`, given the value of its `Eth-Consensus-Version`
+    /// header if any.
+    pub fn decode(
+        endpoint: PublishEndpoint,
+        encoding: Encoding,
+        version: Option<&str>,
+        body: &[u8],
+    ) -> Result<Self, PublishError> {
+        let version = version.map(parse_version).transpose()?;
+        let blinded = endpoint.is_blinded();
+        let version = match (encoding, version) {
+            (Encoding::Ssz, _) if !endpoint.is_v2() => {
+                return Err(PublishError::SszNotAccepted(endpoint.path()))
+            }
+            (_, Some(version)) => version,
+            (Encoding::Json, None) if !endpoint.is_v2() => {
+                let block = if blinded {
+                    Self::Blinded(serde_json::from_slice(body)?)
+                } else {
+                    Self::Full(serde_json::from_slice(body)?)
+                };
+                return Ok(block)
+            }
+            _ => return Err(PublishError::MissingVersion(endpoint.path())),
+        };
+        let unsupported = PublishError::UnsupportedVersion { version, block: Self::kind(blinded) };
+        let block = match (encoding, blinded) {
+            (Encoding::Json, false) => Self::Full(match version {
+                Version::Phase0 => SignedBeaconBlock::Phase0(serde_json::from_slice(body)?),
+                Version::Altair => SignedBeaconBlock::Altair(serde_json::from_slice(body)?),
+                Version::Bellatrix => SignedBeaconBlock::Bellatrix(serde_json::from_slice(body)?),
+                Version::Capella => SignedBeaconBlock::Capella(serde_json::from_slice(body)?),
+                Version::Deneb => SignedBeaconBlock::Deneb(serde_json::from_slice(body)?),
+                Version::Electra => SignedBeaconBlock::Electra(serde_json::from_slice(body)?),
+            }),
+            (Encoding::Json, true) => Self::Blinded(match version {
+                Version::Bellatrix => {
+                    SignedBlindedBeaconBlock::Bellatrix(serde_json::from_slice(body)?)
+                }
+                Version::Capella => {
+                    SignedBlindedBeaconBlock::Capella(serde_json::from_slice(body)?)
+                }
+                Version::Deneb => SignedBlindedBeaconBlock::Deneb(serde_json::from_slice(body)?),
+                Version::Electra => {
+                    SignedBlindedBeaconBlock::Electra(serde_json::from_slice(body)?)
+                }
+                _ => return Err(unsupported),
+            }),
+            (Encoding::Ssz, false) => Self::Full(match version {
+                Version::Phase0 => {
+                    SignedBeaconBlock::Phase0(phase0::SignedBeaconBlock::deserialize(body)?)
+                }
+                Version::Altair => {
+                    SignedBeaconBlock::Altair(altair::SignedBeaconBlock::deserialize(body)?)
+                }
+                Version::Bellatrix => {
+                    SignedBeaconBlock::Bellatrix(bellatrix::SignedBeaconBlock::deserialize(body)?)
+                }
+                Version::Capella => {
+                    SignedBeaconBlock::Capella(capella::SignedBeaconBlock::deserialize(body)?)
+                }
+                Version::Deneb => {
+                    SignedBeaconBlock::Deneb(deneb::SignedBeaconBlock::deserialize(body)?)
+                }
+                Version::Electra => {
+                    SignedBeaconBlock::Electra(electra::SignedBeaconBlock::deserialize(body)?)
+                }
+            }),
+            (Encoding::Ssz, true) => Self::Blinded(match version {
+                Version::Bellatrix => SignedBlindedBeaconBlock::Bellatrix(
+                    bellatrix::SignedBlindedBeaconBlock::deserialize(body)?,
+                ),
+                Version::Capella => SignedBlindedBeaconBlock::Capella(
+                    capella::SignedBlindedBeaconBlock::deserialize(body)?,
+                ),
+                Version::Deneb => SignedBlindedBeaconBlock::Deneb(
+                    deneb::SignedBlindedBeaconBlock::deserialize(body)?,
+                ),
+                Version::Electra => SignedBlindedBeaconBlock::Electra(
+                    electra::SignedBlindedBeaconBlock::deserialize(body)?,
+                ),
+                _ => return Err(unsupported),
+            }),
+        };
+        Ok(block)
+    }
+}
+
+impl fmt::Display for PublishEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.broadcast_validation() {
+            Some(validation) => write!(f, "{}?broadcast_validation={validation}", self.path()),
+            None => write!(f, "{}", self.path()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presets::mainnet::PublishableBlock;
+    use ethereum_consensus::deneb::mainnet as deneb;
+
+    fn deneb_blocks() -> [PublishableBlock; 2] {
+        let mut full = deneb::SignedBeaconBlock::default();
+        full.message.slot = 42;
+        full.message.body.execution_payload.gas_limit = 30_000_000;
+        let mut blinded = deneb::SignedBlindedBeaconBlock::default();
+        blinded.message.slot = 42;
+        blinded.message.body.execution_payload_header.gas_limit = 30_000_000;
+        [
+            PublishableBlock::Full(SignedBeaconBlock::Deneb(full)),
+            PublishableBlock::Blinded(SignedBlindedBeaconBlock::Deneb(blinded)),
+        ]
+    }
+
+    #[test]
+    fn test_round_trip_through_each_endpoint() {
+        let validation = BroadcastValidation::ConsensusAndEquivocation;
+        for block in deneb_blocks() {
+            let (v1, v2) = if block.is_blinded() {
+                (PublishEndpoint::BlindedBlocks, PublishEndpoint::BlindedBlocksV2(validation))
+            } else {
+                (PublishEndpoint::Blocks, PublishEndpoint::BlocksV2(validation))
+            };
+            for (endpoint, encoding) in
+                [(v1, Encoding::Json), (v2, Encoding::Json), (v2, Encoding::Ssz)]
+            {
+                let encoded = block.encode(endpoint, encoding).unwrap();
+                assert_eq!(encoded.version, "deneb");
+                assert_eq!(encoded.content_type, encoding.content_type());
+                let decoded = PublishableBlock::decode(
+                    endpoint,
+                    encoding,
+                    Some(&encoded.version),
+                    &encoded.body,
+                )
+                .unwrap();
+                assert_eq!(decoded, block);
+            }
+            // v1 has no version header
+            let encoded = block.encode(v1, Encoding::Json).unwrap();
+            let decoded =
+                PublishableBlock::decode(v1, Encoding::Json, None, &encoded.body).unwrap();
+            assert_eq!(decoded.is_blinded(), block.is_blinded());
+            assert!(matches!(
+                block.encode(v1, Encoding::Ssz),
+                Err(PublishError::SszNotAccepted(
+                    "eth/v1/beacon/blocks" | "eth/v1/beacon/blinded_blocks"
+                ))
+            ));
+            let encoded = block.encode(v2, Encoding::Ssz).unwrap();
+            assert!(matches!(
+                PublishableBlock::decode(v2, Encoding::Ssz, None, &encoded.body),
+                Err(PublishError::MissingVersion(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_blinded_block_to_full_endpoint_is_an_error() {
+        let [full, blinded] = deneb_blocks();
+        for endpoint in [PublishEndpoint::Blocks, PublishEndpoint::BlocksV2(Default::default())] {
+            assert!(matches!(
+                blinded.encode(endpoint, Encoding::Json),
+                Err(PublishError::WrongEndpoint { block: "blinded", .. })
+            ));
+        }
+        assert!(matches!(
+            full.encode(PublishEndpoint::BlindedBlocks, Encoding::Json),
+            Err(PublishError::WrongEndpoint { block: "full", .. })
+        ));
+
+        let encoded = full.encode(PublishEndpoint::Blocks, Encoding::Json).unwrap();
+        let endpoint = PublishEndpoint::BlindedBlocksV2(Default::default());
+        assert!(PublishableBlock::decode(endpoint, Encoding::Json, Some("phase0"), &encoded.body)
+            .is_err());
+        assert!(matches!(
+            PublishableBlock::decode(endpoint, Encoding::Json, Some("electra"), &encoded.body),
+            Err(PublishError::UnsupportedVersion { version: Version::Electra, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_endpoint() {
+        assert_eq!(
+            PublishEndpoint::parse("/eth/v2/beacon/blocks", None).unwrap(),
+            PublishEndpoint::BlocksV2(BroadcastValidation::Gossip)
+        );
+        let endpoint =
+            PublishEndpoint::parse("eth/v2/beacon/blinded_blocks", Some("consensus")).unwrap();
+        assert_eq!(endpoint, PublishEndpoint::BlindedBlocksV2(BroadcastValidation::Consensus));
+        assert_eq!(
+            endpoint.to_string(),
+            "eth/v2/beacon/blinded_blocks?broadcast_validation=consensus"
+        );
+        assert!(matches!(
+            PublishEndpoint::parse("eth/v2/beacon/blocks", Some("none")),
+            Err(PublishError::UnknownBroadcastValidation(_))
+        ));
+        assert!(matches!(parse_version("fulu"), Err(PublishError::UnknownVersion(_))));
+        assert_eq!(parse_version("capella").unwrap(), Version::Capella);
+    }
+}
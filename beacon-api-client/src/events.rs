@@ -0,0 +1,533 @@
+//! Payloads of the topics of the event stream: `GET /eth/v1/events`.
+//!
+//! Each event names its topic and carries a JSON payload, decoded by [`parse_event`] into a
+//! [`BeaconEvent`]. Attestations changed shape in electra, so their events carry the fork
+//! alongside the payload; events without a fork are taken to be from before electra.
+use crate::{ClientTypes, Error, PayloadAttributesEvent, VersionedValue};
+use ethereum_consensus::{
+    capella::SignedBlsToExecutionChange,
+    deneb::polynomial_commitments::{KzgCommitment, VersionedHash},
+    phase0::{ProposerSlashing, SignedVoluntaryExit},
+    primitives::{Epoch, Root, Slot},
+    Fork,
+};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventTopic {
+    Head,
+    Block,
+    BlockGossip,
+    Attestation,
+    VoluntaryExit,
+    BlsToExecutionChange,
+    ProposerSlashing,
+    AttesterSlashing,
+    FinalizedCheckpoint,
+    ChainReorg,
+    ContributionAndProof,
+    LightClientFinalityUpdate,
+    LightClientOptimisticUpdate,
+    PayloadAttributes,
+    BlobSidecar,
+}
+
+impl EventTopic {
+    pub const ALL: [Self; 15] = [
+        Self::Head,
+        Self::Block,
+        Self::BlockGossip,
+        Self::Attestation,
+        Self::VoluntaryExit,
+        Self::BlsToExecutionChange,
+        Self::ProposerSlashing,
+        Self::AttesterSlashing,
+        Self::FinalizedCheckpoint,
+        Self::ChainReorg,
+        Self::ContributionAndProof,
+        Self::LightClientFinalityUpdate,
+        Self::LightClientOptimisticUpdate,
+        Self::PayloadAttributes,
+        Self::BlobSidecar,
+    ];
+
+    /// The name of the topic in the `topics` query of the event stream.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Head => "head",
+            Self::Block => "block",
+            Self::BlockGossip => "block_gossip",
+            Self::Attestation => "attestation",
+            Self::VoluntaryExit => "voluntary_exit",
+            Self::BlsToExecutionChange => "bls_to_execution_change",
+            Self::ProposerSlashing => "proposer_slashing",
+            Self::AttesterSlashing => "attester_slashing",
+            Self::FinalizedCheckpoint => "finalized_checkpoint",
+            Self::ChainReorg => "chain_reorg",
+            Self::ContributionAndProof => "contribution_and_proof",
+            Self::LightClientFinalityUpdate => "light_client_finality_update",
+            Self::LightClientOptimisticUpdate => "light_client_optimistic_update",
+            Self::PayloadAttributes => "payload_attributes",
+            Self::BlobSidecar => "blob_sidecar",
+        }
+    }
+}
+
+impl fmt::Display for EventTopic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for EventTopic {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|topic| topic.name() == s)
+            .ok_or_else(|| Error::UnknownEventTopic(s.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeadEvent {
+    #[serde(with = "crate::serde::as_str")]
+    pub slot: Slot,
+    pub block: Root,
+    pub state: Root,
+    pub epoch_transition: bool,
+    pub previous_duty_dependent_root: Root,
+    pub current_duty_dependent_root: Root,
+    pub execution_optimistic: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockEvent {
+    #[serde(with = "crate::serde::as_str")]
+    pub slot: Slot,
+    pub block: Root,
+    pub execution_optimistic: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockGossipEvent {
+    #[serde(with = "crate::serde::as_str")]
+    pub slot: Slot,
+    pub block: Root,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalizedCheckpointEvent {
+    pub block: Root,
+    pub state: Root,
+    #[serde(with = "crate::serde::as_str")]
+    pub epoch: Epoch,
+    pub execution_optimistic: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainReorgEvent {
+    #[serde(with = "crate::serde::as_str")]
+    pub slot: Slot,
+    #[serde(with = "crate::serde::as_str")]
+    pub depth: u64,
+    pub old_head_block: Root,
+    pub new_head_block: Root,
+    pub old_head_state: Root,
+    pub new_head_state: Root,
+    #[serde(with = "crate::serde::as_str")]
+    pub epoch: Epoch,
+    pub execution_optimistic: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobSidecarEvent {
+    pub block_root: Root,
+    #[serde(with = "crate::serde::as_str")]
+    pub index: u64,
+    #[serde(with = "crate::serde::as_str")]
+    pub slot: Slot,
+    pub kzg_commitment: KzgCommitment,
+    pub versioned_hash: VersionedHash,
+}
+
+/// An attestation seen on gossip, in the shape of the fork it was made in.
+pub enum AttestationEvent<C: ClientTypes> {
+    /// An attestation from phase0 up to deneb.
+    Phase0(C::Attestation),
+    Electra(C::ElectraAttestation),
+}
+
+pub enum BeaconEvent<C: ClientTypes> {
+    Head(HeadEvent),
+    Block(BlockEvent),
+    BlockGossip(BlockGossipEvent),
+    Attestation(AttestationEvent<C>),
+    VoluntaryExit(SignedVoluntaryExit),
+    BlsToExecutionChange(SignedBlsToExecutionChange),
+    ProposerSlashing(ProposerSlashing),
+    AttesterSlashing(C::AttesterSlashing),
+    FinalizedCheckpoint(FinalizedCheckpointEvent),
+    ChainReorg(ChainReorgEvent),
+    ContributionAndProof(C::SignedContributionAndProof),
+    LightClientFinalityUpdate(VersionedValue<C::LightClientFinalityUpdate>),
+    LightClientOptimisticUpdate(VersionedValue<C::LightClientOptimisticUpdate>),
+    PayloadAttributes(VersionedValue<PayloadAttributesEvent>),
+    BlobSidecar(BlobSidecarEvent),
+}
+
+impl<C: ClientTypes> BeaconEvent<C> {
+    pub fn topic(&self) -> EventTopic {
+        match self {
+            Self::Head(_) => EventTopic::Head,
+            Self::Block(_) => EventTopic::Block,
+            Self::BlockGossip(_) => EventTopic::BlockGossip,
+            Self::Attestation(_) => EventTopic::Attestation,
+            Self::VoluntaryExit(_) => EventTopic::VoluntaryExit,
+            Self::BlsToExecutionChange(_) => EventTopic::BlsToExecutionChange,
+            Self::ProposerSlashing(_) => EventTopic::ProposerSlashing,
+            Self::AttesterSlashing(_) => EventTopic::AttesterSlashing,
+            Self::FinalizedCheckpoint(_) => EventTopic::FinalizedCheckpoint,
+            Self::ChainReorg(_) => EventTopic::ChainReorg,
+            Self::ContributionAndProof(_) => EventTopic::ContributionAndProof,
+            Self::LightClientFinalityUpdate(_) => EventTopic::LightClientFinalityUpdate,
+            Self::LightClientOptimisticUpdate(_) => EventTopic::LightClientOptimisticUpdate,
+            Self::PayloadAttributes(_) => EventTopic::PayloadAttributes,
+            Self::BlobSidecar(_) => EventTopic::BlobSidecar,
+        }
+    }
+}
+
+fn parse_attestation<C: ClientTypes>(data: &str) -> Result<AttestationEvent<C>, Error> {
+    let value: serde_json::Value = serde_json::from_str(data)?;
+    if value.get("version").is_none() {
+        return Ok(AttestationEvent::Phase0(serde_json::from_value(value)?))
+    }
+    let versioned: VersionedValue<serde_json::Value> = serde_json::from_value(value)?;
+    let event = if versioned.version >= Fork::Electra {
+        AttestationEvent::Electra(serde_json::from_value(versioned.data)?)
+    } else {
+        AttestationEvent::Phase0(serde_json::from_value(versioned.data)?)
+    };
+    Ok(event)
+}
+
+/// Decode the `data` of an event from the stream of `topic`.
+pub fn parse_event<C: ClientTypes>(topic: &str, data: &str) -> Result<BeaconEvent<C>, Error> {
+    let event = match topic.parse()? {
+        EventTopic::Head => BeaconEvent::Head(serde_json::from_str(data)?),
+        EventTopic::Block => BeaconEvent::Block(serde_json::from_str(data)?),
+        EventTopic::BlockGossip => BeaconEvent::BlockGossip(serde_json::from_str(data)?),
+        EventTopic::Attestation => BeaconEvent::Attestation(parse_attestation(data)?),
+        EventTopic::VoluntaryExit => BeaconEvent::VoluntaryExit(serde_json::from_str(data)?),
+        EventTopic::BlsToExecutionChange => {
+            BeaconEvent::BlsToExecutionChange(serde_json::from_str(data)?)
+        }
+        EventTopic::ProposerSlashing => BeaconEvent::ProposerSlashing(serde_json::from_str(data)?),
+        EventTopic::AttesterSlashing => BeaconEvent::AttesterSlashing(serde_json::from_str(data)?),
+        EventTopic::FinalizedCheckpoint => {
+            BeaconEvent::FinalizedCheckpoint(serde_json::from_str(data)?)
+        }
+        EventTopic::ChainReorg => BeaconEvent::ChainReorg(serde_json::from_str(data)?),
+        EventTopic::ContributionAndProof => {
+            BeaconEvent::ContributionAndProof(serde_json::from_str(data)?)
+        }
+        EventTopic::LightClientFinalityUpdate => {
+            BeaconEvent::LightClientFinalityUpdate(serde_json::from_str(data)?)
+        }
+        EventTopic::LightClientOptimisticUpdate => {
+            BeaconEvent::LightClientOptimisticUpdate(serde_json::from_str(data)?)
+        }
+        EventTopic::PayloadAttributes => {
+            BeaconEvent::PayloadAttributes(serde_json::from_str(data)?)
+        }
+        EventTopic::BlobSidecar => BeaconEvent::BlobSidecar(serde_json::from_str(data)?),
+    };
+    Ok(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presets::minimal::{self, MinimalClientTypes};
+    use ethereum_consensus::{altair::minimal as altair, phase0::minimal as phase0};
+    use serde_json::json;
+
+    fn hex(byte: u8, len: usize) -> String {
+        format!("0x{}", hex::encode(vec![byte; len]))
+    }
+
+    fn parse(topic: &str, data: serde_json::Value) -> minimal::BeaconEvent {
+        let event = parse_event::<MinimalClientTypes>(topic, &data.to_string()).unwrap();
+        assert_eq!(event.topic().name(), topic);
+        event
+    }
+
+    fn attestation_data() -> serde_json::Value {
+        let checkpoint = |epoch: &str| json!({"epoch": epoch, "root": hex(0xcc, 32)});
+        json!({
+            "slot": "9",
+            "index": "0",
+            "beacon_block_root": hex(0xaa, 32),
+            "source": checkpoint("0"),
+            "target": checkpoint("1"),
+        })
+    }
+
+    #[test]
+    fn test_topic_names() {
+        for topic in EventTopic::ALL {
+            assert_eq!(topic.to_string().parse::<EventTopic>().unwrap(), topic);
+        }
+        assert_eq!("block_gossip".parse::<EventTopic>().unwrap(), EventTopic::BlockGossip);
+        assert!(matches!("blocks".parse::<EventTopic>(), Err(Error::UnknownEventTopic(_))));
+        assert!(matches!(
+            parse_event::<MinimalClientTypes>("execution_payload", "{}"),
+            Err(Error::UnknownEventTopic(_))
+        ));
+    }
+
+    #[test]
+    fn test_block_events() {
+        let data = json!({
+            "slot": "10",
+            "block": hex(0x9a, 32),
+            "state": hex(0x60, 32),
+            "epoch_transition": false,
+            "previous_duty_dependent_root": hex(0x5e, 32),
+            "current_duty_dependent_root": hex(0x5f, 32),
+            "execution_optimistic": true,
+        });
+        let BeaconEvent::Head(head) = parse("head", data) else { panic!("not a head event") };
+        assert_eq!(head.slot, 10);
+        assert_eq!(head.state, Root::try_from([0x60; 32].as_ref()).unwrap());
+        assert!(!head.epoch_transition);
+        assert!(head.execution_optimistic);
+
+        let data = json!({"slot": "10", "block": hex(0x9a, 32), "execution_optimistic": false});
+        let BeaconEvent::Block(block) = parse("block", data) else { panic!("not a block event") };
+        assert_eq!(block.slot, 10);
+        assert_eq!(block.block, head.block);
+
+        let data = json!({"slot": "11", "block": hex(0x9b, 32)});
+        let BeaconEvent::BlockGossip(block) = parse("block_gossip", data) else {
+            panic!("not a block gossip event")
+        };
+        assert_eq!(block.slot, 11);
+
+        let data = json!({
+            "block": hex(0x9a, 32),
+            "state": hex(0x60, 32),
+            "epoch": "2",
+            "execution_optimistic": false,
+        });
+        let BeaconEvent::FinalizedCheckpoint(checkpoint) = parse("finalized_checkpoint", data)
+        else {
+            panic!("not a finalized checkpoint event")
+        };
+        assert_eq!(checkpoint.epoch, 2);
+
+        let data = json!({
+            "slot": "200",
+            "depth": "50",
+            "old_head_block": hex(0x01, 32),
+            "new_head_block": hex(0x02, 32),
+            "old_head_state": hex(0x03, 32),
+            "new_head_state": hex(0x04, 32),
+            "epoch": "25",
+            "execution_optimistic": false,
+        });
+        let BeaconEvent::ChainReorg(reorg) = parse("chain_reorg", data) else {
+            panic!("not a chain reorg event")
+        };
+        assert_eq!((reorg.slot, reorg.depth, reorg.epoch), (200, 50, 25));
+        assert_ne!(reorg.old_head_block, reorg.new_head_block);
+
+        let data = json!({
+            "block_root": hex(0x9a, 32),
+            "index": "1",
+            "slot": "10",
+            "kzg_commitment": hex(0xc0, 48),
+            "versioned_hash": hex(0x01, 32),
+        });
+        let BeaconEvent::BlobSidecar(sidecar) = parse("blob_sidecar", data) else {
+            panic!("not a blob sidecar event")
+        };
+        assert_eq!((sidecar.index, sidecar.slot), (1, 10));
+        assert_eq!(sidecar.kzg_commitment, KzgCommitment::try_from([0xc0; 48].as_ref()).unwrap());
+    }
+
+    #[test]
+    fn test_attestation_events() {
+        let attestation = json!({
+            "aggregation_bits": "0x0b",
+            "data": attestation_data(),
+            "signature": hex(0x11, 96),
+        });
+        let BeaconEvent::Attestation(AttestationEvent::Phase0(unversioned)) =
+            parse("attestation", attestation.clone())
+        else {
+            panic!("not a phase0 attestation event")
+        };
+        assert_eq!(unversioned.data.slot, 9);
+        assert_eq!(unversioned.aggregation_bits.len(), 3);
+
+        let data = json!({"version": "deneb", "data": attestation.clone()});
+        let BeaconEvent::Attestation(AttestationEvent::Phase0(versioned)) =
+            parse("attestation", data)
+        else {
+            panic!("not a phase0 attestation event")
+        };
+        assert_eq!(versioned, unversioned);
+
+        let data = json!({
+            "version": "electra",
+            "data": {
+                "aggregation_bits": "0x0b",
+                "data": attestation_data(),
+                "signature": hex(0x11, 96),
+                "committee_bits": "0x02",
+            },
+        });
+        let BeaconEvent::Attestation(AttestationEvent::Electra(attestation)) =
+            parse("attestation", data)
+        else {
+            panic!("not an electra attestation event")
+        };
+        assert_eq!(attestation.data.target.epoch, 1);
+        assert_eq!(attestation.committee_bits.iter().filter(|bit| **bit).count(), 1);
+        assert!(attestation.committee_bits[1]);
+
+        // the payload is decoded in the shape of the fork it names
+        let data = json!({"version": "electra", "data": attestation});
+        assert!(matches!(
+            parse_event::<MinimalClientTypes>("attestation", &data.to_string()),
+            Err(Error::Json(_))
+        ));
+    }
+
+    #[test]
+    fn test_operation_events() {
+        let data = json!({
+            "message": {"epoch": "1", "validator_index": "7"},
+            "signature": hex(0x11, 96),
+        });
+        let BeaconEvent::VoluntaryExit(exit) = parse("voluntary_exit", data) else {
+            panic!("not a voluntary exit event")
+        };
+        assert_eq!((exit.message.epoch, exit.message.validator_index), (1, 7));
+
+        let data = json!({
+            "message": {
+                "validator_index": "7",
+                "from_bls_pubkey": hex(0x22, 48),
+                "to_execution_address": hex(0x33, 20),
+            },
+            "signature": hex(0x11, 96),
+        });
+        let BeaconEvent::BlsToExecutionChange(change) = parse("bls_to_execution_change", data)
+        else {
+            panic!("not a bls to execution change event")
+        };
+        assert_eq!(change.message.validator_index, 7);
+
+        let header = |body: u8| {
+            json!({
+                "message": {
+                    "slot": "9",
+                    "proposer_index": "3",
+                    "parent_root": hex(0xaa, 32),
+                    "state_root": hex(0xbb, 32),
+                    "body_root": hex(body, 32),
+                },
+                "signature": hex(0x11, 96),
+            })
+        };
+        let data = json!({"signed_header_1": header(1), "signed_header_2": header(2)});
+        let BeaconEvent::ProposerSlashing(slashing) = parse("proposer_slashing", data) else {
+            panic!("not a proposer slashing event")
+        };
+        assert_eq!(slashing.signed_header_1.message.proposer_index, 3);
+        assert_ne!(slashing.signed_header_1.message, slashing.signed_header_2.message);
+
+        let mut attester_slashing = phase0::AttesterSlashing::default();
+        attester_slashing.attestation_1.data.slot = 9;
+        let data = serde_json::to_value(&attester_slashing).unwrap();
+        let BeaconEvent::AttesterSlashing(slashing) = parse("attester_slashing", data) else {
+            panic!("not an attester slashing event")
+        };
+        assert_eq!(slashing, attester_slashing);
+
+        let mut contribution = altair::SignedContributionAndProof::default();
+        contribution.message.aggregator_index = 5;
+        contribution.message.contribution.slot = 9;
+        let data = serde_json::to_value(&contribution).unwrap();
+        let BeaconEvent::ContributionAndProof(event) = parse("contribution_and_proof", data) else {
+            panic!("not a contribution and proof event")
+        };
+        assert_eq!(event.message.aggregator_index, 5);
+        assert_eq!(event.message.contribution.slot, 9);
+    }
+
+    #[test]
+    fn test_light_client_events() {
+        let mut update = altair::LightClientFinalityUpdate::default();
+        update.signature_slot = 9;
+        let data = json!({"version": "altair", "data": update});
+        let BeaconEvent::LightClientFinalityUpdate(event) =
+            parse("light_client_finality_update", data)
+        else {
+            panic!("not a light client finality update event")
+        };
+        assert_eq!(event.version, Fork::Altair);
+        assert_eq!(event.data.signature_slot, 9);
+
+        let mut update = altair::LightClientOptimisticUpdate::default();
+        update.signature_slot = 10;
+        let data = json!({"version": "altair", "data": update});
+        let BeaconEvent::LightClientOptimisticUpdate(event) =
+            parse("light_client_optimistic_update", data)
+        else {
+            panic!("not a light client optimistic update event")
+        };
+        assert_eq!(event.data.signature_slot, 10);
+    }
+
+    #[test]
+    fn test_payload_attributes_event() {
+        let data = json!({
+            "version": "capella",
+            "data": {
+                "proposer_index": "123",
+                "proposal_slot": "10",
+                "parent_block_number": "9",
+                "parent_block_root": hex(0xaa, 32),
+                "parent_block_hash": hex(0xbb, 32),
+                "payload_attributes": {
+                    "timestamp": "123456",
+                    "prev_randao": hex(0xcc, 32),
+                    "suggested_fee_recipient": hex(0xdd, 20),
+                    "withdrawals": [{
+                        "index": "5",
+                        "validator_index": "10",
+                        "address": hex(0xee, 20),
+                        "amount": "15640",
+                    }],
+                },
+            },
+        });
+        let BeaconEvent::PayloadAttributes(event) = parse("payload_attributes", data) else {
+            panic!("not a payload attributes event")
+        };
+        assert_eq!(event.version, Fork::Capella);
+        assert_eq!((event.data.proposer_index, event.data.proposal_slot), (123, 10));
+
+        let data = json!({"slot": "10", "block": hex(0x9a, 32)});
+        assert!(matches!(
+            parse_event::<MinimalClientTypes>("payload_attributes", &data.to_string()),
+            Err(Error::Json(_))
+        ));
+    }
+}
@@ -1,6 +1,8 @@
 mod api_client;
 mod api_error;
+pub mod api_support;
 mod cli;
+mod events;
 mod serde;
 mod types;
 
@@ -8,6 +10,7 @@ pub use api_client::*;
 pub use api_error::*;
 pub use cli::*;
 pub use error::*;
+pub use events::*;
 pub use presets::*;
 pub use types::*;
 
@@ -30,6 +33,8 @@ mod error {
         MissingExpectedData(String),
         #[error("json error: {0}")]
         Json(#[from] serde_json::Error),
+        #[error("unknown event topic `{0}`")]
+        UnknownEventTopic(String),
     }
 }
 
@@ -37,7 +42,10 @@ pub mod presets {
     pub mod mainnet {
         use ethereum_consensus::{
             altair::mainnet as altair,
+            bellatrix::mainnet as bellatrix,
+            capella::mainnet as capella,
             deneb::mainnet as deneb,
+            electra::mainnet as electra,
             phase0::mainnet as phase0,
             types::mainnet::{
                 BeaconBlock, BeaconState, BlindedBeaconBlock, SignedBeaconBlock,
@@ -54,6 +62,7 @@ pub mod presets {
             type BlindedBeaconBlock = BlindedBeaconBlock;
             type SignedBlindedBeaconBlock = SignedBlindedBeaconBlock;
             type Attestation = phase0::Attestation;
+            type ElectraAttestation = electra::Attestation;
             type AttesterSlashing = phase0::AttesterSlashing;
             type BeaconBlock = BeaconBlock;
             type BeaconState = BeaconState;
@@ -67,11 +76,40 @@ pub mod presets {
         }
 
         pub type Client = crate::Client<MainnetClientTypes>;
+
+        pub type BeaconEvent = crate::BeaconEvent<MainnetClientTypes>;
+
+        pub type PublishableBlock = crate::api_support::PublishableBlock<
+            { phase0::MAX_PROPOSER_SLASHINGS },
+            { phase0::MAX_VALIDATORS_PER_COMMITTEE },
+            { phase0::MAX_ATTESTER_SLASHINGS },
+            { phase0::MAX_ATTESTATIONS },
+            { phase0::MAX_DEPOSITS },
+            { phase0::MAX_VOLUNTARY_EXITS },
+            { altair::SYNC_COMMITTEE_SIZE },
+            { bellatrix::BYTES_PER_LOGS_BLOOM },
+            { bellatrix::MAX_EXTRA_DATA_BYTES },
+            { bellatrix::MAX_BYTES_PER_TRANSACTION },
+            { bellatrix::MAX_TRANSACTIONS_PER_PAYLOAD },
+            { capella::MAX_WITHDRAWALS_PER_PAYLOAD },
+            { capella::MAX_BLS_TO_EXECUTION_CHANGES },
+            { deneb::MAX_BLOB_COMMITMENTS_PER_BLOCK },
+            { electra::MAX_VALIDATORS_PER_SLOT },
+            { electra::MAX_COMMITTEES_PER_SLOT },
+            { electra::MAX_ATTESTER_SLASHINGS_ELECTRA },
+            { electra::MAX_ATTESTATIONS_ELECTRA },
+            { electra::MAX_DEPOSIT_REQUESTS_PER_PAYLOAD },
+            { electra::MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD },
+            { electra::MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD },
+        >;
     }
     pub mod minimal {
         use ethereum_consensus::{
             altair::minimal as altair,
+            bellatrix::minimal as bellatrix,
+            capella::minimal as capella,
             deneb::minimal as deneb,
+            electra::minimal as electra,
             phase0::minimal as phase0,
             types::minimal::{
                 BeaconBlock, BeaconState, BlindedBeaconBlock, SignedBeaconBlock,
@@ -88,6 +126,7 @@ pub mod presets {
             type BlindedBeaconBlock = BlindedBeaconBlock;
             type SignedBlindedBeaconBlock = SignedBlindedBeaconBlock;
             type Attestation = phase0::Attestation;
+            type ElectraAttestation = electra::Attestation;
             type AttesterSlashing = phase0::AttesterSlashing;
             type BeaconBlock = BeaconBlock;
             type BeaconState = BeaconState;
@@ -101,5 +140,31 @@ pub mod presets {
         }
 
         pub type Client = crate::Client<MinimalClientTypes>;
+
+        pub type BeaconEvent = crate::BeaconEvent<MinimalClientTypes>;
+
+        pub type PublishableBlock = crate::api_support::PublishableBlock<
+            { phase0::MAX_PROPOSER_SLASHINGS },
+            { phase0::MAX_VALIDATORS_PER_COMMITTEE },
+            { phase0::MAX_ATTESTER_SLASHINGS },
+            { phase0::MAX_ATTESTATIONS },
+            { phase0::MAX_DEPOSITS },
+            { phase0::MAX_VOLUNTARY_EXITS },
+            { altair::SYNC_COMMITTEE_SIZE },
+            { bellatrix::BYTES_PER_LOGS_BLOOM },
+            { bellatrix::MAX_EXTRA_DATA_BYTES },
+            { bellatrix::MAX_BYTES_PER_TRANSACTION },
+            { bellatrix::MAX_TRANSACTIONS_PER_PAYLOAD },
+            { capella::MAX_WITHDRAWALS_PER_PAYLOAD },
+            { capella::MAX_BLS_TO_EXECUTION_CHANGES },
+            { deneb::MAX_BLOB_COMMITMENTS_PER_BLOCK },
+            { electra::MAX_VALIDATORS_PER_SLOT },
+            { electra::MAX_COMMITTEES_PER_SLOT },
+            { electra::MAX_ATTESTER_SLASHINGS_ELECTRA },
+            { electra::MAX_ATTESTATIONS_ELECTRA },
+            { electra::MAX_DEPOSIT_REQUESTS_PER_PAYLOAD },
+            { electra::MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD },
+            { electra::MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD },
+        >;
     }
 }
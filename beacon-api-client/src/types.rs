@@ -6,9 +6,8 @@ use ethereum_consensus::{
     phase0::{Checkpoint, SignedBeaconBlockHeader, Validator},
     primitives::{
         BlsPublicKey, ChainId, CommitteeIndex, Coordinate, Epoch, ExecutionAddress, Gwei, Hash32,
-        Root, Slot, ValidatorIndex, Version,
+        Root, Slot, TryFromHex, ValidatorIndex, Version,
     },
-    serde::try_bytes_from_hex_str,
     Fork,
 };
 use serde::{Deserialize, Serialize};
@@ -89,11 +88,8 @@ impl FromStr for StateId {
             "genesis" => Ok(StateId::Genesis),
             _ => match s.parse::<Slot>() {
                 Ok(slot) => Ok(Self::Slot(slot)),
-                Err(_) => match try_bytes_from_hex_str(s) {
-                    Ok(root_data) => {
-                        let root = Root::try_from(root_data.as_slice()).map_err(|err| format!("could not parse state identifier by root from the provided argument {s}: {err}"))?;
-                        Ok(Self::Root(root))
-                    }
+                Err(_) => match Root::try_from_hex(s) {
+                    Ok(root) => Ok(Self::Root(root)),
                     Err(err) => {
                         let err = format!("could not parse state identifier by root from the provided argument {s}: {err}");
                         Err(err)
@@ -261,9 +257,11 @@ pub struct BeaconHeaderSummary {
     pub header: SignedBeaconBlockHeader,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum BroadcastValidation {
+    /// The level applied by the v2 publishing endpoints when none is given.
+    #[default]
     Gossip,
     Consensus,
     ConsensusAndEquivocation,
@@ -0,0 +1,95 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use ethereum_consensus::{
+    crypto::SecretKey,
+    ops_pool::AttestationPool,
+    phase0::{minimal::Attestation, AttestationData, Checkpoint},
+    primitives::Root,
+    ssz::prelude::HashTreeRootCached,
+};
+use std::{collections::HashMap, hash::Hash};
+
+const DISTINCT_DATA: usize = 4 * 1024;
+// How many attestations arrive for each distinct `AttestationData`.
+const COPIES: usize = 8;
+
+fn root(byte: usize) -> Root {
+    Root::try_from([byte as u8; 32].as_ref()).expect("is 32 bytes")
+}
+
+fn data() -> Vec<AttestationData> {
+    (0..DISTINCT_DATA)
+        .map(|i| AttestationData {
+            slot: (i / 64) as u64,
+            index: i % 64,
+            beacon_block_root: root(i),
+            source: Checkpoint { epoch: 1, root: root(1) },
+            target: Checkpoint { epoch: 2, root: root(2) },
+        })
+        .collect()
+}
+
+// Insert each of `keys` into a pool counting the attestations for each key.
+fn count<K: Hash + Eq + Clone>(keys: &[K]) -> HashMap<K, usize> {
+    let mut pool = HashMap::<K, usize>::new();
+    for key in keys {
+        *pool.entry(key.clone()).or_default() += 1;
+    }
+    pool
+}
+
+/// Inserting attestations into a pool keyed by their `AttestationData` against one keyed by its
+/// hash tree root, run with `cargo bench --bench attestation_pool`.
+///
+/// The roots are computed ahead of time as a pool would already have them from verifying the
+/// signatures of the attestations, which sign over the root of the data.
+fn bench_keys(c: &mut Criterion) {
+    let data = data();
+    let attestations = (0..COPIES).flat_map(|_| data.iter().cloned()).collect::<Vec<_>>();
+    let cached = attestations
+        .iter()
+        .map(|data| HashTreeRootCached::new(data.clone()).expect("can merkleize"))
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("attestation_pool_keys");
+    group.bench_function("by_data", |b| b.iter(|| count(&attestations)));
+    group.bench_function("by_root", |b| b.iter(|| count(&cached)));
+    group.finish();
+}
+
+/// Inserting single attestations into an `AttestationPool`, which computes the root of each
+/// attestation's data and aggregates it with those already in the pool.
+fn bench_insert(c: &mut Criterion) {
+    let secret_key = SecretKey::key_gen(&[1u8; 32]).expect("can derive a key");
+    let attestations = data()
+        .into_iter()
+        .flat_map(|data| {
+            let signature = secret_key.sign(data.beacon_block_root.as_ref());
+            (0..COPIES).map(move |attester| {
+                let mut bits = vec![false; COPIES];
+                bits[attester] = true;
+                Attestation {
+                    aggregation_bits: bits.as_slice().try_into().expect("is within the limit"),
+                    data: data.clone(),
+                    signature: signature.clone(),
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    c.bench_function("attestation_pool_insert", |b| {
+        b.iter_batched(
+            || attestations.clone(),
+            |attestations| {
+                let mut pool = AttestationPool::default();
+                for attestation in attestations {
+                    pool.insert(attestation).expect("can aggregate");
+                }
+                pool
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+criterion_group!(benches, bench_keys, bench_insert);
+criterion_main!(benches);
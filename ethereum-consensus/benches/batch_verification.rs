@@ -0,0 +1,44 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use ethereum_consensus::{
+    fixtures::phase0::{genesis_state, signed_block},
+    state_transition::{minimal::Executor, Context},
+    types::{minimal::SignedBeaconBlock, BeaconState},
+};
+
+// An executor at the slot of `block` with a warm pubkey cache, so only the block is measured.
+fn executor_at(block: &SignedBeaconBlock) -> Executor {
+    let mut executor = Executor::new(BeaconState::Phase0(genesis_state()), Context::for_minimal());
+    executor.process_slots(block.message().slot()).expect("can advance slots");
+    executor.warm_pubkey_cache().expect("keys are valid");
+    executor
+}
+
+/// Applying a block verifying each of its signatures in turn against verifying them all in one
+/// batch, run with `cargo bench --features fixtures --bench batch_verification`.
+fn bench_batch_verification(c: &mut Criterion) {
+    // a block with seven signatures: the proposer's, the RANDAO reveal, a proposer slashing, an
+    // attester slashing and an attestation
+    let block = SignedBeaconBlock::Phase0(signed_block());
+
+    let mut group = c.benchmark_group("apply_block");
+    group.bench_function("individual", |b| {
+        b.iter_batched(
+            || executor_at(&block),
+            |mut executor| executor.apply_block(&block).expect("block is valid"),
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("batched", |b| {
+        b.iter_batched(
+            || executor_at(&block),
+            |mut executor| {
+                executor.apply_block_with_batch_verification(&block).expect("block is valid")
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_batch_verification);
+criterion_main!(benches);
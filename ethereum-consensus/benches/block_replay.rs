@@ -0,0 +1,99 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use ethereum_consensus::{
+    fixtures::{phase0::genesis_state, secret_keys},
+    phase0::{self, compute_epoch_at_slot, get_beacon_proposer_index, get_domain},
+    primitives::DomainType,
+    signing::sign_with_domain,
+    state_transition::{minimal::Executor, Context, ReplayOptions},
+    types::{
+        minimal::{BeaconBlockBody, SignedBeaconBlock},
+        BeaconState,
+    },
+};
+
+const EPOCHS: u64 = 4;
+
+// A chain over `EPOCHS` epochs from the genesis state, with every fourth slot left empty.
+fn chain(context: &Context) -> Vec<SignedBeaconBlock> {
+    let secret_keys = secret_keys();
+    let mut executor = Executor::new(BeaconState::Phase0(genesis_state()), context.clone());
+    let mut blocks = vec![];
+    for slot in (1..=EPOCHS * context.slots_per_epoch).filter(|slot| slot % 4 != 0) {
+        let mut state = executor.state.inner().phase0().expect("is phase0").clone();
+        phase0::process_slots(&mut state, slot, context).expect("can advance slots");
+        let proposer_index = get_beacon_proposer_index(&state, context).expect("has a proposer");
+        let proposer_key = &secret_keys[proposer_index];
+        let epoch = compute_epoch_at_slot(slot, context);
+        let domain =
+            get_domain(&state, DomainType::Randao, Some(epoch), context).expect("has a domain");
+        let body = phase0::minimal::BeaconBlockBody {
+            randao_reveal: sign_with_domain(&epoch, proposer_key, domain).expect("can sign"),
+            ..Default::default()
+        };
+        let block = executor
+            .produce_block(slot, BeaconBlockBody::Phase0(body))
+            .expect("can produce block")
+            .into_phase0()
+            .expect("is phase0");
+        let domain =
+            get_domain(&state, DomainType::BeaconProposer, None, context).expect("has a domain");
+        let signature = sign_with_domain(&block, proposer_key, domain).expect("can sign");
+        let signed_block = SignedBeaconBlock::Phase0(phase0::minimal::SignedBeaconBlock {
+            message: block,
+            signature,
+        });
+        executor.apply_block(&signed_block).expect("block is valid");
+        blocks.push(signed_block);
+    }
+    blocks
+}
+
+/// Replaying a chain segment by applying its blocks one at a time against
+/// `Executor::apply_blocks`, with and without checking the state roots, run with
+/// `cargo bench --features fixtures --bench block_replay`.
+fn bench_block_replay(c: &mut Criterion) {
+    let context = Context::for_minimal();
+    let blocks = chain(&context);
+    // a fresh executor and a copy of the blocks for each replay, neither of which is measured
+    let setup = || {
+        let executor = Executor::new(BeaconState::Phase0(genesis_state()), context.clone());
+        (executor, blocks.clone())
+    };
+
+    let mut group = c.benchmark_group(format!("replay_{}_blocks", blocks.len()));
+    group.sample_size(10);
+    group.bench_function("apply_block_loop", |b| {
+        b.iter_batched(
+            setup,
+            |(mut executor, blocks)| {
+                for block in &blocks {
+                    executor.apply_block(block).expect("block is valid");
+                }
+            },
+            BatchSize::PerIteration,
+        )
+    });
+    group.bench_function("apply_blocks", |b| {
+        b.iter_batched(
+            setup,
+            |(mut executor, blocks)| {
+                executor.apply_blocks(blocks, ReplayOptions::default()).expect("blocks are valid")
+            },
+            BatchSize::PerIteration,
+        )
+    });
+    group.bench_function("apply_blocks_without_state_roots", |b| {
+        b.iter_batched(
+            setup,
+            |(mut executor, blocks)| {
+                let opts = ReplayOptions { verify_state_roots: false, ..Default::default() };
+                executor.apply_blocks(blocks, opts).expect("blocks are valid")
+            },
+            BatchSize::PerIteration,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_block_replay);
+criterion_main!(benches);
@@ -0,0 +1,77 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use ethereum_consensus::{
+    crypto::SecretKey,
+    electra::{
+        apply_pending_deposit, compute_domain, minimal::BeaconState, process_pending_deposits,
+        DepositMessage, PendingDeposit,
+    },
+    primitives::DomainType,
+    signing::sign_with_domain,
+    state_transition::Context,
+};
+
+const QUEUED_DEPOSITS: usize = 1000;
+
+// A state with `QUEUED_DEPOSITS` finalized deposits for new validators, each with a valid
+// signature, and a context letting the whole queue drain in one epoch.
+fn setup() -> (BeaconState, Context) {
+    let mut context = Context::for_minimal();
+    context.max_pending_deposits_per_epoch = QUEUED_DEPOSITS;
+    let domain = compute_domain(DomainType::Deposit, None, None, &context).expect("is valid");
+    let mut state = BeaconState::default();
+    for i in 0..QUEUED_DEPOSITS {
+        let mut ikm = [0u8; 32];
+        ikm[..8].copy_from_slice(&(i as u64).to_le_bytes());
+        let secret_key = SecretKey::key_gen(&ikm).expect("is valid key material");
+        let deposit_message = DepositMessage {
+            public_key: secret_key.public_key(),
+            amount: context.effective_balance_increment,
+            ..Default::default()
+        };
+        let signature = sign_with_domain(&deposit_message, &secret_key, domain).expect("can sign");
+        state.pending_deposits.push(PendingDeposit {
+            public_key: deposit_message.public_key,
+            withdrawal_credentials: deposit_message.withdrawal_credentials,
+            amount: deposit_message.amount,
+            signature,
+            ..Default::default()
+        });
+    }
+    (state, context)
+}
+
+/// Draining a queue of pending deposits verifying each signature as it is applied against
+/// verifying them up front in one batch, run with `cargo bench --bench pending_deposits`.
+fn bench_pending_deposits(c: &mut Criterion) {
+    let (state, context) = setup();
+
+    let mut group = c.benchmark_group("pending_deposits");
+    group.sample_size(10);
+    group.bench_function("individual", |b| {
+        b.iter_batched(
+            || state.clone(),
+            |mut state| {
+                let deposits = std::mem::take(&mut state.pending_deposits);
+                for deposit in deposits.iter() {
+                    apply_pending_deposit(&mut state, deposit, &context).expect("can apply");
+                }
+                state
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("batched", |b| {
+        b.iter_batched(
+            || state.clone(),
+            |mut state| {
+                process_pending_deposits(&mut state, &context).expect("can process");
+                state
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_pending_deposits);
+criterion_main!(benches);
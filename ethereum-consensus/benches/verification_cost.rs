@@ -0,0 +1,37 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use ethereum_consensus::{
+    crypto::{hash, verify_signature, SecretKey},
+    phase0::compute_shuffled_indices,
+    primitives::Bytes32,
+    state_transition::Context,
+};
+
+// The number of validators shuffled for a committee lookup, about as many as on mainnet.
+const REGISTRY_SIZE: usize = 1 << 20;
+
+/// One unit of each resource counted by `cost::VerificationCost`, to derive limits for a time
+/// budget from, run with `cargo bench --bench verification_cost`.
+fn bench_units(c: &mut Criterion) {
+    let secret_key = SecretKey::key_gen(&[1u8; 32]).expect("can derive a key");
+    let public_key = secret_key.public_key();
+    let message = [2u8; 32];
+    let signature = secret_key.sign(&message);
+    let buffer = vec![3u8; 1 << 20];
+    let indices = (0..REGISTRY_SIZE).collect::<Vec<_>>();
+    let seed = Bytes32::default();
+    let context = Context::for_mainnet();
+
+    let mut group = c.benchmark_group("verification_cost");
+    group.bench_function("pairing", |b| {
+        b.iter(|| verify_signature(&public_key, &message, &signature).expect("is valid"))
+    });
+    group.bench_function("hash_1mib", |b| b.iter(|| hash(&buffer)));
+    group.sample_size(10);
+    group.bench_function("committee_lookup", |b| {
+        b.iter(|| compute_shuffled_indices(&indices, &seed, &context))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_units);
+criterion_main!(benches);
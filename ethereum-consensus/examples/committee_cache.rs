@@ -0,0 +1,58 @@
+use ethereum_consensus::{
+    phase0::{get_beacon_committee, get_committee_count_per_slot, mainnet, Validator},
+    primitives::{CommitteeIndex, Slot, FAR_FUTURE_EPOCH},
+    state_transition::Context,
+};
+use std::time::{Duration, Instant};
+
+const VALIDATOR_COUNT: usize = 500_000;
+
+// Time resolving the committee of each of `attestations` under `context`.
+fn time(
+    state: &mainnet::BeaconState,
+    attestations: &[(Slot, CommitteeIndex)],
+    context: &Context,
+) -> Duration {
+    let start = Instant::now();
+    for &(slot, index) in attestations {
+        get_beacon_committee(state, slot, index, context).expect("committee exists");
+    }
+    start.elapsed()
+}
+
+/// Compare resolving the committees of the attestations in a full mainnet block with and without
+/// the committee cache of the context, e.g. with
+/// `cargo run --release --example committee_cache`.
+fn main() {
+    let cached = Context::for_mainnet();
+    let mut uncached = cached.clone();
+    uncached.committee_cache = None;
+
+    let mut state =
+        mainnet::BeaconState { slot: 4 * cached.slots_per_epoch + 2, ..Default::default() };
+    for _ in 0..VALIDATOR_COUNT {
+        state.validators.push(Validator {
+            effective_balance: cached.max_effective_balance,
+            exit_epoch: FAR_FUTURE_EPOCH,
+            withdrawable_epoch: FAR_FUTURE_EPOCH,
+            ..Default::default()
+        });
+        state.balances.push(cached.max_effective_balance);
+    }
+
+    // an aggregate for every committee of the two slots before the block, filling the block
+    let committees_per_slot = get_committee_count_per_slot(&state, 4, &cached);
+    let attestations = (state.slot - 2..state.slot)
+        .flat_map(|slot| (0..committees_per_slot).map(move |index| (slot, index)))
+        .take(mainnet::MAX_ATTESTATIONS)
+        .collect::<Vec<_>>();
+
+    let uncached = time(&state, &attestations, &uncached);
+    let first = time(&state, &attestations, &cached);
+    let warm = time(&state, &attestations, &cached);
+
+    println!("{} attestations over {VALIDATOR_COUNT} validators", attestations.len());
+    println!("uncached: {uncached:?} per block");
+    println!("cached: {first:?} per block with a cold cache, {warm:?} with a warm cache");
+    println!("speedup: {:.2}x", uncached.as_secs_f64() / first.as_secs_f64());
+}
@@ -0,0 +1,53 @@
+use ethereum_consensus::{
+    crypto::{verify_signature, verify_signature_decompressed, SecretKey},
+    phase0::Validator,
+    state_transition::DecompressedPubkeyCache,
+};
+use rand::prelude::*;
+use std::time::Instant;
+
+const VALIDATOR_COUNT: usize = 256;
+const ROUNDS: usize = 8;
+
+/// Compare verifying signatures from a set of validators with and without a cache of their
+/// decompressed public keys, e.g. with `cargo run --release --example pubkey_cache`.
+fn main() {
+    let mut rng = thread_rng();
+    let secret_keys = (0..VALIDATOR_COUNT)
+        .map(|_| SecretKey::random(&mut rng).expect("can randomly generate a secret key"))
+        .collect::<Vec<_>>();
+    let validators = secret_keys
+        .iter()
+        .map(|secret_key| Validator { public_key: secret_key.public_key(), ..Default::default() })
+        .collect::<Vec<_>>();
+    let msg = b"attestation data root";
+    let signatures = secret_keys.iter().map(|secret_key| secret_key.sign(msg)).collect::<Vec<_>>();
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for (validator, signature) in validators.iter().zip(&signatures) {
+            verify_signature(&validator.public_key, msg, signature).expect("is valid");
+        }
+    }
+    let uncached = start.elapsed();
+
+    let start = Instant::now();
+    let mut cache = DecompressedPubkeyCache::default();
+    cache.warm(&validators, 0).expect("keys are valid");
+    let warm = start.elapsed();
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for (index, (validator, signature)) in validators.iter().zip(&signatures).enumerate() {
+            let public_key = cache.get(index, &validator.public_key).expect("key is cached");
+            verify_signature_decompressed(public_key, msg, signature).expect("is valid");
+        }
+    }
+    let cached = start.elapsed();
+
+    let verifications = (VALIDATOR_COUNT * ROUNDS) as u32;
+    println!("{verifications} verifications of {VALIDATOR_COUNT} validators");
+    println!("uncached: {:?} per verification", uncached / verifications);
+    println!("cached: {:?} per verification (warming took {warm:?})", cached / verifications);
+    println!("speedup: {:.2}x", uncached.as_secs_f64() / cached.as_secs_f64());
+}
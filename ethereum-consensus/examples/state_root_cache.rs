@@ -0,0 +1,59 @@
+use ethereum_consensus::{
+    deneb::mainnet::BeaconState,
+    phase0::Validator,
+    primitives::FAR_FUTURE_EPOCH,
+    state_transition::{mainnet::Executor, CacheConfig, Context},
+    types::mainnet,
+};
+use std::time::{Duration, Instant};
+
+const VALIDATOR_COUNT: usize = 250_000;
+const SLOTS: u64 = 32;
+
+// Time advancing `executor` one slot at a time for `SLOTS` slots.
+fn time(executor: &mut Executor) -> Duration {
+    let start = Instant::now();
+    for _ in 0..SLOTS {
+        let slot = executor.state.slot() + 1;
+        executor.process_slots(slot).expect("can process slots");
+    }
+    start.elapsed()
+}
+
+/// Compare processing consecutive slots on a large state with and without the state root cache of
+/// the executor, e.g. with `cargo run --release --example state_root_cache`.
+fn main() {
+    let context = Context::for_mainnet();
+    let mut state = BeaconState::default();
+    for i in 0..VALIDATOR_COUNT {
+        state.validators.push(Validator {
+            activation_epoch: (i % 4) as u64,
+            effective_balance: context.max_effective_balance,
+            exit_epoch: FAR_FUTURE_EPOCH,
+            withdrawable_epoch: FAR_FUTURE_EPOCH,
+            ..Default::default()
+        });
+        state.balances.push(context.max_effective_balance);
+        state.previous_epoch_participation.push(0);
+        state.current_epoch_participation.push(0);
+        state.inactivity_scores.push(0);
+    }
+    let state = mainnet::BeaconState::Deneb(state);
+
+    let mut uncached = Executor::with_cache_config(
+        state.clone(),
+        context.clone(),
+        CacheConfig { state_root_cache: false, ..Default::default() },
+    );
+    let mut cached = Executor::new(state, context);
+    let uncached = time(&mut uncached);
+    let cached_time = time(&mut cached);
+
+    println!("{SLOTS} slots over {VALIDATOR_COUNT} validators");
+    println!("uncached: {uncached:?}");
+    println!(
+        "cached: {cached_time:?}, holding {} MiB",
+        cached.state_root_cache.memory_estimate() >> 20
+    );
+    println!("speedup: {:.2}x", uncached.as_secs_f64() / cached_time.as_secs_f64());
+}
@@ -7,6 +7,8 @@ use crate::{
     ssz::prelude::*,
 };
 
+pub use crate::phase0::beacon_state::compute_state_field_roots;
+
 #[derive(
     Default, Debug, SimpleSerialize, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize,
 )]
@@ -53,3 +55,75 @@ pub struct BeaconState<
     pub current_sync_committee: SyncCommittee<SYNC_COMMITTEE_SIZE>,
     pub next_sync_committee: SyncCommittee<SYNC_COMMITTEE_SIZE>,
 }
+
+crate::ssz::impl_field_roots!(BeaconState<
+    const SLOTS_PER_HISTORICAL_ROOT,
+    const HISTORICAL_ROOTS_LIMIT,
+    const ETH1_DATA_VOTES_BOUND,
+    const VALIDATOR_REGISTRY_LIMIT,
+    const EPOCHS_PER_HISTORICAL_VECTOR,
+    const EPOCHS_PER_SLASHINGS_VECTOR,
+    const MAX_VALIDATORS_PER_COMMITTEE,
+    const SYNC_COMMITTEE_SIZE,
+> {
+    genesis_time,
+    genesis_validators_root,
+    slot,
+    fork,
+    latest_block_header,
+    block_roots,
+    state_roots,
+    historical_roots,
+    eth1_data,
+    eth1_data_votes,
+    eth1_deposit_index,
+    validators,
+    balances,
+    randao_mixes,
+    slashings,
+    previous_epoch_participation,
+    current_epoch_participation,
+    justification_bits,
+    previous_justified_checkpoint,
+    current_justified_checkpoint,
+    finalized_checkpoint,
+    inactivity_scores,
+    current_sync_committee,
+    next_sync_committee,
+});
+
+crate::ssz_size::impl_ssz_size!(BeaconState<
+    const SLOTS_PER_HISTORICAL_ROOT,
+    const HISTORICAL_ROOTS_LIMIT,
+    const ETH1_DATA_VOTES_BOUND,
+    const VALIDATOR_REGISTRY_LIMIT,
+    const EPOCHS_PER_HISTORICAL_VECTOR,
+    const EPOCHS_PER_SLASHINGS_VECTOR,
+    const MAX_VALIDATORS_PER_COMMITTEE,
+    const SYNC_COMMITTEE_SIZE,
+> {
+    genesis_time: u64,
+    genesis_validators_root: Root,
+    slot: Slot,
+    fork: Fork,
+    latest_block_header: BeaconBlockHeader,
+    block_roots: Vector<Root, SLOTS_PER_HISTORICAL_ROOT>,
+    state_roots: Vector<Root, SLOTS_PER_HISTORICAL_ROOT>,
+    historical_roots: List<Root, HISTORICAL_ROOTS_LIMIT>,
+    eth1_data: Eth1Data,
+    eth1_data_votes: List<Eth1Data, ETH1_DATA_VOTES_BOUND>,
+    eth1_deposit_index: u64,
+    validators: List<Validator, VALIDATOR_REGISTRY_LIMIT>,
+    balances: List<Gwei, VALIDATOR_REGISTRY_LIMIT>,
+    randao_mixes: Vector<Bytes32, EPOCHS_PER_HISTORICAL_VECTOR>,
+    slashings: Vector<Gwei, EPOCHS_PER_SLASHINGS_VECTOR>,
+    previous_epoch_participation: List<ParticipationFlags, VALIDATOR_REGISTRY_LIMIT>,
+    current_epoch_participation: List<ParticipationFlags, VALIDATOR_REGISTRY_LIMIT>,
+    justification_bits: Bitvector<JUSTIFICATION_BITS_LENGTH>,
+    previous_justified_checkpoint: Checkpoint,
+    current_justified_checkpoint: Checkpoint,
+    finalized_checkpoint: Checkpoint,
+    inactivity_scores: List<u64, VALIDATOR_REGISTRY_LIMIT>,
+    current_sync_committee: SyncCommittee<SYNC_COMMITTEE_SIZE>,
+    next_sync_committee: SyncCommittee<SYNC_COMMITTEE_SIZE>,
+});
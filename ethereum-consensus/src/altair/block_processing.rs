@@ -3,19 +3,14 @@ use crate::{
         beacon_block::BeaconBlock,
         beacon_state::BeaconState,
         compute_epoch_at_slot,
-        constants::{
-            PARTICIPATION_FLAG_WEIGHTS, PROPOSER_WEIGHT, SYNC_REWARD_WEIGHT, WEIGHT_DENOMINATOR,
-        },
+        constants::PARTICIPATION_FLAG_WEIGHTS,
         decrease_balance, get_attesting_indices, get_base_reward, get_beacon_committee,
         get_beacon_proposer_index, get_block_root_at_slot, get_committee_count_per_slot,
         get_current_epoch, get_domain, get_indexed_attestation, get_previous_epoch,
         get_total_active_balance, get_validator_from_deposit,
-        helpers::{
-            add_flag, get_attestation_participation_flag_indices, get_base_reward_per_increment,
-            has_flag,
-        },
-        increase_balance, is_valid_indexed_attestation, process_block_header, process_eth1_data,
-        process_operations, process_randao,
+        helpers::{add_flag, get_attestation_participation_flag_indices, has_flag},
+        increase_balance, is_valid_indexed_attestation_with_validation, process_block_header,
+        process_eth1_data, process_operations_with_validation, process_randao_with_validation,
         sync::SyncAggregate,
         Attestation, Bytes32, Gwei,
     },
@@ -23,10 +18,14 @@ use crate::{
     domains::DomainType,
     error::{invalid_operation_error, InvalidAttestation, InvalidOperation, InvalidSyncAggregate},
     primitives::{BlsPublicKey, ParticipationFlags, ValidatorIndex},
+    rewards::{proposer_attestation_reward, proposer_sync_reward, sync_participant_reward},
     signing::compute_signing_root,
-    state_transition::{Context, Result},
+    state_transition::{Context, Result, Validation},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    iter::zip,
 };
-use std::{collections::HashMap, iter::zip};
 
 pub fn process_attestation<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
@@ -50,6 +49,33 @@ pub fn process_attestation<
     >,
     attestation: &Attestation<MAX_VALIDATORS_PER_COMMITTEE>,
     context: &Context,
+) -> Result<()> {
+    process_attestation_with_validation(state, attestation, Validation::Enabled, context)
+}
+
+pub fn process_attestation_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    attestation: &Attestation<MAX_VALIDATORS_PER_COMMITTEE>,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let data = &attestation.data;
 
@@ -113,16 +139,25 @@ pub fn process_attestation<
     let participation_flag_indices =
         get_attestation_participation_flag_indices(state, data, inclusion_delay, context)?;
 
-    // Verify signature
-    is_valid_indexed_attestation(
-        state,
-        &get_indexed_attestation(state, attestation, context)?,
-        context,
-    )?;
+    let attesting_indices = if validation.is_assumed_finalized(data.target.epoch) {
+        // NOTE: the committee was computed and checked against the aggregation bits above
+        committee
+            .iter()
+            .enumerate()
+            .filter_map(|(i, index)| attestation.aggregation_bits[i].then_some(*index))
+            .collect::<HashSet<_>>()
+    } else {
+        // Verify signature
+        is_valid_indexed_attestation_with_validation(
+            state,
+            &get_indexed_attestation(state, attestation, context)?,
+            validation,
+            context,
+        )?;
+        get_attesting_indices(state, data, &attestation.aggregation_bits, context)?
+    };
 
     // Update epoch participation flags
-    let attesting_indices =
-        get_attesting_indices(state, data, &attestation.aggregation_bits, context)?;
     let mut proposer_reward_numerator = 0;
     for index in attesting_indices {
         for (flag_index, weight) in PARTICIPATION_FLAG_WEIGHTS.iter().enumerate() {
@@ -145,9 +180,7 @@ pub fn process_attestation<
     }
 
     // Reward proposer
-    let proposer_reward_denominator =
-        (WEIGHT_DENOMINATOR - PROPOSER_WEIGHT) * WEIGHT_DENOMINATOR / PROPOSER_WEIGHT;
-    let proposer_reward = proposer_reward_numerator / proposer_reward_denominator;
+    let proposer_reward = proposer_attestation_reward(proposer_reward_numerator);
     increase_balance(state, get_beacon_proposer_index(state, context)?, proposer_reward);
     Ok(())
 }
@@ -212,46 +245,69 @@ pub fn process_sync_aggregate<
     sync_aggregate: &SyncAggregate<SYNC_COMMITTEE_SIZE>,
     context: &Context,
 ) -> Result<()> {
-    // Verify sync committee aggregate signature signing over the previous slot block root
-    let committee_public_keys = &state.current_sync_committee.public_keys;
-    let participant_public_keys =
-        zip(committee_public_keys.iter(), sync_aggregate.sync_committee_bits.iter())
-            .filter_map(|(public_key, bit)| if *bit { Some(public_key) } else { None })
-            .collect::<Vec<_>>();
-    let previous_slot = u64::max(state.slot, 1) - 1;
-    let domain = get_domain(
-        state,
-        DomainType::SyncCommittee,
-        Some(compute_epoch_at_slot(previous_slot, context)),
-        context,
-    )?;
-    let root_at_slot = *get_block_root_at_slot(state, previous_slot)?;
-    let signing_root = compute_signing_root(&root_at_slot, domain)?;
-    if eth_fast_aggregate_verify(
-        participant_public_keys.as_slice(),
-        signing_root.as_ref(),
-        &sync_aggregate.sync_committee_signature,
-    )
-    .is_err()
-    {
-        return Err(invalid_operation_error(InvalidOperation::SyncAggregate(
-            InvalidSyncAggregate::InvalidSignature {
-                signature: sync_aggregate.sync_committee_signature.clone(),
-                root: signing_root,
-            },
-        )))
+    process_sync_aggregate_with_validation(state, sync_aggregate, Validation::Enabled, context)
+}
+
+pub fn process_sync_aggregate_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    sync_aggregate: &SyncAggregate<SYNC_COMMITTEE_SIZE>,
+    validation: Validation,
+    context: &Context,
+) -> Result<()> {
+    if validation.verifies_signatures() {
+        // Verify sync committee aggregate signature signing over the previous slot block root
+        let committee_public_keys = &state.current_sync_committee.public_keys;
+        let participant_public_keys =
+            zip(committee_public_keys.iter(), sync_aggregate.sync_committee_bits.iter())
+                .filter_map(|(public_key, bit)| if *bit { Some(public_key) } else { None })
+                .collect::<Vec<_>>();
+        let previous_slot = u64::max(state.slot, 1) - 1;
+        let domain = get_domain(
+            state,
+            DomainType::SyncCommittee,
+            Some(compute_epoch_at_slot(previous_slot, context)),
+            context,
+        )?;
+        let root_at_slot = *get_block_root_at_slot(state, previous_slot)?;
+        let signing_root = compute_signing_root(&root_at_slot, domain)?;
+        if eth_fast_aggregate_verify(
+            participant_public_keys.as_slice(),
+            signing_root.as_ref(),
+            &sync_aggregate.sync_committee_signature,
+        )
+        .is_err()
+        {
+            return Err(invalid_operation_error(InvalidOperation::SyncAggregate(
+                InvalidSyncAggregate::InvalidSignature {
+                    signature: sync_aggregate.sync_committee_signature.clone(),
+                    root: signing_root,
+                },
+            )))
+        }
     }
 
     // Compute participant and proposer rewards
-    let total_active_increments =
-        get_total_active_balance(state, context)? / context.effective_balance_increment;
-    let total_base_rewards =
-        get_base_reward_per_increment(state, context)? * total_active_increments;
-    let max_participant_rewards =
-        total_base_rewards * SYNC_REWARD_WEIGHT / WEIGHT_DENOMINATOR / context.slots_per_epoch;
-    let participant_reward = max_participant_rewards / context.sync_committee_size as u64;
-    let proposer_reward =
-        participant_reward * PROPOSER_WEIGHT / (WEIGHT_DENOMINATOR - PROPOSER_WEIGHT);
+    let participant_reward =
+        sync_participant_reward(get_total_active_balance(state, context)?, context);
+    let proposer_reward = proposer_sync_reward(participant_reward);
 
     // Apply participant and proposer rewards
     let all_public_keys = state
@@ -314,11 +370,199 @@ pub fn process_block<
         SYNC_COMMITTEE_SIZE,
     >,
     context: &Context,
+) -> Result<()> {
+    process_block_with_validation(state, block, Validation::Enabled, context)
+}
+
+pub fn process_block_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    block: &BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     process_block_header(state, block, context)?;
-    process_randao(state, &block.body, context)?;
+    process_randao_with_validation(state, &block.body, validation, context)?;
     process_eth1_data(state, &block.body, context);
-    process_operations(state, &block.body, context)?;
-    process_sync_aggregate(state, &block.body.sync_aggregate, context)?;
+    process_operations_with_validation(state, &block.body, validation, context)?;
+    process_sync_aggregate_with_validation(state, &block.body.sync_aggregate, validation, context)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        altair::{minimal, AttestationData, Checkpoint, Validator},
+        crypto::{aggregate, SecretKey},
+        primitives::{BlsSignature, FAR_FUTURE_EPOCH},
+        signing::sign_with_domain,
+    };
+
+    fn honest_chain(
+        context: &Context,
+    ) -> (minimal::BeaconState, Vec<minimal::Attestation>, Vec<minimal::Attestation>) {
+        let secret_keys =
+            (0..64u8).map(|i| SecretKey::key_gen(&[i + 1; 32]).unwrap()).collect::<Vec<_>>();
+        // last slot of epoch 2, so attestations from both epoch 1 and epoch 2 are includable
+        let mut state =
+            minimal::BeaconState { slot: 3 * context.slots_per_epoch - 1, ..Default::default() };
+        for secret_key in &secret_keys {
+            state.validators.push(Validator {
+                public_key: secret_key.public_key(),
+                effective_balance: context.max_effective_balance,
+                exit_epoch: FAR_FUTURE_EPOCH,
+                withdrawable_epoch: FAR_FUTURE_EPOCH,
+                ..Default::default()
+            });
+            state.balances.push(context.max_effective_balance);
+            state.previous_epoch_participation.push(ParticipationFlags::default());
+            state.current_epoch_participation.push(ParticipationFlags::default());
+            state.inactivity_scores.push(0);
+        }
+
+        let (mut previous, mut current) = (vec![], vec![]);
+        for slot in context.slots_per_epoch + 7..state.slot {
+            let epoch = compute_epoch_at_slot(slot, context);
+            let source = if epoch == get_current_epoch(&state, context) {
+                state.current_justified_checkpoint.clone()
+            } else {
+                state.previous_justified_checkpoint.clone()
+            };
+            let domain =
+                get_domain(&state, DomainType::BeaconAttester, Some(epoch), context).unwrap();
+            for index in 0..get_committee_count_per_slot(&state, epoch, context) {
+                let committee = get_beacon_committee(&state, slot, index, context).unwrap();
+                let data = AttestationData {
+                    slot,
+                    index,
+                    beacon_block_root: get_block_root_at_slot(&state, slot).unwrap(),
+                    source: source.clone(),
+                    target: Checkpoint { epoch, ..Default::default() },
+                };
+                let bits = (0..committee.len()).map(|i| i % 3 != 1).collect::<Vec<_>>();
+                let signatures = committee
+                    .iter()
+                    .zip(bits.iter())
+                    .filter(|(_, bit)| **bit)
+                    .map(|(index, _)| {
+                        sign_with_domain(&data, &secret_keys[*index], domain).unwrap()
+                    })
+                    .collect::<Vec<_>>();
+                let attestation = minimal::Attestation {
+                    aggregation_bits: bits.as_slice().try_into().unwrap(),
+                    data,
+                    signature: aggregate(&signatures).unwrap(),
+                };
+                if epoch == get_current_epoch(&state, context) {
+                    current.push(attestation);
+                } else {
+                    previous.push(attestation);
+                }
+            }
+        }
+        (state, previous, current)
+    }
+
+    #[test]
+    fn test_assume_finalized_below_matches_full_validation() {
+        let context = Context::for_minimal();
+        let (state, previous, current) = honest_chain(&context);
+        assert!(!previous.is_empty() && !current.is_empty());
+        let attestations = previous.iter().chain(current.iter());
+
+        let mut expected = state.clone();
+        for attestation in attestations.clone() {
+            process_attestation(&mut expected, attestation, &context).unwrap();
+        }
+        let mut trusted = state.clone();
+        for attestation in attestations {
+            process_attestation_with_validation(
+                &mut trusted,
+                attestation,
+                Validation::AssumeFinalizedBelow(get_current_epoch(&state, &context) + 1),
+                &context,
+            )
+            .unwrap();
+        }
+        assert_eq!(trusted, expected);
+        assert_ne!(trusted, state);
+    }
+
+    #[test]
+    fn test_assume_finalized_below_is_ignored_at_or_above_threshold() {
+        let context = Context::for_minimal();
+        let (state, previous, current) = honest_chain(&context);
+        let current_epoch = get_current_epoch(&state, &context);
+        let validation = Validation::AssumeFinalizedBelow(current_epoch);
+
+        // signatures below the threshold are not checked...
+        let mut attestation = previous[0].clone();
+        attestation.signature = current[0].signature.clone();
+        process_attestation_with_validation(&mut state.clone(), &attestation, validation, &context)
+            .unwrap();
+        assert!(process_attestation(&mut state.clone(), &attestation, &context).is_err());
+
+        // ...but they are for attestations targeting the threshold epoch
+        let mut attestation = current[0].clone();
+        attestation.signature = previous[0].signature.clone();
+        assert!(process_attestation_with_validation(
+            &mut state.clone(),
+            &attestation,
+            validation,
+            &context
+        )
+        .is_err());
+        process_attestation_with_validation(&mut state.clone(), &current[0], validation, &context)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_sync_aggregate_without_participants() {
+        let context = Context::for_minimal();
+        let (mut state, _, _) = honest_chain(&context);
+        for (i, public_key) in state.current_sync_committee.public_keys.iter_mut().enumerate() {
+            *public_key = state.validators[i].public_key.clone();
+        }
+
+        // the SSZ default signature is not the point at infinity, so only the latter is valid
+        // for an aggregate without participants
+        let mut sync_aggregate = minimal::SyncAggregate {
+            sync_committee_signature: BlsSignature::infinity(),
+            ..Default::default()
+        };
+        process_sync_aggregate(&mut state.clone(), &sync_aggregate, &context).unwrap();
+        sync_aggregate.sync_committee_signature = BlsSignature::default();
+        assert!(process_sync_aggregate(&mut state.clone(), &sync_aggregate, &context).is_err());
+    }
+}
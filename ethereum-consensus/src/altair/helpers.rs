@@ -17,6 +17,7 @@ use crate::{
     domains::DomainType,
     error::{invalid_operation_error, Error, InvalidAttestation, InvalidOperation},
     primitives::{BlsPublicKey, Epoch, Gwei, ParticipationFlags, ValidatorIndex},
+    rewards::{base_reward_per_increment, flag_reward},
     ssz::prelude::Vector,
     state_transition::{Context, Result},
 };
@@ -62,6 +63,9 @@ pub fn get_next_sync_committee_indices<
     let max_random_byte = u8::MAX as u64;
     let active_validator_indices = get_active_validator_indices(state, epoch);
     let active_validator_count = active_validator_indices.len();
+    if active_validator_count == 0 {
+        return Err(Error::NoActiveValidators(epoch))
+    }
     let seed = get_seed(state, epoch, DomainType::SyncCommittee, context);
     let mut i: usize = 0;
     let mut sync_committee_indices = vec![];
@@ -144,8 +148,8 @@ pub fn get_base_reward_per_increment<
     >,
     context: &Context,
 ) -> Result<Gwei> {
-    Ok(context.effective_balance_increment * context.base_reward_factor /
-        get_total_active_balance(state, context)?.integer_sqrt())
+    let total_active_balance = get_total_active_balance(state, context)?;
+    Ok(base_reward_per_increment(total_active_balance, context))
 }
 
 // Return the set of validator indices that are both active and unslashed for the given
@@ -303,8 +307,12 @@ pub fn get_flag_index_deltas<
         let base_reward = get_base_reward(state, index, context)?;
         if unslashed_participating_indices.contains(&index) {
             if not_leaking {
-                let reward_numerator = base_reward * weight * unslashed_participating_increments;
-                rewards[index] += reward_numerator / (active_increments * WEIGHT_DENOMINATOR);
+                rewards[index] += flag_reward(
+                    base_reward,
+                    weight,
+                    unslashed_participating_increments,
+                    active_increments,
+                );
             }
         } else if flag_index != TIMELY_HEAD_FLAG_INDEX {
             penalties[index] += base_reward * weight / WEIGHT_DENOMINATOR;
@@ -388,7 +396,7 @@ pub fn slash_validator<
         state.validators[slashed_index].withdrawable_epoch,
         epoch + context.epochs_per_slashings_vector,
     );
-    let slashings_index = epoch as usize % EPOCHS_PER_SLASHINGS_VECTOR;
+    let slashings_index = (epoch % EPOCHS_PER_SLASHINGS_VECTOR as u64) as usize;
     state.slashings[slashings_index] += state.validators[slashed_index].effective_balance;
     decrease_balance(
         state,
@@ -409,3 +417,30 @@ pub fn slash_validator<
     increase_balance(state, whistleblower_index, whistleblower_reward - proposer_reward);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{altair::upgrade_to_altair, fixtures::phase0::genesis_state_with_validators};
+
+    #[test]
+    fn test_sync_committee_of_small_validator_sets() {
+        let context = Context::for_minimal();
+        let result = upgrade_to_altair(&genesis_state_with_validators(0), &context);
+        assert!(matches!(result, Err(Error::NoActiveValidators(1))));
+
+        for count in 1..=2 {
+            let state = upgrade_to_altair(&genesis_state_with_validators(count), &context).unwrap();
+            let indices = get_next_sync_committee_indices(&state, &context).unwrap();
+            assert_eq!(indices.len(), context.sync_committee_size);
+            assert!(indices.iter().all(|&index| index < count));
+
+            assert!(get_base_reward(&state, 0, &context).unwrap() > 0);
+            for flag_index in 0..PARTICIPATION_FLAG_WEIGHTS.len() {
+                let (rewards, penalties) =
+                    get_flag_index_deltas(&state, flag_index, &context).unwrap();
+                assert_eq!((rewards.len(), penalties.len()), (count, count));
+            }
+        }
+    }
+}
@@ -3,9 +3,15 @@ use crate::{
         sync::{SyncAggregate, SyncCommittee},
         BeaconBlockHeader,
     },
-    primitives::{Bytes32, Slot},
+    crypto::fast_aggregate_verify,
+    error::LightClientError,
+    phase0::helpers::{compute_domain, compute_epoch_at_slot},
+    primitives::{Bytes32, DomainType, IsZero, Root, Slot, GENESIS_SLOT},
+    signing::compute_signing_root,
     ssz::prelude::*,
+    state_transition::{Context, Result},
 };
+use std::iter::zip;
 
 pub const FINALIZED_ROOT_INDEX: usize = 105;
 pub const FINALIZED_ROOT_INDEX_FLOOR_LOG_2: usize = 6;
@@ -16,19 +22,25 @@ pub const CURRENT_SYNC_COMMITTEE_INDEX_FLOOR_LOG_2: usize = 5;
 pub const NEXT_SYNC_COMMITTEE_INDEX: usize = 55;
 pub const NEXT_SYNC_COMMITTEE_INDEX_FLOOR_LOG_2: usize = 5;
 
-#[derive(Default, Debug, Clone, SimpleSerialize, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
 pub struct LightClientHeader {
     pub beacon: BeaconBlockHeader,
 }
 
-#[derive(Default, Debug, Clone, SimpleSerialize, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
 pub struct LightClientBootstrap<const SYNC_COMMITTEE_SIZE: usize> {
     pub header: LightClientHeader,
     pub current_sync_committee: SyncCommittee<SYNC_COMMITTEE_SIZE>,
     pub current_sync_committee_branch: Vector<Bytes32, CURRENT_SYNC_COMMITTEE_INDEX_FLOOR_LOG_2>,
 }
 
-#[derive(Default, Debug, Clone, SimpleSerialize, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
 pub struct LightClientUpdate<const SYNC_COMMITTEE_SIZE: usize> {
     pub attested_header: LightClientHeader,
     pub next_sync_committee: SyncCommittee<SYNC_COMMITTEE_SIZE>,
@@ -39,7 +51,9 @@ pub struct LightClientUpdate<const SYNC_COMMITTEE_SIZE: usize> {
     pub signature_slot: Slot,
 }
 
-#[derive(Default, Debug, Clone, SimpleSerialize, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
 pub struct LightClientFinalityUpdate<const SYNC_COMMITTEE_SIZE: usize> {
     pub attested_header: LightClientHeader,
     pub finalized_header: LightClientHeader,
@@ -48,9 +62,469 @@ pub struct LightClientFinalityUpdate<const SYNC_COMMITTEE_SIZE: usize> {
     pub signature_slot: Slot,
 }
 
-#[derive(Default, Debug, Clone, SimpleSerialize, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
 pub struct LightClientOptimisticUpdate<const SYNC_COMMITTEE_SIZE: usize> {
     pub attested_header: LightClientHeader,
     pub sync_aggregate: SyncAggregate<SYNC_COMMITTEE_SIZE>,
     pub signature_slot: Slot,
 }
+
+/// The state of a light client following the chain from a trusted bootstrap.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct LightClientStore<const SYNC_COMMITTEE_SIZE: usize> {
+    pub finalized_header: LightClientHeader,
+    pub current_sync_committee: SyncCommittee<SYNC_COMMITTEE_SIZE>,
+    pub next_sync_committee: SyncCommittee<SYNC_COMMITTEE_SIZE>,
+    pub best_valid_update: Option<LightClientUpdate<SYNC_COMMITTEE_SIZE>>,
+    pub optimistic_header: LightClientHeader,
+    pub previous_max_active_participants: usize,
+    pub current_max_active_participants: usize,
+}
+
+/// Verify `branch` proves `leaf` at the generalized index `gindex` of the tree with `root`.
+///
+/// A branch longer than the depth of `gindex` must start with zero nodes, as when a proof is
+/// normalized to the depth of a later fork.
+pub fn is_valid_light_client_branch(
+    leaf: Root,
+    branch: &[Bytes32],
+    gindex: GeneralizedIndex,
+    root: Root,
+) -> bool {
+    let depth = gindex.ilog2() as usize;
+    let Some(extra) = branch.len().checked_sub(depth) else { return false };
+    if branch[..extra].iter().any(|node| !node.is_zero()) {
+        return false
+    }
+    let branch = branch[extra..]
+        .iter()
+        .map(|node| Node::try_from(node.as_ref()).expect("correct length"))
+        .collect::<Vec<_>>();
+    let index = gindex % 2usize.pow(depth as u32);
+    is_valid_merkle_branch(leaf, &branch, depth, index, root).is_ok()
+}
+
+pub fn compute_sync_committee_period_at_slot(slot: Slot, context: &Context) -> u64 {
+    compute_epoch_at_slot(slot, context) / context.epochs_per_sync_committee_period
+}
+
+pub fn is_valid_light_client_header(_header: &LightClientHeader, _context: &Context) -> bool {
+    true
+}
+
+pub fn is_sync_committee_update<const SYNC_COMMITTEE_SIZE: usize>(
+    update: &LightClientUpdate<SYNC_COMMITTEE_SIZE>,
+) -> bool {
+    update.next_sync_committee_branch.iter().any(|node| !node.is_zero())
+}
+
+pub fn is_finality_update<const SYNC_COMMITTEE_SIZE: usize>(
+    update: &LightClientUpdate<SYNC_COMMITTEE_SIZE>,
+) -> bool {
+    update.finality_branch.iter().any(|node| !node.is_zero())
+}
+
+/// Whether `new_update` should replace `old_update` as the best update seen for a period.
+pub fn is_better_update<const SYNC_COMMITTEE_SIZE: usize>(
+    new_update: &LightClientUpdate<SYNC_COMMITTEE_SIZE>,
+    old_update: &LightClientUpdate<SYNC_COMMITTEE_SIZE>,
+    context: &Context,
+) -> bool {
+    // Compare supermajority (> 2/3) sync committee participation
+    let max_active_participants = SYNC_COMMITTEE_SIZE;
+    let new_num_active_participants =
+        new_update.sync_aggregate.sync_committee_bits.iter().filter(|bit| **bit).count();
+    let old_num_active_participants =
+        old_update.sync_aggregate.sync_committee_bits.iter().filter(|bit| **bit).count();
+    let new_has_supermajority = new_num_active_participants * 3 >= max_active_participants * 2;
+    let old_has_supermajority = old_num_active_participants * 3 >= max_active_participants * 2;
+    if new_has_supermajority != old_has_supermajority {
+        return new_has_supermajority
+    }
+    if !new_has_supermajority && new_num_active_participants != old_num_active_participants {
+        return new_num_active_participants > old_num_active_participants
+    }
+
+    // Compare presence of relevant sync committee
+    let has_relevant_sync_committee = |update: &LightClientUpdate<SYNC_COMMITTEE_SIZE>| {
+        is_sync_committee_update(update) &&
+            compute_sync_committee_period_at_slot(update.attested_header.beacon.slot, context) ==
+                compute_sync_committee_period_at_slot(update.signature_slot, context)
+    };
+    let new_has_relevant_sync_committee = has_relevant_sync_committee(new_update);
+    let old_has_relevant_sync_committee = has_relevant_sync_committee(old_update);
+    if new_has_relevant_sync_committee != old_has_relevant_sync_committee {
+        return new_has_relevant_sync_committee
+    }
+
+    // Compare indication of any finality
+    let new_has_finality = is_finality_update(new_update);
+    let old_has_finality = is_finality_update(old_update);
+    if new_has_finality != old_has_finality {
+        return new_has_finality
+    }
+
+    // Compare sync committee finality
+    if new_has_finality {
+        let has_sync_committee_finality = |update: &LightClientUpdate<SYNC_COMMITTEE_SIZE>| {
+            compute_sync_committee_period_at_slot(update.finalized_header.beacon.slot, context) ==
+                compute_sync_committee_period_at_slot(
+                    update.attested_header.beacon.slot,
+                    context,
+                )
+        };
+        let new_has_sync_committee_finality = has_sync_committee_finality(new_update);
+        let old_has_sync_committee_finality = has_sync_committee_finality(old_update);
+        if new_has_sync_committee_finality != old_has_sync_committee_finality {
+            return new_has_sync_committee_finality
+        }
+    }
+
+    // Tiebreaker 1: Sync committee participation beyond supermajority
+    if new_num_active_participants != old_num_active_participants {
+        return new_num_active_participants > old_num_active_participants
+    }
+
+    // Tiebreaker 2: Prefer older data (fewer changes to best)
+    if new_update.attested_header.beacon.slot != old_update.attested_header.beacon.slot {
+        return new_update.attested_header.beacon.slot < old_update.attested_header.beacon.slot
+    }
+    new_update.signature_slot < old_update.signature_slot
+}
+
+pub fn is_next_sync_committee_known<const SYNC_COMMITTEE_SIZE: usize>(
+    store: &LightClientStore<SYNC_COMMITTEE_SIZE>,
+) -> bool {
+    store.next_sync_committee != SyncCommittee::default()
+}
+
+pub fn get_safety_threshold<const SYNC_COMMITTEE_SIZE: usize>(
+    store: &LightClientStore<SYNC_COMMITTEE_SIZE>,
+) -> usize {
+    store.previous_max_active_participants.max(store.current_max_active_participants) / 2
+}
+
+pub fn initialize_light_client_store<const SYNC_COMMITTEE_SIZE: usize>(
+    trusted_block_root: Root,
+    bootstrap: &LightClientBootstrap<SYNC_COMMITTEE_SIZE>,
+    context: &Context,
+) -> Result<LightClientStore<SYNC_COMMITTEE_SIZE>> {
+    if !is_valid_light_client_header(&bootstrap.header, context) {
+        return Err(LightClientError::InvalidHeader(bootstrap.header.beacon.slot).into())
+    }
+    let root = bootstrap.header.beacon.hash_tree_root()?;
+    if root != trusted_block_root {
+        return Err(LightClientError::UntrustedBootstrap {
+            provided: root,
+            expected: trusted_block_root,
+        }
+        .into())
+    }
+    if !is_valid_light_client_branch(
+        bootstrap.current_sync_committee.hash_tree_root()?,
+        &bootstrap.current_sync_committee_branch,
+        CURRENT_SYNC_COMMITTEE_INDEX,
+        bootstrap.header.beacon.state_root,
+    ) {
+        return Err(LightClientError::InvalidCurrentSyncCommitteeBranch.into())
+    }
+    Ok(LightClientStore {
+        finalized_header: bootstrap.header.clone(),
+        current_sync_committee: bootstrap.current_sync_committee.clone(),
+        next_sync_committee: SyncCommittee::default(),
+        best_valid_update: None,
+        optimistic_header: bootstrap.header.clone(),
+        previous_max_active_participants: 0,
+        current_max_active_participants: 0,
+    })
+}
+
+pub fn validate_light_client_update<const SYNC_COMMITTEE_SIZE: usize>(
+    store: &LightClientStore<SYNC_COMMITTEE_SIZE>,
+    update: &LightClientUpdate<SYNC_COMMITTEE_SIZE>,
+    current_slot: Slot,
+    genesis_validators_root: Root,
+    context: &Context,
+) -> Result<()> {
+    // Verify sync committee has sufficient participants
+    let sync_aggregate = &update.sync_aggregate;
+    let participants = sync_aggregate.sync_committee_bits.iter().filter(|bit| **bit).count();
+    if participants < context.min_sync_committee_participants {
+        return Err(LightClientError::InsufficientParticipation {
+            participants,
+            minimum: context.min_sync_committee_participants,
+        }
+        .into())
+    }
+
+    // Verify update does not skip a sync committee period
+    if !is_valid_light_client_header(&update.attested_header, context) {
+        return Err(LightClientError::InvalidHeader(update.attested_header.beacon.slot).into())
+    }
+    let update_attested_slot = update.attested_header.beacon.slot;
+    let update_finalized_slot = update.finalized_header.beacon.slot;
+    if !(current_slot >= update.signature_slot &&
+        update.signature_slot > update_attested_slot &&
+        update_attested_slot >= update_finalized_slot)
+    {
+        return Err(LightClientError::InvalidSlots {
+            current_slot,
+            signature_slot: update.signature_slot,
+            attested_slot: update_attested_slot,
+            finalized_slot: update_finalized_slot,
+        }
+        .into())
+    }
+    let store_period =
+        compute_sync_committee_period_at_slot(store.finalized_header.beacon.slot, context);
+    let update_signature_period =
+        compute_sync_committee_period_at_slot(update.signature_slot, context);
+    let is_valid_signature_period = if is_next_sync_committee_known(store) {
+        update_signature_period == store_period || update_signature_period == store_period + 1
+    } else {
+        update_signature_period == store_period
+    };
+    if !is_valid_signature_period {
+        return Err(LightClientError::UnexpectedSignaturePeriod {
+            signature_period: update_signature_period,
+            store_period,
+        }
+        .into())
+    }
+
+    // Verify update is relevant
+    let update_attested_period =
+        compute_sync_committee_period_at_slot(update_attested_slot, context);
+    let update_has_next_sync_committee = !is_next_sync_committee_known(store) &&
+        is_sync_committee_update(update) &&
+        update_attested_period == store_period;
+    if !(update_attested_slot > store.finalized_header.beacon.slot ||
+        update_has_next_sync_committee)
+    {
+        let attested_slot = update_attested_slot;
+        return Err(LightClientError::IrrelevantUpdate { attested_slot }.into())
+    }
+
+    // Verify that the `finality_branch`, if present, confirms `finalized_header`
+    // to match the finalized checkpoint root saved in the state of `attested_header`.
+    // Note that the genesis finalized checkpoint root is represented as a zero hash.
+    if !is_finality_update(update) {
+        if update.finalized_header != LightClientHeader::default() {
+            return Err(LightClientError::UnexpectedFinalizedHeader.into())
+        }
+    } else {
+        let finalized_root = if update_finalized_slot == GENESIS_SLOT {
+            if update.finalized_header != LightClientHeader::default() {
+                return Err(LightClientError::UnexpectedFinalizedHeader.into())
+            }
+            Root::default()
+        } else {
+            if !is_valid_light_client_header(&update.finalized_header, context) {
+                return Err(LightClientError::InvalidHeader(update_finalized_slot).into())
+            }
+            update.finalized_header.beacon.hash_tree_root()?
+        };
+        if !is_valid_light_client_branch(
+            finalized_root,
+            &update.finality_branch,
+            FINALIZED_ROOT_INDEX,
+            update.attested_header.beacon.state_root,
+        ) {
+            return Err(LightClientError::InvalidFinalityBranch.into())
+        }
+    }
+
+    // Verify that the `next_sync_committee`, if present, actually is the next sync committee
+    // saved in the state of the `attested_header`
+    if !is_sync_committee_update(update) {
+        if update.next_sync_committee != SyncCommittee::default() {
+            return Err(LightClientError::UnexpectedNextSyncCommittee.into())
+        }
+    } else {
+        if update_attested_period == store_period &&
+            is_next_sync_committee_known(store) &&
+            update.next_sync_committee != store.next_sync_committee
+        {
+            return Err(LightClientError::ConflictingNextSyncCommittee.into())
+        }
+        if !is_valid_light_client_branch(
+            update.next_sync_committee.hash_tree_root()?,
+            &update.next_sync_committee_branch,
+            NEXT_SYNC_COMMITTEE_INDEX,
+            update.attested_header.beacon.state_root,
+        ) {
+            return Err(LightClientError::InvalidNextSyncCommitteeBranch.into())
+        }
+    }
+
+    // Verify sync committee aggregate signature
+    let sync_committee = if update_signature_period == store_period {
+        &store.current_sync_committee
+    } else {
+        &store.next_sync_committee
+    };
+    let participant_public_keys =
+        zip(sync_aggregate.sync_committee_bits.iter(), sync_committee.public_keys.iter())
+            .filter_map(|(bit, public_key)| if *bit { Some(public_key) } else { None })
+            .collect::<Vec<_>>();
+    let fork_version_slot = update.signature_slot.max(1) - 1;
+    let fork_version = context.fork_version_for(context.fork_for(fork_version_slot));
+    let domain = compute_domain(
+        DomainType::SyncCommittee,
+        Some(fork_version),
+        Some(genesis_validators_root),
+        context,
+    )?;
+    let signing_root = compute_signing_root(&update.attested_header.beacon, domain)?;
+    fast_aggregate_verify(
+        &participant_public_keys,
+        signing_root.as_ref(),
+        &sync_aggregate.sync_committee_signature,
+    )
+    .map_err(|_| LightClientError::InvalidSignature { root: signing_root }.into())
+}
+
+pub fn apply_light_client_update<const SYNC_COMMITTEE_SIZE: usize>(
+    store: &mut LightClientStore<SYNC_COMMITTEE_SIZE>,
+    update: &LightClientUpdate<SYNC_COMMITTEE_SIZE>,
+    context: &Context,
+) -> Result<()> {
+    let store_period =
+        compute_sync_committee_period_at_slot(store.finalized_header.beacon.slot, context);
+    let update_finalized_period =
+        compute_sync_committee_period_at_slot(update.finalized_header.beacon.slot, context);
+    if !is_next_sync_committee_known(store) {
+        if update_finalized_period != store_period {
+            return Err(LightClientError::UnexpectedFinalizedPeriod {
+                finalized_period: update_finalized_period,
+                store_period,
+            }
+            .into())
+        }
+        store.next_sync_committee = update.next_sync_committee.clone();
+    } else if update_finalized_period == store_period + 1 {
+        store.current_sync_committee =
+            std::mem::replace(&mut store.next_sync_committee, update.next_sync_committee.clone());
+        store.previous_max_active_participants = store.current_max_active_participants;
+        store.current_max_active_participants = 0;
+    }
+    if update.finalized_header.beacon.slot > store.finalized_header.beacon.slot {
+        store.finalized_header = update.finalized_header.clone();
+        if store.finalized_header.beacon.slot > store.optimistic_header.beacon.slot {
+            store.optimistic_header = store.finalized_header.clone();
+        }
+    }
+    Ok(())
+}
+
+/// Apply the best valid update seen once no update has been finalized for `UPDATE_TIMEOUT` slots,
+/// so the store can progress through extended periods of non-finality.
+pub fn process_light_client_store_force_update<const SYNC_COMMITTEE_SIZE: usize>(
+    store: &mut LightClientStore<SYNC_COMMITTEE_SIZE>,
+    current_slot: Slot,
+    context: &Context,
+) -> Result<()> {
+    if current_slot > store.finalized_header.beacon.slot + context.update_timeout as Slot {
+        if let Some(mut best_valid_update) = store.best_valid_update.take() {
+            // The apply logic waits for `finalized_header.beacon.slot` to indicate sync committee
+            // finality, so the `attested_header` may be treated as `finalized_header` to
+            // guarantee progression into later sync committee periods.
+            if best_valid_update.finalized_header.beacon.slot <= store.finalized_header.beacon.slot
+            {
+                best_valid_update.finalized_header = best_valid_update.attested_header.clone();
+            }
+            apply_light_client_update(store, &best_valid_update, context)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn process_light_client_update<const SYNC_COMMITTEE_SIZE: usize>(
+    store: &mut LightClientStore<SYNC_COMMITTEE_SIZE>,
+    update: &LightClientUpdate<SYNC_COMMITTEE_SIZE>,
+    current_slot: Slot,
+    genesis_validators_root: Root,
+    context: &Context,
+) -> Result<()> {
+    validate_light_client_update(store, update, current_slot, genesis_validators_root, context)?;
+
+    let participants = update.sync_aggregate.sync_committee_bits.iter().filter(|bit| **bit).count();
+
+    // Update the best update in case we have to force-update to it if the timeout elapses
+    let is_best_valid_update = match store.best_valid_update.as_ref() {
+        Some(best_valid_update) => is_better_update(update, best_valid_update, context),
+        None => true,
+    };
+    if is_best_valid_update {
+        store.best_valid_update = Some(update.clone());
+    }
+
+    // Track the maximum number of active participants in the committee signatures
+    store.current_max_active_participants = store.current_max_active_participants.max(participants);
+
+    // Update the optimistic header
+    if participants > get_safety_threshold(store) &&
+        update.attested_header.beacon.slot > store.optimistic_header.beacon.slot
+    {
+        store.optimistic_header = update.attested_header.clone();
+    }
+
+    // Update finalized header
+    let update_has_finalized_next_sync_committee = !is_next_sync_committee_known(store) &&
+        is_sync_committee_update(update) &&
+        is_finality_update(update) &&
+        compute_sync_committee_period_at_slot(update.finalized_header.beacon.slot, context) ==
+            compute_sync_committee_period_at_slot(
+                update.attested_header.beacon.slot,
+                context,
+            );
+    if participants * 3 >= SYNC_COMMITTEE_SIZE * 2 &&
+        (update.finalized_header.beacon.slot > store.finalized_header.beacon.slot ||
+            update_has_finalized_next_sync_committee)
+    {
+        // Normal update through 2/3 threshold
+        apply_light_client_update(store, update, context)?;
+        store.best_valid_update = None;
+    }
+    Ok(())
+}
+
+pub fn process_light_client_finality_update<const SYNC_COMMITTEE_SIZE: usize>(
+    store: &mut LightClientStore<SYNC_COMMITTEE_SIZE>,
+    finality_update: &LightClientFinalityUpdate<SYNC_COMMITTEE_SIZE>,
+    current_slot: Slot,
+    genesis_validators_root: Root,
+    context: &Context,
+) -> Result<()> {
+    let update = LightClientUpdate {
+        attested_header: finality_update.attested_header.clone(),
+        next_sync_committee: SyncCommittee::default(),
+        next_sync_committee_branch: Default::default(),
+        finalized_header: finality_update.finalized_header.clone(),
+        finality_branch: finality_update.finality_branch.clone(),
+        sync_aggregate: finality_update.sync_aggregate.clone(),
+        signature_slot: finality_update.signature_slot,
+    };
+    process_light_client_update(store, &update, current_slot, genesis_validators_root, context)
+}
+
+pub fn process_light_client_optimistic_update<const SYNC_COMMITTEE_SIZE: usize>(
+    store: &mut LightClientStore<SYNC_COMMITTEE_SIZE>,
+    optimistic_update: &LightClientOptimisticUpdate<SYNC_COMMITTEE_SIZE>,
+    current_slot: Slot,
+    genesis_validators_root: Root,
+    context: &Context,
+) -> Result<()> {
+    let update = LightClientUpdate {
+        attested_header: optimistic_update.attested_header.clone(),
+        next_sync_committee: SyncCommittee::default(),
+        next_sync_committee_branch: Default::default(),
+        finalized_header: LightClientHeader::default(),
+        finality_branch: Default::default(),
+        sync_aggregate: optimistic_update.sync_aggregate.clone(),
+        signature_slot: optimistic_update.signature_slot,
+    };
+    process_light_client_update(store, &update, current_slot, genesis_validators_root, context)
+}
@@ -32,6 +32,7 @@ pub const PRESET: Preset = Preset {
     epochs_per_sync_committee_period: EPOCHS_PER_SYNC_COMMITTEE_PERIOD,
     min_sync_committee_participants: MIN_SYNC_COMMITTEE_PARTICIPANTS,
     update_timeout: UPDATE_TIMEOUT,
+    target_aggregators_per_sync_subcommittee: TARGET_AGGREGATORS_PER_SYNC_SUBCOMMITTEE,
 };
 
 pub type SyncAggregate = spec::SyncAggregate<SYNC_COMMITTEE_SIZE>;
@@ -86,3 +87,4 @@ pub type LightClientBootstrap = spec::LightClientBootstrap<SYNC_COMMITTEE_SIZE>;
 pub type LightClientUpdate = spec::LightClientUpdate<SYNC_COMMITTEE_SIZE>;
 pub type LightClientFinalityUpdate = spec::LightClientFinalityUpdate<SYNC_COMMITTEE_SIZE>;
 pub type LightClientOptimisticUpdate = spec::LightClientOptimisticUpdate<SYNC_COMMITTEE_SIZE>;
+pub type LightClientStore = spec::LightClientStore<SYNC_COMMITTEE_SIZE>;
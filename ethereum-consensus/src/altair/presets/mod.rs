@@ -1,3 +1,4 @@
+pub mod gnosis;
 pub mod mainnet;
 pub mod minimal;
 
@@ -11,4 +12,5 @@ pub struct Preset {
     pub epochs_per_sync_committee_period: Epoch,
     pub min_sync_committee_participants: usize,
     pub update_timeout: usize,
+    pub target_aggregators_per_sync_subcommittee: usize,
 }
@@ -20,3 +20,13 @@ pub struct SyncCommittee<const SYNC_COMMITTEE_SIZE: usize> {
     #[serde(rename = "aggregate_pubkey")]
     pub aggregate_public_key: BlsPublicKey,
 }
+
+crate::ssz_size::impl_ssz_size!(SyncAggregate<const SYNC_COMMITTEE_SIZE> {
+    sync_committee_bits: Bitvector<SYNC_COMMITTEE_SIZE>,
+    sync_committee_signature: BlsSignature,
+});
+
+crate::ssz_size::impl_ssz_size!(SyncCommittee<const SYNC_COMMITTEE_SIZE> {
+    public_keys: Vector<BlsPublicKey, SYNC_COMMITTEE_SIZE>,
+    aggregate_public_key: BlsPublicKey,
+});
@@ -1,6 +1,11 @@
 use crate::{
-    primitives::{BlsSignature, Root, Slot, ValidatorIndex},
+    altair::constants::SYNC_COMMITTEE_SUBNET_COUNT,
+    crypto::SecretKey,
+    phase0::validator::{compute_domain_at_slot, is_selected, ForkInfo},
+    primitives::{BlsPublicKey, BlsSignature, DomainType, Root, Slot, ValidatorIndex},
+    signing::{sign_with_domain, verify_signed_data},
     ssz::prelude::*,
+    state_transition::{Context, Result},
 };
 
 #[derive(Debug, Default, Clone, SimpleSerialize, serde::Serialize, serde::Deserialize)]
@@ -40,6 +45,193 @@ pub struct SignedContributionAndProof<const SYNC_SUBCOMMITTEE_SIZE: usize> {
 
 #[derive(Debug, Default, Clone, SimpleSerialize, serde::Serialize, serde::Deserialize)]
 pub struct SyncAggregatorSelectionData {
+    #[serde(with = "crate::serde::as_str")]
     pub slot: Slot,
+    #[serde(with = "crate::serde::as_str")]
     pub subcommittee_index: u64,
 }
+
+pub fn sign_sync_selection_proof(
+    secret_key: &SecretKey,
+    slot: Slot,
+    subcommittee_index: u64,
+    fork_info: &ForkInfo,
+    context: &Context,
+) -> Result<BlsSignature> {
+    let domain =
+        compute_domain_at_slot(DomainType::SyncCommitteeSelectionProof, slot, fork_info, context)?;
+    let signing_data = SyncAggregatorSelectionData { slot, subcommittee_index };
+    sign_with_domain(&signing_data, secret_key, domain)
+}
+
+pub fn verify_sync_selection_proof(
+    selection_proof: &BlsSignature,
+    public_key: &BlsPublicKey,
+    slot: Slot,
+    subcommittee_index: u64,
+    fork_info: &ForkInfo,
+    context: &Context,
+) -> Result<()> {
+    let domain =
+        compute_domain_at_slot(DomainType::SyncCommitteeSelectionProof, slot, fork_info, context)?;
+    let signing_data = SyncAggregatorSelectionData { slot, subcommittee_index };
+    verify_signed_data(&signing_data, selection_proof, public_key, domain)
+}
+
+pub fn sign_sync_committee_message(
+    secret_key: &SecretKey,
+    slot: Slot,
+    beacon_block_root: Root,
+    fork_info: &ForkInfo,
+    context: &Context,
+) -> Result<BlsSignature> {
+    let domain = compute_domain_at_slot(DomainType::SyncCommittee, slot, fork_info, context)?;
+    sign_with_domain(&beacon_block_root, secret_key, domain)
+}
+
+pub fn verify_sync_committee_message(
+    message: &SyncCommitteeMessage,
+    public_key: &BlsPublicKey,
+    fork_info: &ForkInfo,
+    context: &Context,
+) -> Result<()> {
+    let domain =
+        compute_domain_at_slot(DomainType::SyncCommittee, message.slot, fork_info, context)?;
+    verify_signed_data(&message.beacon_block_root, &message.signature, public_key, domain)
+}
+
+pub fn sign_contribution_and_proof<const SYNC_SUBCOMMITTEE_SIZE: usize>(
+    secret_key: &SecretKey,
+    contribution_and_proof: &ContributionAndProof<SYNC_SUBCOMMITTEE_SIZE>,
+    fork_info: &ForkInfo,
+    context: &Context,
+) -> Result<BlsSignature> {
+    let slot = contribution_and_proof.contribution.slot;
+    let domain =
+        compute_domain_at_slot(DomainType::ContributionAndProof, slot, fork_info, context)?;
+    sign_with_domain(contribution_and_proof, secret_key, domain)
+}
+
+pub fn verify_contribution_and_proof_signature<const SYNC_SUBCOMMITTEE_SIZE: usize>(
+    signed_contribution_and_proof: &SignedContributionAndProof<SYNC_SUBCOMMITTEE_SIZE>,
+    public_key: &BlsPublicKey,
+    fork_info: &ForkInfo,
+    context: &Context,
+) -> Result<()> {
+    let contribution_and_proof = &signed_contribution_and_proof.message;
+    let slot = contribution_and_proof.contribution.slot;
+    let domain =
+        compute_domain_at_slot(DomainType::ContributionAndProof, slot, fork_info, context)?;
+    verify_signed_data(
+        contribution_and_proof,
+        &signed_contribution_and_proof.signature,
+        public_key,
+        domain,
+    )
+}
+
+/// Determine if the validator that produced `selection_proof` (see `sign_sync_selection_proof`)
+/// should aggregate for its sync subcommittee.
+pub fn is_sync_committee_aggregator(selection_proof: &BlsSignature, context: &Context) -> bool {
+    let modulo = context.sync_committee_size /
+        SYNC_COMMITTEE_SUBNET_COUNT /
+        context.target_aggregators_per_sync_subcommittee;
+    is_selected(selection_proof, modulo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::hash;
+
+    #[test]
+    fn test_sync_selection_proof() {
+        let secret_key = SecretKey::key_gen(&[2u8; 32]).unwrap();
+        let public_key = secret_key.public_key();
+        let fork_info = ForkInfo::default();
+        let (slot, subcommittee_index) = (17, 1);
+
+        let context = Context::for_minimal();
+        let proof =
+            sign_sync_selection_proof(&secret_key, slot, subcommittee_index, &fork_info, &context)
+                .unwrap();
+        verify_sync_selection_proof(
+            &proof,
+            &public_key,
+            slot,
+            subcommittee_index,
+            &fork_info,
+            &context,
+        )
+        .unwrap();
+        assert!(verify_sync_selection_proof(
+            &proof,
+            &public_key,
+            slot,
+            subcommittee_index + 1,
+            &fork_info,
+            &context,
+        )
+        .is_err());
+        // minimal sync subcommittees are smaller than the aggregator target
+        assert!(is_sync_committee_aggregator(&proof, &context));
+
+        // 512 / 4 / 16 == 8 on mainnet
+        let context = Context::for_mainnet();
+        let digest = hash(&proof[..]);
+        let expected = u64::from_le_bytes(digest[..8].try_into().unwrap()) % 8 == 0;
+        assert_eq!(is_sync_committee_aggregator(&proof, &context), expected);
+        let mut context = context;
+        context.target_aggregators_per_sync_subcommittee = 128;
+        assert!(is_sync_committee_aggregator(&proof, &context));
+    }
+
+    #[test]
+    fn test_sync_committee_signatures() {
+        let context = Context::for_minimal();
+        let secret_key = SecretKey::key_gen(&[2u8; 32]).unwrap();
+        let public_key = secret_key.public_key();
+        let fork_info = ForkInfo::default();
+        let beacon_block_root = Root::try_from([5u8; 32].as_ref()).unwrap();
+
+        let slot = 11;
+        let signature =
+            sign_sync_committee_message(&secret_key, slot, beacon_block_root, &fork_info, &context)
+                .unwrap();
+        let mut message =
+            SyncCommitteeMessage { slot, beacon_block_root, validator_index: 1, signature };
+        verify_sync_committee_message(&message, &public_key, &fork_info, &context).unwrap();
+        message.beacon_block_root = Root::default();
+        assert!(verify_sync_committee_message(&message, &public_key, &fork_info, &context).is_err());
+
+        let subcommittee_index = 1;
+        let mut contribution_and_proof = ContributionAndProof::<8> {
+            aggregator_index: 1,
+            selection_proof: sign_sync_selection_proof(
+                &secret_key,
+                slot,
+                subcommittee_index,
+                &fork_info,
+                &context,
+            )
+            .unwrap(),
+            ..Default::default()
+        };
+        contribution_and_proof.contribution.slot = slot;
+        contribution_and_proof.contribution.subcommittee_index = subcommittee_index;
+        let signature =
+            sign_contribution_and_proof(&secret_key, &contribution_and_proof, &fork_info, &context)
+                .unwrap();
+        let mut signed = SignedContributionAndProof { message: contribution_and_proof, signature };
+        verify_contribution_and_proof_signature(&signed, &public_key, &fork_info, &context)
+            .unwrap();
+        signed.message.contribution.slot += 1;
+        assert!(verify_contribution_and_proof_signature(
+            &signed,
+            &public_key,
+            &fork_info,
+            &context
+        )
+        .is_err());
+    }
+}
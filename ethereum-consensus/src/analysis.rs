@@ -0,0 +1,1069 @@
+//! Read-only views over a `BeaconState` for explorers and dashboards.
+use crate::{
+    electra::{
+        compute_epoch_at_slot, get_active_validator_indices, get_attesting_indices, get_block_root,
+        get_current_epoch, get_previous_epoch, get_total_active_balance, get_total_balance,
+        get_unslashed_participating_indices, Attestation, BeaconState, Checkpoint,
+        PendingConsolidation, PendingDeposit, PendingPartialWithdrawal, Validator,
+        TIMELY_TARGET_FLAG_INDEX,
+    },
+    primitives::{BlsPublicKey, Epoch, Gwei, Slot, ValidatorIndex, FAR_FUTURE_EPOCH},
+    state_transition::{Context, Executor, ReplayOptions, Result, Validation},
+    types::SignedBeaconBlock,
+    validators::is_active_validator,
+    Error,
+};
+use std::{collections::HashSet, iter, ops::Range};
+
+/// Classification of a validator at the current epoch of a state, following the statuses
+/// reported by the beacon node API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidatorStatus {
+    PendingInitialized,
+    PendingQueued,
+    ActiveOngoing,
+    ActiveExiting,
+    ActiveSlashed,
+    ExitedUnslashed,
+    ExitedSlashed,
+    WithdrawalPossible,
+    WithdrawalDone,
+}
+
+/// An epoch in the lifecycle of a validator along with the wall-clock time it starts at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LifecycleEpoch {
+    #[serde(with = "crate::serde::as_str")]
+    pub epoch: Epoch,
+    /// Unix timestamp, in seconds, of the first slot of `epoch`.
+    #[serde(with = "crate::serde::as_str")]
+    pub timestamp: u64,
+}
+
+/// Everything the state knows about a single validator.
+///
+/// Lifecycle epochs that have not been assigned yet (i.e. are `FAR_FUTURE_EPOCH`) are `None`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Timeline {
+    #[serde(with = "crate::serde::as_str")]
+    pub index: ValidatorIndex,
+    #[serde(rename = "pubkey")]
+    pub public_key: BlsPublicKey,
+    pub status: ValidatorStatus,
+    #[serde(with = "crate::serde::as_str")]
+    pub balance: Gwei,
+    #[serde(with = "crate::serde::as_str")]
+    pub effective_balance: Gwei,
+    pub activation_eligibility: Option<LifecycleEpoch>,
+    pub activation: Option<LifecycleEpoch>,
+    pub exit: Option<LifecycleEpoch>,
+    pub withdrawable: Option<LifecycleEpoch>,
+    pub pending_deposits: Vec<PendingDeposit>,
+    pub pending_partial_withdrawals: Vec<PendingPartialWithdrawal>,
+    /// Pending consolidations with the validator as either source or target.
+    pub pending_consolidations: Vec<PendingConsolidation>,
+}
+
+fn lifecycle_epoch(
+    epoch: Epoch,
+    genesis_time: u64,
+    context: &Context,
+) -> Result<Option<LifecycleEpoch>> {
+    if epoch == FAR_FUTURE_EPOCH {
+        return Ok(None)
+    }
+    let slot = epoch.checked_mul(context.slots_per_epoch).ok_or(Error::Overflow)?;
+    let timestamp = context.timestamp_at_slot(slot, genesis_time)?;
+    Ok(Some(LifecycleEpoch { epoch, timestamp }))
+}
+
+/// The status of `validator`, with `balance`, at `epoch`.
+pub fn validator_status(validator: &Validator, balance: Gwei, epoch: Epoch) -> ValidatorStatus {
+    if validator.activation_epoch > epoch {
+        if validator.activation_eligibility_epoch == FAR_FUTURE_EPOCH {
+            ValidatorStatus::PendingInitialized
+        } else {
+            ValidatorStatus::PendingQueued
+        }
+    } else if is_active_validator(validator, epoch) {
+        if validator.slashed {
+            ValidatorStatus::ActiveSlashed
+        } else if validator.exit_epoch == FAR_FUTURE_EPOCH {
+            ValidatorStatus::ActiveOngoing
+        } else {
+            ValidatorStatus::ActiveExiting
+        }
+    } else if epoch < validator.withdrawable_epoch {
+        if validator.slashed {
+            ValidatorStatus::ExitedSlashed
+        } else {
+            ValidatorStatus::ExitedUnslashed
+        }
+    } else if balance != 0 {
+        ValidatorStatus::WithdrawalPossible
+    } else {
+        ValidatorStatus::WithdrawalDone
+    }
+}
+
+/// Collect the lifecycle and pending operations of the validator at `index` in `state`, with the
+/// timestamps of a chain starting at `genesis_time`, e.g. from `Context::genesis_time`.
+pub fn validator_timeline<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    index: ValidatorIndex,
+    genesis_time: u64,
+    context: &Context,
+) -> Result<Timeline> {
+    let validator = state
+        .validators
+        .get(index)
+        .ok_or(Error::OutOfBounds { requested: index, bound: state.validators.len() })?;
+    let balance = state.balances[index];
+    let epoch = get_current_epoch(state, context);
+
+    let status = validator_status(validator, balance, epoch);
+
+    Ok(Timeline {
+        index,
+        public_key: validator.public_key.clone(),
+        status,
+        balance,
+        effective_balance: validator.effective_balance,
+        activation_eligibility: lifecycle_epoch(
+            validator.activation_eligibility_epoch,
+            genesis_time,
+            context,
+        )?,
+        activation: lifecycle_epoch(validator.activation_epoch, genesis_time, context)?,
+        exit: lifecycle_epoch(validator.exit_epoch, genesis_time, context)?,
+        withdrawable: lifecycle_epoch(validator.withdrawable_epoch, genesis_time, context)?,
+        pending_deposits: state
+            .pending_deposits
+            .iter()
+            .filter(|deposit| deposit.public_key == validator.public_key)
+            .cloned()
+            .collect(),
+        pending_partial_withdrawals: state
+            .pending_partial_withdrawals
+            .iter()
+            .filter(|withdrawal| withdrawal.validator_index == index)
+            .cloned()
+            .collect(),
+        pending_consolidations: state
+            .pending_consolidations
+            .iter()
+            .filter(|consolidation| {
+                consolidation.source_index == index || consolidation.target_index == index
+            })
+            .cloned()
+            .collect(),
+    })
+}
+
+/// Justification-relevant figures for one epoch, as seen from a state in that epoch.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EpochSummary {
+    #[serde(with = "crate::serde::as_str")]
+    pub epoch: Epoch,
+    pub justified: Checkpoint,
+    pub finalized: Checkpoint,
+    /// Effective balance of unslashed validators with a timely target vote in the previous epoch.
+    #[serde(with = "crate::serde::as_str")]
+    pub previous_target_balance: Gwei,
+    /// Effective balance of unslashed validators with a timely target vote in the current epoch.
+    #[serde(with = "crate::serde::as_str")]
+    pub current_target_balance: Gwei,
+    /// `previous_target_balance` as a fraction of `total_active_balance`.
+    pub participation_rate_prev: f64,
+    /// `current_target_balance` as a fraction of `total_active_balance`.
+    pub participation_rate_curr: f64,
+    #[serde(with = "crate::serde::as_str")]
+    pub total_active_balance: Gwei,
+    /// Mean (actual, not effective) balance of the validators active in `epoch`.
+    #[serde(with = "crate::serde::as_str")]
+    pub avg_balance: Gwei,
+}
+
+impl EpochSummary {
+    /// Whether `process_justification_and_finalization` justifies the previous epoch given these
+    /// balances.
+    pub fn justifies_previous_epoch(&self) -> bool {
+        self.previous_target_balance * 3 >= self.total_active_balance * 2
+    }
+
+    /// Whether `process_justification_and_finalization` justifies the current epoch given these
+    /// balances.
+    pub fn justifies_current_epoch(&self) -> bool {
+        self.current_target_balance * 3 >= self.total_active_balance * 2
+    }
+}
+
+// An empty active set has no participation to speak of; report 0 rather than NaN.
+fn participation_rate(target_balance: Gwei, total_active_balance: Gwei) -> f64 {
+    if total_active_balance == 0 {
+        0.0
+    } else {
+        target_balance as f64 / total_active_balance as f64
+    }
+}
+
+/// Summarize the current epoch of `state`.
+///
+/// Target participation is weighed exactly as in `process_justification_and_finalization`, so
+/// for a state at the last slot of an epoch the summary predicts the outcome of justification.
+pub fn epoch_summary<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    context: &Context,
+) -> Result<EpochSummary> {
+    let epoch = get_current_epoch(state, context);
+    let previous_indices = get_unslashed_participating_indices(
+        state,
+        TIMELY_TARGET_FLAG_INDEX,
+        get_previous_epoch(state, context),
+        context,
+    )?;
+    let current_indices =
+        get_unslashed_participating_indices(state, TIMELY_TARGET_FLAG_INDEX, epoch, context)?;
+    let total_active_balance = get_total_active_balance(state, context)?;
+    let previous_target_balance = get_total_balance(state, &previous_indices, context)?;
+    let current_target_balance = get_total_balance(state, &current_indices, context)?;
+
+    let active_indices = get_active_validator_indices(state, epoch);
+    let avg_balance = if active_indices.is_empty() {
+        0
+    } else {
+        let total = active_indices
+            .iter()
+            .try_fold(0 as Gwei, |total, &i| total.checked_add(state.balances[i]))
+            .ok_or(Error::Overflow)?;
+        total / active_indices.len() as Gwei
+    };
+
+    Ok(EpochSummary {
+        epoch,
+        justified: state.current_justified_checkpoint.clone(),
+        finalized: state.finalized_checkpoint.clone(),
+        previous_target_balance,
+        current_target_balance,
+        participation_rate_prev: participation_rate(previous_target_balance, total_active_balance),
+        participation_rate_curr: participation_rate(current_target_balance, total_active_balance),
+        total_active_balance,
+        avg_balance,
+    })
+}
+
+/// Summarize each state in `states` whose current epoch falls in `epochs`, e.g. stored
+/// per-epoch states. See `Executor::replay_epoch_summaries` to summarize epochs while replaying
+/// blocks instead.
+///
+/// States are expected in epoch order; the summaries are returned in the order given.
+pub fn epoch_summaries<
+    'a,
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    states: impl IntoIterator<
+        Item = &'a BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    >,
+    epochs: Range<Epoch>,
+    context: &Context,
+) -> Result<Vec<EpochSummary>> {
+    states
+        .into_iter()
+        .filter(|state| epochs.contains(&get_current_epoch(state, context)))
+        .map(|state| epoch_summary(state, context))
+        .collect()
+}
+
+impl<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+    >
+    Executor<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_PROPOSER_SLASHINGS,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >
+{
+    /// Replay `blocks` on top of the current state, as `apply_blocks` does, and summarize each
+    /// epoch in `epochs` on the way, from the state at its last slot. The summaries are the ones
+    /// `epoch_summaries` returns for stored states at those slots, without keeping the states.
+    ///
+    /// Epochs which end after the last block are summarized only if the replay reaches their last
+    /// slot, and epochs before the upgrade to Electra are skipped.
+    pub fn replay_epoch_summaries<
+        I: IntoIterator<
+            Item = SignedBeaconBlock<
+                MAX_PROPOSER_SLASHINGS,
+                MAX_VALIDATORS_PER_COMMITTEE,
+                MAX_ATTESTER_SLASHINGS,
+                MAX_ATTESTATIONS,
+                MAX_DEPOSITS,
+                MAX_VOLUNTARY_EXITS,
+                SYNC_COMMITTEE_SIZE,
+                BYTES_PER_LOGS_BLOOM,
+                MAX_EXTRA_DATA_BYTES,
+                MAX_BYTES_PER_TRANSACTION,
+                MAX_TRANSACTIONS_PER_PAYLOAD,
+                MAX_WITHDRAWALS_PER_PAYLOAD,
+                MAX_BLS_TO_EXECUTION_CHANGES,
+                MAX_BLOB_COMMITMENTS_PER_BLOCK,
+                MAX_VALIDATORS_PER_SLOT,
+                MAX_COMMITTEES_PER_SLOT,
+                MAX_ATTESTER_SLASHINGS_ELECTRA,
+                MAX_ATTESTATIONS_ELECTRA,
+                MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+                MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+                MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+            >,
+        >,
+    >(
+        &mut self,
+        blocks: I,
+        epochs: Range<Epoch>,
+        validation: Validation,
+    ) -> Result<Vec<EpochSummary>> {
+        let mut summaries = vec![];
+        let mut next_epoch =
+            epochs.start.max(compute_epoch_at_slot(self.state.slot(), &self.context));
+        for signed_block in blocks {
+            let slot = signed_block.message().slot();
+            self.summarize_epochs_before(slot, &mut next_epoch, epochs.end, &mut summaries)?;
+            let options = ReplayOptions { validation, ..Default::default() };
+            self.apply_blocks(iter::once(signed_block), options)?;
+        }
+        let end_slot = self.state.slot() + 1;
+        self.summarize_epochs_before(end_slot, &mut next_epoch, epochs.end, &mut summaries)?;
+        Ok(summaries)
+    }
+
+    // Summarize the epochs from `next_epoch` to `end_epoch` which end before `slot`, advancing
+    // through empty slots to the last slot of each.
+    fn summarize_epochs_before(
+        &mut self,
+        slot: Slot,
+        next_epoch: &mut Epoch,
+        end_epoch: Epoch,
+        summaries: &mut Vec<EpochSummary>,
+    ) -> Result<()> {
+        while *next_epoch < end_epoch {
+            let last_slot = (*next_epoch + 1) * self.context.slots_per_epoch - 1;
+            if last_slot >= slot {
+                break
+            }
+            if self.state.slot() < last_slot {
+                self.process_slots(last_slot)?;
+            }
+            if let Ok(state) = self.state.inner().as_electra() {
+                summaries.push(epoch_summary(state, &self.context)?);
+            }
+            *next_epoch += 1;
+        }
+        Ok(())
+    }
+}
+
+/// The validators voting for one target checkpoint, weighed by effective balance.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TargetVotes {
+    pub target: Checkpoint,
+    #[serde(with = "crate::serde::as_str")]
+    pub balance: Gwei,
+    pub validator_count: usize,
+}
+
+/// The target votes for `epoch` (the current or previous epoch of `state`) as recorded in the
+/// participation flags of `state`.
+///
+/// Only votes for the canonical target of `state` earn the flag, so there is at most one entry
+/// and its `balance` is the one `process_justification_and_finalization` weighs. Use
+/// `target_vote_distribution_from_attestations` to see votes for competing targets.
+pub fn target_vote_distribution_from_participation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    epoch: Epoch,
+    context: &Context,
+) -> Result<Vec<TargetVotes>> {
+    let indices =
+        get_unslashed_participating_indices(state, TIMELY_TARGET_FLAG_INDEX, epoch, context)?;
+    if indices.is_empty() {
+        return Ok(vec![])
+    }
+    let target = Checkpoint { epoch, root: *get_block_root(state, epoch, context)? };
+    let balance = get_total_balance(state, &indices, context)?;
+    Ok(vec![TargetVotes { target, balance, validator_count: indices.len() }])
+}
+
+/// The target votes for `epoch` found in `attestations`, e.g. those included in the blocks of
+/// one or more competing branches, ordered by descending balance.
+///
+/// Attesters are resolved with the committees of `state`, which must be able to compute the
+/// committees of `epoch`. Each validator counts once per target it voted for, however often
+/// its vote was included; a validator voting for several targets counts towards each of them.
+/// Effective balances are summed without the lower bound applied by `get_total_balance`.
+pub fn target_vote_distribution_from_attestations<
+    'a,
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    attestations: impl IntoIterator<
+        Item = &'a Attestation<MAX_VALIDATORS_PER_SLOT, MAX_COMMITTEES_PER_SLOT>,
+    >,
+    epoch: Epoch,
+    context: &Context,
+) -> Result<Vec<TargetVotes>> {
+    let mut voters: Vec<(Checkpoint, HashSet<ValidatorIndex>)> = vec![];
+    for attestation in attestations {
+        let target = &attestation.data.target;
+        if target.epoch != epoch {
+            continue
+        }
+        let indices = get_attesting_indices(state, attestation, context)?;
+        match voters.iter_mut().find(|(checkpoint, _)| checkpoint == target) {
+            Some((_, voters)) => voters.extend(indices),
+            None => voters.push((target.clone(), indices)),
+        }
+    }
+
+    let mut distribution = voters
+        .into_iter()
+        .map(|(target, indices)| {
+            let balance = indices
+                .iter()
+                .try_fold(0 as Gwei, |total, &i| {
+                    total.checked_add(state.validators[i].effective_balance)
+                })
+                .ok_or(Error::Overflow)?;
+            Ok(TargetVotes { target, balance, validator_count: indices.len() })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    distribution.sort_by(|a, b| b.balance.cmp(&a.balance));
+    Ok(distribution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        crypto::SecretKey,
+        electra::{
+            add_flag, get_attestation_participation_flag_indices, get_beacon_committee,
+            get_committee_count_per_slot, minimal, process_justification_and_finalization,
+            process_slots, weigh_justification_and_finalization,
+        },
+        fixtures::chain::{self, BlockOperations, ChainGenerator},
+        phase0::{AttestationData, BeaconBlockHeader, Validator},
+        primitives::Root,
+        ssz::prelude::{deserialize, Bitvector, HashTreeRoot},
+    };
+
+    #[test]
+    fn test_validator_timeline() {
+        let context = Context::for_minimal();
+        // epoch 10 of a chain with 6 second slots and 8 slot epochs
+        let genesis_time = 1_600_000_000;
+        let mut state =
+            minimal::BeaconState { slot: 10 * context.slots_per_epoch + 3, ..Default::default() };
+        let exiting = Validator {
+            activation_eligibility_epoch: 1,
+            activation_epoch: 6,
+            exit_epoch: 12,
+            withdrawable_epoch: 20,
+            effective_balance: 32_000_000_000,
+            ..Default::default()
+        };
+        let pending = Validator {
+            activation_eligibility_epoch: FAR_FUTURE_EPOCH,
+            activation_epoch: FAR_FUTURE_EPOCH,
+            exit_epoch: FAR_FUTURE_EPOCH,
+            withdrawable_epoch: FAR_FUTURE_EPOCH,
+            ..Default::default()
+        };
+        let withdrawn =
+            Validator { exit_epoch: 8, withdrawable_epoch: 9, slashed: true, ..exiting.clone() };
+        for (validator, balance) in
+            [(exiting, 32_100_000_000), (pending, 1_000_000_000), (withdrawn, 0)]
+        {
+            let seed = state.validators.len() as u8 + 1;
+            let public_key = SecretKey::key_gen(&[seed; 32]).unwrap().public_key();
+            state.validators.push(Validator { public_key, ..validator });
+            state.balances.push(balance);
+        }
+        let public_keys = state
+            .validators
+            .iter()
+            .map(|validator| validator.public_key.clone())
+            .collect::<Vec<_>>();
+        let deposit = |index: usize, amount| PendingDeposit {
+            public_key: public_keys[index].clone(),
+            amount,
+            ..Default::default()
+        };
+        state.pending_deposits.push(deposit(0, 7));
+        state.pending_deposits.push(deposit(1, 8));
+        state.pending_partial_withdrawals.push(PendingPartialWithdrawal {
+            validator_index: 0,
+            amount: 9,
+            withdrawable_epoch: 11,
+        });
+        state
+            .pending_consolidations
+            .push(PendingConsolidation { source_index: 2, target_index: 0 });
+        state
+            .pending_consolidations
+            .push(PendingConsolidation { source_index: 1, target_index: 2 });
+
+        let timeline = validator_timeline(&state, 0, genesis_time, &context).unwrap();
+        let at =
+            |epoch: Epoch| Some(LifecycleEpoch { epoch, timestamp: genesis_time + epoch * 48 });
+        assert_eq!(
+            timeline,
+            Timeline {
+                index: 0,
+                public_key: public_keys[0].clone(),
+                status: ValidatorStatus::ActiveExiting,
+                balance: 32_100_000_000,
+                effective_balance: 32_000_000_000,
+                activation_eligibility: at(1),
+                activation: at(6),
+                exit: at(12),
+                withdrawable: at(20),
+                pending_deposits: vec![deposit(0, 7)],
+                pending_partial_withdrawals: vec![PendingPartialWithdrawal {
+                    validator_index: 0,
+                    amount: 9,
+                    withdrawable_epoch: 11,
+                }],
+                pending_consolidations: vec![PendingConsolidation {
+                    source_index: 2,
+                    target_index: 0,
+                }],
+            }
+        );
+
+        let timeline = validator_timeline(&state, 1, genesis_time, &context).unwrap();
+        assert_eq!(timeline.status, ValidatorStatus::PendingInitialized);
+        assert_eq!(
+            (timeline.activation_eligibility, timeline.activation, timeline.exit),
+            (None, None, None)
+        );
+        assert_eq!(timeline.withdrawable, None);
+        assert_eq!(timeline.pending_deposits.len(), 1);
+        assert_eq!(timeline.pending_consolidations.len(), 1);
+
+        let timeline = validator_timeline(&state, 2, genesis_time, &context).unwrap();
+        assert_eq!(timeline.status, ValidatorStatus::WithdrawalDone);
+        assert_eq!(timeline.pending_consolidations.len(), 2);
+
+        let json = serde_json::to_value(&timeline).unwrap();
+        assert_eq!(json["status"], "withdrawal_done");
+        assert_eq!(json["withdrawable"]["timestamp"], "1600000432");
+
+        assert!(validator_timeline(&state, 3, genesis_time, &context).is_err());
+    }
+
+    // a state at the last slot of `epoch` where the validators at `previous_voters` and
+    // `current_voters` made timely target votes
+    fn voting_state(
+        epoch: Epoch,
+        previous_voters: Range<usize>,
+        current_voters: Range<usize>,
+        context: &Context,
+    ) -> minimal::BeaconState {
+        let mut state = minimal::BeaconState {
+            slot: (epoch + 1) * context.slots_per_epoch - 1,
+            ..Default::default()
+        };
+        for i in 0..30 {
+            state.validators.push(Validator {
+                activation_epoch: 0,
+                exit_epoch: FAR_FUTURE_EPOCH,
+                withdrawable_epoch: FAR_FUTURE_EPOCH,
+                // vary balances so that the count of voters is not the balance that matters
+                effective_balance: (16 + i as Gwei) * 10u64.pow(9),
+                ..Default::default()
+            });
+            state.balances.push((16 + i as Gwei) * 10u64.pow(9) + 1);
+            let flags = |voters: &Range<usize>| {
+                if voters.contains(&i) {
+                    add_flag(0, TIMELY_TARGET_FLAG_INDEX)
+                } else {
+                    0
+                }
+            };
+            state.previous_epoch_participation.push(flags(&previous_voters));
+            state.current_epoch_participation.push(flags(&current_voters));
+        }
+        state
+    }
+
+    #[test]
+    fn test_epoch_summaries_predict_justification() {
+        let context = Context::for_minimal();
+        let epoch = 5;
+        // the last 17 validators hold just over 2/3 of the stake, the last 16 just under
+        let cases = [(13..30, 0..30), (14..30, 13..30), (0..30, 14..30), (0..0, 0..0)];
+        let states = cases
+            .into_iter()
+            .map(|(previous, current)| voting_state(epoch, previous, current, &context))
+            .collect::<Vec<_>>();
+
+        let summaries = epoch_summaries(&states, epoch..epoch + 1, &context).unwrap();
+        assert_eq!(summaries.len(), cases.len());
+        for (mut state, summary) in states.into_iter().zip(summaries) {
+            assert_eq!(summary.epoch, epoch);
+            assert_eq!(summary.total_active_balance, (16..46).sum::<Gwei>() * 10u64.pow(9));
+            assert_eq!(summary.avg_balance, 30_500_000_001);
+            assert_eq!(
+                summary.justifies_previous_epoch(),
+                summary.participation_rate_prev >= 2.0 / 3.0
+            );
+            assert_eq!(
+                summary.justifies_current_epoch(),
+                summary.participation_rate_curr >= 2.0 / 3.0
+            );
+
+            process_justification_and_finalization(&mut state, &context).unwrap();
+            let justified = state.current_justified_checkpoint.epoch;
+            let expected = if summary.justifies_current_epoch() {
+                epoch
+            } else if summary.justifies_previous_epoch() {
+                epoch - 1
+            } else {
+                0
+            };
+            assert_eq!(justified, expected);
+        }
+
+        assert!(epoch_summaries(&[voting_state(epoch, 0..0, 0..0, &context)], 0..epoch, &context)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_replay_epoch_summaries() {
+        let context = chain::context();
+        let genesis_state = chain::genesis_state(64, &context);
+        let mut generator = ChainGenerator::new(genesis_state.clone(), context.clone());
+        let epochs = 5;
+        let mut blocks = vec![];
+        let mut stored_states = vec![];
+        for slot in 1..epochs * context.slots_per_epoch {
+            blocks.push(generator.next_block(BlockOperations::default()));
+            if (slot + 1) % context.slots_per_epoch == 0 {
+                stored_states.push(generator.executor.state.inner().clone());
+            }
+        }
+        let stored_states =
+            stored_states.iter().filter_map(|state| state.as_electra().ok()).collect::<Vec<_>>();
+
+        let mut executor = Executor::new(genesis_state.clone(), context.clone());
+        let summaries = executor
+            .replay_epoch_summaries(blocks.clone(), 0..epochs, Validation::Enabled)
+            .unwrap();
+        // the first epoch is before the upgrade to Electra
+        assert_eq!(
+            summaries.iter().map(|summary| summary.epoch).collect::<Vec<_>>(),
+            (chain::ELECTRA_FORK_EPOCH..epochs).collect::<Vec<_>>()
+        );
+        assert_eq!(summaries, epoch_summaries(stored_states, 0..epochs, &context).unwrap());
+        assert_eq!(executor.state.slot(), epochs * context.slots_per_epoch - 1);
+
+        assert!(summaries.iter().any(EpochSummary::justifies_current_epoch));
+        for pair in summaries.windows(2) {
+            if pair[0].justifies_current_epoch() {
+                assert!(pair[1].justified.epoch >= pair[0].epoch);
+            }
+        }
+
+        // an epoch is summarized only once the replay reaches its last slot
+        let mut executor = Executor::new(genesis_state, context.clone());
+        let blocks = blocks.into_iter().take(3 * context.slots_per_epoch as usize - 1);
+        let partial =
+            executor.replay_epoch_summaries(blocks, 2..epochs, Validation::Enabled).unwrap();
+        assert_eq!(partial, &summaries[1..2]);
+    }
+
+    #[test]
+    fn test_epoch_summary_without_active_validators() {
+        assert_eq!(participation_rate(0, 0), 0.0);
+
+        let context = Context::for_minimal();
+        let state = voting_state(5, 0..0, 0..0, &context);
+        let empty = minimal::BeaconState { slot: state.slot, ..Default::default() };
+        let summary = epoch_summary(&empty, &context).unwrap();
+        assert_eq!(summary.participation_rate_prev, 0.0);
+        assert_eq!(summary.participation_rate_curr, 0.0);
+        assert_eq!(summary.avg_balance, 0);
+        assert!(!summary.justifies_current_epoch());
+    }
+
+    // a chain of 64 validators of equal balance up to the first slot of `epoch`
+    fn chain_at(epoch: Epoch, context: &Context) -> minimal::BeaconState {
+        let mut state = minimal::BeaconState::default();
+        for _ in 0..64 {
+            state.validators.push(Validator {
+                activation_epoch: 0,
+                exit_epoch: FAR_FUTURE_EPOCH,
+                withdrawable_epoch: FAR_FUTURE_EPOCH,
+                effective_balance: 32 * 10u64.pow(9),
+                ..Default::default()
+            });
+            state.balances.push(32 * 10u64.pow(9));
+            state.previous_epoch_participation.push(0);
+            state.current_epoch_participation.push(0);
+            state.inactivity_scores.push(0);
+        }
+        process_slots(&mut state, epoch * context.slots_per_epoch, context).unwrap();
+        state
+    }
+
+    // the branch of the chain in `state` with its own block at the slot of `state`, advanced to
+    // the last slot of the epoch
+    fn branch(state: &minimal::BeaconState, id: u8, context: &Context) -> minimal::BeaconState {
+        let mut branch = state.clone();
+        // the header `process_block_header` leaves for a block of the branch
+        branch.latest_block_header = BeaconBlockHeader {
+            slot: state.slot,
+            parent_root: state.latest_block_header.hash_tree_root().unwrap(),
+            body_root: Root::try_from(&[id; 32][..]).unwrap(),
+            ..Default::default()
+        };
+        let epoch = get_current_epoch(state, context);
+        process_slots(&mut branch, (epoch + 1) * context.slots_per_epoch - 1, context).unwrap();
+        branch
+    }
+
+    // an attestation to `target` from every member of every committee at `slot`
+    fn slot_attestation(
+        state: &minimal::BeaconState,
+        slot: Slot,
+        target: &Checkpoint,
+        context: &Context,
+    ) -> minimal::Attestation {
+        let committees_per_slot = get_committee_count_per_slot(state, target.epoch, context);
+        let attesters = (0..committees_per_slot)
+            .map(|index| get_beacon_committee(state, slot, index, context).unwrap().len())
+            .sum::<usize>();
+        // a bit for each member of the committees, followed by the delimiting bit
+        let mut bytes = vec![0u8; attesters / 8 + 1];
+        for i in 0..=attesters {
+            bytes[i / 8] |= 1 << (i % 8);
+        }
+        let mut committee_bits = Bitvector::default();
+        for index in 0..committees_per_slot {
+            committee_bits.set(index, true);
+        }
+        minimal::Attestation {
+            aggregation_bits: deserialize(&bytes).unwrap(),
+            data: AttestationData {
+                slot,
+                index: 0,
+                beacon_block_root: target.root,
+                source: state.current_justified_checkpoint.clone(),
+                target: target.clone(),
+            },
+            committee_bits,
+            ..Default::default()
+        }
+    }
+
+    // record the participation of `attestation` in `state`, as `process_attestation` does
+    fn include(
+        state: &mut minimal::BeaconState,
+        attestation: &minimal::Attestation,
+        context: &Context,
+    ) {
+        let inclusion_delay = state.slot - attestation.data.slot;
+        let flags = get_attestation_participation_flag_indices(
+            state,
+            &attestation.data,
+            inclusion_delay,
+            context,
+        )
+        .unwrap();
+        for index in get_attesting_indices(state, attestation, context).unwrap() {
+            for &flag in &flags {
+                let participation = &mut state.current_epoch_participation[index];
+                *participation = add_flag(*participation, flag);
+            }
+        }
+    }
+
+    #[test]
+    fn test_target_vote_distribution() {
+        let context = Context::for_minimal();
+        let epoch = 2;
+        let slots_per_epoch = context.slots_per_epoch;
+        let start_slot = epoch * slots_per_epoch;
+        let validator_balance = 32 * 10u64.pow(9);
+
+        // the chain forks at the first slot of `epoch`: the committees of the first six slots
+        // vote for the target of one branch, the committee of the seventh for the other
+        let chain = chain_at(epoch, &context);
+        let mut branches = [branch(&chain, 1, &context), branch(&chain, 2, &context)];
+        let targets = branches.each_ref().map(|branch| Checkpoint {
+            epoch,
+            root: *get_block_root(branch, epoch, &context).unwrap(),
+        });
+        assert_ne!(targets[0], targets[1]);
+        let voting_slots = [start_slot..start_slot + 6, start_slot + 6..start_slot + 7];
+        let mut included = vec![];
+        for ((branch, target), slots) in branches.iter_mut().zip(&targets).zip(voting_slots) {
+            for slot in slots {
+                let attestation = slot_attestation(branch, slot, target, &context);
+                include(branch, &attestation, &context);
+                included.push(attestation);
+            }
+        }
+
+        // raw mode sees both targets, across both branches; an aggregate included on both
+        // branches counts once and votes for another epoch are left out
+        included.push(included[0].clone());
+        let mut other_epoch = included[0].clone();
+        other_epoch.data.target.epoch = epoch - 1;
+        included.push(other_epoch);
+        let distribution =
+            target_vote_distribution_from_attestations(&branches[0], &included, epoch, &context)
+                .unwrap();
+        let votes = |target: &Checkpoint, validator_count: usize| TargetVotes {
+            target: target.clone(),
+            balance: validator_count as Gwei * validator_balance,
+            validator_count,
+        };
+        assert_eq!(distribution, vec![votes(&targets[0], 48), votes(&targets[1], 8)]);
+
+        // state mode sees only the canonical target of each branch, with the balance that
+        // `process_justification_and_finalization` weighs
+        for (branch, (target, validator_count)) in
+            branches.iter().zip([(&targets[0], 48), (&targets[1], 8)])
+        {
+            let current =
+                target_vote_distribution_from_participation(branch, epoch, &context).unwrap();
+            assert_eq!(current, vec![votes(target, validator_count)]);
+            let previous =
+                target_vote_distribution_from_participation(branch, epoch - 1, &context).unwrap();
+            assert!(previous.is_empty());
+
+            let mut processed = branch.clone();
+            process_justification_and_finalization(&mut processed, &context).unwrap();
+            let mut weighed = branch.clone();
+            let total_active_balance = get_total_active_balance(branch, &context).unwrap();
+            weigh_justification_and_finalization(
+                &mut weighed,
+                total_active_balance,
+                0,
+                current[0].balance,
+                &context,
+            )
+            .unwrap();
+            assert_eq!(
+                processed.current_justified_checkpoint,
+                weighed.current_justified_checkpoint
+            );
+            assert_eq!(processed.justification_bits, weighed.justification_bits);
+        }
+
+        // two thirds of the stake justifies the target of one branch only
+        let justified = branches.map(|mut branch| {
+            process_justification_and_finalization(&mut branch, &context).unwrap();
+            branch.current_justified_checkpoint
+        });
+        assert_eq!(justified[0], targets[0]);
+        assert_eq!(justified[1], chain.current_justified_checkpoint);
+    }
+}
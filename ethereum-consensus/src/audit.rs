@@ -0,0 +1,188 @@
+//! Utilities for auditing the custody of a set of validators.
+#[cfg(feature = "serde")]
+use crate::Error;
+use crate::{
+    phase0::Validator,
+    primitives::{
+        BlsPublicKey, ExecutionAddress, ValidatorIndex, BLS_WITHDRAWAL_PREFIX,
+        COMPOUNDING_WITHDRAWAL_PREFIX, ETH1_ADDRESS_WITHDRAWAL_PREFIX,
+    },
+};
+#[cfg(feature = "serde")]
+use std::path::Path;
+
+/// The set of validators to audit, either by index into the registry or by public key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidatorSelection {
+    Indices(Vec<ValidatorIndex>),
+    PublicKeys(Vec<BlsPublicKey>),
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct KeystorePublicKey {
+    #[serde(with = "crate::serde::as_hex")]
+    pubkey: BlsPublicKey,
+}
+
+impl ValidatorSelection {
+    /// Selects the validators for every EIP-2335 keystore (any `.json` file) in `path`.
+    #[cfg(feature = "serde")]
+    pub fn from_keystore_dir<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let mut paths = vec![];
+        for entry in std::fs::read_dir(path)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().map(|ext| ext == "json").unwrap_or(false) {
+                paths.push(path);
+            }
+        }
+        // NOTE: directory iteration order is platform-dependent
+        paths.sort();
+        let mut public_keys = Vec::with_capacity(paths.len());
+        for path in paths {
+            let file = std::fs::File::open(path)?;
+            let keystore: KeystorePublicKey = serde_json::from_reader(file)?;
+            public_keys.push(keystore.pubkey);
+        }
+        Ok(Self::PublicKeys(public_keys))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditFinding {
+    /// The selected validator could not be found in the registry.
+    UnknownValidator(ValidatorSelector),
+    /// The validator still has BLS (`0x00`) withdrawal credentials.
+    BlsCredentials { index: ValidatorIndex },
+    /// The validator withdraws to an address outside the allowlist.
+    UnapprovedAddress { index: ValidatorIndex, address: ExecutionAddress },
+    /// The validator has compounding (`0x02`) credentials; the allowlist only approves `0x01`.
+    CompoundingCredentials { index: ValidatorIndex, address: ExecutionAddress },
+    /// The validator has credentials with a prefix unknown to this audit.
+    UnknownPrefix { index: ValidatorIndex, prefix: u8 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidatorSelector {
+    Index(ValidatorIndex),
+    PublicKey(BlsPublicKey),
+}
+
+fn audit_validator(
+    index: ValidatorIndex,
+    validator: &Validator,
+    allowlist: &[ExecutionAddress],
+) -> Option<AuditFinding> {
+    let credentials = validator.withdrawal_credentials.as_slice();
+    let prefix = credentials[0];
+    let address = || ExecutionAddress::try_from(&credentials[12..]).expect("correct length");
+    match prefix {
+        BLS_WITHDRAWAL_PREFIX => Some(AuditFinding::BlsCredentials { index }),
+        ETH1_ADDRESS_WITHDRAWAL_PREFIX => {
+            let address = address();
+            (!allowlist.contains(&address))
+                .then_some(AuditFinding::UnapprovedAddress { index, address })
+        }
+        COMPOUNDING_WITHDRAWAL_PREFIX => {
+            let address = address();
+            if allowlist.contains(&address) {
+                Some(AuditFinding::CompoundingCredentials { index, address })
+            } else {
+                Some(AuditFinding::UnapprovedAddress { index, address })
+            }
+        }
+        prefix => Some(AuditFinding::UnknownPrefix { index, prefix }),
+    }
+}
+
+/// Audit the withdrawal credentials of the `selection` of `validators` (e.g. `state.validators()`)
+/// against the approved `allowlist` of execution addresses. Only `0x01` credentials pointing at an
+/// allowlisted address pass; every other selected validator yields exactly one finding.
+/// Findings are returned in the order of `selection`.
+pub fn verify_withdrawal_credentials(
+    validators: &[Validator],
+    selection: &ValidatorSelection,
+    allowlist: &[ExecutionAddress],
+) -> Vec<AuditFinding> {
+    let mut findings = vec![];
+    match selection {
+        ValidatorSelection::Indices(indices) => {
+            for &index in indices {
+                match validators.get(index) {
+                    Some(validator) => {
+                        findings.extend(audit_validator(index, validator, allowlist))
+                    }
+                    None => findings
+                        .push(AuditFinding::UnknownValidator(ValidatorSelector::Index(index))),
+                }
+            }
+        }
+        ValidatorSelection::PublicKeys(public_keys) => {
+            for public_key in public_keys {
+                let index = validators.iter().position(|v| &v.public_key == public_key);
+                match index {
+                    Some(index) => {
+                        findings.extend(audit_validator(index, &validators[index], allowlist))
+                    }
+                    None => findings.push(AuditFinding::UnknownValidator(
+                        ValidatorSelector::PublicKey(public_key.clone()),
+                    )),
+                }
+            }
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::Bytes32;
+
+    fn validator_with(prefix: u8, address: &ExecutionAddress, seed: u8) -> Validator {
+        let mut credentials = vec![prefix];
+        credentials.extend_from_slice(&[0u8; 11]);
+        credentials.extend_from_slice(address.as_slice());
+        let public_key = BlsPublicKey::try_from(&[seed; 48][..]).unwrap();
+        Validator {
+            public_key,
+            withdrawal_credentials: Bytes32::try_from(credentials.as_slice()).unwrap(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_withdrawal_credential_audit() {
+        let approved = ExecutionAddress::try_from(&[0xaa; 20][..]).unwrap();
+        let unapproved = ExecutionAddress::try_from(&[0xbb; 20][..]).unwrap();
+        let validators = vec![
+            validator_with(ETH1_ADDRESS_WITHDRAWAL_PREFIX, &approved, 0),
+            validator_with(BLS_WITHDRAWAL_PREFIX, &ExecutionAddress::default(), 1),
+            validator_with(ETH1_ADDRESS_WITHDRAWAL_PREFIX, &unapproved, 2),
+            validator_with(COMPOUNDING_WITHDRAWAL_PREFIX, &approved, 3),
+        ];
+        let allowlist = [approved.clone()];
+
+        let selection = ValidatorSelection::Indices(vec![0, 1, 2, 3, 4]);
+        let findings = verify_withdrawal_credentials(&validators, &selection, &allowlist);
+        assert_eq!(
+            findings,
+            vec![
+                AuditFinding::BlsCredentials { index: 1 },
+                AuditFinding::UnapprovedAddress { index: 2, address: unapproved },
+                AuditFinding::CompoundingCredentials { index: 3, address: approved },
+                AuditFinding::UnknownValidator(ValidatorSelector::Index(4)),
+            ]
+        );
+
+        let selection = ValidatorSelection::PublicKeys(vec![
+            validators[3].public_key.clone(),
+            validators[0].public_key.clone(),
+        ]);
+        let findings = verify_withdrawal_credentials(&validators, &selection, &allowlist);
+        assert_eq!(
+            findings,
+            vec![AuditFinding::CompoundingCredentials { index: 3, address: allowlist[0].clone() }]
+        );
+    }
+}
@@ -0,0 +1,586 @@
+//! Verification of historical block ranges downloaded while backfilling from a trusted anchor,
+//! e.g. after checkpoint sync.
+use crate::{
+    crypto::{
+        verify_multiple_signatures_decompressed, verify_signature_decompressed,
+        DecompressedPublicKey,
+    },
+    map_fork,
+    phase0::compute_domain,
+    primitives::{BlsPublicKey, DomainType, Root, Slot, ValidatorIndex},
+    signing::compute_signing_root,
+    ssz::prelude::*,
+    state_transition::{Context, DecompressedPubkeyCache},
+    types::SignedBeaconBlock,
+};
+use std::borrow::Cow;
+use thiserror::Error;
+
+/// Order of the blocks in a batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Blocks are in ascending slot order and `anchor` is the `(slot, root)` of the trusted
+    /// block preceding the first block of the batch.
+    Forward,
+    /// Blocks are in descending slot order and `anchor` is the `(slot, parent_root)` of the
+    /// trusted block following the first block of the batch.
+    Backward,
+}
+
+#[derive(Debug, Error)]
+pub enum BatchErrorReason {
+    #[error("parent root {found} does not match the root {expected} of the preceding block")]
+    ParentRootMismatch { expected: Root, found: Root },
+    #[error("block root {found} does not match the parent root {expected} of the following block")]
+    BlockRootMismatch { expected: Root, found: Root },
+    #[error("slot {slot} is out of order after slot {previous_slot}")]
+    SlotOutOfOrder { slot: Slot, previous_slot: Slot },
+    #[error("no public key is known for proposer {0}")]
+    UnknownProposer(ValidatorIndex),
+    #[error("invalid proposer signature")]
+    InvalidSignature,
+    /// The aggregate check of the batch failed while every signature verified on its own; the
+    /// position of such an error is the first block of the batch.
+    #[error("batch signature verification failed but every proposer signature is valid")]
+    InconsistentBatchSignatures,
+    #[error(transparent)]
+    Consensus(#[from] crate::Error),
+}
+
+/// The first block of a batch, by position in the batch, that failed verification.
+#[derive(Debug, Error)]
+#[error("block at position {position} of batch is invalid: {reason}")]
+pub struct BatchError {
+    pub position: usize,
+    pub reason: BatchErrorReason,
+}
+
+fn block_root<
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    block: &SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >,
+) -> Result<Root, BatchErrorReason> {
+    map_fork!(SignedBeaconBlock, block, inner => inner.message.hash_tree_root())
+        .map_err(|err| crate::Error::from(err).into())
+}
+
+/// Check that `blocks` form a chain hanging off of `anchor` in the given `direction`: each block
+/// links to its neighbour by root and slots strictly increase (`Forward`) or decrease
+/// (`Backward`).
+///
+/// Proposer signatures are not checked; see [`verify_block_batch_with_signatures`].
+pub fn verify_block_batch<
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    blocks: &[SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >],
+    anchor: (Slot, Root),
+    direction: Direction,
+) -> Result<(), BatchError> {
+    let (mut previous_slot, mut expected_root) = anchor;
+    for (position, block) in blocks.iter().enumerate() {
+        let message = block.message();
+        let slot = message.slot();
+        let is_ordered = match direction {
+            Direction::Forward => slot > previous_slot,
+            Direction::Backward => slot < previous_slot,
+        };
+        if !is_ordered {
+            let reason = BatchErrorReason::SlotOutOfOrder { slot, previous_slot };
+            return Err(BatchError { position, reason })
+        }
+        let root = block_root(block).map_err(|reason| BatchError { position, reason })?;
+        match direction {
+            Direction::Forward => {
+                let parent_root = message.parent_root();
+                if parent_root != expected_root {
+                    let reason = BatchErrorReason::ParentRootMismatch {
+                        expected: expected_root,
+                        found: parent_root,
+                    };
+                    return Err(BatchError { position, reason })
+                }
+                expected_root = root;
+            }
+            Direction::Backward => {
+                if root != expected_root {
+                    let reason = BatchErrorReason::BlockRootMismatch {
+                        expected: expected_root,
+                        found: root,
+                    };
+                    return Err(BatchError { position, reason })
+                }
+                expected_root = message.parent_root();
+            }
+        }
+        previous_slot = slot;
+    }
+    Ok(())
+}
+
+/// Like [`verify_block_batch`] but also verify the proposer signature of every block, with the
+/// public key of each proposer given by `public_key`.
+///
+/// Signatures are verified together in one batch; only if the batch fails are they checked
+/// individually to find the first invalid one.
+pub fn verify_block_batch_with_signatures<
+    'a,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    blocks: &[SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >],
+    anchor: (Slot, Root),
+    direction: Direction,
+    genesis_validators_root: Root,
+    public_key: impl Fn(ValidatorIndex) -> Option<&'a BlsPublicKey>,
+    context: &Context,
+) -> Result<(), BatchError> {
+    verify_block_batch_with_pubkey_cache(
+        blocks,
+        anchor,
+        direction,
+        genesis_validators_root,
+        public_key,
+        &DecompressedPubkeyCache::default(),
+        context,
+    )
+}
+
+/// Like [`verify_block_batch_with_signatures`] but take the decompressed public keys of proposers
+/// from `pubkey_cache` where present, only decompressing the keys missing from it.
+pub fn verify_block_batch_with_pubkey_cache<
+    'a,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    blocks: &[SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >],
+    anchor: (Slot, Root),
+    direction: Direction,
+    genesis_validators_root: Root,
+    public_key: impl Fn(ValidatorIndex) -> Option<&'a BlsPublicKey>,
+    pubkey_cache: &DecompressedPubkeyCache,
+    context: &Context,
+) -> Result<(), BatchError> {
+    verify_block_batch(blocks, anchor, direction)?;
+    if blocks.is_empty() {
+        return Ok(())
+    }
+
+    let mut public_keys = Vec::with_capacity(blocks.len());
+    let mut signing_roots = Vec::with_capacity(blocks.len());
+    for (position, block) in blocks.iter().enumerate() {
+        let with_position = |reason| BatchError { position, reason };
+        let proposer_index = block.message().proposer_index();
+        let proposer_public_key = public_key(proposer_index)
+            .ok_or_else(|| with_position(BatchErrorReason::UnknownProposer(proposer_index)))?;
+        let fork_version = context.fork_version_for(block.version());
+        let domain = compute_domain(
+            DomainType::BeaconProposer,
+            Some(fork_version),
+            Some(genesis_validators_root),
+            context,
+        )
+        .map_err(|err| with_position(err.into()))?;
+        let signing_root = map_fork!(
+            SignedBeaconBlock,
+            block,
+            inner => compute_signing_root(&inner.message, domain)
+        )
+        .map_err(|err| with_position(err.into()))?;
+        let proposer_public_key = match pubkey_cache.get(proposer_index, proposer_public_key) {
+            Some(public_key) => Cow::Borrowed(public_key),
+            None => Cow::Owned(
+                DecompressedPublicKey::try_from(proposer_public_key)
+                    .map_err(|err| with_position(crate::Error::from(err).into()))?,
+            ),
+        };
+        public_keys.push(proposer_public_key);
+        signing_roots.push(signing_root);
+    }
+
+    let public_keys = public_keys.iter().map(|public_key| &**public_key).collect::<Vec<_>>();
+    let msgs = signing_roots.iter().map(|root| root.as_ref()).collect::<Vec<_>>();
+    let signatures = blocks.iter().map(|block| block.signature()).collect::<Vec<_>>();
+    if verify_multiple_signatures_decompressed(&public_keys, &msgs, &signatures).is_ok() {
+        return Ok(())
+    }
+    for (position, ((public_key, msg), signature)) in
+        public_keys.into_iter().zip(msgs).zip(signatures).enumerate()
+    {
+        if verify_signature_decompressed(public_key, msg, signature).is_err() {
+            return Err(BatchError { position, reason: BatchErrorReason::InvalidSignature })
+        }
+    }
+    Err(BatchError { position: 0, reason: BatchErrorReason::InconsistentBatchSignatures })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::{secret_keys, VALIDATOR_COUNT},
+        phase0::{minimal, Validator},
+        signing::sign_with_domain,
+        types::minimal::SignedBeaconBlock,
+    };
+
+    const ANCHOR_SLOT: Slot = 3;
+
+    fn anchor_root() -> Root {
+        Root::try_from(&[7u8; 32][..]).unwrap()
+    }
+
+    // A chain of signed phase0 blocks following a block at `ANCHOR_SLOT` with `anchor_root()`,
+    // skipping every third slot.
+    fn chain(
+        length: usize,
+        genesis_validators_root: Root,
+        context: &Context,
+    ) -> Vec<SignedBeaconBlock> {
+        let secret_keys = secret_keys();
+        let domain = compute_domain(
+            DomainType::BeaconProposer,
+            None,
+            Some(genesis_validators_root),
+            context,
+        )
+        .unwrap();
+        let mut parent_root = anchor_root();
+        let mut slot = ANCHOR_SLOT;
+        let mut blocks = vec![];
+        for i in 0..length {
+            slot += if i % 3 == 2 { 2 } else { 1 };
+            let mut block = minimal::SignedBeaconBlock::default();
+            block.message.slot = slot;
+            block.message.proposer_index = (i * 5) % VALIDATOR_COUNT;
+            block.message.parent_root = parent_root;
+            block.signature = sign_with_domain(
+                &block.message,
+                &secret_keys[block.message.proposer_index],
+                domain,
+            )
+            .unwrap();
+            parent_root = block.message.hash_tree_root().unwrap();
+            blocks.push(SignedBeaconBlock::Phase0(block));
+        }
+        blocks
+    }
+
+    fn resign(block: &mut SignedBeaconBlock, genesis_validators_root: Root, context: &Context) {
+        let domain = compute_domain(
+            DomainType::BeaconProposer,
+            None,
+            Some(genesis_validators_root),
+            context,
+        )
+        .unwrap();
+        let block = block.as_phase0_mut().unwrap();
+        block.signature =
+            sign_with_domain(&block.message, &secret_keys()[block.message.proposer_index], domain)
+                .unwrap();
+    }
+
+    #[test]
+    fn test_verify_block_batch() {
+        let context = Context::for_minimal();
+        let genesis_validators_root = Root::try_from(&[1u8; 32][..]).unwrap();
+        let public_keys = secret_keys().iter().map(|key| key.public_key()).collect::<Vec<_>>();
+        let mut blocks = chain(16, genesis_validators_root, &context);
+        let lookup = |index: ValidatorIndex| public_keys.get(index);
+
+        verify_block_batch(&blocks, (ANCHOR_SLOT, anchor_root()), Direction::Forward).unwrap();
+        verify_block_batch_with_signatures(
+            &blocks,
+            (ANCHOR_SLOT, anchor_root()),
+            Direction::Forward,
+            genesis_validators_root,
+            lookup,
+            &context,
+        )
+        .unwrap();
+
+        let last = blocks.last().unwrap();
+        let trusted = (last.message().slot() + 1, block_root(last).unwrap());
+        let mut reversed = blocks.iter().rev().cloned().collect::<Vec<_>>();
+        verify_block_batch(&reversed, trusted, Direction::Backward).unwrap();
+
+        // tamper with the parent root of a block in the middle, keeping its signature valid
+        let tampered = 9;
+        *blocks[tampered].message_mut().parent_root_mut() = Root::try_from(&[2u8; 32][..]).unwrap();
+        resign(&mut blocks[tampered], genesis_validators_root, &context);
+        let err = verify_block_batch(&blocks, (ANCHOR_SLOT, anchor_root()), Direction::Forward)
+            .unwrap_err();
+        assert_eq!(err.position, tampered);
+        assert!(matches!(err.reason, BatchErrorReason::ParentRootMismatch { .. }));
+
+        // walking backwards, the tampered block no longer has the root its child links to
+        reversed = blocks.iter().rev().cloned().collect();
+        let trusted = (trusted.0, block_root(blocks.last().unwrap()).unwrap());
+        let err = verify_block_batch(&reversed, trusted, Direction::Backward).unwrap_err();
+        assert_eq!(err.position, blocks.len() - 1 - tampered);
+        assert!(matches!(err.reason, BatchErrorReason::BlockRootMismatch { .. }));
+
+        let err = verify_block_batch(
+            &blocks,
+            (blocks[0].message().slot(), anchor_root()),
+            Direction::Forward,
+        )
+        .unwrap_err();
+        assert_eq!(err.position, 0);
+        assert!(matches!(err.reason, BatchErrorReason::SlotOutOfOrder { .. }));
+    }
+
+    #[test]
+    fn test_verify_block_batch_signatures() {
+        let context = Context::for_minimal();
+        let genesis_validators_root = Root::try_from(&[1u8; 32][..]).unwrap();
+        let public_keys = secret_keys().iter().map(|key| key.public_key()).collect::<Vec<_>>();
+        let anchor = (ANCHOR_SLOT, anchor_root());
+
+        let mut blocks = chain(16, genesis_validators_root, &context);
+        let forged = 11;
+        *blocks[forged].signature_mut() = blocks[forged - 1].signature().clone();
+        let err = verify_block_batch_with_signatures(
+            &blocks,
+            anchor,
+            Direction::Forward,
+            genesis_validators_root,
+            |index| public_keys.get(index),
+            &context,
+        )
+        .unwrap_err();
+        assert_eq!(err.position, forged);
+        assert!(matches!(err.reason, BatchErrorReason::InvalidSignature));
+
+        let err = verify_block_batch_with_signatures(
+            &blocks,
+            anchor,
+            Direction::Forward,
+            genesis_validators_root,
+            |index| public_keys.get(index).filter(|_| index != 0),
+            &context,
+        )
+        .unwrap_err();
+        assert_eq!(err.position, 0);
+        assert!(matches!(err.reason, BatchErrorReason::UnknownProposer(0)));
+    }
+
+    #[test]
+    fn test_verify_block_batch_with_pubkey_cache() {
+        let context = Context::for_minimal();
+        let genesis_validators_root = Root::try_from(&[1u8; 32][..]).unwrap();
+        let validators = secret_keys()
+            .iter()
+            .map(|key| Validator { public_key: key.public_key(), ..Default::default() })
+            .collect::<Vec<_>>();
+        let anchor = (ANCHOR_SLOT, anchor_root());
+        // only some proposers are cached, the rest are decompressed on demand
+        let mut pubkey_cache = DecompressedPubkeyCache::default();
+        pubkey_cache.warm(&validators[..VALIDATOR_COUNT / 2], 0).unwrap();
+        let lookup = |index: ValidatorIndex| validators.get(index).map(|v| &v.public_key);
+
+        let mut blocks = chain(16, genesis_validators_root, &context);
+        verify_block_batch_with_pubkey_cache(
+            &blocks,
+            anchor,
+            Direction::Forward,
+            genesis_validators_root,
+            lookup,
+            &pubkey_cache,
+            &context,
+        )
+        .unwrap();
+
+        for forged in [3, 7] {
+            let signature = blocks[forged - 1].signature().clone();
+            let original = std::mem::replace(blocks[forged].signature_mut(), signature);
+            let uncached = verify_block_batch_with_signatures(
+                &blocks,
+                anchor,
+                Direction::Forward,
+                genesis_validators_root,
+                lookup,
+                &context,
+            )
+            .unwrap_err();
+            let cached = verify_block_batch_with_pubkey_cache(
+                &blocks,
+                anchor,
+                Direction::Forward,
+                genesis_validators_root,
+                lookup,
+                &pubkey_cache,
+                &context,
+            )
+            .unwrap_err();
+            assert_eq!((cached.position, uncached.position), (forged, forged));
+            assert!(matches!(cached.reason, BatchErrorReason::InvalidSignature));
+            *blocks[forged].signature_mut() = original;
+        }
+    }
+}
@@ -1,14 +1,15 @@
 use crate::{
     bellatrix::{
         compute_timestamp_at_slot, get_current_epoch, get_randao_mix, is_execution_enabled,
-        is_merge_transition_complete, process_block_header, process_eth1_data, process_operations,
-        process_randao, process_sync_aggregate, BeaconBlock, BeaconBlockBody, BeaconState,
+        is_merge_transition_complete, process_block_header, process_eth1_data,
+        process_operations_with_validation, process_randao_with_validation,
+        process_sync_aggregate_with_validation, BeaconBlock, BeaconBlockBody, BeaconState,
         ExecutionPayloadHeader,
     },
     error::{invalid_operation_error, InvalidExecutionPayload},
     execution_engine::ExecutionEngine,
     ssz::prelude::*,
-    state_transition::{Context, Result},
+    state_transition::{Context, Result, Validation},
 };
 
 pub fn process_execution_payload<
@@ -162,14 +163,64 @@ pub fn process_block<
         MAX_TRANSACTIONS_PER_PAYLOAD,
     >,
     context: &Context,
+) -> Result<()> {
+    process_block_with_validation(state, block, Validation::Enabled, context)
+}
+
+pub fn process_block_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    block: &BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     process_block_header(state, block, context)?;
     if is_execution_enabled(state, &block.body) {
         process_execution_payload(state, &block.body, context)?;
     }
-    process_randao(state, &block.body, context)?;
+    process_randao_with_validation(state, &block.body, validation, context)?;
     process_eth1_data(state, &block.body, context);
-    process_operations(state, &block.body, context)?;
-    process_sync_aggregate(state, &block.body.sync_aggregate, context)?;
+    process_operations_with_validation(state, &block.body, validation, context)?;
+    process_sync_aggregate_with_validation(state, &block.body.sync_aggregate, validation, context)?;
     Ok(())
 }
@@ -84,17 +84,21 @@ pub fn process_epoch<
     >,
     context: &Context,
 ) -> Result<()> {
-    process_justification_and_finalization(state, context)?;
-    process_inactivity_updates(state, context)?;
-    process_rewards_and_penalties(state, context)?;
-    process_registry_updates(state, context)?;
-    process_slashings(state, context)?;
-    process_eth1_data_reset(state, context);
-    process_effective_balance_updates(state, context);
-    process_slashings_reset(state, context);
-    process_randao_mixes_reset(state, context);
-    process_historical_roots_update(state, context)?;
-    process_participation_flag_updates(state)?;
-    process_sync_committee_updates(state, context)?;
+    trace_span!("process_epoch", epoch = state.slot / context.slots_per_epoch);
+    traced!(
+        "process_justification_and_finalization",
+        process_justification_and_finalization(state, context)
+    )?;
+    traced!("process_inactivity_updates", process_inactivity_updates(state, context))?;
+    traced!("process_rewards_and_penalties", process_rewards_and_penalties(state, context))?;
+    traced!("process_registry_updates", process_registry_updates(state, context))?;
+    traced!("process_slashings", process_slashings(state, context))?;
+    traced!("process_eth1_data_reset", process_eth1_data_reset(state, context));
+    traced!("process_effective_balance_updates", process_effective_balance_updates(state, context));
+    traced!("process_slashings_reset", process_slashings_reset(state, context));
+    traced!("process_randao_mixes_reset", process_randao_mixes_reset(state, context));
+    traced!("process_historical_roots_update", process_historical_roots_update(state, context))?;
+    traced!("process_participation_flag_updates", process_participation_flag_updates(state))?;
+    traced!("process_sync_committee_updates", process_sync_committee_updates(state, context))?;
     Ok(())
 }
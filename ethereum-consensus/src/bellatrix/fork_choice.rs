@@ -2,8 +2,8 @@ use crate::{primitives::Hash32, ssz::prelude::*};
 
 #[derive(Default, Debug, SimpleSerialize, Clone, serde::Serialize, serde::Deserialize)]
 pub struct PowBlock {
-    block_hash: Hash32,
-    parent_hash: Hash32,
+    pub block_hash: Hash32,
+    pub parent_hash: Hash32,
     #[serde(with = "crate::serde::as_str")]
-    total_difficulty: U256,
+    pub total_difficulty: U256,
 }
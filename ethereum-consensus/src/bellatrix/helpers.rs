@@ -93,7 +93,7 @@ pub fn slash_validator<
         state.validators[slashed_index].withdrawable_epoch,
         epoch + context.epochs_per_slashings_vector,
     );
-    let slashings_index = epoch as usize % EPOCHS_PER_SLASHINGS_VECTOR;
+    let slashings_index = (epoch % EPOCHS_PER_SLASHINGS_VECTOR as u64) as usize;
     state.slashings[slashings_index] += state.validators[slashed_index].effective_balance;
     decrease_balance(
         state,
@@ -268,6 +268,5 @@ pub fn compute_timestamp_at_slot<
     context: &Context,
 ) -> Result<u64> {
     let slots_since_genesis = slot.checked_sub(GENESIS_SLOT).ok_or(Error::Underflow)?;
-    let timestamp = state.genesis_time + slots_since_genesis * context.seconds_per_slot;
-    Ok(timestamp)
+    context.timestamp_at_slot(slots_since_genesis, state.genesis_time)
 }
@@ -3,7 +3,7 @@ pub use crate::{
     altair::presets::mainnet::{
         AggregateAndProof, Attestation, AttesterSlashing, ContributionAndProof, HistoricalBatch,
         IndexedAttestation, LightClientBootstrap, LightClientFinalityUpdate,
-        LightClientOptimisticUpdate, LightClientUpdate, PendingAttestation,
+        LightClientOptimisticUpdate, LightClientStore, LightClientUpdate, PendingAttestation,
         SignedAggregateAndProof, SignedContributionAndProof, SyncAggregate, SyncCommittee,
         SyncCommitteeContribution, EPOCHS_PER_HISTORICAL_VECTOR, EPOCHS_PER_SLASHINGS_VECTOR,
         ETH1_DATA_VOTES_BOUND, HISTORICAL_ROOTS_LIMIT, MAX_ATTESTATIONS, MAX_ATTESTER_SLASHINGS,
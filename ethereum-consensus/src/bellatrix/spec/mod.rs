@@ -9,25 +9,36 @@ pub use crate::{
         },
         helpers::{add_flag, has_flag},
         light_client::{
-            LightClientBootstrap, LightClientFinalityUpdate, LightClientHeader,
-            LightClientOptimisticUpdate, LightClientUpdate, CURRENT_SYNC_COMMITTEE_INDEX,
+            apply_light_client_update, compute_sync_committee_period_at_slot, get_safety_threshold,
+            initialize_light_client_store, is_better_update, is_finality_update,
+            is_next_sync_committee_known, is_sync_committee_update, is_valid_light_client_branch,
+            is_valid_light_client_header, process_light_client_finality_update,
+            process_light_client_optimistic_update, process_light_client_store_force_update,
+            process_light_client_update, validate_light_client_update, LightClientBootstrap,
+            LightClientFinalityUpdate, LightClientHeader, LightClientOptimisticUpdate,
+            LightClientStore, LightClientUpdate, CURRENT_SYNC_COMMITTEE_INDEX,
             CURRENT_SYNC_COMMITTEE_INDEX_FLOOR_LOG_2, FINALIZED_ROOT_INDEX,
             FINALIZED_ROOT_INDEX_FLOOR_LOG_2, NEXT_SYNC_COMMITTEE_INDEX,
             NEXT_SYNC_COMMITTEE_INDEX_FLOOR_LOG_2,
         },
         sync::{SyncAggregate, SyncCommittee},
         validator::{
-            ContributionAndProof, SignedContributionAndProof, SyncAggregatorSelectionData,
-            SyncCommitteeContribution, SyncCommitteeMessage,
+            is_sync_committee_aggregator, sign_contribution_and_proof, sign_sync_committee_message,
+            sign_sync_selection_proof, verify_contribution_and_proof_signature,
+            verify_sync_committee_message, verify_sync_selection_proof, ContributionAndProof,
+            SignedContributionAndProof, SyncAggregatorSelectionData, SyncCommitteeContribution,
+            SyncCommitteeMessage,
         },
     },
     bellatrix::{
         beacon_block::{BeaconBlock, BeaconBlockBody, SignedBeaconBlock},
-        beacon_state::BeaconState,
+        beacon_state::{compute_state_field_roots, BeaconState},
         blinded_beacon_block::{
             BlindedBeaconBlock, BlindedBeaconBlockBody, SignedBlindedBeaconBlock,
         },
-        block_processing::{process_block, process_execution_payload},
+        block_processing::{
+            process_block, process_block_with_validation, process_execution_payload,
+        },
         epoch_processing::{process_epoch, process_slashings},
         execution_payload::{ExecutionPayload, ExecutionPayloadHeader, Transaction},
         fork::upgrade_to_bellatrix,
@@ -37,37 +48,56 @@ pub use crate::{
             compute_timestamp_at_slot, get_inactivity_penalty_deltas, is_execution_enabled,
             is_merge_transition_block, is_merge_transition_complete, slash_validator,
         },
-        state_transition::{state_transition, state_transition_block_in_slot},
+        state_transition::{
+            state_transition, state_transition_block_in_slot,
+            state_transition_block_in_slot_with_state_root,
+        },
     },
     error::*,
     phase0::{
         beacon_block::{BeaconBlockHeader, SignedBeaconBlockHeader},
         beacon_state::{Fork, ForkData, HistoricalBatch, HistoricalSummary},
-        block_processing::{get_validator_from_deposit, xor},
+        block_processing::{
+            count_eth1_data_votes, get_validator_from_deposit, xor, Eth1VotingPeriodProgress,
+        },
         constants::{
             BASE_REWARDS_PER_EPOCH, DEPOSIT_CONTRACT_TREE_DEPTH, DEPOSIT_DATA_LIST_BOUND,
             JUSTIFICATION_BITS_LENGTH,
         },
         helpers::{
-            compute_activation_exit_epoch, compute_committee, compute_domain,
-            compute_epoch_at_slot, compute_fork_data_root, compute_fork_digest,
+            compute_activation_exit_epoch, compute_committee, compute_committee_position,
+            compute_domain, compute_epoch_at_slot, compute_fork_data_root, compute_fork_digest,
             compute_shuffled_index, compute_shuffled_indices, compute_start_slot_at_epoch,
-            is_active_validator, is_eligible_for_activation_queue, is_slashable_attestation_data,
-            is_slashable_validator,
+            compute_unshuffled_index, is_active_validator, is_eligible_for_activation_queue,
+            is_slashable_attestation_data, is_slashable_validator,
         },
         operations::{
             Attestation, AttestationData, AttesterSlashing, Checkpoint, Deposit, DepositData,
             DepositMessage, Eth1Data, IndexedAttestation, PendingAttestation, ProposerSlashing,
             SignedVoluntaryExit, VoluntaryExit,
         },
-        validator::{AggregateAndProof, Eth1Block, SignedAggregateAndProof, Validator},
+        slot_processing::{
+            merkleize_field_roots, STATE_BLOCK_ROOTS_FIELD_INDEX,
+            STATE_LATEST_BLOCK_HEADER_FIELD_INDEX, STATE_SLOT_FIELD_INDEX,
+            STATE_STATE_ROOTS_FIELD_INDEX,
+        },
+        validator::{
+            compute_domain_at_slot, is_aggregator, sign_aggregate_and_proof, sign_attestation_data,
+            sign_slot_selection_proof, verify_aggregate_and_proof_signature,
+            verify_attestation_data_signature, verify_slot_selection_proof, AggregateAndProof,
+            Eth1Block, ForkInfo, SignedAggregateAndProof, Validator,
+        },
     },
     primitives::*,
     signing::*,
-    state_transition::{Context, Result, Validation},
+    state_transition::{Context, Result, StateRootCache, Validation},
 };
 use crate::{
     crypto::{eth_aggregate_public_keys, eth_fast_aggregate_verify, fast_aggregate_verify, hash},
+    rewards::{
+        base_reward_per_increment, flag_reward, phase0_proposer_reward,
+        proposer_attestation_reward, proposer_sync_reward, sync_participant_reward,
+    },
     ssz::prelude::*,
 };
 use integer_sqrt::IntegerSquareRoot;
@@ -102,6 +132,37 @@ pub fn process_attestation<
     >,
     attestation: &Attestation<MAX_VALIDATORS_PER_COMMITTEE>,
     context: &Context,
+) -> Result<()> {
+    process_attestation_with_validation(state, attestation, Validation::Enabled, context)
+}
+
+pub fn process_attestation_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    attestation: &Attestation<MAX_VALIDATORS_PER_COMMITTEE>,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let data = &attestation.data;
     let is_previous = data.target.epoch == get_previous_epoch(state, context);
@@ -157,13 +218,21 @@ pub fn process_attestation<
     let inclusion_delay = state.slot - data.slot;
     let participation_flag_indices =
         get_attestation_participation_flag_indices(state, data, inclusion_delay, context)?;
-    is_valid_indexed_attestation(
-        state,
-        &get_indexed_attestation(state, attestation, context)?,
-        context,
-    )?;
-    let attesting_indices =
-        get_attesting_indices(state, data, &attestation.aggregation_bits, context)?;
+    let attesting_indices = if validation.is_assumed_finalized(data.target.epoch) {
+        committee
+            .iter()
+            .enumerate()
+            .filter_map(|(i, index)| attestation.aggregation_bits[i].then_some(*index))
+            .collect::<HashSet<_>>()
+    } else {
+        is_valid_indexed_attestation_with_validation(
+            state,
+            &get_indexed_attestation(state, attestation, context)?,
+            validation,
+            context,
+        )?;
+        get_attesting_indices(state, data, &attestation.aggregation_bits, context)?
+    };
     let mut proposer_reward_numerator = 0;
     for index in attesting_indices {
         for (flag_index, weight) in PARTICIPATION_FLAG_WEIGHTS.iter().enumerate() {
@@ -184,9 +253,7 @@ pub fn process_attestation<
             }
         }
     }
-    let proposer_reward_denominator =
-        (WEIGHT_DENOMINATOR - PROPOSER_WEIGHT) * WEIGHT_DENOMINATOR / PROPOSER_WEIGHT;
-    let proposer_reward = proposer_reward_numerator / proposer_reward_denominator;
+    let proposer_reward = proposer_attestation_reward(proposer_reward_numerator);
     increase_balance(state, get_beacon_proposer_index(state, context)?, proposer_reward);
     Ok(())
 }
@@ -257,43 +324,70 @@ pub fn process_sync_aggregate<
     sync_aggregate: &SyncAggregate<SYNC_COMMITTEE_SIZE>,
     context: &Context,
 ) -> Result<()> {
-    let committee_public_keys = &state.current_sync_committee.public_keys;
-    let participant_public_keys =
-        zip(committee_public_keys.iter(), sync_aggregate.sync_committee_bits.iter())
-            .filter_map(|(public_key, bit)| if *bit { Some(public_key) } else { None })
-            .collect::<Vec<_>>();
-    let previous_slot = u64::max(state.slot, 1) - 1;
-    let domain = get_domain(
-        state,
-        DomainType::SyncCommittee,
-        Some(compute_epoch_at_slot(previous_slot, context)),
-        context,
-    )?;
-    let root_at_slot = *get_block_root_at_slot(state, previous_slot)?;
-    let signing_root = compute_signing_root(&root_at_slot, domain)?;
-    if eth_fast_aggregate_verify(
-        participant_public_keys.as_slice(),
-        signing_root.as_ref(),
-        &sync_aggregate.sync_committee_signature,
-    )
-    .is_err()
-    {
-        return Err(invalid_operation_error(InvalidOperation::SyncAggregate(
-            InvalidSyncAggregate::InvalidSignature {
-                signature: sync_aggregate.sync_committee_signature.clone(),
-                root: signing_root,
-            },
-        )));
+    process_sync_aggregate_with_validation(state, sync_aggregate, Validation::Enabled, context)
+}
+
+pub fn process_sync_aggregate_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    sync_aggregate: &SyncAggregate<SYNC_COMMITTEE_SIZE>,
+    validation: Validation,
+    context: &Context,
+) -> Result<()> {
+    if validation.verifies_signatures() {
+        let committee_public_keys = &state.current_sync_committee.public_keys;
+        let participant_public_keys =
+            zip(committee_public_keys.iter(), sync_aggregate.sync_committee_bits.iter())
+                .filter_map(|(public_key, bit)| if *bit { Some(public_key) } else { None })
+                .collect::<Vec<_>>();
+        let previous_slot = u64::max(state.slot, 1) - 1;
+        let domain = get_domain(
+            state,
+            DomainType::SyncCommittee,
+            Some(compute_epoch_at_slot(previous_slot, context)),
+            context,
+        )?;
+        let root_at_slot = *get_block_root_at_slot(state, previous_slot)?;
+        let signing_root = compute_signing_root(&root_at_slot, domain)?;
+        if eth_fast_aggregate_verify(
+            participant_public_keys.as_slice(),
+            signing_root.as_ref(),
+            &sync_aggregate.sync_committee_signature,
+        )
+        .is_err()
+        {
+            return Err(invalid_operation_error(InvalidOperation::SyncAggregate(
+                InvalidSyncAggregate::InvalidSignature {
+                    signature: sync_aggregate.sync_committee_signature.clone(),
+                    root: signing_root,
+                },
+            )));
+        }
     }
-    let total_active_increments =
-        get_total_active_balance(state, context)? / context.effective_balance_increment;
-    let total_base_rewards =
-        get_base_reward_per_increment(state, context)? * total_active_increments;
-    let max_participant_rewards =
-        total_base_rewards * SYNC_REWARD_WEIGHT / WEIGHT_DENOMINATOR / context.slots_per_epoch;
-    let participant_reward = max_participant_rewards / context.sync_committee_size as u64;
-    let proposer_reward =
-        participant_reward * PROPOSER_WEIGHT / (WEIGHT_DENOMINATOR - PROPOSER_WEIGHT);
+    let participant_reward =
+        sync_participant_reward(get_total_active_balance(state, context)?, context);
+    let proposer_reward = proposer_sync_reward(participant_reward);
     let all_public_keys = state
         .validators
         .iter()
@@ -343,6 +437,42 @@ pub fn process_proposer_slashing<
     >,
     proposer_slashing: &ProposerSlashing,
     context: &Context,
+) -> Result<()> {
+    process_proposer_slashing_with_validation(
+        state,
+        proposer_slashing,
+        Validation::Enabled,
+        context,
+    )
+}
+
+pub fn process_proposer_slashing_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    proposer_slashing: &ProposerSlashing,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let header_1 = &proposer_slashing.signed_header_1.message;
     let header_2 = &proposer_slashing.signed_header_2.message;
@@ -375,16 +505,25 @@ pub fn process_proposer_slashing<
             InvalidProposerSlashing::ProposerIsNotSlashable(header_1.proposer_index),
         )));
     }
-    let epoch = compute_epoch_at_slot(header_1.slot, context);
-    let domain = get_domain(state, DomainType::BeaconProposer, Some(epoch), context)?;
-    for signed_header in [&proposer_slashing.signed_header_1, &proposer_slashing.signed_header_2] {
-        let public_key = &proposer.public_key;
-        if verify_signed_data(&signed_header.message, &signed_header.signature, public_key, domain)
-            .is_err()
+    if validation.verifies_signatures() {
+        let epoch = compute_epoch_at_slot(header_1.slot, context);
+        let domain = get_domain(state, DomainType::BeaconProposer, Some(epoch), context)?;
+        for signed_header in
+            [&proposer_slashing.signed_header_1, &proposer_slashing.signed_header_2]
         {
-            return Err(invalid_operation_error(InvalidOperation::ProposerSlashing(
-                InvalidProposerSlashing::InvalidSignature(signed_header.signature.clone()),
-            )));
+            let public_key = &proposer.public_key;
+            if verify_signed_data(
+                &signed_header.message,
+                &signed_header.signature,
+                public_key,
+                domain,
+            )
+            .is_err()
+            {
+                return Err(invalid_operation_error(InvalidOperation::ProposerSlashing(
+                    InvalidProposerSlashing::InvalidSignature(signed_header.signature.clone()),
+                )));
+            }
         }
     }
     slash_validator(state, proposer_index, None, context)
@@ -415,6 +554,42 @@ pub fn process_attester_slashing<
     >,
     attester_slashing: &AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>,
     context: &Context,
+) -> Result<()> {
+    process_attester_slashing_with_validation(
+        state,
+        attester_slashing,
+        Validation::Enabled,
+        context,
+    )
+}
+
+pub fn process_attester_slashing_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    attester_slashing: &AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let attestation_1 = &attester_slashing.attestation_1;
     let attestation_2 = &attester_slashing.attestation_2;
@@ -426,8 +601,8 @@ pub fn process_attester_slashing<
             ),
         )));
     }
-    is_valid_indexed_attestation(state, attestation_1, context)?;
-    is_valid_indexed_attestation(state, attestation_2, context)?;
+    is_valid_indexed_attestation_with_validation(state, attestation_1, validation, context)?;
+    is_valid_indexed_attestation_with_validation(state, attestation_2, validation, context)?;
     let indices_1: HashSet<ValidatorIndex> =
         HashSet::from_iter(attestation_1.attesting_indices.iter().cloned());
     let indices_2 = HashSet::from_iter(attestation_2.attesting_indices.iter().cloned());
@@ -532,7 +707,7 @@ pub fn process_deposit<
     let leaf = deposit.data.hash_tree_root()?;
     let branch = &deposit.proof;
     let depth = DEPOSIT_CONTRACT_TREE_DEPTH + 1;
-    let index = state.eth1_deposit_index as usize;
+    let index = checked_index(state.eth1_deposit_index)?;
     let root = state.eth1_data.deposit_root;
     if is_valid_merkle_branch(leaf, branch, depth, index, root).is_err() {
         return Err(invalid_operation_error(InvalidOperation::Deposit(
@@ -572,6 +747,42 @@ pub fn process_voluntary_exit<
     >,
     signed_voluntary_exit: &SignedVoluntaryExit,
     context: &Context,
+) -> Result<()> {
+    process_voluntary_exit_with_validation(
+        state,
+        signed_voluntary_exit,
+        Validation::Enabled,
+        context,
+    )
+}
+
+pub fn process_voluntary_exit_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    signed_voluntary_exit: &SignedVoluntaryExit,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let voluntary_exit = &signed_voluntary_exit.message;
     let validator = state.validators.get(voluntary_exit.validator_index).ok_or_else(|| {
@@ -608,14 +819,17 @@ pub fn process_voluntary_exit<
             },
         )));
     }
-    let domain = get_domain(state, DomainType::VoluntaryExit, Some(voluntary_exit.epoch), context)?;
-    let public_key = &validator.public_key;
-    verify_signed_data(voluntary_exit, &signed_voluntary_exit.signature, public_key, domain)
-        .map_err(|_| {
-            invalid_operation_error(InvalidOperation::VoluntaryExit(
-                InvalidVoluntaryExit::InvalidSignature(signed_voluntary_exit.signature.clone()),
-            ))
-        })?;
+    if validation.verifies_signatures() {
+        let domain =
+            get_domain(state, DomainType::VoluntaryExit, Some(voluntary_exit.epoch), context)?;
+        let public_key = &validator.public_key;
+        verify_signed_data(voluntary_exit, &signed_voluntary_exit.signature, public_key, domain)
+            .map_err(|_| {
+                invalid_operation_error(InvalidOperation::VoluntaryExit(
+                    InvalidVoluntaryExit::InvalidSignature(signed_voluntary_exit.signature.clone()),
+                ))
+            })?;
+    }
     initiate_validator_exit(state, voluntary_exit.validator_index, context)
 }
 pub fn process_block_header<
@@ -701,9 +915,256 @@ pub fn process_block_header<
     if proposer.slashed {
         return Err(invalid_header_error(InvalidBeaconBlockHeader::ProposerSlashed(proposer_index)));
     }
-    Ok(())
+    Ok(())
+}
+pub fn process_randao<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    body: &BeaconBlockBody<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    context: &Context,
+) -> Result<()> {
+    process_randao_with_validation(state, body, Validation::Enabled, context)
+}
+
+pub fn process_randao_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    body: &BeaconBlockBody<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    validation: Validation,
+    context: &Context,
+) -> Result<()> {
+    let epoch = get_current_epoch(state, context);
+    if validation.verifies_signatures() {
+        let proposer_index = get_beacon_proposer_index(state, context)?;
+        let proposer = &state.validators[proposer_index];
+        let domain = get_domain(state, DomainType::Randao, Some(epoch), context)?;
+        if verify_signed_data(&epoch, &body.randao_reveal, &proposer.public_key, domain).is_err() {
+            return Err(invalid_operation_error(InvalidOperation::Randao(
+                body.randao_reveal.clone(),
+            )));
+        }
+    }
+    let mix = xor(get_randao_mix(state, epoch), &hash(body.randao_reveal.as_ref()));
+    let mix_index = epoch % context.epochs_per_historical_vector;
+    state.randao_mixes[mix_index as usize] = mix;
+    Ok(())
+}
+pub fn process_eth1_data<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    body: &BeaconBlockBody<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    context: &Context,
+) {
+    state.eth1_data_votes.push(body.eth1_data.clone());
+    let votes_count =
+        state.eth1_data_votes.iter().filter(|&vote| *vote == body.eth1_data).count() as u64;
+    if votes_count * 2 > context.epochs_per_eth1_voting_period * context.slots_per_epoch {
+        state.eth1_data = body.eth1_data.clone();
+    }
+}
+/// The `Eth1Data` holding a majority of the votes in the current voting period, if any.
+///
+/// This is the value `process_eth1_data` adopts as `state.eth1_data` once it is reached, so
+/// it can be recomputed from the votes alone.
+pub fn eth1_data_vote_winner<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    context: &Context,
+) -> Option<Eth1Data> {
+    let period_slots = context.epochs_per_eth1_voting_period * context.slots_per_epoch;
+    count_eth1_data_votes(&state.eth1_data_votes)
+        .into_iter()
+        .find(|(_, count)| count * 2 > period_slots)
+        .map(|(vote, _)| vote.clone())
+}
+pub fn eth1_voting_period_progress<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    context: &Context,
+) -> Eth1VotingPeriodProgress {
+    let period_slots = context.epochs_per_eth1_voting_period * context.slots_per_epoch;
+    let leading_vote = count_eth1_data_votes(&state.eth1_data_votes)
+        .into_iter()
+        .reduce(|leader, next| if next.1 > leader.1 { next } else { leader })
+        .map(|(vote, count)| (vote.clone(), count));
+    Eth1VotingPeriodProgress {
+        start_slot: state.slot - state.slot % period_slots,
+        period_slots,
+        votes: state.eth1_data_votes.len() as u64,
+        majority_threshold: period_slots / 2 + 1,
+        leading_vote,
+    }
 }
-pub fn process_randao<
+/// Choose the `Eth1Data` to vote for in a block built on `state`, following `get_eth1_vote` of
+/// the honest validator guide.
+///
+/// `candidates` are the votes the proposer considers valid for the current voting period,
+/// ordered from oldest to newest. The candidate already holding the most votes in the period is
+/// chosen (the oldest on ties), otherwise the newest candidate. Without any candidates, the
+/// proposer sticks with the current `state.eth1_data`.
+pub fn choose_eth1_vote<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
     const ETH1_DATA_VOTES_BOUND: usize,
@@ -714,15 +1175,9 @@ pub fn process_randao<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
-    const MAX_PROPOSER_SLASHINGS: usize,
-    const MAX_ATTESTER_SLASHINGS: usize,
-    const MAX_ATTESTATIONS: usize,
-    const MAX_DEPOSITS: usize,
-    const MAX_VOLUNTARY_EXITS: usize,
-    const MAX_BYTES_PER_TRANSACTION: usize,
-    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
 >(
-    state: &mut BeaconState<
+    candidates: &[Eth1Data],
+    state: &BeaconState<
         SLOTS_PER_HISTORICAL_ROOT,
         HISTORICAL_ROOTS_LIMIT,
         ETH1_DATA_VOTES_BOUND,
@@ -734,34 +1189,25 @@ pub fn process_randao<
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
     >,
-    body: &BeaconBlockBody<
-        MAX_PROPOSER_SLASHINGS,
-        MAX_VALIDATORS_PER_COMMITTEE,
-        MAX_ATTESTER_SLASHINGS,
-        MAX_ATTESTATIONS,
-        MAX_DEPOSITS,
-        MAX_VOLUNTARY_EXITS,
-        SYNC_COMMITTEE_SIZE,
-        BYTES_PER_LOGS_BLOOM,
-        MAX_EXTRA_DATA_BYTES,
-        MAX_BYTES_PER_TRANSACTION,
-        MAX_TRANSACTIONS_PER_PAYLOAD,
-    >,
-    context: &Context,
-) -> Result<()> {
-    let epoch = get_current_epoch(state, context);
-    let proposer_index = get_beacon_proposer_index(state, context)?;
-    let proposer = &state.validators[proposer_index];
-    let domain = get_domain(state, DomainType::Randao, Some(epoch), context)?;
-    if verify_signed_data(&epoch, &body.randao_reveal, &proposer.public_key, domain).is_err() {
-        return Err(invalid_operation_error(InvalidOperation::Randao(body.randao_reveal.clone())));
+) -> Eth1Data {
+    let tally = count_eth1_data_votes(&state.eth1_data_votes);
+    let votes_for = |candidate: &Eth1Data| {
+        tally.iter().find(|(vote, _)| *vote == candidate).map_or(0, |(_, count)| *count)
+    };
+    let mut choice = None;
+    for candidate in candidates {
+        let count = votes_for(candidate);
+        if count > 0 && choice.map_or(true, |(_, most)| count > most) {
+            choice = Some((candidate, count));
+        }
     }
-    let mix = xor(get_randao_mix(state, epoch), &hash(body.randao_reveal.as_ref()));
-    let mix_index = epoch % context.epochs_per_historical_vector;
-    state.randao_mixes[mix_index as usize] = mix;
-    Ok(())
+    choice
+        .map(|(candidate, _)| candidate)
+        .or_else(|| candidates.last())
+        .unwrap_or(&state.eth1_data)
+        .clone()
 }
-pub fn process_eth1_data<
+pub fn process_operations<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
     const ETH1_DATA_VOTES_BOUND: usize,
@@ -806,15 +1252,11 @@ pub fn process_eth1_data<
         MAX_TRANSACTIONS_PER_PAYLOAD,
     >,
     context: &Context,
-) {
-    state.eth1_data_votes.push(body.eth1_data.clone());
-    let votes_count =
-        state.eth1_data_votes.iter().filter(|&vote| *vote == body.eth1_data).count() as u64;
-    if votes_count * 2 > context.epochs_per_eth1_voting_period * context.slots_per_epoch {
-        state.eth1_data = body.eth1_data.clone();
-    }
+) -> Result<()> {
+    process_operations_with_validation(state, body, Validation::Enabled, context)
 }
-pub fn process_operations<
+
+pub fn process_operations_with_validation<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
     const ETH1_DATA_VOTES_BOUND: usize,
@@ -858,11 +1300,12 @@ pub fn process_operations<
         MAX_BYTES_PER_TRANSACTION,
         MAX_TRANSACTIONS_PER_PAYLOAD,
     >,
+    validation: Validation,
     context: &Context,
 ) -> Result<()> {
     let expected_deposit_count = usize::min(
         context.max_deposits,
-        (state.eth1_data.deposit_count - state.eth1_deposit_index) as usize,
+        saturating_usize(state.eth1_data.deposit_count - state.eth1_deposit_index),
     );
     if body.deposits.len() != expected_deposit_count {
         return Err(invalid_operation_error(InvalidOperation::Deposit(
@@ -872,15 +1315,39 @@ pub fn process_operations<
             },
         )));
     }
-    body.proposer_slashings
-        .iter()
-        .try_for_each(|op| process_proposer_slashing(state, op, context))?;
-    body.attester_slashings
-        .iter()
-        .try_for_each(|op| process_attester_slashing(state, op, context))?;
-    body.attestations.iter().try_for_each(|op| process_attestation(state, op, context))?;
-    body.deposits.iter().try_for_each(|op| process_deposit(state, op, context))?;
-    body.voluntary_exits.iter().try_for_each(|op| process_voluntary_exit(state, op, context))?;
+    traced!(
+        "proposer_slashings",
+        body.proposer_slashings.iter().try_for_each(
+            |op| process_proposer_slashing_with_validation(state, op, validation, context)
+        ),
+        count = body.proposer_slashings.len()
+    )?;
+    traced!(
+        "attester_slashings",
+        body.attester_slashings.iter().try_for_each(
+            |op| process_attester_slashing_with_validation(state, op, validation, context)
+        ),
+        count = body.attester_slashings.len()
+    )?;
+    traced!(
+        "attestations",
+        body.attestations
+            .iter()
+            .try_for_each(|op| process_attestation_with_validation(state, op, validation, context)),
+        count = body.attestations.len()
+    )?;
+    traced!(
+        "deposits",
+        body.deposits.iter().try_for_each(|op| process_deposit(state, op, context)),
+        count = body.deposits.len()
+    )?;
+    traced!(
+        "voluntary_exits",
+        body.voluntary_exits.iter().try_for_each(|op| process_voluntary_exit_with_validation(
+            state, op, validation, context
+        )),
+        count = body.voluntary_exits.len()
+    )?;
     Ok(())
 }
 pub fn get_base_reward<
@@ -1456,7 +1923,8 @@ pub fn get_proposer_reward<
     attesting_index: ValidatorIndex,
     context: &Context,
 ) -> Result<Gwei> {
-    Ok(get_base_reward(state, attesting_index, context)? / context.proposer_reward_quotient)
+    let base_reward = get_base_reward(state, attesting_index, context)?;
+    Ok(phase0_proposer_reward(base_reward, context))
 }
 pub fn get_finality_delay<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
@@ -1624,6 +2092,9 @@ pub fn get_next_sync_committee_indices<
     let max_random_byte = u8::MAX as u64;
     let active_validator_indices = get_active_validator_indices(state, epoch);
     let active_validator_count = active_validator_indices.len();
+    if active_validator_count == 0 {
+        return Err(Error::NoActiveValidators(epoch))
+    }
     let seed = get_seed(state, epoch, DomainType::SyncCommittee, context);
     let mut i: usize = 0;
     let mut sync_committee_indices = vec![];
@@ -1708,8 +2179,8 @@ pub fn get_base_reward_per_increment<
     >,
     context: &Context,
 ) -> Result<Gwei> {
-    Ok(context.effective_balance_increment * context.base_reward_factor /
-        get_total_active_balance(state, context)?.integer_sqrt())
+    let total_active_balance = get_total_active_balance(state, context)?;
+    Ok(base_reward_per_increment(total_active_balance, context))
 }
 pub fn get_unslashed_participating_indices<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
@@ -1867,8 +2338,12 @@ pub fn get_flag_index_deltas<
         let base_reward = get_base_reward(state, index, context)?;
         if unslashed_participating_indices.contains(&index) {
             if not_leaking {
-                let reward_numerator = base_reward * weight * unslashed_participating_increments;
-                rewards[index] += reward_numerator / (active_increments * WEIGHT_DENOMINATOR);
+                rewards[index] += flag_reward(
+                    base_reward,
+                    weight,
+                    unslashed_participating_increments,
+                    active_increments,
+                );
             }
         } else if flag_index != TIMELY_HEAD_FLAG_INDEX {
             penalties[index] += base_reward * weight / WEIGHT_DENOMINATOR;
@@ -1931,6 +2406,42 @@ pub fn is_valid_indexed_attestation<
     >,
     indexed_attestation: &IndexedAttestation<MAX_VALIDATORS_PER_COMMITTEE>,
     context: &Context,
+) -> Result<()> {
+    is_valid_indexed_attestation_with_validation(
+        state,
+        indexed_attestation,
+        Validation::Enabled,
+        context,
+    )
+}
+
+pub fn is_valid_indexed_attestation_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    indexed_attestation: &IndexedAttestation<MAX_VALIDATORS_PER_COMMITTEE>,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let attesting_indices = &indexed_attestation.attesting_indices;
     if attesting_indices.is_empty() {
@@ -1965,6 +2476,10 @@ pub fn is_valid_indexed_attestation<
         })?;
         public_keys.push(public_key);
     }
+    if !validation.verifies_signatures() {
+        return Ok(())
+    }
+
     let domain = get_domain(
         state,
         DomainType::BeaconAttester,
@@ -2285,11 +2800,11 @@ pub fn get_block_root_at_slot<
     if slot >= state.slot || state.slot > (slot + SLOTS_PER_HISTORICAL_ROOT as Slot) {
         return Err(Error::SlotOutOfRange {
             requested: slot,
-            lower_bound: state.slot - 1,
-            upper_bound: state.slot + SLOTS_PER_HISTORICAL_ROOT as Slot,
+            lower_bound: state.slot.saturating_sub(SLOTS_PER_HISTORICAL_ROOT as Slot),
+            upper_bound: state.slot.saturating_sub(1),
         });
     }
-    Ok(&state.block_roots[slot as usize % SLOTS_PER_HISTORICAL_ROOT])
+    Ok(&state.block_roots[(slot % SLOTS_PER_HISTORICAL_ROOT as u64) as usize])
 }
 pub fn get_randao_mix<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
@@ -2317,7 +2832,7 @@ pub fn get_randao_mix<
     >,
     epoch: Epoch,
 ) -> &Bytes32 {
-    let epoch = epoch as usize % EPOCHS_PER_HISTORICAL_VECTOR;
+    let epoch = (epoch % EPOCHS_PER_HISTORICAL_VECTOR as u64) as usize;
     &state.randao_mixes[epoch]
 }
 pub fn get_active_validator_indices<
@@ -2382,10 +2897,10 @@ pub fn get_validator_churn_limit<
 ) -> usize {
     let active_validator_indices =
         get_active_validator_indices(state, get_current_epoch(state, context));
-    u64::max(
+    saturating_usize(u64::max(
         context.min_per_epoch_churn_limit,
         active_validator_indices.len() as u64 / context.churn_limit_quotient,
-    ) as usize
+    ))
 }
 pub fn get_seed<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
@@ -2415,7 +2930,9 @@ pub fn get_seed<
     domain_type: DomainType,
     context: &Context,
 ) -> Bytes32 {
-    let mix_epoch = epoch + (context.epochs_per_historical_vector - context.min_seed_lookahead) - 1;
+    let mix_epoch = epoch % context.epochs_per_historical_vector +
+        (context.epochs_per_historical_vector - context.min_seed_lookahead) -
+        1;
     let mix = get_randao_mix(state, mix_epoch);
     let mut input = [0u8; 44];
     input[..4].copy_from_slice(&domain_type.as_bytes());
@@ -2489,13 +3006,155 @@ pub fn get_beacon_committee<
     context: &Context,
 ) -> Result<Vec<ValidatorIndex>> {
     let epoch = compute_epoch_at_slot(slot, context);
-    let committees_per_slot = get_committee_count_per_slot(state, epoch, context);
     let indices = get_active_validator_indices(state, epoch);
     let seed = get_seed(state, epoch, DomainType::BeaconAttester, context);
+    if let Some(committee_cache) = &context.committee_cache {
+        let shuffling = committee_cache.shuffling(epoch, &seed, indices, context);
+        return shuffling.beacon_committee(slot, index, context).map(|committee| committee.to_vec())
+    }
+    let committees_per_slot = get_committee_count_per_slot(state, epoch, context);
+    if index >= committees_per_slot {
+        return Err(Error::OutOfBounds { requested: index, bound: committees_per_slot })
+    }
     let index = (slot % context.slots_per_epoch) * committees_per_slot as u64 + index as u64;
     let count = committees_per_slot as u64 * context.slots_per_epoch;
     compute_committee(&indices, &seed, index as usize, count as usize, context)
 }
+/// A digest of the committee shuffling for `epoch`, so that two nodes can check they agree on
+/// committees without exchanging states.
+///
+/// The preimage is the `DomainType::BeaconAttester` seed for `epoch` (32 bytes) followed by the
+/// active validator indices for `epoch` in shuffled order, each as a little-endian `u64`. The
+/// digest is the SHA-256 hash of the preimage. This layout is stable across versions of this
+/// crate.
+pub fn committee_fingerprint<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    epoch: Epoch,
+    context: &Context,
+) -> Result<Hash32> {
+    let indices = get_active_validator_indices(state, epoch);
+    let seed = get_seed(state, epoch, DomainType::BeaconAttester, context);
+    let shuffled_indices = compute_committee(&indices, &seed, 0, 1, context)?;
+    let mut preimage = Vec::with_capacity(32 + 8 * shuffled_indices.len());
+    preimage.extend_from_slice(seed.as_ref());
+    for index in shuffled_indices {
+        preimage.extend_from_slice(&(index as u64).to_le_bytes());
+    }
+    Ok(hash(preimage))
+}
+/// A digest of the committees for `slot`, like [`committee_fingerprint`] but localized to a slot.
+///
+/// The preimage is the `DomainType::BeaconAttester` seed for the epoch of `slot` (32 bytes), then
+/// `slot` and the number of committees per slot as little-endian `u64`s, then the members of each
+/// committee of `slot` in committee index order, each as a little-endian `u64`. The digest is the
+/// SHA-256 hash of the preimage. This layout is stable across versions of this crate.
+pub fn committee_fingerprint_at_slot<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    slot: Slot,
+    context: &Context,
+) -> Result<Hash32> {
+    let epoch = compute_epoch_at_slot(slot, context);
+    let committees_per_slot = get_committee_count_per_slot(state, epoch, context);
+    let indices = get_active_validator_indices(state, epoch);
+    let seed = get_seed(state, epoch, DomainType::BeaconAttester, context);
+    let count = committees_per_slot * context.slots_per_epoch as usize;
+    let mut preimage =
+        Vec::with_capacity(48 + 8 * indices.len() / context.slots_per_epoch as usize);
+    preimage.extend_from_slice(seed.as_ref());
+    preimage.extend_from_slice(&slot.to_le_bytes());
+    preimage.extend_from_slice(&(committees_per_slot as u64).to_le_bytes());
+    for index in 0..committees_per_slot {
+        let index = (slot % context.slots_per_epoch) as usize * committees_per_slot + index;
+        for member in compute_committee(&indices, &seed, index, count, context)? {
+            preimage.extend_from_slice(&(member as u64).to_le_bytes());
+        }
+    }
+    Ok(hash(preimage))
+}
+/// The slot, committee index and position within that committee of the attester duty of
+/// `validator_index` in `epoch`, or `None` if the validator is not active in `epoch`.
+///
+/// Only the shuffled position of `validator_index` is computed so this is cheaper than
+/// enumerating the committees of `epoch` with [`get_beacon_committee`].
+pub fn committee_position_of<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    epoch: Epoch,
+    validator_index: ValidatorIndex,
+    context: &Context,
+) -> Option<(Slot, CommitteeIndex, usize)> {
+    let indices = get_active_validator_indices(state, epoch);
+    let index = indices.binary_search(&validator_index).ok()?;
+    let seed = get_seed(state, epoch, DomainType::BeaconAttester, context);
+    let position = compute_unshuffled_index(index, indices.len(), &seed, context).ok()?;
+    let committees_per_slot = get_committee_count_per_slot(state, epoch, context);
+    Some(compute_committee_position(position, indices.len(), committees_per_slot, epoch, context))
+}
 pub fn get_beacon_proposer_index<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
@@ -2529,6 +3188,9 @@ pub fn get_beacon_proposer_index<
     input[32..40].copy_from_slice(&state.slot.to_le_bytes());
     let seed = hash(input);
     let indices = get_active_validator_indices(state, epoch);
+    if indices.is_empty() {
+        return Err(Error::NoActiveValidators(epoch))
+    }
     compute_proposer_index(state, &indices, &seed, context)
 }
 pub fn get_total_balance<
@@ -2850,16 +3512,75 @@ pub fn process_slots<
     >,
     slot: Slot,
     context: &Context,
+) -> Result<()> {
+    let mut state_root_cache = StateRootCache::default();
+    process_slots_with_field_roots(
+        state,
+        slot,
+        |state| state_root_cache.field_roots(state),
+        context,
+    )
+}
+pub fn process_slots_with_field_roots<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    slot: Slot,
+    mut field_roots_of: impl FnMut(
+        &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+    ) -> Result<Vec<Root>>,
+    context: &Context,
 ) -> Result<()> {
     if state.slot >= slot {
-        return Err(Error::TransitionToPreviousSlot { requested: slot, current: state.slot });
+        return Err(Error::TransitionToPreviousSlot { requested: slot, current: state.slot })
     }
+    trace_span!("process_slots", from = state.slot, to = slot);
+    let mut field_roots = traced!("hash_tree_root", field_roots_of(state))?;
     while state.slot < slot {
-        process_slot(state, context)?;
-        if (state.slot + 1) % context.slots_per_epoch == 0 {
+        process_slot_with_field_roots(state, &mut field_roots, context)?;
+        let is_epoch_transition = (state.slot + 1) % context.slots_per_epoch == 0;
+        if is_epoch_transition {
             process_epoch(state, context)?;
         }
         state.slot += 1;
+        if is_epoch_transition {
+            if state.slot < slot {
+                field_roots = traced!("hash_tree_root", field_roots_of(state))?;
+            }
+        } else {
+            field_roots[STATE_SLOT_FIELD_INDEX] = state.slot.hash_tree_root()?;
+        }
     }
     Ok(())
 }
@@ -2892,7 +3613,7 @@ pub fn process_slot<
     let previous_state_root = state.hash_tree_root()?;
     let root_index = state.slot % context.slots_per_historical_root;
     state.state_roots[root_index as usize] = previous_state_root;
-    if state.latest_block_header.state_root == Root::default() {
+    if state.latest_block_header.state_root.is_zero() {
         state.latest_block_header.state_root = previous_state_root;
     }
     let previous_block_root = state.latest_block_header.hash_tree_root()?;
@@ -2900,3 +3621,43 @@ pub fn process_slot<
     state.block_roots[root_index as usize] = previous_block_root;
     Ok(())
 }
+pub fn process_slot_with_field_roots<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    field_roots: &mut [Root],
+    context: &Context,
+) -> Result<()> {
+    let previous_state_root = merkleize_field_roots(field_roots)?;
+    let root_index = (state.slot % context.slots_per_historical_root) as usize;
+    state.state_roots[root_index] = previous_state_root;
+    field_roots[STATE_STATE_ROOTS_FIELD_INDEX] = state.state_roots.hash_tree_root()?;
+    if state.latest_block_header.state_root.is_zero() {
+        state.latest_block_header.state_root = previous_state_root;
+    }
+    let previous_block_root = state.latest_block_header.hash_tree_root()?;
+    field_roots[STATE_LATEST_BLOCK_HEADER_FIELD_INDEX] = previous_block_root;
+    state.block_roots[root_index] = previous_block_root;
+    field_roots[STATE_BLOCK_ROOTS_FIELD_INDEX] = state.block_roots.hash_tree_root()?;
+    Ok(())
+}
@@ -1,7 +1,9 @@
 use crate::{
     bellatrix::{
-        process_block, process_slots, verify_block_signature, BeaconState, SignedBeaconBlock,
+        process_block_with_validation, process_slots, verify_block_signature, BeaconState,
+        SignedBeaconBlock,
     },
+    primitives::Root,
     ssz::prelude::HashTreeRoot,
     state_transition::{Context, Result, Validation},
     Error,
@@ -57,16 +59,91 @@ pub fn state_transition_block_in_slot<
     validation: Validation,
     context: &Context,
 ) -> Result<()> {
+    state_transition_block_in_slot_with_state_root(
+        state,
+        signed_block,
+        validation,
+        |state| Ok(state.hash_tree_root()?),
+        context,
+    )
+}
+
+// Equivalent to `state_transition_block_in_slot`, computing the root of the post-state checked
+// against the block with `state_root_of` (e.g. from a `StateRootCache`).
+pub fn state_transition_block_in_slot_with_state_root<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    signed_block: &SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    validation: Validation,
+    state_root_of: impl FnOnce(
+        &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+    ) -> Result<Root>,
+    context: &Context,
+) -> Result<()> {
+    trace_span!("process_block", slot = signed_block.message.slot);
     let validate_result = match validation {
-        Validation::Enabled => true,
+        Validation::Enabled |
+        Validation::AssumeFinalizedBelow(_) |
+        Validation::SkipSignatureVerification => true,
         Validation::Disabled => false,
     };
-    if validate_result {
+    if validate_result && validation.verifies_signatures() {
         verify_block_signature(state, signed_block, context)?;
     }
     let block = &signed_block.message;
-    process_block(state, block, context)?;
-    if validate_result && block.state_root != state.hash_tree_root()? {
+    process_block_with_validation(state, block, validation, context)?;
+    if validate_result && block.state_root != traced!("hash_tree_root", state_root_of(state))? {
         Err(Error::InvalidStateRoot)
     } else {
         Ok(())
@@ -1,6 +1,6 @@
 use crate::blobs::{Blob, Error};
 use ethereum_consensus::{
-    deneb::{self, polynomial_commitments as spec, presets::TRUSTED_SETUP_JSON},
+    deneb::{self, polynomial_commitments as spec},
     Error as ConsensusError,
 };
 use std::io::Read;
@@ -40,7 +40,6 @@ pub fn bundle(blobs: Vec<Blob>, kzg_settings: &spec::KzgSettings) -> Result<Blob
 
 // Assumes a serde_json-encoded array of `Vec<Blob>` on `reader` and uses the mainnet trusted setup.
 pub fn from_reader(reader: impl Read) -> Result<BlobsBundle, Error> {
-    let kzg_settings = spec::kzg_settings_from_json(TRUSTED_SETUP_JSON)?;
     let blobs: Vec<Blob> = serde_json::from_reader(reader)?;
-    bundle(blobs, &kzg_settings)
+    bundle(blobs, spec::mainnet_kzg_settings())
 }
@@ -1,6 +1,13 @@
 use crate::{
+    crypto::SecretKey,
+    deneb::{
+        polynomial_commitments::KzgCommitment, ExecutionPayloadHeader, SignedBlindedBeaconBlock,
+    },
     phase0::compute_domain,
-    primitives::{BlsPublicKey, BlsSignature, Domain, DomainType, ExecutionAddress},
+    primitives::{
+        BlsPublicKey, BlsSignature, Domain, DomainType, ExecutionAddress, Hash32, Slot, U256,
+    },
+    signing::{sign_with_domain, verify_signed_data},
     ssz::prelude::*,
     state_transition::Context,
     Error,
@@ -27,3 +34,291 @@ pub fn compute_builder_domain(context: &Context) -> Result<Domain, Error> {
     let domain_type = DomainType::ApplicationBuilder;
     compute_domain(domain_type, None, None, context)
 }
+
+#[derive(Debug, Clone, Default, SimpleSerialize, serde::Serialize, serde::Deserialize)]
+pub struct BuilderBid<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+> {
+    pub header: ExecutionPayloadHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    pub blob_kzg_commitments: List<KzgCommitment, MAX_BLOB_COMMITMENTS_PER_BLOCK>,
+    #[serde(with = "crate::serde::as_str")]
+    pub value: U256,
+    #[serde(rename = "pubkey")]
+    pub public_key: BlsPublicKey,
+}
+
+#[derive(Debug, Clone, Default, SimpleSerialize, serde::Serialize, serde::Deserialize)]
+pub struct SignedBuilderBid<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+> {
+    pub message:
+        BuilderBid<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES, MAX_BLOB_COMMITMENTS_PER_BLOCK>,
+    pub signature: BlsSignature,
+}
+
+/// The trace of a bid, as signed by builders when submitting blocks to a relay and as served by
+/// the relay data API.
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, SimpleSerialize, serde::Serialize, serde::Deserialize,
+)]
+pub struct BidTrace {
+    #[serde(with = "crate::serde::as_str")]
+    pub slot: Slot,
+    pub parent_hash: Hash32,
+    pub block_hash: Hash32,
+    #[serde(rename = "builder_pubkey")]
+    pub builder_public_key: BlsPublicKey,
+    #[serde(rename = "proposer_pubkey")]
+    pub proposer_public_key: BlsPublicKey,
+    pub proposer_fee_recipient: ExecutionAddress,
+    #[serde(with = "crate::serde::as_str")]
+    pub gas_limit: u64,
+    #[serde(with = "crate::serde::as_str")]
+    pub gas_used: u64,
+    #[serde(with = "crate::serde::as_str")]
+    pub value: U256,
+}
+
+impl BidTrace {
+    /// Build the trace of `signed_bid` for the proposal at `slot` by the proposer with
+    /// `proposer_public_key`, which are not part of the bid itself.
+    pub fn from_signed_builder_bid<
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    >(
+        signed_bid: &SignedBuilderBid<
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        >,
+        slot: Slot,
+        proposer_public_key: BlsPublicKey,
+    ) -> Self {
+        let bid = &signed_bid.message;
+        let header = &bid.header;
+        Self {
+            slot,
+            parent_hash: header.parent_hash.clone(),
+            block_hash: header.block_hash.clone(),
+            builder_public_key: bid.public_key.clone(),
+            proposer_public_key,
+            proposer_fee_recipient: header.fee_recipient.clone(),
+            gas_limit: header.gas_limit,
+            gas_used: header.gas_used,
+            value: bid.value,
+        }
+    }
+}
+
+/// A `BidTrace` with additional details of the execution payload, as served by the relay data
+/// API.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BidTraceV2 {
+    #[serde(flatten)]
+    pub message: BidTrace,
+    #[serde(with = "crate::serde::as_str")]
+    pub block_number: u64,
+    #[serde(with = "crate::serde::as_str")]
+    pub num_tx: usize,
+    #[serde(default, with = "crate::serde::as_str")]
+    pub num_blobs: usize,
+}
+
+/// The trace of a payload delivered by a relay, as served by the relay data API at
+/// `/relay/v1/data/bidtraces/proposer_payload_delivered`.
+pub type DeliveredPayloadTrace = BidTraceV2;
+
+impl BidTraceV2 {
+    /// Build the trace of a payload delivered for `signed_block`. Fields not committed to by the
+    /// block must be provided from the winning bid.
+    pub fn from_signed_blinded_block<
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+    >(
+        signed_block: &SignedBlindedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+        builder_public_key: BlsPublicKey,
+        proposer_public_key: BlsPublicKey,
+        value: U256,
+        num_tx: usize,
+    ) -> Self {
+        let block = &signed_block.message;
+        let header = &block.body.execution_payload_header;
+        let message = BidTrace {
+            slot: block.slot,
+            parent_hash: header.parent_hash.clone(),
+            block_hash: header.block_hash.clone(),
+            builder_public_key,
+            proposer_public_key,
+            proposer_fee_recipient: header.fee_recipient.clone(),
+            gas_limit: header.gas_limit,
+            gas_used: header.gas_used,
+            value,
+        };
+        Self {
+            message,
+            block_number: header.block_number,
+            num_tx,
+            num_blobs: block.body.blob_kzg_commitments.len(),
+        }
+    }
+}
+
+pub fn sign_bid_trace(
+    bid_trace: &BidTrace,
+    signing_key: &SecretKey,
+    context: &Context,
+) -> Result<BlsSignature, Error> {
+    let domain = compute_builder_domain(context)?;
+    sign_with_domain(bid_trace, signing_key, domain)
+}
+
+pub fn verify_bid_trace_signature(
+    bid_trace: &BidTrace,
+    signature: &BlsSignature,
+    context: &Context,
+) -> Result<(), Error> {
+    let domain = compute_builder_domain(context)?;
+    verify_signed_data(bid_trace, signature, &bid_trace.builder_public_key, domain)
+}
+
+#[cfg(test)]
+#[cfg(feature = "serde")]
+mod tests {
+    use super::*;
+    use crate::deneb::mainnet;
+
+    // NOTE: a hand-written entry in the format a Flashbots relay serves at
+    // `/relay/v1/data/bidtraces/proposer_payload_delivered`; the hashes and keys are made up
+    // rather than captured from a relay, so only the field names and encodings are meaningful
+    const DELIVERED_PAYLOAD_JSON: &str = r#"{
+        "slot": "8729210",
+        "parent_hash": "0x2fb0c3a9b8ba4a2c1e5b1cc4d82d2e5e2b1ad4bc6c1cc4cbd6d8b5ac3c1ba3e4",
+        "block_hash": "0x9a3d2a3f2d2c3cc1e9c2a6d4e8f1b2c3d4e5f6a7b8c9d0e1f2a3b4c5d6e7f8a9",
+        "builder_pubkey": "0xa1dead01e65f0a0eee7b5170223f20c8f0cbf122eac3324d61afbdb33a8885ff8cab2ef514ac2c7698ae0d6289ef27fc",
+        "proposer_pubkey": "0x8f7b6e4e83c6d3c2c01e8dfe2d81e6a5d1bf2ad4a0c1e4cde05fa4e2c3a6f6b1e8f9d1c2b3a4958677f6e5d4c3b2a190",
+        "proposer_fee_recipient": "0x388c818ca8b9251b393131c08a736a67ccb19297",
+        "gas_limit": "30000000",
+        "gas_used": "12417520",
+        "value": "44136723470935690",
+        "block_number": "19538493",
+        "num_tx": "157",
+        "num_blobs": "3"
+    }"#;
+
+    #[test]
+    fn test_relay_data_api_fixtures() {
+        let trace: DeliveredPayloadTrace = serde_json::from_str(DELIVERED_PAYLOAD_JSON).unwrap();
+        assert_eq!(trace.message.slot, 8729210);
+        assert_eq!(trace.message.gas_used, 12417520);
+        assert_eq!(trace.message.value, U256::from(44136723470935690u64));
+        assert_eq!(trace.block_number, 19538493);
+        assert_eq!(trace.num_tx, 157);
+        assert_eq!(trace.num_blobs, 3);
+
+        let json = serde_json::to_value(&trace).unwrap();
+        let expected: serde_json::Value = serde_json::from_str(DELIVERED_PAYLOAD_JSON).unwrap();
+        assert_eq!(json, expected);
+
+        // `BidTrace` ignores the additional fields of `BidTraceV2`
+        let bid_trace: BidTrace = serde_json::from_str(DELIVERED_PAYLOAD_JSON).unwrap();
+        assert_eq!(bid_trace, trace.message);
+    }
+
+    #[test]
+    fn test_bid_trace_from_builder_bid() {
+        let context = Context::for_mainnet();
+        let builder_key = SecretKey::key_gen(&[3u8; 32]).unwrap();
+        let proposer_public_key = SecretKey::key_gen(&[4u8; 32]).unwrap().public_key();
+
+        let mut header = mainnet::ExecutionPayloadHeader::default();
+        header.parent_hash = Hash32::try_from([1u8; 32].as_slice()).unwrap();
+        header.block_hash = Hash32::try_from([2u8; 32].as_slice()).unwrap();
+        header.fee_recipient = ExecutionAddress::try_from([5u8; 20].as_slice()).unwrap();
+        header.gas_limit = 30_000_000;
+        header.gas_used = 21_000;
+        header.block_number = 42;
+        let bid = BuilderBid {
+            header,
+            blob_kzg_commitments: List::try_from(vec![KzgCommitment::default(); 2]).unwrap(),
+            value: U256::from(1_000_000_000u64),
+            public_key: builder_key.public_key(),
+        };
+        let signed_bid = SignedBuilderBid { message: bid, signature: Default::default() };
+
+        let trace =
+            BidTrace::from_signed_builder_bid(&signed_bid, 1234, proposer_public_key.clone());
+        let header = &signed_bid.message.header;
+        assert_eq!(trace.slot, 1234);
+        assert_eq!(trace.parent_hash, header.parent_hash);
+        assert_eq!(trace.block_hash, header.block_hash);
+        assert_eq!(trace.builder_public_key, builder_key.public_key());
+        assert_eq!(trace.proposer_public_key, proposer_public_key);
+        assert_eq!(trace.proposer_fee_recipient, header.fee_recipient);
+        assert_eq!(trace.gas_limit, 30_000_000);
+        assert_eq!(trace.gas_used, 21_000);
+        assert_eq!(trace.value, signed_bid.message.value);
+
+        let mut signed_block = mainnet::SignedBlindedBeaconBlock::default();
+        signed_block.message.slot = 1234;
+        signed_block.message.body.execution_payload_header = header.clone();
+        signed_block.message.body.blob_kzg_commitments =
+            signed_bid.message.blob_kzg_commitments.clone();
+        let delivered = DeliveredPayloadTrace::from_signed_blinded_block(
+            &signed_block,
+            builder_key.public_key(),
+            proposer_public_key,
+            trace.value,
+            7,
+        );
+        assert_eq!(delivered.message, trace);
+        assert_eq!(delivered.block_number, 42);
+        assert_eq!(delivered.num_tx, 7);
+        assert_eq!(delivered.num_blobs, 2);
+
+        let signature = sign_bid_trace(&trace, &builder_key, &context).unwrap();
+        verify_bid_trace_signature(&trace, &signature, &context).unwrap();
+        let mut other = trace.clone();
+        other.value = U256::from(1u64);
+        assert!(verify_bid_trace_signature(&other, &signature, &context).is_err());
+    }
+}
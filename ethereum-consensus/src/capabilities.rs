@@ -0,0 +1,129 @@
+//! A report of what this build of the crate supports, e.g. for a `/version`-style endpoint or
+//! to check the expected feature combination in CI.
+use crate::{
+    altair, bellatrix, capella, deneb, electra, light_client, peerdas, phase0,
+    state_transition::{self, gnosis, mainnet, minimal},
+    Fork,
+};
+
+// The optional features of the crate (see `Cargo.toml`) and whether each is enabled. A test
+// checks this against the features declared in the manifest.
+const FEATURES: &[(&str, bool)] = &[
+    ("serde", cfg!(feature = "serde")),
+    ("async", cfg!(feature = "async")),
+    ("optimized", cfg!(feature = "optimized")),
+    ("shuffling", cfg!(feature = "shuffling")),
+    ("tracing", cfg!(feature = "tracing")),
+    ("secret-key-debug", cfg!(feature = "secret-key-debug")),
+    ("spec-tests", cfg!(feature = "spec-tests")),
+    ("fixtures", cfg!(feature = "fixtures")),
+    ("ffi", cfg!(feature = "ffi")),
+    ("ec", cfg!(feature = "ec")),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CrateCapabilities {
+    pub version: String,
+    /// Forks with an implementation of the spec, in order of activation.
+    pub forks: Vec<Fork>,
+    pub presets: Vec<String>,
+    pub kzg_backend: String,
+    /// The names of the enabled optional features.
+    pub features: Vec<String>,
+    /// If the state transition runs any work in parallel.
+    pub parallelism: bool,
+    pub light_client: bool,
+    pub peerdas: bool,
+}
+
+impl CrateCapabilities {
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features.iter().any(|enabled| enabled == feature)
+    }
+}
+
+pub fn capabilities() -> CrateCapabilities {
+    CrateCapabilities {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        forks: vec![
+            phase0::FORK,
+            altair::FORK,
+            bellatrix::FORK,
+            capella::FORK,
+            deneb::FORK,
+            electra::FORK,
+        ],
+        presets: vec![
+            mainnet::PRESET_NAME.to_string(),
+            minimal::PRESET_NAME.to_string(),
+            gnosis::PRESET_NAME.to_string(),
+        ],
+        kzg_backend: deneb::polynomial_commitments::KZG_BACKEND.to_string(),
+        features: FEATURES
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| name.to_string())
+            .collect(),
+        parallelism: state_transition::PARALLELISM,
+        light_client: light_client::SUPPORTED,
+        peerdas: peerdas::SUPPORTED,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities() {
+        let capabilities = capabilities();
+        assert_eq!(capabilities.forks.first(), Some(&Fork::Phase0));
+        assert_eq!(capabilities.forks.last(), Some(&Fork::Electra));
+        assert!(capabilities.forks.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(capabilities.presets, ["mainnet", "minimal", "gnosis"]);
+        assert_eq!(capabilities.kzg_backend, "c-kzg");
+        assert!(capabilities.light_client);
+        assert!(capabilities.peerdas);
+        assert!(!capabilities.parallelism);
+    }
+
+    #[test]
+    fn test_features_match_manifest() {
+        let manifest = include_str!("../Cargo.toml");
+        let declared = manifest
+            .lines()
+            .skip_while(|line| line.trim() != "[features]")
+            .skip(1)
+            .take_while(|line| !line.starts_with('['))
+            .filter_map(|line| line.split_once('=').map(|(name, _)| name.trim()))
+            .filter(|name| !name.is_empty() && *name != "default")
+            .collect::<Vec<_>>();
+        let known = FEATURES.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+        for feature in &declared {
+            assert!(known.contains(feature), "feature {feature} is missing from FEATURES");
+        }
+        assert_eq!(declared.len(), known.len());
+    }
+
+    #[cfg(all(feature = "serde", feature = "async"))]
+    #[test]
+    fn test_default_features() {
+        let capabilities = capabilities();
+        assert!(capabilities.has_feature("serde"));
+        assert!(capabilities.has_feature("async"));
+
+        let json = serde_json::to_value(&capabilities).unwrap();
+        assert_eq!(json["forks"][0], "phase0");
+        let recovered: CrateCapabilities = serde_json::from_value(json).unwrap();
+        assert_eq!(recovered, capabilities);
+    }
+
+    #[cfg(not(any(feature = "serde", feature = "async")))]
+    #[test]
+    fn test_no_default_features() {
+        let capabilities = capabilities();
+        assert!(!capabilities.has_feature("serde"));
+        assert!(!capabilities.has_feature("async"));
+        assert_eq!(capabilities.forks.len(), 6);
+    }
+}
@@ -2,11 +2,12 @@ use crate::{
     capella::{
         compute_domain, compute_timestamp_at_slot, decrease_balance, get_current_epoch,
         get_randao_mix, is_fully_withdrawable_validator, is_partially_withdrawable_validator,
-        process_attestation, process_attester_slashing, process_block_header, process_deposit,
-        process_eth1_data, process_proposer_slashing, process_randao, process_sync_aggregate,
-        process_voluntary_exit, BeaconBlock, BeaconBlockBody, BeaconState, DomainType,
-        ExecutionAddress, ExecutionPayload, ExecutionPayloadHeader, SignedBlsToExecutionChange,
-        Withdrawal,
+        process_attestation_with_validation, process_attester_slashing_with_validation,
+        process_block_header, process_deposit, process_eth1_data,
+        process_proposer_slashing_with_validation, process_randao_with_validation,
+        process_sync_aggregate_with_validation, process_voluntary_exit_with_validation,
+        BeaconBlock, BeaconBlockBody, BeaconState, DomainType, ExecutionAddress, ExecutionPayload,
+        ExecutionPayloadHeader, SignedBlsToExecutionChange, Withdrawal,
     },
     crypto::hash,
     error::{
@@ -14,10 +15,13 @@ use crate::{
         InvalidExecutionPayload, InvalidOperation, InvalidWithdrawals,
     },
     execution_engine::ExecutionEngine,
-    primitives::{BLS_WITHDRAWAL_PREFIX, ETH1_ADDRESS_WITHDRAWAL_PREFIX},
+    primitives::{
+        saturating_usize, BLS_WITHDRAWAL_PREFIX, COMPOUNDING_WITHDRAWAL_PREFIX,
+        ETH1_ADDRESS_WITHDRAWAL_PREFIX,
+    },
     signing::verify_signed_data,
     ssz::prelude::*,
-    state_transition::{Context, Result},
+    state_transition::{Context, Result, Validation},
 };
 
 pub fn process_bls_to_execution_change<
@@ -46,6 +50,42 @@ pub fn process_bls_to_execution_change<
     >,
     signed_address_change: &SignedBlsToExecutionChange,
     context: &Context,
+) -> Result<()> {
+    process_bls_to_execution_change_with_validation(
+        state,
+        signed_address_change,
+        Validation::Enabled,
+        context,
+    )
+}
+
+pub fn process_bls_to_execution_change_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    signed_address_change: &SignedBlsToExecutionChange,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let address_change = &signed_address_change.message;
     let signature = &signed_address_change.signature;
@@ -58,6 +98,14 @@ pub fn process_bls_to_execution_change<
 
     let withdrawal_credentials =
         &mut state.validators[address_change.validator_index].withdrawal_credentials;
+    if matches!(
+        withdrawal_credentials[0],
+        ETH1_ADDRESS_WITHDRAWAL_PREFIX | COMPOUNDING_WITHDRAWAL_PREFIX
+    ) {
+        return Err(invalid_operation_error(InvalidOperation::BlsToExecutionChange(
+            InvalidBlsToExecutionChange::AlreadyRotated(address_change.validator_index),
+        )))
+    }
     if withdrawal_credentials[0] != BLS_WITHDRAWAL_PREFIX {
         return Err(invalid_operation_error(InvalidOperation::BlsToExecutionChange(
             InvalidBlsToExecutionChange::WithdrawalCredentialsPrefix(withdrawal_credentials[0]),
@@ -71,13 +119,15 @@ pub fn process_bls_to_execution_change<
         )))
     }
 
-    let domain = compute_domain(
-        DomainType::BlsToExecutionChange,
-        None,
-        Some(state.genesis_validators_root),
-        context,
-    )?;
-    verify_signed_data(address_change, signature, public_key, domain)?;
+    if validation.verifies_signatures() {
+        let domain = compute_domain(
+            DomainType::BlsToExecutionChange,
+            None,
+            Some(state.genesis_validators_root),
+            context,
+        )?;
+        verify_signed_data(address_change, signature, public_key, domain)?;
+    }
 
     withdrawal_credentials[0] = ETH1_ADDRESS_WITHDRAWAL_PREFIX;
     withdrawal_credentials[1..12].fill(0);
@@ -135,10 +185,64 @@ pub fn process_operations<
         MAX_BLS_TO_EXECUTION_CHANGES,
     >,
     context: &Context,
+) -> Result<()> {
+    process_operations_with_validation(state, body, Validation::Enabled, context)
+}
+
+pub fn process_operations_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    body: &BeaconBlockBody<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+    >,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let expected_deposit_count = usize::min(
         context.max_deposits,
-        (state.eth1_data.deposit_count - state.eth1_deposit_index) as usize,
+        saturating_usize(state.eth1_data.deposit_count - state.eth1_deposit_index),
     );
     if body.deposits.len() != expected_deposit_count {
         return Err(invalid_operation_error(InvalidOperation::Deposit(
@@ -148,18 +252,46 @@ pub fn process_operations<
             },
         )))
     }
-    body.proposer_slashings
-        .iter()
-        .try_for_each(|op| process_proposer_slashing(state, op, context))?;
-    body.attester_slashings
-        .iter()
-        .try_for_each(|op| process_attester_slashing(state, op, context))?;
-    body.attestations.iter().try_for_each(|op| process_attestation(state, op, context))?;
-    body.deposits.iter().try_for_each(|op| process_deposit(state, op, context))?;
-    body.voluntary_exits.iter().try_for_each(|op| process_voluntary_exit(state, op, context))?;
-    body.bls_to_execution_changes
-        .iter()
-        .try_for_each(|op| process_bls_to_execution_change(state, op, context))?;
+    traced!(
+        "proposer_slashings",
+        body.proposer_slashings.iter().try_for_each(
+            |op| process_proposer_slashing_with_validation(state, op, validation, context)
+        ),
+        count = body.proposer_slashings.len()
+    )?;
+    traced!(
+        "attester_slashings",
+        body.attester_slashings.iter().try_for_each(
+            |op| process_attester_slashing_with_validation(state, op, validation, context)
+        ),
+        count = body.attester_slashings.len()
+    )?;
+    traced!(
+        "attestations",
+        body.attestations
+            .iter()
+            .try_for_each(|op| process_attestation_with_validation(state, op, validation, context)),
+        count = body.attestations.len()
+    )?;
+    traced!(
+        "deposits",
+        body.deposits.iter().try_for_each(|op| process_deposit(state, op, context)),
+        count = body.deposits.len()
+    )?;
+    traced!(
+        "voluntary_exits",
+        body.voluntary_exits.iter().try_for_each(|op| process_voluntary_exit_with_validation(
+            state, op, validation, context
+        )),
+        count = body.voluntary_exits.len()
+    )?;
+    traced!(
+        "bls_to_execution_changes",
+        body.bls_to_execution_changes.iter().try_for_each(|op| {
+            process_bls_to_execution_change_with_validation(state, op, validation, context)
+        }),
+        count = body.bls_to_execution_changes.len()
+    )?;
     Ok(())
 }
 
@@ -459,13 +591,158 @@ pub fn process_block<
         MAX_BLS_TO_EXECUTION_CHANGES,
     >,
     context: &Context,
+) -> Result<()> {
+    process_block_with_validation(state, block, Validation::Enabled, context)
+}
+
+pub fn process_block_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    block: &BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+    >,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     process_block_header(state, block, context)?;
     process_withdrawals(state, &block.body.execution_payload, context)?;
     process_execution_payload(state, &block.body, context)?;
-    process_randao(state, &block.body, context)?;
+    process_randao_with_validation(state, &block.body, validation, context)?;
     process_eth1_data(state, &block.body, context);
-    process_operations(state, &block.body, context)?;
-    process_sync_aggregate(state, &block.body.sync_aggregate, context)?;
+    process_operations_with_validation(state, &block.body, validation, context)?;
+    process_sync_aggregate_with_validation(state, &block.body.sync_aggregate, validation, context)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        capella::{minimal, Validator},
+        primitives::{Bytes32, Epoch, Gwei, FAR_FUTURE_EPOCH},
+    };
+
+    fn validator(prefix: u8, effective_balance: Gwei, withdrawable_epoch: Epoch) -> Validator {
+        let mut credentials = [0xaa; 32];
+        credentials[0] = prefix;
+        Validator {
+            withdrawal_credentials: Bytes32::try_from(credentials.as_ref()).unwrap(),
+            effective_balance,
+            exit_epoch: FAR_FUTURE_EPOCH,
+            withdrawable_epoch,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_expected_withdrawals() {
+        let context = Context::for_minimal();
+        let max = context.max_effective_balance;
+        let mut state = minimal::BeaconState::default();
+        let registry = [
+            // fully withdrawable
+            (validator(ETH1_ADDRESS_WITHDRAWAL_PREFIX, max, 0), max),
+            // partially withdrawable
+            (validator(ETH1_ADDRESS_WITHDRAWAL_PREFIX, max, FAR_FUTURE_EPOCH), max + 3),
+            // excess balance without execution credentials
+            (validator(BLS_WITHDRAWAL_PREFIX, max, 0), max + 3),
+            // no excess balance
+            (validator(ETH1_ADDRESS_WITHDRAWAL_PREFIX, max, FAR_FUTURE_EPOCH), max),
+        ];
+        for (validator, balance) in registry {
+            state.validators.push(validator);
+            state.balances.push(balance);
+        }
+        state.next_withdrawal_index = 7;
+
+        let pre_state = state.clone();
+        let withdrawals = get_expected_withdrawals(&state, &context);
+        assert_eq!(state, pre_state);
+        let address = ExecutionAddress::try_from([0xaa; 20].as_ref()).unwrap();
+        let expected = vec![
+            Withdrawal { index: 7, validator_index: 0, address: address.clone(), amount: max },
+            Withdrawal { index: 8, validator_index: 1, address, amount: 3 },
+        ];
+        assert_eq!(withdrawals, expected);
+
+        let mut payload = minimal::ExecutionPayload::default();
+        payload.withdrawals = List::try_from(withdrawals).unwrap();
+        process_withdrawals(&mut state, &payload, &context).unwrap();
+        assert_eq!(state.balances.as_ref(), [0, max, max + 3, max]);
+        assert_eq!(state.next_withdrawal_index, 9);
+        // the sweep covers the whole registry without filling the payload
+        assert_eq!(state.next_withdrawal_validator_index, 0);
+        assert!(get_expected_withdrawals(&state, &context).is_empty());
+    }
+
+    #[test]
+    fn test_expected_withdrawals_are_bounded() {
+        let context = Context::for_minimal();
+        let max = context.max_effective_balance;
+        let mut state = minimal::BeaconState::default();
+        for _ in 0..2 * context.max_validators_per_withdrawals_sweep {
+            state.validators.push(validator(ETH1_ADDRESS_WITHDRAWAL_PREFIX, max, 0));
+            state.balances.push(max);
+        }
+        state.next_withdrawal_validator_index = 3;
+
+        // at most a payload of withdrawals, starting from the next validator of the sweep
+        let withdrawals = get_expected_withdrawals(&state, &context);
+        assert_eq!(withdrawals.len(), context.max_withdrawals_per_payload);
+        let indices = withdrawals.iter().map(|w| w.validator_index).collect::<Vec<_>>();
+        assert_eq!(indices, [3, 4, 5, 6]);
+
+        // and no further than the sweep bound
+        for balance in state.balances.iter_mut() {
+            *balance = 0;
+        }
+        let last = state.validators.len() - 1;
+        state.balances[last] = max;
+        assert!(get_expected_withdrawals(&state, &context).is_empty());
+        state.next_withdrawal_validator_index =
+            last + 1 - context.max_validators_per_withdrawals_sweep;
+        assert_eq!(get_expected_withdrawals(&state, &context).len(), 1);
+    }
+}
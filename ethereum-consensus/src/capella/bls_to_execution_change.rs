@@ -21,3 +21,14 @@ pub struct SignedBlsToExecutionChange {
     pub message: BlsToExecutionChange,
     pub signature: BlsSignature,
 }
+
+crate::ssz_size::impl_ssz_size!(BlsToExecutionChange {
+    validator_index: ValidatorIndex,
+    from_bls_public_key: BlsPublicKey,
+    to_execution_address: ExecutionAddress,
+});
+
+crate::ssz_size::impl_ssz_size!(SignedBlsToExecutionChange {
+    message: BlsToExecutionChange,
+    signature: BlsSignature,
+});
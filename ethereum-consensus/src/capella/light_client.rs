@@ -1,26 +1,32 @@
 use crate::{
     altair::light_client::{
-        CURRENT_SYNC_COMMITTEE_INDEX_FLOOR_LOG_2, FINALIZED_ROOT_INDEX_FLOOR_LOG_2,
-        NEXT_SYNC_COMMITTEE_INDEX_FLOOR_LOG_2,
+        self as altair, is_valid_light_client_branch, CURRENT_SYNC_COMMITTEE_INDEX_FLOOR_LOG_2,
+        FINALIZED_ROOT_INDEX_FLOOR_LOG_2, NEXT_SYNC_COMMITTEE_INDEX_FLOOR_LOG_2,
     },
     capella::{
         execution_payload::ExecutionPayloadHeader, BeaconBlockHeader, SyncAggregate, SyncCommittee,
     },
-    primitives::{Bytes32, Slot},
+    phase0::helpers::compute_epoch_at_slot,
+    primitives::{Bytes32, IsZero, Root, Slot},
     ssz::prelude::*,
+    state_transition::{Context, Result},
 };
 
 pub const EXECUTION_PAYLOAD_INDEX: usize = 25;
 pub const EXECUTION_PAYLOAD_INDEX_FLOOR_LOG_2: usize = 4;
 
-#[derive(Default, Debug, Clone, SimpleSerialize, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
 pub struct LightClientHeader<const BYTES_PER_LOGS_BLOOM: usize, const MAX_EXTRA_DATA_BYTES: usize> {
     pub beacon: BeaconBlockHeader,
     pub execution: ExecutionPayloadHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
     pub execution_branch: Vector<Bytes32, EXECUTION_PAYLOAD_INDEX_FLOOR_LOG_2>,
 }
 
-#[derive(Default, Debug, Clone, SimpleSerialize, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
 pub struct LightClientBootstrap<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
@@ -31,7 +37,9 @@ pub struct LightClientBootstrap<
     pub current_sync_committee_branch: Vector<Bytes32, CURRENT_SYNC_COMMITTEE_INDEX_FLOOR_LOG_2>,
 }
 
-#[derive(Default, Debug, Clone, SimpleSerialize, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
 pub struct LightClientUpdate<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
@@ -46,7 +54,9 @@ pub struct LightClientUpdate<
     pub signature_slot: Slot,
 }
 
-#[derive(Default, Debug, Clone, SimpleSerialize, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
 pub struct LightClientFinalityUpdate<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
@@ -59,7 +69,9 @@ pub struct LightClientFinalityUpdate<
     pub signature_slot: Slot,
 }
 
-#[derive(Default, Debug, Clone, SimpleSerialize, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
 pub struct LightClientOptimisticUpdate<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
@@ -69,3 +81,147 @@ pub struct LightClientOptimisticUpdate<
     pub sync_aggregate: SyncAggregate<SYNC_COMMITTEE_SIZE>,
     pub signature_slot: Slot,
 }
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct LightClientStore<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+> {
+    pub finalized_header: LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    pub current_sync_committee: SyncCommittee<SYNC_COMMITTEE_SIZE>,
+    pub next_sync_committee: SyncCommittee<SYNC_COMMITTEE_SIZE>,
+    pub best_valid_update:
+        Option<LightClientUpdate<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>>,
+    pub optimistic_header: LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    pub previous_max_active_participants: usize,
+    pub current_max_active_participants: usize,
+}
+
+pub fn get_lc_execution_root<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    header: &LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    context: &Context,
+) -> Result<Root> {
+    let epoch = compute_epoch_at_slot(header.beacon.slot, context);
+    if epoch >= context.capella_fork_epoch {
+        return Ok(header.execution.hash_tree_root()?)
+    }
+    Ok(Root::default())
+}
+
+pub fn is_valid_light_client_header<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    header: &LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    context: &Context,
+) -> bool {
+    let epoch = compute_epoch_at_slot(header.beacon.slot, context);
+    if epoch < context.capella_fork_epoch {
+        return header.execution == ExecutionPayloadHeader::default() &&
+            header.execution_branch.iter().all(|node| node.is_zero())
+    }
+    let Ok(execution_root) = get_lc_execution_root(header, context) else { return false };
+    is_valid_light_client_branch(
+        execution_root,
+        &header.execution_branch,
+        EXECUTION_PAYLOAD_INDEX,
+        header.beacon.body_root,
+    )
+}
+
+pub fn upgrade_lc_header_to_capella<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    pre: &altair::LightClientHeader,
+) -> LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES> {
+    LightClientHeader {
+        beacon: pre.beacon.clone(),
+        execution: ExecutionPayloadHeader::default(),
+        execution_branch: Default::default(),
+    }
+}
+
+pub fn upgrade_lc_bootstrap_to_capella<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    pre: &altair::LightClientBootstrap<SYNC_COMMITTEE_SIZE>,
+) -> LightClientBootstrap<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES> {
+    LightClientBootstrap {
+        header: upgrade_lc_header_to_capella(&pre.header),
+        current_sync_committee: pre.current_sync_committee.clone(),
+        current_sync_committee_branch: pre.current_sync_committee_branch.clone(),
+    }
+}
+
+pub fn upgrade_lc_update_to_capella<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    pre: &altair::LightClientUpdate<SYNC_COMMITTEE_SIZE>,
+) -> LightClientUpdate<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES> {
+    LightClientUpdate {
+        attested_header: upgrade_lc_header_to_capella(&pre.attested_header),
+        next_sync_committee: pre.next_sync_committee.clone(),
+        next_sync_committee_branch: pre.next_sync_committee_branch.clone(),
+        finalized_header: upgrade_lc_header_to_capella(&pre.finalized_header),
+        finality_branch: pre.finality_branch.clone(),
+        sync_aggregate: pre.sync_aggregate.clone(),
+        signature_slot: pre.signature_slot,
+    }
+}
+
+pub fn upgrade_lc_finality_update_to_capella<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    pre: &altair::LightClientFinalityUpdate<SYNC_COMMITTEE_SIZE>,
+) -> LightClientFinalityUpdate<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES> {
+    LightClientFinalityUpdate {
+        attested_header: upgrade_lc_header_to_capella(&pre.attested_header),
+        finalized_header: upgrade_lc_header_to_capella(&pre.finalized_header),
+        finality_branch: pre.finality_branch.clone(),
+        sync_aggregate: pre.sync_aggregate.clone(),
+        signature_slot: pre.signature_slot,
+    }
+}
+
+pub fn upgrade_lc_optimistic_update_to_capella<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    pre: &altair::LightClientOptimisticUpdate<SYNC_COMMITTEE_SIZE>,
+) -> LightClientOptimisticUpdate<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES> {
+    LightClientOptimisticUpdate {
+        attested_header: upgrade_lc_header_to_capella(&pre.attested_header),
+        sync_aggregate: pre.sync_aggregate.clone(),
+        signature_slot: pre.signature_slot,
+    }
+}
+
+pub fn upgrade_lc_store_to_capella<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    pre: &altair::LightClientStore<SYNC_COMMITTEE_SIZE>,
+) -> LightClientStore<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES> {
+    LightClientStore {
+        finalized_header: upgrade_lc_header_to_capella(&pre.finalized_header),
+        current_sync_committee: pre.current_sync_committee.clone(),
+        next_sync_committee: pre.next_sync_committee.clone(),
+        best_valid_update: pre.best_valid_update.as_ref().map(upgrade_lc_update_to_capella),
+        optimistic_header: upgrade_lc_header_to_capella(&pre.optimistic_header),
+        previous_max_active_participants: pre.previous_max_active_participants,
+        current_max_active_participants: pre.current_max_active_participants,
+    }
+}
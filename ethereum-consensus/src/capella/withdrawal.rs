@@ -15,3 +15,95 @@ pub struct Withdrawal {
     #[serde(with = "crate::serde::as_str")]
     pub amount: Gwei,
 }
+
+crate::ssz_size::impl_ssz_size!(Withdrawal {
+    index: WithdrawalIndex,
+    validator_index: ValidatorIndex,
+    address: ExecutionAddress,
+    amount: Gwei,
+});
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::{capella::minimal::BeaconState, primitives::ToChecksummed};
+
+    const ADDRESS: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+
+    #[test]
+    fn test_withdrawal_serde() {
+        let expected = serde_json::json!({
+            "index": "18446744073709551615",
+            "validator_index": "1234",
+            "address": ADDRESS.to_lowercase(),
+            "amount": "32000000000",
+        });
+        let mut checksummed = expected.clone();
+        checksummed["address"] = ADDRESS.into();
+        for json in [expected.clone(), checksummed] {
+            let withdrawal: Withdrawal = serde_json::from_value(json).unwrap();
+            assert_eq!(withdrawal.index, WithdrawalIndex::MAX);
+            assert_eq!(withdrawal.address.to_checksummed(), ADDRESS);
+            assert_eq!(serde_json::to_value(&withdrawal).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_state_withdrawal_fields_serde() {
+        let state = BeaconState {
+            next_withdrawal_index: 7,
+            next_withdrawal_validator_index: 3,
+            ..Default::default()
+        };
+        let mut json = serde_json::to_value(&state).unwrap();
+        assert_eq!(json["next_withdrawal_index"], "7");
+        assert_eq!(json["next_withdrawal_validator_index"], "3");
+        let header = &mut json["latest_execution_payload_header"];
+        assert_eq!(header["fee_recipient"], format!("0x{}", "00".repeat(20)));
+
+        header["fee_recipient"] = ADDRESS.into();
+        let state: BeaconState = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(state.latest_execution_payload_header.fee_recipient.to_checksummed(), ADDRESS);
+        let json = serde_json::to_value(&state).unwrap();
+        assert_eq!(
+            json["latest_execution_payload_header"]["fee_recipient"],
+            ADDRESS.to_lowercase()
+        );
+    }
+
+    // NOTE: the responses are hand-written in the format of `/eth/v2/debug/beacon/states`, one
+    // with the lowercase addresses of the spec and one with the checksummed addresses some clients
+    // emit; they are not captured from clients, so only the field names and encodings are
+    // meaningful
+    #[test]
+    fn test_debug_state_response_round_trip() {
+        let state = BeaconState {
+            next_withdrawal_index: 1_000_000,
+            next_withdrawal_validator_index: 250_000,
+            ..Default::default()
+        };
+        let data = serde_json::to_value(&state).unwrap();
+        let expected = serde_json::json!({
+            "version": "capella",
+            "execution_optimistic": false,
+            "finalized": true,
+            "data": data,
+        });
+        let mut lowercase = expected.clone();
+        lowercase["data"]["latest_execution_payload_header"]["fee_recipient"] =
+            ADDRESS.to_lowercase().into();
+        let mut checksummed = expected;
+        checksummed["data"]["latest_execution_payload_header"]["fee_recipient"] = ADDRESS.into();
+
+        for response in [lowercase.clone(), checksummed] {
+            assert_eq!(response["data"]["next_withdrawal_index"], "1000000");
+            assert_eq!(response["data"]["next_withdrawal_validator_index"], "250000");
+            let state: BeaconState = serde_json::from_value(response["data"].clone()).unwrap();
+            assert_eq!(state.next_withdrawal_index, 1_000_000);
+            assert_eq!(state.next_withdrawal_validator_index, 250_000);
+            let fee_recipient = &state.latest_execution_payload_header.fee_recipient;
+            assert_eq!(fee_recipient.to_checksummed(), ADDRESS);
+            assert_eq!(serde_json::to_value(&state).unwrap(), lowercase["data"]);
+        }
+    }
+}
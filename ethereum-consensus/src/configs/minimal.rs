@@ -1,5 +1,5 @@
 use crate::{
-    configs::Config,
+    configs::{self, Config},
     networks::Network,
     primitives::{Epoch, ExecutionAddress, Gwei, Version, FAR_FUTURE_EPOCH, U256},
 };
@@ -89,5 +89,12 @@ pub fn config() -> Config {
         deposit_chain_id: DEPOSIT_CHAIN_ID,
         deposit_network_id: DEPOSIT_NETWORK_ID,
         deposit_contract_address,
+        max_blobs_per_block_electra: configs::MAX_BLOBS_PER_BLOCK_ELECTRA,
+        blob_schedule: vec![],
+        number_of_columns: configs::NUMBER_OF_COLUMNS,
+        number_of_custody_groups: configs::NUMBER_OF_CUSTODY_GROUPS,
+        data_column_sidecar_subnet_count: configs::DATA_COLUMN_SIDECAR_SUBNET_COUNT,
+        custody_requirement: configs::CUSTODY_REQUIREMENT,
+        samples_per_slot: configs::SAMPLES_PER_SLOT,
     }
 }
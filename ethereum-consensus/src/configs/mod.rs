@@ -1,5 +1,7 @@
+pub mod gnosis;
 pub mod goerli;
 pub mod holesky;
+pub mod hoodi;
 pub mod mainnet;
 pub mod minimal;
 pub mod sepolia;
@@ -62,4 +64,66 @@ pub struct Config {
     pub deposit_chain_id: usize,
     pub deposit_network_id: usize,
     pub deposit_contract_address: ExecutionAddress,
+
+    #[serde(default = "default_max_blobs_per_block_electra")]
+    pub max_blobs_per_block_electra: usize,
+
+    #[serde(default)]
+    pub blob_schedule: Vec<BlobScheduleEntry>,
+
+    #[serde(default = "default_number_of_columns")]
+    pub number_of_columns: usize,
+    #[serde(default = "default_number_of_custody_groups")]
+    pub number_of_custody_groups: usize,
+    #[serde(default = "default_data_column_sidecar_subnet_count")]
+    pub data_column_sidecar_subnet_count: usize,
+    #[serde(default = "default_custody_requirement")]
+    pub custody_requirement: usize,
+    #[serde(default = "default_samples_per_slot")]
+    pub samples_per_slot: usize,
+}
+
+// The blob limit from Electra on, which configurations of networks from before the fork do
+// not carry.
+pub const MAX_BLOBS_PER_BLOCK_ELECTRA: usize = 9;
+
+fn default_max_blobs_per_block_electra() -> usize {
+    MAX_BLOBS_PER_BLOCK_ELECTRA
+}
+
+// The data availability sampling parameters of EIP-7594 (PeerDAS), which configurations of
+// networks that have not scheduled it yet do not carry.
+pub const NUMBER_OF_COLUMNS: usize = 128;
+pub const NUMBER_OF_CUSTODY_GROUPS: usize = 128;
+pub const DATA_COLUMN_SIDECAR_SUBNET_COUNT: usize = 128;
+pub const CUSTODY_REQUIREMENT: usize = 4;
+pub const SAMPLES_PER_SLOT: usize = 8;
+
+fn default_number_of_columns() -> usize {
+    NUMBER_OF_COLUMNS
+}
+
+fn default_number_of_custody_groups() -> usize {
+    NUMBER_OF_CUSTODY_GROUPS
+}
+
+fn default_data_column_sidecar_subnet_count() -> usize {
+    DATA_COLUMN_SIDECAR_SUBNET_COUNT
+}
+
+fn default_custody_requirement() -> usize {
+    CUSTODY_REQUIREMENT
+}
+
+fn default_samples_per_slot() -> usize {
+    SAMPLES_PER_SLOT
+}
+
+/// An entry of the blob parameter schedule: from `epoch` on, a block may carry at most
+/// `max_blobs_per_block` blobs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub struct BlobScheduleEntry {
+    pub epoch: Epoch,
+    pub max_blobs_per_block: usize,
 }
@@ -0,0 +1,338 @@
+//! Estimates of the work to verify a block, so gossip and API layers can budget (and rate limit)
+//! verification before doing any of it.
+//!
+//! Work is counted in three resources that scale independently: BLS pairing checks, bytes run
+//! through SHA-256 and committee lookups (each a shuffling computation without a warm cache).
+//! A [`CostTable`] per fork holds the work of each kind of operation, which follows from how the
+//! state transition of that fork verifies it; [`cost_table`] picks the one for a block.
+//!
+//! # Calibration
+//!
+//! The units are the primitive operations themselves, so the tables only change with the
+//! verification code. What depends on hardware is how much of each resource fits in a time
+//! budget. The `verification_cost` benchmark times one unit of each resource
+//! (`cargo bench --bench verification_cost`):
+//!
+//! - `pairing`: `crypto::verify_signature`, which is dominated by its pairing checks
+//! - `hash_1mib`: `crypto::hash` over a MiB
+//! - `committee_lookup`: shuffling a registry of a million validators, as an epoch of mainnet
+//!
+//! A limit for a time budget is the budget divided by the timing of a unit of that resource. The
+//! `batch_verification` benchmark times whole blocks to check the tables against.
+//!
+//! [`DEFAULT_LIMITS`] is not tied to a machine: it is derived from the mainnet preset maxima so
+//! that it admits every block a well-behaved proposer produces and bounds pathological ones.
+use crate::{
+    bellatrix::networking::GOSSIP_MAX_SIZE_BELLATRIX,
+    capella::mainnet::MAX_BLS_TO_EXECUTION_CHANGES,
+    phase0::{
+        constants::DEPOSIT_CONTRACT_TREE_DEPTH,
+        mainnet::{MAX_ATTESTATIONS, MAX_DEPOSITS, MAX_VOLUNTARY_EXITS},
+    },
+    ssz::prelude::*,
+    types::SignedBeaconBlock,
+    Error, Fork,
+};
+
+/// The work to verify a block, or a limit on it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VerificationCost {
+    pub signature_pairings: u64,
+    pub hashing_bytes: u64,
+    pub committee_lookups: u64,
+}
+
+impl VerificationCost {
+    /// Whether any resource of `self` is above its limit in `limits`.
+    pub fn exceeds(&self, limits: &VerificationCost) -> bool {
+        self.signature_pairings > limits.signature_pairings ||
+            self.hashing_bytes > limits.hashing_bytes ||
+            self.committee_lookups > limits.committee_lookups
+    }
+}
+
+/// The work to verify each kind of operation in a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CostTable {
+    /// The proposer and RANDAO signatures every block carries.
+    pub block_pairings: u64,
+    pub proposer_slashing_pairings: u64,
+    pub attester_slashing_pairings: u64,
+    pub attestation_pairings: u64,
+    pub deposit_pairings: u64,
+    pub voluntary_exit_pairings: u64,
+    pub sync_aggregate_pairings: u64,
+    pub bls_to_execution_change_pairings: u64,
+    /// Bytes hashed per byte of the SSZ encoding of the block to merkleize it: each leaf chunk is
+    /// hashed once and the inner nodes of the trees add about as much again.
+    pub hashing_bytes_per_encoded_byte: u64,
+    /// Bytes hashed to verify the Merkle proof of a deposit, one 64 byte node per proof layer.
+    pub deposit_proof_hashing_bytes: u64,
+    pub attestation_committee_lookups: u64,
+    pub sync_aggregate_committee_lookups: u64,
+}
+
+pub const PHASE0_COSTS: CostTable = CostTable {
+    block_pairings: 2,
+    proposer_slashing_pairings: 2,
+    attester_slashing_pairings: 2,
+    attestation_pairings: 1,
+    deposit_pairings: 1,
+    voluntary_exit_pairings: 1,
+    sync_aggregate_pairings: 0,
+    bls_to_execution_change_pairings: 0,
+    hashing_bytes_per_encoded_byte: 2,
+    deposit_proof_hashing_bytes: 64 * (DEPOSIT_CONTRACT_TREE_DEPTH as u64 + 1),
+    attestation_committee_lookups: 1,
+    sync_aggregate_committee_lookups: 0,
+};
+
+/// Adds the sync aggregate, verified against the current sync committee.
+pub const ALTAIR_COSTS: CostTable =
+    CostTable { sync_aggregate_pairings: 1, sync_aggregate_committee_lookups: 1, ..PHASE0_COSTS };
+
+/// The execution payload is only hashed, as the execution engine verifies its contents.
+pub const BELLATRIX_COSTS: CostTable = ALTAIR_COSTS;
+
+pub const CAPELLA_COSTS: CostTable =
+    CostTable { bls_to_execution_change_pairings: 1, ..BELLATRIX_COSTS };
+
+/// The blob commitments are only hashed, as the blobs are verified against them separately.
+pub const DENEB_COSTS: CostTable = CAPELLA_COSTS;
+
+/// An attestation may span every committee of its slot, which all come from the one shuffling of
+/// its epoch, so it still needs a single lookup. The signatures of deposits made through
+/// execution requests are checked during epoch processing rather than with the block.
+pub const ELECTRA_COSTS: CostTable = DENEB_COSTS;
+
+/// The [`CostTable`] for blocks of `fork`.
+pub const fn cost_table(fork: Fork) -> &'static CostTable {
+    match fork {
+        Fork::Phase0 => &PHASE0_COSTS,
+        Fork::Altair => &ALTAIR_COSTS,
+        Fork::Bellatrix => &BELLATRIX_COSTS,
+        Fork::Capella => &CAPELLA_COSTS,
+        Fork::Deneb => &DENEB_COSTS,
+        Fork::Electra => &ELECTRA_COSTS,
+    }
+}
+
+pub const DEFAULT_LIMITS: VerificationCost = VerificationCost {
+    // a mainnet block with every list of operations full, save for at most one slashing of each
+    // kind as slashable offences are rare
+    signature_pairings: DENEB_COSTS.block_pairings +
+        DENEB_COSTS.proposer_slashing_pairings +
+        DENEB_COSTS.attester_slashing_pairings +
+        DENEB_COSTS.attestation_pairings * MAX_ATTESTATIONS as u64 +
+        DENEB_COSTS.deposit_pairings * MAX_DEPOSITS as u64 +
+        DENEB_COSTS.voluntary_exit_pairings * MAX_VOLUNTARY_EXITS as u64 +
+        DENEB_COSTS.sync_aggregate_pairings +
+        DENEB_COSTS.bls_to_execution_change_pairings * MAX_BLS_TO_EXECUTION_CHANGES as u64,
+    hashing_bytes: 2 * GOSSIP_MAX_SIZE_BELLATRIX as u64,
+    committee_lookups: DENEB_COSTS.attestation_committee_lookups * MAX_ATTESTATIONS as u64 +
+        DENEB_COSTS.sync_aggregate_committee_lookups,
+};
+
+/// The work to verify `block` by the [`CostTable`] of its fork.
+pub fn verification_cost<
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    block: &SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >,
+) -> Result<VerificationCost, Error> {
+    verification_cost_with_table(block, cost_table(block.version()))
+}
+
+pub fn verification_cost_with_table<
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    block: &SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >,
+    table: &CostTable,
+) -> Result<VerificationCost, Error> {
+    let message = block.message();
+    let body = message.body();
+    let count = |len: usize| len as u64;
+    // electra attestations and attester slashings are typed apart from those of earlier forks
+    let (attester_slashings, attestations) = match body.electra() {
+        Some(body) => (body.attester_slashings.len(), body.attestations.len()),
+        None => (
+            body.attester_slashings().map_or(0, |slashings| slashings.len()),
+            body.attestations().map_or(0, |attestations| attestations.len()),
+        ),
+    };
+    let attestations = count(attestations);
+    let deposits = count(body.deposits().len());
+    let sync_aggregates = u64::from(body.sync_aggregate().is_some());
+    let bls_to_execution_changes =
+        count(body.bls_to_execution_changes().map_or(0, |changes| changes.len()));
+
+    let signature_pairings = table.block_pairings +
+        table.proposer_slashing_pairings * count(body.proposer_slashings().len()) +
+        table.attester_slashing_pairings * count(attester_slashings) +
+        table.attestation_pairings * attestations +
+        table.deposit_pairings * deposits +
+        table.voluntary_exit_pairings * count(body.voluntary_exits().len()) +
+        table.sync_aggregate_pairings * sync_aggregates +
+        table.bls_to_execution_change_pairings * bls_to_execution_changes;
+    let encoding = serialize(block).map_err(|err| Error::SimpleSerialize(err.into()))?;
+    let encoded_bytes = count(encoding.len());
+    let hashing_bytes = table.hashing_bytes_per_encoded_byte * encoded_bytes +
+        table.deposit_proof_hashing_bytes * deposits;
+    let committee_lookups = table.attestation_committee_lookups * attestations +
+        table.sync_aggregate_committee_lookups * sync_aggregates;
+    Ok(VerificationCost { signature_pairings, hashing_bytes, committee_lookups })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{deneb::mainnet as deneb, fixtures::phase0::signed_block, types::mainnet};
+
+    fn fill<T: Default, const N: usize>(list: &mut List<T, N>, count: usize) {
+        for _ in 0..count {
+            list.push(T::default());
+        }
+    }
+
+    #[test]
+    fn test_operation_counts() {
+        // a proposer slashing, an attester slashing and an attestation
+        let block = SignedBeaconBlock::Phase0(signed_block());
+        let cost = verification_cost(&block).unwrap();
+        assert_eq!(cost.signature_pairings, 2 + 2 + 2 + 1);
+        assert_eq!(cost.committee_lookups, 1);
+        assert_eq!(cost.hashing_bytes, 2 * serialize(&block).unwrap().len() as u64);
+    }
+
+    #[test]
+    fn test_max_size_block_exceeds_default_limits() {
+        let mut typical = deneb::SignedBeaconBlock::default();
+        let body = &mut typical.message.body;
+        fill(&mut body.attestations, 64);
+        fill(&mut body.deposits, 1);
+        fill(&mut body.blob_kzg_commitments, 3);
+        let typical = mainnet::SignedBeaconBlock::Deneb(typical);
+        let cost = verification_cost(&typical).unwrap();
+        assert_eq!(cost.signature_pairings, 2 + 64 + 1 + 1);
+        assert_eq!(cost.committee_lookups, 64 + 1);
+        assert!(!cost.exceeds(&DEFAULT_LIMITS));
+
+        let mut max_size = deneb::SignedBeaconBlock::default();
+        let body = &mut max_size.message.body;
+        fill(&mut body.proposer_slashings, deneb::MAX_PROPOSER_SLASHINGS);
+        fill(&mut body.attester_slashings, deneb::MAX_ATTESTER_SLASHINGS);
+        fill(&mut body.attestations, deneb::MAX_ATTESTATIONS);
+        fill(&mut body.deposits, deneb::MAX_DEPOSITS);
+        fill(&mut body.voluntary_exits, deneb::MAX_VOLUNTARY_EXITS);
+        fill(&mut body.bls_to_execution_changes, deneb::MAX_BLS_TO_EXECUTION_CHANGES);
+        fill(&mut body.blob_kzg_commitments, deneb::MAX_BLOB_COMMITMENTS_PER_BLOCK);
+        let max_size = mainnet::SignedBeaconBlock::Deneb(max_size);
+        let cost = verification_cost(&max_size).unwrap();
+        assert_eq!(cost.signature_pairings, 2 + 16 * 2 + 2 * 2 + 128 + 16 + 16 + 1 + 16);
+        assert!(cost.exceeds(&DEFAULT_LIMITS));
+
+        // the same block with a single slashing of each kind is admitted
+        let mut full = deneb::SignedBeaconBlock::default();
+        let body = &mut full.message.body;
+        fill(&mut body.proposer_slashings, 1);
+        fill(&mut body.attester_slashings, 1);
+        fill(&mut body.attestations, deneb::MAX_ATTESTATIONS);
+        fill(&mut body.deposits, deneb::MAX_DEPOSITS);
+        fill(&mut body.voluntary_exits, deneb::MAX_VOLUNTARY_EXITS);
+        fill(&mut body.bls_to_execution_changes, deneb::MAX_BLS_TO_EXECUTION_CHANGES);
+        let full = mainnet::SignedBeaconBlock::Deneb(full);
+        let cost = verification_cost(&full).unwrap();
+        assert!(!cost.exceeds(&DEFAULT_LIMITS));
+
+        // each resource is limited on its own
+        let hashing_only = VerificationCost {
+            hashing_bytes: DEFAULT_LIMITS.hashing_bytes + 1,
+            ..Default::default()
+        };
+        assert!(hashing_only.exceeds(&DEFAULT_LIMITS));
+        assert!(!DEFAULT_LIMITS.exceeds(&DEFAULT_LIMITS));
+    }
+}
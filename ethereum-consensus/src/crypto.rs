@@ -1,8 +1,13 @@
 #[cfg(feature = "serde")]
 use crate::serde::try_bytes_from_hex_str;
-use crate::{primitives::Bytes32, ssz::prelude::*};
-use blst::{min_pk as bls_impl, BLST_ERROR};
+use crate::{
+    primitives::{impl_try_from_hex, Bytes32, IsZero},
+    ssz::prelude::*,
+};
+use blst::{blst_scalar, min_pk as bls_impl, BLST_ERROR};
+use rand::Rng;
 use sha2::{Digest, Sha256};
+use sha3::Keccak256;
 use std::{
     fmt,
     ops::{Deref, DerefMut},
@@ -19,10 +24,21 @@ pub fn hash<D: AsRef<[u8]>>(data: D) -> Bytes32 {
     result
 }
 
+/// The Keccak-256 hash used by the execution layer, e.g. for address checksums.
+pub fn keccak256<D: AsRef<[u8]>>(data: D) -> Bytes32 {
+    let mut hasher = Keccak256::new();
+    hasher.update(data);
+
+    let mut result = Bytes32::default();
+    let inner = &mut result[..];
+    hasher.finalize_into(inner.into());
+    result
+}
+
 const BLS_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
-const BLS_PUBLIC_KEY_BYTES_LEN: usize = 48;
+pub const BLS_PUBLIC_KEY_BYTES_LEN: usize = 48;
 const BLS_SECRET_KEY_BYTES_LEN: usize = 32;
-const BLS_SIGNATURE_BYTES_LEN: usize = 96;
+pub const BLS_SIGNATURE_BYTES_LEN: usize = 96;
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -66,9 +82,19 @@ pub fn verify_signature(
     msg: &[u8],
     signature: &Signature,
 ) -> Result<(), Error> {
-    let public_key: bls_impl::PublicKey = public_key.try_into()?;
+    let public_key = DecompressedPublicKey::try_from(public_key)?;
+    verify_signature_decompressed(&public_key, msg, signature)
+}
+
+/// Like [`verify_signature`] but with a public key that has already been decompressed, e.g. by a
+/// `DecompressedPubkeyCache`.
+pub fn verify_signature_decompressed(
+    public_key: &DecompressedPublicKey,
+    msg: &[u8],
+    signature: &Signature,
+) -> Result<(), Error> {
     let signature: bls_impl::Signature = signature.try_into()?;
-    let res = signature.verify(true, msg, BLS_DST, &[], &public_key, true);
+    let res = signature.verify(true, msg, BLS_DST, &[], &public_key.0, true);
     if res == BLST_ERROR::BLST_SUCCESS {
         Ok(())
     } else {
@@ -97,6 +123,7 @@ pub fn aggregate_verify(
     msgs: &[&[u8]],
     signature: &Signature,
 ) -> Result<(), Error> {
+    trace_span!("aggregate_verify", public_keys = public_keys.len());
     let public_keys = public_keys
         .iter()
         .map(bls_impl::PublicKey::try_from)
@@ -119,9 +146,20 @@ pub fn fast_aggregate_verify(
     let public_keys = public_keys
         .iter()
         .cloned()
-        .map(bls_impl::PublicKey::try_from)
-        .collect::<Result<Vec<bls_impl::PublicKey>, Error>>()?;
-    let public_keys: Vec<&bls_impl::PublicKey> = public_keys.iter().collect();
+        .map(DecompressedPublicKey::try_from)
+        .collect::<Result<Vec<_>, Error>>()?;
+    let public_keys = public_keys.iter().collect::<Vec<_>>();
+    fast_aggregate_verify_decompressed(&public_keys, msg, signature)
+}
+
+/// Like [`fast_aggregate_verify`] but with public keys that have already been decompressed.
+pub fn fast_aggregate_verify_decompressed(
+    public_keys: &[&DecompressedPublicKey],
+    msg: &[u8],
+    signature: &Signature,
+) -> Result<(), Error> {
+    trace_span!("fast_aggregate_verify", public_keys = public_keys.len());
+    let public_keys = public_keys.iter().map(|public_key| &public_key.0).collect::<Vec<_>>();
     let signature: bls_impl::Signature = signature.try_into()?;
     let res = signature.fast_aggregate_verify(true, msg, BLS_DST, &public_keys);
     if res == BLST_ERROR::BLST_SUCCESS {
@@ -147,6 +185,20 @@ pub fn eth_aggregate_public_keys(public_keys: &[PublicKey]) -> Result<PublicKey,
         .map_err(|e| BLSTError::from(e).into())
 }
 
+/// Like [`eth_aggregate_public_keys`] but with public keys that have already been decompressed
+/// (and so validated), keeping the aggregate decompressed.
+pub fn aggregate_decompressed_public_keys(
+    public_keys: &[&DecompressedPublicKey],
+) -> Result<DecompressedPublicKey, Error> {
+    if public_keys.is_empty() {
+        return Err(Error::EmptyAggregate)
+    }
+    let public_keys = public_keys.iter().map(|public_key| &public_key.0).collect::<Vec<_>>();
+    bls_impl::AggregatePublicKey::aggregate(&public_keys, false)
+        .map(|aggregate| DecompressedPublicKey(aggregate.to_public_key()))
+        .map_err(|e| BLSTError::from(e).into())
+}
+
 pub fn eth_fast_aggregate_verify(
     public_keys: &[&PublicKey],
     message: &[u8],
@@ -159,6 +211,72 @@ pub fn eth_fast_aggregate_verify(
     }
 }
 
+// Verify each `signatures[i]` over `msgs[i]` by `public_keys[i]` in a single multi-pairing,
+// weighting each signature by a random scalar so invalid signatures cannot cancel out.
+// NOTE: a failure does not identify the offending signature; callers needing that should fall
+// back to `verify_signature` on each item.
+pub fn verify_multiple_signatures(
+    public_keys: &[&PublicKey],
+    msgs: &[&[u8]],
+    signatures: &[&Signature],
+) -> Result<(), Error> {
+    let public_keys = public_keys
+        .iter()
+        .cloned()
+        .map(DecompressedPublicKey::try_from)
+        .collect::<Result<Vec<_>, Error>>()?;
+    let public_keys = public_keys.iter().collect::<Vec<_>>();
+    verify_multiple_signatures_decompressed(&public_keys, msgs, signatures)
+}
+
+/// Like [`verify_multiple_signatures`] but with public keys that have already been decompressed.
+pub fn verify_multiple_signatures_decompressed(
+    public_keys: &[&DecompressedPublicKey],
+    msgs: &[&[u8]],
+    signatures: &[&Signature],
+) -> Result<(), Error> {
+    trace_span!("verify_multiple_signatures", signatures = signatures.len());
+    if signatures.is_empty() {
+        return Err(Error::EmptyAggregate)
+    }
+    if public_keys.len() != signatures.len() || msgs.len() != signatures.len() {
+        return Err(Error::InvalidSignature)
+    }
+    let public_keys = public_keys.iter().map(|public_key| &public_key.0).collect::<Vec<_>>();
+    let signatures = signatures
+        .iter()
+        .cloned()
+        .map(bls_impl::Signature::try_from)
+        .collect::<Result<Vec<bls_impl::Signature>, Error>>()?;
+    let signatures: Vec<&bls_impl::Signature> = signatures.iter().collect();
+
+    let mut rng = rand::thread_rng();
+    let scalars = (0..signatures.len())
+        .map(|_| {
+            let mut scalar = blst_scalar::default();
+            // scalars must be non-zero
+            let value = rng.gen_range(1..=u64::MAX);
+            scalar.b[..8].copy_from_slice(&value.to_le_bytes());
+            scalar
+        })
+        .collect::<Vec<_>>();
+    let res = bls_impl::Signature::verify_multiple_aggregate_signatures(
+        msgs,
+        BLS_DST,
+        &public_keys,
+        true,
+        &signatures,
+        true,
+        &scalars,
+        64,
+    );
+    if res == BLST_ERROR::BLST_SUCCESS {
+        Ok(())
+    } else {
+        Err(Error::InvalidSignature)
+    }
+}
+
 #[derive(Clone, Default, serde::Deserialize)]
 #[serde(try_from = "String")]
 pub struct SecretKey(bls_impl::SecretKey);
@@ -227,6 +345,7 @@ impl SecretKey {
 #[derive(
     Clone, Default, Hash, PartialEq, Eq, SimpleSerialize, serde::Serialize, serde::Deserialize,
 )]
+#[repr(transparent)]
 pub struct PublicKey(ByteVector<BLS_PUBLIC_KEY_BYTES_LEN>);
 
 impl fmt::Debug for PublicKey {
@@ -275,9 +394,26 @@ impl TryFrom<&PublicKey> for bls_impl::PublicKey {
     }
 }
 
+/// A public key decompressed to a point on the curve and checked to be a valid key.
+///
+/// Decompression dominates the cost of verifying a signature against a single key, so keys used
+/// repeatedly (e.g. validator keys) are best decompressed once and kept in this form.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct DecompressedPublicKey(bls_impl::PublicKey);
+
+impl TryFrom<&PublicKey> for DecompressedPublicKey {
+    type Error = Error;
+
+    fn try_from(public_key: &PublicKey) -> Result<Self, Error> {
+        bls_impl::PublicKey::try_from(public_key).map(Self)
+    }
+}
+
 #[derive(
     Clone, Default, Hash, PartialEq, Eq, SimpleSerialize, serde::Serialize, serde::Deserialize,
 )]
+#[repr(transparent)]
 pub struct Signature(ByteVector<BLS_SIGNATURE_BYTES_LEN>);
 
 impl fmt::Debug for Signature {
@@ -326,6 +462,8 @@ impl TryFrom<&Signature> for bls_impl::Signature {
     }
 }
 
+impl_try_from_hex!(PublicKey => BLS_PUBLIC_KEY_BYTES_LEN, Signature => BLS_SIGNATURE_BYTES_LEN);
+
 const INFINITY_COMPRESSED_SIGNATURE: [u8; BLS_SIGNATURE_BYTES_LEN] = [
     192, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
@@ -334,11 +472,26 @@ const INFINITY_COMPRESSED_SIGNATURE: [u8; BLS_SIGNATURE_BYTES_LEN] = [
 ];
 
 impl Signature {
+    /// The compressed point at infinity: the aggregate of no signatures, e.g. of a sync
+    /// aggregate without participants.
+    ///
+    /// NOTE: this is not the SSZ default of a signature, which is all zero bytes and not a valid
+    /// encoding of any point (see [`IsZero`]).
+    pub fn infinity() -> Self {
+        Self(ByteVector::try_from(INFINITY_COMPRESSED_SIGNATURE.as_ref()).expect("correct length"))
+    }
+
     pub fn is_infinity(&self) -> bool {
         self.as_ref() == INFINITY_COMPRESSED_SIGNATURE
     }
 }
 
+impl IsZero for Signature {
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -507,17 +660,58 @@ mod tests {
         let signatures: Vec<_> = sks.iter().map(|sk| sk.sign(msg)).collect();
 
         let pks = pks.iter().collect::<Vec<_>>();
-        let sig = aggregate(signatures.as_slice()).unwrap();
+        let sig = aggregate(signatures.as_ref()).unwrap();
         let v = fast_aggregate_verify(&pks, msg, &sig);
 
         assert!(v.is_ok());
     }
 
+    #[test]
+    fn test_verify_multiple_signatures() {
+        let n = 20;
+        let mut rng = thread_rng();
+        let sks: Vec<_> = (0..n).map(|_| SecretKey::random(&mut rng).unwrap()).collect();
+        let pks: Vec<_> = sks.iter().map(|sk| sk.public_key()).collect();
+        let msgs: Vec<Vec<u8>> = (0..n).map(|i| vec![i as u8; 32]).collect();
+        let mut signatures: Vec<_> = msgs.iter().zip(&sks).map(|(msg, sk)| sk.sign(msg)).collect();
+
+        let pks = pks.iter().collect::<Vec<_>>();
+        let msgs = msgs.iter().map(|msg| msg.as_slice()).collect::<Vec<_>>();
+        assert!(
+            verify_multiple_signatures(&pks, &msgs, &signatures.iter().collect::<Vec<_>>()).is_ok()
+        );
+
+        signatures.swap(3, 4);
+        assert!(verify_multiple_signatures(&pks, &msgs, &signatures.iter().collect::<Vec<_>>())
+            .is_err());
+        assert!(verify_multiple_signatures(
+            &pks[1..],
+            &msgs,
+            &signatures.iter().collect::<Vec<_>>()
+        )
+        .is_err());
+        assert!(verify_multiple_signatures(&[], &[], &[]).is_err());
+    }
+
     #[test]
     fn test_can_make_default_signature() {
         let _ = Signature::default();
     }
 
+    #[test]
+    fn test_infinity_is_not_the_default_signature() {
+        let infinity = Signature::infinity();
+        assert!(infinity.is_infinity() && !infinity.is_zero());
+        let default = Signature::default();
+        assert!(default.is_zero() && !default.is_infinity());
+
+        // only the point at infinity is the signature of no participants
+        let msg = b"previous block root";
+        assert!(eth_fast_aggregate_verify(&[], msg, &infinity).is_ok());
+        assert!(eth_fast_aggregate_verify(&[], msg, &default).is_err());
+        assert!(bls_impl::Signature::try_from(&default).is_err());
+    }
+
     #[test]
     fn test_can_sign() {
         let secret_key_hex = "40094c5c6c378857eac09b8ec64c87182f58700c056a8b371ad0eb0a5b983d50";
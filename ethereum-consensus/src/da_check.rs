@@ -0,0 +1,177 @@
+//! Bookkeeping for the availability of blob sidecars: which sidecars a node must serve (and so
+//! retain), and how many sidecars a block may have at a given epoch.
+use crate::{
+    deneb::networking::MIN_EPOCHS_FOR_BLOB_SIDECARS_REQUESTS,
+    phase0::compute_epoch_at_slot,
+    primitives::{Epoch, Slot},
+    state_transition::Context,
+};
+use std::ops::RangeInclusive;
+
+/// The epochs for which sidecars must be retained (and served) at `current_epoch`.
+///
+/// The window spans the last `MIN_EPOCHS_FOR_BLOB_SIDECARS_REQUESTS` epochs but never reaches
+/// back before the Deneb fork, so it is empty if Deneb has not activated by `current_epoch`.
+pub fn blob_retention_window(current_epoch: Epoch, context: &Context) -> RangeInclusive<Epoch> {
+    let start = current_epoch
+        .saturating_sub(MIN_EPOCHS_FOR_BLOB_SIDECARS_REQUESTS as Epoch)
+        .max(context.deneb_fork_epoch);
+    start..=current_epoch
+}
+
+/// Determine if the sidecar for a block at `sidecar_slot` must be kept at `current_epoch`.
+///
+/// Sidecars ahead of `current_epoch` are retained as they can not be pruned yet.
+pub fn should_retain_sidecar(sidecar_slot: Slot, current_epoch: Epoch, context: &Context) -> bool {
+    let epoch = compute_epoch_at_slot(sidecar_slot, context);
+    epoch >= *blob_retention_window(current_epoch, context).start()
+}
+
+/// The maximum number of blobs a block at `epoch` may carry, following the blob schedule of
+/// `context` after Deneb.
+pub fn max_blobs_per_block_at(epoch: Epoch, context: &Context) -> usize {
+    if epoch < context.deneb_fork_epoch {
+        return 0
+    }
+    scheduled_max_blobs_per_block(epoch, context)
+}
+
+/// The maximum number of blobs a block at `epoch` may carry under the blob schedule of `context`,
+/// for a block already known to be from Deneb or later.
+///
+/// The limit of the latest fork at `epoch` applies until a later entry of the schedule replaces
+/// it; an entry at the same epoch as the fork takes precedence.
+pub fn scheduled_max_blobs_per_block(epoch: Epoch, context: &Context) -> usize {
+    let fork_limit = if epoch >= context.electra_fork_epoch {
+        (context.electra_fork_epoch, context.max_blobs_per_block_electra)
+    } else {
+        (context.deneb_fork_epoch, context.max_blobs_per_block)
+    };
+    context
+        .blob_schedule
+        .iter()
+        .filter(|entry| entry.epoch <= epoch)
+        .map(|entry| (entry.epoch, entry.max_blobs_per_block))
+        .fold(fork_limit, |limit, entry| if entry.0 >= limit.0 { entry } else { limit })
+        .1
+}
+
+/// The range of the number of sidecars a block at `epoch` may have.
+pub fn expected_sidecar_count_range(epoch: Epoch, context: &Context) -> RangeInclusive<usize> {
+    0..=max_blobs_per_block_at(epoch, context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::BlobScheduleEntry;
+
+    const DENEB_FORK_EPOCH: Epoch = 10;
+    const WINDOW: Epoch = MIN_EPOCHS_FOR_BLOB_SIDECARS_REQUESTS as Epoch;
+
+    fn context() -> Context {
+        let mut context = Context::for_minimal();
+        context.deneb_fork_epoch = DENEB_FORK_EPOCH;
+        context.blob_schedule = vec![
+            BlobScheduleEntry { epoch: 5000, max_blobs_per_block: 9 },
+            BlobScheduleEntry { epoch: 7000, max_blobs_per_block: 12 },
+        ];
+        context
+    }
+
+    #[test]
+    fn test_blob_retention_window() {
+        let context = context();
+        let cases = [
+            (0, None),
+            (DENEB_FORK_EPOCH - 1, None),
+            (DENEB_FORK_EPOCH, Some(DENEB_FORK_EPOCH..=DENEB_FORK_EPOCH)),
+            (WINDOW, Some(DENEB_FORK_EPOCH..=WINDOW)),
+            (WINDOW + DENEB_FORK_EPOCH, Some(DENEB_FORK_EPOCH..=WINDOW + DENEB_FORK_EPOCH)),
+            (
+                WINDOW + DENEB_FORK_EPOCH + 1,
+                Some(DENEB_FORK_EPOCH + 1..=WINDOW + DENEB_FORK_EPOCH + 1),
+            ),
+            (3 * WINDOW, Some(2 * WINDOW..=3 * WINDOW)),
+        ];
+        for (current_epoch, expected) in cases {
+            let window = blob_retention_window(current_epoch, &context);
+            match expected {
+                Some(expected) => assert_eq!(window, expected, "{current_epoch}"),
+                None => assert!(window.is_empty(), "{current_epoch}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_should_retain_sidecar() {
+        let context = context();
+        let slots_per_epoch = context.slots_per_epoch;
+        let current_epoch = 3 * WINDOW;
+        let cases = [
+            // the last and first slot of the epoch before the window
+            (2 * WINDOW * slots_per_epoch - 1, false),
+            (2 * WINDOW * slots_per_epoch - slots_per_epoch, false),
+            // the first slot of the window
+            (2 * WINDOW * slots_per_epoch, true),
+            (current_epoch * slots_per_epoch, true),
+            // not yet prunable
+            ((current_epoch + 1) * slots_per_epoch, true),
+        ];
+        for (slot, expected) in cases {
+            assert_eq!(should_retain_sidecar(slot, current_epoch, &context), expected, "{slot}");
+        }
+
+        // sidecars can not exist before deneb, even while the window reaches back that far
+        let deneb_slot = DENEB_FORK_EPOCH * slots_per_epoch;
+        assert!(!should_retain_sidecar(deneb_slot - 1, DENEB_FORK_EPOCH + 1, &context));
+        assert!(should_retain_sidecar(deneb_slot, DENEB_FORK_EPOCH + 1, &context));
+    }
+
+    #[test]
+    fn test_expected_sidecar_count_range() {
+        let context = context();
+        let max_blobs_per_block = context.max_blobs_per_block;
+        let cases = [
+            (0, 0),
+            (DENEB_FORK_EPOCH - 1, 0),
+            (DENEB_FORK_EPOCH, max_blobs_per_block),
+            (4999, max_blobs_per_block),
+            (5000, 9),
+            (6999, 9),
+            (7000, 12),
+            (u64::MAX, 12),
+        ];
+        for (epoch, max) in cases {
+            assert_eq!(expected_sidecar_count_range(epoch, &context), 0..=max, "{epoch}");
+        }
+    }
+
+    #[test]
+    fn test_electra_blob_limit() {
+        let mut context = context();
+        context.electra_fork_epoch = 6000;
+        // distinct from every limit of the schedule
+        context.max_blobs_per_block_electra = 10;
+        let max_blobs_per_block = context.max_blobs_per_block;
+        let max_blobs_per_block_electra = context.max_blobs_per_block_electra;
+        let cases = [
+            (DENEB_FORK_EPOCH, max_blobs_per_block),
+            (5000, 9),
+            (5999, 9),
+            (6000, max_blobs_per_block_electra),
+            (6999, max_blobs_per_block_electra),
+            (7000, 12),
+        ];
+        for (epoch, max) in cases {
+            assert_eq!(max_blobs_per_block_at(epoch, &context), max, "{epoch}");
+        }
+
+        // a schedule entry at the fork replaces the limit of the fork
+        context.electra_fork_epoch = 5000;
+        assert_eq!(max_blobs_per_block_at(5000, &context), 9);
+        context.blob_schedule.clear();
+        assert_eq!(max_blobs_per_block_at(4999, &context), max_blobs_per_block);
+        assert_eq!(max_blobs_per_block_at(5000, &context), max_blobs_per_block_electra);
+    }
+}
@@ -0,0 +1,682 @@
+//! Consistency checks over an arbitrary `BeaconState`, for triaging states of unknown provenance.
+//!
+//! The state transition keeps a number of structural invariants that the SSZ types alone cannot
+//! express. A state loaded from disk, received from a peer or assembled by hand may break them
+//! long before any state transition function notices, so [`check_state_invariants`] reports every
+//! broken invariant at once instead of stopping at the first.
+use crate::{
+    electra::{self, PendingConsolidation, PendingPartialWithdrawal},
+    phase0::{compute_epoch_at_slot, BeaconBlockHeader, Checkpoint, Validator},
+    primitives::{Epoch, Gwei, IsZero, Root, Slot, ValidatorIndex},
+    state_transition::Context,
+    types::BeaconState,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvariantViolation {
+    /// `balances` is not the same length as the registry.
+    BalancesLength { validators: usize, balances: usize },
+    /// A per-validator list (participation or inactivity scores) is not the same length as the
+    /// registry.
+    RegistryListLength { field: &'static str, validators: usize, length: usize },
+    /// A history vector is not the length the context prescribes.
+    VectorLength { field: &'static str, expected: u64, length: usize },
+    /// More deposits were processed than the deposit contract has seen.
+    Eth1DepositIndex { eth1_deposit_index: u64, deposit_count: u64 },
+    /// The validator exits before it activates.
+    ExitBeforeActivation { index: ValidatorIndex, activation_epoch: Epoch, exit_epoch: Epoch },
+    /// The validator becomes withdrawable before it exits.
+    WithdrawableBeforeExit { index: ValidatorIndex, exit_epoch: Epoch, withdrawable_epoch: Epoch },
+    /// A checkpoint is from an epoch the state has not reached.
+    CheckpointInFuture { field: &'static str, epoch: Epoch, current_epoch: Epoch },
+    /// A checkpoint is newer than the current justified checkpoint.
+    CheckpointAfterCurrentJustified {
+        field: &'static str,
+        epoch: Epoch,
+        current_justified_epoch: Epoch,
+    },
+    /// The most recent justification bit is set but the current justified checkpoint is not the
+    /// epoch it marks.
+    JustificationBits { current_justified_epoch: Epoch, expected_epoch: Epoch },
+    /// The latest block header is from a slot the state has not reached, or (with an unfilled
+    /// state root) from a slot the state has already moved past.
+    LatestBlockHeaderSlot { header_slot: Slot, slot: Slot, state_root: Root },
+    /// The state root filled into the latest block header differs from the one recorded in
+    /// `state_roots`.
+    LatestBlockHeaderStateRoot { header_slot: Slot, state_root: Root, recorded_root: Root },
+    /// An entry of an electra queue refers to a validator outside the registry.
+    QueueIndexOutOfBounds {
+        field: &'static str,
+        position: usize,
+        index: ValidatorIndex,
+        validators: usize,
+    },
+    /// An entry of an electra queue that is processed in order is out of order.
+    QueueOutOfOrder { field: &'static str, position: usize, previous_epoch: Epoch, epoch: Epoch },
+}
+
+// Borrowed view of the fields shared by every fork, so each fork's state is checked the same way.
+struct CommonFields<'a> {
+    slot: Slot,
+    latest_block_header: &'a BeaconBlockHeader,
+    state_roots: &'a [Root],
+    block_roots_length: usize,
+    randao_mixes_length: usize,
+    slashings_length: usize,
+    eth1_deposit_index: u64,
+    deposit_count: u64,
+    validators: &'a [Validator],
+    balances: &'a [Gwei],
+    registry_lists: Vec<(&'static str, usize)>,
+    most_recent_justification_bit: bool,
+    previous_justified_checkpoint: &'a Checkpoint,
+    current_justified_checkpoint: &'a Checkpoint,
+    finalized_checkpoint: &'a Checkpoint,
+}
+
+fn check_common_fields(
+    fields: &CommonFields<'_>,
+    context: &Context,
+    violations: &mut Vec<InvariantViolation>,
+) {
+    let validator_count = fields.validators.len();
+    if fields.balances.len() != validator_count {
+        violations.push(InvariantViolation::BalancesLength {
+            validators: validator_count,
+            balances: fields.balances.len(),
+        });
+    }
+    for &(field, length) in &fields.registry_lists {
+        if length != validator_count {
+            violations.push(InvariantViolation::RegistryListLength {
+                field,
+                validators: validator_count,
+                length,
+            });
+        }
+    }
+
+    for (field, expected, length) in [
+        ("block_roots", context.slots_per_historical_root, fields.block_roots_length),
+        ("state_roots", context.slots_per_historical_root, fields.state_roots.len()),
+        ("randao_mixes", context.epochs_per_historical_vector, fields.randao_mixes_length),
+        ("slashings", context.epochs_per_slashings_vector, fields.slashings_length),
+    ] {
+        if length as u64 != expected {
+            violations.push(InvariantViolation::VectorLength { field, expected, length });
+        }
+    }
+
+    if fields.eth1_deposit_index > fields.deposit_count {
+        violations.push(InvariantViolation::Eth1DepositIndex {
+            eth1_deposit_index: fields.eth1_deposit_index,
+            deposit_count: fields.deposit_count,
+        });
+    }
+
+    for (index, validator) in fields.validators.iter().enumerate() {
+        if validator.exit_epoch < validator.activation_epoch {
+            violations.push(InvariantViolation::ExitBeforeActivation {
+                index,
+                activation_epoch: validator.activation_epoch,
+                exit_epoch: validator.exit_epoch,
+            });
+        }
+        if validator.withdrawable_epoch < validator.exit_epoch {
+            violations.push(InvariantViolation::WithdrawableBeforeExit {
+                index,
+                exit_epoch: validator.exit_epoch,
+                withdrawable_epoch: validator.withdrawable_epoch,
+            });
+        }
+    }
+
+    check_checkpoints(fields, context, violations);
+    check_latest_block_header(fields, violations);
+}
+
+fn check_checkpoints(
+    fields: &CommonFields<'_>,
+    context: &Context,
+    violations: &mut Vec<InvariantViolation>,
+) {
+    let current_epoch = compute_epoch_at_slot(fields.slot, context);
+    let current_justified_epoch = fields.current_justified_checkpoint.epoch;
+    for (field, checkpoint) in [
+        ("previous_justified_checkpoint", fields.previous_justified_checkpoint),
+        ("current_justified_checkpoint", fields.current_justified_checkpoint),
+        ("finalized_checkpoint", fields.finalized_checkpoint),
+    ] {
+        if checkpoint.epoch > current_epoch {
+            violations.push(InvariantViolation::CheckpointInFuture {
+                field,
+                epoch: checkpoint.epoch,
+                current_epoch,
+            });
+        }
+    }
+    for (field, checkpoint) in [
+        ("previous_justified_checkpoint", fields.previous_justified_checkpoint),
+        ("finalized_checkpoint", fields.finalized_checkpoint),
+    ] {
+        if checkpoint.epoch > current_justified_epoch {
+            violations.push(InvariantViolation::CheckpointAfterCurrentJustified {
+                field,
+                epoch: checkpoint.epoch,
+                current_justified_epoch,
+            });
+        }
+    }
+
+    // NOTE: justification runs in the last slot of an epoch, so by the time the state is in
+    // `current_epoch` the most recent bit marks the previous epoch.
+    if fields.most_recent_justification_bit {
+        let expected_epoch = current_epoch.saturating_sub(1);
+        if current_justified_epoch != expected_epoch {
+            violations.push(InvariantViolation::JustificationBits {
+                current_justified_epoch,
+                expected_epoch,
+            });
+        }
+    }
+}
+
+fn check_latest_block_header(fields: &CommonFields<'_>, violations: &mut Vec<InvariantViolation>) {
+    let header = fields.latest_block_header;
+    let slot_violation = InvariantViolation::LatestBlockHeaderSlot {
+        header_slot: header.slot,
+        slot: fields.slot,
+        state_root: header.state_root,
+    };
+    if header.slot > fields.slot {
+        violations.push(slot_violation);
+        return
+    }
+    // The state root of the latest block is left empty until the next slot is processed, when it
+    // is filled with the root also recorded in `state_roots`.
+    if header.state_root.is_zero() {
+        if header.slot != fields.slot {
+            violations.push(slot_violation);
+        }
+        return
+    }
+    if header.slot == fields.slot {
+        violations.push(slot_violation);
+        return
+    }
+    let history_length = fields.state_roots.len() as Slot;
+    if history_length == 0 || fields.slot - header.slot > history_length {
+        return
+    }
+    let recorded_root = fields.state_roots[(header.slot % history_length) as usize];
+    if recorded_root != header.state_root {
+        violations.push(InvariantViolation::LatestBlockHeaderStateRoot {
+            header_slot: header.slot,
+            state_root: header.state_root,
+            recorded_root,
+        });
+    }
+}
+
+/// Check the structural invariants of `state`, returning every violation found.
+///
+/// An empty result does not mean `state` is reachable from genesis; only that nothing in it
+/// contradicts the way the state transition updates each field.
+pub fn check_state_invariants<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    context: &Context,
+) -> Vec<InvariantViolation> {
+    let mut registry_lists = vec![];
+    if let Some(participation) = state.previous_epoch_participation() {
+        registry_lists.push(("previous_epoch_participation", participation.len()));
+    }
+    if let Some(participation) = state.current_epoch_participation() {
+        registry_lists.push(("current_epoch_participation", participation.len()));
+    }
+    if let Some(scores) = state.inactivity_scores() {
+        registry_lists.push(("inactivity_scores", scores.len()));
+    }
+    let fields = CommonFields {
+        slot: state.slot(),
+        latest_block_header: state.latest_block_header(),
+        state_roots: &state.state_roots()[..],
+        block_roots_length: state.block_roots().len(),
+        randao_mixes_length: state.randao_mixes().len(),
+        slashings_length: state.slashings().len(),
+        eth1_deposit_index: state.eth1_deposit_index(),
+        deposit_count: state.eth1_data().deposit_count,
+        validators: &state.validators()[..],
+        balances: &state.balances()[..],
+        registry_lists,
+        most_recent_justification_bit: state.justification_bits()[0],
+        previous_justified_checkpoint: state.previous_justified_checkpoint(),
+        current_justified_checkpoint: state.current_justified_checkpoint(),
+        finalized_checkpoint: state.finalized_checkpoint(),
+    };
+    let mut violations = vec![];
+    check_common_fields(&fields, context, &mut violations);
+    violations
+}
+
+/// Check the structural invariants of an electra `state`, including the ordering of its queues.
+///
+/// Electra states are not yet part of the polymorphic [`BeaconState`], so they are checked here.
+pub fn check_electra_state_invariants<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &electra::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    context: &Context,
+) -> Vec<InvariantViolation> {
+    let fields = CommonFields {
+        slot: state.slot,
+        latest_block_header: &state.latest_block_header,
+        state_roots: &state.state_roots[..],
+        block_roots_length: state.block_roots.len(),
+        randao_mixes_length: state.randao_mixes.len(),
+        slashings_length: state.slashings.len(),
+        eth1_deposit_index: state.eth1_deposit_index,
+        deposit_count: state.eth1_data.deposit_count,
+        validators: &state.validators[..],
+        balances: &state.balances[..],
+        registry_lists: vec![
+            ("previous_epoch_participation", state.previous_epoch_participation.len()),
+            ("current_epoch_participation", state.current_epoch_participation.len()),
+            ("inactivity_scores", state.inactivity_scores.len()),
+        ],
+        most_recent_justification_bit: state.justification_bits[0],
+        previous_justified_checkpoint: &state.previous_justified_checkpoint,
+        current_justified_checkpoint: &state.current_justified_checkpoint,
+        finalized_checkpoint: &state.finalized_checkpoint,
+    };
+    let mut violations = vec![];
+    check_common_fields(&fields, context, &mut violations);
+    check_queues(
+        &state.pending_partial_withdrawals,
+        &state.pending_consolidations,
+        state.validators.len(),
+        &mut violations,
+    );
+    violations
+}
+
+fn check_queues(
+    pending_partial_withdrawals: &[PendingPartialWithdrawal],
+    pending_consolidations: &[PendingConsolidation],
+    validators: usize,
+    violations: &mut Vec<InvariantViolation>,
+) {
+    let mut check_index = |field, position, index: ValidatorIndex| {
+        if index >= validators {
+            violations.push(InvariantViolation::QueueIndexOutOfBounds {
+                field,
+                position,
+                index,
+                validators,
+            });
+        }
+    };
+    for (position, withdrawal) in pending_partial_withdrawals.iter().enumerate() {
+        check_index("pending_partial_withdrawals", position, withdrawal.validator_index);
+    }
+    for (position, consolidation) in pending_consolidations.iter().enumerate() {
+        check_index("pending_consolidations.source_index", position, consolidation.source_index);
+        check_index("pending_consolidations.target_index", position, consolidation.target_index);
+    }
+
+    // Withdrawals are queued with the withdrawable epoch of a churn-limited exit, which never
+    // decreases, and are dequeued as soon as the first one is not yet withdrawable.
+    let mut previous_epoch = 0;
+    for (position, withdrawal) in pending_partial_withdrawals.iter().enumerate() {
+        let epoch = withdrawal.withdrawable_epoch;
+        if epoch < previous_epoch {
+            violations.push(InvariantViolation::QueueOutOfOrder {
+                field: "pending_partial_withdrawals",
+                position,
+                previous_epoch,
+                epoch,
+            });
+        }
+        previous_epoch = epoch;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        altair::upgrade_to_altair,
+        fixtures::phase0::{genesis_state, signed_block},
+        phase0::{self, state_transition, Validation},
+        types::minimal,
+    };
+
+    fn check(state: phase0::minimal::BeaconState, context: &Context) -> Vec<InvariantViolation> {
+        check_state_invariants(&minimal::BeaconState::Phase0(state), context)
+    }
+
+    #[test]
+    fn test_valid_states() {
+        let context = Context::for_minimal();
+        assert!(check(genesis_state(), &context).is_empty());
+
+        let mut state = genesis_state();
+        state_transition(&mut state, &signed_block(), Validation::Enabled, &context).unwrap();
+        assert!(check(state.clone(), &context).is_empty());
+
+        let state = upgrade_to_altair(&state, &context).unwrap();
+        assert!(check_state_invariants(&minimal::BeaconState::Altair(state), &context).is_empty());
+
+        let state = electra::minimal::BeaconState::default();
+        assert!(check_electra_state_invariants(&state, &context).is_empty());
+    }
+
+    #[test]
+    fn test_registry_lengths() {
+        let context = Context::for_minimal();
+        let mut state = genesis_state();
+        state.balances.pop();
+        assert_eq!(
+            check(state, &context),
+            vec![InvariantViolation::BalancesLength { validators: 64, balances: 63 }]
+        );
+
+        let mut state = upgrade_to_altair(&genesis_state(), &context).unwrap();
+        state.previous_epoch_participation.pop();
+        state.inactivity_scores.push(0);
+        assert_eq!(
+            check_state_invariants(&minimal::BeaconState::Altair(state), &context),
+            vec![
+                InvariantViolation::RegistryListLength {
+                    field: "previous_epoch_participation",
+                    validators: 64,
+                    length: 63,
+                },
+                InvariantViolation::RegistryListLength {
+                    field: "inactivity_scores",
+                    validators: 64,
+                    length: 65,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_vector_lengths() {
+        // a state decoded under the wrong preset
+        let mut context = Context::for_minimal();
+        context.epochs_per_slashings_vector = 8;
+        assert_eq!(
+            check(genesis_state(), &context),
+            vec![InvariantViolation::VectorLength { field: "slashings", expected: 8, length: 64 }]
+        );
+    }
+
+    #[test]
+    fn test_eth1_deposit_index() {
+        let context = Context::for_minimal();
+        let mut state = genesis_state();
+        state.eth1_deposit_index += 1;
+        assert_eq!(
+            check(state, &context),
+            vec![InvariantViolation::Eth1DepositIndex {
+                eth1_deposit_index: 65,
+                deposit_count: 64
+            }]
+        );
+    }
+
+    #[test]
+    fn test_validator_epochs() {
+        let context = Context::for_minimal();
+        let mut state = genesis_state();
+        state.validators[3].activation_epoch = 2;
+        state.validators[3].exit_epoch = 1;
+        state.validators[3].withdrawable_epoch = 5;
+        state.validators[7].exit_epoch = 4;
+        state.validators[7].withdrawable_epoch = 3;
+        assert_eq!(
+            check(state, &context),
+            vec![
+                InvariantViolation::ExitBeforeActivation {
+                    index: 3,
+                    activation_epoch: 2,
+                    exit_epoch: 1,
+                },
+                InvariantViolation::WithdrawableBeforeExit {
+                    index: 7,
+                    exit_epoch: 4,
+                    withdrawable_epoch: 3,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_checkpoints() {
+        let context = Context::for_minimal();
+        let mut state = genesis_state();
+        state.finalized_checkpoint.epoch = 1;
+        assert_eq!(
+            check(state, &context),
+            vec![
+                InvariantViolation::CheckpointInFuture {
+                    field: "finalized_checkpoint",
+                    epoch: 1,
+                    current_epoch: 0,
+                },
+                InvariantViolation::CheckpointAfterCurrentJustified {
+                    field: "finalized_checkpoint",
+                    epoch: 1,
+                    current_justified_epoch: 0,
+                },
+            ]
+        );
+
+        let mut state = genesis_state();
+        state.slot = 3 * context.slots_per_epoch;
+        state.latest_block_header.slot = state.slot;
+        state.previous_justified_checkpoint.epoch = 2;
+        state.current_justified_checkpoint.epoch = 1;
+        assert_eq!(
+            check(state, &context),
+            vec![InvariantViolation::CheckpointAfterCurrentJustified {
+                field: "previous_justified_checkpoint",
+                epoch: 2,
+                current_justified_epoch: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_justification_bits() {
+        let context = Context::for_minimal();
+        let mut state = genesis_state();
+        state.slot = 3 * context.slots_per_epoch;
+        state.latest_block_header.slot = state.slot;
+        state.current_justified_checkpoint.epoch = 2;
+        state.justification_bits.set(0, true);
+        assert!(check(state.clone(), &context).is_empty());
+
+        state.current_justified_checkpoint.epoch = 1;
+        assert_eq!(
+            check(state, &context),
+            vec![InvariantViolation::JustificationBits {
+                current_justified_epoch: 1,
+                expected_epoch: 2,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_latest_block_header() {
+        let context = Context::for_minimal();
+        let mut state = genesis_state();
+        state.latest_block_header.slot = 5;
+        assert_eq!(
+            check(state, &context),
+            vec![InvariantViolation::LatestBlockHeaderSlot {
+                header_slot: 5,
+                slot: 0,
+                state_root: Root::default(),
+            }]
+        );
+
+        // the state moved on without filling in the state root of the latest block
+        let mut state = genesis_state();
+        state.slot = 1;
+        assert_eq!(
+            check(state, &context),
+            vec![InvariantViolation::LatestBlockHeaderSlot {
+                header_slot: 0,
+                slot: 1,
+                state_root: Root::default(),
+            }]
+        );
+
+        let mut state = genesis_state();
+        state_transition(&mut state, &signed_block(), Validation::Enabled, &context).unwrap();
+        let slot = state.slot + 1;
+        phase0::process_slots(&mut state, slot, &context).unwrap();
+        assert!(check(state.clone(), &context).is_empty());
+        let recorded_root = state.latest_block_header.state_root;
+        let state_root = Root::try_from(&[1u8; 32][..]).unwrap();
+        state.latest_block_header.state_root = state_root;
+        assert_eq!(
+            check(state, &context),
+            vec![InvariantViolation::LatestBlockHeaderStateRoot {
+                header_slot: 2,
+                state_root,
+                recorded_root,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_reports_every_violation() {
+        let context = Context::for_minimal();
+        let mut state = genesis_state();
+        state.balances.push(0);
+        state.eth1_data.deposit_count = 0;
+        state.validators[0].withdrawable_epoch = 0;
+        state.latest_block_header.slot = 1;
+        let violations = check(state, &context);
+        assert_eq!(violations.len(), 4);
+        assert!(violations
+            .contains(&InvariantViolation::BalancesLength { validators: 64, balances: 65 }));
+        assert!(violations.contains(&InvariantViolation::Eth1DepositIndex {
+            eth1_deposit_index: 64,
+            deposit_count: 0
+        }));
+        assert!(violations.iter().any(|violation| matches!(
+            violation,
+            InvariantViolation::WithdrawableBeforeExit { index: 0, .. }
+        )));
+        assert!(violations.iter().any(|violation| matches!(
+            violation,
+            InvariantViolation::LatestBlockHeaderSlot { header_slot: 1, .. }
+        )));
+    }
+
+    #[test]
+    fn test_electra_queues() {
+        let context = Context::for_minimal();
+        let mut state = electra::minimal::BeaconState::default();
+        state.validators.push(Default::default());
+        state.balances.push(0);
+        state.previous_epoch_participation.push(0);
+        state.current_epoch_participation.push(0);
+        state.inactivity_scores.push(0);
+        // the default validator exits and withdraws in epoch 0
+        assert!(check_electra_state_invariants(&state, &context).is_empty());
+
+        state
+            .pending_consolidations
+            .push(PendingConsolidation { source_index: 0, target_index: 2 });
+        for (validator_index, withdrawable_epoch) in [(1, 5), (0, 7), (0, 6)] {
+            state.pending_partial_withdrawals.push(PendingPartialWithdrawal {
+                validator_index,
+                amount: 1,
+                withdrawable_epoch,
+            });
+        }
+        assert_eq!(
+            check_electra_state_invariants(&state, &context),
+            vec![
+                InvariantViolation::QueueIndexOutOfBounds {
+                    field: "pending_partial_withdrawals",
+                    position: 0,
+                    index: 1,
+                    validators: 1,
+                },
+                InvariantViolation::QueueIndexOutOfBounds {
+                    field: "pending_consolidations.target_index",
+                    position: 0,
+                    index: 2,
+                    validators: 1,
+                },
+                InvariantViolation::QueueOutOfOrder {
+                    field: "pending_partial_withdrawals",
+                    position: 2,
+                    previous_epoch: 7,
+                    epoch: 6,
+                },
+            ]
+        );
+    }
+}
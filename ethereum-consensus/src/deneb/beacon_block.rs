@@ -130,6 +130,116 @@ pub struct SignedBeaconBlock<
     pub signature: BlsSignature,
 }
 
+crate::ssz_size::impl_ssz_size!(BeaconBlockBody<
+    const MAX_PROPOSER_SLASHINGS,
+    const MAX_VALIDATORS_PER_COMMITTEE,
+    const MAX_ATTESTER_SLASHINGS,
+    const MAX_ATTESTATIONS,
+    const MAX_DEPOSITS,
+    const MAX_VOLUNTARY_EXITS,
+    const SYNC_COMMITTEE_SIZE,
+    const BYTES_PER_LOGS_BLOOM,
+    const MAX_EXTRA_DATA_BYTES,
+    const MAX_BYTES_PER_TRANSACTION,
+    const MAX_TRANSACTIONS_PER_PAYLOAD,
+    const MAX_WITHDRAWALS_PER_PAYLOAD,
+    const MAX_BLS_TO_EXECUTION_CHANGES,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK,
+> {
+    randao_reveal: BlsSignature,
+    eth1_data: Eth1Data,
+    graffiti: Bytes32,
+    proposer_slashings: List<ProposerSlashing, MAX_PROPOSER_SLASHINGS>,
+    attester_slashings:
+        List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS>,
+    attestations: List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS>,
+    deposits: List<Deposit, MAX_DEPOSITS>,
+    voluntary_exits: List<SignedVoluntaryExit, MAX_VOLUNTARY_EXITS>,
+    sync_aggregate: SyncAggregate<SYNC_COMMITTEE_SIZE>,
+    execution_payload: ExecutionPayload<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+    >,
+    bls_to_execution_changes: List<SignedBlsToExecutionChange, MAX_BLS_TO_EXECUTION_CHANGES>,
+    blob_kzg_commitments: List<KzgCommitment, MAX_BLOB_COMMITMENTS_PER_BLOCK>,
+});
+
+crate::ssz_size::impl_ssz_size!(BeaconBlock<
+    const MAX_PROPOSER_SLASHINGS,
+    const MAX_VALIDATORS_PER_COMMITTEE,
+    const MAX_ATTESTER_SLASHINGS,
+    const MAX_ATTESTATIONS,
+    const MAX_DEPOSITS,
+    const MAX_VOLUNTARY_EXITS,
+    const SYNC_COMMITTEE_SIZE,
+    const BYTES_PER_LOGS_BLOOM,
+    const MAX_EXTRA_DATA_BYTES,
+    const MAX_BYTES_PER_TRANSACTION,
+    const MAX_TRANSACTIONS_PER_PAYLOAD,
+    const MAX_WITHDRAWALS_PER_PAYLOAD,
+    const MAX_BLS_TO_EXECUTION_CHANGES,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK,
+> {
+    slot: Slot,
+    proposer_index: ValidatorIndex,
+    parent_root: Root,
+    state_root: Root,
+    body: BeaconBlockBody<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    >,
+});
+
+crate::ssz_size::impl_ssz_size!(SignedBeaconBlock<
+    const MAX_PROPOSER_SLASHINGS,
+    const MAX_VALIDATORS_PER_COMMITTEE,
+    const MAX_ATTESTER_SLASHINGS,
+    const MAX_ATTESTATIONS,
+    const MAX_DEPOSITS,
+    const MAX_VOLUNTARY_EXITS,
+    const SYNC_COMMITTEE_SIZE,
+    const BYTES_PER_LOGS_BLOOM,
+    const MAX_EXTRA_DATA_BYTES,
+    const MAX_BYTES_PER_TRANSACTION,
+    const MAX_TRANSACTIONS_PER_PAYLOAD,
+    const MAX_WITHDRAWALS_PER_PAYLOAD,
+    const MAX_BLS_TO_EXECUTION_CHANGES,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK,
+> {
+    message: BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    >,
+    signature: BlsSignature,
+});
+
 #[cfg(test)]
 mod tests {
     use crate::deneb::mainnet::BeaconBlockBody;
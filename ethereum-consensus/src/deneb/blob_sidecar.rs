@@ -1,16 +1,23 @@
 use crate::{
     deneb::{
         polynomial_commitments::{KzgCommitment, KzgProof},
-        SignedBeaconBlockHeader,
+        BeaconBlockBody, SignedBeaconBlock, SignedBeaconBlockHeader,
     },
+    error::WrongFork,
+    phase0::BeaconBlockHeader,
     primitives::{BlobIndex, Root},
     ssz::prelude::*,
-    Error,
+    state_transition::Context,
+    Error, Fork,
 };
 use ssz_rs::proofs::get_subtree_index;
 
 pub const VERSIONED_HASH_VERSION_KZG: u8 = 1;
 
+// The position of `blob_kzg_commitments` among the fields of the block body, which is the same
+// in every fork with blobs.
+pub(crate) const BLOB_KZG_COMMITMENTS_FIELD_INDEX: usize = 11;
+
 pub type Blob<const BYTES_PER_BLOB: usize> = ByteVector<BYTES_PER_BLOB>;
 
 #[derive(
@@ -45,25 +52,196 @@ pub struct BlobSidecar<
     pub kzg_commitment_inclusion_proof: Vector<Node, KZG_COMMITMENT_INCLUSION_PROOF_DEPTH>,
 }
 
+/// The generalized index of the commitment at `index` in the `blob_kzg_commitments` of a block
+/// body of `fork`. The depth of the index grows with the number of fields of the block body.
+pub fn blob_kzg_commitment_gindex(
+    index: BlobIndex,
+    fork: Fork,
+    context: &Context,
+) -> Result<GeneralizedIndex, Error> {
+    let field_count = match fork {
+        Fork::Deneb => 12,
+        Fork::Electra => 13,
+        fork => return Err(WrongFork { expected: Fork::Deneb, found: fork }.into()),
+    };
+    let bound = context.max_blob_commitments_per_block;
+    if index >= bound {
+        return Err(Error::OutOfBounds { requested: index, bound })
+    }
+    let body_depth = field_count.next_power_of_two().ilog2();
+    let list_depth = bound.next_power_of_two().ilog2();
+    // the data root of the list is the left child of its node, next to the length
+    let list_gindex = ((1 << body_depth) + BLOB_KZG_COMMITMENTS_FIELD_INDEX) * 2;
+    Ok((list_gindex << list_depth) + index)
+}
+
+/// Verify the `kzg_commitment_inclusion_proof` of `blob_sidecar` against the body root in its
+/// block header, for the fork of the slot of the header.
 pub fn verify_blob_sidecar_inclusion_proof<
-    const KZG_COMMITMENT_INCLUSION_PROOF_DEPTH: usize,
     const BYTES_PER_BLOB: usize,
-    BlockBody: SimpleSerialize,
+    const KZG_COMMITMENT_INCLUSION_PROOF_DEPTH: usize,
 >(
     blob_sidecar: &BlobSidecar<BYTES_PER_BLOB, KZG_COMMITMENT_INCLUSION_PROOF_DEPTH>,
+    context: &Context,
 ) -> Result<(), Error> {
-    let path = &["blob_kzg_commitments".into(), blob_sidecar.index.into()];
-    let g_index = BlockBody::generalized_index(path)?;
+    let header = &blob_sidecar.signed_block_header.message;
+    let fork = context.fork_for(header.slot);
+    let g_index = blob_kzg_commitment_gindex(blob_sidecar.index, fork, context)?;
+    let depth = g_index.ilog2() as usize;
+    if depth != KZG_COMMITMENT_INCLUSION_PROOF_DEPTH {
+        return Err(Error::OutOfBounds {
+            requested: KZG_COMMITMENT_INCLUSION_PROOF_DEPTH,
+            bound: depth,
+        })
+    }
     let subtree_index = get_subtree_index(g_index)?;
 
     let leaf = blob_sidecar.kzg_commitment.hash_tree_root()?;
     let branch = blob_sidecar.kzg_commitment_inclusion_proof.as_ref();
-    let root = blob_sidecar.signed_block_header.message.body_root;
+    is_valid_merkle_branch(leaf, branch, depth, subtree_index, header.body_root).map_err(Into::into)
+}
+
+pub fn compute_kzg_commitment_inclusion_proof<
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+>(
+    body: &BeaconBlockBody<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    >,
+    index: BlobIndex,
+) -> Result<Vec<Node>, Error> {
+    let commitment_count = body.blob_kzg_commitments.len();
+    if index >= commitment_count {
+        return Err(Error::OutOfBounds { requested: index, bound: commitment_count })
+    }
+    let path = &["blob_kzg_commitments".into(), index.into()];
+    let (proof, _) = body.prove(path)?;
+    Ok(proof.branch)
+}
+
+/// Build the sidecar for the blob at `index` of `signed_block`, ready to be gossiped, from the
+/// `blob` and its `kzg_proof` as returned by the execution layer.
+pub fn compute_blob_sidecar<
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const BYTES_PER_BLOB: usize,
+    const KZG_COMMITMENT_INCLUSION_PROOF_DEPTH: usize,
+>(
+    signed_block: &SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    >,
+    index: BlobIndex,
+    blob: Blob<BYTES_PER_BLOB>,
+    kzg_proof: KzgProof,
+) -> Result<BlobSidecar<BYTES_PER_BLOB, KZG_COMMITMENT_INCLUSION_PROOF_DEPTH>, Error> {
+    let block = &signed_block.message;
+    let proof = compute_kzg_commitment_inclusion_proof(&block.body, index)?;
+    let kzg_commitment_inclusion_proof = Vector::try_from(proof).map_err(|(_, err)| err)?;
+    let message = BeaconBlockHeader {
+        slot: block.slot,
+        proposer_index: block.proposer_index,
+        parent_root: block.parent_root,
+        state_root: block.state_root,
+        body_root: block.body.hash_tree_root()?,
+    };
+    Ok(BlobSidecar {
+        index,
+        blob,
+        kzg_commitment: block.body.blob_kzg_commitments[index].clone(),
+        kzg_proof,
+        signed_block_header: SignedBeaconBlockHeader {
+            message,
+            signature: signed_block.signature.clone(),
+        },
+        kzg_commitment_inclusion_proof,
+    })
+}
+
+/// Verify `proof` of the inclusion of `commitment` at `index` in the `blob_kzg_commitments`
+/// of the block body with root `body_root`. The expected depth of the proof is derived from
+/// the `BlockBody` type, which must match the fork (and preset) the proof was made for.
+pub fn verify_kzg_commitment_inclusion_proof<BlockBody: SimpleSerialize>(
+    commitment: &KzgCommitment,
+    proof: &[Node],
+    index: BlobIndex,
+    body_root: Root,
+) -> Result<(), Error> {
+    let path = &["blob_kzg_commitments".into(), index.into()];
+    let g_index = BlockBody::generalized_index(path)?;
+    let depth = g_index.ilog2() as usize;
+    if proof.len() != depth {
+        return Err(Error::OutOfBounds { requested: proof.len(), bound: depth })
+    }
+    let subtree_index = get_subtree_index(g_index)?;
 
-    is_valid_merkle_branch(leaf, branch, KZG_COMMITMENT_INCLUSION_PROOF_DEPTH, subtree_index, root)
-        .map_err(Into::into)
+    let leaf = commitment.hash_tree_root()?;
+    is_valid_merkle_branch(leaf, proof, depth, subtree_index, body_root).map_err(Into::into)
 }
 
+crate::ssz_size::impl_ssz_size!(BlobIdentifier { block_root: Root, index: BlobIndex });
+
+crate::ssz_size::impl_ssz_size!(BlobSidecar<
+    const BYTES_PER_BLOB,
+    const KZG_COMMITMENT_INCLUSION_PROOF_DEPTH,
+> {
+    index: BlobIndex,
+    blob: Blob<BYTES_PER_BLOB>,
+    kzg_commitment: KzgCommitment,
+    kzg_proof: KzgProof,
+    signed_block_header: SignedBeaconBlockHeader,
+    kzg_commitment_inclusion_proof: Vector<Node, KZG_COMMITMENT_INCLUSION_PROOF_DEPTH>,
+});
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -127,11 +305,107 @@ mod tests {
         )
         .is_ok());
 
-        assert!(spec::verify_blob_sidecar_inclusion_proof::<
-            { spec::KZG_COMMITMENT_INCLUSION_PROOF_DEPTH },
-            { spec::BYTES_PER_BLOB },
-            spec::BeaconBlockBody,
-        >(&blob_sidecar)
-        .is_ok());
+        assert!(spec::verify_blob_sidecar_inclusion_proof(&blob_sidecar, &context).is_ok());
+    }
+
+    #[test]
+    fn test_kzg_commitment_inclusion_proofs() {
+        use crate::{
+            deneb::{blob_sidecar::*, minimal},
+            electra::minimal as electra,
+            ssz::prelude::*,
+        };
+
+        let mut body = minimal::BeaconBlockBody::default();
+        let mut electra_body = electra::BeaconBlockBody::default();
+        for i in 0..minimal::MAX_BLOB_COMMITMENTS_PER_BLOCK {
+            let commitment = KzgCommitment::try_from(&[i as u8; 48][..]).unwrap();
+            body.blob_kzg_commitments.push(commitment.clone());
+            electra_body.blob_kzg_commitments.push(commitment);
+        }
+        let body_root = body.hash_tree_root().unwrap();
+        let electra_body_root = electra_body.hash_tree_root().unwrap();
+        assert_ne!(body_root, electra_body_root);
+
+        for (index, commitment) in body.blob_kzg_commitments.iter().enumerate() {
+            let proof = compute_kzg_commitment_inclusion_proof(&body, index).unwrap();
+            assert_eq!(proof.len(), minimal::KZG_COMMITMENT_INCLUSION_PROOF_DEPTH);
+            verify_kzg_commitment_inclusion_proof::<minimal::BeaconBlockBody>(
+                commitment, &proof, index, body_root,
+            )
+            .unwrap();
+            // proof does not hold against a different body
+            assert!(verify_kzg_commitment_inclusion_proof::<minimal::BeaconBlockBody>(
+                commitment,
+                &proof,
+                index,
+                electra_body_root,
+            )
+            .is_err());
+
+            let proof =
+                electra::compute_kzg_commitment_inclusion_proof(&electra_body, index).unwrap();
+            verify_kzg_commitment_inclusion_proof::<electra::BeaconBlockBody>(
+                commitment,
+                &proof,
+                index,
+                electra_body_root,
+            )
+            .unwrap();
+        }
+
+        let index = minimal::MAX_BLOB_COMMITMENTS_PER_BLOCK;
+        assert!(compute_kzg_commitment_inclusion_proof(&body, index).is_err());
+        let body = minimal::BeaconBlockBody::default();
+        assert!(compute_kzg_commitment_inclusion_proof(&body, 0).is_err());
+    }
+
+    #[test]
+    fn test_blob_sidecars() {
+        use crate::{
+            deneb::{blob_sidecar::*, minimal},
+            electra::minimal as electra,
+            Fork,
+        };
+
+        let mut context = Context::for_minimal();
+        context.deneb_fork_epoch = 0;
+        context.electra_fork_epoch = 1;
+        let electra_slot = context.slots_per_epoch;
+
+        let mut block = minimal::SignedBeaconBlock::default();
+        let mut electra_block = electra::SignedBeaconBlock::default();
+        electra_block.message.slot = electra_slot;
+        for i in 0..minimal::MAX_BLOB_COMMITMENTS_PER_BLOCK {
+            let commitment = KzgCommitment::try_from(&[i as u8; 48][..]).unwrap();
+            block.message.body.blob_kzg_commitments.push(commitment.clone());
+            electra_block.message.body.blob_kzg_commitments.push(commitment);
+        }
+
+        for index in 0..minimal::MAX_BLOB_COMMITMENTS_PER_BLOCK {
+            let path = &["blob_kzg_commitments".into(), index.into()];
+            let g_index = blob_kzg_commitment_gindex(index, Fork::Deneb, &context).unwrap();
+            assert_eq!(g_index, minimal::BeaconBlockBody::generalized_index(path).unwrap());
+            let g_index = blob_kzg_commitment_gindex(index, Fork::Electra, &context).unwrap();
+            assert_eq!(g_index, electra::BeaconBlockBody::generalized_index(path).unwrap());
+
+            let blob = minimal::Blob::default();
+            let mut sidecar: minimal::BlobSidecar =
+                compute_blob_sidecar(&block, index, blob.clone(), KzgProof::default()).unwrap();
+            verify_blob_sidecar_inclusion_proof(&sidecar, &context).unwrap();
+            // the header of the sidecar must commit to the body holding the commitment
+            sidecar.signed_block_header.message.slot = electra_slot;
+            sidecar.kzg_commitment = KzgCommitment::default();
+            assert!(verify_blob_sidecar_inclusion_proof(&sidecar, &context).is_err());
+
+            let sidecar: minimal::BlobSidecar =
+                electra::compute_blob_sidecar(&electra_block, index, blob, KzgProof::default())
+                    .unwrap();
+            verify_blob_sidecar_inclusion_proof(&sidecar, &context).unwrap();
+        }
+
+        let index = minimal::MAX_BLOB_COMMITMENTS_PER_BLOCK;
+        assert!(blob_kzg_commitment_gindex(index, Fork::Deneb, &context).is_err());
+        assert!(blob_kzg_commitment_gindex(0, Fork::Capella, &context).is_err());
     }
 }
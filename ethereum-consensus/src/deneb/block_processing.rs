@@ -1,15 +1,16 @@
 use crate::{
+    da_check::scheduled_max_blobs_per_block,
     deneb::{
         add_flag, compute_domain, compute_epoch_at_slot, compute_timestamp_at_slot,
         get_attestation_participation_flag_indices, get_attesting_indices, get_base_reward,
         get_beacon_committee, get_beacon_proposer_index, get_committee_count_per_slot,
         get_current_epoch, get_indexed_attestation, get_previous_epoch, get_randao_mix, has_flag,
         increase_balance, initiate_validator_exit, is_active_validator,
-        is_valid_indexed_attestation, kzg_commitment_to_versioned_hash, process_block_header,
-        process_eth1_data, process_operations, process_randao, process_sync_aggregate,
+        is_valid_indexed_attestation_with_validation, kzg_commitment_to_versioned_hash,
+        process_block_header, process_eth1_data, process_operations_with_validation,
+        process_randao_with_validation, process_sync_aggregate_with_validation,
         process_withdrawals, Attestation, BeaconBlock, BeaconBlockBody, BeaconState,
         ExecutionPayloadHeader, NewPayloadRequest, SignedVoluntaryExit, PARTICIPATION_FLAG_WEIGHTS,
-        PROPOSER_WEIGHT, WEIGHT_DENOMINATOR,
     },
     domains::DomainType,
     error::{
@@ -18,10 +19,12 @@ use crate::{
     },
     execution_engine::ExecutionEngine,
     primitives::FAR_FUTURE_EPOCH,
+    rewards::proposer_attestation_reward,
     signing::verify_signed_data,
     ssz::prelude::*,
-    state_transition::{Context, Result},
+    state_transition::{Context, Result, Validation},
 };
+use std::collections::HashSet;
 
 pub fn process_attestation<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
@@ -49,6 +52,37 @@ pub fn process_attestation<
     >,
     attestation: &Attestation<MAX_VALIDATORS_PER_COMMITTEE>,
     context: &Context,
+) -> Result<()> {
+    process_attestation_with_validation(state, attestation, Validation::Enabled, context)
+}
+
+pub fn process_attestation_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    attestation: &Attestation<MAX_VALIDATORS_PER_COMMITTEE>,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let data = &attestation.data;
     let is_previous = data.target.epoch == get_previous_epoch(state, context);
@@ -101,13 +135,21 @@ pub fn process_attestation<
     let inclusion_delay = state.slot - data.slot;
     let participation_flag_indices =
         get_attestation_participation_flag_indices(state, data, inclusion_delay, context)?;
-    is_valid_indexed_attestation(
-        state,
-        &get_indexed_attestation(state, attestation, context)?,
-        context,
-    )?;
-    let attesting_indices =
-        get_attesting_indices(state, data, &attestation.aggregation_bits, context)?;
+    let attesting_indices = if validation.is_assumed_finalized(data.target.epoch) {
+        committee
+            .iter()
+            .enumerate()
+            .filter_map(|(i, index)| attestation.aggregation_bits[i].then_some(*index))
+            .collect::<HashSet<_>>()
+    } else {
+        is_valid_indexed_attestation_with_validation(
+            state,
+            &get_indexed_attestation(state, attestation, context)?,
+            validation,
+            context,
+        )?;
+        get_attesting_indices(state, data, &attestation.aggregation_bits, context)?
+    };
     let mut proposer_reward_numerator = 0;
     for index in attesting_indices {
         for (flag_index, weight) in PARTICIPATION_FLAG_WEIGHTS.iter().enumerate() {
@@ -128,9 +170,7 @@ pub fn process_attestation<
             }
         }
     }
-    let proposer_reward_denominator =
-        (WEIGHT_DENOMINATOR - PROPOSER_WEIGHT) * WEIGHT_DENOMINATOR / PROPOSER_WEIGHT;
-    let proposer_reward = proposer_reward_numerator / proposer_reward_denominator;
+    let proposer_reward = proposer_attestation_reward(proposer_reward_numerator);
     increase_balance(state, get_beacon_proposer_index(state, context)?, proposer_reward);
     Ok(())
 }
@@ -224,11 +264,12 @@ pub fn process_execution_payload<
         ))
     }
 
-    if body.blob_kzg_commitments.len() > context.max_blobs_per_block {
+    let max_blobs_per_block = scheduled_max_blobs_per_block(current_epoch, context);
+    if body.blob_kzg_commitments.len() > max_blobs_per_block {
         return Err(invalid_operation_error(
             InvalidExecutionPayload::InvalidBlobCommitments {
                 provided: body.blob_kzg_commitments.len(),
-                limit: context.max_blobs_per_block,
+                limit: max_blobs_per_block,
             }
             .into(),
         ))
@@ -294,6 +335,42 @@ pub fn process_voluntary_exit<
     >,
     signed_voluntary_exit: &SignedVoluntaryExit,
     context: &Context,
+) -> Result<()> {
+    process_voluntary_exit_with_validation(
+        state,
+        signed_voluntary_exit,
+        Validation::Enabled,
+        context,
+    )
+}
+
+pub fn process_voluntary_exit_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    signed_voluntary_exit: &SignedVoluntaryExit,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let voluntary_exit = &signed_voluntary_exit.message;
     let validator = state.validators.get(voluntary_exit.validator_index).ok_or_else(|| {
@@ -330,19 +407,21 @@ pub fn process_voluntary_exit<
             },
         )))
     }
-    let domain = compute_domain(
-        DomainType::VoluntaryExit,
-        Some(context.capella_fork_version),
-        Some(state.genesis_validators_root),
-        context,
-    )?;
-    let public_key = &validator.public_key;
-    verify_signed_data(voluntary_exit, &signed_voluntary_exit.signature, public_key, domain)
-        .map_err(|_| {
-            invalid_operation_error(InvalidOperation::VoluntaryExit(
-                InvalidVoluntaryExit::InvalidSignature(signed_voluntary_exit.signature.clone()),
-            ))
-        })?;
+    if validation.verifies_signatures() {
+        let domain = compute_domain(
+            DomainType::VoluntaryExit,
+            Some(context.capella_fork_version),
+            Some(state.genesis_validators_root),
+            context,
+        )?;
+        let public_key = &validator.public_key;
+        verify_signed_data(voluntary_exit, &signed_voluntary_exit.signature, public_key, domain)
+            .map_err(|_| {
+                invalid_operation_error(InvalidOperation::VoluntaryExit(
+                    InvalidVoluntaryExit::InvalidSignature(signed_voluntary_exit.signature.clone()),
+                ))
+            })?;
+    }
     initiate_validator_exit(state, voluntary_exit.validator_index, context)?;
     Ok(())
 }
@@ -398,13 +477,69 @@ pub fn process_block<
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
     >,
     context: &Context,
+) -> Result<()> {
+    process_block_with_validation(state, block, Validation::Enabled, context)
+}
+
+pub fn process_block_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    block: &BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    >,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     process_block_header(state, block, context)?;
     process_withdrawals(state, &block.body.execution_payload, context)?;
     process_execution_payload(state, &block.body, context)?;
-    process_randao(state, &block.body, context)?;
+    process_randao_with_validation(state, &block.body, validation, context)?;
     process_eth1_data(state, &block.body, context);
-    process_operations(state, &block.body, context)?;
-    process_sync_aggregate(state, &block.body.sync_aggregate, context)?;
+    process_operations_with_validation(state, &block.body, validation, context)?;
+    process_sync_aggregate_with_validation(state, &block.body.sync_aggregate, validation, context)?;
     Ok(())
 }
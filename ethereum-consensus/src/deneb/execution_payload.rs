@@ -129,3 +129,52 @@ impl<
         })
     }
 }
+
+crate::ssz_size::impl_ssz_size!(ExecutionPayload<
+    const BYTES_PER_LOGS_BLOOM,
+    const MAX_EXTRA_DATA_BYTES,
+    const MAX_BYTES_PER_TRANSACTION,
+    const MAX_TRANSACTIONS_PER_PAYLOAD,
+    const MAX_WITHDRAWALS_PER_PAYLOAD,
+> {
+    parent_hash: Hash32,
+    fee_recipient: ExecutionAddress,
+    state_root: Bytes32,
+    receipts_root: Bytes32,
+    logs_bloom: ByteVector<BYTES_PER_LOGS_BLOOM>,
+    prev_randao: Bytes32,
+    block_number: u64,
+    gas_limit: u64,
+    gas_used: u64,
+    timestamp: u64,
+    extra_data: ByteList<MAX_EXTRA_DATA_BYTES>,
+    base_fee_per_gas: U256,
+    block_hash: Hash32,
+    transactions: List<Transaction<MAX_BYTES_PER_TRANSACTION>, MAX_TRANSACTIONS_PER_PAYLOAD>,
+    withdrawals: List<Withdrawal, MAX_WITHDRAWALS_PER_PAYLOAD>,
+    blob_gas_used: u64,
+    excess_blob_gas: u64,
+});
+
+crate::ssz_size::impl_ssz_size!(ExecutionPayloadHeader<
+    const BYTES_PER_LOGS_BLOOM,
+    const MAX_EXTRA_DATA_BYTES,
+> {
+    parent_hash: Hash32,
+    fee_recipient: ExecutionAddress,
+    state_root: Bytes32,
+    receipts_root: Bytes32,
+    logs_bloom: ByteVector<BYTES_PER_LOGS_BLOOM>,
+    prev_randao: Bytes32,
+    block_number: u64,
+    gas_limit: u64,
+    gas_used: u64,
+    timestamp: u64,
+    extra_data: ByteList<MAX_EXTRA_DATA_BYTES>,
+    base_fee_per_gas: U256,
+    block_hash: Hash32,
+    transactions_root: Root,
+    withdrawals_root: Root,
+    blob_gas_used: u64,
+    excess_blob_gas: u64,
+});
@@ -9,7 +9,10 @@ use crate::{
         polynomial_commitments::{KzgCommitment, VersionedHash},
         AttestationData, VERSIONED_HASH_VERSION_KZG,
     },
-    error::{invalid_operation_error, InvalidAttestation, InvalidOperation},
+    error::{
+        invalid_operation_error, InvalidAttestation, InvalidExecutionPayload, InvalidOperation,
+    },
+    primitives::saturating_usize,
     state_transition::{Context, Result},
 };
 use integer_sqrt::IntegerSquareRoot;
@@ -21,6 +24,39 @@ pub fn kzg_commitment_to_versioned_hash(kzg_commitment: &KzgCommitment) -> Versi
     result
 }
 
+/// Check that the versioned hashes of an execution payload are those of the `blob_kzg_commitments`
+/// of the block carrying it, in the same order.
+pub fn verify_blob_commitments_against_payload(
+    blob_kzg_commitments: &[KzgCommitment],
+    payload_versioned_hashes: &[VersionedHash],
+) -> Result<()> {
+    if blob_kzg_commitments.len() != payload_versioned_hashes.len() {
+        return Err(invalid_operation_error(
+            InvalidExecutionPayload::BlobCountMismatch {
+                commitments: blob_kzg_commitments.len(),
+                versioned_hashes: payload_versioned_hashes.len(),
+            }
+            .into(),
+        ))
+    }
+    for (index, (commitment, provided)) in
+        blob_kzg_commitments.iter().zip(payload_versioned_hashes).enumerate()
+    {
+        let expected = kzg_commitment_to_versioned_hash(commitment);
+        if *provided != expected {
+            return Err(invalid_operation_error(
+                InvalidExecutionPayload::VersionedHashMismatch {
+                    index,
+                    provided: provided.clone(),
+                    expected,
+                }
+                .into(),
+            ))
+        }
+    }
+    Ok(())
+}
+
 pub fn get_attestation_participation_flag_indices<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
@@ -110,6 +146,51 @@ pub fn get_validator_activation_churn_limit<
     >,
     context: &Context,
 ) -> usize {
-    let limit = context.max_per_epoch_activation_churn_limit as usize;
+    let limit = saturating_usize(context.max_per_epoch_activation_churn_limit);
     limit.min(get_validator_churn_limit(state, context))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::InvalidBlock, Error};
+
+    fn payload_error(result: Result<()>) -> InvalidExecutionPayload {
+        let Err(Error::InvalidBlock(err)) = result else { panic!("expected an invalid payload") };
+        let InvalidBlock::InvalidOperation(InvalidOperation::ExecutionPayload(err)) = *err else {
+            panic!("expected an invalid payload")
+        };
+        err
+    }
+
+    #[test]
+    fn test_blob_commitments_against_payload() {
+        let commitments = (1..=3u8)
+            .map(|i| KzgCommitment::try_from([i; 48].as_ref()).unwrap())
+            .collect::<Vec<_>>();
+        let versioned_hashes =
+            commitments.iter().map(kzg_commitment_to_versioned_hash).collect::<Vec<_>>();
+        assert!(versioned_hashes.iter().all(|hash| hash[0] == VERSIONED_HASH_VERSION_KZG));
+        verify_blob_commitments_against_payload(&commitments, &versioned_hashes).unwrap();
+        verify_blob_commitments_against_payload(&[], &[]).unwrap();
+
+        let reordered =
+            [versioned_hashes[0].clone(), versioned_hashes[2].clone(), versioned_hashes[1].clone()];
+        let result = verify_blob_commitments_against_payload(&commitments, &reordered);
+        assert!(matches!(
+            payload_error(result),
+            InvalidExecutionPayload::VersionedHashMismatch { index: 1, .. }
+        ));
+
+        let result = verify_blob_commitments_against_payload(&commitments, &versioned_hashes[..2]);
+        assert!(matches!(
+            payload_error(result),
+            InvalidExecutionPayload::BlobCountMismatch { commitments: 3, versioned_hashes: 2 }
+        ));
+        let result = verify_blob_commitments_against_payload(&[], &versioned_hashes);
+        assert!(matches!(
+            payload_error(result),
+            InvalidExecutionPayload::BlobCountMismatch { commitments: 0, versioned_hashes: 3 }
+        ));
+    }
+}
@@ -20,4 +20,7 @@ pub mod spec;
 
 pub use spec::*;
 
-pub use presets::{mainnet, minimal, Preset};
+pub use presets::{gnosis, mainnet, minimal, Preset};
+
+/// The fork implemented by this module.
+pub const FORK: crate::Fork = crate::Fork::Deneb;
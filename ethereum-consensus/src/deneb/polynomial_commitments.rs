@@ -1,9 +1,19 @@
 use crate::{
-    deneb::blob_sidecar::Blob, primitives::Bytes32, ssz::prelude::*, Error as ConsensusError,
+    deneb::{blob_sidecar::Blob, presets::TRUSTED_SETUP_JSON},
+    primitives::Bytes32,
+    ssz::prelude::*,
+    Error as ConsensusError,
 };
 pub use c_kzg::KzgSettings;
+use std::{
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
 use thiserror::Error;
 
+/// The library providing the KZG operations.
+pub const KZG_BACKEND: &str = "c-kzg";
+
 pub const BYTES_PER_FIELD_ELEMENT: usize = 32;
 pub const BYTES_PER_COMMITMENT: usize = 48;
 pub const BYTES_PER_PROOF: usize = 48;
@@ -17,10 +27,16 @@ pub type KzgProof = ByteVector<BYTES_PER_PROOF>;
 pub type G1Point = KzgCommitment;
 pub type G2Point = ByteVector<BYTES_PER_G2_POINT>;
 
+// Both the layout with only the Lagrange form of the G1 points and the newer layout that also
+// carries their monomial form are accepted; the backend only uses the Lagrange form.
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 struct TrustedSetup {
+    #[serde(alias = "setup_G1_lagrange")]
     g1_lagrange: Vec<G1Point>,
+    #[serde(alias = "setup_G2")]
     g2_monomial: Vec<G2Point>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    g1_monomial: Option<Vec<G1Point>>,
 }
 
 impl TrustedSetup {
@@ -39,8 +55,51 @@ impl TrustedSetup {
     }
 }
 
+/// The number of field elements in a blob supported by the KZG backend, which is fixed when the
+/// backend is compiled.
+pub const BACKEND_FIELD_ELEMENTS_PER_BLOB: usize = c_kzg::BYTES_PER_BLOB / BYTES_PER_FIELD_ELEMENT;
+
 pub fn kzg_settings_from_json(trusted_setup_json: &str) -> Result<KzgSettings, ConsensusError> {
-    let trusted_setup: TrustedSetup = serde_json::from_str(trusted_setup_json)?;
+    kzg_settings_from_json_for_preset(trusted_setup_json, BACKEND_FIELD_ELEMENTS_PER_BLOB)
+}
+
+/// Load a trusted setup in either JSON layout from the file at `path`, e.g. for a devnet with its
+/// own setup.
+pub fn kzg_settings_from_file(path: &Path) -> Result<KzgSettings, ConsensusError> {
+    let trusted_setup_json = std::fs::read_to_string(path)
+        .map_err(|source| Error::TrustedSetupFile { path: path.to_path_buf(), source })?;
+    kzg_settings_from_json(&trusted_setup_json)
+}
+
+/// The trusted setup of mainnet, which is loaded on first use and then shared by every caller.
+pub fn mainnet_kzg_settings() -> &'static KzgSettings {
+    static KZG_SETTINGS: OnceLock<KzgSettings> = OnceLock::new();
+    KZG_SETTINGS.get_or_init(|| {
+        kzg_settings_from_json(TRUSTED_SETUP_JSON).expect("trusted setup of mainnet is valid")
+    })
+}
+
+/// Load a trusted setup for a preset with `field_elements_per_blob` field elements per blob,
+/// checking that the setup is sized for the preset (and that the backend supports it).
+pub fn kzg_settings_from_json_for_preset(
+    trusted_setup_json: &str,
+    field_elements_per_blob: usize,
+) -> Result<KzgSettings, ConsensusError> {
+    let trusted_setup: TrustedSetup =
+        serde_json::from_str(trusted_setup_json).map_err(Error::MalformedTrustedSetup)?;
+
+    let setup_size = trusted_setup.g1_lagrange.len();
+    if setup_size != field_elements_per_blob {
+        return Err(Error::TrustedSetupSize { expected: field_elements_per_blob, setup_size }.into())
+    }
+    if let Some(g1_monomial) = &trusted_setup.g1_monomial {
+        if g1_monomial.len() != setup_size {
+            return Err(
+                Error::MonomialSetupSize { setup_size, monomial_size: g1_monomial.len() }.into()
+            )
+        }
+    }
+    check_blob_size(field_elements_per_blob * BYTES_PER_FIELD_ELEMENT)?;
 
     KzgSettings::load_trusted_setup(&trusted_setup.to_g1_bytes(), &trusted_setup.to_g2_bytes())
         .map_err(|err| ConsensusError::from(Error::from(err)))
@@ -52,6 +111,35 @@ pub enum Error {
     CKzg(#[from] c_kzg::Error),
     #[error("proof verification failed")]
     InvalidProof,
+    #[error("trusted setup has {setup_size} G1 points but the preset expects {expected}")]
+    TrustedSetupSize { expected: usize, setup_size: usize },
+    #[error("trusted setup has {setup_size} G1 points in Lagrange form but {monomial_size} in monomial form")]
+    MonomialSetupSize { setup_size: usize, monomial_size: usize },
+    #[error("malformed trusted setup: {0}")]
+    MalformedTrustedSetup(#[source] serde_json::Error),
+    #[error("could not read trusted setup from {path:?}: {source}")]
+    TrustedSetupFile { path: PathBuf, source: std::io::Error },
+    #[error("blobs of {found} bytes are not supported by the KZG backend, which expects {expected} bytes")]
+    UnsupportedBlobSize { expected: usize, found: usize },
+    #[error("batch has {blobs} blobs, {commitments} commitments and {proofs} proofs")]
+    BatchLengthMismatch { blobs: usize, commitments: usize, proofs: usize },
+}
+
+fn check_blob_size(bytes_per_blob: usize) -> Result<(), Error> {
+    if bytes_per_blob != c_kzg::BYTES_PER_BLOB {
+        return Err(Error::UnsupportedBlobSize {
+            expected: c_kzg::BYTES_PER_BLOB,
+            found: bytes_per_blob,
+        })
+    }
+    Ok(())
+}
+
+fn to_c_kzg_blob<const BYTES_PER_BLOB: usize>(
+    blob: &Blob<BYTES_PER_BLOB>,
+) -> Result<c_kzg::Blob, Error> {
+    check_blob_size(BYTES_PER_BLOB)?;
+    c_kzg::Blob::from_bytes(blob.as_ref()).map_err(Into::into)
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -64,7 +152,7 @@ pub fn blob_to_kzg_commitment<const BYTES_PER_BLOB: usize>(
     blob: &Blob<BYTES_PER_BLOB>,
     kzg_settings: &KzgSettings,
 ) -> Result<KzgCommitment, Error> {
-    let blob = c_kzg::Blob::from_bytes(blob.as_ref())?;
+    let blob = to_c_kzg_blob(blob)?;
 
     let commitment = c_kzg::KzgCommitment::blob_to_kzg_commitment(&blob, kzg_settings)?;
     let inner = KzgCommitment::try_from(commitment.to_bytes().as_slice()).expect("correct size");
@@ -76,7 +164,7 @@ pub fn compute_kzg_proof<const BYTES_PER_BLOB: usize>(
     evaluation_point: &FieldElement,
     kzg_settings: &KzgSettings,
 ) -> Result<ProofAndEvaluation, Error> {
-    let blob = c_kzg::Blob::from_bytes(blob.as_ref())?;
+    let blob = to_c_kzg_blob(blob)?;
     let evaluation_point = c_kzg::Bytes32::from_bytes(evaluation_point.as_ref())?;
 
     let (proof, evaluation) =
@@ -93,7 +181,7 @@ pub fn compute_blob_kzg_proof<const BYTES_PER_BLOB: usize>(
     commitment: &KzgCommitment,
     kzg_settings: &KzgSettings,
 ) -> Result<KzgProof, Error> {
-    let blob = c_kzg::Blob::from_bytes(blob.as_ref())?;
+    let blob = to_c_kzg_blob(blob)?;
     let commitment = c_kzg::Bytes48::from_bytes(commitment.as_ref()).expect("correct size");
 
     let proof = c_kzg::KzgProof::compute_blob_kzg_proof(&blob, &commitment, kzg_settings)?;
@@ -130,7 +218,7 @@ pub fn verify_blob_kzg_proof<const BYTES_PER_BLOB: usize>(
     proof: &KzgProof,
     kzg_settings: &KzgSettings,
 ) -> Result<(), Error> {
-    let blob = c_kzg::Blob::from_bytes(blob.as_ref())?;
+    let blob = to_c_kzg_blob(blob)?;
     let commitment = c_kzg::Bytes48::from_bytes(commitment.as_ref()).unwrap();
     let proof = c_kzg::Bytes48::from_bytes(proof.as_ref()).unwrap();
 
@@ -145,12 +233,20 @@ pub fn verify_blob_kzg_proof_batch<const BYTES_PER_BLOB: usize>(
     proofs: &[KzgProof],
     kzg_settings: &KzgSettings,
 ) -> Result<(), Error> {
+    if blobs.len() != commitments.len() || blobs.len() != proofs.len() {
+        return Err(Error::BatchLengthMismatch {
+            blobs: blobs.len(),
+            commitments: commitments.len(),
+            proofs: proofs.len(),
+        })
+    }
+
     let mut c_kzg_blobs = Vec::with_capacity(blobs.len());
     let mut c_kzg_commitments = Vec::with_capacity(commitments.len());
     let mut c_kzg_proofs = Vec::with_capacity(proofs.len());
 
     for blob in blobs {
-        let blob = c_kzg::Blob::from_bytes(blob.as_ref())?;
+        let blob = to_c_kzg_blob(blob)?;
         c_kzg_blobs.push(blob);
     }
     for commitment in commitments {
@@ -171,3 +267,119 @@ pub fn verify_blob_kzg_proof_batch<const BYTES_PER_BLOB: usize>(
 
     res.then_some(()).ok_or(Error::InvalidProof)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deneb::{mainnet, minimal};
+
+    #[test]
+    fn test_trusted_setup_size_matches_preset() {
+        for field_elements_per_blob in
+            [mainnet::FIELD_ELEMENTS_PER_BLOB, minimal::FIELD_ELEMENTS_PER_BLOB]
+        {
+            kzg_settings_from_json_for_preset(TRUSTED_SETUP_JSON, field_elements_per_blob).unwrap();
+        }
+
+        // e.g. a preset with 4 field elements per blob, as minimal had in early Deneb releases
+        let result = kzg_settings_from_json_for_preset(TRUSTED_SETUP_JSON, 4);
+        assert!(matches!(
+            result,
+            Err(ConsensusError::PolynomialCommitment(Error::TrustedSetupSize {
+                expected: 4,
+                setup_size: 4096,
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_trusted_setup_layouts() {
+        let dir = std::env::temp_dir().join(format!("trusted-setup-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let path = dir.join("lagrange.json");
+        std::fs::write(&path, TRUSTED_SETUP_JSON).unwrap();
+        kzg_settings_from_file(&path).unwrap();
+
+        let mut trusted_setup: TrustedSetup = serde_json::from_str(TRUSTED_SETUP_JSON).unwrap();
+        trusted_setup.g1_monomial = Some(trusted_setup.g1_lagrange.clone());
+        let path = dir.join("monomial.json");
+        std::fs::write(&path, serde_json::to_string(&trusted_setup).unwrap()).unwrap();
+        kzg_settings_from_file(&path).unwrap();
+
+        trusted_setup.g1_monomial.as_mut().unwrap().pop();
+        let result = kzg_settings_from_json(&serde_json::to_string(&trusted_setup).unwrap());
+        assert!(matches!(
+            result,
+            Err(ConsensusError::PolynomialCommitment(Error::MonomialSetupSize {
+                setup_size: 4096,
+                monomial_size: 4095,
+            }))
+        ));
+
+        let result = kzg_settings_from_json(r#"{"g1_lagrange": ["0x00"]}"#);
+        assert!(matches!(
+            result,
+            Err(ConsensusError::PolynomialCommitment(Error::MalformedTrustedSetup(..)))
+        ));
+        let result = kzg_settings_from_file(&dir.join("missing.json"));
+        assert!(matches!(
+            result,
+            Err(ConsensusError::PolynomialCommitment(Error::TrustedSetupFile { .. }))
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(std::ptr::eq(mainnet_kzg_settings(), mainnet_kzg_settings()));
+    }
+
+    #[test]
+    fn test_unsupported_blob_size() {
+        let kzg_settings = kzg_settings_from_json(TRUSTED_SETUP_JSON).unwrap();
+        let blob = Blob::<{ 4 * BYTES_PER_FIELD_ELEMENT }>::default();
+        let result = blob_to_kzg_commitment(&blob, &kzg_settings);
+        assert!(matches!(
+            result,
+            Err(Error::UnsupportedBlobSize { expected, found: 128 }) if expected == c_kzg::BYTES_PER_BLOB
+        ));
+    }
+
+    #[test]
+    fn test_verify_blob_kzg_proof_batch() {
+        let kzg_settings = kzg_settings_from_json(TRUSTED_SETUP_JSON).unwrap();
+        let blobs = (0..3u8)
+            .map(|i| {
+                // keep each field element below the modulus
+                let mut bytes = vec![0u8; mainnet::BYTES_PER_BLOB];
+                bytes[BYTES_PER_FIELD_ELEMENT - 1] = i;
+                Blob::<{ mainnet::BYTES_PER_BLOB }>::try_from(bytes.as_slice()).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let commitments = blobs
+            .iter()
+            .map(|blob| blob_to_kzg_commitment(blob, &kzg_settings).unwrap())
+            .collect::<Vec<_>>();
+        let mut proofs = blobs
+            .iter()
+            .zip(&commitments)
+            .map(|(blob, commitment)| {
+                compute_blob_kzg_proof(blob, commitment, &kzg_settings).unwrap()
+            })
+            .collect::<Vec<_>>();
+        verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs, &kzg_settings).unwrap();
+        verify_blob_kzg_proof_batch::<{ mainnet::BYTES_PER_BLOB }>(&[], &[], &[], &kzg_settings)
+            .unwrap();
+
+        let result = verify_blob_kzg_proof_batch(&blobs, &commitments[..2], &proofs, &kzg_settings);
+        assert!(matches!(
+            result,
+            Err(Error::BatchLengthMismatch { blobs: 3, commitments: 2, proofs: 3 })
+        ));
+
+        // one invalid proof among many fails the batch as a single proof would
+        proofs.swap(0, 1);
+        let result = verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs, &kzg_settings);
+        assert!(matches!(result, Err(Error::InvalidProof)));
+        let result = verify_blob_kzg_proof(&blobs[0], &commitments[0], &proofs[0], &kzg_settings);
+        assert!(matches!(result, Err(Error::InvalidProof)));
+    }
+}
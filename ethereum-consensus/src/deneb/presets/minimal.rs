@@ -168,3 +168,5 @@ pub type LightClientOptimisticUpdate = spec::LightClientOptimisticUpdate<
     BYTES_PER_LOGS_BLOOM,
     MAX_EXTRA_DATA_BYTES,
 >;
+pub type LightClientStore =
+    spec::LightClientStore<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>;
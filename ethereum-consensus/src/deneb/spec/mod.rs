@@ -9,14 +9,18 @@ pub use crate::{
         },
         helpers::{add_flag, has_flag},
         light_client::{
+            compute_sync_committee_period_at_slot, is_valid_light_client_branch,
             CURRENT_SYNC_COMMITTEE_INDEX, CURRENT_SYNC_COMMITTEE_INDEX_FLOOR_LOG_2,
             FINALIZED_ROOT_INDEX, FINALIZED_ROOT_INDEX_FLOOR_LOG_2, NEXT_SYNC_COMMITTEE_INDEX,
             NEXT_SYNC_COMMITTEE_INDEX_FLOOR_LOG_2,
         },
         sync::{SyncAggregate, SyncCommittee},
         validator::{
-            ContributionAndProof, SignedContributionAndProof, SyncAggregatorSelectionData,
-            SyncCommitteeContribution, SyncCommitteeMessage,
+            is_sync_committee_aggregator, sign_contribution_and_proof, sign_sync_committee_message,
+            sign_sync_selection_proof, verify_contribution_and_proof_signature,
+            verify_sync_committee_message, verify_sync_selection_proof, ContributionAndProof,
+            SignedContributionAndProof, SyncAggregatorSelectionData, SyncCommitteeContribution,
+            SyncCommitteeMessage,
         },
     },
     bellatrix::{execution_payload::Transaction, fork_choice::PowBlock},
@@ -31,16 +35,20 @@ pub use crate::{
     },
     deneb::{
         beacon_block::{BeaconBlock, BeaconBlockBody, SignedBeaconBlock},
-        beacon_state::BeaconState,
+        beacon_state::{compute_state_field_roots, BeaconState},
         blinded_beacon_block::{
             BlindedBeaconBlock, BlindedBeaconBlockBody, SignedBlindedBeaconBlock,
         },
         blob_sidecar::{
-            verify_blob_sidecar_inclusion_proof, Blob, BlobIdentifier, BlobSidecar, BlobsBundle,
+            blob_kzg_commitment_gindex, compute_blob_sidecar,
+            compute_kzg_commitment_inclusion_proof, verify_blob_sidecar_inclusion_proof,
+            verify_kzg_commitment_inclusion_proof, Blob, BlobIdentifier, BlobSidecar, BlobsBundle,
             VERSIONED_HASH_VERSION_KZG,
         },
         block_processing::{
-            process_attestation, process_block, process_execution_payload, process_voluntary_exit,
+            process_attestation, process_attestation_with_validation, process_block,
+            process_block_with_validation, process_execution_payload, process_voluntary_exit,
+            process_voluntary_exit_with_validation,
         },
         epoch_processing::process_registry_updates,
         execution_engine::NewPayloadRequest,
@@ -49,45 +57,63 @@ pub use crate::{
         genesis::initialize_beacon_state_from_eth1,
         helpers::{
             get_attestation_participation_flag_indices, get_validator_activation_churn_limit,
-            kzg_commitment_to_versioned_hash,
+            kzg_commitment_to_versioned_hash, verify_blob_commitments_against_payload,
         },
         light_client::{
-            LightClientBootstrap, LightClientFinalityUpdate, LightClientHeader,
-            LightClientOptimisticUpdate, LightClientUpdate,
+            get_lc_execution_root, is_valid_light_client_header, upgrade_lc_bootstrap_to_deneb,
+            upgrade_lc_finality_update_to_deneb, upgrade_lc_header_to_deneb,
+            upgrade_lc_optimistic_update_to_deneb, upgrade_lc_store_to_deneb,
+            upgrade_lc_update_to_deneb, LightClientBootstrap, LightClientFinalityUpdate,
+            LightClientHeader, LightClientOptimisticUpdate, LightClientStore, LightClientUpdate,
         },
     },
     error::*,
     phase0::{
         beacon_block::{BeaconBlockHeader, SignedBeaconBlockHeader},
         beacon_state::{Fork, ForkData, HistoricalBatch, HistoricalSummary},
-        block_processing::{get_validator_from_deposit, xor},
+        block_processing::{
+            count_eth1_data_votes, get_validator_from_deposit, xor, Eth1VotingPeriodProgress,
+        },
         constants::{
             BASE_REWARDS_PER_EPOCH, DEPOSIT_CONTRACT_TREE_DEPTH, DEPOSIT_DATA_LIST_BOUND,
             JUSTIFICATION_BITS_LENGTH,
         },
         helpers::{
-            compute_activation_exit_epoch, compute_committee, compute_domain,
-            compute_epoch_at_slot, compute_fork_data_root, compute_fork_digest,
+            compute_activation_exit_epoch, compute_committee, compute_committee_position,
+            compute_domain, compute_epoch_at_slot, compute_fork_data_root, compute_fork_digest,
             compute_shuffled_index, compute_shuffled_indices, compute_start_slot_at_epoch,
-            is_active_validator, is_eligible_for_activation_queue, is_slashable_attestation_data,
-            is_slashable_validator,
+            compute_unshuffled_index, is_active_validator, is_eligible_for_activation_queue,
+            is_slashable_attestation_data, is_slashable_validator,
         },
         operations::{
             Attestation, AttestationData, AttesterSlashing, Checkpoint, Deposit, DepositData,
             DepositMessage, Eth1Data, IndexedAttestation, PendingAttestation, ProposerSlashing,
             SignedVoluntaryExit, VoluntaryExit,
         },
-        validator::{AggregateAndProof, Eth1Block, SignedAggregateAndProof, Validator},
+        slot_processing::{
+            merkleize_field_roots, STATE_BLOCK_ROOTS_FIELD_INDEX,
+            STATE_LATEST_BLOCK_HEADER_FIELD_INDEX, STATE_SLOT_FIELD_INDEX,
+            STATE_STATE_ROOTS_FIELD_INDEX,
+        },
+        validator::{
+            compute_domain_at_slot, is_aggregator, sign_aggregate_and_proof, sign_attestation_data,
+            sign_slot_selection_proof, verify_aggregate_and_proof_signature,
+            verify_attestation_data_signature, verify_slot_selection_proof, AggregateAndProof,
+            Eth1Block, ForkInfo, SignedAggregateAndProof, Validator,
+        },
     },
     primitives::*,
     signing::*,
-    state_transition::{Context, Result, Validation},
+    state_transition::{Context, Result, StateRootCache, Validation},
 };
 use crate::{
     crypto::{eth_aggregate_public_keys, eth_fast_aggregate_verify, fast_aggregate_verify, hash},
+    rewards::{
+        base_reward_per_increment, flag_reward, phase0_proposer_reward, proposer_sync_reward,
+        sync_participant_reward,
+    },
     ssz::prelude::*,
 };
-use integer_sqrt::IntegerSquareRoot;
 use std::{
     collections::{HashMap, HashSet},
     iter::zip,
@@ -119,6 +145,42 @@ pub fn process_bls_to_execution_change<
     >,
     signed_address_change: &SignedBlsToExecutionChange,
     context: &Context,
+) -> Result<()> {
+    process_bls_to_execution_change_with_validation(
+        state,
+        signed_address_change,
+        Validation::Enabled,
+        context,
+    )
+}
+
+pub fn process_bls_to_execution_change_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    signed_address_change: &SignedBlsToExecutionChange,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let address_change = &signed_address_change.message;
     let signature = &signed_address_change.signature;
@@ -129,6 +191,14 @@ pub fn process_bls_to_execution_change<
     }
     let withdrawal_credentials =
         &mut state.validators[address_change.validator_index].withdrawal_credentials;
+    if matches!(
+        withdrawal_credentials[0],
+        ETH1_ADDRESS_WITHDRAWAL_PREFIX | COMPOUNDING_WITHDRAWAL_PREFIX
+    ) {
+        return Err(invalid_operation_error(InvalidOperation::BlsToExecutionChange(
+            InvalidBlsToExecutionChange::AlreadyRotated(address_change.validator_index),
+        )));
+    }
     if withdrawal_credentials[0] != BLS_WITHDRAWAL_PREFIX {
         return Err(invalid_operation_error(InvalidOperation::BlsToExecutionChange(
             InvalidBlsToExecutionChange::WithdrawalCredentialsPrefix(withdrawal_credentials[0]),
@@ -140,13 +210,15 @@ pub fn process_bls_to_execution_change<
             InvalidBlsToExecutionChange::PublicKeyMismatch(public_key.clone()),
         )));
     }
-    let domain = compute_domain(
-        DomainType::BlsToExecutionChange,
-        None,
-        Some(state.genesis_validators_root),
-        context,
-    )?;
-    verify_signed_data(address_change, signature, public_key, domain)?;
+    if validation.verifies_signatures() {
+        let domain = compute_domain(
+            DomainType::BlsToExecutionChange,
+            None,
+            Some(state.genesis_validators_root),
+            context,
+        )?;
+        verify_signed_data(address_change, signature, public_key, domain)?;
+    }
     withdrawal_credentials[0] = ETH1_ADDRESS_WITHDRAWAL_PREFIX;
     withdrawal_credentials[1..12].fill(0);
     withdrawal_credentials[12..].copy_from_slice(address_change.to_execution_address.as_ref());
@@ -203,10 +275,66 @@ pub fn process_operations<
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
     >,
     context: &Context,
+) -> Result<()> {
+    process_operations_with_validation(state, body, Validation::Enabled, context)
+}
+
+pub fn process_operations_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    body: &BeaconBlockBody<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    >,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let expected_deposit_count = usize::min(
         context.max_deposits,
-        (state.eth1_data.deposit_count - state.eth1_deposit_index) as usize,
+        saturating_usize(state.eth1_data.deposit_count - state.eth1_deposit_index),
     );
     if body.deposits.len() != expected_deposit_count {
         return Err(invalid_operation_error(InvalidOperation::Deposit(
@@ -216,18 +344,46 @@ pub fn process_operations<
             },
         )));
     }
-    body.proposer_slashings
-        .iter()
-        .try_for_each(|op| process_proposer_slashing(state, op, context))?;
-    body.attester_slashings
-        .iter()
-        .try_for_each(|op| process_attester_slashing(state, op, context))?;
-    body.attestations.iter().try_for_each(|op| process_attestation(state, op, context))?;
-    body.deposits.iter().try_for_each(|op| process_deposit(state, op, context))?;
-    body.voluntary_exits.iter().try_for_each(|op| process_voluntary_exit(state, op, context))?;
-    body.bls_to_execution_changes
-        .iter()
-        .try_for_each(|op| process_bls_to_execution_change(state, op, context))?;
+    traced!(
+        "proposer_slashings",
+        body.proposer_slashings.iter().try_for_each(
+            |op| process_proposer_slashing_with_validation(state, op, validation, context)
+        ),
+        count = body.proposer_slashings.len()
+    )?;
+    traced!(
+        "attester_slashings",
+        body.attester_slashings.iter().try_for_each(
+            |op| process_attester_slashing_with_validation(state, op, validation, context)
+        ),
+        count = body.attester_slashings.len()
+    )?;
+    traced!(
+        "attestations",
+        body.attestations
+            .iter()
+            .try_for_each(|op| process_attestation_with_validation(state, op, validation, context)),
+        count = body.attestations.len()
+    )?;
+    traced!(
+        "deposits",
+        body.deposits.iter().try_for_each(|op| process_deposit(state, op, context)),
+        count = body.deposits.len()
+    )?;
+    traced!(
+        "voluntary_exits",
+        body.voluntary_exits.iter().try_for_each(|op| process_voluntary_exit_with_validation(
+            state, op, validation, context
+        )),
+        count = body.voluntary_exits.len()
+    )?;
+    traced!(
+        "bls_to_execution_changes",
+        body.bls_to_execution_changes.iter().try_for_each(|op| {
+            process_bls_to_execution_change_with_validation(state, op, validation, context)
+        }),
+        count = body.bls_to_execution_changes.len()
+    )?;
     Ok(())
 }
 pub fn process_withdrawals<
@@ -423,43 +579,70 @@ pub fn process_sync_aggregate<
     sync_aggregate: &SyncAggregate<SYNC_COMMITTEE_SIZE>,
     context: &Context,
 ) -> Result<()> {
-    let committee_public_keys = &state.current_sync_committee.public_keys;
-    let participant_public_keys =
-        zip(committee_public_keys.iter(), sync_aggregate.sync_committee_bits.iter())
-            .filter_map(|(public_key, bit)| if *bit { Some(public_key) } else { None })
-            .collect::<Vec<_>>();
-    let previous_slot = u64::max(state.slot, 1) - 1;
-    let domain = get_domain(
-        state,
-        DomainType::SyncCommittee,
-        Some(compute_epoch_at_slot(previous_slot, context)),
-        context,
-    )?;
-    let root_at_slot = *get_block_root_at_slot(state, previous_slot)?;
-    let signing_root = compute_signing_root(&root_at_slot, domain)?;
-    if eth_fast_aggregate_verify(
-        participant_public_keys.as_slice(),
-        signing_root.as_ref(),
-        &sync_aggregate.sync_committee_signature,
-    )
-    .is_err()
-    {
-        return Err(invalid_operation_error(InvalidOperation::SyncAggregate(
-            InvalidSyncAggregate::InvalidSignature {
-                signature: sync_aggregate.sync_committee_signature.clone(),
-                root: signing_root,
-            },
-        )));
+    process_sync_aggregate_with_validation(state, sync_aggregate, Validation::Enabled, context)
+}
+
+pub fn process_sync_aggregate_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    sync_aggregate: &SyncAggregate<SYNC_COMMITTEE_SIZE>,
+    validation: Validation,
+    context: &Context,
+) -> Result<()> {
+    if validation.verifies_signatures() {
+        let committee_public_keys = &state.current_sync_committee.public_keys;
+        let participant_public_keys =
+            zip(committee_public_keys.iter(), sync_aggregate.sync_committee_bits.iter())
+                .filter_map(|(public_key, bit)| if *bit { Some(public_key) } else { None })
+                .collect::<Vec<_>>();
+        let previous_slot = u64::max(state.slot, 1) - 1;
+        let domain = get_domain(
+            state,
+            DomainType::SyncCommittee,
+            Some(compute_epoch_at_slot(previous_slot, context)),
+            context,
+        )?;
+        let root_at_slot = *get_block_root_at_slot(state, previous_slot)?;
+        let signing_root = compute_signing_root(&root_at_slot, domain)?;
+        if eth_fast_aggregate_verify(
+            participant_public_keys.as_slice(),
+            signing_root.as_ref(),
+            &sync_aggregate.sync_committee_signature,
+        )
+        .is_err()
+        {
+            return Err(invalid_operation_error(InvalidOperation::SyncAggregate(
+                InvalidSyncAggregate::InvalidSignature {
+                    signature: sync_aggregate.sync_committee_signature.clone(),
+                    root: signing_root,
+                },
+            )));
+        }
     }
-    let total_active_increments =
-        get_total_active_balance(state, context)? / context.effective_balance_increment;
-    let total_base_rewards =
-        get_base_reward_per_increment(state, context)? * total_active_increments;
-    let max_participant_rewards =
-        total_base_rewards * SYNC_REWARD_WEIGHT / WEIGHT_DENOMINATOR / context.slots_per_epoch;
-    let participant_reward = max_participant_rewards / context.sync_committee_size as u64;
-    let proposer_reward =
-        participant_reward * PROPOSER_WEIGHT / (WEIGHT_DENOMINATOR - PROPOSER_WEIGHT);
+    let participant_reward =
+        sync_participant_reward(get_total_active_balance(state, context)?, context);
+    let proposer_reward = proposer_sync_reward(participant_reward);
     let all_public_keys = state
         .validators
         .iter()
@@ -509,6 +692,42 @@ pub fn process_proposer_slashing<
     >,
     proposer_slashing: &ProposerSlashing,
     context: &Context,
+) -> Result<()> {
+    process_proposer_slashing_with_validation(
+        state,
+        proposer_slashing,
+        Validation::Enabled,
+        context,
+    )
+}
+
+pub fn process_proposer_slashing_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    proposer_slashing: &ProposerSlashing,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let header_1 = &proposer_slashing.signed_header_1.message;
     let header_2 = &proposer_slashing.signed_header_2.message;
@@ -541,16 +760,25 @@ pub fn process_proposer_slashing<
             InvalidProposerSlashing::ProposerIsNotSlashable(header_1.proposer_index),
         )));
     }
-    let epoch = compute_epoch_at_slot(header_1.slot, context);
-    let domain = get_domain(state, DomainType::BeaconProposer, Some(epoch), context)?;
-    for signed_header in [&proposer_slashing.signed_header_1, &proposer_slashing.signed_header_2] {
-        let public_key = &proposer.public_key;
-        if verify_signed_data(&signed_header.message, &signed_header.signature, public_key, domain)
-            .is_err()
+    if validation.verifies_signatures() {
+        let epoch = compute_epoch_at_slot(header_1.slot, context);
+        let domain = get_domain(state, DomainType::BeaconProposer, Some(epoch), context)?;
+        for signed_header in
+            [&proposer_slashing.signed_header_1, &proposer_slashing.signed_header_2]
         {
-            return Err(invalid_operation_error(InvalidOperation::ProposerSlashing(
-                InvalidProposerSlashing::InvalidSignature(signed_header.signature.clone()),
-            )));
+            let public_key = &proposer.public_key;
+            if verify_signed_data(
+                &signed_header.message,
+                &signed_header.signature,
+                public_key,
+                domain,
+            )
+            .is_err()
+            {
+                return Err(invalid_operation_error(InvalidOperation::ProposerSlashing(
+                    InvalidProposerSlashing::InvalidSignature(signed_header.signature.clone()),
+                )));
+            }
         }
     }
     slash_validator(state, proposer_index, None, context)
@@ -581,6 +809,42 @@ pub fn process_attester_slashing<
     >,
     attester_slashing: &AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>,
     context: &Context,
+) -> Result<()> {
+    process_attester_slashing_with_validation(
+        state,
+        attester_slashing,
+        Validation::Enabled,
+        context,
+    )
+}
+
+pub fn process_attester_slashing_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    attester_slashing: &AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let attestation_1 = &attester_slashing.attestation_1;
     let attestation_2 = &attester_slashing.attestation_2;
@@ -592,8 +856,8 @@ pub fn process_attester_slashing<
             ),
         )));
     }
-    is_valid_indexed_attestation(state, attestation_1, context)?;
-    is_valid_indexed_attestation(state, attestation_2, context)?;
+    is_valid_indexed_attestation_with_validation(state, attestation_1, validation, context)?;
+    is_valid_indexed_attestation_with_validation(state, attestation_2, validation, context)?;
     let indices_1: HashSet<ValidatorIndex> =
         HashSet::from_iter(attestation_1.attesting_indices.iter().cloned());
     let indices_2 = HashSet::from_iter(attestation_2.attesting_indices.iter().cloned());
@@ -698,7 +962,7 @@ pub fn process_deposit<
     let leaf = deposit.data.hash_tree_root()?;
     let branch = &deposit.proof;
     let depth = DEPOSIT_CONTRACT_TREE_DEPTH + 1;
-    let index = state.eth1_deposit_index as usize;
+    let index = checked_index(state.eth1_deposit_index)?;
     let root = state.eth1_data.deposit_root;
     if is_valid_merkle_branch(leaf, branch, depth, index, root).is_err() {
         return Err(invalid_operation_error(InvalidOperation::Deposit(
@@ -855,19 +1119,10 @@ pub fn process_randao<
     >,
     context: &Context,
 ) -> Result<()> {
-    let epoch = get_current_epoch(state, context);
-    let proposer_index = get_beacon_proposer_index(state, context)?;
-    let proposer = &state.validators[proposer_index];
-    let domain = get_domain(state, DomainType::Randao, Some(epoch), context)?;
-    if verify_signed_data(&epoch, &body.randao_reveal, &proposer.public_key, domain).is_err() {
-        return Err(invalid_operation_error(InvalidOperation::Randao(body.randao_reveal.clone())));
-    }
-    let mix = xor(get_randao_mix(state, epoch), &hash(body.randao_reveal.as_ref()));
-    let mix_index = epoch % context.epochs_per_historical_vector;
-    state.randao_mixes[mix_index as usize] = mix;
-    Ok(())
+    process_randao_with_validation(state, body, Validation::Enabled, context)
 }
-pub fn process_eth1_data<
+
+pub fn process_randao_with_validation<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
     const ETH1_DATA_VOTES_BOUND: usize,
@@ -917,16 +1172,26 @@ pub fn process_eth1_data<
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
     >,
+    validation: Validation,
     context: &Context,
-) {
-    state.eth1_data_votes.push(body.eth1_data.clone());
-    let votes_count =
-        state.eth1_data_votes.iter().filter(|&vote| *vote == body.eth1_data).count() as u64;
-    if votes_count * 2 > context.epochs_per_eth1_voting_period * context.slots_per_epoch {
-        state.eth1_data = body.eth1_data.clone();
+) -> Result<()> {
+    let epoch = get_current_epoch(state, context);
+    if validation.verifies_signatures() {
+        let proposer_index = get_beacon_proposer_index(state, context)?;
+        let proposer = &state.validators[proposer_index];
+        let domain = get_domain(state, DomainType::Randao, Some(epoch), context)?;
+        if verify_signed_data(&epoch, &body.randao_reveal, &proposer.public_key, domain).is_err() {
+            return Err(invalid_operation_error(InvalidOperation::Randao(
+                body.randao_reveal.clone(),
+            )));
+        }
     }
+    let mix = xor(get_randao_mix(state, epoch), &hash(body.randao_reveal.as_ref()));
+    let mix_index = epoch % context.epochs_per_historical_vector;
+    state.randao_mixes[mix_index as usize] = mix;
+    Ok(())
 }
-pub fn process_historical_summaries_update<
+pub fn process_eth1_data<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
     const ETH1_DATA_VOTES_BOUND: usize,
@@ -937,13 +1202,197 @@ pub fn process_historical_summaries_update<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
->(
-    state: &mut BeaconState<
-        SLOTS_PER_HISTORICAL_ROOT,
-        HISTORICAL_ROOTS_LIMIT,
-        ETH1_DATA_VOTES_BOUND,
-        VALIDATOR_REGISTRY_LIMIT,
-        EPOCHS_PER_HISTORICAL_VECTOR,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    body: &BeaconBlockBody<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    >,
+    context: &Context,
+) {
+    state.eth1_data_votes.push(body.eth1_data.clone());
+    let votes_count =
+        state.eth1_data_votes.iter().filter(|&vote| *vote == body.eth1_data).count() as u64;
+    if votes_count * 2 > context.epochs_per_eth1_voting_period * context.slots_per_epoch {
+        state.eth1_data = body.eth1_data.clone();
+    }
+}
+/// The `Eth1Data` holding a majority of the votes in the current voting period, if any.
+///
+/// This is the value `process_eth1_data` adopts as `state.eth1_data` once it is reached, so
+/// it can be recomputed from the votes alone.
+pub fn eth1_data_vote_winner<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    context: &Context,
+) -> Option<Eth1Data> {
+    let period_slots = context.epochs_per_eth1_voting_period * context.slots_per_epoch;
+    count_eth1_data_votes(&state.eth1_data_votes)
+        .into_iter()
+        .find(|(_, count)| count * 2 > period_slots)
+        .map(|(vote, _)| vote.clone())
+}
+pub fn eth1_voting_period_progress<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    context: &Context,
+) -> Eth1VotingPeriodProgress {
+    let period_slots = context.epochs_per_eth1_voting_period * context.slots_per_epoch;
+    let leading_vote = count_eth1_data_votes(&state.eth1_data_votes)
+        .into_iter()
+        .reduce(|leader, next| if next.1 > leader.1 { next } else { leader })
+        .map(|(vote, count)| (vote.clone(), count));
+    Eth1VotingPeriodProgress {
+        start_slot: state.slot - state.slot % period_slots,
+        period_slots,
+        votes: state.eth1_data_votes.len() as u64,
+        majority_threshold: period_slots / 2 + 1,
+        leading_vote,
+    }
+}
+/// Choose the `Eth1Data` to vote for in a block built on `state`, following `get_eth1_vote` of
+/// the honest validator guide.
+///
+/// `candidates` are the votes the proposer considers valid for the current voting period,
+/// ordered from oldest to newest. The candidate already holding the most votes in the period is
+/// chosen (the oldest on ties), otherwise the newest candidate. Without any candidates, the
+/// proposer sticks with the current `state.eth1_data`.
+pub fn choose_eth1_vote<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    candidates: &[Eth1Data],
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+) -> Eth1Data {
+    let tally = count_eth1_data_votes(&state.eth1_data_votes);
+    let votes_for = |candidate: &Eth1Data| {
+        tally.iter().find(|(vote, _)| *vote == candidate).map_or(0, |(_, count)| *count)
+    };
+    let mut choice = None;
+    for candidate in candidates {
+        let count = votes_for(candidate);
+        if count > 0 && choice.map_or(true, |(_, most)| count > most) {
+            choice = Some((candidate, count));
+        }
+    }
+    choice
+        .map(|(candidate, _)| candidate)
+        .or_else(|| candidates.last())
+        .unwrap_or(&state.eth1_data)
+        .clone()
+}
+pub fn process_historical_summaries_update<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
         EPOCHS_PER_SLASHINGS_VECTOR,
         MAX_VALIDATORS_PER_COMMITTEE,
         SYNC_COMMITTEE_SIZE,
@@ -988,18 +1437,25 @@ pub fn process_epoch<
     >,
     context: &Context,
 ) -> Result<()> {
-    process_justification_and_finalization(state, context)?;
-    process_inactivity_updates(state, context)?;
-    process_rewards_and_penalties(state, context)?;
-    process_registry_updates(state, context)?;
-    process_slashings(state, context)?;
-    process_eth1_data_reset(state, context);
-    process_effective_balance_updates(state, context);
-    process_slashings_reset(state, context);
-    process_randao_mixes_reset(state, context);
-    process_historical_summaries_update(state, context)?;
-    process_participation_flag_updates(state)?;
-    process_sync_committee_updates(state, context)?;
+    trace_span!("process_epoch", epoch = state.slot / context.slots_per_epoch);
+    traced!(
+        "process_justification_and_finalization",
+        process_justification_and_finalization(state, context)
+    )?;
+    traced!("process_inactivity_updates", process_inactivity_updates(state, context))?;
+    traced!("process_rewards_and_penalties", process_rewards_and_penalties(state, context))?;
+    traced!("process_registry_updates", process_registry_updates(state, context))?;
+    traced!("process_slashings", process_slashings(state, context))?;
+    traced!("process_eth1_data_reset", process_eth1_data_reset(state, context));
+    traced!("process_effective_balance_updates", process_effective_balance_updates(state, context));
+    traced!("process_slashings_reset", process_slashings_reset(state, context));
+    traced!("process_randao_mixes_reset", process_randao_mixes_reset(state, context));
+    traced!(
+        "process_historical_summaries_update",
+        process_historical_summaries_update(state, context)
+    )?;
+    traced!("process_participation_flag_updates", process_participation_flag_updates(state))?;
+    traced!("process_sync_committee_updates", process_sync_committee_updates(state, context))?;
     Ok(())
 }
 pub fn process_slashings<
@@ -1558,7 +2014,8 @@ pub fn get_proposer_reward<
     attesting_index: ValidatorIndex,
     context: &Context,
 ) -> Result<Gwei> {
-    Ok(get_base_reward(state, attesting_index, context)? / context.proposer_reward_quotient)
+    let base_reward = get_base_reward(state, attesting_index, context)?;
+    Ok(phase0_proposer_reward(base_reward, context))
 }
 pub fn get_finality_delay<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
@@ -1784,7 +2241,7 @@ pub fn slash_validator<
         state.validators[slashed_index].withdrawable_epoch,
         epoch + context.epochs_per_slashings_vector,
     );
-    let slashings_index = epoch as usize % EPOCHS_PER_SLASHINGS_VECTOR;
+    let slashings_index = (epoch % EPOCHS_PER_SLASHINGS_VECTOR as u64) as usize;
     state.slashings[slashings_index] += state.validators[slashed_index].effective_balance;
     decrease_balance(
         state,
@@ -1967,8 +2424,7 @@ pub fn compute_timestamp_at_slot<
     context: &Context,
 ) -> Result<u64> {
     let slots_since_genesis = slot.checked_sub(GENESIS_SLOT).ok_or(Error::Underflow)?;
-    let timestamp = state.genesis_time + slots_since_genesis * context.seconds_per_slot;
-    Ok(timestamp)
+    context.timestamp_at_slot(slots_since_genesis, state.genesis_time)
 }
 pub fn get_next_sync_committee_indices<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
@@ -2000,6 +2456,9 @@ pub fn get_next_sync_committee_indices<
     let max_random_byte = u8::MAX as u64;
     let active_validator_indices = get_active_validator_indices(state, epoch);
     let active_validator_count = active_validator_indices.len();
+    if active_validator_count == 0 {
+        return Err(Error::NoActiveValidators(epoch))
+    }
     let seed = get_seed(state, epoch, DomainType::SyncCommittee, context);
     let mut i: usize = 0;
     let mut sync_committee_indices = vec![];
@@ -2084,8 +2543,8 @@ pub fn get_base_reward_per_increment<
     >,
     context: &Context,
 ) -> Result<Gwei> {
-    Ok(context.effective_balance_increment * context.base_reward_factor /
-        get_total_active_balance(state, context)?.integer_sqrt())
+    let total_active_balance = get_total_active_balance(state, context)?;
+    Ok(base_reward_per_increment(total_active_balance, context))
 }
 pub fn get_unslashed_participating_indices<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
@@ -2184,8 +2643,12 @@ pub fn get_flag_index_deltas<
         let base_reward = get_base_reward(state, index, context)?;
         if unslashed_participating_indices.contains(&index) {
             if not_leaking {
-                let reward_numerator = base_reward * weight * unslashed_participating_increments;
-                rewards[index] += reward_numerator / (active_increments * WEIGHT_DENOMINATOR);
+                rewards[index] += flag_reward(
+                    base_reward,
+                    weight,
+                    unslashed_participating_increments,
+                    active_increments,
+                );
             }
         } else if flag_index != TIMELY_HEAD_FLAG_INDEX {
             penalties[index] += base_reward * weight / WEIGHT_DENOMINATOR;
@@ -2248,6 +2711,42 @@ pub fn is_valid_indexed_attestation<
     >,
     indexed_attestation: &IndexedAttestation<MAX_VALIDATORS_PER_COMMITTEE>,
     context: &Context,
+) -> Result<()> {
+    is_valid_indexed_attestation_with_validation(
+        state,
+        indexed_attestation,
+        Validation::Enabled,
+        context,
+    )
+}
+
+pub fn is_valid_indexed_attestation_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    indexed_attestation: &IndexedAttestation<MAX_VALIDATORS_PER_COMMITTEE>,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let attesting_indices = &indexed_attestation.attesting_indices;
     if attesting_indices.is_empty() {
@@ -2282,6 +2781,10 @@ pub fn is_valid_indexed_attestation<
         })?;
         public_keys.push(public_key);
     }
+    if !validation.verifies_signatures() {
+        return Ok(())
+    }
+
     let domain = get_domain(
         state,
         DomainType::BeaconAttester,
@@ -2608,11 +3111,11 @@ pub fn get_block_root_at_slot<
     if slot >= state.slot || state.slot > (slot + SLOTS_PER_HISTORICAL_ROOT as Slot) {
         return Err(Error::SlotOutOfRange {
             requested: slot,
-            lower_bound: state.slot - 1,
-            upper_bound: state.slot + SLOTS_PER_HISTORICAL_ROOT as Slot,
+            lower_bound: state.slot.saturating_sub(SLOTS_PER_HISTORICAL_ROOT as Slot),
+            upper_bound: state.slot.saturating_sub(1),
         });
     }
-    Ok(&state.block_roots[slot as usize % SLOTS_PER_HISTORICAL_ROOT])
+    Ok(&state.block_roots[(slot % SLOTS_PER_HISTORICAL_ROOT as u64) as usize])
 }
 pub fn get_randao_mix<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
@@ -2640,7 +3143,7 @@ pub fn get_randao_mix<
     >,
     epoch: Epoch,
 ) -> &Bytes32 {
-    let epoch = epoch as usize % EPOCHS_PER_HISTORICAL_VECTOR;
+    let epoch = (epoch % EPOCHS_PER_HISTORICAL_VECTOR as u64) as usize;
     &state.randao_mixes[epoch]
 }
 pub fn get_active_validator_indices<
@@ -2705,10 +3208,10 @@ pub fn get_validator_churn_limit<
 ) -> usize {
     let active_validator_indices =
         get_active_validator_indices(state, get_current_epoch(state, context));
-    u64::max(
+    saturating_usize(u64::max(
         context.min_per_epoch_churn_limit,
         active_validator_indices.len() as u64 / context.churn_limit_quotient,
-    ) as usize
+    ))
 }
 pub fn get_seed<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
@@ -2738,7 +3241,9 @@ pub fn get_seed<
     domain_type: DomainType,
     context: &Context,
 ) -> Bytes32 {
-    let mix_epoch = epoch + (context.epochs_per_historical_vector - context.min_seed_lookahead) - 1;
+    let mix_epoch = epoch % context.epochs_per_historical_vector +
+        (context.epochs_per_historical_vector - context.min_seed_lookahead) -
+        1;
     let mix = get_randao_mix(state, mix_epoch);
     let mut input = [0u8; 44];
     input[..4].copy_from_slice(&domain_type.as_bytes());
@@ -2812,14 +3317,28 @@ pub fn get_beacon_committee<
     context: &Context,
 ) -> Result<Vec<ValidatorIndex>> {
     let epoch = compute_epoch_at_slot(slot, context);
-    let committees_per_slot = get_committee_count_per_slot(state, epoch, context);
     let indices = get_active_validator_indices(state, epoch);
     let seed = get_seed(state, epoch, DomainType::BeaconAttester, context);
+    if let Some(committee_cache) = &context.committee_cache {
+        let shuffling = committee_cache.shuffling(epoch, &seed, indices, context);
+        return shuffling.beacon_committee(slot, index, context).map(|committee| committee.to_vec())
+    }
+    let committees_per_slot = get_committee_count_per_slot(state, epoch, context);
+    if index >= committees_per_slot {
+        return Err(Error::OutOfBounds { requested: index, bound: committees_per_slot })
+    }
     let index = (slot % context.slots_per_epoch) * committees_per_slot as u64 + index as u64;
     let count = committees_per_slot as u64 * context.slots_per_epoch;
     compute_committee(&indices, &seed, index as usize, count as usize, context)
 }
-pub fn get_beacon_proposer_index<
+/// A digest of the committee shuffling for `epoch`, so that two nodes can check they agree on
+/// committees without exchanging states.
+///
+/// The preimage is the `DomainType::BeaconAttester` seed for `epoch` (32 bytes) followed by the
+/// active validator indices for `epoch` in shuffled order, each as a little-endian `u64`. The
+/// digest is the SHA-256 hash of the preimage. This layout is stable across versions of this
+/// crate.
+pub fn committee_fingerprint<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
     const ETH1_DATA_VOTES_BOUND: usize,
@@ -2843,18 +3362,26 @@ pub fn get_beacon_proposer_index<
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
     >,
+    epoch: Epoch,
     context: &Context,
-) -> Result<ValidatorIndex> {
-    let epoch = get_current_epoch(state, context);
-    let mut input = [0u8; 40];
-    input[..32]
-        .copy_from_slice(get_seed(state, epoch, DomainType::BeaconProposer, context).as_ref());
-    input[32..40].copy_from_slice(&state.slot.to_le_bytes());
-    let seed = hash(input);
+) -> Result<Hash32> {
     let indices = get_active_validator_indices(state, epoch);
-    compute_proposer_index(state, &indices, &seed, context)
+    let seed = get_seed(state, epoch, DomainType::BeaconAttester, context);
+    let shuffled_indices = compute_committee(&indices, &seed, 0, 1, context)?;
+    let mut preimage = Vec::with_capacity(32 + 8 * shuffled_indices.len());
+    preimage.extend_from_slice(seed.as_ref());
+    for index in shuffled_indices {
+        preimage.extend_from_slice(&(index as u64).to_le_bytes());
+    }
+    Ok(hash(preimage))
 }
-pub fn get_total_balance<
+/// A digest of the committees for `slot`, like [`committee_fingerprint`] but localized to a slot.
+///
+/// The preimage is the `DomainType::BeaconAttester` seed for the epoch of `slot` (32 bytes), then
+/// `slot` and the number of committees per slot as little-endian `u64`s, then the members of each
+/// committee of `slot` in committee index order, each as a little-endian `u64`. The digest is the
+/// SHA-256 hash of the preimage. This layout is stable across versions of this crate.
+pub fn committee_fingerprint_at_slot<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
     const ETH1_DATA_VOTES_BOUND: usize,
@@ -2878,16 +3405,33 @@ pub fn get_total_balance<
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
     >,
-    indices: &HashSet<ValidatorIndex>,
+    slot: Slot,
     context: &Context,
-) -> Result<Gwei> {
-    let total_balance = indices
-        .iter()
-        .try_fold(Gwei::default(), |acc, i| acc.checked_add(state.validators[*i].effective_balance))
-        .ok_or(Error::Overflow)?;
-    Ok(u64::max(total_balance, context.effective_balance_increment))
+) -> Result<Hash32> {
+    let epoch = compute_epoch_at_slot(slot, context);
+    let committees_per_slot = get_committee_count_per_slot(state, epoch, context);
+    let indices = get_active_validator_indices(state, epoch);
+    let seed = get_seed(state, epoch, DomainType::BeaconAttester, context);
+    let count = committees_per_slot * context.slots_per_epoch as usize;
+    let mut preimage =
+        Vec::with_capacity(48 + 8 * indices.len() / context.slots_per_epoch as usize);
+    preimage.extend_from_slice(seed.as_ref());
+    preimage.extend_from_slice(&slot.to_le_bytes());
+    preimage.extend_from_slice(&(committees_per_slot as u64).to_le_bytes());
+    for index in 0..committees_per_slot {
+        let index = (slot % context.slots_per_epoch) as usize * committees_per_slot + index;
+        for member in compute_committee(&indices, &seed, index, count, context)? {
+            preimage.extend_from_slice(&(member as u64).to_le_bytes());
+        }
+    }
+    Ok(hash(preimage))
 }
-pub fn get_total_active_balance<
+/// The slot, committee index and position within that committee of the attester duty of
+/// `validator_index` in `epoch`, or `None` if the validator is not active in `epoch`.
+///
+/// Only the shuffled position of `validator_index` is computed so this is cheaper than
+/// enumerating the committees of `epoch` with [`get_beacon_committee`].
+pub fn committee_position_of<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
     const ETH1_DATA_VOTES_BOUND: usize,
@@ -2911,12 +3455,18 @@ pub fn get_total_active_balance<
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
     >,
+    epoch: Epoch,
+    validator_index: ValidatorIndex,
     context: &Context,
-) -> Result<Gwei> {
-    let indices = get_active_validator_indices(state, get_current_epoch(state, context));
-    get_total_balance(state, &HashSet::from_iter(indices), context)
+) -> Option<(Slot, CommitteeIndex, usize)> {
+    let indices = get_active_validator_indices(state, epoch);
+    let index = indices.binary_search(&validator_index).ok()?;
+    let seed = get_seed(state, epoch, DomainType::BeaconAttester, context);
+    let position = compute_unshuffled_index(index, indices.len(), &seed, context).ok()?;
+    let committees_per_slot = get_committee_count_per_slot(state, epoch, context);
+    Some(compute_committee_position(position, indices.len(), committees_per_slot, epoch, context))
 }
-pub fn get_indexed_attestation<
+pub fn get_beacon_proposer_index<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
     const ETH1_DATA_VOTES_BOUND: usize,
@@ -2940,22 +3490,21 @@ pub fn get_indexed_attestation<
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
     >,
-    attestation: &Attestation<MAX_VALIDATORS_PER_COMMITTEE>,
     context: &Context,
-) -> Result<IndexedAttestation<MAX_VALIDATORS_PER_COMMITTEE>> {
-    let bits = &attestation.aggregation_bits;
-    let mut attesting_indices = get_attesting_indices(state, &attestation.data, bits, context)?
-        .into_iter()
-        .collect::<Vec<_>>();
-    attesting_indices.sort_unstable();
-    let attesting_indices = attesting_indices.try_into().map_err(|(_, err)| err)?;
-    Ok(IndexedAttestation {
-        attesting_indices,
-        data: attestation.data.clone(),
-        signature: attestation.signature.clone(),
-    })
+) -> Result<ValidatorIndex> {
+    let epoch = get_current_epoch(state, context);
+    let mut input = [0u8; 40];
+    input[..32]
+        .copy_from_slice(get_seed(state, epoch, DomainType::BeaconProposer, context).as_ref());
+    input[32..40].copy_from_slice(&state.slot.to_le_bytes());
+    let seed = hash(input);
+    let indices = get_active_validator_indices(state, epoch);
+    if indices.is_empty() {
+        return Err(Error::NoActiveValidators(epoch))
+    }
+    compute_proposer_index(state, &indices, &seed, context)
 }
-pub fn get_attesting_indices<
+pub fn get_total_balance<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
     const ETH1_DATA_VOTES_BOUND: usize,
@@ -2979,11 +3528,112 @@ pub fn get_attesting_indices<
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
     >,
-    data: &AttestationData,
-    bits: &Bitlist<MAX_VALIDATORS_PER_COMMITTEE>,
+    indices: &HashSet<ValidatorIndex>,
     context: &Context,
-) -> Result<HashSet<ValidatorIndex>> {
-    let committee = get_beacon_committee(state, data.slot, data.index, context)?;
+) -> Result<Gwei> {
+    let total_balance = indices
+        .iter()
+        .try_fold(Gwei::default(), |acc, i| acc.checked_add(state.validators[*i].effective_balance))
+        .ok_or(Error::Overflow)?;
+    Ok(u64::max(total_balance, context.effective_balance_increment))
+}
+pub fn get_total_active_balance<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    context: &Context,
+) -> Result<Gwei> {
+    let indices = get_active_validator_indices(state, get_current_epoch(state, context));
+    get_total_balance(state, &HashSet::from_iter(indices), context)
+}
+pub fn get_indexed_attestation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    attestation: &Attestation<MAX_VALIDATORS_PER_COMMITTEE>,
+    context: &Context,
+) -> Result<IndexedAttestation<MAX_VALIDATORS_PER_COMMITTEE>> {
+    let bits = &attestation.aggregation_bits;
+    let mut attesting_indices = get_attesting_indices(state, &attestation.data, bits, context)?
+        .into_iter()
+        .collect::<Vec<_>>();
+    attesting_indices.sort_unstable();
+    let attesting_indices = attesting_indices.try_into().map_err(|(_, err)| err)?;
+    Ok(IndexedAttestation {
+        attesting_indices,
+        data: attestation.data.clone(),
+        signature: attestation.signature.clone(),
+    })
+}
+pub fn get_attesting_indices<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    data: &AttestationData,
+    bits: &Bitlist<MAX_VALIDATORS_PER_COMMITTEE>,
+    context: &Context,
+) -> Result<HashSet<ValidatorIndex>> {
+    let committee = get_beacon_committee(state, data.slot, data.index, context)?;
     if bits.len() != committee.len() {
         return Err(invalid_operation_error(InvalidOperation::Attestation(
             InvalidAttestation::Bitfield { expected_length: committee.len(), length: bits.len() },
@@ -3173,19 +3823,546 @@ pub fn process_slots<
     >,
     slot: Slot,
     context: &Context,
+) -> Result<()> {
+    let mut state_root_cache = StateRootCache::default();
+    process_slots_with_field_roots(
+        state,
+        slot,
+        |state| state_root_cache.field_roots(state),
+        context,
+    )
+}
+pub fn process_slots_with_field_roots<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    slot: Slot,
+    mut field_roots_of: impl FnMut(
+        &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+    ) -> Result<Vec<Root>>,
+    context: &Context,
 ) -> Result<()> {
     if state.slot >= slot {
-        return Err(Error::TransitionToPreviousSlot { requested: slot, current: state.slot });
+        return Err(Error::TransitionToPreviousSlot { requested: slot, current: state.slot })
     }
+    trace_span!("process_slots", from = state.slot, to = slot);
+    let mut field_roots = traced!("hash_tree_root", field_roots_of(state))?;
     while state.slot < slot {
-        process_slot(state, context)?;
-        if (state.slot + 1) % context.slots_per_epoch == 0 {
+        process_slot_with_field_roots(state, &mut field_roots, context)?;
+        let is_epoch_transition = (state.slot + 1) % context.slots_per_epoch == 0;
+        if is_epoch_transition {
             process_epoch(state, context)?;
         }
         state.slot += 1;
+        if is_epoch_transition {
+            if state.slot < slot {
+                field_roots = traced!("hash_tree_root", field_roots_of(state))?;
+            }
+        } else {
+            field_roots[STATE_SLOT_FIELD_INDEX] = state.slot.hash_tree_root()?;
+        }
+    }
+    Ok(())
+}
+pub fn is_sync_committee_update<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    update: &LightClientUpdate<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+) -> bool {
+    update.next_sync_committee_branch.iter().any(|node| !node.is_zero())
+}
+pub fn is_finality_update<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    update: &LightClientUpdate<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+) -> bool {
+    update.finality_branch.iter().any(|node| !node.is_zero())
+}
+pub fn is_better_update<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    new_update: &LightClientUpdate<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    old_update: &LightClientUpdate<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    context: &Context,
+) -> bool {
+    // Compare supermajority (> 2/3) sync committee participation
+    let max_active_participants = SYNC_COMMITTEE_SIZE;
+    let new_num_active_participants =
+        new_update.sync_aggregate.sync_committee_bits.iter().filter(|bit| **bit).count();
+    let old_num_active_participants =
+        old_update.sync_aggregate.sync_committee_bits.iter().filter(|bit| **bit).count();
+    let new_has_supermajority = new_num_active_participants * 3 >= max_active_participants * 2;
+    let old_has_supermajority = old_num_active_participants * 3 >= max_active_participants * 2;
+    if new_has_supermajority != old_has_supermajority {
+        return new_has_supermajority
+    }
+    if !new_has_supermajority && new_num_active_participants != old_num_active_participants {
+        return new_num_active_participants > old_num_active_participants
+    }
+
+    // Compare presence of relevant sync committee
+    let has_relevant_sync_committee = |update: &LightClientUpdate<
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >| {
+        is_sync_committee_update(update) &&
+            compute_sync_committee_period_at_slot(update.attested_header.beacon.slot, context) ==
+                compute_sync_committee_period_at_slot(update.signature_slot, context)
+    };
+    let new_has_relevant_sync_committee = has_relevant_sync_committee(new_update);
+    let old_has_relevant_sync_committee = has_relevant_sync_committee(old_update);
+    if new_has_relevant_sync_committee != old_has_relevant_sync_committee {
+        return new_has_relevant_sync_committee
+    }
+
+    // Compare indication of any finality
+    let new_has_finality = is_finality_update(new_update);
+    let old_has_finality = is_finality_update(old_update);
+    if new_has_finality != old_has_finality {
+        return new_has_finality
+    }
+
+    // Compare sync committee finality
+    if new_has_finality {
+        let has_sync_committee_finality = |update: &LightClientUpdate<
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >| {
+            compute_sync_committee_period_at_slot(update.finalized_header.beacon.slot, context) ==
+                compute_sync_committee_period_at_slot(
+                    update.attested_header.beacon.slot,
+                    context,
+                )
+        };
+        let new_has_sync_committee_finality = has_sync_committee_finality(new_update);
+        let old_has_sync_committee_finality = has_sync_committee_finality(old_update);
+        if new_has_sync_committee_finality != old_has_sync_committee_finality {
+            return new_has_sync_committee_finality
+        }
+    }
+
+    // Tiebreaker 1: Sync committee participation beyond supermajority
+    if new_num_active_participants != old_num_active_participants {
+        return new_num_active_participants > old_num_active_participants
+    }
+
+    // Tiebreaker 2: Prefer older data (fewer changes to best)
+    if new_update.attested_header.beacon.slot != old_update.attested_header.beacon.slot {
+        return new_update.attested_header.beacon.slot < old_update.attested_header.beacon.slot
+    }
+    new_update.signature_slot < old_update.signature_slot
+}
+pub fn is_next_sync_committee_known<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    store: &LightClientStore<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+) -> bool {
+    store.next_sync_committee != SyncCommittee::default()
+}
+pub fn get_safety_threshold<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    store: &LightClientStore<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+) -> usize {
+    store.previous_max_active_participants.max(store.current_max_active_participants) / 2
+}
+pub fn initialize_light_client_store<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    trusted_block_root: Root,
+    bootstrap: &LightClientBootstrap<
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    context: &Context,
+) -> Result<LightClientStore<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>> {
+    if !is_valid_light_client_header(&bootstrap.header, context) {
+        return Err(LightClientError::InvalidHeader(bootstrap.header.beacon.slot).into())
+    }
+    let root = bootstrap.header.beacon.hash_tree_root()?;
+    if root != trusted_block_root {
+        return Err(LightClientError::UntrustedBootstrap {
+            provided: root,
+            expected: trusted_block_root,
+        }
+        .into())
+    }
+    if !is_valid_light_client_branch(
+        bootstrap.current_sync_committee.hash_tree_root()?,
+        &bootstrap.current_sync_committee_branch,
+        CURRENT_SYNC_COMMITTEE_INDEX,
+        bootstrap.header.beacon.state_root,
+    ) {
+        return Err(LightClientError::InvalidCurrentSyncCommitteeBranch.into())
+    }
+    Ok(LightClientStore {
+        finalized_header: bootstrap.header.clone(),
+        current_sync_committee: bootstrap.current_sync_committee.clone(),
+        next_sync_committee: SyncCommittee::default(),
+        best_valid_update: None,
+        optimistic_header: bootstrap.header.clone(),
+        previous_max_active_participants: 0,
+        current_max_active_participants: 0,
+    })
+}
+pub fn validate_light_client_update<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    store: &LightClientStore<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    update: &LightClientUpdate<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    current_slot: Slot,
+    genesis_validators_root: Root,
+    context: &Context,
+) -> Result<()> {
+    // Verify sync committee has sufficient participants
+    let sync_aggregate = &update.sync_aggregate;
+    let participants = sync_aggregate.sync_committee_bits.iter().filter(|bit| **bit).count();
+    if participants < context.min_sync_committee_participants {
+        return Err(LightClientError::InsufficientParticipation {
+            participants,
+            minimum: context.min_sync_committee_participants,
+        }
+        .into())
+    }
+
+    // Verify update does not skip a sync committee period
+    if !is_valid_light_client_header(&update.attested_header, context) {
+        return Err(LightClientError::InvalidHeader(update.attested_header.beacon.slot).into())
+    }
+    let update_attested_slot = update.attested_header.beacon.slot;
+    let update_finalized_slot = update.finalized_header.beacon.slot;
+    if !(current_slot >= update.signature_slot &&
+        update.signature_slot > update_attested_slot &&
+        update_attested_slot >= update_finalized_slot)
+    {
+        return Err(LightClientError::InvalidSlots {
+            current_slot,
+            signature_slot: update.signature_slot,
+            attested_slot: update_attested_slot,
+            finalized_slot: update_finalized_slot,
+        }
+        .into())
+    }
+    let store_period =
+        compute_sync_committee_period_at_slot(store.finalized_header.beacon.slot, context);
+    let update_signature_period =
+        compute_sync_committee_period_at_slot(update.signature_slot, context);
+    let is_valid_signature_period = if is_next_sync_committee_known(store) {
+        update_signature_period == store_period || update_signature_period == store_period + 1
+    } else {
+        update_signature_period == store_period
+    };
+    if !is_valid_signature_period {
+        return Err(LightClientError::UnexpectedSignaturePeriod {
+            signature_period: update_signature_period,
+            store_period,
+        }
+        .into())
+    }
+
+    // Verify update is relevant
+    let update_attested_period =
+        compute_sync_committee_period_at_slot(update_attested_slot, context);
+    let update_has_next_sync_committee = !is_next_sync_committee_known(store) &&
+        is_sync_committee_update(update) &&
+        update_attested_period == store_period;
+    if !(update_attested_slot > store.finalized_header.beacon.slot ||
+        update_has_next_sync_committee)
+    {
+        let attested_slot = update_attested_slot;
+        return Err(LightClientError::IrrelevantUpdate { attested_slot }.into())
+    }
+
+    // Verify that the `finality_branch`, if present, confirms `finalized_header`
+    // to match the finalized checkpoint root saved in the state of `attested_header`.
+    // Note that the genesis finalized checkpoint root is represented as a zero hash.
+    if !is_finality_update(update) {
+        if update.finalized_header != LightClientHeader::default() {
+            return Err(LightClientError::UnexpectedFinalizedHeader.into())
+        }
+    } else {
+        let finalized_root = if update_finalized_slot == GENESIS_SLOT {
+            if update.finalized_header != LightClientHeader::default() {
+                return Err(LightClientError::UnexpectedFinalizedHeader.into())
+            }
+            Root::default()
+        } else {
+            if !is_valid_light_client_header(&update.finalized_header, context) {
+                return Err(LightClientError::InvalidHeader(update_finalized_slot).into())
+            }
+            update.finalized_header.beacon.hash_tree_root()?
+        };
+        if !is_valid_light_client_branch(
+            finalized_root,
+            &update.finality_branch,
+            FINALIZED_ROOT_INDEX,
+            update.attested_header.beacon.state_root,
+        ) {
+            return Err(LightClientError::InvalidFinalityBranch.into())
+        }
+    }
+
+    // Verify that the `next_sync_committee`, if present, actually is the next sync committee
+    // saved in the state of the `attested_header`
+    if !is_sync_committee_update(update) {
+        if update.next_sync_committee != SyncCommittee::default() {
+            return Err(LightClientError::UnexpectedNextSyncCommittee.into())
+        }
+    } else {
+        if update_attested_period == store_period &&
+            is_next_sync_committee_known(store) &&
+            update.next_sync_committee != store.next_sync_committee
+        {
+            return Err(LightClientError::ConflictingNextSyncCommittee.into())
+        }
+        if !is_valid_light_client_branch(
+            update.next_sync_committee.hash_tree_root()?,
+            &update.next_sync_committee_branch,
+            NEXT_SYNC_COMMITTEE_INDEX,
+            update.attested_header.beacon.state_root,
+        ) {
+            return Err(LightClientError::InvalidNextSyncCommitteeBranch.into())
+        }
+    }
+
+    // Verify sync committee aggregate signature
+    let sync_committee = if update_signature_period == store_period {
+        &store.current_sync_committee
+    } else {
+        &store.next_sync_committee
+    };
+    let participant_public_keys =
+        zip(sync_aggregate.sync_committee_bits.iter(), sync_committee.public_keys.iter())
+            .filter_map(|(bit, public_key)| if *bit { Some(public_key) } else { None })
+            .collect::<Vec<_>>();
+    let fork_version_slot = update.signature_slot.max(1) - 1;
+    let fork_version = context.fork_version_for(context.fork_for(fork_version_slot));
+    let domain = compute_domain(
+        DomainType::SyncCommittee,
+        Some(fork_version),
+        Some(genesis_validators_root),
+        context,
+    )?;
+    let signing_root = compute_signing_root(&update.attested_header.beacon, domain)?;
+    fast_aggregate_verify(
+        &participant_public_keys,
+        signing_root.as_ref(),
+        &sync_aggregate.sync_committee_signature,
+    )
+    .map_err(|_| LightClientError::InvalidSignature { root: signing_root }.into())
+}
+pub fn apply_light_client_update<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    store: &mut LightClientStore<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    update: &LightClientUpdate<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    context: &Context,
+) -> Result<()> {
+    let store_period =
+        compute_sync_committee_period_at_slot(store.finalized_header.beacon.slot, context);
+    let update_finalized_period =
+        compute_sync_committee_period_at_slot(update.finalized_header.beacon.slot, context);
+    if !is_next_sync_committee_known(store) {
+        if update_finalized_period != store_period {
+            return Err(LightClientError::UnexpectedFinalizedPeriod {
+                finalized_period: update_finalized_period,
+                store_period,
+            }
+            .into())
+        }
+        store.next_sync_committee = update.next_sync_committee.clone();
+    } else if update_finalized_period == store_period + 1 {
+        store.current_sync_committee =
+            mem::replace(&mut store.next_sync_committee, update.next_sync_committee.clone());
+        store.previous_max_active_participants = store.current_max_active_participants;
+        store.current_max_active_participants = 0;
+    }
+    if update.finalized_header.beacon.slot > store.finalized_header.beacon.slot {
+        store.finalized_header = update.finalized_header.clone();
+        if store.finalized_header.beacon.slot > store.optimistic_header.beacon.slot {
+            store.optimistic_header = store.finalized_header.clone();
+        }
     }
     Ok(())
 }
+pub fn process_light_client_store_force_update<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    store: &mut LightClientStore<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    current_slot: Slot,
+    context: &Context,
+) -> Result<()> {
+    if current_slot > store.finalized_header.beacon.slot + context.update_timeout as Slot {
+        if let Some(mut best_valid_update) = store.best_valid_update.take() {
+            // The apply logic waits for `finalized_header.beacon.slot` to indicate sync committee
+            // finality, so the `attested_header` may be treated as `finalized_header` to
+            // guarantee progression into later sync committee periods.
+            if best_valid_update.finalized_header.beacon.slot <= store.finalized_header.beacon.slot
+            {
+                best_valid_update.finalized_header = best_valid_update.attested_header.clone();
+            }
+            apply_light_client_update(store, &best_valid_update, context)?;
+        }
+    }
+    Ok(())
+}
+pub fn process_light_client_update<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    store: &mut LightClientStore<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    update: &LightClientUpdate<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    current_slot: Slot,
+    genesis_validators_root: Root,
+    context: &Context,
+) -> Result<()> {
+    validate_light_client_update(store, update, current_slot, genesis_validators_root, context)?;
+
+    let participants = update.sync_aggregate.sync_committee_bits.iter().filter(|bit| **bit).count();
+
+    // Update the best update in case we have to force-update to it if the timeout elapses
+    let is_best_valid_update = match store.best_valid_update.as_ref() {
+        Some(best_valid_update) => is_better_update(update, best_valid_update, context),
+        None => true,
+    };
+    if is_best_valid_update {
+        store.best_valid_update = Some(update.clone());
+    }
+
+    // Track the maximum number of active participants in the committee signatures
+    store.current_max_active_participants = store.current_max_active_participants.max(participants);
+
+    // Update the optimistic header
+    if participants > get_safety_threshold(store) &&
+        update.attested_header.beacon.slot > store.optimistic_header.beacon.slot
+    {
+        store.optimistic_header = update.attested_header.clone();
+    }
+
+    // Update finalized header
+    let update_has_finalized_next_sync_committee = !is_next_sync_committee_known(store) &&
+        is_sync_committee_update(update) &&
+        is_finality_update(update) &&
+        compute_sync_committee_period_at_slot(update.finalized_header.beacon.slot, context) ==
+            compute_sync_committee_period_at_slot(
+                update.attested_header.beacon.slot,
+                context,
+            );
+    if participants * 3 >= SYNC_COMMITTEE_SIZE * 2 &&
+        (update.finalized_header.beacon.slot > store.finalized_header.beacon.slot ||
+            update_has_finalized_next_sync_committee)
+    {
+        // Normal update through 2/3 threshold
+        apply_light_client_update(store, update, context)?;
+        store.best_valid_update = None;
+    }
+    Ok(())
+}
+pub fn process_light_client_finality_update<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    store: &mut LightClientStore<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    finality_update: &LightClientFinalityUpdate<
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    current_slot: Slot,
+    genesis_validators_root: Root,
+    context: &Context,
+) -> Result<()> {
+    let update = LightClientUpdate {
+        attested_header: finality_update.attested_header.clone(),
+        next_sync_committee: SyncCommittee::default(),
+        next_sync_committee_branch: Default::default(),
+        finalized_header: finality_update.finalized_header.clone(),
+        finality_branch: finality_update.finality_branch.clone(),
+        sync_aggregate: finality_update.sync_aggregate.clone(),
+        signature_slot: finality_update.signature_slot,
+    };
+    process_light_client_update(store, &update, current_slot, genesis_validators_root, context)
+}
+pub fn process_light_client_optimistic_update<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    store: &mut LightClientStore<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    optimistic_update: &LightClientOptimisticUpdate<
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    current_slot: Slot,
+    genesis_validators_root: Root,
+    context: &Context,
+) -> Result<()> {
+    let update = LightClientUpdate {
+        attested_header: optimistic_update.attested_header.clone(),
+        next_sync_committee: SyncCommittee::default(),
+        next_sync_committee_branch: Default::default(),
+        finalized_header: LightClientHeader::default(),
+        finality_branch: Default::default(),
+        sync_aggregate: optimistic_update.sync_aggregate.clone(),
+        signature_slot: optimistic_update.signature_slot,
+    };
+    process_light_client_update(store, &update, current_slot, genesis_validators_root, context)
+}
 pub fn process_slot<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
@@ -3215,7 +4392,7 @@ pub fn process_slot<
     let previous_state_root = state.hash_tree_root()?;
     let root_index = state.slot % context.slots_per_historical_root;
     state.state_roots[root_index as usize] = previous_state_root;
-    if state.latest_block_header.state_root == Root::default() {
+    if state.latest_block_header.state_root.is_zero() {
         state.latest_block_header.state_root = previous_state_root;
     }
     let previous_block_root = state.latest_block_header.hash_tree_root()?;
@@ -3223,6 +4400,46 @@ pub fn process_slot<
     state.block_roots[root_index as usize] = previous_block_root;
     Ok(())
 }
+pub fn process_slot_with_field_roots<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    field_roots: &mut [Root],
+    context: &Context,
+) -> Result<()> {
+    let previous_state_root = merkleize_field_roots(field_roots)?;
+    let root_index = (state.slot % context.slots_per_historical_root) as usize;
+    state.state_roots[root_index] = previous_state_root;
+    field_roots[STATE_STATE_ROOTS_FIELD_INDEX] = state.state_roots.hash_tree_root()?;
+    if state.latest_block_header.state_root.is_zero() {
+        state.latest_block_header.state_root = previous_state_root;
+    }
+    let previous_block_root = state.latest_block_header.hash_tree_root()?;
+    field_roots[STATE_LATEST_BLOCK_HEADER_FIELD_INDEX] = previous_block_root;
+    state.block_roots[root_index] = previous_block_root;
+    field_roots[STATE_BLOCK_ROOTS_FIELD_INDEX] = state.block_roots.hash_tree_root()?;
+    Ok(())
+}
 pub fn state_transition_block_in_slot<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
@@ -3276,16 +4493,94 @@ pub fn state_transition_block_in_slot<
     validation: Validation,
     context: &Context,
 ) -> Result<()> {
+    state_transition_block_in_slot_with_state_root(
+        state,
+        signed_block,
+        validation,
+        |state| Ok(state.hash_tree_root()?),
+        context,
+    )
+}
+pub fn state_transition_block_in_slot_with_state_root<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    signed_block: &SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    >,
+    validation: Validation,
+    state_root_of: impl FnOnce(
+        &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+    ) -> Result<Root>,
+    context: &Context,
+) -> Result<()> {
+    trace_span!("process_block", slot = signed_block.message.slot);
     let validate_result = match validation {
-        Validation::Enabled => true,
+        Validation::Enabled |
+        Validation::AssumeFinalizedBelow(_) |
+        Validation::SkipSignatureVerification => true,
         Validation::Disabled => false,
     };
-    if validate_result {
+    if validate_result && validation.verifies_signatures() {
         verify_block_signature(state, signed_block, context)?;
     }
     let block = &signed_block.message;
-    process_block(state, block, context)?;
-    if validate_result && block.state_root != state.hash_tree_root()? {
+    process_block_with_validation(state, block, validation, context)?;
+    if validate_result && block.state_root != traced!("hash_tree_root", state_root_of(state))? {
         Err(Error::InvalidStateRoot)
     } else {
         Ok(())
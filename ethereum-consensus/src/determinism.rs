@@ -0,0 +1,546 @@
+//! A harness to check that the state transition stays deterministic across versions: bundles of
+//! a pre-state, the blocks applied to it and the root of the post-state are replayed, and the
+//! first slot whose state root diverges from the recorded one is reported with the top-level
+//! fields of the state that differ.
+//!
+//! A bundle is a directory holding:
+//! - `pre_state.ssz_snappy`, the pre-state in the encoding of `storage::encode_state`,
+//! - `block_<i>.ssz_snappy` for `i` from 0, each block in the encoding of `storage::encode_block`,
+//! - `field_roots_<i>.ssz` (optional), the concatenated roots of the fields of the post-state of
+//!   block `i`, as returned by `state_field_roots`,
+//! - `post_state_root.ssz`, the root of the state after the last block.
+//!
+//! Bundles are made with [`Bundle::generate`] from any chain whose blocks are valid on the
+//! pre-state, e.g. blocks made with `Executor::produce_block` or exported from a node. Replays
+//! skip validation so that a changed state root is reported as a divergence rather than as an
+//! invalid block. [`Executor::check_bundles`] is the entry point for CI.
+use crate::{
+    altair, bellatrix, capella, deneb, electra, phase0,
+    primitives::{Root, Slot},
+    sparse::{state_fields, StateField},
+    ssz::prelude::*,
+    state_transition::{Context, Executor, Result, Validation},
+    storage::{decode_block, decode_state, encode_block, encode_state},
+    types::{BeaconState, SignedBeaconBlock},
+};
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+const PRE_STATE_FILE: &str = "pre_state.ssz_snappy";
+const POST_STATE_ROOT_FILE: &str = "post_state_root.ssz";
+const ROOT_SIZE: usize = 32;
+
+fn block_file(index: usize) -> String {
+    format!("block_{index}.ssz_snappy")
+}
+
+fn field_roots_file(index: usize) -> String {
+    format!("field_roots_{index}.ssz")
+}
+
+#[derive(Debug, Error)]
+pub enum DeterminismError {
+    #[error("{path:?} has {len} bytes which is not a whole number of roots")]
+    InvalidRoots { path: PathBuf, len: usize },
+    #[error("bundle {bundle:?} diverged at {divergence}")]
+    Diverged { bundle: PathBuf, divergence: Box<Divergence> },
+}
+
+/// A top-level field of the state whose root differs from the recorded one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    pub field: StateField,
+    pub expected: Root,
+    pub actual: Root,
+}
+
+/// The first state in a replay whose root differs from the recorded one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub slot: Slot,
+    pub expected_root: Root,
+    pub actual_root: Root,
+    /// Empty if the bundle has no field roots for the state.
+    pub fields: Vec<FieldDiff>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "slot {}: expected state root {} but found {}",
+            self.slot, self.expected_root, self.actual_root
+        )?;
+        for diff in &self.fields {
+            write!(f, "; {} expected {} but found {}", diff.field, diff.expected, diff.actual)?;
+        }
+        Ok(())
+    }
+}
+
+/// The contents of a bundle, with the pre-state and blocks kept in their storage encoding so that
+/// a bundle can be handled without picking a preset.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bundle {
+    pub pre_state: Vec<u8>,
+    pub blocks: Vec<Vec<u8>>,
+    /// The field roots of the post-state of each block, to locate a divergence within the state.
+    pub field_roots: Vec<Vec<Root>>,
+    pub expected_post_root: Root,
+}
+
+impl Bundle {
+    /// Make a bundle by applying `blocks` to `pre_state` with full validation, recording the
+    /// roots of each post-state.
+    pub fn generate<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+    >(
+        pre_state: &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+        blocks: &[SignedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >],
+        context: &Context,
+    ) -> Result<Self> {
+        let mut executor = Executor::<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_PROPOSER_SLASHINGS,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >::new(pre_state.clone(), context.clone());
+        let mut encoded_blocks = Vec::with_capacity(blocks.len());
+        let mut field_roots = Vec::with_capacity(blocks.len());
+        for block in blocks {
+            executor.apply_block(block)?;
+            encoded_blocks.push(encode_block(block)?);
+            let roots = state_field_roots(executor.state.inner())?;
+            field_roots.push(roots.into_iter().map(|(_, root)| root).collect());
+        }
+        Ok(Self {
+            pre_state: encode_state(pre_state)?,
+            blocks: encoded_blocks,
+            field_roots,
+            expected_post_root: executor.state.hash_tree_root()?,
+        })
+    }
+
+    pub fn read<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let dir = dir.as_ref();
+        let pre_state = fs::read(dir.join(PRE_STATE_FILE))?;
+        let mut blocks = vec![];
+        let mut field_roots = vec![];
+        while dir.join(block_file(blocks.len())).exists() {
+            let path = dir.join(field_roots_file(blocks.len()));
+            if path.exists() {
+                field_roots.push(read_roots(&path)?);
+            }
+            blocks.push(fs::read(dir.join(block_file(blocks.len())))?);
+        }
+        let path = dir.join(POST_STATE_ROOT_FILE);
+        let expected_post_root = match read_roots(&path)?.as_slice() {
+            [root] => *root,
+            roots => {
+                let len = roots.len() * ROOT_SIZE;
+                return Err(DeterminismError::InvalidRoots { path, len }.into())
+            }
+        };
+        Ok(Self { pre_state, blocks, field_roots, expected_post_root })
+    }
+
+    pub fn write<P: AsRef<Path>>(&self, dir: P) -> Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join(PRE_STATE_FILE), &self.pre_state)?;
+        for (index, block) in self.blocks.iter().enumerate() {
+            fs::write(dir.join(block_file(index)), block)?;
+        }
+        for (index, roots) in self.field_roots.iter().enumerate() {
+            let bytes = roots.iter().flat_map(|root| root.as_ref()).copied().collect::<Vec<_>>();
+            fs::write(dir.join(field_roots_file(index)), bytes)?;
+        }
+        fs::write(dir.join(POST_STATE_ROOT_FILE), self.expected_post_root.as_ref())?;
+        Ok(())
+    }
+}
+
+fn read_roots(path: &Path) -> Result<Vec<Root>> {
+    let bytes = fs::read(path)?;
+    if bytes.len() % ROOT_SIZE != 0 {
+        return Err(
+            DeterminismError::InvalidRoots { path: path.to_path_buf(), len: bytes.len() }.into()
+        )
+    }
+    Ok(bytes
+        .chunks(ROOT_SIZE)
+        .map(|chunk| Root::try_from(chunk).expect("chunk is the size of a root"))
+        .collect())
+}
+
+/// Returns the root of each top-level field of `state`, in SSZ order.
+pub fn state_field_roots<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+) -> Result<Vec<(StateField, Root)>> {
+    let roots = match state {
+        BeaconState::Phase0(inner) => phase0::beacon_state::compute_state_field_roots(inner)?,
+        BeaconState::Altair(inner) => altair::beacon_state::compute_state_field_roots(inner)?,
+        BeaconState::Bellatrix(inner) => bellatrix::beacon_state::compute_state_field_roots(inner)?,
+        BeaconState::Capella(inner) => capella::beacon_state::compute_state_field_roots(inner)?,
+        BeaconState::Deneb(inner) => deneb::beacon_state::compute_state_field_roots(inner)?,
+        BeaconState::Electra(inner) => electra::beacon_state::compute_state_field_roots(inner)?,
+    };
+    Ok(state_fields(state.version()).iter().copied().zip(roots).collect())
+}
+
+impl<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+    >
+    Executor<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_PROPOSER_SLASHINGS,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >
+{
+    /// Replay `bundle` under this preset, returning the first state whose root differs from the
+    /// one recorded in the bundle, if any.
+    ///
+    /// The state after each block is compared with the state root of the block, and the final
+    /// state with the expected post-state root of the bundle.
+    pub fn replay_bundle(bundle: &Bundle, context: Context) -> Result<Option<Divergence>> {
+        let mut executor = Self::new(decode_state(&bundle.pre_state)?, context);
+        for (index, encoding) in bundle.blocks.iter().enumerate() {
+            let block: SignedBeaconBlock<
+                MAX_PROPOSER_SLASHINGS,
+                MAX_VALIDATORS_PER_COMMITTEE,
+                MAX_ATTESTER_SLASHINGS,
+                MAX_ATTESTATIONS,
+                MAX_DEPOSITS,
+                MAX_VOLUNTARY_EXITS,
+                SYNC_COMMITTEE_SIZE,
+                BYTES_PER_LOGS_BLOOM,
+                MAX_EXTRA_DATA_BYTES,
+                MAX_BYTES_PER_TRANSACTION,
+                MAX_TRANSACTIONS_PER_PAYLOAD,
+                MAX_WITHDRAWALS_PER_PAYLOAD,
+                MAX_BLS_TO_EXECUTION_CHANGES,
+                MAX_BLOB_COMMITMENTS_PER_BLOCK,
+                MAX_VALIDATORS_PER_SLOT,
+                MAX_COMMITTEES_PER_SLOT,
+                MAX_ATTESTER_SLASHINGS_ELECTRA,
+                MAX_ATTESTATIONS_ELECTRA,
+                MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+                MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+                MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+            > = decode_block(encoding)?;
+            executor.apply_block_with_validation(&block, Validation::Disabled)?;
+            let expected_root = block.message().state_root();
+            if let Some(divergence) =
+                executor.divergence(expected_root, bundle.field_roots.get(index))?
+            {
+                return Ok(Some(divergence))
+            }
+        }
+        executor.divergence(bundle.expected_post_root, bundle.field_roots.last())
+    }
+
+    // Compare the current state with the expected root and, if they differ, with the expected
+    // roots of its fields.
+    fn divergence(
+        &self,
+        expected_root: Root,
+        expected_field_roots: Option<&Vec<Root>>,
+    ) -> Result<Option<Divergence>> {
+        let actual_root = self.state.hash_tree_root()?;
+        if actual_root == expected_root {
+            return Ok(None)
+        }
+        let fields = match expected_field_roots {
+            Some(expected_field_roots) => state_field_roots(self.state.inner())?
+                .into_iter()
+                .zip(expected_field_roots)
+                .filter(|((_, actual), expected)| actual != *expected)
+                .map(|((field, actual), expected)| FieldDiff { field, expected: *expected, actual })
+                .collect(),
+            None => vec![],
+        };
+        Ok(Some(Divergence { slot: self.state.slot(), expected_root, actual_root, fields }))
+    }
+
+    /// Replay every bundle in the subdirectories of `dir` under this preset, failing on the first
+    /// divergence; returns the number of bundles replayed.
+    pub fn check_bundles<P: AsRef<Path>>(dir: P, context: &Context) -> Result<usize> {
+        let mut bundles = vec![];
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                bundles.push(path);
+            }
+        }
+        bundles.sort();
+        for path in &bundles {
+            let bundle = Bundle::read(path)?;
+            if let Some(divergence) = Self::replay_bundle(&bundle, context.clone())? {
+                return Err(DeterminismError::Diverged {
+                    bundle: path.clone(),
+                    divergence: Box::new(divergence),
+                }
+                .into())
+            }
+        }
+        Ok(bundles.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::determinism::{context, starter_bundles},
+        state_transition::minimal,
+        Error, Fork,
+    };
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("determinism-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_starter_bundles_replay() {
+        let context = context();
+        let bundles = starter_bundles();
+        let forks = bundles.iter().map(|(fork, _)| *fork).collect::<Vec<_>>();
+        assert_eq!(
+            forks,
+            [
+                Fork::Phase0,
+                Fork::Altair,
+                Fork::Bellatrix,
+                Fork::Capella,
+                Fork::Deneb,
+                Fork::Electra
+            ]
+        );
+
+        let dir = temp_dir("starter");
+        for (fork, bundle) in &bundles {
+            bundle.write(dir.join(fork.to_string())).unwrap();
+            assert_eq!(&Bundle::read(dir.join(fork.to_string())).unwrap(), bundle);
+        }
+        assert_eq!(minimal::Executor::check_bundles(&dir, &context).unwrap(), bundles.len());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_reports_first_divergent_slot() {
+        let context = context();
+        let (_, mut bundle) = starter_bundles().remove(0);
+
+        // a pre-state the blocks were not made for
+        let mut pre_state: crate::types::minimal::BeaconState =
+            decode_state(&bundle.pre_state).unwrap();
+        pre_state.balances_mut()[0] += 1;
+        bundle.pre_state = encode_state(&pre_state).unwrap();
+
+        let first_block: crate::types::minimal::SignedBeaconBlock =
+            decode_block(&bundle.blocks[0]).unwrap();
+        let divergence =
+            minimal::Executor::replay_bundle(&bundle, context.clone()).unwrap().unwrap();
+        assert_eq!(divergence.slot, first_block.message().slot());
+        assert_eq!(divergence.expected_root, first_block.message().state_root());
+        assert!(divergence.fields.iter().any(|diff| diff.field == StateField::Balances));
+        assert!(divergence.fields.iter().all(|diff| diff.field != StateField::Validators));
+
+        let dir = temp_dir("divergent");
+        bundle.write(dir.join("phase0")).unwrap();
+        let err = minimal::Executor::check_bundles(&dir, &context).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Determinism(DeterminismError::Diverged { divergence: d, .. })
+                if *d == divergence
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
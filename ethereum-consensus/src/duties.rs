@@ -0,0 +1,550 @@
+//! Proposer and attester duties of an epoch, for validator clients.
+//!
+//! Duties are computed with the shuffling of the state transition from any state that already
+//! determines them. Attester duties are settled once the randao mix they are seeded from is, so a
+//! state can answer for up to the epoch after its current epoch. Proposer duties also depend on
+//! effective balances, which change at each epoch boundary, so a state only answers for its
+//! current epoch.
+use crate::{
+    crypto::hash,
+    electra,
+    phase0::{compute_epoch_at_slot, compute_shuffled_index, Validator},
+    primitives::{Bytes32, CommitteeIndex, DomainType, Epoch, Gwei, Slot, ValidatorIndex},
+    state_transition::{Context, Shuffling},
+    types::BeaconState,
+    validators::{active_validator_indices, is_active_validator},
+    Error,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum DutiesError {
+    #[error(
+        "duties of epoch {epoch} are not determined by the state, which determines those of epochs {earliest_epoch} to {latest_epoch}"
+    )]
+    UndeterminedEpoch { epoch: Epoch, earliest_epoch: Epoch, latest_epoch: Epoch },
+}
+
+/// The committee a validator is assigned to in some epoch, with the index of the committee and
+/// the slot it attests in.
+pub type CommitteeAssignment = (Vec<ValidatorIndex>, CommitteeIndex, Slot);
+
+// The parts of a state of any fork that duties are computed from.
+struct DutiesView<'a> {
+    slot: Slot,
+    validators: &'a [Validator],
+    randao_mixes: &'a [Bytes32],
+}
+
+impl DutiesView<'_> {
+    fn current_epoch(&self, context: &Context) -> Epoch {
+        compute_epoch_at_slot(self.slot, context)
+    }
+
+    // The seed of an epoch uses the randao mix of `MIN_SEED_LOOKAHEAD + 1` epochs earlier, which
+    // the state only holds for so long
+    fn earliest_seeded_epoch(&self, context: &Context) -> Epoch {
+        let current_epoch = self.current_epoch(context);
+        (current_epoch + context.min_seed_lookahead + 2)
+            .saturating_sub(context.epochs_per_historical_vector)
+            .min(current_epoch)
+    }
+
+    fn check_epoch(epoch: Epoch, earliest_epoch: Epoch, latest_epoch: Epoch) -> Result<(), Error> {
+        if epoch < earliest_epoch || epoch > latest_epoch {
+            return Err(DutiesError::UndeterminedEpoch { epoch, earliest_epoch, latest_epoch }.into())
+        }
+        Ok(())
+    }
+
+    // Mirrors `get_seed`
+    fn seed(&self, epoch: Epoch, domain_type: DomainType, context: &Context) -> Bytes32 {
+        let mix_epoch =
+            epoch + (context.epochs_per_historical_vector - context.min_seed_lookahead) - 1;
+        let mix = &self.randao_mixes[(mix_epoch % context.epochs_per_historical_vector) as usize];
+        let mut input = [0u8; 44];
+        input[..4].copy_from_slice(&domain_type.as_bytes());
+        input[4..12].copy_from_slice(&epoch.to_le_bytes());
+        input[12..].copy_from_slice(mix.as_ref());
+        hash(input)
+    }
+
+    fn committee_assignment(
+        &self,
+        epoch: Epoch,
+        validator_index: ValidatorIndex,
+        context: &Context,
+    ) -> Result<Option<CommitteeAssignment>, Error> {
+        let current_epoch = self.current_epoch(context);
+        Self::check_epoch(epoch, self.earliest_seeded_epoch(context), current_epoch + 1)?;
+        let is_active = self
+            .validators
+            .get(validator_index)
+            .is_some_and(|validator| is_active_validator(validator, epoch));
+        if !is_active {
+            return Ok(None)
+        }
+
+        let mut indices = vec![];
+        active_validator_indices(self.validators, epoch, &mut indices);
+        let seed = self.seed(epoch, DomainType::BeaconAttester, context);
+        let shuffling = Shuffling::new(epoch, seed, indices, context);
+        let start_slot = epoch * context.slots_per_epoch;
+        for slot in start_slot..start_slot + context.slots_per_epoch {
+            for index in 0..shuffling.committee_count_per_slot(context) {
+                let committee = shuffling.beacon_committee(slot, index, context)?;
+                if committee.contains(&validator_index) {
+                    return Ok(Some((committee.to_vec(), index, slot)))
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn proposer_indices(
+        &self,
+        epoch: Epoch,
+        max_effective_balance: Gwei,
+        context: &Context,
+    ) -> Result<Vec<ValidatorIndex>, Error> {
+        let current_epoch = self.current_epoch(context);
+        Self::check_epoch(epoch, current_epoch, current_epoch)?;
+        let mut indices = vec![];
+        active_validator_indices(self.validators, epoch, &mut indices);
+        if indices.is_empty() {
+            return Err(Error::NoActiveValidators(epoch))
+        }
+
+        let epoch_seed = self.seed(epoch, DomainType::BeaconProposer, context);
+        let start_slot = epoch * context.slots_per_epoch;
+        (start_slot..start_slot + context.slots_per_epoch)
+            .map(|slot| {
+                let mut input = [0u8; 40];
+                input[..32].copy_from_slice(epoch_seed.as_ref());
+                input[32..].copy_from_slice(&slot.to_le_bytes());
+                let seed = hash(input);
+                self.proposer_index(&indices, &seed, max_effective_balance, context)
+            })
+            .collect()
+    }
+
+    // Mirrors `compute_proposer_index`, with the balance each fork samples proposers out of
+    fn proposer_index(
+        &self,
+        indices: &[ValidatorIndex],
+        seed: &Bytes32,
+        max_effective_balance: Gwei,
+        context: &Context,
+    ) -> Result<ValidatorIndex, Error> {
+        let max_byte = u8::MAX as u64;
+        let total = indices.len();
+        let mut hash_input = [0u8; 40];
+        hash_input[..32].copy_from_slice(seed.as_ref());
+        let mut round = 0;
+        loop {
+            let candidate_index =
+                indices[compute_shuffled_index(round % total, total, seed, context)?];
+            hash_input[32..].copy_from_slice(&((round / 32) as u64).to_le_bytes());
+            let random_byte = hash(hash_input).as_ref()[round % 32] as u64;
+            let effective_balance = self.validators[candidate_index].effective_balance;
+            if effective_balance * max_byte >= max_effective_balance * random_byte {
+                return Ok(candidate_index)
+            }
+            round += 1;
+        }
+    }
+}
+
+fn view<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+) -> DutiesView<'_> {
+    DutiesView {
+        slot: state.slot(),
+        validators: &state.validators()[..],
+        randao_mixes: &state.randao_mixes()[..],
+    }
+}
+
+fn electra_view<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &electra::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+) -> DutiesView<'_> {
+    DutiesView {
+        slot: state.slot,
+        validators: &state.validators[..],
+        randao_mixes: &state.randao_mixes[..],
+    }
+}
+
+/// The committee `validator_index` is assigned to in `epoch`, if it is active then, as defined by
+/// `get_committee_assignment` in the honest validator guide.
+///
+/// `epoch` may be at most the epoch after the current epoch of `state`.
+pub fn get_committee_assignment<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    epoch: Epoch,
+    validator_index: ValidatorIndex,
+    context: &Context,
+) -> Result<Option<CommitteeAssignment>, Error> {
+    view(state).committee_assignment(epoch, validator_index, context)
+}
+
+/// The proposer of each slot of `epoch`, which must be the current epoch of `state`.
+pub fn get_proposer_indices_for_epoch<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    epoch: Epoch,
+    context: &Context,
+) -> Result<Vec<ValidatorIndex>, Error> {
+    view(state).proposer_indices(epoch, context.max_effective_balance, context)
+}
+
+/// [`get_committee_assignment`] for an electra `state`, which is not yet part of the polymorphic
+/// [`BeaconState`].
+pub fn get_electra_committee_assignment<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &electra::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    epoch: Epoch,
+    validator_index: ValidatorIndex,
+    context: &Context,
+) -> Result<Option<CommitteeAssignment>, Error> {
+    electra_view(state).committee_assignment(epoch, validator_index, context)
+}
+
+/// [`get_proposer_indices_for_epoch`] for an electra `state`, where proposers are sampled by
+/// effective balance out of `MAX_EFFECTIVE_BALANCE_ELECTRA`.
+pub fn get_electra_proposer_indices_for_epoch<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &electra::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    epoch: Epoch,
+    context: &Context,
+) -> Result<Vec<ValidatorIndex>, Error> {
+    electra_view(state).proposer_indices(epoch, context.max_effective_balance_electra, context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::{phase0::genesis_state, VALIDATOR_COUNT},
+        phase0,
+        primitives::FAR_FUTURE_EPOCH,
+        types::minimal,
+    };
+
+    fn undetermined_epoch(result: Result<impl std::fmt::Debug, Error>) -> DutiesError {
+        match result.unwrap_err() {
+            Error::Duties(err) => err,
+            err => panic!("{err}"),
+        }
+    }
+
+    #[test]
+    fn test_committee_assignments_match_beacon_committees() {
+        let context = Context::for_minimal();
+        let mut phase0_state = genesis_state();
+        // in an epoch whose seed does not come from the genesis randao mixes
+        phase0_state.slot = 3 * context.slots_per_epoch + 1;
+        phase0_state.randao_mixes[1] = Bytes32::try_from([7u8; 32].as_ref()).unwrap();
+        let state = minimal::BeaconState::Phase0(phase0_state.clone());
+
+        let epoch = 3;
+        let mut assigned = 0;
+        for validator_index in 0..VALIDATOR_COUNT {
+            let (committee, index, slot) =
+                get_committee_assignment(&state, epoch, validator_index, &context)
+                    .unwrap()
+                    .unwrap();
+            assert_eq!(compute_epoch_at_slot(slot, &context), epoch);
+            assert!(committee.contains(&validator_index));
+            let expected =
+                phase0::get_beacon_committee(&phase0_state, slot, index, &context).unwrap();
+            assert_eq!(committee, expected);
+            assigned += 1;
+        }
+        assert_eq!(assigned, VALIDATOR_COUNT);
+
+        // unknown and inactive validators have no assignment
+        assert!(get_committee_assignment(&state, epoch, VALIDATOR_COUNT, &context)
+            .unwrap()
+            .is_none());
+        let mut phase0_state = phase0_state;
+        phase0_state.validators[5].exit_epoch = epoch;
+        let state = minimal::BeaconState::Phase0(phase0_state);
+        assert!(get_committee_assignment(&state, epoch, 5, &context).unwrap().is_none());
+        assert!(get_committee_assignment(&state, epoch - 1, 5, &context).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_proposer_indices_match_beacon_proposers() {
+        let context = Context::for_minimal();
+        let mut phase0_state = genesis_state();
+        phase0_state.slot = 2 * context.slots_per_epoch;
+        // proposers are sampled by effective balance
+        for validator in phase0_state.validators.iter_mut().step_by(3) {
+            validator.effective_balance = context.ejection_balance;
+        }
+        let state = minimal::BeaconState::Phase0(phase0_state.clone());
+
+        let proposers = get_proposer_indices_for_epoch(&state, 2, &context).unwrap();
+        assert_eq!(proposers.len(), context.slots_per_epoch as usize);
+        for (slot, proposer) in (phase0_state.slot..).zip(proposers) {
+            phase0_state.slot = slot;
+            let expected = phase0::get_beacon_proposer_index(&phase0_state, &context).unwrap();
+            assert_eq!(proposer, expected);
+        }
+    }
+
+    #[test]
+    fn test_undetermined_epochs() {
+        let context = Context::for_minimal();
+        let mut phase0_state = genesis_state();
+        phase0_state.slot = 2 * context.slots_per_epoch;
+        let state = minimal::BeaconState::Phase0(phase0_state);
+
+        // attester duties are known one epoch ahead, proposer duties only for the current epoch
+        get_committee_assignment(&state, 3, 0, &context).unwrap();
+        let err = undetermined_epoch(get_committee_assignment(&state, 4, 0, &context));
+        assert_eq!(
+            err,
+            DutiesError::UndeterminedEpoch { epoch: 4, earliest_epoch: 0, latest_epoch: 3 }
+        );
+        let err = undetermined_epoch(get_proposer_indices_for_epoch(&state, 3, &context));
+        assert_eq!(
+            err,
+            DutiesError::UndeterminedEpoch { epoch: 3, earliest_epoch: 2, latest_epoch: 2 }
+        );
+        let err = undetermined_epoch(get_proposer_indices_for_epoch(&state, 1, &context));
+        assert_eq!(
+            err,
+            DutiesError::UndeterminedEpoch { epoch: 1, earliest_epoch: 2, latest_epoch: 2 }
+        );
+
+        // the randao mixes seeding old epochs are eventually overwritten
+        let mut phase0_state = genesis_state();
+        let current_epoch = context.epochs_per_historical_vector + 10;
+        phase0_state.slot = current_epoch * context.slots_per_epoch;
+        let state = minimal::BeaconState::Phase0(phase0_state);
+        let earliest_epoch =
+            current_epoch + context.min_seed_lookahead + 2 - context.epochs_per_historical_vector;
+        get_committee_assignment(&state, earliest_epoch, 0, &context).unwrap();
+        let err =
+            undetermined_epoch(get_committee_assignment(&state, earliest_epoch - 1, 0, &context));
+        assert_eq!(
+            err,
+            DutiesError::UndeterminedEpoch {
+                epoch: earliest_epoch - 1,
+                earliest_epoch,
+                latest_epoch: current_epoch + 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_electra_duties() {
+        let context = Context::for_minimal();
+        let mut state = electra::minimal::BeaconState::default();
+        state.slot = context.slots_per_epoch;
+        for i in 0..16u64 {
+            state.validators.push(Validator {
+                // a mix of compounding validators and validators at the old maximum
+                effective_balance: if i % 2 == 0 {
+                    context.max_effective_balance_electra
+                } else {
+                    context.max_effective_balance
+                },
+                exit_epoch: FAR_FUTURE_EPOCH,
+                withdrawable_epoch: FAR_FUTURE_EPOCH,
+                ..Default::default()
+            });
+            state.balances.push(0);
+        }
+
+        let proposers = get_electra_proposer_indices_for_epoch(&state, 1, &context).unwrap();
+        for (slot, proposer) in (state.slot..).zip(proposers) {
+            let mut state = state.clone();
+            state.slot = slot;
+            let expected = electra::get_beacon_proposer_index(&state, &context).unwrap();
+            assert_eq!(proposer, expected);
+        }
+
+        let (committee, index, slot) =
+            get_electra_committee_assignment(&state, 2, 3, &context).unwrap().unwrap();
+        let mut state = state;
+        state.slot = slot;
+        assert_eq!(
+            committee,
+            electra::get_beacon_committee(&state, slot, index, &context).unwrap()
+        );
+    }
+}
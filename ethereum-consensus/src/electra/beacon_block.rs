@@ -1,10 +1,10 @@
 use crate::{
     altair::SyncAggregate,
     capella::SignedBlsToExecutionChange,
-    deneb::polynomial_commitments::KzgCommitment,
+    deneb::{polynomial_commitments::KzgCommitment, ExecutionPayload},
     electra::{
-        execution_payload::ExecutionPayload,
-        operations::{Attestation, AttesterSlashing, SignedConsolidation},
+        execution_requests::ExecutionRequests,
+        operations::{Attestation, AttesterSlashing},
     },
     phase0::{Deposit, Eth1Data, ProposerSlashing, SignedVoluntaryExit},
     primitives::{BlsSignature, Bytes32, Root, Slot, ValidatorIndex},
@@ -18,8 +18,8 @@ pub struct BeaconBlockBody<
     const MAX_PROPOSER_SLASHINGS: usize,
     const MAX_VALIDATORS_PER_SLOT: usize,
     const MAX_COMMITTEES_PER_SLOT: usize,
-    const MAX_ATTESTER_SLASHINGS: usize,
-    const MAX_ATTESTATIONS: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
     const MAX_DEPOSITS: usize,
     const MAX_VOLUNTARY_EXITS: usize,
     const SYNC_COMMITTEE_SIZE: usize,
@@ -28,19 +28,22 @@ pub struct BeaconBlockBody<
     const MAX_BYTES_PER_TRANSACTION: usize,
     const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
     const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
-    const MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD: usize,
-    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
     const MAX_BLS_TO_EXECUTION_CHANGES: usize,
     const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
-    const MAX_CONSOLIDATIONS: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
 > {
     pub randao_reveal: BlsSignature,
     pub eth1_data: Eth1Data,
     pub graffiti: Bytes32,
     pub proposer_slashings: List<ProposerSlashing, MAX_PROPOSER_SLASHINGS>,
-    pub attester_slashings: List<AttesterSlashing<MAX_VALIDATORS_PER_SLOT>, MAX_ATTESTER_SLASHINGS>,
-    pub attestations:
-        List<Attestation<MAX_VALIDATORS_PER_SLOT, MAX_COMMITTEES_PER_SLOT>, MAX_ATTESTATIONS>,
+    pub attester_slashings:
+        List<AttesterSlashing<MAX_VALIDATORS_PER_SLOT>, MAX_ATTESTER_SLASHINGS_ELECTRA>,
+    pub attestations: List<
+        Attestation<MAX_VALIDATORS_PER_SLOT, MAX_COMMITTEES_PER_SLOT>,
+        MAX_ATTESTATIONS_ELECTRA,
+    >,
     pub deposits: List<Deposit, MAX_DEPOSITS>,
     pub voluntary_exits: List<SignedVoluntaryExit, MAX_VOLUNTARY_EXITS>,
     pub sync_aggregate: SyncAggregate<SYNC_COMMITTEE_SIZE>,
@@ -50,12 +53,14 @@ pub struct BeaconBlockBody<
         MAX_BYTES_PER_TRANSACTION,
         MAX_TRANSACTIONS_PER_PAYLOAD,
         MAX_WITHDRAWALS_PER_PAYLOAD,
-        MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD,
-        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
     >,
     pub bls_to_execution_changes: List<SignedBlsToExecutionChange, MAX_BLS_TO_EXECUTION_CHANGES>,
     pub blob_kzg_commitments: List<KzgCommitment, MAX_BLOB_COMMITMENTS_PER_BLOCK>,
-    pub consolidations: List<SignedConsolidation, MAX_CONSOLIDATIONS>,
+    pub execution_requests: ExecutionRequests<
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >,
 }
 
 #[derive(
@@ -65,8 +70,8 @@ pub struct BeaconBlock<
     const MAX_PROPOSER_SLASHINGS: usize,
     const MAX_VALIDATORS_PER_SLOT: usize,
     const MAX_COMMITTEES_PER_SLOT: usize,
-    const MAX_ATTESTER_SLASHINGS: usize,
-    const MAX_ATTESTATIONS: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
     const MAX_DEPOSITS: usize,
     const MAX_VOLUNTARY_EXITS: usize,
     const SYNC_COMMITTEE_SIZE: usize,
@@ -75,11 +80,11 @@ pub struct BeaconBlock<
     const MAX_BYTES_PER_TRANSACTION: usize,
     const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
     const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
-    const MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD: usize,
-    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
     const MAX_BLS_TO_EXECUTION_CHANGES: usize,
     const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
-    const MAX_CONSOLIDATIONS: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
 > {
     #[serde(with = "crate::serde::as_str")]
     pub slot: Slot,
@@ -91,8 +96,8 @@ pub struct BeaconBlock<
         MAX_PROPOSER_SLASHINGS,
         MAX_VALIDATORS_PER_SLOT,
         MAX_COMMITTEES_PER_SLOT,
-        MAX_ATTESTER_SLASHINGS,
-        MAX_ATTESTATIONS,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
         MAX_DEPOSITS,
         MAX_VOLUNTARY_EXITS,
         SYNC_COMMITTEE_SIZE,
@@ -101,11 +106,11 @@ pub struct BeaconBlock<
         MAX_BYTES_PER_TRANSACTION,
         MAX_TRANSACTIONS_PER_PAYLOAD,
         MAX_WITHDRAWALS_PER_PAYLOAD,
-        MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD,
-        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
-        MAX_CONSOLIDATIONS,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >,
 }
 
@@ -116,8 +121,8 @@ pub struct SignedBeaconBlock<
     const MAX_PROPOSER_SLASHINGS: usize,
     const MAX_VALIDATORS_PER_SLOT: usize,
     const MAX_COMMITTEES_PER_SLOT: usize,
-    const MAX_ATTESTER_SLASHINGS: usize,
-    const MAX_ATTESTATIONS: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
     const MAX_DEPOSITS: usize,
     const MAX_VOLUNTARY_EXITS: usize,
     const SYNC_COMMITTEE_SIZE: usize,
@@ -126,18 +131,18 @@ pub struct SignedBeaconBlock<
     const MAX_BYTES_PER_TRANSACTION: usize,
     const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
     const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
-    const MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD: usize,
-    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
     const MAX_BLS_TO_EXECUTION_CHANGES: usize,
     const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
-    const MAX_CONSOLIDATIONS: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
 > {
     pub message: BeaconBlock<
         MAX_PROPOSER_SLASHINGS,
         MAX_VALIDATORS_PER_SLOT,
         MAX_COMMITTEES_PER_SLOT,
-        MAX_ATTESTER_SLASHINGS,
-        MAX_ATTESTATIONS,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
         MAX_DEPOSITS,
         MAX_VOLUNTARY_EXITS,
         SYNC_COMMITTEE_SIZE,
@@ -146,11 +151,146 @@ pub struct SignedBeaconBlock<
         MAX_BYTES_PER_TRANSACTION,
         MAX_TRANSACTIONS_PER_PAYLOAD,
         MAX_WITHDRAWALS_PER_PAYLOAD,
-        MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD,
-        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
-        MAX_CONSOLIDATIONS,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >,
     pub signature: BlsSignature,
 }
+
+crate::ssz_size::impl_ssz_size!(BeaconBlockBody<
+    const MAX_PROPOSER_SLASHINGS,
+    const MAX_VALIDATORS_PER_SLOT,
+    const MAX_COMMITTEES_PER_SLOT,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA,
+    const MAX_ATTESTATIONS_ELECTRA,
+    const MAX_DEPOSITS,
+    const MAX_VOLUNTARY_EXITS,
+    const SYNC_COMMITTEE_SIZE,
+    const BYTES_PER_LOGS_BLOOM,
+    const MAX_EXTRA_DATA_BYTES,
+    const MAX_BYTES_PER_TRANSACTION,
+    const MAX_TRANSACTIONS_PER_PAYLOAD,
+    const MAX_WITHDRAWALS_PER_PAYLOAD,
+    const MAX_BLS_TO_EXECUTION_CHANGES,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+> {
+    randao_reveal: BlsSignature,
+    eth1_data: Eth1Data,
+    graffiti: Bytes32,
+    proposer_slashings: List<ProposerSlashing, MAX_PROPOSER_SLASHINGS>,
+    attester_slashings: List<AttesterSlashing<MAX_VALIDATORS_PER_SLOT>, MAX_ATTESTER_SLASHINGS_ELECTRA>,
+    attestations:
+        List<Attestation<MAX_VALIDATORS_PER_SLOT, MAX_COMMITTEES_PER_SLOT>, MAX_ATTESTATIONS_ELECTRA>,
+    deposits: List<Deposit, MAX_DEPOSITS>,
+    voluntary_exits: List<SignedVoluntaryExit, MAX_VOLUNTARY_EXITS>,
+    sync_aggregate: SyncAggregate<SYNC_COMMITTEE_SIZE>,
+    execution_payload: ExecutionPayload<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+    >,
+    bls_to_execution_changes: List<SignedBlsToExecutionChange, MAX_BLS_TO_EXECUTION_CHANGES>,
+    blob_kzg_commitments: List<KzgCommitment, MAX_BLOB_COMMITMENTS_PER_BLOCK>,
+    execution_requests: ExecutionRequests<
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >,
+});
+
+crate::ssz_size::impl_ssz_size!(BeaconBlock<
+    const MAX_PROPOSER_SLASHINGS,
+    const MAX_VALIDATORS_PER_SLOT,
+    const MAX_COMMITTEES_PER_SLOT,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA,
+    const MAX_ATTESTATIONS_ELECTRA,
+    const MAX_DEPOSITS,
+    const MAX_VOLUNTARY_EXITS,
+    const SYNC_COMMITTEE_SIZE,
+    const BYTES_PER_LOGS_BLOOM,
+    const MAX_EXTRA_DATA_BYTES,
+    const MAX_BYTES_PER_TRANSACTION,
+    const MAX_TRANSACTIONS_PER_PAYLOAD,
+    const MAX_WITHDRAWALS_PER_PAYLOAD,
+    const MAX_BLS_TO_EXECUTION_CHANGES,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+> {
+    slot: Slot,
+    proposer_index: ValidatorIndex,
+    parent_root: Root,
+    state_root: Root,
+    body: BeaconBlockBody<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >,
+});
+
+crate::ssz_size::impl_ssz_size!(SignedBeaconBlock<
+    const MAX_PROPOSER_SLASHINGS,
+    const MAX_VALIDATORS_PER_SLOT,
+    const MAX_COMMITTEES_PER_SLOT,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA,
+    const MAX_ATTESTATIONS_ELECTRA,
+    const MAX_DEPOSITS,
+    const MAX_VOLUNTARY_EXITS,
+    const SYNC_COMMITTEE_SIZE,
+    const BYTES_PER_LOGS_BLOOM,
+    const MAX_EXTRA_DATA_BYTES,
+    const MAX_BYTES_PER_TRANSACTION,
+    const MAX_TRANSACTIONS_PER_PAYLOAD,
+    const MAX_WITHDRAWALS_PER_PAYLOAD,
+    const MAX_BLS_TO_EXECUTION_CHANGES,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+> {
+    message: BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >,
+    signature: BlsSignature,
+});
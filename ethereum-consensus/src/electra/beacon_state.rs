@@ -1,19 +1,21 @@
 use crate::{
     altair::SyncCommittee,
     capella::HistoricalSummary,
-    electra::ExecutionPayloadHeader,
+    deneb::ExecutionPayloadHeader,
     phase0::{BeaconBlockHeader, Checkpoint, Eth1Data, Fork, Validator, JUSTIFICATION_BITS_LENGTH},
     primitives::{
-        BlsPublicKey, BlsSignature, Bytes32, Epoch, ExecutionAddress, Gwei, ParticipationFlags,
-        Root, Slot, ValidatorIndex, WithdrawalIndex,
+        BlsPublicKey, BlsSignature, Bytes32, Epoch, Gwei, ParticipationFlags, Root, Slot,
+        ValidatorIndex, WithdrawalIndex,
     },
     ssz::prelude::*,
 };
 
+pub use crate::phase0::beacon_state::compute_state_field_roots;
+
 #[derive(
     Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
 )]
-pub struct DepositReceipt {
+pub struct PendingDeposit {
     #[serde(rename = "pubkey")]
     pub public_key: BlsPublicKey,
     pub withdrawal_credentials: Bytes32,
@@ -21,17 +23,7 @@ pub struct DepositReceipt {
     pub amount: Gwei,
     pub signature: BlsSignature,
     #[serde(with = "crate::serde::as_str")]
-    pub index: u64,
-}
-
-#[derive(
-    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
-)]
-pub struct PendingBalanceDeposit {
-    #[serde(with = "crate::serde::as_str")]
-    pub index: ValidatorIndex,
-    #[serde(with = "crate::serde::as_str")]
-    pub amount: Gwei,
+    pub slot: Slot,
 }
 
 #[derive(
@@ -39,7 +31,7 @@ pub struct PendingBalanceDeposit {
 )]
 pub struct PendingPartialWithdrawal {
     #[serde(with = "crate::serde::as_str")]
-    pub index: ValidatorIndex,
+    pub validator_index: ValidatorIndex,
     #[serde(with = "crate::serde::as_str")]
     pub amount: Gwei,
     #[serde(with = "crate::serde::as_str")]
@@ -56,17 +48,6 @@ pub struct PendingConsolidation {
     pub target_index: ValidatorIndex,
 }
 
-#[derive(
-    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
-)]
-pub struct ExecutionLayerWithdrawalRequest {
-    pub source_address: ExecutionAddress,
-    #[serde(rename = "pubkey")]
-    pub validator_public_key: BlsPublicKey,
-    #[serde(with = "crate::serde::as_str")]
-    pub amount: Gwei,
-}
-
 #[derive(
     Default, Debug, SimpleSerialize, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize,
 )]
@@ -81,7 +62,7 @@ pub struct BeaconState<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
-    const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
     const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
     const PENDING_CONSOLIDATIONS_LIMIT: usize,
 > {
@@ -125,7 +106,7 @@ pub struct BeaconState<
     pub next_withdrawal_validator_index: ValidatorIndex,
     pub historical_summaries: List<HistoricalSummary, HISTORICAL_ROOTS_LIMIT>,
     #[serde(with = "crate::serde::as_str")]
-    pub deposit_receipts_start_index: u64,
+    pub deposit_requests_start_index: u64,
     #[serde(with = "crate::serde::as_str")]
     pub deposit_balance_to_consume: Gwei,
     #[serde(with = "crate::serde::as_str")]
@@ -136,8 +117,136 @@ pub struct BeaconState<
     pub consolidation_balance_to_consume: Gwei,
     #[serde(with = "crate::serde::as_str")]
     pub earliest_consolidation_epoch: Epoch,
-    pub pending_balance_deposits: List<PendingBalanceDeposit, PENDING_BALANCE_DEPOSITS_LIMIT>,
+    pub pending_deposits: List<PendingDeposit, PENDING_DEPOSITS_LIMIT>,
     pub pending_partial_withdrawals:
         List<PendingPartialWithdrawal, PENDING_PARTIAL_WITHDRAWALS_LIMIT>,
     pub pending_consolidations: List<PendingConsolidation, PENDING_CONSOLIDATIONS_LIMIT>,
 }
+
+crate::ssz::impl_field_roots!(BeaconState<
+    const SLOTS_PER_HISTORICAL_ROOT,
+    const HISTORICAL_ROOTS_LIMIT,
+    const ETH1_DATA_VOTES_BOUND,
+    const VALIDATOR_REGISTRY_LIMIT,
+    const EPOCHS_PER_HISTORICAL_VECTOR,
+    const EPOCHS_PER_SLASHINGS_VECTOR,
+    const MAX_VALIDATORS_PER_COMMITTEE,
+    const SYNC_COMMITTEE_SIZE,
+    const BYTES_PER_LOGS_BLOOM,
+    const MAX_EXTRA_DATA_BYTES,
+    const PENDING_DEPOSITS_LIMIT,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+    const PENDING_CONSOLIDATIONS_LIMIT,
+> {
+    genesis_time,
+    genesis_validators_root,
+    slot,
+    fork,
+    latest_block_header,
+    block_roots,
+    state_roots,
+    historical_roots,
+    eth1_data,
+    eth1_data_votes,
+    eth1_deposit_index,
+    validators,
+    balances,
+    randao_mixes,
+    slashings,
+    previous_epoch_participation,
+    current_epoch_participation,
+    justification_bits,
+    previous_justified_checkpoint,
+    current_justified_checkpoint,
+    finalized_checkpoint,
+    inactivity_scores,
+    current_sync_committee,
+    next_sync_committee,
+    latest_execution_payload_header,
+    next_withdrawal_index,
+    next_withdrawal_validator_index,
+    historical_summaries,
+    deposit_requests_start_index,
+    deposit_balance_to_consume,
+    exit_balance_to_consume,
+    earliest_exit_epoch,
+    consolidation_balance_to_consume,
+    earliest_consolidation_epoch,
+    pending_deposits,
+    pending_partial_withdrawals,
+    pending_consolidations,
+});
+
+crate::ssz_size::impl_ssz_size!(PendingDeposit {
+    public_key: BlsPublicKey,
+    withdrawal_credentials: Bytes32,
+    amount: Gwei,
+    signature: BlsSignature,
+    slot: Slot,
+});
+
+crate::ssz_size::impl_ssz_size!(PendingPartialWithdrawal {
+    validator_index: ValidatorIndex,
+    amount: Gwei,
+    withdrawable_epoch: Epoch,
+});
+
+crate::ssz_size::impl_ssz_size!(PendingConsolidation {
+    source_index: ValidatorIndex,
+    target_index: ValidatorIndex,
+});
+
+crate::ssz_size::impl_ssz_size!(BeaconState<
+    const SLOTS_PER_HISTORICAL_ROOT,
+    const HISTORICAL_ROOTS_LIMIT,
+    const ETH1_DATA_VOTES_BOUND,
+    const VALIDATOR_REGISTRY_LIMIT,
+    const EPOCHS_PER_HISTORICAL_VECTOR,
+    const EPOCHS_PER_SLASHINGS_VECTOR,
+    const MAX_VALIDATORS_PER_COMMITTEE,
+    const SYNC_COMMITTEE_SIZE,
+    const BYTES_PER_LOGS_BLOOM,
+    const MAX_EXTRA_DATA_BYTES,
+    const PENDING_DEPOSITS_LIMIT,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+    const PENDING_CONSOLIDATIONS_LIMIT,
+> {
+    genesis_time: u64,
+    genesis_validators_root: Root,
+    slot: Slot,
+    fork: Fork,
+    latest_block_header: BeaconBlockHeader,
+    block_roots: Vector<Root, SLOTS_PER_HISTORICAL_ROOT>,
+    state_roots: Vector<Root, SLOTS_PER_HISTORICAL_ROOT>,
+    historical_roots: List<Root, HISTORICAL_ROOTS_LIMIT>,
+    eth1_data: Eth1Data,
+    eth1_data_votes: List<Eth1Data, ETH1_DATA_VOTES_BOUND>,
+    eth1_deposit_index: u64,
+    validators: List<Validator, VALIDATOR_REGISTRY_LIMIT>,
+    balances: List<Gwei, VALIDATOR_REGISTRY_LIMIT>,
+    randao_mixes: Vector<Bytes32, EPOCHS_PER_HISTORICAL_VECTOR>,
+    slashings: Vector<Gwei, EPOCHS_PER_SLASHINGS_VECTOR>,
+    previous_epoch_participation: List<ParticipationFlags, VALIDATOR_REGISTRY_LIMIT>,
+    current_epoch_participation: List<ParticipationFlags, VALIDATOR_REGISTRY_LIMIT>,
+    justification_bits: Bitvector<JUSTIFICATION_BITS_LENGTH>,
+    previous_justified_checkpoint: Checkpoint,
+    current_justified_checkpoint: Checkpoint,
+    finalized_checkpoint: Checkpoint,
+    inactivity_scores: List<u64, VALIDATOR_REGISTRY_LIMIT>,
+    current_sync_committee: SyncCommittee<SYNC_COMMITTEE_SIZE>,
+    next_sync_committee: SyncCommittee<SYNC_COMMITTEE_SIZE>,
+    latest_execution_payload_header:
+        ExecutionPayloadHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    next_withdrawal_index: WithdrawalIndex,
+    next_withdrawal_validator_index: ValidatorIndex,
+    historical_summaries: List<HistoricalSummary, HISTORICAL_ROOTS_LIMIT>,
+    deposit_requests_start_index: u64,
+    deposit_balance_to_consume: Gwei,
+    exit_balance_to_consume: Gwei,
+    earliest_exit_epoch: Epoch,
+    consolidation_balance_to_consume: Gwei,
+    earliest_consolidation_epoch: Epoch,
+    pending_deposits: List<PendingDeposit, PENDING_DEPOSITS_LIMIT>,
+    pending_partial_withdrawals: List<PendingPartialWithdrawal, PENDING_PARTIAL_WITHDRAWALS_LIMIT>,
+    pending_consolidations: List<PendingConsolidation, PENDING_CONSOLIDATIONS_LIMIT>,
+});
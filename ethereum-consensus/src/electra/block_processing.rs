@@ -1,6 +1,38 @@
 use crate::{
-    electra::{Attestation, BeaconBlockBody, BeaconState},
-    state_transition::Context,
+    da_check::scheduled_max_blobs_per_block,
+    electra::{
+        add_flag, compute_consolidation_epoch_and_update_churn, compute_domain,
+        compute_epoch_at_slot, compute_exit_epoch_and_update_churn, compute_timestamp_at_slot,
+        decrease_balance, get_attestation_participation_flag_indices, get_attesting_indices,
+        get_base_reward, get_beacon_committee, get_beacon_proposer_index,
+        get_committee_count_per_slot, get_committee_indices, get_consolidation_churn_limit,
+        get_current_epoch, get_indexed_attestation, get_max_effective_balance,
+        get_pending_balance_to_withdraw, get_previous_epoch, get_randao_mix,
+        has_compounding_withdrawal_credential, has_eth1_withdrawal_credential,
+        has_execution_withdrawal_credential, has_flag, increase_balance, initiate_validator_exit,
+        is_active_validator, is_fully_withdrawable_validator, is_partially_withdrawable_validator,
+        is_valid_indexed_attestation_with_validation, kzg_commitment_to_versioned_hash,
+        process_attester_slashing_with_validation, process_bls_to_execution_change_with_validation,
+        process_deposit, process_proposer_slashing_with_validation,
+        switch_to_compounding_validator, Attestation, BeaconBlockBody, BeaconState,
+        ConsolidationRequest, DepositMessage, DepositRequest, ExecutionAddress, ExecutionPayload,
+        ExecutionPayloadHeader, NewPayloadRequest, PendingConsolidation, PendingDeposit,
+        PendingPartialWithdrawal, SignedVoluntaryExit, Validator, Withdrawal, WithdrawalRequest,
+        FULL_EXIT_REQUEST_AMOUNT, PARTICIPATION_FLAG_WEIGHTS, UNSET_DEPOSIT_REQUESTS_START_INDEX,
+    },
+    error::{
+        invalid_operation_error, InvalidAttestation, InvalidDeposit, InvalidExecutionPayload,
+        InvalidOperation, InvalidVoluntaryExit, InvalidWithdrawals,
+    },
+    execution_engine::ExecutionEngine,
+    primitives::{
+        saturating_usize, BlsPublicKey, BlsSignature, Bytes32, DomainType, Gwei,
+        ParticipationFlags, ValidatorIndex, FAR_FUTURE_EPOCH, GENESIS_SLOT,
+    },
+    rewards::proposer_attestation_reward,
+    signing::verify_signed_data,
+    ssz::prelude::*,
+    state_transition::{Context, Validation},
     Error,
 };
 
@@ -15,13 +47,13 @@ pub fn process_attestation<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
-    const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
     const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
     const PENDING_CONSOLIDATIONS_LIMIT: usize,
     const MAX_VALIDATORS_PER_SLOT: usize,
     const MAX_COMMITTEES_PER_SLOT: usize,
 >(
-    _state: &mut BeaconState<
+    state: &mut BeaconState<
         SLOTS_PER_HISTORICAL_ROOT,
         HISTORICAL_ROOTS_LIMIT,
         ETH1_DATA_VOTES_BOUND,
@@ -32,14 +64,166 @@ pub fn process_attestation<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
-        PENDING_BALANCE_DEPOSITS_LIMIT,
+        PENDING_DEPOSITS_LIMIT,
         PENDING_PARTIAL_WITHDRAWALS_LIMIT,
         PENDING_CONSOLIDATIONS_LIMIT,
     >,
-    _attestation: &Attestation<MAX_VALIDATORS_PER_SLOT, MAX_COMMITTEES_PER_SLOT>,
-    _context: &Context,
+    attestation: &Attestation<MAX_VALIDATORS_PER_SLOT, MAX_COMMITTEES_PER_SLOT>,
+    context: &Context,
 ) -> Result<(), Error> {
-    todo!()
+    process_attestation_with_validation(state, attestation, Validation::Enabled, context)
+}
+
+pub fn process_attestation_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    attestation: &Attestation<MAX_VALIDATORS_PER_SLOT, MAX_COMMITTEES_PER_SLOT>,
+    validation: Validation,
+    context: &Context,
+) -> Result<(), Error> {
+    let data = &attestation.data;
+    let is_previous = data.target.epoch == get_previous_epoch(state, context);
+    let current_epoch = get_current_epoch(state, context);
+    let is_current = data.target.epoch == current_epoch;
+    let valid_target_epoch = is_previous || is_current;
+    if !valid_target_epoch {
+        return Err(invalid_operation_error(InvalidOperation::Attestation(
+            InvalidAttestation::InvalidTargetEpoch {
+                target: data.target.epoch,
+                current: current_epoch,
+            },
+        )))
+    }
+    let attestation_epoch = compute_epoch_at_slot(data.slot, context);
+    if data.target.epoch != attestation_epoch {
+        return Err(invalid_operation_error(InvalidOperation::Attestation(
+            InvalidAttestation::InvalidSlot {
+                slot: data.slot,
+                epoch: attestation_epoch,
+                target: data.target.epoch,
+            },
+        )))
+    }
+    let attestation_is_timely = data.slot + context.min_attestation_inclusion_delay <= state.slot;
+    if !attestation_is_timely {
+        return Err(invalid_operation_error(InvalidOperation::Attestation(
+            InvalidAttestation::NoDelay {
+                attestation_slot: data.slot,
+                state_slot: state.slot,
+                required_delay: context.min_attestation_inclusion_delay,
+            },
+        )))
+    }
+
+    // The committees are given by the committee bits, so the index of the data is unused
+    if data.index != 0 {
+        return Err(invalid_operation_error(InvalidOperation::Attestation(
+            InvalidAttestation::NonZeroIndex(data.index),
+        )))
+    }
+    let committee_count = get_committee_count_per_slot(state, data.target.epoch, context);
+    let mut committee_offset = 0;
+    for committee_index in get_committee_indices(&attestation.committee_bits) {
+        if committee_index >= committee_count {
+            return Err(invalid_operation_error(InvalidOperation::Attestation(
+                InvalidAttestation::InvalidIndex {
+                    index: committee_index,
+                    upper_bound: committee_count,
+                },
+            )))
+        }
+        let committee = get_beacon_committee(state, data.slot, committee_index, context)?;
+        let committee_end = committee_offset + committee.len();
+        if attestation.aggregation_bits.len() < committee_end {
+            return Err(invalid_operation_error(InvalidOperation::Attestation(
+                InvalidAttestation::Bitfield {
+                    expected_length: committee_end,
+                    length: attestation.aggregation_bits.len(),
+                },
+            )))
+        }
+        let has_attester =
+            (committee_offset..committee_end).any(|i| attestation.aggregation_bits[i]);
+        if !has_attester {
+            return Err(invalid_operation_error(InvalidOperation::Attestation(
+                InvalidAttestation::EmptyCommittee(committee_index),
+            )))
+        }
+        committee_offset = committee_end;
+    }
+    if attestation.aggregation_bits.len() != committee_offset {
+        return Err(invalid_operation_error(InvalidOperation::Attestation(
+            InvalidAttestation::Bitfield {
+                expected_length: committee_offset,
+                length: attestation.aggregation_bits.len(),
+            },
+        )))
+    }
+
+    let inclusion_delay = state.slot - data.slot;
+    let participation_flag_indices =
+        get_attestation_participation_flag_indices(state, data, inclusion_delay, context)?;
+    if !validation.is_assumed_finalized(data.target.epoch) {
+        is_valid_indexed_attestation_with_validation(
+            state,
+            &get_indexed_attestation(state, attestation, context)?,
+            validation,
+            context,
+        )?;
+    }
+    let attesting_indices = get_attesting_indices(state, attestation, context)?;
+    let mut proposer_reward_numerator = 0;
+    for index in attesting_indices {
+        for (flag_index, weight) in PARTICIPATION_FLAG_WEIGHTS.iter().enumerate() {
+            if is_current {
+                if participation_flag_indices.contains(&flag_index) &&
+                    !has_flag(state.current_epoch_participation[index], flag_index)
+                {
+                    state.current_epoch_participation[index] =
+                        add_flag(state.current_epoch_participation[index], flag_index);
+                    proposer_reward_numerator += get_base_reward(state, index, context)? * weight;
+                }
+            } else if participation_flag_indices.contains(&flag_index) &&
+                !has_flag(state.previous_epoch_participation[index], flag_index)
+            {
+                state.previous_epoch_participation[index] =
+                    add_flag(state.previous_epoch_participation[index], flag_index);
+                proposer_reward_numerator += get_base_reward(state, index, context)? * weight;
+            }
+        }
+    }
+    let proposer_reward = proposer_attestation_reward(proposer_reward_numerator);
+    increase_balance(state, get_beacon_proposer_index(state, context)?, proposer_reward);
+    Ok(())
 }
 
 pub fn process_execution_payload<
@@ -53,7 +237,7 @@ pub fn process_execution_payload<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
-    const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
     const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
     const PENDING_CONSOLIDATIONS_LIMIT: usize,
     const MAX_PROPOSER_SLASHINGS: usize,
@@ -66,13 +250,13 @@ pub fn process_execution_payload<
     const MAX_BYTES_PER_TRANSACTION: usize,
     const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
     const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
-    const MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD: usize,
-    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
     const MAX_BLS_TO_EXECUTION_CHANGES: usize,
     const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
-    const MAX_CONSOLIDATIONS: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
 >(
-    _state: &mut BeaconState<
+    state: &mut BeaconState<
         SLOTS_PER_HISTORICAL_ROOT,
         HISTORICAL_ROOTS_LIMIT,
         ETH1_DATA_VOTES_BOUND,
@@ -83,11 +267,11 @@ pub fn process_execution_payload<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
-        PENDING_BALANCE_DEPOSITS_LIMIT,
+        PENDING_DEPOSITS_LIMIT,
         PENDING_PARTIAL_WITHDRAWALS_LIMIT,
         PENDING_CONSOLIDATIONS_LIMIT,
     >,
-    _body: &BeaconBlockBody<
+    body: &BeaconBlockBody<
         MAX_PROPOSER_SLASHINGS,
         MAX_VALIDATORS_PER_SLOT,
         MAX_COMMITTEES_PER_SLOT,
@@ -101,13 +285,1691 @@ pub fn process_execution_payload<
         MAX_BYTES_PER_TRANSACTION,
         MAX_TRANSACTIONS_PER_PAYLOAD,
         MAX_WITHDRAWALS_PER_PAYLOAD,
-        MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
         MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >,
+    context: &Context,
+) -> Result<(), Error> {
+    let payload = &body.execution_payload;
+
+    let parent_hash_invalid =
+        payload.parent_hash != state.latest_execution_payload_header.block_hash;
+    if parent_hash_invalid {
+        return Err(invalid_operation_error(
+            InvalidExecutionPayload::InvalidParentHash {
+                provided: payload.parent_hash.clone(),
+                expected: state.latest_execution_payload_header.block_hash.clone(),
+            }
+            .into(),
+        ))
+    }
+
+    let current_epoch = get_current_epoch(state, context);
+    let randao_mix = get_randao_mix(state, current_epoch);
+    if &payload.prev_randao != randao_mix {
+        return Err(invalid_operation_error(
+            InvalidExecutionPayload::InvalidPrevRandao {
+                provided: payload.prev_randao.clone(),
+                expected: randao_mix.clone(),
+            }
+            .into(),
+        ))
+    }
+
+    let timestamp = compute_timestamp_at_slot(state, state.slot, context)?;
+    if payload.timestamp != timestamp {
+        return Err(invalid_operation_error(
+            InvalidExecutionPayload::InvalidTimestamp {
+                provided: payload.timestamp,
+                expected: timestamp,
+            }
+            .into(),
+        ))
+    }
+
+    let max_blobs_per_block = scheduled_max_blobs_per_block(current_epoch, context);
+    if body.blob_kzg_commitments.len() > max_blobs_per_block {
+        return Err(invalid_operation_error(
+            InvalidExecutionPayload::InvalidBlobCommitments {
+                provided: body.blob_kzg_commitments.len(),
+                limit: max_blobs_per_block,
+            }
+            .into(),
+        ))
+    }
+
+    let versioned_hashes =
+        body.blob_kzg_commitments.iter().map(kzg_commitment_to_versioned_hash).collect::<Vec<_>>();
+
+    let execution_engine = context.execution_engine();
+    let new_payload_request = NewPayloadRequest {
+        execution_payload: payload.clone(),
+        versioned_hashes,
+        parent_beacon_block_root: state.latest_block_header.parent_root,
+        execution_requests: body.execution_requests.clone(),
+    };
+    execution_engine.verify_and_notify_new_payload(&new_payload_request)?;
+
+    state.latest_execution_payload_header = ExecutionPayloadHeader {
+        parent_hash: payload.parent_hash.clone(),
+        fee_recipient: payload.fee_recipient.clone(),
+        state_root: payload.state_root.clone(),
+        receipts_root: payload.receipts_root.clone(),
+        logs_bloom: payload.logs_bloom.clone(),
+        prev_randao: payload.prev_randao.clone(),
+        block_number: payload.block_number,
+        gas_limit: payload.gas_limit,
+        gas_used: payload.gas_used,
+        timestamp: payload.timestamp,
+        extra_data: payload.extra_data.clone(),
+        base_fee_per_gas: payload.base_fee_per_gas,
+        block_hash: payload.block_hash.clone(),
+        transactions_root: payload.transactions.hash_tree_root()?,
+        withdrawals_root: payload.withdrawals.hash_tree_root()?,
+        blob_gas_used: payload.blob_gas_used,
+        excess_blob_gas: payload.excess_blob_gas,
+    };
+
+    Ok(())
+}
+
+pub fn process_voluntary_exit<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    signed_voluntary_exit: &SignedVoluntaryExit,
+    context: &Context,
+) -> Result<(), Error> {
+    process_voluntary_exit_with_validation(
+        state,
+        signed_voluntary_exit,
+        Validation::Enabled,
+        context,
+    )
+}
+
+/// Process a voluntary exit, which is only valid for a validator without pending partial
+/// withdrawals.
+pub fn process_voluntary_exit_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    signed_voluntary_exit: &SignedVoluntaryExit,
+    validation: Validation,
+    context: &Context,
+) -> Result<(), Error> {
+    let voluntary_exit = &signed_voluntary_exit.message;
+    let validator = state.validators.get(voluntary_exit.validator_index).ok_or_else(|| {
+        invalid_operation_error(InvalidOperation::VoluntaryExit(
+            InvalidVoluntaryExit::InvalidIndex(voluntary_exit.validator_index),
+        ))
+    })?;
+    let current_epoch = get_current_epoch(state, context);
+    if !is_active_validator(validator, current_epoch) {
+        return Err(invalid_operation_error(InvalidOperation::VoluntaryExit(
+            InvalidVoluntaryExit::InactiveValidator(current_epoch),
+        )))
+    }
+    if validator.exit_epoch != FAR_FUTURE_EPOCH {
+        return Err(invalid_operation_error(InvalidOperation::VoluntaryExit(
+            InvalidVoluntaryExit::ValidatorAlreadyExited {
+                index: voluntary_exit.validator_index,
+                epoch: validator.exit_epoch,
+            },
+        )))
+    }
+    if current_epoch < voluntary_exit.epoch {
+        return Err(invalid_operation_error(InvalidOperation::VoluntaryExit(
+            InvalidVoluntaryExit::EarlyExit { current_epoch, exit_epoch: voluntary_exit.epoch },
+        )))
+    }
+    let minimum_time_active =
+        validator.activation_eligibility_epoch + context.shard_committee_period;
+    if current_epoch < minimum_time_active {
+        return Err(invalid_operation_error(InvalidOperation::VoluntaryExit(
+            InvalidVoluntaryExit::ValidatorIsNotActiveForLongEnough {
+                current_epoch,
+                minimum_time_active,
+            },
+        )))
+    }
+    // Only exit a validator without partial withdrawals still to be made
+    let pending_balance_to_withdraw =
+        get_pending_balance_to_withdraw(state, voluntary_exit.validator_index);
+    if pending_balance_to_withdraw != 0 {
+        return Err(invalid_operation_error(InvalidOperation::VoluntaryExit(
+            InvalidVoluntaryExit::PendingWithdrawals {
+                index: voluntary_exit.validator_index,
+                amount: pending_balance_to_withdraw,
+            },
+        )))
+    }
+    if validation.verifies_signatures() {
+        let domain = compute_domain(
+            DomainType::VoluntaryExit,
+            Some(context.capella_fork_version),
+            Some(state.genesis_validators_root),
+            context,
+        )?;
+        let public_key = &validator.public_key;
+        verify_signed_data(voluntary_exit, &signed_voluntary_exit.signature, public_key, domain)
+            .map_err(|_| {
+                invalid_operation_error(InvalidOperation::VoluntaryExit(
+                    InvalidVoluntaryExit::InvalidSignature(signed_voluntary_exit.signature.clone()),
+                ))
+            })?;
+    }
+    initiate_validator_exit(state, voluntary_exit.validator_index, context)?;
+    Ok(())
+}
+pub fn process_withdrawals<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    execution_payload: &ExecutionPayload<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+    >,
+    context: &Context,
+) -> Result<(), Error> {
+    let (expected_withdrawals, processed_partial_withdrawals_count) =
+        get_expected_withdrawals(state, context);
+
+    if execution_payload.withdrawals.as_ref() != expected_withdrawals {
+        return Err(invalid_operation_error(InvalidOperation::Withdrawal(
+            InvalidWithdrawals::IncorrectWithdrawals {
+                provided: execution_payload.withdrawals.to_vec(),
+                expected: expected_withdrawals,
+            },
+        )))
+    }
+
+    for withdrawal in &expected_withdrawals {
+        decrease_balance(state, withdrawal.validator_index, withdrawal.amount);
+    }
+
+    // Drop the pending partial withdrawals consumed by this block
+    let pending_partial_withdrawals =
+        state.pending_partial_withdrawals[processed_partial_withdrawals_count..].to_vec();
+    state.pending_partial_withdrawals =
+        List::try_from(pending_partial_withdrawals).expect("fewer entries than before");
+
+    // Update the next withdrawal index if this block contained withdrawals
+    if let Some(latest_withdrawal) = expected_withdrawals.last() {
+        state.next_withdrawal_index = latest_withdrawal.index + 1;
+    }
+
+    // Update the next validator index to start the next withdrawal sweep
+    if expected_withdrawals.len() == context.max_withdrawals_per_payload {
+        // Next sweep starts after the latest withdrawal's validator index
+        let latest_withdrawal = expected_withdrawals.last().expect("empty withdrawals");
+        let next_validator_index = (latest_withdrawal.validator_index + 1) % state.validators.len();
+        state.next_withdrawal_validator_index = next_validator_index;
+    } else {
+        // Advance sweep by the max length of the sweep if there was not a full set of withdrawals
+        let next_index =
+            state.next_withdrawal_validator_index + context.max_validators_per_withdrawals_sweep;
+        state.next_withdrawal_validator_index = next_index % state.validators.len();
+    }
+    Ok(())
+}
+
+/// The withdrawals the next block on top of `state` must carry, and how many of the pending
+/// partial withdrawals of `state` they consume. The pending partial withdrawals come first, then
+/// those of the validator sweep.
+pub fn get_expected_withdrawals<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    context: &Context,
+) -> (Vec<Withdrawal>, usize) {
+    let epoch = get_current_epoch(state, context);
+    let mut withdrawal_index = state.next_withdrawal_index;
+    let mut validator_index = state.next_withdrawal_validator_index;
+    let mut withdrawals = vec![];
+    let address_of = |credentials: &[u8]| {
+        ExecutionAddress::try_from(&credentials[12..])
+            .expect("providing the correct amount of input to type")
+    };
+
+    let mut processed_partial_withdrawals_count = 0;
+    for withdrawal in state.pending_partial_withdrawals.iter() {
+        if withdrawal.withdrawable_epoch > epoch ||
+            withdrawals.len() == context.max_pending_partials_per_withdrawals_sweep
+        {
+            break
+        }
+        let validator = &state.validators[withdrawal.validator_index];
+        // an earlier request of the same validator may already withdraw part of the balance
+        let withdrawn = withdrawals
+            .iter()
+            .filter(|w| w.validator_index == withdrawal.validator_index)
+            .map(|w| w.amount)
+            .sum::<u64>();
+        let balance = state.balances[withdrawal.validator_index].saturating_sub(withdrawn);
+        let has_sufficient_effective_balance =
+            validator.effective_balance >= context.min_activation_balance;
+        let has_excess_balance = balance > context.min_activation_balance;
+        if validator.exit_epoch == FAR_FUTURE_EPOCH &&
+            has_sufficient_effective_balance &&
+            has_excess_balance
+        {
+            let amount = withdrawal.amount.min(balance - context.min_activation_balance);
+            withdrawals.push(Withdrawal {
+                index: withdrawal_index,
+                validator_index: withdrawal.validator_index,
+                address: address_of(validator.withdrawal_credentials.as_slice()),
+                amount,
+            });
+            withdrawal_index += 1;
+        }
+        processed_partial_withdrawals_count += 1;
+    }
+
+    let bound = state.validators.len().min(context.max_validators_per_withdrawals_sweep);
+    for _ in 0..bound {
+        let validator = &state.validators[validator_index];
+        // the sweep sees the balance left by the partial withdrawals above
+        let withdrawn = withdrawals
+            .iter()
+            .filter(|withdrawal| withdrawal.validator_index == validator_index)
+            .map(|withdrawal| withdrawal.amount)
+            .sum::<u64>();
+        let balance = state.balances[validator_index].saturating_sub(withdrawn);
+        if is_fully_withdrawable_validator(validator, balance, epoch) {
+            withdrawals.push(Withdrawal {
+                index: withdrawal_index,
+                validator_index,
+                address: address_of(validator.withdrawal_credentials.as_slice()),
+                amount: balance,
+            });
+            withdrawal_index += 1;
+        } else if is_partially_withdrawable_validator(validator, balance, context) {
+            withdrawals.push(Withdrawal {
+                index: withdrawal_index,
+                validator_index,
+                address: address_of(validator.withdrawal_credentials.as_slice()),
+                amount: balance - get_max_effective_balance(validator, context),
+            });
+            withdrawal_index += 1;
+        }
+        if withdrawals.len() == context.max_withdrawals_per_payload {
+            break
+        }
+        validator_index = (validator_index + 1) % state.validators.len();
+    }
+    (withdrawals, processed_partial_withdrawals_count)
+}
+
+pub fn process_operations<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    body: &BeaconBlockBody<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
-        MAX_CONSOLIDATIONS,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >,
-    _context: &Context,
+    context: &Context,
 ) -> Result<(), Error> {
-    todo!();
+    process_operations_with_validation(state, body, Validation::Enabled, context)
+}
+
+pub fn process_operations_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    body: &BeaconBlockBody<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >,
+    validation: Validation,
+    context: &Context,
+) -> Result<(), Error> {
+    // Deposits from the deposit contract are only expected until they reach the first deposit
+    // made by request, after which the former mechanism is disabled
+    let eth1_deposit_index_limit =
+        state.eth1_data.deposit_count.min(state.deposit_requests_start_index);
+    let expected_deposit_count = if state.eth1_deposit_index < eth1_deposit_index_limit {
+        context
+            .max_deposits
+            .min(saturating_usize(eth1_deposit_index_limit - state.eth1_deposit_index))
+    } else {
+        0
+    };
+    if body.deposits.len() != expected_deposit_count {
+        return Err(invalid_operation_error(InvalidOperation::Deposit(
+            InvalidDeposit::IncorrectCount {
+                expected: expected_deposit_count,
+                count: body.deposits.len(),
+            },
+        )))
+    }
+    traced!(
+        "proposer_slashings",
+        body.proposer_slashings.iter().try_for_each(
+            |op| process_proposer_slashing_with_validation(state, op, validation, context)
+        ),
+        count = body.proposer_slashings.len()
+    )?;
+    traced!(
+        "attester_slashings",
+        body.attester_slashings.iter().try_for_each(
+            |op| process_attester_slashing_with_validation(state, op, validation, context)
+        ),
+        count = body.attester_slashings.len()
+    )?;
+    traced!(
+        "attestations",
+        body.attestations
+            .iter()
+            .try_for_each(|op| process_attestation_with_validation(state, op, validation, context)),
+        count = body.attestations.len()
+    )?;
+    traced!(
+        "deposits",
+        body.deposits.iter().try_for_each(|op| process_deposit(state, op, context)),
+        count = body.deposits.len()
+    )?;
+    traced!(
+        "voluntary_exits",
+        body.voluntary_exits.iter().try_for_each(|op| process_voluntary_exit_with_validation(
+            state, op, validation, context
+        )),
+        count = body.voluntary_exits.len()
+    )?;
+    traced!(
+        "bls_to_execution_changes",
+        body.bls_to_execution_changes.iter().try_for_each(|op| {
+            process_bls_to_execution_change_with_validation(state, op, validation, context)
+        }),
+        count = body.bls_to_execution_changes.len()
+    )?;
+    traced!(
+        "deposit_requests",
+        body.execution_requests.deposits.iter().for_each(|op| process_deposit_request(state, op)),
+        count = body.execution_requests.deposits.len()
+    );
+    traced!(
+        "withdrawal_requests",
+        body.execution_requests
+            .withdrawals
+            .iter()
+            .try_for_each(|op| process_withdrawal_request(state, op, context)),
+        count = body.execution_requests.withdrawals.len()
+    )?;
+    traced!(
+        "consolidation_requests",
+        body.execution_requests
+            .consolidations
+            .iter()
+            .try_for_each(|op| process_consolidation_request(state, op, context)),
+        count = body.execution_requests.consolidations.len()
+    )?;
+    Ok(())
+}
+
+/// The validator for a new deposit, with an effective balance capped by the maximum for its
+/// withdrawal credentials.
+pub fn get_validator_from_deposit(
+    public_key: BlsPublicKey,
+    withdrawal_credentials: Bytes32,
+    amount: Gwei,
+    context: &Context,
+) -> Validator {
+    let mut validator = Validator {
+        public_key,
+        withdrawal_credentials,
+        activation_eligibility_epoch: FAR_FUTURE_EPOCH,
+        activation_epoch: FAR_FUTURE_EPOCH,
+        exit_epoch: FAR_FUTURE_EPOCH,
+        withdrawable_epoch: FAR_FUTURE_EPOCH,
+        ..Default::default()
+    };
+    let max_effective_balance = get_max_effective_balance(&validator, context);
+    validator.effective_balance =
+        Gwei::min(amount - amount % context.effective_balance_increment, max_effective_balance);
+    validator
+}
+
+pub fn add_validator_to_registry<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    public_key: BlsPublicKey,
+    withdrawal_credentials: Bytes32,
+    amount: Gwei,
+    context: &Context,
+) {
+    state.validators.push(get_validator_from_deposit(
+        public_key,
+        withdrawal_credentials,
+        amount,
+        context,
+    ));
+    state.balances.push(amount);
+    state.previous_epoch_participation.push(ParticipationFlags::default());
+    state.current_epoch_participation.push(ParticipationFlags::default());
+    state.inactivity_scores.push(0);
+}
+
+/// Verify the signature of a deposit, a proof of possession not checked by the deposit contract.
+pub fn is_valid_deposit_signature(
+    public_key: &BlsPublicKey,
+    withdrawal_credentials: &Bytes32,
+    amount: Gwei,
+    signature: &BlsSignature,
+    context: &Context,
+) -> Result<bool, Error> {
+    let deposit_message = DepositMessage {
+        public_key: public_key.clone(),
+        withdrawal_credentials: withdrawal_credentials.clone(),
+        amount,
+    };
+    let domain = compute_domain(DomainType::Deposit, None, None, context)?;
+    Ok(verify_signed_data(&deposit_message, signature, public_key, domain).is_ok())
+}
+
+/// Apply a deposit from the deposit contract: a new validator is added without balance if the
+/// signature is valid, and the amount is queued to be applied within the activation churn.
+pub fn apply_deposit<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    public_key: &BlsPublicKey,
+    withdrawal_credentials: &Bytes32,
+    amount: Gwei,
+    signature: &BlsSignature,
+    context: &Context,
+) -> Result<(), Error> {
+    let is_new_validator = !state.validators.iter().any(|v| v.public_key == *public_key);
+    if is_new_validator {
+        if !is_valid_deposit_signature(
+            public_key,
+            withdrawal_credentials,
+            amount,
+            signature,
+            context,
+        )? {
+            return Ok(())
+        }
+        add_validator_to_registry(
+            state,
+            public_key.clone(),
+            withdrawal_credentials.clone(),
+            0,
+            context,
+        );
+    }
+    state.pending_deposits.push(PendingDeposit {
+        public_key: public_key.clone(),
+        withdrawal_credentials: withdrawal_credentials.clone(),
+        amount,
+        signature: signature.clone(),
+        slot: GENESIS_SLOT,
+    });
+    Ok(())
+}
+
+/// Queue a deposit made by request from the execution layer, recording the index of the first
+/// such deposit so that deposits from the deposit contract stop there.
+pub fn process_deposit_request<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    deposit_request: &DepositRequest,
+) {
+    if state.deposit_requests_start_index == UNSET_DEPOSIT_REQUESTS_START_INDEX {
+        state.deposit_requests_start_index = deposit_request.index;
+    }
+    state.pending_deposits.push(PendingDeposit {
+        public_key: deposit_request.public_key.clone(),
+        withdrawal_credentials: deposit_request.withdrawal_credentials.clone(),
+        amount: deposit_request.amount,
+        signature: deposit_request.signature.clone(),
+        slot: state.slot,
+    });
+}
+
+fn find_validator_index<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    public_key: &BlsPublicKey,
+) -> Option<ValidatorIndex> {
+    state.validators.iter().position(|validator| &validator.public_key == public_key)
+}
+
+fn is_valid_switch_to_compounding_request<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    consolidation_request: &ConsolidationRequest,
+    context: &Context,
+) -> bool {
+    // A self-consolidation is a request to switch to compounding withdrawal credentials
+    if consolidation_request.source_public_key != consolidation_request.target_public_key {
+        return false
+    }
+    let Some(source_index) = find_validator_index(state, &consolidation_request.source_public_key)
+    else {
+        return false
+    };
+    let source_validator = &state.validators[source_index];
+    let current_epoch = get_current_epoch(state, context);
+    source_validator.withdrawal_credentials[12..] == consolidation_request.source_address[..] &&
+        has_eth1_withdrawal_credential(source_validator) &&
+        is_active_validator(source_validator, current_epoch) &&
+        source_validator.exit_epoch == FAR_FUTURE_EPOCH
+}
+
+/// Process a consolidation request from the execution layer. Invalid requests cannot fail the
+/// block carrying them, so they are ignored rather than reported.
+pub fn process_consolidation_request<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    consolidation_request: &ConsolidationRequest,
+    context: &Context,
+) -> Result<(), Error> {
+    if is_valid_switch_to_compounding_request(state, consolidation_request, context) {
+        let source_index = find_validator_index(state, &consolidation_request.source_public_key)
+            .expect("validator is present");
+        return switch_to_compounding_validator(state, source_index, context)
+    }
+
+    // Otherwise, the source and target must differ so that a consolidation cannot be used as an
+    // exit
+    if consolidation_request.source_public_key == consolidation_request.target_public_key {
+        return Ok(())
+    }
+    if state.pending_consolidations.len() == PENDING_CONSOLIDATIONS_LIMIT {
+        return Ok(())
+    }
+    if get_consolidation_churn_limit(state, context)? <= context.min_activation_balance {
+        return Ok(())
+    }
+
+    let Some(source_index) = find_validator_index(state, &consolidation_request.source_public_key)
+    else {
+        return Ok(())
+    };
+    let Some(target_index) = find_validator_index(state, &consolidation_request.target_public_key)
+    else {
+        return Ok(())
+    };
+    let source_validator = &state.validators[source_index];
+    let target_validator = &state.validators[target_index];
+
+    let is_correct_source_address =
+        source_validator.withdrawal_credentials[12..] == consolidation_request.source_address[..];
+    if !(has_execution_withdrawal_credential(source_validator) && is_correct_source_address) {
+        return Ok(())
+    }
+    if !has_compounding_withdrawal_credential(target_validator) {
+        return Ok(())
+    }
+
+    let current_epoch = get_current_epoch(state, context);
+    if !is_active_validator(source_validator, current_epoch) ||
+        !is_active_validator(target_validator, current_epoch)
+    {
+        return Ok(())
+    }
+    if source_validator.exit_epoch != FAR_FUTURE_EPOCH ||
+        target_validator.exit_epoch != FAR_FUTURE_EPOCH
+    {
+        return Ok(())
+    }
+    if current_epoch < source_validator.activation_epoch + context.shard_committee_period {
+        return Ok(())
+    }
+    if get_pending_balance_to_withdraw(state, source_index) > 0 {
+        return Ok(())
+    }
+
+    // Initiate the exit of the source validator and queue the move of its balance
+    let source_effective_balance = source_validator.effective_balance;
+    let exit_epoch =
+        compute_consolidation_epoch_and_update_churn(state, source_effective_balance, context)?;
+    let source_validator = &mut state.validators[source_index];
+    source_validator.exit_epoch = exit_epoch;
+    source_validator.withdrawable_epoch = exit_epoch
+        .checked_add(context.min_validator_withdrawability_delay)
+        .ok_or(Error::Overflow)?;
+    state.pending_consolidations.push(PendingConsolidation { source_index, target_index });
+    Ok(())
+}
+
+/// Process a withdrawal request from the execution layer: a full exit when the amount is
+/// `FULL_EXIT_REQUEST_AMOUNT`, otherwise a partial withdrawal of the excess balance of a
+/// compounding validator. Invalid requests cannot fail the block carrying them, so they are
+/// ignored rather than reported.
+pub fn process_withdrawal_request<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    withdrawal_request: &WithdrawalRequest,
+    context: &Context,
+) -> Result<(), Error> {
+    let amount = withdrawal_request.amount;
+    let is_full_exit_request = amount == FULL_EXIT_REQUEST_AMOUNT;
+    // Only full exits are processed once the partial withdrawal queue is full
+    if state.pending_partial_withdrawals.len() == PENDING_PARTIAL_WITHDRAWALS_LIMIT &&
+        !is_full_exit_request
+    {
+        return Ok(())
+    }
+
+    let Some(index) = find_validator_index(state, &withdrawal_request.validator_public_key) else {
+        return Ok(())
+    };
+    let validator = &state.validators[index];
+
+    let is_correct_source_address =
+        validator.withdrawal_credentials[12..] == withdrawal_request.source_address[..];
+    if !(has_execution_withdrawal_credential(validator) && is_correct_source_address) {
+        return Ok(())
+    }
+    let current_epoch = get_current_epoch(state, context);
+    if !is_active_validator(validator, current_epoch) {
+        return Ok(())
+    }
+    if validator.exit_epoch != FAR_FUTURE_EPOCH {
+        return Ok(())
+    }
+    if current_epoch < validator.activation_epoch + context.shard_committee_period {
+        return Ok(())
+    }
+
+    let pending_balance_to_withdraw = get_pending_balance_to_withdraw(state, index);
+    if is_full_exit_request {
+        // Only exit a validator without partial withdrawals still to be made
+        if pending_balance_to_withdraw == 0 {
+            initiate_validator_exit(state, index, context)?;
+        }
+        return Ok(())
+    }
+
+    let balance = state.balances[index];
+    let has_sufficient_effective_balance =
+        validator.effective_balance >= context.min_activation_balance;
+    let has_excess_balance = balance > context.min_activation_balance + pending_balance_to_withdraw;
+    if has_compounding_withdrawal_credential(validator) &&
+        has_sufficient_effective_balance &&
+        has_excess_balance
+    {
+        let to_withdraw =
+            (balance - context.min_activation_balance - pending_balance_to_withdraw).min(amount);
+        let exit_queue_epoch = compute_exit_epoch_and_update_churn(state, to_withdraw, context)?;
+        let withdrawable_epoch = exit_queue_epoch
+            .checked_add(context.min_validator_withdrawability_delay)
+            .ok_or(Error::Overflow)?;
+        state.pending_partial_withdrawals.push(PendingPartialWithdrawal {
+            validator_index: index,
+            amount: to_withdraw,
+            withdrawable_epoch,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        crypto::SecretKey,
+        electra::{
+            compute_activation_exit_epoch, minimal, process_pending_consolidations, VoluntaryExit,
+        },
+        error::InvalidBlock,
+        primitives::{COMPOUNDING_WITHDRAWAL_PREFIX, ETH1_ADDRESS_WITHDRAWAL_PREFIX},
+        signing::sign_with_domain,
+    };
+
+    #[test]
+    fn test_expected_withdrawals_with_pending_partial_withdrawals() {
+        let context = Context::for_minimal();
+        let min_balance = context.min_activation_balance;
+        let mut state = minimal::BeaconState::default();
+        let mut credentials = [0xaa; 32];
+        credentials[0] = ETH1_ADDRESS_WITHDRAWAL_PREFIX;
+        let validator = Validator {
+            withdrawal_credentials: Bytes32::try_from(credentials.as_ref()).unwrap(),
+            effective_balance: min_balance,
+            exit_epoch: FAR_FUTURE_EPOCH,
+            withdrawable_epoch: FAR_FUTURE_EPOCH,
+            ..Default::default()
+        };
+        state.validators.push(validator.clone());
+        state.balances.push(min_balance + 5);
+        // exiting validators skip their pending partial withdrawals
+        let mut exited = validator;
+        exited.exit_epoch = 3;
+        state.validators.push(exited);
+        state.balances.push(min_balance + 5);
+        for (validator_index, amount) in [(1, 4), (0, 2), (0, 1), (0, 1)] {
+            let withdrawal =
+                PendingPartialWithdrawal { validator_index, amount, withdrawable_epoch: 0 };
+            state.pending_partial_withdrawals.push(withdrawal);
+        }
+
+        // two pending partial withdrawals per block on minimal, after the skipped request; each
+        // sees the balance left by the ones before it, and the sweep then takes the rest of the
+        // excess balance
+        assert_eq!(context.max_pending_partials_per_withdrawals_sweep, 2);
+        let pre_state = state.clone();
+        let (withdrawals, processed_count) = get_expected_withdrawals(&state, &context);
+        assert_eq!(state, pre_state);
+        assert_eq!(processed_count, 3);
+        let amounts = withdrawals.iter().map(|w| (w.index, w.validator_index, w.amount));
+        assert_eq!(amounts.collect::<Vec<_>>(), [(0, 0, 2), (1, 0, 1), (2, 0, 2), (3, 1, 5)]);
+
+        let mut payload = minimal::ExecutionPayload::default();
+        payload.withdrawals = List::try_from(withdrawals).unwrap();
+        process_withdrawals(&mut state, &payload, &context).unwrap();
+        assert_eq!(state.balances.as_ref(), [min_balance, min_balance]);
+        assert_eq!(state.pending_partial_withdrawals.len(), 1);
+        assert_eq!(state.next_withdrawal_index, 4);
+
+        // the remaining request has no excess balance left to withdraw, but is still consumed
+        let (withdrawals, processed_count) = get_expected_withdrawals(&state, &context);
+        assert!(withdrawals.is_empty());
+        assert_eq!(processed_count, 1);
+
+        // requests are not processed before they are withdrawable
+        state.pending_partial_withdrawals[0].withdrawable_epoch = 1;
+        assert_eq!(get_expected_withdrawals(&state, &context).1, 0);
+    }
+
+    fn consolidation_fixture() -> (minimal::BeaconState, Context) {
+        let mut context = Context::for_minimal();
+        // leave all of the balance churn to consolidations, as a registry this small would
+        // otherwise have none
+        context.max_per_epoch_activation_exit_churn_limit = 0;
+        let mut state = minimal::BeaconState::default();
+        state.slot = context.shard_committee_period * context.slots_per_epoch;
+        for (seed, prefix) in
+            [(1, ETH1_ADDRESS_WITHDRAWAL_PREFIX), (2, COMPOUNDING_WITHDRAWAL_PREFIX)]
+        {
+            let mut credentials = [0xaa; 32];
+            credentials[0] = prefix;
+            state.validators.push(Validator {
+                public_key: SecretKey::key_gen(&[seed; 32]).unwrap().public_key(),
+                withdrawal_credentials: Bytes32::try_from(credentials.as_ref()).unwrap(),
+                effective_balance: context.min_activation_balance,
+                exit_epoch: FAR_FUTURE_EPOCH,
+                withdrawable_epoch: FAR_FUTURE_EPOCH,
+                ..Default::default()
+            });
+            state.balances.push(context.min_activation_balance + 7);
+        }
+        (state, context)
+    }
+
+    fn consolidation_request(
+        state: &minimal::BeaconState,
+        source_index: usize,
+        target_index: usize,
+    ) -> ConsolidationRequest {
+        ConsolidationRequest {
+            source_address: ExecutionAddress::try_from([0xaa; 20].as_ref()).unwrap(),
+            source_public_key: state.validators[source_index].public_key.clone(),
+            target_public_key: state.validators[target_index].public_key.clone(),
+        }
+    }
+
+    #[test]
+    fn test_self_consolidation_switches_to_compounding() {
+        let (mut state, context) = consolidation_fixture();
+        let request = consolidation_request(&state, 0, 0);
+        process_consolidation_request(&mut state, &request, &context).unwrap();
+        assert!(has_compounding_withdrawal_credential(&state.validators[0]));
+        assert_eq!(state.validators[0].exit_epoch, FAR_FUTURE_EPOCH);
+        assert_eq!(state.balances[0], context.min_activation_balance);
+        assert_eq!(state.pending_deposits.len(), 1);
+        assert_eq!(state.pending_deposits[0].amount, 7);
+        assert_eq!(state.pending_deposits[0].slot, GENESIS_SLOT);
+        assert!(state.pending_consolidations.is_empty());
+
+        // the validator no longer has eth1 credentials, so a second request does nothing
+        let pre_state = state.clone();
+        process_consolidation_request(&mut state, &request, &context).unwrap();
+        assert_eq!(state, pre_state);
+
+        // only the address in the withdrawal credentials may request the switch
+        let (mut state, context) = consolidation_fixture();
+        let mut request = consolidation_request(&state, 0, 0);
+        request.source_address = ExecutionAddress::try_from([0xbb; 20].as_ref()).unwrap();
+        let pre_state = state.clone();
+        process_consolidation_request(&mut state, &request, &context).unwrap();
+        assert_eq!(state, pre_state);
+    }
+
+    #[test]
+    fn test_consolidation_request() {
+        let (mut state, context) = consolidation_fixture();
+        let request = consolidation_request(&state, 0, 1);
+        process_consolidation_request(&mut state, &request, &context).unwrap();
+        assert_eq!(
+            state.pending_consolidations.as_ref(),
+            [PendingConsolidation { source_index: 0, target_index: 1 }]
+        );
+        let source = &state.validators[0];
+        let current_epoch = get_current_epoch(&state, &context);
+        assert!(source.exit_epoch > current_epoch && source.exit_epoch != FAR_FUTURE_EPOCH);
+        assert_eq!(
+            source.withdrawable_epoch,
+            source.exit_epoch + context.min_validator_withdrawability_delay
+        );
+        assert_eq!(state.earliest_consolidation_epoch, source.exit_epoch);
+
+        // the source is now exiting, so it cannot be consolidated again
+        let pre_state = state.clone();
+        process_consolidation_request(&mut state, &request, &context).unwrap();
+        assert_eq!(state, pre_state);
+
+        // nothing moves before the source is withdrawable
+        process_pending_consolidations(&mut state, &context);
+        assert_eq!(state, pre_state);
+
+        let withdrawable_epoch = state.validators[0].withdrawable_epoch;
+        state.slot = (withdrawable_epoch - 1) * context.slots_per_epoch;
+        process_pending_consolidations(&mut state, &context);
+        assert!(state.pending_consolidations.is_empty());
+        assert_eq!(state.balances[0], 7);
+        assert_eq!(state.balances[1], 2 * context.min_activation_balance + 7);
+    }
+
+    #[test]
+    fn test_ignored_consolidation_requests() {
+        // the target must have compounding credentials
+        let (mut state, context) = consolidation_fixture();
+        let request = consolidation_request(&state, 1, 0);
+        let pre_state = state.clone();
+        process_consolidation_request(&mut state, &request, &context).unwrap();
+        assert_eq!(state, pre_state);
+
+        // a source that has already initiated its exit
+        let (mut state, context) = consolidation_fixture();
+        state.validators[0].exit_epoch = 100;
+        let request = consolidation_request(&state, 0, 1);
+        let pre_state = state.clone();
+        process_consolidation_request(&mut state, &request, &context).unwrap();
+        assert_eq!(state, pre_state);
+
+        // a full queue of pending consolidations
+        let (mut state, context) = consolidation_fixture();
+        for _ in 0..minimal::PENDING_CONSOLIDATIONS_LIMIT {
+            state.pending_consolidations.push(PendingConsolidation::default());
+        }
+        let request = consolidation_request(&state, 0, 1);
+        let pre_state = state.clone();
+        process_consolidation_request(&mut state, &request, &context).unwrap();
+        assert_eq!(state, pre_state);
+
+        // too little consolidation churn
+        let (mut state, mut context) = consolidation_fixture();
+        context.max_per_epoch_activation_exit_churn_limit =
+            Context::for_minimal().max_per_epoch_activation_exit_churn_limit;
+        let request = consolidation_request(&state, 0, 1);
+        let pre_state = state.clone();
+        process_consolidation_request(&mut state, &request, &context).unwrap();
+        assert_eq!(state, pre_state);
+    }
+
+    fn deposit_request(
+        secret_key: &SecretKey,
+        prefix: u8,
+        amount: Gwei,
+        index: u64,
+        context: &Context,
+    ) -> DepositRequest {
+        let mut credentials = [0xaa; 32];
+        credentials[0] = prefix;
+        let message = DepositMessage {
+            public_key: secret_key.public_key(),
+            withdrawal_credentials: Bytes32::try_from(credentials.as_ref()).unwrap(),
+            amount,
+        };
+        let domain = compute_domain(DomainType::Deposit, None, None, context).unwrap();
+        DepositRequest {
+            signature: sign_with_domain(&message, secret_key, domain).unwrap(),
+            public_key: message.public_key,
+            withdrawal_credentials: message.withdrawal_credentials,
+            amount,
+            index,
+        }
+    }
+
+    #[test]
+    fn test_deposit_requests() {
+        let (mut state, context) = consolidation_fixture();
+        state.deposit_requests_start_index = UNSET_DEPOSIT_REQUESTS_START_INDEX;
+        let secret_key = SecretKey::key_gen(&[3; 32]).unwrap();
+        let request =
+            deposit_request(&secret_key, ETH1_ADDRESS_WITHDRAWAL_PREFIX, 40, 12, &context);
+        process_deposit_request(&mut state, &request);
+        assert_eq!(state.deposit_requests_start_index, 12);
+        // the deposit is only queued, and the validator is added when it is applied
+        assert_eq!(state.validators.len(), 2);
+        assert_eq!(
+            state.pending_deposits.as_ref(),
+            [PendingDeposit {
+                public_key: request.public_key,
+                withdrawal_credentials: request.withdrawal_credentials,
+                amount: 40,
+                signature: request.signature,
+                slot: state.slot,
+            }]
+        );
+
+        // the start index is set once, and signatures are only checked when applied
+        let mut request = deposit_request(
+            &SecretKey::key_gen(&[4; 32]).unwrap(),
+            ETH1_ADDRESS_WITHDRAWAL_PREFIX,
+            40,
+            13,
+            &context,
+        );
+        request.signature = BlsSignature::default();
+        process_deposit_request(&mut state, &request);
+        assert_eq!(state.deposit_requests_start_index, 12);
+        assert_eq!(state.pending_deposits.len(), 2);
+
+        // a top-up with compounding credentials does not switch a validator with eth1
+        // credentials
+        let secret_key = SecretKey::key_gen(&[1; 32]).unwrap();
+        let request = deposit_request(&secret_key, COMPOUNDING_WITHDRAWAL_PREFIX, 5, 14, &context);
+        process_deposit_request(&mut state, &request);
+        assert!(has_eth1_withdrawal_credential(&state.validators[0]));
+        assert_eq!(state.balances[0], context.min_activation_balance + 7);
+        let queued = state.pending_deposits.iter().map(|d| d.amount);
+        assert_eq!(queued.collect::<Vec<_>>(), [40, 40, 5]);
+    }
+
+    #[test]
+    fn test_apply_deposit() {
+        let (mut state, context) = consolidation_fixture();
+        let secret_key = SecretKey::key_gen(&[3; 32]).unwrap();
+        let request = deposit_request(&secret_key, COMPOUNDING_WITHDRAWAL_PREFIX, 40, 0, &context);
+        apply_deposit(
+            &mut state,
+            &request.public_key,
+            &request.withdrawal_credentials,
+            request.amount,
+            &request.signature,
+            &context,
+        )
+        .unwrap();
+        // the new validator starts without balance and its deposit waits for the churn
+        assert_eq!(state.validators[2].public_key, request.public_key);
+        assert_eq!(state.validators[2].effective_balance, 0);
+        assert_eq!(state.balances[2], 0);
+        assert_eq!(state.pending_deposits.len(), 1);
+        assert_eq!(state.pending_deposits[0].amount, 40);
+        assert_eq!(state.pending_deposits[0].slot, GENESIS_SLOT);
+
+        // a bad signature from an unknown key adds nothing
+        let mut request = deposit_request(
+            &SecretKey::key_gen(&[4; 32]).unwrap(),
+            ETH1_ADDRESS_WITHDRAWAL_PREFIX,
+            40,
+            1,
+            &context,
+        );
+        request.signature = BlsSignature::default();
+        let pre_state = state.clone();
+        apply_deposit(
+            &mut state,
+            &request.public_key,
+            &request.withdrawal_credentials,
+            request.amount,
+            &request.signature,
+            &context,
+        )
+        .unwrap();
+        assert_eq!(state, pre_state);
+    }
+
+    #[test]
+    fn test_validator_from_deposit_caps_by_credentials() {
+        let context = Context::for_minimal();
+        let amount = context.max_effective_balance_electra + context.effective_balance_increment;
+        let mut credentials = [0xaa; 32];
+        credentials[0] = ETH1_ADDRESS_WITHDRAWAL_PREFIX;
+        let eth1_credentials = Bytes32::try_from(credentials.as_ref()).unwrap();
+        credentials[0] = COMPOUNDING_WITHDRAWAL_PREFIX;
+        let compounding_credentials = Bytes32::try_from(credentials.as_ref()).unwrap();
+
+        let validator =
+            get_validator_from_deposit(BlsPublicKey::default(), eth1_credentials, amount, &context);
+        assert_eq!(validator.effective_balance, context.min_activation_balance);
+        let validator = get_validator_from_deposit(
+            BlsPublicKey::default(),
+            compounding_credentials,
+            amount,
+            &context,
+        );
+        assert_eq!(validator.effective_balance, context.max_effective_balance_electra);
+    }
+
+    #[test]
+    fn test_deposits_stop_at_the_first_deposit_request() {
+        let (mut state, context) = consolidation_fixture();
+        state.eth1_data.deposit_count = 5;
+        state.eth1_deposit_index = 2;
+        let body = minimal::BeaconBlockBody::default();
+
+        let expected_count = |state: &minimal::BeaconState| {
+            let err = process_operations(&mut state.clone(), &body, &context).unwrap_err();
+            match err {
+                Error::InvalidBlock(invalid) => match *invalid {
+                    InvalidBlock::InvalidOperation(InvalidOperation::Deposit(
+                        InvalidDeposit::IncorrectCount { expected, .. },
+                    )) => expected,
+                    other => panic!("{other}"),
+                },
+                other => panic!("{other}"),
+            }
+        };
+
+        // before any deposit request, the remaining deposits of the contract are expected
+        state.deposit_requests_start_index = UNSET_DEPOSIT_REQUESTS_START_INDEX;
+        assert_eq!(expected_count(&state), 3);
+
+        // deposits from the contract up to the first deposit request are still expected
+        state.deposit_requests_start_index = 3;
+        assert_eq!(expected_count(&state), 1);
+
+        // after which none are
+        state.eth1_deposit_index = 3;
+        process_operations(&mut state, &body, &context).unwrap();
+    }
+
+    fn withdrawal_fixture() -> (minimal::BeaconState, Context) {
+        let (state, _) = consolidation_fixture();
+        // unlike consolidations, exits and partial withdrawals draw on the activation and exit
+        // churn
+        (state, Context::for_minimal())
+    }
+
+    fn withdrawal_request(
+        state: &minimal::BeaconState,
+        index: usize,
+        amount: Gwei,
+    ) -> WithdrawalRequest {
+        WithdrawalRequest {
+            source_address: ExecutionAddress::try_from([0xaa; 20].as_ref()).unwrap(),
+            validator_public_key: state.validators[index].public_key.clone(),
+            amount,
+        }
+    }
+
+    #[test]
+    fn test_full_exit_request() {
+        let (mut state, context) = withdrawal_fixture();
+        let exit_epoch =
+            compute_activation_exit_epoch(get_current_epoch(&state, &context), &context);
+        let request = withdrawal_request(&state, 0, FULL_EXIT_REQUEST_AMOUNT);
+        process_withdrawal_request(&mut state, &request, &context).unwrap();
+        assert_eq!(state.validators[0].exit_epoch, exit_epoch);
+        assert_eq!(
+            state.validators[0].withdrawable_epoch,
+            exit_epoch + context.min_validator_withdrawability_delay
+        );
+        assert!(state.pending_partial_withdrawals.is_empty());
+
+        // a validator with partial withdrawals still pending does not exit
+        let (mut state, context) = withdrawal_fixture();
+        state.pending_partial_withdrawals.push(PendingPartialWithdrawal {
+            validator_index: 1,
+            amount: 1,
+            withdrawable_epoch: 0,
+        });
+        let request = withdrawal_request(&state, 1, FULL_EXIT_REQUEST_AMOUNT);
+        let pre_state = state.clone();
+        process_withdrawal_request(&mut state, &request, &context).unwrap();
+        assert_eq!(state, pre_state);
+    }
+
+    #[test]
+    fn test_partial_withdrawal_requests() {
+        let (mut state, context) = withdrawal_fixture();
+        let exit_epoch =
+            compute_activation_exit_epoch(get_current_epoch(&state, &context), &context);
+        let withdrawable_epoch = exit_epoch + context.min_validator_withdrawability_delay;
+
+        let request = withdrawal_request(&state, 1, 5);
+        process_withdrawal_request(&mut state, &request, &context).unwrap();
+        // later requests only withdraw what is left above the minimum activation balance
+        let request = withdrawal_request(&state, 1, 10);
+        process_withdrawal_request(&mut state, &request, &context).unwrap();
+        let request = withdrawal_request(&state, 1, 10);
+        process_withdrawal_request(&mut state, &request, &context).unwrap();
+        assert_eq!(
+            state.pending_partial_withdrawals.as_ref(),
+            [
+                PendingPartialWithdrawal { validator_index: 1, amount: 5, withdrawable_epoch },
+                PendingPartialWithdrawal { validator_index: 1, amount: 2, withdrawable_epoch },
+            ]
+        );
+        assert_eq!(state.earliest_exit_epoch, exit_epoch);
+        // the balance only moves once the withdrawal is swept
+        assert_eq!(state.balances[1], context.min_activation_balance + 7);
+        assert_eq!(state.validators[1].exit_epoch, FAR_FUTURE_EPOCH);
+
+        // validators without compounding credentials only make full exits
+        let request = withdrawal_request(&state, 0, 5);
+        let pre_state = state.clone();
+        process_withdrawal_request(&mut state, &request, &context).unwrap();
+        assert_eq!(state, pre_state);
+    }
+
+    #[test]
+    fn test_ignored_withdrawal_requests() {
+        let assert_ignored = |state: &minimal::BeaconState, request: &WithdrawalRequest| {
+            let (_, context) = withdrawal_fixture();
+            let mut post_state = state.clone();
+            process_withdrawal_request(&mut post_state, request, &context).unwrap();
+            assert_eq!(&post_state, state);
+        };
+
+        // a source address other than the one in the withdrawal credentials
+        let (state, _) = withdrawal_fixture();
+        let mut request = withdrawal_request(&state, 0, FULL_EXIT_REQUEST_AMOUNT);
+        request.source_address = ExecutionAddress::try_from([0xbb; 20].as_ref()).unwrap();
+        assert_ignored(&state, &request);
+
+        // an unknown validator
+        let mut request = withdrawal_request(&state, 0, FULL_EXIT_REQUEST_AMOUNT);
+        request.validator_public_key = SecretKey::key_gen(&[3; 32]).unwrap().public_key();
+        assert_ignored(&state, &request);
+
+        // a validator that is not active yet
+        let (mut state, _) = withdrawal_fixture();
+        state.validators[1].activation_epoch = FAR_FUTURE_EPOCH;
+        assert_ignored(&state, &withdrawal_request(&state, 1, 5));
+
+        // a validator that has already initiated its exit
+        let (mut state, _) = withdrawal_fixture();
+        state.validators[1].exit_epoch = 100;
+        assert_ignored(&state, &withdrawal_request(&state, 1, FULL_EXIT_REQUEST_AMOUNT));
+        assert_ignored(&state, &withdrawal_request(&state, 1, 5));
+
+        // a validator that has not been active for long enough
+        let (mut state, _) = withdrawal_fixture();
+        state.validators[1].activation_epoch = 1;
+        assert_ignored(&state, &withdrawal_request(&state, 1, FULL_EXIT_REQUEST_AMOUNT));
+
+        // a full queue of pending partial withdrawals, which still admits full exits
+        let (mut state, context) = withdrawal_fixture();
+        for _ in 0..minimal::PENDING_PARTIAL_WITHDRAWALS_LIMIT {
+            state.pending_partial_withdrawals.push(PendingPartialWithdrawal {
+                validator_index: 0,
+                amount: 0,
+                withdrawable_epoch: FAR_FUTURE_EPOCH,
+            });
+        }
+        assert_ignored(&state, &withdrawal_request(&state, 1, 5));
+        let request = withdrawal_request(&state, 1, FULL_EXIT_REQUEST_AMOUNT);
+        process_withdrawal_request(&mut state, &request, &context).unwrap();
+        assert_ne!(state.validators[1].exit_epoch, FAR_FUTURE_EPOCH);
+    }
+
+    #[test]
+    fn test_withdrawal_requests_in_block() {
+        let (mut state, context) = withdrawal_fixture();
+        let mut body = minimal::BeaconBlockBody::default();
+        for request in [
+            withdrawal_request(&state, 1, 5),
+            withdrawal_request(&state, 0, FULL_EXIT_REQUEST_AMOUNT),
+            // an invalid request does not fail the block
+            withdrawal_request(&state, 0, 5),
+        ] {
+            body.execution_requests.withdrawals.push(request);
+        }
+        process_operations(&mut state, &body, &context).unwrap();
+        assert_ne!(state.validators[0].exit_epoch, FAR_FUTURE_EPOCH);
+        assert_eq!(state.pending_partial_withdrawals.len(), 1);
+    }
+
+    #[test]
+    fn test_voluntary_exit_with_pending_withdrawals() {
+        let (mut state, context) = withdrawal_fixture();
+        state.pending_partial_withdrawals.push(PendingPartialWithdrawal {
+            validator_index: 1,
+            amount: 1,
+            withdrawable_epoch: FAR_FUTURE_EPOCH,
+        });
+        let exit = |index| SignedVoluntaryExit {
+            message: VoluntaryExit { epoch: 0, validator_index: index },
+            ..Default::default()
+        };
+        let validation = Validation::SkipSignatureVerification;
+
+        let err =
+            process_voluntary_exit_with_validation(&mut state, &exit(1), validation, &context)
+                .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::InvalidBlock(invalid) if matches!(
+                *invalid,
+                InvalidBlock::InvalidOperation(InvalidOperation::VoluntaryExit(
+                    InvalidVoluntaryExit::PendingWithdrawals { index: 1, amount: 1 },
+                ))
+            )
+        ));
+
+        process_voluntary_exit_with_validation(&mut state, &exit(0), validation, &context).unwrap();
+        assert_ne!(state.validators[0].exit_epoch, FAR_FUTURE_EPOCH);
+    }
 }
@@ -0,0 +1,825 @@
+use crate::{
+    electra::{
+        add_validator_to_registry, compute_activation_exit_epoch, compute_domain,
+        compute_start_slot_at_epoch, decrease_balance, get_activation_exit_churn_limit,
+        get_current_epoch, get_max_effective_balance, get_total_active_balance, increase_balance,
+        initiate_validator_exit, is_active_validator, is_eligible_for_activation,
+        is_eligible_for_activation_queue, is_valid_deposit_signature, process_eth1_data_reset,
+        process_historical_summaries_update, process_inactivity_updates,
+        process_justification_and_finalization, process_participation_flag_updates,
+        process_randao_mixes_reset, process_rewards_and_penalties, process_slashings_reset,
+        process_sync_committee_updates, BeaconState, DepositMessage, PendingDeposit,
+    },
+    primitives::{DomainType, Gwei, FAR_FUTURE_EPOCH, GENESIS_SLOT},
+    signing::compute_signing_root,
+    ssz::prelude::*,
+    state_transition::{Context, DecompressedPubkeyCache, Result, SignatureVerifier, Signers},
+};
+use std::collections::{HashMap, HashSet};
+
+/// Apply a pending deposit: a deposit for an unknown public key with a valid signature adds a
+/// new validator, anything else tops up the balance of the validator it names.
+pub fn apply_pending_deposit<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    deposit: &PendingDeposit,
+    context: &Context,
+) -> Result<()> {
+    apply_pending_deposit_with_signature_check(state, deposit, None, context)
+}
+
+// `apply_pending_deposit`, taking the validity of the deposit signature if it is already known.
+fn apply_pending_deposit_with_signature_check<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    deposit: &PendingDeposit,
+    is_valid_signature: Option<bool>,
+    context: &Context,
+) -> Result<()> {
+    let index = state.validators.iter().position(|v| v.public_key == deposit.public_key);
+    match index {
+        Some(index) => increase_balance(state, index, deposit.amount),
+        None => {
+            let is_valid_signature = match is_valid_signature {
+                Some(is_valid) => is_valid,
+                None => is_valid_deposit_signature(
+                    &deposit.public_key,
+                    &deposit.withdrawal_credentials,
+                    deposit.amount,
+                    &deposit.signature,
+                    context,
+                )?,
+            };
+            if is_valid_signature {
+                add_validator_to_registry(
+                    state,
+                    deposit.public_key.clone(),
+                    deposit.withdrawal_credentials.clone(),
+                    deposit.amount,
+                    context,
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Verify the signatures of `deposits` in one batch, falling back to verifying them one at a
+/// time to find the invalid ones if the batch fails. The result is that of
+/// `is_valid_deposit_signature` for each deposit, in order.
+pub fn verify_deposit_signatures(
+    deposits: &[&PendingDeposit],
+    context: &Context,
+) -> Result<Vec<bool>> {
+    let domain = compute_domain(DomainType::Deposit, None, None, context)?;
+    let mut verifier = SignatureVerifier::default();
+    for deposit in deposits {
+        let deposit_message = DepositMessage {
+            public_key: deposit.public_key.clone(),
+            withdrawal_credentials: deposit.withdrawal_credentials.clone(),
+            amount: deposit.amount,
+        };
+        let signing_root = compute_signing_root(&deposit_message, domain)?;
+        let signers = Signers::PublicKeys(vec![deposit.public_key.clone()]);
+        verifier.push(signers, signing_root, &deposit.signature);
+    }
+    // public keys that are not valid points fail the batch as well as invalid signatures
+    if verifier.verify(&[], &mut DecompressedPubkeyCache::default()).is_ok() {
+        return Ok(vec![true; deposits.len()])
+    }
+    deposits
+        .iter()
+        .map(|deposit| {
+            is_valid_deposit_signature(
+                &deposit.public_key,
+                &deposit.withdrawal_credentials,
+                deposit.amount,
+                &deposit.signature,
+                context,
+            )
+        })
+        .collect()
+}
+
+/// Apply the pending deposits that are finalized and fit in the activation churn of this epoch,
+/// and at most `max_pending_deposits_per_epoch` of them. Deposits to exited validators do not
+/// consume churn and wait until the validator is withdrawn.
+///
+/// The signatures of the deposits that may add a validator are verified up front in a batch with
+/// `verify_deposit_signatures`, with the same outcome as verifying each as it is applied.
+pub fn process_pending_deposits<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    context: &Context,
+) -> Result<()> {
+    let next_epoch = get_current_epoch(state, context) + 1;
+    let available_for_processing =
+        state.deposit_balance_to_consume + get_activation_exit_churn_limit(state, context)?;
+    let finalized_slot = compute_start_slot_at_epoch(state.finalized_checkpoint.epoch, context);
+    let mut processed_amount = 0;
+    let mut next_deposit_index = 0;
+    let mut deposits_to_postpone = vec![];
+    let mut is_churn_limit_reached = false;
+
+    // only the first `max_pending_deposits_per_epoch` deposits can be applied, and of those only
+    // the deposits for unknown public keys have their signature checked
+    let signature_checks = {
+        let known_public_keys =
+            state.validators.iter().map(|v| &v.public_key).collect::<HashSet<_>>();
+        let (positions, candidates): (Vec<_>, Vec<_>) = state
+            .pending_deposits
+            .iter()
+            .take(context.max_pending_deposits_per_epoch)
+            .enumerate()
+            .filter(|(_, deposit)| !known_public_keys.contains(&deposit.public_key))
+            .unzip();
+        let is_valid = verify_deposit_signatures(&candidates, context)?;
+        positions.into_iter().zip(is_valid).collect::<HashMap<_, _>>()
+    };
+
+    for i in 0..state.pending_deposits.len() {
+        let deposit = state.pending_deposits[i].clone();
+        // deposits from the eth1 bridge are applied before any deposit request
+        if deposit.slot > GENESIS_SLOT &&
+            state.eth1_deposit_index < state.deposit_requests_start_index
+        {
+            break
+        }
+        if deposit.slot > finalized_slot {
+            break
+        }
+        if next_deposit_index >= context.max_pending_deposits_per_epoch {
+            break
+        }
+
+        let validator = state.validators.iter().find(|v| v.public_key == deposit.public_key);
+        let (is_validator_exited, is_validator_withdrawn) = validator
+            .map(|v| (v.exit_epoch < FAR_FUTURE_EPOCH, v.withdrawable_epoch < next_epoch))
+            .unwrap_or_default();
+
+        if is_validator_withdrawn {
+            // the balance will never become active, so it does not consume churn
+            let is_valid_signature = signature_checks.get(&i).copied();
+            apply_pending_deposit_with_signature_check(
+                state,
+                &deposit,
+                is_valid_signature,
+                context,
+            )?;
+        } else if is_validator_exited {
+            deposits_to_postpone.push(deposit);
+        } else {
+            is_churn_limit_reached = processed_amount + deposit.amount > available_for_processing;
+            if is_churn_limit_reached {
+                break
+            }
+            processed_amount += deposit.amount;
+            let is_valid_signature = signature_checks.get(&i).copied();
+            apply_pending_deposit_with_signature_check(
+                state,
+                &deposit,
+                is_valid_signature,
+                context,
+            )?;
+        }
+        next_deposit_index += 1;
+    }
+
+    let mut pending_deposits = state.pending_deposits[next_deposit_index..].to_vec();
+    pending_deposits.extend(deposits_to_postpone);
+    state.pending_deposits = List::try_from(pending_deposits).expect("no more entries than before");
+    // churn left over is only carried forward if it is what held back the queue
+    state.deposit_balance_to_consume =
+        if is_churn_limit_reached { available_for_processing - processed_amount } else { 0 };
+    Ok(())
+}
+
+/// Mark validators eligible for the activation queue, eject those under the ejection balance
+/// and activate every eligible validator; the churn is applied to deposits instead.
+pub fn process_registry_updates<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    context: &Context,
+) -> Result<()> {
+    let current_epoch = get_current_epoch(state, context);
+    let activation_epoch = compute_activation_exit_epoch(current_epoch, context);
+    for i in 0..state.validators.len() {
+        let validator = &state.validators[i];
+        if is_eligible_for_activation_queue(validator, context) {
+            state.validators[i].activation_eligibility_epoch = current_epoch + 1;
+        } else if is_active_validator(validator, current_epoch) &&
+            validator.effective_balance <= context.ejection_balance
+        {
+            initiate_validator_exit(state, i, context)?;
+        } else if is_eligible_for_activation(state, validator) {
+            state.validators[i].activation_epoch = activation_epoch;
+        }
+    }
+    Ok(())
+}
+
+/// Apply the correlated slashing penalty, computed per effective balance increment so that the
+/// penalty scales linearly with the effective balance of the slashed validator.
+pub fn process_slashings<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    context: &Context,
+) -> Result<()> {
+    let epoch = get_current_epoch(state, context);
+    let total_balance = get_total_active_balance(state, context)?;
+    let adjusted_total_slashing_balance = Gwei::min(
+        state.slashings.iter().sum::<Gwei>() * context.proportional_slashing_multiplier_bellatrix,
+        total_balance,
+    );
+    let increment = context.effective_balance_increment;
+    let penalty_per_effective_balance_increment =
+        adjusted_total_slashing_balance / (total_balance / increment);
+    for i in 0..state.validators.len() {
+        let validator = &state.validators[i];
+        if validator.slashed &&
+            (epoch + context.epochs_per_slashings_vector / 2) == validator.withdrawable_epoch
+        {
+            let effective_balance_increments = validator.effective_balance / increment;
+            let penalty = penalty_per_effective_balance_increment * effective_balance_increments;
+            decrease_balance(state, i, penalty);
+        }
+    }
+    Ok(())
+}
+
+/// Move the balance of each source validator that has become withdrawable to its target,
+/// dropping the consolidations of slashed source validators without moving their balance.
+pub fn process_pending_consolidations<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    context: &Context,
+) {
+    let next_epoch = get_current_epoch(state, context) + 1;
+    let mut next_pending_consolidation = 0;
+    for i in 0..state.pending_consolidations.len() {
+        let source_index = state.pending_consolidations[i].source_index;
+        let target_index = state.pending_consolidations[i].target_index;
+        let source_validator = &state.validators[source_index];
+        if source_validator.slashed {
+            next_pending_consolidation += 1;
+            continue
+        }
+        if source_validator.withdrawable_epoch > next_epoch {
+            break
+        }
+
+        let source_effective_balance =
+            state.balances[source_index].min(source_validator.effective_balance);
+        decrease_balance(state, source_index, source_effective_balance);
+        increase_balance(state, target_index, source_effective_balance);
+        next_pending_consolidation += 1;
+    }
+
+    let pending_consolidations =
+        state.pending_consolidations[next_pending_consolidation..].to_vec();
+    state.pending_consolidations =
+        List::try_from(pending_consolidations).expect("fewer entries than before");
+}
+
+/// Update the effective balance of each validator with hysteresis, up to the maximum for its
+/// withdrawal credentials so that compounding validators may exceed `min_activation_balance`.
+pub fn process_effective_balance_updates<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    context: &Context,
+) {
+    let hysteresis_increment = context.effective_balance_increment / context.hysteresis_quotient;
+    let downward_threshold = hysteresis_increment * context.hysteresis_downward_multiplier;
+    let upward_threshold = hysteresis_increment * context.hysteresis_upward_multiplier;
+    for i in 0..state.validators.len() {
+        let validator = &mut state.validators[i];
+        let balance = state.balances[i];
+        let max_effective_balance = get_max_effective_balance(validator, context);
+        if balance + downward_threshold < validator.effective_balance ||
+            validator.effective_balance + upward_threshold < balance
+        {
+            validator.effective_balance = Gwei::min(
+                balance - balance % context.effective_balance_increment,
+                max_effective_balance,
+            );
+        }
+    }
+}
+
+pub fn process_epoch<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    context: &Context,
+) -> Result<()> {
+    trace_span!("process_epoch", epoch = state.slot / context.slots_per_epoch);
+    traced!(
+        "process_justification_and_finalization",
+        process_justification_and_finalization(state, context)
+    )?;
+    traced!("process_inactivity_updates", process_inactivity_updates(state, context))?;
+    traced!("process_rewards_and_penalties", process_rewards_and_penalties(state, context))?;
+    traced!("process_registry_updates", process_registry_updates(state, context))?;
+    traced!("process_slashings", process_slashings(state, context))?;
+    traced!("process_eth1_data_reset", process_eth1_data_reset(state, context));
+    traced!("process_pending_deposits", process_pending_deposits(state, context))?;
+    traced!("process_pending_consolidations", process_pending_consolidations(state, context));
+    traced!("process_effective_balance_updates", process_effective_balance_updates(state, context));
+    traced!("process_slashings_reset", process_slashings_reset(state, context));
+    traced!("process_randao_mixes_reset", process_randao_mixes_reset(state, context));
+    traced!(
+        "process_historical_summaries_update",
+        process_historical_summaries_update(state, context)
+    )?;
+    traced!("process_participation_flag_updates", process_participation_flag_updates(state))?;
+    traced!("process_sync_committee_updates", process_sync_committee_updates(state, context))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        crypto::SecretKey,
+        electra::{minimal, PendingConsolidation, Validator},
+        primitives::{BlsPublicKey, Bytes32, Slot, COMPOUNDING_WITHDRAWAL_PREFIX},
+        signing::sign_with_domain,
+    };
+
+    fn public_key(seed: u8) -> BlsPublicKey {
+        SecretKey::key_gen(&[seed; 32]).unwrap().public_key()
+    }
+
+    fn deposit(index: usize, amount: Gwei, slot: Slot) -> PendingDeposit {
+        PendingDeposit {
+            public_key: public_key(index as u8 + 1),
+            amount,
+            slot,
+            ..Default::default()
+        }
+    }
+
+    fn pending(state: &minimal::BeaconState) -> Vec<(usize, u64)> {
+        state
+            .pending_deposits
+            .iter()
+            .map(|d| {
+                let index = state.validators.iter().position(|v| v.public_key == d.public_key);
+                (index.unwrap(), d.amount)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_pending_deposits() {
+        let context = Context::for_minimal();
+        let increment = context.effective_balance_increment;
+        let mut state = minimal::BeaconState::default();
+        for seed in 1..=3 {
+            state.validators.push(Validator {
+                public_key: public_key(seed),
+                effective_balance: context.min_activation_balance,
+                exit_epoch: FAR_FUTURE_EPOCH,
+                withdrawable_epoch: FAR_FUTURE_EPOCH,
+                ..Default::default()
+            });
+            state.balances.push(0);
+        }
+        let churn = get_activation_exit_churn_limit(&state, &context).unwrap();
+        assert_eq!(churn, 64 * increment);
+
+        // validator 2 has exited, so its deposit waits without consuming churn
+        state.validators[2].exit_epoch = 1;
+        state.validators[2].withdrawable_epoch = 2;
+        for (index, amount) in [(2, 1), (0, 40 * increment), (1, 30 * increment), (0, 1)] {
+            state.pending_deposits.push(deposit(index, amount, GENESIS_SLOT));
+        }
+        process_pending_deposits(&mut state, &context).unwrap();
+        assert_eq!(state.balances.as_ref(), [40 * increment, 0, 0]);
+        assert_eq!(state.deposit_balance_to_consume, 24 * increment);
+        assert_eq!(pending(&state), [(1, 30 * increment), (0, 1), (2, 1)]);
+
+        // the churn left over carries into the next epoch, and the withdrawn validator is
+        // topped up without consuming churn
+        state.slot = 3 * context.slots_per_epoch;
+        process_pending_deposits(&mut state, &context).unwrap();
+        assert_eq!(state.balances.as_ref(), [40 * increment + 1, 30 * increment, 1]);
+        assert_eq!(state.deposit_balance_to_consume, 0);
+        assert!(state.pending_deposits.is_empty());
+
+        // at most `max_pending_deposits_per_epoch` deposits are applied in an epoch
+        let count = context.max_pending_deposits_per_epoch + 2;
+        for _ in 0..count {
+            state.pending_deposits.push(deposit(1, 1, GENESIS_SLOT));
+        }
+        process_pending_deposits(&mut state, &context).unwrap();
+        assert_eq!(state.pending_deposits.len(), 2);
+        assert_eq!(state.deposit_balance_to_consume, 0);
+    }
+
+    #[test]
+    fn test_pending_deposits_wait_for_finality_and_eth1_bridge() {
+        let context = Context::for_minimal();
+        let mut state = minimal::BeaconState::default();
+        state.validators.push(Validator {
+            public_key: public_key(1),
+            effective_balance: context.min_activation_balance,
+            exit_epoch: FAR_FUTURE_EPOCH,
+            withdrawable_epoch: FAR_FUTURE_EPOCH,
+            ..Default::default()
+        });
+        state.balances.push(0);
+        state.slot = 3 * context.slots_per_epoch;
+        state.pending_deposits.push(deposit(0, 1, context.slots_per_epoch + 1));
+
+        // deposits past the finalized checkpoint wait
+        process_pending_deposits(&mut state, &context).unwrap();
+        assert_eq!(state.balances.as_ref(), [0]);
+        assert_eq!(state.pending_deposits.len(), 1);
+
+        // deposit requests wait until the eth1 bridge deposits before them are applied
+        state.finalized_checkpoint.epoch = 2;
+        state.deposit_requests_start_index = 10;
+        state.eth1_deposit_index = 9;
+        process_pending_deposits(&mut state, &context).unwrap();
+        assert_eq!(state.balances.as_ref(), [0]);
+        assert_eq!(state.pending_deposits.len(), 1);
+
+        state.eth1_deposit_index = 10;
+        process_pending_deposits(&mut state, &context).unwrap();
+        assert_eq!(state.balances.as_ref(), [1]);
+        assert!(state.pending_deposits.is_empty());
+
+        // a deposit for a new public key without a valid signature is dropped
+        state.pending_deposits.push(deposit(1, context.min_activation_balance, GENESIS_SLOT));
+        process_pending_deposits(&mut state, &context).unwrap();
+        assert_eq!(state.validators.len(), 1);
+        assert!(state.pending_deposits.is_empty());
+    }
+
+    // a deposit of `amount` for the key from `seed`, signed by the key from `signer`
+    fn signed_deposit(seed: u8, signer: u8, amount: Gwei, context: &Context) -> PendingDeposit {
+        let mut deposit = deposit(seed as usize - 1, amount, GENESIS_SLOT);
+        let deposit_message = DepositMessage {
+            public_key: deposit.public_key.clone(),
+            withdrawal_credentials: deposit.withdrawal_credentials.clone(),
+            amount,
+        };
+        let domain = compute_domain(DomainType::Deposit, None, None, context).unwrap();
+        let secret_key = SecretKey::key_gen(&[signer; 32]).unwrap();
+        deposit.signature = sign_with_domain(&deposit_message, &secret_key, domain).unwrap();
+        deposit
+    }
+
+    #[test]
+    fn test_batched_deposit_signatures_match_individual_checks() {
+        let context = Context::for_minimal();
+        let increment = context.effective_balance_increment;
+        let mut state = minimal::BeaconState::default();
+        state.validators.push(Validator {
+            public_key: public_key(1),
+            effective_balance: context.min_activation_balance,
+            exit_epoch: FAR_FUTURE_EPOCH,
+            withdrawable_epoch: FAR_FUTURE_EPOCH,
+            ..Default::default()
+        });
+        state.balances.push(context.min_activation_balance);
+        // a top-up with any signature, new validators with valid and invalid signatures, an
+        // invalid deposit followed by a valid one for the same key and a valid deposit followed
+        // by an invalid top-up
+        let deposits = [
+            signed_deposit(1, 9, increment, &context),
+            signed_deposit(2, 2, increment, &context),
+            signed_deposit(3, 9, increment, &context),
+            signed_deposit(4, 9, increment, &context),
+            signed_deposit(4, 4, 2 * increment, &context),
+            signed_deposit(5, 5, increment, &context),
+            signed_deposit(5, 9, 3 * increment, &context),
+            signed_deposit(6, 6, increment, &context),
+        ];
+        for deposit in &deposits {
+            state.pending_deposits.push(deposit.clone());
+        }
+
+        let candidates = deposits.iter().collect::<Vec<_>>();
+        let is_valid = verify_deposit_signatures(&candidates, &context).unwrap();
+        assert_eq!(is_valid, [false, true, false, false, true, true, false, true]);
+        let valid = [&deposits[1], &deposits[5], &deposits[7]];
+        assert_eq!(verify_deposit_signatures(&valid, &context).unwrap(), [true; 3]);
+
+        // every deposit fits in the churn, so the naive path applies each of them in turn
+        let mut expected = state.clone();
+        for deposit in &deposits {
+            apply_pending_deposit(&mut expected, deposit, &context).unwrap();
+        }
+        expected.pending_deposits = Default::default();
+        process_pending_deposits(&mut state, &context).unwrap();
+        assert_eq!(state, expected);
+        let keys = state.validators.iter().map(|v| v.public_key.clone()).collect::<Vec<_>>();
+        assert_eq!(keys, [1, 2, 4, 5, 6].map(public_key));
+        assert_eq!(
+            state.balances.as_ref(),
+            [
+                context.min_activation_balance + increment,
+                increment,
+                2 * increment,
+                4 * increment,
+                increment
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pending_consolidations() {
+        let context = Context::for_minimal();
+        let increment = context.effective_balance_increment;
+        let mut state = minimal::BeaconState::default();
+        for _ in 0..4 {
+            state.validators.push(Validator {
+                effective_balance: context.min_activation_balance,
+                exit_epoch: FAR_FUTURE_EPOCH,
+                withdrawable_epoch: FAR_FUTURE_EPOCH,
+                ..Default::default()
+            });
+            state.balances.push(context.min_activation_balance + increment);
+        }
+        // validator 0 is withdrawable, validator 1 is slashed and validator 2 is not withdrawable
+        state.validators[0].withdrawable_epoch = 1;
+        state.validators[1].slashed = true;
+        for source_index in [0, 1, 2, 0] {
+            state
+                .pending_consolidations
+                .push(PendingConsolidation { source_index, target_index: 3 });
+        }
+
+        process_pending_consolidations(&mut state, &context);
+        // only the effective balance of the source moves, and the queue stops at the first source
+        // that is not withdrawable
+        assert_eq!(
+            state.balances.as_ref(),
+            [
+                increment,
+                context.min_activation_balance + increment,
+                context.min_activation_balance + increment,
+                2 * context.min_activation_balance + increment,
+            ]
+        );
+        let sources =
+            state.pending_consolidations.iter().map(|c| c.source_index).collect::<Vec<_>>();
+        assert_eq!(sources, [2, 0]);
+    }
+
+    #[test]
+    fn test_effective_balance_updates_with_compounding_credentials() {
+        let context = Context::for_minimal();
+        let increment = context.effective_balance_increment;
+        let mut credentials = [0u8; 32];
+        credentials[0] = COMPOUNDING_WITHDRAWAL_PREFIX;
+        let compounding_credentials = Bytes32::try_from(credentials.as_ref()).unwrap();
+        let mut state = minimal::BeaconState::default();
+        let balance = context.min_activation_balance + 10 * increment;
+        for withdrawal_credentials in [Bytes32::default(), compounding_credentials.clone()] {
+            state.validators.push(Validator {
+                withdrawal_credentials,
+                effective_balance: context.min_activation_balance,
+                ..Default::default()
+            });
+            state.balances.push(balance);
+        }
+        // a balance past the maximum for any credentials
+        state.validators.push(Validator {
+            withdrawal_credentials: compounding_credentials,
+            effective_balance: context.min_activation_balance,
+            ..Default::default()
+        });
+        state.balances.push(context.max_effective_balance_electra + 7 * increment);
+
+        process_effective_balance_updates(&mut state, &context);
+        let effective_balances =
+            state.validators.iter().map(|v| v.effective_balance).collect::<Vec<_>>();
+        assert_eq!(
+            effective_balances,
+            [context.min_activation_balance, balance, context.max_effective_balance_electra]
+        );
+
+        // within the hysteresis, the effective balance does not move
+        state.balances[1] -= increment / 8;
+        process_effective_balance_updates(&mut state, &context);
+        assert_eq!(state.validators[1].effective_balance, balance);
+    }
+}
@@ -0,0 +1,697 @@
+//! Requests from the execution layer to the consensus layer (EIP-7685) and their encodings.
+//!
+//! The beacon node API and the engine API represent these differently: the former as structured
+//! JSON in the same shape as the consensus types, the latter (`executionRequests`) as a list of
+//! hex strings, each holding a request type byte followed by the SSZ encoding of all requests
+//! of that type. [`BeaconApiFormat`] and [`EngineFormat`] select between the two. Each format
+//! rejects input in the other's shape rather than attempting to guess.
+use crate::{
+    primitives::{BlsPublicKey, BlsSignature, Bytes32, ExecutionAddress, Gwei},
+    ssz::prelude::*,
+    Error,
+};
+use serde::{de::Error as _, Deserializer, Serializer};
+
+pub const DEPOSIT_REQUEST_TYPE: u8 = 0x00;
+pub const WITHDRAWAL_REQUEST_TYPE: u8 = 0x01;
+pub const CONSOLIDATION_REQUEST_TYPE: u8 = 0x02;
+
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct DepositRequest {
+    #[serde(rename = "pubkey")]
+    pub public_key: BlsPublicKey,
+    pub withdrawal_credentials: Bytes32,
+    #[serde(with = "crate::serde::as_str")]
+    pub amount: Gwei,
+    pub signature: BlsSignature,
+    #[serde(with = "crate::serde::as_str")]
+    pub index: u64,
+}
+
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct WithdrawalRequest {
+    pub source_address: ExecutionAddress,
+    #[serde(rename = "validator_pubkey")]
+    pub validator_public_key: BlsPublicKey,
+    #[serde(with = "crate::serde::as_str")]
+    pub amount: Gwei,
+}
+
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct ConsolidationRequest {
+    pub source_address: ExecutionAddress,
+    #[serde(rename = "source_pubkey")]
+    pub source_public_key: BlsPublicKey,
+    #[serde(rename = "target_pubkey")]
+    pub target_public_key: BlsPublicKey,
+}
+
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct ExecutionRequests<
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+> {
+    pub deposits: List<DepositRequest, MAX_DEPOSIT_REQUESTS_PER_PAYLOAD>,
+    pub withdrawals: List<WithdrawalRequest, MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD>,
+    pub consolidations: List<ConsolidationRequest, MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD>,
+}
+
+crate::ssz_size::impl_ssz_size!(DepositRequest {
+    public_key: BlsPublicKey,
+    withdrawal_credentials: Bytes32,
+    amount: Gwei,
+    signature: BlsSignature,
+    index: u64,
+});
+
+crate::ssz_size::impl_ssz_size!(WithdrawalRequest {
+    source_address: ExecutionAddress,
+    validator_public_key: BlsPublicKey,
+    amount: Gwei,
+});
+
+crate::ssz_size::impl_ssz_size!(ConsolidationRequest {
+    source_address: ExecutionAddress,
+    source_public_key: BlsPublicKey,
+    target_public_key: BlsPublicKey,
+});
+
+crate::ssz_size::impl_ssz_size!(ExecutionRequests<
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+> {
+    deposits: List<DepositRequest, MAX_DEPOSIT_REQUESTS_PER_PAYLOAD>,
+    withdrawals: List<WithdrawalRequest, MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD>,
+    consolidations: List<ConsolidationRequest, MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD>,
+});
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum ExecutionRequestsError {
+    #[error("execution request of unknown type {0:#04x}")]
+    UnknownRequestType(u8),
+    #[error("execution request of type {request_type:#04x} follows type {previous:#04x}")]
+    OutOfOrder { request_type: u8, previous: u8 },
+    #[error("execution request of type {0:#04x} has no data")]
+    EmptyRequestData(u8),
+    #[error("execution request has no type byte")]
+    MissingRequestType,
+}
+
+fn encode_requests<T: SimpleSerialize>(
+    request_type: u8,
+    requests: &T,
+    encoding: &mut Vec<Vec<u8>>,
+) -> Result<(), Error> {
+    let data = serialize(requests).map_err(|err| Error::SimpleSerialize(err.into()))?;
+    // the SSZ encoding of a list of fixed-size items is empty exactly when the list is empty
+    if !data.is_empty() {
+        encoding.push([&[request_type], data.as_slice()].concat());
+    }
+    Ok(())
+}
+
+/// The engine API encoding of `execution_requests`: for each type with at least one request, the
+/// type byte followed by the SSZ encoding of the requests of that type, in ascending type order.
+pub fn get_execution_requests_list<
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    execution_requests: &ExecutionRequests<
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >,
+) -> Result<Vec<Vec<u8>>, Error> {
+    let mut encoding = vec![];
+    encode_requests(DEPOSIT_REQUEST_TYPE, &execution_requests.deposits, &mut encoding)?;
+    encode_requests(WITHDRAWAL_REQUEST_TYPE, &execution_requests.withdrawals, &mut encoding)?;
+    encode_requests(CONSOLIDATION_REQUEST_TYPE, &execution_requests.consolidations, &mut encoding)?;
+    Ok(encoding)
+}
+
+/// Decode the engine API encoding of `execution_requests`, rejecting unknown or repeated types,
+/// types out of ascending order and types with no requests.
+pub fn get_execution_requests<
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    execution_requests_list: &[impl AsRef<[u8]>],
+) -> Result<
+    ExecutionRequests<
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >,
+    Error,
+> {
+    let mut execution_requests = ExecutionRequests::default();
+    let mut previous = None;
+    for request in execution_requests_list {
+        let (&request_type, data) =
+            request.as_ref().split_first().ok_or(ExecutionRequestsError::MissingRequestType)?;
+        if let Some(previous) = previous.filter(|&previous| previous >= request_type) {
+            return Err(ExecutionRequestsError::OutOfOrder { request_type, previous }.into())
+        }
+        previous = Some(request_type);
+        if data.is_empty() {
+            return Err(ExecutionRequestsError::EmptyRequestData(request_type).into())
+        }
+        let decode_error = |err: DeserializeError| Error::SimpleSerialize(err.into());
+        match request_type {
+            DEPOSIT_REQUEST_TYPE => {
+                execution_requests.deposits = List::deserialize(data).map_err(decode_error)?;
+            }
+            WITHDRAWAL_REQUEST_TYPE => {
+                execution_requests.withdrawals = List::deserialize(data).map_err(decode_error)?;
+            }
+            CONSOLIDATION_REQUEST_TYPE => {
+                execution_requests.consolidations =
+                    List::deserialize(data).map_err(decode_error)?;
+            }
+            other => return Err(ExecutionRequestsError::UnknownRequestType(other).into()),
+        }
+    }
+    Ok(execution_requests)
+}
+
+/// Serde support for `T` in the shape used by the beacon node API: snake_case fields with
+/// integers as decimal strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BeaconApiFormat<T>(pub T);
+
+/// Serde support for `T` in the shape used by the engine API: camelCase fields with integers as
+/// hex quantities for individual requests, and the prefix-encoded hex list (see
+/// [`get_execution_requests_list`]) for `ExecutionRequests`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineFormat<T>(pub T);
+
+mod as_hex_quantity {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{value:#x}"))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let digits = s.strip_prefix("0x").ok_or_else(|| {
+            D::Error::custom(format!("quantity `{s}` is missing the `0x` prefix"))
+        })?;
+        u64::from_str_radix(digits, 16).map_err(D::Error::custom)
+    }
+}
+
+// The beacon node API shape matches the consensus types, but unknown fields are rejected so
+// that input in the engine API shape is not accepted with defaults.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BeaconApiDepositRequest {
+    pubkey: BlsPublicKey,
+    withdrawal_credentials: Bytes32,
+    #[serde(with = "crate::serde::as_str")]
+    amount: Gwei,
+    signature: BlsSignature,
+    #[serde(with = "crate::serde::as_str")]
+    index: u64,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BeaconApiWithdrawalRequest {
+    source_address: ExecutionAddress,
+    validator_pubkey: BlsPublicKey,
+    #[serde(with = "crate::serde::as_str")]
+    amount: Gwei,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BeaconApiConsolidationRequest {
+    source_address: ExecutionAddress,
+    source_pubkey: BlsPublicKey,
+    target_pubkey: BlsPublicKey,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BeaconApiExecutionRequests {
+    deposits: Vec<BeaconApiDepositRequest>,
+    withdrawals: Vec<BeaconApiWithdrawalRequest>,
+    consolidations: Vec<BeaconApiConsolidationRequest>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct EngineDepositRequest {
+    pubkey: BlsPublicKey,
+    withdrawal_credentials: Bytes32,
+    #[serde(with = "as_hex_quantity")]
+    amount: Gwei,
+    signature: BlsSignature,
+    #[serde(with = "as_hex_quantity")]
+    index: u64,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct EngineWithdrawalRequest {
+    source_address: ExecutionAddress,
+    validator_pubkey: BlsPublicKey,
+    #[serde(with = "as_hex_quantity")]
+    amount: Gwei,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct EngineConsolidationRequest {
+    source_address: ExecutionAddress,
+    source_pubkey: BlsPublicKey,
+    target_pubkey: BlsPublicKey,
+}
+
+// Implements the serde traits for `$format<$target>` by way of the mirror type `$mirror`,
+// given conversions in both directions.
+macro_rules! impl_serde_via {
+    ($format:ident, $target:ty, $mirror:ty, $to_mirror:expr, $from_mirror:expr) => {
+        impl serde::Serialize for $format<$target> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let to_mirror: fn(&$target) -> $mirror = $to_mirror;
+                serde::Serialize::serialize(&to_mirror(&self.0), serializer)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $format<$target> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let from_mirror: fn($mirror) -> $target = $from_mirror;
+                <$mirror as serde::Deserialize>::deserialize(deserializer)
+                    .map(|mirror| Self(from_mirror(mirror)))
+            }
+        }
+    };
+}
+
+fn beacon_api_deposit(request: &DepositRequest) -> BeaconApiDepositRequest {
+    BeaconApiDepositRequest {
+        pubkey: request.public_key.clone(),
+        withdrawal_credentials: request.withdrawal_credentials.clone(),
+        amount: request.amount,
+        signature: request.signature.clone(),
+        index: request.index,
+    }
+}
+
+fn from_beacon_api_deposit(request: BeaconApiDepositRequest) -> DepositRequest {
+    DepositRequest {
+        public_key: request.pubkey,
+        withdrawal_credentials: request.withdrawal_credentials,
+        amount: request.amount,
+        signature: request.signature,
+        index: request.index,
+    }
+}
+
+fn beacon_api_withdrawal(request: &WithdrawalRequest) -> BeaconApiWithdrawalRequest {
+    BeaconApiWithdrawalRequest {
+        source_address: request.source_address.clone(),
+        validator_pubkey: request.validator_public_key.clone(),
+        amount: request.amount,
+    }
+}
+
+fn from_beacon_api_withdrawal(request: BeaconApiWithdrawalRequest) -> WithdrawalRequest {
+    WithdrawalRequest {
+        source_address: request.source_address,
+        validator_public_key: request.validator_pubkey,
+        amount: request.amount,
+    }
+}
+
+fn beacon_api_consolidation(request: &ConsolidationRequest) -> BeaconApiConsolidationRequest {
+    BeaconApiConsolidationRequest {
+        source_address: request.source_address.clone(),
+        source_pubkey: request.source_public_key.clone(),
+        target_pubkey: request.target_public_key.clone(),
+    }
+}
+
+fn from_beacon_api_consolidation(request: BeaconApiConsolidationRequest) -> ConsolidationRequest {
+    ConsolidationRequest {
+        source_address: request.source_address,
+        source_public_key: request.source_pubkey,
+        target_public_key: request.target_pubkey,
+    }
+}
+
+impl_serde_via!(
+    BeaconApiFormat,
+    DepositRequest,
+    BeaconApiDepositRequest,
+    beacon_api_deposit,
+    from_beacon_api_deposit
+);
+impl_serde_via!(
+    BeaconApiFormat,
+    WithdrawalRequest,
+    BeaconApiWithdrawalRequest,
+    beacon_api_withdrawal,
+    from_beacon_api_withdrawal
+);
+impl_serde_via!(
+    BeaconApiFormat,
+    ConsolidationRequest,
+    BeaconApiConsolidationRequest,
+    beacon_api_consolidation,
+    from_beacon_api_consolidation
+);
+fn engine_deposit(request: &DepositRequest) -> EngineDepositRequest {
+    EngineDepositRequest {
+        pubkey: request.public_key.clone(),
+        withdrawal_credentials: request.withdrawal_credentials.clone(),
+        amount: request.amount,
+        signature: request.signature.clone(),
+        index: request.index,
+    }
+}
+
+fn from_engine_deposit(request: EngineDepositRequest) -> DepositRequest {
+    DepositRequest {
+        public_key: request.pubkey,
+        withdrawal_credentials: request.withdrawal_credentials,
+        amount: request.amount,
+        signature: request.signature,
+        index: request.index,
+    }
+}
+
+fn engine_withdrawal(request: &WithdrawalRequest) -> EngineWithdrawalRequest {
+    EngineWithdrawalRequest {
+        source_address: request.source_address.clone(),
+        validator_pubkey: request.validator_public_key.clone(),
+        amount: request.amount,
+    }
+}
+
+fn from_engine_withdrawal(request: EngineWithdrawalRequest) -> WithdrawalRequest {
+    WithdrawalRequest {
+        source_address: request.source_address,
+        validator_public_key: request.validator_pubkey,
+        amount: request.amount,
+    }
+}
+
+fn engine_consolidation(request: &ConsolidationRequest) -> EngineConsolidationRequest {
+    EngineConsolidationRequest {
+        source_address: request.source_address.clone(),
+        source_pubkey: request.source_public_key.clone(),
+        target_pubkey: request.target_public_key.clone(),
+    }
+}
+
+fn from_engine_consolidation(request: EngineConsolidationRequest) -> ConsolidationRequest {
+    ConsolidationRequest {
+        source_address: request.source_address,
+        source_public_key: request.source_pubkey,
+        target_public_key: request.target_pubkey,
+    }
+}
+
+impl_serde_via!(
+    EngineFormat,
+    DepositRequest,
+    EngineDepositRequest,
+    engine_deposit,
+    from_engine_deposit
+);
+impl_serde_via!(
+    EngineFormat,
+    WithdrawalRequest,
+    EngineWithdrawalRequest,
+    engine_withdrawal,
+    from_engine_withdrawal
+);
+impl_serde_via!(
+    EngineFormat,
+    ConsolidationRequest,
+    EngineConsolidationRequest,
+    engine_consolidation,
+    from_engine_consolidation
+);
+
+impl<
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+    > serde::Serialize
+    for BeaconApiFormat<
+        ExecutionRequests<
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    >
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let requests = BeaconApiExecutionRequests {
+            deposits: self.0.deposits.iter().map(beacon_api_deposit).collect(),
+            withdrawals: self.0.withdrawals.iter().map(beacon_api_withdrawal).collect(),
+            consolidations: self.0.consolidations.iter().map(beacon_api_consolidation).collect(),
+        };
+        serde::Serialize::serialize(&requests, serializer)
+    }
+}
+
+impl<
+        'de,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+    > serde::Deserialize<'de>
+    for BeaconApiFormat<
+        ExecutionRequests<
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    >
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let requests =
+            <BeaconApiExecutionRequests as serde::Deserialize>::deserialize(deserializer)?;
+        let deposits = requests.deposits.into_iter().map(from_beacon_api_deposit).collect();
+        let withdrawals =
+            requests.withdrawals.into_iter().map(from_beacon_api_withdrawal).collect();
+        let consolidations =
+            requests.consolidations.into_iter().map(from_beacon_api_consolidation).collect();
+        Ok(Self(ExecutionRequests {
+            deposits: List::try_from(deposits).map_err(|(_, err)| D::Error::custom(err))?,
+            withdrawals: List::try_from(withdrawals).map_err(|(_, err)| D::Error::custom(err))?,
+            consolidations: List::try_from(consolidations)
+                .map_err(|(_, err)| D::Error::custom(err))?,
+        }))
+    }
+}
+
+impl<
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+    > serde::Serialize
+    for EngineFormat<
+        ExecutionRequests<
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    >
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let encoding = get_execution_requests_list(&self.0)
+            .map_err(<S::Error as serde::ser::Error>::custom)?;
+        serializer.collect_seq(encoding.iter().map(|request| format!("0x{}", hex::encode(request))))
+    }
+}
+
+impl<
+        'de,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+    > serde::Deserialize<'de>
+    for EngineFormat<
+        ExecutionRequests<
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    >
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let encoding = <Vec<String> as serde::Deserialize>::deserialize(deserializer)?;
+        let encoding = encoding
+            .iter()
+            .map(|request| {
+                let digits = request.strip_prefix("0x").ok_or_else(|| {
+                    D::Error::custom(format!("request `{request}` is missing the `0x` prefix"))
+                })?;
+                hex::decode(digits).map_err(D::Error::custom)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        get_execution_requests(&encoding).map(Self).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::electra::minimal;
+
+    // the compressed generators of G1 and G2
+    const DEPOSIT_PUBLIC_KEY: &str = "0x97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+    const SIGNATURE: &str = "0x93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8";
+
+    fn beacon_api_fixture() -> serde_json::Value {
+        serde_json::json!({
+            "deposits": [{
+                "pubkey": DEPOSIT_PUBLIC_KEY,
+                "withdrawal_credentials": "0x0100000000000000000000008943545177806ed17b9f23f0a21ee5948ecaa776",
+                "amount": "32000000000",
+                "signature": SIGNATURE,
+                "index": "57"
+            }],
+            "withdrawals": [{
+                "source_address": "0x8943545177806ed17b9f23f0a21ee5948ecaa776",
+                "validator_pubkey": DEPOSIT_PUBLIC_KEY,
+                "amount": "0"
+            }],
+            "consolidations": []
+        })
+    }
+
+    #[test]
+    fn test_beacon_api_roundtrip() {
+        let json = beacon_api_fixture();
+        let requests: BeaconApiFormat<minimal::ExecutionRequests> =
+            serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(requests.0.deposits[0].amount, 32_000_000_000);
+        assert_eq!(requests.0.deposits[0].index, 57);
+        assert_eq!(requests.0.withdrawals[0].amount, 0);
+        assert_eq!(serde_json::to_value(&requests).unwrap(), json);
+    }
+
+    #[test]
+    fn test_engine_roundtrip() {
+        let requests: BeaconApiFormat<minimal::ExecutionRequests> =
+            serde_json::from_value(beacon_api_fixture()).unwrap();
+        let json = serde_json::to_value(EngineFormat(requests.0.clone())).unwrap();
+        let encoding = json.as_array().unwrap();
+        // no consolidations, so no entry for them
+        assert_eq!(encoding.len(), 2);
+        let deposits = encoding[0].as_str().unwrap();
+        // type, then public key, withdrawal credentials, amount, signature and index
+        assert_eq!(deposits.len(), 2 + 2 * (1 + 48 + 32 + 8 + 96 + 8));
+        assert!(deposits.starts_with(&format!("0x00{}", &DEPOSIT_PUBLIC_KEY[2..])));
+        assert!(encoding[1]
+            .as_str()
+            .unwrap()
+            .starts_with("0x018943545177806ed17b9f23f0a21ee5948ecaa776"));
+
+        let recovered: EngineFormat<minimal::ExecutionRequests> =
+            serde_json::from_value(json).unwrap();
+        assert_eq!(recovered.0, requests.0);
+
+        let empty =
+            serde_json::to_value(EngineFormat(minimal::ExecutionRequests::default())).unwrap();
+        assert_eq!(empty, serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_engine_request_objects() {
+        let json = serde_json::json!({
+            "sourceAddress": "0x8943545177806ed17b9f23f0a21ee5948ecaa776",
+            "validatorPubkey": DEPOSIT_PUBLIC_KEY,
+            "amount": "0x3b9aca00"
+        });
+        let request: EngineFormat<WithdrawalRequest> =
+            serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(request.0.amount, 1_000_000_000);
+        assert_eq!(serde_json::to_value(&request).unwrap(), json);
+
+        let beacon_api = serde_json::to_value(BeaconApiFormat(request.0)).unwrap();
+        assert_eq!(beacon_api["amount"], "1000000000");
+        assert_eq!(beacon_api["validator_pubkey"], DEPOSIT_PUBLIC_KEY);
+
+        let consolidation = ConsolidationRequest::default();
+        let json = serde_json::to_value(EngineFormat(consolidation.clone())).unwrap();
+        assert!(json.get("sourcePubkey").is_some());
+        let recovered: EngineFormat<ConsolidationRequest> = serde_json::from_value(json).unwrap();
+        assert_eq!(recovered.0, consolidation);
+    }
+
+    #[test]
+    fn test_mixed_formats_are_rejected() {
+        let beacon_api = beacon_api_fixture();
+        let err =
+            serde_json::from_value::<EngineFormat<minimal::ExecutionRequests>>(beacon_api.clone())
+                .unwrap_err();
+        assert!(err.to_string().contains("expected a sequence"), "{err}");
+
+        let deposit = beacon_api["deposits"][0].clone();
+        let err = serde_json::from_value::<EngineFormat<DepositRequest>>(deposit).unwrap_err();
+        assert!(err.to_string().contains("unknown field `withdrawal_credentials`"), "{err}");
+
+        let engine = serde_json::json!({
+            "sourceAddress": "0x8943545177806ed17b9f23f0a21ee5948ecaa776",
+            "validatorPubkey": DEPOSIT_PUBLIC_KEY,
+            "amount": "0x0"
+        });
+        let err = serde_json::from_value::<BeaconApiFormat<WithdrawalRequest>>(engine).unwrap_err();
+        assert!(err.to_string().contains("unknown field `sourceAddress`"), "{err}");
+
+        let requests: BeaconApiFormat<minimal::ExecutionRequests> =
+            serde_json::from_value(beacon_api).unwrap();
+        let engine = serde_json::to_value(EngineFormat(requests.0)).unwrap();
+        let err = serde_json::from_value::<BeaconApiFormat<minimal::ExecutionRequests>>(engine)
+            .unwrap_err();
+        assert!(err.to_string().contains("expected struct"), "{err}");
+    }
+
+    #[test]
+    fn test_invalid_requests_list() {
+        let decode = |list: &[&[u8]]| get_execution_requests::<4, 2, 2>(list);
+        let withdrawal = [&[WITHDRAWAL_REQUEST_TYPE][..], &[0u8; 76]].concat();
+        let consolidation = [&[CONSOLIDATION_REQUEST_TYPE][..], &[0u8; 116]].concat();
+        assert_eq!(decode(&[&withdrawal, &consolidation]).unwrap().consolidations.len(), 1);
+        assert!(matches!(
+            decode(&[&consolidation, &withdrawal]),
+            Err(Error::ExecutionRequests(ExecutionRequestsError::OutOfOrder {
+                request_type: WITHDRAWAL_REQUEST_TYPE,
+                previous: CONSOLIDATION_REQUEST_TYPE,
+            }))
+        ));
+        assert!(matches!(
+            decode(&[&withdrawal, &withdrawal]),
+            Err(Error::ExecutionRequests(ExecutionRequestsError::OutOfOrder { .. }))
+        ));
+        assert!(matches!(
+            decode(&[&[WITHDRAWAL_REQUEST_TYPE]]),
+            Err(Error::ExecutionRequests(ExecutionRequestsError::EmptyRequestData(0x01)))
+        ));
+        assert!(matches!(
+            decode(&[&[0x03, 0]]),
+            Err(Error::ExecutionRequests(ExecutionRequestsError::UnknownRequestType(0x03)))
+        ));
+        assert!(matches!(decode(&[&withdrawal[..40]]), Err(Error::SimpleSerialize(_))));
+    }
+}
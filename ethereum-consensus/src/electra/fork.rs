@@ -1,17 +1,15 @@
 use crate::{
     deneb,
     electra::{
-        beacon_state::BeaconState,
-        constants::UNSET_DEPOSIT_RECEIPTS_START_INDEX,
-        execution_payload::ExecutionPayloadHeader,
+        beacon_state::{BeaconState, PendingDeposit},
+        constants::UNSET_DEPOSIT_REQUESTS_START_INDEX,
         helpers::{
             get_activation_exit_churn_limit, get_consolidation_churn_limit,
-            has_compounding_withdrawal_credential, queue_entire_balance_and_reset_validator,
-            queue_excess_active_balance,
+            has_compounding_withdrawal_credential, queue_excess_active_balance,
         },
     },
     phase0::{helpers::compute_activation_exit_epoch, Fork},
-    primitives::FAR_FUTURE_EPOCH,
+    primitives::{BlsSignature, Epoch, FAR_FUTURE_EPOCH, GENESIS_SLOT},
     state_transition::Context,
     Error,
 };
@@ -27,7 +25,7 @@ pub fn upgrade_to_electra<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
-    const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
     const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
     const PENDING_CONSOLIDATIONS_LIMIT: usize,
 >(
@@ -56,43 +54,19 @@ pub fn upgrade_to_electra<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
-        PENDING_BALANCE_DEPOSITS_LIMIT,
+        PENDING_DEPOSITS_LIMIT,
         PENDING_PARTIAL_WITHDRAWALS_LIMIT,
         PENDING_CONSOLIDATIONS_LIMIT,
     >,
     Error,
 > {
     let epoch = deneb::get_current_epoch(state, context);
-    let latest_execution_payload_header = &state.latest_execution_payload_header;
-    let latest_execution_payload_header = ExecutionPayloadHeader {
-        parent_hash: latest_execution_payload_header.parent_hash.clone(),
-        fee_recipient: latest_execution_payload_header.fee_recipient.clone(),
-        state_root: latest_execution_payload_header.state_root.clone(),
-        receipts_root: latest_execution_payload_header.receipts_root.clone(),
-        logs_bloom: latest_execution_payload_header.logs_bloom.clone(),
-        prev_randao: latest_execution_payload_header.prev_randao.clone(),
-        block_number: latest_execution_payload_header.block_number,
-        gas_limit: latest_execution_payload_header.gas_limit,
-        gas_used: latest_execution_payload_header.gas_used,
-        timestamp: latest_execution_payload_header.timestamp,
-        extra_data: latest_execution_payload_header.extra_data.clone(),
-        base_fee_per_gas: latest_execution_payload_header.base_fee_per_gas,
-        block_hash: latest_execution_payload_header.block_hash.clone(),
-        transactions_root: latest_execution_payload_header.transactions_root,
-        withdrawals_root: latest_execution_payload_header.withdrawals_root,
-        blob_gas_used: latest_execution_payload_header.blob_gas_used,
-        excess_blob_gas: latest_execution_payload_header.excess_blob_gas,
-        deposit_receipts_root: Default::default(),
-        withdrawal_requests_root: Default::default(),
-    };
-
-    let exit_epoch = state
+    let earliest_exit_epoch = state
         .validators
         .iter()
         .filter_map(|v| if v.exit_epoch != FAR_FUTURE_EPOCH { Some(v.exit_epoch) } else { None })
-        .max()
-        .or(Some(epoch));
-    let earliest_exit_epoch = exit_epoch.unwrap() + 1;
+        .fold(compute_activation_exit_epoch(epoch, context), Epoch::max) +
+        1;
 
     let mut post = BeaconState {
         genesis_time: state.genesis_time,
@@ -123,17 +97,17 @@ pub fn upgrade_to_electra<
         inactivity_scores: state.inactivity_scores.clone(),
         current_sync_committee: state.current_sync_committee.clone(),
         next_sync_committee: state.next_sync_committee.clone(),
-        latest_execution_payload_header,
+        latest_execution_payload_header: state.latest_execution_payload_header.clone(),
         next_withdrawal_index: state.next_withdrawal_index,
         next_withdrawal_validator_index: state.next_withdrawal_validator_index,
         historical_summaries: state.historical_summaries.clone(),
-        deposit_receipts_start_index: UNSET_DEPOSIT_RECEIPTS_START_INDEX,
+        deposit_requests_start_index: UNSET_DEPOSIT_REQUESTS_START_INDEX,
         deposit_balance_to_consume: 0,
         exit_balance_to_consume: 0,
         earliest_exit_epoch,
         consolidation_balance_to_consume: 0,
         earliest_consolidation_epoch: compute_activation_exit_epoch(epoch, context),
-        pending_balance_deposits: Default::default(),
+        pending_deposits: Default::default(),
         pending_partial_withdrawals: Default::default(),
         pending_consolidations: Default::default(),
     };
@@ -154,8 +128,20 @@ pub fn upgrade_to_electra<
         .collect::<Vec<_>>();
     pre_activation_validators.sort();
 
+    // their balance is queued as deposits, to be applied within the activation churn
     for (_, index) in pre_activation_validators {
-        queue_entire_balance_and_reset_validator(&mut post, index);
+        let balance = post.balances[index];
+        post.balances[index] = 0;
+        let validator = &mut post.validators[index];
+        validator.effective_balance = 0;
+        validator.activation_eligibility_epoch = FAR_FUTURE_EPOCH;
+        post.pending_deposits.push(PendingDeposit {
+            public_key: validator.public_key.clone(),
+            withdrawal_credentials: validator.withdrawal_credentials.clone(),
+            amount: balance,
+            signature: BlsSignature::infinity(),
+            slot: GENESIS_SLOT,
+        });
     }
 
     let indices_to_queue = post
@@ -1,15 +1,19 @@
 use crate::{
+    crypto::SecretKey,
     electra::{
-        get_next_sync_committee, increase_balance, process_deposit, BeaconBlockBody,
-        BeaconBlockHeader, BeaconState, Deposit, DepositData, Eth1Data, ExecutionPayloadHeader,
-        Fork, DEPOSIT_DATA_LIST_BOUND,
+        add_validator_to_registry, constants::UNSET_DEPOSIT_REQUESTS_START_INDEX,
+        get_max_effective_balance, get_next_sync_committee, increase_balance, process_deposit,
+        BeaconBlockBody, BeaconBlockHeader, BeaconState, Deposit, DepositData, Eth1Data,
+        ExecutionPayloadHeader, Fork, DEPOSIT_DATA_LIST_BOUND,
     },
+    interop::interop_deposit_data,
     primitives::{Gwei, Hash32, GENESIS_EPOCH},
     ssz::prelude::*,
     state_transition::{Context, Result},
 };
 
-pub fn initialize_beacon_state_from_eth1<
+// The genesis state before any validators are added, for `deposit_count` deposits.
+fn genesis_state_without_validators<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
     const ETH1_DATA_VOTES_BOUND: usize,
@@ -18,7 +22,7 @@ pub fn initialize_beacon_state_from_eth1<
     const EPOCHS_PER_SLASHINGS_VECTOR: usize,
     const MAX_VALIDATORS_PER_COMMITTEE: usize,
     const SYNC_COMMITTEE_SIZE: usize,
-    const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
     const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
     const PENDING_CONSOLIDATIONS_LIMIT: usize,
     const MAX_PROPOSER_SLASHINGS: usize,
@@ -33,15 +37,15 @@ pub fn initialize_beacon_state_from_eth1<
     const MAX_BYTES_PER_TRANSACTION: usize,
     const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
     const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
-    const MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD: usize,
-    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
     const MAX_BLS_TO_EXECUTION_CHANGES: usize,
     const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
-    const MAX_CONSOLIDATIONS: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
 >(
     eth1_block_hash: Hash32,
     eth1_timestamp: u64,
-    deposits: &[Deposit],
+    deposit_count: usize,
     execution_payload_header: Option<
         &ExecutionPayloadHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
     >,
@@ -58,7 +62,7 @@ pub fn initialize_beacon_state_from_eth1<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
-        PENDING_BALANCE_DEPOSITS_LIMIT,
+        PENDING_DEPOSITS_LIMIT,
         PENDING_PARTIAL_WITHDRAWALS_LIMIT,
         PENDING_CONSOLIDATIONS_LIMIT,
     >,
@@ -70,7 +74,7 @@ pub fn initialize_beacon_state_from_eth1<
     };
     let eth1_data = Eth1Data {
         block_hash: eth1_block_hash.clone(),
-        deposit_count: deposits.len() as u64,
+        deposit_count: deposit_count as u64,
         ..Default::default()
     };
     let latest_block_body = BeaconBlockBody::<
@@ -87,11 +91,11 @@ pub fn initialize_beacon_state_from_eth1<
         MAX_BYTES_PER_TRANSACTION,
         MAX_TRANSACTIONS_PER_PAYLOAD,
         MAX_WITHDRAWALS_PER_PAYLOAD,
-        MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD,
-        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
-        MAX_CONSOLIDATIONS,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >::default();
     let body_root = latest_block_body.hash_tree_root()?;
     let latest_block_header = BeaconBlockHeader { body_root, ..Default::default() };
@@ -102,26 +106,61 @@ pub fn initialize_beacon_state_from_eth1<
     )
     .map_err(|(_, err)| err)?;
     let execution_payload_header = execution_payload_header.cloned().unwrap_or_default();
-    let mut state = BeaconState {
+    let state = BeaconState {
         genesis_time: eth1_timestamp + context.genesis_delay,
         fork,
         eth1_data,
         latest_block_header,
         randao_mixes,
         latest_execution_payload_header: execution_payload_header,
+        deposit_requests_start_index: UNSET_DEPOSIT_REQUESTS_START_INDEX,
         ..Default::default()
     };
+    Ok(state)
+}
 
-    let mut leaves = List::<DepositData, DEPOSIT_DATA_LIST_BOUND>::default();
-    for deposit in deposits.iter() {
-        leaves.push(deposit.data.clone());
-        state.eth1_data.deposit_root = leaves.hash_tree_root()?;
-        process_deposit(&mut state, deposit, context)?;
-    }
-
-    let pending_balance_deposits = std::mem::take(&mut state.pending_balance_deposits);
-    for deposit in pending_balance_deposits.iter() {
-        increase_balance(&mut state, deposit.index, deposit.amount);
+// Apply the deposits queued while adding the genesis validators, and activate the validators
+// with enough balance.
+fn activate_genesis_validators<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    context: &Context,
+) -> Result<()> {
+    let pending_deposits = std::mem::take(&mut state.pending_deposits);
+    for deposit in pending_deposits.iter() {
+        let index = state
+            .validators
+            .iter()
+            .position(|validator| validator.public_key == deposit.public_key)
+            .expect("deposits are only queued for validators in the registry");
+        increase_balance(state, index, deposit.amount);
     }
 
     for i in 0..state.validators.len() {
@@ -129,10 +168,10 @@ pub fn initialize_beacon_state_from_eth1<
         let balance = state.balances[i];
         let effective_balance = Gwei::min(
             balance - balance % context.effective_balance_increment,
-            context.max_effective_balance,
+            get_max_effective_balance(validator, context),
         );
         validator.effective_balance = effective_balance;
-        if validator.effective_balance == context.max_effective_balance {
+        if validator.effective_balance >= context.min_activation_balance {
             validator.activation_eligibility_epoch = GENESIS_EPOCH;
             validator.activation_epoch = GENESIS_EPOCH;
         }
@@ -140,9 +179,305 @@ pub fn initialize_beacon_state_from_eth1<
 
     state.genesis_validators_root = state.validators.hash_tree_root()?;
 
-    let sync_committee = get_next_sync_committee(&state, context)?;
+    let sync_committee = get_next_sync_committee(state, context)?;
     state.current_sync_committee = sync_committee.clone();
     state.next_sync_committee = sync_committee;
 
+    Ok(())
+}
+
+pub fn initialize_beacon_state_from_eth1<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    eth1_block_hash: Hash32,
+    eth1_timestamp: u64,
+    deposits: &[Deposit],
+    execution_payload_header: Option<
+        &ExecutionPayloadHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    >,
+    context: &Context,
+) -> Result<
+    BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+> {
+    let mut state =
+        genesis_state_without_validators::<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >(
+            eth1_block_hash, eth1_timestamp, deposits.len(), execution_payload_header, context
+        )?;
+
+    let mut leaves = List::<DepositData, DEPOSIT_DATA_LIST_BOUND>::default();
+    for deposit in deposits.iter() {
+        leaves.push(deposit.data.clone());
+        state.eth1_data.deposit_root = leaves.hash_tree_root()?;
+        process_deposit(&mut state, deposit, context)?;
+    }
+
+    activate_genesis_validators(&mut state, context)?;
+    Ok(state)
+}
+
+/// A genesis state like [`initialize_beacon_state_from_eth1`] for the interop validators with
+/// `secret_keys` (see [`crate::interop`]), each with a deposit of the minimum activation balance,
+/// as used to start devnets without a deposit contract.
+///
+/// The deposits are added to the registry directly rather than proven against the deposit root,
+/// which still commits to them so that the `eth1_data` of the state is consistent.
+pub fn initialize_beacon_state_from_interop<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    eth1_block_hash: Hash32,
+    eth1_timestamp: u64,
+    secret_keys: &[SecretKey],
+    execution_payload_header: Option<
+        &ExecutionPayloadHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    >,
+    context: &Context,
+) -> Result<
+    BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+> {
+    let mut state =
+        genesis_state_without_validators::<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >(
+            eth1_block_hash, eth1_timestamp, secret_keys.len(), execution_payload_header, context
+        )?;
+
+    let mut leaves = List::<DepositData, DEPOSIT_DATA_LIST_BOUND>::default();
+    for secret_key in secret_keys {
+        let data = interop_deposit_data(secret_key, context.min_activation_balance, context)?;
+        add_validator_to_registry(
+            &mut state,
+            data.public_key.clone(),
+            data.withdrawal_credentials.clone(),
+            data.amount,
+            context,
+        );
+        leaves.push(data);
+    }
+    state.eth1_data.deposit_root = leaves.hash_tree_root()?;
+    state.eth1_deposit_index = secret_keys.len() as u64;
+
+    activate_genesis_validators(&mut state, context)?;
     Ok(state)
 }
+
+#[cfg(test)]
+mod tests {
+    // the aliases of the preset, rather than the generic types of the parent module
+    use crate::{
+        electra::minimal::*,
+        eth1::DepositCache,
+        interop::{interop_deposit_data, interop_secret_keys},
+        primitives::{Hash32, GENESIS_EPOCH},
+        state_transition::Context,
+    };
+
+    macro_rules! genesis_with {
+        ($initialize:ident, $validators:expr, $context:expr) => {
+            $initialize::<
+                SLOTS_PER_HISTORICAL_ROOT,
+                HISTORICAL_ROOTS_LIMIT,
+                ETH1_DATA_VOTES_BOUND,
+                VALIDATOR_REGISTRY_LIMIT,
+                EPOCHS_PER_HISTORICAL_VECTOR,
+                EPOCHS_PER_SLASHINGS_VECTOR,
+                MAX_VALIDATORS_PER_COMMITTEE,
+                SYNC_COMMITTEE_SIZE,
+                PENDING_DEPOSITS_LIMIT,
+                PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+                PENDING_CONSOLIDATIONS_LIMIT,
+                MAX_PROPOSER_SLASHINGS,
+                MAX_VALIDATORS_PER_SLOT,
+                MAX_COMMITTEES_PER_SLOT,
+                MAX_ATTESTER_SLASHINGS_ELECTRA,
+                MAX_ATTESTATIONS_ELECTRA,
+                MAX_DEPOSITS,
+                MAX_VOLUNTARY_EXITS,
+                BYTES_PER_LOGS_BLOOM,
+                MAX_EXTRA_DATA_BYTES,
+                MAX_BYTES_PER_TRANSACTION,
+                MAX_TRANSACTIONS_PER_PAYLOAD,
+                MAX_WITHDRAWALS_PER_PAYLOAD,
+                MAX_BLS_TO_EXECUTION_CHANGES,
+                MAX_BLOB_COMMITMENTS_PER_BLOCK,
+                MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+                MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+                MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+            >(Hash32::default(), $context.min_genesis_time, $validators, None, $context)
+            .unwrap()
+        };
+    }
+
+    #[test]
+    fn test_interop_genesis_matches_deposits() {
+        let context = Context::for_minimal();
+        let secret_keys = interop_secret_keys(context.min_genesis_active_validator_count);
+        let state: BeaconState =
+            genesis_with!(initialize_beacon_state_from_interop, &secret_keys, &context);
+        assert_eq!(state.validators.len(), secret_keys.len());
+        assert_eq!(state.deposit_requests_start_index, UNSET_DEPOSIT_REQUESTS_START_INDEX);
+        assert!(state.pending_deposits.is_empty());
+        assert!(state.validators.iter().all(|validator| {
+            validator.activation_epoch == GENESIS_EPOCH &&
+                validator.effective_balance == context.min_activation_balance
+        }));
+        assert!(is_valid_genesis_state(&state, &context));
+
+        // the same validators deposited through the deposit contract
+        let mut cache = DepositCache::default();
+        for (index, secret_key) in secret_keys.iter().enumerate() {
+            let data =
+                interop_deposit_data(secret_key, context.min_activation_balance, &context).unwrap();
+            cache.insert(data, index as u64, 0, Hash32::default()).unwrap();
+        }
+        let deposits = (0..cache.len())
+            .map(|index| cache.deposit_at_count(index, index + 1).unwrap())
+            .collect::<Vec<_>>();
+        let from_deposits: BeaconState =
+            genesis_with!(initialize_beacon_state_from_eth1, &deposits, &context);
+        assert_eq!(state.eth1_data.deposit_root, cache.root());
+        assert_eq!(state, from_deposits);
+
+        // too few validators for the preset
+        let state: BeaconState =
+            genesis_with!(initialize_beacon_state_from_interop, &secret_keys[1..], &context);
+        assert!(!is_valid_genesis_state(&state, &context));
+    }
+}
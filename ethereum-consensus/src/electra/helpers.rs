@@ -1,16 +1,19 @@
 use crate::{
     altair::{PROPOSER_WEIGHT, WEIGHT_DENOMINATOR},
     capella::has_eth1_withdrawal_credential,
+    crypto::hash,
     electra::{
-        beacon_state::{BeaconState, PendingBalanceDeposit},
-        decrease_balance, get_beacon_committee, get_beacon_proposer_index, get_current_epoch,
+        beacon_state::{BeaconState, PendingDeposit},
+        compute_shuffled_index, decrease_balance, get_active_validator_indices,
+        get_beacon_committee, get_beacon_proposer_index, get_current_epoch, get_seed,
         get_total_active_balance, increase_balance,
         operations::{Attestation, IndexedAttestation},
     },
+    error::{invalid_operation_error, InvalidAttestation, InvalidOperation},
     phase0::{compute_activation_exit_epoch, Validator},
     primitives::{
-        Bytes32, CommitteeIndex, Epoch, Gwei, ValidatorIndex, COMPOUNDING_WITHDRAWAL_PREFIX,
-        FAR_FUTURE_EPOCH,
+        BlsSignature, Bytes32, CommitteeIndex, DomainType, Epoch, Gwei, ValidatorIndex,
+        COMPOUNDING_WITHDRAWAL_PREFIX, FAR_FUTURE_EPOCH, GENESIS_SLOT,
     },
     ssz::prelude::*,
     state_transition::Context,
@@ -47,7 +50,7 @@ pub fn is_partially_withdrawable_validator(
     balance: Gwei,
     context: &Context,
 ) -> bool {
-    let max_effective_balance = get_validator_max_effective_balance(validator, context);
+    let max_effective_balance = get_max_effective_balance(validator, context);
     let has_max_effective_balance = validator.effective_balance == max_effective_balance;
     let has_excess_balance = balance > max_effective_balance;
     has_execution_withdrawal_credential(validator) &&
@@ -61,7 +64,7 @@ pub fn get_committee_indices<const MAX_COMMITTEES_PER_SLOT: usize>(
     committee_bits.iter().enumerate().flat_map(|(i, bit)| bit.then_some(i)).collect()
 }
 
-pub fn get_validator_max_effective_balance(validator: &Validator, context: &Context) -> Gwei {
+pub fn get_max_effective_balance(validator: &Validator, context: &Context) -> Gwei {
     if has_compounding_withdrawal_credential(validator) {
         context.max_effective_balance_electra
     } else {
@@ -80,7 +83,7 @@ pub fn get_balance_churn_limit<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
-    const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
     const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
     const PENDING_CONSOLIDATIONS_LIMIT: usize,
 >(
@@ -95,7 +98,7 @@ pub fn get_balance_churn_limit<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
-        PENDING_BALANCE_DEPOSITS_LIMIT,
+        PENDING_DEPOSITS_LIMIT,
         PENDING_PARTIAL_WITHDRAWALS_LIMIT,
         PENDING_CONSOLIDATIONS_LIMIT,
     >,
@@ -117,7 +120,7 @@ pub fn get_activation_exit_churn_limit<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
-    const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
     const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
     const PENDING_CONSOLIDATIONS_LIMIT: usize,
 >(
@@ -132,7 +135,7 @@ pub fn get_activation_exit_churn_limit<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
-        PENDING_BALANCE_DEPOSITS_LIMIT,
+        PENDING_DEPOSITS_LIMIT,
         PENDING_PARTIAL_WITHDRAWALS_LIMIT,
         PENDING_CONSOLIDATIONS_LIMIT,
     >,
@@ -153,7 +156,7 @@ pub fn get_consolidation_churn_limit<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
-    const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
     const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
     const PENDING_CONSOLIDATIONS_LIMIT: usize,
 >(
@@ -168,7 +171,7 @@ pub fn get_consolidation_churn_limit<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
-        PENDING_BALANCE_DEPOSITS_LIMIT,
+        PENDING_DEPOSITS_LIMIT,
         PENDING_PARTIAL_WITHDRAWALS_LIMIT,
         PENDING_CONSOLIDATIONS_LIMIT,
     >,
@@ -188,7 +191,7 @@ pub fn get_active_balance<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
-    const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
     const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
     const PENDING_CONSOLIDATIONS_LIMIT: usize,
 >(
@@ -203,7 +206,7 @@ pub fn get_active_balance<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
-        PENDING_BALANCE_DEPOSITS_LIMIT,
+        PENDING_DEPOSITS_LIMIT,
         PENDING_PARTIAL_WITHDRAWALS_LIMIT,
         PENDING_CONSOLIDATIONS_LIMIT,
     >,
@@ -211,7 +214,7 @@ pub fn get_active_balance<
     context: &Context,
 ) -> Gwei {
     let max_effective_balance =
-        get_validator_max_effective_balance(&state.validators[validator_index], context);
+        get_max_effective_balance(&state.validators[validator_index], context);
     state.balances[validator_index].min(max_effective_balance)
 }
 
@@ -226,7 +229,7 @@ pub fn get_pending_balance_to_withdraw<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
-    const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
     const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
     const PENDING_CONSOLIDATIONS_LIMIT: usize,
 >(
@@ -241,7 +244,7 @@ pub fn get_pending_balance_to_withdraw<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
-        PENDING_BALANCE_DEPOSITS_LIMIT,
+        PENDING_DEPOSITS_LIMIT,
         PENDING_PARTIAL_WITHDRAWALS_LIMIT,
         PENDING_CONSOLIDATIONS_LIMIT,
     >,
@@ -251,7 +254,7 @@ pub fn get_pending_balance_to_withdraw<
         .pending_partial_withdrawals
         .iter()
         .filter_map(|withdrawal| {
-            if withdrawal.index == validator_index {
+            if withdrawal.validator_index == validator_index {
                 Some(withdrawal.amount)
             } else {
                 None
@@ -271,7 +274,7 @@ pub fn get_attesting_indices<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
-    const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
     const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
     const PENDING_CONSOLIDATIONS_LIMIT: usize,
     const MAX_VALIDATORS_PER_SLOT: usize,
@@ -288,7 +291,7 @@ pub fn get_attesting_indices<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
-        PENDING_BALANCE_DEPOSITS_LIMIT,
+        PENDING_DEPOSITS_LIMIT,
         PENDING_PARTIAL_WITHDRAWALS_LIMIT,
         PENDING_CONSOLIDATIONS_LIMIT,
     >,
@@ -299,13 +302,24 @@ pub fn get_attesting_indices<
 
     let mut indices = HashSet::with_capacity(MAX_VALIDATORS_PER_SLOT);
 
-    for (committee_offset, index) in committee_indices.into_iter().enumerate() {
+    let mut committee_offset = 0;
+    for index in committee_indices {
         let committee = get_beacon_committee(state, attestation.data.slot, index, context)?;
+        let expected_length = committee_offset + committee.len();
+        if attestation.aggregation_bits.len() < expected_length {
+            return Err(invalid_operation_error(InvalidOperation::Attestation(
+                InvalidAttestation::Bitfield {
+                    expected_length,
+                    length: attestation.aggregation_bits.len(),
+                },
+            )))
+        }
         for (i, validator_index) in committee.iter().enumerate() {
             if attestation.aggregation_bits[committee_offset + i] {
                 indices.insert(*validator_index);
             }
         }
+        committee_offset = expected_length;
     }
 
     Ok(indices)
@@ -322,7 +336,7 @@ pub fn get_indexed_attestation<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
-    const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
     const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
     const PENDING_CONSOLIDATIONS_LIMIT: usize,
     const MAX_VALIDATORS_PER_SLOT: usize,
@@ -339,7 +353,7 @@ pub fn get_indexed_attestation<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
-        PENDING_BALANCE_DEPOSITS_LIMIT,
+        PENDING_DEPOSITS_LIMIT,
         PENDING_PARTIAL_WITHDRAWALS_LIMIT,
         PENDING_CONSOLIDATIONS_LIMIT,
     >,
@@ -369,7 +383,7 @@ pub fn initiate_validator_exit<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
-    const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
     const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
     const PENDING_CONSOLIDATIONS_LIMIT: usize,
 >(
@@ -384,7 +398,7 @@ pub fn initiate_validator_exit<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
-        PENDING_BALANCE_DEPOSITS_LIMIT,
+        PENDING_DEPOSITS_LIMIT,
         PENDING_PARTIAL_WITHDRAWALS_LIMIT,
         PENDING_CONSOLIDATIONS_LIMIT,
     >,
@@ -420,11 +434,9 @@ pub fn switch_to_compounding_validator<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
-    const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
     const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
     const PENDING_CONSOLIDATIONS_LIMIT: usize,
-    const MAX_VALIDATORS_PER_SLOT: usize,
-    const MAX_COMMITTEES_PER_SLOT: usize,
 >(
     state: &mut BeaconState<
         SLOTS_PER_HISTORICAL_ROOT,
@@ -437,18 +449,15 @@ pub fn switch_to_compounding_validator<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
-        PENDING_BALANCE_DEPOSITS_LIMIT,
+        PENDING_DEPOSITS_LIMIT,
         PENDING_PARTIAL_WITHDRAWALS_LIMIT,
         PENDING_CONSOLIDATIONS_LIMIT,
     >,
     index: ValidatorIndex,
     context: &Context,
 ) -> Result<(), Error> {
-    if has_eth1_withdrawal_credential(&state.validators[index]) {
-        state.validators[index].withdrawal_credentials[0] = COMPOUNDING_WITHDRAWAL_PREFIX;
-        queue_excess_active_balance(state, index, context)?;
-    }
-    Ok(())
+    state.validators[index].withdrawal_credentials[0] = COMPOUNDING_WITHDRAWAL_PREFIX;
+    queue_excess_active_balance(state, index, context)
 }
 
 pub fn queue_excess_active_balance<
@@ -462,7 +471,7 @@ pub fn queue_excess_active_balance<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
-    const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
     const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
     const PENDING_CONSOLIDATIONS_LIMIT: usize,
 >(
@@ -477,7 +486,7 @@ pub fn queue_excess_active_balance<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
-        PENDING_BALANCE_DEPOSITS_LIMIT,
+        PENDING_DEPOSITS_LIMIT,
         PENDING_PARTIAL_WITHDRAWALS_LIMIT,
         PENDING_CONSOLIDATIONS_LIMIT,
     >,
@@ -489,12 +498,34 @@ pub fn queue_excess_active_balance<
         let excess_balance =
             balance.checked_sub(context.min_activation_balance).ok_or(Error::Underflow)?;
         state.balances[index] = context.min_activation_balance;
-        state.pending_balance_deposits.push(PendingBalanceDeposit { index, amount: excess_balance })
+        // the deposit is not checked against a signature, so it uses the point at infinity and
+        // the genesis slot to be told apart from deposits made on the execution layer
+        let validator = &state.validators[index];
+        state.pending_deposits.push(PendingDeposit {
+            public_key: validator.public_key.clone(),
+            withdrawal_credentials: validator.withdrawal_credentials.clone(),
+            amount: excess_balance,
+            signature: BlsSignature::infinity(),
+            slot: GENESIS_SLOT,
+        })
     }
     Ok(())
 }
 
-pub fn queue_entire_balance_and_reset_validator<
+// The two bytes of `hash(hash_input)` selected by `i`, where `hash_input` holds the seed followed
+// by `i / 16`, read as a little-endian integer.
+fn random_u16(hash_input: &[u8], i: usize) -> u64 {
+    let offset = i % 16 * 2;
+    let random_bytes = hash(hash_input);
+    let random_bytes = random_bytes.as_ref();
+    u16::from_le_bytes([random_bytes[offset], random_bytes[offset + 1]]) as u64
+}
+
+/// Sample `candidate_index` as a proposer with probability proportional to its effective balance,
+/// out of `MAX_EFFECTIVE_BALANCE_ELECTRA` so that compounding validators are weighted by their
+/// larger balance. The random value is 16 bits wide to keep the sampling fine enough across that
+/// wider range of balances.
+pub fn sample_proposer_index<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
     const ETH1_DATA_VOTES_BOUND: usize,
@@ -505,11 +536,11 @@ pub fn queue_entire_balance_and_reset_validator<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
-    const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
     const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
     const PENDING_CONSOLIDATIONS_LIMIT: usize,
 >(
-    state: &mut BeaconState<
+    state: &BeaconState<
         SLOTS_PER_HISTORICAL_ROOT,
         HISTORICAL_ROOTS_LIMIT,
         ETH1_DATA_VOTES_BOUND,
@@ -520,19 +551,96 @@ pub fn queue_entire_balance_and_reset_validator<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
-        PENDING_BALANCE_DEPOSITS_LIMIT,
+        PENDING_DEPOSITS_LIMIT,
         PENDING_PARTIAL_WITHDRAWALS_LIMIT,
         PENDING_CONSOLIDATIONS_LIMIT,
     >,
-    index: ValidatorIndex,
-) {
-    let balance = state.balances[index];
-    state.balances[index] = 0;
+    candidate_index: ValidatorIndex,
+    round: usize,
+    hash_input: &mut [u8],
+    context: &Context,
+) -> Option<ValidatorIndex> {
+    let max_random_value = u16::MAX as u64;
+    let round_bytes: [u8; 8] = ((round / 16) as u64).to_le_bytes();
+    hash_input[32..].copy_from_slice(&round_bytes);
+    let random_value = random_u16(hash_input, round);
+
+    let effective_balance = state.validators[candidate_index].effective_balance;
+    if effective_balance * max_random_value >= context.max_effective_balance_electra * random_value
+    {
+        Some(candidate_index)
+    } else {
+        None
+    }
+}
 
-    let validator = &mut state.validators[index];
-    validator.effective_balance = 0;
-    validator.activation_eligibility_epoch = FAR_FUTURE_EPOCH;
-    state.pending_balance_deposits.push(PendingBalanceDeposit { index, amount: balance });
+// Return the sync committee indices, with possible duplicates, for the next sync committee.
+pub fn get_next_sync_committee_indices<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    context: &Context,
+) -> Result<Vec<ValidatorIndex>, Error> {
+    let epoch = get_current_epoch(state, context) + 1;
+    let max_random_value = u16::MAX as u64;
+    let active_validator_indices = get_active_validator_indices(state, epoch);
+    let active_validator_count = active_validator_indices.len();
+    if active_validator_count == 0 {
+        return Err(Error::NoActiveValidators(epoch))
+    }
+    let seed = get_seed(state, epoch, DomainType::SyncCommittee, context);
+    let mut i: usize = 0;
+    let mut sync_committee_indices = vec![];
+    let mut hash_input = [0u8; 40];
+    hash_input[..32].copy_from_slice(seed.as_ref());
+    while sync_committee_indices.len() < context.sync_committee_size {
+        let shuffled_index = compute_shuffled_index(
+            i % active_validator_count,
+            active_validator_count,
+            &seed,
+            context,
+        )?;
+        let candidate_index = active_validator_indices[shuffled_index];
+
+        let i_bytes: [u8; 8] = ((i / 16) as u64).to_le_bytes();
+        hash_input[32..].copy_from_slice(&i_bytes);
+        let random_value = random_u16(&hash_input, i);
+        let effective_balance = state.validators[candidate_index].effective_balance;
+
+        if effective_balance * max_random_value >=
+            context.max_effective_balance_electra * random_value
+        {
+            sync_committee_indices.push(candidate_index);
+        }
+        i += 1;
+    }
+    Ok(sync_committee_indices)
 }
 
 pub fn compute_exit_epoch_and_update_churn<
@@ -546,7 +654,7 @@ pub fn compute_exit_epoch_and_update_churn<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
-    const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
     const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
     const PENDING_CONSOLIDATIONS_LIMIT: usize,
 >(
@@ -561,7 +669,7 @@ pub fn compute_exit_epoch_and_update_churn<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
-        PENDING_BALANCE_DEPOSITS_LIMIT,
+        PENDING_DEPOSITS_LIMIT,
         PENDING_PARTIAL_WITHDRAWALS_LIMIT,
         PENDING_CONSOLIDATIONS_LIMIT,
     >,
@@ -602,7 +710,7 @@ pub fn compute_consolidation_epoch_and_update_churn<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
-    const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
     const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
     const PENDING_CONSOLIDATIONS_LIMIT: usize,
 >(
@@ -617,7 +725,7 @@ pub fn compute_consolidation_epoch_and_update_churn<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
-        PENDING_BALANCE_DEPOSITS_LIMIT,
+        PENDING_DEPOSITS_LIMIT,
         PENDING_PARTIAL_WITHDRAWALS_LIMIT,
         PENDING_CONSOLIDATIONS_LIMIT,
     >,
@@ -628,7 +736,7 @@ pub fn compute_consolidation_epoch_and_update_churn<
     let activation_exit_epoch = compute_activation_exit_epoch(current_epoch, context);
     let mut earliest_consolidation_epoch =
         state.earliest_consolidation_epoch.max(activation_exit_epoch);
-    let per_epoch_churn = get_activation_exit_churn_limit(state, context)?;
+    let per_epoch_churn = get_consolidation_churn_limit(state, context)?;
     let mut consolidation_balance_to_consume =
         if state.earliest_consolidation_epoch < earliest_consolidation_epoch {
             per_epoch_churn
@@ -661,7 +769,7 @@ pub fn slash_validator<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
-    const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
     const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
     const PENDING_CONSOLIDATIONS_LIMIT: usize,
 >(
@@ -676,7 +784,7 @@ pub fn slash_validator<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
-        PENDING_BALANCE_DEPOSITS_LIMIT,
+        PENDING_DEPOSITS_LIMIT,
         PENDING_PARTIAL_WITHDRAWALS_LIMIT,
         PENDING_CONSOLIDATIONS_LIMIT,
     >,
@@ -691,7 +799,7 @@ pub fn slash_validator<
         state.validators[slashed_index].withdrawable_epoch,
         epoch + context.epochs_per_slashings_vector,
     );
-    let slashings_index = epoch as usize % EPOCHS_PER_SLASHINGS_VECTOR;
+    let slashings_index = (epoch % EPOCHS_PER_SLASHINGS_VECTOR as u64) as usize;
     state.slashings[slashings_index] += state.validators[slashed_index].effective_balance;
     decrease_balance(
         state,
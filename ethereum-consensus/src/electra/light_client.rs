@@ -0,0 +1,521 @@
+use crate::{
+    altair::light_client::{
+        is_valid_light_client_branch, CURRENT_SYNC_COMMITTEE_INDEX, FINALIZED_ROOT_INDEX,
+        NEXT_SYNC_COMMITTEE_INDEX,
+    },
+    capella::{
+        self,
+        light_client::{EXECUTION_PAYLOAD_INDEX, EXECUTION_PAYLOAD_INDEX_FLOOR_LOG_2},
+    },
+    crypto::fast_aggregate_verify,
+    deneb::{self, ExecutionPayloadHeader},
+    electra::{
+        compute_sync_committee_period_at_slot, is_finality_update, is_next_sync_committee_known,
+        is_sync_committee_update, BeaconBlockHeader, SyncAggregate, SyncCommittee,
+    },
+    error::LightClientError,
+    phase0::helpers::{compute_domain, compute_epoch_at_slot},
+    primitives::{Bytes32, DomainType, IsZero, Root, Slot, GENESIS_SLOT},
+    signing::compute_signing_root,
+    ssz::prelude::*,
+    state_transition::{Context, Result},
+};
+use std::iter::zip;
+
+// The `BeaconState` has more than 32 fields from Electra, deepening the proofs into it by one.
+pub const FINALIZED_ROOT_INDEX_ELECTRA: usize = 169;
+pub const FINALIZED_ROOT_INDEX_ELECTRA_FLOOR_LOG_2: usize = 7;
+
+pub const CURRENT_SYNC_COMMITTEE_INDEX_ELECTRA: usize = 86;
+pub const CURRENT_SYNC_COMMITTEE_INDEX_ELECTRA_FLOOR_LOG_2: usize = 6;
+
+pub const NEXT_SYNC_COMMITTEE_INDEX_ELECTRA: usize = 87;
+pub const NEXT_SYNC_COMMITTEE_INDEX_ELECTRA_FLOOR_LOG_2: usize = 6;
+
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct LightClientHeader<const BYTES_PER_LOGS_BLOOM: usize, const MAX_EXTRA_DATA_BYTES: usize> {
+    pub beacon: BeaconBlockHeader,
+    pub execution: ExecutionPayloadHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    pub execution_branch: Vector<Bytes32, EXECUTION_PAYLOAD_INDEX_FLOOR_LOG_2>,
+}
+
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct LightClientBootstrap<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+> {
+    pub header: LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    pub current_sync_committee: SyncCommittee<SYNC_COMMITTEE_SIZE>,
+    pub current_sync_committee_branch:
+        Vector<Bytes32, CURRENT_SYNC_COMMITTEE_INDEX_ELECTRA_FLOOR_LOG_2>,
+}
+
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct LightClientUpdate<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+> {
+    pub attested_header: LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    pub next_sync_committee: SyncCommittee<SYNC_COMMITTEE_SIZE>,
+    pub next_sync_committee_branch: Vector<Bytes32, NEXT_SYNC_COMMITTEE_INDEX_ELECTRA_FLOOR_LOG_2>,
+    pub finalized_header: LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    pub finality_branch: Vector<Bytes32, FINALIZED_ROOT_INDEX_ELECTRA_FLOOR_LOG_2>,
+    pub sync_aggregate: SyncAggregate<SYNC_COMMITTEE_SIZE>,
+    pub signature_slot: Slot,
+}
+
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct LightClientFinalityUpdate<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+> {
+    pub attested_header: LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    pub finalized_header: LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    pub finality_branch: Vector<Bytes32, FINALIZED_ROOT_INDEX_ELECTRA_FLOOR_LOG_2>,
+    pub sync_aggregate: SyncAggregate<SYNC_COMMITTEE_SIZE>,
+    pub signature_slot: Slot,
+}
+
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct LightClientOptimisticUpdate<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+> {
+    pub attested_header: LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    pub sync_aggregate: SyncAggregate<SYNC_COMMITTEE_SIZE>,
+    pub signature_slot: Slot,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct LightClientStore<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+> {
+    pub finalized_header: LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    pub current_sync_committee: SyncCommittee<SYNC_COMMITTEE_SIZE>,
+    pub next_sync_committee: SyncCommittee<SYNC_COMMITTEE_SIZE>,
+    pub best_valid_update:
+        Option<LightClientUpdate<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>>,
+    pub optimistic_header: LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    pub previous_max_active_participants: usize,
+    pub current_max_active_participants: usize,
+}
+
+pub fn finalized_root_gindex_at_slot(slot: Slot, context: &Context) -> GeneralizedIndex {
+    if compute_epoch_at_slot(slot, context) >= context.electra_fork_epoch {
+        FINALIZED_ROOT_INDEX_ELECTRA
+    } else {
+        FINALIZED_ROOT_INDEX
+    }
+}
+
+pub fn current_sync_committee_gindex_at_slot(slot: Slot, context: &Context) -> GeneralizedIndex {
+    if compute_epoch_at_slot(slot, context) >= context.electra_fork_epoch {
+        CURRENT_SYNC_COMMITTEE_INDEX_ELECTRA
+    } else {
+        CURRENT_SYNC_COMMITTEE_INDEX
+    }
+}
+
+pub fn next_sync_committee_gindex_at_slot(slot: Slot, context: &Context) -> GeneralizedIndex {
+    if compute_epoch_at_slot(slot, context) >= context.electra_fork_epoch {
+        NEXT_SYNC_COMMITTEE_INDEX_ELECTRA
+    } else {
+        NEXT_SYNC_COMMITTEE_INDEX
+    }
+}
+
+/// Pad `branch` with leading zero nodes to the depth of `gindex`, so a proof from an earlier fork
+/// fits the deeper proofs of Electra.
+pub fn normalize_merkle_branch(branch: &[Bytes32], gindex: GeneralizedIndex) -> Vec<Bytes32> {
+    let depth = gindex.ilog2() as usize;
+    let extra = depth.saturating_sub(branch.len());
+    let mut normalized = vec![Bytes32::default(); extra];
+    normalized.extend_from_slice(branch);
+    normalized
+}
+
+pub fn get_lc_execution_root<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    header: &LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    context: &Context,
+) -> Result<Root> {
+    let epoch = compute_epoch_at_slot(header.beacon.slot, context);
+    let execution = &header.execution;
+    if epoch >= context.deneb_fork_epoch {
+        return Ok(execution.hash_tree_root()?)
+    }
+    if epoch >= context.capella_fork_epoch {
+        let execution_header =
+            capella::ExecutionPayloadHeader::<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES> {
+                parent_hash: execution.parent_hash.clone(),
+                fee_recipient: execution.fee_recipient.clone(),
+                state_root: execution.state_root.clone(),
+                receipts_root: execution.receipts_root.clone(),
+                logs_bloom: execution.logs_bloom.clone(),
+                prev_randao: execution.prev_randao.clone(),
+                block_number: execution.block_number,
+                gas_limit: execution.gas_limit,
+                gas_used: execution.gas_used,
+                timestamp: execution.timestamp,
+                extra_data: execution.extra_data.clone(),
+                base_fee_per_gas: execution.base_fee_per_gas,
+                block_hash: execution.block_hash.clone(),
+                transactions_root: execution.transactions_root,
+                withdrawals_root: execution.withdrawals_root,
+            };
+        return Ok(execution_header.hash_tree_root()?)
+    }
+    Ok(Root::default())
+}
+
+pub fn is_valid_light_client_header<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    header: &LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    context: &Context,
+) -> bool {
+    let epoch = compute_epoch_at_slot(header.beacon.slot, context);
+    let execution = &header.execution;
+    if epoch < context.deneb_fork_epoch &&
+        (execution.blob_gas_used != 0 || execution.excess_blob_gas != 0)
+    {
+        return false
+    }
+    if epoch < context.capella_fork_epoch {
+        return *execution == ExecutionPayloadHeader::default() &&
+            header.execution_branch.iter().all(|node| node.is_zero())
+    }
+    let Ok(execution_root) = get_lc_execution_root(header, context) else { return false };
+    is_valid_light_client_branch(
+        execution_root,
+        &header.execution_branch,
+        EXECUTION_PAYLOAD_INDEX,
+        header.beacon.body_root,
+    )
+}
+
+pub fn initialize_light_client_store<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    trusted_block_root: Root,
+    bootstrap: &LightClientBootstrap<
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    context: &Context,
+) -> Result<LightClientStore<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>> {
+    if !is_valid_light_client_header(&bootstrap.header, context) {
+        return Err(LightClientError::InvalidHeader(bootstrap.header.beacon.slot).into())
+    }
+    let root = bootstrap.header.beacon.hash_tree_root()?;
+    if root != trusted_block_root {
+        return Err(LightClientError::UntrustedBootstrap {
+            provided: root,
+            expected: trusted_block_root,
+        }
+        .into())
+    }
+    if !is_valid_light_client_branch(
+        bootstrap.current_sync_committee.hash_tree_root()?,
+        &bootstrap.current_sync_committee_branch,
+        current_sync_committee_gindex_at_slot(bootstrap.header.beacon.slot, context),
+        bootstrap.header.beacon.state_root,
+    ) {
+        return Err(LightClientError::InvalidCurrentSyncCommitteeBranch.into())
+    }
+    Ok(LightClientStore {
+        finalized_header: bootstrap.header.clone(),
+        current_sync_committee: bootstrap.current_sync_committee.clone(),
+        next_sync_committee: SyncCommittee::default(),
+        best_valid_update: None,
+        optimistic_header: bootstrap.header.clone(),
+        previous_max_active_participants: 0,
+        current_max_active_participants: 0,
+    })
+}
+
+pub fn validate_light_client_update<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    store: &LightClientStore<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    update: &LightClientUpdate<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    current_slot: Slot,
+    genesis_validators_root: Root,
+    context: &Context,
+) -> Result<()> {
+    // Verify sync committee has sufficient participants
+    let sync_aggregate = &update.sync_aggregate;
+    let participants = sync_aggregate.sync_committee_bits.iter().filter(|bit| **bit).count();
+    if participants < context.min_sync_committee_participants {
+        return Err(LightClientError::InsufficientParticipation {
+            participants,
+            minimum: context.min_sync_committee_participants,
+        }
+        .into())
+    }
+
+    // Verify update does not skip a sync committee period
+    if !is_valid_light_client_header(&update.attested_header, context) {
+        return Err(LightClientError::InvalidHeader(update.attested_header.beacon.slot).into())
+    }
+    let update_attested_slot = update.attested_header.beacon.slot;
+    let update_finalized_slot = update.finalized_header.beacon.slot;
+    if !(current_slot >= update.signature_slot &&
+        update.signature_slot > update_attested_slot &&
+        update_attested_slot >= update_finalized_slot)
+    {
+        return Err(LightClientError::InvalidSlots {
+            current_slot,
+            signature_slot: update.signature_slot,
+            attested_slot: update_attested_slot,
+            finalized_slot: update_finalized_slot,
+        }
+        .into())
+    }
+    let store_period =
+        compute_sync_committee_period_at_slot(store.finalized_header.beacon.slot, context);
+    let update_signature_period =
+        compute_sync_committee_period_at_slot(update.signature_slot, context);
+    let is_valid_signature_period = if is_next_sync_committee_known(store) {
+        update_signature_period == store_period || update_signature_period == store_period + 1
+    } else {
+        update_signature_period == store_period
+    };
+    if !is_valid_signature_period {
+        return Err(LightClientError::UnexpectedSignaturePeriod {
+            signature_period: update_signature_period,
+            store_period,
+        }
+        .into())
+    }
+
+    // Verify update is relevant
+    let update_attested_period =
+        compute_sync_committee_period_at_slot(update_attested_slot, context);
+    let update_has_next_sync_committee = !is_next_sync_committee_known(store) &&
+        is_sync_committee_update(update) &&
+        update_attested_period == store_period;
+    if !(update_attested_slot > store.finalized_header.beacon.slot ||
+        update_has_next_sync_committee)
+    {
+        let attested_slot = update_attested_slot;
+        return Err(LightClientError::IrrelevantUpdate { attested_slot }.into())
+    }
+
+    // Verify that the `finality_branch`, if present, confirms `finalized_header`
+    // to match the finalized checkpoint root saved in the state of `attested_header`.
+    // Note that the genesis finalized checkpoint root is represented as a zero hash.
+    if !is_finality_update(update) {
+        if update.finalized_header != LightClientHeader::default() {
+            return Err(LightClientError::UnexpectedFinalizedHeader.into())
+        }
+    } else {
+        let finalized_root = if update_finalized_slot == GENESIS_SLOT {
+            if update.finalized_header != LightClientHeader::default() {
+                return Err(LightClientError::UnexpectedFinalizedHeader.into())
+            }
+            Root::default()
+        } else {
+            if !is_valid_light_client_header(&update.finalized_header, context) {
+                return Err(LightClientError::InvalidHeader(update_finalized_slot).into())
+            }
+            update.finalized_header.beacon.hash_tree_root()?
+        };
+        if !is_valid_light_client_branch(
+            finalized_root,
+            &update.finality_branch,
+            finalized_root_gindex_at_slot(update_attested_slot, context),
+            update.attested_header.beacon.state_root,
+        ) {
+            return Err(LightClientError::InvalidFinalityBranch.into())
+        }
+    }
+
+    // Verify that the `next_sync_committee`, if present, actually is the next sync committee
+    // saved in the state of the `attested_header`
+    if !is_sync_committee_update(update) {
+        if update.next_sync_committee != SyncCommittee::default() {
+            return Err(LightClientError::UnexpectedNextSyncCommittee.into())
+        }
+    } else {
+        if update_attested_period == store_period &&
+            is_next_sync_committee_known(store) &&
+            update.next_sync_committee != store.next_sync_committee
+        {
+            return Err(LightClientError::ConflictingNextSyncCommittee.into())
+        }
+        if !is_valid_light_client_branch(
+            update.next_sync_committee.hash_tree_root()?,
+            &update.next_sync_committee_branch,
+            next_sync_committee_gindex_at_slot(update_attested_slot, context),
+            update.attested_header.beacon.state_root,
+        ) {
+            return Err(LightClientError::InvalidNextSyncCommitteeBranch.into())
+        }
+    }
+
+    // Verify sync committee aggregate signature
+    let sync_committee = if update_signature_period == store_period {
+        &store.current_sync_committee
+    } else {
+        &store.next_sync_committee
+    };
+    let participant_public_keys =
+        zip(sync_aggregate.sync_committee_bits.iter(), sync_committee.public_keys.iter())
+            .filter_map(|(bit, public_key)| if *bit { Some(public_key) } else { None })
+            .collect::<Vec<_>>();
+    let fork_version_slot = update.signature_slot.max(1) - 1;
+    let fork_version = context.fork_version_for(context.fork_for(fork_version_slot));
+    let domain = compute_domain(
+        DomainType::SyncCommittee,
+        Some(fork_version),
+        Some(genesis_validators_root),
+        context,
+    )?;
+    let signing_root = compute_signing_root(&update.attested_header.beacon, domain)?;
+    fast_aggregate_verify(
+        &participant_public_keys,
+        signing_root.as_ref(),
+        &sync_aggregate.sync_committee_signature,
+    )
+    .map_err(|_| LightClientError::InvalidSignature { root: signing_root }.into())
+}
+
+pub fn upgrade_lc_header_to_electra<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    pre: &deneb::LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+) -> LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES> {
+    LightClientHeader {
+        beacon: pre.beacon.clone(),
+        execution: pre.execution.clone(),
+        execution_branch: pre.execution_branch.clone(),
+    }
+}
+
+fn normalize<const N: usize>(branch: &[Bytes32], gindex: GeneralizedIndex) -> Vector<Bytes32, N> {
+    Vector::try_from(normalize_merkle_branch(branch, gindex)).expect("correct length")
+}
+
+pub fn upgrade_lc_bootstrap_to_electra<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    pre: &deneb::LightClientBootstrap<
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+) -> LightClientBootstrap<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES> {
+    LightClientBootstrap {
+        header: upgrade_lc_header_to_electra(&pre.header),
+        current_sync_committee: pre.current_sync_committee.clone(),
+        current_sync_committee_branch: normalize(
+            &pre.current_sync_committee_branch,
+            CURRENT_SYNC_COMMITTEE_INDEX_ELECTRA,
+        ),
+    }
+}
+
+pub fn upgrade_lc_update_to_electra<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    pre: &deneb::LightClientUpdate<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+) -> LightClientUpdate<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES> {
+    LightClientUpdate {
+        attested_header: upgrade_lc_header_to_electra(&pre.attested_header),
+        next_sync_committee: pre.next_sync_committee.clone(),
+        next_sync_committee_branch: normalize(
+            &pre.next_sync_committee_branch,
+            NEXT_SYNC_COMMITTEE_INDEX_ELECTRA,
+        ),
+        finalized_header: upgrade_lc_header_to_electra(&pre.finalized_header),
+        finality_branch: normalize(&pre.finality_branch, FINALIZED_ROOT_INDEX_ELECTRA),
+        sync_aggregate: pre.sync_aggregate.clone(),
+        signature_slot: pre.signature_slot,
+    }
+}
+
+pub fn upgrade_lc_finality_update_to_electra<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    pre: &deneb::LightClientFinalityUpdate<
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+) -> LightClientFinalityUpdate<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES> {
+    LightClientFinalityUpdate {
+        attested_header: upgrade_lc_header_to_electra(&pre.attested_header),
+        finalized_header: upgrade_lc_header_to_electra(&pre.finalized_header),
+        finality_branch: normalize(&pre.finality_branch, FINALIZED_ROOT_INDEX_ELECTRA),
+        sync_aggregate: pre.sync_aggregate.clone(),
+        signature_slot: pre.signature_slot,
+    }
+}
+
+pub fn upgrade_lc_optimistic_update_to_electra<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    pre: &deneb::LightClientOptimisticUpdate<
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+) -> LightClientOptimisticUpdate<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES> {
+    LightClientOptimisticUpdate {
+        attested_header: upgrade_lc_header_to_electra(&pre.attested_header),
+        sync_aggregate: pre.sync_aggregate.clone(),
+        signature_slot: pre.signature_slot,
+    }
+}
+
+pub fn upgrade_lc_store_to_electra<
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    pre: &deneb::LightClientStore<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+) -> LightClientStore<SYNC_COMMITTEE_SIZE, BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES> {
+    LightClientStore {
+        finalized_header: upgrade_lc_header_to_electra(&pre.finalized_header),
+        current_sync_committee: pre.current_sync_committee.clone(),
+        next_sync_committee: pre.next_sync_committee.clone(),
+        best_valid_update: pre.best_valid_update.as_ref().map(upgrade_lc_update_to_electra),
+        optimistic_header: upgrade_lc_header_to_electra(&pre.optimistic_header),
+        previous_max_active_participants: pre.previous_max_active_participants,
+        current_max_active_participants: pre.current_max_active_participants,
+    }
+}
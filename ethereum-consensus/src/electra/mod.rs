@@ -1,15 +1,22 @@
 pub mod beacon_block;
 pub mod beacon_state;
+pub mod blinded_beacon_block;
 pub mod block_processing;
 pub mod constants;
-pub mod execution_payload;
+pub mod epoch_processing;
+pub mod execution_engine;
+pub mod execution_requests;
 pub mod fork;
 pub mod genesis;
 pub mod helpers;
+pub mod light_client;
 pub mod operations;
 pub mod presets;
 pub mod spec;
 
 pub use spec::*;
 
-pub use presets::{mainnet, minimal, Preset};
+pub use presets::{gnosis, mainnet, minimal, Preset};
+
+/// The fork implemented by this module.
+pub const FORK: crate::Fork = crate::Fork::Electra;
@@ -1,6 +1,6 @@
 use crate::{
     phase0::AttestationData,
-    primitives::{BlsSignature, Epoch, ValidatorIndex},
+    primitives::{BlsSignature, CommitteeIndex, ValidatorIndex},
     ssz::prelude::*,
 };
 
@@ -35,19 +35,175 @@ pub struct Attestation<const MAX_VALIDATORS_PER_SLOT: usize, const MAX_COMMITTEE
 #[derive(
     Default, Debug, SimpleSerialize, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize,
 )]
-pub struct Consolidation {
+pub struct SingleAttestation {
     #[serde(with = "crate::serde::as_str")]
-    pub source_index: ValidatorIndex,
+    pub committee_index: CommitteeIndex,
     #[serde(with = "crate::serde::as_str")]
-    pub target_index: ValidatorIndex,
-    #[serde(with = "crate::serde::as_str")]
-    pub epoch: Epoch,
+    pub attester_index: ValidatorIndex,
+    pub data: AttestationData,
+    pub signature: BlsSignature,
 }
 
-#[derive(
-    Default, Debug, SimpleSerialize, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize,
-)]
-pub struct SignedConsolidation {
-    pub message: Consolidation,
-    pub signature: BlsSignature,
+crate::ssz_size::impl_ssz_size!(AttesterSlashing<const MAX_VALIDATORS_PER_SLOT> {
+    attestation_1: IndexedAttestation<MAX_VALIDATORS_PER_SLOT>,
+    attestation_2: IndexedAttestation<MAX_VALIDATORS_PER_SLOT>,
+});
+
+crate::ssz_size::impl_ssz_size!(IndexedAttestation<const MAX_VALIDATORS_PER_SLOT> {
+    attesting_indices: List<ValidatorIndex, MAX_VALIDATORS_PER_SLOT>,
+    data: AttestationData,
+    signature: BlsSignature,
+});
+
+crate::ssz_size::impl_ssz_size!(Attestation<
+    const MAX_VALIDATORS_PER_SLOT,
+    const MAX_COMMITTEES_PER_SLOT,
+> {
+    aggregation_bits: Bitlist<MAX_VALIDATORS_PER_SLOT>,
+    data: AttestationData,
+    committee_bits: Bitvector<MAX_COMMITTEES_PER_SLOT>,
+    signature: BlsSignature,
+});
+
+crate::ssz_size::impl_ssz_size!(SingleAttestation {
+    committee_index: CommitteeIndex,
+    attester_index: ValidatorIndex,
+    data: AttestationData,
+    signature: BlsSignature,
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::electra::minimal::{
+        self, BeaconBlockBody, MAX_ATTESTATIONS_ELECTRA, MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_COMMITTEES_PER_SLOT, MAX_VALIDATORS_PER_COMMITTEE, MAX_VALIDATORS_PER_SLOT,
+    };
+
+    #[test]
+    fn test_bounds_follow_the_preset() {
+        assert_eq!(MAX_VALIDATORS_PER_SLOT, MAX_VALIDATORS_PER_COMMITTEE * MAX_COMMITTEES_PER_SLOT);
+        let attestation = minimal::Attestation::default();
+        assert_eq!(attestation.committee_bits.len(), MAX_COMMITTEES_PER_SLOT);
+    }
+
+    #[test]
+    fn test_indexed_attestation_indices_bound() {
+        let mut attestation = minimal::IndexedAttestation::default();
+        attestation.attesting_indices =
+            List::try_from(vec![0; MAX_VALIDATORS_PER_SLOT]).expect("at limit");
+        let encoding = serialize(&attestation).unwrap();
+        let recovered: minimal::IndexedAttestation = deserialize(&encoding).unwrap();
+        assert_eq!(recovered, attestation);
+
+        // the indices are the trailing variable-size data, so append one more index
+        let mut encoding = encoding;
+        encoding.extend_from_slice(&0u64.to_le_bytes());
+        assert!(deserialize::<minimal::IndexedAttestation>(&encoding).is_err());
+
+        let mut json = serde_json::to_value(&attestation).unwrap();
+        let recovered: minimal::IndexedAttestation = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(recovered, attestation);
+        json["attesting_indices"].as_array_mut().unwrap().push("0".into());
+        let err = serde_json::from_value::<minimal::IndexedAttestation>(json).unwrap_err();
+        assert!(err.to_string().contains(&MAX_VALIDATORS_PER_SLOT.to_string()), "{err}");
+    }
+
+    #[test]
+    fn test_attester_slashing_indices_bound() {
+        let indices = List::try_from(vec![0; MAX_VALIDATORS_PER_SLOT]).expect("at limit");
+        let mut slashing = minimal::AttesterSlashing::default();
+        slashing.attestation_1.attesting_indices = indices.clone();
+        slashing.attestation_2.attesting_indices = indices;
+        let encoding = serialize(&slashing).unwrap();
+        let recovered: minimal::AttesterSlashing = deserialize(&encoding).unwrap();
+        assert_eq!(recovered, slashing);
+
+        // the indices of the second attestation are the trailing variable-size data
+        let mut encoding = encoding;
+        encoding.extend_from_slice(&0u64.to_le_bytes());
+        assert!(deserialize::<minimal::AttesterSlashing>(&encoding).is_err());
+    }
+
+    #[test]
+    fn test_attestation_bits_bounds() {
+        // every bit set, followed by the delimiting bit
+        let mut bytes = vec![0xff; MAX_VALIDATORS_PER_SLOT / 8];
+        bytes.push(0b01);
+        let aggregation_bits = deserialize(&bytes).expect("at limit");
+        let attestation = minimal::Attestation { aggregation_bits, ..Default::default() };
+        assert_eq!(attestation.aggregation_bits.len(), MAX_VALIDATORS_PER_SLOT);
+        let encoding = serialize(&attestation).unwrap();
+        let recovered: minimal::Attestation = deserialize(&encoding).unwrap();
+        assert_eq!(recovered, attestation);
+        let json = serde_json::to_value(&attestation).unwrap();
+        let recovered: minimal::Attestation = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(recovered, attestation);
+
+        // one more bit moves the delimiting bit up by one
+        let mut encoding = encoding;
+        *encoding.last_mut().unwrap() = 0b11;
+        assert!(deserialize::<minimal::Attestation>(&encoding).is_err());
+        *bytes.last_mut().unwrap() = 0b11;
+        let mut too_long = json.clone();
+        too_long["aggregation_bits"] = format!("0x{}", hex::encode(bytes)).into();
+        assert!(serde_json::from_value::<minimal::Attestation>(too_long).is_err());
+
+        // `committee_bits` is a single byte in the minimal preset, with any bits past
+        // `MAX_COMMITTEES_PER_SLOT` unset
+        let committee_bits_offset = 4 + 128;
+        let mut encoding = serialize(&minimal::Attestation::default()).unwrap();
+        encoding[committee_bits_offset] = (1 << MAX_COMMITTEES_PER_SLOT) - 1;
+        let recovered: minimal::Attestation = deserialize(&encoding).unwrap();
+        assert!(recovered.committee_bits.all());
+        encoding[committee_bits_offset] = 1 << MAX_COMMITTEES_PER_SLOT;
+        assert!(deserialize::<minimal::Attestation>(&encoding).is_err());
+        let mut too_long = json;
+        too_long["committee_bits"] = "0x0000".into();
+        assert!(serde_json::from_value::<minimal::Attestation>(too_long).is_err());
+    }
+
+    #[test]
+    fn test_block_body_operation_bounds() {
+        let mut body = BeaconBlockBody::default();
+        for _ in 0..MAX_ATTESTER_SLASHINGS_ELECTRA {
+            body.attester_slashings.push(Default::default());
+        }
+        for _ in 0..MAX_ATTESTATIONS_ELECTRA {
+            body.attestations.push(Default::default());
+        }
+        let encoding = serialize(&body).unwrap();
+        let recovered: BeaconBlockBody = deserialize(&encoding).unwrap();
+        assert_eq!(recovered, body);
+        let json = serde_json::to_value(&body).unwrap();
+        let recovered: BeaconBlockBody = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(recovered, body);
+
+        // the lists of the body decode as lists of their own bound, which reject one more element
+        let too_many =
+            List::<minimal::AttesterSlashing, { MAX_ATTESTER_SLASHINGS_ELECTRA + 1 }>::try_from(
+                vec![Default::default(); MAX_ATTESTER_SLASHINGS_ELECTRA + 1],
+            )
+            .unwrap();
+        let encoding = serialize(&too_many).unwrap();
+        assert!(deserialize::<List<minimal::AttesterSlashing, MAX_ATTESTER_SLASHINGS_ELECTRA>>(
+            &encoding
+        )
+        .is_err());
+        let too_many = List::<minimal::Attestation, { MAX_ATTESTATIONS_ELECTRA + 1 }>::try_from(
+            vec![Default::default(); MAX_ATTESTATIONS_ELECTRA + 1],
+        )
+        .unwrap();
+        let encoding = serialize(&too_many).unwrap();
+        assert!(
+            deserialize::<List<minimal::Attestation, MAX_ATTESTATIONS_ELECTRA>>(&encoding).is_err()
+        );
+
+        for field in ["attester_slashings", "attestations"] {
+            let mut too_long = json.clone();
+            let list = too_long[field].as_array_mut().unwrap();
+            list.push(list[0].clone());
+            assert!(serde_json::from_value::<BeaconBlockBody>(too_long).is_err(), "{field}");
+        }
+    }
 }
@@ -2,9 +2,28 @@ use crate::{
     capella::Withdrawal,
     crypto::Error as CryptoError,
     deneb::polynomial_commitments::Error as PolynomialCommitmentError,
+    determinism::DeterminismError,
+    duties::DutiesError,
+    electra::execution_requests::ExecutionRequestsError,
+    eth1::{DepositCacheError, DepositLogError},
+    fork_choice::ForkChoiceError,
+    genesis::GenesisTimeError,
+    historical::CommitteeProviderError,
+    light_client::TrackerError,
+    peerdas::PeerDasError,
     phase0::{AttestationData, BeaconBlockHeader, Checkpoint},
-    primitives::{BlsPublicKey, BlsSignature, Bytes32, Epoch, Hash32, Root, Slot, ValidatorIndex},
+    primitives::{
+        BlsPublicKey, BlsSignature, Bytes32, Epoch, Gwei, Hash32, HexError, Root, Slot,
+        ValidatorIndex,
+    },
+    proof_of_ownership::OwnershipProofError,
+    proofs::ProofError,
+    replay::BoundaryIndexError,
+    slasher::SlasherError,
+    sparse::StateField,
     ssz::prelude::*,
+    state_transition::{BatchVerificationError, PagingError, ShufflingCacheError},
+    storage::StorageError,
     Fork,
 };
 use thiserror::Error;
@@ -17,6 +36,8 @@ pub enum Error {
     SimpleSerialize(#[from] SimpleSerializeError),
     #[error("{0}")]
     Crypto(#[from] CryptoError),
+    #[error("{0}")]
+    Hex(#[from] HexError),
     #[cfg(feature = "serde")]
     #[error("{0}")]
     Io(#[from] std::io::Error),
@@ -30,12 +51,16 @@ pub enum Error {
     OutOfBounds { requested: usize, bound: usize },
     #[error("collection cannot be empty")]
     CollectionCannotBeEmpty,
+    #[error("there are no active validators at epoch {0}")]
+    NoActiveValidators(Epoch),
     #[error("given index {index} is greater than the total amount of indices {total}")]
     InvalidShufflingIndex { index: usize, total: usize },
     #[error("slot {requested} is outside of allowed range ({lower_bound}, {upper_bound})")]
     SlotOutOfRange { requested: Slot, lower_bound: Slot, upper_bound: Slot },
     #[error("overflow")]
     Overflow,
+    #[error("{0} can not be used as an index on this target")]
+    IndexOverflow(u64),
     #[error("underflow")]
     Underflow,
     #[error("{0}")]
@@ -44,6 +69,12 @@ pub enum Error {
     TransitionToPreviousSlot { current: Slot, requested: Slot },
     #[error("invalid state root")]
     InvalidStateRoot,
+    #[error(
+        "advanced state was computed from state root {expected} but the state has root {found}"
+    )]
+    StaleAdvancedState { expected: Root, found: Root },
+    #[error("block {root} in slot {slot} could not be replayed: {source}")]
+    ReplayFailed { slot: Slot, root: Root, source: Box<Error> },
     #[error(
     "the requested epoch {requested} is not in the required current epoch {current} or previous epoch {previous}"
     )]
@@ -60,7 +91,58 @@ pub enum Error {
     #[error(transparent)]
     ExecutionEngine(#[from] ExecutionEngineError),
     #[error(transparent)]
+    LightClient(#[from] LightClientError),
+    #[error(transparent)]
     PolynomialCommitment(#[from] PolynomialCommitmentError),
+    #[error(transparent)]
+    ShufflingCache(#[from] ShufflingCacheError),
+    #[error(transparent)]
+    DepositLog(#[from] DepositLogError),
+    #[error(transparent)]
+    DepositCache(#[from] DepositCacheError),
+    #[error(transparent)]
+    ExecutionRequests(#[from] ExecutionRequestsError),
+    #[error(transparent)]
+    Proof(#[from] ProofError),
+    #[error(transparent)]
+    OwnershipProof(#[from] OwnershipProofError),
+    #[error(transparent)]
+    BoundaryIndex(#[from] BoundaryIndexError),
+    #[error(transparent)]
+    LightClientTracker(#[from] TrackerError),
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+    #[error(transparent)]
+    Determinism(#[from] DeterminismError),
+    #[error(transparent)]
+    CommitteeProvider(#[from] CommitteeProviderError),
+    #[error(transparent)]
+    BatchVerification(#[from] BatchVerificationError),
+    #[error(transparent)]
+    Duties(#[from] DutiesError),
+    #[error(transparent)]
+    ForkChoice(#[from] ForkChoiceError),
+    #[error(transparent)]
+    GenesisTime(#[from] GenesisTimeError),
+    #[error(transparent)]
+    Paging(#[from] PagingError),
+    #[error(transparent)]
+    PeerDas(#[from] PeerDasError),
+    #[error(transparent)]
+    Slasher(#[from] SlasherError),
+    #[error("field {field} is not present in the state for fork {fork}")]
+    MissingStateField { field: StateField, fork: Fork },
+    #[error("sync committee member {0:?} is not in the validator registry")]
+    UnknownSyncCommitteeMember(BlsPublicKey),
+    #[error(transparent)]
+    WrongFork(#[from] WrongFork),
+}
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+#[error("expected an object from fork {expected} but found one from fork {found}")]
+pub struct WrongFork {
+    pub expected: Fork,
+    pub found: Fork,
 }
 
 #[derive(Debug, Error)]
@@ -127,6 +209,10 @@ pub enum InvalidAttestation {
     InvalidSource { expected: Checkpoint, source_checkpoint: Checkpoint, current: Epoch },
     #[error("attestation in slot {attestation_slot} does not have the minimum delay {required_delay} against state {state_slot}")]
     NoDelay { attestation_slot: Slot, state_slot: Slot, required_delay: Slot },
+    #[error("attestation's index {0} is not zero, but committees are given by its committee bits")]
+    NonZeroIndex(usize),
+    #[error("attestation has no attester set in committee {0}")]
+    EmptyCommittee(usize),
 }
 
 #[derive(Debug, Error)]
@@ -187,6 +273,8 @@ pub enum InvalidVoluntaryExit {
     EarlyExit { current_epoch: Epoch, exit_epoch: Epoch },
     #[error("validator needs to be active for a minimum period of time (from epoch {minimum_time_active}, currently in {current_epoch})")]
     ValidatorIsNotActiveForLongEnough { current_epoch: Epoch, minimum_time_active: Epoch },
+    #[error("validator {index} has {amount} Gwei of partial withdrawals pending")]
+    PendingWithdrawals { index: ValidatorIndex, amount: Gwei },
     #[error("voluntary exit has invalid signature: {0:?}")]
     InvalidSignature(BlsSignature),
 }
@@ -203,6 +291,8 @@ pub enum InvalidBlsToExecutionChange {
     ValidatorIndexOutOfBounds(usize),
     #[error("invalid withdrawal credentials prefix: {0}")]
     WithdrawalCredentialsPrefix(u8),
+    #[error("validator {0} already has execution withdrawal credentials")]
+    AlreadyRotated(ValidatorIndex),
     #[error("operation's public key did not match the registered key: {0:?}")]
     PublicKeyMismatch(BlsPublicKey),
 }
@@ -223,6 +313,10 @@ pub enum InvalidExecutionPayload {
     InvalidTimestamp { provided: u64, expected: u64 },
     #[error("expected up to {limit} blob commmitments but block has {provided}")]
     InvalidBlobCommitments { provided: usize, limit: usize },
+    #[error("block has {commitments} blob commitments but payload has {versioned_hashes} versioned hashes")]
+    BlobCountMismatch { commitments: usize, versioned_hashes: usize },
+    #[error("expected versioned hash {expected} at index {index} but payload has {provided}")]
+    VersionedHashMismatch { index: usize, provided: Bytes32, expected: Bytes32 },
 }
 
 pub(crate) fn invalid_header_error(error: InvalidBeaconBlockHeader) -> Error {
@@ -242,3 +336,42 @@ pub enum ExecutionEngineError {
     #[error("invalid versioned hashes in payload")]
     InvalidVersionedHashes,
 }
+
+#[derive(Debug, Error)]
+pub enum LightClientError {
+    #[error("light client header at slot {0} does not commit to its execution payload header")]
+    InvalidHeader(Slot),
+    #[error("bootstrap header has root {provided} but the trusted block root is {expected}")]
+    UntrustedBootstrap { provided: Root, expected: Root },
+    #[error("invalid proof of the current sync committee in the bootstrap state")]
+    InvalidCurrentSyncCommitteeBranch,
+    #[error(
+        "update has {participants} sync committee participants but at least {minimum} are required"
+    )]
+    InsufficientParticipation { participants: usize, minimum: usize },
+    #[error("update slots are out of order: current slot {current_slot}, signature slot {signature_slot}, attested slot {attested_slot}, finalized slot {finalized_slot}")]
+    InvalidSlots {
+        current_slot: Slot,
+        signature_slot: Slot,
+        attested_slot: Slot,
+        finalized_slot: Slot,
+    },
+    #[error("update signed in sync committee period {signature_period} can not be verified in store period {store_period}")]
+    UnexpectedSignaturePeriod { signature_period: u64, store_period: u64 },
+    #[error("update attested at slot {attested_slot} is not relevant to the store")]
+    IrrelevantUpdate { attested_slot: Slot },
+    #[error("update without a finality branch has a non-empty finalized header")]
+    UnexpectedFinalizedHeader,
+    #[error("invalid proof of the finalized header in the attested state")]
+    InvalidFinalityBranch,
+    #[error("update without a sync committee branch has a non-empty next sync committee")]
+    UnexpectedNextSyncCommittee,
+    #[error("update has a next sync committee that conflicts with the one in the store")]
+    ConflictingNextSyncCommittee,
+    #[error("invalid proof of the next sync committee in the attested state")]
+    InvalidNextSyncCommitteeBranch,
+    #[error("invalid sync committee signature over attested header with root {root}")]
+    InvalidSignature { root: Root },
+    #[error("update finalized in sync committee period {finalized_period} can not be applied to store period {store_period}")]
+    UnexpectedFinalizedPeriod { finalized_period: u64, store_period: u64 },
+}
@@ -0,0 +1,590 @@
+//! Decoding of the deposit contract's `DepositEvent` logs emitted on the execution layer, and
+//! the [`DepositCache`] tracking the deposit tree as those logs arrive.
+//!
+//! The event is declared as
+//! `DepositEvent(bytes pubkey, bytes withdrawal_credentials, bytes amount, bytes signature, bytes
+//! index)` with no indexed fields, so the log data is the ABI encoding of five dynamic `bytes`
+//! values. `amount` and `index` are little-endian `uint64`s.
+use crate::{
+    crypto::{BLS_PUBLIC_KEY_BYTES_LEN, BLS_SIGNATURE_BYTES_LEN},
+    phase0::{constants::DEPOSIT_CONTRACT_TREE_DEPTH, Deposit, DepositData},
+    primitives::{BlsPublicKey, BlsSignature, Bytes32, Gwei, Hash32, Root},
+    ssz::prelude::*,
+};
+use sha2::{Digest, Sha256};
+use std::sync::OnceLock;
+use thiserror::Error;
+
+const WORD_LEN: usize = 32;
+const FIELD_COUNT: usize = 5;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DepositLogError {
+    #[error("deposit log of {length} bytes is too short to contain {required} bytes")]
+    Truncated { length: usize, required: usize },
+    #[error("deposit log word at {at} does not fit in a usize")]
+    WordOverflow { at: usize },
+    #[error("deposit log field `{field}` has offset {offset} outside of the log")]
+    InvalidOffset { field: &'static str, offset: usize },
+    #[error("deposit log field `{field}` has length {length} but expected {expected}")]
+    InvalidFieldLength { field: &'static str, length: usize, expected: usize },
+    #[error("deposit amount does not fit in Gwei")]
+    AmountOverflow,
+    #[error("deposit data root {computed} does not match expected root {expected}")]
+    DepositDataRootMismatch { expected: Root, computed: Root },
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DepositCacheError {
+    #[error("deposit has index {index} but the next deposit has index {expected}")]
+    NonConsecutiveIndex { index: u64, expected: u64 },
+    #[error("deposit is from block {block_number} but deposits up to block {latest} are known")]
+    UnorderedBlock { block_number: u64, latest: u64 },
+    #[error("deposits from block {block_number} were already seen in a block with another hash")]
+    ConflictingBlock { block_number: u64 },
+    #[error("block {0} did not include any deposit known to the cache")]
+    UnknownBlock(Hash32),
+    #[error("requested {count} deposits but only {len} are known")]
+    CountOutOfRange { count: usize, len: usize },
+    #[error("deposit {index} is not among the first {count} deposits")]
+    IndexOutOfRange { index: usize, count: usize },
+}
+
+// Reads a 32-byte big-endian ABI word that must fit in a `usize`.
+fn read_word(data: &[u8], at: usize) -> Result<usize, DepositLogError> {
+    let word = data
+        .get(at..at + WORD_LEN)
+        .ok_or(DepositLogError::Truncated { length: data.len(), required: at + WORD_LEN })?;
+    let (high, low) = word.split_at(WORD_LEN - 8);
+    if high.iter().any(|byte| *byte != 0) {
+        return Err(DepositLogError::WordOverflow { at })
+    }
+    let value = u64::from_be_bytes(low.try_into().expect("correct length"));
+    usize::try_from(value).map_err(|_| DepositLogError::WordOverflow { at })
+}
+
+// Reads the `i`th dynamic `bytes` value of the log.
+fn read_bytes<'a>(
+    data: &'a [u8],
+    i: usize,
+    field: &'static str,
+) -> Result<&'a [u8], DepositLogError> {
+    let offset = read_word(data, i * WORD_LEN)?;
+    if offset >= data.len() {
+        return Err(DepositLogError::InvalidOffset { field, offset })
+    }
+    let length = read_word(data, offset)?;
+    let start = offset + WORD_LEN;
+    let end = start.checked_add(length).ok_or(DepositLogError::WordOverflow { at: offset })?;
+    data.get(start..end).ok_or(DepositLogError::Truncated { length: data.len(), required: end })
+}
+
+fn read_exact<'a>(
+    data: &'a [u8],
+    i: usize,
+    field: &'static str,
+    expected: usize,
+) -> Result<&'a [u8], DepositLogError> {
+    let bytes = read_bytes(data, i, field)?;
+    if bytes.len() != expected {
+        return Err(DepositLogError::InvalidFieldLength { field, length: bytes.len(), expected })
+    }
+    Ok(bytes)
+}
+
+// Decodes a little-endian integer, allowing zero-valued high bytes beyond the width of `u64`.
+fn read_le_u64(bytes: &[u8], field: &'static str) -> Result<u64, DepositLogError> {
+    if bytes.is_empty() {
+        return Err(DepositLogError::InvalidFieldLength { field, length: 0, expected: 8 })
+    }
+    let (low, high) = bytes.split_at(bytes.len().min(8));
+    if high.iter().any(|byte| *byte != 0) {
+        return Err(DepositLogError::AmountOverflow)
+    }
+    let mut buffer = [0u8; 8];
+    buffer[..low.len()].copy_from_slice(low);
+    Ok(u64::from_le_bytes(buffer))
+}
+
+/// Decode the data of a `DepositEvent` log into the corresponding `DepositData` and the index
+/// of the deposit in the deposit contract's tree.
+pub fn parse_deposit_log(log_data: &[u8]) -> Result<(DepositData, u64), crate::Error> {
+    if log_data.len() < FIELD_COUNT * WORD_LEN {
+        return Err(DepositLogError::Truncated {
+            length: log_data.len(),
+            required: FIELD_COUNT * WORD_LEN,
+        }
+        .into())
+    }
+    let public_key = read_exact(log_data, 0, "pubkey", BLS_PUBLIC_KEY_BYTES_LEN)?;
+    let withdrawal_credentials = read_exact(log_data, 1, "withdrawal_credentials", 32)?;
+    let amount: Gwei = read_le_u64(read_bytes(log_data, 2, "amount")?, "amount")?;
+    let signature = read_exact(log_data, 3, "signature", BLS_SIGNATURE_BYTES_LEN)?;
+    let index = read_exact(log_data, 4, "index", 8)?;
+    let index = u64::from_le_bytes(index.try_into().expect("correct length"));
+
+    let deposit_data = DepositData {
+        public_key: BlsPublicKey::try_from(public_key)?,
+        withdrawal_credentials: Bytes32::try_from(withdrawal_credentials).expect("correct length"),
+        amount,
+        signature: BlsSignature::try_from(signature)?,
+    };
+    Ok((deposit_data, index))
+}
+
+/// Check that `deposit_data` recovered from a `DepositEvent` log has the `deposit_data_root`
+/// committed to by the deposit contract.
+pub fn verify_deposit_event_consistency(
+    deposit_data: &DepositData,
+    deposit_data_root: Root,
+) -> Result<(), crate::Error> {
+    let computed = deposit_data.hash_tree_root()?;
+    if computed != deposit_data_root {
+        return Err(DepositLogError::DepositDataRootMismatch {
+            expected: deposit_data_root,
+            computed,
+        }
+        .into())
+    }
+    Ok(())
+}
+
+type Chunk = [u8; 32];
+
+fn hash_pair(left: &Chunk, right: &Chunk) -> Chunk {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+// The root of a subtree of `depth` levels holding no deposits.
+fn zero_hash(depth: usize) -> Chunk {
+    static ZERO_HASHES: OnceLock<Vec<Chunk>> = OnceLock::new();
+    ZERO_HASHES.get_or_init(|| {
+        let mut zero_hashes = vec![Chunk::default()];
+        for depth in 0..DEPOSIT_CONTRACT_TREE_DEPTH {
+            let zero_hash = zero_hashes[depth];
+            zero_hashes.push(hash_pair(&zero_hash, &zero_hash));
+        }
+        zero_hashes
+    })[depth]
+}
+
+fn length_chunk(count: usize) -> Chunk {
+    let mut chunk = Chunk::default();
+    chunk[..8].copy_from_slice(&(count as u64).to_le_bytes());
+    chunk
+}
+
+fn to_root(chunk: Chunk) -> Root {
+    Root::try_from(chunk.as_ref()).expect("correct length")
+}
+
+/// An execution block to revert the [`DepositCache`] to, by number or by hash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockId {
+    Number(u64),
+    Hash(Hash32),
+}
+
+// An execution block that included deposits, with the number of deposits made up to and
+// including the block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DepositBlock {
+    number: u64,
+    hash: Hash32,
+    deposit_count: usize,
+}
+
+/// The deposits made to the deposit contract so far, each tagged with the execution block that
+/// included it, from which the root of the deposit tree (and proofs against it) can be computed
+/// at any deposit count.
+///
+/// Deposits are inserted in order as their logs are observed. If the execution layer reorgs, the
+/// cache is reverted to the last block both chains agree on with [`DepositCache::revert_to_block`]
+/// before the deposits of the new canonical chain are inserted.
+#[derive(Debug, Clone, Default)]
+pub struct DepositCache {
+    deposits: Vec<DepositData>,
+    leaves: Vec<Chunk>,
+    blocks: Vec<DepositBlock>,
+}
+
+impl DepositCache {
+    pub fn len(&self) -> usize {
+        self.deposits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.deposits.is_empty()
+    }
+
+    pub fn deposits(&self) -> &[DepositData] {
+        &self.deposits
+    }
+
+    /// Append the deposit with `index` included in the execution block with `block_number` and
+    /// `block_hash`.
+    pub fn insert(
+        &mut self,
+        deposit_data: DepositData,
+        index: u64,
+        block_number: u64,
+        block_hash: Hash32,
+    ) -> Result<(), crate::Error> {
+        let expected = self.len() as u64;
+        if index != expected {
+            return Err(DepositCacheError::NonConsecutiveIndex { index, expected }.into())
+        }
+        let leaf = deposit_data.hash_tree_root()?;
+        match self.blocks.last_mut() {
+            Some(block) if block.number > block_number => {
+                return Err(
+                    DepositCacheError::UnorderedBlock { block_number, latest: block.number }.into()
+                )
+            }
+            Some(block) if block.number == block_number => {
+                if block.hash != block_hash {
+                    return Err(DepositCacheError::ConflictingBlock { block_number }.into())
+                }
+                block.deposit_count += 1;
+            }
+            _ => self.blocks.push(DepositBlock {
+                number: block_number,
+                hash: block_hash,
+                deposit_count: self.deposits.len() + 1,
+            }),
+        }
+        self.deposits.push(deposit_data);
+        self.leaves.push(leaf.as_ref().try_into().expect("correct length"));
+        Ok(())
+    }
+
+    /// The number of deposits made up to and including the block with `block_number`.
+    pub fn deposit_count_at_block(&self, block_number: u64) -> usize {
+        self.blocks
+            .iter()
+            .take_while(|block| block.number <= block_number)
+            .last()
+            .map_or(0, |block| block.deposit_count)
+    }
+
+    /// Drop every deposit made after `block`, e.g. once the execution layer has reorged past it,
+    /// returning the number of deposits dropped.
+    ///
+    /// A block given by hash must have included a deposit known to the cache, as the cache does
+    /// not know of other blocks.
+    pub fn revert_to_block(&mut self, block: BlockId) -> Result<usize, crate::Error> {
+        let retained_blocks = match block {
+            BlockId::Number(number) => {
+                self.blocks.iter().take_while(|block| block.number <= number).count()
+            }
+            BlockId::Hash(hash) => {
+                let position = self
+                    .blocks
+                    .iter()
+                    .position(|block| block.hash == hash)
+                    .ok_or(DepositCacheError::UnknownBlock(hash))?;
+                position + 1
+            }
+        };
+        self.blocks.truncate(retained_blocks);
+        let count = self.blocks.last().map_or(0, |block| block.deposit_count);
+        let dropped = self.len() - count;
+        self.deposits.truncate(count);
+        self.leaves.truncate(count);
+        Ok(dropped)
+    }
+
+    // The layers of the tree over the first `count` leaves, from the leaves up to (but excluding)
+    // the root, without the trailing zero subtrees.
+    fn layers(&self, count: usize) -> Result<Vec<Vec<Chunk>>, crate::Error> {
+        if count > self.len() {
+            return Err(DepositCacheError::CountOutOfRange { count, len: self.len() }.into())
+        }
+        let mut layers = vec![self.leaves[..count].to_vec()];
+        for depth in 0..DEPOSIT_CONTRACT_TREE_DEPTH {
+            let layer = &layers[depth];
+            let parents = layer
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_pair(left, right),
+                    [left] => hash_pair(left, &zero_hash(depth)),
+                    _ => unreachable!("chunks are non-empty"),
+                })
+                .collect();
+            layers.push(parents);
+        }
+        Ok(layers)
+    }
+
+    /// The root of the deposit tree after the first `count` deposits, i.e. the `deposit_root` of
+    /// an `Eth1Data` with `deposit_count` of `count`.
+    pub fn root_at_count(&self, count: usize) -> Result<Root, crate::Error> {
+        let layers = self.layers(count)?;
+        let root = layers[DEPOSIT_CONTRACT_TREE_DEPTH]
+            .first()
+            .copied()
+            .unwrap_or_else(|| zero_hash(DEPOSIT_CONTRACT_TREE_DEPTH));
+        Ok(to_root(hash_pair(&root, &length_chunk(count))))
+    }
+
+    /// The root of the deposit tree over every known deposit.
+    pub fn root(&self) -> Root {
+        self.root_at_count(self.len()).expect("count is in range")
+    }
+
+    /// The deposit with `index` along with its proof against the root of the tree after the first
+    /// `count` deposits, as included in a block.
+    pub fn deposit_at_count(&self, index: usize, count: usize) -> Result<Deposit, crate::Error> {
+        if index >= count {
+            return Err(DepositCacheError::IndexOutOfRange { index, count }.into())
+        }
+        let layers = self.layers(count)?;
+        let mut proof = Vec::with_capacity(DEPOSIT_CONTRACT_TREE_DEPTH + 1);
+        for (depth, layer) in layers.iter().take(DEPOSIT_CONTRACT_TREE_DEPTH).enumerate() {
+            let sibling =
+                layer.get((index >> depth) ^ 1).copied().unwrap_or_else(|| zero_hash(depth));
+            proof.push(to_root(sibling));
+        }
+        proof.push(to_root(length_chunk(count)));
+        Ok(Deposit {
+            proof: Vector::try_from(proof).expect("correct length"),
+            data: self.deposits[index].clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        crypto::SecretKey,
+        phase0::{compute_domain, constants::DEPOSIT_DATA_LIST_BOUND, DepositMessage},
+        primitives::DomainType,
+        signing::{sign_with_domain, verify_signed_data},
+        state_transition::Context,
+    };
+
+    // ABI-encodes `fields` as a sequence of dynamic `bytes` values.
+    fn encode(fields: &[&[u8]]) -> Vec<u8> {
+        let mut head = vec![];
+        let mut tail = vec![];
+        for field in fields {
+            let offset = fields.len() * WORD_LEN + tail.len();
+            head.extend_from_slice(&[0u8; 24]);
+            head.extend_from_slice(&(offset as u64).to_be_bytes());
+            tail.extend_from_slice(&[0u8; 24]);
+            tail.extend_from_slice(&(field.len() as u64).to_be_bytes());
+            tail.extend_from_slice(field);
+            tail.resize(tail.len().next_multiple_of(WORD_LEN), 0);
+        }
+        head.extend(tail);
+        head
+    }
+
+    // A `DepositEvent` log for a deposit signed here rather than one captured from the deposit
+    // contract; `test_deposit_log_layout` pins the encoding to the one the contract emits.
+    fn deposit_log(seed: u8, amount: Gwei, index: u64, context: &Context) -> (Vec<u8>, Root) {
+        let secret_key = SecretKey::key_gen(&[seed; 32]).unwrap();
+        let message = DepositMessage {
+            public_key: secret_key.public_key(),
+            withdrawal_credentials: Bytes32::try_from(&[seed; 32][..]).unwrap(),
+            amount,
+        };
+        let domain = compute_domain(DomainType::Deposit, None, None, context).unwrap();
+        let deposit_data = DepositData {
+            signature: sign_with_domain(&message, &secret_key, domain).unwrap(),
+            public_key: message.public_key,
+            withdrawal_credentials: message.withdrawal_credentials,
+            amount,
+        };
+        let log = encode(&[
+            deposit_data.public_key.as_slice(),
+            deposit_data.withdrawal_credentials.as_slice(),
+            &amount.to_le_bytes(),
+            deposit_data.signature.as_slice(),
+            &index.to_le_bytes(),
+        ]);
+        (log, deposit_data.hash_tree_root().unwrap())
+    }
+
+    #[test]
+    fn test_parse_deposit_log() {
+        let context = Context::for_mainnet();
+        let domain = compute_domain(DomainType::Deposit, None, None, &context).unwrap();
+        for (i, amount) in
+            [32_000_000_000, 1_000_000_000, 2_048_000_000_000].into_iter().enumerate()
+        {
+            let (log, root) = deposit_log(i as u8 + 1, amount, 1000 + i as u64, &context);
+            assert_eq!(log.len(), 576);
+            let (deposit_data, index) = parse_deposit_log(&log).unwrap();
+            assert_eq!(index, 1000 + i as u64);
+            assert_eq!(deposit_data.amount, amount);
+            verify_deposit_event_consistency(&deposit_data, root).unwrap();
+            let message = DepositMessage {
+                public_key: deposit_data.public_key.clone(),
+                withdrawal_credentials: deposit_data.withdrawal_credentials.clone(),
+                amount: deposit_data.amount,
+            };
+            verify_signed_data(&message, &deposit_data.signature, &deposit_data.public_key, domain)
+                .unwrap();
+            assert!(verify_deposit_event_consistency(&deposit_data, Root::default()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_deposit_log_layout() {
+        // the deposit contract emits its five fields at fixed offsets, as each has a fixed length
+        let context = Context::for_mainnet();
+        let (log, _) = deposit_log(1, 32_000_000_000, 7, &context);
+        assert_eq!(log.len(), 0x240);
+        let offsets = (0..5).map(|i| read_word(&log, i * WORD_LEN).unwrap()).collect::<Vec<_>>();
+        assert_eq!(offsets, vec![0xa0, 0x100, 0x140, 0x180, 0x200]);
+        let lengths = offsets.iter().map(|&offset| read_word(&log, offset).unwrap());
+        assert_eq!(lengths.collect::<Vec<_>>(), vec![48, 32, 8, 96, 8]);
+        // the amount and index are little-endian, left-aligned in their word
+        assert_eq!(log[0x1a0..0x1a8], 32_000_000_000u64.to_le_bytes());
+        assert_eq!(log[0x220..0x228], 7u64.to_le_bytes());
+    }
+
+    #[test]
+    fn test_parse_malformed_deposit_log() {
+        let context = Context::for_minimal();
+        let (log, _) = deposit_log(1, 32_000_000_000, 0, &context);
+        assert!(matches!(
+            parse_deposit_log(&log[..log.len() - WORD_LEN]),
+            Err(crate::Error::DepositLog(DepositLogError::Truncated { .. }))
+        ));
+
+        let public_key = [1u8; 47];
+        let log = encode(&[&public_key, &[0u8; 32], &[0u8; 8], &[0u8; 96], &[0u8; 8]]);
+        assert!(matches!(
+            parse_deposit_log(&log),
+            Err(crate::Error::DepositLog(DepositLogError::InvalidFieldLength {
+                field: "pubkey",
+                ..
+            }))
+        ));
+
+        let mut amount = [0u8; 9];
+        amount[8] = 1;
+        let log = encode(&[&[0u8; 48], &[0u8; 32], &amount, &[0u8; 96], &[0u8; 8]]);
+        assert!(matches!(
+            parse_deposit_log(&log),
+            Err(crate::Error::DepositLog(DepositLogError::AmountOverflow))
+        ));
+    }
+
+    fn block_hash(number: u64) -> Hash32 {
+        Hash32::try_from(&[number as u8; 32][..]).unwrap()
+    }
+
+    fn expected_root(deposits: &[DepositData]) -> Root {
+        List::<DepositData, DEPOSIT_DATA_LIST_BOUND>::try_from(deposits.to_vec())
+            .unwrap()
+            .hash_tree_root()
+            .unwrap()
+    }
+
+    fn verify_proofs(cache: &DepositCache, count: usize) {
+        let root = cache.root_at_count(count).unwrap();
+        for index in 0..count {
+            let deposit = cache.deposit_at_count(index, count).unwrap();
+            let leaf = deposit.data.hash_tree_root().unwrap();
+            let depth = DEPOSIT_CONTRACT_TREE_DEPTH + 1;
+            is_valid_merkle_branch(leaf, &deposit.proof, depth, index, root).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_deposit_cache_reorg() {
+        let deposit = |amount| DepositData { amount, ..Default::default() };
+        let mut cache = DepositCache::default();
+        assert_eq!(cache.root(), expected_root(&[]));
+
+        // two deposits in block 1, one in block 2 and two in block 4, the last of which are
+        // dropped by a reorg of the execution layer after block 2
+        let blocks = [1, 1, 2, 4, 4];
+        for (index, number) in blocks.into_iter().enumerate() {
+            cache.insert(deposit(index as Gwei), index as u64, number, block_hash(number)).unwrap();
+        }
+        assert_eq!(cache.len(), 5);
+        for count in 0..=5 {
+            assert_eq!(
+                cache.root_at_count(count).unwrap(),
+                expected_root(&cache.deposits()[..count])
+            );
+            verify_proofs(&cache, count);
+        }
+        assert_eq!(cache.deposit_count_at_block(0), 0);
+        assert_eq!(cache.deposit_count_at_block(1), 2);
+        assert_eq!(cache.deposit_count_at_block(3), 3);
+        assert_eq!(cache.deposit_count_at_block(4), 5);
+        let root_before_reorg = cache.root_at_count(3).unwrap();
+
+        assert_eq!(cache.revert_to_block(BlockId::Number(3)).unwrap(), 2);
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.root(), root_before_reorg);
+        assert!(matches!(
+            cache.root_at_count(4),
+            Err(crate::Error::DepositCache(DepositCacheError::CountOutOfRange {
+                count: 4,
+                len: 3
+            }))
+        ));
+
+        // the canonical chain includes a single different deposit in block 3
+        let canonical = [deposit(0), deposit(1), deposit(2), deposit(10)];
+        cache.insert(canonical[3].clone(), 3, 3, block_hash(30)).unwrap();
+        assert_eq!(cache.root(), expected_root(&canonical));
+        for count in 0..=4 {
+            assert_eq!(cache.root_at_count(count).unwrap(), expected_root(&canonical[..count]));
+            verify_proofs(&cache, count);
+        }
+        let deposit = cache.deposit_at_count(3, 4).unwrap();
+        assert_eq!(deposit.data, canonical[3]);
+
+        assert_eq!(cache.revert_to_block(BlockId::Hash(block_hash(1))).unwrap(), 2);
+        assert_eq!(cache.root(), expected_root(&canonical[..2]));
+        assert!(matches!(
+            cache.revert_to_block(BlockId::Hash(block_hash(4))),
+            Err(crate::Error::DepositCache(DepositCacheError::UnknownBlock(_)))
+        ));
+    }
+
+    #[test]
+    fn test_deposit_cache_invalid_insert() {
+        let mut cache = DepositCache::default();
+        cache.insert(DepositData::default(), 0, 5, block_hash(5)).unwrap();
+        assert!(matches!(
+            cache.insert(DepositData::default(), 2, 5, block_hash(5)),
+            Err(crate::Error::DepositCache(DepositCacheError::NonConsecutiveIndex {
+                index: 2,
+                expected: 1
+            }))
+        ));
+        assert!(matches!(
+            cache.insert(DepositData::default(), 1, 4, block_hash(4)),
+            Err(crate::Error::DepositCache(DepositCacheError::UnorderedBlock {
+                block_number: 4,
+                latest: 5
+            }))
+        ));
+        assert!(matches!(
+            cache.insert(DepositData::default(), 1, 5, block_hash(50)),
+            Err(crate::Error::DepositCache(DepositCacheError::ConflictingBlock {
+                block_number: 5
+            }))
+        ));
+        assert!(matches!(
+            cache.deposit_at_count(1, 1),
+            Err(crate::Error::DepositCache(DepositCacheError::IndexOutOfRange {
+                index: 1,
+                count: 1
+            }))
+        ));
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -0,0 +1,474 @@
+//! Light-weight inspection of the transactions carried in execution payloads, e.g. for analytics
+//! over consensus blocks, without depending on an execution layer client.
+//!
+//! Only enough of a transaction is decoded to describe it: the signature is not recovered (so
+//! the sender is unknown) and nothing is checked beyond the structure of the encoding. Supported
+//! are legacy transactions and the typed envelopes of EIP-2930 (type 1), EIP-1559 (type 2),
+//! EIP-4844 (type 3) and EIP-7702 (type 4).
+use crate::{
+    deneb::polynomial_commitments::VersionedHash,
+    primitives::{ExecutionAddress, U256},
+    types::{ExecutionPayload, ExecutionPayloadRef},
+};
+
+pub const LEGACY_TX_TYPE: u8 = 0x00;
+pub const ACCESS_LIST_TX_TYPE: u8 = 0x01;
+pub const DYNAMIC_FEE_TX_TYPE: u8 = 0x02;
+pub const BLOB_TX_TYPE: u8 = 0x03;
+pub const SET_CODE_TX_TYPE: u8 = 0x04;
+
+/// The fields of a transaction relevant to its inspection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionEnvelope {
+    pub tx_type: u8,
+    /// The destination of the transaction, or `None` if it creates a contract.
+    pub to: Option<ExecutionAddress>,
+    pub value: U256,
+    pub gas_limit: u64,
+    /// The versioned hashes of the blobs of a blob transaction, empty for any other type.
+    pub blob_versioned_hashes: Vec<VersionedHash>,
+}
+
+/// A transaction as parsed by [`TransactionEnvelope::parse`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Envelope {
+    Transaction(TransactionEnvelope),
+    /// A transaction of an unknown type or with a malformed encoding, of the given length in
+    /// bytes.
+    Opaque(usize),
+}
+
+impl Envelope {
+    pub fn transaction(&self) -> Option<&TransactionEnvelope> {
+        match self {
+            Self::Transaction(transaction) => Some(transaction),
+            Self::Opaque(_) => None,
+        }
+    }
+
+    pub fn is_blob_carrying(&self) -> bool {
+        self.transaction().is_some_and(|transaction| transaction.tx_type == BLOB_TX_TYPE)
+    }
+
+    pub fn to(&self) -> Option<&ExecutionAddress> {
+        self.transaction().and_then(|transaction| transaction.to.as_ref())
+    }
+}
+
+// Positions of the fields of each transaction type in its RLP list.
+struct Layout {
+    field_count: usize,
+    gas_limit: usize,
+    to: usize,
+    value: usize,
+    blob_versioned_hashes: Option<usize>,
+}
+
+fn layout(tx_type: u8) -> Option<Layout> {
+    let layout = match tx_type {
+        // [nonce, gas_price, gas_limit, to, value, data, v, r, s]
+        LEGACY_TX_TYPE => {
+            Layout { field_count: 9, gas_limit: 2, to: 3, value: 4, blob_versioned_hashes: None }
+        }
+        // [chain_id, nonce, gas_price, gas_limit, to, value, data, access_list, y_parity, r, s]
+        ACCESS_LIST_TX_TYPE => {
+            Layout { field_count: 11, gas_limit: 3, to: 4, value: 5, blob_versioned_hashes: None }
+        }
+        // [chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, gas_limit, to, value,
+        //  data, access_list, y_parity, r, s]
+        DYNAMIC_FEE_TX_TYPE => {
+            Layout { field_count: 12, gas_limit: 4, to: 5, value: 6, blob_versioned_hashes: None }
+        }
+        // as a dynamic fee transaction, with max_fee_per_blob_gas and blob_versioned_hashes
+        // after the access list
+        BLOB_TX_TYPE => Layout {
+            field_count: 14,
+            gas_limit: 4,
+            to: 5,
+            value: 6,
+            blob_versioned_hashes: Some(10),
+        },
+        // as a dynamic fee transaction, with an authorization_list after the access list
+        SET_CODE_TX_TYPE => {
+            Layout { field_count: 13, gas_limit: 4, to: 5, value: 6, blob_versioned_hashes: None }
+        }
+        _ => return None,
+    };
+    Some(layout)
+}
+
+impl TransactionEnvelope {
+    /// Parse the transaction encoded in `bytes` as it appears in an execution payload: an RLP
+    /// list for a legacy transaction, or the type byte followed by an RLP list otherwise.
+    ///
+    /// Rather than failing, transactions that can not be parsed are returned as
+    /// [`Envelope::Opaque`], so one bad transaction does not stop the inspection of a payload.
+    pub fn parse(bytes: &[u8]) -> Envelope {
+        Self::try_parse(bytes).map_or(Envelope::Opaque(bytes.len()), Envelope::Transaction)
+    }
+
+    fn try_parse(bytes: &[u8]) -> Option<Self> {
+        let (&first, rest) = bytes.split_first()?;
+        // a legacy transaction is an RLP list, whose prefix is at least 0xc0
+        let (tx_type, payload) =
+            if first >= 0xc0 { (LEGACY_TX_TYPE, bytes) } else { (first, rest) };
+        let layout = layout(tx_type)?;
+        let (item, remainder) = decode_item(payload)?;
+        let Item::List(list) = item else { return None };
+        if !remainder.is_empty() {
+            return None
+        }
+        let fields = decode_list(list)?;
+        if fields.len() != layout.field_count {
+            return None
+        }
+
+        let to = match fields[layout.to].bytes()? {
+            [] if tx_type != BLOB_TX_TYPE && tx_type != SET_CODE_TX_TYPE => None,
+            address => Some(ExecutionAddress::try_from(address).ok()?),
+        };
+        let value = fields[layout.value].bytes()?;
+        if value.len() > 32 {
+            return None
+        }
+        let gas_limit = fields[layout.gas_limit].bytes()?;
+        if gas_limit.len() > 8 {
+            return None
+        }
+        let blob_versioned_hashes = match layout.blob_versioned_hashes {
+            Some(index) => {
+                let Item::List(hashes) = fields[index] else { return None };
+                decode_list(hashes)?
+                    .into_iter()
+                    .map(|hash| VersionedHash::try_from(hash.bytes()?).ok())
+                    .collect::<Option<Vec<_>>>()?
+            }
+            None => vec![],
+        };
+        Some(Self {
+            tx_type,
+            to,
+            value: U256::from_be_slice(value),
+            gas_limit: gas_limit.iter().fold(0, |acc, byte| (acc << 8) | *byte as u64),
+            blob_versioned_hashes,
+        })
+    }
+}
+
+// An RLP item, with the payload of a list left undecoded.
+#[derive(Clone, Copy)]
+enum Item<'a> {
+    Bytes(&'a [u8]),
+    List(&'a [u8]),
+}
+
+impl<'a> Item<'a> {
+    fn bytes(&self) -> Option<&'a [u8]> {
+        match self {
+            Self::Bytes(bytes) => Some(bytes),
+            Self::List(_) => None,
+        }
+    }
+}
+
+// Decode the RLP item at the start of `input`, returning it with the rest of `input`.
+fn decode_item(input: &[u8]) -> Option<(Item<'_>, &[u8])> {
+    let (&prefix, rest) = input.split_first()?;
+    let (is_list, offset, len) = match prefix {
+        0x00..=0x7f => return Some((Item::Bytes(&input[..1]), rest)),
+        0x80..=0xb7 => (false, 0, (prefix - 0x80) as usize),
+        0xb8..=0xbf => (false, (prefix - 0xb7) as usize, decode_length(rest, prefix - 0xb7)?),
+        0xc0..=0xf7 => (true, 0, (prefix - 0xc0) as usize),
+        0xf8..=0xff => (true, (prefix - 0xf7) as usize, decode_length(rest, prefix - 0xf7)?),
+    };
+    let end = offset.checked_add(len)?;
+    let payload = rest.get(offset..end)?;
+    let item = if is_list { Item::List(payload) } else { Item::Bytes(payload) };
+    Some((item, &rest[end..]))
+}
+
+// Decode the big-endian length of `len_of_len` bytes at the start of `input`.
+fn decode_length(input: &[u8], len_of_len: u8) -> Option<usize> {
+    let bytes = input.get(..len_of_len as usize)?;
+    if bytes.len() > std::mem::size_of::<usize>() {
+        return None
+    }
+    Some(bytes.iter().fold(0, |acc, byte| (acc << 8) | *byte as usize))
+}
+
+fn decode_list(mut payload: &[u8]) -> Option<Vec<Item<'_>>> {
+    let mut items = vec![];
+    while !payload.is_empty() {
+        let (item, rest) = decode_item(payload)?;
+        items.push(item);
+        payload = rest;
+    }
+    Some(items)
+}
+
+impl<
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    >
+    ExecutionPayload<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+    >
+{
+    /// Parse each transaction of the payload in order, see [`TransactionEnvelope::parse`].
+    pub fn iter_transactions(&self) -> impl Iterator<Item = Envelope> + '_ {
+        self.transactions()
+            .iter()
+            .map(|transaction| TransactionEnvelope::parse(transaction.as_ref()))
+    }
+}
+
+impl<
+        'a,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    >
+    ExecutionPayloadRef<
+        'a,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+    >
+{
+    /// Parse each transaction of the payload in order, see [`TransactionEnvelope::parse`].
+    pub fn iter_transactions(&self) -> impl Iterator<Item = Envelope> + '_ {
+        self.transactions()
+            .iter()
+            .map(|transaction| TransactionEnvelope::parse(transaction.as_ref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bellatrix::mainnet::Transaction, deneb::mainnet as deneb, serde::try_bytes_from_hex_str,
+        types,
+    };
+
+    // the signed example transaction of EIP-155
+    const EIP_155_TRANSACTION: &str = "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+
+    fn encode_length(len: usize, offset: u8) -> Vec<u8> {
+        if len < 56 {
+            return vec![offset + len as u8]
+        }
+        let bytes = len.to_be_bytes();
+        let bytes = &bytes[bytes.iter().position(|byte| *byte != 0).unwrap()..];
+        let mut encoding = vec![offset + 55 + bytes.len() as u8];
+        encoding.extend_from_slice(bytes);
+        encoding
+    }
+
+    fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
+        if bytes.len() == 1 && bytes[0] < 0x80 {
+            return bytes.to_vec()
+        }
+        let mut encoding = encode_length(bytes.len(), 0x80);
+        encoding.extend_from_slice(bytes);
+        encoding
+    }
+
+    fn encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+        let payload = items.concat();
+        let mut encoding = encode_length(payload.len(), 0xc0);
+        encoding.extend(payload);
+        encoding
+    }
+
+    fn encode_uint(value: u64) -> Vec<u8> {
+        let bytes = value.to_be_bytes();
+        let start = bytes.iter().position(|byte| *byte != 0).unwrap_or(bytes.len());
+        encode_bytes(&bytes[start..])
+    }
+
+    // A typed transaction of `tx_type` with `fields` followed by a signature.
+    fn typed(tx_type: u8, mut fields: Vec<Vec<u8>>) -> Vec<u8> {
+        fields.extend([encode_uint(1), encode_bytes(&[0x11; 32]), encode_bytes(&[0x22; 32])]);
+        let mut encoding = vec![tx_type];
+        encoding.extend(encode_list(&fields));
+        encoding
+    }
+
+    fn address(byte: u8) -> ExecutionAddress {
+        ExecutionAddress::try_from([byte; 20].as_ref()).unwrap()
+    }
+
+    fn parse(bytes: &[u8]) -> TransactionEnvelope {
+        TransactionEnvelope::parse(bytes).transaction().expect("can parse").clone()
+    }
+
+    #[test]
+    fn test_legacy_transaction() {
+        let bytes = try_bytes_from_hex_str(EIP_155_TRANSACTION).unwrap();
+        let envelope = parse(&bytes);
+        assert_eq!(envelope.tx_type, LEGACY_TX_TYPE);
+        assert_eq!(envelope.to, Some(address(0x35)));
+        assert_eq!(envelope.value, U256::from(1_000_000_000_000_000_000u64));
+        assert_eq!(envelope.gas_limit, 21_000);
+        assert!(envelope.blob_versioned_hashes.is_empty());
+    }
+
+    // NOTE: the typed transactions are hand-encoded with the field layouts of EIP-2930, EIP-1559,
+    // EIP-4844 and EIP-7702 rather than taken from mainnet payloads, and their signatures are
+    // placeholders, so they check the envelope layouts and not any real transaction
+    #[test]
+    fn test_typed_transactions() {
+        let access_list =
+            encode_list(&[encode_list(&[encode_bytes(&[0xaa; 20]), encode_list(&[])])]);
+
+        let access_list_tx = typed(
+            ACCESS_LIST_TX_TYPE,
+            vec![
+                encode_uint(1),
+                encode_uint(7),
+                encode_uint(30_000_000_000),
+                encode_uint(50_000),
+                encode_bytes(&[0x01; 20]),
+                encode_bytes(&[0x01; 12]),
+                encode_bytes(&[]),
+                access_list.clone(),
+            ],
+        );
+        let envelope = parse(&access_list_tx);
+        assert_eq!(envelope.tx_type, ACCESS_LIST_TX_TYPE);
+        assert_eq!(envelope.to, Some(address(0x01)));
+        assert_eq!(envelope.value, U256::from_be_slice(&[0x01; 12]));
+        assert_eq!(envelope.gas_limit, 50_000);
+
+        // a contract creation with init code long enough for a multi-byte length prefix
+        let creation = typed(
+            DYNAMIC_FEE_TX_TYPE,
+            vec![
+                encode_uint(1),
+                encode_uint(0),
+                encode_uint(1_000_000_000),
+                encode_uint(30_000_000_000),
+                encode_uint(3_000_000),
+                encode_bytes(&[]),
+                encode_uint(0),
+                encode_bytes(&[0x60; 300]),
+                encode_list(&[]),
+            ],
+        );
+        let envelope = parse(&creation);
+        assert_eq!(envelope.tx_type, DYNAMIC_FEE_TX_TYPE);
+        assert_eq!(envelope.to, None);
+        assert_eq!(envelope.value, U256::ZERO);
+        assert_eq!(envelope.gas_limit, 3_000_000);
+
+        let hashes = [[0x01; 32], [0x01; 32]].map(|mut hash| {
+            hash[31] = 0xff;
+            hash
+        });
+        let blob_fields = |to: &[u8]| {
+            vec![
+                encode_uint(1),
+                encode_uint(3),
+                encode_uint(1_000_000_000),
+                encode_uint(30_000_000_000),
+                encode_uint(21_000),
+                encode_bytes(to),
+                encode_uint(0),
+                encode_bytes(&[]),
+                encode_list(&[]),
+                encode_uint(1),
+                encode_list(&hashes.iter().map(|hash| encode_bytes(hash)).collect::<Vec<_>>()),
+            ]
+        };
+        let blob_tx = TransactionEnvelope::parse(&typed(BLOB_TX_TYPE, blob_fields(&[0x02; 20])));
+        assert!(blob_tx.is_blob_carrying());
+        assert_eq!(blob_tx.to(), Some(&address(0x02)));
+        let envelope = blob_tx.transaction().unwrap();
+        assert_eq!(
+            envelope.blob_versioned_hashes,
+            hashes.map(|hash| VersionedHash::try_from(hash.as_ref()).unwrap()).to_vec()
+        );
+        // blob transactions can not create contracts
+        let creation = typed(BLOB_TX_TYPE, blob_fields(&[]));
+        assert_eq!(TransactionEnvelope::parse(&creation), Envelope::Opaque(creation.len()));
+
+        let authorization = encode_list(&[
+            encode_uint(1),
+            encode_bytes(&[0x04; 20]),
+            encode_uint(0),
+            encode_uint(0),
+            encode_bytes(&[0x33; 32]),
+            encode_bytes(&[0x44; 32]),
+        ]);
+        let set_code_tx = typed(
+            SET_CODE_TX_TYPE,
+            vec![
+                encode_uint(1),
+                encode_uint(9),
+                encode_uint(1_000_000_000),
+                encode_uint(30_000_000_000),
+                encode_uint(100_000),
+                encode_bytes(&[0x03; 20]),
+                encode_uint(5),
+                encode_bytes(&[]),
+                access_list,
+                encode_list(&[authorization]),
+            ],
+        );
+        let envelope = parse(&set_code_tx);
+        assert_eq!(envelope.tx_type, SET_CODE_TX_TYPE);
+        assert_eq!(envelope.to, Some(address(0x03)));
+        assert_eq!(envelope.value, U256::from(5u64));
+        assert!(!TransactionEnvelope::parse(&set_code_tx).is_blob_carrying());
+    }
+
+    #[test]
+    fn test_malformed_transactions_are_opaque() {
+        let legacy = try_bytes_from_hex_str(EIP_155_TRANSACTION).unwrap();
+        let truncated = &legacy[..legacy.len() - 1];
+        let mut trailing = legacy.clone();
+        trailing.push(0);
+        let mut unknown_type = vec![0x05];
+        unknown_type.extend(encode_list(&[]));
+        let missing_field = typed(ACCESS_LIST_TX_TYPE, vec![encode_uint(1)]);
+        // a length prefix claiming more bytes than fit in memory
+        let huge_length = [0xbf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+
+        for bytes in
+            [truncated, &trailing, &unknown_type, &missing_field, &huge_length, &[], &[0x02]]
+        {
+            assert_eq!(TransactionEnvelope::parse(bytes), Envelope::Opaque(bytes.len()));
+        }
+    }
+
+    #[test]
+    fn test_iter_transactions() {
+        let legacy = try_bytes_from_hex_str(EIP_155_TRANSACTION).unwrap();
+        let mut payload = deneb::ExecutionPayload::default();
+        let transactions: [&[u8]; 3] = [&legacy, &[0xff, 0x00], &legacy];
+        for bytes in transactions {
+            payload.transactions.push(Transaction::try_from(bytes).unwrap());
+        }
+        let envelopes =
+            ExecutionPayloadRef::Deneb(&payload).iter_transactions().collect::<Vec<_>>();
+        assert_eq!(envelopes.len(), 3);
+        assert_eq!(envelopes[0].to(), Some(&address(0x35)));
+        assert_eq!(envelopes[1], Envelope::Opaque(2));
+        assert_eq!(envelopes[2], envelopes[0]);
+
+        let payload = types::mainnet::ExecutionPayload::Deneb(payload);
+        assert_eq!(payload.iter_transactions().collect::<Vec<_>>(), envelopes);
+    }
+}
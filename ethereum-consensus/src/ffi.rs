@@ -0,0 +1,284 @@
+//! A C ABI for verifying block signatures and blob KZG proofs, for embedding this crate in
+//! other languages.
+//!
+//! Each `extern "C"` function is a thin shim over a safe function taking the same arguments as
+//! slices and arrays, which spells out the contract the raw pointers must uphold. Objects are
+//! passed as the bytes of their SSZ encoding and the mainnet preset is used throughout.
+use crate::{
+    crypto::{verify_signature_decompressed, DecompressedPublicKey},
+    deneb::{
+        mainnet::Blob,
+        polynomial_commitments::{
+            self, KzgCommitment, KzgProof, BYTES_PER_COMMITMENT, BYTES_PER_PROOF,
+        },
+    },
+    phase0::{compute_domain, SignedBeaconBlockHeader},
+    primitives::{BlsPublicKey, DomainType, FixedBytes, Version},
+    signing::compute_signing_root,
+    ssz::prelude::*,
+    state_transition::Context,
+};
+use std::{slice, sync::OnceLock};
+
+pub const BLS_PUBLIC_KEY_LEN: usize = 48;
+
+/// The outcome of a call, returned to C callers as an integer.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok = 0,
+    NullPointer = 1,
+    /// An input has the wrong length, e.g. a blob not of the size of a mainnet blob.
+    InvalidLength = 2,
+    /// An input does not decode, e.g. malformed SSZ or a public key not on the curve.
+    InvalidEncoding = 3,
+    InvalidSignature = 4,
+    InvalidProof = 5,
+}
+
+// The mainnet context, loaded once as it carries the KZG trusted setup.
+fn context() -> &'static Context {
+    static CONTEXT: OnceLock<Context> = OnceLock::new();
+    CONTEXT.get_or_init(Context::for_mainnet)
+}
+
+/// Verify the proposer signature over the SSZ-encoded `signed_header` of a block (of any fork,
+/// as a block and its header share a root).
+///
+/// `fork_version` is the version of the fork at the epoch of the block.
+pub fn verify_block_signature(
+    signed_header: &[u8],
+    public_key: &[u8; BLS_PUBLIC_KEY_LEN],
+    fork_version: &[u8; 4],
+    genesis_validators_root: &[u8; 32],
+) -> Status {
+    let Ok(signed_header) = SignedBeaconBlockHeader::deserialize(signed_header) else {
+        return Status::InvalidEncoding
+    };
+    let Ok(public_key) = DecompressedPublicKey::try_from(&BlsPublicKey::from_bytes(public_key))
+    else {
+        return Status::InvalidEncoding
+    };
+    let domain = compute_domain(
+        DomainType::BeaconProposer,
+        Some(Version::from_bytes(fork_version)),
+        Some(FixedBytes::from_bytes(genesis_validators_root)),
+        context(),
+    );
+    let Ok(signing_root) =
+        domain.and_then(|domain| compute_signing_root(&signed_header.message, domain))
+    else {
+        return Status::InvalidEncoding
+    };
+    match verify_signature_decompressed(
+        &public_key,
+        signing_root.as_ref(),
+        &signed_header.signature,
+    ) {
+        Ok(()) => Status::Ok,
+        Err(_) => Status::InvalidSignature,
+    }
+}
+
+/// Verify the KZG `proof` that `blob` (of the mainnet blob size) matches `commitment`.
+pub fn verify_blob_kzg_proof(
+    blob: &[u8],
+    commitment: &[u8; BYTES_PER_COMMITMENT],
+    proof: &[u8; BYTES_PER_PROOF],
+) -> Status {
+    let Ok(blob) = Blob::try_from(blob) else { return Status::InvalidLength };
+    let result = polynomial_commitments::verify_blob_kzg_proof(
+        &blob,
+        &KzgCommitment::from_bytes(commitment),
+        &KzgProof::from_bytes(proof),
+        &context().kzg_settings,
+    );
+    match result {
+        Ok(()) => Status::Ok,
+        Err(polynomial_commitments::Error::InvalidProof) => Status::InvalidProof,
+        Err(_) => Status::InvalidEncoding,
+    }
+}
+
+unsafe fn as_slice<'a>(data: *const u8, len: usize) -> Option<&'a [u8]> {
+    (!data.is_null()).then(|| slice::from_raw_parts(data, len))
+}
+
+unsafe fn as_array<'a, const N: usize>(data: *const u8) -> Option<&'a [u8; N]> {
+    (!data.is_null()).then(|| &*(data as *const [u8; N]))
+}
+
+/// C entry point for [`verify_block_signature`].
+///
+/// # Safety
+///
+/// `signed_header` must be valid for reads of `signed_header_len` bytes, `public_key` of 48
+/// bytes, `fork_version` of 4 bytes and `genesis_validators_root` of 32 bytes, none of which may
+/// be mutated during the call. Null pointers are reported as `Status::NullPointer`.
+#[no_mangle]
+pub unsafe extern "C" fn ec_verify_block_signature(
+    signed_header: *const u8,
+    signed_header_len: usize,
+    public_key: *const u8,
+    fork_version: *const u8,
+    genesis_validators_root: *const u8,
+) -> Status {
+    let inputs = (
+        as_slice(signed_header, signed_header_len),
+        as_array(public_key),
+        as_array(fork_version),
+        as_array(genesis_validators_root),
+    );
+    let (Some(signed_header), Some(public_key), Some(fork_version), Some(root)) = inputs else {
+        return Status::NullPointer
+    };
+    verify_block_signature(signed_header, public_key, fork_version, root)
+}
+
+/// C entry point for [`verify_blob_kzg_proof`].
+///
+/// # Safety
+///
+/// `blob` must be valid for reads of `blob_len` bytes, and `commitment` and `proof` of 48 bytes
+/// each, none of which may be mutated during the call. Null pointers are reported as
+/// `Status::NullPointer`.
+#[no_mangle]
+pub unsafe extern "C" fn ec_verify_blob_kzg_proof(
+    blob: *const u8,
+    blob_len: usize,
+    commitment: *const u8,
+    proof: *const u8,
+) -> Status {
+    let (Some(blob), Some(commitment), Some(proof)) =
+        (as_slice(blob, blob_len), as_array(commitment), as_array(proof))
+    else {
+        return Status::NullPointer
+    };
+    verify_blob_kzg_proof(blob, commitment, proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        crypto::SecretKey,
+        deneb::{
+            mainnet::BYTES_PER_BLOB,
+            polynomial_commitments::{blob_to_kzg_commitment, compute_blob_kzg_proof},
+        },
+        phase0::BeaconBlockHeader,
+        signing::sign_with_domain,
+    };
+    use std::ptr;
+
+    #[test]
+    fn test_verify_block_signature() {
+        let context = context();
+        let secret_key = SecretKey::key_gen(&[1u8; 32]).unwrap();
+        let public_key = secret_key.public_key();
+        let fork_version = context.capella_fork_version;
+        let genesis_validators_root = [2u8; 32];
+        let domain = compute_domain(
+            DomainType::BeaconProposer,
+            Some(fork_version),
+            Some(FixedBytes::from_bytes(&genesis_validators_root)),
+            context,
+        )
+        .unwrap();
+        let message = BeaconBlockHeader { slot: 12, proposer_index: 3, ..Default::default() };
+        let signature = sign_with_domain(&message, &secret_key, domain).unwrap();
+        let mut signed_header = SignedBeaconBlockHeader { message, signature };
+
+        let verify = |signed_header: &SignedBeaconBlockHeader, public_key: &BlsPublicKey| {
+            let encoding = serialize(signed_header).unwrap();
+            let safe = verify_block_signature(
+                &encoding,
+                public_key.as_bytes(),
+                &fork_version,
+                &genesis_validators_root,
+            );
+            let status = unsafe {
+                ec_verify_block_signature(
+                    encoding.as_ptr(),
+                    encoding.len(),
+                    public_key.as_bytes().as_ptr(),
+                    fork_version.as_ptr(),
+                    genesis_validators_root.as_ptr(),
+                )
+            };
+            assert_eq!(safe, status);
+            status
+        };
+        assert_eq!(verify(&signed_header, &public_key), Status::Ok);
+        let other_key = SecretKey::key_gen(&[3u8; 32]).unwrap().public_key();
+        assert_eq!(verify(&signed_header, &other_key), Status::InvalidSignature);
+        assert_eq!(
+            verify(&signed_header, &BlsPublicKey::from_bytes(&[0xff; 48])),
+            Status::InvalidEncoding
+        );
+        signed_header.message.slot += 1;
+        assert_eq!(verify(&signed_header, &public_key), Status::InvalidSignature);
+
+        let encoding = serialize(&signed_header).unwrap();
+        assert_eq!(
+            verify_block_signature(
+                &encoding[1..],
+                public_key.as_bytes(),
+                &fork_version,
+                &genesis_validators_root
+            ),
+            Status::InvalidEncoding
+        );
+        let status = unsafe {
+            ec_verify_block_signature(
+                encoding.as_ptr(),
+                encoding.len(),
+                ptr::null(),
+                fork_version.as_ptr(),
+                genesis_validators_root.as_ptr(),
+            )
+        };
+        assert_eq!(status, Status::NullPointer);
+    }
+
+    #[test]
+    fn test_verify_blob_kzg_proof() {
+        let kzg_settings = &context().kzg_settings;
+        // keep each field element below the modulus by leaving its leading byte zero
+        let bytes = (0..BYTES_PER_BLOB)
+            .map(|i| if i % 32 == 0 { 0 } else { (i % 251) as u8 })
+            .collect::<Vec<_>>();
+        let blob = Blob::try_from(bytes.as_slice()).unwrap();
+        let commitment = blob_to_kzg_commitment(&blob, kzg_settings).unwrap();
+        let proof = compute_blob_kzg_proof(&blob, &commitment, kzg_settings).unwrap();
+
+        let verify = |blob: &[u8], proof: &KzgProof| {
+            let safe = verify_blob_kzg_proof(blob, commitment.as_bytes(), proof.as_bytes());
+            let status = unsafe {
+                ec_verify_blob_kzg_proof(
+                    blob.as_ptr(),
+                    blob.len(),
+                    commitment.as_bytes().as_ptr(),
+                    proof.as_bytes().as_ptr(),
+                )
+            };
+            assert_eq!(safe, status);
+            status
+        };
+        assert_eq!(verify(blob.as_ref(), &proof), Status::Ok);
+        let other_proof =
+            compute_blob_kzg_proof(&Blob::default(), &commitment, kzg_settings).unwrap();
+        assert_eq!(verify(blob.as_ref(), &other_proof), Status::InvalidProof);
+        assert_eq!(verify(&blob.as_ref()[1..], &proof), Status::InvalidLength);
+
+        let status = unsafe {
+            ec_verify_blob_kzg_proof(
+                ptr::null(),
+                0,
+                commitment.as_bytes().as_ptr(),
+                proof.as_bytes().as_ptr(),
+            )
+        };
+        assert_eq!(status, Status::NullPointer);
+    }
+}
@@ -0,0 +1,93 @@
+//! Altair fixtures made by the [`chain`] generator.
+use crate::{
+    altair::minimal,
+    fixtures::{
+        chain::{self, BlockOperations, ChainGenerator},
+        VALIDATOR_COUNT,
+    },
+    primitives::{Slot, FAR_FUTURE_EPOCH},
+    state_transition::Context,
+    types::SignedBeaconBlock,
+};
+use std::sync::OnceLock;
+
+/// The slot of the block returned by [`signed_block`].
+pub const BLOCK_SLOT: Slot = 1;
+
+/// The context the Altair fixtures are valid under: the context of the [`chain`] generator without
+/// the forks after Altair, where validators can exit from genesis.
+pub fn context() -> Context {
+    let mut context = chain::context();
+    context.bellatrix_fork_epoch = FAR_FUTURE_EPOCH;
+    context.capella_fork_epoch = FAR_FUTURE_EPOCH;
+    context.deneb_fork_epoch = FAR_FUTURE_EPOCH;
+    context.electra_fork_epoch = FAR_FUTURE_EPOCH;
+    context.shard_committee_period = 0;
+    context
+}
+
+/// A genesis state of [`VALIDATOR_COUNT`] interop validators, made from their deposits.
+pub fn genesis_state() -> minimal::BeaconState {
+    fixtures().genesis_state.clone()
+}
+
+/// A block on top of [`genesis_state`] at [`BLOCK_SLOT`] carrying every operation but deposits.
+///
+/// Deposits are left empty as the genesis state has processed every deposit it knows of, and
+/// learning of new ones takes a majority of the votes of an eth1 voting period.
+pub fn signed_block() -> minimal::SignedBeaconBlock {
+    fixtures().signed_block.clone()
+}
+
+struct Fixtures {
+    genesis_state: minimal::BeaconState,
+    signed_block: minimal::SignedBeaconBlock,
+}
+
+// The fixtures are made once per process, as they are signed and validated on the way.
+fn fixtures() -> &'static Fixtures {
+    static FIXTURES: OnceLock<Fixtures> = OnceLock::new();
+    FIXTURES.get_or_init(|| {
+        let context = context();
+        let genesis_state = chain::altair_genesis_state(VALIDATOR_COUNT, &context);
+        let mut generator = ChainGenerator::new(genesis_state.clone(), context);
+
+        // each operation is for a different validator, none of them the proposer
+        let proposer_index = generator.next_proposer_index();
+        let mut validators = (0..VALIDATOR_COUNT).filter(|&index| index != proposer_index);
+        let mut next_validator = || validators.next().expect("enough validators");
+        let operations = BlockOperations {
+            slashed_proposers: vec![next_validator()],
+            slashed_attesters: vec![next_validator(), next_validator()],
+            voluntary_exits: vec![generator.signed_voluntary_exit(next_validator())],
+            ..Default::default()
+        };
+        let SignedBeaconBlock::Altair(signed_block) = generator.next_block(operations) else {
+            unreachable!("the block is in the fork of the genesis state")
+        };
+        Fixtures {
+            genesis_state: genesis_state.as_altair().expect("genesis is in Altair").clone(),
+            signed_block,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{state_transition::minimal::Executor, types::BeaconState};
+
+    #[test]
+    fn test_fixtures_are_valid() {
+        let signed_block = signed_block();
+        let body = &signed_block.message.body;
+        assert_eq!(signed_block.message.slot, BLOCK_SLOT);
+        assert!(!body.proposer_slashings.is_empty());
+        assert!(!body.attester_slashings.is_empty());
+        assert!(!body.attestations.is_empty());
+        assert!(!body.voluntary_exits.is_empty());
+
+        let mut executor = Executor::new(BeaconState::Altair(genesis_state()), context());
+        executor.apply_block(&SignedBeaconBlock::Altair(signed_block.clone())).unwrap();
+    }
+}
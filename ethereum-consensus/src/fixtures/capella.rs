@@ -0,0 +1,99 @@
+//! Capella fixtures made by the [`chain`] generator.
+use crate::{
+    capella::minimal,
+    fixtures::{
+        chain::{self, BlockOperations, ChainGenerator},
+        VALIDATOR_COUNT,
+    },
+    primitives::{ExecutionAddress, Slot, FAR_FUTURE_EPOCH},
+    state_transition::Context,
+    types::SignedBeaconBlock,
+};
+use std::sync::OnceLock;
+
+/// The slot of the block returned by [`signed_block`].
+pub const BLOCK_SLOT: Slot = 1;
+
+/// The context the Capella fixtures are valid under: the context of the [`chain`] generator without
+/// the forks after Capella, where validators can exit from genesis.
+pub fn context() -> Context {
+    let mut context = chain::context();
+    context.deneb_fork_epoch = FAR_FUTURE_EPOCH;
+    context.electra_fork_epoch = FAR_FUTURE_EPOCH;
+    context.shard_committee_period = 0;
+    context
+}
+
+/// A genesis state of [`VALIDATOR_COUNT`] interop validators, made from their deposits.
+pub fn genesis_state() -> minimal::BeaconState {
+    fixtures().genesis_state.clone()
+}
+
+/// A block on top of [`genesis_state`] at [`BLOCK_SLOT`] carrying every operation but deposits.
+///
+/// The merge is complete from genesis, so the block carries an execution payload.
+///
+/// Deposits are left empty as the genesis state has processed every deposit it knows of, and
+/// learning of new ones takes a majority of the votes of an eth1 voting period.
+pub fn signed_block() -> minimal::SignedBeaconBlock {
+    fixtures().signed_block.clone()
+}
+
+struct Fixtures {
+    genesis_state: minimal::BeaconState,
+    signed_block: minimal::SignedBeaconBlock,
+}
+
+// The fixtures are made once per process, as they are signed and validated on the way.
+fn fixtures() -> &'static Fixtures {
+    static FIXTURES: OnceLock<Fixtures> = OnceLock::new();
+    FIXTURES.get_or_init(|| {
+        let context = context();
+        let genesis_state = chain::capella_genesis_state(VALIDATOR_COUNT, &context);
+        let mut generator = ChainGenerator::new(genesis_state.clone(), context);
+
+        // each operation is for a different validator, none of them the proposer
+        let proposer_index = generator.next_proposer_index();
+        let mut validators = (0..VALIDATOR_COUNT).filter(|&index| index != proposer_index);
+        let mut next_validator = || validators.next().expect("enough validators");
+        let address = ExecutionAddress::try_from([0x42u8; 20].as_ref()).expect("correct length");
+        let operations = BlockOperations {
+            slashed_proposers: vec![next_validator()],
+            slashed_attesters: vec![next_validator(), next_validator()],
+            voluntary_exits: vec![generator.signed_voluntary_exit(next_validator())],
+            bls_to_execution_changes: vec![
+                generator.signed_bls_to_execution_change(next_validator(), address)
+            ],
+            ..Default::default()
+        };
+        let SignedBeaconBlock::Capella(signed_block) = generator.next_block(operations) else {
+            unreachable!("the block is in the fork of the genesis state")
+        };
+        Fixtures {
+            genesis_state: genesis_state.as_capella().expect("genesis is in Capella").clone(),
+            signed_block,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{state_transition::minimal::Executor, types::BeaconState};
+
+    #[test]
+    fn test_fixtures_are_valid() {
+        let signed_block = signed_block();
+        let body = &signed_block.message.body;
+        assert_eq!(signed_block.message.slot, BLOCK_SLOT);
+        assert!(!body.proposer_slashings.is_empty());
+        assert!(!body.attester_slashings.is_empty());
+        assert!(!body.attestations.is_empty());
+        assert!(!body.voluntary_exits.is_empty());
+        assert!(!body.bls_to_execution_changes.is_empty());
+        assert_ne!(body.execution_payload, Default::default());
+
+        let mut executor = Executor::new(BeaconState::Capella(genesis_state()), context());
+        executor.apply_block(&SignedBeaconBlock::Capella(signed_block.clone())).unwrap();
+    }
+}
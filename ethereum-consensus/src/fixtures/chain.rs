@@ -0,0 +1,795 @@
+//! A chain generator for tests spanning forks: an interop genesis in Deneb (or Electra) advanced
+//! one full block per slot by [`ChainGenerator`], across the upgrade to Electra, with every active
+//! validator attesting and any operations, blobs or execution requests given for each block.
+//!
+//! Chains can also start from an interop genesis in Altair, Bellatrix or Capella, under a context
+//! without the later forks.
+//!
+//! Validators are the interop validators, so their keys are derived from their index by
+//! [`interop_secret_key`], including for validators joining through deposits.
+use crate::{
+    altair, bellatrix,
+    capella::{self, BlsToExecutionChange, SignedBlsToExecutionChange},
+    crypto::{aggregate, hash},
+    deneb::{self, minimal::BlobSidecar},
+    electra::{self, DepositRequest},
+    eth1::DepositCache,
+    fixtures::deneb::blob_bundle,
+    interop::{interop_deposit_data, interop_secret_key, interop_secret_keys},
+    phase0::{
+        self, compute_domain, compute_epoch_at_slot, compute_start_slot_at_epoch,
+        minimal::SLOTS_PER_HISTORICAL_ROOT, AttestationData, BeaconBlockHeader, Checkpoint,
+        Deposit, ProposerSlashing, SignedBeaconBlockHeader, SignedVoluntaryExit, VoluntaryExit,
+    },
+    primitives::{
+        BlsSignature, Domain, DomainType, Epoch, ExecutionAddress, Gwei, Hash32, Root, Slot,
+        ValidatorIndex,
+    },
+    signing::sign_with_domain,
+    ssz::prelude::*,
+    state_transition::{compute_shuffling, minimal::Executor, Context},
+    types::{minimal, BeaconBlock, BeaconState, SignedBeaconBlock},
+    Fork,
+};
+
+/// The epoch of the upgrade to Electra in [`context`]; every earlier fork is active from genesis.
+pub const ELECTRA_FORK_EPOCH: Epoch = 1;
+
+/// The context chains are generated under: the `minimal` preset starting in Deneb, with the
+/// upgrade to Electra at [`ELECTRA_FORK_EPOCH`].
+///
+/// The shard committee period is shortened to two epochs so that genesis validators can exit
+/// within a short chain.
+pub fn context() -> Context {
+    let mut context = Context::for_minimal();
+    context.altair_fork_epoch = 0;
+    context.bellatrix_fork_epoch = 0;
+    context.capella_fork_epoch = 0;
+    context.deneb_fork_epoch = 0;
+    context.electra_fork_epoch = ELECTRA_FORK_EPOCH;
+    context.shard_committee_period = 2;
+    context
+}
+
+/// A Deneb genesis state of the first `validator_count` interop validators, made from their
+/// deposits by `initialize_beacon_state_from_eth1`.
+///
+/// The genesis execution payload header is not empty, so the merge is complete from genesis.
+pub fn genesis_state(validator_count: usize, context: &Context) -> minimal::BeaconState {
+    let (eth1_block_hash, deposits) = interop_deposits(validator_count, context);
+    let execution_payload_header = deneb::minimal::ExecutionPayloadHeader {
+        block_hash: Hash32::try_from([2u8; 32].as_ref()).expect("correct length"),
+        ..Default::default()
+    };
+    let state = deneb::initialize_beacon_state_from_eth1::<
+        { deneb::minimal::SLOTS_PER_HISTORICAL_ROOT },
+        { deneb::minimal::HISTORICAL_ROOTS_LIMIT },
+        { deneb::minimal::ETH1_DATA_VOTES_BOUND },
+        { deneb::minimal::VALIDATOR_REGISTRY_LIMIT },
+        { deneb::minimal::EPOCHS_PER_HISTORICAL_VECTOR },
+        { deneb::minimal::EPOCHS_PER_SLASHINGS_VECTOR },
+        { deneb::minimal::MAX_VALIDATORS_PER_COMMITTEE },
+        { deneb::minimal::SYNC_COMMITTEE_SIZE },
+        { deneb::minimal::MAX_PROPOSER_SLASHINGS },
+        { deneb::minimal::MAX_ATTESTER_SLASHINGS },
+        { deneb::minimal::MAX_ATTESTATIONS },
+        { deneb::minimal::MAX_DEPOSITS },
+        { deneb::minimal::MAX_VOLUNTARY_EXITS },
+        { deneb::minimal::BYTES_PER_LOGS_BLOOM },
+        { deneb::minimal::MAX_EXTRA_DATA_BYTES },
+        { deneb::minimal::MAX_BYTES_PER_TRANSACTION },
+        { deneb::minimal::MAX_TRANSACTIONS_PER_PAYLOAD },
+        { deneb::minimal::MAX_WITHDRAWALS_PER_PAYLOAD },
+        { deneb::minimal::MAX_BLS_TO_EXECUTION_CHANGES },
+        { deneb::minimal::MAX_BLOB_COMMITMENTS_PER_BLOCK },
+    >(
+        eth1_block_hash,
+        context.min_genesis_time,
+        &deposits,
+        Some(&execution_payload_header),
+        context,
+    )
+    .expect("can initialize state");
+    BeaconState::Deneb(state)
+}
+
+/// An Electra genesis state of the first `validator_count` interop validators, made by
+/// `initialize_beacon_state_from_interop`.
+pub fn electra_genesis_state(validator_count: usize, context: &Context) -> minimal::BeaconState {
+    let execution_payload_header = electra::minimal::ExecutionPayloadHeader {
+        block_hash: Hash32::try_from([2u8; 32].as_ref()).expect("correct length"),
+        ..Default::default()
+    };
+    let state = electra::initialize_beacon_state_from_interop::<
+        { electra::minimal::SLOTS_PER_HISTORICAL_ROOT },
+        { electra::minimal::HISTORICAL_ROOTS_LIMIT },
+        { electra::minimal::ETH1_DATA_VOTES_BOUND },
+        { electra::minimal::VALIDATOR_REGISTRY_LIMIT },
+        { electra::minimal::EPOCHS_PER_HISTORICAL_VECTOR },
+        { electra::minimal::EPOCHS_PER_SLASHINGS_VECTOR },
+        { electra::minimal::MAX_VALIDATORS_PER_COMMITTEE },
+        { electra::minimal::SYNC_COMMITTEE_SIZE },
+        { electra::minimal::PENDING_DEPOSITS_LIMIT },
+        { electra::minimal::PENDING_PARTIAL_WITHDRAWALS_LIMIT },
+        { electra::minimal::PENDING_CONSOLIDATIONS_LIMIT },
+        { electra::minimal::MAX_PROPOSER_SLASHINGS },
+        { electra::minimal::MAX_VALIDATORS_PER_SLOT },
+        { electra::minimal::MAX_COMMITTEES_PER_SLOT },
+        { electra::minimal::MAX_ATTESTER_SLASHINGS_ELECTRA },
+        { electra::minimal::MAX_ATTESTATIONS_ELECTRA },
+        { electra::minimal::MAX_DEPOSITS },
+        { electra::minimal::MAX_VOLUNTARY_EXITS },
+        { electra::minimal::BYTES_PER_LOGS_BLOOM },
+        { electra::minimal::MAX_EXTRA_DATA_BYTES },
+        { electra::minimal::MAX_BYTES_PER_TRANSACTION },
+        { electra::minimal::MAX_TRANSACTIONS_PER_PAYLOAD },
+        { electra::minimal::MAX_WITHDRAWALS_PER_PAYLOAD },
+        { electra::minimal::MAX_BLS_TO_EXECUTION_CHANGES },
+        { electra::minimal::MAX_BLOB_COMMITMENTS_PER_BLOCK },
+        { electra::minimal::MAX_DEPOSIT_REQUESTS_PER_PAYLOAD },
+        { electra::minimal::MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD },
+        { electra::minimal::MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD },
+    >(
+        Hash32::try_from([1u8; 32].as_ref()).expect("correct length"),
+        context.min_genesis_time,
+        &interop_secret_keys(validator_count),
+        Some(&execution_payload_header),
+        context,
+    )
+    .expect("can initialize state");
+    BeaconState::Electra(state)
+}
+
+/// An Altair genesis state of the first `validator_count` interop validators, made from their
+/// deposits by `initialize_beacon_state_from_eth1`.
+pub fn altair_genesis_state(validator_count: usize, context: &Context) -> minimal::BeaconState {
+    let (eth1_block_hash, deposits) = interop_deposits(validator_count, context);
+    let state = altair::initialize_beacon_state_from_eth1::<
+        { altair::minimal::SLOTS_PER_HISTORICAL_ROOT },
+        { altair::minimal::HISTORICAL_ROOTS_LIMIT },
+        { altair::minimal::ETH1_DATA_VOTES_BOUND },
+        { altair::minimal::VALIDATOR_REGISTRY_LIMIT },
+        { altair::minimal::EPOCHS_PER_HISTORICAL_VECTOR },
+        { altair::minimal::EPOCHS_PER_SLASHINGS_VECTOR },
+        { altair::minimal::MAX_VALIDATORS_PER_COMMITTEE },
+        { altair::minimal::SYNC_COMMITTEE_SIZE },
+        { altair::minimal::MAX_PROPOSER_SLASHINGS },
+        { altair::minimal::MAX_ATTESTER_SLASHINGS },
+        { altair::minimal::MAX_ATTESTATIONS },
+        { altair::minimal::MAX_DEPOSITS },
+        { altair::minimal::MAX_VOLUNTARY_EXITS },
+    >(eth1_block_hash, context.min_genesis_time, &deposits, context)
+    .expect("can initialize state");
+    BeaconState::Altair(state)
+}
+
+/// A Bellatrix genesis state of the first `validator_count` interop validators, made from their
+/// deposits by `initialize_beacon_state_from_eth1`.
+///
+/// As for [`genesis_state`], the merge is complete from genesis.
+pub fn bellatrix_genesis_state(validator_count: usize, context: &Context) -> minimal::BeaconState {
+    let (eth1_block_hash, deposits) = interop_deposits(validator_count, context);
+    let execution_payload_header = bellatrix::minimal::ExecutionPayloadHeader {
+        block_hash: Hash32::try_from([2u8; 32].as_ref()).expect("correct length"),
+        ..Default::default()
+    };
+    let state = bellatrix::initialize_beacon_state_from_eth1::<
+        { bellatrix::minimal::SLOTS_PER_HISTORICAL_ROOT },
+        { bellatrix::minimal::HISTORICAL_ROOTS_LIMIT },
+        { bellatrix::minimal::ETH1_DATA_VOTES_BOUND },
+        { bellatrix::minimal::VALIDATOR_REGISTRY_LIMIT },
+        { bellatrix::minimal::EPOCHS_PER_HISTORICAL_VECTOR },
+        { bellatrix::minimal::EPOCHS_PER_SLASHINGS_VECTOR },
+        { bellatrix::minimal::MAX_VALIDATORS_PER_COMMITTEE },
+        { bellatrix::minimal::SYNC_COMMITTEE_SIZE },
+        { bellatrix::minimal::MAX_PROPOSER_SLASHINGS },
+        { bellatrix::minimal::MAX_ATTESTER_SLASHINGS },
+        { bellatrix::minimal::MAX_ATTESTATIONS },
+        { bellatrix::minimal::MAX_DEPOSITS },
+        { bellatrix::minimal::MAX_VOLUNTARY_EXITS },
+        { bellatrix::minimal::BYTES_PER_LOGS_BLOOM },
+        { bellatrix::minimal::MAX_EXTRA_DATA_BYTES },
+        { bellatrix::minimal::MAX_BYTES_PER_TRANSACTION },
+        { bellatrix::minimal::MAX_TRANSACTIONS_PER_PAYLOAD },
+    >(
+        eth1_block_hash,
+        context.min_genesis_time,
+        &deposits,
+        Some(&execution_payload_header),
+        context,
+    )
+    .expect("can initialize state");
+    BeaconState::Bellatrix(state)
+}
+
+/// A Capella genesis state of the first `validator_count` interop validators, made from their
+/// deposits by `initialize_beacon_state_from_eth1`.
+///
+/// As for [`genesis_state`], the merge is complete from genesis.
+pub fn capella_genesis_state(validator_count: usize, context: &Context) -> minimal::BeaconState {
+    let (eth1_block_hash, deposits) = interop_deposits(validator_count, context);
+    let execution_payload_header = capella::minimal::ExecutionPayloadHeader {
+        block_hash: Hash32::try_from([2u8; 32].as_ref()).expect("correct length"),
+        ..Default::default()
+    };
+    let state = capella::initialize_beacon_state_from_eth1::<
+        { capella::minimal::SLOTS_PER_HISTORICAL_ROOT },
+        { capella::minimal::HISTORICAL_ROOTS_LIMIT },
+        { capella::minimal::ETH1_DATA_VOTES_BOUND },
+        { capella::minimal::VALIDATOR_REGISTRY_LIMIT },
+        { capella::minimal::EPOCHS_PER_HISTORICAL_VECTOR },
+        { capella::minimal::EPOCHS_PER_SLASHINGS_VECTOR },
+        { capella::minimal::MAX_VALIDATORS_PER_COMMITTEE },
+        { capella::minimal::SYNC_COMMITTEE_SIZE },
+        { capella::minimal::MAX_PROPOSER_SLASHINGS },
+        { capella::minimal::MAX_ATTESTER_SLASHINGS },
+        { capella::minimal::MAX_ATTESTATIONS },
+        { capella::minimal::MAX_DEPOSITS },
+        { capella::minimal::MAX_VOLUNTARY_EXITS },
+        { capella::minimal::BYTES_PER_LOGS_BLOOM },
+        { capella::minimal::MAX_EXTRA_DATA_BYTES },
+        { capella::minimal::MAX_BYTES_PER_TRANSACTION },
+        { capella::minimal::MAX_TRANSACTIONS_PER_PAYLOAD },
+        { capella::minimal::MAX_WITHDRAWALS_PER_PAYLOAD },
+        { capella::minimal::MAX_BLS_TO_EXECUTION_CHANGES },
+    >(
+        eth1_block_hash,
+        context.min_genesis_time,
+        &deposits,
+        Some(&execution_payload_header),
+        context,
+    )
+    .expect("can initialize state");
+    BeaconState::Capella(state)
+}
+
+// The deposits of the first `validator_count` interop validators, each proven against the deposit
+// root after it as it was processed by the deposit contract, with the hash of the eth1 block they
+// are in.
+fn interop_deposits(validator_count: usize, context: &Context) -> (Hash32, Vec<Deposit>) {
+    let eth1_block_hash = Hash32::try_from([1u8; 32].as_ref()).expect("correct length");
+    let mut deposit_cache = DepositCache::default();
+    for index in 0..validator_count {
+        let secret_key = interop_secret_key(index);
+        let deposit_data =
+            interop_deposit_data(&secret_key, context.max_effective_balance, context)
+                .expect("can sign deposit");
+        deposit_cache
+            .insert(deposit_data, index as u64, 0, eth1_block_hash.clone())
+            .expect("deposits are consecutive");
+    }
+    let deposits = (0..validator_count)
+        .map(|index| deposit_cache.deposit_at_count(index, index + 1).expect("deposit is cached"))
+        .collect();
+    (eth1_block_hash, deposits)
+}
+
+/// The header of the latest block of `state`, committing to `state`, e.g. to anchor a fork
+/// choice store at a genesis state.
+pub fn anchor_block(state: &minimal::BeaconState) -> BeaconBlockHeader {
+    let mut header = state.latest_block_header().clone();
+    header.state_root = state.hash_tree_root().expect("can merkleize");
+    header
+}
+
+/// The operations to include in a block made by [`ChainGenerator::next_block`], besides the
+/// attestations of the previous slot.
+#[derive(Debug, Default, Clone)]
+pub struct BlockOperations {
+    /// The validators to slash, each with two conflicting headers for the previous slot.
+    pub slashed_proposers: Vec<ValidatorIndex>,
+    /// The validators to slash together, with two conflicting votes for the previous slot.
+    pub slashed_attesters: Vec<ValidatorIndex>,
+    pub voluntary_exits: Vec<SignedVoluntaryExit>,
+    pub bls_to_execution_changes: Vec<SignedBlsToExecutionChange>,
+    /// The number of blobs (from [`blob_bundle`]) the block commits to.
+    pub blob_count: usize,
+    /// Only allowed in Electra blocks.
+    pub execution_requests: electra::minimal::ExecutionRequests,
+}
+
+/// Extends a chain by one valid block per slot, applied with full validation.
+pub struct ChainGenerator {
+    pub executor: Executor,
+}
+
+impl ChainGenerator {
+    pub fn new(state: minimal::BeaconState, context: Context) -> Self {
+        Self { executor: Executor::new(state, context) }
+    }
+
+    /// The proposer of the block made by the next call to [`Self::next_block`].
+    pub fn next_proposer_index(&self) -> ValidatorIndex {
+        let state = self.executor.precompute_next_slot().expect("can advance slots").state;
+        proposer_index(&state, &self.executor.context)
+    }
+
+    /// A voluntary exit of the validator at `index` in the current epoch.
+    pub fn signed_voluntary_exit(&self, index: ValidatorIndex) -> SignedVoluntaryExit {
+        let context = &self.executor.context;
+        let state = self.executor.state.inner();
+        let epoch = compute_epoch_at_slot(state.slot(), context);
+        let message = VoluntaryExit { epoch, validator_index: index };
+        // exits are signed for the Capella fork from Deneb on
+        let domain = if state.version() >= Fork::Deneb {
+            compute_domain(
+                DomainType::VoluntaryExit,
+                Some(context.capella_fork_version),
+                Some(state.genesis_validators_root()),
+                context,
+            )
+            .expect("can compute domain")
+        } else {
+            domain_at_epoch(state, DomainType::VoluntaryExit, epoch, context)
+        };
+        let signature =
+            sign_with_domain(&message, &interop_secret_key(index), domain).expect("can sign");
+        SignedVoluntaryExit { message, signature }
+    }
+
+    /// A change of the BLS withdrawal credentials of the validator at `index` to withdraw to
+    /// `address`, signed with the key of the validator as interop credentials commit to it.
+    pub fn signed_bls_to_execution_change(
+        &self,
+        index: ValidatorIndex,
+        address: ExecutionAddress,
+    ) -> SignedBlsToExecutionChange {
+        let context = &self.executor.context;
+        let state = self.executor.state.inner();
+        let secret_key = interop_secret_key(index);
+        let message = BlsToExecutionChange {
+            validator_index: index,
+            from_bls_public_key: secret_key.public_key(),
+            to_execution_address: address,
+        };
+        let domain = compute_domain(
+            DomainType::BlsToExecutionChange,
+            None,
+            Some(state.genesis_validators_root()),
+            context,
+        )
+        .expect("can compute domain");
+        let signature = sign_with_domain(&message, &secret_key, domain).expect("can sign");
+        SignedBlsToExecutionChange { message, signature }
+    }
+
+    /// A deposit request of `amount` for the interop validator at `validator_index`, with the
+    /// deposit `index` assigned by the deposit contract.
+    pub fn deposit_request(
+        &self,
+        validator_index: usize,
+        amount: Gwei,
+        index: u64,
+    ) -> DepositRequest {
+        let secret_key = interop_secret_key(validator_index);
+        let deposit_data = interop_deposit_data(&secret_key, amount, &self.executor.context)
+            .expect("can sign deposit");
+        DepositRequest {
+            public_key: deposit_data.public_key,
+            withdrawal_credentials: deposit_data.withdrawal_credentials,
+            amount,
+            signature: deposit_data.signature,
+            index,
+        }
+    }
+
+    /// Make the block at the slot after the current state with `operations` and the attestations
+    /// of every committee of the previous slot, and apply it.
+    pub fn next_block(&mut self, operations: BlockOperations) -> minimal::SignedBeaconBlock {
+        let block = self.produce_block(operations);
+        self.executor.apply_block(&block).expect("block is valid");
+        block
+    }
+
+    fn produce_block(&self, operations: BlockOperations) -> minimal::SignedBeaconBlock {
+        let context = &self.executor.context;
+        let state = self.executor.precompute_next_slot().expect("can advance slots").state;
+        let slot = state.slot();
+        let epoch = compute_epoch_at_slot(slot, context);
+        // the state is in the fork of the block, so its current version signs the block itself
+        let domain = |domain_type| {
+            compute_domain(
+                domain_type,
+                Some(state.fork().current_version),
+                Some(state.genesis_validators_root()),
+                context,
+            )
+            .expect("can compute domain")
+        };
+        let committee_attestations = committee_attestations(&state, context);
+        let proposer_slashings = operations
+            .slashed_proposers
+            .iter()
+            .map(|&index| proposer_slashing(&state, index, context))
+            .collect::<Vec<_>>();
+        let mut slashed_attesters = operations.slashed_attesters.clone();
+        slashed_attesters.sort_unstable();
+        let double_votes = double_votes(&state, &slashed_attesters, context);
+        let (_, commitments, _) = blob_bundle(operations.blob_count);
+        let commitments = List::try_from(commitments.to_vec()).expect("within bound");
+        // a distinct execution block hash for each slot
+        let block_hash =
+            Hash32::try_from(hash(slot.to_le_bytes()).as_ref()).expect("correct length");
+
+        if state.version() < Fork::Electra {
+            assert!(
+                operations.execution_requests == Default::default(),
+                "execution requests are only processed from Electra"
+            );
+        }
+        if state.version() < Fork::Deneb {
+            assert_eq!(operations.blob_count, 0, "blobs are only committed to from Deneb");
+        }
+        if state.version() < Fork::Capella {
+            assert!(
+                operations.bls_to_execution_changes.is_empty(),
+                "BLS to execution changes are only processed from Capella"
+            );
+        }
+        // until Electra, attester slashings and attestations are the phase0 containers
+        let phase0_attester_slashings = || {
+            let mut votes =
+                double_votes.iter().map(|(data, signature)| phase0::minimal::IndexedAttestation {
+                    attesting_indices: List::try_from(slashed_attesters.clone())
+                        .expect("within bound"),
+                    data: data.clone(),
+                    signature: signature.clone(),
+                });
+            let mut slashings = List::default();
+            if let (Some(attestation_1), Some(attestation_2)) = (votes.next(), votes.next()) {
+                slashings.push(phase0::minimal::AttesterSlashing { attestation_1, attestation_2 });
+            }
+            slashings
+        };
+        let phase0_attestations = || {
+            let attestations = committee_attestations
+                .iter()
+                .map(|(data, committee)| phase0::minimal::Attestation {
+                    aggregation_bits: vec![true; committee.len()]
+                        .as_slice()
+                        .try_into()
+                        .expect("within bound"),
+                    signature: sign_attestation_data(&state, data, committee, context),
+                    data: data.clone(),
+                })
+                .collect::<Vec<_>>();
+            List::try_from(attestations).expect("within bound")
+        };
+
+        let mut body = match &state {
+            BeaconState::Altair(inner) => {
+                let mut body = altair::minimal::BeaconBlockBody::default();
+                body.eth1_data = inner.eth1_data.clone();
+                body.proposer_slashings = List::try_from(proposer_slashings).expect("within bound");
+                body.attester_slashings = phase0_attester_slashings();
+                body.attestations = phase0_attestations();
+                body.voluntary_exits =
+                    List::try_from(operations.voluntary_exits).expect("within bound");
+                minimal::BeaconBlockBody::Altair(body)
+            }
+            BeaconState::Bellatrix(inner) => {
+                let mut body = bellatrix::minimal::BeaconBlockBody::default();
+                body.eth1_data = inner.eth1_data.clone();
+                body.proposer_slashings = List::try_from(proposer_slashings).expect("within bound");
+                body.attester_slashings = phase0_attester_slashings();
+                body.attestations = phase0_attestations();
+                body.voluntary_exits =
+                    List::try_from(operations.voluntary_exits).expect("within bound");
+                let payload = &mut body.execution_payload;
+                payload.parent_hash = inner.latest_execution_payload_header.block_hash.clone();
+                payload.block_hash = block_hash;
+                payload.prev_randao = bellatrix::get_randao_mix(inner, epoch).clone();
+                payload.timestamp =
+                    bellatrix::compute_timestamp_at_slot(inner, slot, context).expect("valid slot");
+                minimal::BeaconBlockBody::Bellatrix(body)
+            }
+            BeaconState::Capella(inner) => {
+                let mut body = capella::minimal::BeaconBlockBody::default();
+                body.eth1_data = inner.eth1_data.clone();
+                body.proposer_slashings = List::try_from(proposer_slashings).expect("within bound");
+                body.attester_slashings = phase0_attester_slashings();
+                body.attestations = phase0_attestations();
+                body.voluntary_exits =
+                    List::try_from(operations.voluntary_exits).expect("within bound");
+                body.bls_to_execution_changes =
+                    List::try_from(operations.bls_to_execution_changes).expect("within bound");
+                let payload = &mut body.execution_payload;
+                payload.parent_hash = inner.latest_execution_payload_header.block_hash.clone();
+                payload.block_hash = block_hash;
+                payload.prev_randao = capella::get_randao_mix(inner, epoch).clone();
+                payload.timestamp =
+                    capella::compute_timestamp_at_slot(inner, slot, context).expect("valid slot");
+                payload.withdrawals =
+                    List::try_from(capella::get_expected_withdrawals(inner, context))
+                        .expect("within bound");
+                minimal::BeaconBlockBody::Capella(body)
+            }
+            BeaconState::Deneb(inner) => {
+                let mut body = deneb::minimal::BeaconBlockBody::default();
+                body.eth1_data = inner.eth1_data.clone();
+                body.proposer_slashings = List::try_from(proposer_slashings).expect("within bound");
+                body.attester_slashings = phase0_attester_slashings();
+                body.attestations = phase0_attestations();
+                body.voluntary_exits =
+                    List::try_from(operations.voluntary_exits).expect("within bound");
+                body.bls_to_execution_changes =
+                    List::try_from(operations.bls_to_execution_changes).expect("within bound");
+                body.blob_kzg_commitments = commitments;
+                let payload = &mut body.execution_payload;
+                payload.parent_hash = inner.latest_execution_payload_header.block_hash.clone();
+                payload.block_hash = block_hash;
+                payload.prev_randao = deneb::get_randao_mix(inner, epoch).clone();
+                payload.timestamp =
+                    deneb::compute_timestamp_at_slot(inner, slot, context).expect("valid slot");
+                payload.withdrawals =
+                    List::try_from(deneb::get_expected_withdrawals(inner, context))
+                        .expect("within bound");
+                minimal::BeaconBlockBody::Deneb(body)
+            }
+            BeaconState::Electra(inner) => {
+                let mut body = electra::minimal::BeaconBlockBody::default();
+                body.eth1_data = inner.eth1_data.clone();
+                body.proposer_slashings = List::try_from(proposer_slashings).expect("within bound");
+                let mut votes = double_votes.iter().map(|(data, signature)| {
+                    electra::minimal::IndexedAttestation {
+                        attesting_indices: List::try_from(slashed_attesters.clone())
+                            .expect("within bound"),
+                        data: data.clone(),
+                        signature: signature.clone(),
+                    }
+                });
+                if let (Some(attestation_1), Some(attestation_2)) = (votes.next(), votes.next()) {
+                    body.attester_slashings
+                        .push(electra::minimal::AttesterSlashing { attestation_1, attestation_2 });
+                }
+                // the attestations of every committee of the slot go in a single aggregate, which
+                // may be for a slot before the upgrade
+                if let Some((data, _)) = committee_attestations.first() {
+                    let data = AttestationData { index: 0, ..data.clone() };
+                    let mut committee_bits = Bitvector::default();
+                    let mut attesters = vec![];
+                    for (data, committee) in &committee_attestations {
+                        committee_bits.set(data.index, true);
+                        attesters.extend_from_slice(committee);
+                    }
+                    body.attestations.push(electra::minimal::Attestation {
+                        aggregation_bits: vec![true; attesters.len()]
+                            .as_slice()
+                            .try_into()
+                            .expect("within bound"),
+                        signature: sign_attestation_data(&state, &data, &attesters, context),
+                        data,
+                        committee_bits,
+                    });
+                }
+                body.voluntary_exits =
+                    List::try_from(operations.voluntary_exits).expect("within bound");
+                body.bls_to_execution_changes =
+                    List::try_from(operations.bls_to_execution_changes).expect("within bound");
+                body.blob_kzg_commitments = commitments;
+                body.execution_requests = operations.execution_requests;
+                let payload = &mut body.execution_payload;
+                payload.parent_hash = inner.latest_execution_payload_header.block_hash.clone();
+                payload.block_hash = block_hash;
+                payload.prev_randao = electra::get_randao_mix(inner, epoch).clone();
+                payload.timestamp =
+                    electra::compute_timestamp_at_slot(inner, slot, context).expect("valid slot");
+                let (withdrawals, _) = electra::get_expected_withdrawals(inner, context);
+                payload.withdrawals = List::try_from(withdrawals).expect("within bound");
+                minimal::BeaconBlockBody::Electra(body)
+            }
+            state => panic!("chains are only generated from Altair, not {:?}", state.version()),
+        };
+
+        let proposer_key = interop_secret_key(proposer_index(&state, context));
+        let randao_reveal =
+            sign_with_domain(&epoch, &proposer_key, domain(DomainType::Randao)).expect("can sign");
+        *body.randao_reveal_mut() = randao_reveal;
+        // an empty sync aggregate carries the signature of no participants
+        body.sync_aggregate_mut().expect("has a sync aggregate").sync_committee_signature =
+            BlsSignature::infinity();
+
+        let block = self.executor.produce_block(slot, body).expect("block is valid");
+        let signature = sign_with_domain(&block, &proposer_key, domain(DomainType::BeaconProposer))
+            .expect("can sign");
+        match block {
+            BeaconBlock::Altair(message) => {
+                SignedBeaconBlock::Altair(altair::SignedBeaconBlock { message, signature })
+            }
+            BeaconBlock::Bellatrix(message) => {
+                SignedBeaconBlock::Bellatrix(bellatrix::SignedBeaconBlock { message, signature })
+            }
+            BeaconBlock::Capella(message) => {
+                SignedBeaconBlock::Capella(capella::SignedBeaconBlock { message, signature })
+            }
+            BeaconBlock::Deneb(message) => {
+                SignedBeaconBlock::Deneb(deneb::SignedBeaconBlock { message, signature })
+            }
+            BeaconBlock::Electra(message) => {
+                SignedBeaconBlock::Electra(electra::SignedBeaconBlock { message, signature })
+            }
+            _ => unreachable!("the block is in the fork of the state"),
+        }
+    }
+}
+
+/// The sidecars of the blobs `signed_block` commits to, with the blobs from [`blob_bundle`] as
+/// committed to by [`ChainGenerator::next_block`].
+pub fn blob_sidecars(signed_block: &minimal::SignedBeaconBlock) -> Vec<BlobSidecar> {
+    match signed_block {
+        SignedBeaconBlock::Deneb(signed_block) => {
+            let (blobs, _, proofs) =
+                blob_bundle(signed_block.message.body.blob_kzg_commitments.len());
+            blobs
+                .iter()
+                .zip(proofs)
+                .enumerate()
+                .map(|(index, (blob, proof))| {
+                    deneb::compute_blob_sidecar(signed_block, index, blob.clone(), proof.clone())
+                        .expect("can compute sidecar")
+                })
+                .collect()
+        }
+        // the sidecar container is unchanged, but the proof is against the Electra block body
+        SignedBeaconBlock::Electra(signed_block) => {
+            let block = &signed_block.message;
+            let (blobs, _, proofs) = blob_bundle(block.body.blob_kzg_commitments.len());
+            let header = BeaconBlockHeader {
+                slot: block.slot,
+                proposer_index: block.proposer_index,
+                parent_root: block.parent_root,
+                state_root: block.state_root,
+                body_root: block.body.hash_tree_root().expect("can merkleize"),
+            };
+            blobs
+                .iter()
+                .zip(proofs)
+                .enumerate()
+                .map(|(index, (blob, proof))| {
+                    let path = &["blob_kzg_commitments".into(), index.into()];
+                    let (inclusion_proof, _) = block.body.prove(path).expect("can prove");
+                    BlobSidecar {
+                        index,
+                        blob: blob.clone(),
+                        kzg_commitment: block.body.blob_kzg_commitments[index].clone(),
+                        kzg_proof: proof.clone(),
+                        signed_block_header: SignedBeaconBlockHeader {
+                            message: header.clone(),
+                            signature: signed_block.signature.clone(),
+                        },
+                        kzg_commitment_inclusion_proof: Vector::try_from(inclusion_proof.branch)
+                            .expect("correct depth"),
+                    }
+                })
+                .collect()
+        }
+        block => panic!("chains are only generated from Deneb, not {:?}", block.version()),
+    }
+}
+
+// The data of the attestation of each committee of the slot before the slot of `state`, along with
+// the committee.
+fn committee_attestations(
+    state: &minimal::BeaconState,
+    context: &Context,
+) -> Vec<(AttestationData, Vec<ValidatorIndex>)> {
+    let slot = state.slot() - 1;
+    let epoch = compute_epoch_at_slot(slot, context);
+    let block_root_at_slot =
+        |slot: Slot| state.block_roots()[(slot % SLOTS_PER_HISTORICAL_ROOT as Slot) as usize];
+    let source = if epoch == compute_epoch_at_slot(state.slot(), context) {
+        state.current_justified_checkpoint()
+    } else {
+        state.previous_justified_checkpoint()
+    };
+    let target =
+        Checkpoint { epoch, root: block_root_at_slot(compute_start_slot_at_epoch(epoch, context)) };
+    let shuffling = compute_shuffling(state, epoch, context);
+    (0..shuffling.committee_count_per_slot(context))
+        .map(|index| {
+            let data = AttestationData {
+                slot,
+                index,
+                beacon_block_root: block_root_at_slot(slot),
+                source: source.clone(),
+                target: target.clone(),
+            };
+            let committee = shuffling
+                .beacon_committee(slot, index, context)
+                .expect("committee is in range")
+                .to_vec();
+            (data, committee)
+        })
+        .collect()
+}
+
+fn proposer_index(state: &minimal::BeaconState, context: &Context) -> ValidatorIndex {
+    match state {
+        BeaconState::Altair(state) => altair::get_beacon_proposer_index(state, context),
+        BeaconState::Bellatrix(state) => bellatrix::get_beacon_proposer_index(state, context),
+        BeaconState::Capella(state) => capella::get_beacon_proposer_index(state, context),
+        BeaconState::Deneb(state) => deneb::get_beacon_proposer_index(state, context),
+        BeaconState::Electra(state) => electra::get_beacon_proposer_index(state, context),
+        state => panic!("chains are only generated from Altair, not {:?}", state.version()),
+    }
+    .expect("has a proposer")
+}
+
+// The domain of `domain_type` at `epoch`, following `get_domain`.
+fn domain_at_epoch(
+    state: &minimal::BeaconState,
+    domain_type: DomainType,
+    epoch: Epoch,
+    context: &Context,
+) -> Domain {
+    let fork = state.fork();
+    let fork_version =
+        if epoch < fork.epoch { fork.previous_version } else { fork.current_version };
+    compute_domain(domain_type, Some(fork_version), Some(state.genesis_validators_root()), context)
+        .expect("can compute domain")
+}
+
+// Two conflicting headers of `proposer_index` for the slot before the slot of `state`.
+fn proposer_slashing(
+    state: &minimal::BeaconState,
+    proposer_index: ValidatorIndex,
+    context: &Context,
+) -> ProposerSlashing {
+    let header = BeaconBlockHeader { slot: state.slot() - 1, proposer_index, ..Default::default() };
+    let conflicting_header = BeaconBlockHeader {
+        body_root: Root::try_from(&[1u8; 32][..]).expect("correct length"),
+        ..header.clone()
+    };
+    let epoch = compute_epoch_at_slot(header.slot, context);
+    let domain = domain_at_epoch(state, DomainType::BeaconProposer, epoch, context);
+    let secret_key = interop_secret_key(proposer_index);
+    let sign = |message: BeaconBlockHeader| {
+        let signature = sign_with_domain(&message, &secret_key, domain).expect("can sign");
+        SignedBeaconBlockHeader { message, signature }
+    };
+    ProposerSlashing { signed_header_1: sign(header), signed_header_2: sign(conflicting_header) }
+}
+
+// Two conflicting votes of `attesters` for the slot before the slot of `state`, if any, each with
+// the aggregate signature of `attesters`.
+fn double_votes(
+    state: &minimal::BeaconState,
+    attesters: &[ValidatorIndex],
+    context: &Context,
+) -> Vec<(AttestationData, BlsSignature)> {
+    if attesters.is_empty() {
+        return vec![]
+    }
+    let slot = state.slot() - 1;
+    let data = AttestationData {
+        slot,
+        target: Checkpoint { epoch: compute_epoch_at_slot(slot, context), ..Default::default() },
+        ..Default::default()
+    };
+    let conflicting_data = AttestationData {
+        beacon_block_root: Root::try_from(&[1u8; 32][..]).expect("correct length"),
+        ..data.clone()
+    };
+    [data, conflicting_data]
+        .into_iter()
+        .map(|data| {
+            let signature = sign_attestation_data(state, &data, attesters, context);
+            (data, signature)
+        })
+        .collect()
+}
+
+// Sign `data` for the fork of its target epoch with the keys of `signers`, aggregated.
+fn sign_attestation_data(
+    state: &minimal::BeaconState,
+    data: &AttestationData,
+    signers: &[ValidatorIndex],
+    context: &Context,
+) -> BlsSignature {
+    let domain = domain_at_epoch(state, DomainType::BeaconAttester, data.target.epoch, context);
+    let signatures = signers
+        .iter()
+        .map(|&index| sign_with_domain(data, &interop_secret_key(index), domain).expect("can sign"))
+        .collect::<Vec<_>>();
+    aggregate(&signatures).expect("can aggregate")
+}
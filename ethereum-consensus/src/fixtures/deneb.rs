@@ -0,0 +1,169 @@
+//! Deneb fixtures made by the [`chain`] generator, and the blobs committed to in generated blocks.
+use crate::{
+    deneb::{
+        minimal::{self, Blob, BlobSidecar, BYTES_PER_BLOB, MAX_BLOBS_PER_BLOCK},
+        polynomial_commitments::{KzgCommitment, KzgProof},
+    },
+    fixtures::{
+        chain::{self, BlockOperations, ChainGenerator},
+        VALIDATOR_COUNT,
+    },
+    primitives::{ExecutionAddress, Slot, FAR_FUTURE_EPOCH},
+    state_transition::Context,
+    test_utils::{random_blob_bundle, BlobBundle},
+    types::SignedBeaconBlock,
+};
+use std::sync::OnceLock;
+
+/// The slot of the block returned by [`signed_block`].
+pub const BLOCK_SLOT: Slot = 1;
+
+/// The number of blobs committed to by the block returned by [`signed_block`].
+pub const BLOB_COUNT: usize = 2;
+
+/// The seed of the blobs returned by [`blob_bundle`].
+pub const BLOB_BUNDLE_SEED: u64 = 0;
+
+/// The first `blob_count` blobs (at most `MAX_BLOBS_PER_BLOCK`) of a bundle made by
+/// [`random_blob_bundle`] from [`BLOB_BUNDLE_SEED`], with their commitments and proofs.
+///
+/// The bundle is made once per process, so only the first caller pays for computing the proofs.
+pub fn blob_bundle(
+    blob_count: usize,
+) -> (&'static [Blob], &'static [KzgCommitment], &'static [KzgProof]) {
+    static BUNDLE: OnceLock<BlobBundle<BYTES_PER_BLOB>> = OnceLock::new();
+    let (blobs, commitments, proofs) = BUNDLE.get_or_init(|| {
+        let context = Context::for_minimal();
+        random_blob_bundle(BLOB_BUNDLE_SEED, MAX_BLOBS_PER_BLOCK, &context.kzg_settings)
+            .expect("can compute proofs")
+    });
+    (&blobs[..blob_count], &commitments[..blob_count], &proofs[..blob_count])
+}
+
+/// The context the Deneb fixtures are valid under: the context of the [`chain`] generator without
+/// the upgrade to Electra, where validators can exit from genesis.
+pub fn context() -> Context {
+    let mut context = chain::context();
+    context.electra_fork_epoch = FAR_FUTURE_EPOCH;
+    context.shard_committee_period = 0;
+    context
+}
+
+/// A genesis state of [`VALIDATOR_COUNT`] interop validators, made from their deposits.
+pub fn genesis_state() -> minimal::BeaconState {
+    fixtures().genesis_state.clone()
+}
+
+/// A block on top of [`genesis_state`] at [`BLOCK_SLOT`] carrying every operation but deposits,
+/// and committing to [`BLOB_COUNT`] blobs.
+///
+/// Deposits are left empty as the genesis state has processed every deposit it knows of, and
+/// learning of new ones takes a majority of the votes of an eth1 voting period.
+pub fn signed_block() -> minimal::SignedBeaconBlock {
+    fixtures().signed_block.clone()
+}
+
+/// The sidecars of the blobs committed to by [`signed_block`].
+pub fn blob_sidecars() -> Vec<BlobSidecar> {
+    fixtures().blob_sidecars.clone()
+}
+
+struct Fixtures {
+    genesis_state: minimal::BeaconState,
+    signed_block: minimal::SignedBeaconBlock,
+    blob_sidecars: Vec<BlobSidecar>,
+}
+
+// The fixtures are made once per process, as they are signed and validated on the way.
+fn fixtures() -> &'static Fixtures {
+    static FIXTURES: OnceLock<Fixtures> = OnceLock::new();
+    FIXTURES.get_or_init(|| {
+        let context = context();
+        let genesis_state = chain::genesis_state(VALIDATOR_COUNT, &context);
+        let mut generator = ChainGenerator::new(genesis_state.clone(), context);
+
+        // each operation is for a different validator, none of them the proposer
+        let proposer_index = generator.next_proposer_index();
+        let mut validators = (0..VALIDATOR_COUNT).filter(|&index| index != proposer_index);
+        let mut next_validator = || validators.next().expect("enough validators");
+        let address = ExecutionAddress::try_from([0x42u8; 20].as_ref()).expect("correct length");
+        let operations = BlockOperations {
+            slashed_proposers: vec![next_validator()],
+            slashed_attesters: vec![next_validator(), next_validator()],
+            voluntary_exits: vec![generator.signed_voluntary_exit(next_validator())],
+            bls_to_execution_changes: vec![
+                generator.signed_bls_to_execution_change(next_validator(), address)
+            ],
+            blob_count: BLOB_COUNT,
+            ..Default::default()
+        };
+        let signed_block = generator.next_block(operations);
+        let blob_sidecars = chain::blob_sidecars(&signed_block);
+        let SignedBeaconBlock::Deneb(signed_block) = signed_block else {
+            unreachable!("the block is in the fork of the genesis state")
+        };
+        Fixtures {
+            genesis_state: genesis_state.as_deneb().expect("genesis is in Deneb").clone(),
+            signed_block,
+            blob_sidecars,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        deneb::{
+            polynomial_commitments::verify_blob_kzg_proof_batch,
+            verify_blob_sidecar_inclusion_proof,
+        },
+        state_transition::minimal::Executor,
+        test_utils::random_valid_blob,
+        types::BeaconState,
+    };
+
+    #[test]
+    fn test_fixtures_are_valid() {
+        let context = context();
+        let signed_block = signed_block();
+        let body = &signed_block.message.body;
+        assert_eq!(signed_block.message.slot, BLOCK_SLOT);
+        assert!(!body.proposer_slashings.is_empty());
+        assert!(!body.attester_slashings.is_empty());
+        assert!(!body.attestations.is_empty());
+        assert!(!body.voluntary_exits.is_empty());
+        assert!(!body.bls_to_execution_changes.is_empty());
+        assert_eq!(body.blob_kzg_commitments.len(), BLOB_COUNT);
+
+        let mut executor = Executor::new(BeaconState::Deneb(genesis_state()), context.clone());
+        executor.apply_block(&SignedBeaconBlock::Deneb(signed_block.clone())).unwrap();
+
+        let sidecars = blob_sidecars();
+        assert_eq!(sidecars.len(), BLOB_COUNT);
+        for (index, sidecar) in sidecars.iter().enumerate() {
+            assert_eq!(sidecar.kzg_commitment, body.blob_kzg_commitments[index]);
+            verify_blob_sidecar_inclusion_proof(sidecar, &context).unwrap();
+        }
+        let blobs = sidecars.iter().map(|sidecar| sidecar.blob.clone()).collect::<Vec<_>>();
+        let proofs = sidecars.iter().map(|sidecar| sidecar.kzg_proof.clone()).collect::<Vec<_>>();
+        verify_blob_kzg_proof_batch(
+            &blobs,
+            &body.blob_kzg_commitments,
+            &proofs,
+            &context.kzg_settings,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_blob_bundle() {
+        let context = Context::for_minimal();
+        let (blobs, commitments, proofs) = blob_bundle(MAX_BLOBS_PER_BLOCK);
+        verify_blob_kzg_proof_batch(blobs, commitments, proofs, &context.kzg_settings).unwrap();
+        assert_eq!(blobs[1], random_valid_blob(BLOB_BUNDLE_SEED + 1));
+
+        let (blobs, commitments, proofs) = blob_bundle(2);
+        assert_eq!((blobs.len(), commitments.len(), proofs.len()), (2, 2, 2));
+    }
+}
@@ -0,0 +1,179 @@
+//! Starter bundles for the `determinism` harness: a short chain of empty blocks in each fork with
+//! polymorphic types, made with `Executor::produce_block`.
+use crate::{
+    altair, bellatrix, capella, deneb,
+    determinism::Bundle,
+    electra,
+    fixtures::{phase0::genesis_state, secret_keys},
+    phase0::{self, compute_domain, compute_epoch_at_slot},
+    primitives::{BlsSignature, DomainType, Slot},
+    signing::sign_with_domain,
+    state_transition::{minimal::Executor, Context},
+    types::{minimal, BeaconBlock, BeaconState, SignedBeaconBlock},
+    Fork,
+};
+
+/// Number of blocks in each starter bundle.
+pub const BLOCKS_PER_BUNDLE: usize = 2;
+
+/// The context the starter bundles are made and replayed under: the `minimal` preset with each
+/// fork scheduled one epoch after the last.
+pub fn context() -> Context {
+    let mut context = Context::for_minimal();
+    context.altair_fork_epoch = 1;
+    context.bellatrix_fork_epoch = 2;
+    context.capella_fork_epoch = 3;
+    context.deneb_fork_epoch = 4;
+    context.electra_fork_epoch = 5;
+    context
+}
+
+/// A bundle for each fork from phase0 to electra, of [`BLOCKS_PER_BUNDLE`] blocks from the first
+/// slot of the fork (or the slot after genesis).
+///
+/// The pre-state of each bundle after phase0 is in the last slot of the previous fork, so the
+/// bundle also covers the upgrade to its fork.
+pub fn starter_bundles() -> Vec<(Fork, Bundle)> {
+    let context = context();
+    let mut executor = Executor::new(BeaconState::Phase0(genesis_state()), context.clone());
+    let forks = [
+        (Fork::Phase0, 0),
+        (Fork::Altair, context.altair_fork_epoch),
+        (Fork::Bellatrix, context.bellatrix_fork_epoch),
+        (Fork::Capella, context.capella_fork_epoch),
+        (Fork::Deneb, context.deneb_fork_epoch),
+        (Fork::Electra, context.electra_fork_epoch),
+    ];
+    forks
+        .into_iter()
+        .map(|(fork, epoch)| {
+            let start_slot = (epoch * context.slots_per_epoch).max(1);
+            if executor.state.slot() < start_slot - 1 {
+                executor.process_slots(start_slot - 1).expect("can advance slots");
+            }
+            let pre_state = executor.state.inner().clone();
+            let blocks = (start_slot..start_slot + BLOCKS_PER_BUNDLE as Slot)
+                .map(|slot| {
+                    let block = signed_empty_block(&executor, slot);
+                    executor.apply_block(&block).expect("block is valid");
+                    block
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(executor.state.version(), fork);
+            let bundle = Bundle::generate(&pre_state, &blocks, &context).expect("can generate");
+            (fork, bundle)
+        })
+        .collect()
+}
+
+// A signed block at `slot`, the slot after the executor's state, with only the fields required
+// for the block to be valid in its fork.
+fn signed_empty_block(executor: &Executor, slot: Slot) -> minimal::SignedBeaconBlock {
+    let context = &executor.context;
+    let state = executor.precompute_next_slot().expect("can advance slots").state;
+    let epoch = compute_epoch_at_slot(slot, context);
+    let proposer_index = match &state {
+        BeaconState::Phase0(state) => phase0::get_beacon_proposer_index(state, context),
+        BeaconState::Altair(state) => altair::get_beacon_proposer_index(state, context),
+        BeaconState::Bellatrix(state) => bellatrix::get_beacon_proposer_index(state, context),
+        BeaconState::Capella(state) => capella::get_beacon_proposer_index(state, context),
+        BeaconState::Deneb(state) => deneb::get_beacon_proposer_index(state, context),
+        BeaconState::Electra(state) => electra::get_beacon_proposer_index(state, context),
+    }
+    .expect("has a proposer");
+    let secret_keys = secret_keys();
+    let proposer_key = &secret_keys[proposer_index];
+    // the state is in the fork of the block, so its current version signs for every domain
+    let domain = |domain_type| {
+        compute_domain(
+            domain_type,
+            Some(state.fork().current_version),
+            Some(state.genesis_validators_root()),
+            context,
+        )
+        .expect("can compute domain")
+    };
+    let randao_reveal =
+        sign_with_domain(&epoch, proposer_key, domain(DomainType::Randao)).expect("can sign");
+    // an empty sync aggregate carries the signature of no participants
+    let sync_committee_signature = BlsSignature::infinity();
+
+    let body = match &state {
+        BeaconState::Phase0(_) => {
+            let mut body = phase0::minimal::BeaconBlockBody::default();
+            body.randao_reveal = randao_reveal;
+            minimal::BeaconBlockBody::Phase0(body)
+        }
+        BeaconState::Altair(_) => {
+            let mut body = altair::minimal::BeaconBlockBody::default();
+            body.randao_reveal = randao_reveal;
+            body.sync_aggregate.sync_committee_signature = sync_committee_signature;
+            minimal::BeaconBlockBody::Altair(body)
+        }
+        // the merge has not happened, so the execution payload can stay empty
+        BeaconState::Bellatrix(_) => {
+            let mut body = bellatrix::minimal::BeaconBlockBody::default();
+            body.randao_reveal = randao_reveal;
+            body.sync_aggregate.sync_committee_signature = sync_committee_signature;
+            minimal::BeaconBlockBody::Bellatrix(body)
+        }
+        // from capella, the execution payload is processed whether or not the merge happened
+        BeaconState::Capella(state) => {
+            let mut body = capella::minimal::BeaconBlockBody::default();
+            body.randao_reveal = randao_reveal;
+            body.sync_aggregate.sync_committee_signature = sync_committee_signature;
+            let payload = &mut body.execution_payload;
+            payload.parent_hash = state.latest_execution_payload_header.block_hash.clone();
+            payload.prev_randao = capella::get_randao_mix(state, epoch).clone();
+            payload.timestamp =
+                capella::compute_timestamp_at_slot(state, slot, context).expect("valid slot");
+            minimal::BeaconBlockBody::Capella(body)
+        }
+        BeaconState::Deneb(state) => {
+            let mut body = deneb::minimal::BeaconBlockBody::default();
+            body.randao_reveal = randao_reveal;
+            body.sync_aggregate.sync_committee_signature = sync_committee_signature;
+            let payload = &mut body.execution_payload;
+            payload.parent_hash = state.latest_execution_payload_header.block_hash.clone();
+            payload.prev_randao = deneb::get_randao_mix(state, epoch).clone();
+            payload.timestamp =
+                deneb::compute_timestamp_at_slot(state, slot, context).expect("valid slot");
+            minimal::BeaconBlockBody::Deneb(body)
+        }
+        BeaconState::Electra(state) => {
+            let mut body = electra::minimal::BeaconBlockBody::default();
+            body.randao_reveal = randao_reveal;
+            body.sync_aggregate.sync_committee_signature = sync_committee_signature;
+            let payload = &mut body.execution_payload;
+            payload.parent_hash = state.latest_execution_payload_header.block_hash.clone();
+            payload.prev_randao = electra::get_randao_mix(state, epoch).clone();
+            payload.timestamp =
+                electra::compute_timestamp_at_slot(state, slot, context).expect("valid slot");
+            minimal::BeaconBlockBody::Electra(body)
+        }
+    };
+
+    let block = executor.produce_block(slot, body).expect("block is valid");
+    let signature = sign_with_domain(&block, proposer_key, domain(DomainType::BeaconProposer))
+        .expect("can sign");
+    match block {
+        BeaconBlock::Phase0(message) => {
+            SignedBeaconBlock::Phase0(phase0::SignedBeaconBlock { message, signature })
+        }
+        BeaconBlock::Altair(message) => {
+            SignedBeaconBlock::Altair(altair::SignedBeaconBlock { message, signature })
+        }
+        BeaconBlock::Bellatrix(message) => {
+            SignedBeaconBlock::Bellatrix(bellatrix::SignedBeaconBlock { message, signature })
+        }
+        BeaconBlock::Capella(message) => {
+            SignedBeaconBlock::Capella(capella::SignedBeaconBlock { message, signature })
+        }
+        BeaconBlock::Deneb(message) => {
+            SignedBeaconBlock::Deneb(deneb::SignedBeaconBlock { message, signature })
+        }
+        BeaconBlock::Electra(message) => {
+            SignedBeaconBlock::Electra(electra::SignedBeaconBlock { message, signature })
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! Electra fixtures made by the [`chain`] generator.
+use crate::{
+    electra::{
+        minimal::{self, BlobSidecar},
+        ConsolidationRequest, WithdrawalRequest, FULL_EXIT_REQUEST_AMOUNT,
+    },
+    fixtures::{
+        chain::{self, BlockOperations, ChainGenerator},
+        VALIDATOR_COUNT,
+    },
+    interop::interop_secret_key,
+    primitives::{ExecutionAddress, Slot, ValidatorIndex},
+    state_transition::Context,
+    types::SignedBeaconBlock,
+};
+use std::sync::OnceLock;
+
+/// The slot of the block returned by [`signed_block`].
+pub const BLOCK_SLOT: Slot = 1;
+
+/// The number of blobs committed to by the block returned by [`signed_block`].
+pub const BLOB_COUNT: usize = 2;
+
+/// The validator exiting through the withdrawal request of [`signed_block`].
+pub const WITHDRAWAL_REQUEST_VALIDATOR: ValidatorIndex = 0;
+
+/// The validator switching to compounding credentials through the consolidation request of
+/// [`signed_block`].
+pub const CONSOLIDATION_REQUEST_VALIDATOR: ValidatorIndex = 1;
+
+/// The context the Electra fixtures are valid under: the context of the [`chain`] generator with
+/// Electra from genesis, where validators can exit from genesis.
+pub fn context() -> Context {
+    let mut context = chain::context();
+    context.electra_fork_epoch = 0;
+    context.shard_committee_period = 0;
+    context
+}
+
+/// A genesis state of [`VALIDATOR_COUNT`] interop validators, added to the registry directly.
+pub fn genesis_state() -> minimal::BeaconState {
+    fixtures().genesis_state.clone()
+}
+
+/// A block on top of [`genesis_state`] at [`BLOCK_SLOT`] carrying every operation but deposits
+/// from the deposit contract, committing to [`BLOB_COUNT`] blobs and with one execution request of
+/// each type.
+///
+/// The requests take effect: the deposit is for the next interop validator, and the withdrawal
+/// and consolidation requests are from the address [`WITHDRAWAL_REQUEST_VALIDATOR`] and
+/// [`CONSOLIDATION_REQUEST_VALIDATOR`] change their credentials to in the same block.
+pub fn signed_block() -> minimal::SignedBeaconBlock {
+    fixtures().signed_block.clone()
+}
+
+/// The sidecars of the blobs committed to by [`signed_block`].
+pub fn blob_sidecars() -> Vec<BlobSidecar> {
+    fixtures().blob_sidecars.clone()
+}
+
+struct Fixtures {
+    genesis_state: minimal::BeaconState,
+    signed_block: minimal::SignedBeaconBlock,
+    blob_sidecars: Vec<BlobSidecar>,
+}
+
+// The fixtures are made once per process, as they are signed and validated on the way.
+fn fixtures() -> &'static Fixtures {
+    static FIXTURES: OnceLock<Fixtures> = OnceLock::new();
+    FIXTURES.get_or_init(|| {
+        let context = context();
+        let genesis_state = chain::electra_genesis_state(VALIDATOR_COUNT, &context);
+        let mut generator = ChainGenerator::new(genesis_state.clone(), context.clone());
+
+        let address = ExecutionAddress::try_from([0x42u8; 20].as_ref()).expect("correct length");
+        let mut operations = BlockOperations { blob_count: BLOB_COUNT, ..Default::default() };
+        for index in [WITHDRAWAL_REQUEST_VALIDATOR, CONSOLIDATION_REQUEST_VALIDATOR] {
+            let change = generator.signed_bls_to_execution_change(index, address.clone());
+            operations.bls_to_execution_changes.push(change);
+        }
+        let requests = &mut operations.execution_requests;
+        let deposit_request = generator.deposit_request(
+            VALIDATOR_COUNT,
+            context.min_activation_balance,
+            VALIDATOR_COUNT as u64,
+        );
+        requests.deposits.push(deposit_request);
+        requests.withdrawals.push(WithdrawalRequest {
+            source_address: address.clone(),
+            validator_public_key: interop_secret_key(WITHDRAWAL_REQUEST_VALIDATOR).public_key(),
+            amount: FULL_EXIT_REQUEST_AMOUNT,
+        });
+        // a consolidation into the source itself switches it to compounding credentials
+        let public_key = interop_secret_key(CONSOLIDATION_REQUEST_VALIDATOR).public_key();
+        requests.consolidations.push(ConsolidationRequest {
+            source_address: address,
+            source_public_key: public_key.clone(),
+            target_public_key: public_key,
+        });
+
+        // the other operations are for other validators, none of them the proposer
+        let proposer_index = generator.next_proposer_index();
+        let mut validators = (CONSOLIDATION_REQUEST_VALIDATOR + 1..VALIDATOR_COUNT)
+            .filter(|&index| index != proposer_index);
+        let mut next_validator = || validators.next().expect("enough validators");
+        operations.slashed_proposers.push(next_validator());
+        operations.slashed_attesters.extend([next_validator(), next_validator()]);
+        operations.voluntary_exits.push(generator.signed_voluntary_exit(next_validator()));
+
+        let signed_block = generator.next_block(operations);
+        let blob_sidecars = chain::blob_sidecars(&signed_block);
+        let SignedBeaconBlock::Electra(signed_block) = signed_block else {
+            unreachable!("the block is in the fork of the genesis state")
+        };
+        Fixtures {
+            genesis_state: genesis_state.as_electra().expect("genesis is in Electra").clone(),
+            signed_block,
+            blob_sidecars,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        deneb::{
+            polynomial_commitments::verify_blob_kzg_proof_batch,
+            verify_blob_sidecar_inclusion_proof,
+        },
+        primitives::{COMPOUNDING_WITHDRAWAL_PREFIX, FAR_FUTURE_EPOCH},
+        state_transition::minimal::Executor,
+        types::BeaconState,
+    };
+
+    #[test]
+    fn test_fixtures_are_valid() {
+        let context = context();
+        let signed_block = signed_block();
+        let body = &signed_block.message.body;
+        assert_eq!(signed_block.message.slot, BLOCK_SLOT);
+        assert!(!body.proposer_slashings.is_empty());
+        assert!(!body.attester_slashings.is_empty());
+        assert!(!body.attestations.is_empty());
+        assert!(!body.voluntary_exits.is_empty());
+        assert!(!body.bls_to_execution_changes.is_empty());
+        assert!(!body.execution_requests.deposits.is_empty());
+        assert!(!body.execution_requests.withdrawals.is_empty());
+        assert!(!body.execution_requests.consolidations.is_empty());
+        assert_eq!(body.blob_kzg_commitments.len(), BLOB_COUNT);
+
+        let mut executor = Executor::new(BeaconState::Electra(genesis_state()), context.clone());
+        executor.apply_block(&SignedBeaconBlock::Electra(signed_block.clone())).unwrap();
+        let state = executor.state.inner().as_electra().unwrap();
+        let public_key = interop_secret_key(VALIDATOR_COUNT).public_key();
+        assert!(state.pending_deposits.iter().any(|deposit| deposit.public_key == public_key));
+        assert_ne!(state.validators[WITHDRAWAL_REQUEST_VALIDATOR].exit_epoch, FAR_FUTURE_EPOCH);
+        assert_eq!(
+            state.validators[CONSOLIDATION_REQUEST_VALIDATOR].withdrawal_credentials[0],
+            COMPOUNDING_WITHDRAWAL_PREFIX
+        );
+
+        let sidecars = blob_sidecars();
+        assert_eq!(sidecars.len(), BLOB_COUNT);
+        for (index, sidecar) in sidecars.iter().enumerate() {
+            assert_eq!(sidecar.kzg_commitment, body.blob_kzg_commitments[index]);
+            verify_blob_sidecar_inclusion_proof(sidecar, &context).unwrap();
+        }
+        let blobs = sidecars.iter().map(|sidecar| sidecar.blob.clone()).collect::<Vec<_>>();
+        let proofs = sidecars.iter().map(|sidecar| sidecar.kzg_proof.clone()).collect::<Vec<_>>();
+        verify_blob_kzg_proof_batch(
+            &blobs,
+            &body.blob_kzg_commitments,
+            &proofs,
+            &context.kzg_settings,
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,30 @@
+//! Small, valid and internally consistent objects for use in tests, under the `minimal` preset.
+//!
+//! Fixtures are built on demand from deterministic keys rather than shipped as encoded bytes, so
+//! they always reflect the current state transition. This crate's own tests apply every fixture
+//! block with full validation.
+//!
+//! Each of [`phase0`], [`altair`], [`bellatrix`], [`capella`], [`deneb`] and [`electra`] provides
+//! a genesis state and a block on top of it carrying operations of every type the fork can include
+//! from genesis. The fixtures from Altair on are made by the [`chain`] generator from the interop
+//! validators, and the Deneb and Electra ones come with the sidecars of the blobs of the block.
+pub mod altair;
+pub mod bellatrix;
+pub mod capella;
+pub mod chain;
+pub mod deneb;
+pub mod determinism;
+pub mod electra;
+pub mod phase0;
+
+use crate::crypto::SecretKey;
+
+/// Number of validators in the genesis state of each fixture chain.
+pub const VALIDATOR_COUNT: usize = 64;
+
+/// The secret keys backing the validators of each fixture chain, indexed by validator index.
+pub fn secret_keys() -> Vec<SecretKey> {
+    (0..VALIDATOR_COUNT as u8)
+        .map(|i| SecretKey::key_gen(&[i + 1; 32]).expect("valid key material"))
+        .collect()
+}
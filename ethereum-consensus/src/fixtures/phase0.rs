@@ -0,0 +1,225 @@
+use crate::{
+    crypto::{aggregate, hash, SecretKey},
+    fixtures::{secret_keys, VALIDATOR_COUNT},
+    phase0::{
+        compute_epoch_at_slot, get_beacon_committee, get_beacon_proposer_index, get_block_root,
+        get_block_root_at_slot, get_domain, minimal, process_block, process_slots, AttestationData,
+        BeaconBlockHeader, Checkpoint, Eth1Data, Fork, ProposerSlashing, SignedBeaconBlockHeader,
+        Validator,
+    },
+    primitives::{
+        BlsSignature, Bytes32, DomainType, Root, Slot, ValidatorIndex, BLS_WITHDRAWAL_PREFIX,
+        FAR_FUTURE_EPOCH, GENESIS_EPOCH,
+    },
+    signing::sign_with_domain,
+    ssz::prelude::*,
+    state_transition::Context,
+};
+
+/// The slot of the block returned by [`signed_block`].
+pub const BLOCK_SLOT: Slot = 2;
+
+/// A genesis state with [`VALIDATOR_COUNT`] active validators.
+pub fn genesis_state() -> minimal::BeaconState {
+    let context = Context::for_minimal();
+    let mut state = minimal::BeaconState {
+        genesis_time: context.min_genesis_time,
+        fork: Fork {
+            previous_version: context.genesis_fork_version,
+            current_version: context.genesis_fork_version,
+            epoch: GENESIS_EPOCH,
+        },
+        latest_block_header: BeaconBlockHeader {
+            body_root: minimal::BeaconBlockBody::default().hash_tree_root().expect("can merkleize"),
+            ..Default::default()
+        },
+        eth1_data: Eth1Data { deposit_count: VALIDATOR_COUNT as u64, ..Default::default() },
+        eth1_deposit_index: VALIDATOR_COUNT as u64,
+        ..Default::default()
+    };
+    for secret_key in secret_keys() {
+        let public_key = secret_key.public_key();
+        let mut withdrawal_credentials = hash(public_key.as_slice()).as_slice().to_vec();
+        withdrawal_credentials[0] = BLS_WITHDRAWAL_PREFIX;
+        let withdrawal_credentials =
+            Bytes32::try_from(withdrawal_credentials.as_slice()).expect("correct length");
+        state.validators.push(Validator {
+            public_key,
+            withdrawal_credentials,
+            effective_balance: context.max_effective_balance,
+            activation_eligibility_epoch: GENESIS_EPOCH,
+            activation_epoch: GENESIS_EPOCH,
+            exit_epoch: FAR_FUTURE_EPOCH,
+            withdrawable_epoch: FAR_FUTURE_EPOCH,
+            ..Default::default()
+        });
+        state.balances.push(context.max_effective_balance);
+    }
+    state.genesis_validators_root = state.validators.hash_tree_root().expect("can merkleize");
+    state
+}
+
+/// A genesis state with only the first `count` validators of [`genesis_state`], for exercising
+/// degenerate validator sets.
+pub fn genesis_state_with_validators(count: usize) -> minimal::BeaconState {
+    let mut state = genesis_state();
+    state.validators = List::try_from(state.validators[..count].to_vec()).expect("within bound");
+    state.balances = List::try_from(state.balances[..count].to_vec()).expect("within bound");
+    state.eth1_data.deposit_count = count as u64;
+    state.eth1_deposit_index = count as u64;
+    state.genesis_validators_root = state.validators.hash_tree_root().expect("can merkleize");
+    state
+}
+
+fn signed_header(
+    state: &minimal::BeaconState,
+    header: BeaconBlockHeader,
+    secret_key: &SecretKey,
+    context: &Context,
+) -> SignedBeaconBlockHeader {
+    let epoch = compute_epoch_at_slot(header.slot, context);
+    let domain = get_domain(state, DomainType::BeaconProposer, Some(epoch), context)
+        .expect("can compute domain");
+    let signature = sign_with_domain(&header, secret_key, domain).expect("can sign");
+    SignedBeaconBlockHeader { message: header, signature }
+}
+
+fn sign_attestation_data(
+    state: &minimal::BeaconState,
+    data: &AttestationData,
+    signers: &[ValidatorIndex],
+    secret_keys: &[SecretKey],
+    context: &Context,
+) -> BlsSignature {
+    let domain = get_domain(state, DomainType::BeaconAttester, Some(data.target.epoch), context)
+        .expect("can compute domain");
+    let signatures = signers
+        .iter()
+        .map(|&index| sign_with_domain(data, &secret_keys[index], domain).expect("can sign"))
+        .collect::<Vec<_>>();
+    aggregate(&signatures).expect("can aggregate")
+}
+
+/// A block on top of [`genesis_state`] at [`BLOCK_SLOT`] carrying a proposer slashing, an
+/// attester slashing and an attestation.
+///
+/// Deposits and voluntary exits are left empty: the genesis state has no pending deposits and
+/// its validators are too young to exit.
+pub fn signed_block() -> minimal::SignedBeaconBlock {
+    let context = Context::for_minimal();
+    let secret_keys = secret_keys();
+    let mut state = genesis_state();
+    process_slots(&mut state, BLOCK_SLOT, &context).expect("can advance slots");
+
+    let proposer_index = get_beacon_proposer_index(&state, &context).expect("has a proposer");
+    let epoch = compute_epoch_at_slot(BLOCK_SLOT, &context);
+    let mut block = minimal::SignedBeaconBlock::default();
+    block.message.slot = BLOCK_SLOT;
+    block.message.proposer_index = proposer_index;
+    block.message.parent_root = state.latest_block_header.hash_tree_root().expect("can merkleize");
+    let domain =
+        get_domain(&state, DomainType::Randao, Some(epoch), &context).expect("can compute domain");
+    block.message.body.randao_reveal =
+        sign_with_domain(&epoch, &secret_keys[proposer_index], domain).expect("can sign");
+    block.message.body.eth1_data = state.eth1_data.clone();
+
+    let attestation_slot = BLOCK_SLOT - 1;
+    let committee =
+        get_beacon_committee(&state, attestation_slot, 0, &context).expect("has a committee");
+    let data = AttestationData {
+        slot: attestation_slot,
+        index: 0,
+        beacon_block_root: get_block_root_at_slot(&state, attestation_slot)
+            .expect("root is available"),
+        source: state.current_justified_checkpoint.clone(),
+        target: Checkpoint {
+            epoch,
+            root: get_block_root(&state, epoch, &context).expect("root is available"),
+        },
+    };
+    let attestation = minimal::Attestation {
+        aggregation_bits: vec![true; committee.len()].as_slice().try_into().expect("fits"),
+        signature: sign_attestation_data(&state, &data, &committee, &secret_keys, &context),
+        data,
+    };
+    block.message.body.attestations.push(attestation);
+
+    // slash validators from outside of the attesting committee
+    let mut outsiders =
+        (0..VALIDATOR_COUNT).filter(|index| *index != proposer_index && !committee.contains(index));
+
+    let slashed_proposer = outsiders.next().expect("enough validators");
+    let header = BeaconBlockHeader {
+        slot: attestation_slot,
+        proposer_index: slashed_proposer,
+        ..Default::default()
+    };
+    let conflicting_header = BeaconBlockHeader {
+        body_root: Root::try_from(&[1u8; 32][..]).expect("correct length"),
+        ..header.clone()
+    };
+    let proposer_slashing = ProposerSlashing {
+        signed_header_1: signed_header(&state, header, &secret_keys[slashed_proposer], &context),
+        signed_header_2: signed_header(
+            &state,
+            conflicting_header,
+            &secret_keys[slashed_proposer],
+            &context,
+        ),
+    };
+    block.message.body.proposer_slashings.push(proposer_slashing);
+
+    let mut slashed_attesters = outsiders.take(2).collect::<Vec<_>>();
+    slashed_attesters.sort();
+    let data = AttestationData {
+        slot: attestation_slot,
+        target: Checkpoint { epoch, ..Default::default() },
+        ..Default::default()
+    };
+    let conflicting_data = AttestationData {
+        beacon_block_root: Root::try_from(&[1u8; 32][..]).expect("correct length"),
+        ..data.clone()
+    };
+    let indexed_attestation = |data: AttestationData| minimal::IndexedAttestation {
+        attesting_indices: slashed_attesters.clone().try_into().expect("fits"),
+        signature: sign_attestation_data(&state, &data, &slashed_attesters, &secret_keys, &context),
+        data,
+    };
+    let attester_slashing = minimal::AttesterSlashing {
+        attestation_1: indexed_attestation(data),
+        attestation_2: indexed_attestation(conflicting_data),
+    };
+    block.message.body.attester_slashings.push(attester_slashing);
+
+    process_block(&mut state, &block.message, &context).expect("block is valid");
+    block.message.state_root = state.hash_tree_root().expect("can merkleize");
+    let domain =
+        get_domain(&state, DomainType::BeaconProposer, None, &context).expect("can compute domain");
+    block.signature =
+        sign_with_domain(&block.message, &secret_keys[proposer_index], domain).expect("can sign");
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        state_transition::minimal::Executor,
+        types::{BeaconState, SignedBeaconBlock},
+    };
+
+    #[test]
+    fn test_fixtures_are_valid() {
+        let block = signed_block();
+        let body = &block.message.body;
+        assert_eq!(body.proposer_slashings.len(), 1);
+        assert_eq!(body.attester_slashings.len(), 1);
+        assert_eq!(body.attestations.len(), 1);
+
+        let mut executor =
+            Executor::new(BeaconState::Phase0(genesis_state()), Context::for_minimal());
+        executor.apply_block(&SignedBeaconBlock::Phase0(block)).unwrap();
+        let state = executor.state.phase0().unwrap();
+        assert_eq!(state.validators.iter().filter(|validator| validator.slashed).count(), 3);
+    }
+}
@@ -1,7 +1,10 @@
 use std::fmt;
 
 // Identifies the fork of the protocol the associated object belongs to.
-#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+// Forks are ordered by activation.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum Fork {
     Phase0,
@@ -9,6 +12,7 @@ pub enum Fork {
     Bellatrix,
     Capella,
     Deneb,
+    Electra,
 }
 
 impl fmt::Display for Fork {
@@ -19,6 +23,7 @@ impl fmt::Display for Fork {
             Self::Bellatrix => write!(f, "bellatrix"),
             Self::Capella => write!(f, "capella"),
             Self::Deneb => write!(f, "deneb"),
+            Self::Electra => write!(f, "electra"),
         }
     }
 }
@@ -0,0 +1,77 @@
+//! The fork choice of the consensus specs: a [`Store`] of the blocks, states and votes seen so far
+//! from which the head of the chain is selected with LMD-GHOST, bounded by Casper FFG.
+//!
+//! A node drives the store by calling [`Store::on_tick`] as time passes and [`Store::on_block`],
+//! [`Store::on_attestation`] and [`Store::on_attester_slashing`] as objects arrive, reading the
+//! head of the chain with [`Store::get_head`]. [`Store::serialize`] snapshots the store so that
+//! a node can resume from it with [`Store::deserialize`] after a restart.
+mod presets;
+mod snapshot;
+mod store;
+
+pub use store::*;
+
+use crate::primitives::{Epoch, Hash32, Root, Slot};
+use thiserror::Error;
+
+/// The number of intervals a slot is divided into; a block is timely if it arrives in the first.
+pub const INTERVALS_PER_SLOT: u64 = 3;
+
+#[derive(Debug, Error)]
+pub enum ForkChoiceError {
+    #[error("anchor block does not commit to the anchor state")]
+    InvalidAnchor,
+    #[error("block {0} is not in the store")]
+    UnknownBlock(Root),
+    #[error("no state in the store for the checkpoint at epoch {epoch} with root {root}")]
+    UnknownCheckpointState { epoch: Epoch, root: Root },
+    #[error("block at slot {slot} is from the future as the current slot is {current_slot}")]
+    FutureBlock { slot: Slot, current_slot: Slot },
+    #[error("block at slot {slot} is not after the finalized slot {finalized_slot}")]
+    BlockNotAfterFinalized { slot: Slot, finalized_slot: Slot },
+    #[error("block {0} does not descend from the finalized checkpoint")]
+    NotDescendantOfFinalized(Root),
+    #[error("the blobs committed to by block {0} are not available")]
+    DataUnavailable(Root),
+    #[error("proof-of-work block {0} is unknown")]
+    UnknownPowBlock(Hash32),
+    #[error("merge block {0} does not build on a valid terminal proof-of-work block")]
+    InvalidMergeBlock(Root),
+    #[error("attestation targets epoch {target} but the current epoch is {current_epoch}")]
+    StaleAttestationTarget { target: Epoch, current_epoch: Epoch },
+    #[error("attestation targets epoch {target} but its slot is in epoch {slot_epoch}")]
+    AttestationTargetMismatch { target: Epoch, slot_epoch: Epoch },
+    #[error("attestation for slot {slot} votes for block {root} from a later slot")]
+    AttestationForFutureBlock { slot: Slot, root: Root },
+    #[error("attestation targets block {target} which is not an ancestor of its head vote")]
+    InconsistentAttestationTarget { target: Root },
+    #[error("attestation for slot {slot} can not be counted before the next slot")]
+    PrematureAttestation { slot: Slot },
+    #[error("attester slashing is made of attestations that are not slashable")]
+    NotSlashable,
+    #[error("snapshot version {0} is not supported")]
+    UnsupportedSnapshotVersion(u8),
+    #[error("snapshot is malformed")]
+    MalformedSnapshot,
+    #[error("snapshot is inconsistent at block {0}")]
+    InconsistentSnapshot(Root),
+}
+
+/// The latest vote of a validator for the head of the chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatestMessage {
+    pub epoch: Epoch,
+    pub root: Root,
+}
+
+pub mod gnosis {
+    pub use super::presets::gnosis::*;
+}
+
+pub mod mainnet {
+    pub use super::presets::mainnet::*;
+}
+
+pub mod minimal {
+    pub use super::presets::minimal::*;
+}
@@ -0,0 +1,515 @@
+use crate::{
+    bellatrix::fork_choice::PowBlock,
+    fork_choice::{ForkChoiceError, LatestMessage, Store},
+    phase0::{BeaconBlockHeader, Checkpoint},
+    primitives::{IsZero, Root, ValidatorIndex},
+    ssz::prelude::*,
+    state_transition::{Context, Result},
+    storage::{decode_state, encode_state},
+    Error,
+};
+use std::collections::{HashMap, HashSet};
+
+const SNAPSHOT_VERSION: u8 = 0;
+
+// The lengths of the SSZ encodings of the fixed-size containers in a snapshot.
+const BLOCK_HEADER_LEN: usize = 112;
+const CHECKPOINT_LEN: usize = 40;
+const POW_BLOCK_LEN: usize = 96;
+
+impl<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+    >
+    Store<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_PROPOSER_SLASHINGS,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >
+{
+    /// Encode the store so that a node can resume fork choice after a restart without replaying
+    /// the blocks since finalization.
+    ///
+    /// The encoding is a version byte, the time and checkpoints of the store, the proposer boost
+    /// root and the (ascending) equivocating indices, followed by:
+    /// - each block, parents first, as its header, timeliness, unrealized justification and
+    ///   post-state in the `storage` encoding
+    /// - each checkpoint with a state, as the checkpoint and the root of the state, which is
+    ///   recomputed from the state of its block when decoding
+    /// - the latest messages, by ascending validator index
+    /// - the known proof-of-work blocks
+    ///
+    /// Counts and integers are unsigned LEB128 varints and ascending indices are delta-encoded.
+    pub fn serialize(&self) -> Result<Vec<u8>> {
+        let mut buffer = vec![SNAPSHOT_VERSION];
+        write_varint(&mut buffer, self.time);
+        write_varint(&mut buffer, self.genesis_time);
+        for checkpoint in [
+            &self.justified_checkpoint,
+            &self.finalized_checkpoint,
+            &self.unrealized_justified_checkpoint,
+            &self.unrealized_finalized_checkpoint,
+        ] {
+            write_ssz(&mut buffer, checkpoint)?;
+        }
+        buffer.extend_from_slice(self.proposer_boost_root.as_ref());
+
+        let mut equivocating_indices =
+            self.equivocating_indices.iter().copied().collect::<Vec<_>>();
+        equivocating_indices.sort_unstable();
+        write_varint(&mut buffer, equivocating_indices.len() as u64);
+        let mut previous = 0;
+        for index in equivocating_indices {
+            write_varint(&mut buffer, (index - previous) as u64);
+            previous = index;
+        }
+
+        let mut blocks = self.blocks.iter().collect::<Vec<_>>();
+        blocks.sort_by(|(a, a_block), (b, b_block)| {
+            (a_block.slot, a.as_ref()).cmp(&(b_block.slot, b.as_ref()))
+        });
+        write_varint(&mut buffer, blocks.len() as u64);
+        for (root, block) in blocks {
+            write_ssz(&mut buffer, block)?;
+            let timeliness = match self.block_timeliness.get(root) {
+                None => 0,
+                Some(false) => 1,
+                Some(true) => 2,
+            };
+            buffer.push(timeliness);
+            let justification = self
+                .unrealized_justifications
+                .get(root)
+                .ok_or(ForkChoiceError::UnknownBlock(*root))?;
+            write_ssz(&mut buffer, justification)?;
+            let state = encode_state(self.block_state(root)?)?;
+            write_varint(&mut buffer, state.len() as u64);
+            buffer.extend(state);
+        }
+
+        let mut checkpoint_states = self.checkpoint_states.iter().collect::<Vec<_>>();
+        checkpoint_states
+            .sort_by(|(a, _), (b, _)| (a.epoch, a.root.as_ref()).cmp(&(b.epoch, b.root.as_ref())));
+        write_varint(&mut buffer, checkpoint_states.len() as u64);
+        for (checkpoint, state) in checkpoint_states {
+            write_ssz(&mut buffer, &**checkpoint)?;
+            buffer.extend_from_slice(state.hash_tree_root()?.as_ref());
+        }
+
+        let mut latest_messages = self.latest_messages.iter().collect::<Vec<_>>();
+        latest_messages.sort_unstable_by_key(|(index, _)| **index);
+        write_varint(&mut buffer, latest_messages.len() as u64);
+        let mut previous = 0;
+        for (&index, message) in latest_messages {
+            write_varint(&mut buffer, (index - previous) as u64);
+            write_varint(&mut buffer, message.epoch);
+            buffer.extend_from_slice(message.root.as_ref());
+            previous = index;
+        }
+
+        let mut pow_blocks = self.pow_blocks.values().collect::<Vec<_>>();
+        pow_blocks.sort_by(|a, b| a.block_hash.as_ref().cmp(b.block_hash.as_ref()));
+        write_varint(&mut buffer, pow_blocks.len() as u64);
+        for pow_block in pow_blocks {
+            write_ssz(&mut buffer, pow_block)?;
+        }
+        Ok(buffer)
+    }
+
+    /// Decode a store produced by `serialize`.
+    ///
+    /// The snapshot is rejected unless it describes a store `on_block` and `on_attestation` could
+    /// have built: every state is the one its block commits to, the blocks form a single tree
+    /// with parents before children, every checkpoint and vote is for a known block and each
+    /// checkpoint state matches the state of its block advanced to the checkpoint.
+    pub fn deserialize(bytes: &[u8], context: Context) -> Result<Self> {
+        let mut reader = Reader { bytes, offset: 0 };
+        let version = reader.read_byte()?;
+        if version != SNAPSHOT_VERSION {
+            return Err(ForkChoiceError::UnsupportedSnapshotVersion(version).into())
+        }
+        let time = reader.read_varint()?;
+        let genesis_time = reader.read_varint()?;
+        let justified_checkpoint: Checkpoint = reader.read_ssz(CHECKPOINT_LEN)?;
+        let finalized_checkpoint: Checkpoint = reader.read_ssz(CHECKPOINT_LEN)?;
+        let unrealized_justified_checkpoint: Checkpoint = reader.read_ssz(CHECKPOINT_LEN)?;
+        let unrealized_finalized_checkpoint: Checkpoint = reader.read_ssz(CHECKPOINT_LEN)?;
+        let proposer_boost_root = reader.read_root()?;
+
+        let count = reader.read_count()?;
+        let mut equivocating_indices = HashSet::with_capacity(count);
+        let mut previous: ValidatorIndex = 0;
+        for i in 0..count {
+            let index = reader.read_index(previous, i == 0)?;
+            equivocating_indices.insert(index);
+            previous = index;
+        }
+
+        let count = reader.read_count()?;
+        let mut blocks = HashMap::with_capacity(count);
+        let mut block_states = HashMap::with_capacity(count);
+        let mut block_timeliness = HashMap::with_capacity(count);
+        let mut unrealized_justifications = HashMap::with_capacity(count);
+        let mut anchor = None;
+        for _ in 0..count {
+            let block: BeaconBlockHeader = reader.read_ssz(BLOCK_HEADER_LEN)?;
+            let root = block.hash_tree_root()?;
+            match reader.read_byte()? {
+                0 => {}
+                timeliness @ (1 | 2) => {
+                    block_timeliness.insert(root, timeliness == 2);
+                }
+                _ => return Err(ForkChoiceError::MalformedSnapshot.into()),
+            }
+            let justification: Checkpoint = reader.read_ssz(CHECKPOINT_LEN)?;
+            let len = reader.read_count()?;
+            let state = decode_state(reader.read_bytes(len)?)
+                .map_err(|_| ForkChoiceError::MalformedSnapshot)?;
+            if state.hash_tree_root()? != block.state_root {
+                return Err(ForkChoiceError::InconsistentSnapshot(root).into())
+            }
+            // parents come before their children, and only the anchor has no parent
+            match blocks.get(&block.parent_root) {
+                Some(parent) if parent.slot < block.slot => {}
+                None if anchor.is_none() => anchor = Some(root),
+                _ => return Err(ForkChoiceError::InconsistentSnapshot(root).into()),
+            }
+            if blocks.insert(root, block).is_some() {
+                return Err(ForkChoiceError::MalformedSnapshot.into())
+            }
+            block_states.insert(root, state);
+            unrealized_justifications.insert(root, justification);
+        }
+        if anchor.is_none() {
+            return Err(ForkChoiceError::MalformedSnapshot.into())
+        }
+        for checkpoint in [
+            &justified_checkpoint,
+            &finalized_checkpoint,
+            &unrealized_justified_checkpoint,
+            &unrealized_finalized_checkpoint,
+        ] {
+            if !blocks.contains_key(&checkpoint.root) {
+                return Err(ForkChoiceError::InconsistentSnapshot(checkpoint.root).into())
+            }
+        }
+        if !proposer_boost_root.is_zero() && !blocks.contains_key(&proposer_boost_root) {
+            return Err(ForkChoiceError::InconsistentSnapshot(proposer_boost_root).into())
+        }
+
+        let mut store = Self {
+            time,
+            genesis_time,
+            justified_checkpoint,
+            finalized_checkpoint,
+            unrealized_justified_checkpoint,
+            unrealized_finalized_checkpoint,
+            proposer_boost_root,
+            equivocating_indices,
+            blocks,
+            block_states,
+            block_timeliness,
+            checkpoint_states: Default::default(),
+            latest_messages: Default::default(),
+            unrealized_justifications,
+            pow_blocks: Default::default(),
+            context,
+        };
+
+        let count = reader.read_count()?;
+        for _ in 0..count {
+            let checkpoint: Checkpoint = reader.read_ssz(CHECKPOINT_LEN)?;
+            let state_root = reader.read_root()?;
+            if store.checkpoint_state(&checkpoint).is_ok() {
+                return Err(ForkChoiceError::MalformedSnapshot.into())
+            }
+            if !store.blocks.contains_key(&checkpoint.root) {
+                return Err(ForkChoiceError::InconsistentSnapshot(checkpoint.root).into())
+            }
+            store.store_target_checkpoint_state(&checkpoint)?;
+            if store.checkpoint_state(&checkpoint)?.hash_tree_root()? != state_root {
+                return Err(ForkChoiceError::InconsistentSnapshot(checkpoint.root).into())
+            }
+        }
+        if store.checkpoint_state(&store.justified_checkpoint).is_err() {
+            return Err(ForkChoiceError::InconsistentSnapshot(store.justified_checkpoint.root).into())
+        }
+
+        let count = reader.read_count()?;
+        let mut previous: ValidatorIndex = 0;
+        for i in 0..count {
+            let index = reader.read_index(previous, i == 0)?;
+            let epoch = reader.read_varint()?;
+            let root = reader.read_root()?;
+            if !store.blocks.contains_key(&root) {
+                return Err(ForkChoiceError::InconsistentSnapshot(root).into())
+            }
+            store.latest_messages.insert(index, LatestMessage { epoch, root });
+            previous = index;
+        }
+
+        let count = reader.read_count()?;
+        for _ in 0..count {
+            let pow_block: PowBlock = reader.read_ssz(POW_BLOCK_LEN)?;
+            store.pow_blocks.insert(pow_block.block_hash.clone(), pow_block);
+        }
+
+        if reader.remaining() != 0 {
+            return Err(ForkChoiceError::MalformedSnapshot.into())
+        }
+        Ok(store)
+    }
+}
+
+fn write_ssz<T: SimpleSerialize>(buffer: &mut Vec<u8>, value: &T) -> Result<()> {
+    let encoding = serialize(value).map_err(|err| Error::SimpleSerialize(err.into()))?;
+    buffer.extend(encoding);
+    Ok(())
+}
+
+fn write_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.offset
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if len > self.remaining() {
+            return Err(ForkChoiceError::MalformedSnapshot.into())
+        }
+        let bytes = &self.bytes[self.offset..self.offset + len];
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn read_byte(&mut self) -> Result<u8> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_varint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_byte()?;
+            let bits = (byte & 0x7f) as u64;
+            if shift == 63 && bits > 1 {
+                return Err(ForkChoiceError::MalformedSnapshot.into())
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value)
+            }
+        }
+        Err(ForkChoiceError::MalformedSnapshot.into())
+    }
+
+    // A count of entries or bytes, each of which takes at least one byte, bounding any
+    // allocation by the input.
+    fn read_count(&mut self) -> Result<usize> {
+        let count = self.read_varint()?;
+        if count > self.remaining() as u64 {
+            return Err(ForkChoiceError::MalformedSnapshot.into())
+        }
+        Ok(count as usize)
+    }
+
+    // The next of a strictly ascending sequence of indices after `previous`.
+    fn read_index(&mut self, previous: ValidatorIndex, is_first: bool) -> Result<ValidatorIndex> {
+        let delta = self.read_varint()?;
+        if !is_first && delta == 0 {
+            return Err(ForkChoiceError::MalformedSnapshot.into())
+        }
+        usize::try_from(delta)
+            .ok()
+            .and_then(|delta| previous.checked_add(delta))
+            .ok_or_else(|| ForkChoiceError::MalformedSnapshot.into())
+    }
+
+    fn read_root(&mut self) -> Result<Root> {
+        Root::try_from(self.read_bytes(32)?).map_err(|_| ForkChoiceError::MalformedSnapshot.into())
+    }
+
+    fn read_ssz<T: SimpleSerialize>(&mut self, len: usize) -> Result<T> {
+        T::deserialize(self.read_bytes(len)?).map_err(|_| ForkChoiceError::MalformedSnapshot.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixtures::phase0::{genesis_state, signed_block, BLOCK_SLOT},
+        fork_choice::{minimal::Store, ForkChoiceError, LatestMessage},
+        phase0::minimal,
+        primitives::{Root, Slot},
+        ssz::prelude::*,
+        state_transition::Context,
+        types::{BeaconState, SignedBeaconBlock},
+        Error,
+    };
+
+    // A store with a block, votes, an equivocation and the state of a checkpoint besides the
+    // anchor.
+    fn store() -> Store {
+        let state = genesis_state();
+        let mut anchor_block = state.latest_block_header.clone();
+        anchor_block.state_root = state.hash_tree_root().unwrap();
+        let mut store =
+            Store::new(BeaconState::Phase0(state), anchor_block, Context::for_minimal()).unwrap();
+        let block = signed_block();
+        let slot_start =
+            |store: &Store, slot: Slot| store.genesis_time + slot * store.context.seconds_per_slot;
+        store.on_tick(slot_start(&store, BLOCK_SLOT));
+        store.on_block(&SignedBeaconBlock::Phase0(block.clone())).unwrap();
+        let attestation: &minimal::Attestation = &block.message.body.attestations[0];
+        store.on_attestation(attestation, true).unwrap();
+        store.on_attester_slashing(&block.message.body.attester_slashings[0]).unwrap();
+        let epoch_start = store.context.slots_per_epoch;
+        store.on_tick(slot_start(&store, epoch_start));
+        store
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let store = store();
+        let snapshot = store.serialize().unwrap();
+        let restored = Store::deserialize(&snapshot, store.context.clone()).unwrap();
+
+        assert_eq!(restored.time, store.time);
+        assert_eq!(restored.justified_checkpoint, store.justified_checkpoint);
+        assert_eq!(restored.finalized_checkpoint, store.finalized_checkpoint);
+        assert_eq!(restored.proposer_boost_root, store.proposer_boost_root);
+        assert_eq!(restored.equivocating_indices, store.equivocating_indices);
+        assert_eq!(restored.blocks, store.blocks);
+        assert_eq!(restored.block_states, store.block_states);
+        assert_eq!(restored.block_timeliness, store.block_timeliness);
+        assert_eq!(restored.latest_messages, store.latest_messages);
+        assert_eq!(restored.unrealized_justifications, store.unrealized_justifications);
+        assert_eq!(restored.checkpoint_states.len(), store.checkpoint_states.len());
+        for (checkpoint, state) in &store.checkpoint_states {
+            assert_eq!(restored.checkpoint_state(checkpoint).unwrap(), state);
+        }
+        assert_eq!(restored.get_head().unwrap(), store.get_head().unwrap());
+        assert_eq!(restored.serialize().unwrap(), snapshot);
+    }
+
+    #[test]
+    fn test_corrupt_snapshots_are_rejected() {
+        let store = store();
+        let snapshot = store.serialize().unwrap();
+        let context = &store.context;
+
+        // every prefix of the header and a sample of the rest
+        let lengths = (0..256).chain((256..snapshot.len()).step_by(97));
+        for len in lengths {
+            let result = Store::deserialize(&snapshot[..len], context.clone());
+            assert!(matches!(
+                result,
+                Err(Error::ForkChoice(ForkChoiceError::MalformedSnapshot)) |
+                    Err(Error::ForkChoice(ForkChoiceError::UnsupportedSnapshotVersion(_)))
+            ));
+        }
+
+        let mut trailing = snapshot.clone();
+        trailing.push(0);
+        assert!(matches!(
+            Store::deserialize(&trailing, context.clone()),
+            Err(Error::ForkChoice(ForkChoiceError::MalformedSnapshot))
+        ));
+
+        let mut version = snapshot.clone();
+        version[0] = 1;
+        assert!(matches!(
+            Store::deserialize(&version, context.clone()),
+            Err(Error::ForkChoice(ForkChoiceError::UnsupportedSnapshotVersion(1)))
+        ));
+
+        // a vote for a block that is not in the store
+        let (index, message) = store.latest_messages.iter().next().unwrap();
+        let mut unknown = store.clone();
+        let root = Root::try_from([1u8; 32].as_ref()).unwrap();
+        unknown.latest_messages.insert(*index, LatestMessage { root, ..*message });
+        assert!(matches!(
+            Store::deserialize(&unknown.serialize().unwrap(), context.clone()),
+            Err(Error::ForkChoice(ForkChoiceError::InconsistentSnapshot(inconsistent))) if inconsistent == root
+        ));
+
+        // a state that its block does not commit to
+        let mut state_changed = store.clone();
+        let anchor_root = store.justified_checkpoint.root;
+        let head = *store.blocks.keys().find(|root| **root != anchor_root).unwrap();
+        let state = state_changed.block_states.get_mut(&head).unwrap();
+        *state = store.block_states[&anchor_root].clone();
+        assert!(matches!(
+            Store::deserialize(&state_changed.serialize().unwrap(), context.clone()),
+            Err(Error::ForkChoice(ForkChoiceError::InconsistentSnapshot(inconsistent))) if inconsistent == head
+        ));
+    }
+}
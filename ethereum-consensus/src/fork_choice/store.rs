@@ -0,0 +1,1295 @@
+use crate::{
+    bellatrix::{self, fork_choice::PowBlock},
+    capella, deneb,
+    deneb::{
+        blob_sidecar::Blob,
+        polynomial_commitments::{verify_blob_kzg_proof_batch, KzgCommitment, KzgProof},
+    },
+    electra,
+    error::WrongFork,
+    fork_choice::{ForkChoiceError, LatestMessage, INTERVALS_PER_SLOT},
+    phase0::{
+        compute_epoch_at_slot, compute_start_slot_at_epoch, is_active_validator,
+        is_slashable_attestation_data, Attestation, AttestationData, AttesterSlashing,
+        BeaconBlockHeader, Checkpoint,
+    },
+    primitives::{
+        Epoch, Gwei, Hash32, IsZero, Root, Slot, ValidatorIndex, GENESIS_EPOCH, GENESIS_SLOT,
+    },
+    ssz::prelude::*,
+    state_transition::{Context, Executor, Result},
+    types::{BeaconState, SignedBeaconBlock},
+    Fork,
+};
+use std::collections::{HashMap, HashSet};
+
+// Evaluate `$body` with `$state` bound to the state of the fork inside the polymorphic
+// `$polymorphic` state and the module of that fork in scope as `spec`.
+//
+// Electra changed the attestation containers, so code handling those can give a separate body for
+// Electra states.
+macro_rules! for_fork {
+    ($polymorphic:expr, $state:ident => $body:expr) => {
+        for_fork!($polymorphic, $state => $body, Electra($state) => {
+            use crate::electra as spec;
+            $body
+        })
+    };
+    ($polymorphic:expr, $state:ident => $body:expr, Electra($electra:pat) => $electra_body:expr) => {
+        match $polymorphic {
+            BeaconState::Phase0($state) => {
+                use crate::phase0 as spec;
+                $body
+            }
+            BeaconState::Altair($state) => {
+                use crate::altair as spec;
+                $body
+            }
+            BeaconState::Bellatrix($state) => {
+                use crate::bellatrix as spec;
+                $body
+            }
+            BeaconState::Capella($state) => {
+                use crate::capella as spec;
+                $body
+            }
+            BeaconState::Deneb($state) => {
+                use crate::deneb as spec;
+                $body
+            }
+            BeaconState::Electra($electra) => $electra_body,
+        }
+    };
+}
+
+/// The fork choice store, following the `Store` of the consensus specs.
+///
+/// Blocks are kept as their headers, which share their root, next to their post-states.
+/// Nothing is pruned from the store as finality advances.
+#[derive(Clone)]
+pub struct Store<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+> {
+    /// The current time, in seconds since the UNIX epoch.
+    pub time: u64,
+    pub genesis_time: u64,
+    pub justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+    pub unrealized_justified_checkpoint: Checkpoint,
+    pub unrealized_finalized_checkpoint: Checkpoint,
+    /// The root of the timely block of the current slot, if any, or the zero root otherwise.
+    pub proposer_boost_root: Root,
+    pub equivocating_indices: HashSet<ValidatorIndex>,
+    pub blocks: HashMap<Root, BeaconBlockHeader>,
+    pub block_states: HashMap<
+        Root,
+        BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    >,
+    pub block_timeliness: HashMap<Root, bool>,
+    /// Keyed by checkpoint, and searchable by the root of the checkpoint.
+    pub checkpoint_states: HashMap<
+        HashTreeRootCached<Checkpoint>,
+        BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    >,
+    pub latest_messages: HashMap<ValidatorIndex, LatestMessage>,
+    pub unrealized_justifications: HashMap<Root, Checkpoint>,
+    /// Proof-of-work blocks known to the node, consulted to validate the merge transition block.
+    pub pow_blocks: HashMap<Hash32, PowBlock>,
+    pub context: Context,
+}
+
+impl<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+    >
+    Store<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_PROPOSER_SLASHINGS,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >
+{
+    /// Build a store from a trusted anchor, e.g. the genesis state and block or a state obtained
+    /// through checkpoint sync, following `get_forkchoice_store`.
+    ///
+    /// The anchor is given as the header of its block, as the body is not needed by the store.
+    pub fn new(
+        anchor_state: BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+        anchor_block: BeaconBlockHeader,
+        context: Context,
+    ) -> Result<Self> {
+        if anchor_block.state_root != anchor_state.hash_tree_root()? {
+            return Err(ForkChoiceError::InvalidAnchor.into())
+        }
+        let anchor_root = anchor_block.hash_tree_root()?;
+        let anchor_epoch = compute_epoch_at_slot(anchor_state.slot(), &context);
+        let justified_checkpoint = Checkpoint { epoch: anchor_epoch, root: anchor_root };
+        let finalized_checkpoint = justified_checkpoint.clone();
+        Ok(Self {
+            time: anchor_state.genesis_time() + context.seconds_per_slot * anchor_state.slot(),
+            genesis_time: anchor_state.genesis_time(),
+            justified_checkpoint: justified_checkpoint.clone(),
+            finalized_checkpoint: finalized_checkpoint.clone(),
+            unrealized_justified_checkpoint: justified_checkpoint.clone(),
+            unrealized_finalized_checkpoint: finalized_checkpoint,
+            proposer_boost_root: Root::default(),
+            equivocating_indices: Default::default(),
+            blocks: HashMap::from_iter([(anchor_root, anchor_block)]),
+            block_states: HashMap::from_iter([(anchor_root, anchor_state.clone())]),
+            block_timeliness: Default::default(),
+            checkpoint_states: HashMap::from_iter([(
+                HashTreeRootCached::new(justified_checkpoint.clone())?,
+                anchor_state,
+            )]),
+            latest_messages: Default::default(),
+            unrealized_justifications: HashMap::from_iter([(anchor_root, justified_checkpoint)]),
+            pow_blocks: Default::default(),
+            context,
+        })
+    }
+
+    fn executor(
+        &self,
+        state: BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    ) -> Executor<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_PROPOSER_SLASHINGS,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    > {
+        Executor::new(state, self.context.clone())
+    }
+
+    pub fn block(&self, root: &Root) -> Result<&BeaconBlockHeader> {
+        self.blocks.get(root).ok_or_else(|| ForkChoiceError::UnknownBlock(*root).into())
+    }
+
+    pub fn block_state(
+        &self,
+        root: &Root,
+    ) -> Result<
+        &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    > {
+        self.block_states.get(root).ok_or_else(|| ForkChoiceError::UnknownBlock(*root).into())
+    }
+
+    pub fn checkpoint_state(
+        &self,
+        checkpoint: &Checkpoint,
+    ) -> Result<
+        &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    > {
+        let root = checkpoint.hash_tree_root()?;
+        self.checkpoint_states.get(&root).ok_or_else(|| {
+            ForkChoiceError::UnknownCheckpointState {
+                epoch: checkpoint.epoch,
+                root: checkpoint.root,
+            }
+            .into()
+        })
+    }
+
+    pub fn get_current_slot(&self) -> Slot {
+        GENESIS_SLOT + self.time.saturating_sub(self.genesis_time) / self.context.seconds_per_slot
+    }
+
+    fn get_current_epoch(&self) -> Epoch {
+        compute_epoch_at_slot(self.get_current_slot(), &self.context)
+    }
+
+    pub fn get_ancestor(&self, root: Root, slot: Slot) -> Result<Root> {
+        let mut root = root;
+        loop {
+            let block = self.block(&root)?;
+            if block.slot <= slot {
+                return Ok(root)
+            }
+            root = block.parent_root;
+        }
+    }
+
+    /// The root of the block at the start of `epoch` in the chain of the block with `root`.
+    pub fn get_checkpoint_block(&self, root: Root, epoch: Epoch) -> Result<Root> {
+        let epoch_first_slot = compute_start_slot_at_epoch(epoch, &self.context);
+        self.get_ancestor(root, epoch_first_slot)
+    }
+
+    /// The weight of the subtree rooted at the block with `root`: the effective balance of the
+    /// validators whose latest message supports it, with the proposer boost if it applies.
+    pub fn get_weight(&self, root: &Root) -> Result<Gwei> {
+        let state = self.checkpoint_state(&self.justified_checkpoint)?;
+        let current_epoch = compute_epoch_at_slot(state.slot(), &self.context);
+        let slot = self.block(root)?.slot;
+        let mut attestation_score = 0;
+        for (index, validator) in state.validators().iter().enumerate() {
+            if !is_active_validator(validator, current_epoch) ||
+                validator.slashed ||
+                self.equivocating_indices.contains(&index)
+            {
+                continue
+            }
+            if let Some(message) = self.latest_messages.get(&index) {
+                if self.get_ancestor(message.root, slot)? == *root {
+                    attestation_score += validator.effective_balance;
+                }
+            }
+        }
+
+        if self.proposer_boost_root.is_zero() {
+            return Ok(attestation_score)
+        }
+        let mut proposer_score = 0;
+        if self.get_ancestor(self.proposer_boost_root, slot)? == *root {
+            proposer_score = calculate_committee_fraction(
+                state,
+                self.context.proposer_score_boost,
+                &self.context,
+            );
+        }
+        Ok(attestation_score + proposer_score)
+    }
+
+    /// The justified checkpoint of the block with `root`, as seen from the current epoch.
+    pub fn get_voting_source(&self, root: &Root) -> Result<Checkpoint> {
+        let block = self.block(root)?;
+        let block_epoch = compute_epoch_at_slot(block.slot, &self.context);
+        if self.get_current_epoch() > block_epoch {
+            // the block is from a prior epoch, so its justification has been pulled up already
+            self.unrealized_justifications
+                .get(root)
+                .cloned()
+                .ok_or_else(|| ForkChoiceError::UnknownBlock(*root).into())
+        } else {
+            Ok(self.block_state(root)?.current_justified_checkpoint().clone())
+        }
+    }
+
+    // Add the block with `root` to `blocks` if any leaf of its subtree is a viable head, i.e.
+    // agrees with the justified and finalized checkpoints of the store.
+    fn filter_block_tree(
+        &self,
+        root: Root,
+        blocks: &mut HashMap<Root, BeaconBlockHeader>,
+    ) -> Result<bool> {
+        let block = self.block(&root)?;
+        let children = self
+            .blocks
+            .iter()
+            .filter(|(_, child)| child.parent_root == root)
+            .map(|(child_root, _)| *child_root)
+            .collect::<Vec<_>>();
+
+        if !children.is_empty() {
+            let mut is_viable = false;
+            for child in children {
+                is_viable |= self.filter_block_tree(child, blocks)?;
+            }
+            if is_viable {
+                blocks.insert(root, block.clone());
+            }
+            return Ok(is_viable)
+        }
+
+        let current_epoch = self.get_current_epoch();
+        let voting_source = self.get_voting_source(&root)?;
+        // the voting source should be at the same height as the justified checkpoint of the store
+        // or not more than two epochs ago
+        let is_correct_justified = self.justified_checkpoint.epoch == GENESIS_EPOCH ||
+            voting_source.epoch == self.justified_checkpoint.epoch ||
+            voting_source.epoch + 2 >= current_epoch;
+        let is_correct_finalized = self.finalized_checkpoint.epoch == GENESIS_EPOCH ||
+            self.finalized_checkpoint.root ==
+                self.get_checkpoint_block(root, self.finalized_checkpoint.epoch)?;
+        let is_viable = is_correct_justified && is_correct_finalized;
+        if is_viable {
+            blocks.insert(root, block.clone());
+        }
+        Ok(is_viable)
+    }
+
+    /// The tree of blocks descending from the justified checkpoint that lead to a viable head.
+    pub fn get_filtered_block_tree(&self) -> Result<HashMap<Root, BeaconBlockHeader>> {
+        let mut blocks = HashMap::new();
+        self.filter_block_tree(self.justified_checkpoint.root, &mut blocks)?;
+        Ok(blocks)
+    }
+
+    /// The head of the chain under LMD-GHOST, starting from the justified checkpoint and
+    /// descending into the heaviest child, with ties broken by the greater root.
+    pub fn get_head(&self) -> Result<Root> {
+        let blocks = self.get_filtered_block_tree()?;
+        let mut head = self.justified_checkpoint.root;
+        loop {
+            let mut heaviest: Option<(Gwei, Root)> = None;
+            for (root, _) in blocks.iter().filter(|(_, block)| block.parent_root == head) {
+                let weight = self.get_weight(root)?;
+                let is_heavier = heaviest.map_or(true, |(heaviest_weight, heaviest_root)| {
+                    (weight, root.as_ref()) > (heaviest_weight, heaviest_root.as_ref())
+                });
+                if is_heavier {
+                    heaviest = Some((weight, *root));
+                }
+            }
+            match heaviest {
+                Some((_, root)) => head = root,
+                None => return Ok(head),
+            }
+        }
+    }
+
+    fn update_checkpoints(
+        &mut self,
+        justified_checkpoint: &Checkpoint,
+        finalized_checkpoint: &Checkpoint,
+    ) {
+        if justified_checkpoint.epoch > self.justified_checkpoint.epoch {
+            self.justified_checkpoint = justified_checkpoint.clone();
+        }
+        if finalized_checkpoint.epoch > self.finalized_checkpoint.epoch {
+            self.finalized_checkpoint = finalized_checkpoint.clone();
+        }
+    }
+
+    fn update_unrealized_checkpoints(
+        &mut self,
+        unrealized_justified_checkpoint: &Checkpoint,
+        unrealized_finalized_checkpoint: &Checkpoint,
+    ) {
+        if unrealized_justified_checkpoint.epoch > self.unrealized_justified_checkpoint.epoch {
+            self.unrealized_justified_checkpoint = unrealized_justified_checkpoint.clone();
+        }
+        if unrealized_finalized_checkpoint.epoch > self.unrealized_finalized_checkpoint.epoch {
+            self.unrealized_finalized_checkpoint = unrealized_finalized_checkpoint.clone();
+        }
+    }
+
+    // Run justification and finalization on a copy of the post-state of the block with `root`,
+    // recording the checkpoints the block would justify and finalize at the end of its epoch.
+    fn compute_pulled_up_tip(&mut self, root: Root) -> Result<()> {
+        let mut state = self.block_state(&root)?.clone();
+        for_fork!(&mut state, state => {
+            spec::process_justification_and_finalization(state, &self.context)
+        })?;
+        let justified_checkpoint = state.current_justified_checkpoint().clone();
+        let finalized_checkpoint = state.finalized_checkpoint().clone();
+
+        self.unrealized_justifications.insert(root, justified_checkpoint.clone());
+        self.update_unrealized_checkpoints(&justified_checkpoint, &finalized_checkpoint);
+
+        // if the block is from a prior epoch, apply the realized values
+        let block_epoch = compute_epoch_at_slot(self.block(&root)?.slot, &self.context);
+        if block_epoch < self.get_current_epoch() {
+            self.update_checkpoints(&justified_checkpoint, &finalized_checkpoint);
+        }
+        Ok(())
+    }
+
+    fn on_tick_per_slot(&mut self, time: u64) {
+        let previous_slot = self.get_current_slot();
+        self.time = time;
+        let current_slot = self.get_current_slot();
+
+        if current_slot > previous_slot {
+            self.proposer_boost_root = Root::default();
+            // pull up the unrealized checkpoints at epoch boundaries
+            if current_slot % self.context.slots_per_epoch == 0 {
+                let justified_checkpoint = self.unrealized_justified_checkpoint.clone();
+                let finalized_checkpoint = self.unrealized_finalized_checkpoint.clone();
+                self.update_checkpoints(&justified_checkpoint, &finalized_checkpoint);
+            }
+        }
+    }
+
+    /// Advance the clock of the store to `time`, in seconds since the UNIX epoch, running the
+    /// per-slot updates of every slot passed on the way.
+    pub fn on_tick(&mut self, time: u64) {
+        let tick_slot = time.saturating_sub(self.genesis_time) / self.context.seconds_per_slot;
+        while self.get_current_slot() < tick_slot {
+            let previous_time =
+                self.genesis_time + (self.get_current_slot() + 1) * self.context.seconds_per_slot;
+            self.on_tick_per_slot(previous_time);
+        }
+        self.on_tick_per_slot(time);
+    }
+
+    /// Import a block which commits to no blobs; see [`Self::on_block_with_blobs`] otherwise.
+    ///
+    /// A block committing to blobs is rejected as unavailable.
+    pub fn on_block(
+        &mut self,
+        signed_block: &SignedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    ) -> Result<()> {
+        self.on_block_with_availability(signed_block, |commitments| commitments.is_empty())
+    }
+
+    /// Import a block along with the blobs (and their proofs) it commits to, in order.
+    pub fn on_block_with_blobs<const BYTES_PER_BLOB: usize>(
+        &mut self,
+        signed_block: &SignedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+        blobs: &[Blob<BYTES_PER_BLOB>],
+        proofs: &[KzgProof],
+    ) -> Result<()> {
+        let kzg_settings = self.context.kzg_settings.clone();
+        self.on_block_with_availability(signed_block, |commitments| {
+            verify_blob_kzg_proof_batch(blobs, commitments, proofs, &kzg_settings).is_ok()
+        })
+    }
+
+    fn on_block_with_availability(
+        &mut self,
+        signed_block: &SignedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+        is_data_available: impl FnOnce(&[KzgCommitment]) -> bool,
+    ) -> Result<()> {
+        let block = signed_block.message();
+        let slot = block.slot();
+        let parent_root = block.parent_root();
+        let pre_state = self.block_state(&parent_root)?;
+
+        let current_slot = self.get_current_slot();
+        if current_slot < slot {
+            return Err(ForkChoiceError::FutureBlock { slot, current_slot }.into())
+        }
+        let finalized_slot =
+            compute_start_slot_at_epoch(self.finalized_checkpoint.epoch, &self.context);
+        if slot <= finalized_slot {
+            return Err(ForkChoiceError::BlockNotAfterFinalized { slot, finalized_slot }.into())
+        }
+        let finalized_checkpoint_block =
+            self.get_checkpoint_block(parent_root, self.finalized_checkpoint.epoch)?;
+        if finalized_checkpoint_block != self.finalized_checkpoint.root {
+            return Err(ForkChoiceError::NotDescendantOfFinalized(parent_root).into())
+        }
+
+        let block_root = block.hash_tree_root()?;
+        let body = block.body();
+        let commitments = body.blob_kzg_commitments().map(|commitments| &commitments[..]);
+        if !is_data_available(commitments.unwrap_or_default()) {
+            return Err(ForkChoiceError::DataUnavailable(block_root).into())
+        }
+
+        let mut executor = self.executor(pre_state.clone());
+        executor.apply_block(signed_block)?;
+        let state = executor.state.into_inner();
+
+        if is_merge_transition_block(pre_state, signed_block) {
+            self.validate_merge_block(block_root, signed_block)?;
+        }
+
+        let header = BeaconBlockHeader {
+            slot,
+            proposer_index: block.proposer_index(),
+            parent_root,
+            state_root: block.state_root(),
+            body_root: body.hash_tree_root()?,
+        };
+        let justified_checkpoint = state.current_justified_checkpoint().clone();
+        let finalized_checkpoint = state.finalized_checkpoint().clone();
+        self.blocks.insert(block_root, header);
+        self.block_states.insert(block_root, state);
+
+        // add the proposer boost if the block is the first timely block of the slot
+        let time_into_slot = (self.time - self.genesis_time) % self.context.seconds_per_slot;
+        let is_before_attesting_interval =
+            time_into_slot < self.context.seconds_per_slot / INTERVALS_PER_SLOT;
+        let is_timely = current_slot == slot && is_before_attesting_interval;
+        self.block_timeliness.insert(block_root, is_timely);
+        if is_timely && self.proposer_boost_root.is_zero() {
+            self.proposer_boost_root = block_root;
+        }
+
+        self.update_checkpoints(&justified_checkpoint, &finalized_checkpoint);
+        self.compute_pulled_up_tip(block_root)
+    }
+
+    // Check that the merge transition block builds on a terminal proof-of-work block.
+    fn validate_merge_block(
+        &self,
+        block_root: Root,
+        signed_block: &SignedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    ) -> Result<()> {
+        let block = signed_block.message();
+        let body = block.body();
+        let Some(payload) = body.execution_payload() else {
+            return Err(ForkChoiceError::InvalidMergeBlock(block_root).into())
+        };
+        let parent_hash = payload.parent_hash();
+
+        if !self.context.terminal_block_hash.is_zero() {
+            let epoch = compute_epoch_at_slot(block.slot(), &self.context);
+            if epoch < self.context.terminal_block_hash_activation_epoch ||
+                *parent_hash != self.context.terminal_block_hash
+            {
+                return Err(ForkChoiceError::InvalidMergeBlock(block_root).into())
+            }
+            return Ok(())
+        }
+
+        let pow_block = self
+            .pow_blocks
+            .get(parent_hash)
+            .ok_or_else(|| ForkChoiceError::UnknownPowBlock(parent_hash.clone()))?;
+        let pow_parent = self
+            .pow_blocks
+            .get(&pow_block.parent_hash)
+            .ok_or_else(|| ForkChoiceError::UnknownPowBlock(pow_block.parent_hash.clone()))?;
+        let is_total_difficulty_reached =
+            pow_block.total_difficulty >= self.context.terminal_total_difficulty;
+        let is_parent_total_difficulty_valid =
+            pow_parent.total_difficulty < self.context.terminal_total_difficulty;
+        if !(is_total_difficulty_reached && is_parent_total_difficulty_valid) {
+            return Err(ForkChoiceError::InvalidMergeBlock(block_root).into())
+        }
+        Ok(())
+    }
+
+    fn validate_on_attestation(&self, data: &AttestationData, is_from_block: bool) -> Result<()> {
+        let target = &data.target;
+
+        // attestations received over the network must be from the current or previous epoch
+        if !is_from_block {
+            let current_epoch = self.get_current_epoch();
+            let previous_epoch = current_epoch.saturating_sub(1).max(GENESIS_EPOCH);
+            if target.epoch != current_epoch && target.epoch != previous_epoch {
+                return Err(ForkChoiceError::StaleAttestationTarget {
+                    target: target.epoch,
+                    current_epoch,
+                }
+                .into())
+            }
+        }
+
+        let slot_epoch = compute_epoch_at_slot(data.slot, &self.context);
+        if target.epoch != slot_epoch {
+            return Err(ForkChoiceError::AttestationTargetMismatch {
+                target: target.epoch,
+                slot_epoch,
+            }
+            .into())
+        }
+        self.block(&target.root)?;
+        if self.block(&data.beacon_block_root)?.slot > data.slot {
+            return Err(ForkChoiceError::AttestationForFutureBlock {
+                slot: data.slot,
+                root: data.beacon_block_root,
+            }
+            .into())
+        }
+        if target.root != self.get_checkpoint_block(data.beacon_block_root, target.epoch)? {
+            let target = target.root;
+            return Err(ForkChoiceError::InconsistentAttestationTarget { target }.into())
+        }
+        // attestations can only affect the fork choice of subsequent slots
+        if self.get_current_slot() < data.slot + 1 {
+            return Err(ForkChoiceError::PrematureAttestation { slot: data.slot }.into())
+        }
+        Ok(())
+    }
+
+    // Store the state of the checkpoint `target`, advancing the state of its block to the start
+    // of the epoch if needed.
+    pub(super) fn store_target_checkpoint_state(&mut self, target: &Checkpoint) -> Result<()> {
+        let key = HashTreeRootCached::new(target.clone())?;
+        if self.checkpoint_states.contains_key(&key) {
+            return Ok(())
+        }
+        let base_state = self.block_state(&target.root)?.clone();
+        let target_slot = compute_start_slot_at_epoch(target.epoch, &self.context);
+        let state = if base_state.slot() < target_slot {
+            let mut executor = self.executor(base_state);
+            executor.process_slots(target_slot)?;
+            executor.state.into_inner()
+        } else {
+            base_state
+        };
+        self.checkpoint_states.insert(key, state);
+        Ok(())
+    }
+
+    fn update_latest_messages(
+        &mut self,
+        attesting_indices: &[ValidatorIndex],
+        data: &AttestationData,
+    ) {
+        let target = &data.target;
+        let root = data.beacon_block_root;
+        for &index in attesting_indices {
+            if self.equivocating_indices.contains(&index) {
+                continue
+            }
+            let is_newer = self
+                .latest_messages
+                .get(&index)
+                .map_or(true, |message| target.epoch > message.epoch);
+            if is_newer {
+                self.latest_messages.insert(index, LatestMessage { epoch: target.epoch, root });
+            }
+        }
+    }
+
+    /// Count the votes of `attestation`, received either over the network or in a block.
+    ///
+    /// Attestations of the target epoch of an Electra state must be given to
+    /// [`Self::on_electra_attestation`] instead.
+    pub fn on_attestation(
+        &mut self,
+        attestation: &Attestation<MAX_VALIDATORS_PER_COMMITTEE>,
+        is_from_block: bool,
+    ) -> Result<()> {
+        self.validate_on_attestation(&attestation.data, is_from_block)?;
+
+        let target = &attestation.data.target;
+        self.store_target_checkpoint_state(target)?;
+        let target_state = self.checkpoint_state(target)?;
+        let indexed_attestation = for_fork!(target_state, state => {
+            let indexed_attestation =
+                spec::get_indexed_attestation(state, attestation, &self.context)?;
+            spec::is_valid_indexed_attestation(state, &indexed_attestation, &self.context)?;
+            indexed_attestation
+        }, Electra(_) => {
+            return Err(WrongFork { expected: Fork::Deneb, found: Fork::Electra }.into())
+        });
+
+        self.update_latest_messages(&indexed_attestation.attesting_indices, &attestation.data);
+        Ok(())
+    }
+
+    /// Count the votes of the Electra `attestation`, received either over the network or in a
+    /// block.
+    pub fn on_electra_attestation(
+        &mut self,
+        attestation: &electra::Attestation<MAX_VALIDATORS_PER_SLOT, MAX_COMMITTEES_PER_SLOT>,
+        is_from_block: bool,
+    ) -> Result<()> {
+        self.validate_on_attestation(&attestation.data, is_from_block)?;
+
+        let target = &attestation.data.target;
+        self.store_target_checkpoint_state(target)?;
+        let state = self.checkpoint_state(target)?.as_electra()?;
+        let indexed_attestation =
+            electra::get_indexed_attestation(state, attestation, &self.context)?;
+        electra::is_valid_indexed_attestation(state, &indexed_attestation, &self.context)?;
+
+        self.update_latest_messages(&indexed_attestation.attesting_indices, &attestation.data);
+        Ok(())
+    }
+
+    /// Mark the validators slashed by `attester_slashing` as equivocating, discounting their
+    /// votes from the fork choice.
+    pub fn on_attester_slashing(
+        &mut self,
+        attester_slashing: &AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>,
+    ) -> Result<()> {
+        let attestation_1 = &attester_slashing.attestation_1;
+        let attestation_2 = &attester_slashing.attestation_2;
+        if !is_slashable_attestation_data(&attestation_1.data, &attestation_2.data) {
+            return Err(ForkChoiceError::NotSlashable.into())
+        }
+        let state = self.block_state(&self.justified_checkpoint.root)?;
+        for_fork!(state, state => {
+            spec::is_valid_indexed_attestation(state, attestation_1, &self.context)?;
+            spec::is_valid_indexed_attestation(state, attestation_2, &self.context)?;
+        }, Electra(_) => {
+            return Err(WrongFork { expected: Fork::Deneb, found: Fork::Electra }.into())
+        });
+
+        self.update_equivocating_indices(
+            &attestation_1.attesting_indices,
+            &attestation_2.attesting_indices,
+        );
+        Ok(())
+    }
+
+    /// Mark the validators slashed by the Electra `attester_slashing` as equivocating,
+    /// discounting their votes from the fork choice.
+    pub fn on_electra_attester_slashing(
+        &mut self,
+        attester_slashing: &electra::AttesterSlashing<MAX_VALIDATORS_PER_SLOT>,
+    ) -> Result<()> {
+        let attestation_1 = &attester_slashing.attestation_1;
+        let attestation_2 = &attester_slashing.attestation_2;
+        if !is_slashable_attestation_data(&attestation_1.data, &attestation_2.data) {
+            return Err(ForkChoiceError::NotSlashable.into())
+        }
+        let state = self.block_state(&self.justified_checkpoint.root)?.as_electra()?;
+        electra::is_valid_indexed_attestation(state, attestation_1, &self.context)?;
+        electra::is_valid_indexed_attestation(state, attestation_2, &self.context)?;
+
+        self.update_equivocating_indices(
+            &attestation_1.attesting_indices,
+            &attestation_2.attesting_indices,
+        );
+        Ok(())
+    }
+
+    fn update_equivocating_indices(
+        &mut self,
+        attesting_indices_1: &[ValidatorIndex],
+        attesting_indices_2: &[ValidatorIndex],
+    ) {
+        let indices = attesting_indices_1.iter().collect::<HashSet<_>>();
+        for &index in attesting_indices_2 {
+            if indices.contains(&index) {
+                self.equivocating_indices.insert(index);
+            }
+        }
+    }
+}
+
+fn calculate_committee_fraction<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    committee_percent: u64,
+    context: &Context,
+) -> Gwei {
+    let current_epoch = compute_epoch_at_slot(state.slot(), context);
+    let total_active_balance = state
+        .validators()
+        .iter()
+        .filter(|validator| is_active_validator(validator, current_epoch))
+        .map(|validator| validator.effective_balance)
+        .sum::<Gwei>()
+        .max(context.effective_balance_increment);
+    let committee_weight = total_active_balance / context.slots_per_epoch;
+    committee_weight * committee_percent / 100
+}
+
+// Determine if `signed_block` is the first block with an execution payload, i.e. follows
+// `is_merge_transition_block` with `pre_state` as the state before the block.
+fn is_merge_transition_block<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    pre_state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    signed_block: &SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >,
+) -> bool {
+    let is_merge_transition_complete = match pre_state {
+        BeaconState::Phase0(_) | BeaconState::Altair(_) => false,
+        BeaconState::Bellatrix(state) => bellatrix::is_merge_transition_complete(state),
+        BeaconState::Capella(state) => capella::is_merge_transition_complete(state),
+        BeaconState::Deneb(state) => deneb::is_merge_transition_complete(state),
+        BeaconState::Electra(state) => electra::is_merge_transition_complete(state),
+    };
+    let has_execution_payload = match signed_block {
+        SignedBeaconBlock::Phase0(_) | SignedBeaconBlock::Altair(_) => false,
+        SignedBeaconBlock::Bellatrix(block) => {
+            block.message.body.execution_payload != Default::default()
+        }
+        SignedBeaconBlock::Capella(block) => {
+            block.message.body.execution_payload != Default::default()
+        }
+        SignedBeaconBlock::Deneb(block) => {
+            block.message.body.execution_payload != Default::default()
+        }
+        SignedBeaconBlock::Electra(block) => {
+            block.message.body.execution_payload != Default::default()
+        }
+    };
+    !is_merge_transition_complete && has_execution_payload
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::phase0::{genesis_state, signed_block, BLOCK_SLOT},
+        fork_choice::minimal::Store,
+        phase0::minimal,
+    };
+
+    fn genesis_store() -> (Store, Root) {
+        let context = Context::for_minimal();
+        let state = genesis_state();
+        let mut anchor_block = state.latest_block_header.clone();
+        anchor_block.state_root = state.hash_tree_root().unwrap();
+        let anchor_root = anchor_block.hash_tree_root().unwrap();
+        let store = Store::new(BeaconState::Phase0(state), anchor_block, context).unwrap();
+        (store, anchor_root)
+    }
+
+    fn slot_start(store: &Store, slot: Slot) -> u64 {
+        store.genesis_time + slot * store.context.seconds_per_slot
+    }
+
+    #[test]
+    fn test_store_from_anchor() {
+        let (store, anchor_root) = genesis_store();
+        assert_eq!(store.get_current_slot(), GENESIS_SLOT);
+        assert_eq!(
+            store.justified_checkpoint,
+            Checkpoint { epoch: GENESIS_EPOCH, root: anchor_root }
+        );
+        assert_eq!(store.finalized_checkpoint, store.justified_checkpoint);
+        assert_eq!(store.get_head().unwrap(), anchor_root);
+        let justified_state = store.checkpoint_state(&store.justified_checkpoint).unwrap();
+        assert_eq!(justified_state.slot(), GENESIS_SLOT);
+        let unknown = Checkpoint { epoch: 1, root: anchor_root };
+        assert!(matches!(
+            store.checkpoint_state(&unknown),
+            Err(crate::Error::ForkChoice(ForkChoiceError::UnknownCheckpointState { .. }))
+        ));
+
+        let state = genesis_state();
+        let result = Store::new(
+            BeaconState::Phase0(state.clone()),
+            state.latest_block_header,
+            store.context,
+        );
+        assert!(matches!(result, Err(crate::Error::ForkChoice(ForkChoiceError::InvalidAnchor))));
+    }
+
+    #[test]
+    fn test_on_block_updates_head_and_proposer_boost() {
+        let (mut store, anchor_root) = genesis_store();
+        let block = SignedBeaconBlock::Phase0(signed_block());
+        let block_root = block.message().hash_tree_root().unwrap();
+
+        let result = store.on_block(&block);
+        assert!(matches!(
+            result,
+            Err(crate::Error::ForkChoice(ForkChoiceError::FutureBlock { slot: BLOCK_SLOT, .. }))
+        ));
+
+        store.on_tick(slot_start(&store, BLOCK_SLOT));
+        store.on_block(&block).unwrap();
+        assert_eq!(store.block(&block_root).unwrap().parent_root, anchor_root);
+        assert!(store.block_timeliness[&block_root]);
+        assert_eq!(store.proposer_boost_root, block_root);
+        assert_eq!(store.get_head().unwrap(), block_root);
+        let boost = store.get_weight(&block_root).unwrap();
+        assert!(boost > 0);
+
+        // the boost only lasts for the slot of the block
+        store.on_tick(slot_start(&store, BLOCK_SLOT + 1));
+        assert_eq!(store.proposer_boost_root, Root::default());
+        assert_eq!(store.get_weight(&block_root).unwrap(), 0);
+        assert_eq!(store.get_head().unwrap(), block_root);
+    }
+
+    #[test]
+    fn test_on_attestation_and_attester_slashing() {
+        let (mut store, anchor_root) = genesis_store();
+        let block = signed_block();
+        store.on_tick(slot_start(&store, BLOCK_SLOT));
+        store.on_block(&SignedBeaconBlock::Phase0(block.clone())).unwrap();
+
+        let attestation: &minimal::Attestation = &block.message.body.attestations[0];
+        store.on_attestation(attestation, true).unwrap();
+        let attesters = store.latest_messages.len();
+        assert!(attesters > 0);
+        assert!(store.latest_messages.values().all(|message| message.root == anchor_root));
+
+        let mut premature = attestation.clone();
+        premature.data.slot = BLOCK_SLOT;
+        let result = store.on_attestation(&premature, false);
+        assert!(matches!(
+            result,
+            Err(crate::Error::ForkChoice(ForkChoiceError::PrematureAttestation { .. }))
+        ));
+
+        let attester_slashing = &block.message.body.attester_slashings[0];
+        store.on_attester_slashing(attester_slashing).unwrap();
+        assert_eq!(store.equivocating_indices.len(), 2);
+        let mut not_slashable = attester_slashing.clone();
+        not_slashable.attestation_2 = not_slashable.attestation_1.clone();
+        let result = store.on_attester_slashing(&not_slashable);
+        assert!(matches!(result, Err(crate::Error::ForkChoice(ForkChoiceError::NotSlashable))));
+    }
+
+    #[test]
+    fn test_electra_attestation_needs_electra_state() {
+        let (mut store, _) = genesis_store();
+        let block = signed_block();
+        store.on_tick(slot_start(&store, BLOCK_SLOT));
+        store.on_block(&SignedBeaconBlock::Phase0(block.clone())).unwrap();
+
+        let attestation = &block.message.body.attestations[0];
+        let electra_attestation = electra::minimal::Attestation {
+            data: attestation.data.clone(),
+            signature: attestation.signature.clone(),
+            ..Default::default()
+        };
+        let result = store.on_electra_attestation(&electra_attestation, true);
+        let expected = WrongFork { expected: Fork::Electra, found: Fork::Phase0 };
+        assert!(matches!(result, Err(crate::Error::WrongFork(err)) if err == expected));
+        assert!(store.latest_messages.is_empty());
+    }
+}
@@ -0,0 +1,224 @@
+//! Choosing the genesis time of a local devnet, so that every consensus client agrees on it and
+//! its slots line up with the blocks of the execution layer genesis.
+use crate::{state_transition::Context, Error};
+use std::time::Duration;
+use thiserror::Error;
+
+/// How long before the present the execution layer genesis may be. An older one most likely
+/// comes from a genesis file left over from an earlier run.
+pub const MAX_EL_GENESIS_AGE: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Error)]
+pub enum GenesisTimeError {
+    #[error("slot alignment must be at least one second")]
+    ZeroSlotAlignment,
+    #[error(
+        "execution layer genesis at {el_genesis_time} is more than {:?} before {now:?}",
+        MAX_EL_GENESIS_AGE
+    )]
+    StaleElGenesis { el_genesis_time: u64, now: Duration },
+    #[error("genesis time {genesis_time} is before MIN_GENESIS_TIME {min_genesis_time}")]
+    BeforeMinGenesisTime { genesis_time: u64, min_genesis_time: u64 },
+    #[error("genesis time {genesis_time} is before GENESIS_DELAY {genesis_delay} after the epoch")]
+    BeforeGenesisDelay { genesis_time: u64, genesis_delay: u64 },
+}
+
+/// The earliest genesis time at least `min_genesis_delay` seconds after `now` (a duration since
+/// the unix epoch) that is a whole number of `slot_alignment` seconds after the execution layer
+/// genesis at `el_genesis_time`, and not before it.
+///
+/// A fraction of a second in `now` rounds up, so the result only depends on the second `now`
+/// falls in and every orchestrator computing it within that second agrees.
+pub fn compute_aligned_genesis_time(
+    now: Duration,
+    el_genesis_time: u64,
+    min_genesis_delay: u64,
+    slot_alignment: u64,
+) -> Result<u64, Error> {
+    if slot_alignment == 0 {
+        return Err(GenesisTimeError::ZeroSlotAlignment.into())
+    }
+    let el_genesis_deadline = Duration::from_secs(el_genesis_time)
+        .checked_add(MAX_EL_GENESIS_AGE)
+        .ok_or(Error::Overflow)?;
+    if el_genesis_deadline < now {
+        return Err(GenesisTimeError::StaleElGenesis { el_genesis_time, now }.into())
+    }
+    let now = now.as_secs() + u64::from(now.subsec_nanos() > 0);
+    let earliest = now.checked_add(min_genesis_delay).ok_or(Error::Overflow)?;
+    if earliest <= el_genesis_time {
+        return Ok(el_genesis_time)
+    }
+    let steps = (earliest - el_genesis_time).div_ceil(slot_alignment);
+    steps
+        .checked_mul(slot_alignment)
+        .and_then(|offset| el_genesis_time.checked_add(offset))
+        .ok_or(Error::Overflow)
+}
+
+/// Check that a genesis state with `genesis_time` is valid under `context`, returning the
+/// timestamp of the eth1 block to initialize it from (`genesis_time` is `GENESIS_DELAY` after
+/// that block).
+pub fn validate_genesis_time(genesis_time: u64, context: &Context) -> Result<u64, Error> {
+    if genesis_time < context.min_genesis_time {
+        return Err(GenesisTimeError::BeforeMinGenesisTime {
+            genesis_time,
+            min_genesis_time: context.min_genesis_time,
+        }
+        .into())
+    }
+    genesis_time.checked_sub(context.genesis_delay).ok_or_else(|| {
+        GenesisTimeError::BeforeGenesisDelay { genesis_time, genesis_delay: context.genesis_delay }
+            .into()
+    })
+}
+
+/// The genesis time of a devnet, with the values that configure other clients for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GenesisTime {
+    pub genesis_time: u64,
+    /// The timestamp of the eth1 block the genesis state is initialized from.
+    pub eth1_timestamp: u64,
+    pub min_genesis_time: u64,
+    pub genesis_delay: u64,
+}
+
+impl GenesisTime {
+    /// Align the genesis time as in [`compute_aligned_genesis_time`], with slots of `context`,
+    /// and validate it as in [`validate_genesis_time`].
+    pub fn aligned(
+        now: Duration,
+        el_genesis_time: u64,
+        min_genesis_delay: u64,
+        context: &Context,
+    ) -> Result<Self, Error> {
+        let genesis_time = compute_aligned_genesis_time(
+            now,
+            el_genesis_time,
+            min_genesis_delay,
+            context.seconds_per_slot,
+        )?;
+        let eth1_timestamp = validate_genesis_time(genesis_time, context)?;
+        Ok(Self {
+            genesis_time,
+            eth1_timestamp,
+            min_genesis_time: context.min_genesis_time,
+            genesis_delay: context.genesis_delay,
+        })
+    }
+
+    /// The command line arguments setting the genesis time of clients that take it directly.
+    pub fn genesis_time_args(&self) -> [String; 2] {
+        ["--genesis-time".to_string(), self.genesis_time.to_string()]
+    }
+
+    /// The lines of a config file (in the format of `configs/*.yaml`) under which a genesis
+    /// state initialized from the eth1 block at `eth1_timestamp` is valid.
+    pub fn config_fragment(&self) -> String {
+        format!(
+            "MIN_GENESIS_TIME: {}\nGENESIS_DELAY: {}\n",
+            self.min_genesis_time, self.genesis_delay
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_aligned_genesis_time() {
+        let el_genesis_time = 1_700_000_000;
+        let at = |secs: u64, nanos: u32| Duration::new(secs, nanos);
+
+        // an execution layer genesis far enough ahead is kept as it is
+        assert_eq!(
+            compute_aligned_genesis_time(at(el_genesis_time - 60, 0), el_genesis_time, 30, 12)
+                .unwrap(),
+            el_genesis_time
+        );
+        // exactly the minimum delay ahead, and a nanosecond too late for it
+        let now = el_genesis_time - 30;
+        assert_eq!(
+            compute_aligned_genesis_time(at(now, 0), el_genesis_time, 30, 12).unwrap(),
+            el_genesis_time
+        );
+        assert_eq!(
+            compute_aligned_genesis_time(at(now, 1), el_genesis_time, 30, 12).unwrap(),
+            el_genesis_time + 12
+        );
+        // the fraction of a second does not matter past the first nanosecond
+        assert_eq!(
+            compute_aligned_genesis_time(at(now, 999_999_999), el_genesis_time, 30, 12).unwrap(),
+            el_genesis_time + 12
+        );
+        // a delay ending on a later slot boundary, and one just past it
+        assert_eq!(
+            compute_aligned_genesis_time(at(el_genesis_time, 0), el_genesis_time, 24, 12).unwrap(),
+            el_genesis_time + 24
+        );
+        assert_eq!(
+            compute_aligned_genesis_time(at(el_genesis_time, 0), el_genesis_time, 25, 12).unwrap(),
+            el_genesis_time + 36
+        );
+
+        // a recent execution layer genesis is caught up with, an older one rejected
+        let now = at(el_genesis_time, 0) + MAX_EL_GENESIS_AGE;
+        assert_eq!(
+            compute_aligned_genesis_time(now, el_genesis_time, 0, 12).unwrap(),
+            el_genesis_time + 300
+        );
+        assert!(matches!(
+            compute_aligned_genesis_time(now + Duration::from_nanos(1), el_genesis_time, 0, 12),
+            Err(Error::GenesisTime(GenesisTimeError::StaleElGenesis { .. }))
+        ));
+        assert!(matches!(
+            compute_aligned_genesis_time(at(0, 0), el_genesis_time, 0, 0),
+            Err(Error::GenesisTime(GenesisTimeError::ZeroSlotAlignment))
+        ));
+        assert!(matches!(
+            compute_aligned_genesis_time(at(0, 0), u64::MAX, 0, 12),
+            Err(Error::Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_validate_genesis_time() {
+        let context = Context::for_minimal();
+        let genesis_time = context.min_genesis_time + 7;
+        assert_eq!(
+            validate_genesis_time(genesis_time, &context).unwrap(),
+            genesis_time - context.genesis_delay
+        );
+        assert!(matches!(
+            validate_genesis_time(context.min_genesis_time - 1, &context),
+            Err(Error::GenesisTime(GenesisTimeError::BeforeMinGenesisTime { .. }))
+        ));
+
+        let mut context = Context::for_minimal();
+        context.min_genesis_time = 0;
+        assert!(matches!(
+            validate_genesis_time(context.genesis_delay - 1, &context),
+            Err(Error::GenesisTime(GenesisTimeError::BeforeGenesisDelay { .. }))
+        ));
+
+        let context = Context::for_minimal();
+        let el_genesis_time = context.min_genesis_time + 100;
+        let now = Duration::from_secs(el_genesis_time) + Duration::from_millis(1500);
+        let genesis = GenesisTime::aligned(now, el_genesis_time, 11, &context).unwrap();
+        // 13 seconds after the execution layer genesis at the earliest, in slots of 6 seconds
+        assert_eq!(genesis.genesis_time, el_genesis_time + 3 * context.seconds_per_slot);
+        assert_eq!(genesis.eth1_timestamp + context.genesis_delay, genesis.genesis_time);
+        assert_eq!(
+            genesis.genesis_time_args(),
+            ["--genesis-time".to_string(), genesis.genesis_time.to_string()]
+        );
+        assert_eq!(
+            genesis.config_fragment(),
+            format!(
+                "MIN_GENESIS_TIME: {}\nGENESIS_DELAY: {}\n",
+                context.min_genesis_time, context.genesis_delay
+            )
+        );
+    }
+}
@@ -0,0 +1,803 @@
+//! Validation of unaggregated attestations received on the `beacon_attestation_{subnet_id}`
+//! gossip topics without holding a state: the chain is described by the caller through the
+//! shufflings it has cached and a [`CheckpointLookup`] over the blocks it has seen, e.g. its
+//! fork choice [`Store`].
+//!
+//! The rules that depend on what the caller has seen (duplicate attestations, the validity of the
+//! block voted for, the subnet the attestation arrived on) are left to the caller.
+use crate::{
+    crypto::{verify_signature_decompressed, DecompressedPublicKey},
+    electra,
+    fork_choice::Store,
+    phase0::{
+        self, compute_domain, compute_epoch_at_slot,
+        networking::ATTESTATION_PROPAGATION_SLOT_RANGE, AttestationData,
+    },
+    primitives::{
+        BlsPublicKey, BlsSignature, CommitteeIndex, DomainType, Epoch, Root, Slot, ValidatorIndex,
+    },
+    signing::compute_signing_root,
+    ssz::prelude::Bitlist,
+    state_transition::{Context, DecompressedPubkeyCache, ShufflingCache},
+    Error,
+};
+use std::{borrow::Cow, collections::BTreeMap};
+
+/// A source of the checkpoints of the blocks the caller has seen, on any fork.
+pub trait CheckpointLookup {
+    /// The root of the latest block at or before the first slot of `epoch` in the chain of the
+    /// block with `block_root`, i.e. `get_checkpoint_block(store, block_root, epoch)`, or `None`
+    /// if the block (or one of its ancestors down to that slot) has not been seen.
+    fn checkpoint_block_root(&self, block_root: Root, epoch: Epoch) -> Option<Root>;
+}
+
+impl<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+    > CheckpointLookup
+    for Store<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_PROPOSER_SLASHINGS,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >
+{
+    fn checkpoint_block_root(&self, block_root: Root, epoch: Epoch) -> Option<Root> {
+        self.get_checkpoint_block(block_root, epoch).ok()
+    }
+}
+
+/// An attestation in any of the formats propagated on the attestation subnets.
+pub trait GossipAttestation {
+    fn data(&self) -> &AttestationData;
+
+    /// The index of the committee the attestation is from, or why the attestation does not
+    /// name exactly one.
+    fn committee_index(&self) -> Result<CommitteeIndex, RejectReason>;
+
+    /// The single attester in `committee` the attestation is from, or why it does not name
+    /// exactly one member of the committee.
+    fn attester(&self, committee: &[ValidatorIndex]) -> Result<ValidatorIndex, RejectReason>;
+
+    fn signature(&self) -> &BlsSignature;
+}
+
+// The attester of an attestation carrying aggregation bits over `committee`.
+fn attester_from_bits<const N: usize>(
+    aggregation_bits: &Bitlist<N>,
+    committee: &[ValidatorIndex],
+) -> Result<ValidatorIndex, RejectReason> {
+    if aggregation_bits.len() != committee.len() {
+        return Err(RejectReason::AggregationBitsLength {
+            expected: committee.len(),
+            found: aggregation_bits.len(),
+        })
+    }
+    let positions = aggregation_bits
+        .iter()
+        .enumerate()
+        .filter(|(_, bit)| **bit)
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    match positions[..] {
+        [position] => Ok(committee[position]),
+        _ => Err(RejectReason::NotExactlyOneAttester(positions.len())),
+    }
+}
+
+impl<const MAX_VALIDATORS_PER_COMMITTEE: usize> GossipAttestation
+    for phase0::Attestation<MAX_VALIDATORS_PER_COMMITTEE>
+{
+    fn data(&self) -> &AttestationData {
+        &self.data
+    }
+
+    fn committee_index(&self) -> Result<CommitteeIndex, RejectReason> {
+        Ok(self.data.index)
+    }
+
+    fn attester(&self, committee: &[ValidatorIndex]) -> Result<ValidatorIndex, RejectReason> {
+        attester_from_bits(&self.aggregation_bits, committee)
+    }
+
+    fn signature(&self) -> &BlsSignature {
+        &self.signature
+    }
+}
+
+impl<const MAX_VALIDATORS_PER_SLOT: usize, const MAX_COMMITTEES_PER_SLOT: usize> GossipAttestation
+    for electra::Attestation<MAX_VALIDATORS_PER_SLOT, MAX_COMMITTEES_PER_SLOT>
+{
+    fn data(&self) -> &AttestationData {
+        &self.data
+    }
+
+    fn committee_index(&self) -> Result<CommitteeIndex, RejectReason> {
+        if self.data.index != 0 {
+            return Err(RejectReason::NonZeroDataIndex(self.data.index))
+        }
+        match electra::get_committee_indices(&self.committee_bits)[..] {
+            [index] => Ok(index),
+            ref indices => Err(RejectReason::CommitteeBitCount(indices.len())),
+        }
+    }
+
+    fn attester(&self, committee: &[ValidatorIndex]) -> Result<ValidatorIndex, RejectReason> {
+        attester_from_bits(&self.aggregation_bits, committee)
+    }
+
+    fn signature(&self) -> &BlsSignature {
+        &self.signature
+    }
+}
+
+// From electra on, unaggregated attestations propagate as `SingleAttestation`, naming the
+// attester by its validator index rather than by aggregation bits.
+impl GossipAttestation for electra::SingleAttestation {
+    fn data(&self) -> &AttestationData {
+        &self.data
+    }
+
+    fn committee_index(&self) -> Result<CommitteeIndex, RejectReason> {
+        if self.data.index != 0 {
+            return Err(RejectReason::NonZeroDataIndex(self.data.index))
+        }
+        Ok(self.committee_index)
+    }
+
+    fn attester(&self, committee: &[ValidatorIndex]) -> Result<ValidatorIndex, RejectReason> {
+        if committee.contains(&self.attester_index) {
+            Ok(self.attester_index)
+        } else {
+            Err(RejectReason::AttesterNotInCommittee(self.attester_index))
+        }
+    }
+
+    fn signature(&self) -> &BlsSignature {
+        &self.signature
+    }
+}
+
+/// What is known of the chain at the time of validation.
+#[derive(Debug, Clone, Copy)]
+pub struct HeadInfo<'a> {
+    /// The current slot; allowing for `MAXIMUM_GOSSIP_CLOCK_DISPARITY` is left to the caller.
+    pub current_slot: Slot,
+    /// The shufflings of the epochs attestations are accepted for.
+    pub shufflings: &'a ShufflingCache,
+    /// The decision roots of the shufflings of those epochs on the chain of the head, see
+    /// `shuffling_decision_root`.
+    pub shuffling_decision_roots: &'a BTreeMap<Epoch, Root>,
+    /// What is needed to verify signatures, which are not verified without it.
+    pub signatures: Option<SignatureInfo<'a>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SignatureInfo<'a> {
+    pub fork: &'a phase0::Fork,
+    pub genesis_validators_root: Root,
+    /// The public keys of the validator registry, by validator index.
+    pub public_keys: &'a [BlsPublicKey],
+    pub pubkey_cache: &'a DecompressedPubkeyCache,
+}
+
+/// The outcome of gossip validation, as reported back to the gossip layer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Validity {
+    Accept,
+    Ignore(IgnoreReason),
+    Reject(RejectReason),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IgnoreReason {
+    FutureSlot {
+        slot: Slot,
+        current_slot: Slot,
+    },
+    PastSlot {
+        slot: Slot,
+        current_slot: Slot,
+    },
+    UnknownShuffling(Epoch),
+    /// The block voted for, `beacon_block_root`, has not been seen.
+    UnknownBlock(Root),
+    /// The attester is not in the provided registry, e.g. as the registry is stale.
+    UnknownValidator(ValidatorIndex),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RejectReason {
+    /// An electra attestation sets `data.index`, which must be zero from electra on.
+    NonZeroDataIndex(CommitteeIndex),
+    /// An electra attestation does not set exactly one committee bit.
+    CommitteeBitCount(usize),
+    CommitteeIndexOutOfRange {
+        index: CommitteeIndex,
+        committees_per_slot: usize,
+    },
+    TargetEpochMismatch {
+        slot: Slot,
+        target_epoch: Epoch,
+    },
+    AggregationBitsLength {
+        expected: usize,
+        found: usize,
+    },
+    /// The attestation is aggregated (or empty) rather than from a single attester.
+    NotExactlyOneAttester(usize),
+    /// A `SingleAttestation` names an attester outside of the committee.
+    AttesterNotInCommittee(ValidatorIndex),
+    WrongTargetRoot {
+        expected: Root,
+        found: Root,
+    },
+    InvalidSignature,
+}
+
+// The slot window of the p2p specs: from deneb on, attestations from the whole previous epoch
+// propagate rather than those of the last `ATTESTATION_PROPAGATION_SLOT_RANGE` slots.
+fn check_slot_window(slot: Slot, current_slot: Slot, context: &Context) -> Option<IgnoreReason> {
+    if slot > current_slot {
+        return Some(IgnoreReason::FutureSlot { slot, current_slot })
+    }
+    let current_epoch = compute_epoch_at_slot(current_slot, context);
+    let is_past = if current_epoch >= context.deneb_fork_epoch {
+        compute_epoch_at_slot(slot, context) + 1 < current_epoch
+    } else {
+        slot + (ATTESTATION_PROPAGATION_SLOT_RANGE as Slot) < current_slot
+    };
+    is_past.then_some(IgnoreReason::PastSlot { slot, current_slot })
+}
+
+/// Validate `attestation` received on an attestation subnet, following the p2p rules for the
+/// slot window, the consistency of the target epoch, the committee and its aggregation bits,
+/// the block voted for and the target root in its chain and, if `head_info` carries what is
+/// needed, the signature.
+pub fn validate_attestation(
+    attestation: &impl GossipAttestation,
+    head_info: &HeadInfo,
+    checkpoints: &impl CheckpointLookup,
+    context: &Context,
+) -> Result<Validity, Error> {
+    let data = attestation.data();
+    let index = match attestation.committee_index() {
+        Ok(index) => index,
+        Err(reason) => return Ok(Validity::Reject(reason)),
+    };
+    if let Some(reason) = check_slot_window(data.slot, head_info.current_slot, context) {
+        return Ok(Validity::Ignore(reason))
+    }
+    let epoch = compute_epoch_at_slot(data.slot, context);
+    if data.target.epoch != epoch {
+        let reason =
+            RejectReason::TargetEpochMismatch { slot: data.slot, target_epoch: data.target.epoch };
+        return Ok(Validity::Reject(reason))
+    }
+
+    let shuffling = head_info
+        .shuffling_decision_roots
+        .get(&epoch)
+        .and_then(|&decision_root| head_info.shufflings.get(epoch, decision_root));
+    let Some(shuffling) = shuffling else {
+        return Ok(Validity::Ignore(IgnoreReason::UnknownShuffling(epoch)))
+    };
+    let committees_per_slot = shuffling.committee_count_per_slot(context);
+    if index >= committees_per_slot {
+        let reason = RejectReason::CommitteeIndexOutOfRange { index, committees_per_slot };
+        return Ok(Validity::Reject(reason))
+    }
+    let committee = shuffling.beacon_committee(data.slot, index, context)?;
+    let attester = match attestation.attester(committee) {
+        Ok(attester) => attester,
+        Err(reason) => return Ok(Validity::Reject(reason)),
+    };
+
+    let Some(expected) = checkpoints.checkpoint_block_root(data.beacon_block_root, epoch) else {
+        return Ok(Validity::Ignore(IgnoreReason::UnknownBlock(data.beacon_block_root)))
+    };
+    if data.target.root != expected {
+        let reason = RejectReason::WrongTargetRoot { expected, found: data.target.root };
+        return Ok(Validity::Reject(reason))
+    }
+
+    if let Some(info) = &head_info.signatures {
+        let Some(public_key) = info.public_keys.get(attester) else {
+            return Ok(Validity::Ignore(IgnoreReason::UnknownValidator(attester)))
+        };
+        let public_key = match info.pubkey_cache.get(attester, public_key) {
+            Some(public_key) => Cow::Borrowed(public_key),
+            None => Cow::Owned(DecompressedPublicKey::try_from(public_key)?),
+        };
+        let fork_version = if epoch < info.fork.epoch {
+            info.fork.previous_version
+        } else {
+            info.fork.current_version
+        };
+        let domain = compute_domain(
+            DomainType::BeaconAttester,
+            Some(fork_version),
+            Some(info.genesis_validators_root),
+            context,
+        )?;
+        let signing_root = compute_signing_root(data, domain)?;
+        let signature = attestation.signature();
+        if verify_signature_decompressed(&public_key, signing_root.as_ref(), signature).is_err() {
+            return Ok(Validity::Reject(RejectReason::InvalidSignature))
+        }
+    }
+    Ok(Validity::Accept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::{phase0::genesis_state, secret_keys},
+        fork_choice::minimal::Store,
+        phase0::{get_domain, minimal, BeaconBlockHeader, Checkpoint},
+        signing::sign_with_domain,
+        ssz::prelude::*,
+        state_transition::{
+            compute_shuffling, shuffling_decision_root, PubkeyCacheMode, Shuffling,
+        },
+        types::BeaconState,
+    };
+
+    // attestations are from the first slot of epoch 1, validated in the slot after
+    const SLOT: Slot = 8;
+
+    struct Chain {
+        context: Context,
+        state: minimal::BeaconState,
+        store: Store,
+        anchor_root: Root,
+        shufflings: ShufflingCache,
+        decision_roots: BTreeMap<Epoch, Root>,
+        public_keys: Vec<BlsPublicKey>,
+        pubkey_cache: DecompressedPubkeyCache,
+    }
+
+    impl Chain {
+        fn new() -> Self {
+            let context = Context::for_minimal();
+            let mut state = genesis_state();
+            phase0::process_slots(&mut state, SLOT + 1, &context).unwrap();
+            let polymorphic = BeaconState::Phase0(state.clone());
+            // the chain is the genesis block followed by empty slots
+            let genesis = genesis_state();
+            let mut anchor_block = genesis.latest_block_header.clone();
+            anchor_block.state_root = genesis.hash_tree_root().unwrap();
+            let anchor_root = anchor_block.hash_tree_root().unwrap();
+            let store =
+                Store::new(BeaconState::Phase0(genesis), anchor_block, context.clone()).unwrap();
+            let decision_root = shuffling_decision_root(&polymorphic, 1, &context);
+            let mut shufflings = ShufflingCache::default();
+            shufflings.insert(decision_root, compute_shuffling(&polymorphic, 1, &context));
+            let decision_roots = BTreeMap::from([(1, decision_root)]);
+            let public_keys =
+                state.validators.iter().map(|validator| validator.public_key.clone()).collect();
+            let mut pubkey_cache = DecompressedPubkeyCache::new(PubkeyCacheMode::All);
+            pubkey_cache.warm(&state.validators, 1).unwrap();
+            Self {
+                context,
+                state,
+                store,
+                anchor_root,
+                shufflings,
+                decision_roots,
+                public_keys,
+                pubkey_cache,
+            }
+        }
+
+        fn head_info(&self) -> HeadInfo<'_> {
+            HeadInfo {
+                current_slot: SLOT + 1,
+                shufflings: &self.shufflings,
+                shuffling_decision_roots: &self.decision_roots,
+                signatures: Some(SignatureInfo {
+                    fork: &self.state.fork,
+                    genesis_validators_root: self.state.genesis_validators_root,
+                    public_keys: &self.public_keys,
+                    pubkey_cache: &self.pubkey_cache,
+                }),
+            }
+        }
+
+        fn shuffling(&self) -> &Shuffling {
+            self.shufflings.get(1, self.decision_roots[&1]).unwrap()
+        }
+
+        fn committee(&self, index: CommitteeIndex) -> &[ValidatorIndex] {
+            self.shuffling().beacon_committee(SLOT, index, &self.context).unwrap()
+        }
+
+        fn data(&self) -> AttestationData {
+            AttestationData {
+                slot: SLOT,
+                index: 0,
+                beacon_block_root: self.anchor_root,
+                source: self.state.current_justified_checkpoint.clone(),
+                target: Checkpoint { epoch: 1, root: self.anchor_root },
+            }
+        }
+
+        fn sign(&self, data: &AttestationData, attester: ValidatorIndex) -> BlsSignature {
+            let domain =
+                get_domain(&self.state, DomainType::BeaconAttester, Some(1), &self.context)
+                    .unwrap();
+            sign_with_domain(data, &secret_keys()[attester], domain).unwrap()
+        }
+
+        // an attestation from the committee member at `position`, signed over `data`
+        fn attestation(&self, data: AttestationData, position: usize) -> minimal::Attestation {
+            let committee = self.committee(0);
+            let mut bits = vec![false; committee.len()];
+            bits[position] = true;
+            let signature = self.sign(&data, committee[position]);
+            minimal::Attestation {
+                aggregation_bits: bits.as_slice().try_into().unwrap(),
+                data,
+                signature,
+            }
+        }
+
+        fn validate(&self, attestation: &impl GossipAttestation) -> Validity {
+            validate_attestation(attestation, &self.head_info(), &self.store, &self.context)
+                .unwrap()
+        }
+    }
+
+    #[test]
+    fn test_valid_attestation() {
+        let chain = Chain::new();
+        let attestation = chain.attestation(chain.data(), 1);
+        assert_eq!(chain.validate(&attestation), Validity::Accept);
+
+        // without signature information the signature is not checked
+        let mut attestation = attestation;
+        attestation.signature = BlsSignature::default();
+        let head_info = HeadInfo { signatures: None, ..chain.head_info() };
+        let validity = validate_attestation(&attestation, &head_info, &chain.store, &chain.context);
+        assert_eq!(validity.unwrap(), Validity::Accept);
+        // but is with it, whether the key is cached or not
+        assert_eq!(chain.validate(&attestation), Validity::Reject(RejectReason::InvalidSignature));
+        let pubkey_cache = DecompressedPubkeyCache::default();
+        let mut head_info = chain.head_info();
+        head_info.signatures.as_mut().unwrap().pubkey_cache = &pubkey_cache;
+        let attestation = chain.attestation(chain.data(), 1);
+        let validity = validate_attestation(&attestation, &head_info, &chain.store, &chain.context);
+        assert_eq!(validity.unwrap(), Validity::Accept);
+    }
+
+    #[test]
+    fn test_slot_window() {
+        let chain = Chain::new();
+        let attestation = chain.attestation(chain.data(), 0);
+        let validate = |current_slot| {
+            let head_info = HeadInfo { current_slot, ..chain.head_info() };
+            validate_attestation(&attestation, &head_info, &chain.store, &chain.context).unwrap()
+        };
+        assert_eq!(
+            validate(SLOT - 1),
+            Validity::Ignore(IgnoreReason::FutureSlot { slot: SLOT, current_slot: SLOT - 1 })
+        );
+        let last_slot = SLOT + ATTESTATION_PROPAGATION_SLOT_RANGE as Slot;
+        assert_eq!(validate(last_slot), Validity::Accept);
+        assert_eq!(
+            validate(last_slot + 1),
+            Validity::Ignore(IgnoreReason::PastSlot { slot: SLOT, current_slot: last_slot + 1 })
+        );
+
+        // from deneb on, the previous epoch is accepted in full
+        let mut context = chain.context.clone();
+        context.deneb_fork_epoch = 0;
+        let slots_per_epoch = context.slots_per_epoch;
+        let validate = |current_slot| {
+            let head_info = HeadInfo { current_slot, ..chain.head_info() };
+            validate_attestation(&attestation, &head_info, &chain.store, &context).unwrap()
+        };
+        assert_eq!(validate(3 * slots_per_epoch - 1), Validity::Accept);
+        assert!(matches!(
+            validate(3 * slots_per_epoch),
+            Validity::Ignore(IgnoreReason::PastSlot { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rejected_attestations() {
+        let chain = Chain::new();
+
+        let mut data = chain.data();
+        data.target.epoch = 0;
+        let attestation = chain.attestation(data, 0);
+        assert_eq!(
+            chain.validate(&attestation),
+            Validity::Reject(RejectReason::TargetEpochMismatch { slot: SLOT, target_epoch: 0 })
+        );
+
+        let committees_per_slot = chain.shuffling().committee_count_per_slot(&chain.context);
+        let mut data = chain.data();
+        data.index = committees_per_slot;
+        let attestation = chain.attestation(data, 0);
+        assert_eq!(
+            chain.validate(&attestation),
+            Validity::Reject(RejectReason::CommitteeIndexOutOfRange {
+                index: committees_per_slot,
+                committees_per_slot
+            })
+        );
+
+        let committee_size = chain.committee(0).len();
+        let mut attestation = chain.attestation(chain.data(), 0);
+        attestation.aggregation_bits =
+            vec![true; committee_size + 1].as_slice().try_into().unwrap();
+        assert_eq!(
+            chain.validate(&attestation),
+            Validity::Reject(RejectReason::AggregationBitsLength {
+                expected: committee_size,
+                found: committee_size + 1
+            })
+        );
+        for attesters in [0, 2] {
+            let mut bits = vec![false; committee_size];
+            bits[..attesters].iter_mut().for_each(|bit| *bit = true);
+            attestation.aggregation_bits = bits.as_slice().try_into().unwrap();
+            assert_eq!(
+                chain.validate(&attestation),
+                Validity::Reject(RejectReason::NotExactlyOneAttester(attesters))
+            );
+        }
+
+        let mut data = chain.data();
+        let expected = data.target.root;
+        data.target = Checkpoint { root: Root::try_from(&[1u8; 32][..]).unwrap(), ..data.target };
+        let found = data.target.root;
+        let attestation = chain.attestation(data, 0);
+        assert_eq!(
+            chain.validate(&attestation),
+            Validity::Reject(RejectReason::WrongTargetRoot { expected, found })
+        );
+
+        // signed by another member of the committee
+        let mut attestation = chain.attestation(chain.data(), 0);
+        attestation.signature = chain.sign(&attestation.data, chain.committee(0)[1]);
+        assert_eq!(chain.validate(&attestation), Validity::Reject(RejectReason::InvalidSignature));
+    }
+
+    #[test]
+    fn test_ignored_attestations() {
+        let chain = Chain::new();
+        let attestation = chain.attestation(chain.data(), 0);
+
+        let mut data = chain.data();
+        let unknown = Root::try_from([2u8; 32].as_ref()).unwrap();
+        data.beacon_block_root = unknown;
+        let unseen = chain.attestation(data, 0);
+        assert_eq!(chain.validate(&unseen), Validity::Ignore(IgnoreReason::UnknownBlock(unknown)));
+
+        let shufflings = ShufflingCache::default();
+        let head_info = HeadInfo { shufflings: &shufflings, ..chain.head_info() };
+        let validity = validate_attestation(&attestation, &head_info, &chain.store, &chain.context);
+        assert_eq!(validity.unwrap(), Validity::Ignore(IgnoreReason::UnknownShuffling(1)));
+
+        // the shuffling of a fork decided by another block is not used
+        let fork_roots = BTreeMap::from([(1, Root::try_from([1u8; 32].as_ref()).unwrap())]);
+        let head_info = HeadInfo { shuffling_decision_roots: &fork_roots, ..chain.head_info() };
+        let validity = validate_attestation(&attestation, &head_info, &chain.store, &chain.context);
+        assert_eq!(validity.unwrap(), Validity::Ignore(IgnoreReason::UnknownShuffling(1)));
+
+        let mut head_info = chain.head_info();
+        let public_keys = &chain.public_keys[..chain.committee(0)[0]];
+        head_info.signatures.as_mut().unwrap().public_keys = public_keys;
+        let validity = validate_attestation(&attestation, &head_info, &chain.store, &chain.context);
+        assert_eq!(
+            validity.unwrap(),
+            Validity::Ignore(IgnoreReason::UnknownValidator(chain.committee(0)[0]))
+        );
+    }
+
+    #[test]
+    fn test_target_in_chain_of_voted_block() {
+        let mut chain = Chain::new();
+        // a block in the first slot of the epoch is its own checkpoint block
+        let block = BeaconBlockHeader {
+            slot: SLOT,
+            parent_root: chain.anchor_root,
+            state_root: Root::try_from([3u8; 32].as_ref()).unwrap(),
+            ..Default::default()
+        };
+        let block_root = block.hash_tree_root().unwrap();
+        chain.store.blocks.insert(block_root, block);
+
+        let mut data = chain.data();
+        data.beacon_block_root = block_root;
+        let attestation = chain.attestation(data.clone(), 0);
+        assert_eq!(
+            chain.validate(&attestation),
+            Validity::Reject(RejectReason::WrongTargetRoot {
+                expected: block_root,
+                found: chain.anchor_root
+            })
+        );
+        data.target.root = block_root;
+        let attestation = chain.attestation(data, 0);
+        assert_eq!(chain.validate(&attestation), Validity::Accept);
+
+        // while a vote for its parent still targets the parent
+        let attestation = chain.attestation(chain.data(), 0);
+        assert_eq!(chain.validate(&attestation), Validity::Accept);
+    }
+
+    #[test]
+    fn test_adversarial_attestations() {
+        let chain = Chain::new();
+
+        let mut data = chain.data();
+        data.slot = Slot::MAX;
+        let attestation = chain.attestation(data, 0);
+        assert_eq!(
+            chain.validate(&attestation),
+            Validity::Ignore(IgnoreReason::FutureSlot { slot: Slot::MAX, current_slot: SLOT + 1 })
+        );
+
+        let mut data = chain.data();
+        data.target.epoch = Epoch::MAX;
+        let attestation = chain.attestation(data, 0);
+        assert_eq!(
+            chain.validate(&attestation),
+            Validity::Reject(RejectReason::TargetEpochMismatch {
+                slot: SLOT,
+                target_epoch: Epoch::MAX
+            })
+        );
+
+        let committees_per_slot = chain.shuffling().committee_count_per_slot(&chain.context);
+        let mut data = chain.data();
+        data.index = CommitteeIndex::MAX;
+        let attestation = chain.attestation(data, 0);
+        assert_eq!(
+            chain.validate(&attestation),
+            Validity::Reject(RejectReason::CommitteeIndexOutOfRange {
+                index: CommitteeIndex::MAX,
+                committees_per_slot
+            })
+        );
+    }
+
+    #[test]
+    fn test_electra_attestation() {
+        let chain = Chain::new();
+        // the genesis validators make up two committees per slot on the minimal preset
+        let committee_index = 1;
+        let committee = chain.committee(committee_index);
+        let data = chain.data();
+        let mut attestation = electra::minimal::Attestation {
+            aggregation_bits: vec![false; committee.len()].as_slice().try_into().unwrap(),
+            signature: chain.sign(&data, committee[0]),
+            data,
+            ..Default::default()
+        };
+        attestation.aggregation_bits.set(0, true);
+        attestation.committee_bits.set(committee_index, true);
+        assert_eq!(chain.validate(&attestation), Validity::Accept);
+
+        let mut invalid = attestation.clone();
+        invalid.data.index = committee_index;
+        assert_eq!(chain.validate(&invalid), Validity::Reject(RejectReason::NonZeroDataIndex(1)));
+
+        for count in [0, 2] {
+            let mut invalid = attestation.clone();
+            invalid.committee_bits = Default::default();
+            for index in 0..count {
+                invalid.committee_bits.set(index, true);
+            }
+            assert_eq!(
+                chain.validate(&invalid),
+                Validity::Reject(RejectReason::CommitteeBitCount(count))
+            );
+        }
+    }
+
+    #[test]
+    fn test_single_attestation() {
+        let chain = Chain::new();
+        let committee_index = 1;
+        let committee = chain.committee(committee_index);
+        let data = chain.data();
+        let attestation = electra::SingleAttestation {
+            committee_index,
+            attester_index: committee[0],
+            signature: chain.sign(&data, committee[0]),
+            data,
+        };
+        assert_eq!(chain.validate(&attestation), Validity::Accept);
+
+        let mut invalid = attestation.clone();
+        invalid.data.index = committee_index;
+        assert_eq!(chain.validate(&invalid), Validity::Reject(RejectReason::NonZeroDataIndex(1)));
+
+        let committees_per_slot = chain.shuffling().committee_count_per_slot(&chain.context);
+        let mut invalid = attestation.clone();
+        invalid.committee_index = committees_per_slot;
+        assert_eq!(
+            chain.validate(&invalid),
+            Validity::Reject(RejectReason::CommitteeIndexOutOfRange {
+                index: committees_per_slot,
+                committees_per_slot
+            })
+        );
+
+        // a member of another committee, even with its own signature
+        let outsider = chain.committee(0)[0];
+        let mut invalid = attestation.clone();
+        invalid.attester_index = outsider;
+        invalid.signature = chain.sign(&invalid.data, outsider);
+        assert_eq!(
+            chain.validate(&invalid),
+            Validity::Reject(RejectReason::AttesterNotInCommittee(outsider))
+        );
+
+        // signed by another member of the committee
+        let mut invalid = attestation;
+        invalid.signature = chain.sign(&invalid.data, committee[1]);
+        assert_eq!(chain.validate(&invalid), Validity::Reject(RejectReason::InvalidSignature));
+    }
+}
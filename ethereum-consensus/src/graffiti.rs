@@ -0,0 +1,144 @@
+//! Choosing the graffiti of a block at proposal time, e.g. to rotate through messages or to let
+//! operators change it without a restart.
+use crate::primitives::{BlsPublicKey, Bytes32, Slot};
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The graffiti for the block at `slot` proposed by the validator with `public_key`.
+pub trait GraffitiProvider {
+    fn graffiti(&self, slot: Slot, public_key: &BlsPublicKey) -> Bytes32;
+}
+
+impl<P: GraffitiProvider + ?Sized> GraffitiProvider for &P {
+    fn graffiti(&self, slot: Slot, public_key: &BlsPublicKey) -> Bytes32 {
+        (**self).graffiti(slot, public_key)
+    }
+}
+
+/// The graffiti encoding `text`, truncated to the last character that fits in 32 bytes and
+/// padded with zeros.
+pub fn graffiti_from_str(text: &str) -> Bytes32 {
+    let mut end = text.len().min(32);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let mut graffiti = [0u8; 32];
+    graffiti[..end].copy_from_slice(&text.as_bytes()[..end]);
+    Bytes32::try_from(graffiti.as_ref()).expect("correct length")
+}
+
+/// The same graffiti for every proposal.
+#[derive(Debug, Clone, Default)]
+pub struct FixedGraffiti(pub Bytes32);
+
+impl GraffitiProvider for FixedGraffiti {
+    fn graffiti(&self, _slot: Slot, _public_key: &BlsPublicKey) -> Bytes32 {
+        self.0.clone()
+    }
+}
+
+/// Each graffiti of a list in turn, one per proposal, starting over after the last one.
+#[derive(Debug, Default)]
+pub struct RoundRobinGraffiti {
+    graffitis: Vec<Bytes32>,
+    next: AtomicUsize,
+}
+
+impl RoundRobinGraffiti {
+    pub fn new(graffitis: Vec<Bytes32>) -> Self {
+        Self { graffitis, next: AtomicUsize::new(0) }
+    }
+}
+
+impl GraffitiProvider for RoundRobinGraffiti {
+    fn graffiti(&self, _slot: Slot, _public_key: &BlsPublicKey) -> Bytes32 {
+        if self.graffitis.is_empty() {
+            return Default::default()
+        }
+        let next = self.next.fetch_add(1, Ordering::Relaxed);
+        self.graffitis[next % self.graffitis.len()].clone()
+    }
+}
+
+/// The first line of a file, read again for every proposal so that it can be changed at any
+/// time. An over-long line is truncated as in [`graffiti_from_str`], and the `fallback` is used
+/// while the file is missing or is not UTF-8.
+#[derive(Debug, Clone)]
+pub struct GraffitiFile {
+    pub path: PathBuf,
+    pub fallback: Bytes32,
+}
+
+impl GraffitiProvider for GraffitiFile {
+    fn graffiti(&self, _slot: Slot, _public_key: &BlsPublicKey) -> Bytes32 {
+        match fs::read_to_string(&self.path) {
+            Ok(contents) => graffiti_from_str(contents.lines().next().unwrap_or_default()),
+            Err(_) => self.fallback.clone(),
+        }
+    }
+}
+
+/// The graffiti of `overrides` for the validators it has one for, and of `default` for the
+/// others.
+#[derive(Debug, Default)]
+pub struct ValidatorGraffiti<P> {
+    pub overrides: HashMap<BlsPublicKey, Bytes32>,
+    pub default: P,
+}
+
+impl<P: GraffitiProvider> GraffitiProvider for ValidatorGraffiti<P> {
+    fn graffiti(&self, slot: Slot, public_key: &BlsPublicKey) -> Bytes32 {
+        match self.overrides.get(public_key) {
+            Some(graffiti) => graffiti.clone(),
+            None => self.default.graffiti(slot, public_key),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graffiti_providers() {
+        let public_key = BlsPublicKey::default();
+        let graffitis = ["one", "two", "three"].map(graffiti_from_str);
+        let provider = RoundRobinGraffiti::new(graffitis.to_vec());
+        for (slot, expected) in graffitis.iter().cycle().take(7).enumerate() {
+            assert_eq!(&provider.graffiti(slot as Slot, &public_key), expected);
+        }
+        assert_eq!(RoundRobinGraffiti::default().graffiti(0, &public_key), Bytes32::default());
+
+        // 31 bytes of ascii, then a two-byte character that would straddle the limit
+        let text = format!("{}é and more", "a".repeat(31));
+        let graffiti = graffiti_from_str(&text);
+        assert_eq!(&graffiti[..31], "a".repeat(31).as_bytes());
+        assert_eq!(graffiti[31], 0);
+        let graffiti = graffiti_from_str("graffiti");
+        assert_eq!(&graffiti[..8], b"graffiti");
+        assert!(graffiti[8..].iter().all(|&byte| byte == 0));
+
+        let path = std::env::temp_dir().join(format!("graffiti-{}", std::process::id()));
+        let fallback = graffiti_from_str("fallback");
+        let provider = GraffitiFile { path: path.clone(), fallback: fallback.clone() };
+        let _ = fs::remove_file(&path);
+        assert_eq!(provider.graffiti(0, &public_key), fallback);
+        fs::write(&path, "from the file\nignored\n").unwrap();
+        assert_eq!(provider.graffiti(1, &public_key), graffiti_from_str("from the file"));
+        fs::write(&path, &text).unwrap();
+        assert_eq!(provider.graffiti(2, &public_key), graffiti_from_str(&text));
+        fs::remove_file(&path).unwrap();
+
+        let other_key = BlsPublicKey::try_from([1u8; 48].as_ref()).unwrap();
+        let provider = ValidatorGraffiti {
+            overrides: HashMap::from([(other_key.clone(), graffiti_from_str("override"))]),
+            default: FixedGraffiti(fallback.clone()),
+        };
+        assert_eq!(provider.graffiti(0, &other_key), graffiti_from_str("override"));
+        assert_eq!(provider.graffiti(0, &public_key), fallback);
+    }
+}
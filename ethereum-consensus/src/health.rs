@@ -0,0 +1,293 @@
+//! A summary of the health of a chain for monitoring, e.g. to expose on an admin endpoint and
+//! alert on.
+use crate::{
+    analysis::{epoch_summary, validator_status, ValidatorStatus},
+    da_check::scheduled_max_blobs_per_block,
+    electra::{
+        get_current_epoch, get_finality_delay, is_in_inactivity_leak, BeaconState,
+        SignedBeaconBlock,
+    },
+    primitives::{Epoch, Slot},
+    state_transition::{Context, Result},
+};
+use std::collections::BTreeSet;
+
+/// Health figures of a chain, as seen from a state and the blocks leading up to it.
+///
+/// The figures derived from blocks are `None` if no blocks are given.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct HealthReport {
+    #[serde(with = "crate::serde::as_str")]
+    pub epoch: Epoch,
+    /// Fraction of the active balance with a timely target vote in the previous epoch, i.e. the
+    /// last complete epoch.
+    pub participation_rate: f64,
+    /// Epochs from the last finalized epoch to the previous epoch, as the inactivity leak counts
+    /// them.
+    #[serde(with = "crate::serde::as_str")]
+    pub epochs_since_finality: Epoch,
+    pub inactivity_leak: bool,
+    /// Fraction of the slots from the first given block to the state without a block.
+    pub proposer_miss_rate: Option<f64>,
+    /// Mean fraction of the sync committee participating in the given blocks.
+    pub sync_participation: Option<f64>,
+    /// Mean number of blobs in the given blocks over the target number of blobs per block.
+    pub blob_inclusion_rate: Option<f64>,
+    /// Number of validators eligible for activation but not yet activated.
+    pub entry_queue_length: usize,
+    /// Number of active validators with an exit epoch set.
+    pub exit_queue_length: usize,
+}
+
+/// Report on the health of the chain with `state` at its head.
+///
+/// `recent_blocks` are the canonical blocks of a window of slots ending at the slot of `state`, in
+/// any order; the first slot of the window is the slot of the earliest block given. The target
+/// number of blobs per block is half of the maximum under the blob schedule at the epoch of
+/// `state`, as in EIP-4844.
+pub fn chain_health<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    recent_blocks: &[SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >],
+    context: &Context,
+) -> Result<HealthReport> {
+    let summary = epoch_summary(state, context)?;
+    let epoch = get_current_epoch(state, context);
+
+    let (mut entry_queue_length, mut exit_queue_length) = (0, 0);
+    for (validator, &balance) in state.validators.iter().zip(state.balances.iter()) {
+        match validator_status(validator, balance, epoch) {
+            ValidatorStatus::PendingQueued => entry_queue_length += 1,
+            ValidatorStatus::ActiveExiting => exit_queue_length += 1,
+            _ => {}
+        }
+    }
+
+    let proposed_slots = recent_blocks
+        .iter()
+        .map(|block| block.message.slot)
+        .filter(|&slot| slot <= state.slot)
+        .collect::<BTreeSet<Slot>>();
+    let proposer_miss_rate = proposed_slots.first().map(|&first_slot| {
+        let window = state.slot - first_slot + 1;
+        1.0 - proposed_slots.len() as f64 / window as f64
+    });
+
+    let block_count = recent_blocks.len() as f64;
+    let (sync_participation, blob_inclusion_rate) = if recent_blocks.is_empty() {
+        (None, None)
+    } else {
+        let sync_participants = recent_blocks
+            .iter()
+            .map(|block| {
+                let bits = &block.message.body.sync_aggregate.sync_committee_bits;
+                bits.iter().filter(|participated| **participated).count()
+            })
+            .sum::<usize>();
+        let blobs = recent_blocks
+            .iter()
+            .map(|block| block.message.body.blob_kzg_commitments.len())
+            .sum::<usize>();
+        let target_blobs_per_block = (scheduled_max_blobs_per_block(epoch, context) / 2) as f64;
+        (
+            Some(sync_participants as f64 / (SYNC_COMMITTEE_SIZE as f64 * block_count)),
+            Some(blobs as f64 / (target_blobs_per_block * block_count)),
+        )
+    };
+
+    Ok(HealthReport {
+        epoch,
+        participation_rate: summary.participation_rate_prev,
+        epochs_since_finality: get_finality_delay(state, context),
+        inactivity_leak: is_in_inactivity_leak(state, context),
+        proposer_miss_rate,
+        sync_participation,
+        blob_inclusion_rate,
+        entry_queue_length,
+        exit_queue_length,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        electra::{add_flag, minimal, Checkpoint, TIMELY_TARGET_FLAG_INDEX},
+        phase0::Validator,
+        primitives::FAR_FUTURE_EPOCH,
+    };
+
+    const VALIDATOR_COUNT: usize = 32;
+    const EPOCH: Epoch = 7;
+
+    // a state at the last slot of `EPOCH` where the first `voters` validators made timely target
+    // votes in the previous epoch, with validator 0 exiting and validator 1 queued for activation
+    // (so not counted as a voter)
+    fn generate_state(
+        voters: usize,
+        finalized_epoch: Epoch,
+        context: &Context,
+    ) -> minimal::BeaconState {
+        let mut state = minimal::BeaconState {
+            slot: (EPOCH + 1) * context.slots_per_epoch - 1,
+            finalized_checkpoint: Checkpoint { epoch: finalized_epoch, ..Default::default() },
+            ..Default::default()
+        };
+        for i in 0..VALIDATOR_COUNT {
+            state.validators.push(Validator {
+                activation_epoch: 0,
+                exit_epoch: FAR_FUTURE_EPOCH,
+                withdrawable_epoch: FAR_FUTURE_EPOCH,
+                effective_balance: context.max_effective_balance,
+                ..Default::default()
+            });
+            state.balances.push(context.max_effective_balance);
+            let flags = if i < voters { add_flag(0, TIMELY_TARGET_FLAG_INDEX) } else { 0 };
+            state.previous_epoch_participation.push(flags);
+            state.current_epoch_participation.push(0);
+        }
+        state.validators[0].exit_epoch = EPOCH + 2;
+        state.validators[1].activation_eligibility_epoch = EPOCH - 1;
+        state.validators[1].activation_epoch = FAR_FUTURE_EPOCH;
+        state
+    }
+
+    // a block at each of `slots` with the first `sync_participants` members of the sync committee
+    // participating and `blob_count` blobs
+    fn generate_blocks(
+        slots: impl Iterator<Item = Slot>,
+        sync_participants: usize,
+        blob_count: usize,
+    ) -> Vec<minimal::SignedBeaconBlock> {
+        slots
+            .map(|slot| {
+                let mut block = minimal::SignedBeaconBlock::default();
+                block.message.slot = slot;
+                let body = &mut block.message.body;
+                for position in 0..sync_participants {
+                    body.sync_aggregate.sync_committee_bits.set(position, true);
+                }
+                for _ in 0..blob_count {
+                    body.blob_kzg_commitments.push(Default::default());
+                }
+                block
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_chain_health() {
+        let context = Context::for_minimal();
+        let window = EPOCH * context.slots_per_epoch..(EPOCH + 1) * context.slots_per_epoch;
+
+        let state = generate_state(VALIDATOR_COUNT, EPOCH - 2, &context);
+        let blocks = generate_blocks(window.clone(), minimal::SYNC_COMMITTEE_SIZE, 3);
+        let healthy = chain_health(&state, &blocks, &context).unwrap();
+        assert_eq!(
+            healthy,
+            HealthReport {
+                epoch: EPOCH,
+                participation_rate: 1.0,
+                epochs_since_finality: 1,
+                inactivity_leak: false,
+                proposer_miss_rate: Some(0.0),
+                sync_participation: Some(1.0),
+                blob_inclusion_rate: Some(1.0),
+                entry_queue_length: 1,
+                exit_queue_length: 1,
+            }
+        );
+
+        // attestations are withheld, so the chain stops finalizing, and only every other slot
+        // has a block
+        let state = generate_state(VALIDATOR_COUNT / 2, 1, &context);
+        let blocks = generate_blocks(window.step_by(2), minimal::SYNC_COMMITTEE_SIZE / 2, 1);
+        let degraded = chain_health(&state, &blocks, &context).unwrap();
+        assert_eq!(
+            degraded,
+            HealthReport {
+                epoch: EPOCH,
+                participation_rate: 15.0 / 31.0,
+                epochs_since_finality: 5,
+                inactivity_leak: true,
+                proposer_miss_rate: Some(0.5),
+                sync_participation: Some(0.5),
+                blob_inclusion_rate: Some(1.0 / 3.0),
+                entry_queue_length: 1,
+                exit_queue_length: 1,
+            }
+        );
+        assert!(degraded.participation_rate < healthy.participation_rate);
+
+        let json = serde_json::to_value(&degraded).unwrap();
+        assert_eq!(json["epochs_since_finality"], "5");
+        assert_eq!(json["inactivity_leak"], true);
+
+        let report = chain_health(&state, &[], &context).unwrap();
+        assert_eq!(
+            (report.proposer_miss_rate, report.sync_participation, report.blob_inclusion_rate),
+            (None, None, None)
+        );
+    }
+}
@@ -0,0 +1,221 @@
+//! Committees of past epochs for consumers of old attestations (e.g. a slasher backfilling its
+//! history) once the states that could compute them are gone.
+use crate::{
+    phase0::compute_epoch_at_slot,
+    primitives::{CommitteeIndex, Epoch, Root, Slot, ValidatorIndex},
+    state_transition::{
+        compute_shuffling, shuffling_decision_root, Context, Shuffling, ShufflingCache,
+    },
+    types::BeaconState,
+    Error,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CommitteeProviderError {
+    #[error("no shuffling is known for epoch {0}")]
+    UnknownEpoch(Epoch),
+}
+
+/// Answers `get_beacon_committee` for every epoch whose shuffling it holds, without a state.
+///
+/// A shuffling is described by the seed and the active validator indices of its epoch. They are
+/// extracted by observing states, e.g. as blocks are replayed, and can be persisted with
+/// `to_bytes` in that compact form. Restored shufflings are not checked against any state, so
+/// only bytes produced by a trusted provider should be loaded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitteeProvider {
+    shufflings: ShufflingCache,
+}
+
+impl CommitteeProvider {
+    /// Record the shufflings of the previous and current epochs of `state`, if not already
+    /// known. Both are settled by the time a state is in its current epoch.
+    ///
+    /// A shuffling decided by a different block than the one known for its epoch replaces it,
+    /// as the states observed are taken to be of a single chain, e.g. after a reorg.
+    pub fn observe<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    >(
+        &mut self,
+        state: &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+        context: &Context,
+    ) {
+        let current_epoch = compute_epoch_at_slot(state.slot(), context);
+        for epoch in [current_epoch.saturating_sub(1), current_epoch] {
+            let decision_root = shuffling_decision_root(state, epoch, context);
+            if self.shufflings.get(epoch, decision_root).is_none() {
+                self.insert(decision_root, compute_shuffling(state, epoch, context));
+            }
+        }
+    }
+
+    /// Record `shuffling`, replacing any shuffling of its epoch decided by another block.
+    pub fn insert(&mut self, decision_root: Root, shuffling: Shuffling) {
+        self.shufflings.remove(shuffling.epoch);
+        self.shufflings.insert(decision_root, shuffling);
+    }
+
+    pub fn contains(&self, epoch: Epoch) -> bool {
+        self.shufflings.shufflings_at(epoch).next().is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.shufflings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shufflings.is_empty()
+    }
+
+    fn shuffling(&self, epoch: Epoch) -> Result<&Shuffling, Error> {
+        self.shufflings
+            .shufflings_at(epoch)
+            .next()
+            .map(|(_, shuffling)| shuffling)
+            .ok_or(CommitteeProviderError::UnknownEpoch(epoch).into())
+    }
+
+    pub fn get_committee_count_per_slot(
+        &self,
+        epoch: Epoch,
+        context: &Context,
+    ) -> Result<usize, Error> {
+        Ok(self.shuffling(epoch)?.committee_count_per_slot(context))
+    }
+
+    /// The committee at `slot` with `index`, which `slot` must be in `epoch` for.
+    pub fn get_beacon_committee(
+        &self,
+        epoch: Epoch,
+        slot: Slot,
+        index: CommitteeIndex,
+        context: &Context,
+    ) -> Result<&[ValidatorIndex], Error> {
+        self.shuffling(epoch)?.beacon_committee(slot, index, context)
+    }
+
+    /// Add the shufflings of `other`, e.g. a provider loaded from storage, preferring `other`
+    /// where both have a shuffling.
+    pub fn merge(&mut self, other: CommitteeProvider) {
+        for (decision_root, shuffling) in other.shufflings.into_shufflings() {
+            self.insert(decision_root, shuffling);
+        }
+    }
+
+    /// Encode the seed and active validator indices of each shuffling, see
+    /// `ShufflingCache::serialize`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.shufflings.serialize()
+    }
+
+    /// Decode a provider from `to_bytes`, recomputing each shuffling from its seed.
+    pub fn from_bytes(encoding: &[u8], context: &Context) -> Result<Self, Error> {
+        let shufflings = ShufflingCache::deserialize(encoding, context)?;
+        Ok(Self { shufflings })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::phase0::genesis_state,
+        phase0::{get_beacon_committee, get_committee_count_per_slot, process_slots},
+        types,
+    };
+
+    #[test]
+    fn test_committees_match_live_states() {
+        let context = Context::for_minimal();
+        let slots_per_epoch = context.slots_per_epoch;
+        let epochs = 6;
+        let mut state = genesis_state();
+        // change the active set partway through
+        state.validators[5].exit_epoch = 3;
+        state.validators[9].exit_epoch = 4;
+
+        let mut provider = CommitteeProvider::default();
+        let mut expected = vec![];
+        for slot in 0..epochs * slots_per_epoch {
+            if slot > 0 {
+                process_slots(&mut state, slot, &context).unwrap();
+            }
+            let epoch = compute_epoch_at_slot(slot, &context);
+            let committees_per_slot = get_committee_count_per_slot(&state, epoch, &context);
+            for index in 0..committees_per_slot {
+                let committee = get_beacon_committee(&state, slot, index, &context).unwrap();
+                expected.push((epoch, slot, index, committee));
+            }
+            provider.observe(&types::minimal::BeaconState::Phase0(state.clone()), &context);
+        }
+        assert_eq!(provider.len(), epochs as usize);
+
+        let restored = CommitteeProvider::from_bytes(&provider.to_bytes(), &context).unwrap();
+        assert_eq!(restored, provider);
+        for (epoch, slot, index, committee) in &expected {
+            assert_eq!(
+                restored.get_beacon_committee(*epoch, *slot, *index, &context).unwrap(),
+                committee
+            );
+        }
+        // the exits shrink the committees of later epochs
+        let size = |epoch: Epoch| {
+            expected
+                .iter()
+                .filter(|entry| entry.0 == epoch)
+                .map(|entry| entry.3.len())
+                .sum::<usize>()
+        };
+        assert_eq!(size(2), size(3) + 1);
+        assert_eq!(size(3), size(4) + 1);
+
+        assert!(matches!(
+            restored.get_beacon_committee(epochs, epochs * slots_per_epoch, 0, &context),
+            Err(Error::CommitteeProvider(CommitteeProviderError::UnknownEpoch(_)))
+        ));
+        assert!(matches!(
+            restored.get_beacon_committee(1, 0, 0, &context),
+            Err(Error::SlotOutOfRange { .. })
+        ));
+
+        let mut merged = CommitteeProvider::default();
+        merged.merge(restored);
+        let committees_per_slot = get_committee_count_per_slot(&genesis_state(), 0, &context);
+        assert_eq!(merged.get_committee_count_per_slot(0, &context).unwrap(), committees_per_slot);
+
+        // a shuffling decided by another block, e.g. after a reorg, replaces the known one
+        let shuffling = merged.shuffling(2).unwrap().clone();
+        let len = merged.len();
+        merged.insert(Root::try_from([1u8; 32].as_ref()).unwrap(), shuffling);
+        assert_eq!(merged.len(), len);
+    }
+}
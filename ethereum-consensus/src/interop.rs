@@ -0,0 +1,120 @@
+//! The deterministic validators of interop ("mocked start") testnets, whose keys anyone can
+//! derive from the index of the validator alone.
+//!
+//! See <https://github.com/ethereum/eth2.0-pm/tree/master/interop/mocked_start>.
+use crate::{
+    crypto::{hash, SecretKey},
+    deneb::polynomial_commitments::BYTES_PER_FIELD_ELEMENT,
+    phase0::{DepositData, DepositMessage},
+    primitives::{Bytes32, Gwei, BLS_WITHDRAWAL_PREFIX},
+    signing::sign_deposit_message,
+    state_transition::Context,
+    Error,
+};
+
+/// The modulus of the BLS12-381 scalar field, big-endian.
+pub(crate) const BLS_MODULUS: [u8; BYTES_PER_FIELD_ELEMENT] = [
+    0x73, 0xed, 0xa7, 0x53, 0x29, 0x9d, 0x7d, 0x48, 0x33, 0x39, 0xd8, 0x08, 0x09, 0xa1, 0xd8, 0x05,
+    0x53, 0xbd, 0xa4, 0x02, 0xff, 0xfe, 0x5b, 0xfe, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01,
+];
+
+// Subtract the modulus until `element` is below it. As `2^256 < 3 * BLS_MODULUS`, this takes at
+// most two subtractions.
+pub(crate) fn reduce(element: &mut [u8]) {
+    while element[..] >= BLS_MODULUS[..] {
+        let mut borrow = 0u16;
+        for (byte, modulus_byte) in element.iter_mut().zip(BLS_MODULUS).rev() {
+            let difference = (*byte as u16).wrapping_sub(modulus_byte as u16 + borrow);
+            borrow = difference >> 15;
+            *byte = difference as u8;
+        }
+    }
+}
+
+/// The secret key of the interop validator at `index`: the SHA-256 digest of the index, as a
+/// little-endian integer modulo the order of the BLS12-381 curve.
+pub fn interop_secret_key(index: usize) -> SecretKey {
+    let mut preimage = [0u8; 32];
+    preimage[..8].copy_from_slice(&(index as u64).to_le_bytes());
+    let mut key: [u8; 32] = hash(preimage).as_ref().try_into().expect("correct length");
+    key.reverse();
+    reduce(&mut key);
+    SecretKey::try_from(key.as_ref()).expect("reduced below the curve order")
+}
+
+/// The secret keys of the first `count` interop validators.
+pub fn interop_secret_keys(count: usize) -> Vec<SecretKey> {
+    (0..count).map(interop_secret_key).collect()
+}
+
+/// The BLS withdrawal credentials of interop validators, committing to their own public key.
+pub fn interop_withdrawal_credentials(secret_key: &SecretKey) -> Bytes32 {
+    let mut withdrawal_credentials = hash(secret_key.public_key().as_slice()).as_ref().to_vec();
+    withdrawal_credentials[0] = BLS_WITHDRAWAL_PREFIX;
+    Bytes32::try_from(withdrawal_credentials.as_slice()).expect("correct length")
+}
+
+/// The data of a signed deposit of `amount` for the interop validator with `secret_key`.
+pub fn interop_deposit_data(
+    secret_key: &SecretKey,
+    amount: Gwei,
+    context: &Context,
+) -> Result<DepositData, Error> {
+    let message = DepositMessage {
+        public_key: secret_key.public_key(),
+        withdrawal_credentials: interop_withdrawal_credentials(secret_key),
+        amount,
+    };
+    let signature = sign_deposit_message(&message, context, secret_key)?;
+    Ok(DepositData {
+        public_key: message.public_key,
+        withdrawal_credentials: message.withdrawal_credentials,
+        amount,
+        signature,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interop_keys() {
+        // the first keys of the mocked start specification
+        let expected = [
+            "0x25295f0d1d592a90b333e26e85149708208e9f8e8bc18f6c77bd62f8ad7a6866",
+            "0x51d0b65185db6989ab0b560d6deed19c7ead0e24b9b6372cbecb1f26bdfad000",
+            "0x315ed405fafe339603932eebe8dbfd650ce5dafa561f6928664c75db85f97857",
+        ];
+        for (index, expected) in expected.into_iter().enumerate() {
+            let secret_key = SecretKey::try_from(expected.to_string()).unwrap();
+            assert_eq!(interop_secret_key(index).public_key(), secret_key.public_key());
+        }
+        assert_eq!(interop_secret_keys(3).len(), 3);
+
+        let context = Context::for_minimal();
+        let secret_key = interop_secret_key(0);
+        let data =
+            interop_deposit_data(&secret_key, context.max_effective_balance, &context).unwrap();
+        assert_eq!(data.withdrawal_credentials[0], BLS_WITHDRAWAL_PREFIX);
+        let digest = hash(data.public_key.as_slice());
+        assert_eq!(data.withdrawal_credentials.as_ref()[1..], digest.as_ref()[1..]);
+    }
+
+    #[test]
+    fn test_reduce() {
+        let mut element = [0xff; BYTES_PER_FIELD_ELEMENT];
+        reduce(&mut element);
+        assert!(element < BLS_MODULUS);
+
+        let mut element = BLS_MODULUS;
+        reduce(&mut element);
+        assert_eq!(element, [0; BYTES_PER_FIELD_ELEMENT]);
+
+        let mut below_modulus = BLS_MODULUS;
+        below_modulus[BYTES_PER_FIELD_ELEMENT - 1] = 0;
+        let mut element = below_modulus;
+        reduce(&mut element);
+        assert_eq!(element, below_modulus);
+    }
+}
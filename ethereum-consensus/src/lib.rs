@@ -1,26 +1,89 @@
+// Enter a `tracing` span for the rest of the enclosing block if the `tracing` feature is enabled;
+// expands to nothing otherwise. The guard is bound to a local of the caller's block (`let _span`,
+// not `let _`, which would drop it at once), so the span is exited when that block ends.
+#[cfg(feature = "tracing")]
+macro_rules! trace_span {
+    ($($arg:tt)*) => {
+        let _span = tracing::info_span!($($arg)*).entered();
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_span {
+    ($($arg:tt)*) => {};
+}
+
+// Evaluate `$body` inside a `tracing` span named `$name` with any given fields.
+macro_rules! traced {
+    ($name:literal, $body:expr $(, $($field:tt)*)?) => {{
+        trace_span!($name $(, $($field)*)?);
+        $body
+    }};
+}
+
 pub mod altair;
+pub mod analysis;
+pub mod audit;
+pub mod backfill;
 pub mod bellatrix;
 pub mod builder;
+pub mod capabilities;
 pub mod capella;
 pub mod clock;
 pub mod configs;
+pub mod cost;
 pub mod crypto;
+pub mod da_check;
+pub mod debug;
 pub mod deneb;
+pub mod determinism;
 pub mod domains;
+pub mod duties;
 pub mod electra;
 pub mod error;
+pub mod eth1;
+pub mod execution;
 pub mod execution_engine;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(any(test, feature = "fixtures"))]
+pub mod fixtures;
 mod fork;
+pub mod fork_choice;
+pub mod genesis;
+pub mod gossip_validation;
+pub mod graffiti;
+pub mod health;
+pub mod historical;
+pub mod interop;
+pub mod light_client;
+pub mod limits;
 pub mod networking;
 pub mod networks;
+pub mod ops_pool;
+pub mod peerdas;
 pub mod phase0;
 pub mod primitives;
+pub mod proof_of_ownership;
+pub mod proofs;
+#[cfg(feature = "serde")]
+pub mod remote_signer;
+pub mod replay;
+pub mod rewards;
 #[cfg(feature = "serde")]
 pub mod serde;
 pub mod signing;
+pub mod slasher;
+pub mod slashing_detection;
+pub mod sparse;
 pub mod ssz;
+pub mod ssz_size;
 pub mod state_transition;
+pub mod storage;
+#[cfg(any(test, feature = "fixtures"))]
+pub mod test_utils;
 pub mod types;
+pub mod validators;
 
 pub use error::Error;
 pub use fork::Fork;
@@ -0,0 +1,533 @@
+//! Following the chain from a stream of light client updates.
+use crate::{
+    altair, capella, deneb, electra,
+    phase0::BeaconBlockHeader,
+    primitives::{IsZero, Slot},
+    ssz::prelude::Bitvector,
+    state_transition::Context,
+};
+use thiserror::Error;
+
+/// If this build can follow the chain from light client updates, with the [`Tracker`].
+pub const SUPPORTED: bool = true;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TrackerError {
+    #[error(
+        "update has {participants} sync committee participants but at least {minimum} are required"
+    )]
+    InsufficientParticipation { participants: usize, minimum: usize },
+    #[error("update signed at slot {signature_slot} is ahead of the current slot {current_slot}")]
+    FutureUpdate { signature_slot: Slot, current_slot: Slot },
+    #[error("update signed at slot {signature_slot} does not follow its attested header at slot {attested_slot}")]
+    InvalidSignatureSlot { signature_slot: Slot, attested_slot: Slot },
+    #[error("finalized header at slot {finalized_slot} is ahead of the attested header at slot {attested_slot}")]
+    InvalidFinalizedHeader { finalized_slot: Slot, attested_slot: Slot },
+}
+
+/// A light client header of any fork.
+pub trait LightClientHeader: Clone {
+    fn beacon(&self) -> &BeaconBlockHeader;
+}
+
+/// A light client update carrying an attested header, and optionally a finalized header, signed
+/// by a sync committee.
+pub trait LightClientUpdate {
+    type Header: LightClientHeader;
+
+    fn attested_header(&self) -> &Self::Header;
+
+    fn finalized_header(&self) -> Option<&Self::Header>;
+
+    /// The number of sync committee members that signed and the size of the committee.
+    fn participation(&self) -> (usize, usize);
+
+    fn signature_slot(&self) -> Slot;
+}
+
+fn participation<const SYNC_COMMITTEE_SIZE: usize>(
+    bits: &Bitvector<SYNC_COMMITTEE_SIZE>,
+) -> (usize, usize) {
+    (bits.iter().filter(|bit| **bit).count(), SYNC_COMMITTEE_SIZE)
+}
+
+impl LightClientHeader for altair::light_client::LightClientHeader {
+    fn beacon(&self) -> &BeaconBlockHeader {
+        &self.beacon
+    }
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize> LightClientUpdate
+    for altair::light_client::LightClientOptimisticUpdate<SYNC_COMMITTEE_SIZE>
+{
+    type Header = altair::light_client::LightClientHeader;
+
+    fn attested_header(&self) -> &Self::Header {
+        &self.attested_header
+    }
+
+    fn finalized_header(&self) -> Option<&Self::Header> {
+        None
+    }
+
+    fn participation(&self) -> (usize, usize) {
+        participation(&self.sync_aggregate.sync_committee_bits)
+    }
+
+    fn signature_slot(&self) -> Slot {
+        self.signature_slot
+    }
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize> LightClientUpdate
+    for altair::light_client::LightClientFinalityUpdate<SYNC_COMMITTEE_SIZE>
+{
+    type Header = altair::light_client::LightClientHeader;
+
+    fn attested_header(&self) -> &Self::Header {
+        &self.attested_header
+    }
+
+    fn finalized_header(&self) -> Option<&Self::Header> {
+        Some(&self.finalized_header)
+    }
+
+    fn participation(&self) -> (usize, usize) {
+        participation(&self.sync_aggregate.sync_committee_bits)
+    }
+
+    fn signature_slot(&self) -> Slot {
+        self.signature_slot
+    }
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize> LightClientUpdate
+    for altair::light_client::LightClientUpdate<SYNC_COMMITTEE_SIZE>
+{
+    type Header = altair::light_client::LightClientHeader;
+
+    fn attested_header(&self) -> &Self::Header {
+        &self.attested_header
+    }
+
+    fn finalized_header(&self) -> Option<&Self::Header> {
+        altair::light_client::is_finality_update(self).then_some(&self.finalized_header)
+    }
+
+    fn participation(&self) -> (usize, usize) {
+        participation(&self.sync_aggregate.sync_committee_bits)
+    }
+
+    fn signature_slot(&self) -> Slot {
+        self.signature_slot
+    }
+}
+
+// From Capella on, headers carry the execution payload header and the updates are generic over
+// its bounds.
+macro_rules! impl_with_execution {
+    ($fork:ident) => {
+        impl<const BYTES_PER_LOGS_BLOOM: usize, const MAX_EXTRA_DATA_BYTES: usize> LightClientHeader
+            for $fork::light_client::LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>
+        {
+            fn beacon(&self) -> &BeaconBlockHeader {
+                &self.beacon
+            }
+        }
+
+        impl<
+                const SYNC_COMMITTEE_SIZE: usize,
+                const BYTES_PER_LOGS_BLOOM: usize,
+                const MAX_EXTRA_DATA_BYTES: usize,
+            > LightClientUpdate
+            for $fork::light_client::LightClientOptimisticUpdate<
+                SYNC_COMMITTEE_SIZE,
+                BYTES_PER_LOGS_BLOOM,
+                MAX_EXTRA_DATA_BYTES,
+            >
+        {
+            type Header =
+                $fork::light_client::LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>;
+
+            fn attested_header(&self) -> &Self::Header {
+                &self.attested_header
+            }
+
+            fn finalized_header(&self) -> Option<&Self::Header> {
+                None
+            }
+
+            fn participation(&self) -> (usize, usize) {
+                participation(&self.sync_aggregate.sync_committee_bits)
+            }
+
+            fn signature_slot(&self) -> Slot {
+                self.signature_slot
+            }
+        }
+
+        impl<
+                const SYNC_COMMITTEE_SIZE: usize,
+                const BYTES_PER_LOGS_BLOOM: usize,
+                const MAX_EXTRA_DATA_BYTES: usize,
+            > LightClientUpdate
+            for $fork::light_client::LightClientFinalityUpdate<
+                SYNC_COMMITTEE_SIZE,
+                BYTES_PER_LOGS_BLOOM,
+                MAX_EXTRA_DATA_BYTES,
+            >
+        {
+            type Header =
+                $fork::light_client::LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>;
+
+            fn attested_header(&self) -> &Self::Header {
+                &self.attested_header
+            }
+
+            fn finalized_header(&self) -> Option<&Self::Header> {
+                Some(&self.finalized_header)
+            }
+
+            fn participation(&self) -> (usize, usize) {
+                participation(&self.sync_aggregate.sync_committee_bits)
+            }
+
+            fn signature_slot(&self) -> Slot {
+                self.signature_slot
+            }
+        }
+
+        impl<
+                const SYNC_COMMITTEE_SIZE: usize,
+                const BYTES_PER_LOGS_BLOOM: usize,
+                const MAX_EXTRA_DATA_BYTES: usize,
+            > LightClientUpdate
+            for $fork::light_client::LightClientUpdate<
+                SYNC_COMMITTEE_SIZE,
+                BYTES_PER_LOGS_BLOOM,
+                MAX_EXTRA_DATA_BYTES,
+            >
+        {
+            type Header =
+                $fork::light_client::LightClientHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>;
+
+            fn attested_header(&self) -> &Self::Header {
+                &self.attested_header
+            }
+
+            fn finalized_header(&self) -> Option<&Self::Header> {
+                let is_finality_update = self.finality_branch.iter().any(|node| !node.is_zero());
+                is_finality_update.then_some(&self.finalized_header)
+            }
+
+            fn participation(&self) -> (usize, usize) {
+                participation(&self.sync_aggregate.sync_committee_bits)
+            }
+
+            fn signature_slot(&self) -> Slot {
+                self.signature_slot
+            }
+        }
+    };
+}
+
+impl_with_execution!(capella);
+impl_with_execution!(deneb);
+impl_with_execution!(electra);
+
+/// A change to the headers followed by a [`Tracker`].
+#[derive(Debug, Clone)]
+pub enum TrackerEvent<H> {
+    NewHead(H),
+    NewFinalized(H),
+}
+
+/// Follows the best (optimistic) head and the finalized header of the chain from a stream of
+/// optimistic and finality updates, by the ranking rules of the light client sync protocol.
+///
+/// Updates must already have been verified against the sync committee of their signature slot;
+/// the tracker only ranks them:
+/// - the head moves to a newer attested header signed by more than half of the most participants
+///   seen over the sync committee periods of the finalized header and the one before it, so that a
+///   later header with a thin signature cannot displace one the committee broadly signed;
+/// - the finalized header moves to a newer finalized header from an update signed by a
+///   supermajority (two thirds) of the sync committee.
+///
+/// Updates older than the head are ignored and updates signed ahead of the current slot are
+/// rejected.
+pub struct Tracker<H: LightClientHeader> {
+    head: H,
+    finalized: H,
+    current_period: u64,
+    previous_max_active_participants: usize,
+    current_max_active_participants: usize,
+    slots_per_period: u64,
+    min_sync_committee_participants: usize,
+    on_change: Option<Box<dyn FnMut(&TrackerEvent<H>) + Send>>,
+}
+
+impl<H: LightClientHeader> Tracker<H> {
+    /// A tracker starting from a trusted `finalized` header, e.g. that of a bootstrap.
+    pub fn new(finalized: H, context: &Context) -> Self {
+        let slots_per_period = context.slots_per_epoch * context.epochs_per_sync_committee_period;
+        let current_period = finalized.beacon().slot / slots_per_period;
+        Self {
+            head: finalized.clone(),
+            finalized,
+            current_period,
+            previous_max_active_participants: 0,
+            current_max_active_participants: 0,
+            slots_per_period,
+            min_sync_committee_participants: context.min_sync_committee_participants,
+            on_change: None,
+        }
+    }
+
+    /// Call `f` with every change to the head or the finalized header, in the order they happen.
+    pub fn on_change(&mut self, f: impl FnMut(&TrackerEvent<H>) + Send + 'static) {
+        self.on_change = Some(Box::new(f));
+    }
+
+    pub fn current_head(&self) -> &H {
+        &self.head
+    }
+
+    pub fn finalized(&self) -> &H {
+        &self.finalized
+    }
+
+    // The participation an update needs to move the head, per `get_safety_threshold`.
+    fn safety_threshold(&self) -> usize {
+        self.previous_max_active_participants.max(self.current_max_active_participants) / 2
+    }
+
+    /// Rank `update` against the tracked headers at `current_slot`, returning whether either
+    /// changed.
+    pub fn process<U: LightClientUpdate<Header = H>>(
+        &mut self,
+        update: &U,
+        current_slot: Slot,
+    ) -> Result<bool, TrackerError> {
+        let (participants, committee_size) = update.participation();
+        if participants < self.min_sync_committee_participants {
+            return Err(TrackerError::InsufficientParticipation {
+                participants,
+                minimum: self.min_sync_committee_participants,
+            })
+        }
+        let signature_slot = update.signature_slot();
+        if signature_slot > current_slot {
+            return Err(TrackerError::FutureUpdate { signature_slot, current_slot })
+        }
+        let attested_header = update.attested_header();
+        let attested_slot = attested_header.beacon().slot;
+        if signature_slot <= attested_slot {
+            return Err(TrackerError::InvalidSignatureSlot { signature_slot, attested_slot })
+        }
+        if let Some(finalized_header) = update.finalized_header() {
+            let finalized_slot = finalized_header.beacon().slot;
+            if finalized_slot > attested_slot {
+                return Err(TrackerError::InvalidFinalizedHeader { finalized_slot, attested_slot })
+            }
+        }
+
+        self.current_max_active_participants =
+            self.current_max_active_participants.max(participants);
+
+        let mut events = vec![];
+        if participants > self.safety_threshold() && attested_slot > self.head.beacon().slot {
+            self.head = attested_header.clone();
+            events.push(TrackerEvent::NewHead(self.head.clone()));
+        }
+        if let Some(finalized_header) = update.finalized_header() {
+            let is_supermajority = participants * 3 >= committee_size * 2;
+            let finalized_slot = finalized_header.beacon().slot;
+            if is_supermajority && finalized_slot > self.finalized.beacon().slot {
+                // roll the participation record over as finality enters a new sync committee
+                // period, like `apply_light_client_update`
+                let period = finalized_slot / self.slots_per_period;
+                if period > self.current_period {
+                    self.previous_max_active_participants = if period == self.current_period + 1 {
+                        self.current_max_active_participants
+                    } else {
+                        0
+                    };
+                    self.current_max_active_participants = 0;
+                    self.current_period = period;
+                }
+                self.finalized = finalized_header.clone();
+                events.push(TrackerEvent::NewFinalized(self.finalized.clone()));
+            }
+        }
+        if let Some(on_change) = self.on_change.as_mut() {
+            events.iter().for_each(|event| on_change(event));
+        }
+        Ok(!events.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::altair::minimal::{
+        LightClientFinalityUpdate, LightClientOptimisticUpdate, SYNC_COMMITTEE_SIZE,
+    };
+    use std::sync::{Arc, Mutex};
+
+    type Header = altair::light_client::LightClientHeader;
+
+    fn header(slot: Slot, proposer_index: usize) -> Header {
+        Header { beacon: BeaconBlockHeader { slot, proposer_index, ..Default::default() } }
+    }
+
+    fn bits(participants: usize) -> Bitvector<SYNC_COMMITTEE_SIZE> {
+        let mut bits = Bitvector::default();
+        for i in 0..participants {
+            bits.set(i, true);
+        }
+        bits
+    }
+
+    fn optimistic(attested: Header, participants: usize) -> LightClientOptimisticUpdate {
+        let signature_slot = attested.beacon.slot + 1;
+        let mut update = LightClientOptimisticUpdate {
+            attested_header: attested,
+            signature_slot,
+            ..Default::default()
+        };
+        update.sync_aggregate.sync_committee_bits = bits(participants);
+        update
+    }
+
+    fn finality(
+        attested: Header,
+        finalized: Header,
+        participants: usize,
+    ) -> LightClientFinalityUpdate {
+        let signature_slot = attested.beacon.slot + 1;
+        let mut update = LightClientFinalityUpdate {
+            attested_header: attested,
+            finalized_header: finalized,
+            signature_slot,
+            ..Default::default()
+        };
+        update.sync_aggregate.sync_committee_bits = bits(participants);
+        update
+    }
+
+    #[test]
+    fn test_tracker_follows_updates() {
+        let context = Context::for_minimal();
+        let slots_per_epoch = context.slots_per_epoch;
+        let mut tracker = Tracker::new(header(0, 0), &context);
+        let events = Arc::new(Mutex::new(vec![]));
+        let recorded = events.clone();
+        tracker.on_change(move |event| {
+            let summary = match event {
+                TrackerEvent::NewHead(header) => ("head", header.beacon.clone()),
+                TrackerEvent::NewFinalized(header) => ("finalized", header.beacon.clone()),
+            };
+            recorded.lock().unwrap().push(summary);
+        });
+
+        // a fully signed optimistic update moves the head
+        let update = optimistic(header(3, 1), SYNC_COMMITTEE_SIZE);
+        assert!(tracker.process(&update, 4).unwrap());
+        assert_eq!(tracker.current_head().beacon.slot, 3);
+
+        // a later but conflicting header signed by too few members does not displace it
+        let conflicting = optimistic(header(4, 2), SYNC_COMMITTEE_SIZE / 2);
+        assert!(!tracker.process(&conflicting, 5).unwrap());
+        assert_eq!(tracker.current_head().beacon, header(3, 1).beacon);
+
+        // nor does an update older than the head, however well signed
+        let stale = optimistic(header(2, 3), SYNC_COMMITTEE_SIZE);
+        assert!(!tracker.process(&stale, 5).unwrap());
+
+        // a finality update without a supermajority moves the head but not the finalized header
+        let attested = header(3 * slots_per_epoch, 4);
+        let update = finality(attested.clone(), header(slots_per_epoch, 5), 20);
+        assert!(tracker.process(&update, 3 * slots_per_epoch + 1).unwrap());
+        assert_eq!(tracker.current_head().beacon, attested.beacon);
+        assert_eq!(tracker.finalized().beacon.slot, 0);
+
+        let attested = header(3 * slots_per_epoch + 1, 4);
+        let finalized = header(slots_per_epoch, 5);
+        let update = finality(attested.clone(), finalized.clone(), 22);
+        assert!(tracker.process(&update, 3 * slots_per_epoch + 2).unwrap());
+        assert_eq!(tracker.current_head().beacon, attested.beacon);
+        assert_eq!(tracker.finalized().beacon, finalized.beacon);
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                ("head", header(3, 1).beacon),
+                ("head", header(3 * slots_per_epoch, 4).beacon),
+                ("head", attested.beacon),
+                ("finalized", finalized.beacon),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tracker_rolls_participation_over_with_finality() {
+        let context = Context::for_minimal();
+        let slots_per_period = context.slots_per_epoch * context.epochs_per_sync_committee_period;
+        let mut tracker = Tracker::new(header(0, 0), &context);
+
+        tracker.process(&optimistic(header(3, 1), SYNC_COMMITTEE_SIZE), 4).unwrap();
+        // finality entering the next period keeps the participation of the last as the record
+        let attested = header(slots_per_period + 2, 2);
+        let update = finality(attested, header(slots_per_period + 1, 3), 22);
+        let current_slot = slots_per_period + 3;
+        assert!(tracker.process(&update, current_slot).unwrap());
+        let thin = optimistic(header(slots_per_period + 3, 4), SYNC_COMMITTEE_SIZE / 2);
+        assert!(!tracker.process(&thin, current_slot + 1).unwrap());
+
+        // once finality moves two periods on, the record starts over
+        let attested = header(3 * slots_per_period + 2, 5);
+        let update = finality(attested, header(3 * slots_per_period + 1, 6), 22);
+        let current_slot = 3 * slots_per_period + 3;
+        assert!(tracker.process(&update, current_slot).unwrap());
+        let thin = optimistic(header(3 * slots_per_period + 3, 7), 12);
+        assert!(tracker.process(&thin, current_slot + 1).unwrap());
+        assert_eq!(tracker.current_head().beacon.proposer_index, 7);
+    }
+
+    #[test]
+    fn test_tracker_rejects_invalid_updates() {
+        let context = Context::for_minimal();
+        let mut tracker = Tracker::new(header(0, 0), &context);
+
+        let update = optimistic(header(3, 1), 0);
+        assert_eq!(
+            tracker.process(&update, 4),
+            Err(TrackerError::InsufficientParticipation { participants: 0, minimum: 1 })
+        );
+        let update = optimistic(header(3, 1), SYNC_COMMITTEE_SIZE);
+        assert_eq!(
+            tracker.process(&update, 3),
+            Err(TrackerError::FutureUpdate { signature_slot: 4, current_slot: 3 })
+        );
+        let mut update = optimistic(header(3, 1), SYNC_COMMITTEE_SIZE);
+        update.signature_slot = 3;
+        assert!(matches!(
+            tracker.process(&update, 4),
+            Err(TrackerError::InvalidSignatureSlot { .. })
+        ));
+        let update = finality(header(3, 1), header(8, 2), SYNC_COMMITTEE_SIZE);
+        assert!(matches!(
+            tracker.process(&update, 4),
+            Err(TrackerError::InvalidFinalizedHeader { .. })
+        ));
+        assert_eq!(tracker.current_head().beacon.slot, 0);
+
+        // an update attesting behind the head can still advance finality
+        tracker.process(&optimistic(header(20, 4), SYNC_COMMITTEE_SIZE), 21).unwrap();
+        let finalized = header(8, 2);
+        let update = finality(header(16, 3), finalized.clone(), SYNC_COMMITTEE_SIZE);
+        assert!(tracker.process(&update, 21).unwrap());
+        assert_eq!(tracker.finalized().beacon, finalized.beacon);
+        assert_eq!(tracker.current_head().beacon.slot, 20);
+    }
+}
@@ -0,0 +1,279 @@
+//! Per-fork maximums on the operations a block may carry, and how close a given block comes to
+//! them.
+use crate::{
+    da_check::scheduled_max_blobs_per_block, phase0::compute_epoch_at_slot,
+    state_transition::Context, types::BeaconBlock, Fork,
+};
+
+/// The maximum number of each kind of operation in a block at a given fork.
+///
+/// Operations introduced after the fork in question are `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct OperationLimits {
+    pub proposer_slashings: usize,
+    pub attester_slashings: usize,
+    pub attestations: usize,
+    pub deposits: usize,
+    pub voluntary_exits: usize,
+    pub bls_to_execution_changes: Option<usize>,
+    /// The number of commitments that may be included, i.e. `MAX_BLOBS_PER_BLOCK` (or its
+    /// Electra counterpart) rather than the (much larger) bound on the SSZ list.
+    pub blob_kzg_commitments: Option<usize>,
+    pub deposit_requests: Option<usize>,
+    pub withdrawal_requests: Option<usize>,
+    pub consolidation_requests: Option<usize>,
+}
+
+pub fn operation_limits(fork: Fork, context: &Context) -> OperationLimits {
+    let is_electra = fork >= Fork::Electra;
+    let (attester_slashings, attestations) = if is_electra {
+        (context.max_attester_slashings_electra, context.max_attestations_electra)
+    } else {
+        (context.max_attester_slashings, context.max_attestations)
+    };
+    let electra = |limit: usize| is_electra.then_some(limit);
+    OperationLimits {
+        proposer_slashings: context.max_proposer_slashings,
+        attester_slashings,
+        attestations,
+        deposits: context.max_deposits,
+        voluntary_exits: context.max_voluntary_exits,
+        bls_to_execution_changes: (fork >= Fork::Capella)
+            .then_some(context.max_bls_to_execution_changes),
+        blob_kzg_commitments: (fork >= Fork::Deneb).then_some(if is_electra {
+            context.max_blobs_per_block_electra
+        } else {
+            context.max_blobs_per_block
+        }),
+        deposit_requests: electra(context.max_deposit_requests_per_payload),
+        withdrawal_requests: electra(context.max_withdrawal_requests_per_payload),
+        consolidation_requests: electra(context.max_consolidation_requests_per_payload),
+    }
+}
+
+/// The occupancy of each operation list in a block body as a fraction of its limit in
+/// [`OperationLimits`], where `1.0` is a full list.
+///
+/// Lists not present at the fork of the block are `None`.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FullnessReport {
+    pub fork: Fork,
+    pub proposer_slashings: f64,
+    pub attester_slashings: f64,
+    pub attestations: f64,
+    pub deposits: f64,
+    pub voluntary_exits: f64,
+    pub bls_to_execution_changes: Option<f64>,
+    pub blob_kzg_commitments: Option<f64>,
+    pub deposit_requests: Option<f64>,
+    pub withdrawal_requests: Option<f64>,
+    pub consolidation_requests: Option<f64>,
+}
+
+impl FullnessReport {
+    /// The names of the lists in the report that are at (or beyond) their limit.
+    pub fn full_lists(&self) -> Vec<&'static str> {
+        [
+            ("proposer_slashings", Some(self.proposer_slashings)),
+            ("attester_slashings", Some(self.attester_slashings)),
+            ("attestations", Some(self.attestations)),
+            ("deposits", Some(self.deposits)),
+            ("voluntary_exits", Some(self.voluntary_exits)),
+            ("bls_to_execution_changes", self.bls_to_execution_changes),
+            ("blob_kzg_commitments", self.blob_kzg_commitments),
+            ("deposit_requests", self.deposit_requests),
+            ("withdrawal_requests", self.withdrawal_requests),
+            ("consolidation_requests", self.consolidation_requests),
+        ]
+        .into_iter()
+        .filter_map(|(name, fullness)| fullness.filter(|&fullness| fullness >= 1.0).map(|_| name))
+        .collect()
+    }
+}
+
+fn fullness(len: usize, limit: usize) -> f64 {
+    if limit == 0 {
+        // nothing can be included, so the list is trivially full
+        1.0
+    } else {
+        len as f64 / limit as f64
+    }
+}
+
+pub fn block_fullness<
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    block: &BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >,
+    context: &Context,
+) -> FullnessReport {
+    let fork = block.version();
+    let mut limits = operation_limits(fork, context);
+    // the blob schedule may raise or lower the limit of the fork from a later epoch
+    if limits.blob_kzg_commitments.is_some() {
+        let epoch = compute_epoch_at_slot(block.slot(), context);
+        limits.blob_kzg_commitments = Some(scheduled_max_blobs_per_block(epoch, context));
+    }
+    let body = block.body();
+    // electra attestations and attester slashings are typed apart from those of earlier forks
+    let (attester_slashings, attestations) = match body.electra() {
+        Some(body) => (body.attester_slashings.len(), body.attestations.len()),
+        None => (
+            body.attester_slashings().map_or(0, |slashings| slashings.len()),
+            body.attestations().map_or(0, |attestations| attestations.len()),
+        ),
+    };
+    FullnessReport {
+        fork,
+        proposer_slashings: fullness(body.proposer_slashings().len(), limits.proposer_slashings),
+        attester_slashings: fullness(attester_slashings, limits.attester_slashings),
+        attestations: fullness(attestations, limits.attestations),
+        deposits: fullness(body.deposits().len(), limits.deposits),
+        voluntary_exits: fullness(body.voluntary_exits().len(), limits.voluntary_exits),
+        bls_to_execution_changes: body
+            .bls_to_execution_changes()
+            .zip(limits.bls_to_execution_changes)
+            .map(|(changes, limit)| fullness(changes.len(), limit)),
+        blob_kzg_commitments: body
+            .blob_kzg_commitments()
+            .zip(limits.blob_kzg_commitments)
+            .map(|(commitments, limit)| fullness(commitments.len(), limit)),
+        deposit_requests: body
+            .execution_requests()
+            .zip(limits.deposit_requests)
+            .map(|(requests, limit)| fullness(requests.deposits.len(), limit)),
+        withdrawal_requests: body
+            .execution_requests()
+            .zip(limits.withdrawal_requests)
+            .map(|(requests, limit)| fullness(requests.withdrawals.len(), limit)),
+        consolidation_requests: body
+            .execution_requests()
+            .zip(limits.consolidation_requests)
+            .map(|(requests, limit)| fullness(requests.consolidations.len(), limit)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{deneb, electra, types::minimal};
+
+    #[test]
+    fn test_operation_limits_across_forks() {
+        let context = Context::for_mainnet();
+        let deneb = operation_limits(Fork::Deneb, &context);
+        let electra = operation_limits(Fork::Electra, &context);
+        assert_eq!((deneb.attestations, deneb.attester_slashings), (128, 2));
+        assert_eq!((electra.attestations, electra.attester_slashings), (8, 1));
+        assert_eq!(deneb.proposer_slashings, electra.proposer_slashings);
+        assert_eq!(deneb.blob_kzg_commitments, Some(6));
+        assert_eq!(electra.blob_kzg_commitments, Some(9));
+        assert_eq!(deneb.deposit_requests, None);
+        assert_eq!(electra.deposit_requests, Some(8192));
+        assert_eq!(electra.withdrawal_requests, Some(16));
+        assert_eq!(electra.consolidation_requests, Some(2));
+
+        let phase0 = operation_limits(Fork::Phase0, &context);
+        assert_eq!(phase0.bls_to_execution_changes, None);
+        assert_eq!(phase0.blob_kzg_commitments, None);
+        assert_eq!(operation_limits(Fork::Capella, &context).bls_to_execution_changes, Some(16));
+    }
+
+    #[test]
+    fn test_block_fullness() {
+        let context = Context::for_minimal();
+        let mut inner = deneb::minimal::BeaconBlock::default();
+        for _ in 0..context.max_attestations {
+            inner.body.attestations.push(Default::default());
+        }
+        inner.body.blob_kzg_commitments.push(Default::default());
+        let block = minimal::BeaconBlock::Deneb(inner);
+
+        let report = block_fullness(&block, &context);
+        assert_eq!(report.fork, Fork::Deneb);
+        assert_eq!(report.attestations, 1.0);
+        assert_eq!(report.deposits, 0.0);
+        assert_eq!(report.blob_kzg_commitments, Some(1.0 / context.max_blobs_per_block as f64));
+        assert_eq!(report.full_lists(), vec!["attestations"]);
+
+        let block = minimal::BeaconBlock::Phase0(Default::default());
+        let report = block_fullness(&block, &context);
+        assert_eq!(report.bls_to_execution_changes, None);
+        assert_eq!(report.deposit_requests, None);
+        assert!(report.full_lists().is_empty());
+    }
+
+    #[test]
+    fn test_electra_block_fullness() {
+        let mut context = Context::for_minimal();
+        context.deneb_fork_epoch = 0;
+        context.electra_fork_epoch = 1;
+        let mut inner = electra::minimal::BeaconBlock {
+            slot: context.electra_fork_epoch * context.slots_per_epoch,
+            ..Default::default()
+        };
+        for _ in 0..context.max_attestations_electra {
+            inner.body.attestations.push(Default::default());
+        }
+        inner.body.attester_slashings.push(Default::default());
+        inner.body.blob_kzg_commitments.push(Default::default());
+        inner.body.execution_requests.consolidations.push(Default::default());
+        let block = minimal::BeaconBlock::Electra(inner);
+
+        let report = block_fullness(&block, &context);
+        assert_eq!(report.fork, Fork::Electra);
+        assert_eq!(report.attestations, 1.0);
+        assert_eq!(report.attester_slashings, 1.0);
+        assert_eq!(
+            report.blob_kzg_commitments,
+            Some(1.0 / context.max_blobs_per_block_electra as f64)
+        );
+        assert_eq!(report.deposit_requests, Some(0.0));
+        assert_eq!(
+            report.consolidation_requests,
+            Some(1.0 / context.max_consolidation_requests_per_payload as f64)
+        );
+        assert_eq!(report.full_lists(), vec!["attester_slashings", "attestations"]);
+    }
+}
@@ -0,0 +1,347 @@
+//! Keeping a pool of operations that are waiting for inclusion in a block consistent, and
+//! selecting which of them to pack into a block.
+//!
+//! A block that changes the withdrawal credentials of a validator twice is invalid: the second
+//! change finds the credentials already rotated. Pools may still receive several changes for the
+//! same validator, e.g. when an operator signs a change to a new address, so each change is
+//! keyed by its validator here.
+//!
+//! Attestations are pooled by their data in an [`AttestationPool`], which aggregates those that
+//! can be aggregated as they arrive.
+use crate::{
+    capella::SignedBlsToExecutionChange,
+    crypto::{aggregate, hash},
+    error::{invalid_operation_error, InvalidBlsToExecutionChange, InvalidOperation},
+    phase0::{compute_domain, Attestation, AttestationData, Validator},
+    primitives::{
+        DomainType, Root, Slot, BLS_WITHDRAWAL_PREFIX, COMPOUNDING_WITHDRAWAL_PREFIX,
+        ETH1_ADDRESS_WITHDRAWAL_PREFIX,
+    },
+    signing::verify_signed_data,
+    ssz::prelude::*,
+    state_transition::{Context, Result},
+};
+use std::collections::{HashMap, HashSet};
+
+/// Check `signed_address_change` against the `validators` of the chain it is for, as
+/// `process_bls_to_execution_change` would, without modifying any state.
+///
+/// A change for a validator that already has execution withdrawal credentials is reported as
+/// [`InvalidBlsToExecutionChange::AlreadyRotated`]: it was valid once but has been superseded,
+/// unlike a change for a validator that could never have been changed.
+pub fn validate_address_change(
+    validators: &[Validator],
+    genesis_validators_root: Root,
+    signed_address_change: &SignedBlsToExecutionChange,
+    context: &Context,
+) -> Result<()> {
+    let address_change = &signed_address_change.message;
+    let validator_index = address_change.validator_index;
+    let Some(validator) = validators.get(validator_index) else {
+        return Err(invalid_operation_error(InvalidOperation::BlsToExecutionChange(
+            InvalidBlsToExecutionChange::ValidatorIndexOutOfBounds(validator_index),
+        )))
+    };
+
+    let withdrawal_credentials = &validator.withdrawal_credentials;
+    match withdrawal_credentials[0] {
+        BLS_WITHDRAWAL_PREFIX => {}
+        ETH1_ADDRESS_WITHDRAWAL_PREFIX | COMPOUNDING_WITHDRAWAL_PREFIX => {
+            return Err(invalid_operation_error(InvalidOperation::BlsToExecutionChange(
+                InvalidBlsToExecutionChange::AlreadyRotated(validator_index),
+            )))
+        }
+        prefix => {
+            return Err(invalid_operation_error(InvalidOperation::BlsToExecutionChange(
+                InvalidBlsToExecutionChange::WithdrawalCredentialsPrefix(prefix),
+            )))
+        }
+    }
+
+    let public_key = &address_change.from_bls_public_key;
+    if withdrawal_credentials[1..] != hash(public_key.as_ref())[1..] {
+        return Err(invalid_operation_error(InvalidOperation::BlsToExecutionChange(
+            InvalidBlsToExecutionChange::PublicKeyMismatch(public_key.clone()),
+        )))
+    }
+
+    let domain = compute_domain(
+        DomainType::BlsToExecutionChange,
+        None,
+        Some(genesis_validators_root),
+        context,
+    )?;
+    verify_signed_data(address_change, &signed_address_change.signature, public_key, domain)
+}
+
+/// Drop the invalid changes of `changes` and keep the first valid change for each validator,
+/// preserving the order of the changes that are kept.
+pub fn dedup_address_changes(
+    changes: impl IntoIterator<Item = SignedBlsToExecutionChange>,
+    validators: &[Validator],
+    genesis_validators_root: Root,
+    context: &Context,
+) -> Vec<SignedBlsToExecutionChange> {
+    let mut seen = HashSet::new();
+    changes
+        .into_iter()
+        .filter(|change| {
+            let validator_index = change.message.validator_index;
+            !seen.contains(&validator_index) &&
+                validate_address_change(validators, genesis_validators_root, change, context)
+                    .is_ok() &&
+                seen.insert(validator_index)
+        })
+        .collect()
+}
+
+/// Select up to `limit` of `changes`, in order, for inclusion in a block, skipping any change
+/// for a validator that already has a change selected so that the block stays valid.
+pub fn select_address_changes<'a>(
+    changes: impl IntoIterator<Item = &'a SignedBlsToExecutionChange>,
+    limit: usize,
+) -> Vec<SignedBlsToExecutionChange> {
+    let mut seen = HashSet::new();
+    changes
+        .into_iter()
+        .filter(|change| seen.insert(change.message.validator_index))
+        .take(limit)
+        .cloned()
+        .collect()
+}
+
+/// Attestations waiting for inclusion in a block, aggregated greedily by their data.
+///
+/// The pool is keyed by the root of each `AttestationData`, computed once per attestation, so
+/// that the many attestations arriving for the same data are matched by comparing roots rather
+/// than whole containers. The signatures of the attestations are assumed to be verified.
+#[derive(Debug, Clone, Default)]
+pub struct AttestationPool<const MAX_VALIDATORS_PER_COMMITTEE: usize> {
+    aggregates: HashMap<
+        HashTreeRootCached<AttestationData>,
+        Vec<Attestation<MAX_VALIDATORS_PER_COMMITTEE>>,
+    >,
+}
+
+impl<const MAX_VALIDATORS_PER_COMMITTEE: usize> AttestationPool<MAX_VALIDATORS_PER_COMMITTEE> {
+    /// Add `attestation` to the pool, merging it into the first aggregate for the same data that
+    /// none of its attesters are part of. An attestation whose attesters are all part of one
+    /// aggregate already adds nothing and is dropped.
+    pub fn insert(&mut self, attestation: Attestation<MAX_VALIDATORS_PER_COMMITTEE>) -> Result<()> {
+        let key = HashTreeRootCached::new(attestation.data.clone())?;
+        let aggregates = self.aggregates.entry(key).or_default();
+        let bits = &attestation.aggregation_bits;
+        if aggregates.iter().any(|existing| is_subset(bits, &existing.aggregation_bits)) {
+            return Ok(())
+        }
+        let Some(existing) =
+            aggregates.iter_mut().find(|existing| is_disjoint(bits, &existing.aggregation_bits))
+        else {
+            aggregates.push(attestation);
+            return Ok(())
+        };
+        existing.signature = aggregate(&[existing.signature.clone(), attestation.signature])?;
+        for (index, bit) in attestation.aggregation_bits.iter().enumerate() {
+            if *bit {
+                existing.aggregation_bits.set(index, true);
+            }
+        }
+        Ok(())
+    }
+
+    /// The aggregates for the data with `data_root`.
+    pub fn aggregates(&self, data_root: &Root) -> &[Attestation<MAX_VALIDATORS_PER_COMMITTEE>] {
+        self.aggregates.get(data_root).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Drop every attestation from a slot before `slot`, e.g. once they can no longer be included.
+    pub fn prune(&mut self, slot: Slot) {
+        self.aggregates.retain(|data, _| data.slot >= slot);
+    }
+
+    /// The number of distinct `AttestationData` in the pool.
+    pub fn len(&self) -> usize {
+        self.aggregates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.aggregates.is_empty()
+    }
+}
+
+// Whether every bit set in `bits` is set in `other`, for bitlists over the same committee.
+fn is_subset<const N: usize>(bits: &Bitlist<N>, other: &Bitlist<N>) -> bool {
+    bits.len() == other.len() && bits.iter().zip(other.iter()).all(|(bit, other)| !*bit || *other)
+}
+
+// Whether no bit is set in both `bits` and `other`, for bitlists over the same committee.
+fn is_disjoint<const N: usize>(bits: &Bitlist<N>, other: &Bitlist<N>) -> bool {
+    bits.len() == other.len() && bits.iter().zip(other.iter()).all(|(bit, other)| !(*bit && *other))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        capella::{self, BlsToExecutionChange},
+        error::InvalidBlock,
+        fixtures::{phase0::genesis_state, secret_keys},
+        phase0,
+        primitives::ExecutionAddress,
+        signing::sign_with_domain,
+        Error,
+    };
+
+    fn address_change(
+        validator_index: usize,
+        address: u8,
+        genesis_validators_root: Root,
+        context: &Context,
+    ) -> SignedBlsToExecutionChange {
+        let secret_key = &secret_keys()[validator_index];
+        let message = BlsToExecutionChange {
+            validator_index,
+            from_bls_public_key: secret_key.public_key(),
+            to_execution_address: ExecutionAddress::try_from([address; 20].as_ref()).unwrap(),
+        };
+        let domain = compute_domain(
+            DomainType::BlsToExecutionChange,
+            None,
+            Some(genesis_validators_root),
+            context,
+        )
+        .unwrap();
+        let signature = sign_with_domain(&message, secret_key, domain).unwrap();
+        SignedBlsToExecutionChange { message, signature }
+    }
+
+    fn address_change_error(result: Result<()>) -> InvalidBlsToExecutionChange {
+        let Err(Error::InvalidBlock(err)) = result else {
+            panic!("expected an invalid address change")
+        };
+        let InvalidBlock::InvalidOperation(InvalidOperation::BlsToExecutionChange(err)) = *err
+        else {
+            panic!("expected an invalid address change")
+        };
+        err
+    }
+
+    fn attestation(data: &AttestationData, attesters: &[usize]) -> phase0::minimal::Attestation {
+        let secret_keys = secret_keys();
+        let mut bits = vec![false; 4];
+        let signatures = attesters
+            .iter()
+            .map(|&attester| {
+                bits[attester] = true;
+                sign_with_domain(data, &secret_keys[attester], Default::default()).unwrap()
+            })
+            .collect::<Vec<_>>();
+        phase0::minimal::Attestation {
+            aggregation_bits: bits.as_slice().try_into().unwrap(),
+            data: data.clone(),
+            signature: aggregate(&signatures).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_attestation_pool_aggregates_by_data() {
+        let data = AttestationData { slot: 1, ..Default::default() };
+        let other = AttestationData { slot: 2, ..Default::default() };
+        let mut pool = AttestationPool::default();
+        for attesters in [&[0][..], &[1], &[0, 2], &[1], &[3]] {
+            pool.insert(attestation(&data, attesters)).unwrap();
+        }
+        pool.insert(attestation(&other, &[0])).unwrap();
+        assert_eq!(pool.len(), 2);
+
+        // {0} and {1} then {3} merge, {0, 2} overlaps and the repeated {1} adds nothing
+        let root = data.hash_tree_root().unwrap();
+        assert_eq!(
+            pool.aggregates(&root),
+            [attestation(&data, &[0, 1, 3]), attestation(&data, &[0, 2])]
+        );
+        // a structurally equal copy of the data finds the same aggregates
+        let copy = HashTreeRootCached::new(data.clone()).unwrap();
+        assert_eq!(pool.aggregates(&copy.root()).len(), 2);
+
+        pool.prune(2);
+        assert!(pool.aggregates(&root).is_empty());
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[test]
+    fn test_pool_with_conflicting_changes() {
+        let context = Context::for_minimal();
+        let state = genesis_state();
+        let root = state.genesis_validators_root;
+        let validators = &state.validators[..];
+
+        let mut forged = address_change(0, 1, root, &context);
+        forged.signature = address_change(1, 1, root, &context).signature;
+        let changes = vec![
+            forged,
+            address_change(0, 2, root, &context),
+            address_change(1, 3, root, &context),
+            address_change(0, 4, root, &context),
+            address_change(1, 5, root, &context),
+        ];
+        let kept = dedup_address_changes(changes.clone(), validators, root, &context);
+        assert_eq!(kept, vec![changes[1].clone(), changes[2].clone()]);
+
+        // once rotated, the remaining changes are distinguishable from ineligible ones
+        let mut state = capella::minimal::BeaconState {
+            genesis_validators_root: root,
+            validators: state.validators.clone(),
+            ..Default::default()
+        };
+        capella::process_bls_to_execution_change(&mut state, &kept[0], &context).unwrap();
+        let result = validate_address_change(&state.validators[..], root, &changes[3], &context);
+        assert!(matches!(
+            address_change_error(result),
+            InvalidBlsToExecutionChange::AlreadyRotated(0)
+        ));
+        let result = capella::process_bls_to_execution_change(&mut state, &changes[3], &context);
+        assert!(matches!(
+            address_change_error(result),
+            InvalidBlsToExecutionChange::AlreadyRotated(0)
+        ));
+
+        state.validators[1].withdrawal_credentials[0] = 0xff;
+        let result = validate_address_change(&state.validators[..], root, &changes[2], &context);
+        assert!(matches!(
+            address_change_error(result),
+            InvalidBlsToExecutionChange::WithdrawalCredentialsPrefix(0xff)
+        ));
+        assert!(dedup_address_changes(changes, &state.validators[..], root, &context).is_empty());
+    }
+
+    #[test]
+    fn test_block_packing_selects_one_change_per_validator() {
+        let context = Context::for_minimal();
+        let state = genesis_state();
+        let root = state.genesis_validators_root;
+
+        let changes = [
+            address_change(0, 1, root, &context),
+            address_change(0, 2, root, &context),
+            address_change(1, 3, root, &context),
+            address_change(2, 4, root, &context),
+        ];
+        let selected = select_address_changes(&changes, 16);
+        assert_eq!(selected, vec![changes[0].clone(), changes[2].clone(), changes[3].clone()]);
+        assert_eq!(
+            select_address_changes(&changes, 2),
+            vec![changes[0].clone(), changes[2].clone()]
+        );
+
+        // the selected changes make a valid block
+        let mut state = capella::minimal::BeaconState {
+            genesis_validators_root: root,
+            validators: state.validators.clone(),
+            ..Default::default()
+        };
+        for change in &selected {
+            capella::process_bls_to_execution_change(&mut state, change, &context).unwrap();
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{
+    crypto::hash,
+    peerdas::PeerDasError,
+    primitives::{ColumnIndex, CustodyIndex},
+    state_transition::Context,
+    Error,
+};
+
+/// The id of a node on the network, as the big-endian encoding of a 256-bit integer like in
+/// discv5.
+pub type NodeId = [u8; 32];
+
+/// The `custody_group_count` custody groups of the node with `node_id`, in increasing order.
+pub fn get_custody_groups(
+    node_id: &NodeId,
+    custody_group_count: usize,
+    context: &Context,
+) -> Result<Vec<CustodyIndex>, Error> {
+    let limit = context.number_of_custody_groups;
+    if custody_group_count > limit {
+        return Err(PeerDasError::CustodyGroupCount { count: custody_group_count, limit }.into())
+    }
+
+    // the spec hashes the little-endian encoding of the id, and of each id after it in turn
+    let mut current_id = *node_id;
+    current_id.reverse();
+    let mut custody_groups = Vec::with_capacity(custody_group_count);
+    while custody_groups.len() < custody_group_count {
+        let digest = hash(current_id);
+        let value = u64::from_le_bytes(digest[..8].try_into().expect("correct size"));
+        let custody_group = (value % limit as u64) as CustodyIndex;
+        if !custody_groups.contains(&custody_group) {
+            custody_groups.push(custody_group);
+        }
+        // wraps around to zero past the largest id
+        for byte in current_id.iter_mut() {
+            let (next, overflow) = byte.overflowing_add(1);
+            *byte = next;
+            if !overflow {
+                break
+            }
+        }
+    }
+    custody_groups.sort_unstable();
+    Ok(custody_groups)
+}
+
+/// The columns of `custody_group`, in increasing order.
+pub fn compute_columns_for_custody_group(
+    custody_group: CustodyIndex,
+    context: &Context,
+) -> Result<Vec<ColumnIndex>, Error> {
+    let number_of_custody_groups = context.number_of_custody_groups;
+    if custody_group >= number_of_custody_groups {
+        return Err(PeerDasError::CustodyGroupOutOfRange(custody_group).into())
+    }
+    let columns_per_group = context.number_of_columns / number_of_custody_groups;
+    Ok((0..columns_per_group).map(|i| number_of_custody_groups * i + custody_group).collect())
+}
+
+/// The columns custodied by the node with `node_id` when it custodies `custody_group_count`
+/// custody groups, in increasing order.
+pub fn get_custody_columns(
+    node_id: &NodeId,
+    custody_group_count: usize,
+    context: &Context,
+) -> Result<Vec<ColumnIndex>, Error> {
+    let mut columns = vec![];
+    for custody_group in get_custody_groups(node_id, custody_group_count, context)? {
+        columns.extend(compute_columns_for_custody_group(custody_group, context)?);
+    }
+    columns.sort_unstable();
+    Ok(columns)
+}
+
+/// The subnet the sidecar of the column at `column_index` is gossiped on.
+pub fn compute_subnet_for_data_column_sidecar(column_index: ColumnIndex, context: &Context) -> u64 {
+    (column_index % context.data_column_sidecar_subnet_count) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_custody_columns() {
+        let mut context = Context::for_minimal();
+        let node_id = [7; 32];
+
+        let groups = get_custody_groups(&node_id, context.custody_requirement, &context).unwrap();
+        assert_eq!(groups.len(), context.custody_requirement);
+        assert!(groups.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(
+            groups,
+            get_custody_groups(&node_id, context.custody_requirement, &context).unwrap()
+        );
+        // a larger custody extends a smaller one
+        let more_groups = get_custody_groups(&node_id, 16, &context).unwrap();
+        assert!(groups.iter().all(|group| more_groups.contains(group)));
+
+        // the largest id wraps around
+        let groups = get_custody_groups(&[0xff; 32], 2, &context).unwrap();
+        assert_eq!(groups.len(), 2);
+
+        let all_columns =
+            get_custody_columns(&node_id, context.number_of_custody_groups, &context).unwrap();
+        assert_eq!(all_columns, (0..context.number_of_columns).collect::<Vec<_>>());
+        let result = get_custody_columns(&node_id, context.number_of_custody_groups + 1, &context);
+        assert!(matches!(
+            result,
+            Err(Error::PeerDas(PeerDasError::CustodyGroupCount { count: 129, limit: 128 }))
+        ));
+
+        // with fewer groups than columns, each group custodies several columns
+        context.number_of_custody_groups = 32;
+        let mut seen = HashSet::new();
+        for group in 0..context.number_of_custody_groups {
+            let columns = compute_columns_for_custody_group(group, &context).unwrap();
+            assert_eq!(columns.len(), 4);
+            assert!(columns.iter().all(|column| column % 32 == group && seen.insert(*column)));
+        }
+        assert_eq!(seen.len(), context.number_of_columns);
+        let result = compute_columns_for_custody_group(32, &context);
+        assert!(matches!(result, Err(Error::PeerDas(PeerDasError::CustodyGroupOutOfRange(32)))));
+        let columns = get_custody_columns(&node_id, context.custody_requirement, &context).unwrap();
+        assert_eq!(columns.len(), 4 * context.custody_requirement);
+
+        assert_eq!(compute_subnet_for_data_column_sidecar(130, &context), 2);
+    }
+}
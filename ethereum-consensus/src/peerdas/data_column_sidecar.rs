@@ -0,0 +1,304 @@
+use crate::{
+    deneb::{
+        blob_sidecar::BLOB_KZG_COMMITMENTS_FIELD_INDEX,
+        polynomial_commitments::{KzgCommitment, KzgProof, BYTES_PER_FIELD_ELEMENT},
+        BeaconBlockBody, SignedBeaconBlockHeader,
+    },
+    error::WrongFork,
+    peerdas::PeerDasError,
+    primitives::{ColumnIndex, Root},
+    ssz::prelude::*,
+    state_transition::Context,
+    Error, Fork,
+};
+
+pub const FIELD_ELEMENTS_PER_CELL: usize = 64;
+pub const BYTES_PER_CELL: usize = FIELD_ELEMENTS_PER_CELL * BYTES_PER_FIELD_ELEMENT;
+/// The depth of the proof of `blob_kzg_commitments` in the block body, which is the same in every
+/// fork with blobs.
+pub const KZG_COMMITMENTS_INCLUSION_PROOF_DEPTH: usize = 4;
+
+pub type Cell = ByteVector<BYTES_PER_CELL>;
+
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct DataColumnIdentifier {
+    pub block_root: Root,
+    #[serde(with = "crate::serde::as_str")]
+    pub index: ColumnIndex,
+}
+
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct DataColumnSidecar<const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize> {
+    #[serde(with = "crate::serde::as_str")]
+    pub index: ColumnIndex,
+    pub column: List<Cell, MAX_BLOB_COMMITMENTS_PER_BLOCK>,
+    pub kzg_commitments: List<KzgCommitment, MAX_BLOB_COMMITMENTS_PER_BLOCK>,
+    pub kzg_proofs: List<KzgProof, MAX_BLOB_COMMITMENTS_PER_BLOCK>,
+    pub signed_block_header: SignedBeaconBlockHeader,
+    pub kzg_commitments_inclusion_proof: Vector<Node, KZG_COMMITMENTS_INCLUSION_PROOF_DEPTH>,
+}
+
+/// Check that `sidecar` is for an existing column and carries a cell and a proof for each of its
+/// commitments, of which there must be some.
+pub fn verify_data_column_sidecar<const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize>(
+    sidecar: &DataColumnSidecar<MAX_BLOB_COMMITMENTS_PER_BLOCK>,
+    context: &Context,
+) -> Result<(), Error> {
+    if sidecar.index >= context.number_of_columns {
+        return Err(PeerDasError::ColumnOutOfRange(sidecar.index).into())
+    }
+    if sidecar.kzg_commitments.is_empty() {
+        return Err(PeerDasError::EmptyColumn(sidecar.index).into())
+    }
+    let commitments = sidecar.kzg_commitments.len();
+    if sidecar.column.len() != commitments || sidecar.kzg_proofs.len() != commitments {
+        return Err(PeerDasError::ColumnLengthMismatch {
+            cells: sidecar.column.len(),
+            commitments,
+            proofs: sidecar.kzg_proofs.len(),
+        }
+        .into())
+    }
+    Ok(())
+}
+
+/// Verify the `kzg_commitments_inclusion_proof` of `sidecar` against the body root in its block
+/// header.
+pub fn verify_data_column_sidecar_inclusion_proof<const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize>(
+    sidecar: &DataColumnSidecar<MAX_BLOB_COMMITMENTS_PER_BLOCK>,
+    context: &Context,
+) -> Result<(), Error> {
+    let header = &sidecar.signed_block_header.message;
+    match context.fork_for(header.slot) {
+        Fork::Deneb | Fork::Electra => {}
+        fork => return Err(WrongFork { expected: Fork::Deneb, found: fork }.into()),
+    }
+    let leaf = sidecar.kzg_commitments.hash_tree_root()?;
+    let branch = sidecar.kzg_commitments_inclusion_proof.as_ref();
+    is_valid_merkle_branch(
+        leaf,
+        branch,
+        KZG_COMMITMENTS_INCLUSION_PROOF_DEPTH,
+        BLOB_KZG_COMMITMENTS_FIELD_INDEX,
+        header.body_root,
+    )
+    .map_err(Into::into)
+}
+
+/// The proof of the `blob_kzg_commitments` of `body` against the root of `body`.
+pub fn compute_kzg_commitments_inclusion_proof<
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    body: &BeaconBlockBody<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >,
+) -> Result<Vec<Node>, Error> {
+    let (proof, _) = body.prove(&["blob_kzg_commitments".into()])?;
+    Ok(proof.branch)
+}
+
+/// Build the sidecar of every column of a block from the cells and proofs of each of its blobs,
+/// in the order of the blobs, as `get_data_column_sidecars` in the spec.
+///
+/// `signed_block_header` is the header of the block with the `kzg_commitments`, which
+/// `kzg_commitments_inclusion_proof` proves against its body root. A block without blobs has no
+/// sidecars.
+pub fn compute_data_column_sidecars<const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize>(
+    signed_block_header: &SignedBeaconBlockHeader,
+    kzg_commitments: &List<KzgCommitment, MAX_BLOB_COMMITMENTS_PER_BLOCK>,
+    kzg_commitments_inclusion_proof: &[Node],
+    cells_and_kzg_proofs: &[(Vec<Cell>, Vec<KzgProof>)],
+    context: &Context,
+) -> Result<Vec<DataColumnSidecar<MAX_BLOB_COMMITMENTS_PER_BLOCK>>, Error> {
+    if cells_and_kzg_proofs.len() != kzg_commitments.len() {
+        return Err(PeerDasError::BlobCount {
+            commitments: kzg_commitments.len(),
+            blobs: cells_and_kzg_proofs.len(),
+        }
+        .into())
+    }
+    if kzg_commitments.is_empty() {
+        return Ok(vec![])
+    }
+    let number_of_columns = context.number_of_columns;
+    for (blob_index, (cells, proofs)) in cells_and_kzg_proofs.iter().enumerate() {
+        for found in [cells.len(), proofs.len()] {
+            if found != number_of_columns {
+                return Err(PeerDasError::CellCount {
+                    blob_index,
+                    expected: number_of_columns,
+                    found,
+                }
+                .into())
+            }
+        }
+    }
+    let kzg_commitments_inclusion_proof =
+        Vector::try_from(kzg_commitments_inclusion_proof.to_vec()).map_err(|(_, err)| err)?;
+
+    (0..number_of_columns)
+        .map(|index| {
+            let column = cells_and_kzg_proofs.iter().map(|(cells, _)| cells[index].clone());
+            let kzg_proofs = cells_and_kzg_proofs.iter().map(|(_, proofs)| proofs[index].clone());
+            Ok(DataColumnSidecar {
+                index,
+                column: List::try_from(column.collect::<Vec<_>>()).map_err(|(_, err)| err)?,
+                kzg_commitments: kzg_commitments.clone(),
+                kzg_proofs: List::try_from(kzg_proofs.collect::<Vec<_>>())
+                    .map_err(|(_, err)| err)?,
+                signed_block_header: signed_block_header.clone(),
+                kzg_commitments_inclusion_proof: kzg_commitments_inclusion_proof.clone(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        deneb::minimal as deneb, electra::minimal as electra, peerdas::minimal::DataColumnSidecar,
+        phase0::BeaconBlockHeader,
+    };
+
+    fn cells_and_kzg_proofs(blob: u8, context: &Context) -> (Vec<Cell>, Vec<KzgProof>) {
+        (0..context.number_of_columns)
+            .map(|index| {
+                let cell = Cell::try_from(vec![blob; BYTES_PER_CELL].as_slice()).unwrap();
+                let proof = KzgProof::try_from([index as u8; 48].as_ref()).unwrap();
+                (cell, proof)
+            })
+            .unzip()
+    }
+
+    #[test]
+    fn test_data_column_sidecars() {
+        let mut context = Context::for_minimal();
+        context.deneb_fork_epoch = 0;
+        context.electra_fork_epoch = 1;
+
+        let path = &["blob_kzg_commitments".into()];
+        let g_index = deneb::BeaconBlockBody::generalized_index(path).unwrap();
+        assert_eq!(g_index, (1 << KZG_COMMITMENTS_INCLUSION_PROOF_DEPTH) + 11);
+        let g_index = electra::BeaconBlockBody::generalized_index(path).unwrap();
+        assert_eq!(g_index, (1 << KZG_COMMITMENTS_INCLUSION_PROOF_DEPTH) + 11);
+
+        let mut body = deneb::BeaconBlockBody::default();
+        for i in 0..2u8 {
+            body.blob_kzg_commitments.push(KzgCommitment::try_from([i; 48].as_ref()).unwrap());
+        }
+        let signed_block_header = SignedBeaconBlockHeader {
+            message: BeaconBlockHeader {
+                slot: 3,
+                body_root: body.hash_tree_root().unwrap(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let proof = compute_kzg_commitments_inclusion_proof(&body).unwrap();
+        let blobs = [cells_and_kzg_proofs(1, &context), cells_and_kzg_proofs(2, &context)];
+
+        let sidecars: Vec<DataColumnSidecar> = compute_data_column_sidecars(
+            &signed_block_header,
+            &body.blob_kzg_commitments,
+            &proof,
+            &blobs,
+            &context,
+        )
+        .unwrap();
+        assert_eq!(sidecars.len(), context.number_of_columns);
+        for (index, sidecar) in sidecars.iter().enumerate() {
+            assert_eq!(sidecar.index, index);
+            assert_eq!(sidecar.column[1], blobs[1].0[index]);
+            assert_eq!(sidecar.kzg_proofs[0], blobs[0].1[index]);
+            verify_data_column_sidecar(sidecar, &context).unwrap();
+            verify_data_column_sidecar_inclusion_proof(sidecar, &context).unwrap();
+        }
+
+        let mut sidecar = sidecars[0].clone();
+        sidecar.kzg_commitments.pop();
+        let result = verify_data_column_sidecar(&sidecar, &context);
+        assert!(matches!(
+            result,
+            Err(Error::PeerDas(PeerDasError::ColumnLengthMismatch {
+                cells: 2,
+                commitments: 1,
+                proofs: 2
+            }))
+        ));
+        assert!(verify_data_column_sidecar_inclusion_proof(&sidecar, &context).is_err());
+        sidecar.index = context.number_of_columns;
+        let result = verify_data_column_sidecar(&sidecar, &context);
+        assert!(matches!(result, Err(Error::PeerDas(PeerDasError::ColumnOutOfRange(_)))));
+
+        let result = compute_data_column_sidecars::<{ deneb::MAX_BLOB_COMMITMENTS_PER_BLOCK }>(
+            &signed_block_header,
+            &body.blob_kzg_commitments,
+            &proof,
+            &[blobs[0].clone(), (blobs[1].0[1..].to_vec(), blobs[1].1.clone())],
+            &context,
+        );
+        assert!(matches!(
+            result,
+            Err(Error::PeerDas(PeerDasError::CellCount {
+                blob_index: 1,
+                expected: 128,
+                found: 127
+            }))
+        ));
+
+        // the proof is the same for the larger block body of electra
+        let mut electra_body = electra::BeaconBlockBody::default();
+        electra_body.blob_kzg_commitments = body.blob_kzg_commitments.clone();
+        let (proof, _) = electra_body.prove(path).unwrap();
+        let mut sidecar = sidecars[0].clone();
+        sidecar.signed_block_header.message.slot = context.slots_per_epoch;
+        sidecar.signed_block_header.message.body_root = electra_body.hash_tree_root().unwrap();
+        sidecar.kzg_commitments_inclusion_proof = Vector::try_from(proof.branch).unwrap();
+        verify_data_column_sidecar_inclusion_proof(&sidecar, &context).unwrap();
+    }
+}
@@ -0,0 +1,51 @@
+//! Data availability sampling from EIP-7594 (PeerDAS): each blob is extended and split into
+//! cells, and the cells at the same position in every blob of a block make up a column, which is
+//! gossiped as a [`DataColumnSidecar`]. A node custodies the columns of the custody groups derived
+//! from its node id rather than every blob.
+//!
+//! The KZG operations over cells (`compute_cells_and_kzg_proofs`, `recover_cells_and_kzg_proofs`
+//! and `verify_cell_kzg_proof_batch`) are not provided: the KZG backend of this crate does not
+//! implement them in the version it is pinned to. Sidecars are built from cells and proofs
+//! computed elsewhere.
+mod custody;
+mod data_column_sidecar;
+
+pub use custody::*;
+pub use data_column_sidecar::*;
+
+use crate::primitives::{ColumnIndex, CustodyIndex};
+use thiserror::Error;
+
+/// If this build supports PeerDAS, i.e. custody and data column sidecars (see the module docs
+/// for the KZG operations it leaves to the caller).
+pub const SUPPORTED: bool = true;
+
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum PeerDasError {
+    #[error("custody group count {count} exceeds the {limit} custody groups")]
+    CustodyGroupCount { count: usize, limit: usize },
+    #[error("custody group {0} is out of range")]
+    CustodyGroupOutOfRange(CustodyIndex),
+    #[error("column {0} is out of range")]
+    ColumnOutOfRange(ColumnIndex),
+    #[error("sidecar of column {0} has no commitments")]
+    EmptyColumn(ColumnIndex),
+    #[error("sidecar has {cells} cells, {commitments} commitments and {proofs} proofs")]
+    ColumnLengthMismatch { cells: usize, commitments: usize, proofs: usize },
+    #[error("block has {commitments} blob commitments but cells for {blobs} blobs")]
+    BlobCount { commitments: usize, blobs: usize },
+    #[error("expected {expected} cells and proofs for blob {blob_index} but found {found}")]
+    CellCount { blob_index: usize, expected: usize, found: usize },
+}
+
+pub mod mainnet {
+    use crate::deneb::mainnet::MAX_BLOB_COMMITMENTS_PER_BLOCK;
+
+    pub type DataColumnSidecar = super::DataColumnSidecar<MAX_BLOB_COMMITMENTS_PER_BLOCK>;
+}
+
+pub mod minimal {
+    use crate::deneb::minimal::MAX_BLOB_COMMITMENTS_PER_BLOCK;
+
+    pub type DataColumnSidecar = super::DataColumnSidecar<MAX_BLOB_COMMITMENTS_PER_BLOCK>;
+}
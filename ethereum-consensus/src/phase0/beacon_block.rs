@@ -97,3 +97,76 @@ pub struct SignedBeaconBlockHeader {
     pub message: BeaconBlockHeader,
     pub signature: BlsSignature,
 }
+
+crate::ssz_size::impl_ssz_size!(BeaconBlockBody<
+    const MAX_PROPOSER_SLASHINGS,
+    const MAX_VALIDATORS_PER_COMMITTEE,
+    const MAX_ATTESTER_SLASHINGS,
+    const MAX_ATTESTATIONS,
+    const MAX_DEPOSITS,
+    const MAX_VOLUNTARY_EXITS,
+> {
+    randao_reveal: BlsSignature,
+    eth1_data: Eth1Data,
+    graffiti: Bytes32,
+    proposer_slashings: List<ProposerSlashing, MAX_PROPOSER_SLASHINGS>,
+    attester_slashings:
+        List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS>,
+    attestations: List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS>,
+    deposits: List<Deposit, MAX_DEPOSITS>,
+    voluntary_exits: List<SignedVoluntaryExit, MAX_VOLUNTARY_EXITS>,
+});
+
+crate::ssz_size::impl_ssz_size!(BeaconBlock<
+    const MAX_PROPOSER_SLASHINGS,
+    const MAX_VALIDATORS_PER_COMMITTEE,
+    const MAX_ATTESTER_SLASHINGS,
+    const MAX_ATTESTATIONS,
+    const MAX_DEPOSITS,
+    const MAX_VOLUNTARY_EXITS,
+> {
+    slot: Slot,
+    proposer_index: ValidatorIndex,
+    parent_root: Root,
+    state_root: Root,
+    body: BeaconBlockBody<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+    >,
+});
+
+crate::ssz_size::impl_ssz_size!(SignedBeaconBlock<
+    const MAX_PROPOSER_SLASHINGS,
+    const MAX_VALIDATORS_PER_COMMITTEE,
+    const MAX_ATTESTER_SLASHINGS,
+    const MAX_ATTESTATIONS,
+    const MAX_DEPOSITS,
+    const MAX_VOLUNTARY_EXITS,
+> {
+    message: BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+    >,
+    signature: BlsSignature,
+});
+
+crate::ssz_size::impl_ssz_size!(BeaconBlockHeader {
+    slot: Slot,
+    proposer_index: ValidatorIndex,
+    parent_root: Root,
+    state_root: Root,
+    body_root: Root,
+});
+
+crate::ssz_size::impl_ssz_size!(SignedBeaconBlockHeader {
+    message: BeaconBlockHeader,
+    signature: BlsSignature,
+});
@@ -86,3 +86,90 @@ pub struct BeaconState<
     pub current_justified_checkpoint: Checkpoint,
     pub finalized_checkpoint: Checkpoint,
 }
+
+crate::ssz::impl_field_roots!(BeaconState<
+    const SLOTS_PER_HISTORICAL_ROOT,
+    const HISTORICAL_ROOTS_LIMIT,
+    const ETH1_DATA_VOTES_BOUND,
+    const VALIDATOR_REGISTRY_LIMIT,
+    const EPOCHS_PER_HISTORICAL_VECTOR,
+    const EPOCHS_PER_SLASHINGS_VECTOR,
+    const MAX_VALIDATORS_PER_COMMITTEE,
+    const PENDING_ATTESTATIONS_BOUND,
+> {
+    genesis_time,
+    genesis_validators_root,
+    slot,
+    fork,
+    latest_block_header,
+    block_roots,
+    state_roots,
+    historical_roots,
+    eth1_data,
+    eth1_data_votes,
+    eth1_deposit_index,
+    validators,
+    balances,
+    randao_mixes,
+    slashings,
+    previous_epoch_attestations,
+    current_epoch_attestations,
+    justification_bits,
+    previous_justified_checkpoint,
+    current_justified_checkpoint,
+    finalized_checkpoint,
+});
+
+/// Returns the root of each field of the `state` in order, so that the root of the `state` is the
+/// merkleization of these roots.
+pub fn compute_state_field_roots<S: FieldRoots>(
+    state: &S,
+) -> Result<Vec<Root>, MerkleizationError> {
+    state.field_roots()
+}
+
+crate::ssz_size::impl_ssz_size!(Fork {
+    previous_version: Version,
+    current_version: Version,
+    epoch: Epoch,
+});
+
+crate::ssz_size::impl_ssz_size!(HistoricalSummary {
+    block_summary_root: Root,
+    state_summary_root: Root,
+});
+
+crate::ssz_size::impl_ssz_size!(BeaconState<
+    const SLOTS_PER_HISTORICAL_ROOT,
+    const HISTORICAL_ROOTS_LIMIT,
+    const ETH1_DATA_VOTES_BOUND,
+    const VALIDATOR_REGISTRY_LIMIT,
+    const EPOCHS_PER_HISTORICAL_VECTOR,
+    const EPOCHS_PER_SLASHINGS_VECTOR,
+    const MAX_VALIDATORS_PER_COMMITTEE,
+    const PENDING_ATTESTATIONS_BOUND,
+> {
+    genesis_time: u64,
+    genesis_validators_root: Root,
+    slot: Slot,
+    fork: Fork,
+    latest_block_header: BeaconBlockHeader,
+    block_roots: Vector<Root, SLOTS_PER_HISTORICAL_ROOT>,
+    state_roots: Vector<Root, SLOTS_PER_HISTORICAL_ROOT>,
+    historical_roots: List<Root, HISTORICAL_ROOTS_LIMIT>,
+    eth1_data: Eth1Data,
+    eth1_data_votes: List<Eth1Data, ETH1_DATA_VOTES_BOUND>,
+    eth1_deposit_index: u64,
+    validators: List<Validator, VALIDATOR_REGISTRY_LIMIT>,
+    balances: List<Gwei, VALIDATOR_REGISTRY_LIMIT>,
+    randao_mixes: Vector<Bytes32, EPOCHS_PER_HISTORICAL_VECTOR>,
+    slashings: Vector<Gwei, EPOCHS_PER_SLASHINGS_VECTOR>,
+    previous_epoch_attestations:
+        List<PendingAttestation<MAX_VALIDATORS_PER_COMMITTEE>, PENDING_ATTESTATIONS_BOUND>,
+    current_epoch_attestations:
+        List<PendingAttestation<MAX_VALIDATORS_PER_COMMITTEE>, PENDING_ATTESTATIONS_BOUND>,
+    justification_bits: Bitvector<JUSTIFICATION_BITS_LENGTH>,
+    previous_justified_checkpoint: Checkpoint,
+    current_justified_checkpoint: Checkpoint,
+    finalized_checkpoint: Checkpoint,
+});
@@ -14,20 +14,21 @@ use crate::{
             get_committee_count_per_slot, get_current_epoch, get_domain, get_indexed_attestation,
             get_previous_epoch, get_randao_mix, increase_balance, initiate_validator_exit,
             is_active_validator, is_slashable_attestation_data, is_slashable_validator,
-            is_valid_indexed_attestation, slash_validator,
+            is_valid_indexed_attestation_with_validation, slash_validator,
         },
         operations::{
-            Attestation, AttesterSlashing, Deposit, DepositMessage, PendingAttestation,
+            Attestation, AttesterSlashing, Deposit, DepositMessage, Eth1Data, PendingAttestation,
             ProposerSlashing, SignedVoluntaryExit,
         },
         validator::Validator,
     },
     primitives::{
-        BlsPublicKey, BlsSignature, Bytes32, DomainType, Gwei, ValidatorIndex, FAR_FUTURE_EPOCH,
+        checked_index, saturating_usize, BlsPublicKey, BlsSignature, Bytes32, DomainType, Gwei,
+        Slot, ValidatorIndex, FAR_FUTURE_EPOCH,
     },
     signing::verify_signed_data,
     ssz::prelude::*,
-    state_transition::{Context, Result},
+    state_transition::{Context, Result, Validation},
 };
 use std::collections::HashSet;
 
@@ -53,6 +54,38 @@ pub fn process_proposer_slashing<
     >,
     proposer_slashing: &ProposerSlashing,
     context: &Context,
+) -> Result<()> {
+    process_proposer_slashing_with_validation(
+        state,
+        proposer_slashing,
+        Validation::Enabled,
+        context,
+    )
+}
+
+pub fn process_proposer_slashing_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    proposer_slashing: &ProposerSlashing,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let header_1 = &proposer_slashing.signed_header_1.message;
     let header_2 = &proposer_slashing.signed_header_2.message;
@@ -90,16 +123,25 @@ pub fn process_proposer_slashing<
         )))
     }
 
-    let epoch = compute_epoch_at_slot(header_1.slot, context);
-    let domain = get_domain(state, DomainType::BeaconProposer, Some(epoch), context)?;
-    for signed_header in [&proposer_slashing.signed_header_1, &proposer_slashing.signed_header_2] {
-        let public_key = &proposer.public_key;
-        if verify_signed_data(&signed_header.message, &signed_header.signature, public_key, domain)
-            .is_err()
+    if validation.verifies_signatures() {
+        let epoch = compute_epoch_at_slot(header_1.slot, context);
+        let domain = get_domain(state, DomainType::BeaconProposer, Some(epoch), context)?;
+        for signed_header in
+            [&proposer_slashing.signed_header_1, &proposer_slashing.signed_header_2]
         {
-            return Err(invalid_operation_error(InvalidOperation::ProposerSlashing(
-                InvalidProposerSlashing::InvalidSignature(signed_header.signature.clone()),
-            )))
+            let public_key = &proposer.public_key;
+            if verify_signed_data(
+                &signed_header.message,
+                &signed_header.signature,
+                public_key,
+                domain,
+            )
+            .is_err()
+            {
+                return Err(invalid_operation_error(InvalidOperation::ProposerSlashing(
+                    InvalidProposerSlashing::InvalidSignature(signed_header.signature.clone()),
+                )))
+            }
         }
     }
 
@@ -128,6 +170,38 @@ pub fn process_attester_slashing<
     >,
     attester_slashing: &AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>,
     context: &Context,
+) -> Result<()> {
+    process_attester_slashing_with_validation(
+        state,
+        attester_slashing,
+        Validation::Enabled,
+        context,
+    )
+}
+
+pub fn process_attester_slashing_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    attester_slashing: &AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let attestation_1 = &attester_slashing.attestation_1;
     let attestation_2 = &attester_slashing.attestation_2;
@@ -141,9 +215,9 @@ pub fn process_attester_slashing<
         )))
     }
 
-    is_valid_indexed_attestation(state, attestation_1, context)?;
+    is_valid_indexed_attestation_with_validation(state, attestation_1, validation, context)?;
 
-    is_valid_indexed_attestation(state, attestation_2, context)?;
+    is_valid_indexed_attestation_with_validation(state, attestation_2, validation, context)?;
 
     let indices_1: HashSet<ValidatorIndex> =
         HashSet::from_iter(attestation_1.attesting_indices.iter().cloned());
@@ -191,6 +265,33 @@ pub fn process_attestation<
     >,
     attestation: &Attestation<MAX_VALIDATORS_PER_COMMITTEE>,
     context: &Context,
+) -> Result<()> {
+    process_attestation_with_validation(state, attestation, Validation::Enabled, context)
+}
+
+pub fn process_attestation_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    attestation: &Attestation<MAX_VALIDATORS_PER_COMMITTEE>,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let data = &attestation.data;
 
@@ -252,11 +353,14 @@ pub fn process_attestation<
 
     // NOTE: swap order of these wrt the spec to avoid mutation
     // to the state that would need to be undone
-    is_valid_indexed_attestation(
-        state,
-        &get_indexed_attestation(state, attestation, context)?,
-        context,
-    )?;
+    if !validation.is_assumed_finalized(data.target.epoch) {
+        is_valid_indexed_attestation_with_validation(
+            state,
+            &get_indexed_attestation(state, attestation, context)?,
+            validation,
+            context,
+        )?;
+    }
 
     let pending_attestation = PendingAttestation {
         aggregation_bits: attestation.aggregation_bits.clone(),
@@ -428,7 +532,7 @@ pub fn process_deposit<
     let leaf = deposit.data.hash_tree_root()?;
     let branch = &deposit.proof;
     let depth = DEPOSIT_CONTRACT_TREE_DEPTH + 1;
-    let index = state.eth1_deposit_index as usize;
+    let index = checked_index(state.eth1_deposit_index)?;
     let root = state.eth1_data.deposit_root;
     if is_valid_merkle_branch(leaf, branch, depth, index, root).is_err() {
         return Err(invalid_operation_error(InvalidOperation::Deposit(
@@ -467,6 +571,38 @@ pub fn process_voluntary_exit<
     >,
     signed_voluntary_exit: &SignedVoluntaryExit,
     context: &Context,
+) -> Result<()> {
+    process_voluntary_exit_with_validation(
+        state,
+        signed_voluntary_exit,
+        Validation::Enabled,
+        context,
+    )
+}
+
+pub fn process_voluntary_exit_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    signed_voluntary_exit: &SignedVoluntaryExit,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let voluntary_exit = &signed_voluntary_exit.message;
     let validator = state.validators.get(voluntary_exit.validator_index).ok_or_else(|| {
@@ -508,14 +644,17 @@ pub fn process_voluntary_exit<
         )))
     }
 
-    let domain = get_domain(state, DomainType::VoluntaryExit, Some(voluntary_exit.epoch), context)?;
-    let public_key = &validator.public_key;
-    verify_signed_data(voluntary_exit, &signed_voluntary_exit.signature, public_key, domain)
-        .map_err(|_| {
-            invalid_operation_error(InvalidOperation::VoluntaryExit(
-                InvalidVoluntaryExit::InvalidSignature(signed_voluntary_exit.signature.clone()),
-            ))
-        })?;
+    if validation.verifies_signatures() {
+        let domain =
+            get_domain(state, DomainType::VoluntaryExit, Some(voluntary_exit.epoch), context)?;
+        let public_key = &validator.public_key;
+        verify_signed_data(voluntary_exit, &signed_voluntary_exit.signature, public_key, domain)
+            .map_err(|_| {
+                invalid_operation_error(InvalidOperation::VoluntaryExit(
+                    InvalidVoluntaryExit::InvalidSignature(signed_voluntary_exit.signature.clone()),
+                ))
+            })?;
+    }
     initiate_validator_exit(state, voluntary_exit.validator_index, context)
 }
 
@@ -639,17 +778,61 @@ pub fn process_randao<
         MAX_VOLUNTARY_EXITS,
     >,
     context: &Context,
+) -> Result<()> {
+    process_randao_with_validation(state, body, Validation::Enabled, context)
+}
+
+pub fn process_randao_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    body: &BeaconBlockBody<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+    >,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let epoch = get_current_epoch(state, context);
 
-    let proposer_index = get_beacon_proposer_index(state, context)?;
-    let proposer = &state.validators[proposer_index];
+    if validation.verifies_signatures() {
+        let proposer_index = get_beacon_proposer_index(state, context)?;
+        let proposer = &state.validators[proposer_index];
 
-    let domain = get_domain(state, DomainType::Randao, Some(epoch), context)?;
-    if verify_signed_data(&epoch, &body.randao_reveal, &proposer.public_key, domain).is_err() {
-        return Err(invalid_operation_error(InvalidOperation::Randao(body.randao_reveal.clone())))
+        let domain = get_domain(state, DomainType::Randao, Some(epoch), context)?;
+        if verify_signed_data(&epoch, &body.randao_reveal, &proposer.public_key, domain).is_err() {
+            return Err(invalid_operation_error(InvalidOperation::Randao(
+                body.randao_reveal.clone(),
+            )))
+        }
     }
 
+    // the reveal is mixed in whether or not its signature was verified
     let mix = xor(get_randao_mix(state, epoch), &hash(body.randao_reveal.as_ref()));
     let mix_index = epoch % context.epochs_per_historical_vector;
     state.randao_mixes[mix_index as usize] = mix;
@@ -701,6 +884,102 @@ pub fn process_eth1_data<
     }
 }
 
+/// How far the eth1 data voting period of a state has progressed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Eth1VotingPeriodProgress {
+    /// The first slot of the voting period containing the state's slot.
+    pub start_slot: Slot,
+    /// The number of slots in a voting period.
+    pub period_slots: u64,
+    /// The number of votes included so far in the period.
+    pub votes: u64,
+    /// The number of votes an `Eth1Data` needs to become `state.eth1_data`.
+    pub majority_threshold: u64,
+    /// The `Eth1Data` with the most votes so far (the earliest voted for on ties) and its count.
+    pub leading_vote: Option<(Eth1Data, u64)>,
+}
+
+/// Count the votes for each distinct `Eth1Data` in `votes`, in order of their first vote.
+pub fn count_eth1_data_votes(votes: &[Eth1Data]) -> Vec<(&Eth1Data, u64)> {
+    let mut tally: Vec<(&Eth1Data, u64)> = vec![];
+    for vote in votes {
+        match tally.iter_mut().find(|(candidate, _)| *candidate == vote) {
+            Some((_, count)) => *count += 1,
+            None => tally.push((vote, 1)),
+        }
+    }
+    tally
+}
+
+/// The `Eth1Data` holding a majority of the votes in the current voting period, if any.
+///
+/// This is the value `process_eth1_data` adopts as `state.eth1_data` once it is reached, so
+/// it can be recomputed from the votes alone.
+pub fn eth1_data_vote_winner<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    context: &Context,
+) -> Option<Eth1Data> {
+    let period_slots = context.epochs_per_eth1_voting_period * context.slots_per_epoch;
+    count_eth1_data_votes(&state.eth1_data_votes)
+        .into_iter()
+        .find(|(_, count)| count * 2 > period_slots)
+        .map(|(vote, _)| vote.clone())
+}
+
+pub fn eth1_voting_period_progress<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    context: &Context,
+) -> Eth1VotingPeriodProgress {
+    let period_slots = context.epochs_per_eth1_voting_period * context.slots_per_epoch;
+    let leading_vote = count_eth1_data_votes(&state.eth1_data_votes)
+        .into_iter()
+        .reduce(|leader, next| if next.1 > leader.1 { next } else { leader })
+        .map(|(vote, count)| (vote.clone(), count));
+    Eth1VotingPeriodProgress {
+        start_slot: state.slot - state.slot % period_slots,
+        period_slots,
+        votes: state.eth1_data_votes.len() as u64,
+        majority_threshold: period_slots / 2 + 1,
+        leading_vote,
+    }
+}
+
 pub fn process_operations<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
@@ -735,10 +1014,49 @@ pub fn process_operations<
         MAX_VOLUNTARY_EXITS,
     >,
     context: &Context,
+) -> Result<()> {
+    process_operations_with_validation(state, body, Validation::Enabled, context)
+}
+
+pub fn process_operations_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    body: &BeaconBlockBody<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+    >,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let expected_deposit_count = usize::min(
         context.max_deposits,
-        (state.eth1_data.deposit_count - state.eth1_deposit_index) as usize,
+        saturating_usize(state.eth1_data.deposit_count - state.eth1_deposit_index),
     );
 
     if body.deposits.len() != expected_deposit_count {
@@ -750,15 +1068,39 @@ pub fn process_operations<
         )))
     }
 
-    body.proposer_slashings
-        .iter()
-        .try_for_each(|op| process_proposer_slashing(state, op, context))?;
-    body.attester_slashings
-        .iter()
-        .try_for_each(|op| process_attester_slashing(state, op, context))?;
-    body.attestations.iter().try_for_each(|op| process_attestation(state, op, context))?;
-    body.deposits.iter().try_for_each(|op| process_deposit(state, op, context))?;
-    body.voluntary_exits.iter().try_for_each(|op| process_voluntary_exit(state, op, context))?;
+    traced!(
+        "proposer_slashings",
+        body.proposer_slashings.iter().try_for_each(
+            |op| process_proposer_slashing_with_validation(state, op, validation, context)
+        ),
+        count = body.proposer_slashings.len()
+    )?;
+    traced!(
+        "attester_slashings",
+        body.attester_slashings.iter().try_for_each(
+            |op| process_attester_slashing_with_validation(state, op, validation, context)
+        ),
+        count = body.attester_slashings.len()
+    )?;
+    traced!(
+        "attestations",
+        body.attestations
+            .iter()
+            .try_for_each(|op| process_attestation_with_validation(state, op, validation, context)),
+        count = body.attestations.len()
+    )?;
+    traced!(
+        "deposits",
+        body.deposits.iter().try_for_each(|op| process_deposit(state, op, context)),
+        count = body.deposits.len()
+    )?;
+    traced!(
+        "voluntary_exits",
+        body.voluntary_exits.iter().try_for_each(|op| process_voluntary_exit_with_validation(
+            state, op, validation, context
+        )),
+        count = body.voluntary_exits.len()
+    )?;
     Ok(())
 }
 
@@ -796,10 +1138,203 @@ pub fn process_block<
         MAX_VOLUNTARY_EXITS,
     >,
     context: &Context,
+) -> Result<()> {
+    process_block_with_validation(state, block, Validation::Enabled, context)
+}
+
+pub fn process_block_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    block: &BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+    >,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     process_block_header(state, block, context)?;
-    process_randao(state, &block.body, context)?;
+    process_randao_with_validation(state, &block.body, validation, context)?;
     process_eth1_data(state, &block.body, context);
-    process_operations(state, &block.body, context)?;
+    process_operations_with_validation(state, &block.body, validation, context)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::InvalidBlock,
+        fixtures::phase0::{genesis_state, signed_block, BLOCK_SLOT},
+        phase0::{presets::minimal, process_block, process_slots, AttestationData},
+        primitives::{CommitteeIndex, Epoch, Root},
+        Error,
+    };
+
+    fn eth1_data(seed: u8) -> Eth1Data {
+        Eth1Data {
+            deposit_root: Root::try_from([seed; 32].as_ref()).unwrap(),
+            deposit_count: seed as u64,
+            block_hash: Bytes32::try_from([seed; 32].as_ref()).unwrap(),
+        }
+    }
+
+    // Process a block voting for `vote` in the slot after the current one.
+    fn vote(state: &mut minimal::BeaconState, vote: &Eth1Data, context: &Context) {
+        let body = minimal::BeaconBlockBody { eth1_data: vote.clone(), ..Default::default() };
+        state.slot += 1;
+        process_eth1_data(state, &body, context);
+    }
+
+    #[test]
+    fn test_expected_deposit_count_beyond_u32() {
+        let context = Context::for_minimal();
+        // more pending deposits than fit in a 32-bit `usize`, which must not wrap around to a
+        // handful of expected deposits
+        let mut state = minimal::BeaconState {
+            eth1_data: Eth1Data { deposit_count: u32::MAX as u64 + 2, ..Default::default() },
+            ..Default::default()
+        };
+        let body = minimal::BeaconBlockBody::default();
+        let err = process_operations(&mut state, &body, &context).unwrap_err();
+        let expected =
+            format!("expected {} deposits but only had 0 deposits", context.max_deposits);
+        assert!(err.to_string().contains(&expected), "{err}");
+    }
+
+    #[test]
+    fn test_eth1_data_vote_winner_when_majority_flips() {
+        let context = Context::for_minimal();
+        let period_slots = context.epochs_per_eth1_voting_period * context.slots_per_epoch;
+        let mut state = minimal::BeaconState { slot: period_slots - 1, ..Default::default() };
+        let (first, second) = (eth1_data(1), eth1_data(2));
+
+        for _ in 0..10 {
+            vote(&mut state, &first, &context);
+        }
+        let progress = eth1_voting_period_progress(&state, &context);
+        assert_eq!(progress.start_slot, period_slots);
+        assert_eq!(progress.votes, 10);
+        assert_eq!(progress.majority_threshold, 17);
+        assert_eq!(progress.leading_vote, Some((first.clone(), 10)));
+        assert_eq!(eth1_data_vote_winner(&state, &context), None);
+
+        // the second candidate overtakes the first, then reaches a majority
+        for i in 1..=17 {
+            vote(&mut state, &second, &context);
+            let winner = eth1_data_vote_winner(&state, &context);
+            if i < 17 {
+                assert_eq!(winner, None);
+                assert_eq!(state.eth1_data, Eth1Data::default());
+            } else {
+                assert_eq!(winner.as_ref(), Some(&second));
+                assert_eq!(state.eth1_data, second);
+            }
+        }
+        let progress = eth1_voting_period_progress(&state, &context);
+        assert_eq!(progress.leading_vote, Some((second.clone(), 17)));
+
+        // further votes cannot change the outcome of the period
+        for _ in 0..5 {
+            vote(&mut state, &first, &context);
+        }
+        assert_eq!(eth1_data_vote_winner(&state, &context), Some(second.clone()));
+        assert_eq!(state.eth1_data, second);
+        assert_eq!(eth1_voting_period_progress(&state, &context).votes, period_slots);
+    }
+
+    #[test]
+    fn test_eth1_data_vote_winner_without_majority() {
+        let context = Context::for_minimal();
+        let current = eth1_data(9);
+        let mut state = minimal::BeaconState { eth1_data: current.clone(), ..Default::default() };
+        for i in 0..context.epochs_per_eth1_voting_period * context.slots_per_epoch {
+            vote(&mut state, &eth1_data(i as u8 % 3), &context);
+        }
+        let progress = eth1_voting_period_progress(&state, &context);
+        assert_eq!(progress.leading_vote, Some((eth1_data(0), 11)));
+        assert_eq!(eth1_data_vote_winner(&state, &context), None);
+        assert_eq!(state.eth1_data, current);
+    }
+
+    #[test]
+    fn test_adversarial_attestations_are_rejected() {
+        let context = Context::for_minimal();
+        let block = signed_block();
+        let committee_count = get_committee_count_per_slot(&genesis_state(), 0, &context);
+
+        let cases: Vec<fn(&mut AttestationData, usize)> = vec![
+            // future slots, in and beyond the target epoch
+            |data, _| data.slot = BLOCK_SLOT + 1,
+            |data, _| data.slot = Slot::MAX,
+            // absurd target epochs
+            |data, _| data.target.epoch = Epoch::MAX,
+            |data, _| {
+                data.slot = Slot::MAX;
+                data.target.epoch = Epoch::MAX;
+            },
+            // committee indices past the committee count
+            |data, committee_count| data.index = committee_count,
+            |data, _| data.index = CommitteeIndex::MAX,
+        ];
+        for mutate in cases {
+            let mut block = block.message.clone();
+            mutate(&mut block.body.attestations[0].data, committee_count);
+
+            let mut state = genesis_state();
+            process_slots(&mut state, BLOCK_SLOT, &context).unwrap();
+            let mut pre_state = state.clone();
+            let attestation = &block.body.attestations[0];
+            for validation in [Validation::Enabled, Validation::Disabled] {
+                let result = process_attestation_with_validation(
+                    &mut pre_state,
+                    attestation,
+                    validation,
+                    &context,
+                );
+                let Err(Error::InvalidBlock(err)) = result else {
+                    panic!("attestation with {:?} is invalid", attestation.data)
+                };
+                assert!(matches!(*err, InvalidBlock::InvalidOperation(_)));
+            }
+
+            let Err(Error::InvalidBlock(err)) = process_block(&mut state, &block, &context) else {
+                panic!("block with attestation {:?} is invalid", attestation.data)
+            };
+            assert!(matches!(
+                *err,
+                InvalidBlock::InvalidOperation(InvalidOperation::Attestation(
+                    InvalidAttestation::InvalidTargetEpoch { .. } |
+                        InvalidAttestation::InvalidSlot { .. } |
+                        InvalidAttestation::NotTimely { .. } |
+                        InvalidAttestation::InvalidIndex { .. }
+                ))
+            ));
+        }
+    }
+}
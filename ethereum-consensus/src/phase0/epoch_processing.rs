@@ -12,11 +12,11 @@ use crate::{
         operations::{Checkpoint, PendingAttestation},
     },
     primitives::{Epoch, Gwei, ValidatorIndex, GENESIS_EPOCH},
+    rewards::{phase0_attestation_component_reward, phase0_base_reward, phase0_proposer_reward},
     ssz::prelude::*,
     state_transition::{Context, Result},
     Error,
 };
-use integer_sqrt::IntegerSquareRoot;
 use std::{collections::HashSet, mem};
 
 pub fn get_matching_source_attestations<
@@ -678,9 +678,7 @@ pub fn get_base_reward<
 ) -> Result<Gwei> {
     let total_balance = get_total_active_balance(state, context)?;
     let effective_balance = state.validators[index].effective_balance;
-    Ok(effective_balance * context.base_reward_factor /
-        total_balance.integer_sqrt() /
-        BASE_REWARDS_PER_EPOCH)
+    Ok(phase0_base_reward(effective_balance, total_balance, context))
 }
 
 pub fn get_proposer_reward<
@@ -706,7 +704,8 @@ pub fn get_proposer_reward<
     attesting_index: ValidatorIndex,
     context: &Context,
 ) -> Result<Gwei> {
-    Ok(get_base_reward(state, attesting_index, context)? / context.proposer_reward_quotient)
+    let base_reward = get_base_reward(state, attesting_index, context)?;
+    Ok(phase0_proposer_reward(base_reward, context))
 }
 
 pub fn get_finality_delay<
@@ -791,7 +790,6 @@ pub fn get_attestation_component_deltas<
     let unslashed_attesting_indices =
         get_unslashed_attesting_indices(state, attestations, context)?;
     let attesting_balance = get_total_balance(state, &unslashed_attesting_indices, context)?;
-    let increment = context.effective_balance_increment; // Factored out from balance totals to avoid uint64 overflow
     for i in get_eligible_validator_indices(state, context) {
         if unslashed_attesting_indices.contains(&i) {
             if is_in_inactivity_leak(state, context) {
@@ -799,9 +797,12 @@ pub fn get_attestation_component_deltas<
                 // optimal participation receives full base reward compensation here.
                 rewards[i] += get_base_reward(state, i, context)?;
             } else {
-                let reward_numerator =
-                    get_base_reward(state, i, context)? * (attesting_balance / increment);
-                rewards[i] += reward_numerator / (total_balance / increment);
+                rewards[i] += phase0_attestation_component_reward(
+                    get_base_reward(state, i, context)?,
+                    attesting_balance,
+                    total_balance,
+                    context,
+                );
             }
         } else {
             penalties[i] += get_base_reward(state, i, context)?;
@@ -1058,15 +1059,19 @@ pub fn process_epoch<
     >,
     context: &Context,
 ) -> Result<()> {
-    process_justification_and_finalization(state, context)?;
-    process_rewards_and_penalties(state, context)?;
-    process_registry_updates(state, context)?;
-    process_slashings(state, context)?;
-    process_eth1_data_reset(state, context);
-    process_effective_balance_updates(state, context);
-    process_slashings_reset(state, context);
-    process_randao_mixes_reset(state, context);
-    process_historical_roots_update(state, context)?;
-    process_participation_record_updates(state);
+    trace_span!("process_epoch", epoch = state.slot / context.slots_per_epoch);
+    traced!(
+        "process_justification_and_finalization",
+        process_justification_and_finalization(state, context)
+    )?;
+    traced!("process_rewards_and_penalties", process_rewards_and_penalties(state, context))?;
+    traced!("process_registry_updates", process_registry_updates(state, context))?;
+    traced!("process_slashings", process_slashings(state, context))?;
+    traced!("process_eth1_data_reset", process_eth1_data_reset(state, context));
+    traced!("process_effective_balance_updates", process_effective_balance_updates(state, context));
+    traced!("process_slashings_reset", process_slashings_reset(state, context));
+    traced!("process_randao_mixes_reset", process_randao_mixes_reset(state, context));
+    traced!("process_historical_roots_update", process_historical_roots_update(state, context))?;
+    traced!("process_participation_record_updates", process_participation_record_updates(state));
     Ok(())
 }
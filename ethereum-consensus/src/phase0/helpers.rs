@@ -10,12 +10,13 @@ use crate::{
         validator::Validator,
     },
     primitives::{
-        Bytes32, CommitteeIndex, Domain, DomainType, Epoch, ForkDigest, Gwei, Root,
-        ShuffledIndices, Slot, ValidatorIndex, Version, FAR_FUTURE_EPOCH, GENESIS_EPOCH,
+        saturating_usize, Bytes32, CommitteeIndex, Domain, DomainType, Epoch, ForkDigest, Gwei,
+        Hash32, Root, ShuffledIndices, Slot, ValidatorIndex, Version, FAR_FUTURE_EPOCH,
+        GENESIS_EPOCH,
     },
     signing::{compute_signing_root, verify_signed_data},
     ssz::prelude::*,
-    state_transition::{Context, Result},
+    state_transition::{Context, Result, Validation},
     Error,
 };
 use std::{cmp, collections::HashSet};
@@ -90,6 +91,38 @@ pub fn is_valid_indexed_attestation<
     >,
     indexed_attestation: &IndexedAttestation<MAX_VALIDATORS_PER_COMMITTEE>,
     context: &Context,
+) -> Result<()> {
+    is_valid_indexed_attestation_with_validation(
+        state,
+        indexed_attestation,
+        Validation::Enabled,
+        context,
+    )
+}
+
+pub fn is_valid_indexed_attestation_with_validation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    indexed_attestation: &IndexedAttestation<MAX_VALIDATORS_PER_COMMITTEE>,
+    validation: Validation,
+    context: &Context,
 ) -> Result<()> {
     let attesting_indices = &indexed_attestation.attesting_indices;
 
@@ -130,6 +163,10 @@ pub fn is_valid_indexed_attestation<
         public_keys.push(public_key);
     }
 
+    if !validation.verifies_signatures() {
+        return Ok(())
+    }
+
     let domain = get_domain(
         state,
         DomainType::BeaconAttester,
@@ -247,7 +284,7 @@ pub fn get_current_epoch<
 }
 
 pub fn compute_shuffled_index(
-    mut index: usize,
+    index: usize,
     index_count: usize,
     seed: &Bytes32,
     context: &Context,
@@ -256,30 +293,58 @@ pub fn compute_shuffled_index(
         return Err(Error::InvalidShufflingIndex { index, total: index_count })
     }
 
+    let index = (0..context.shuffle_round_count)
+        .fold(index, |index, round| swap_or_not(index, index_count, seed, round as u8));
+    Ok(index)
+}
+
+/// The inverse of [`compute_shuffled_index`]: the position `index` is moved to by the shuffle.
+///
+/// Each round of the swap-or-not shuffle is an involution, so the shuffle is undone by running
+/// the rounds in reverse order.
+pub fn compute_unshuffled_index(
+    index: usize,
+    index_count: usize,
+    seed: &Bytes32,
+    context: &Context,
+) -> Result<usize> {
+    if index >= index_count {
+        return Err(Error::InvalidShufflingIndex { index, total: index_count })
+    }
+
+    let index = (0..context.shuffle_round_count)
+        .rev()
+        .fold(index, |index, round| swap_or_not(index, index_count, seed, round as u8));
+    Ok(index)
+}
+
+// A single round of the swap-or-not shuffle, swapping `index` with its mirror around the pivot
+// of the round if the bit for the pair is set.
+fn swap_or_not(index: usize, index_count: usize, seed: &Bytes32, round: u8) -> usize {
     let mut pivot_input = [0u8; 33];
     pivot_input[..32].copy_from_slice(seed.as_ref());
-    let mut source_input = [0u8; 37];
-    source_input[..32].copy_from_slice(seed.as_ref());
-    for current_round in 0..context.shuffle_round_count {
-        pivot_input[32] = current_round as u8;
-        let pivot_bytes: [u8; 8] = hash(pivot_input).as_ref()[..8].try_into().unwrap();
+    pivot_input[32] = round;
+    let pivot_bytes: [u8; 8] = hash(pivot_input).as_ref()[..8].try_into().unwrap();
 
-        let pivot = (u64::from_le_bytes(pivot_bytes) as usize) % index_count;
-        let flip = (pivot + index_count - index) % index_count;
-        let position = cmp::max(index, flip);
-        let position_bytes: [u8; 4] = ((position / 256) as u32).to_le_bytes();
+    let pivot = (u64::from_le_bytes(pivot_bytes) % index_count as u64) as usize;
+    let flip = (pivot + index_count - index) % index_count;
+    let position = cmp::max(index, flip);
+    let position_bytes: [u8; 4] = ((position / 256) as u32).to_le_bytes();
 
-        source_input[32] = current_round as u8;
-        source_input[33..].copy_from_slice(&position_bytes);
+    let mut source_input = [0u8; 37];
+    source_input[..32].copy_from_slice(seed.as_ref());
+    source_input[32] = round;
+    source_input[33..].copy_from_slice(&position_bytes);
 
-        let source = hash(source_input);
+    let source = hash(source_input);
 
-        let byte = source.as_ref()[(position % 256) / 8];
-        let bit = (byte >> (position % 8)) % 2;
-        index = if bit != 0 { flip } else { index };
+    let byte = source.as_ref()[(position % 256) / 8];
+    let bit = (byte >> (position % 8)) % 2;
+    if bit != 0 {
+        flip
+    } else {
+        index
     }
-
-    Ok(index)
 }
 
 // Compute the consensus shuffling across an entire list of `indices`.
@@ -305,7 +370,7 @@ pub fn compute_shuffled_indices(
     for current_round in (0..=context.shuffle_round_count - 1).rev() {
         pivot_input[32] = current_round as u8;
         let pivot_bytes: [u8; 8] = hash(pivot_input).as_ref()[..8].try_into().unwrap();
-        let pivot = u64::from_le_bytes(pivot_bytes) as usize % index_count;
+        let pivot = (u64::from_le_bytes(pivot_bytes) % index_count as u64) as usize;
         source_input[32] = current_round as u8;
 
         let position = (pivot >> 8) as u32;
@@ -610,11 +675,11 @@ pub fn get_block_root_at_slot<
     if slot >= state.slot || state.slot > (slot + SLOTS_PER_HISTORICAL_ROOT as Slot) {
         return Err(Error::SlotOutOfRange {
             requested: slot,
-            lower_bound: state.slot - 1,
-            upper_bound: state.slot + SLOTS_PER_HISTORICAL_ROOT as Slot,
+            lower_bound: state.slot.saturating_sub(SLOTS_PER_HISTORICAL_ROOT as Slot),
+            upper_bound: state.slot.saturating_sub(1),
         })
     }
-    Ok(&state.block_roots[slot as usize % SLOTS_PER_HISTORICAL_ROOT])
+    Ok(&state.block_roots[(slot % SLOTS_PER_HISTORICAL_ROOT as u64) as usize])
 }
 
 pub fn get_randao_mix<
@@ -639,7 +704,7 @@ pub fn get_randao_mix<
     >,
     epoch: Epoch,
 ) -> &Bytes32 {
-    let epoch = epoch as usize % EPOCHS_PER_HISTORICAL_VECTOR;
+    let epoch = (epoch % EPOCHS_PER_HISTORICAL_VECTOR as u64) as usize;
     &state.randao_mixes[epoch]
 }
 
@@ -699,10 +764,10 @@ pub fn get_validator_churn_limit<
 ) -> usize {
     let active_validator_indices =
         get_active_validator_indices(state, get_current_epoch(state, context));
-    u64::max(
+    saturating_usize(u64::max(
         context.min_per_epoch_churn_limit,
         active_validator_indices.len() as u64 / context.churn_limit_quotient,
-    ) as usize
+    ))
 }
 
 pub fn get_seed<
@@ -729,7 +794,11 @@ pub fn get_seed<
     domain_type: DomainType,
     context: &Context,
 ) -> Bytes32 {
-    let mix_epoch = epoch + (context.epochs_per_historical_vector - context.min_seed_lookahead) - 1;
+    // NOTE: `get_randao_mix` reduces modulo `EPOCHS_PER_HISTORICAL_VECTOR` so reduce `epoch` first
+    // to avoid overflow on attacker-supplied epochs
+    let mix_epoch = epoch % context.epochs_per_historical_vector +
+        (context.epochs_per_historical_vector - context.min_seed_lookahead) -
+        1;
     let mix = get_randao_mix(state, mix_epoch);
     let mut input = [0u8; 44];
     input[..4].copy_from_slice(&domain_type.as_bytes());
@@ -797,14 +866,167 @@ pub fn get_beacon_committee<
     context: &Context,
 ) -> Result<Vec<ValidatorIndex>> {
     let epoch = compute_epoch_at_slot(slot, context);
-    let committees_per_slot = get_committee_count_per_slot(state, epoch, context);
     let indices = get_active_validator_indices(state, epoch);
     let seed = get_seed(state, epoch, DomainType::BeaconAttester, context);
+    if let Some(committee_cache) = &context.committee_cache {
+        let shuffling = committee_cache.shuffling(epoch, &seed, indices, context);
+        return shuffling.beacon_committee(slot, index, context).map(|committee| committee.to_vec())
+    }
+    let committees_per_slot = get_committee_count_per_slot(state, epoch, context);
+    if index >= committees_per_slot {
+        return Err(Error::OutOfBounds { requested: index, bound: committees_per_slot })
+    }
     let index = (slot % context.slots_per_epoch) * committees_per_slot as u64 + index as u64;
     let count = committees_per_slot as u64 * context.slots_per_epoch;
     compute_committee(&indices, &seed, index as usize, count as usize, context)
 }
 
+/// A digest of the committee shuffling for `epoch`, so that two nodes can check they agree on
+/// committees without exchanging states.
+///
+/// The preimage is the `DomainType::BeaconAttester` seed for `epoch` (32 bytes) followed by the
+/// active validator indices for `epoch` in shuffled order, each as a little-endian `u64`. The
+/// digest is the SHA-256 hash of the preimage. This layout is stable across versions of this
+/// crate.
+pub fn committee_fingerprint<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    epoch: Epoch,
+    context: &Context,
+) -> Result<Hash32> {
+    let indices = get_active_validator_indices(state, epoch);
+    let seed = get_seed(state, epoch, DomainType::BeaconAttester, context);
+    // the shuffling is the concatenation of all committees, i.e. a single committee of everyone
+    let shuffled_indices = compute_committee(&indices, &seed, 0, 1, context)?;
+    let mut preimage = Vec::with_capacity(32 + 8 * shuffled_indices.len());
+    preimage.extend_from_slice(seed.as_ref());
+    for index in shuffled_indices {
+        preimage.extend_from_slice(&(index as u64).to_le_bytes());
+    }
+    Ok(hash(preimage))
+}
+
+/// A digest of the committees for `slot`, like [`committee_fingerprint`] but localized to a slot.
+///
+/// The preimage is the `DomainType::BeaconAttester` seed for the epoch of `slot` (32 bytes), then
+/// `slot` and the number of committees per slot as little-endian `u64`s, then the members of each
+/// committee of `slot` in committee index order, each as a little-endian `u64`. The digest is the
+/// SHA-256 hash of the preimage. This layout is stable across versions of this crate.
+pub fn committee_fingerprint_at_slot<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    slot: Slot,
+    context: &Context,
+) -> Result<Hash32> {
+    let epoch = compute_epoch_at_slot(slot, context);
+    let committees_per_slot = get_committee_count_per_slot(state, epoch, context);
+    let indices = get_active_validator_indices(state, epoch);
+    let seed = get_seed(state, epoch, DomainType::BeaconAttester, context);
+    let count = committees_per_slot * context.slots_per_epoch as usize;
+    let mut preimage =
+        Vec::with_capacity(48 + 8 * indices.len() / context.slots_per_epoch as usize);
+    preimage.extend_from_slice(seed.as_ref());
+    preimage.extend_from_slice(&slot.to_le_bytes());
+    preimage.extend_from_slice(&(committees_per_slot as u64).to_le_bytes());
+    for index in 0..committees_per_slot {
+        let index = (slot % context.slots_per_epoch) as usize * committees_per_slot + index;
+        for member in compute_committee(&indices, &seed, index, count, context)? {
+            preimage.extend_from_slice(&(member as u64).to_le_bytes());
+        }
+    }
+    Ok(hash(preimage))
+}
+
+/// The slot, committee index and position within that committee of the member at `position` in
+/// the shuffling of `index_count` active validators for `epoch`.
+pub fn compute_committee_position(
+    position: usize,
+    index_count: usize,
+    committees_per_slot: usize,
+    epoch: Epoch,
+    context: &Context,
+) -> (Slot, CommitteeIndex, usize) {
+    let count = committees_per_slot * context.slots_per_epoch as usize;
+    // committee `k` spans `index_count * k / count..index_count * (k + 1) / count` so `position`
+    // is in the last committee starting at or before it
+    let committee = ((position + 1) * count - 1) / index_count;
+    let start = index_count * committee / count;
+    let slot =
+        compute_start_slot_at_epoch(epoch, context) + (committee / committees_per_slot) as Slot;
+    (slot, committee % committees_per_slot, position - start)
+}
+
+/// The slot, committee index and position within that committee of the attester duty of
+/// `validator_index` in `epoch`, or `None` if the validator is not active in `epoch`.
+///
+/// Only the shuffled position of `validator_index` is computed so this is cheaper than
+/// enumerating the committees of `epoch` with [`get_beacon_committee`].
+pub fn committee_position_of<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    epoch: Epoch,
+    validator_index: ValidatorIndex,
+    context: &Context,
+) -> Option<(Slot, CommitteeIndex, usize)> {
+    let indices = get_active_validator_indices(state, epoch);
+    let index = indices.binary_search(&validator_index).ok()?;
+    let seed = get_seed(state, epoch, DomainType::BeaconAttester, context);
+    let position = compute_unshuffled_index(index, indices.len(), &seed, context).ok()?;
+    let committees_per_slot = get_committee_count_per_slot(state, epoch, context);
+    Some(compute_committee_position(position, indices.len(), committees_per_slot, epoch, context))
+}
+
 pub fn get_beacon_proposer_index<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
@@ -834,6 +1056,9 @@ pub fn get_beacon_proposer_index<
     input[32..40].copy_from_slice(&state.slot.to_le_bytes());
     let seed = hash(input);
     let indices = get_active_validator_indices(state, epoch);
+    if indices.is_empty() {
+        return Err(Error::NoActiveValidators(epoch))
+    }
     compute_proposer_index(state, &indices, &seed, context)
 }
 
@@ -1116,7 +1341,7 @@ pub fn slash_validator<
         state.validators[slashed_index].withdrawable_epoch,
         epoch + context.epochs_per_slashings_vector,
     );
-    let slashings_index = epoch as usize % EPOCHS_PER_SLASHINGS_VECTOR;
+    let slashings_index = (epoch % EPOCHS_PER_SLASHINGS_VECTOR as u64) as usize;
     state.slashings[slashings_index] += state.validators[slashed_index].effective_balance;
     decrease_balance(
         state,
@@ -1170,3 +1395,196 @@ pub fn get_eligible_validator_indices<
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::phase0::{genesis_state, genesis_state_with_validators},
+        phase0::{get_base_reward, minimal, process_slots, state_transition},
+        state_transition::{Shuffling, Validation},
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_empty_and_small_validator_sets() {
+        let context = Context::for_minimal();
+        for count in 0..=2 {
+            let mut state = genesis_state_with_validators(count);
+            let proposer_index = get_beacon_proposer_index(&state, &context);
+            if count == 0 {
+                assert!(matches!(proposer_index, Err(Error::NoActiveValidators(0))));
+            } else {
+                assert!(proposer_index.unwrap() < count);
+            }
+
+            assert_eq!(get_committee_count_per_slot(&state, 0, &context), 1);
+            let mut members = vec![];
+            for slot in 0..context.slots_per_epoch {
+                members.extend(get_beacon_committee(&state, slot, 0, &context).unwrap());
+            }
+            members.sort();
+            assert_eq!(members, (0..count).collect::<Vec<_>>());
+
+            let churn_limit = get_validator_churn_limit(&state, &context);
+            assert_eq!(churn_limit as u64, context.min_per_epoch_churn_limit);
+            let total_active_balance = get_total_active_balance(&state, &context).unwrap();
+            if count == 0 {
+                assert_eq!(total_active_balance, context.effective_balance_increment);
+            } else {
+                assert_eq!(total_active_balance, count as u64 * context.max_effective_balance);
+            }
+            for index in 0..count {
+                assert!(get_base_reward(&state, index, &context).unwrap() > 0);
+            }
+
+            // epoch processing, rewards included, goes through
+            process_slots(&mut state, 2 * context.slots_per_epoch + 1, &context).unwrap();
+        }
+
+        // a block cannot be applied without a proposer
+        let mut state = genesis_state_with_validators(0);
+        let mut block = minimal::SignedBeaconBlock::default();
+        block.message.slot = 1;
+        let result = state_transition(&mut state, &block, Validation::Disabled, &context);
+        assert!(matches!(result, Err(Error::NoActiveValidators(0))));
+    }
+
+    #[test]
+    fn test_committee_fingerprint_is_pinned() {
+        let context = Context::for_minimal();
+        let state = genesis_state();
+
+        let fingerprint = committee_fingerprint(&state, 0, &context).unwrap();
+        assert_eq!(
+            hex::encode(fingerprint),
+            "2e40ce12ff6c16fb4f76b177588a9d1042f8f0b77439e957bc74a4e27e90ca7a"
+        );
+        let fingerprint = committee_fingerprint(&state, 1, &context).unwrap();
+        assert_eq!(
+            hex::encode(fingerprint),
+            "2b253ed8f525c5ee359d2e572b143daefba0335650038194d5a7ff1bf6e9fb1b"
+        );
+        let fingerprint = committee_fingerprint_at_slot(&state, 0, &context).unwrap();
+        assert_eq!(
+            hex::encode(fingerprint),
+            "b20400977c2beb98927e2a89e999f6498c1a352893eb83416e3b984b451f0f10"
+        );
+        let fingerprint = committee_fingerprint_at_slot(&state, 3, &context).unwrap();
+        assert_eq!(
+            hex::encode(fingerprint),
+            "2edcc0754d1c3a5b3c6bb37a7d45b74022b9e5c79c4dbd6ff547e0044414d234"
+        );
+    }
+
+    #[test]
+    fn test_committee_fingerprint_tracks_committees() {
+        let context = Context::for_minimal();
+        let state = genesis_state();
+        let epoch = 0;
+
+        // the preimage is the seed followed by every committee of the epoch in order
+        let mut preimage = get_seed(&state, epoch, DomainType::BeaconAttester, &context).to_vec();
+        let committees_per_slot = get_committee_count_per_slot(&state, epoch, &context);
+        for slot in 0..context.slots_per_epoch {
+            for index in 0..committees_per_slot {
+                for member in get_beacon_committee(&state, slot, index, &context).unwrap() {
+                    preimage.extend_from_slice(&(member as u64).to_le_bytes());
+                }
+            }
+        }
+        assert_eq!(committee_fingerprint(&state, epoch, &context).unwrap(), hash(preimage));
+
+        let mut other = state.clone();
+        other.validators[7].exit_epoch = epoch;
+        assert_ne!(
+            committee_fingerprint(&other, epoch, &context).unwrap(),
+            committee_fingerprint(&state, epoch, &context).unwrap()
+        );
+        let mut other = state.clone();
+        for mix in other.randao_mixes.iter_mut() {
+            *mix = Bytes32::try_from([1u8; 32].as_ref()).unwrap();
+        }
+        assert_ne!(
+            committee_fingerprint_at_slot(&other, 1, &context).unwrap(),
+            committee_fingerprint_at_slot(&state, 1, &context).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_adversarial_committee_inputs_are_errors() {
+        let state = genesis_state();
+        let mut uncached = Context::for_minimal();
+        uncached.committee_cache = None;
+
+        for context in [&Context::for_minimal(), &uncached] {
+            let committees_per_slot = get_committee_count_per_slot(&state, 0, context);
+            let result = get_beacon_committee(&state, 0, committees_per_slot, context);
+            assert!(matches!(
+                result,
+                Err(Error::OutOfBounds { requested, bound })
+                    if requested == committees_per_slot && bound == committees_per_slot
+            ));
+            let result = get_beacon_committee(&state, Slot::MAX, CommitteeIndex::MAX, context);
+            assert!(matches!(result, Err(Error::OutOfBounds { .. })));
+            assert!(get_beacon_committee(&state, Slot::MAX, 0, context).is_ok());
+        }
+
+        let context = Context::for_minimal();
+        let _ = get_seed(&state, Epoch::MAX, DomainType::BeaconAttester, &context);
+
+        // the state is at genesis so there is no block root to return yet
+        for slot in [0, 1, Slot::MAX] {
+            let result = get_block_root_at_slot(&state, slot);
+            assert!(matches!(
+                result,
+                Err(Error::SlotOutOfRange { lower_bound: 0, upper_bound: 0, .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_committee_position_of_matches_committees() {
+        let context = Context::for_minimal();
+        let seed = Bytes32::try_from([7u8; 32].as_ref()).unwrap();
+        for index_count in [1, 2, 5, 100] {
+            for index in 0..index_count {
+                let shuffled = compute_shuffled_index(index, index_count, &seed, &context).unwrap();
+                let unshuffled =
+                    compute_unshuffled_index(shuffled, index_count, &seed, &context).unwrap();
+                assert_eq!(unshuffled, index);
+            }
+        }
+
+        let mut exited = genesis_state();
+        exited.validators[3].exit_epoch = 1;
+        for state in [genesis_state(), genesis_state_with_validators(5), exited] {
+            for epoch in 0..2 {
+                let mut expected = HashMap::new();
+                let committees_per_slot = get_committee_count_per_slot(&state, epoch, &context);
+                let start_slot = compute_start_slot_at_epoch(epoch, &context);
+                for slot in start_slot..start_slot + context.slots_per_epoch {
+                    for index in 0..committees_per_slot {
+                        let committee =
+                            get_beacon_committee(&state, slot, index, &context).unwrap();
+                        for (position, member) in committee.into_iter().enumerate() {
+                            expected.insert(member, (slot, index, position));
+                        }
+                    }
+                }
+
+                let seed = get_seed(&state, epoch, DomainType::BeaconAttester, &context);
+                let indices = get_active_validator_indices(&state, epoch);
+                let shuffling = Shuffling::new(epoch, seed, indices, &context);
+                for validator_index in 0..state.validators.len() + 1 {
+                    let position = committee_position_of(&state, epoch, validator_index, &context);
+                    assert_eq!(position, expected.get(&validator_index).copied());
+                    assert_eq!(
+                        shuffling.committee_position_of(validator_index, &context),
+                        position
+                    );
+                }
+            }
+        }
+    }
+}
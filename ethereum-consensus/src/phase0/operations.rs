@@ -1,14 +1,22 @@
 use crate::{
     phase0::{beacon_block::SignedBeaconBlockHeader, constants::DEPOSIT_CONTRACT_TREE_DEPTH},
     primitives::{
-        BlsPublicKey, BlsSignature, Bytes32, CommitteeIndex, Epoch, Gwei, Hash32, Root, Slot,
-        ValidatorIndex,
+        BlsPublicKey, BlsSignature, Bytes32, CommitteeIndex, Epoch, Gwei, Hash32, IsZero, Root,
+        Slot, ValidatorIndex,
     },
     ssz::prelude::*,
 };
 
 #[derive(
-    Default, Clone, Debug, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+    Default,
+    Clone,
+    Debug,
+    SimpleSerialize,
+    PartialEq,
+    Eq,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
 )]
 pub struct Checkpoint {
     #[serde(with = "crate::serde::as_str")]
@@ -16,8 +24,22 @@ pub struct Checkpoint {
     pub root: Root,
 }
 
+impl IsZero for Checkpoint {
+    fn is_zero(&self) -> bool {
+        self.epoch == 0 && self.root.is_zero()
+    }
+}
+
 #[derive(
-    Default, Clone, Debug, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+    Default,
+    Clone,
+    Debug,
+    SimpleSerialize,
+    PartialEq,
+    Eq,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
 )]
 pub struct AttestationData {
     #[serde(with = "crate::serde::as_str")]
@@ -70,6 +92,12 @@ pub struct Eth1Data {
     pub block_hash: Hash32,
 }
 
+impl IsZero for Eth1Data {
+    fn is_zero(&self) -> bool {
+        self.deposit_root.is_zero() && self.deposit_count == 0 && self.block_hash.is_zero()
+    }
+}
+
 #[derive(Default, Debug, SimpleSerialize, Clone, serde::Serialize, serde::Deserialize)]
 pub struct DepositMessage {
     #[serde(rename = "pubkey")]
@@ -138,3 +166,67 @@ pub struct SignedVoluntaryExit {
     pub message: VoluntaryExit,
     pub signature: BlsSignature,
 }
+
+crate::ssz_size::impl_ssz_size!(Checkpoint { epoch: Epoch, root: Root });
+
+crate::ssz_size::impl_ssz_size!(AttestationData {
+    slot: Slot,
+    index: CommitteeIndex,
+    beacon_block_root: Root,
+    source: Checkpoint,
+    target: Checkpoint,
+});
+
+crate::ssz_size::impl_ssz_size!(IndexedAttestation<const MAX_VALIDATORS_PER_COMMITTEE> {
+    attesting_indices: List<ValidatorIndex, MAX_VALIDATORS_PER_COMMITTEE>,
+    data: AttestationData,
+    signature: BlsSignature,
+});
+
+crate::ssz_size::impl_ssz_size!(PendingAttestation<const MAX_VALIDATORS_PER_COMMITTEE> {
+    aggregation_bits: Bitlist<MAX_VALIDATORS_PER_COMMITTEE>,
+    data: AttestationData,
+    inclusion_delay: Slot,
+    proposer_index: ValidatorIndex,
+});
+
+crate::ssz_size::impl_ssz_size!(Attestation<const MAX_VALIDATORS_PER_COMMITTEE> {
+    aggregation_bits: Bitlist<MAX_VALIDATORS_PER_COMMITTEE>,
+    data: AttestationData,
+    signature: BlsSignature,
+});
+
+crate::ssz_size::impl_ssz_size!(Eth1Data {
+    deposit_root: Root,
+    deposit_count: u64,
+    block_hash: Hash32,
+});
+
+crate::ssz_size::impl_ssz_size!(DepositData {
+    public_key: BlsPublicKey,
+    withdrawal_credentials: Bytes32,
+    amount: Gwei,
+    signature: BlsSignature,
+});
+
+crate::ssz_size::impl_ssz_size!(ProposerSlashing {
+    signed_header_1: SignedBeaconBlockHeader,
+    signed_header_2: SignedBeaconBlockHeader,
+});
+
+crate::ssz_size::impl_ssz_size!(AttesterSlashing<const MAX_VALIDATORS_PER_COMMITTEE> {
+    attestation_1: IndexedAttestation<MAX_VALIDATORS_PER_COMMITTEE>,
+    attestation_2: IndexedAttestation<MAX_VALIDATORS_PER_COMMITTEE>,
+});
+
+crate::ssz_size::impl_ssz_size!(Deposit {
+    proof: Vector<Node, DEPOSIT_PROOF_LENGTH>,
+    data: DepositData,
+});
+
+crate::ssz_size::impl_ssz_size!(VoluntaryExit { epoch: Epoch, validator_index: ValidatorIndex });
+
+crate::ssz_size::impl_ssz_size!(SignedVoluntaryExit {
+    message: VoluntaryExit,
+    signature: BlsSignature,
+});
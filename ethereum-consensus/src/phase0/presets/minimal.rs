@@ -72,6 +72,7 @@ pub const PRESET: Preset = Preset {
     max_attestations: MAX_ATTESTATIONS,
     max_deposits: MAX_DEPOSITS,
     max_voluntary_exits: MAX_VOLUNTARY_EXITS,
+    target_aggregators_per_committee: TARGET_AGGREGATORS_PER_COMMITTEE,
 };
 
 pub type IndexedAttestation = spec::IndexedAttestation<MAX_VALIDATORS_PER_COMMITTEE>;
@@ -1,3 +1,4 @@
+pub mod gnosis;
 pub mod mainnet;
 pub mod minimal;
 
@@ -36,4 +37,5 @@ pub struct Preset {
     pub max_attestations: usize,
     pub max_deposits: usize,
     pub max_voluntary_exits: usize,
+    pub target_aggregators_per_committee: usize,
 }
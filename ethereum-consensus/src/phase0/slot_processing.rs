@@ -1,11 +1,32 @@
 use crate::{
     phase0::{beacon_state::BeaconState, epoch_processing::process_epoch},
-    primitives::{Root, Slot},
+    primitives::{IsZero, Root, Slot},
     ssz::prelude::*,
-    state_transition::{Context, Result},
+    state_transition::{Context, Result, StateRootCache},
     Error,
 };
 
+// Indices of the fields of the `BeaconState` touched by `process_slot`; these are the same across
+// all forks.
+pub const STATE_SLOT_FIELD_INDEX: usize = 2;
+pub const STATE_LATEST_BLOCK_HEADER_FIELD_INDEX: usize = 4;
+pub const STATE_BLOCK_ROOTS_FIELD_INDEX: usize = 5;
+pub const STATE_STATE_ROOTS_FIELD_INDEX: usize = 6;
+
+// Computes the root of a container from the roots of its fields; supports containers with
+// between 17 and 64 fields, like every version of the `BeaconState`.
+pub fn merkleize_field_roots(field_roots: &[Root]) -> Result<Root> {
+    let mut leaves = field_roots.to_vec();
+    let root = if leaves.len() <= 32 {
+        leaves.resize(32, Root::default());
+        Vector::<Root, 32>::try_from(leaves).expect("correct length").hash_tree_root()?
+    } else {
+        leaves.resize(64, Root::default());
+        Vector::<Root, 64>::try_from(leaves).expect("correct length").hash_tree_root()?
+    };
+    Ok(root)
+}
+
 pub fn process_slots<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
@@ -28,16 +49,77 @@ pub fn process_slots<
     >,
     slot: Slot,
     context: &Context,
+) -> Result<()> {
+    // NOTE: the state root cache keeps the merkle trees of the largest fields across epochs, so
+    // only the parts of them changed by epoch processing are hashed again.
+    let mut state_root_cache = StateRootCache::default();
+    process_slots_with_field_roots(
+        state,
+        slot,
+        |state| state_root_cache.field_roots(state),
+        context,
+    )
+}
+
+// Equivalent to `process_slots`, taking the roots of the fields of the `state` from
+// `field_roots_of` (e.g. a `StateRootCache`) whenever they are not already known.
+pub fn process_slots_with_field_roots<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    slot: Slot,
+    mut field_roots_of: impl FnMut(
+        &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+        >,
+    ) -> Result<Vec<Root>>,
+    context: &Context,
 ) -> Result<()> {
     if state.slot >= slot {
         return Err(Error::TransitionToPreviousSlot { requested: slot, current: state.slot })
     }
+    trace_span!("process_slots", from = state.slot, to = slot);
+    // NOTE: `process_slot` only touches a handful of fields, so rather than merkleizing the entire
+    // state for every slot, the roots of each field are cached and only the touched fields are
+    // updated; the cache is refreshed after each epoch transition.
+    let mut field_roots = traced!("hash_tree_root", field_roots_of(state))?;
     while state.slot < slot {
-        process_slot(state, context)?;
-        if (state.slot + 1) % context.slots_per_epoch == 0 {
+        process_slot_with_field_roots(state, &mut field_roots, context)?;
+        let is_epoch_transition = (state.slot + 1) % context.slots_per_epoch == 0;
+        if is_epoch_transition {
             process_epoch(state, context)?;
         }
         state.slot += 1;
+        if is_epoch_transition {
+            if state.slot < slot {
+                field_roots = traced!("hash_tree_root", field_roots_of(state))?;
+            }
+        } else {
+            field_roots[STATE_SLOT_FIELD_INDEX] = state.slot.hash_tree_root()?;
+        }
     }
     Ok(())
 }
@@ -68,7 +150,7 @@ pub fn process_slot<
     let root_index = state.slot % context.slots_per_historical_root;
     state.state_roots[root_index as usize] = previous_state_root;
 
-    if state.latest_block_header.state_root == Root::default() {
+    if state.latest_block_header.state_root.is_zero() {
         state.latest_block_header.state_root = previous_state_root;
     }
 
@@ -78,3 +160,122 @@ pub fn process_slot<
 
     Ok(())
 }
+
+// Equivalent to `process_slot` given the current `field_roots` of the `state`, which are kept up to
+// date with the changes made to the `state`.
+pub fn process_slot_with_field_roots<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    field_roots: &mut [Root],
+    context: &Context,
+) -> Result<()> {
+    let previous_state_root = merkleize_field_roots(field_roots)?;
+    let root_index = (state.slot % context.slots_per_historical_root) as usize;
+    state.state_roots[root_index] = previous_state_root;
+    field_roots[STATE_STATE_ROOTS_FIELD_INDEX] = state.state_roots.hash_tree_root()?;
+
+    if state.latest_block_header.state_root.is_zero() {
+        state.latest_block_header.state_root = previous_state_root;
+    }
+
+    let previous_block_root = state.latest_block_header.hash_tree_root()?;
+    field_roots[STATE_LATEST_BLOCK_HEADER_FIELD_INDEX] = previous_block_root;
+    state.block_roots[root_index] = previous_block_root;
+    field_roots[STATE_BLOCK_ROOTS_FIELD_INDEX] = state.block_roots.hash_tree_root()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        crypto::SecretKey, phase0::Validator, primitives::FAR_FUTURE_EPOCH, ssz::prelude::*,
+        state_transition::Context,
+    };
+
+    const VALIDATOR_COUNT: usize = 64;
+    const EPOCHS: u64 = 50;
+
+    fn validators(context: &Context) -> Vec<Validator> {
+        (0..VALIDATOR_COUNT)
+            .map(|i| {
+                let secret_key = SecretKey::key_gen(&[i as u8 + 1; 32]).unwrap();
+                Validator {
+                    public_key: secret_key.public_key(),
+                    effective_balance: context.max_effective_balance,
+                    exit_epoch: FAR_FUTURE_EPOCH,
+                    withdrawable_epoch: FAR_FUTURE_EPOCH,
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_process_slots_matches_unoptimized_phase0() {
+        use crate::phase0::{minimal::BeaconState, process_epoch, process_slot, process_slots};
+
+        let context = Context::for_minimal();
+        let mut state = BeaconState::default();
+        for validator in validators(&context) {
+            state.validators.push(validator);
+            state.balances.push(context.max_effective_balance);
+        }
+        let mut expected = state.clone();
+
+        let slot = EPOCHS * context.slots_per_epoch + 3;
+        process_slots(&mut state, slot, &context).unwrap();
+        while expected.slot < slot {
+            process_slot(&mut expected, &context).unwrap();
+            if (expected.slot + 1) % context.slots_per_epoch == 0 {
+                process_epoch(&mut expected, &context).unwrap();
+            }
+            expected.slot += 1;
+        }
+        assert_eq!(serialize(&state).unwrap(), serialize(&expected).unwrap());
+    }
+
+    #[test]
+    fn test_process_slots_matches_unoptimized_deneb() {
+        use crate::deneb::{minimal::BeaconState, process_epoch, process_slot, process_slots};
+
+        let context = Context::for_minimal();
+        let mut state = BeaconState::default();
+        for validator in validators(&context) {
+            state.validators.push(validator);
+            state.balances.push(context.max_effective_balance);
+            state.previous_epoch_participation.push(0);
+            state.current_epoch_participation.push(0);
+            state.inactivity_scores.push(0);
+        }
+        let mut expected = state.clone();
+
+        let slot = EPOCHS * context.slots_per_epoch + 3;
+        process_slots(&mut state, slot, &context).unwrap();
+        while expected.slot < slot {
+            process_slot(&mut expected, &context).unwrap();
+            if (expected.slot + 1) % context.slots_per_epoch == 0 {
+                process_epoch(&mut expected, &context).unwrap();
+            }
+            expected.slot += 1;
+        }
+        assert_eq!(serialize(&state).unwrap(), serialize(&expected).unwrap());
+    }
+}
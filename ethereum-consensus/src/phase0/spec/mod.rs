@@ -6,12 +6,20 @@ pub use crate::{
             BeaconBlock, BeaconBlockBody, BeaconBlockHeader, SignedBeaconBlock,
             SignedBeaconBlockHeader,
         },
-        beacon_state::{BeaconState, Fork, ForkData, HistoricalBatch, HistoricalSummary},
+        beacon_state::{
+            compute_state_field_roots, BeaconState, Fork, ForkData, HistoricalBatch,
+            HistoricalSummary,
+        },
         block_processing::{
-            add_validator_to_registry, apply_deposit, get_validator_from_deposit,
-            process_attestation, process_attester_slashing, process_block, process_block_header,
-            process_deposit, process_eth1_data, process_operations, process_proposer_slashing,
-            process_randao, process_voluntary_exit, xor,
+            add_validator_to_registry, apply_deposit, count_eth1_data_votes, eth1_data_vote_winner,
+            eth1_voting_period_progress, get_validator_from_deposit, process_attestation,
+            process_attestation_with_validation, process_attester_slashing,
+            process_attester_slashing_with_validation, process_block, process_block_header,
+            process_block_with_validation, process_deposit, process_eth1_data, process_operations,
+            process_operations_with_validation, process_proposer_slashing,
+            process_proposer_slashing_with_validation, process_randao,
+            process_randao_with_validation, process_voluntary_exit,
+            process_voluntary_exit_with_validation, xor, Eth1VotingPeriodProgress,
         },
         constants::{
             BASE_REWARDS_PER_EPOCH, DEPOSIT_CONTRACT_TREE_DEPTH, DEPOSIT_DATA_LIST_BOUND,
@@ -32,27 +40,43 @@ pub use crate::{
         },
         genesis::{get_genesis_block, initialize_beacon_state_from_eth1, is_valid_genesis_state},
         helpers::{
-            compute_activation_exit_epoch, compute_committee, compute_domain,
-            compute_epoch_at_slot, compute_fork_data_root, compute_fork_digest,
+            committee_fingerprint, committee_fingerprint_at_slot, committee_position_of,
+            compute_activation_exit_epoch, compute_committee, compute_committee_position,
+            compute_domain, compute_epoch_at_slot, compute_fork_data_root, compute_fork_digest,
             compute_proposer_index, compute_shuffled_index, compute_shuffled_indices,
-            compute_start_slot_at_epoch, decrease_balance, get_active_validator_indices,
-            get_attesting_indices, get_beacon_committee, get_beacon_proposer_index, get_block_root,
-            get_block_root_at_slot, get_committee_count_per_slot, get_current_epoch, get_domain,
+            compute_start_slot_at_epoch, compute_unshuffled_index, decrease_balance,
+            get_active_validator_indices, get_attesting_indices, get_beacon_committee,
+            get_beacon_proposer_index, get_block_root, get_block_root_at_slot,
+            get_committee_count_per_slot, get_current_epoch, get_domain,
             get_eligible_validator_indices, get_indexed_attestation, get_previous_epoch,
             get_randao_mix, get_seed, get_total_active_balance, get_total_balance,
             get_validator_churn_limit, increase_balance, initiate_validator_exit,
             is_active_validator, is_eligible_for_activation, is_eligible_for_activation_queue,
             is_slashable_attestation_data, is_slashable_validator, is_valid_indexed_attestation,
-            sample_proposer_index, slash_validator, verify_block_signature,
+            is_valid_indexed_attestation_with_validation, sample_proposer_index, slash_validator,
+            verify_block_signature,
         },
         operations::{
             Attestation, AttestationData, AttesterSlashing, Checkpoint, Deposit, DepositData,
             DepositMessage, Eth1Data, IndexedAttestation, PendingAttestation, ProposerSlashing,
             SignedVoluntaryExit, VoluntaryExit,
         },
-        slot_processing::{process_slot, process_slots},
-        state_transition::{state_transition, state_transition_block_in_slot},
-        validator::{AggregateAndProof, Eth1Block, SignedAggregateAndProof, Validator},
+        slot_processing::{
+            merkleize_field_roots, process_slot, process_slot_with_field_roots, process_slots,
+            process_slots_with_field_roots, STATE_BLOCK_ROOTS_FIELD_INDEX,
+            STATE_LATEST_BLOCK_HEADER_FIELD_INDEX, STATE_SLOT_FIELD_INDEX,
+            STATE_STATE_ROOTS_FIELD_INDEX,
+        },
+        state_transition::{
+            state_transition, state_transition_block_in_slot,
+            state_transition_block_in_slot_with_state_root,
+        },
+        validator::{
+            choose_eth1_vote, compute_domain_at_slot, is_aggregator, sign_aggregate_and_proof,
+            sign_attestation_data, sign_slot_selection_proof, verify_aggregate_and_proof_signature,
+            verify_attestation_data_signature, verify_slot_selection_proof, AggregateAndProof,
+            Eth1Block, ForkInfo, SignedAggregateAndProof, Validator,
+        },
     },
     primitives::*,
     signing::*,
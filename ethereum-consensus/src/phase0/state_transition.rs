@@ -1,9 +1,10 @@
 use crate::{
     phase0::{
         beacon_block::SignedBeaconBlock, beacon_state::BeaconState,
-        block_processing::process_block, helpers::verify_block_signature,
+        block_processing::process_block_with_validation, helpers::verify_block_signature,
         slot_processing::process_slots,
     },
+    primitives::Root,
     ssz::prelude::*,
     state_transition::{Context, Result, Validation},
     Error,
@@ -48,16 +49,78 @@ pub fn state_transition_block_in_slot<
     validation: Validation,
     context: &Context,
 ) -> Result<()> {
+    state_transition_block_in_slot_with_state_root(
+        state,
+        signed_block,
+        validation,
+        |state| Ok(state.hash_tree_root()?),
+        context,
+    )
+}
+
+// Equivalent to `state_transition_block_in_slot`, computing the root of the post-state checked
+// against the block with `state_root_of` (e.g. from a `StateRootCache`).
+pub fn state_transition_block_in_slot_with_state_root<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    signed_block: &SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+    >,
+    validation: Validation,
+    state_root_of: impl FnOnce(
+        &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+        >,
+    ) -> Result<Root>,
+    context: &Context,
+) -> Result<()> {
+    trace_span!("process_block", slot = signed_block.message.slot);
     let validate_result = match validation {
-        Validation::Enabled => true,
+        Validation::Enabled |
+        Validation::AssumeFinalizedBelow(_) |
+        Validation::SkipSignatureVerification => true,
         Validation::Disabled => false,
     };
-    if validate_result {
+    if validate_result && validation.verifies_signatures() {
         verify_block_signature(state, signed_block, context)?;
     }
     let block = &signed_block.message;
-    process_block(state, block, context)?;
-    if validate_result && block.state_root != state.hash_tree_root()? {
+    process_block_with_validation(state, block, validation, context)?;
+    if validate_result && block.state_root != traced!("hash_tree_root", state_root_of(state))? {
         Err(Error::InvalidStateRoot)
     } else {
         Ok(())
@@ -104,3 +167,72 @@ pub fn state_transition<
 
     state_transition_block_in_slot(state, signed_block, validation, context)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        crypto::hash,
+        fixtures::phase0::{genesis_state, signed_block},
+        phase0::{get_current_epoch, get_randao_mix, process_randao_with_validation, xor},
+        primitives::{BlsSignature, Root},
+    };
+
+    #[test]
+    fn test_skip_signature_verification_ignores_signatures() {
+        let context = Context::for_minimal();
+        let mut block = signed_block();
+        let mut expected = genesis_state();
+        state_transition(&mut expected, &block, Validation::Enabled, &context).unwrap();
+
+        block.signature = BlsSignature::default();
+        let mut state = genesis_state();
+        let err = state_transition(&mut state, &block, Validation::Enabled, &context).unwrap_err();
+        assert!(matches!(err, Error::Crypto(_)), "{err}");
+
+        let mut state = genesis_state();
+        state_transition(&mut state, &block, Validation::SkipSignatureVerification, &context)
+            .unwrap();
+        assert_eq!(state.hash_tree_root().unwrap(), expected.hash_tree_root().unwrap());
+    }
+
+    #[test]
+    fn test_skip_signature_verification_checks_state_root() {
+        let context = Context::for_minimal();
+        let mut block = signed_block();
+        block.signature = BlsSignature::default();
+        block.message.state_root = Root::try_from([0xffu8; 32].as_ref()).unwrap();
+        let mut state = genesis_state();
+        let err =
+            state_transition(&mut state, &block, Validation::SkipSignatureVerification, &context)
+                .unwrap_err();
+        assert!(matches!(err, Error::InvalidStateRoot), "{err}");
+    }
+
+    #[test]
+    fn test_unverified_randao_reveal_is_mixed_in() {
+        let context = Context::for_minimal();
+        let mut block = signed_block();
+        let mut state = genesis_state();
+        process_slots(&mut state, block.message.slot, &context).unwrap();
+        let epoch = get_current_epoch(&state, &context);
+        let previous_mix = get_randao_mix(&state, epoch).clone();
+
+        let mut garbage = [0u8; 96];
+        garbage[0] = 0xc1;
+        block.message.body.randao_reveal = BlsSignature::try_from(garbage.as_slice()).unwrap();
+        let body = &block.message.body;
+        let mut checked = state.clone();
+        assert!(process_randao_with_validation(&mut checked, body, Validation::Enabled, &context)
+            .is_err());
+        process_randao_with_validation(
+            &mut state,
+            body,
+            Validation::SkipSignatureVerification,
+            &context,
+        )
+        .unwrap();
+        let expected = xor(&previous_mix, &hash(body.randao_reveal.as_ref()));
+        assert_eq!(get_randao_mix(&state, epoch), &expected);
+    }
+}
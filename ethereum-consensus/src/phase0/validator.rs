@@ -1,7 +1,19 @@
 use crate::{
-    phase0::operations::Attestation,
-    primitives::{BlsPublicKey, BlsSignature, Bytes32, Epoch, Gwei, Root, ValidatorIndex},
+    crypto::{hash, SecretKey},
+    phase0::{
+        beacon_state::{BeaconState, Fork},
+        block_processing::count_eth1_data_votes,
+        helpers::{compute_domain, compute_epoch_at_slot},
+        operations::{Attestation, AttestationData, Eth1Data},
+    },
+    primitives::{
+        BlsPublicKey, BlsSignature, Bytes32, Domain, DomainType, Epoch, Gwei, Root, Slot,
+        ValidatorIndex, Version, GENESIS_EPOCH,
+    },
+    signing::{sign_with_domain, verify_signed_data},
     ssz::prelude::*,
+    state_transition::{Context, Result},
+    types,
 };
 
 #[derive(
@@ -45,3 +57,445 @@ pub struct SignedAggregateAndProof<const MAX_VALIDATORS_PER_COMMITTEE: usize> {
     pub message: AggregateAndProof<MAX_VALIDATORS_PER_COMMITTEE>,
     pub signature: BlsSignature,
 }
+
+/// The `fork` and `genesis_validators_root` of a chain, i.e. what is needed from a
+/// `BeaconState` to compute signing domains.
+///
+/// The serde representation matches the `fork_info` object of remote signer (Web3Signer)
+/// requests.
+#[derive(Default, Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ForkInfo {
+    pub fork: Fork,
+    pub genesis_validators_root: Root,
+}
+
+impl ForkInfo {
+    pub fn new(
+        previous_version: Version,
+        current_version: Version,
+        epoch: Epoch,
+        genesis_validators_root: Root,
+    ) -> Self {
+        Self { fork: Fork { previous_version, current_version, epoch }, genesis_validators_root }
+    }
+
+    pub fn from_state<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+    >(
+        state: &types::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+    ) -> Self {
+        Self {
+            fork: state.fork().clone(),
+            genesis_validators_root: state.genesis_validators_root(),
+        }
+    }
+
+    /// The fork info of a chain following the fork schedule in `context` at `epoch`, matching
+    /// the `fork` of its states in that epoch.
+    pub fn at_epoch(context: &Context, epoch: Epoch, genesis_validators_root: Root) -> Self {
+        let schedule = [
+            (crate::Fork::Phase0, GENESIS_EPOCH),
+            (crate::Fork::Altair, context.altair_fork_epoch),
+            (crate::Fork::Bellatrix, context.bellatrix_fork_epoch),
+            (crate::Fork::Capella, context.capella_fork_epoch),
+            (crate::Fork::Deneb, context.deneb_fork_epoch),
+            (crate::Fork::Electra, context.electra_fork_epoch),
+        ];
+        let position =
+            schedule.iter().rposition(|&(_, activation)| activation <= epoch).unwrap_or_default();
+        let (current, activation) = schedule[position];
+        let previous = position.checked_sub(1).map_or(current, |previous| schedule[previous].0);
+        Self::new(
+            context.fork_version_for(previous),
+            context.fork_version_for(current),
+            activation,
+            genesis_validators_root,
+        )
+    }
+}
+
+/// Compute the domain of `domain_type` for a message at `slot` given the `fork_info` of the
+/// chain, i.e. `get_domain` without access to a `BeaconState`.
+pub fn compute_domain_at_slot(
+    domain_type: DomainType,
+    slot: Slot,
+    fork_info: &ForkInfo,
+    context: &Context,
+) -> Result<Domain> {
+    let epoch = compute_epoch_at_slot(slot, context);
+    compute_domain_at_epoch(domain_type, epoch, fork_info, context)
+}
+
+/// Compute the domain of `domain_type` for a message in `epoch` given the `fork_info` of the
+/// chain, i.e. `get_domain` without access to a `BeaconState`.
+pub fn compute_domain_at_epoch(
+    domain_type: DomainType,
+    epoch: Epoch,
+    fork_info: &ForkInfo,
+    context: &Context,
+) -> Result<Domain> {
+    let fork = &fork_info.fork;
+    let fork_version =
+        if epoch < fork.epoch { fork.previous_version } else { fork.current_version };
+    compute_domain(
+        domain_type,
+        Some(fork_version),
+        Some(fork_info.genesis_validators_root),
+        context,
+    )
+}
+
+pub fn sign_slot_selection_proof(
+    secret_key: &SecretKey,
+    slot: Slot,
+    fork_info: &ForkInfo,
+    context: &Context,
+) -> Result<BlsSignature> {
+    let domain = compute_domain_at_slot(DomainType::SelectionProof, slot, fork_info, context)?;
+    sign_with_domain(&slot, secret_key, domain)
+}
+
+pub fn verify_slot_selection_proof(
+    selection_proof: &BlsSignature,
+    public_key: &BlsPublicKey,
+    slot: Slot,
+    fork_info: &ForkInfo,
+    context: &Context,
+) -> Result<()> {
+    let domain = compute_domain_at_slot(DomainType::SelectionProof, slot, fork_info, context)?;
+    verify_signed_data(&slot, selection_proof, public_key, domain)
+}
+
+pub fn sign_attestation_data(
+    secret_key: &SecretKey,
+    data: &AttestationData,
+    fork_info: &ForkInfo,
+    context: &Context,
+) -> Result<BlsSignature> {
+    let domain =
+        compute_domain_at_epoch(DomainType::BeaconAttester, data.target.epoch, fork_info, context)?;
+    sign_with_domain(data, secret_key, domain)
+}
+
+pub fn verify_attestation_data_signature(
+    signature: &BlsSignature,
+    public_key: &BlsPublicKey,
+    data: &AttestationData,
+    fork_info: &ForkInfo,
+    context: &Context,
+) -> Result<()> {
+    let domain =
+        compute_domain_at_epoch(DomainType::BeaconAttester, data.target.epoch, fork_info, context)?;
+    verify_signed_data(data, signature, public_key, domain)
+}
+
+pub fn sign_aggregate_and_proof<const MAX_VALIDATORS_PER_COMMITTEE: usize>(
+    secret_key: &SecretKey,
+    aggregate_and_proof: &AggregateAndProof<MAX_VALIDATORS_PER_COMMITTEE>,
+    fork_info: &ForkInfo,
+    context: &Context,
+) -> Result<BlsSignature> {
+    let slot = aggregate_and_proof.aggregate.data.slot;
+    let domain = compute_domain_at_slot(DomainType::AggregateAndProof, slot, fork_info, context)?;
+    sign_with_domain(aggregate_and_proof, secret_key, domain)
+}
+
+pub fn verify_aggregate_and_proof_signature<const MAX_VALIDATORS_PER_COMMITTEE: usize>(
+    signed_aggregate_and_proof: &SignedAggregateAndProof<MAX_VALIDATORS_PER_COMMITTEE>,
+    public_key: &BlsPublicKey,
+    fork_info: &ForkInfo,
+    context: &Context,
+) -> Result<()> {
+    let aggregate_and_proof = &signed_aggregate_and_proof.message;
+    let slot = aggregate_and_proof.aggregate.data.slot;
+    let domain = compute_domain_at_slot(DomainType::AggregateAndProof, slot, fork_info, context)?;
+    verify_signed_data(
+        aggregate_and_proof,
+        &signed_aggregate_and_proof.signature,
+        public_key,
+        domain,
+    )
+}
+
+pub(crate) fn is_selected(selection_proof: &BlsSignature, modulo: usize) -> bool {
+    let modulo = modulo.max(1) as u64;
+    let digest = hash(&selection_proof[..]);
+    let bytes: [u8; 8] = digest[..8].try_into().expect("digest has at least 8 bytes");
+    u64::from_le_bytes(bytes) % modulo == 0
+}
+
+/// Determine if the validator that produced `slot_signature` (see `sign_slot_selection_proof`)
+/// should aggregate for its committee of `committee_length` validators.
+pub fn is_aggregator(
+    committee_length: usize,
+    slot_signature: &BlsSignature,
+    context: &Context,
+) -> bool {
+    is_selected(slot_signature, committee_length / context.target_aggregators_per_committee)
+}
+
+/// Choose the `Eth1Data` to vote for in a block built on `state`, following `get_eth1_vote` of
+/// the honest validator guide.
+///
+/// `candidates` are the votes the proposer considers valid for the current voting period,
+/// ordered from oldest to newest. The candidate already holding the most votes in the period is
+/// chosen (on ties, the one voted for first in the period), otherwise the newest candidate.
+/// Without any candidates, the proposer sticks with the current `state.eth1_data`.
+pub fn choose_eth1_vote<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    candidates: &[Eth1Data],
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+) -> Eth1Data {
+    // `count_eth1_data_votes` lists votes by their first occurrence in the period, so keeping the
+    // first of the most voted candidates breaks ties by the earliest vote
+    let tally = count_eth1_data_votes(&state.eth1_data_votes);
+    let mut choice: Option<(&Eth1Data, u64)> = None;
+    for (vote, count) in tally {
+        if candidates.contains(vote) && choice.map_or(true, |(_, most)| count > most) {
+            choice = Some((vote, count));
+        }
+    }
+    choice
+        .map(|(candidate, _)| candidate)
+        .or_else(|| candidates.last())
+        .unwrap_or(&state.eth1_data)
+        .clone()
+}
+
+crate::ssz_size::impl_ssz_size!(Validator {
+    public_key: BlsPublicKey,
+    withdrawal_credentials: Bytes32,
+    effective_balance: Gwei,
+    slashed: bool,
+    activation_eligibility_epoch: Epoch,
+    activation_epoch: Epoch,
+    exit_epoch: Epoch,
+    withdrawable_epoch: Epoch,
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::TryFromHex;
+
+    #[test]
+    fn test_slot_selection_proof() {
+        let context = Context::for_minimal();
+        let secret_key = SecretKey::key_gen(&[1u8; 32]).unwrap();
+        let public_key = secret_key.public_key();
+        let fork_info = ForkInfo::new(
+            context.genesis_fork_version,
+            context.altair_fork_version,
+            2,
+            Root::try_from([7u8; 32].as_ref()).unwrap(),
+        );
+        let slot = 3 * context.slots_per_epoch + 1;
+
+        let proof = sign_slot_selection_proof(&secret_key, slot, &fork_info, &context).unwrap();
+        verify_slot_selection_proof(&proof, &public_key, slot, &fork_info, &context).unwrap();
+        assert!(verify_slot_selection_proof(&proof, &public_key, slot + 1, &fork_info, &context)
+            .is_err());
+
+        // committees smaller than `2 * TARGET_AGGREGATORS_PER_COMMITTEE` always aggregate
+        let target = context.target_aggregators_per_committee;
+        for committee_length in 0..2 * target {
+            assert!(is_aggregator(committee_length, &proof, &context));
+        }
+        let modulo = 4;
+        let digest = hash(&proof[..]);
+        let expected = u64::from_le_bytes(digest[..8].try_into().unwrap()) % modulo == 0;
+        assert_eq!(is_aggregator(modulo as usize * target, &proof, &context), expected);
+
+        // the target comes from the context, e.g. for a custom preset
+        let mut context = context.clone();
+        context.target_aggregators_per_committee = 1;
+        assert_eq!(is_aggregator(modulo as usize, &proof, &context), expected);
+    }
+
+    #[test]
+    fn test_choose_eth1_vote() {
+        let eth1_data = |seed: u8| Eth1Data {
+            deposit_root: Root::try_from([seed; 32].as_ref()).unwrap(),
+            deposit_count: seed as u64,
+            block_hash: Bytes32::try_from([seed; 32].as_ref()).unwrap(),
+        };
+        let current = eth1_data(9);
+        let mut state = crate::phase0::minimal::BeaconState {
+            eth1_data: current.clone(),
+            ..Default::default()
+        };
+        let candidates = [eth1_data(1), eth1_data(2), eth1_data(3)];
+
+        // no candidates, so stick with the current data
+        assert_eq!(choose_eth1_vote(&[], &state), current);
+        // no votes for any candidate yet, so take the newest
+        assert_eq!(choose_eth1_vote(&candidates, &state), eth1_data(3));
+
+        for seed in [2, 1, 4, 4, 4, 2, 1] {
+            state.eth1_data_votes.push(eth1_data(seed));
+        }
+        // votes for non-candidates are ignored and ties go to the earliest vote in the period
+        assert_eq!(choose_eth1_vote(&candidates, &state), eth1_data(2));
+        state.eth1_data_votes.push(eth1_data(1));
+        assert_eq!(choose_eth1_vote(&candidates, &state), eth1_data(1));
+        assert_eq!(choose_eth1_vote(&candidates[2..], &state), eth1_data(3));
+    }
+
+    #[test]
+    fn test_fork_info_at_epoch() {
+        let mut context = Context::for_minimal();
+        context.altair_fork_epoch = 0;
+        context.bellatrix_fork_epoch = 0;
+        context.capella_fork_epoch = 3;
+        context.deneb_fork_epoch = 5;
+        let root = Root::try_from([7u8; 32].as_ref()).unwrap();
+        let at = |epoch| ForkInfo::at_epoch(&context, epoch, root).fork;
+
+        let bellatrix = Fork {
+            previous_version: context.altair_fork_version,
+            current_version: context.bellatrix_fork_version,
+            epoch: 0,
+        };
+        assert_eq!(at(0), bellatrix);
+        assert_eq!(at(2), bellatrix);
+        let capella = Fork {
+            previous_version: context.bellatrix_fork_version,
+            current_version: context.capella_fork_version,
+            epoch: 3,
+        };
+        assert_eq!(at(3), capella);
+        assert_eq!(at(4), capella);
+        assert_eq!(at(u64::MAX - 1).current_version, context.deneb_fork_version);
+
+        let context = Context::for_minimal();
+        let genesis = ForkInfo::at_epoch(&context, 0, root);
+        assert_eq!(genesis.fork.previous_version, context.genesis_fork_version);
+        assert_eq!(genesis.fork.current_version, context.genesis_fork_version);
+
+        let state =
+            crate::types::minimal::BeaconState::Phase0(crate::phase0::minimal::BeaconState {
+                fork: bellatrix.clone(),
+                genesis_validators_root: root,
+                ..Default::default()
+            });
+        assert_eq!(
+            ForkInfo::from_state(&state),
+            ForkInfo { fork: bellatrix, genesis_validators_root: root }
+        );
+    }
+
+    #[test]
+    fn test_attestation_signatures() {
+        let context = Context::for_minimal();
+        let secret_key = SecretKey::key_gen(&[1u8; 32]).unwrap();
+        let public_key = secret_key.public_key();
+        let root = Root::try_from([7u8; 32].as_ref()).unwrap();
+        let fork_info =
+            ForkInfo::new(context.genesis_fork_version, context.altair_fork_version, 2, root);
+
+        // attestations are signed with the fork version of their target epoch
+        let mut data = AttestationData { slot: 9, ..Default::default() };
+        data.target.epoch = 1;
+        let signature = sign_attestation_data(&secret_key, &data, &fork_info, &context).unwrap();
+        verify_attestation_data_signature(&signature, &public_key, &data, &fork_info, &context)
+            .unwrap();
+        let domain = compute_domain(
+            DomainType::BeaconAttester,
+            Some(context.genesis_fork_version),
+            Some(root),
+            &context,
+        )
+        .unwrap();
+        verify_signed_data(&data, &signature, &public_key, domain).unwrap();
+        data.target.epoch = 2;
+        assert!(verify_attestation_data_signature(
+            &signature,
+            &public_key,
+            &data,
+            &fork_info,
+            &context
+        )
+        .is_err());
+
+        let slot = 2 * context.slots_per_epoch;
+        let mut aggregate_and_proof = AggregateAndProof::<4> {
+            aggregator_index: 3,
+            selection_proof: sign_slot_selection_proof(&secret_key, slot, &fork_info, &context)
+                .unwrap(),
+            ..Default::default()
+        };
+        aggregate_and_proof.aggregate.data.slot = slot;
+        let signature =
+            sign_aggregate_and_proof(&secret_key, &aggregate_and_proof, &fork_info, &context)
+                .unwrap();
+        let mut signed = SignedAggregateAndProof { message: aggregate_and_proof, signature };
+        verify_aggregate_and_proof_signature(&signed, &public_key, &fork_info, &context).unwrap();
+        signed.message.aggregator_index = 4;
+        assert!(verify_aggregate_and_proof_signature(&signed, &public_key, &fork_info, &context)
+            .is_err());
+    }
+
+    #[test]
+    fn test_fork_info_serde() {
+        // the `fork_info` of remote signer requests on mainnet during Deneb
+        let fork_info_json = serde_json::json!({
+            "fork": {
+                "previous_version": "0x03000000",
+                "current_version": "0x04000000",
+                "epoch": "269568"
+            },
+            "genesis_validators_root": "0x4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95"
+        });
+        let fork_info: ForkInfo = serde_json::from_value(fork_info_json.clone()).unwrap();
+        let genesis_validators_root = Root::try_from_hex(
+            "0x4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95",
+        )
+        .unwrap();
+        assert_eq!(
+            fork_info,
+            ForkInfo::new([3, 0, 0, 0], [4, 0, 0, 0], 269568, genesis_validators_root)
+        );
+        let context = Context::for_mainnet();
+        assert_eq!(fork_info, ForkInfo::at_epoch(&context, 269568, genesis_validators_root));
+        assert_eq!(serde_json::to_value(&fork_info).unwrap(), fork_info_json);
+    }
+}
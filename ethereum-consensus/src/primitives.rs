@@ -1,9 +1,10 @@
-use crate::ssz::prelude::*;
+use crate::{crypto::keccak256, ssz::prelude::*};
 pub use crate::{
     crypto::{PublicKey as BlsPublicKey, Signature as BlsSignature},
     domains::DomainType,
     ssz::prelude::U256,
 };
+use std::fmt;
 
 pub type Root = Node;
 pub type Slot = u64;
@@ -13,6 +14,8 @@ pub type CommitteeIndex = usize;
 pub type ValidatorIndex = usize;
 pub type WithdrawalIndex = usize;
 pub type BlobIndex = usize;
+pub type ColumnIndex = usize;
+pub type CustodyIndex = usize;
 pub type Gwei = u64;
 pub type Hash32 = Bytes32;
 
@@ -48,11 +51,357 @@ pub const BLS_WITHDRAWAL_PREFIX: u8 = 0x00;
 pub const ETH1_ADDRESS_WITHDRAWAL_PREFIX: u8 = 0x01;
 pub const COMPOUNDING_WITHDRAWAL_PREFIX: u8 = 0x02;
 
+/// Convert `value` (e.g. a deposit index) to a `usize` for indexing, failing rather than
+/// truncating on targets where `usize` is narrower than 64 bits.
+pub fn checked_index(value: u64) -> Result<usize, crate::Error> {
+    usize::try_from(value).map_err(|_| crate::Error::IndexOverflow(value))
+}
+
+/// Convert a limit to a `usize`, saturating on targets where `usize` is narrower than 64 bits: a
+/// limit beyond the size of any collection is as good as unbounded.
+pub fn saturating_usize(value: u64) -> usize {
+    usize::try_from(value).unwrap_or(usize::MAX)
+}
+
+/// Comparison against the zero value of a type, e.g. the zero root standing for an unset root in
+/// the spec. The zero value is the SSZ default of the type.
+pub trait IsZero {
+    fn is_zero(&self) -> bool;
+}
+
+impl IsZero for Root {
+    fn is_zero(&self) -> bool {
+        self.as_ref().iter().all(|byte| *byte == 0)
+    }
+}
+
+impl<const N: usize> IsZero for ByteVector<N> {
+    fn is_zero(&self) -> bool {
+        self.iter().all(|byte| *byte == 0)
+    }
+}
+
+/// Error from parsing a fixed-size byte type from its hex encoding.
+///
+/// Lengths count hex digits, excluding any `0x` prefix. `bad_char_offset` is the byte offset of
+/// the first non-hex character in the original input (including any prefix).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexError {
+    pub expected_len: usize,
+    pub found_len: usize,
+    pub bad_char_offset: Option<usize>,
+}
+
+impl fmt::Display for HexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(offset) = self.bad_char_offset {
+            write!(f, "invalid hex character at offset {offset}")
+        } else {
+            let (expected, found) = (self.expected_len, self.found_len);
+            write!(f, "expected {expected} hex digits but found {found}")
+        }
+    }
+}
+
+impl std::error::Error for HexError {}
+
+fn hex_digit_value(c: u8) -> u8 {
+    match c {
+        b'0'..=b'9' => c - b'0',
+        b'a'..=b'f' => c - b'a' + 10,
+        b'A'..=b'F' => c - b'A' + 10,
+        _ => unreachable!("input is validated"),
+    }
+}
+
+/// Decode the (optionally `0x`-prefixed) hex `input` into exactly `expected_len` bytes.
+pub fn decode_hex_exact(input: &[u8], expected_len: usize) -> Result<Vec<u8>, HexError> {
+    let prefix_len = if input.starts_with(b"0x") || input.starts_with(b"0X") { 2 } else { 0 };
+    let digits = &input[prefix_len..];
+    let error = |bad_char_offset| HexError {
+        expected_len: 2 * expected_len,
+        found_len: digits.len(),
+        bad_char_offset,
+    };
+    if let Some(offset) = digits.iter().position(|c| !c.is_ascii_hexdigit()) {
+        return Err(error(Some(prefix_len + offset)))
+    }
+    if digits.len() != 2 * expected_len {
+        return Err(error(None))
+    }
+    let bytes =
+        digits.chunks(2).map(|pair| (hex_digit_value(pair[0]) << 4) | hex_digit_value(pair[1]));
+    Ok(bytes.collect())
+}
+
+/// Parse a fixed-size byte type from its hex encoding, with or without the `0x` prefix.
+///
+/// This is a trait rather than `TryFrom<&str>` so that `T::try_from(bytes.as_ref())` keeps
+/// inferring the byte slice conversion for the types implementing it.
+pub trait TryFromHex: Sized {
+    fn try_from_hex_bytes(input: &[u8]) -> Result<Self, HexError>;
+
+    fn try_from_hex(input: &str) -> Result<Self, HexError> {
+        Self::try_from_hex_bytes(input.as_bytes())
+    }
+}
+
+impl<const N: usize> TryFromHex for [u8; N] {
+    fn try_from_hex_bytes(input: &[u8]) -> Result<Self, HexError> {
+        let bytes = decode_hex_exact(input, N)?;
+        Ok(bytes.try_into().expect("correct length"))
+    }
+}
+
+impl<const N: usize> TryFromHex for ByteVector<N> {
+    fn try_from_hex_bytes(input: &[u8]) -> Result<Self, HexError> {
+        let bytes = decode_hex_exact(input, N)?;
+        Ok(Self::try_from(bytes.as_slice()).expect("correct length"))
+    }
+}
+
+impl TryFromHex for Root {
+    fn try_from_hex_bytes(input: &[u8]) -> Result<Self, HexError> {
+        let bytes = decode_hex_exact(input, 32)?;
+        Ok(Self::try_from(bytes.as_slice()).expect("correct length"))
+    }
+}
+
+/// Implement `TryFromHex` for a byte newtype of the given length that
+/// implements `TryFrom<&[u8]>`.
+macro_rules! impl_try_from_hex {
+    ($($t:ty => $len:expr),* $(,)?) => {
+        $(
+            impl $crate::primitives::TryFromHex for $t {
+                fn try_from_hex_bytes(
+                    input: &[u8],
+                ) -> Result<Self, $crate::primitives::HexError> {
+                    let bytes = $crate::primitives::decode_hex_exact(input, $len)?;
+                    Ok(Self::try_from(bytes.as_slice()).expect("correct length"))
+                }
+            }
+        )*
+    };
+}
+
+pub(crate) use impl_try_from_hex;
+
+/// Conversion between a fixed-size primitive and the raw bytes of its (SSZ) encoding, e.g. to
+/// pass it across an FFI boundary.
+///
+/// `as_bytes` borrows the bytes in place, without copying.
+pub trait FixedBytes<const N: usize>: Sized {
+    fn as_bytes(&self) -> &[u8; N];
+
+    fn from_bytes(bytes: &[u8; N]) -> Self;
+}
+
+impl<const N: usize> FixedBytes<N> for [u8; N] {
+    fn as_bytes(&self) -> &[u8; N] {
+        self
+    }
+
+    fn from_bytes(bytes: &[u8; N]) -> Self {
+        *bytes
+    }
+}
+
+impl<const N: usize> FixedBytes<N> for ByteVector<N> {
+    fn as_bytes(&self) -> &[u8; N] {
+        self.as_ref().try_into().expect("correct length")
+    }
+
+    fn from_bytes(bytes: &[u8; N]) -> Self {
+        Self::try_from(bytes.as_slice()).expect("correct length")
+    }
+}
+
+impl FixedBytes<32> for Root {
+    fn as_bytes(&self) -> &[u8; 32] {
+        self.as_ref().try_into().expect("correct length")
+    }
+
+    fn from_bytes(bytes: &[u8; 32]) -> Self {
+        Self::try_from(bytes.as_slice()).expect("correct length")
+    }
+}
+
+impl FixedBytes<48> for BlsPublicKey {
+    fn as_bytes(&self) -> &[u8; 48] {
+        FixedBytes::as_bytes(&**self)
+    }
+
+    fn from_bytes(bytes: &[u8; 48]) -> Self {
+        Self::try_from(bytes.as_slice()).expect("correct length")
+    }
+}
+
+impl FixedBytes<96> for BlsSignature {
+    fn as_bytes(&self) -> &[u8; 96] {
+        FixedBytes::as_bytes(&**self)
+    }
+
+    fn from_bytes(bytes: &[u8; 96]) -> Self {
+        Self::try_from(bytes.as_slice()).expect("correct length")
+    }
+}
+
+/// Render an execution address with the mixed-case checksum of EIP-55, for display.
+///
+/// Serialization (e.g. in the Beacon API) uses lowercase hex, while deserialization accepts
+/// either.
+pub trait ToChecksummed {
+    fn to_checksummed(&self) -> String;
+}
+
+impl ToChecksummed for ExecutionAddress {
+    fn to_checksummed(&self) -> String {
+        let lower = self.iter().map(|byte| format!("{byte:02x}")).collect::<String>();
+        let digest = keccak256(lower.as_bytes());
+        let checksummed = lower.char_indices().map(|(i, c)| {
+            let nibble = (digest[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0xf;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        });
+        format!("0x{}", checksummed.collect::<String>())
+    }
+}
+
 #[cfg(test)]
-#[cfg(feature = "serde")]
 mod tests {
     use super::*;
+    use crate::{
+        deneb::polynomial_commitments::KzgCommitment,
+        phase0::{Checkpoint, Eth1Data},
+    };
+
+    #[test]
+    fn test_fixed_bytes_round_trip() {
+        fn round_trip<T: FixedBytes<N> + PartialEq + fmt::Debug, const N: usize>(bytes: [u8; N]) {
+            let value = T::from_bytes(&bytes);
+            assert_eq!(value.as_bytes(), &bytes);
+            assert_eq!(T::from_bytes(value.as_bytes()), value);
+        }
+
+        round_trip::<Version, 4>([1, 2, 3, 4]);
+        round_trip::<Domain, 32>([5; 32]);
+        round_trip::<Root, 32>([6; 32]);
+        round_trip::<Bytes32, 32>([7; 32]);
+        round_trip::<ExecutionAddress, 20>([8; 20]);
+        round_trip::<KzgCommitment, 48>([9; 48]);
+        round_trip::<BlsPublicKey, 48>([10; 48]);
+        round_trip::<BlsSignature, 96>([11; 96]);
+
+        // the bytes are those of the SSZ encoding
+        let root = Root::from_bytes(&[12; 32]);
+        assert_eq!(serialize(&root).unwrap(), root.as_bytes().to_vec());
+    }
+
+    #[test]
+    fn test_index_conversions() {
+        assert_eq!(checked_index(7).unwrap(), 7);
+        assert_eq!(saturating_usize(7), 7);
+
+        let large = u32::MAX as u64 + 1;
+        #[cfg(target_pointer_width = "64")]
+        {
+            assert_eq!(checked_index(large).unwrap() as u64, large);
+            assert_eq!(saturating_usize(large) as u64, large);
+        }
+        #[cfg(target_pointer_width = "32")]
+        {
+            assert!(
+                matches!(checked_index(large), Err(crate::Error::IndexOverflow(v)) if v == large)
+            );
+            assert_eq!(saturating_usize(large), usize::MAX);
+        }
+    }
+
+    #[test]
+    fn test_try_from_hex() {
+        let root = Root::try_from_hex(&format!("0x{}", "ab".repeat(32))).unwrap();
+        assert_eq!(root, Root::try_from(&[0xab; 32][..]).unwrap());
+        let bare = Root::try_from_hex(&"ab".repeat(32)).unwrap();
+        assert_eq!(root, bare);
+
+        let address = ExecutionAddress::try_from_hex(&"0F".repeat(20)).unwrap();
+        assert_eq!(&address[..], &[0x0f; 20][..]);
+        let version = Version::try_from_hex("0x01020304").unwrap();
+        assert_eq!(version, [1, 2, 3, 4]);
+
+        let signature = BlsSignature::try_from_hex(&format!("0x{}", "c0".repeat(96))).unwrap();
+        assert_eq!(&signature[..], &[0xc0; 96][..]);
+        let public_key = BlsPublicKey::try_from_hex(&"11".repeat(48)).unwrap();
+        assert_eq!(&public_key[..], &[0x11; 48][..]);
+
+        // wrong length
+        let err = ExecutionAddress::try_from_hex("0x0102").unwrap_err();
+        assert_eq!(err, HexError { expected_len: 40, found_len: 4, bad_char_offset: None });
+        let err = KzgCommitment::try_from_hex(&"00".repeat(49)).unwrap_err();
+        assert_eq!(err, HexError { expected_len: 96, found_len: 98, bad_char_offset: None });
+        // odd length
+        let err = Version::try_from_hex("0x0102030").unwrap_err();
+        assert_eq!(err, HexError { expected_len: 8, found_len: 7, bad_char_offset: None });
+        // bad characters, reported by offset into the original input
+        let err = Version::try_from_hex("0x01g20304").unwrap_err();
+        assert_eq!(err, HexError { expected_len: 8, found_len: 8, bad_char_offset: Some(4) });
+        let err = Version::try_from_hex("01g20304").unwrap_err();
+        assert_eq!(err, HexError { expected_len: 8, found_len: 8, bad_char_offset: Some(2) });
+        let err = Root::try_from_hex("0x0x").unwrap_err();
+        assert_eq!(err.bad_char_offset, Some(3));
+        let err = BlsPublicKey::try_from_hex(" 11").unwrap_err();
+        assert_eq!(err.bad_char_offset, Some(0));
+    }
+
+    #[test]
+    fn test_to_checksummed() {
+        // from EIP-55, along with the mainnet deposit contract
+        let cases = [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+            "0x00000000219ab540356cBB839Cbe05303d7705Fa",
+        ];
+        for expected in cases {
+            let address = ExecutionAddress::try_from_hex(&expected.to_lowercase()).unwrap();
+            assert_eq!(address.to_checksummed(), expected);
+            assert_eq!(ExecutionAddress::try_from_hex(expected).unwrap(), address);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_execution_address_serde() {
+        let checksummed = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+        let lower = checksummed.to_lowercase();
+        for input in [checksummed, &lower] {
+            let address: ExecutionAddress = serde_json::from_value(input.into()).unwrap();
+            assert_eq!(serde_json::to_value(&address).unwrap(), lower.as_str());
+        }
+    }
+
+    #[test]
+    fn test_is_zero() {
+        assert!(Root::default().is_zero());
+        assert!(!Root::try_from(&[1u8; 32][..]).unwrap().is_zero());
+        assert!(Bytes32::default().is_zero());
+
+        let mut checkpoint = Checkpoint::default();
+        assert!(checkpoint.is_zero());
+        checkpoint.epoch = 1;
+        assert!(!checkpoint.is_zero());
+
+        let mut eth1_data = Eth1Data::default();
+        assert!(eth1_data.is_zero());
+        eth1_data.block_hash[31] = 1;
+        assert!(!eth1_data.is_zero());
+    }
 
+    #[cfg(feature = "serde")]
     #[test]
     fn test_serde() {
         let bytes = Bytes32::default();
@@ -0,0 +1,209 @@
+//! Proofs that a validator holds the secret key of its public key, e.g. as asked for by staking
+//! marketplaces: a signature over an arbitrary challenge in a domain reserved for applications,
+//! so that it can never verify as a signature over a consensus object.
+use crate::{
+    crypto::{hash, SecretKey},
+    phase0::compute_domain,
+    primitives::{BlsPublicKey, BlsSignature, Bytes32, Domain, DomainType},
+    signing::{sign_with_domain, verify_signed_data},
+    ssz::prelude::*,
+    state_transition::Context,
+    Error,
+};
+use thiserror::Error;
+
+/// Tags ownership proofs among the applications sharing `DOMAIN_APPLICATION_MASK`.
+const APPLICATION_TAG: [u8; 3] = *b"own";
+/// Tags expiring ownership proofs, so that they never verify as a plain proof nor the reverse.
+const EXPIRING_APPLICATION_TAG: [u8; 3] = *b"oex";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum OwnershipProofError {
+    #[error("proof expired at {expiry} which is not after {now}")]
+    Expired { expiry: u64, now: u64 },
+}
+
+/// The container signed for a challenge, so that the signing root commits to the hash of the
+/// challenge rather than to the challenge itself.
+#[derive(Debug, Clone, Default, SimpleSerialize, PartialEq, Eq)]
+pub struct OwnershipChallenge {
+    pub challenge_hash: Bytes32,
+}
+
+impl OwnershipChallenge {
+    pub fn new(challenge: &[u8]) -> Self {
+        Self { challenge_hash: hash(challenge) }
+    }
+}
+
+/// The container signed for an [`OwnershipProof`], committing to its expiry so the expiry can not
+/// be extended without invalidating the signature.
+#[derive(Debug, Clone, Default, SimpleSerialize, PartialEq, Eq)]
+pub struct ExpiringOwnershipChallenge {
+    pub challenge_hash: Bytes32,
+    pub expiry: u64,
+}
+
+impl ExpiringOwnershipChallenge {
+    pub fn new(challenge: &[u8], expiry: u64) -> Self {
+        Self { challenge_hash: hash(challenge), expiry }
+    }
+}
+
+fn compute_application_domain(tag: [u8; 3], context: &Context) -> Result<Domain, Error> {
+    let mut domain = compute_domain(DomainType::ApplicationMask, None, None, context)?;
+    for (byte, tag) in domain[..tag.len()].iter_mut().zip(tag) {
+        *byte |= tag;
+    }
+    Ok(domain)
+}
+
+/// The domain of ownership proofs: the application mask with the bytes of [`APPLICATION_TAG`]
+/// set, which no consensus domain nor the builder domain can share.
+pub fn compute_ownership_domain(context: &Context) -> Result<Domain, Error> {
+    compute_application_domain(APPLICATION_TAG, context)
+}
+
+/// The domain of expiring ownership proofs, as [`compute_ownership_domain`] but with the bytes of
+/// [`EXPIRING_APPLICATION_TAG`] set.
+pub fn compute_expiring_ownership_domain(context: &Context) -> Result<Domain, Error> {
+    compute_application_domain(EXPIRING_APPLICATION_TAG, context)
+}
+
+pub fn sign(
+    secret_key: &SecretKey,
+    challenge: &[u8],
+    context: &Context,
+) -> Result<BlsSignature, Error> {
+    let domain = compute_ownership_domain(context)?;
+    sign_with_domain(&OwnershipChallenge::new(challenge), secret_key, domain)
+}
+
+pub fn verify(
+    public_key: &BlsPublicKey,
+    challenge: &[u8],
+    signature: &BlsSignature,
+    context: &Context,
+) -> Result<(), Error> {
+    let domain = compute_ownership_domain(context)?;
+    verify_signed_data(&OwnershipChallenge::new(challenge), signature, public_key, domain)
+}
+
+/// A self-contained proof of ownership valid until the unix timestamp `expiry`.
+///
+/// Its SSZ encoding is the compact wire format: a fixed 152 bytes, as the challenge is known to
+/// the verifier and not included.
+#[derive(Debug, Clone, Default, SimpleSerialize, PartialEq, Eq)]
+pub struct OwnershipProof {
+    pub public_key: BlsPublicKey,
+    pub expiry: u64,
+    pub signature: BlsSignature,
+}
+
+impl OwnershipProof {
+    pub fn new(
+        secret_key: &SecretKey,
+        challenge: &[u8],
+        expiry: u64,
+        context: &Context,
+    ) -> Result<Self, Error> {
+        let domain = compute_expiring_ownership_domain(context)?;
+        let message = ExpiringOwnershipChallenge::new(challenge, expiry);
+        let signature = sign_with_domain(&message, secret_key, domain)?;
+        Ok(Self { public_key: secret_key.public_key(), expiry, signature })
+    }
+
+    /// Verify the proof for `challenge` at the unix timestamp `now`.
+    pub fn verify(&self, challenge: &[u8], now: u64, context: &Context) -> Result<(), Error> {
+        if self.expiry <= now {
+            return Err(OwnershipProofError::Expired { expiry: self.expiry, now }.into())
+        }
+        let domain = compute_expiring_ownership_domain(context)?;
+        let message = ExpiringOwnershipChallenge::new(challenge, self.expiry);
+        verify_signed_data(&message, &self.signature, &self.public_key, domain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::compute_builder_domain;
+
+    const CHALLENGE: &[u8] = b"prove you own validator 42 for listing 7";
+
+    #[test]
+    fn test_proof_is_not_a_consensus_signature() {
+        let context = Context::for_mainnet();
+        let secret_key = SecretKey::key_gen(&[1u8; 32]).unwrap();
+        let public_key = secret_key.public_key();
+        let signature = sign(&secret_key, CHALLENGE, &context).unwrap();
+        assert!(verify(&public_key, CHALLENGE, &signature, &context).is_ok());
+        assert!(verify(&public_key, b"another challenge", &signature, &context).is_err());
+        let other_key = SecretKey::key_gen(&[2u8; 32]).unwrap().public_key();
+        assert!(verify(&other_key, CHALLENGE, &signature, &context).is_err());
+
+        let domain = compute_ownership_domain(&context).unwrap();
+        assert_eq!(domain[3], DomainType::ApplicationMask.as_bytes()[3]);
+        assert_ne!(domain, compute_builder_domain(&context).unwrap());
+        let message = OwnershipChallenge::new(CHALLENGE);
+        let consensus_domains = [
+            DomainType::BeaconProposer,
+            DomainType::BeaconAttester,
+            DomainType::Randao,
+            DomainType::Deposit,
+            DomainType::VoluntaryExit,
+            DomainType::SelectionProof,
+            DomainType::AggregateAndProof,
+            DomainType::SyncCommittee,
+            DomainType::SyncCommitteeSelectionProof,
+            DomainType::ContributionAndProof,
+            DomainType::BlsToExecutionChange,
+            DomainType::Consolidation,
+        ];
+        for domain_type in consensus_domains {
+            for fork_version in [context.genesis_fork_version, context.deneb_fork_version] {
+                let domain =
+                    compute_domain(domain_type, Some(fork_version), None, &context).unwrap();
+                assert!(verify_signed_data(&message, &signature, &public_key, domain).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn test_expired_proof_is_rejected() {
+        let context = Context::for_mainnet();
+        let secret_key = SecretKey::key_gen(&[1u8; 32]).unwrap();
+        let expiry = 1_700_000_000;
+        let proof = OwnershipProof::new(&secret_key, CHALLENGE, expiry, &context).unwrap();
+
+        let encoding = serialize(&proof).unwrap();
+        assert_eq!(encoding.len(), 152);
+        let proof = OwnershipProof::deserialize(&encoding).unwrap();
+        assert!(proof.verify(CHALLENGE, expiry - 1, &context).is_ok());
+        assert!(proof.verify(b"another challenge", expiry - 1, &context).is_err());
+
+        for now in [expiry, expiry + 1] {
+            let err = proof.verify(CHALLENGE, now, &context).unwrap_err();
+            assert!(matches!(
+                err,
+                Error::OwnershipProof(OwnershipProofError::Expired { expiry: e, now: n })
+                    if e == expiry && n == now
+            ));
+        }
+
+        // the expiry is signed over, so it can not be extended
+        let mut extended = proof.clone();
+        extended.expiry += 3600;
+        assert!(matches!(extended.verify(CHALLENGE, expiry, &context), Err(Error::Crypto(_))));
+
+        // expiring and plain proofs are signed in distinct domains over distinct containers
+        assert_ne!(
+            compute_expiring_ownership_domain(&context).unwrap(),
+            compute_ownership_domain(&context).unwrap()
+        );
+        assert!(verify(&proof.public_key, CHALLENGE, &proof.signature, &context).is_err());
+        let signature = sign(&secret_key, CHALLENGE, &context).unwrap();
+        let plain = OwnershipProof { signature, ..proof };
+        assert!(plain.verify(CHALLENGE, expiry - 1, &context).is_err());
+    }
+}
@@ -0,0 +1,610 @@
+//! Merkle proofs that a validator's attestation was included in a block, e.g. for a staking
+//! provider to show that a vote made it on chain, and that a withdrawal was made in an execution
+//! payload, e.g. for a staking pool contract to account for it.
+use crate::{
+    capella::Withdrawal,
+    electra::{self, get_beacon_committee, get_committee_indices},
+    phase0::{self, compute_epoch_at_slot},
+    primitives::{CommitteeIndex, ExecutionAddress, Gwei, Root, Slot, ValidatorIndex},
+    ssz::prelude::*,
+    state_transition::{
+        compute_shuffling, shuffling_decision_root, Context, Result, ShufflingCache,
+    },
+    types::{BeaconState, ExecutionPayload, SignedBeaconBlock},
+};
+use ssz_rs::proofs::get_subtree_index;
+use std::collections::{hash_map::Entry, HashMap};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ProofError {
+    #[error("no attestation in the block carries a vote from validator {0}")]
+    NoAttestation(ValidatorIndex),
+    #[error("bit {0} is not set in the aggregation bits of the attestation")]
+    UnsetAggregationBit(usize),
+    #[error("execution payload predates withdrawals")]
+    NoWithdrawals,
+    #[error("withdrawal {index} is out of range of the {count} withdrawals in the payload")]
+    WithdrawalOutOfRange { index: usize, count: usize },
+}
+
+/// Access to the aggregation bits of an attestation, regardless of fork.
+pub trait AggregationBits {
+    fn is_aggregation_bit_set(&self, index: usize) -> bool;
+}
+
+impl<const MAX_VALIDATORS_PER_COMMITTEE: usize> AggregationBits
+    for phase0::Attestation<MAX_VALIDATORS_PER_COMMITTEE>
+{
+    fn is_aggregation_bit_set(&self, index: usize) -> bool {
+        index < self.aggregation_bits.len() && self.aggregation_bits[index]
+    }
+}
+
+impl<const MAX_VALIDATORS_PER_SLOT: usize, const MAX_COMMITTEES_PER_SLOT: usize> AggregationBits
+    for electra::Attestation<MAX_VALIDATORS_PER_SLOT, MAX_COMMITTEES_PER_SLOT>
+{
+    fn is_aggregation_bit_set(&self, index: usize) -> bool {
+        index < self.aggregation_bits.len() && self.aggregation_bits[index]
+    }
+}
+
+/// A proof that `attestation`, carrying a vote from `validator_index`, is in a block.
+///
+/// The proof binds the attestation (and so its aggregation bits) to the block root; that
+/// `aggregation_bit_index` is the bit of `validator_index` follows from the committees of the
+/// state the proof was made with.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AttestationInclusionProof<A> {
+    #[serde(with = "crate::serde::as_str")]
+    pub validator_index: ValidatorIndex,
+    pub attestation: A,
+    /// The position of the attestation in the `attestations` of the block body.
+    #[serde(with = "crate::serde::as_str")]
+    pub attestation_index: usize,
+    /// The position of the bit of the validator in the `aggregation_bits` of the attestation.
+    #[serde(with = "crate::serde::as_str")]
+    pub aggregation_bit_index: usize,
+    /// The branch from the root of the attestation to the block root.
+    pub branch: Vec<Node>,
+}
+
+/// Prove the inclusion of a vote from `validator_index` in `block`, using the first attestation
+/// in the block that carries it.
+///
+/// `state_at_parent` is used to compute committees so must be within one epoch of every
+/// attestation in the block, as the post-state of the parent of `block` is.
+pub fn prove_attestation_inclusion<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    block: &SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >,
+    state_at_parent: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    validator_index: ValidatorIndex,
+    context: &Context,
+) -> Result<AttestationInclusionProof<phase0::Attestation<MAX_VALIDATORS_PER_COMMITTEE>>> {
+    let mut shufflings = ShufflingCache::default();
+    // an electra block carries no attestations of this type, see
+    // `prove_electra_attestation_inclusion`
+    let attestations = block.message().body().attestations().into_iter().flatten();
+    for (attestation_index, attestation) in attestations.enumerate() {
+        let data = &attestation.data;
+        let epoch = compute_epoch_at_slot(data.slot, context);
+        let decision_root = shuffling_decision_root(state_at_parent, epoch, context);
+        if shufflings.get(epoch, decision_root).is_none() {
+            shufflings.insert(decision_root, compute_shuffling(state_at_parent, epoch, context));
+        }
+        let shuffling = shufflings.get(epoch, decision_root).expect("shuffling is cached");
+        let committee = shuffling.beacon_committee(data.slot, data.index, context)?;
+        let Some(position) = committee.iter().position(|&index| index == validator_index) else {
+            continue
+        };
+        if !attestation.is_aggregation_bit_set(position) {
+            continue
+        }
+        let path = &["body".into(), "attestations".into(), attestation_index.into()];
+        let (proof, _) = crate::map_fork!(
+            SignedBeaconBlock,
+            block,
+            inner => inner.message.prove(path)
+        )?;
+        return Ok(AttestationInclusionProof {
+            validator_index,
+            attestation: attestation.clone(),
+            attestation_index,
+            aggregation_bit_index: position,
+            branch: proof.branch,
+        })
+    }
+    Err(ProofError::NoAttestation(validator_index).into())
+}
+
+/// Like [`prove_attestation_inclusion`] for an Electra block, where an attestation may span
+/// several committees of a slot: the bit of a validator is offset by the sizes of the committees
+/// before its own in `committee_bits`.
+pub fn prove_electra_attestation_inclusion<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    block: &electra::SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >,
+    state_at_parent: &electra::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    validator_index: ValidatorIndex,
+    context: &Context,
+) -> Result<
+    AttestationInclusionProof<
+        electra::Attestation<MAX_VALIDATORS_PER_SLOT, MAX_COMMITTEES_PER_SLOT>,
+    >,
+> {
+    let mut committees = HashMap::<(Slot, CommitteeIndex), Vec<ValidatorIndex>>::new();
+    for (attestation_index, attestation) in block.message.body.attestations.iter().enumerate() {
+        let slot = attestation.data.slot;
+        let mut offset = 0;
+        for index in get_committee_indices(&attestation.committee_bits) {
+            let committee = match committees.entry((slot, index)) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    entry.insert(get_beacon_committee(state_at_parent, slot, index, context)?)
+                }
+            };
+            let position = committee.iter().position(|&index| index == validator_index);
+            let committee_size = committee.len();
+            if let Some(position) = position {
+                let aggregation_bit_index = offset + position;
+                if !attestation.is_aggregation_bit_set(aggregation_bit_index) {
+                    break
+                }
+                let path = &["body".into(), "attestations".into(), attestation_index.into()];
+                let (proof, _) = block.message.prove(path)?;
+                return Ok(AttestationInclusionProof {
+                    validator_index,
+                    attestation: attestation.clone(),
+                    attestation_index,
+                    aggregation_bit_index,
+                    branch: proof.branch,
+                })
+            }
+            offset += committee_size;
+        }
+    }
+    Err(ProofError::NoAttestation(validator_index).into())
+}
+
+/// Verify `proof` against the root of a block. The expected depth of the proof is derived from
+/// the `Block` type, which must match the fork (and preset) the proof was made for.
+pub fn verify_attestation_inclusion<
+    Block: SimpleSerialize,
+    A: SimpleSerialize + AggregationBits,
+>(
+    proof: &AttestationInclusionProof<A>,
+    block_root: Root,
+) -> Result<()> {
+    if !proof.attestation.is_aggregation_bit_set(proof.aggregation_bit_index) {
+        return Err(ProofError::UnsetAggregationBit(proof.aggregation_bit_index).into())
+    }
+    let path = &["body".into(), "attestations".into(), proof.attestation_index.into()];
+    let g_index = Block::generalized_index(path)?;
+    let depth = g_index.ilog2() as usize;
+    if proof.branch.len() != depth {
+        return Err(crate::Error::OutOfBounds { requested: proof.branch.len(), bound: depth })
+    }
+    let subtree_index = get_subtree_index(g_index)?;
+
+    let leaf = proof.attestation.hash_tree_root()?;
+    is_valid_merkle_branch(leaf, &proof.branch, depth, subtree_index, block_root)
+        .map_err(Into::into)
+}
+
+/// A proof that `withdrawal` is in the `withdrawals` of an execution payload.
+///
+/// `branch` leads to the `withdrawals_root` of the payload, which is also in its header, and
+/// `payload_branch` carries on from there to the root of the payload (and so of its header).
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WithdrawalProof {
+    pub withdrawal: Withdrawal,
+    /// The position of the withdrawal in the `withdrawals` of the payload.
+    #[serde(with = "crate::serde::as_str")]
+    pub withdrawal_index: usize,
+    /// The branch from the root of the withdrawal to the `withdrawals_root`.
+    pub branch: Vec<Node>,
+    /// The branch from the `withdrawals_root` to the root of the payload.
+    pub payload_branch: Vec<Node>,
+}
+
+impl WithdrawalProof {
+    pub fn validator_index(&self) -> ValidatorIndex {
+        self.withdrawal.validator_index
+    }
+
+    pub fn amount(&self) -> Gwei {
+        self.withdrawal.amount
+    }
+
+    pub fn address(&self) -> &ExecutionAddress {
+        &self.withdrawal.address
+    }
+}
+
+/// Prove the inclusion of the withdrawal at `withdrawal_index` in the `withdrawals` of `payload`.
+pub fn prove_withdrawal<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+>(
+    payload: &ExecutionPayload<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+    >,
+    withdrawal_index: usize,
+) -> Result<WithdrawalProof> {
+    let withdrawals = payload.withdrawals().ok_or(ProofError::NoWithdrawals)?;
+    let withdrawal = withdrawals.get(withdrawal_index).ok_or(ProofError::WithdrawalOutOfRange {
+        index: withdrawal_index,
+        count: withdrawals.len(),
+    })?;
+    let path = &["withdrawals".into(), withdrawal_index.into()];
+    let (proof, _) = crate::map_fork!(ExecutionPayload, payload, inner => inner.prove(path))?;
+
+    // the proof runs from the leaf up, so the nodes below the `withdrawals_root` come first
+    let g_index = List::<Withdrawal, MAX_WITHDRAWALS_PER_PAYLOAD>::generalized_index(&[
+        withdrawal_index.into(),
+    ])?;
+    let mut branch = proof.branch;
+    let payload_branch = branch.split_off(g_index.ilog2() as usize);
+    Ok(WithdrawalProof { withdrawal: withdrawal.clone(), withdrawal_index, branch, payload_branch })
+}
+
+/// Verify `proof` against the `withdrawals_root` of an execution payload (or its header) with
+/// room for `MAX_WITHDRAWALS_PER_PAYLOAD` withdrawals.
+pub fn verify_withdrawal<const MAX_WITHDRAWALS_PER_PAYLOAD: usize>(
+    proof: &WithdrawalProof,
+    withdrawals_root: Root,
+) -> Result<()> {
+    let path = &[proof.withdrawal_index.into()];
+    let g_index = List::<Withdrawal, MAX_WITHDRAWALS_PER_PAYLOAD>::generalized_index(path)?;
+    verify_withdrawal_branch(&proof.withdrawal, &proof.branch, g_index, withdrawals_root)
+}
+
+/// Verify `proof` against the root of an execution payload, or equivalently of its header. The
+/// expected depth of the proof is derived from the `Payload` type, which must match the fork (and
+/// preset) the proof was made for.
+pub fn verify_withdrawal_in_payload<Payload: SimpleSerialize>(
+    proof: &WithdrawalProof,
+    payload_root: Root,
+) -> Result<()> {
+    let path = &["withdrawals".into(), proof.withdrawal_index.into()];
+    let g_index = Payload::generalized_index(path)?;
+    let branch = proof.branch.iter().chain(&proof.payload_branch).cloned().collect::<Vec<_>>();
+    verify_withdrawal_branch(&proof.withdrawal, &branch, g_index, payload_root)
+}
+
+fn verify_withdrawal_branch(
+    withdrawal: &Withdrawal,
+    branch: &[Node],
+    g_index: GeneralizedIndex,
+    root: Root,
+) -> Result<()> {
+    let depth = g_index.ilog2() as usize;
+    if branch.len() != depth {
+        return Err(crate::Error::OutOfBounds { requested: branch.len(), bound: depth })
+    }
+    let subtree_index = get_subtree_index(g_index)?;
+
+    let leaf = withdrawal.hash_tree_root()?;
+    is_valid_merkle_branch(leaf, branch, depth, subtree_index, root).map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::{
+            phase0::{genesis_state, signed_block, BLOCK_SLOT},
+            VALIDATOR_COUNT,
+        },
+        phase0::{AttestationData, Validator},
+        primitives::FAR_FUTURE_EPOCH,
+        types::minimal,
+    };
+
+    #[test]
+    fn test_attestation_inclusion_proofs() {
+        let context = Context::for_minimal();
+        let state = genesis_state();
+        let original = signed_block();
+        // the fixture block attests with all of committee 0 at slot 1; add attestations for the
+        // other committees of the epoch so far, each missing the vote of the first member
+        let mut block = original.clone();
+        let mut absent = vec![];
+        for (slot, index) in [(0, 0), (0, 1), (BLOCK_SLOT - 1, 1)] {
+            let committee = phase0::get_beacon_committee(&state, slot, index, &context).unwrap();
+            let mut bits = vec![true; committee.len()];
+            bits[0] = false;
+            absent.push(committee[0]);
+            block.message.body.attestations.push(phase0::minimal::Attestation {
+                aggregation_bits: bits.as_slice().try_into().unwrap(),
+                data: AttestationData { slot, index, ..Default::default() },
+                ..Default::default()
+            });
+        }
+        let block_root = block.message.hash_tree_root().unwrap();
+        let other_root = original.message.hash_tree_root().unwrap();
+        let block = minimal::SignedBeaconBlock::Phase0(block);
+        let state = minimal::BeaconState::Phase0(state);
+
+        let mut proven = 0;
+        for validator_index in 0..VALIDATOR_COUNT {
+            let proof = match prove_attestation_inclusion(&block, &state, validator_index, &context)
+            {
+                Ok(proof) => proof,
+                Err(crate::Error::Proof(ProofError::NoAttestation(index))) => {
+                    assert_eq!(index, validator_index);
+                    continue
+                }
+                Err(err) => panic!("{err}"),
+            };
+            assert!(!absent.contains(&validator_index));
+            verify_attestation_inclusion::<phase0::minimal::BeaconBlock, _>(&proof, block_root)
+                .unwrap();
+            // a proof replayed against a different block does not hold
+            assert!(verify_attestation_inclusion::<phase0::minimal::BeaconBlock, _>(
+                &proof, other_root
+            )
+            .is_err());
+
+            let mut tampered = proof.clone();
+            tampered.attestation.data.slot += 1;
+            assert!(verify_attestation_inclusion::<phase0::minimal::BeaconBlock, _>(
+                &tampered, block_root
+            )
+            .is_err());
+            proven += 1;
+        }
+        // four committees of four, less the three missing votes
+        assert_eq!(proven, 13);
+    }
+
+    #[test]
+    fn test_electra_attestation_inclusion_proofs() {
+        let context = Context::for_minimal();
+        let mut state = electra::minimal::BeaconState::default();
+        for _ in 0..VALIDATOR_COUNT {
+            state.validators.push(Validator {
+                exit_epoch: FAR_FUTURE_EPOCH,
+                withdrawable_epoch: FAR_FUTURE_EPOCH,
+                ..Default::default()
+            });
+        }
+        let slot = 1;
+        let first = get_beacon_committee(&state, slot, 0, &context).unwrap();
+        let second = get_beacon_committee(&state, slot, 1, &context).unwrap();
+
+        // a single attestation for both committees of the slot, missing the vote of the first
+        // member of the second committee
+        let mut bits = vec![true; first.len() + second.len()];
+        bits[first.len()] = false;
+        let mut block = electra::minimal::SignedBeaconBlock::default();
+        block.message.slot = slot + 1;
+        block.message.body.attestations.push(electra::minimal::Attestation {
+            aggregation_bits: bits.as_slice().try_into().unwrap(),
+            data: AttestationData { slot, ..Default::default() },
+            committee_bits: deserialize(&[0b11u8]).unwrap(),
+            ..Default::default()
+        });
+        let block_root = block.message.hash_tree_root().unwrap();
+        let other_root = electra::minimal::BeaconBlock::default().hash_tree_root().unwrap();
+
+        for (position, &validator_index) in second.iter().enumerate().skip(1) {
+            let proof =
+                prove_electra_attestation_inclusion(&block, &state, validator_index, &context)
+                    .unwrap();
+            assert_eq!(proof.aggregation_bit_index, first.len() + position);
+            verify_attestation_inclusion::<electra::minimal::BeaconBlock, _>(&proof, block_root)
+                .unwrap();
+            assert!(verify_attestation_inclusion::<electra::minimal::BeaconBlock, _>(
+                &proof, other_root
+            )
+            .is_err());
+        }
+        for (position, &validator_index) in first.iter().enumerate() {
+            let proof =
+                prove_electra_attestation_inclusion(&block, &state, validator_index, &context)
+                    .unwrap();
+            assert_eq!(proof.aggregation_bit_index, position);
+        }
+
+        assert!(matches!(
+            prove_electra_attestation_inclusion(&block, &state, second[0], &context),
+            Err(crate::Error::Proof(ProofError::NoAttestation(_)))
+        ));
+        // claiming the unset bit of the missing vote is rejected
+        let mut proof =
+            prove_electra_attestation_inclusion(&block, &state, second[1], &context).unwrap();
+        proof.aggregation_bit_index = first.len();
+        assert!(matches!(
+            verify_attestation_inclusion::<electra::minimal::BeaconBlock, _>(&proof, block_root),
+            Err(crate::Error::Proof(ProofError::UnsetAggregationBit(_)))
+        ));
+    }
+
+    #[test]
+    fn test_withdrawal_proofs() {
+        let mut inner = crate::capella::mainnet::ExecutionPayload::default();
+        for i in 0..crate::capella::mainnet::MAX_WITHDRAWALS_PER_PAYLOAD {
+            inner.withdrawals.push(Withdrawal {
+                index: 100 + i as u64,
+                validator_index: 7 * i,
+                address: ExecutionAddress::try_from(&[i as u8; 20][..]).unwrap(),
+                amount: 32_000_000_000 + i as Gwei,
+            });
+        }
+        let withdrawals_root = inner.withdrawals.hash_tree_root().unwrap();
+        let payload_root = inner.hash_tree_root().unwrap();
+        let payload = crate::types::mainnet::ExecutionPayload::Capella(inner.clone());
+
+        for (i, withdrawal) in inner.withdrawals.iter().enumerate() {
+            let proof = prove_withdrawal(&payload, i).unwrap();
+            assert_eq!(proof.validator_index(), withdrawal.validator_index);
+            assert_eq!(proof.amount(), withdrawal.amount);
+            assert_eq!(proof.address(), &withdrawal.address);
+            verify_withdrawal::<16>(&proof, withdrawals_root).unwrap();
+            verify_withdrawal_in_payload::<crate::capella::mainnet::ExecutionPayload>(
+                &proof,
+                payload_root,
+            )
+            .unwrap();
+
+            let mut tampered = proof.clone();
+            tampered.withdrawal.amount += 1;
+            assert!(verify_withdrawal::<16>(&tampered, withdrawals_root).is_err());
+            assert!(verify_withdrawal_in_payload::<crate::capella::mainnet::ExecutionPayload>(
+                &tampered,
+                payload_root
+            )
+            .is_err());
+            // a withdrawal claimed at another position does not hold
+            let mut moved = proof.clone();
+            moved.withdrawal_index = (i + 1) % inner.withdrawals.len();
+            assert!(verify_withdrawal::<16>(&moved, withdrawals_root).is_err());
+        }
+
+        assert!(matches!(
+            prove_withdrawal(&payload, 16),
+            Err(crate::Error::Proof(ProofError::WithdrawalOutOfRange { index: 16, count: 16 }))
+        ));
+        let payload = crate::types::mainnet::ExecutionPayload::Bellatrix(Default::default());
+        assert!(matches!(
+            prove_withdrawal(&payload, 0),
+            Err(crate::Error::Proof(ProofError::NoWithdrawals))
+        ));
+    }
+}
@@ -0,0 +1,721 @@
+//! Request bodies for remote signers implementing the Web3Signer eth2 signing API, i.e.
+//! `POST /api/v1/eth2/sign/{identifier}`.
+//!
+//! Each request carries the `signingRoot` computed locally so the signer's own computation can
+//! be cross-checked against it.
+use crate::{
+    altair::validator::{ContributionAndProof, SyncAggregatorSelectionData},
+    builder::{compute_builder_domain, ValidatorRegistration},
+    error::WrongFork,
+    phase0::{
+        beacon_block::BeaconBlockHeader,
+        operations::{AttestationData, VoluntaryExit},
+        validator::{compute_domain_at_epoch, compute_domain_at_slot, AggregateAndProof, ForkInfo},
+    },
+    primitives::{Domain, DomainType, Epoch, Root, Slot},
+    signing::compute_signing_root,
+    ssz::prelude::*,
+    state_transition::{Context, Result},
+    types, Fork,
+};
+use serde_json::{Map, Value};
+
+/// The `type` of a remote signing request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SigningRequestKind {
+    BlockV2,
+    Attestation,
+    AggregationSlot,
+    AggregateAndProof,
+    RandaoReveal,
+    SyncCommitteeMessage,
+    SyncCommitteeSelectionProof,
+    SyncCommitteeContributionAndProof,
+    VoluntaryExit,
+    ValidatorRegistration,
+}
+
+/// A remote signing request, serializing to the JSON body the signer expects.
+///
+/// The object to sign is kept in `payload` under the field name of its request `kind`, e.g.
+/// `attestation` or `randao_reveal`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SigningRequest {
+    #[serde(rename = "type")]
+    pub kind: SigningRequestKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fork_info: Option<ForkInfo>,
+    #[serde(rename = "signingRoot")]
+    pub signing_root: Root,
+    #[serde(flatten)]
+    pub payload: Map<String, Value>,
+}
+
+#[derive(serde::Serialize)]
+struct VersionedBlock<'a, T: serde::Serialize> {
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    block: Option<&'a T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    block_header: Option<&'a BeaconBlockHeader>,
+}
+
+#[derive(serde::Serialize)]
+struct AggregationSlot {
+    #[serde(with = "crate::serde::as_str")]
+    slot: Slot,
+}
+
+#[derive(serde::Serialize)]
+struct RandaoReveal {
+    #[serde(with = "crate::serde::as_str")]
+    epoch: Epoch,
+}
+
+#[derive(serde::Serialize)]
+struct SyncCommitteeMessageData {
+    beacon_block_root: Root,
+    #[serde(with = "crate::serde::as_str")]
+    slot: Slot,
+}
+
+impl SigningRequest {
+    fn new<T: serde::Serialize, S: HashTreeRoot>(
+        kind: SigningRequestKind,
+        fork_info: Option<&ForkInfo>,
+        (field, payload): (&str, &T),
+        signing_data: &S,
+        domain: Domain,
+    ) -> Result<Self> {
+        let signing_root = compute_signing_root(signing_data, domain)?;
+        let mut fields = Map::new();
+        fields.insert(field.to_string(), serde_json::to_value(payload)?);
+        Ok(Self { kind, fork_info: fork_info.cloned(), signing_root, payload: fields })
+    }
+
+    /// A `BLOCK_V2` request to sign `block` as its proposer.
+    ///
+    /// Phase0 and Altair blocks are sent in full, while later blocks are sent as their header.
+    pub fn block_v2<
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+    >(
+        block: &types::BeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+        fork_info: &ForkInfo,
+        context: &Context,
+    ) -> Result<Self> {
+        let version = block.version();
+        if version >= Fork::Bellatrix {
+            let body_root =
+                crate::map_fork!(BeaconBlock, block, inner => inner.body.hash_tree_root())?;
+            let header = BeaconBlockHeader {
+                slot: block.slot(),
+                proposer_index: block.proposer_index(),
+                parent_root: block.parent_root(),
+                state_root: block.state_root(),
+                body_root,
+            };
+            return Self::block_header_v2(version, &header, fork_info, context)
+        }
+        let domain =
+            compute_domain_at_slot(DomainType::BeaconProposer, block.slot(), fork_info, context)?;
+        let payload = VersionedBlock {
+            version: version.to_string().to_uppercase(),
+            block: Some(block),
+            block_header: None,
+        };
+        Self::new(
+            SigningRequestKind::BlockV2,
+            Some(fork_info),
+            ("beacon_block", &payload),
+            block,
+            domain,
+        )
+    }
+
+    /// A `BLOCK_V2` request to sign the block with `header` from the `version` fork, which must
+    /// be Bellatrix or later.
+    pub fn block_header_v2(
+        version: Fork,
+        header: &BeaconBlockHeader,
+        fork_info: &ForkInfo,
+        context: &Context,
+    ) -> Result<Self> {
+        if version < Fork::Bellatrix {
+            return Err(WrongFork { expected: Fork::Bellatrix, found: version }.into())
+        }
+        let domain =
+            compute_domain_at_slot(DomainType::BeaconProposer, header.slot, fork_info, context)?;
+        let payload = VersionedBlock::<BeaconBlockHeader> {
+            version: version.to_string().to_uppercase(),
+            block: None,
+            block_header: Some(header),
+        };
+        Self::new(
+            SigningRequestKind::BlockV2,
+            Some(fork_info),
+            ("beacon_block", &payload),
+            header,
+            domain,
+        )
+    }
+
+    pub fn attestation(
+        data: &AttestationData,
+        fork_info: &ForkInfo,
+        context: &Context,
+    ) -> Result<Self> {
+        let domain = compute_domain_at_epoch(
+            DomainType::BeaconAttester,
+            data.target.epoch,
+            fork_info,
+            context,
+        )?;
+        Self::new(
+            SigningRequestKind::Attestation,
+            Some(fork_info),
+            ("attestation", data),
+            data,
+            domain,
+        )
+    }
+
+    /// An `AGGREGATION_SLOT` request for the selection proof of an aggregator at `slot`.
+    pub fn aggregation_slot(slot: Slot, fork_info: &ForkInfo, context: &Context) -> Result<Self> {
+        let domain = compute_domain_at_slot(DomainType::SelectionProof, slot, fork_info, context)?;
+        Self::new(
+            SigningRequestKind::AggregationSlot,
+            Some(fork_info),
+            ("aggregation_slot", &AggregationSlot { slot }),
+            &slot,
+            domain,
+        )
+    }
+
+    pub fn aggregate_and_proof<const MAX_VALIDATORS_PER_COMMITTEE: usize>(
+        aggregate_and_proof: &AggregateAndProof<MAX_VALIDATORS_PER_COMMITTEE>,
+        fork_info: &ForkInfo,
+        context: &Context,
+    ) -> Result<Self> {
+        let domain = compute_domain_at_slot(
+            DomainType::AggregateAndProof,
+            aggregate_and_proof.aggregate.data.slot,
+            fork_info,
+            context,
+        )?;
+        Self::new(
+            SigningRequestKind::AggregateAndProof,
+            Some(fork_info),
+            ("aggregate_and_proof", aggregate_and_proof),
+            aggregate_and_proof,
+            domain,
+        )
+    }
+
+    pub fn randao_reveal(epoch: Epoch, fork_info: &ForkInfo, context: &Context) -> Result<Self> {
+        let domain = compute_domain_at_epoch(DomainType::Randao, epoch, fork_info, context)?;
+        Self::new(
+            SigningRequestKind::RandaoReveal,
+            Some(fork_info),
+            ("randao_reveal", &RandaoReveal { epoch }),
+            &epoch,
+            domain,
+        )
+    }
+
+    /// A `SYNC_COMMITTEE_MESSAGE` request to sign `beacon_block_root` as the head at `slot`.
+    pub fn sync_committee_message(
+        beacon_block_root: Root,
+        slot: Slot,
+        fork_info: &ForkInfo,
+        context: &Context,
+    ) -> Result<Self> {
+        let domain = compute_domain_at_slot(DomainType::SyncCommittee, slot, fork_info, context)?;
+        Self::new(
+            SigningRequestKind::SyncCommitteeMessage,
+            Some(fork_info),
+            ("sync_committee_message", &SyncCommitteeMessageData { beacon_block_root, slot }),
+            &beacon_block_root,
+            domain,
+        )
+    }
+
+    pub fn sync_committee_selection_proof(
+        selection_data: &SyncAggregatorSelectionData,
+        fork_info: &ForkInfo,
+        context: &Context,
+    ) -> Result<Self> {
+        let domain = compute_domain_at_slot(
+            DomainType::SyncCommitteeSelectionProof,
+            selection_data.slot,
+            fork_info,
+            context,
+        )?;
+        Self::new(
+            SigningRequestKind::SyncCommitteeSelectionProof,
+            Some(fork_info),
+            ("sync_aggregator_selection_data", selection_data),
+            selection_data,
+            domain,
+        )
+    }
+
+    pub fn sync_committee_contribution_and_proof<const SYNC_SUBCOMMITTEE_SIZE: usize>(
+        contribution_and_proof: &ContributionAndProof<SYNC_SUBCOMMITTEE_SIZE>,
+        fork_info: &ForkInfo,
+        context: &Context,
+    ) -> Result<Self> {
+        let domain = compute_domain_at_slot(
+            DomainType::ContributionAndProof,
+            contribution_and_proof.contribution.slot,
+            fork_info,
+            context,
+        )?;
+        Self::new(
+            SigningRequestKind::SyncCommitteeContributionAndProof,
+            Some(fork_info),
+            ("contribution_and_proof", contribution_and_proof),
+            contribution_and_proof,
+            domain,
+        )
+    }
+
+    /// A `VOLUNTARY_EXIT` request to sign `exit`.
+    ///
+    /// The signing root follows `fork_info`, so from Deneb on (EIP-7044) callers should pass the
+    /// fork info of the Capella fork.
+    pub fn voluntary_exit(
+        exit: &VoluntaryExit,
+        fork_info: &ForkInfo,
+        context: &Context,
+    ) -> Result<Self> {
+        let domain =
+            compute_domain_at_epoch(DomainType::VoluntaryExit, exit.epoch, fork_info, context)?;
+        Self::new(
+            SigningRequestKind::VoluntaryExit,
+            Some(fork_info),
+            ("voluntary_exit", exit),
+            exit,
+            domain,
+        )
+    }
+
+    /// A `VALIDATOR_REGISTRATION` request for the builder network, which is signed independently
+    /// of any fork and so carries no `fork_info`.
+    pub fn validator_registration(
+        registration: &ValidatorRegistration,
+        context: &Context,
+    ) -> Result<Self> {
+        let domain = compute_builder_domain(context)?;
+        Self::new(
+            SigningRequestKind::ValidatorRegistration,
+            None,
+            ("validator_registration", registration),
+            registration,
+            domain,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        altair::validator::{
+            sign_sync_selection_proof, verify_sync_selection_proof, SyncCommitteeContribution,
+        },
+        crypto::SecretKey,
+        phase0::{
+            operations::{Attestation, Checkpoint},
+            validator::{sign_slot_selection_proof, verify_slot_selection_proof},
+        },
+        primitives::{BlsPublicKey, BlsSignature, TryFromHex},
+        signing::{sign_with_domain, verify_signed_data},
+    };
+    use serde_json::json;
+
+    const GENESIS_VALIDATORS_ROOT: &str =
+        "0x04700007fabc8282644aed6d1c7c9e21d38a03a0c4ba193f3afe428824b3a673";
+
+    fn fork_info() -> ForkInfo {
+        ForkInfo::new(
+            [0, 0, 0, 1],
+            [0, 0, 0, 2],
+            1,
+            Root::try_from_hex(GENESIS_VALIDATORS_ROOT).unwrap(),
+        )
+    }
+
+    fn fork_info_json() -> Value {
+        json!({
+            "fork": {
+                "previous_version": "0x00000001",
+                "current_version": "0x00000002",
+                "epoch": "1"
+            },
+            "genesis_validators_root": GENESIS_VALIDATORS_ROOT
+        })
+    }
+
+    fn root(byte: u8) -> Root {
+        Root::try_from([byte; 32].as_ref()).unwrap()
+    }
+
+    fn hex(root: &Root) -> Value {
+        serde_json::to_value(root).unwrap()
+    }
+
+    // Sign the request's `signingRoot` as a remote signer would.
+    fn sign(request: &SigningRequest, secret_key: &SecretKey) -> BlsSignature {
+        secret_key.sign(request.signing_root.as_ref())
+    }
+
+    fn check_roundtrip(request: &SigningRequest, expected: Value) {
+        let json = serde_json::to_value(request).unwrap();
+        assert_eq!(json, expected);
+        let decoded: SigningRequest = serde_json::from_value(json).unwrap();
+        assert_eq!(&decoded, request);
+    }
+
+    fn verify_with_domain<T: HashTreeRoot>(
+        data: &T,
+        signature: &BlsSignature,
+        public_key: &BlsPublicKey,
+        domain: Domain,
+    ) {
+        verify_signed_data(data, signature, public_key, domain).unwrap();
+    }
+
+    #[test]
+    fn test_block_v2() {
+        let context = Context::for_minimal();
+        let secret_key = SecretKey::key_gen(&[1u8; 32]).unwrap();
+        let public_key = secret_key.public_key();
+        let fork_info = fork_info();
+
+        let phase0_block = crate::phase0::minimal::BeaconBlock {
+            slot: 17,
+            proposer_index: 3,
+            parent_root: root(1),
+            state_root: root(2),
+            ..Default::default()
+        };
+        let block = types::minimal::BeaconBlock::Phase0(phase0_block.clone());
+        let request = SigningRequest::block_v2(&block, &fork_info, &context).unwrap();
+        check_roundtrip(
+            &request,
+            json!({
+                "type": "BLOCK_V2",
+                "fork_info": fork_info_json(),
+                "signingRoot": hex(&request.signing_root),
+                "beacon_block": {
+                    "version": "PHASE0",
+                    "block": serde_json::to_value(&phase0_block).unwrap()
+                }
+            }),
+        );
+        let domain =
+            compute_domain_at_slot(DomainType::BeaconProposer, 17, &fork_info, &context).unwrap();
+        verify_with_domain(&phase0_block, &sign(&request, &secret_key), &public_key, domain);
+
+        let bellatrix_block = crate::bellatrix::minimal::BeaconBlock {
+            slot: 17,
+            proposer_index: 3,
+            parent_root: root(1),
+            state_root: root(2),
+            ..Default::default()
+        };
+        let block = types::minimal::BeaconBlock::Bellatrix(bellatrix_block.clone());
+        let request = SigningRequest::block_v2(&block, &fork_info, &context).unwrap();
+        check_roundtrip(
+            &request,
+            json!({
+                "type": "BLOCK_V2",
+                "fork_info": fork_info_json(),
+                "signingRoot": hex(&request.signing_root),
+                "beacon_block": {
+                    "version": "BELLATRIX",
+                    "block_header": {
+                        "slot": "17",
+                        "proposer_index": "3",
+                        "parent_root": hex(&root(1)),
+                        "state_root": hex(&root(2)),
+                        "body_root": hex(&bellatrix_block.body.hash_tree_root().unwrap())
+                    }
+                }
+            }),
+        );
+        // the header has the same root as the full block
+        verify_with_domain(&bellatrix_block, &sign(&request, &secret_key), &public_key, domain);
+
+        let header = BeaconBlockHeader { slot: 17, ..Default::default() };
+        let request =
+            SigningRequest::block_header_v2(Fork::Electra, &header, &fork_info, &context).unwrap();
+        assert_eq!(request.payload["beacon_block"]["version"], "ELECTRA");
+        assert!(matches!(
+            SigningRequest::block_header_v2(Fork::Altair, &header, &fork_info, &context),
+            Err(crate::Error::WrongFork(WrongFork {
+                expected: Fork::Bellatrix,
+                found: Fork::Altair
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_attestation_requests() {
+        let context = Context::for_minimal();
+        let secret_key = SecretKey::key_gen(&[2u8; 32]).unwrap();
+        let public_key = secret_key.public_key();
+        let fork_info = fork_info();
+
+        let data = AttestationData {
+            slot: 32,
+            index: 1,
+            beacon_block_root: root(3),
+            source: Checkpoint { epoch: 2, root: root(4) },
+            target: Checkpoint { epoch: 4, root: root(5) },
+        };
+        let request = SigningRequest::attestation(&data, &fork_info, &context).unwrap();
+        check_roundtrip(
+            &request,
+            json!({
+                "type": "ATTESTATION",
+                "fork_info": fork_info_json(),
+                "signingRoot": hex(&request.signing_root),
+                "attestation": {
+                    "slot": "32",
+                    "index": "1",
+                    "beacon_block_root": hex(&root(3)),
+                    "source": { "epoch": "2", "root": hex(&root(4)) },
+                    "target": { "epoch": "4", "root": hex(&root(5)) }
+                }
+            }),
+        );
+        let domain =
+            compute_domain_at_epoch(DomainType::BeaconAttester, 4, &fork_info, &context).unwrap();
+        verify_with_domain(&data, &sign(&request, &secret_key), &public_key, domain);
+
+        let request = SigningRequest::aggregation_slot(32, &fork_info, &context).unwrap();
+        check_roundtrip(
+            &request,
+            json!({
+                "type": "AGGREGATION_SLOT",
+                "fork_info": fork_info_json(),
+                "signingRoot": hex(&request.signing_root),
+                "aggregation_slot": { "slot": "32" }
+            }),
+        );
+        let selection_proof = sign(&request, &secret_key);
+        assert_eq!(
+            selection_proof,
+            sign_slot_selection_proof(&secret_key, 32, &fork_info, &context).unwrap()
+        );
+        verify_slot_selection_proof(&selection_proof, &public_key, 32, &fork_info, &context)
+            .unwrap();
+
+        let aggregate_and_proof = AggregateAndProof {
+            aggregator_index: 7,
+            aggregate: Attestation::<4> { data: data.clone(), ..Default::default() },
+            selection_proof,
+        };
+        let request =
+            SigningRequest::aggregate_and_proof(&aggregate_and_proof, &fork_info, &context)
+                .unwrap();
+        check_roundtrip(
+            &request,
+            json!({
+                "type": "AGGREGATE_AND_PROOF",
+                "fork_info": fork_info_json(),
+                "signingRoot": hex(&request.signing_root),
+                "aggregate_and_proof": serde_json::to_value(&aggregate_and_proof).unwrap()
+            }),
+        );
+        assert_eq!(request.payload["aggregate_and_proof"]["aggregator_index"], "7");
+        let domain =
+            compute_domain_at_slot(DomainType::AggregateAndProof, 32, &fork_info, &context)
+                .unwrap();
+        verify_with_domain(&aggregate_and_proof, &sign(&request, &secret_key), &public_key, domain);
+    }
+
+    #[test]
+    fn test_proposer_and_exit_requests() {
+        let context = Context::for_minimal();
+        let secret_key = SecretKey::key_gen(&[3u8; 32]).unwrap();
+        let public_key = secret_key.public_key();
+        let fork_info = fork_info();
+
+        let request = SigningRequest::randao_reveal(3, &fork_info, &context).unwrap();
+        check_roundtrip(
+            &request,
+            json!({
+                "type": "RANDAO_REVEAL",
+                "fork_info": fork_info_json(),
+                "signingRoot": hex(&request.signing_root),
+                "randao_reveal": { "epoch": "3" }
+            }),
+        );
+        let domain = compute_domain_at_epoch(DomainType::Randao, 3, &fork_info, &context).unwrap();
+        verify_with_domain(&3u64, &sign(&request, &secret_key), &public_key, domain);
+
+        let exit = VoluntaryExit { epoch: 5, validator_index: 11 };
+        let request = SigningRequest::voluntary_exit(&exit, &fork_info, &context).unwrap();
+        check_roundtrip(
+            &request,
+            json!({
+                "type": "VOLUNTARY_EXIT",
+                "fork_info": fork_info_json(),
+                "signingRoot": hex(&request.signing_root),
+                "voluntary_exit": { "epoch": "5", "validator_index": "11" }
+            }),
+        );
+        let domain =
+            compute_domain_at_epoch(DomainType::VoluntaryExit, 5, &fork_info, &context).unwrap();
+        verify_with_domain(&exit, &sign(&request, &secret_key), &public_key, domain);
+    }
+
+    #[test]
+    fn test_sync_committee_requests() {
+        let context = Context::for_minimal();
+        let secret_key = SecretKey::key_gen(&[4u8; 32]).unwrap();
+        let public_key = secret_key.public_key();
+        let fork_info = fork_info();
+
+        let request =
+            SigningRequest::sync_committee_message(root(6), 40, &fork_info, &context).unwrap();
+        check_roundtrip(
+            &request,
+            json!({
+                "type": "SYNC_COMMITTEE_MESSAGE",
+                "fork_info": fork_info_json(),
+                "signingRoot": hex(&request.signing_root),
+                "sync_committee_message": { "beacon_block_root": hex(&root(6)), "slot": "40" }
+            }),
+        );
+        let domain =
+            compute_domain_at_slot(DomainType::SyncCommittee, 40, &fork_info, &context).unwrap();
+        verify_with_domain(&root(6), &sign(&request, &secret_key), &public_key, domain);
+
+        let selection_data = SyncAggregatorSelectionData { slot: 40, subcommittee_index: 2 };
+        let request =
+            SigningRequest::sync_committee_selection_proof(&selection_data, &fork_info, &context)
+                .unwrap();
+        check_roundtrip(
+            &request,
+            json!({
+                "type": "SYNC_COMMITTEE_SELECTION_PROOF",
+                "fork_info": fork_info_json(),
+                "signingRoot": hex(&request.signing_root),
+                "sync_aggregator_selection_data": { "slot": "40", "subcommittee_index": "2" }
+            }),
+        );
+        let selection_proof = sign(&request, &secret_key);
+        assert_eq!(
+            selection_proof,
+            sign_sync_selection_proof(&secret_key, 40, 2, &fork_info, &context).unwrap()
+        );
+        verify_sync_selection_proof(&selection_proof, &public_key, 40, 2, &fork_info, &context)
+            .unwrap();
+
+        let contribution_and_proof = ContributionAndProof {
+            aggregator_index: 9,
+            contribution: SyncCommitteeContribution::<8> {
+                slot: 40,
+                beacon_block_root: root(6),
+                subcommittee_index: 2,
+                ..Default::default()
+            },
+            selection_proof,
+        };
+        let request = SigningRequest::sync_committee_contribution_and_proof(
+            &contribution_and_proof,
+            &fork_info,
+            &context,
+        )
+        .unwrap();
+        check_roundtrip(
+            &request,
+            json!({
+                "type": "SYNC_COMMITTEE_CONTRIBUTION_AND_PROOF",
+                "fork_info": fork_info_json(),
+                "signingRoot": hex(&request.signing_root),
+                "contribution_and_proof": serde_json::to_value(&contribution_and_proof).unwrap()
+            }),
+        );
+        let domain =
+            compute_domain_at_slot(DomainType::ContributionAndProof, 40, &fork_info, &context)
+                .unwrap();
+        let signature = sign_with_domain(&contribution_and_proof, &secret_key, domain).unwrap();
+        assert_eq!(sign(&request, &secret_key), signature);
+    }
+
+    #[test]
+    fn test_validator_registration() {
+        let context = Context::for_mainnet();
+        let secret_key = SecretKey::key_gen(&[5u8; 32]).unwrap();
+        let registration = ValidatorRegistration {
+            gas_limit: 30_000_000,
+            timestamp: 1_700_000_000,
+            public_key: secret_key.public_key(),
+            ..Default::default()
+        };
+        let request = SigningRequest::validator_registration(&registration, &context).unwrap();
+        check_roundtrip(
+            &request,
+            json!({
+                "type": "VALIDATOR_REGISTRATION",
+                "signingRoot": hex(&request.signing_root),
+                "validator_registration": {
+                    "fee_recipient": "0x0000000000000000000000000000000000000000",
+                    "gas_limit": "30000000",
+                    "timestamp": "1700000000",
+                    "pubkey": serde_json::to_value(&registration.public_key).unwrap()
+                }
+            }),
+        );
+        let signature = sign(&request, &secret_key);
+        let domain = compute_builder_domain(&context).unwrap();
+        verify_with_domain(&registration, &signature, &registration.public_key, domain);
+    }
+}
@@ -0,0 +1,342 @@
+//! Indices over a chain collected while replaying its blocks.
+use crate::{
+    phase0::{compute_epoch_at_slot, compute_start_slot_at_epoch, Checkpoint},
+    primitives::{Epoch, Root},
+    state_transition::Context,
+    types::BeaconState,
+};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+// Size of an encoded `BoundaryIndex` entry: the epoch followed by both roots.
+const ENTRY_SIZE: usize = 8 + 32 + 32;
+
+#[derive(Debug, Error)]
+pub enum BoundaryIndexError {
+    #[error("encoding of {0} bytes is not a whole number of entries")]
+    InvalidLength(usize),
+    #[error("entry for epoch {0} is out of order")]
+    UnorderedEntry(Epoch),
+}
+
+/// The roots of the chain at the first slot of an epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BoundaryRoots {
+    /// The root of the latest block at or before the boundary slot, i.e. `get_block_root`.
+    pub block_root: Root,
+    /// The root of the state at the boundary slot, after any block in that slot.
+    pub state_root: Root,
+}
+
+/// An index from epochs to the roots of the chain at their boundary slots.
+///
+/// A state only holds the roots of the last `SLOTS_PER_HISTORICAL_ROOT` slots, so the index is
+/// built up by observing states as blocks are replayed (or by loading stored states in any
+/// order) and keeps every boundary seen so far.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BoundaryIndex {
+    entries: BTreeMap<Epoch, BoundaryRoots>,
+}
+
+impl BoundaryIndex {
+    pub fn from_state<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    >(
+        state: &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+        context: &Context,
+    ) -> Self {
+        let mut index = Self::default();
+        index.observe(state, context);
+        index
+    }
+
+    /// Record the boundaries whose roots are in the history of `state`.
+    ///
+    /// The boundary at `state.slot` itself is left for a later state: until the chain moves
+    /// past the slot, a block may still be applied in it.
+    pub fn observe<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    >(
+        &mut self,
+        state: &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+        context: &Context,
+    ) {
+        let slot = state.slot();
+        if slot == 0 {
+            return
+        }
+        // the same window as `get_block_root_at_slot`: `slot - N <= boundary < slot`
+        let oldest_slot = slot.saturating_sub(SLOTS_PER_HISTORICAL_ROOT as u64);
+        let mut epoch = compute_epoch_at_slot(oldest_slot, context);
+        if compute_start_slot_at_epoch(epoch, context) < oldest_slot {
+            epoch += 1;
+        }
+        loop {
+            let boundary_slot = compute_start_slot_at_epoch(epoch, context);
+            if boundary_slot >= slot {
+                break
+            }
+            let index = (boundary_slot % SLOTS_PER_HISTORICAL_ROOT as u64) as usize;
+            let roots = BoundaryRoots {
+                block_root: state.block_roots()[index],
+                state_root: state.state_roots()[index],
+            };
+            self.entries.insert(epoch, roots);
+            epoch += 1;
+        }
+    }
+
+    pub fn get(&self, epoch: Epoch) -> Option<&BoundaryRoots> {
+        self.entries.get(&epoch)
+    }
+
+    /// The block root at the boundary of `epoch`, matching `get_block_root(state, epoch)` of any
+    /// state on the chain that can answer it.
+    pub fn block_root(&self, epoch: Epoch) -> Option<Root> {
+        self.get(epoch).map(|roots| roots.block_root)
+    }
+
+    pub fn state_root(&self, epoch: Epoch) -> Option<Root> {
+        self.get(epoch).map(|roots| roots.state_root)
+    }
+
+    /// The checkpoint of the chain for `epoch`, e.g. the target attestations in `epoch` vote for.
+    pub fn checkpoint(&self, epoch: Epoch) -> Option<Checkpoint> {
+        self.block_root(epoch).map(|root| Checkpoint { epoch, root })
+    }
+
+    /// Whether `target` is the checkpoint of the chain for its epoch, or `None` if the boundary
+    /// of that epoch is not indexed.
+    pub fn is_canonical_target(&self, target: &Checkpoint) -> Option<bool> {
+        self.block_root(target.epoch).map(|root| root == target.root)
+    }
+
+    /// The indexed boundaries in epoch order.
+    pub fn iter(&self) -> impl Iterator<Item = (Epoch, &BoundaryRoots)> {
+        self.entries.iter().map(|(epoch, roots)| (*epoch, roots))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Add the entries of `other`, e.g. an index loaded from storage, preferring `other` where
+    /// both have an entry.
+    pub fn merge(&mut self, other: BoundaryIndex) {
+        self.entries.extend(other.entries);
+    }
+
+    /// Encode the index for persistence as a sequence of fixed-size entries in epoch order.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buffer = Vec::with_capacity(self.entries.len() * ENTRY_SIZE);
+        for (epoch, roots) in &self.entries {
+            buffer.extend_from_slice(&epoch.to_le_bytes());
+            buffer.extend_from_slice(roots.block_root.as_ref());
+            buffer.extend_from_slice(roots.state_root.as_ref());
+        }
+        buffer
+    }
+
+    pub fn from_bytes(encoding: &[u8]) -> Result<Self, BoundaryIndexError> {
+        if encoding.len() % ENTRY_SIZE != 0 {
+            return Err(BoundaryIndexError::InvalidLength(encoding.len()))
+        }
+        let mut entries = BTreeMap::new();
+        let mut previous = None;
+        for entry in encoding.chunks_exact(ENTRY_SIZE) {
+            let epoch = Epoch::from_le_bytes(entry[..8].try_into().expect("correct length"));
+            if previous.map_or(false, |previous| epoch <= previous) {
+                return Err(BoundaryIndexError::UnorderedEntry(epoch))
+            }
+            previous = Some(epoch);
+            let roots = BoundaryRoots {
+                block_root: Root::try_from(&entry[8..40]).expect("correct length"),
+                state_root: Root::try_from(&entry[40..]).expect("correct length"),
+            };
+            entries.insert(epoch, roots);
+        }
+        Ok(Self { entries })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::phase0::genesis_state,
+        phase0::{
+            get_beacon_proposer_index, get_block_root, minimal, process_block_header, process_slots,
+        },
+        primitives::Slot,
+        ssz::prelude::*,
+        types,
+    };
+
+    // Replay a chain of empty blocks from the genesis state, skipping the slots in `skipped`,
+    // and return the index built along the way, the final state and the block root and state
+    // root at each slot.
+    fn replay(
+        until: Slot,
+        skipped: &[Slot],
+        context: &Context,
+    ) -> (BoundaryIndex, minimal::BeaconState, Vec<(Root, Root)>) {
+        // the root `process_slot` records for the latest block, once it fills in its state root
+        let latest_block_root = |state: &minimal::BeaconState| {
+            let mut header = state.latest_block_header.clone();
+            header.state_root = state.hash_tree_root().unwrap();
+            header.hash_tree_root().unwrap()
+        };
+        let mut state = genesis_state();
+        let mut index = BoundaryIndex::default();
+        let mut block_root = latest_block_root(&state);
+        let mut roots = vec![(block_root, state.hash_tree_root().unwrap())];
+        for slot in 1..=until {
+            process_slots(&mut state, slot, context).unwrap();
+            if !skipped.contains(&slot) {
+                let block = minimal::BeaconBlock {
+                    slot,
+                    proposer_index: get_beacon_proposer_index(&state, context).unwrap(),
+                    parent_root: state.latest_block_header.hash_tree_root().unwrap(),
+                    ..Default::default()
+                };
+                process_block_header(&mut state, &block, context).unwrap();
+                block_root = latest_block_root(&state);
+            }
+            roots.push((block_root, state.hash_tree_root().unwrap()));
+            index.observe(&types::minimal::BeaconState::Phase0(state.clone()), context);
+        }
+        (index, state, roots)
+    }
+
+    #[test]
+    fn test_boundaries_with_skipped_slots() {
+        let context = Context::for_minimal();
+        let slots_per_epoch = context.slots_per_epoch;
+        // skip the boundary slots of epochs 1 and 3, and the slots around the boundary of 2
+        let skipped = [
+            slots_per_epoch,
+            2 * slots_per_epoch - 1,
+            2 * slots_per_epoch,
+            2 * slots_per_epoch + 1,
+            3 * slots_per_epoch,
+        ];
+        let until = 12 * slots_per_epoch + 3;
+        let (index, state, roots) = replay(until, &skipped, &context);
+
+        // every boundary strictly before the head is indexed, including those older than the
+        // history of the final state
+        assert_eq!(index.len(), 13);
+        for (epoch, boundary) in index.iter() {
+            let slot = compute_start_slot_at_epoch(epoch, &context) as usize;
+            assert_eq!(
+                *boundary,
+                BoundaryRoots { block_root: roots[slot].0, state_root: roots[slot].1 }
+            );
+            if let Ok(root) = get_block_root(&state, epoch, &context) {
+                assert_eq!(index.block_root(epoch).unwrap(), *root);
+            }
+        }
+
+        // a skipped boundary slot takes the root of the last block before it
+        let boundary = compute_start_slot_at_epoch(2, &context) as usize;
+        assert_eq!(index.block_root(2).unwrap(), roots[boundary - 2].0);
+        assert_eq!(index.block_root(1).unwrap(), roots[slots_per_epoch as usize - 1].0);
+        assert_ne!(index.state_root(1).unwrap(), roots[slots_per_epoch as usize - 1].1);
+
+        let target = index.checkpoint(4).unwrap();
+        assert_eq!(index.is_canonical_target(&target), Some(true));
+        let wrong_target = Checkpoint { epoch: 4, root: roots[1].0 };
+        assert_eq!(index.is_canonical_target(&wrong_target), Some(false));
+        assert_eq!(index.is_canonical_target(&Checkpoint { epoch: 13, ..target }), None);
+
+        // a stored state only contributes the boundaries in its own history
+        let from_state =
+            BoundaryIndex::from_state(&types::minimal::BeaconState::Phase0(state), &context);
+        assert_eq!(from_state.len(), 8);
+        assert!(from_state.iter().all(|(epoch, roots)| index.get(epoch) == Some(roots)));
+        let mut merged = BoundaryIndex::default();
+        merged.merge(from_state);
+        assert!(merged.get(0).is_none());
+    }
+
+    #[test]
+    fn test_boundary_index_encoding() {
+        let context = Context::for_minimal();
+        let (index, _, _) = replay(3 * context.slots_per_epoch + 1, &[], &context);
+        let encoding = index.to_bytes();
+        assert_eq!(encoding.len(), index.len() * ENTRY_SIZE);
+        assert_eq!(BoundaryIndex::from_bytes(&encoding).unwrap(), index);
+        assert_eq!(BoundaryIndex::from_bytes(&[]).unwrap(), BoundaryIndex::default());
+
+        assert!(matches!(
+            BoundaryIndex::from_bytes(&encoding[1..]),
+            Err(BoundaryIndexError::InvalidLength(_))
+        ));
+        let mut unordered = encoding[ENTRY_SIZE..2 * ENTRY_SIZE].to_vec();
+        unordered.extend_from_slice(&encoding[..ENTRY_SIZE]);
+        assert!(matches!(
+            BoundaryIndex::from_bytes(&unordered),
+            Err(BoundaryIndexError::UnorderedEntry(0))
+        ));
+    }
+}
@@ -137,14 +137,35 @@ pub mod seq_of_str {
         }
     }
 
+    /// Errors from building a collection out of the parsed elements.
+    pub trait CollectionError {
+        fn describe(&self) -> String;
+    }
+
+    // The error from `List` and `Vector` states the bound that was violated.
+    impl<U, E: fmt::Display> CollectionError for (Vec<U>, E) {
+        fn describe(&self) -> String {
+            self.1.to_string()
+        }
+    }
+
+    impl CollectionError for std::convert::Infallible {
+        fn describe(&self) -> String {
+            match *self {}
+        }
+    }
+
     pub fn deserialize<'de, D, T, U>(deserializer: D) -> Result<T, D::Error>
     where
         D: Deserializer<'de>,
         T: TryFrom<Vec<U>>,
+        T::Error: CollectionError,
         U: FromStr,
     {
         let data = deserializer.deserialize_seq(Visitor(PhantomData))?;
-        T::try_from(data).map_err(|_| serde::de::Error::custom("failure to parse collection"))
+        T::try_from(data).map_err(|err| {
+            serde::de::Error::custom(format!("failure to parse collection: {}", err.describe()))
+        })
     }
 }
 
@@ -1,8 +1,14 @@
 use crate::{
     crypto::{self, SecretKey},
-    primitives::{BlsPublicKey, BlsSignature, Domain, Root},
+    phase0::{
+        compute_domain,
+        validator::{compute_domain_at_epoch, ForkInfo},
+        DepositMessage, VoluntaryExit,
+    },
+    primitives::{BlsPublicKey, BlsSignature, Domain, DomainType, Epoch, Root, Slot},
     ssz::prelude::*,
-    Error,
+    state_transition::Context,
+    Error, Fork,
 };
 
 #[derive(Default, Debug, SimpleSerialize)]
@@ -39,3 +45,280 @@ pub fn verify_signed_data<T: HashTreeRoot>(
     let signing_root = compute_signing_root(data, domain)?;
     crypto::verify_signature(public_key, signing_root.as_ref(), signature).map_err(Into::into)
 }
+
+/// The domain of `domain_type` for a message in `epoch` on the chain of `fork_info`, with the
+/// fork version in effect at `epoch`.
+///
+/// Without `fork_info`, the domain is the one of messages signed before genesis, like deposits:
+/// the genesis fork version and a zero genesis validators root.
+pub fn compute_signing_domain(
+    domain_type: DomainType,
+    epoch: Epoch,
+    fork_info: Option<&ForkInfo>,
+    context: &Context,
+) -> Result<Domain, Error> {
+    match fork_info {
+        Some(fork_info) => compute_domain_at_epoch(domain_type, epoch, fork_info, context),
+        None => compute_domain(domain_type, None, None, context),
+    }
+}
+
+/// Sign `object` in the domain of `domain_type` at `epoch`, see [`compute_signing_domain`].
+pub fn sign_with_domain_type<T: HashTreeRoot>(
+    object: &T,
+    domain_type: DomainType,
+    epoch: Epoch,
+    fork_info: Option<&ForkInfo>,
+    context: &Context,
+    secret_key: &SecretKey,
+) -> Result<BlsSignature, Error> {
+    let domain = compute_signing_domain(domain_type, epoch, fork_info, context)?;
+    sign_with_domain(object, secret_key, domain)
+}
+
+/// Verify the `signature` over `object` in the domain of `domain_type` at `epoch`, as signed
+/// with [`sign_with_domain_type`].
+pub fn verify_signed_envelope<T: HashTreeRoot>(
+    object: &T,
+    signature: &BlsSignature,
+    public_key: &BlsPublicKey,
+    domain_type: DomainType,
+    epoch: Epoch,
+    fork_info: Option<&ForkInfo>,
+    context: &Context,
+) -> Result<(), Error> {
+    let domain = compute_signing_domain(domain_type, epoch, fork_info, context)?;
+    verify_signed_data(object, signature, public_key, domain)
+}
+
+/// Sign `block` as its proposer, for a block at `slot` of any fork.
+pub fn sign_beacon_block<T: HashTreeRoot>(
+    block: &T,
+    slot: Slot,
+    fork_info: &ForkInfo,
+    context: &Context,
+    secret_key: &SecretKey,
+) -> Result<BlsSignature, Error> {
+    let epoch = slot / context.slots_per_epoch;
+    sign_with_domain_type(
+        block,
+        DomainType::BeaconProposer,
+        epoch,
+        Some(fork_info),
+        context,
+        secret_key,
+    )
+}
+
+pub fn verify_beacon_block<T: HashTreeRoot>(
+    block: &T,
+    slot: Slot,
+    signature: &BlsSignature,
+    public_key: &BlsPublicKey,
+    fork_info: &ForkInfo,
+    context: &Context,
+) -> Result<(), Error> {
+    let epoch = slot / context.slots_per_epoch;
+    verify_signed_envelope(
+        block,
+        signature,
+        public_key,
+        DomainType::BeaconProposer,
+        epoch,
+        Some(fork_info),
+        context,
+    )
+}
+
+// Since Deneb, exits are signed with the Capella fork version whatever the fork at their epoch,
+// so that they stay valid across forks.
+fn voluntary_exit_domain(
+    exit: &VoluntaryExit,
+    fork_info: &ForkInfo,
+    context: &Context,
+) -> Result<Domain, Error> {
+    if context.fork_at_epoch(fork_info.fork.epoch) >= Fork::Deneb {
+        compute_domain(
+            DomainType::VoluntaryExit,
+            Some(context.capella_fork_version),
+            Some(fork_info.genesis_validators_root),
+            context,
+        )
+    } else {
+        compute_domain_at_epoch(DomainType::VoluntaryExit, exit.epoch, fork_info, context)
+    }
+}
+
+pub fn sign_voluntary_exit(
+    exit: &VoluntaryExit,
+    fork_info: &ForkInfo,
+    context: &Context,
+    secret_key: &SecretKey,
+) -> Result<BlsSignature, Error> {
+    let domain = voluntary_exit_domain(exit, fork_info, context)?;
+    sign_with_domain(exit, secret_key, domain)
+}
+
+pub fn verify_voluntary_exit(
+    exit: &VoluntaryExit,
+    signature: &BlsSignature,
+    public_key: &BlsPublicKey,
+    fork_info: &ForkInfo,
+    context: &Context,
+) -> Result<(), Error> {
+    let domain = voluntary_exit_domain(exit, fork_info, context)?;
+    verify_signed_data(exit, signature, public_key, domain)
+}
+
+pub fn sign_randao_reveal(
+    epoch: Epoch,
+    fork_info: &ForkInfo,
+    context: &Context,
+    secret_key: &SecretKey,
+) -> Result<BlsSignature, Error> {
+    sign_with_domain_type(&epoch, DomainType::Randao, epoch, Some(fork_info), context, secret_key)
+}
+
+pub fn verify_randao_reveal(
+    randao_reveal: &BlsSignature,
+    public_key: &BlsPublicKey,
+    epoch: Epoch,
+    fork_info: &ForkInfo,
+    context: &Context,
+) -> Result<(), Error> {
+    verify_signed_envelope(
+        &epoch,
+        randao_reveal,
+        public_key,
+        DomainType::Randao,
+        epoch,
+        Some(fork_info),
+        context,
+    )
+}
+
+/// Sign `message` for a deposit, which is valid on any chain sharing the genesis fork version.
+pub fn sign_deposit_message(
+    message: &DepositMessage,
+    context: &Context,
+    secret_key: &SecretKey,
+) -> Result<BlsSignature, Error> {
+    sign_with_domain_type(message, DomainType::Deposit, 0, None, context, secret_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::{phase0::genesis_state, secret_keys},
+        phase0::{get_beacon_proposer_index, process_block, process_slots, state_transition},
+        state_transition::Validation,
+    };
+
+    #[test]
+    fn test_signed_block_passes_verification() {
+        let context = Context::for_minimal();
+        let secret_keys = secret_keys();
+        let genesis = genesis_state();
+        let fork_info = ForkInfo {
+            fork: genesis.fork.clone(),
+            genesis_validators_root: genesis.genesis_validators_root,
+        };
+
+        let slot = 3;
+        let mut state = genesis.clone();
+        process_slots(&mut state, slot, &context).unwrap();
+        let proposer_index = get_beacon_proposer_index(&state, &context).unwrap();
+        let secret_key = &secret_keys[proposer_index];
+        let mut block = crate::phase0::minimal::SignedBeaconBlock::default();
+        block.message.slot = slot;
+        block.message.proposer_index = proposer_index;
+        block.message.parent_root = state.latest_block_header.hash_tree_root().unwrap();
+        let epoch = slot / context.slots_per_epoch;
+        block.message.body.randao_reveal =
+            sign_randao_reveal(epoch, &fork_info, &context, secret_key).unwrap();
+        block.message.body.eth1_data = state.eth1_data.clone();
+        process_block(&mut state, &block.message, &context).unwrap();
+        block.message.state_root = state.hash_tree_root().unwrap();
+        block.signature =
+            sign_beacon_block(&block.message, slot, &fork_info, &context, secret_key).unwrap();
+
+        let public_key = &state.validators[proposer_index].public_key;
+        verify_beacon_block(
+            &block.message,
+            slot,
+            &block.signature,
+            public_key,
+            &fork_info,
+            &context,
+        )
+        .unwrap();
+        verify_randao_reveal(
+            &block.message.body.randao_reveal,
+            public_key,
+            epoch,
+            &fork_info,
+            &context,
+        )
+        .unwrap();
+        let mut state = genesis.clone();
+        state_transition(&mut state, &block, Validation::Enabled, &context).unwrap();
+
+        // a signature over a different epoch or from a different key is rejected
+        assert!(verify_randao_reveal(
+            &block.message.body.randao_reveal,
+            public_key,
+            epoch + 1,
+            &fork_info,
+            &context,
+        )
+        .is_err());
+        block.signature = sign_beacon_block(
+            &block.message,
+            slot,
+            &fork_info,
+            &context,
+            &secret_keys[(proposer_index + 1) % secret_keys.len()],
+        )
+        .unwrap();
+        let mut state = genesis;
+        assert!(state_transition(&mut state, &block, Validation::Enabled, &context).is_err());
+    }
+
+    #[test]
+    fn test_signing_domains() {
+        let mut context = Context::for_minimal();
+        let secret_key = &secret_keys()[0];
+        let public_key = secret_key.public_key();
+
+        // deposits are signed before genesis, with no validators root
+        let message = DepositMessage { public_key: public_key.clone(), ..Default::default() };
+        let signature = sign_deposit_message(&message, &context, secret_key).unwrap();
+        let deposit_domain = compute_domain(DomainType::Deposit, None, None, &context).unwrap();
+        verify_signed_data(&message, &signature, &public_key, deposit_domain).unwrap();
+
+        // exits from Deneb on use the Capella fork version
+        let genesis_validators_root = Root::try_from([3u8; 32].as_ref()).unwrap();
+        context.altair_fork_epoch = 0;
+        context.bellatrix_fork_epoch = 0;
+        context.capella_fork_epoch = 1;
+        context.deneb_fork_epoch = 2;
+        let capella = ForkInfo::at_epoch(&context, 1, genesis_validators_root);
+        let deneb = ForkInfo::at_epoch(&context, 2, genesis_validators_root);
+        let exit = VoluntaryExit { epoch: 1, validator_index: 0 };
+        let signature = sign_voluntary_exit(&exit, &deneb, &context, secret_key).unwrap();
+        verify_voluntary_exit(&exit, &signature, &public_key, &capella, &context).unwrap();
+        let capella_domain = compute_domain(
+            DomainType::VoluntaryExit,
+            Some(context.capella_fork_version),
+            Some(genesis_validators_root),
+            &context,
+        )
+        .unwrap();
+        verify_signed_data(&exit, &signature, &public_key, capella_domain).unwrap();
+        // before Deneb, the fork version at the epoch of the exit applies
+        let exit = VoluntaryExit { epoch: 0, validator_index: 0 };
+        let signature = sign_voluntary_exit(&exit, &capella, &context, secret_key).unwrap();
+        assert!(verify_voluntary_exit(&exit, &signature, &public_key, &deneb, &context).is_err());
+    }
+}
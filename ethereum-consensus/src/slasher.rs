@@ -0,0 +1,688 @@
+//! A minimal slasher for attestations: it records the votes of each validator and finds the
+//! attestations that double vote, or surround (or are surrounded by) a vote seen before, within a
+//! bounded history of epochs.
+//!
+//! Surround votes are found with the min-max spans of each validator, indexed by source epoch:
+//! `min_targets[e]` is the lowest target of the votes with a source after `e`, and
+//! `max_targets[e]` the highest target of the votes with a source before `e`. A vote from `source`
+//! to `target` surrounds an earlier vote if `min_targets[source] < target`, and is surrounded by
+//! one if `max_targets[source] > target`. Both checks take constant time, and recording a vote
+//! stops updating the spans as soon as they are already tight, so updates are amortized over the
+//! votes.
+//!
+//! Attestations and spans are persisted in a [`KeyValueStore`]. The spans of the validators seen
+//! are cached in memory, and written back with [`MinimalSlasher::flush`].
+use crate::{
+    electra,
+    phase0::{self, AttestationData},
+    primitives::{Epoch, Root, ValidatorIndex},
+    ssz::prelude::*,
+    Error,
+};
+use std::{
+    collections::{hash_map::Entry, BTreeMap, HashMap, HashSet},
+    marker::PhantomData,
+    ops::Bound,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SlasherError {
+    #[error("attestation has source epoch {source_epoch} after its target epoch {target_epoch}")]
+    InvalidEpochs { source_epoch: Epoch, target_epoch: Epoch },
+    #[error(
+        "attestation has source epoch {source_epoch} before the oldest epoch {oldest_epoch} kept"
+    )]
+    SourceTooOld { source_epoch: Epoch, oldest_epoch: Epoch },
+    #[error("record under key {0:?} is corrupt")]
+    CorruptRecord(Vec<u8>),
+    #[error("{0}")]
+    Serialize(#[from] SerializeError),
+}
+
+/// Access to an indexed attestation, and to the attester slashing made from two of them,
+/// regardless of fork.
+pub trait SlashableAttestation: SimpleSerialize + Clone {
+    type Slashing;
+
+    fn attesting_indices(&self) -> &[ValidatorIndex];
+
+    fn data(&self) -> &AttestationData;
+
+    fn into_slashing(attestation_1: Self, attestation_2: Self) -> Self::Slashing;
+}
+
+impl<const MAX_VALIDATORS_PER_COMMITTEE: usize> SlashableAttestation
+    for phase0::IndexedAttestation<MAX_VALIDATORS_PER_COMMITTEE>
+{
+    type Slashing = phase0::AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>;
+
+    fn attesting_indices(&self) -> &[ValidatorIndex] {
+        &self.attesting_indices
+    }
+
+    fn data(&self) -> &AttestationData {
+        &self.data
+    }
+
+    fn into_slashing(attestation_1: Self, attestation_2: Self) -> Self::Slashing {
+        phase0::AttesterSlashing { attestation_1, attestation_2 }
+    }
+}
+
+impl<const MAX_VALIDATORS_PER_SLOT: usize> SlashableAttestation
+    for electra::IndexedAttestation<MAX_VALIDATORS_PER_SLOT>
+{
+    type Slashing = electra::AttesterSlashing<MAX_VALIDATORS_PER_SLOT>;
+
+    fn attesting_indices(&self) -> &[ValidatorIndex] {
+        &self.attesting_indices
+    }
+
+    fn data(&self) -> &AttestationData {
+        &self.data
+    }
+
+    fn into_slashing(attestation_1: Self, attestation_2: Self) -> Self::Slashing {
+        electra::AttesterSlashing { attestation_1, attestation_2 }
+    }
+}
+
+/// An ordered key-value store persisting the records of a [`MinimalSlasher`], e.g. an embedded
+/// database. A `BTreeMap` serves as a store in memory.
+pub trait KeyValueStore {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>>;
+
+    fn put(&mut self, key: Vec<u8>, value: Vec<u8>);
+
+    /// Delete the keys from `start` (inclusive) to `end` (exclusive).
+    fn delete_range(&mut self, start: &[u8], end: &[u8]);
+}
+
+impl KeyValueStore for BTreeMap<Vec<u8>, Vec<u8>> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        BTreeMap::get(self, key).cloned()
+    }
+
+    fn put(&mut self, key: Vec<u8>, value: Vec<u8>) {
+        self.insert(key, value);
+    }
+
+    fn delete_range(&mut self, start: &[u8], end: &[u8]) {
+        let keys = self
+            .range::<[u8], _>((Bound::Included(start), Bound::Excluded(end)))
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        for key in keys {
+            self.remove(&key);
+        }
+    }
+}
+
+// Votes are keyed by target epoch and validator, and attestations by target epoch and root, so
+// that both can be pruned by target epoch.
+const VOTE_PREFIX: u8 = 0;
+const ATTESTATION_PREFIX: u8 = 1;
+const SPANS_PREFIX: u8 = 2;
+const CURRENT_EPOCH_KEY: [u8; 1] = [3];
+
+fn epoch_key(prefix: u8, epoch: Epoch, suffix: &[u8]) -> Vec<u8> {
+    let mut key = vec![prefix];
+    key.extend_from_slice(&epoch.to_be_bytes());
+    key.extend_from_slice(suffix);
+    key
+}
+
+fn vote_key(target_epoch: Epoch, validator_index: ValidatorIndex) -> Vec<u8> {
+    epoch_key(VOTE_PREFIX, target_epoch, &(validator_index as u64).to_be_bytes())
+}
+
+fn attestation_key(target_epoch: Epoch, attestation_root: &Root) -> Vec<u8> {
+    epoch_key(ATTESTATION_PREFIX, target_epoch, attestation_root.as_ref())
+}
+
+fn spans_key(validator_index: ValidatorIndex) -> Vec<u8> {
+    let mut key = vec![SPANS_PREFIX];
+    key.extend_from_slice(&(validator_index as u64).to_be_bytes());
+    key
+}
+
+// A vote recorded for a validator: the roots of the attestation data and of the attestation.
+struct Vote {
+    data_root: Root,
+    attestation_root: Root,
+}
+
+impl Vote {
+    fn encode(&self) -> Vec<u8> {
+        [self.data_root.as_ref(), self.attestation_root.as_ref()].concat()
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != 64 {
+            return None
+        }
+        let data_root = Root::try_from(&bytes[..32]).ok()?;
+        let attestation_root = Root::try_from(&bytes[32..]).ok()?;
+        Some(Self { data_root, attestation_root })
+    }
+}
+
+// `min_targets` without a vote with a later source.
+const NO_TARGET: Epoch = Epoch::MAX;
+
+// The min-max spans of a validator over the epochs of the history, as circular buffers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Spans {
+    // the latest epoch covered
+    epoch: Epoch,
+    // the highest target of the votes recorded, which bounds `max_targets` of later epochs
+    max_target: Epoch,
+    min_targets: Vec<Epoch>,
+    max_targets: Vec<Epoch>,
+}
+
+impl Spans {
+    fn new(history_length: usize) -> Self {
+        Self {
+            epoch: 0,
+            max_target: 0,
+            min_targets: vec![NO_TARGET; history_length],
+            max_targets: vec![0; history_length],
+        }
+    }
+
+    fn index(&self, epoch: Epoch) -> usize {
+        (epoch % self.min_targets.len() as u64) as usize
+    }
+
+    // Cover the epochs up to `epoch`. None of the votes recorded has a source after the epochs
+    // covered so far.
+    fn advance_to(&mut self, epoch: Epoch) {
+        let history_length = self.min_targets.len() as u64;
+        let start = (self.epoch + 1).max((epoch + 1).saturating_sub(history_length));
+        for epoch in start..=epoch {
+            let index = self.index(epoch);
+            self.min_targets[index] = NO_TARGET;
+            self.max_targets[index] = self.max_target;
+        }
+        self.epoch = self.epoch.max(epoch);
+    }
+
+    fn record(&mut self, source_epoch: Epoch, target_epoch: Epoch, oldest_epoch: Epoch) {
+        let mut epoch = source_epoch;
+        while epoch > oldest_epoch {
+            epoch -= 1;
+            let index = self.index(epoch);
+            if self.min_targets[index] <= target_epoch {
+                break
+            }
+            self.min_targets[index] = target_epoch;
+        }
+        for epoch in source_epoch + 1..=self.epoch {
+            let index = self.index(epoch);
+            if self.max_targets[index] >= target_epoch {
+                break
+            }
+            self.max_targets[index] = target_epoch;
+        }
+        self.max_target = self.max_target.max(target_epoch);
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        [self.epoch, self.max_target]
+            .iter()
+            .chain(&self.min_targets)
+            .chain(&self.max_targets)
+            .flat_map(|epoch| epoch.to_le_bytes())
+            .collect()
+    }
+
+    fn decode(bytes: &[u8], history_length: usize) -> Option<Self> {
+        if bytes.len() != 8 * (2 + 2 * history_length) {
+            return None
+        }
+        let mut epochs = bytes
+            .chunks_exact(8)
+            .map(|chunk| Epoch::from_le_bytes(chunk.try_into().expect("correct length")));
+        let epoch = epochs.next()?;
+        let max_target = epochs.next()?;
+        let min_targets = epochs.by_ref().take(history_length).collect();
+        let max_targets = epochs.collect();
+        Some(Self { epoch, max_target, min_targets, max_targets })
+    }
+}
+
+enum Check {
+    New,
+    // the same vote as one recorded before
+    Duplicate,
+    Conflict(Conflict),
+}
+
+// An earlier attestation conflicting with a new one, and whether the new one comes first in the
+// slashing, i.e. surrounds the earlier one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Conflict {
+    target_epoch: Epoch,
+    attestation_root: Root,
+    surrounds: bool,
+}
+
+/// Detects slashable attestations among the attestations of any fork (see
+/// [`SlashableAttestation`]) it is given, over the last `history_length` epochs.
+///
+/// The first vote of a validator for each target is recorded, and a later vote that conflicts
+/// with a recorded one is reported rather than recorded. The spans are kept in memory for every
+/// validator seen, so the store must be reopened with the same history length.
+pub struct MinimalSlasher<A, S> {
+    store: S,
+    history_length: u64,
+    current_epoch: Epoch,
+    spans: HashMap<ValidatorIndex, Spans>,
+    dirty: HashSet<ValidatorIndex>,
+    _attestation: PhantomData<A>,
+}
+
+impl<A: SlashableAttestation, S: KeyValueStore> MinimalSlasher<A, S> {
+    /// A slasher over the last `history_length` epochs, resuming from the records in `store`.
+    pub fn new(store: S, history_length: u64) -> Self {
+        let current_epoch = store
+            .get(&CURRENT_EPOCH_KEY)
+            .and_then(|bytes| Some(Epoch::from_le_bytes(bytes.try_into().ok()?)))
+            .unwrap_or_default();
+        Self {
+            store,
+            history_length: history_length.max(1),
+            current_epoch,
+            spans: HashMap::new(),
+            dirty: HashSet::new(),
+            _attestation: PhantomData,
+        }
+    }
+
+    /// The latest target epoch seen.
+    pub fn current_epoch(&self) -> Epoch {
+        self.current_epoch
+    }
+
+    /// The oldest source epoch of the attestations the slasher accepts.
+    pub fn oldest_epoch(&self) -> Epoch {
+        (self.current_epoch + 1).saturating_sub(self.history_length)
+    }
+
+    /// Record the votes of `attestation`, returning a slashing against each recorded attestation
+    /// it conflicts with, ready to broadcast.
+    ///
+    /// Each slashing holds the two conflicting attestations, so it slashes every validator that
+    /// attested to both.
+    pub fn process_attestation(&mut self, attestation: &A) -> Result<Vec<A::Slashing>, Error> {
+        let data = attestation.data();
+        let source_epoch = data.source.epoch;
+        let target_epoch = data.target.epoch;
+        if source_epoch > target_epoch {
+            return Err(SlasherError::InvalidEpochs { source_epoch, target_epoch }.into())
+        }
+        if target_epoch > self.current_epoch {
+            self.advance_to(target_epoch);
+        }
+        let oldest_epoch = self.oldest_epoch();
+        if source_epoch < oldest_epoch {
+            return Err(SlasherError::SourceTooOld { source_epoch, oldest_epoch }.into())
+        }
+
+        let vote = Vote {
+            data_root: data.hash_tree_root()?,
+            attestation_root: attestation.hash_tree_root()?,
+        };
+        let mut conflicts = Vec::new();
+        let mut is_recorded = false;
+        for &validator_index in attestation.attesting_indices() {
+            match self.check(validator_index, source_epoch, target_epoch, &vote.data_root)? {
+                Check::New => {
+                    self.store.put(vote_key(target_epoch, validator_index), vote.encode());
+                    self.spans_mut(validator_index)?.record(
+                        source_epoch,
+                        target_epoch,
+                        oldest_epoch,
+                    );
+                    self.dirty.insert(validator_index);
+                    is_recorded = true;
+                }
+                Check::Duplicate => {}
+                Check::Conflict(conflict) => {
+                    // the validators of an aggregate usually conflict with the same attestation
+                    if !conflicts.contains(&conflict) {
+                        conflicts.push(conflict);
+                    }
+                }
+            }
+        }
+        if is_recorded {
+            let key = attestation_key(target_epoch, &vote.attestation_root);
+            self.store.put(key, serialize(attestation).map_err(SlasherError::from)?);
+        }
+
+        let mut slashings = Vec::with_capacity(conflicts.len());
+        for conflict in conflicts {
+            let key = attestation_key(conflict.target_epoch, &conflict.attestation_root);
+            let Some(bytes) = self.store.get(&key) else { continue };
+            let earlier = A::deserialize(&bytes).map_err(|_| SlasherError::CorruptRecord(key))?;
+            let slashing = if conflict.surrounds {
+                A::into_slashing(attestation.clone(), earlier)
+            } else {
+                A::into_slashing(earlier, attestation.clone())
+            };
+            slashings.push(slashing);
+        }
+        Ok(slashings)
+    }
+
+    /// Write the spans changed since the last flush to the store.
+    pub fn flush(&mut self) {
+        for validator_index in self.dirty.drain() {
+            let spans = &self.spans[&validator_index];
+            self.store.put(spans_key(validator_index), spans.encode());
+        }
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Flush the slasher and hand back its store.
+    pub fn into_store(mut self) -> S {
+        self.flush();
+        self.store
+    }
+
+    // Move the history to end at `epoch`, dropping the records of the epochs left behind: an
+    // attestation with a target before the oldest source accepted conflicts with none accepted.
+    fn advance_to(&mut self, epoch: Epoch) {
+        self.current_epoch = epoch;
+        self.store.put(CURRENT_EPOCH_KEY.to_vec(), epoch.to_le_bytes().to_vec());
+        let oldest_epoch = self.oldest_epoch();
+        for prefix in [VOTE_PREFIX, ATTESTATION_PREFIX] {
+            self.store
+                .delete_range(&epoch_key(prefix, 0, &[]), &epoch_key(prefix, oldest_epoch, &[]));
+        }
+    }
+
+    fn spans_mut(&mut self, validator_index: ValidatorIndex) -> Result<&mut Spans, Error> {
+        let history_length = self.history_length as usize;
+        let spans = match self.spans.entry(validator_index) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let key = spans_key(validator_index);
+                let spans = match self.store.get(&key) {
+                    Some(bytes) => Spans::decode(&bytes, history_length)
+                        .ok_or(SlasherError::CorruptRecord(key))?,
+                    None => Spans::new(history_length),
+                };
+                entry.insert(spans)
+            }
+        };
+        spans.advance_to(self.current_epoch);
+        Ok(spans)
+    }
+
+    fn vote(
+        &self,
+        target_epoch: Epoch,
+        validator_index: ValidatorIndex,
+    ) -> Result<Option<Vote>, Error> {
+        let key = vote_key(target_epoch, validator_index);
+        match self.store.get(&key) {
+            Some(bytes) => Ok(Some(Vote::decode(&bytes).ok_or(SlasherError::CorruptRecord(key))?)),
+            None => Ok(None),
+        }
+    }
+
+    fn check(
+        &mut self,
+        validator_index: ValidatorIndex,
+        source_epoch: Epoch,
+        target_epoch: Epoch,
+        data_root: &Root,
+    ) -> Result<Check, Error> {
+        if let Some(vote) = self.vote(target_epoch, validator_index)? {
+            if vote.data_root == *data_root {
+                return Ok(Check::Duplicate)
+            }
+            return Ok(Check::Conflict(Conflict {
+                target_epoch,
+                attestation_root: vote.attestation_root,
+                surrounds: false,
+            }))
+        }
+
+        let spans = self.spans_mut(validator_index)?;
+        let index = spans.index(source_epoch);
+        let (min_target, max_target) = (spans.min_targets[index], spans.max_targets[index]);
+        // each target has a single vote recorded, so the target of the span finds the vote
+        if min_target != NO_TARGET && min_target < target_epoch {
+            if let Some(vote) = self.vote(min_target, validator_index)? {
+                return Ok(Check::Conflict(Conflict {
+                    target_epoch: min_target,
+                    attestation_root: vote.attestation_root,
+                    surrounds: true,
+                }))
+            }
+        }
+        if max_target > target_epoch {
+            if let Some(vote) = self.vote(max_target, validator_index)? {
+                return Ok(Check::Conflict(Conflict {
+                    target_epoch: max_target,
+                    attestation_root: vote.attestation_root,
+                    surrounds: false,
+                }))
+            }
+        }
+        Ok(Check::New)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::phase0::{is_slashable_attestation_data, minimal::IndexedAttestation, Checkpoint};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    type Slasher = MinimalSlasher<IndexedAttestation, BTreeMap<Vec<u8>, Vec<u8>>>;
+
+    fn attestation(
+        indices: &[ValidatorIndex],
+        source_epoch: Epoch,
+        target_epoch: Epoch,
+        block: u8,
+    ) -> IndexedAttestation {
+        let mut attestation = IndexedAttestation {
+            attesting_indices: List::try_from(indices.to_vec()).unwrap(),
+            ..Default::default()
+        };
+        attestation.data.beacon_block_root = Root::try_from([block; 32].as_ref()).unwrap();
+        attestation.data.source = Checkpoint { epoch: source_epoch, ..Default::default() };
+        attestation.data.target = Checkpoint { epoch: target_epoch, ..Default::default() };
+        attestation
+    }
+
+    fn slasher(history_length: u64) -> Slasher {
+        MinimalSlasher::new(BTreeMap::new(), history_length)
+    }
+
+    // Process `first` then `second`, returning the slashings found for `second`.
+    fn detect(
+        first: &IndexedAttestation,
+        second: &IndexedAttestation,
+    ) -> Vec<phase0::minimal::AttesterSlashing> {
+        let mut slasher = slasher(16);
+        assert!(slasher.process_attestation(first).unwrap().is_empty());
+        slasher.process_attestation(second).unwrap()
+    }
+
+    #[test]
+    fn test_detects_conflicting_votes() {
+        let surrounded = attestation(&[1], 1, 2, 0);
+        let surrounding = attestation(&[1], 0, 3, 0);
+        for (first, second) in [(&surrounded, &surrounding), (&surrounding, &surrounded)] {
+            let slashings = detect(first, second);
+            assert_eq!(slashings.len(), 1);
+            // the surrounding vote comes first in the slashing
+            assert_eq!(slashings[0].attestation_1, surrounding);
+            assert_eq!(slashings[0].attestation_2, surrounded);
+        }
+
+        let first = attestation(&[1], 0, 2, 0);
+        let double = attestation(&[1], 1, 2, 1);
+        let slashings = detect(&first, &double);
+        assert_eq!(slashings.len(), 1);
+        assert_eq!(slashings[0].attestation_1, first);
+        assert_eq!(slashings[0].attestation_2, double);
+        assert!(is_slashable_attestation_data(&first.data, &double.data));
+
+        // the same vote again, or an aggregate including it
+        assert!(detect(&first, &first).is_empty());
+        assert!(detect(&first, &attestation(&[1, 2], 0, 2, 0)).is_empty());
+        // votes sharing a source, adjacent votes, and overlapping ones
+        assert!(detect(&first, &attestation(&[1], 0, 3, 0)).is_empty());
+        assert!(detect(&first, &attestation(&[1], 2, 3, 0)).is_empty());
+        assert!(detect(&attestation(&[1], 1, 3, 0), &attestation(&[1], 2, 4, 0)).is_empty());
+        assert!(detect(&attestation(&[1], 2, 4, 0), &attestation(&[1], 1, 3, 0)).is_empty());
+        // another validator
+        assert!(detect(&first, &attestation(&[2], 0, 2, 1)).is_empty());
+
+        // a vote with equal source and target can be surrounded
+        let slashings = detect(&attestation(&[1], 2, 2, 0), &attestation(&[1], 1, 3, 0));
+        assert_eq!(slashings.len(), 1);
+        assert!(is_slashable_attestation_data(
+            &slashings[0].attestation_1.data,
+            &slashings[0].attestation_2.data
+        ));
+    }
+
+    #[test]
+    fn test_slashes_aggregates_once() {
+        let mut slasher = slasher(16);
+        let first = attestation(&[1, 2, 3], 1, 2, 0);
+        assert!(slasher.process_attestation(&first).unwrap().is_empty());
+        let double = attestation(&[2, 3, 4], 1, 2, 1);
+        let slashings = slasher.process_attestation(&double).unwrap();
+        assert_eq!(slashings.len(), 1);
+        assert_eq!(slashings[0].attestation_1, first);
+        assert_eq!(slashings[0].attestation_2, double);
+
+        // validator 4 voted for the first time, and is caught surrounding its vote
+        let surrounding = attestation(&[4], 0, 3, 0);
+        let slashings = slasher.process_attestation(&surrounding).unwrap();
+        assert_eq!(slashings.len(), 1);
+        assert_eq!(slashings[0].attestation_2, double);
+    }
+
+    #[test]
+    fn test_history_window() {
+        let mut slasher = slasher(4);
+        assert!(slasher.process_attestation(&attestation(&[1], 0, 1, 0)).unwrap().is_empty());
+        assert_eq!(slasher.process_attestation(&attestation(&[1], 0, 1, 1)).unwrap().len(), 1);
+        assert!(matches!(
+            slasher.process_attestation(&attestation(&[1], 3, 2, 0)),
+            Err(Error::Slasher(SlasherError::InvalidEpochs { source_epoch: 3, target_epoch: 2 }))
+        ));
+
+        // moving to epoch 10 leaves epochs 7 to 10 in the window, and drops the older records
+        slasher.process_attestation(&attestation(&[1, 2], 8, 9, 0)).unwrap();
+        slasher.process_attestation(&attestation(&[1], 9, 10, 0)).unwrap();
+        assert_eq!(slasher.current_epoch(), 10);
+        assert_eq!(slasher.oldest_epoch(), 7);
+        assert!(matches!(
+            slasher.process_attestation(&attestation(&[1], 6, 10, 1)),
+            Err(Error::Slasher(SlasherError::SourceTooOld { source_epoch: 6, oldest_epoch: 7 }))
+        ));
+        let targets = slasher
+            .store()
+            .keys()
+            .filter(|key| key[0] == VOTE_PREFIX || key[0] == ATTESTATION_PREFIX)
+            .map(|key| Epoch::from_be_bytes(key[1..9].try_into().unwrap()))
+            .collect::<Vec<_>>();
+        assert!(!targets.is_empty());
+        assert!(targets.iter().all(|&target| target >= 7));
+
+        // a double vote of validator 1 and a surround vote of validator 2 in one aggregate
+        let aggregate = attestation(&[1, 2], 7, 10, 1);
+        let slashings = slasher.process_attestation(&aggregate).unwrap();
+        assert_eq!(slashings.len(), 2);
+        assert!(slashings.iter().any(|slashing| slashing.attestation_1 == aggregate));
+        assert!(slashings.iter().any(|slashing| slashing.attestation_2 == aggregate));
+    }
+
+    #[test]
+    fn test_resumes_from_store() {
+        let mut slasher = slasher(8);
+        let first = attestation(&[1, 2], 2, 5, 0);
+        slasher.process_attestation(&first).unwrap();
+        let store = slasher.into_store();
+
+        let mut slasher = Slasher::new(store, 8);
+        assert_eq!(slasher.current_epoch(), 5);
+        let slashings = slasher.process_attestation(&attestation(&[2], 3, 4, 0)).unwrap();
+        assert_eq!(slashings.len(), 1);
+        assert_eq!(slashings[0].attestation_1, first);
+
+        let mut store = slasher.into_store();
+        store.insert(spans_key(1), vec![0; 3]);
+        let mut slasher = Slasher::new(store, 8);
+        assert!(matches!(
+            slasher.process_attestation(&attestation(&[1], 1, 6, 0)),
+            Err(Error::Slasher(SlasherError::CorruptRecord(_)))
+        ));
+    }
+
+    // Whether `attestation` is slashable together with one of `recorded`, checked pairwise.
+    fn is_slashable(recorded: &[IndexedAttestation], attestation: &IndexedAttestation) -> bool {
+        recorded.iter().any(|other| {
+            other
+                .attesting_indices
+                .iter()
+                .any(|index| attestation.attesting_indices.contains(index)) &&
+                (is_slashable_attestation_data(&other.data, &attestation.data) ||
+                    is_slashable_attestation_data(&attestation.data, &other.data))
+        })
+    }
+
+    #[test]
+    fn test_matches_naive_detection() {
+        let mut rng = StdRng::seed_from_u64(273);
+        for _ in 0..50 {
+            let history_length = rng.gen_range(4..12);
+            let mut slasher = slasher(history_length);
+            // the votes recorded by the slasher, one validator each
+            let mut recorded = Vec::new();
+            let mut current_epoch = 0;
+            for _ in 0..60 {
+                let validator_index = rng.gen_range(0..4);
+                let target_epoch = (current_epoch + rng.gen_range(0..3)).saturating_sub(1);
+                current_epoch = current_epoch.max(target_epoch);
+                let oldest_epoch = (current_epoch + 1).saturating_sub(history_length);
+                if target_epoch < oldest_epoch {
+                    continue
+                }
+                let source_epoch = rng.gen_range(oldest_epoch..=target_epoch);
+                let block = rng.gen_range(0..2);
+                let vote = attestation(&[validator_index], source_epoch, target_epoch, block);
+
+                let slashings = slasher.process_attestation(&vote).unwrap();
+                let is_duplicate = recorded.contains(&vote);
+                assert_eq!(!slashings.is_empty(), is_slashable(&recorded, &vote));
+                for slashing in &slashings {
+                    assert!(is_slashable_attestation_data(
+                        &slashing.attestation_1.data,
+                        &slashing.attestation_2.data
+                    ));
+                    assert!(slashing.attestation_1 == vote || slashing.attestation_2 == vote);
+                }
+                if slashings.is_empty() && !is_duplicate {
+                    recorded.push(vote);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Detecting proposers that sign two different blocks for the same slot, from the block headers
+//! seen on the network.
+use crate::{
+    phase0::{ProposerSlashing, SignedBeaconBlockHeader},
+    primitives::{Slot, ValidatorIndex},
+};
+use std::collections::BTreeMap;
+
+/// What a [`ProposalTracker`] made of a header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Observation {
+    /// The first header seen from the proposer for the slot.
+    New,
+    /// The same block as the header seen before from the proposer for the slot.
+    Duplicate,
+    /// A different block from the header seen before from the proposer for the slot, which is
+    /// carried here.
+    Equivocation(SignedBeaconBlockHeader),
+}
+
+/// The first header seen from each proposer at each slot, kept until the slot is finalized.
+///
+/// Serializes as the list of the headers it holds, so it can be persisted and restored across
+/// restarts.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(from = "Vec<SignedBeaconBlockHeader>", into = "Vec<SignedBeaconBlockHeader>")]
+pub struct ProposalTracker {
+    headers: BTreeMap<(Slot, ValidatorIndex), SignedBeaconBlockHeader>,
+}
+
+impl ProposalTracker {
+    /// Record `header` if it is the first from its proposer for its slot. An equivocation leaves
+    /// the header seen before in place, so that every later conflicting header is reported
+    /// against the same one.
+    pub fn observe(&mut self, header: &SignedBeaconBlockHeader) -> Observation {
+        let key = (header.message.slot, header.message.proposer_index);
+        match self.headers.get(&key) {
+            None => {
+                self.headers.insert(key, header.clone());
+                Observation::New
+            }
+            Some(seen) if seen.message == header.message => Observation::Duplicate,
+            Some(seen) => Observation::Equivocation(seen.clone()),
+        }
+    }
+
+    /// Like [`Self::observe`], returning a slashing of the proposer of `header` if it equivocates.
+    pub fn observe_for_slashing(
+        &mut self,
+        header: &SignedBeaconBlockHeader,
+    ) -> Option<ProposerSlashing> {
+        match self.observe(header) {
+            Observation::Equivocation(seen) => {
+                Some(ProposerSlashing { signed_header_1: seen, signed_header_2: header.clone() })
+            }
+            _ => None,
+        }
+    }
+
+    /// Drop the headers of slots before `finalized_slot`; blocks at those slots can no longer
+    /// make it on chain.
+    pub fn prune(&mut self, finalized_slot: Slot) {
+        self.headers = self.headers.split_off(&(finalized_slot, 0));
+    }
+
+    pub fn len(&self) -> usize {
+        self.headers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty()
+    }
+}
+
+impl From<Vec<SignedBeaconBlockHeader>> for ProposalTracker {
+    fn from(headers: Vec<SignedBeaconBlockHeader>) -> Self {
+        let mut tracker = Self::default();
+        for header in &headers {
+            tracker.observe(header);
+        }
+        tracker
+    }
+}
+
+impl From<ProposalTracker> for Vec<SignedBeaconBlockHeader> {
+    fn from(tracker: ProposalTracker) -> Self {
+        tracker.headers.into_values().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::{phase0::genesis_state, secret_keys},
+        phase0::{compute_epoch_at_slot, get_domain, process_proposer_slashing, BeaconBlockHeader},
+        primitives::{DomainType, Root},
+        signing::sign_with_domain,
+        state_transition::Context,
+    };
+
+    #[test]
+    fn test_proposal_tracker() {
+        let context = Context::for_minimal();
+        let mut state = genesis_state();
+        let secret_keys = secret_keys();
+        let proposer_index = 3;
+        let sign = |slot: Slot, body_root: u8| {
+            let message = BeaconBlockHeader {
+                slot,
+                proposer_index,
+                body_root: Root::try_from(&[body_root; 32][..]).unwrap(),
+                ..Default::default()
+            };
+            let epoch = compute_epoch_at_slot(slot, &context);
+            let domain =
+                get_domain(&state, DomainType::BeaconProposer, Some(epoch), &context).unwrap();
+            let signature =
+                sign_with_domain(&message, &secret_keys[proposer_index], domain).unwrap();
+            SignedBeaconBlockHeader { message, signature }
+        };
+        let first = sign(1, 1);
+        let conflicting = sign(1, 2);
+        let later = sign(9, 1);
+
+        let mut tracker = ProposalTracker::default();
+        assert_eq!(tracker.observe(&first), Observation::New);
+        assert_eq!(tracker.observe(&first), Observation::Duplicate);
+        assert_eq!(tracker.observe(&later), Observation::New);
+        assert_eq!(tracker.observe(&conflicting), Observation::Equivocation(first.clone()));
+
+        // the tracker survives a round trip through its serialized form
+        let serialized = serde_json::to_string(&tracker).unwrap();
+        let mut tracker: ProposalTracker = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(tracker.len(), 2);
+
+        let slashing = tracker.observe_for_slashing(&conflicting).unwrap();
+        assert_eq!(slashing.signed_header_1, first);
+        assert_eq!(slashing.signed_header_2, conflicting);
+        process_proposer_slashing(&mut state, &slashing, &context).unwrap();
+        assert!(state.validators[proposer_index].slashed);
+
+        tracker.prune(context.slots_per_epoch);
+        assert_eq!(tracker.len(), 1);
+        assert_eq!(tracker.observe(&later), Observation::Duplicate);
+        assert_eq!(tracker.observe(&conflicting), Observation::New);
+    }
+}
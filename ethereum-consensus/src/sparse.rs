@@ -0,0 +1,753 @@
+//! Read individual top-level fields of a `BeaconState` from its SSZ encoding
+//! without decoding the entire state.
+use crate::{
+    ssz::prelude::*,
+    state_transition::{Context, Result},
+    Error, Fork,
+};
+use std::fmt;
+
+const BYTES_PER_LENGTH_OFFSET: usize = 4;
+
+// Fixed sizes of the (preset-independent) containers found in the `BeaconState`.
+const FORK_SIZE: usize = 16;
+const BEACON_BLOCK_HEADER_SIZE: usize = 112;
+const ETH1_DATA_SIZE: usize = 72;
+const CHECKPOINT_SIZE: usize = 40;
+const JUSTIFICATION_BITS_SIZE: usize = 1;
+const BLS_PUBLIC_KEY_SIZE: usize = 48;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StateField {
+    GenesisTime,
+    GenesisValidatorsRoot,
+    Slot,
+    Fork,
+    LatestBlockHeader,
+    BlockRoots,
+    StateRoots,
+    HistoricalRoots,
+    Eth1Data,
+    Eth1DataVotes,
+    Eth1DepositIndex,
+    Validators,
+    Balances,
+    RandaoMixes,
+    Slashings,
+    PreviousEpochAttestations,
+    CurrentEpochAttestations,
+    PreviousEpochParticipation,
+    CurrentEpochParticipation,
+    JustificationBits,
+    PreviousJustifiedCheckpoint,
+    CurrentJustifiedCheckpoint,
+    FinalizedCheckpoint,
+    InactivityScores,
+    CurrentSyncCommittee,
+    NextSyncCommittee,
+    LatestExecutionPayloadHeader,
+    NextWithdrawalIndex,
+    NextWithdrawalValidatorIndex,
+    HistoricalSummaries,
+    DepositRequestsStartIndex,
+    DepositBalanceToConsume,
+    ExitBalanceToConsume,
+    EarliestExitEpoch,
+    ConsolidationBalanceToConsume,
+    EarliestConsolidationEpoch,
+    PendingDeposits,
+    PendingPartialWithdrawals,
+    PendingConsolidations,
+}
+
+impl fmt::Display for StateField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+const PHASE0_FIELDS: &[StateField] = &[
+    StateField::GenesisTime,
+    StateField::GenesisValidatorsRoot,
+    StateField::Slot,
+    StateField::Fork,
+    StateField::LatestBlockHeader,
+    StateField::BlockRoots,
+    StateField::StateRoots,
+    StateField::HistoricalRoots,
+    StateField::Eth1Data,
+    StateField::Eth1DataVotes,
+    StateField::Eth1DepositIndex,
+    StateField::Validators,
+    StateField::Balances,
+    StateField::RandaoMixes,
+    StateField::Slashings,
+    StateField::PreviousEpochAttestations,
+    StateField::CurrentEpochAttestations,
+    StateField::JustificationBits,
+    StateField::PreviousJustifiedCheckpoint,
+    StateField::CurrentJustifiedCheckpoint,
+    StateField::FinalizedCheckpoint,
+];
+
+const ALTAIR_FIELDS: &[StateField] = &[
+    StateField::GenesisTime,
+    StateField::GenesisValidatorsRoot,
+    StateField::Slot,
+    StateField::Fork,
+    StateField::LatestBlockHeader,
+    StateField::BlockRoots,
+    StateField::StateRoots,
+    StateField::HistoricalRoots,
+    StateField::Eth1Data,
+    StateField::Eth1DataVotes,
+    StateField::Eth1DepositIndex,
+    StateField::Validators,
+    StateField::Balances,
+    StateField::RandaoMixes,
+    StateField::Slashings,
+    StateField::PreviousEpochParticipation,
+    StateField::CurrentEpochParticipation,
+    StateField::JustificationBits,
+    StateField::PreviousJustifiedCheckpoint,
+    StateField::CurrentJustifiedCheckpoint,
+    StateField::FinalizedCheckpoint,
+    StateField::InactivityScores,
+    StateField::CurrentSyncCommittee,
+    StateField::NextSyncCommittee,
+];
+
+const BELLATRIX_FIELDS: &[StateField] = &[
+    StateField::GenesisTime,
+    StateField::GenesisValidatorsRoot,
+    StateField::Slot,
+    StateField::Fork,
+    StateField::LatestBlockHeader,
+    StateField::BlockRoots,
+    StateField::StateRoots,
+    StateField::HistoricalRoots,
+    StateField::Eth1Data,
+    StateField::Eth1DataVotes,
+    StateField::Eth1DepositIndex,
+    StateField::Validators,
+    StateField::Balances,
+    StateField::RandaoMixes,
+    StateField::Slashings,
+    StateField::PreviousEpochParticipation,
+    StateField::CurrentEpochParticipation,
+    StateField::JustificationBits,
+    StateField::PreviousJustifiedCheckpoint,
+    StateField::CurrentJustifiedCheckpoint,
+    StateField::FinalizedCheckpoint,
+    StateField::InactivityScores,
+    StateField::CurrentSyncCommittee,
+    StateField::NextSyncCommittee,
+    StateField::LatestExecutionPayloadHeader,
+];
+
+// NOTE: `deneb` did not add any top-level fields to the state.
+const CAPELLA_FIELDS: &[StateField] = &[
+    StateField::GenesisTime,
+    StateField::GenesisValidatorsRoot,
+    StateField::Slot,
+    StateField::Fork,
+    StateField::LatestBlockHeader,
+    StateField::BlockRoots,
+    StateField::StateRoots,
+    StateField::HistoricalRoots,
+    StateField::Eth1Data,
+    StateField::Eth1DataVotes,
+    StateField::Eth1DepositIndex,
+    StateField::Validators,
+    StateField::Balances,
+    StateField::RandaoMixes,
+    StateField::Slashings,
+    StateField::PreviousEpochParticipation,
+    StateField::CurrentEpochParticipation,
+    StateField::JustificationBits,
+    StateField::PreviousJustifiedCheckpoint,
+    StateField::CurrentJustifiedCheckpoint,
+    StateField::FinalizedCheckpoint,
+    StateField::InactivityScores,
+    StateField::CurrentSyncCommittee,
+    StateField::NextSyncCommittee,
+    StateField::LatestExecutionPayloadHeader,
+    StateField::NextWithdrawalIndex,
+    StateField::NextWithdrawalValidatorIndex,
+    StateField::HistoricalSummaries,
+];
+
+const ELECTRA_FIELDS: &[StateField] = &[
+    StateField::GenesisTime,
+    StateField::GenesisValidatorsRoot,
+    StateField::Slot,
+    StateField::Fork,
+    StateField::LatestBlockHeader,
+    StateField::BlockRoots,
+    StateField::StateRoots,
+    StateField::HistoricalRoots,
+    StateField::Eth1Data,
+    StateField::Eth1DataVotes,
+    StateField::Eth1DepositIndex,
+    StateField::Validators,
+    StateField::Balances,
+    StateField::RandaoMixes,
+    StateField::Slashings,
+    StateField::PreviousEpochParticipation,
+    StateField::CurrentEpochParticipation,
+    StateField::JustificationBits,
+    StateField::PreviousJustifiedCheckpoint,
+    StateField::CurrentJustifiedCheckpoint,
+    StateField::FinalizedCheckpoint,
+    StateField::InactivityScores,
+    StateField::CurrentSyncCommittee,
+    StateField::NextSyncCommittee,
+    StateField::LatestExecutionPayloadHeader,
+    StateField::NextWithdrawalIndex,
+    StateField::NextWithdrawalValidatorIndex,
+    StateField::HistoricalSummaries,
+    StateField::DepositRequestsStartIndex,
+    StateField::DepositBalanceToConsume,
+    StateField::ExitBalanceToConsume,
+    StateField::EarliestExitEpoch,
+    StateField::ConsolidationBalanceToConsume,
+    StateField::EarliestConsolidationEpoch,
+    StateField::PendingDeposits,
+    StateField::PendingPartialWithdrawals,
+    StateField::PendingConsolidations,
+];
+
+/// Returns the top-level fields of the `BeaconState` for `fork`, in SSZ order.
+pub fn state_fields(fork: Fork) -> &'static [StateField] {
+    match fork {
+        Fork::Phase0 => PHASE0_FIELDS,
+        Fork::Altair => ALTAIR_FIELDS,
+        Fork::Bellatrix => BELLATRIX_FIELDS,
+        Fork::Capella | Fork::Deneb => CAPELLA_FIELDS,
+        Fork::Electra => ELECTRA_FIELDS,
+    }
+}
+
+// Returns the size of the field's encoding in the fixed part of the state,
+// or `None` if the field is variable-size.
+fn fixed_size(field: StateField, context: &Context) -> Option<usize> {
+    let size = match field {
+        StateField::GenesisTime |
+        StateField::Slot |
+        StateField::Eth1DepositIndex |
+        StateField::NextWithdrawalIndex |
+        StateField::NextWithdrawalValidatorIndex |
+        StateField::DepositRequestsStartIndex |
+        StateField::DepositBalanceToConsume |
+        StateField::ExitBalanceToConsume |
+        StateField::EarliestExitEpoch |
+        StateField::ConsolidationBalanceToConsume |
+        StateField::EarliestConsolidationEpoch => 8,
+        StateField::GenesisValidatorsRoot => 32,
+        StateField::Fork => FORK_SIZE,
+        StateField::LatestBlockHeader => BEACON_BLOCK_HEADER_SIZE,
+        StateField::BlockRoots | StateField::StateRoots => {
+            32 * context.slots_per_historical_root as usize
+        }
+        StateField::Eth1Data => ETH1_DATA_SIZE,
+        StateField::RandaoMixes => 32 * context.epochs_per_historical_vector as usize,
+        StateField::Slashings => 8 * context.epochs_per_slashings_vector as usize,
+        StateField::JustificationBits => JUSTIFICATION_BITS_SIZE,
+        StateField::PreviousJustifiedCheckpoint |
+        StateField::CurrentJustifiedCheckpoint |
+        StateField::FinalizedCheckpoint => CHECKPOINT_SIZE,
+        StateField::CurrentSyncCommittee | StateField::NextSyncCommittee => {
+            BLS_PUBLIC_KEY_SIZE * (context.sync_committee_size + 1)
+        }
+        StateField::HistoricalRoots |
+        StateField::Eth1DataVotes |
+        StateField::Validators |
+        StateField::Balances |
+        StateField::PreviousEpochAttestations |
+        StateField::CurrentEpochAttestations |
+        StateField::PreviousEpochParticipation |
+        StateField::CurrentEpochParticipation |
+        StateField::InactivityScores |
+        StateField::LatestExecutionPayloadHeader |
+        StateField::HistoricalSummaries |
+        StateField::PendingDeposits |
+        StateField::PendingPartialWithdrawals |
+        StateField::PendingConsolidations => return None,
+    };
+    Some(size)
+}
+
+fn read_offset(bytes: &[u8], position: usize) -> Result<usize> {
+    let end = position + BYTES_PER_LENGTH_OFFSET;
+    let data = bytes
+        .get(position..end)
+        .ok_or(Error::OutOfBounds { requested: end, bound: bytes.len() })?;
+    let offset = u32::from_le_bytes(data.try_into().expect("correct length"));
+    Ok(offset as usize)
+}
+
+/// Returns the byte range of `field` in the SSZ encoding `bytes` of a `BeaconState` from `fork`.
+pub fn state_field_range(
+    bytes: &[u8],
+    field: StateField,
+    fork: Fork,
+    context: &Context,
+) -> Result<std::ops::Range<usize>> {
+    let fields = state_fields(fork);
+    let target =
+        fields.iter().position(|&f| f == field).ok_or(Error::MissingStateField { field, fork })?;
+
+    let mut position = 0;
+    for &f in &fields[..target] {
+        position += fixed_size(f, context).unwrap_or(BYTES_PER_LENGTH_OFFSET);
+    }
+
+    let range = if let Some(size) = fixed_size(field, context) {
+        position..position + size
+    } else {
+        let start = read_offset(bytes, position)?;
+        // the encoding of a variable-size field runs until the offset of the next variable-size
+        // field, or the end of the encoding if it is the last one
+        let mut next_position = position + BYTES_PER_LENGTH_OFFSET;
+        let mut end = bytes.len();
+        for &f in &fields[target + 1..] {
+            match fixed_size(f, context) {
+                Some(size) => next_position += size,
+                None => {
+                    end = read_offset(bytes, next_position)?;
+                    break
+                }
+            }
+        }
+        if start > end {
+            return Err(Error::OutOfBounds { requested: start, bound: end })
+        }
+        start..end
+    };
+
+    if range.end > bytes.len() {
+        return Err(Error::OutOfBounds { requested: range.end, bound: bytes.len() })
+    }
+    Ok(range)
+}
+
+/// Decodes the single top-level `field` from the SSZ encoding `bytes` of a `BeaconState` from
+/// `fork`, using the offsets in the fixed part of the encoding to skip over every other field.
+///
+/// `T` must be the type of `field` for the given `fork` and the preset of `context`.
+pub fn read_state_field<T: Deserialize>(
+    bytes: &[u8],
+    field: StateField,
+    fork: Fork,
+    context: &Context,
+) -> Result<T> {
+    let range = state_field_range(bytes, field, fork, context)?;
+    T::deserialize(&bytes[range]).map_err(|err| Error::SimpleSerialize(err.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        deneb::minimal as deneb,
+        electra::minimal as electra,
+        fixtures,
+        phase0::Validator,
+        primitives::{Gwei, Root},
+        state_transition::minimal::Executor,
+        types::minimal::{BeaconState, SignedBeaconBlock},
+    };
+
+    fn assert_field<T: Deserialize + PartialEq + fmt::Debug>(
+        bytes: &[u8],
+        field: StateField,
+        fork: Fork,
+        context: &Context,
+        expected: &T,
+    ) {
+        let value: T = read_state_field(bytes, field, fork, context).unwrap();
+        assert_eq!(&value, expected, "mismatch for field {field}");
+    }
+
+    // The state after applying the fixture `signed_block` of a fork to the fixture `state`.
+    fn fixture_post_state(
+        state: BeaconState,
+        signed_block: SignedBeaconBlock,
+        context: &Context,
+    ) -> BeaconState {
+        let mut executor = Executor::new(state, context.clone());
+        executor.apply_block(&signed_block).unwrap();
+        executor.state.inner().clone()
+    }
+
+    // Decode `bytes` as a value of the type of `value`.
+    fn decode_like<T: Deserialize>(_value: &T, bytes: &[u8]) -> T {
+        T::deserialize(bytes).unwrap()
+    }
+
+    // Read each field of `$state` from its encoding and compare it with the field of the decoded
+    // state, checking that the fields listed are every field of the state in `$fork`, in order.
+    macro_rules! assert_every_field {
+        (
+            $state:expr, $fork:expr, $context:expr,
+            { $($variant:ident => $field:ident),+ $(,)? }
+        ) => {{
+            let bytes = serialize(&$state).unwrap();
+            let decoded = decode_like(&$state, &bytes);
+            assert_eq!(decoded, $state);
+            $(
+                assert_field(&bytes, StateField::$variant, $fork, $context, &decoded.$field);
+            )+
+            assert_eq!(&[$(StateField::$variant),+][..], state_fields($fork));
+        }};
+    }
+
+    #[test]
+    fn test_read_deneb_state_fields() {
+        let context = Context::for_minimal();
+        let mut state = deneb::BeaconState { slot: 42, ..Default::default() };
+        state.validators.push(Validator { effective_balance: 32, ..Default::default() });
+        state.balances.push(33);
+        state.historical_roots.push(Root::default());
+        state.finalized_checkpoint.epoch = 3;
+        state.next_withdrawal_index = 12;
+        let bytes = serialize(&state).unwrap();
+
+        let fork = Fork::Deneb;
+        assert_field(&bytes, StateField::Slot, fork, &context, &state.slot);
+        assert_field(&bytes, StateField::Validators, fork, &context, &state.validators);
+        assert_field(&bytes, StateField::Balances, fork, &context, &state.balances);
+        assert_field(&bytes, StateField::HistoricalRoots, fork, &context, &state.historical_roots);
+        assert_field(&bytes, StateField::RandaoMixes, fork, &context, &state.randao_mixes);
+        assert_field(
+            &bytes,
+            StateField::FinalizedCheckpoint,
+            fork,
+            &context,
+            &state.finalized_checkpoint,
+        );
+        assert_field(
+            &bytes,
+            StateField::CurrentSyncCommittee,
+            fork,
+            &context,
+            &state.current_sync_committee,
+        );
+        assert_field(
+            &bytes,
+            StateField::LatestExecutionPayloadHeader,
+            fork,
+            &context,
+            &state.latest_execution_payload_header,
+        );
+        assert_field(&bytes, StateField::NextWithdrawalIndex, fork, &context, &12usize);
+        assert_field(
+            &bytes,
+            StateField::HistoricalSummaries,
+            fork,
+            &context,
+            &state.historical_summaries,
+        );
+
+        let result =
+            read_state_field::<Gwei>(&bytes, StateField::EarliestExitEpoch, fork, &context);
+        assert!(matches!(result, Err(Error::MissingStateField { .. })));
+        let result = read_state_field::<Gwei>(&bytes[..64], StateField::Validators, fork, &context);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_read_electra_state_fields() {
+        let context = Context::for_minimal();
+        let mut state = electra::BeaconState { slot: 42, ..Default::default() };
+        state.validators.push(Validator { effective_balance: 32, ..Default::default() });
+        state.balances.push(33);
+        state.earliest_exit_epoch = 9;
+        state.pending_deposits.push(Default::default());
+        state.pending_consolidations.push(Default::default());
+        let bytes = serialize(&state).unwrap();
+
+        let fork = Fork::Electra;
+        assert_field(&bytes, StateField::Validators, fork, &context, &state.validators);
+        assert_field(&bytes, StateField::Balances, fork, &context, &state.balances);
+        assert_field(&bytes, StateField::EarliestExitEpoch, fork, &context, &9u64);
+        assert_field(&bytes, StateField::PendingDeposits, fork, &context, &state.pending_deposits);
+        assert_field(
+            &bytes,
+            StateField::PendingPartialWithdrawals,
+            fork,
+            &context,
+            &state.pending_partial_withdrawals,
+        );
+        assert_field(
+            &bytes,
+            StateField::PendingConsolidations,
+            fork,
+            &context,
+            &state.pending_consolidations,
+        );
+    }
+
+    #[test]
+    fn test_read_every_phase0_state_field() {
+        let context = Context::for_minimal();
+        let state = fixture_post_state(
+            BeaconState::Phase0(fixtures::phase0::genesis_state()),
+            SignedBeaconBlock::Phase0(fixtures::phase0::signed_block()),
+            &context,
+        );
+        let BeaconState::Phase0(mut state) = state else {
+            unreachable!("no fork boundary is crossed")
+        };
+        state.historical_roots.push(Root::default());
+        assert_every_field!(state, Fork::Phase0, &context, {
+            GenesisTime => genesis_time,
+            GenesisValidatorsRoot => genesis_validators_root,
+            Slot => slot,
+            Fork => fork,
+            LatestBlockHeader => latest_block_header,
+            BlockRoots => block_roots,
+            StateRoots => state_roots,
+            HistoricalRoots => historical_roots,
+            Eth1Data => eth1_data,
+            Eth1DataVotes => eth1_data_votes,
+            Eth1DepositIndex => eth1_deposit_index,
+            Validators => validators,
+            Balances => balances,
+            RandaoMixes => randao_mixes,
+            Slashings => slashings,
+            PreviousEpochAttestations => previous_epoch_attestations,
+            CurrentEpochAttestations => current_epoch_attestations,
+            JustificationBits => justification_bits,
+            PreviousJustifiedCheckpoint => previous_justified_checkpoint,
+            CurrentJustifiedCheckpoint => current_justified_checkpoint,
+            FinalizedCheckpoint => finalized_checkpoint,
+        });
+    }
+
+    #[test]
+    fn test_read_every_altair_state_field() {
+        let context = fixtures::altair::context();
+        let state = fixture_post_state(
+            BeaconState::Altair(fixtures::altair::genesis_state()),
+            SignedBeaconBlock::Altair(fixtures::altair::signed_block()),
+            &context,
+        );
+        let BeaconState::Altair(mut state) = state else {
+            unreachable!("no fork boundary is crossed")
+        };
+        state.historical_roots.push(Root::default());
+        assert_every_field!(state, Fork::Altair, &context, {
+            GenesisTime => genesis_time,
+            GenesisValidatorsRoot => genesis_validators_root,
+            Slot => slot,
+            Fork => fork,
+            LatestBlockHeader => latest_block_header,
+            BlockRoots => block_roots,
+            StateRoots => state_roots,
+            HistoricalRoots => historical_roots,
+            Eth1Data => eth1_data,
+            Eth1DataVotes => eth1_data_votes,
+            Eth1DepositIndex => eth1_deposit_index,
+            Validators => validators,
+            Balances => balances,
+            RandaoMixes => randao_mixes,
+            Slashings => slashings,
+            PreviousEpochParticipation => previous_epoch_participation,
+            CurrentEpochParticipation => current_epoch_participation,
+            JustificationBits => justification_bits,
+            PreviousJustifiedCheckpoint => previous_justified_checkpoint,
+            CurrentJustifiedCheckpoint => current_justified_checkpoint,
+            FinalizedCheckpoint => finalized_checkpoint,
+            InactivityScores => inactivity_scores,
+            CurrentSyncCommittee => current_sync_committee,
+            NextSyncCommittee => next_sync_committee,
+        });
+    }
+
+    #[test]
+    fn test_read_every_bellatrix_state_field() {
+        let context = fixtures::bellatrix::context();
+        let state = fixture_post_state(
+            BeaconState::Bellatrix(fixtures::bellatrix::genesis_state()),
+            SignedBeaconBlock::Bellatrix(fixtures::bellatrix::signed_block()),
+            &context,
+        );
+        let BeaconState::Bellatrix(mut state) = state else {
+            unreachable!("no fork boundary is crossed")
+        };
+        state.historical_roots.push(Root::default());
+        assert_every_field!(state, Fork::Bellatrix, &context, {
+            GenesisTime => genesis_time,
+            GenesisValidatorsRoot => genesis_validators_root,
+            Slot => slot,
+            Fork => fork,
+            LatestBlockHeader => latest_block_header,
+            BlockRoots => block_roots,
+            StateRoots => state_roots,
+            HistoricalRoots => historical_roots,
+            Eth1Data => eth1_data,
+            Eth1DataVotes => eth1_data_votes,
+            Eth1DepositIndex => eth1_deposit_index,
+            Validators => validators,
+            Balances => balances,
+            RandaoMixes => randao_mixes,
+            Slashings => slashings,
+            PreviousEpochParticipation => previous_epoch_participation,
+            CurrentEpochParticipation => current_epoch_participation,
+            JustificationBits => justification_bits,
+            PreviousJustifiedCheckpoint => previous_justified_checkpoint,
+            CurrentJustifiedCheckpoint => current_justified_checkpoint,
+            FinalizedCheckpoint => finalized_checkpoint,
+            InactivityScores => inactivity_scores,
+            CurrentSyncCommittee => current_sync_committee,
+            NextSyncCommittee => next_sync_committee,
+            LatestExecutionPayloadHeader => latest_execution_payload_header,
+        });
+    }
+
+    #[test]
+    fn test_read_every_capella_state_field() {
+        let context = fixtures::capella::context();
+        let state = fixture_post_state(
+            BeaconState::Capella(fixtures::capella::genesis_state()),
+            SignedBeaconBlock::Capella(fixtures::capella::signed_block()),
+            &context,
+        );
+        let BeaconState::Capella(mut state) = state else {
+            unreachable!("no fork boundary is crossed")
+        };
+        state.historical_roots.push(Root::default());
+        state.historical_summaries.push(Default::default());
+        assert_every_field!(state, Fork::Capella, &context, {
+            GenesisTime => genesis_time,
+            GenesisValidatorsRoot => genesis_validators_root,
+            Slot => slot,
+            Fork => fork,
+            LatestBlockHeader => latest_block_header,
+            BlockRoots => block_roots,
+            StateRoots => state_roots,
+            HistoricalRoots => historical_roots,
+            Eth1Data => eth1_data,
+            Eth1DataVotes => eth1_data_votes,
+            Eth1DepositIndex => eth1_deposit_index,
+            Validators => validators,
+            Balances => balances,
+            RandaoMixes => randao_mixes,
+            Slashings => slashings,
+            PreviousEpochParticipation => previous_epoch_participation,
+            CurrentEpochParticipation => current_epoch_participation,
+            JustificationBits => justification_bits,
+            PreviousJustifiedCheckpoint => previous_justified_checkpoint,
+            CurrentJustifiedCheckpoint => current_justified_checkpoint,
+            FinalizedCheckpoint => finalized_checkpoint,
+            InactivityScores => inactivity_scores,
+            CurrentSyncCommittee => current_sync_committee,
+            NextSyncCommittee => next_sync_committee,
+            LatestExecutionPayloadHeader => latest_execution_payload_header,
+            NextWithdrawalIndex => next_withdrawal_index,
+            NextWithdrawalValidatorIndex => next_withdrawal_validator_index,
+            HistoricalSummaries => historical_summaries,
+        });
+    }
+
+    #[test]
+    fn test_read_every_deneb_state_field() {
+        let context = fixtures::deneb::context();
+        let state = fixture_post_state(
+            BeaconState::Deneb(fixtures::deneb::genesis_state()),
+            SignedBeaconBlock::Deneb(fixtures::deneb::signed_block()),
+            &context,
+        );
+        let BeaconState::Deneb(mut state) = state else {
+            unreachable!("no fork boundary is crossed")
+        };
+        state.historical_roots.push(Root::default());
+        state.historical_summaries.push(Default::default());
+        assert_every_field!(state, Fork::Deneb, &context, {
+            GenesisTime => genesis_time,
+            GenesisValidatorsRoot => genesis_validators_root,
+            Slot => slot,
+            Fork => fork,
+            LatestBlockHeader => latest_block_header,
+            BlockRoots => block_roots,
+            StateRoots => state_roots,
+            HistoricalRoots => historical_roots,
+            Eth1Data => eth1_data,
+            Eth1DataVotes => eth1_data_votes,
+            Eth1DepositIndex => eth1_deposit_index,
+            Validators => validators,
+            Balances => balances,
+            RandaoMixes => randao_mixes,
+            Slashings => slashings,
+            PreviousEpochParticipation => previous_epoch_participation,
+            CurrentEpochParticipation => current_epoch_participation,
+            JustificationBits => justification_bits,
+            PreviousJustifiedCheckpoint => previous_justified_checkpoint,
+            CurrentJustifiedCheckpoint => current_justified_checkpoint,
+            FinalizedCheckpoint => finalized_checkpoint,
+            InactivityScores => inactivity_scores,
+            CurrentSyncCommittee => current_sync_committee,
+            NextSyncCommittee => next_sync_committee,
+            LatestExecutionPayloadHeader => latest_execution_payload_header,
+            NextWithdrawalIndex => next_withdrawal_index,
+            NextWithdrawalValidatorIndex => next_withdrawal_validator_index,
+            HistoricalSummaries => historical_summaries,
+        });
+    }
+
+    #[test]
+    fn test_read_every_electra_state_field() {
+        let context = fixtures::electra::context();
+        let state = fixture_post_state(
+            BeaconState::Electra(fixtures::electra::genesis_state()),
+            SignedBeaconBlock::Electra(fixtures::electra::signed_block()),
+            &context,
+        );
+        let BeaconState::Electra(mut state) = state else {
+            unreachable!("no fork boundary is crossed")
+        };
+        state.historical_roots.push(Root::default());
+        state.historical_summaries.push(Default::default());
+        state.pending_partial_withdrawals.push(Default::default());
+        assert_every_field!(state, Fork::Electra, &context, {
+            GenesisTime => genesis_time,
+            GenesisValidatorsRoot => genesis_validators_root,
+            Slot => slot,
+            Fork => fork,
+            LatestBlockHeader => latest_block_header,
+            BlockRoots => block_roots,
+            StateRoots => state_roots,
+            HistoricalRoots => historical_roots,
+            Eth1Data => eth1_data,
+            Eth1DataVotes => eth1_data_votes,
+            Eth1DepositIndex => eth1_deposit_index,
+            Validators => validators,
+            Balances => balances,
+            RandaoMixes => randao_mixes,
+            Slashings => slashings,
+            PreviousEpochParticipation => previous_epoch_participation,
+            CurrentEpochParticipation => current_epoch_participation,
+            JustificationBits => justification_bits,
+            PreviousJustifiedCheckpoint => previous_justified_checkpoint,
+            CurrentJustifiedCheckpoint => current_justified_checkpoint,
+            FinalizedCheckpoint => finalized_checkpoint,
+            InactivityScores => inactivity_scores,
+            CurrentSyncCommittee => current_sync_committee,
+            NextSyncCommittee => next_sync_committee,
+            LatestExecutionPayloadHeader => latest_execution_payload_header,
+            NextWithdrawalIndex => next_withdrawal_index,
+            NextWithdrawalValidatorIndex => next_withdrawal_validator_index,
+            HistoricalSummaries => historical_summaries,
+            DepositRequestsStartIndex => deposit_requests_start_index,
+            DepositBalanceToConsume => deposit_balance_to_consume,
+            ExitBalanceToConsume => exit_balance_to_consume,
+            EarliestExitEpoch => earliest_exit_epoch,
+            ConsolidationBalanceToConsume => consolidation_balance_to_consume,
+            EarliestConsolidationEpoch => earliest_consolidation_epoch,
+            PendingDeposits => pending_deposits,
+            PendingPartialWithdrawals => pending_partial_withdrawals,
+            PendingConsolidations => pending_consolidations,
+        });
+    }
+}
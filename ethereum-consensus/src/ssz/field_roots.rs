@@ -0,0 +1,49 @@
+use crate::ssz::prelude::*;
+
+/// The roots of the fields of a container, in order, so that the root of the container is the
+/// merkleization of these roots, e.g. to update the root of a large container after changing
+/// only a few of its fields.
+pub trait FieldRoots {
+    fn field_roots(&self) -> Result<Vec<Node>, MerkleizationError>;
+
+    /// Bring `roots`, the field roots of `previous`, up to date with `self`, hashing only the
+    /// fields that differ between the two.
+    fn update_field_roots(
+        &self,
+        previous: &Self,
+        roots: &mut [Node],
+    ) -> Result<(), MerkleizationError>;
+}
+
+// Implement `FieldRoots` for a container from the names of its fields, in order.
+macro_rules! impl_field_roots {
+    ($name:ident $(<$(const $param:ident),+ $(,)?>)? { $($field:ident),+ $(,)? }) => {
+        impl$(<$(const $param: usize),+>)? $crate::ssz::prelude::FieldRoots
+            for $name$(<$($param),+>)?
+        {
+            fn field_roots(
+                &self,
+            ) -> Result<Vec<$crate::ssz::prelude::Node>, $crate::ssz::prelude::MerkleizationError>
+            {
+                Ok(vec![$($crate::ssz::prelude::HashTreeRoot::hash_tree_root(&self.$field)?),+])
+            }
+
+            fn update_field_roots(
+                &self,
+                previous: &Self,
+                roots: &mut [$crate::ssz::prelude::Node],
+            ) -> Result<(), $crate::ssz::prelude::MerkleizationError> {
+                let mut roots = roots.iter_mut();
+                $(
+                    let root = roots.next().expect("a root for each field");
+                    if self.$field != previous.$field {
+                        *root = $crate::ssz::prelude::HashTreeRoot::hash_tree_root(&self.$field)?;
+                    }
+                )+
+                Ok(())
+            }
+        }
+    };
+}
+
+pub(crate) use impl_field_roots;
@@ -0,0 +1,98 @@
+use crate::ssz::prelude::*;
+use std::{
+    borrow::Borrow,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+/// A value along with its hash tree root, computed once when the value is wrapped, so that it is
+/// compared and hashed by its root alone, e.g. as the key of a map in a hot path.
+///
+/// The value can only be read through the wrapper so that the root stays current. A map keyed by
+/// wrapped values can be searched by root as the wrapper borrows as its root.
+#[derive(Debug, Clone)]
+pub struct HashTreeRootCached<T> {
+    value: T,
+    root: Node,
+}
+
+impl<T: HashTreeRoot> HashTreeRootCached<T> {
+    pub fn new(value: T) -> Result<Self, MerkleizationError> {
+        let root = value.hash_tree_root()?;
+        Ok(Self { value, root })
+    }
+}
+
+impl<T> HashTreeRootCached<T> {
+    pub fn root(&self) -> Node {
+        self.root
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> PartialEq for HashTreeRootCached<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.root == other.root
+    }
+}
+
+impl<T> Eq for HashTreeRootCached<T> {}
+
+impl<T> Hash for HashTreeRootCached<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.root.hash(state);
+    }
+}
+
+impl<T> Borrow<Node> for HashTreeRootCached<T> {
+    fn borrow(&self) -> &Node {
+        &self.root
+    }
+}
+
+impl<T> Deref for HashTreeRootCached<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::phase0::{AttestationData, Checkpoint};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_compare_by_root() {
+        let data = || AttestationData {
+            slot: 9,
+            index: 1,
+            target: Checkpoint { epoch: 1, root: Node::try_from([3u8; 32].as_ref()).unwrap() },
+            ..Default::default()
+        };
+        let a = HashTreeRootCached::new(data()).unwrap();
+        let b = HashTreeRootCached::new(data()).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.root(), data().hash_tree_root().unwrap());
+        assert_eq!(a.target.epoch, 1);
+
+        let mut other = data();
+        other.index = 2;
+        let c = HashTreeRootCached::new(other).unwrap();
+        assert_ne!(a, c);
+
+        let mut map = HashMap::new();
+        map.insert(a, "first");
+        assert_eq!(map.insert(b, "second"), Some("first"));
+        map.insert(c.clone(), "other");
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&data().hash_tree_root().unwrap()), Some(&"second"));
+        assert_eq!(map.get(&c.root()), Some(&"other"));
+        assert_eq!(c.into_inner().index, 2);
+    }
+}
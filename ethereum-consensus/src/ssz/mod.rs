@@ -1,7 +1,14 @@
 mod byte_list;
 mod byte_vector;
+mod field_roots;
+mod hash_tree_root_cached;
+
+pub(crate) use field_roots::impl_field_roots;
 
 pub mod prelude {
-    pub use super::{byte_list::ByteList, byte_vector::ByteVector};
+    pub use super::{
+        byte_list::ByteList, byte_vector::ByteVector, field_roots::FieldRoots,
+        hash_tree_root_cached::HashTreeRootCached,
+    };
     pub use ssz_rs::prelude::*;
 }
@@ -0,0 +1,258 @@
+//! The length of the SSZ encoding of a value, computed from its structure rather than by encoding
+//! it, e.g. for rate limiting or gossip scoring, and the largest such length a type allows given
+//! its bounds, e.g. to size buffers up front.
+//!
+//! Lists of bytes (like transactions) and of fixed-size elements are measured from their lengths
+//! alone; only lists of variable-size elements are walked.
+use crate::{
+    crypto::{PublicKey, Signature, BLS_PUBLIC_KEY_BYTES_LEN, BLS_SIGNATURE_BYTES_LEN},
+    ssz::prelude::*,
+};
+
+/// The number of bytes of an offset to a variable-size part of an encoding.
+pub const BYTES_PER_LENGTH_OFFSET: usize = 4;
+
+pub trait SszSize {
+    const IS_VARIABLE_SIZE: bool;
+    /// The length of the longest encoding of a value of this type, which is the length of every
+    /// encoding for a fixed-size type.
+    const MAX_ENCODED_SIZE: usize;
+
+    fn ssz_size(&self) -> usize;
+}
+
+/// The length of the SSZ encoding of `value`.
+pub fn estimate<T: SszSize>(value: &T) -> usize {
+    value.ssz_size()
+}
+
+// The length `value` takes up when it is a field of a container or an element of a sequence: a
+// variable-size value is also pointed to by an offset.
+#[doc(hidden)]
+pub fn part_size<T: SszSize>(value: &T) -> usize {
+    if T::IS_VARIABLE_SIZE {
+        BYTES_PER_LENGTH_OFFSET + value.ssz_size()
+    } else {
+        value.ssz_size()
+    }
+}
+
+#[doc(hidden)]
+pub const fn max_part_size<T: SszSize>() -> usize {
+    if T::IS_VARIABLE_SIZE {
+        BYTES_PER_LENGTH_OFFSET + T::MAX_ENCODED_SIZE
+    } else {
+        T::MAX_ENCODED_SIZE
+    }
+}
+
+// Implement `SszSize` for a container from its fields, in order, and their types.
+macro_rules! impl_ssz_size {
+    ($name:ident $(<$(const $param:ident),+ $(,)?>)? { $($field:ident: $ty:ty),+ $(,)? }) => {
+        impl$(<$(const $param: usize),+>)? $crate::ssz_size::SszSize for $name$(<$($param),+>)? {
+            const IS_VARIABLE_SIZE: bool =
+                false $(|| <$ty as $crate::ssz_size::SszSize>::IS_VARIABLE_SIZE)+;
+            const MAX_ENCODED_SIZE: usize = 0 $(+ $crate::ssz_size::max_part_size::<$ty>())+;
+
+            fn ssz_size(&self) -> usize {
+                0 $(+ $crate::ssz_size::part_size(&self.$field))+
+            }
+        }
+    };
+}
+
+pub(crate) use impl_ssz_size;
+
+macro_rules! impl_fixed_size {
+    ($($ty:ty => $size:expr),+ $(,)?) => {
+        $(
+            impl SszSize for $ty {
+                const IS_VARIABLE_SIZE: bool = false;
+                const MAX_ENCODED_SIZE: usize = $size;
+
+                fn ssz_size(&self) -> usize {
+                    $size
+                }
+            }
+        )+
+    };
+}
+
+impl_fixed_size!(
+    bool => 1,
+    u8 => 1,
+    u16 => 2,
+    u32 => 4,
+    u64 => 8,
+    // encoded as a `u64`
+    usize => 8,
+    u128 => 16,
+    U256 => 32,
+    Node => 32,
+    PublicKey => BLS_PUBLIC_KEY_BYTES_LEN,
+    Signature => BLS_SIGNATURE_BYTES_LEN,
+);
+
+impl<const N: usize> SszSize for [u8; N] {
+    const IS_VARIABLE_SIZE: bool = false;
+    const MAX_ENCODED_SIZE: usize = N;
+
+    fn ssz_size(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> SszSize for ByteVector<N> {
+    const IS_VARIABLE_SIZE: bool = false;
+    const MAX_ENCODED_SIZE: usize = N;
+
+    fn ssz_size(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> SszSize for ByteList<N> {
+    const IS_VARIABLE_SIZE: bool = true;
+    const MAX_ENCODED_SIZE: usize = N;
+
+    fn ssz_size(&self) -> usize {
+        self.as_ref().len()
+    }
+}
+
+impl<const N: usize> SszSize for Bitvector<N> {
+    const IS_VARIABLE_SIZE: bool = false;
+    const MAX_ENCODED_SIZE: usize = N.div_ceil(8);
+
+    fn ssz_size(&self) -> usize {
+        Self::MAX_ENCODED_SIZE
+    }
+}
+
+impl<const N: usize> SszSize for Bitlist<N> {
+    const IS_VARIABLE_SIZE: bool = true;
+    // the bits are followed by a delimiting bit
+    const MAX_ENCODED_SIZE: usize = N / 8 + 1;
+
+    fn ssz_size(&self) -> usize {
+        self.len() / 8 + 1
+    }
+}
+
+impl<T: SszSize, const N: usize> SszSize for Vector<T, N> {
+    const IS_VARIABLE_SIZE: bool = T::IS_VARIABLE_SIZE;
+    const MAX_ENCODED_SIZE: usize = N * max_part_size::<T>();
+
+    fn ssz_size(&self) -> usize {
+        if T::IS_VARIABLE_SIZE {
+            self.iter().map(part_size).sum()
+        } else {
+            N * T::MAX_ENCODED_SIZE
+        }
+    }
+}
+
+impl<T: SszSize, const N: usize> SszSize for List<T, N> {
+    const IS_VARIABLE_SIZE: bool = true;
+    const MAX_ENCODED_SIZE: usize = N * max_part_size::<T>();
+
+    fn ssz_size(&self) -> usize {
+        if T::IS_VARIABLE_SIZE {
+            self.iter().map(part_size).sum()
+        } else {
+            self.len() * T::MAX_ENCODED_SIZE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        altair, bellatrix, capella,
+        capella::Withdrawal,
+        deneb, electra,
+        fixtures::phase0::{genesis_state, signed_block},
+        phase0::{self, SignedBeaconBlockHeader, Validator},
+    };
+
+    fn assert_exact<T: SimpleSerialize + SszSize>(value: &T) {
+        let encoding = serialize(value).unwrap();
+        assert_eq!(estimate(value), encoding.len());
+        assert!(encoding.len() <= T::MAX_ENCODED_SIZE);
+    }
+
+    fn transactions<const N: usize, const M: usize>() -> List<ByteList<N>, M> {
+        let transactions =
+            [0, 1, 100].map(|len| ByteList::try_from(vec![7u8; len].as_ref()).unwrap());
+        List::try_from(transactions.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_estimate_matches_encoding() {
+        let block = signed_block();
+        assert!(!block.message.body.attestations.is_empty());
+        assert_exact(&block);
+        let state = genesis_state();
+        assert!(!state.validators.is_empty());
+        assert_exact(&state);
+
+        let mut block = altair::minimal::SignedBeaconBlock::default();
+        block.message.body.proposer_slashings.push(Default::default());
+        assert_exact(&block);
+        let mut state = altair::minimal::BeaconState::default();
+        state.validators.push(Validator::default());
+        state.inactivity_scores.push(3);
+        assert_exact(&state);
+
+        let mut block = bellatrix::minimal::SignedBeaconBlock::default();
+        block.message.body.execution_payload.transactions = transactions();
+        block.message.body.execution_payload.extra_data =
+            ByteList::try_from(b"extra".as_ref()).unwrap();
+        assert_exact(&block);
+        assert_exact(&bellatrix::minimal::BeaconState::default());
+
+        let mut block = capella::minimal::SignedBeaconBlock::default();
+        block.message.body.execution_payload.transactions = transactions();
+        block.message.body.execution_payload.withdrawals.push(Withdrawal::default());
+        block.message.body.bls_to_execution_changes.push(Default::default());
+        assert_exact(&block);
+        let mut state = capella::minimal::BeaconState::default();
+        state.historical_summaries.push(Default::default());
+        assert_exact(&state);
+
+        let mut block = deneb::minimal::SignedBeaconBlock::default();
+        block.message.body.execution_payload.transactions = transactions();
+        block.message.body.blob_kzg_commitments.push(Default::default());
+        assert_exact(&block);
+        assert_exact(&deneb::minimal::BeaconState::default());
+        assert_exact(&deneb::minimal::BlobSidecar::default());
+
+        let mut block = electra::minimal::SignedBeaconBlock::default();
+        let mut attestation = electra::minimal::Attestation::default();
+        attestation.aggregation_bits = Bitlist::try_from([true; 13].as_ref()).unwrap();
+        block.message.body.attestations.push(attestation);
+        block.message.body.attester_slashings.push(Default::default());
+        block.message.body.execution_payload.transactions = transactions();
+        assert_exact(&block);
+        let mut state = electra::minimal::BeaconState::default();
+        state.pending_consolidations.push(Default::default());
+        assert_exact(&state);
+    }
+
+    #[test]
+    fn test_max_encoded_sizes() {
+        assert!(!Validator::IS_VARIABLE_SIZE);
+        assert_eq!(Validator::MAX_ENCODED_SIZE, 121);
+        assert_eq!(SignedBeaconBlockHeader::MAX_ENCODED_SIZE, 208);
+        // 4 bytes of offset and 257 of aggregation bits, 128 of data and 96 of signature
+        assert!(phase0::mainnet::Attestation::IS_VARIABLE_SIZE);
+        assert_eq!(phase0::mainnet::Attestation::MAX_ENCODED_SIZE, 485);
+
+        // a full attestation reaches the bound
+        let mut attestation = phase0::mainnet::Attestation::default();
+        attestation.aggregation_bits = Bitlist::try_from([true; 2048].as_ref()).unwrap();
+        assert_eq!(estimate(&attestation), 485);
+        assert_exact(&attestation);
+    }
+}
@@ -0,0 +1,502 @@
+use crate::{
+    altair, bellatrix, capella, deneb, electra,
+    phase0::{self, Validator},
+    primitives::{Epoch, Gwei, Root},
+    ssz::prelude::List,
+    state_transition::{Context, Result},
+    types::BeaconState,
+};
+use std::ops::Deref;
+
+/// The caches derived from a state that a mutation of the state may have made stale.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheInvalidation {
+    /// Committee shufflings, which depend on the validator registry and the randao mixes.
+    pub shufflings: bool,
+    /// Decompressed validator public keys.
+    pub pubkeys: bool,
+    /// The total active balance, which depends on the effective balances of the validators.
+    pub total_active_balance: bool,
+}
+
+impl CacheInvalidation {
+    pub const fn none() -> Self {
+        Self { shufflings: false, pubkeys: false, total_active_balance: false }
+    }
+
+    pub const fn all() -> Self {
+        Self { shufflings: true, pubkeys: true, total_active_balance: true }
+    }
+
+    pub const fn balances_only() -> Self {
+        Self { shufflings: false, pubkeys: false, total_active_balance: true }
+    }
+
+    /// Edits to the validator registry: keys, activity and effective balances all live there.
+    pub const fn validators() -> Self {
+        Self::all()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::none()
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            shufflings: self.shufflings || other.shufflings,
+            pubkeys: self.pubkeys || other.pubkeys,
+            total_active_balance: self.total_active_balance || other.total_active_balance,
+        }
+    }
+}
+
+/// A `BeaconState` whose mutable access goes through accessors that record which of the caches
+/// derived from it (here and in e.g. the `Executor`) are made stale.
+///
+/// Reads go through `Deref`. Edits are scoped, e.g. `with_balances_mut(|balances| ..)`, or declare
+/// what they touch with `with_mut`; `as_inner_mut` is the escape hatch and invalidates every
+/// cache. The invalidations accumulate until taken by the owner of the other caches with
+/// `take_invalidation`.
+#[derive(Debug)]
+pub struct CachedState<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+> {
+    state: BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    total_active_balance: Option<(Epoch, Gwei)>,
+    cache_total_active_balance: bool,
+    invalidation: CacheInvalidation,
+    // the root of the state, if known without hashing it since it was last edited
+    state_root: Option<Root>,
+}
+
+impl<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    >
+    CachedState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >
+{
+    pub fn new(
+        state: BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    ) -> Self {
+        Self {
+            state,
+            total_active_balance: None,
+            cache_total_active_balance: true,
+            invalidation: CacheInvalidation::none(),
+            state_root: None,
+        }
+    }
+
+    /// Whether the total active balance is kept once computed, which it is by default.
+    pub fn set_cache_total_active_balance(&mut self, enabled: bool) {
+        self.cache_total_active_balance = enabled;
+        if !enabled {
+            self.total_active_balance = None;
+        }
+    }
+
+    pub fn inner(
+        &self,
+    ) -> &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    > {
+        &self.state
+    }
+
+    pub fn into_inner(
+        self,
+    ) -> BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    > {
+        self.state
+    }
+
+    /// Mutable access to the whole state, invalidating every cache as any field may change.
+    ///
+    /// Prefer a scoped accessor, or `with_mut` for bulk edits whose reach is known.
+    pub fn as_inner_mut(
+        &mut self,
+    ) -> &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    > {
+        self.invalidate(CacheInvalidation::all());
+        &mut self.state
+    }
+
+    /// Mutable access for the state transition, invalidating the total active balance held here.
+    ///
+    /// The caches held by the `Executor` stay valid: shufflings are keyed by epoch and fixed a
+    /// lookahead in advance, and cached public keys are checked against the registry on every
+    /// lookup.
+    pub(crate) fn transition_mut(
+        &mut self,
+    ) -> &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    > {
+        self.invalidate(CacheInvalidation::balances_only());
+        &mut self.state
+    }
+
+    /// Edit the state with `f`, invalidating the caches in `invalidation`.
+    pub fn with_mut<T>(
+        &mut self,
+        invalidation: CacheInvalidation,
+        f: impl FnOnce(
+            &mut BeaconState<
+                SLOTS_PER_HISTORICAL_ROOT,
+                HISTORICAL_ROOTS_LIMIT,
+                ETH1_DATA_VOTES_BOUND,
+                VALIDATOR_REGISTRY_LIMIT,
+                EPOCHS_PER_HISTORICAL_VECTOR,
+                EPOCHS_PER_SLASHINGS_VECTOR,
+                MAX_VALIDATORS_PER_COMMITTEE,
+                PENDING_ATTESTATIONS_BOUND,
+                SYNC_COMMITTEE_SIZE,
+                BYTES_PER_LOGS_BLOOM,
+                MAX_EXTRA_DATA_BYTES,
+                PENDING_DEPOSITS_LIMIT,
+                PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+                PENDING_CONSOLIDATIONS_LIMIT,
+            >,
+        ) -> T,
+    ) -> T {
+        self.invalidate(invalidation);
+        f(&mut self.state)
+    }
+
+    pub fn with_balances_mut<T>(
+        &mut self,
+        f: impl FnOnce(&mut List<Gwei, VALIDATOR_REGISTRY_LIMIT>) -> T,
+    ) -> T {
+        self.with_mut(CacheInvalidation::balances_only(), |state| f(state.balances_mut()))
+    }
+
+    /// Edit the validator registry with `f`, invalidating every cache: keys, activity and
+    /// effective balances all live in the registry.
+    pub fn with_validators_mut<T>(
+        &mut self,
+        f: impl FnOnce(&mut List<Validator, VALIDATOR_REGISTRY_LIMIT>) -> T,
+    ) -> T {
+        self.with_mut(CacheInvalidation::validators(), |state| f(state.validators_mut()))
+    }
+
+    /// Mark the caches in `invalidation` as stale, dropping those held here.
+    ///
+    /// Every edit goes through here, so the recorded state root is always dropped.
+    pub fn invalidate(&mut self, invalidation: CacheInvalidation) {
+        self.state_root = None;
+        if invalidation.total_active_balance {
+            self.total_active_balance = None;
+        }
+        self.invalidation = self.invalidation.union(invalidation);
+    }
+
+    /// The root of the state as recorded with `record_state_root`, unless the state was edited
+    /// since.
+    pub fn known_state_root(&self) -> Option<Root> {
+        self.state_root
+    }
+
+    /// Record `root` as the root of the state as it is now, e.g. the state root of the block just
+    /// applied and checked against it.
+    pub(crate) fn record_state_root(&mut self, root: Root) {
+        self.state_root = Some(root);
+    }
+
+    /// The caches made stale since the last call to `take_invalidation`.
+    pub fn pending_invalidation(&self) -> CacheInvalidation {
+        self.invalidation
+    }
+
+    pub fn take_invalidation(&mut self) -> CacheInvalidation {
+        std::mem::take(&mut self.invalidation)
+    }
+
+    /// The total active balance at the current epoch, computed once per epoch unless invalidated.
+    pub fn total_active_balance(&mut self, context: &Context) -> Result<Gwei> {
+        let epoch = phase0::compute_epoch_at_slot(self.state.slot(), context);
+        if let Some((cached_epoch, balance)) = self.total_active_balance {
+            if cached_epoch == epoch {
+                return Ok(balance)
+            }
+        }
+        let balance = match &self.state {
+            BeaconState::Phase0(state) => phase0::get_total_active_balance(state, context),
+            BeaconState::Altair(state) => altair::get_total_active_balance(state, context),
+            BeaconState::Bellatrix(state) => bellatrix::get_total_active_balance(state, context),
+            BeaconState::Capella(state) => capella::get_total_active_balance(state, context),
+            BeaconState::Deneb(state) => deneb::get_total_active_balance(state, context),
+            BeaconState::Electra(state) => electra::get_total_active_balance(state, context),
+        }?;
+        if self.cache_total_active_balance {
+            self.total_active_balance = Some((epoch, balance));
+        }
+        Ok(balance)
+    }
+}
+
+impl<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    > Deref
+    for CachedState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >
+{
+    type Target = BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >;
+
+    fn deref(&self) -> &Self::Target {
+        &self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::phase0::genesis_state,
+        state_transition::{
+            minimal::{CachedState, Executor},
+            shuffling_decision_root,
+        },
+    };
+
+    #[test]
+    fn test_scoped_edits_invalidate_affected_caches() {
+        let context = Context::for_minimal();
+        let increment = context.effective_balance_increment;
+        let mut state = CachedState::new(BeaconState::Phase0(genesis_state()));
+        let total = state.total_active_balance(&context).unwrap();
+        assert!(state.pending_invalidation().is_empty());
+
+        state.with_balances_mut(|balances| balances[0] += 1);
+        assert_eq!(state.take_invalidation(), CacheInvalidation::balances_only());
+        assert_eq!(state.total_active_balance(&context).unwrap(), total);
+
+        state.with_validators_mut(|validators| validators[0].effective_balance -= increment);
+        assert_eq!(state.pending_invalidation(), CacheInvalidation::all());
+        assert_eq!(state.total_active_balance(&context).unwrap(), total - increment);
+        let expected = phase0::get_total_active_balance(state.phase0().unwrap(), &context);
+        assert_eq!(state.total_active_balance(&context).unwrap(), expected.unwrap());
+
+        // bulk edits declare what they touch
+        state.take_invalidation();
+        state.with_mut(CacheInvalidation::validators(), |state| {
+            for validator in state.validators_mut().iter_mut() {
+                validator.effective_balance -= increment;
+            }
+        });
+        assert_eq!(state.take_invalidation(), CacheInvalidation::validators());
+        let validator_count = state.validators().len() as u64;
+        assert_eq!(
+            state.total_active_balance(&context).unwrap(),
+            total - (validator_count + 1) * increment
+        );
+    }
+
+    #[test]
+    fn test_transition_invalidates_total_active_balance() {
+        let context = Context::for_minimal();
+        let mut executor = Executor::new(BeaconState::Phase0(genesis_state()), context.clone());
+        executor.beacon_committee(0, 0).unwrap();
+        let total = executor.state.total_active_balance(&context).unwrap();
+        assert!(executor.state.pending_invalidation().is_empty());
+
+        executor.process_slots(1).unwrap();
+        assert_eq!(executor.state.pending_invalidation(), CacheInvalidation::balances_only());
+        assert_eq!(executor.state.total_active_balance(&context).unwrap(), total);
+
+        // the shufflings outlive the transition
+        executor.beacon_committee(1, 0).unwrap();
+        assert!(executor.state.pending_invalidation().is_empty());
+        assert_eq!(executor.shuffling_cache.len(), 1);
+    }
+
+    #[test]
+    fn test_escape_hatch_invalidates_executor_caches() {
+        let context = Context::for_minimal();
+        let increment = context.effective_balance_increment;
+        let mut executor = Executor::new(BeaconState::Phase0(genesis_state()), context.clone());
+        executor.warm_pubkey_cache().unwrap();
+        executor.beacon_committee(context.slots_per_epoch, 0).unwrap();
+        let total = executor.state.total_active_balance(&context).unwrap();
+        assert!(!executor.pubkey_cache.is_empty());
+        assert_eq!(executor.shuffling_cache.len(), 1);
+
+        let state = executor.state.as_inner_mut();
+        state.validators_mut()[0].effective_balance -= increment;
+        assert_eq!(executor.state.pending_invalidation(), CacheInvalidation::all());
+        assert_eq!(executor.state.total_active_balance(&context).unwrap(), total - increment);
+
+        // the executor drops its caches before using them again
+        executor.beacon_committee(0, 0).unwrap();
+        assert!(executor.state.pending_invalidation().is_empty());
+        assert!(executor.pubkey_cache.is_empty());
+        assert_eq!(executor.shuffling_cache.len(), 1);
+        let decision_root = shuffling_decision_root(executor.state.inner(), 0, &context);
+        assert!(executor.shuffling_cache.get(1, decision_root).is_none());
+        assert!(executor.shuffling_cache.get(0, decision_root).is_some());
+    }
+}
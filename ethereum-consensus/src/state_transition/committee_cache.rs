@@ -0,0 +1,221 @@
+use crate::{
+    primitives::{Bytes32, Epoch, ValidatorIndex},
+    state_transition::{Context, Shuffling},
+};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+/// Number of shufflings a `CommitteeCache` holds by default: the previous, current and next epoch
+/// of a chain and one to spare, e.g. for a competing branch.
+pub const DEFAULT_COMMITTEE_CACHE_ENTRIES: usize = 4;
+
+/// Attester shufflings shared by the clones of a `Context`, so that `get_beacon_committee`
+/// shuffles the active validators of an epoch once rather than once per committee.
+///
+/// Shufflings are keyed by their inputs (the epoch, its seed and the active validator indices)
+/// rather than by epoch alone, so the cache stays correct when shared by states on different
+/// branches. The least recently used shuffling is evicted first.
+#[derive(Debug)]
+pub struct CommitteeCache {
+    shufflings: Mutex<VecDeque<Arc<Shuffling>>>,
+    max_entries: usize,
+}
+
+impl Default for CommitteeCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_COMMITTEE_CACHE_ENTRIES)
+    }
+}
+
+impl CommitteeCache {
+    pub fn new(max_entries: usize) -> Self {
+        Self { shufflings: Default::default(), max_entries }
+    }
+
+    pub fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+
+    /// The shuffling of `active_validator_indices` for `epoch` with `seed`, computing and caching
+    /// it if it is not already in the cache.
+    pub fn shuffling(
+        &self,
+        epoch: Epoch,
+        seed: &Bytes32,
+        active_validator_indices: Vec<ValidatorIndex>,
+        context: &Context,
+    ) -> Arc<Shuffling> {
+        {
+            let mut shufflings = self.shufflings.lock().unwrap();
+            let position = shufflings.iter().position(|shuffling| {
+                shuffling.epoch == epoch &&
+                    &shuffling.seed == seed &&
+                    shuffling.active_validator_indices == active_validator_indices
+            });
+            if let Some(position) = position {
+                let shuffling = shufflings.remove(position).expect("position is in bounds");
+                shufflings.push_back(shuffling.clone());
+                return shuffling
+            }
+        }
+        // shuffle without holding the lock so other lookups are not blocked
+        let shuffling = Arc::new(Shuffling::new(epoch, *seed, active_validator_indices, context));
+        let mut shufflings = self.shufflings.lock().unwrap();
+        shufflings.push_back(shuffling.clone());
+        while shufflings.len() > self.max_entries {
+            shufflings.pop_front();
+        }
+        shuffling
+    }
+
+    pub fn len(&self) -> usize {
+        self.shufflings.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        self.shufflings.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        phase0::{
+            compute_epoch_at_slot, get_active_validator_indices, get_attesting_indices,
+            get_beacon_committee, get_committee_count_per_slot, get_seed, minimal, Attestation,
+            AttestationData, Validator,
+        },
+        primitives::{DomainType, FAR_FUTURE_EPOCH},
+        ssz::prelude::deserialize,
+    };
+
+    const VALIDATOR_COUNT: usize = 300;
+
+    fn state(context: &Context) -> minimal::BeaconState {
+        let mut state =
+            minimal::BeaconState { slot: 2 * context.slots_per_epoch, ..Default::default() };
+        for i in 0..VALIDATOR_COUNT {
+            state.validators.push(Validator {
+                effective_balance: context.max_effective_balance,
+                exit_epoch: FAR_FUTURE_EPOCH,
+                withdrawable_epoch: FAR_FUTURE_EPOCH,
+                ..Default::default()
+            });
+            state.balances.push(context.max_effective_balance);
+            state.randao_mixes[i % state.randao_mixes.len()] =
+                Bytes32::try_from([i as u8; 32].as_ref()).unwrap();
+        }
+        state
+    }
+
+    fn uncached_context() -> Context {
+        let mut context = Context::for_minimal();
+        context.committee_cache = None;
+        context
+    }
+
+    // every committee of the previous, current and next epoch of `state`
+    fn all_committees(state: &minimal::BeaconState, context: &Context) -> Vec<Vec<ValidatorIndex>> {
+        let epoch = compute_epoch_at_slot(state.slot, context);
+        let slots = (epoch - 1) * context.slots_per_epoch..(epoch + 2) * context.slots_per_epoch;
+        slots
+            .flat_map(|slot| {
+                let epoch = compute_epoch_at_slot(slot, context);
+                let count = get_committee_count_per_slot(state, epoch, context);
+                (0..count).map(move |index| get_beacon_committee(state, slot, index, context))
+            })
+            .collect::<crate::state_transition::Result<Vec<_>>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_cached_committees_match_uncached() {
+        let context = Context::for_minimal();
+        let mut state = state(&context);
+        let uncached = uncached_context();
+
+        let committees = all_committees(&state, &context);
+        assert_eq!(committees, all_committees(&state, &uncached));
+        assert_eq!(committees.len(), 3 * 8 * 4);
+        let cache = context.committee_cache.as_ref().unwrap();
+        assert_eq!(cache.len(), 3);
+        // looking the committees up again reuses the cached shufflings
+        assert_eq!(committees, all_committees(&state, &context));
+        assert_eq!(cache.len(), 3);
+
+        // a state with other active validators for the same epochs gets its own shufflings
+        state.validators[7].exit_epoch = 0;
+        let committees = all_committees(&state, &context);
+        assert_eq!(committees, all_committees(&state, &uncached));
+        assert!(committees.iter().all(|committee| !committee.contains(&7)));
+        assert_eq!(cache.len(), DEFAULT_COMMITTEE_CACHE_ENTRIES);
+
+        // the clones of a context share its cache
+        let clone = context.clone();
+        assert_eq!(clone.committee_cache.as_ref().unwrap().len(), DEFAULT_COMMITTEE_CACHE_ENTRIES);
+        cache.clear();
+        assert!(clone.committee_cache.as_ref().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cached_attesting_indices_match_uncached() {
+        let context = Context::for_minimal();
+        let state = state(&context);
+        let slot = state.slot - 1;
+        let committee = get_beacon_committee(&state, slot, 1, &context).unwrap();
+        // a bit for every third member of the committee, followed by the delimiting bit
+        let mut bytes = vec![0u8; committee.len() / 8 + 1];
+        for i in (0..committee.len()).step_by(3) {
+            bytes[i / 8] |= 1 << (i % 8);
+        }
+        bytes[committee.len() / 8] |= 1 << (committee.len() % 8);
+        let attestation = Attestation {
+            aggregation_bits: deserialize(&bytes).unwrap(),
+            data: AttestationData { slot, index: 1, ..Default::default() },
+            ..Default::default()
+        };
+        assert_eq!(
+            get_attesting_indices(
+                &state,
+                &attestation.data,
+                &attestation.aggregation_bits,
+                &context
+            )
+            .unwrap(),
+            get_attesting_indices(
+                &state,
+                &attestation.data,
+                &attestation.aggregation_bits,
+                &uncached_context(),
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_eviction() {
+        let context = uncached_context();
+        let state = state(&context);
+        let cache = CommitteeCache::new(2);
+        let shuffling_at = |epoch| {
+            let seed = get_seed(&state, epoch, DomainType::BeaconAttester, &context);
+            let indices = get_active_validator_indices(&state, epoch);
+            cache.shuffling(epoch, &seed, indices, &context)
+        };
+        let first = shuffling_at(1);
+        shuffling_at(2);
+        // reusing the first shuffling makes the second the least recently used
+        assert!(Arc::ptr_eq(&first, &shuffling_at(1)));
+        shuffling_at(3);
+        assert_eq!(cache.len(), 2);
+        assert!(Arc::ptr_eq(&first, &shuffling_at(1)));
+        assert_eq!(cache.len(), 2);
+    }
+}
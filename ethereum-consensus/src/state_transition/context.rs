@@ -1,24 +1,42 @@
 use crate::{
     altair, bellatrix, capella,
     clock::{self, Clock, SystemTimeProvider},
-    configs::{self, Config},
+    configs::{self, BlobScheduleEntry, Config},
     deneb::{
         self,
-        polynomial_commitments::{kzg_settings_from_json, KzgSettings},
+        polynomial_commitments::{kzg_settings_from_json_for_preset, KzgSettings},
         presets::TRUSTED_SETUP_JSON,
     },
     electra,
     execution_engine::ExecutionEngine,
     networks::Network,
-    phase0,
-    primitives::{Epoch, ExecutionAddress, Gwei, Hash32, Slot, Version, U256},
+    phase0::{self, compute_fork_digest},
+    primitives::{
+        Epoch, ExecutionAddress, ForkDigest, Gwei, Hash32, Root, Slot, Version, FAR_FUTURE_EPOCH,
+        GENESIS_EPOCH, U256,
+    },
+    state_transition::CommitteeCache,
     Error, Fork,
 };
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, OnceLock, PoisonError},
+};
 
 // Controls the default behavior of the execution engine via the `bool` impl of `ExecutionEngine`.
 pub const DEFAULT_EXECUTION_ENGINE_VALIDITY: bool = true;
 
+/// Where a slot falls in the chain under some `Context`, e.g. to enrich logs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotAnnotation {
+    pub epoch: Epoch,
+    pub fork: Fork,
+    pub is_epoch_start: bool,
+    pub sync_committee_period: u64,
+    /// The start of the slot in seconds since the UNIX epoch.
+    pub timestamp: u64,
+}
+
 #[derive(Clone)]
 pub struct Context {
     // phase0 preset
@@ -54,6 +72,7 @@ pub struct Context {
     pub max_attestations: usize,
     pub max_deposits: usize,
     pub max_voluntary_exits: usize,
+    pub target_aggregators_per_committee: usize,
 
     // altair preset
     pub inactivity_penalty_quotient_altair: u64,
@@ -63,6 +82,7 @@ pub struct Context {
     pub epochs_per_sync_committee_period: Epoch,
     pub min_sync_committee_participants: usize,
     pub update_timeout: usize,
+    pub target_aggregators_per_sync_subcommittee: usize,
 
     // bellatrix preset
     pub inactivity_penalty_quotient_bellatrix: u64,
@@ -88,15 +108,16 @@ pub struct Context {
     pub max_effective_balance_electra: Gwei,
     pub min_slashing_penalty_quotient_electra: u64,
     pub whistleblower_reward_quotient_electra: u64,
-    pub pending_balance_deposits_limit: usize,
+    pub pending_deposits_limit: usize,
     pub pending_partial_withdrawals_limit: usize,
     pub pending_consolidations_limit: usize,
     pub max_attester_slashings_electra: usize,
     pub max_attestations_electra: usize,
-    pub max_consolidations: usize,
-    pub max_deposit_receipts_per_payload: usize,
+    pub max_deposit_requests_per_payload: usize,
     pub max_withdrawal_requests_per_payload: usize,
+    pub max_consolidation_requests_per_payload: usize,
     pub max_pending_partials_per_withdrawals_sweep: usize,
+    pub max_pending_deposits_per_epoch: usize,
 
     // config
     pub name: Network,
@@ -142,6 +163,15 @@ pub struct Context {
     pub deposit_network_id: usize,
     pub deposit_contract_address: ExecutionAddress,
 
+    pub max_blobs_per_block_electra: usize,
+    pub blob_schedule: Vec<BlobScheduleEntry>,
+
+    pub number_of_columns: usize,
+    pub number_of_custody_groups: usize,
+    pub data_column_sidecar_subnet_count: usize,
+    pub custody_requirement: usize,
+    pub samples_per_slot: usize,
+
     // Provides an implementation of `execution_engine::ExecutionEngine`.
     #[cfg(feature = "spec-tests")]
     // This field is exposed so that the execution engine behavior can be mocked when testing.
@@ -150,13 +180,40 @@ pub struct Context {
     execution_engine: bool,
 
     pub kzg_settings: Arc<KzgSettings>,
+
+    // Attester shufflings for `get_beacon_committee`, shared with the clones of this context. If
+    // `None`, committees are computed one shuffled index at a time as in the spec.
+    pub committee_cache: Option<Arc<CommitteeCache>>,
+}
+
+// The trusted setup is the same for every network with blobs of the same size, so it is loaded
+// once per process for each size rather than once per `Context`.
+fn kzg_settings_for(field_elements_per_blob: usize) -> Result<Arc<KzgSettings>, Error> {
+    static KZG_SETTINGS: OnceLock<Mutex<HashMap<usize, Arc<KzgSettings>>>> = OnceLock::new();
+    let mut kzg_settings =
+        KZG_SETTINGS.get_or_init(Default::default).lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(settings) = kzg_settings.get(&field_elements_per_blob) {
+        return Ok(settings.clone())
+    }
+    let settings = kzg_settings_from_json_for_preset(TRUSTED_SETUP_JSON, field_elements_per_blob)?;
+    let settings = Arc::new(settings);
+    kzg_settings.insert(field_elements_per_blob, settings.clone());
+    Ok(settings)
 }
 
 impl Context {
     #[cfg(feature = "serde")]
     pub fn try_from_file<P: AsRef<std::path::Path>>(config_file: P) -> Result<Self, Error> {
-        let mut file = std::fs::File::open(config_file)?;
-        let config: Config = serde_yaml::from_reader(&mut file)?;
+        let file = std::fs::File::open(config_file)?;
+        Self::try_from_config_yaml(file)
+    }
+
+    /// Build a `Context` from a network configuration in the `config.yaml` format clients ship,
+    /// on top of the preset named by its `PRESET_BASE`. Keys this crate does not use are ignored
+    /// and a missing key is reported by name.
+    #[cfg(feature = "serde")]
+    pub fn try_from_config_yaml(reader: impl std::io::Read) -> Result<Self, Error> {
+        let config: Config = serde_yaml::from_reader(reader)?;
         let context = match config.preset_base.as_ref() {
             "mainnet" => {
                 let phase0_preset = &phase0::mainnet::PRESET;
@@ -165,7 +222,24 @@ impl Context {
                 let capella_preset = &capella::mainnet::PRESET;
                 let deneb_preset = &deneb::mainnet::PRESET;
                 let electra_preset = &electra::mainnet::PRESET;
-                Self::from(
+                Self::try_from_presets(
+                    phase0_preset,
+                    altair_preset,
+                    bellatrix_preset,
+                    capella_preset,
+                    deneb_preset,
+                    electra_preset,
+                    &config,
+                )?
+            }
+            "gnosis" => {
+                let phase0_preset = &phase0::gnosis::PRESET;
+                let altair_preset = &altair::gnosis::PRESET;
+                let bellatrix_preset = &bellatrix::gnosis::PRESET;
+                let capella_preset = &capella::gnosis::PRESET;
+                let deneb_preset = &deneb::gnosis::PRESET;
+                let electra_preset = &electra::gnosis::PRESET;
+                Self::try_from_presets(
                     phase0_preset,
                     altair_preset,
                     bellatrix_preset,
@@ -173,7 +247,7 @@ impl Context {
                     deneb_preset,
                     electra_preset,
                     &config,
-                )
+                )?
             }
             "minimal" => {
                 let phase0_preset = &phase0::minimal::PRESET;
@@ -182,7 +256,7 @@ impl Context {
                 let capella_preset = &capella::minimal::PRESET;
                 let deneb_preset = &deneb::minimal::PRESET;
                 let electra_preset = &electra::minimal::PRESET;
-                Self::from(
+                Self::try_from_presets(
                     phase0_preset,
                     altair_preset,
                     bellatrix_preset,
@@ -190,7 +264,7 @@ impl Context {
                     deneb_preset,
                     electra_preset,
                     &config,
-                )
+                )?
             }
             other => return Err(Error::UnknownPreset(other.to_string())),
         };
@@ -206,9 +280,30 @@ impl Context {
         electra_preset: &electra::Preset,
         config: &Config,
     ) -> Self {
-        let kzg_settings = kzg_settings_from_json(TRUSTED_SETUP_JSON).unwrap();
+        Self::try_from_presets(
+            phase0_preset,
+            altair_preset,
+            bellatrix_preset,
+            capella_preset,
+            deneb_preset,
+            electra_preset,
+            config,
+        )
+        .expect("the bundled trusted setup matches the blob size of every bundled preset")
+    }
 
-        Self {
+    /// Like [`Context::from`], but returning an error if the KZG trusted setup cannot be loaded
+    /// for the blob size of `deneb_preset`.
+    pub fn try_from_presets(
+        phase0_preset: &phase0::Preset,
+        altair_preset: &altair::Preset,
+        bellatrix_preset: &bellatrix::Preset,
+        capella_preset: &capella::Preset,
+        deneb_preset: &deneb::Preset,
+        electra_preset: &electra::Preset,
+        config: &Config,
+    ) -> Result<Self, Error> {
+        Ok(Self {
             // phase0
             max_committees_per_slot: phase0_preset.max_committees_per_slot,
             target_committee_size: phase0_preset.target_committee_size,
@@ -242,6 +337,7 @@ impl Context {
             max_attestations: phase0_preset.max_attestations,
             max_deposits: phase0_preset.max_deposits,
             max_voluntary_exits: phase0_preset.max_voluntary_exits,
+            target_aggregators_per_committee: phase0_preset.target_aggregators_per_committee,
             // altair
             inactivity_penalty_quotient_altair: altair_preset.inactivity_penalty_quotient_altair,
             min_slashing_penalty_quotient_altair: altair_preset
@@ -252,6 +348,8 @@ impl Context {
             epochs_per_sync_committee_period: altair_preset.epochs_per_sync_committee_period,
             min_sync_committee_participants: altair_preset.min_sync_committee_participants,
             update_timeout: altair_preset.update_timeout,
+            target_aggregators_per_sync_subcommittee: altair_preset
+                .target_aggregators_per_sync_subcommittee,
             // bellatrix
             inactivity_penalty_quotient_bellatrix: bellatrix_preset
                 .inactivity_penalty_quotient_bellatrix,
@@ -279,16 +377,18 @@ impl Context {
                 .min_slashing_penalty_quotient_electra,
             whistleblower_reward_quotient_electra: electra_preset
                 .whistleblower_reward_quotient_electra,
-            pending_balance_deposits_limit: electra_preset.pending_balance_deposits_limit,
+            pending_deposits_limit: electra_preset.pending_deposits_limit,
             pending_partial_withdrawals_limit: electra_preset.pending_partial_withdrawals_limit,
             pending_consolidations_limit: electra_preset.pending_consolidations_limit,
             max_attester_slashings_electra: electra_preset.max_attester_slashings_electra,
             max_attestations_electra: electra_preset.max_attestations_electra,
-            max_consolidations: electra_preset.max_consolidations,
-            max_deposit_receipts_per_payload: electra_preset.max_deposit_receipts_per_payload,
+            max_deposit_requests_per_payload: electra_preset.max_deposit_requests_per_payload,
             max_withdrawal_requests_per_payload: electra_preset.max_withdrawal_requests_per_payload,
+            max_consolidation_requests_per_payload: electra_preset
+                .max_consolidation_requests_per_payload,
             max_pending_partials_per_withdrawals_sweep: electra_preset
                 .max_pending_partials_per_withdrawals_sweep,
+            max_pending_deposits_per_epoch: electra_preset.max_pending_deposits_per_epoch,
             // config
             name: config.name.clone(),
             terminal_total_difficulty: config.terminal_total_difficulty,
@@ -326,9 +426,17 @@ impl Context {
             deposit_chain_id: config.deposit_chain_id,
             deposit_network_id: config.deposit_network_id,
             deposit_contract_address: config.deposit_contract_address.clone(),
+            max_blobs_per_block_electra: config.max_blobs_per_block_electra,
+            blob_schedule: config.blob_schedule.clone(),
+            number_of_columns: config.number_of_columns,
+            number_of_custody_groups: config.number_of_custody_groups,
+            data_column_sidecar_subnet_count: config.data_column_sidecar_subnet_count,
+            custody_requirement: config.custody_requirement,
+            samples_per_slot: config.samples_per_slot,
             execution_engine: DEFAULT_EXECUTION_ENGINE_VALIDITY,
-            kzg_settings: Arc::new(kzg_settings),
-        }
+            kzg_settings: kzg_settings_for(deneb_preset.field_elements_per_blob)?,
+            committee_cache: Some(Default::default()),
+        })
     }
 
     pub fn for_mainnet() -> Self {
@@ -426,15 +534,60 @@ impl Context {
         )
     }
 
+    pub fn for_hoodi() -> Self {
+        let config = &configs::hoodi::config();
+        let phase0_preset = &phase0::mainnet::PRESET;
+        let altair_preset = &altair::mainnet::PRESET;
+        let bellatrix_preset = &bellatrix::mainnet::PRESET;
+        let capella_preset = &capella::mainnet::PRESET;
+        let deneb_preset = &deneb::mainnet::PRESET;
+        let electra_preset = &electra::mainnet::PRESET;
+        Self::from(
+            phase0_preset,
+            altair_preset,
+            bellatrix_preset,
+            capella_preset,
+            deneb_preset,
+            electra_preset,
+            config,
+        )
+    }
+
+    pub fn for_gnosis() -> Self {
+        let config = &configs::gnosis::config();
+        let phase0_preset = &phase0::gnosis::PRESET;
+        let altair_preset = &altair::gnosis::PRESET;
+        let bellatrix_preset = &bellatrix::gnosis::PRESET;
+        let capella_preset = &capella::gnosis::PRESET;
+        let deneb_preset = &deneb::gnosis::PRESET;
+        let electra_preset = &electra::gnosis::PRESET;
+        Self::from(
+            phase0_preset,
+            altair_preset,
+            bellatrix_preset,
+            capella_preset,
+            deneb_preset,
+            electra_preset,
+            config,
+        )
+    }
+
     pub fn fork_for(&self, slot: Slot) -> Fork {
-        let epoch = slot / self.slots_per_epoch;
-        if epoch >= self.deneb_fork_epoch {
+        self.fork_at_epoch(slot / self.slots_per_epoch)
+    }
+
+    /// The fork active at `epoch`; a fork scheduled at `FAR_FUTURE_EPOCH` is never active.
+    pub fn fork_at_epoch(&self, epoch: Epoch) -> Fork {
+        let is_active = |fork_epoch: Epoch| fork_epoch != FAR_FUTURE_EPOCH && epoch >= fork_epoch;
+        if is_active(self.electra_fork_epoch) {
+            Fork::Electra
+        } else if is_active(self.deneb_fork_epoch) {
             Fork::Deneb
-        } else if epoch >= self.capella_fork_epoch {
+        } else if is_active(self.capella_fork_epoch) {
             Fork::Capella
-        } else if epoch >= self.bellatrix_fork_epoch {
+        } else if is_active(self.bellatrix_fork_epoch) {
             Fork::Bellatrix
-        } else if epoch >= self.altair_fork_epoch {
+        } else if is_active(self.altair_fork_epoch) {
             Fork::Altair
         } else {
             Fork::Phase0
@@ -448,15 +601,84 @@ impl Context {
             Fork::Bellatrix => self.bellatrix_fork_version,
             Fork::Capella => self.capella_fork_version,
             Fork::Deneb => self.deneb_fork_version,
+            Fork::Electra => self.electra_fork_version,
+        }
+    }
+
+    pub fn fork_version_at_epoch(&self, epoch: Epoch) -> Version {
+        self.fork_version_for(self.fork_at_epoch(epoch))
+    }
+
+    /// The epoch `fork` is scheduled at, `FAR_FUTURE_EPOCH` if it is not scheduled.
+    pub fn fork_epoch(&self, fork: Fork) -> Epoch {
+        match fork {
+            Fork::Phase0 => GENESIS_EPOCH,
+            Fork::Altair => self.altair_fork_epoch,
+            Fork::Bellatrix => self.bellatrix_fork_epoch,
+            Fork::Capella => self.capella_fork_epoch,
+            Fork::Deneb => self.deneb_fork_epoch,
+            Fork::Electra => self.electra_fork_epoch,
         }
     }
 
+    /// The first slot of `fork`, saturating for a fork that is not scheduled.
+    pub fn fork_start_slot(&self, fork: Fork) -> Slot {
+        self.fork_epoch(fork).saturating_mul(self.slots_per_epoch)
+    }
+
+    /// The digest of `fork` on the chain with `genesis_validators_root`, as used in gossip topics
+    /// and ENRs.
+    pub fn fork_digest(
+        &self,
+        fork: Fork,
+        genesis_validators_root: Root,
+    ) -> Result<ForkDigest, Error> {
+        compute_fork_digest(self.fork_version_for(fork), genesis_validators_root)
+    }
+
+    /// The digest of the fork active at `epoch` on the chain with `genesis_validators_root`.
+    pub fn fork_digest_at(
+        &self,
+        epoch: Epoch,
+        genesis_validators_root: Root,
+    ) -> Result<ForkDigest, Error> {
+        self.fork_digest(self.fork_at_epoch(epoch), genesis_validators_root)
+    }
+
+    /// The start of `slot` in seconds since the UNIX epoch, for a chain starting at
+    /// `genesis_time`, or an error if it does not fit in a `u64`.
+    pub fn timestamp_at_slot(&self, slot: Slot, genesis_time: u64) -> Result<u64, Error> {
+        slot.checked_mul(self.seconds_per_slot)
+            .and_then(|offset| genesis_time.checked_add(offset))
+            .ok_or(Error::Overflow)
+    }
+
+    /// The slot in progress at `timestamp`, or `None` if it is before `genesis_time`.
+    pub fn slot_at_timestamp(&self, timestamp: u64, genesis_time: u64) -> Option<Slot> {
+        let delta = timestamp.checked_sub(genesis_time)?;
+        Some(delta / self.seconds_per_slot)
+    }
+
+    /// The [`SlotAnnotation`] of `slot` for a chain starting at `genesis_time`.
+    pub fn annotate(&self, slot: Slot, genesis_time: u64) -> Result<SlotAnnotation, Error> {
+        let epoch = slot / self.slots_per_epoch;
+        Ok(SlotAnnotation {
+            epoch,
+            fork: self.fork_for(slot),
+            is_epoch_start: slot % self.slots_per_epoch == 0,
+            sync_committee_period: epoch / self.epochs_per_sync_committee_period,
+            timestamp: self.timestamp_at_slot(slot, genesis_time)?,
+        })
+    }
+
     pub fn genesis_time(&self) -> Result<u64, Error> {
         match &self.name {
             Network::Mainnet => Ok(crate::clock::MAINNET_GENESIS_TIME),
             Network::Sepolia => Ok(crate::clock::SEPOLIA_GENESIS_TIME),
             Network::Goerli => Ok(crate::clock::GOERLI_GENESIS_TIME),
             Network::Holesky => Ok(crate::clock::HOLESKY_GENESIS_TIME),
+            Network::Hoodi => Ok(crate::clock::HOODI_GENESIS_TIME),
+            Network::Gnosis => Ok(crate::clock::GNOSIS_GENESIS_TIME),
             name => Err(Error::UnknownGenesisTime(name.to_string())),
         }
     }
@@ -467,6 +689,8 @@ impl Context {
             Network::Sepolia => Some(clock::for_sepolia()),
             Network::Goerli => Some(clock::for_goerli()),
             Network::Holesky => Some(clock::for_holesky()),
+            Network::Hoodi => Some(clock::for_hoodi()),
+            Network::Gnosis => Some(clock::for_gnosis()),
             _ => None,
         }
     }
@@ -483,3 +707,228 @@ impl Context {
         self.execution_engine
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        clock::MAINNET_GENESIS_TIME,
+        fixtures::phase0::{genesis_state, signed_block},
+        state_transition::minimal::Executor,
+        types::{BeaconState, SignedBeaconBlock},
+    };
+
+    #[test]
+    fn test_mainnet_slot_timestamps() {
+        let context = Context::for_mainnet();
+        let genesis_time = context.genesis_time().unwrap();
+        assert_eq!(genesis_time, MAINNET_GENESIS_TIME);
+        // genesis, the merge block and the first slots of capella and deneb
+        let pairs =
+            [(0, 1606824023), (4700013, 1663224179), (6209536, 1681338455), (8626176, 1710338135)];
+        for (slot, timestamp) in pairs {
+            assert_eq!(context.timestamp_at_slot(slot, genesis_time).unwrap(), timestamp);
+            assert_eq!(context.slot_at_timestamp(timestamp, genesis_time), Some(slot));
+            // floor semantics within the slot
+            let last_second = timestamp + context.seconds_per_slot - 1;
+            assert_eq!(context.slot_at_timestamp(last_second, genesis_time), Some(slot));
+        }
+        assert_eq!(context.slot_at_timestamp(genesis_time - 1, genesis_time), None);
+        assert_eq!(context.slot_at_timestamp(0, genesis_time), None);
+
+        let annotation = context.annotate(8626176, genesis_time).unwrap();
+        assert_eq!(
+            annotation,
+            SlotAnnotation {
+                epoch: 269568,
+                fork: Fork::Deneb,
+                is_epoch_start: true,
+                sync_committee_period: 269568 / 256,
+                timestamp: 1710338135,
+            }
+        );
+        let annotation = context.annotate(6209535, genesis_time).unwrap();
+        assert_eq!(annotation.fork, Fork::Bellatrix);
+        assert!(!annotation.is_epoch_start);
+
+        // slots too far in the future for a timestamp
+        assert!(matches!(context.timestamp_at_slot(u64::MAX, genesis_time), Err(Error::Overflow)));
+        assert!(matches!(context.timestamp_at_slot(0, u64::MAX), Ok(u64::MAX)));
+        assert!(matches!(context.annotate(u64::MAX / 12, genesis_time), Err(Error::Overflow)));
+    }
+
+    #[test]
+    fn test_mainnet_fork_schedule() {
+        let context = Context::for_mainnet();
+        let genesis_validators_root = Root::try_from(
+            [
+                75, 54, 61, 185, 78, 40, 97, 32, 215, 110, 185, 5, 52, 15, 221, 78, 84, 191, 233,
+                240, 107, 243, 63, 246, 207, 90, 210, 127, 81, 27, 254, 149,
+            ]
+            .as_ref(),
+        )
+        .unwrap();
+        let schedule = [
+            (Fork::Phase0, 0, [0xb5, 0x30, 0x3f, 0x2a]),
+            (Fork::Altair, 74240, [0xaf, 0xca, 0xab, 0xa0]),
+            (Fork::Bellatrix, 144896, [0x4a, 0x26, 0xc5, 0x8b]),
+            (Fork::Capella, 194048, [0xbb, 0xa4, 0xda, 0x96]),
+            (Fork::Deneb, 269568, [0x6a, 0x95, 0xa1, 0xa9]),
+        ];
+        for (i, (fork, epoch, digest)) in schedule.into_iter().enumerate() {
+            assert_eq!(context.fork_epoch(fork), epoch);
+            assert_eq!(context.fork_at_epoch(epoch), fork);
+            assert_eq!(context.fork_for(context.fork_start_slot(fork)), fork);
+            assert_eq!(context.fork_version_at_epoch(epoch), [i as u8, 0, 0, 0]);
+            assert_eq!(context.fork_digest_at(epoch, genesis_validators_root).unwrap(), digest);
+            if epoch > 0 {
+                let (previous, _, previous_digest) = schedule[i - 1];
+                assert_eq!(context.fork_at_epoch(epoch - 1), previous);
+                assert_eq!(context.fork_for(context.fork_start_slot(fork) - 1), previous);
+                let digest = context.fork_digest_at(epoch - 1, genesis_validators_root).unwrap();
+                assert_eq!(digest, previous_digest);
+            }
+        }
+
+        // electra is not scheduled on mainnet here
+        assert_eq!(context.fork_epoch(Fork::Electra), FAR_FUTURE_EPOCH);
+        assert_eq!(context.fork_start_slot(Fork::Electra), u64::MAX);
+        assert_eq!(context.fork_at_epoch(FAR_FUTURE_EPOCH), Fork::Deneb);
+        assert_eq!(context.fork_for(u64::MAX), Fork::Deneb);
+        assert_eq!(context.fork_version_at_epoch(FAR_FUTURE_EPOCH), [4, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_contexts_do_not_contaminate_each_other() {
+        let mainnet = Context::for_mainnet();
+        let minimal = Context::for_minimal();
+        let mut devnet = Context::for_minimal();
+        devnet.altair_fork_version = [1, 0, 0, 0x42];
+        devnet.altair_fork_epoch = 0;
+        devnet.electra_fork_epoch = 1;
+        assert!(Arc::ptr_eq(&minimal.kzg_settings, &devnet.kzg_settings));
+
+        let genesis_validators_root = genesis_state().genesis_validators_root;
+        let contexts = [&mainnet, &minimal, &devnet];
+        let expected_digests = contexts.map(|context| {
+            compute_fork_digest(context.altair_fork_version, genesis_validators_root).unwrap()
+        });
+        assert_ne!(expected_digests[1], expected_digests[2]);
+
+        let block = SignedBeaconBlock::Phase0(signed_block());
+        for _ in 0..2 {
+            // the block is valid on a chain still in phase0, but not where altair starts at genesis
+            for (context, fork) in [(&minimal, Fork::Phase0), (&devnet, Fork::Altair)] {
+                assert_eq!(context.fork_for(1), fork);
+                let mut executor =
+                    Executor::new(BeaconState::Phase0(genesis_state()), context.clone());
+                executor.process_slots(1).unwrap();
+                assert_eq!(executor.state.version(), fork);
+                assert_eq!(executor.apply_block(&block).is_ok(), fork == Fork::Phase0);
+            }
+            for (context, expected) in contexts.iter().zip(&expected_digests) {
+                let digest = context.fork_digest(Fork::Altair, genesis_validators_root).unwrap();
+                assert_eq!(&digest, expected);
+            }
+        }
+        assert_eq!(mainnet.fork_for(0), Fork::Phase0);
+        assert_eq!(devnet.fork_for(devnet.slots_per_epoch), Fork::Electra);
+
+        // digests follow the versions of each context
+        let mut clone = devnet.clone();
+        clone.altair_fork_version = minimal.altair_fork_version;
+        let digest = clone.fork_digest(Fork::Altair, genesis_validators_root).unwrap();
+        assert_eq!(digest, expected_digests[1]);
+    }
+
+    #[test]
+    fn test_kzg_settings_for_unknown_blob_size() {
+        let deneb_preset = deneb::Preset {
+            field_elements_per_blob: 3,
+            max_blob_commitments_per_block: 16,
+            max_blobs_per_block: 6,
+        };
+        let result = Context::try_from_presets(
+            &phase0::minimal::PRESET,
+            &altair::minimal::PRESET,
+            &bellatrix::minimal::PRESET,
+            &capella::minimal::PRESET,
+            &deneb_preset,
+            &electra::minimal::PRESET,
+            &configs::minimal::config(),
+        );
+        assert!(matches!(result, Err(Error::PolynomialCommitment(_))));
+        let minimal = Context::for_minimal();
+        assert!(Arc::ptr_eq(&minimal.kzg_settings, &Context::for_minimal().kzg_settings));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_context_from_config_yaml() {
+        let config_yaml = include_str!("../configs/mainnet.yaml");
+        let context = Context::try_from_config_yaml(config_yaml.as_bytes()).unwrap();
+        let mainnet = Context::for_mainnet();
+        macro_rules! assert_fields_eq {
+            ($($field:ident),+) => {
+                $(assert_eq!(context.$field, mainnet.$field, stringify!($field));)+
+            };
+        }
+        assert_fields_eq!(
+            name,
+            slots_per_epoch,
+            max_blob_commitments_per_block,
+            terminal_total_difficulty,
+            terminal_block_hash,
+            terminal_block_hash_activation_epoch,
+            min_genesis_active_validator_count,
+            min_genesis_time,
+            genesis_fork_version,
+            genesis_delay,
+            altair_fork_version,
+            altair_fork_epoch,
+            bellatrix_fork_version,
+            bellatrix_fork_epoch,
+            capella_fork_version,
+            capella_fork_epoch,
+            deneb_fork_version,
+            deneb_fork_epoch,
+            electra_fork_version,
+            electra_fork_epoch,
+            seconds_per_slot,
+            seconds_per_eth1_block,
+            min_validator_withdrawability_delay,
+            shard_committee_period,
+            eth1_follow_distance,
+            inactivity_score_bias,
+            inactivity_score_recovery_rate,
+            ejection_balance,
+            min_per_epoch_churn_limit,
+            max_per_epoch_activation_churn_limit,
+            min_per_epoch_churn_limit_electra,
+            max_per_epoch_activation_exit_churn_limit,
+            churn_limit_quotient,
+            proposer_score_boost,
+            deposit_chain_id,
+            deposit_network_id,
+            deposit_contract_address,
+            max_blobs_per_block_electra,
+            blob_schedule,
+            number_of_columns,
+            custody_requirement
+        );
+
+        let missing_key = config_yaml.replace("SECONDS_PER_SLOT: 12\n", "");
+        let err = Context::try_from_config_yaml(missing_key.as_bytes()).err().unwrap();
+        assert!(err.to_string().contains("SECONDS_PER_SLOT"), "{err}");
+        let unknown_preset = config_yaml.replace("'mainnet'", "'mainnet-fork'");
+        assert!(matches!(
+            Context::try_from_config_yaml(unknown_preset.as_bytes()),
+            Err(Error::UnknownPreset(preset)) if preset == "mainnet-fork"
+        ));
+
+        let hoodi = Context::for_hoodi();
+        assert_eq!(hoodi.genesis_time().unwrap(), crate::clock::HOODI_GENESIS_TIME);
+        assert_eq!(hoodi.genesis_time().unwrap(), hoodi.min_genesis_time + hoodi.genesis_delay);
+        assert_eq!(Context::try_from(Network::Hoodi).unwrap().deposit_chain_id, 560048);
+    }
+}
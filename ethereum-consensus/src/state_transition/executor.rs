@@ -1,9 +1,336 @@
 use crate::{
-    altair, bellatrix, capella, deneb, phase0,
-    state_transition::{Context, Result, Validation},
-    types::{BeaconState, SignedBeaconBlock},
+    altair, bellatrix, capella, deneb, electra,
+    error::WrongFork,
+    graffiti::GraffitiProvider,
+    phase0::{self, AttesterSlashing, Checkpoint, ProposerSlashing},
+    primitives::{CommitteeIndex, Gwei, Root, Slot, ValidatorIndex},
+    rewards::sync_participant_reward,
+    ssz::prelude::*,
+    state_transition::{
+        collect_block_signatures, compute_shuffling, shuffling_decision_root, BlobStore,
+        CachedState, Context, DecompressedPubkeyCache, PagedState, PubkeyCacheMode, Result,
+        ShufflingCache, StateRootCache, Validation,
+    },
+    types::{BeaconBlock, BeaconBlockBody, BeaconState, SignedBeaconBlock},
     Error, Fork,
 };
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+// Advance `state` to `slot`, upgrading it at each fork boundary scheduled in `context` along
+// the way, with the roots of the state computed through `state_root_cache`.
+fn process_slots_with_upgrades<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    slot: Slot,
+    state_root_cache: &mut StateRootCache,
+    context: &Context,
+) -> Result<()> {
+    if state.slot() >= slot {
+        return Err(Error::TransitionToPreviousSlot { requested: slot, current: state.slot() })
+    }
+    loop {
+        match state {
+            BeaconState::Phase0(inner) => {
+                let fork_slot = context.fork_start_slot(Fork::Altair);
+                if slot < fork_slot {
+                    if inner.slot < slot {
+                        phase0::process_slots_with_field_roots(
+                            inner,
+                            slot,
+                            |state| state_root_cache.field_roots(state),
+                            context,
+                        )?;
+                    }
+                    return Ok(())
+                }
+                if inner.slot < fork_slot {
+                    phase0::process_slots_with_field_roots(
+                        inner,
+                        fork_slot,
+                        |state| state_root_cache.field_roots(state),
+                        context,
+                    )?;
+                }
+                let upgraded = altair::upgrade_to_altair(inner, context)?;
+                *state = BeaconState::Altair(upgraded);
+            }
+            BeaconState::Altair(inner) => {
+                let fork_slot = context.fork_start_slot(Fork::Bellatrix);
+                if slot < fork_slot {
+                    if inner.slot < slot {
+                        altair::process_slots_with_field_roots(
+                            inner,
+                            slot,
+                            |state| state_root_cache.field_roots(state),
+                            context,
+                        )?;
+                    }
+                    return Ok(())
+                }
+                if inner.slot < fork_slot {
+                    altair::process_slots_with_field_roots(
+                        inner,
+                        fork_slot,
+                        |state| state_root_cache.field_roots(state),
+                        context,
+                    )?;
+                }
+                let upgraded = bellatrix::upgrade_to_bellatrix(inner, context);
+                *state = BeaconState::Bellatrix(upgraded);
+            }
+            BeaconState::Bellatrix(inner) => {
+                let fork_slot = context.fork_start_slot(Fork::Capella);
+                if slot < fork_slot {
+                    if inner.slot < slot {
+                        bellatrix::process_slots_with_field_roots(
+                            inner,
+                            slot,
+                            |state| state_root_cache.field_roots(state),
+                            context,
+                        )?;
+                    }
+                    return Ok(())
+                }
+                if inner.slot < fork_slot {
+                    bellatrix::process_slots_with_field_roots(
+                        inner,
+                        fork_slot,
+                        |state| state_root_cache.field_roots(state),
+                        context,
+                    )?;
+                }
+                let upgraded = capella::upgrade_to_capella(inner, context);
+                *state = BeaconState::Capella(upgraded);
+            }
+            BeaconState::Capella(inner) => {
+                let fork_slot = context.fork_start_slot(Fork::Deneb);
+                if slot < fork_slot {
+                    if inner.slot < slot {
+                        capella::process_slots_with_field_roots(
+                            inner,
+                            slot,
+                            |state| state_root_cache.field_roots(state),
+                            context,
+                        )?;
+                    }
+                    return Ok(())
+                }
+                if inner.slot < fork_slot {
+                    capella::process_slots_with_field_roots(
+                        inner,
+                        fork_slot,
+                        |state| state_root_cache.field_roots(state),
+                        context,
+                    )?;
+                }
+                let upgraded = deneb::upgrade_to_deneb(inner, context);
+                *state = BeaconState::Deneb(upgraded);
+            }
+            BeaconState::Deneb(inner) => {
+                let fork_slot = context.fork_start_slot(Fork::Electra);
+                if slot < fork_slot {
+                    if inner.slot < slot {
+                        deneb::process_slots_with_field_roots(
+                            inner,
+                            slot,
+                            |state| state_root_cache.field_roots(state),
+                            context,
+                        )?;
+                    }
+                    return Ok(())
+                }
+                if inner.slot < fork_slot {
+                    deneb::process_slots_with_field_roots(
+                        inner,
+                        fork_slot,
+                        |state| state_root_cache.field_roots(state),
+                        context,
+                    )?;
+                }
+                let upgraded = electra::upgrade_to_electra(inner, context)?;
+                *state = BeaconState::Electra(upgraded);
+            }
+            BeaconState::Electra(inner) => {
+                if inner.slot < slot {
+                    electra::process_slots_with_field_roots(
+                        inner,
+                        slot,
+                        |state| state_root_cache.field_roots(state),
+                        context,
+                    )?;
+                }
+                return Ok(())
+            }
+        }
+    }
+}
+
+/// A copy of an executor's state advanced ahead of time to the next slot, along with a copy of
+/// the shuffling cache with the shuffling for the epoch of that slot.
+///
+/// The copy is produced by `Executor::precompute_next_slot` and either handed back with
+/// `Executor::promote_advanced_state` if the head has not changed in the meantime, or dropped.
+#[derive(Debug, Clone)]
+pub struct AdvancedState<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+> {
+    pub state: BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    pub shuffling_cache: ShufflingCache,
+    /// The root of the state the advance started from.
+    pub parent_state_root: Root,
+}
+
+/// Which caches an `Executor` keeps and how much they hold, e.g. to trade speed for memory on
+/// constrained hosts. Values are computed directly whenever their cache is disabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// The most shufflings kept, or `None` for no bound.
+    pub shuffling_entries: Option<usize>,
+    pub pubkey_cache: PubkeyCacheMode,
+    /// Whether the total active balance of the state is kept for the rest of its epoch.
+    pub total_active_balance: bool,
+    /// Whether the merkle trees of the largest fields of the state are kept between computations
+    /// of its root.
+    pub state_root_cache: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            shuffling_entries: None,
+            pubkey_cache: Default::default(),
+            total_active_balance: true,
+            state_root_cache: true,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Every cache disabled.
+    pub const fn disabled() -> Self {
+        Self {
+            shuffling_entries: Some(0),
+            pubkey_cache: PubkeyCacheMode::Disabled,
+            total_active_balance: false,
+            state_root_cache: false,
+        }
+    }
+}
+
+/// Evidence of slashable behaviour carried by an imported block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SlashableEvidence<
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+> {
+    Proposer(ProposerSlashing),
+    Attester(AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>),
+    ElectraAttester(electra::AttesterSlashing<MAX_VALIDATORS_PER_SLOT>),
+}
+
+/// What importing a block with `Executor::apply_block_detailed` did to the state, gathered while
+/// processing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockImportOutcome<
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+> {
+    pub post_state_root: Root,
+    /// The new finalized checkpoint, if finality advanced while processing the slots up to the
+    /// block.
+    pub finality_advanced: Option<Checkpoint>,
+    /// The rewards the block paid its proposer: the change in the proposer's balance over the
+    /// block, net of its withdrawals, deposits, slashing penalty and own sync committee duties.
+    pub proposer_reward: Gwei,
+    pub detected_slashable:
+        Vec<SlashableEvidence<MAX_VALIDATORS_PER_COMMITTEE, MAX_VALIDATORS_PER_SLOT>>,
+    pub blob_commitment_count: usize,
+}
+
+/// How `Executor::apply_blocks` replays a chain segment.
+pub struct ReplayOptions<'a> {
+    pub validation: Validation,
+    /// Whether the state root of each block is checked against the post-state. Without the
+    /// check, the root of the post-state is assumed to be the one the block commits to.
+    pub verify_state_roots: bool,
+    /// Called after each block is applied with its slot and the time since the replay started.
+    pub on_block: Option<&'a mut dyn FnMut(Slot, Duration)>,
+}
+
+impl Default for ReplayOptions<'_> {
+    fn default() -> Self {
+        Self { validation: Validation::Enabled, verify_state_roots: true, on_block: None }
+    }
+}
+
+/// What `Executor::apply_blocks` did over a chain segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplaySummary {
+    pub blocks_applied: usize,
+    /// The slots processed without a block on the way to the blocks of the segment.
+    pub empty_slots_processed: u64,
+    pub total_time: Duration,
+}
 
 pub struct Executor<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
@@ -17,6 +344,9 @@ pub struct Executor<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
     const MAX_BYTES_PER_TRANSACTION: usize,
     const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
     const MAX_PROPOSER_SLASHINGS: usize,
@@ -28,7 +358,7 @@ pub struct Executor<
     const MAX_BLS_TO_EXECUTION_CHANGES: usize,
     const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
 > {
-    pub state: BeaconState<
+    pub state: CachedState<
         SLOTS_PER_HISTORICAL_ROOT,
         HISTORICAL_ROOTS_LIMIT,
         ETH1_DATA_VOTES_BOUND,
@@ -40,8 +370,15 @@ pub struct Executor<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
     >,
     pub context: Context,
+    pub shuffling_cache: ShufflingCache,
+    pub pubkey_cache: DecompressedPubkeyCache,
+    pub state_root_cache: StateRootCache,
+    cache_config: CacheConfig,
 }
 
 impl<
@@ -56,6 +393,9 @@ impl<
         const SYNC_COMMITTEE_SIZE: usize,
         const BYTES_PER_LOGS_BLOOM: usize,
         const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
         const MAX_BYTES_PER_TRANSACTION: usize,
         const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
         const MAX_PROPOSER_SLASHINGS: usize,
@@ -66,6 +406,13 @@ impl<
         const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     Executor<
         SLOTS_PER_HISTORICAL_ROOT,
@@ -79,6 +426,9 @@ impl<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
         MAX_BYTES_PER_TRANSACTION,
         MAX_TRANSACTIONS_PER_PAYLOAD,
         MAX_PROPOSER_SLASHINGS,
@@ -89,6 +439,13 @@ impl<
         MAX_WITHDRAWALS_PER_PAYLOAD,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     pub fn new(
@@ -104,10 +461,505 @@ impl<
             SYNC_COMMITTEE_SIZE,
             BYTES_PER_LOGS_BLOOM,
             MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
         >,
         context: Context,
     ) -> Self {
-        Self { state, context }
+        Self::with_cache_config(state, context, CacheConfig::default())
+    }
+
+    pub fn with_cache_config(
+        state: BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+        context: Context,
+        cache_config: CacheConfig,
+    ) -> Self {
+        let mut state = CachedState::new(state);
+        state.set_cache_total_active_balance(cache_config.total_active_balance);
+        Self {
+            state,
+            context,
+            shuffling_cache: ShufflingCache::new(cache_config.shuffling_entries),
+            pubkey_cache: DecompressedPubkeyCache::new(cache_config.pubkey_cache),
+            state_root_cache: StateRootCache::new(cache_config.state_root_cache),
+            cache_config,
+        }
+    }
+
+    /// An executor over the state of `state`, which must not have any field spilled: pin the
+    /// fields the executor needs with `PagedState::pin` beforehand.
+    pub fn from_paged_state<S: BlobStore>(
+        state: PagedState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+            S,
+        >,
+        context: Context,
+    ) -> Result<Self> {
+        Ok(Self::new(state.into_pinned_state()?, context))
+    }
+
+    pub fn cache_config(&self) -> CacheConfig {
+        self.cache_config
+    }
+
+    /// Approximate bytes held by the caches of the executor, not counting the state itself.
+    pub fn current_memory_estimate(&self) -> usize {
+        self.shuffling_cache.memory_estimate() +
+            self.pubkey_cache.memory_estimate() +
+            self.state_root_cache.memory_estimate()
+    }
+
+    // Drop the caches made stale by edits to the state since they were last used.
+    fn sync_caches(&mut self) {
+        let invalidation = self.state.take_invalidation();
+        if invalidation.shufflings {
+            self.shuffling_cache.clear();
+        }
+        if invalidation.pubkeys {
+            self.pubkey_cache.clear();
+        }
+    }
+
+    /// Decompress the public keys of the validators in the current state that are not yet in the
+    /// public key cache, subject to the mode of the cache.
+    pub fn warm_pubkey_cache(&mut self) -> Result<()> {
+        self.sync_caches();
+        let epoch = phase0::compute_epoch_at_slot(self.state.slot(), &self.context);
+        self.pubkey_cache.warm(self.state.validators(), epoch)
+    }
+
+    /// Replace the shuffling cache with one restored from `bytes` (see
+    /// `ShufflingCache::serialize`), if every entry matches the current state.
+    pub fn prewarm_shuffling_cache(&mut self, bytes: &[u8]) -> Result<()> {
+        self.sync_caches();
+        let cache = ShufflingCache::deserialize(bytes, &self.context)?;
+        cache.verify(self.state.inner(), &self.context)?;
+        self.shuffling_cache = ShufflingCache::new(self.cache_config.shuffling_entries);
+        for (decision_root, shuffling) in cache.into_shufflings() {
+            self.shuffling_cache.insert(decision_root, shuffling);
+        }
+        Ok(())
+    }
+
+    /// Get the beacon committee at `slot` with `index`, computing and caching the shuffling for
+    /// the epoch of `slot` if it is not already in the shuffling cache.
+    pub fn beacon_committee(
+        &mut self,
+        slot: Slot,
+        index: CommitteeIndex,
+    ) -> Result<Vec<ValidatorIndex>> {
+        self.sync_caches();
+        let epoch = phase0::compute_epoch_at_slot(slot, &self.context);
+        let current_epoch = phase0::compute_epoch_at_slot(self.state.slot(), &self.context);
+        if epoch + 1 < current_epoch || epoch > current_epoch + 1 {
+            return Err(Error::SlotOutOfRange {
+                requested: slot,
+                lower_bound: current_epoch.saturating_sub(1) * self.context.slots_per_epoch,
+                upper_bound: (current_epoch + 2) * self.context.slots_per_epoch - 1,
+            })
+        }
+        let decision_root = shuffling_decision_root(self.state.inner(), epoch, &self.context);
+        if let Some(shuffling) = self.shuffling_cache.get(epoch, decision_root) {
+            return shuffling
+                .beacon_committee(slot, index, &self.context)
+                .map(|committee| committee.to_vec())
+        }
+        let shuffling = compute_shuffling(self.state.inner(), epoch, &self.context);
+        let committee = shuffling.beacon_committee(slot, index, &self.context)?.to_vec();
+        self.shuffling_cache.insert(decision_root, shuffling);
+        Ok(committee)
+    }
+
+    /// Advance the state to `slot`, upgrading it at any fork boundary scheduled in the context.
+    pub fn process_slots(&mut self, slot: Slot) -> Result<()> {
+        process_slots_with_upgrades(
+            self.state.transition_mut(),
+            slot,
+            &mut self.state_root_cache,
+            &self.context,
+        )
+    }
+
+    /// Advance a copy of the current state to the next slot, e.g. ahead of a proposal in that
+    /// slot, leaving the executor's own state untouched.
+    ///
+    /// Any epoch processing (and fork upgrade) due at the next slot is done here, and the
+    /// shuffling for the epoch of the next slot is added to the copied shuffling cache. The state
+    /// is a full copy as `BeaconState` does not share structure between clones.
+    pub fn precompute_next_slot(
+        &self,
+    ) -> Result<
+        AdvancedState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    > {
+        // advance with a copy of the state root cache so the unchanged fields are not hashed again
+        let mut state_root_cache = self.state_root_cache.clone();
+        let parent_slot = self.state.slot();
+        let mut state = self.state.inner().clone();
+        process_slots_with_upgrades(
+            &mut state,
+            parent_slot + 1,
+            &mut state_root_cache,
+            &self.context,
+        )?;
+        // processing the slot recorded the root of the state it started from
+        let parent_state_root =
+            state.state_roots()[(parent_slot % SLOTS_PER_HISTORICAL_ROOT as u64) as usize];
+        let mut shuffling_cache = if self.state.pending_invalidation().shufflings {
+            ShufflingCache::new(self.cache_config.shuffling_entries)
+        } else {
+            self.shuffling_cache.clone()
+        };
+        let epoch = phase0::compute_epoch_at_slot(state.slot(), &self.context);
+        let decision_root = shuffling_decision_root(&state, epoch, &self.context);
+        if shuffling_cache.get(epoch, decision_root).is_none() {
+            shuffling_cache.insert(decision_root, compute_shuffling(&state, epoch, &self.context));
+        }
+        Ok(AdvancedState { state, shuffling_cache, parent_state_root })
+    }
+
+    /// Replace the current state (and shuffling cache) with `advanced`, if it was advanced from
+    /// the current state; otherwise the head has moved on and `advanced` is stale.
+    pub fn promote_advanced_state(
+        &mut self,
+        advanced: AdvancedState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    ) -> Result<()> {
+        let state_root = self.state_root()?;
+        if state_root != advanced.parent_state_root {
+            return Err(Error::StaleAdvancedState {
+                expected: advanced.parent_state_root,
+                found: state_root,
+            })
+        }
+        let invalidation = self.state.take_invalidation();
+        self.state = CachedState::new(advanced.state);
+        self.state.set_cache_total_active_balance(self.cache_config.total_active_balance);
+        self.state.invalidate(invalidation);
+        self.shuffling_cache = advanced.shuffling_cache;
+        Ok(())
+    }
+
+    /// Produce the (unsigned) block at `slot` on top of the current state with the given `body`.
+    ///
+    /// The state is advanced to `slot` first, so if `slot` is the first slot of a new fork the
+    /// block is built against the upgraded state and `body` must be from the new fork. The
+    /// proposer index, parent root and state root are filled in; the executor's own state is
+    /// left untouched.
+    pub fn produce_block(
+        &self,
+        slot: Slot,
+        body: BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    ) -> Result<
+        BeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        self.produce_block_inner(slot, body, None)
+    }
+
+    /// Like [`Self::produce_block`], with the graffiti of `body` replaced by the one `graffiti`
+    /// provides for the proposer of the block.
+    pub fn produce_block_with_graffiti(
+        &self,
+        slot: Slot,
+        body: BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+        graffiti: &impl GraffitiProvider,
+    ) -> Result<
+        BeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        self.produce_block_inner(slot, body, Some(graffiti))
+    }
+
+    fn produce_block_inner(
+        &self,
+        slot: Slot,
+        mut body: BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+        graffiti: Option<&dyn GraffitiProvider>,
+    ) -> Result<
+        BeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        trace_span!("produce_block", slot, fork = ?body.version());
+        let context = &self.context;
+        let mut state = self.state.inner().clone();
+        let mut state_root_cache = self.state_root_cache.clone();
+        process_slots_with_upgrades(&mut state, slot, &mut state_root_cache, context)?;
+        let proposer_index = match &state {
+            BeaconState::Phase0(state) => phase0::get_beacon_proposer_index(state, context)?,
+            BeaconState::Altair(state) => altair::get_beacon_proposer_index(state, context)?,
+            BeaconState::Bellatrix(state) => bellatrix::get_beacon_proposer_index(state, context)?,
+            BeaconState::Capella(state) => capella::get_beacon_proposer_index(state, context)?,
+            BeaconState::Deneb(state) => deneb::get_beacon_proposer_index(state, context)?,
+            BeaconState::Electra(state) => electra::get_beacon_proposer_index(state, context)?,
+        };
+        if let Some(graffiti) = graffiti {
+            let public_key = &state.validators()[proposer_index].public_key;
+            *body.graffiti_mut() = graffiti.graffiti(slot, public_key);
+        }
+        match (state, body) {
+            (BeaconState::Phase0(mut state), BeaconBlockBody::Phase0(body)) => {
+                let mut block = phase0::BeaconBlock {
+                    slot,
+                    proposer_index,
+                    parent_root: state.latest_block_header.hash_tree_root()?,
+                    state_root: Default::default(),
+                    body,
+                };
+                phase0::process_block(&mut state, &block, context)?;
+                block.state_root = state.hash_tree_root()?;
+                Ok(BeaconBlock::Phase0(block))
+            }
+            (BeaconState::Altair(mut state), BeaconBlockBody::Altair(body)) => {
+                let mut block = altair::BeaconBlock {
+                    slot,
+                    proposer_index,
+                    parent_root: state.latest_block_header.hash_tree_root()?,
+                    state_root: Default::default(),
+                    body,
+                };
+                altair::process_block(&mut state, &block, context)?;
+                block.state_root = state.hash_tree_root()?;
+                Ok(BeaconBlock::Altair(block))
+            }
+            (BeaconState::Bellatrix(mut state), BeaconBlockBody::Bellatrix(body)) => {
+                let mut block = bellatrix::BeaconBlock {
+                    slot,
+                    proposer_index,
+                    parent_root: state.latest_block_header.hash_tree_root()?,
+                    state_root: Default::default(),
+                    body,
+                };
+                bellatrix::process_block(&mut state, &block, context)?;
+                block.state_root = state.hash_tree_root()?;
+                Ok(BeaconBlock::Bellatrix(block))
+            }
+            (BeaconState::Capella(mut state), BeaconBlockBody::Capella(body)) => {
+                let mut block = capella::BeaconBlock {
+                    slot,
+                    proposer_index,
+                    parent_root: state.latest_block_header.hash_tree_root()?,
+                    state_root: Default::default(),
+                    body,
+                };
+                capella::process_block(&mut state, &block, context)?;
+                block.state_root = state.hash_tree_root()?;
+                Ok(BeaconBlock::Capella(block))
+            }
+            (BeaconState::Deneb(mut state), BeaconBlockBody::Deneb(body)) => {
+                let mut block = deneb::BeaconBlock {
+                    slot,
+                    proposer_index,
+                    parent_root: state.latest_block_header.hash_tree_root()?,
+                    state_root: Default::default(),
+                    body,
+                };
+                deneb::process_block(&mut state, &block, context)?;
+                block.state_root = state.hash_tree_root()?;
+                Ok(BeaconBlock::Deneb(block))
+            }
+            (BeaconState::Electra(mut state), BeaconBlockBody::Electra(body)) => {
+                let mut block = electra::BeaconBlock {
+                    slot,
+                    proposer_index,
+                    parent_root: state.latest_block_header.hash_tree_root()?,
+                    state_root: Default::default(),
+                    body,
+                };
+                electra::process_block(&mut state, &block, context)?;
+                block.state_root = state.hash_tree_root()?;
+                Ok(BeaconBlock::Electra(block))
+            }
+            (state, body) => {
+                Err(WrongFork { expected: state.version(), found: body.version() }.into())
+            }
+        }
     }
 
     pub fn apply_block(
@@ -127,6 +979,13 @@ impl<
             MAX_WITHDRAWALS_PER_PAYLOAD,
             MAX_BLS_TO_EXECUTION_CHANGES,
             MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
         >,
     ) -> Result<()> {
         self.apply_block_with_validation(signed_block, Validation::Enabled)
@@ -149,24 +1008,512 @@ impl<
             MAX_WITHDRAWALS_PER_PAYLOAD,
             MAX_BLS_TO_EXECUTION_CHANGES,
             MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
         >,
         validation: Validation,
     ) -> Result<()> {
+        trace_span!(
+            "apply_block",
+            slot = signed_block.message().slot(),
+            fork = ?signed_block.version()
+        );
+        // the state may already be in the slot of the block, e.g. after `process_slots` or
+        // `promote_advanced_state`, but only if no block was applied in that slot
+        let slot = signed_block.message().slot();
+        let state_slot = self.state.slot();
+        if slot < state_slot ||
+            (slot == state_slot && self.state.latest_block_header().slot == slot)
+        {
+            return Err(Error::TransitionToPreviousSlot { current: state_slot, requested: slot })
+        }
         match signed_block {
             SignedBeaconBlock::Phase0(signed_block) => {
                 self.apply_phase0_block_with_validation(signed_block, validation)
             }
-            SignedBeaconBlock::Altair(signed_block) => {
-                self.apply_altair_block_with_validation(signed_block, validation)
+            SignedBeaconBlock::Altair(signed_block) => {
+                self.apply_altair_block_with_validation(signed_block, validation)
+            }
+            SignedBeaconBlock::Bellatrix(signed_block) => {
+                self.apply_bellatrix_block_with_validation(signed_block, validation)
+            }
+            SignedBeaconBlock::Capella(signed_block) => {
+                self.apply_capella_block_with_validation(signed_block, validation)
+            }
+            SignedBeaconBlock::Deneb(signed_block) => {
+                self.apply_deneb_block_with_validation(signed_block, validation)
+            }
+            SignedBeaconBlock::Electra(signed_block) => {
+                self.apply_electra_block_with_validation(signed_block, validation)
+            }
+        }?;
+        if validation.checks_state_root() {
+            self.state.record_state_root(signed_block.message().state_root());
+        }
+        Ok(())
+    }
+
+    /// The root of the current state, hashed (with the state root cache) only if it is not
+    /// already known from the block last applied.
+    pub fn state_root(&mut self) -> Result<Root> {
+        if let Some(root) = self.state.known_state_root() {
+            return Ok(root)
+        }
+        let root = self.state_root_cache.hash_tree_root(self.state.inner())?;
+        self.state.record_state_root(root);
+        Ok(root)
+    }
+
+    /// Apply `signed_block` as `apply_block_with_validation` does and report what the import did.
+    ///
+    /// The state root is taken from the block when `validation` checks it against the post-state
+    /// and computed otherwise.
+    pub fn apply_block_detailed(
+        &mut self,
+        signed_block: &SignedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+        validation: Validation,
+    ) -> Result<BlockImportOutcome<MAX_VALIDATORS_PER_COMMITTEE, MAX_VALIDATORS_PER_SLOT>> {
+        let block = signed_block.message();
+        let finalized_checkpoint = self.state.finalized_checkpoint().clone();
+
+        let slot = block.slot();
+        if self.state.slot() < slot {
+            self.process_slots(slot)?;
+        }
+        let proposer_index = block.proposer_index();
+        let proposer = self.state.validators().get(proposer_index).cloned();
+        let balance_before = self.state.balances().get(proposer_index).copied().unwrap_or_default();
+        // the positions of the proposer in the sync committee, where the block also rewards (or
+        // penalizes) it as a participant
+        let sync_committee_positions = match (&proposer, self.state.current_sync_committee()) {
+            (Some(proposer), Some(committee)) => committee
+                .public_keys
+                .iter()
+                .enumerate()
+                .filter(|(_, public_key)| **public_key == proposer.public_key)
+                .map(|(position, _)| position)
+                .collect(),
+            _ => vec![],
+        };
+        let participant_reward = if sync_committee_positions.is_empty() {
+            0
+        } else {
+            sync_participant_reward(self.state.total_active_balance(&self.context)?, &self.context)
+        };
+        let version = self.state.version();
+
+        self.apply_block_with_validation(signed_block, validation)?;
+
+        let proposer_reward = match proposer {
+            Some(proposer) => {
+                let body = block.body();
+                let balance_after =
+                    self.state.balances().get(proposer_index).copied().unwrap_or_default();
+                let mut credits = balance_after as i128;
+                let mut debits = balance_before as i128;
+                if let Some(withdrawals) =
+                    body.execution_payload().and_then(|payload| payload.withdrawals())
+                {
+                    credits += withdrawals
+                        .iter()
+                        .filter(|withdrawal| withdrawal.validator_index == proposer_index)
+                        .map(|withdrawal| withdrawal.amount as i128)
+                        .sum::<i128>();
+                }
+                // deposits are only credited directly before Electra, which queues them instead
+                if version < Fork::Electra {
+                    debits += body
+                        .deposits()
+                        .iter()
+                        .filter(|deposit| deposit.data.public_key == proposer.public_key)
+                        .map(|deposit| deposit.data.amount as i128)
+                        .sum::<i128>();
+                }
+                let slashed = self.state.validators().get(proposer_index).map(|v| v.slashed);
+                if !proposer.slashed && slashed == Some(true) {
+                    let quotient = match version {
+                        Fork::Phase0 => self.context.min_slashing_penalty_quotient,
+                        Fork::Altair => self.context.min_slashing_penalty_quotient_altair,
+                        Fork::Bellatrix | Fork::Capella | Fork::Deneb => {
+                            self.context.min_slashing_penalty_quotient_bellatrix
+                        }
+                        _ => self.context.min_slashing_penalty_quotient_electra,
+                    };
+                    credits += (proposer.effective_balance / quotient) as i128;
+                }
+                if let Some(sync_aggregate) = body.sync_aggregate() {
+                    for position in sync_committee_positions {
+                        if sync_aggregate.sync_committee_bits[position] {
+                            debits += participant_reward as i128;
+                        } else {
+                            credits += participant_reward as i128;
+                        }
+                    }
+                }
+                (credits - debits).max(0) as Gwei
+            }
+            None => 0,
+        };
+
+        let post_state_root = self.state_root()?;
+        let finality_advanced = (self.state.finalized_checkpoint() != &finalized_checkpoint)
+            .then(|| self.state.finalized_checkpoint().clone());
+        let body = block.body();
+        let detected_slashable = body
+            .proposer_slashings()
+            .iter()
+            .cloned()
+            .map(SlashableEvidence::Proposer)
+            .chain(
+                body.attester_slashings()
+                    .into_iter()
+                    .flatten()
+                    .cloned()
+                    .map(SlashableEvidence::Attester),
+            )
+            .chain(
+                body.electra()
+                    .into_iter()
+                    .flat_map(|body| body.attester_slashings.iter())
+                    .cloned()
+                    .map(SlashableEvidence::ElectraAttester),
+            )
+            .collect();
+        Ok(BlockImportOutcome {
+            post_state_root,
+            finality_advanced,
+            proposer_reward,
+            detected_slashable,
+            blob_commitment_count: body
+                .blob_kzg_commitments()
+                .map_or(0, |commitments| commitments.len()),
+        })
+    }
+
+    /// Verify every signature `signed_block` carries (but those of its deposits) in a single
+    /// batch against the current state, which must be at the slot of the block.
+    pub fn verify_block_signatures(
+        &mut self,
+        signed_block: &SignedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    ) -> Result<()> {
+        // resolve the committees up front so the shuffling cache serves every attestation
+        let body = signed_block.message().body();
+        let mut keys = body
+            .attestations()
+            .into_iter()
+            .flatten()
+            .map(|attestation| (attestation.data.slot, attestation.data.index))
+            .collect::<Vec<_>>();
+        for attestation in body.electra().into_iter().flat_map(|body| body.attestations.iter()) {
+            let committee_indices = attestation.committee_bits.iter().enumerate();
+            keys.extend(
+                committee_indices
+                    .filter(|(_, bit)| **bit)
+                    .map(|(index, _)| (attestation.data.slot, index as CommitteeIndex)),
+            );
+        }
+        let mut committees = HashMap::new();
+        for key in keys {
+            if !committees.contains_key(&key) {
+                committees.insert(key, self.beacon_committee(key.0, key.1)?);
+            }
+        }
+        let verifier = collect_block_signatures(
+            self.state.inner(),
+            signed_block,
+            |slot, index| Ok(committees[&(slot, index)].clone()),
+            &self.context,
+        )?;
+        verifier.verify(self.state.validators(), &mut self.pubkey_cache)
+    }
+
+    /// Apply `signed_block` with its signatures verified in a single batch ahead of the state
+    /// transition, which is then run with `Validation::SkipSignatureVerification`.
+    ///
+    /// If the batch fails, the block is applied with full validation instead so the error names
+    /// the offending signature.
+    pub fn apply_block_with_batch_verification(
+        &mut self,
+        signed_block: &SignedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    ) -> Result<()> {
+        let slot = signed_block.message().slot();
+        if self.state.slot() < slot {
+            self.process_slots(slot)?;
+        }
+        let validation = match self.verify_block_signatures(signed_block) {
+            Ok(()) => Validation::SkipSignatureVerification,
+            Err(_) => Validation::Enabled,
+        };
+        self.apply_block_with_validation(signed_block, validation)
+    }
+
+    /// Replay `blocks`, in ascending slot order, on top of the current state, e.g. to backfill
+    /// the chain from a checkpoint.
+    ///
+    /// The slots between blocks are processed once per gap through the state root cache, and
+    /// the replay stops at the first block that fails to apply, with an `Error::ReplayFailed`
+    /// naming it. The state is then left partway through that block, so keep a copy of the
+    /// starting state to retry from.
+    pub fn apply_blocks<
+        I: IntoIterator<
+            Item = SignedBeaconBlock<
+                MAX_PROPOSER_SLASHINGS,
+                MAX_VALIDATORS_PER_COMMITTEE,
+                MAX_ATTESTER_SLASHINGS,
+                MAX_ATTESTATIONS,
+                MAX_DEPOSITS,
+                MAX_VOLUNTARY_EXITS,
+                SYNC_COMMITTEE_SIZE,
+                BYTES_PER_LOGS_BLOOM,
+                MAX_EXTRA_DATA_BYTES,
+                MAX_BYTES_PER_TRANSACTION,
+                MAX_TRANSACTIONS_PER_PAYLOAD,
+                MAX_WITHDRAWALS_PER_PAYLOAD,
+                MAX_BLS_TO_EXECUTION_CHANGES,
+                MAX_BLOB_COMMITMENTS_PER_BLOCK,
+                MAX_VALIDATORS_PER_SLOT,
+                MAX_COMMITTEES_PER_SLOT,
+                MAX_ATTESTER_SLASHINGS_ELECTRA,
+                MAX_ATTESTATIONS_ELECTRA,
+                MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+                MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+                MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+            >,
+        >,
+    >(
+        &mut self,
+        blocks: I,
+        mut opts: ReplayOptions<'_>,
+    ) -> Result<ReplaySummary> {
+        trace_span!("apply_blocks");
+        let start = Instant::now();
+        let mut summary = ReplaySummary {
+            blocks_applied: 0,
+            empty_slots_processed: 0,
+            total_time: Duration::ZERO,
+        };
+        for signed_block in blocks {
+            let slot = signed_block.message().slot();
+            let current_slot = self.state.slot();
+            let result = if current_slot < slot {
+                summary.empty_slots_processed += slot - current_slot - 1;
+                self.process_slots(slot)
+            } else {
+                Ok(())
+            }
+            .and_then(|_| {
+                self.apply_block_in_slot(&signed_block, opts.validation, opts.verify_state_roots)
+            });
+            if let Err(err) = result {
+                let root = signed_block.message().hash_tree_root()?;
+                return Err(Error::ReplayFailed { slot, root, source: Box::new(err) })
+            }
+            summary.blocks_applied += 1;
+            if let Some(on_block) = opts.on_block.as_mut() {
+                on_block(slot, start.elapsed());
+            }
+        }
+        summary.total_time = start.elapsed();
+        Ok(summary)
+    }
+
+    // Apply `signed_block` to the state, which must already be at the slot of the block and in
+    // its fork. Unless `verify_state_root` is set, the state root the block commits to is taken
+    // as the root of the post-state.
+    fn apply_block_in_slot(
+        &mut self,
+        signed_block: &SignedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+        validation: Validation,
+        verify_state_root: bool,
+    ) -> Result<()> {
+        let state_root_cache = &mut self.state_root_cache;
+        let context = &self.context;
+        match (self.state.transition_mut(), signed_block) {
+            (BeaconState::Phase0(state), SignedBeaconBlock::Phase0(signed_block)) => {
+                phase0::state_transition_block_in_slot_with_state_root(
+                    state,
+                    signed_block,
+                    validation,
+                    |state| {
+                        if verify_state_root {
+                            state_root_cache.state_root(state)
+                        } else {
+                            Ok(signed_block.message.state_root)
+                        }
+                    },
+                    context,
+                )
+            }
+            (BeaconState::Altair(state), SignedBeaconBlock::Altair(signed_block)) => {
+                altair::state_transition_block_in_slot_with_state_root(
+                    state,
+                    signed_block,
+                    validation,
+                    |state| {
+                        if verify_state_root {
+                            state_root_cache.state_root(state)
+                        } else {
+                            Ok(signed_block.message.state_root)
+                        }
+                    },
+                    context,
+                )
             }
-            SignedBeaconBlock::Bellatrix(signed_block) => {
-                self.apply_bellatrix_block_with_validation(signed_block, validation)
+            (BeaconState::Bellatrix(state), SignedBeaconBlock::Bellatrix(signed_block)) => {
+                bellatrix::state_transition_block_in_slot_with_state_root(
+                    state,
+                    signed_block,
+                    validation,
+                    |state| {
+                        if verify_state_root {
+                            state_root_cache.state_root(state)
+                        } else {
+                            Ok(signed_block.message.state_root)
+                        }
+                    },
+                    context,
+                )
             }
-            SignedBeaconBlock::Capella(signed_block) => {
-                self.apply_capella_block_with_validation(signed_block, validation)
+            (BeaconState::Capella(state), SignedBeaconBlock::Capella(signed_block)) => {
+                capella::state_transition_block_in_slot_with_state_root(
+                    state,
+                    signed_block,
+                    validation,
+                    |state| {
+                        if verify_state_root {
+                            state_root_cache.state_root(state)
+                        } else {
+                            Ok(signed_block.message.state_root)
+                        }
+                    },
+                    context,
+                )
             }
-            SignedBeaconBlock::Deneb(signed_block) => {
-                self.apply_deneb_block_with_validation(signed_block, validation)
+            (BeaconState::Deneb(state), SignedBeaconBlock::Deneb(signed_block)) => {
+                deneb::state_transition_block_in_slot_with_state_root(
+                    state,
+                    signed_block,
+                    validation,
+                    |state| {
+                        if verify_state_root {
+                            state_root_cache.state_root(state)
+                        } else {
+                            Ok(signed_block.message.state_root)
+                        }
+                    },
+                    context,
+                )
+            }
+            (BeaconState::Electra(state), SignedBeaconBlock::Electra(signed_block)) => {
+                electra::state_transition_block_in_slot_with_state_root(
+                    state,
+                    signed_block,
+                    validation,
+                    |state| {
+                        if verify_state_root {
+                            state_root_cache.state_root(state)
+                        } else {
+                            Ok(signed_block.message.state_root)
+                        }
+                    },
+                    context,
+                )
+            }
+            (state, signed_block) => {
+                Err(WrongFork { expected: state.version(), found: signed_block.version() }.into())
             }
         }
     }
@@ -183,9 +1530,24 @@ impl<
         >,
         validation: Validation,
     ) -> Result<()> {
-        match &mut self.state {
+        match self.state.transition_mut() {
             BeaconState::Phase0(state) => {
-                phase0::state_transition(state, signed_block, validation, &self.context)
+                let state_root_cache = &mut self.state_root_cache;
+                if signed_block.message.slot != state.slot {
+                    phase0::process_slots_with_field_roots(
+                        state,
+                        signed_block.message.slot,
+                        |state| state_root_cache.field_roots(state),
+                        &self.context,
+                    )?;
+                }
+                phase0::state_transition_block_in_slot_with_state_root(
+                    state,
+                    signed_block,
+                    validation,
+                    |state| state_root_cache.state_root(state),
+                    &self.context,
+                )
             }
             state => Err(Error::InvalidForkTransition {
                 source_fork: state.version(),
@@ -207,9 +1569,9 @@ impl<
         >,
         validation: Validation,
     ) -> Result<()> {
-        match &mut self.state {
+        match self.state.transition_mut() {
             BeaconState::Phase0(state) => {
-                let fork_slot = self.context.altair_fork_epoch * self.context.slots_per_epoch;
+                let fork_slot = self.context.fork_start_slot(Fork::Altair);
                 phase0::process_slots(state, fork_slot, &self.context)?;
                 let mut state = altair::upgrade_to_altair(state, &self.context)?;
                 if signed_block.message.slot == state.slot {
@@ -222,11 +1584,26 @@ impl<
                 } else {
                     altair::state_transition(&mut state, signed_block, validation, &self.context)?;
                 }
-                self.state = BeaconState::Altair(state);
+                *self.state.transition_mut() = BeaconState::Altair(state);
                 Ok(())
             }
             BeaconState::Altair(state) => {
-                altair::state_transition(state, signed_block, validation, &self.context)
+                let state_root_cache = &mut self.state_root_cache;
+                if signed_block.message.slot != state.slot {
+                    altair::process_slots_with_field_roots(
+                        state,
+                        signed_block.message.slot,
+                        |state| state_root_cache.field_roots(state),
+                        &self.context,
+                    )?;
+                }
+                altair::state_transition_block_in_slot_with_state_root(
+                    state,
+                    signed_block,
+                    validation,
+                    |state| state_root_cache.state_root(state),
+                    &self.context,
+                )
             }
             state => Err(Error::InvalidForkTransition {
                 source_fork: state.version(),
@@ -252,13 +1629,13 @@ impl<
         >,
         validation: Validation,
     ) -> Result<()> {
-        match &mut self.state {
+        match self.state.transition_mut() {
             BeaconState::Phase0(state) => {
-                let fork_slot = self.context.altair_fork_epoch * self.context.slots_per_epoch;
+                let fork_slot = self.context.fork_start_slot(Fork::Altair);
                 phase0::process_slots(state, fork_slot, &self.context)?;
                 let mut state = altair::upgrade_to_altair(state, &self.context)?;
 
-                let fork_slot = self.context.bellatrix_fork_epoch * self.context.slots_per_epoch;
+                let fork_slot = self.context.fork_start_slot(Fork::Bellatrix);
                 altair::process_slots(&mut state, fork_slot, &self.context)?;
                 let mut state = bellatrix::upgrade_to_bellatrix(&state, &self.context);
                 if signed_block.message.slot == state.slot {
@@ -276,11 +1653,11 @@ impl<
                         &self.context,
                     )?;
                 }
-                self.state = BeaconState::Bellatrix(state);
+                *self.state.transition_mut() = BeaconState::Bellatrix(state);
                 Ok(())
             }
             BeaconState::Altair(state) => {
-                let fork_slot = self.context.bellatrix_fork_epoch * self.context.slots_per_epoch;
+                let fork_slot = self.context.fork_start_slot(Fork::Bellatrix);
                 altair::process_slots(state, fork_slot, &self.context)?;
                 let mut state = bellatrix::upgrade_to_bellatrix(state, &self.context);
                 if signed_block.message.slot == state.slot {
@@ -298,11 +1675,26 @@ impl<
                         &self.context,
                     )?;
                 }
-                self.state = BeaconState::Bellatrix(state);
+                *self.state.transition_mut() = BeaconState::Bellatrix(state);
                 Ok(())
             }
             BeaconState::Bellatrix(state) => {
-                bellatrix::state_transition(state, signed_block, validation, &self.context)
+                let state_root_cache = &mut self.state_root_cache;
+                if signed_block.message.slot != state.slot {
+                    bellatrix::process_slots_with_field_roots(
+                        state,
+                        signed_block.message.slot,
+                        |state| state_root_cache.field_roots(state),
+                        &self.context,
+                    )?;
+                }
+                bellatrix::state_transition_block_in_slot_with_state_root(
+                    state,
+                    signed_block,
+                    validation,
+                    |state| state_root_cache.state_root(state),
+                    &self.context,
+                )
             }
             state => Err(Error::InvalidForkTransition {
                 source_fork: state.version(),
@@ -330,17 +1722,17 @@ impl<
         >,
         validation: Validation,
     ) -> Result<()> {
-        match &mut self.state {
+        match self.state.transition_mut() {
             BeaconState::Phase0(state) => {
-                let fork_slot = self.context.altair_fork_epoch * self.context.slots_per_epoch;
+                let fork_slot = self.context.fork_start_slot(Fork::Altair);
                 phase0::process_slots(state, fork_slot, &self.context)?;
                 let mut state = altair::upgrade_to_altair(state, &self.context)?;
 
-                let fork_slot = self.context.bellatrix_fork_epoch * self.context.slots_per_epoch;
+                let fork_slot = self.context.fork_start_slot(Fork::Bellatrix);
                 altair::process_slots(&mut state, fork_slot, &self.context)?;
                 let mut state = bellatrix::upgrade_to_bellatrix(&state, &self.context);
 
-                let fork_slot = self.context.capella_fork_epoch * self.context.slots_per_epoch;
+                let fork_slot = self.context.fork_start_slot(Fork::Capella);
                 bellatrix::process_slots(&mut state, fork_slot, &self.context)?;
                 let mut state = capella::upgrade_to_capella(&state, &self.context);
 
@@ -354,15 +1746,15 @@ impl<
                 } else {
                     capella::state_transition(&mut state, signed_block, validation, &self.context)?;
                 }
-                self.state = BeaconState::Capella(state);
+                *self.state.transition_mut() = BeaconState::Capella(state);
                 Ok(())
             }
             BeaconState::Altair(state) => {
-                let fork_slot = self.context.bellatrix_fork_epoch * self.context.slots_per_epoch;
+                let fork_slot = self.context.fork_start_slot(Fork::Bellatrix);
                 altair::process_slots(state, fork_slot, &self.context)?;
                 let mut state = bellatrix::upgrade_to_bellatrix(state, &self.context);
 
-                let fork_slot = self.context.capella_fork_epoch * self.context.slots_per_epoch;
+                let fork_slot = self.context.fork_start_slot(Fork::Capella);
                 bellatrix::process_slots(&mut state, fork_slot, &self.context)?;
                 let mut state = capella::upgrade_to_capella(&state, &self.context);
 
@@ -376,11 +1768,11 @@ impl<
                 } else {
                     capella::state_transition(&mut state, signed_block, validation, &self.context)?;
                 }
-                self.state = BeaconState::Capella(state);
+                *self.state.transition_mut() = BeaconState::Capella(state);
                 Ok(())
             }
             BeaconState::Bellatrix(state) => {
-                let fork_slot = self.context.capella_fork_epoch * self.context.slots_per_epoch;
+                let fork_slot = self.context.fork_start_slot(Fork::Capella);
                 bellatrix::process_slots(state, fork_slot, &self.context)?;
                 let mut state = capella::upgrade_to_capella(state, &self.context);
 
@@ -394,11 +1786,26 @@ impl<
                 } else {
                     capella::state_transition(&mut state, signed_block, validation, &self.context)?;
                 }
-                self.state = BeaconState::Capella(state);
+                *self.state.transition_mut() = BeaconState::Capella(state);
                 Ok(())
             }
             BeaconState::Capella(state) => {
-                capella::state_transition(state, signed_block, validation, &self.context)
+                let state_root_cache = &mut self.state_root_cache;
+                if signed_block.message.slot != state.slot {
+                    capella::process_slots_with_field_roots(
+                        state,
+                        signed_block.message.slot,
+                        |state| state_root_cache.field_roots(state),
+                        &self.context,
+                    )?;
+                }
+                capella::state_transition_block_in_slot_with_state_root(
+                    state,
+                    signed_block,
+                    validation,
+                    |state| state_root_cache.state_root(state),
+                    &self.context,
+                )
             }
             state => Err(Error::InvalidForkTransition {
                 source_fork: state.version(),
@@ -427,21 +1834,21 @@ impl<
         >,
         validation: Validation,
     ) -> Result<()> {
-        match &mut self.state {
+        match self.state.transition_mut() {
             BeaconState::Phase0(state) => {
-                let fork_slot = self.context.altair_fork_epoch * self.context.slots_per_epoch;
+                let fork_slot = self.context.fork_start_slot(Fork::Altair);
                 phase0::process_slots(state, fork_slot, &self.context)?;
                 let mut state = altair::upgrade_to_altair(state, &self.context)?;
 
-                let fork_slot = self.context.bellatrix_fork_epoch * self.context.slots_per_epoch;
+                let fork_slot = self.context.fork_start_slot(Fork::Bellatrix);
                 altair::process_slots(&mut state, fork_slot, &self.context)?;
                 let mut state = bellatrix::upgrade_to_bellatrix(&state, &self.context);
 
-                let fork_slot = self.context.capella_fork_epoch * self.context.slots_per_epoch;
+                let fork_slot = self.context.fork_start_slot(Fork::Capella);
                 bellatrix::process_slots(&mut state, fork_slot, &self.context)?;
                 let mut state = capella::upgrade_to_capella(&state, &self.context);
 
-                let fork_slot = self.context.deneb_fork_epoch * self.context.slots_per_epoch;
+                let fork_slot = self.context.fork_start_slot(Fork::Deneb);
                 capella::process_slots(&mut state, fork_slot, &self.context)?;
                 let mut state = deneb::upgrade_to_deneb(&state, &self.context);
 
@@ -455,19 +1862,19 @@ impl<
                 } else {
                     deneb::state_transition(&mut state, signed_block, validation, &self.context)?;
                 }
-                self.state = BeaconState::Deneb(state);
+                *self.state.transition_mut() = BeaconState::Deneb(state);
                 Ok(())
             }
             BeaconState::Altair(state) => {
-                let fork_slot = self.context.bellatrix_fork_epoch * self.context.slots_per_epoch;
+                let fork_slot = self.context.fork_start_slot(Fork::Bellatrix);
                 altair::process_slots(state, fork_slot, &self.context)?;
                 let mut state = bellatrix::upgrade_to_bellatrix(state, &self.context);
 
-                let fork_slot = self.context.capella_fork_epoch * self.context.slots_per_epoch;
+                let fork_slot = self.context.fork_start_slot(Fork::Capella);
                 bellatrix::process_slots(&mut state, fork_slot, &self.context)?;
                 let mut state = capella::upgrade_to_capella(&state, &self.context);
 
-                let fork_slot = self.context.deneb_fork_epoch * self.context.slots_per_epoch;
+                let fork_slot = self.context.fork_start_slot(Fork::Deneb);
                 capella::process_slots(&mut state, fork_slot, &self.context)?;
                 let mut state = deneb::upgrade_to_deneb(&state, &self.context);
 
@@ -481,15 +1888,15 @@ impl<
                 } else {
                     deneb::state_transition(&mut state, signed_block, validation, &self.context)?;
                 }
-                self.state = BeaconState::Deneb(state);
+                *self.state.transition_mut() = BeaconState::Deneb(state);
                 Ok(())
             }
             BeaconState::Bellatrix(state) => {
-                let fork_slot = self.context.capella_fork_epoch * self.context.slots_per_epoch;
+                let fork_slot = self.context.fork_start_slot(Fork::Capella);
                 bellatrix::process_slots(state, fork_slot, &self.context)?;
                 let mut state = capella::upgrade_to_capella(state, &self.context);
 
-                let fork_slot = self.context.deneb_fork_epoch * self.context.slots_per_epoch;
+                let fork_slot = self.context.fork_start_slot(Fork::Deneb);
                 capella::process_slots(&mut state, fork_slot, &self.context)?;
                 let mut state = deneb::upgrade_to_deneb(&state, &self.context);
 
@@ -503,11 +1910,11 @@ impl<
                 } else {
                     deneb::state_transition(&mut state, signed_block, validation, &self.context)?;
                 }
-                self.state = BeaconState::Deneb(state);
+                *self.state.transition_mut() = BeaconState::Deneb(state);
                 Ok(())
             }
             BeaconState::Capella(state) => {
-                let fork_slot = self.context.deneb_fork_epoch * self.context.slots_per_epoch;
+                let fork_slot = self.context.fork_start_slot(Fork::Deneb);
                 capella::process_slots(state, fork_slot, &self.context)?;
                 let mut state = deneb::upgrade_to_deneb(state, &self.context);
 
@@ -521,12 +1928,970 @@ impl<
                 } else {
                     deneb::state_transition(&mut state, signed_block, validation, &self.context)?;
                 }
-                self.state = BeaconState::Deneb(state);
+                *self.state.transition_mut() = BeaconState::Deneb(state);
+                Ok(())
+            }
+            BeaconState::Deneb(state) => {
+                let state_root_cache = &mut self.state_root_cache;
+                if signed_block.message.slot != state.slot {
+                    deneb::process_slots_with_field_roots(
+                        state,
+                        signed_block.message.slot,
+                        |state| state_root_cache.field_roots(state),
+                        &self.context,
+                    )?;
+                }
+                deneb::state_transition_block_in_slot_with_state_root(
+                    state,
+                    signed_block,
+                    validation,
+                    |state| state_root_cache.state_root(state),
+                    &self.context,
+                )
+            }
+            state => Err(Error::InvalidForkTransition {
+                source_fork: state.version(),
+                destination_fork: Fork::Deneb,
+            }),
+        }
+    }
+
+    pub fn apply_electra_block_with_validation(
+        &mut self,
+        signed_block: &electra::SignedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+        validation: Validation,
+    ) -> Result<()> {
+        match self.state.transition_mut() {
+            BeaconState::Phase0(state) => {
+                let fork_slot = self.context.fork_start_slot(Fork::Altair);
+                phase0::process_slots(state, fork_slot, &self.context)?;
+                let mut state = altair::upgrade_to_altair(state, &self.context)?;
+
+                let fork_slot = self.context.fork_start_slot(Fork::Bellatrix);
+                altair::process_slots(&mut state, fork_slot, &self.context)?;
+                let mut state = bellatrix::upgrade_to_bellatrix(&state, &self.context);
+
+                let fork_slot = self.context.fork_start_slot(Fork::Capella);
+                bellatrix::process_slots(&mut state, fork_slot, &self.context)?;
+                let mut state = capella::upgrade_to_capella(&state, &self.context);
+
+                let fork_slot = self.context.fork_start_slot(Fork::Deneb);
+                capella::process_slots(&mut state, fork_slot, &self.context)?;
+                let mut state = deneb::upgrade_to_deneb(&state, &self.context);
+
+                let fork_slot = self.context.fork_start_slot(Fork::Electra);
+                deneb::process_slots(&mut state, fork_slot, &self.context)?;
+                let mut state = electra::upgrade_to_electra(&state, &self.context)?;
+
+                if signed_block.message.slot == state.slot {
+                    electra::state_transition_block_in_slot(
+                        &mut state,
+                        signed_block,
+                        validation,
+                        &self.context,
+                    )?;
+                } else {
+                    electra::state_transition(&mut state, signed_block, validation, &self.context)?;
+                }
+                *self.state.transition_mut() = BeaconState::Electra(state);
+                Ok(())
+            }
+            BeaconState::Altair(state) => {
+                let fork_slot = self.context.fork_start_slot(Fork::Bellatrix);
+                altair::process_slots(state, fork_slot, &self.context)?;
+                let mut state = bellatrix::upgrade_to_bellatrix(state, &self.context);
+
+                let fork_slot = self.context.fork_start_slot(Fork::Capella);
+                bellatrix::process_slots(&mut state, fork_slot, &self.context)?;
+                let mut state = capella::upgrade_to_capella(&state, &self.context);
+
+                let fork_slot = self.context.fork_start_slot(Fork::Deneb);
+                capella::process_slots(&mut state, fork_slot, &self.context)?;
+                let mut state = deneb::upgrade_to_deneb(&state, &self.context);
+
+                let fork_slot = self.context.fork_start_slot(Fork::Electra);
+                deneb::process_slots(&mut state, fork_slot, &self.context)?;
+                let mut state = electra::upgrade_to_electra(&state, &self.context)?;
+
+                if signed_block.message.slot == state.slot {
+                    electra::state_transition_block_in_slot(
+                        &mut state,
+                        signed_block,
+                        validation,
+                        &self.context,
+                    )?;
+                } else {
+                    electra::state_transition(&mut state, signed_block, validation, &self.context)?;
+                }
+                *self.state.transition_mut() = BeaconState::Electra(state);
+                Ok(())
+            }
+            BeaconState::Bellatrix(state) => {
+                let fork_slot = self.context.fork_start_slot(Fork::Capella);
+                bellatrix::process_slots(state, fork_slot, &self.context)?;
+                let mut state = capella::upgrade_to_capella(state, &self.context);
+
+                let fork_slot = self.context.fork_start_slot(Fork::Deneb);
+                capella::process_slots(&mut state, fork_slot, &self.context)?;
+                let mut state = deneb::upgrade_to_deneb(&state, &self.context);
+
+                let fork_slot = self.context.fork_start_slot(Fork::Electra);
+                deneb::process_slots(&mut state, fork_slot, &self.context)?;
+                let mut state = electra::upgrade_to_electra(&state, &self.context)?;
+
+                if signed_block.message.slot == state.slot {
+                    electra::state_transition_block_in_slot(
+                        &mut state,
+                        signed_block,
+                        validation,
+                        &self.context,
+                    )?;
+                } else {
+                    electra::state_transition(&mut state, signed_block, validation, &self.context)?;
+                }
+                *self.state.transition_mut() = BeaconState::Electra(state);
+                Ok(())
+            }
+            BeaconState::Capella(state) => {
+                let fork_slot = self.context.fork_start_slot(Fork::Deneb);
+                capella::process_slots(state, fork_slot, &self.context)?;
+                let mut state = deneb::upgrade_to_deneb(state, &self.context);
+
+                let fork_slot = self.context.fork_start_slot(Fork::Electra);
+                deneb::process_slots(&mut state, fork_slot, &self.context)?;
+                let mut state = electra::upgrade_to_electra(&state, &self.context)?;
+
+                if signed_block.message.slot == state.slot {
+                    electra::state_transition_block_in_slot(
+                        &mut state,
+                        signed_block,
+                        validation,
+                        &self.context,
+                    )?;
+                } else {
+                    electra::state_transition(&mut state, signed_block, validation, &self.context)?;
+                }
+                *self.state.transition_mut() = BeaconState::Electra(state);
                 Ok(())
             }
             BeaconState::Deneb(state) => {
-                deneb::state_transition(state, signed_block, validation, &self.context)
+                let fork_slot = self.context.fork_start_slot(Fork::Electra);
+                deneb::process_slots(state, fork_slot, &self.context)?;
+                let mut state = electra::upgrade_to_electra(state, &self.context)?;
+
+                if signed_block.message.slot == state.slot {
+                    electra::state_transition_block_in_slot(
+                        &mut state,
+                        signed_block,
+                        validation,
+                        &self.context,
+                    )?;
+                } else {
+                    electra::state_transition(&mut state, signed_block, validation, &self.context)?;
+                }
+                *self.state.transition_mut() = BeaconState::Electra(state);
+                Ok(())
+            }
+            BeaconState::Electra(state) => {
+                let state_root_cache = &mut self.state_root_cache;
+                if signed_block.message.slot != state.slot {
+                    electra::process_slots_with_field_roots(
+                        state,
+                        signed_block.message.slot,
+                        |state| state_root_cache.field_roots(state),
+                        &self.context,
+                    )?;
+                }
+                electra::state_transition_block_in_slot_with_state_root(
+                    state,
+                    signed_block,
+                    validation,
+                    |state| state_root_cache.state_root(state),
+                    &self.context,
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod produce_block_tests {
+    use super::*;
+    use crate::{
+        fixtures::{phase0::genesis_state, secret_keys},
+        primitives::{BlsSignature, DomainType},
+        signing::sign_with_domain,
+        state_transition::minimal::Executor,
+        types::minimal,
+    };
+
+    #[test]
+    fn test_produce_first_block_of_fork() {
+        let mut context = Context::for_minimal();
+        context.altair_fork_epoch = 1;
+        context.bellatrix_fork_epoch = 1;
+        context.capella_fork_epoch = 1;
+        context.deneb_fork_epoch = 2;
+        let secret_keys = secret_keys();
+        let slot = context.deneb_fork_epoch * context.slots_per_epoch;
+        let mut executor = Executor::new(BeaconState::Phase0(genesis_state()), context);
+
+        // the parent state is in the last slot before the fork
+        executor.process_slots(slot - 1).unwrap();
+        assert_eq!(executor.state.version(), Fork::Capella);
+
+        // gather what the proposer needs from the state at `slot`
+        let context = &executor.context;
+        let mut state = executor.state.inner().clone();
+        process_slots_with_upgrades(&mut state, slot, &mut StateRootCache::default(), context)
+            .unwrap();
+        let state = state.deneb().unwrap();
+        let epoch = context.deneb_fork_epoch;
+        let proposer_index = deneb::get_beacon_proposer_index(state, context).unwrap();
+        let proposer_key = &secret_keys[proposer_index];
+
+        let mut body = deneb::minimal::BeaconBlockBody::default();
+        let domain = deneb::get_domain(state, DomainType::Randao, Some(epoch), context).unwrap();
+        body.randao_reveal = sign_with_domain(&epoch, proposer_key, domain).unwrap();
+        body.eth1_data = state.eth1_data.clone();
+        body.sync_aggregate.sync_committee_signature = BlsSignature::infinity();
+        let payload = &mut body.execution_payload;
+        payload.parent_hash = state.latest_execution_payload_header.block_hash.clone();
+        payload.prev_randao = deneb::get_randao_mix(state, epoch).clone();
+        payload.timestamp = deneb::compute_timestamp_at_slot(state, slot, context).unwrap();
+
+        let err = executor
+            .produce_block(slot, minimal::BeaconBlockBody::Capella(Default::default()))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::WrongFork(WrongFork { expected: Fork::Deneb, found: Fork::Capella })
+        ));
+
+        let block = executor.produce_block(slot, minimal::BeaconBlockBody::Deneb(body)).unwrap();
+        assert_eq!(block.version(), Fork::Deneb);
+        assert_eq!(block.proposer_index(), proposer_index);
+        let block = block.into_deneb().unwrap();
+        let domain = deneb::get_domain(state, DomainType::BeaconProposer, None, context).unwrap();
+        let signature = sign_with_domain(&block, proposer_key, domain).unwrap();
+        let signed_block = deneb::minimal::SignedBeaconBlock { message: block, signature };
+
+        executor.apply_block(&SignedBeaconBlock::Deneb(signed_block)).unwrap();
+        assert_eq!(executor.state.version(), Fork::Deneb);
+        assert_eq!(executor.state.slot(), slot);
+    }
+
+    #[test]
+    fn test_produce_first_electra_block() {
+        let mut context = Context::for_minimal();
+        context.altair_fork_epoch = 0;
+        context.bellatrix_fork_epoch = 0;
+        context.capella_fork_epoch = 0;
+        context.deneb_fork_epoch = 1;
+        context.electra_fork_epoch = 2;
+        let secret_keys = secret_keys();
+        let slot = context.electra_fork_epoch * context.slots_per_epoch;
+        let mut executor = Executor::new(BeaconState::Phase0(genesis_state()), context);
+
+        // the parent state is in the last slot before the fork
+        executor.process_slots(slot - 1).unwrap();
+        assert_eq!(executor.state.version(), Fork::Deneb);
+
+        // gather what the proposer needs from the state at `slot`
+        let context = &executor.context;
+        let mut state = executor.state.inner().clone();
+        process_slots_with_upgrades(&mut state, slot, &mut StateRootCache::default(), context)
+            .unwrap();
+        let state = state.electra().unwrap();
+        let epoch = context.electra_fork_epoch;
+        let proposer_index = electra::get_beacon_proposer_index(state, context).unwrap();
+        let proposer_key = &secret_keys[proposer_index];
+
+        let mut body = electra::minimal::BeaconBlockBody::default();
+        let domain = electra::get_domain(state, DomainType::Randao, Some(epoch), context).unwrap();
+        body.randao_reveal = sign_with_domain(&epoch, proposer_key, domain).unwrap();
+        body.eth1_data = state.eth1_data.clone();
+        body.sync_aggregate.sync_committee_signature = BlsSignature::infinity();
+        let payload = &mut body.execution_payload;
+        payload.parent_hash = state.latest_execution_payload_header.block_hash.clone();
+        payload.prev_randao = electra::get_randao_mix(state, epoch).clone();
+        payload.timestamp = electra::compute_timestamp_at_slot(state, slot, context).unwrap();
+
+        let err = executor
+            .produce_block(slot, minimal::BeaconBlockBody::Deneb(Default::default()))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::WrongFork(WrongFork { expected: Fork::Electra, found: Fork::Deneb })
+        ));
+
+        let block = executor.produce_block(slot, minimal::BeaconBlockBody::Electra(body)).unwrap();
+        assert_eq!(block.version(), Fork::Electra);
+        assert_eq!(block.proposer_index(), proposer_index);
+        let block = block.into_electra().unwrap();
+        let domain = electra::get_domain(state, DomainType::BeaconProposer, None, context).unwrap();
+        let signature = sign_with_domain(&block, proposer_key, domain).unwrap();
+        let signed_block = electra::minimal::SignedBeaconBlock { message: block, signature };
+
+        executor.apply_block(&SignedBeaconBlock::Electra(signed_block)).unwrap();
+        assert_eq!(executor.state.version(), Fork::Electra);
+        assert_eq!(executor.state.slot(), slot);
+        let state = executor.state.inner().electra().unwrap();
+        assert_eq!(state.fork.current_version, executor.context.electra_fork_version);
+    }
+}
+
+#[cfg(test)]
+mod precompute_tests {
+    use super::*;
+    use crate::{
+        crypto::aggregate,
+        fixtures::{phase0::genesis_state, secret_keys, VALIDATOR_COUNT},
+        graffiti::{graffiti_from_str, RoundRobinGraffiti},
+        primitives::DomainType,
+        signing::sign_with_domain,
+        state_transition::minimal::Executor,
+        types::minimal,
+    };
+
+    fn signed_block_at(executor: &Executor, slot: Slot) -> minimal::SignedBeaconBlock {
+        signed_block_with_body(executor, slot, Default::default())
+    }
+
+    fn signed_block_with_body(
+        executor: &Executor,
+        slot: Slot,
+        mut body: phase0::minimal::BeaconBlockBody,
+    ) -> minimal::SignedBeaconBlock {
+        let context = &executor.context;
+        let mut state = executor.state.inner().clone();
+        process_slots_with_upgrades(&mut state, slot, &mut StateRootCache::default(), context)
+            .unwrap();
+        let state = state.phase0().unwrap();
+        let epoch = phase0::compute_epoch_at_slot(slot, context);
+        let proposer_index = phase0::get_beacon_proposer_index(state, context).unwrap();
+        let secret_keys = secret_keys();
+        let proposer_key = &secret_keys[proposer_index];
+
+        let domain = phase0::get_domain(state, DomainType::Randao, Some(epoch), context).unwrap();
+        body.randao_reveal = sign_with_domain(&epoch, proposer_key, domain).unwrap();
+        let block = executor.produce_block(slot, minimal::BeaconBlockBody::Phase0(body)).unwrap();
+        let block = block.into_phase0().unwrap();
+        let domain = phase0::get_domain(state, DomainType::BeaconProposer, None, context).unwrap();
+        let signature = sign_with_domain(&block, proposer_key, domain).unwrap();
+        SignedBeaconBlock::Phase0(phase0::minimal::SignedBeaconBlock { message: block, signature })
+    }
+
+    #[test]
+    fn test_produce_block_with_graffiti() {
+        let context = Context::for_minimal();
+        let executor = Executor::new(BeaconState::Phase0(genesis_state()), context);
+        let graffitis = ["first", "second"].map(graffiti_from_str);
+        let provider = RoundRobinGraffiti::new(graffitis.to_vec());
+        let secret_keys = secret_keys();
+        for (slot, expected) in (1..=4).zip(graffitis.iter().cycle()) {
+            let mut state = executor.state.inner().clone();
+            let mut state_root_cache = StateRootCache::default();
+            process_slots_with_upgrades(&mut state, slot, &mut state_root_cache, &executor.context)
+                .unwrap();
+            let state = state.phase0().unwrap();
+            let proposer_index =
+                phase0::get_beacon_proposer_index(state, &executor.context).unwrap();
+            let epoch = phase0::compute_epoch_at_slot(slot, &executor.context);
+            let domain =
+                phase0::get_domain(state, DomainType::Randao, Some(epoch), &executor.context)
+                    .unwrap();
+            let body = phase0::minimal::BeaconBlockBody {
+                randao_reveal: sign_with_domain(&epoch, &secret_keys[proposer_index], domain)
+                    .unwrap(),
+                graffiti: graffiti_from_str("replaced"),
+                ..Default::default()
+            };
+            let block = executor
+                .produce_block_with_graffiti(
+                    slot,
+                    minimal::BeaconBlockBody::Phase0(body),
+                    &provider,
+                )
+                .unwrap();
+            assert_eq!(block.body().graffiti(), expected);
+        }
+    }
+
+    #[test]
+    fn test_promote_then_apply_block_matches_direct_transition() {
+        let context = Context::for_minimal();
+        // advancing from the last slot of the epoch runs epoch processing
+        let slot = context.slots_per_epoch;
+        let mut executor = Executor::new(BeaconState::Phase0(genesis_state()), context);
+        executor.process_slots(slot - 1).unwrap();
+        let signed_block = signed_block_at(&executor, slot);
+
+        let mut expected = Executor::new(executor.state.inner().clone(), executor.context.clone());
+        expected.apply_block(&signed_block).unwrap();
+
+        let root = executor.state.hash_tree_root().unwrap();
+        let advanced = executor.precompute_next_slot().unwrap();
+        assert_eq!(executor.state.hash_tree_root().unwrap(), root);
+        assert_eq!(executor.state.slot(), slot - 1);
+        assert_eq!(advanced.state.slot(), slot);
+        assert_eq!(advanced.parent_state_root, root);
+        let decision_root = shuffling_decision_root(&advanced.state, 1, &executor.context);
+        assert!(advanced.shuffling_cache.get(1, decision_root).is_some());
+
+        executor.promote_advanced_state(advanced).unwrap();
+        executor.apply_block(&signed_block).unwrap();
+        assert_eq!(
+            executor.state.hash_tree_root().unwrap(),
+            expected.state.hash_tree_root().unwrap()
+        );
+        // the checked state root of the block is kept rather than hashed again
+        assert_eq!(executor.state.known_state_root(), Some(signed_block.message().state_root()));
+
+        // the state is in the slot of the block, but a block was already applied in it
+        let err = executor.apply_block(&signed_block).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::TransitionToPreviousSlot { current, requested } if current == slot && requested == slot
+        ));
+    }
+
+    #[test]
+    fn test_apply_block_detailed() {
+        let context = Context::for_minimal();
+        // the block is the first of epoch 4, so epoch 3 is processed on the way to it
+        let slot = 4 * context.slots_per_epoch;
+        let mut executor = Executor::new(BeaconState::Phase0(genesis_state()), context);
+        executor.process_slots(slot - 1).unwrap();
+
+        // epochs 1 and 2 are justified, so processing epoch 3 finalizes epoch 1 even without
+        // any attestations
+        let finalized = Checkpoint { epoch: 1, root: Root::try_from(&[1u8; 32][..]).unwrap() };
+        let state = executor.state.as_inner_mut().phase0_mut().unwrap();
+        state.justification_bits.set(0, true);
+        state.justification_bits.set(1, true);
+        state.previous_justified_checkpoint = finalized.clone();
+        state.current_justified_checkpoint =
+            Checkpoint { epoch: 2, root: Root::try_from(&[2u8; 32][..]).unwrap() };
+        let state = state.clone();
+
+        let mut advanced = state.clone();
+        phase0::process_slots(&mut advanced, slot, &executor.context).unwrap();
+        let proposer_index =
+            phase0::get_beacon_proposer_index(&advanced, &executor.context).unwrap();
+        let slashed =
+            (0..VALIDATOR_COUNT).filter(|&i| i != proposer_index).take(2).collect::<Vec<_>>();
+        let secret_keys = secret_keys();
+        let target = Checkpoint { epoch: 4, ..Default::default() };
+        let domain = phase0::get_domain(
+            &state,
+            DomainType::BeaconAttester,
+            Some(target.epoch),
+            &executor.context,
+        )
+        .unwrap();
+        let indexed_attestation = |beacon_block_root: Root| {
+            let data = phase0::AttestationData {
+                slot,
+                beacon_block_root,
+                target: target.clone(),
+                ..Default::default()
+            };
+            let signatures = slashed
+                .iter()
+                .map(|&index| sign_with_domain(&data, &secret_keys[index], domain).unwrap())
+                .collect::<Vec<_>>();
+            phase0::minimal::IndexedAttestation {
+                attesting_indices: slashed.clone().try_into().unwrap(),
+                signature: aggregate(&signatures).unwrap(),
+                data,
+            }
+        };
+        let attester_slashing = phase0::minimal::AttesterSlashing {
+            attestation_1: indexed_attestation(Root::default()),
+            attestation_2: indexed_attestation(Root::try_from(&[3u8; 32][..]).unwrap()),
+        };
+        let mut body = phase0::minimal::BeaconBlockBody::default();
+        body.attester_slashings.push(attester_slashing.clone());
+        let signed_block = signed_block_with_body(&executor, slot, body);
+
+        let outcome = executor.apply_block_detailed(&signed_block, Validation::Enabled).unwrap();
+        let post_state_root = executor.state.hash_tree_root().unwrap();
+        assert_eq!(outcome.post_state_root, post_state_root);
+        assert_eq!(outcome.post_state_root, signed_block.message().state_root());
+        assert_eq!(outcome.finality_advanced, Some(finalized.clone()));
+        assert_eq!(executor.state.finalized_checkpoint(), &finalized);
+        assert_eq!(
+            outcome.detected_slashable,
+            vec![SlashableEvidence::Attester(attester_slashing)]
+        );
+        assert_eq!(outcome.blob_commitment_count, 0);
+        // without a whistleblower the proposer collects the whole whistleblower reward of each
+        // slashed validator
+        let context = &executor.context;
+        let whistleblower_reward =
+            context.max_effective_balance / context.whistleblower_reward_quotient;
+        assert_eq!(outcome.proposer_reward, 2 * whistleblower_reward);
+
+        // the same import without validation computes the root and reports the same outcome
+        let mut unvalidated = Executor::new(BeaconState::Phase0(state), executor.context.clone());
+        let unvalidated_outcome =
+            unvalidated.apply_block_detailed(&signed_block, Validation::Disabled).unwrap();
+        assert_eq!(unvalidated_outcome, outcome);
+    }
+
+    #[test]
+    fn test_proposer_reward_of_slashed_proposer() {
+        let context = Context::for_minimal();
+        let slot = 1;
+        let mut executor = Executor::new(BeaconState::Phase0(genesis_state()), context);
+        let mut advanced = genesis_state();
+        phase0::process_slots(&mut advanced, slot, &executor.context).unwrap();
+        let proposer_index =
+            phase0::get_beacon_proposer_index(&advanced, &executor.context).unwrap();
+
+        // the proposer includes a slashing of itself, so its balance drops over the block
+        let secret_keys = secret_keys();
+        let header = |graffiti: u8| {
+            let message = phase0::BeaconBlockHeader {
+                slot,
+                proposer_index,
+                body_root: Root::try_from(&[graffiti; 32][..]).unwrap(),
+                ..Default::default()
+            };
+            let domain =
+                phase0::get_domain(&advanced, DomainType::BeaconProposer, None, &executor.context)
+                    .unwrap();
+            let signature =
+                sign_with_domain(&message, &secret_keys[proposer_index], domain).unwrap();
+            phase0::SignedBeaconBlockHeader { message, signature }
+        };
+        let mut body = phase0::minimal::BeaconBlockBody::default();
+        body.proposer_slashings
+            .push(ProposerSlashing { signed_header_1: header(1), signed_header_2: header(2) });
+        let signed_block = signed_block_with_body(&executor, slot, body);
+
+        let pre_balance = advanced.balances[proposer_index];
+        let outcome = executor.apply_block_detailed(&signed_block, Validation::Enabled).unwrap();
+        let post_balance = executor.state.balances()[proposer_index];
+        assert!(post_balance < pre_balance);
+        let context = &executor.context;
+        let whistleblower_reward =
+            context.max_effective_balance / context.whistleblower_reward_quotient;
+        assert_eq!(outcome.proposer_reward, whistleblower_reward);
+    }
+
+    // A chain of blocks from the genesis state, with empty slots in between and an epoch
+    // boundary, along with the state root after the last of them.
+    fn chain() -> (Vec<minimal::SignedBeaconBlock>, Root) {
+        let context = Context::for_minimal();
+        let mut executor = Executor::new(BeaconState::Phase0(genesis_state()), context);
+        let blocks = [1, 2, 5, 9]
+            .into_iter()
+            .map(|slot| {
+                let signed_block = signed_block_at(&executor, slot);
+                executor.apply_block(&signed_block).unwrap();
+                signed_block
+            })
+            .collect();
+        (blocks, executor.state.hash_tree_root().unwrap())
+    }
+
+    #[test]
+    fn test_apply_blocks() {
+        let (blocks, state_root) = chain();
+        let context = Context::for_minimal();
+        let mut executor = Executor::new(BeaconState::Phase0(genesis_state()), context);
+        let mut slots = vec![];
+        let mut on_block = |slot: Slot, _: Duration| slots.push(slot);
+        let opts = ReplayOptions { on_block: Some(&mut on_block), ..Default::default() };
+        let summary = executor.apply_blocks(blocks.clone(), opts).unwrap();
+        assert_eq!(summary.blocks_applied, 4);
+        assert_eq!(summary.empty_slots_processed, 5);
+        assert_eq!(slots, vec![1, 2, 5, 9]);
+        assert_eq!(executor.state.slot(), 9);
+        assert_eq!(executor.state.hash_tree_root().unwrap(), state_root);
+
+        // the same replay without checking state roots reaches the same state
+        let context = Context::for_minimal();
+        let mut executor = Executor::new(BeaconState::Phase0(genesis_state()), context);
+        let opts = ReplayOptions { verify_state_roots: false, ..Default::default() };
+        executor.apply_blocks(blocks, opts).unwrap();
+        assert_eq!(executor.state.hash_tree_root().unwrap(), state_root);
+    }
+
+    #[test]
+    fn test_apply_blocks_stops_at_invalid_block() {
+        let (mut blocks, _) = chain();
+        // the signatures no longer match, so only the state roots are checked
+        let invalid_root = Root::try_from(&[1u8; 32][..]).unwrap();
+        *blocks[2].message_mut().state_root_mut() = invalid_root;
+        *blocks[3].message_mut().state_root_mut() = invalid_root;
+
+        let context = Context::for_minimal();
+        let mut executor = Executor::new(BeaconState::Phase0(genesis_state()), context);
+        let mut slots = vec![];
+        let mut on_block = |slot: Slot, _: Duration| slots.push(slot);
+        let opts = ReplayOptions {
+            validation: Validation::SkipSignatureVerification,
+            on_block: Some(&mut on_block),
+            ..Default::default()
+        };
+        let err = executor.apply_blocks(blocks.clone(), opts).unwrap_err();
+        let expected_root = blocks[2].message().hash_tree_root().unwrap();
+        assert!(matches!(
+            err,
+            Error::ReplayFailed { slot: 5, root, source }
+                if root == expected_root && matches!(*source, Error::InvalidStateRoot)
+        ));
+        assert_eq!(slots, vec![1, 2]);
+
+        // an invalid state root in the last block goes unnoticed without the check
+        blocks.truncate(3);
+        let context = Context::for_minimal();
+        let mut executor = Executor::new(BeaconState::Phase0(genesis_state()), context);
+        let opts = ReplayOptions {
+            validation: Validation::SkipSignatureVerification,
+            verify_state_roots: false,
+            on_block: None,
+        };
+        let summary = executor.apply_blocks(blocks, opts).unwrap();
+        assert_eq!(summary.blocks_applied, 3);
+        assert_eq!(summary.empty_slots_processed, 2);
+    }
+
+    #[test]
+    fn test_promote_stale_advanced_state() {
+        let context = Context::for_minimal();
+        let mut executor = Executor::new(BeaconState::Phase0(genesis_state()), context);
+        let advanced = executor.precompute_next_slot().unwrap();
+
+        // the head moves on before the advanced state is used
+        executor.process_slots(2).unwrap();
+        let root = executor.state.hash_tree_root().unwrap();
+        let err = executor.promote_advanced_state(advanced).unwrap_err();
+        assert!(matches!(err, Error::StaleAdvancedState { found, .. } if found == root));
+        assert_eq!(executor.state.slot(), 2);
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "tracing")]
+mod tests {
+    use super::*;
+    use crate::{
+        crypto::SecretKey,
+        phase0::{get_beacon_proposer_index, get_domain, minimal, Validator},
+        primitives::{DomainType, FAR_FUTURE_EPOCH},
+        signing::sign_with_domain,
+        ssz::prelude::*,
+        state_transition::minimal::Executor,
+    };
+    use std::sync::{Arc, Mutex};
+    use tracing::{
+        span::{Attributes, Id, Record},
+        Event, Metadata, Subscriber,
+    };
+
+    #[derive(Default)]
+    struct Spans {
+        // name and parent of each span, indexed by span id - 1
+        spans: Vec<(&'static str, Option<usize>)>,
+        entered: Vec<usize>,
+    }
+
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Spans>>);
+
+    impl Recorder {
+        fn parent_of(&self, name: &str) -> Option<&'static str> {
+            let inner = self.0.lock().unwrap();
+            let (_, parent) = inner.spans.iter().find(|(span, _)| *span == name)?;
+            parent.map(|parent| inner.spans[parent].0)
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+            let mut inner = self.0.lock().unwrap();
+            let parent = if let Some(parent) = attributes.parent() {
+                Some(parent.into_u64() as usize - 1)
+            } else if attributes.is_contextual() {
+                inner.entered.last().copied()
+            } else {
+                None
+            };
+            inner.spans.push((attributes.metadata().name(), parent));
+            Id::from_u64(inner.spans.len() as u64)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, span: &Id) {
+            self.0.lock().unwrap().entered.push(span.into_u64() as usize - 1);
+        }
+
+        fn exit(&self, _: &Id) {
+            self.0.lock().unwrap().entered.pop();
+        }
+    }
+
+    // A state in the second to last slot of epoch 0 and a block in the first slot of epoch 1.
+    fn epoch_boundary_block(
+        context: &Context,
+    ) -> (minimal::BeaconState, minimal::SignedBeaconBlock) {
+        let secret_keys =
+            (0..64u8).map(|i| SecretKey::key_gen(&[i + 1; 32]).unwrap()).collect::<Vec<_>>();
+        let mut state =
+            minimal::BeaconState { slot: context.slots_per_epoch - 2, ..Default::default() };
+        for secret_key in &secret_keys {
+            state.validators.push(Validator {
+                public_key: secret_key.public_key(),
+                effective_balance: context.max_effective_balance,
+                exit_epoch: FAR_FUTURE_EPOCH,
+                withdrawable_epoch: FAR_FUTURE_EPOCH,
+                ..Default::default()
+            });
+            state.balances.push(context.max_effective_balance);
+        }
+
+        // build a block in the first slot of the next epoch
+        let slot = context.slots_per_epoch;
+        let mut advanced_state = state.clone();
+        phase0::process_slots(&mut advanced_state, slot, &context).unwrap();
+        let proposer_index = get_beacon_proposer_index(&advanced_state, &context).unwrap();
+        let epoch = 1u64;
+        let domain =
+            get_domain(&advanced_state, DomainType::Randao, Some(epoch), &context).unwrap();
+        let mut block = minimal::SignedBeaconBlock::default();
+        block.message.slot = slot;
+        block.message.proposer_index = proposer_index;
+        block.message.parent_root = advanced_state.latest_block_header.hash_tree_root().unwrap();
+        block.message.body.randao_reveal =
+            sign_with_domain(&epoch, &secret_keys[proposer_index], domain).unwrap();
+        (state, block)
+    }
+
+    #[test]
+    fn test_apply_block_spans() {
+        let context = Context::for_minimal();
+        let (state, block) = epoch_boundary_block(&context);
+        let recorder = Recorder::default();
+        let mut executor = Executor::new(BeaconState::Phase0(state), context);
+        tracing::subscriber::with_default(recorder.clone(), || {
+            executor
+                .apply_block_with_validation(
+                    &SignedBeaconBlock::Phase0(block),
+                    Validation::Disabled,
+                )
+                .unwrap();
+        });
+
+        assert_eq!(recorder.parent_of("apply_block"), None);
+        assert_eq!(recorder.parent_of("process_slots"), Some("apply_block"));
+        assert_eq!(recorder.parent_of("process_epoch"), Some("process_slots"));
+        assert_eq!(
+            recorder.parent_of("process_justification_and_finalization"),
+            Some("process_epoch")
+        );
+        assert_eq!(recorder.parent_of("process_rewards_and_penalties"), Some("process_epoch"));
+        assert_eq!(recorder.parent_of("hash_tree_root"), Some("process_slots"));
+        assert_eq!(recorder.parent_of("process_block"), Some("apply_block"));
+        assert_eq!(recorder.parent_of("attestations"), Some("process_block"));
+        assert_eq!(recorder.parent_of("deposits"), Some("process_block"));
+    }
+
+    #[test]
+    fn test_apply_blocks_spans() {
+        let context = Context::for_minimal();
+        let (state, block) = epoch_boundary_block(&context);
+        let recorder = Recorder::default();
+        let mut executor = Executor::new(BeaconState::Phase0(state), context);
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let opts = ReplayOptions {
+                validation: Validation::Disabled,
+                verify_state_roots: false,
+                ..Default::default()
+            };
+            executor.apply_blocks([SignedBeaconBlock::Phase0(block)], opts).unwrap();
+        });
+
+        // the span stays entered for the whole replay
+        assert_eq!(recorder.parent_of("apply_blocks"), None);
+        assert_eq!(recorder.parent_of("process_slots"), Some("apply_blocks"));
+        assert_eq!(recorder.parent_of("process_block"), Some("apply_blocks"));
+    }
+}
+
+#[cfg(test)]
+mod cache_config_tests {
+    use super::*;
+    use crate::{
+        fixtures::{determinism, phase0::genesis_state},
+        phase0::{get_beacon_committee, get_committee_count_per_slot},
+        state_transition::minimal::Executor,
+        storage::{decode_block, decode_state},
+        types::minimal,
+    };
+
+    // Skipping slots computes no shufflings: they are only computed once a committee is queried.
+    #[test]
+    fn test_process_slots_defers_shufflings() {
+        let context = Context::for_minimal();
+        let slot = 10 * context.slots_per_epoch;
+        let mut executor = Executor::new(BeaconState::Phase0(genesis_state()), context);
+        executor.process_slots(slot).unwrap();
+        assert!(executor.shuffling_cache.is_empty());
+
+        executor.beacon_committee(slot, 0).unwrap();
+        assert_eq!(executor.shuffling_cache.len(), 1);
+    }
+
+    // Disabled caches compute the same values the caches would hold.
+    #[test]
+    fn test_disabled_caches_agree() {
+        let context = Context::for_minimal();
+        let state = BeaconState::Phase0(genesis_state());
+        let mut cached = Executor::new(state.clone(), context.clone());
+        let mut uncached =
+            Executor::with_cache_config(state, context.clone(), CacheConfig::disabled());
+
+        cached.warm_pubkey_cache().unwrap();
+        uncached.warm_pubkey_cache().unwrap();
+        let genesis = genesis_state();
+        for epoch in 0..2 {
+            let committees_per_slot = get_committee_count_per_slot(&genesis, epoch, &context);
+            for slot in epoch * context.slots_per_epoch..(epoch + 1) * context.slots_per_epoch {
+                for index in 0..committees_per_slot {
+                    let expected = get_beacon_committee(&genesis, slot, index, &context).unwrap();
+                    assert_eq!(cached.beacon_committee(slot, index).unwrap(), expected);
+                    assert_eq!(uncached.beacon_committee(slot, index).unwrap(), expected);
+                }
+            }
+        }
+        for _ in 0..2 {
+            assert_eq!(
+                cached.state.total_active_balance(&context).unwrap(),
+                uncached.state.total_active_balance(&context).unwrap()
+            );
+        }
+        assert_eq!(cached.shuffling_cache.len(), 2);
+        assert!(cached.current_memory_estimate() > 0);
+        assert!(uncached.shuffling_cache.is_empty());
+        assert!(uncached.pubkey_cache.is_empty());
+        assert_eq!(uncached.current_memory_estimate(), 0);
+    }
+
+    // Blocks apply to the same post-states whichever caches are enabled.
+    #[test]
+    fn test_cache_config_does_not_change_transition() {
+        let context = determinism::context();
+        for (fork, bundle) in determinism::starter_bundles() {
+            for cache_config in [CacheConfig::default(), CacheConfig::disabled()] {
+                let state = decode_state(&bundle.pre_state).unwrap();
+                let mut executor =
+                    Executor::with_cache_config(state, context.clone(), cache_config);
+                for encoding in &bundle.blocks {
+                    let block: minimal::SignedBeaconBlock = decode_block(encoding).unwrap();
+                    executor.apply_block(&block).unwrap();
+                }
+                let root = executor.state.hash_tree_root().unwrap();
+                assert_eq!(root, bundle.expected_post_root, "{fork}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod validation_tests {
+    use super::*;
+    use crate::{
+        fixtures::determinism,
+        primitives::BlsSignature,
+        storage::{decode_block, decode_state},
+        types::minimal,
+    };
+
+    // The starter bundles of each fork with the proposer signature of every block replaced.
+    fn unsigned_bundles() -> Vec<(Fork, BeaconState, Vec<minimal::SignedBeaconBlock>, Root)> {
+        let mut garbage = [0u8; 96];
+        garbage[0] = 0xc1;
+        let garbage = BlsSignature::try_from(garbage.as_slice()).unwrap();
+        determinism::starter_bundles()
+            .into_iter()
+            .map(|(fork, bundle)| {
+                let state = decode_state(&bundle.pre_state).unwrap();
+                let blocks = bundle
+                    .blocks
+                    .iter()
+                    .map(|encoding| {
+                        let mut block: minimal::SignedBeaconBlock = decode_block(encoding).unwrap();
+                        *block.signature_mut() = garbage.clone();
+                        block
+                    })
+                    .collect();
+                (fork, state, blocks, bundle.expected_post_root)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_skip_signature_verification_across_forks() {
+        let context = determinism::context();
+        for (fork, state, blocks, expected_post_root) in unsigned_bundles() {
+            let mut checked = Executor::new(state.clone(), context.clone());
+            let err = checked.apply_block(&blocks[0]).unwrap_err();
+            assert!(matches!(err, Error::Crypto(_)), "{fork}: {err}");
+
+            let mut executor = Executor::new(state, context.clone());
+            for block in &blocks {
+                executor
+                    .apply_block_with_validation(block, Validation::SkipSignatureVerification)
+                    .unwrap();
             }
+            let root = executor.state.hash_tree_root().unwrap();
+            assert_eq!(root, expected_post_root, "{fork}");
+        }
+    }
+
+    #[test]
+    fn test_skip_signature_verification_checks_state_root_across_forks() {
+        let context = determinism::context();
+        for (fork, state, mut blocks, _) in unsigned_bundles() {
+            let block = &mut blocks[0];
+            *block.message_mut().state_root_mut() = Root::try_from([0xffu8; 32].as_ref()).unwrap();
+            let mut executor = Executor::new(state, context.clone());
+            let err = executor
+                .apply_block_with_validation(block, Validation::SkipSignatureVerification)
+                .unwrap_err();
+            assert!(matches!(err, Error::InvalidStateRoot), "{fork}: {err}");
         }
     }
 }
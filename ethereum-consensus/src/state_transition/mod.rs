@@ -1,16 +1,76 @@
+mod cached_state;
+mod committee_cache;
 mod context;
 mod executor;
+mod paged_state;
 mod presets;
+mod pubkey_cache;
+mod shuffling_cache;
+mod signature_verifier;
+mod state_root_cache;
 
+pub use cached_state::*;
+pub use committee_cache::*;
 pub use context::*;
 pub use executor::*;
+pub use paged_state::*;
+pub use pubkey_cache::*;
+pub use shuffling_cache::*;
+pub use signature_verifier::*;
+pub use state_root_cache::*;
+
+use crate::primitives::Epoch;
 
 pub type Result<T> = std::result::Result<T, crate::Error>;
 
+/// If the state transition runs any work in parallel. It does not: `rayon` is only used by the
+/// `ec` binary.
+pub const PARALLELISM: bool = false;
+
 #[derive(Clone, Copy)]
 pub enum Validation {
+    /// Every check of the state transition.
     Enabled,
+    /// Skip the signature over the block and the check of its state root against the post-state,
+    /// e.g. to compute the state root of a block being produced. The operations are still
+    /// validated in full.
     Disabled,
+    /// Full validation, except that attestations targeting an epoch below the given epoch are
+    /// assumed to be finalized: their signatures are not verified and their committees are not
+    /// re-derived. Intended for backfilling blocks below a known finalized checkpoint.
+    AssumeFinalizedBelow(Epoch),
+    /// Full validation, except that the BLS signatures over the block and its operations are not
+    /// verified, e.g. when replaying finalized blocks from a trusted source or because they were
+    /// verified together in a batch beforehand. The RANDAO reveal is still mixed in and the state
+    /// root is still checked. Deposit signatures are still verified as an invalid one skips the
+    /// deposit rather than the block.
+    SkipSignatureVerification,
+}
+
+impl Validation {
+    pub fn verifies_signatures(&self) -> bool {
+        !matches!(self, Self::SkipSignatureVerification)
+    }
+
+    /// Whether the state root of a block is checked against the post-state.
+    pub fn checks_state_root(&self) -> bool {
+        !matches!(self, Self::Disabled)
+    }
+
+    pub fn assume_finalized_below(&self) -> Option<Epoch> {
+        match self {
+            Self::AssumeFinalizedBelow(epoch) => Some(*epoch),
+            _ => None,
+        }
+    }
+
+    pub fn is_assumed_finalized(&self, target_epoch: Epoch) -> bool {
+        self.assume_finalized_below().map(|epoch| target_epoch < epoch).unwrap_or(false)
+    }
+}
+
+pub mod gnosis {
+    pub use super::presets::gnosis::*;
 }
 
 pub mod mainnet {
@@ -0,0 +1,512 @@
+//! A `BeaconState` whose largest fields can be moved out to a caller-provided [`BlobStore`] while
+//! the state sits idle, e.g. for tools holding many states at once, and are read back when the
+//! state is next used.
+//!
+//! The spillable fields are the growing lists of the state (the registry, the balances and the
+//! historical roots) and its largest vectors (the block and state roots and the randao mixes).
+//! A spilled list is left empty, while a spilled vector keeps its fixed length and is reset to
+//! its default contents. Blobs are keyed by the hash of their contents, so states sharing a field
+//! (e.g. the registry of consecutive states) share a blob.
+use crate::{
+    crypto::hash, primitives::Bytes32, sparse::StateField, ssz::prelude::*, types::BeaconState,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+use thiserror::Error;
+
+// The fields that can be spilled, in the order they are reported in.
+const SPILLABLE_FIELDS: &[StateField] = &[
+    StateField::BlockRoots,
+    StateField::StateRoots,
+    StateField::HistoricalRoots,
+    StateField::Validators,
+    StateField::Balances,
+    StateField::RandaoMixes,
+];
+
+#[derive(Debug, Error)]
+pub enum PagingError {
+    #[error("field {0} can not be spilled")]
+    Unsupported(StateField),
+    #[error("field {0} is pinned and can not be spilled")]
+    Pinned(StateField),
+    #[error("field {0} is spilled and must be pinned before the state is used")]
+    Spilled(StateField),
+    #[error("blob {key} holding field {field} is missing from the store")]
+    MissingBlob { field: StateField, key: Bytes32 },
+    #[error("blob holding field {field} does not match its key {key}")]
+    CorruptBlob { field: StateField, key: Bytes32 },
+    #[error("{0}")]
+    Serialize(#[from] SerializeError),
+    #[error("{0}")]
+    Deserialize(#[from] DeserializeError),
+}
+
+/// Storage for the fields spilled by a [`PagedState`].
+///
+/// A blob must be kept for as long as any state may read it back; states never remove blobs, as
+/// another state may share them.
+pub trait BlobStore {
+    fn get(&self, key: &Bytes32) -> Option<Vec<u8>>;
+    fn put(&self, key: Bytes32, blob: Vec<u8>);
+}
+
+impl<S: BlobStore + ?Sized> BlobStore for &S {
+    fn get(&self, key: &Bytes32) -> Option<Vec<u8>> {
+        (**self).get(key)
+    }
+
+    fn put(&self, key: Bytes32, blob: Vec<u8>) {
+        (**self).put(key, blob)
+    }
+}
+
+impl<S: BlobStore + ?Sized> BlobStore for Arc<S> {
+    fn get(&self, key: &Bytes32) -> Option<Vec<u8>> {
+        (**self).get(key)
+    }
+
+    fn put(&self, key: Bytes32, blob: Vec<u8>) {
+        (**self).put(key, blob)
+    }
+}
+
+/// A [`BlobStore`] in memory, e.g. for tests or to hold blobs compactly in one place.
+#[derive(Debug, Default)]
+pub struct MemoryBlobStore {
+    blobs: Mutex<HashMap<Bytes32, Vec<u8>>>,
+}
+
+impl MemoryBlobStore {
+    pub fn len(&self) -> usize {
+        self.blobs.lock().expect("not poisoned").len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl BlobStore for MemoryBlobStore {
+    fn get(&self, key: &Bytes32) -> Option<Vec<u8>> {
+        self.blobs.lock().expect("not poisoned").get(key).cloned()
+    }
+
+    fn put(&self, key: Bytes32, blob: Vec<u8>) {
+        self.blobs.lock().expect("not poisoned").insert(key, blob);
+    }
+}
+
+// Evaluate `$body` with `$value` bound to the spillable `$field` of `$state`.
+macro_rules! with_field {
+    ($state:expr, $field:expr, |$value:ident| $body:expr) => {
+        match $field {
+            StateField::BlockRoots => {
+                let $value = $state.block_roots_mut();
+                $body
+            }
+            StateField::StateRoots => {
+                let $value = $state.state_roots_mut();
+                $body
+            }
+            StateField::HistoricalRoots => {
+                let $value = $state.historical_roots_mut();
+                $body
+            }
+            StateField::Validators => {
+                let $value = $state.validators_mut();
+                $body
+            }
+            StateField::Balances => {
+                let $value = $state.balances_mut();
+                $body
+            }
+            StateField::RandaoMixes => {
+                let $value = $state.randao_mixes_mut();
+                $body
+            }
+            field => return Err(PagingError::Unsupported(field)),
+        }
+    };
+}
+
+pub struct PagedState<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    S: BlobStore,
+> {
+    state: BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    store: S,
+    // the key of the blob holding each spilled field, which is left empty in `state`
+    spilled: HashMap<StateField, Bytes32>,
+    pinned: HashSet<StateField>,
+}
+
+impl<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
+        S: BlobStore,
+    >
+    PagedState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+        S,
+    >
+{
+    pub fn new(
+        state: BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+        store: S,
+    ) -> Self {
+        Self { state, store, spilled: HashMap::new(), pinned: HashSet::new() }
+    }
+
+    /// Move `field` out of the state to the store, leaving it empty (or, for a vector, at its
+    /// default) until it is next loaded.
+    pub fn spill(&mut self, field: StateField) -> Result<(), PagingError> {
+        if self.pinned.contains(&field) {
+            return Err(PagingError::Pinned(field))
+        }
+        if self.spilled.contains_key(&field) {
+            return Ok(())
+        }
+        let blob = with_field!(self.state, field, |value| {
+            let blob = serialize(&*value)?;
+            *value = Default::default();
+            blob
+        });
+        let key = hash(&blob);
+        self.store.put(key.clone(), blob);
+        self.spilled.insert(field, key);
+        Ok(())
+    }
+
+    /// Read `field` back into the state from the store, if it is spilled.
+    pub fn load(&mut self, field: StateField) -> Result<(), PagingError> {
+        let Some(key) = self.spilled.get(&field) else { return Ok(()) };
+        let blob = self
+            .store
+            .get(key)
+            .ok_or_else(|| PagingError::MissingBlob { field, key: key.clone() })?;
+        if hash(&blob) != *key {
+            return Err(PagingError::CorruptBlob { field, key: key.clone() })
+        }
+        with_field!(self.state, field, |value| *value = deserialize(&blob)?);
+        self.spilled.remove(&field);
+        Ok(())
+    }
+
+    /// Load `field` and keep it in the state until it is unpinned, e.g. for the duration of a
+    /// state transition that reads it.
+    pub fn pin(&mut self, field: StateField) -> Result<(), PagingError> {
+        self.load(field)?;
+        self.pinned.insert(field);
+        Ok(())
+    }
+
+    pub fn unpin(&mut self, field: StateField) {
+        self.pinned.remove(&field);
+    }
+
+    pub fn is_spilled(&self, field: StateField) -> bool {
+        self.spilled.contains_key(&field)
+    }
+
+    pub fn is_pinned(&self, field: StateField) -> bool {
+        self.pinned.contains(&field)
+    }
+
+    pub fn spilled_fields(&self) -> impl Iterator<Item = StateField> + '_ {
+        SPILLABLE_FIELDS.iter().copied().filter(|field| self.is_spilled(*field))
+    }
+
+    /// The state as held in memory, with every spilled field empty.
+    pub fn resident(
+        &self,
+    ) -> &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    > {
+        &self.state
+    }
+
+    /// The whole state, loading any spilled field. Fields stay loaded until spilled again.
+    pub fn state(
+        &mut self,
+    ) -> Result<
+        &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+        PagingError,
+    > {
+        self.load_all()?;
+        Ok(&self.state)
+    }
+
+    /// Mutable access to the whole state, loading any spilled field.
+    pub fn state_mut(
+        &mut self,
+    ) -> Result<
+        &mut BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+        PagingError,
+    > {
+        self.load_all()?;
+        Ok(&mut self.state)
+    }
+
+    /// The whole state, loading any spilled field.
+    pub fn into_state(
+        mut self,
+    ) -> Result<
+        BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+        PagingError,
+    > {
+        self.load_all()?;
+        Ok(self.state)
+    }
+
+    /// The state for a state transition, which every spilled field must be pinned for.
+    pub(crate) fn into_pinned_state(
+        self,
+    ) -> Result<
+        BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+        PagingError,
+    > {
+        if let Some(field) = self.spilled_fields().next() {
+            return Err(PagingError::Spilled(field))
+        }
+        Ok(self.state)
+    }
+
+    fn load_all(&mut self) -> Result<(), PagingError> {
+        for &field in SPILLABLE_FIELDS {
+            self.load(field)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        duties::{get_committee_assignment, get_proposer_indices_for_epoch},
+        fixtures::phase0::genesis_state,
+        primitives::IsZero,
+        state_transition::{
+            minimal::{BeaconState, Executor, PagedState},
+            Context,
+        },
+        Error,
+    };
+
+    #[test]
+    fn test_duties_of_spilled_state() {
+        let context = Context::for_minimal();
+        let state = BeaconState::Phase0(genesis_state());
+        let proposers = get_proposer_indices_for_epoch(&state, 0, &context).unwrap();
+        let assignments = (0..state.validators().len())
+            .map(|index| get_committee_assignment(&state, 1, index, &context).unwrap())
+            .collect::<Vec<_>>();
+
+        let store = MemoryBlobStore::default();
+        let mut paged = PagedState::new(state.clone(), &store);
+        paged.spill(StateField::Validators).unwrap();
+        paged.spill(StateField::Balances).unwrap();
+        assert_eq!(store.len(), 2);
+        assert!(paged.resident().validators().is_empty());
+        assert_eq!(paged.spilled_fields().collect::<Vec<_>>().len(), 2);
+
+        // a second state with the same registry shares its blob
+        let mut other = PagedState::new(state.clone(), &store);
+        other.spill(StateField::Validators).unwrap();
+        assert_eq!(store.len(), 2);
+
+        let reloaded = paged.state().unwrap();
+        assert_eq!(get_proposer_indices_for_epoch(reloaded, 0, &context).unwrap(), proposers);
+        for (index, assignment) in assignments.iter().enumerate() {
+            let found = get_committee_assignment(reloaded, 1, index, &context).unwrap();
+            assert_eq!(&found, assignment);
+        }
+        assert!(!paged.is_spilled(StateField::Validators));
+        assert_eq!(paged.into_state().unwrap(), state);
+
+        // the historical vectors keep their length while spilled
+        let mut paged = PagedState::new(state.clone(), &store);
+        for field in [StateField::BlockRoots, StateField::StateRoots, StateField::RandaoMixes] {
+            paged.spill(field).unwrap();
+        }
+        assert_eq!(paged.spilled_fields().count(), 3);
+        assert_eq!(paged.resident().randao_mixes().len(), state.randao_mixes().len());
+        assert!(paged.resident().randao_mixes().iter().all(|mix| mix.is_zero()));
+        assert_eq!(paged.into_state().unwrap(), state);
+
+        let result = other.spill(StateField::Slashings);
+        assert!(matches!(result, Err(PagingError::Unsupported(StateField::Slashings))));
+    }
+
+    #[test]
+    fn test_executor_requires_pinned_fields() {
+        let context = Context::for_minimal();
+        let state = BeaconState::Phase0(genesis_state());
+        let store = MemoryBlobStore::default();
+
+        let mut paged = PagedState::new(state.clone(), &store);
+        paged.spill(StateField::Validators).unwrap();
+        let result = Executor::from_paged_state(paged, context.clone());
+        assert!(matches!(result, Err(Error::Paging(PagingError::Spilled(StateField::Validators)))));
+
+        let mut paged = PagedState::new(state.clone(), &store);
+        paged.spill(StateField::Validators).unwrap();
+        paged.pin(StateField::Validators).unwrap();
+        let result = paged.spill(StateField::Validators);
+        assert!(matches!(result, Err(PagingError::Pinned(StateField::Validators))));
+        let executor = Executor::from_paged_state(paged, context).unwrap();
+        assert_eq!(executor.state.inner(), &state);
+
+        // blobs are checked against their key when read back
+        let mut paged = PagedState::new(state, &store);
+        paged.spill(StateField::Balances).unwrap();
+        let key = paged.spilled[&StateField::Balances].clone();
+        store.put(key, vec![0; 8]);
+        let result = paged.load(StateField::Balances);
+        assert!(matches!(result, Err(PagingError::CorruptBlob { .. })));
+    }
+}
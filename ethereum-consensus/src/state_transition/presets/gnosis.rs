@@ -0,0 +1,245 @@
+use crate::{
+    altair::gnosis::SYNC_COMMITTEE_SIZE,
+    bellatrix::gnosis::{
+        BYTES_PER_LOGS_BLOOM, MAX_BYTES_PER_TRANSACTION, MAX_EXTRA_DATA_BYTES,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    },
+    capella::gnosis::{MAX_BLS_TO_EXECUTION_CHANGES, MAX_WITHDRAWALS_PER_PAYLOAD},
+    deneb::gnosis::MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    electra::gnosis::{
+        MAX_ATTESTATIONS_ELECTRA, MAX_ATTESTER_SLASHINGS_ELECTRA, MAX_COMMITTEES_PER_SLOT,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD, MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_VALIDATORS_PER_SLOT, MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD, PENDING_CONSOLIDATIONS_LIMIT,
+        PENDING_DEPOSITS_LIMIT, PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+    },
+    phase0::gnosis::{
+        EPOCHS_PER_HISTORICAL_VECTOR, EPOCHS_PER_SLASHINGS_VECTOR, ETH1_DATA_VOTES_BOUND,
+        HISTORICAL_ROOTS_LIMIT, MAX_ATTESTATIONS, MAX_ATTESTER_SLASHINGS, MAX_DEPOSITS,
+        MAX_PROPOSER_SLASHINGS, MAX_VALIDATORS_PER_COMMITTEE, MAX_VOLUNTARY_EXITS,
+        PENDING_ATTESTATIONS_BOUND, SLOTS_PER_HISTORICAL_ROOT, VALIDATOR_REGISTRY_LIMIT,
+    },
+    state_transition,
+};
+
+pub use crate::{
+    altair::gnosis as altair,
+    bellatrix::gnosis as bellatrix,
+    capella::gnosis as capella,
+    deneb::gnosis as deneb,
+    electra::gnosis as electra,
+    phase0::gnosis as phase0,
+    types::gnosis::{
+        BeaconBlock, BeaconBlockBody, BeaconState, BlindedBeaconBlock, BlindedBeaconBlockBody,
+        ExecutionPayload, ExecutionPayloadHeader, SignedBeaconBlock, SignedBlindedBeaconBlock,
+    },
+    Error,
+};
+pub use state_transition::{Context, Validation};
+
+pub const PRESET_NAME: &str = "gnosis";
+
+pub type Executor = state_transition::Executor<
+    SLOTS_PER_HISTORICAL_ROOT,
+    HISTORICAL_ROOTS_LIMIT,
+    ETH1_DATA_VOTES_BOUND,
+    VALIDATOR_REGISTRY_LIMIT,
+    EPOCHS_PER_HISTORICAL_VECTOR,
+    EPOCHS_PER_SLASHINGS_VECTOR,
+    MAX_VALIDATORS_PER_COMMITTEE,
+    PENDING_ATTESTATIONS_BOUND,
+    SYNC_COMMITTEE_SIZE,
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    PENDING_DEPOSITS_LIMIT,
+    PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+    PENDING_CONSOLIDATIONS_LIMIT,
+    MAX_BYTES_PER_TRANSACTION,
+    MAX_TRANSACTIONS_PER_PAYLOAD,
+    MAX_PROPOSER_SLASHINGS,
+    MAX_ATTESTER_SLASHINGS,
+    MAX_ATTESTATIONS,
+    MAX_DEPOSITS,
+    MAX_VOLUNTARY_EXITS,
+    MAX_WITHDRAWALS_PER_PAYLOAD,
+    MAX_BLS_TO_EXECUTION_CHANGES,
+    MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    MAX_VALIDATORS_PER_SLOT,
+    MAX_COMMITTEES_PER_SLOT,
+    MAX_ATTESTER_SLASHINGS_ELECTRA,
+    MAX_ATTESTATIONS_ELECTRA,
+    MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+    MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+    MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+>;
+
+pub type AdvancedState = state_transition::AdvancedState<
+    SLOTS_PER_HISTORICAL_ROOT,
+    HISTORICAL_ROOTS_LIMIT,
+    ETH1_DATA_VOTES_BOUND,
+    VALIDATOR_REGISTRY_LIMIT,
+    EPOCHS_PER_HISTORICAL_VECTOR,
+    EPOCHS_PER_SLASHINGS_VECTOR,
+    MAX_VALIDATORS_PER_COMMITTEE,
+    PENDING_ATTESTATIONS_BOUND,
+    SYNC_COMMITTEE_SIZE,
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    PENDING_DEPOSITS_LIMIT,
+    PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+    PENDING_CONSOLIDATIONS_LIMIT,
+>;
+
+pub type BlockImportOutcome =
+    state_transition::BlockImportOutcome<MAX_VALIDATORS_PER_COMMITTEE, MAX_VALIDATORS_PER_SLOT>;
+
+pub type CachedState = state_transition::CachedState<
+    SLOTS_PER_HISTORICAL_ROOT,
+    HISTORICAL_ROOTS_LIMIT,
+    ETH1_DATA_VOTES_BOUND,
+    VALIDATOR_REGISTRY_LIMIT,
+    EPOCHS_PER_HISTORICAL_VECTOR,
+    EPOCHS_PER_SLASHINGS_VECTOR,
+    MAX_VALIDATORS_PER_COMMITTEE,
+    PENDING_ATTESTATIONS_BOUND,
+    SYNC_COMMITTEE_SIZE,
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    PENDING_DEPOSITS_LIMIT,
+    PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+    PENDING_CONSOLIDATIONS_LIMIT,
+>;
+
+pub type PagedState<S> = state_transition::PagedState<
+    SLOTS_PER_HISTORICAL_ROOT,
+    HISTORICAL_ROOTS_LIMIT,
+    ETH1_DATA_VOTES_BOUND,
+    VALIDATOR_REGISTRY_LIMIT,
+    EPOCHS_PER_HISTORICAL_VECTOR,
+    EPOCHS_PER_SLASHINGS_VECTOR,
+    MAX_VALIDATORS_PER_COMMITTEE,
+    PENDING_ATTESTATIONS_BOUND,
+    SYNC_COMMITTEE_SIZE,
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    PENDING_DEPOSITS_LIMIT,
+    PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+    PENDING_CONSOLIDATIONS_LIMIT,
+    S,
+>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        clock::GNOSIS_GENESIS_TIME,
+        configs,
+        deneb::KzgCommitment,
+        error::{InvalidBlock, InvalidExecutionPayload, InvalidOperation},
+        fixtures::secret_keys,
+        networks::Network,
+        primitives::{FAR_FUTURE_EPOCH, GENESIS_EPOCH},
+        Fork,
+    };
+
+    // A gnosis genesis state with a validator for each of the fixture keys.
+    fn genesis_state(context: &Context) -> phase0::BeaconState {
+        let mut state = phase0::BeaconState {
+            genesis_time: GNOSIS_GENESIS_TIME,
+            fork: phase0::Fork {
+                previous_version: context.genesis_fork_version,
+                current_version: context.genesis_fork_version,
+                epoch: GENESIS_EPOCH,
+            },
+            ..Default::default()
+        };
+        for secret_key in secret_keys() {
+            state.validators.push(phase0::Validator {
+                public_key: secret_key.public_key(),
+                effective_balance: context.max_effective_balance,
+                exit_epoch: FAR_FUTURE_EPOCH,
+                withdrawable_epoch: FAR_FUTURE_EPOCH,
+                ..Default::default()
+            });
+            state.balances.push(context.max_effective_balance);
+        }
+        state
+    }
+
+    #[test]
+    fn test_gnosis_values() {
+        let context = Context::try_from(Network::Gnosis).unwrap();
+        assert_eq!(context.network(), &Network::Gnosis);
+        assert_eq!(context.slots_per_epoch, 16);
+        assert_eq!(context.base_reward_factor, 25);
+        assert_eq!(context.epochs_per_sync_committee_period, 512);
+        assert_eq!(context.max_withdrawals_per_payload, 8);
+        assert_eq!(context.max_validators_per_withdrawals_sweep, 8192);
+        assert_eq!(context.seconds_per_slot, 5);
+        assert_eq!(context.genesis_time().unwrap(), GNOSIS_GENESIS_TIME);
+        // the first slot of deneb
+        let deneb_slot = configs::gnosis::DENEB_FORK_EPOCH * context.slots_per_epoch;
+        assert_eq!(context.timestamp_at_slot(deneb_slot, GNOSIS_GENESIS_TIME).unwrap(), 1710181820);
+        assert_eq!(context.fork_for(deneb_slot - 1), Fork::Capella);
+        assert_eq!(context.fork_for(deneb_slot), Fork::Deneb);
+        assert_eq!(crate::da_check::max_blobs_per_block_at(context.deneb_fork_epoch, &context), 2);
+    }
+
+    #[test]
+    fn test_gnosis_chain_smoke() {
+        let mut context = Context::for_gnosis();
+        context.altair_fork_epoch = 1;
+        context.bellatrix_fork_epoch = 2;
+        context.capella_fork_epoch = 3;
+        context.deneb_fork_epoch = 4;
+        context.blob_schedule[0].epoch = context.deneb_fork_epoch;
+        let state = BeaconState::Phase0(genesis_state(&context));
+        let mut executor = Executor::new(state, context.clone());
+
+        let slot = (context.deneb_fork_epoch + 1) * context.slots_per_epoch;
+        executor.process_slots(slot).unwrap();
+        assert_eq!(executor.state.version(), Fork::Deneb);
+        assert_eq!(executor.state.slot(), slot);
+        let BeaconState::Deneb(state) = executor.state.inner() else { unreachable!() };
+        assert_eq!(state.validators.len(), secret_keys().len());
+        assert_eq!(state.fork.current_version, configs::gnosis::DENEB_FORK_VERSION);
+
+        // a payload may carry as many blobs as the schedule allows, not the preset
+        let epoch = deneb::get_current_epoch(state, &context);
+        let mut body = deneb::BeaconBlockBody::default();
+        let payload = &mut body.execution_payload;
+        payload.parent_hash = state.latest_execution_payload_header.block_hash.clone();
+        payload.prev_randao = deneb::get_randao_mix(state, epoch).clone();
+        payload.timestamp = deneb::compute_timestamp_at_slot(state, slot, &context).unwrap();
+        for _ in 0..configs::gnosis::MAX_BLOBS_PER_BLOCK {
+            body.blob_kzg_commitments.push(KzgCommitment::default());
+        }
+        deneb::process_execution_payload(&mut state.clone(), &body, &context).unwrap();
+
+        body.blob_kzg_commitments.push(KzgCommitment::default());
+        assert!(body.blob_kzg_commitments.len() <= deneb::MAX_BLOBS_PER_BLOCK);
+        let Err(Error::InvalidBlock(err)) =
+            deneb::process_execution_payload(&mut state.clone(), &body, &context)
+        else {
+            panic!("block has too many blobs")
+        };
+        assert!(matches!(
+            *err,
+            InvalidBlock::InvalidOperation(InvalidOperation::ExecutionPayload(
+                InvalidExecutionPayload::InvalidBlobCommitments { provided: 3, limit: 2 }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_context_matches_preset() {
+        let context = Context::for_gnosis();
+        assert_eq!(context.slots_per_historical_root, SLOTS_PER_HISTORICAL_ROOT as u64);
+        assert_eq!(context.epochs_per_historical_vector, EPOCHS_PER_HISTORICAL_VECTOR as u64);
+        assert_eq!(context.validator_registry_limit, VALIDATOR_REGISTRY_LIMIT);
+        assert_eq!(context.max_validators_per_committee, MAX_VALIDATORS_PER_COMMITTEE);
+        assert_eq!(context.max_attestations, MAX_ATTESTATIONS);
+        assert_eq!(context.sync_committee_size, SYNC_COMMITTEE_SIZE);
+        assert_eq!(context.max_withdrawals_per_payload, MAX_WITHDRAWALS_PER_PAYLOAD);
+        assert_eq!(context.max_blob_commitments_per_block, MAX_BLOB_COMMITMENTS_PER_BLOCK);
+    }
+}
@@ -6,6 +6,12 @@ use crate::{
     },
     capella::minimal::{MAX_BLS_TO_EXECUTION_CHANGES, MAX_WITHDRAWALS_PER_PAYLOAD},
     deneb::minimal::MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    electra::minimal::{
+        MAX_ATTESTATIONS_ELECTRA, MAX_ATTESTER_SLASHINGS_ELECTRA, MAX_COMMITTEES_PER_SLOT,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD, MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_VALIDATORS_PER_SLOT, MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD, PENDING_CONSOLIDATIONS_LIMIT,
+        PENDING_DEPOSITS_LIMIT, PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+    },
     phase0::minimal::{
         EPOCHS_PER_HISTORICAL_VECTOR, EPOCHS_PER_SLASHINGS_VECTOR, ETH1_DATA_VOTES_BOUND,
         HISTORICAL_ROOTS_LIMIT, MAX_ATTESTATIONS, MAX_ATTESTER_SLASHINGS, MAX_DEPOSITS,
@@ -15,9 +21,23 @@ use crate::{
     state_transition,
 };
 
-pub use crate::Error;
+pub use crate::{
+    altair::minimal as altair,
+    bellatrix::minimal as bellatrix,
+    capella::minimal as capella,
+    deneb::minimal as deneb,
+    electra::minimal as electra,
+    phase0::minimal as phase0,
+    types::minimal::{
+        BeaconBlock, BeaconBlockBody, BeaconState, BlindedBeaconBlock, BlindedBeaconBlockBody,
+        ExecutionPayload, ExecutionPayloadHeader, SignedBeaconBlock, SignedBlindedBeaconBlock,
+    },
+    Error,
+};
 pub use state_transition::{Context, Validation};
 
+pub const PRESET_NAME: &str = "minimal";
+
 pub type Executor = state_transition::Executor<
     SLOTS_PER_HISTORICAL_ROOT,
     HISTORICAL_ROOTS_LIMIT,
@@ -30,6 +50,9 @@ pub type Executor = state_transition::Executor<
     SYNC_COMMITTEE_SIZE,
     BYTES_PER_LOGS_BLOOM,
     MAX_EXTRA_DATA_BYTES,
+    PENDING_DEPOSITS_LIMIT,
+    PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+    PENDING_CONSOLIDATIONS_LIMIT,
     MAX_BYTES_PER_TRANSACTION,
     MAX_TRANSACTIONS_PER_PAYLOAD,
     MAX_PROPOSER_SLASHINGS,
@@ -40,4 +63,84 @@ pub type Executor = state_transition::Executor<
     MAX_WITHDRAWALS_PER_PAYLOAD,
     MAX_BLS_TO_EXECUTION_CHANGES,
     MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    MAX_VALIDATORS_PER_SLOT,
+    MAX_COMMITTEES_PER_SLOT,
+    MAX_ATTESTER_SLASHINGS_ELECTRA,
+    MAX_ATTESTATIONS_ELECTRA,
+    MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+    MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+    MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+>;
+
+pub type AdvancedState = state_transition::AdvancedState<
+    SLOTS_PER_HISTORICAL_ROOT,
+    HISTORICAL_ROOTS_LIMIT,
+    ETH1_DATA_VOTES_BOUND,
+    VALIDATOR_REGISTRY_LIMIT,
+    EPOCHS_PER_HISTORICAL_VECTOR,
+    EPOCHS_PER_SLASHINGS_VECTOR,
+    MAX_VALIDATORS_PER_COMMITTEE,
+    PENDING_ATTESTATIONS_BOUND,
+    SYNC_COMMITTEE_SIZE,
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    PENDING_DEPOSITS_LIMIT,
+    PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+    PENDING_CONSOLIDATIONS_LIMIT,
+>;
+
+pub type BlockImportOutcome =
+    state_transition::BlockImportOutcome<MAX_VALIDATORS_PER_COMMITTEE, MAX_VALIDATORS_PER_SLOT>;
+
+pub type CachedState = state_transition::CachedState<
+    SLOTS_PER_HISTORICAL_ROOT,
+    HISTORICAL_ROOTS_LIMIT,
+    ETH1_DATA_VOTES_BOUND,
+    VALIDATOR_REGISTRY_LIMIT,
+    EPOCHS_PER_HISTORICAL_VECTOR,
+    EPOCHS_PER_SLASHINGS_VECTOR,
+    MAX_VALIDATORS_PER_COMMITTEE,
+    PENDING_ATTESTATIONS_BOUND,
+    SYNC_COMMITTEE_SIZE,
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    PENDING_DEPOSITS_LIMIT,
+    PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+    PENDING_CONSOLIDATIONS_LIMIT,
 >;
+
+pub type PagedState<S> = state_transition::PagedState<
+    SLOTS_PER_HISTORICAL_ROOT,
+    HISTORICAL_ROOTS_LIMIT,
+    ETH1_DATA_VOTES_BOUND,
+    VALIDATOR_REGISTRY_LIMIT,
+    EPOCHS_PER_HISTORICAL_VECTOR,
+    EPOCHS_PER_SLASHINGS_VECTOR,
+    MAX_VALIDATORS_PER_COMMITTEE,
+    PENDING_ATTESTATIONS_BOUND,
+    SYNC_COMMITTEE_SIZE,
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    PENDING_DEPOSITS_LIMIT,
+    PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+    PENDING_CONSOLIDATIONS_LIMIT,
+    S,
+>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_matches_preset() {
+        let context = Context::for_minimal();
+        assert_eq!(context.slots_per_historical_root, SLOTS_PER_HISTORICAL_ROOT as u64);
+        assert_eq!(context.epochs_per_historical_vector, EPOCHS_PER_HISTORICAL_VECTOR as u64);
+        assert_eq!(context.validator_registry_limit, VALIDATOR_REGISTRY_LIMIT);
+        assert_eq!(context.max_validators_per_committee, MAX_VALIDATORS_PER_COMMITTEE);
+        assert_eq!(context.max_attestations, MAX_ATTESTATIONS);
+        assert_eq!(context.sync_committee_size, SYNC_COMMITTEE_SIZE);
+        assert_eq!(context.max_withdrawals_per_payload, MAX_WITHDRAWALS_PER_PAYLOAD);
+        assert_eq!(context.max_blob_commitments_per_block, MAX_BLOB_COMMITMENTS_PER_BLOCK);
+    }
+}
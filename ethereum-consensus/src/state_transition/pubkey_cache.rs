@@ -0,0 +1,265 @@
+use crate::{
+    crypto::DecompressedPublicKey,
+    phase0::{is_active_validator, Validator},
+    primitives::{BlsPublicKey, Epoch, ValidatorIndex},
+    state_transition::Result,
+};
+use std::{borrow::Cow, collections::HashMap, mem::size_of};
+
+/// Which validators a [`DecompressedPubkeyCache`] holds keys for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PubkeyCacheMode {
+    /// Every validator in the registry.
+    #[default]
+    All,
+    /// Only validators active at the epoch the cache was last warmed at; keys of other validators
+    /// are decompressed on demand and not retained.
+    ActiveOnly,
+    /// No validators; every key is decompressed on demand.
+    Disabled,
+}
+
+/// A cache of decompressed validator public keys, keyed by validator index.
+///
+/// Each entry keeps the compressed key it was decompressed from and lookups must present the
+/// compressed key they expect, so a cache shared across states (or left stale by a reorg) never
+/// yields a key for the wrong validator.
+#[derive(Debug, Clone, Default)]
+pub struct DecompressedPubkeyCache {
+    mode: PubkeyCacheMode,
+    keys: HashMap<ValidatorIndex, (BlsPublicKey, DecompressedPublicKey)>,
+}
+
+impl DecompressedPubkeyCache {
+    pub fn new(mode: PubkeyCacheMode) -> Self {
+        Self { mode, keys: Default::default() }
+    }
+
+    pub fn mode(&self) -> PubkeyCacheMode {
+        self.mode
+    }
+
+    /// Decompress the keys in `validators` not yet in the cache, e.g. the validators appended by
+    /// deposits since the last call.
+    ///
+    /// In `ActiveOnly` mode only validators active at `epoch` are cached and the keys of any
+    /// other validators are dropped. In `Disabled` mode nothing is cached.
+    pub fn warm(&mut self, validators: &[Validator], epoch: Epoch) -> Result<()> {
+        if self.mode == PubkeyCacheMode::Disabled {
+            return Ok(())
+        }
+        if self.mode == PubkeyCacheMode::ActiveOnly {
+            self.keys.retain(|&index, _| {
+                validators
+                    .get(index)
+                    .map_or(false, |validator| is_active_validator(validator, epoch))
+            });
+        }
+        for (index, validator) in validators.iter().enumerate() {
+            if self.mode == PubkeyCacheMode::ActiveOnly && !is_active_validator(validator, epoch) {
+                continue
+            }
+            if self.get(index, &validator.public_key).is_some() {
+                continue
+            }
+            self.insert(index, &validator.public_key)?;
+        }
+        Ok(())
+    }
+
+    /// The decompressed key of validator `index` if it is cached for `public_key`.
+    pub fn get(
+        &self,
+        index: ValidatorIndex,
+        public_key: &BlsPublicKey,
+    ) -> Option<&DecompressedPublicKey> {
+        self.keys
+            .get(&index)
+            .filter(|(compressed, _)| compressed == public_key)
+            .map(|(_, decompressed)| decompressed)
+    }
+
+    /// The decompressed key of validator `index`, decompressing `public_key` if it is not cached.
+    ///
+    /// The key is only retained in `All` mode; in `ActiveOnly` mode membership is decided by
+    /// [`Self::warm`] and in `Disabled` mode nothing is retained.
+    pub fn get_or_decompress(
+        &mut self,
+        index: ValidatorIndex,
+        public_key: &BlsPublicKey,
+    ) -> Result<Cow<'_, DecompressedPublicKey>> {
+        if self.get(index, public_key).is_none() {
+            if self.mode != PubkeyCacheMode::All {
+                return Ok(Cow::Owned(DecompressedPublicKey::try_from(public_key)?))
+            }
+            self.insert(index, public_key)?;
+        }
+        Ok(Cow::Borrowed(self.get(index, public_key).expect("key is cached")))
+    }
+
+    fn insert(&mut self, index: ValidatorIndex, public_key: &BlsPublicKey) -> Result<()> {
+        let decompressed = DecompressedPublicKey::try_from(public_key)?;
+        self.keys.insert(index, (public_key.clone(), decompressed));
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.keys.clear();
+    }
+
+    /// Approximate bytes held by the cache: the entries of the map holding them, with one control
+    /// byte each, and the bytes of the compressed keys.
+    pub fn memory_estimate(&self) -> usize {
+        let entry = size_of::<(ValidatorIndex, (BlsPublicKey, DecompressedPublicKey))>() + 1;
+        self.keys.values().map(|(compressed, _)| entry + compressed.len()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        crypto::{
+            fast_aggregate_verify, fast_aggregate_verify_decompressed, verify_signature,
+            verify_signature_decompressed,
+        },
+        fixtures::{secret_keys, VALIDATOR_COUNT},
+        primitives::FAR_FUTURE_EPOCH,
+    };
+
+    fn registry(count: usize) -> Vec<Validator> {
+        secret_keys()
+            .iter()
+            .take(count)
+            .map(|secret_key| Validator {
+                public_key: secret_key.public_key(),
+                exit_epoch: FAR_FUTURE_EPOCH,
+                withdrawable_epoch: FAR_FUTURE_EPOCH,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cached_and_uncached_verification_agree() {
+        let secret_keys = secret_keys();
+        let initial_count = VALIDATOR_COUNT / 2;
+        let mut validators = registry(initial_count);
+        let mut cache = DecompressedPubkeyCache::default();
+        cache.warm(&validators, 0).unwrap();
+        assert_eq!(cache.len(), initial_count);
+
+        let msg = b"message";
+        let check = |cache: &DecompressedPubkeyCache, validators: &[Validator]| {
+            for (index, validator) in validators.iter().enumerate() {
+                let public_key = cache.get(index, &validator.public_key).unwrap();
+                let valid = secret_keys[index].sign(msg);
+                let invalid = secret_keys[(index + 1) % VALIDATOR_COUNT].sign(msg);
+                for signature in [valid, invalid] {
+                    assert_eq!(
+                        verify_signature(&validator.public_key, msg, &signature).is_ok(),
+                        verify_signature_decompressed(public_key, msg, &signature).is_ok(),
+                    );
+                }
+            }
+        };
+        check(&cache, &validators);
+
+        // new deposits append validators, which are picked up by the next warm
+        validators.extend(registry(VALIDATOR_COUNT).into_iter().skip(initial_count));
+        assert!(cache.get(initial_count, &validators[initial_count].public_key).is_none());
+        cache.warm(&validators, 0).unwrap();
+        assert_eq!(cache.len(), VALIDATOR_COUNT);
+        check(&cache, &validators);
+
+        let signature = crate::crypto::aggregate(
+            &secret_keys.iter().map(|secret_key| secret_key.sign(msg)).collect::<Vec<_>>(),
+        )
+        .unwrap();
+        let public_keys =
+            validators.iter().map(|validator| &validator.public_key).collect::<Vec<_>>();
+        let decompressed = validators
+            .iter()
+            .enumerate()
+            .map(|(index, validator)| cache.get(index, &validator.public_key).unwrap())
+            .collect::<Vec<_>>();
+        assert!(fast_aggregate_verify(&public_keys, msg, &signature).is_ok());
+        assert!(fast_aggregate_verify_decompressed(&decompressed, msg, &signature).is_ok());
+    }
+
+    #[test]
+    fn test_lookup_checks_compressed_key() {
+        let validators = registry(2);
+        let mut cache = DecompressedPubkeyCache::default();
+        cache.warm(&validators, 0).unwrap();
+        assert!(cache.get(0, &validators[1].public_key).is_none());
+
+        let public_key =
+            cache.get_or_decompress(0, &validators[1].public_key).unwrap().into_owned();
+        assert_eq!(&public_key, cache.get(1, &validators[1].public_key).unwrap());
+        // the entry for the index is replaced with the key presented
+        assert!(cache.get(0, &validators[1].public_key).is_some());
+        assert!(cache.get(0, &validators[0].public_key).is_none());
+    }
+
+    #[test]
+    fn test_active_only_mode() {
+        let mut validators = registry(4);
+        validators[1].exit_epoch = 2;
+        validators[2].activation_epoch = 3;
+        let mut cache = DecompressedPubkeyCache::new(PubkeyCacheMode::ActiveOnly);
+
+        cache.warm(&validators, 0).unwrap();
+        assert_eq!(cache.len(), 3);
+        assert!(cache.get(2, &validators[2].public_key).is_none());
+
+        cache.warm(&validators, 3).unwrap();
+        assert_eq!(cache.len(), 3);
+        assert!(cache.get(1, &validators[1].public_key).is_none());
+        assert!(cache.get(2, &validators[2].public_key).is_some());
+
+        // inactive keys are still served but not retained
+        let public_key = cache.get_or_decompress(1, &validators[1].public_key).unwrap();
+        assert!(matches!(public_key, Cow::Owned(_)));
+        drop(public_key);
+        assert_eq!(cache.len(), 3);
+    }
+
+    #[test]
+    fn test_disabled_mode() {
+        let validators = registry(4);
+        let mut cache = DecompressedPubkeyCache::new(PubkeyCacheMode::Disabled);
+        cache.warm(&validators, 0).unwrap();
+        assert!(cache.is_empty());
+
+        let public_key = cache.get_or_decompress(0, &validators[0].public_key).unwrap();
+        assert!(matches!(public_key, Cow::Owned(_)));
+        let expected = DecompressedPublicKey::try_from(&validators[0].public_key).unwrap();
+        assert_eq!(*public_key, expected);
+        drop(public_key);
+        assert!(cache.is_empty());
+        assert_eq!(cache.memory_estimate(), 0);
+    }
+
+    #[test]
+    fn test_memory_estimate() {
+        let validators = registry(VALIDATOR_COUNT);
+        let mut cache = DecompressedPubkeyCache::default();
+        cache.warm(&validators, 0).unwrap();
+
+        // the estimate is within a factor of two of the allocations of the map and the keys
+        let entry = size_of::<(ValidatorIndex, (BlsPublicKey, DecompressedPublicKey))>() + 1;
+        let key_bytes = VALIDATOR_COUNT * validators[0].public_key.len();
+        let allocated = cache.keys.capacity() * entry + key_bytes;
+        let estimate = cache.memory_estimate();
+        assert!(estimate <= allocated && 2 * estimate >= allocated);
+    }
+}
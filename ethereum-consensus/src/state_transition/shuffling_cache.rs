@@ -0,0 +1,693 @@
+use crate::{
+    crypto::hash,
+    phase0::{
+        compute_committee_position, compute_epoch_at_slot, compute_shuffled_indices,
+        compute_unshuffled_index, is_active_validator,
+    },
+    primitives::{Bytes32, CommitteeIndex, DomainType, Epoch, Root, Slot, ValidatorIndex},
+    state_transition::{Context, Result},
+    types::BeaconState,
+};
+use std::{collections::BTreeMap, mem::size_of};
+use thiserror::Error;
+
+const ENCODING_VERSION: u8 = 2;
+
+#[derive(Debug, Error)]
+pub enum ShufflingCacheError {
+    #[error("malformed shuffling cache encoding")]
+    Malformed,
+    #[error("unsupported shuffling cache encoding version {0}")]
+    UnsupportedVersion(u8),
+    #[error("cached shuffling for epoch {0} does not match the provided state")]
+    Mismatch(Epoch),
+}
+
+/// The attester shuffling for a single epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shuffling {
+    pub epoch: Epoch,
+    pub seed: Bytes32,
+    pub active_validator_indices: Vec<ValidatorIndex>,
+    shuffled_indices: Vec<ValidatorIndex>,
+}
+
+impl Shuffling {
+    pub fn new(
+        epoch: Epoch,
+        seed: Bytes32,
+        active_validator_indices: Vec<ValidatorIndex>,
+        context: &Context,
+    ) -> Self {
+        let shuffled_indices = compute_shuffled_indices(&active_validator_indices, &seed, context);
+        Self { epoch, seed, active_validator_indices, shuffled_indices }
+    }
+
+    pub fn committee_count_per_slot(&self, context: &Context) -> usize {
+        let committees = self.active_validator_indices.len() as u64 /
+            context.slots_per_epoch /
+            context.target_committee_size;
+        committees.clamp(1, context.max_committees_per_slot) as usize
+    }
+
+    pub fn beacon_committee(
+        &self,
+        slot: Slot,
+        index: CommitteeIndex,
+        context: &Context,
+    ) -> Result<&[ValidatorIndex]> {
+        if compute_epoch_at_slot(slot, context) != self.epoch {
+            let lower_bound = self.epoch * context.slots_per_epoch;
+            let upper_bound = lower_bound + context.slots_per_epoch - 1;
+            return Err(crate::Error::SlotOutOfRange { requested: slot, lower_bound, upper_bound })
+        }
+        let committees_per_slot = self.committee_count_per_slot(context);
+        if index >= committees_per_slot {
+            return Err(crate::Error::OutOfBounds { requested: index, bound: committees_per_slot })
+        }
+        let index = (slot % context.slots_per_epoch) as usize * committees_per_slot + index;
+        let count = committees_per_slot * context.slots_per_epoch as usize;
+        let index_count = self.shuffled_indices.len();
+        let start = index_count * index / count;
+        let end = index_count * (index + 1) / count;
+        Ok(&self.shuffled_indices[start..end])
+    }
+
+    /// The slot, committee index and position within that committee of `validator_index`, or
+    /// `None` if the validator is not active in the epoch of this shuffling.
+    pub fn committee_position_of(
+        &self,
+        validator_index: ValidatorIndex,
+        context: &Context,
+    ) -> Option<(Slot, CommitteeIndex, usize)> {
+        let index = self.active_validator_indices.binary_search(&validator_index).ok()?;
+        let index_count = self.active_validator_indices.len();
+        let position = compute_unshuffled_index(index, index_count, &self.seed, context).ok()?;
+        let committees_per_slot = self.committee_count_per_slot(context);
+        Some(compute_committee_position(
+            position,
+            index_count,
+            committees_per_slot,
+            self.epoch,
+            context,
+        ))
+    }
+}
+
+/// A cache of attester shufflings, keyed by epoch and decision root (see
+/// `shuffling_decision_root`), holding at most `max_entries` shufflings if bounded: the earliest
+/// epochs are evicted first.
+///
+/// Keying by the decision root keeps the shufflings of competing forks apart, so a cache can be
+/// shared across a reorg.
+///
+/// The cache can be persisted with `serialize` and restored with `deserialize`; restored caches
+/// should be checked against a state with `verify` (e.g. via `Executor::prewarm_shuffling_cache`)
+/// before use.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShufflingCache {
+    shufflings: BTreeMap<(Epoch, Root), Shuffling>,
+    max_entries: Option<usize>,
+}
+
+impl ShufflingCache {
+    pub fn new(max_entries: Option<usize>) -> Self {
+        Self { shufflings: Default::default(), max_entries }
+    }
+
+    pub fn max_entries(&self) -> Option<usize> {
+        self.max_entries
+    }
+
+    pub fn get(&self, epoch: Epoch, decision_root: Root) -> Option<&Shuffling> {
+        self.shufflings.get(&(epoch, decision_root))
+    }
+
+    /// The cached shufflings of `epoch` with their decision roots, one for each fork seen.
+    pub fn shufflings_at(&self, epoch: Epoch) -> impl Iterator<Item = (Root, &Shuffling)> {
+        self.shufflings
+            .range((epoch, Root::default())..)
+            .take_while(move |((cached_epoch, _), _)| *cached_epoch == epoch)
+            .map(|((_, decision_root), shuffling)| (*decision_root, shuffling))
+    }
+
+    /// Drop the shufflings of `epoch`, e.g. once a reorg has replaced the block that decided
+    /// them.
+    pub fn remove(&mut self, epoch: Epoch) {
+        self.shufflings.retain(|&(cached_epoch, _), _| cached_epoch != epoch);
+    }
+
+    pub fn insert(&mut self, decision_root: Root, shuffling: Shuffling) {
+        self.shufflings.insert((shuffling.epoch, decision_root), shuffling);
+        if let Some(max_entries) = self.max_entries {
+            while self.shufflings.len() > max_entries {
+                self.shufflings.pop_first();
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.shufflings.len()
+    }
+
+    /// The cached shufflings with their decision roots, in epoch order.
+    pub fn into_shufflings(self) -> impl Iterator<Item = (Root, Shuffling)> {
+        self.shufflings
+            .into_iter()
+            .map(|((_, decision_root), shuffling)| (decision_root, shuffling))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shufflings.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.shufflings.clear();
+    }
+
+    /// Approximate bytes held by the cache: the indices of each shuffling and its entry in the
+    /// map.
+    pub fn memory_estimate(&self) -> usize {
+        self.shufflings
+            .values()
+            .map(|shuffling| {
+                let indices =
+                    shuffling.active_validator_indices.len() + shuffling.shuffled_indices.len();
+                size_of::<((Epoch, Root), Shuffling)>() +
+                    shuffling.seed.len() +
+                    indices * size_of::<ValidatorIndex>()
+            })
+            .sum()
+    }
+
+    /// Drop all shufflings for epochs before `epoch`.
+    pub fn prune(&mut self, epoch: Epoch) {
+        self.shufflings = self.shufflings.split_off(&(epoch, Root::default()));
+    }
+
+    /// Encode the cache as: a version byte, the number of entries and then, for each entry, the
+    /// epoch, the decision root, the seed and the (ascending) active validator indices as
+    /// delta-encoded varints.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut buffer = vec![ENCODING_VERSION];
+        write_varint(&mut buffer, self.shufflings.len() as u64);
+        for ((epoch, decision_root), shuffling) in &self.shufflings {
+            write_varint(&mut buffer, *epoch);
+            buffer.extend_from_slice(decision_root.as_ref());
+            buffer.extend_from_slice(shuffling.seed.as_ref());
+            write_varint(&mut buffer, shuffling.active_validator_indices.len() as u64);
+            let mut previous = 0;
+            for &index in &shuffling.active_validator_indices {
+                write_varint(&mut buffer, (index - previous) as u64);
+                previous = index;
+            }
+        }
+        buffer
+    }
+
+    /// Decode a cache produced by `serialize`. The shuffled indices are recomputed from the
+    /// decoded seeds, so the result must still be checked against a trusted state before use.
+    pub fn deserialize(bytes: &[u8], context: &Context) -> Result<Self> {
+        let mut reader = Reader { bytes, offset: 0 };
+        let version = reader.read_byte()?;
+        if version != ENCODING_VERSION {
+            return Err(ShufflingCacheError::UnsupportedVersion(version).into())
+        }
+        let count = reader.read_varint()?;
+        let mut cache = Self::default();
+        for _ in 0..count {
+            let epoch = reader.read_varint()?;
+            let decision_root = Root::try_from(reader.read_bytes(32)?)
+                .map_err(|_| ShufflingCacheError::Malformed)?;
+            let seed = Bytes32::try_from(reader.read_bytes(32)?)
+                .map_err(|_| ShufflingCacheError::Malformed)?;
+            let len = reader.read_varint()? as usize;
+            // NOTE: each index takes at least one byte, bounding the allocation by the input
+            if len > reader.remaining() {
+                return Err(ShufflingCacheError::Malformed.into())
+            }
+            let mut active_validator_indices = Vec::with_capacity(len);
+            let mut previous: ValidatorIndex = 0;
+            for i in 0..len {
+                let delta = reader.read_varint()? as usize;
+                if i > 0 && delta == 0 {
+                    return Err(ShufflingCacheError::Malformed.into())
+                }
+                let index = previous.checked_add(delta).ok_or(ShufflingCacheError::Malformed)?;
+                active_validator_indices.push(index);
+                previous = index;
+            }
+            if cache.shufflings.contains_key(&(epoch, decision_root)) {
+                return Err(ShufflingCacheError::Malformed.into())
+            }
+            let shuffling = Shuffling::new(epoch, seed, active_validator_indices, context);
+            cache.insert(decision_root, shuffling);
+        }
+        if reader.remaining() != 0 {
+            return Err(ShufflingCacheError::Malformed.into())
+        }
+        Ok(cache)
+    }
+
+    /// Check every cached shuffling against the decision root, seed and active validator
+    /// indices derived from `state`. Only the previous, current and next epochs of `state` can
+    /// be checked, so entries for any other epoch are rejected.
+    pub fn verify<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    >(
+        &self,
+        state: &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+        context: &Context,
+    ) -> Result<()> {
+        let current_epoch = compute_epoch_at_slot(state.slot(), context);
+        for (&(epoch, decision_root), shuffling) in &self.shufflings {
+            let is_in_range = epoch + 1 >= current_epoch && epoch <= current_epoch + 1;
+            if !is_in_range ||
+                decision_root != shuffling_decision_root(state, epoch, context) ||
+                shuffling.seed != compute_attester_seed(state, epoch, context) ||
+                shuffling.active_validator_indices != active_validator_indices(state, epoch)
+            {
+                return Err(ShufflingCacheError::Mismatch(epoch).into())
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compute the attester shuffling for `epoch`, which must be within one epoch of `state`.
+pub fn compute_shuffling<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    epoch: Epoch,
+    context: &Context,
+) -> Shuffling {
+    let seed = compute_attester_seed(state, epoch, context);
+    Shuffling::new(epoch, seed, active_validator_indices(state, epoch), context)
+}
+
+/// The root of the block that decided the attester shuffling of `epoch`, which must be within one
+/// epoch of `state`: the latest block before the first slot of `epoch - 1`, after which neither
+/// the seed nor the active validators of `epoch` can change.
+///
+/// The shufflings of the first two epochs are decided at genesis, shared by every fork of the
+/// chain, and have the zero root.
+pub fn shuffling_decision_root<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    epoch: Epoch,
+    context: &Context,
+) -> Root {
+    let boundary_slot = epoch.saturating_sub(1) * context.slots_per_epoch;
+    if boundary_slot == 0 {
+        return Root::default()
+    }
+    let slot = boundary_slot - 1;
+    state.block_roots()[(slot % SLOTS_PER_HISTORICAL_ROOT as u64) as usize]
+}
+
+fn active_validator_indices<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    epoch: Epoch,
+) -> Vec<ValidatorIndex> {
+    state
+        .validators()
+        .iter()
+        .enumerate()
+        .filter_map(|(i, validator)| is_active_validator(validator, epoch).then_some(i))
+        .collect()
+}
+
+// Mirrors `get_seed` with `DomainType::BeaconAttester` for any fork of `state`
+fn compute_attester_seed<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    epoch: Epoch,
+    context: &Context,
+) -> Bytes32 {
+    let mix_epoch = epoch + (context.epochs_per_historical_vector - context.min_seed_lookahead) - 1;
+    let mix = &state.randao_mixes()[(mix_epoch % context.epochs_per_historical_vector) as usize];
+    let mut input = [0u8; 44];
+    input[..4].copy_from_slice(&DomainType::BeaconAttester.as_bytes());
+    input[4..12].copy_from_slice(&epoch.to_le_bytes());
+    input[12..].copy_from_slice(mix.as_ref());
+    hash(input)
+}
+
+fn write_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.offset
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if len > self.remaining() {
+            return Err(ShufflingCacheError::Malformed.into())
+        }
+        let bytes = &self.bytes[self.offset..self.offset + len];
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn read_byte(&mut self) -> Result<u8> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_varint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_byte()?;
+            let bits = (byte & 0x7f) as u64;
+            if shift == 63 && bits > 1 {
+                return Err(ShufflingCacheError::Malformed.into())
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value)
+            }
+        }
+        Err(ShufflingCacheError::Malformed.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        phase0::{get_beacon_committee, get_committee_count_per_slot, minimal, Validator},
+        primitives::FAR_FUTURE_EPOCH,
+        state_transition::minimal::Executor,
+    };
+
+    fn build_state(context: &Context) -> minimal::BeaconState {
+        let mut state =
+            minimal::BeaconState { slot: 3 * context.slots_per_epoch + 5, ..Default::default() };
+        for i in 0..200u64 {
+            let mut validator = Validator {
+                effective_balance: context.max_effective_balance,
+                exit_epoch: FAR_FUTURE_EPOCH,
+                ..Default::default()
+            };
+            if i % 17 == 0 {
+                // some validators are not active in every epoch
+                validator.activation_epoch = 4;
+            }
+            state.validators.push(validator);
+            state.balances.push(context.max_effective_balance);
+        }
+        for (i, mix) in state.randao_mixes.iter_mut().enumerate() {
+            *mix = hash((i as u64).to_le_bytes());
+        }
+        for (i, root) in state.block_roots.iter_mut().enumerate() {
+            *root = Root::try_from(hash((i as u64 + 1).to_be_bytes()).as_ref()).unwrap();
+        }
+        state
+    }
+
+    #[test]
+    fn test_shuffling_cache_round_trip() {
+        let context = Context::for_minimal();
+        let state = build_state(&context);
+        let mut executor = Executor::new(BeaconState::Phase0(state.clone()), context.clone());
+
+        let decision_root =
+            |epoch| shuffling_decision_root(&BeaconState::Phase0(state.clone()), epoch, &context);
+        let mut cache = ShufflingCache::default();
+        for epoch in 2..=4 {
+            let shuffling = compute_shuffling(executor.state.inner(), epoch, &context);
+            cache.insert(decision_root(epoch), shuffling);
+        }
+        let bytes = cache.serialize();
+
+        let mut tampered = bytes.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 0x01;
+        assert!(executor.prewarm_shuffling_cache(&tampered).is_err());
+        // flip a bit in the decision root, then in the seed, of the first entry
+        for offset in [3, 35] {
+            let mut tampered = bytes.clone();
+            tampered[offset] ^= 0x01;
+            assert!(executor.prewarm_shuffling_cache(&tampered).is_err());
+        }
+        assert!(executor.shuffling_cache.is_empty());
+
+        executor.prewarm_shuffling_cache(&bytes).unwrap();
+        assert_eq!(executor.shuffling_cache, cache);
+        for epoch in 2..=4 {
+            let committees_per_slot = get_committee_count_per_slot(&state, epoch, &context);
+            let shuffling = executor.shuffling_cache.get(epoch, decision_root(epoch)).unwrap();
+            assert_eq!(shuffling.committee_count_per_slot(&context), committees_per_slot);
+            for slot in epoch * context.slots_per_epoch..(epoch + 1) * context.slots_per_epoch {
+                for index in 0..committees_per_slot {
+                    let expected = get_beacon_committee(&state, slot, index, &context).unwrap();
+                    assert_eq!(
+                        shuffling.beacon_committee(slot, index, &context).unwrap(),
+                        expected
+                    );
+                    assert_eq!(executor.beacon_committee(slot, index).unwrap(), expected);
+                }
+            }
+        }
+
+        // stale entries are rejected
+        let mut stale = ShufflingCache::default();
+        stale.insert(decision_root(1), compute_shuffling(executor.state.inner(), 1, &context));
+        assert!(executor.prewarm_shuffling_cache(&stale.serialize()).is_err());
+    }
+
+    #[test]
+    fn test_shufflings_of_competing_forks() {
+        let context = Context::for_minimal();
+        let state = build_state(&context);
+        // a fork whose blocks differ from the first slot of epoch 2 on, and which activated
+        // fewer validators for epoch 4 as a result
+        let mut fork = state.clone();
+        let (start, end) = (2 * context.slots_per_epoch, fork.slot);
+        for root in fork.block_roots.iter_mut().skip(start as usize).take((end - start) as usize) {
+            *root = Root::default();
+        }
+        for validator in fork.validators.iter_mut().step_by(34) {
+            validator.activation_epoch = FAR_FUTURE_EPOCH;
+        }
+        let (state, fork) = (BeaconState::Phase0(state), BeaconState::Phase0(fork));
+
+        let mut cache = ShufflingCache::default();
+        for state in [&state, &fork] {
+            for epoch in 2..=4 {
+                let decision_root = shuffling_decision_root(state, epoch, &context);
+                cache.insert(decision_root, compute_shuffling(state, epoch, &context));
+            }
+        }
+        // both forks agree on the blocks that decided epochs 2 and 3
+        assert_eq!(cache.len(), 4);
+        for state in [&state, &fork] {
+            let decision_root = shuffling_decision_root(state, 4, &context);
+            let shuffling = cache.get(4, decision_root).unwrap();
+            assert_eq!(shuffling, &compute_shuffling(state, 4, &context));
+        }
+        assert_ne!(
+            shuffling_decision_root(&state, 4, &context),
+            shuffling_decision_root(&fork, 4, &context)
+        );
+        assert_eq!(shuffling_decision_root(&state, 1, &context), Root::default());
+
+        // a cache of both forks only verifies against neither
+        assert!(cache.verify(&state, &context).is_err());
+        cache.prune(4);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_bounded_cache_and_memory_estimate() {
+        let context = Context::for_minimal();
+        let state = BeaconState::Phase0(build_state(&context));
+        let decision_root = |epoch| shuffling_decision_root(&state, epoch, &context);
+        let mut cache = ShufflingCache::new(Some(2));
+        for epoch in 2..=4 {
+            cache.insert(decision_root(epoch), compute_shuffling(&state, epoch, &context));
+        }
+        // the earliest epoch is evicted
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(2, decision_root(2)).is_none());
+        assert!(cache.get(3, decision_root(3)).is_some());
+        assert!(cache.get(4, decision_root(4)).is_some());
+
+        // the estimate is within a factor of two of the allocations of the entries
+        let allocated = cache
+            .shufflings
+            .values()
+            .map(|shuffling| {
+                let indices = shuffling.active_validator_indices.capacity() +
+                    shuffling.shuffled_indices.capacity();
+                size_of::<((Epoch, Root), Shuffling)>() +
+                    shuffling.seed.len() +
+                    indices * size_of::<ValidatorIndex>()
+            })
+            .sum::<usize>();
+        let estimate = cache.memory_estimate();
+        assert!(estimate <= allocated && 2 * estimate >= allocated);
+        // 200 validators active in each epoch, less the 12 activating at epoch 4
+        assert!(estimate >= (188 + 200) * 2 * size_of::<ValidatorIndex>());
+
+        let mut disabled = ShufflingCache::new(Some(0));
+        disabled.insert(decision_root(3), compute_shuffling(&state, 3, &context));
+        assert!(disabled.is_empty());
+        assert_eq!(disabled.memory_estimate(), 0);
+    }
+}
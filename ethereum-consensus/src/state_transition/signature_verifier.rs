@@ -0,0 +1,517 @@
+//! Batch verification of the BLS signatures carried by a block.
+//!
+//! [`collect_block_signatures`] gathers every signature the state transition would verify for a
+//! block (except those of deposits, see below) into a [`SignatureVerifier`], which checks them
+//! all in a single multi-pairing. Once the batch passes, the block can be applied with
+//! [`Validation::SkipSignatureVerification`](crate::state_transition::Validation).
+//!
+//! Deposit signatures are left out: an invalid deposit signature skips the deposit rather than
+//! invalidating the block, so they are verified inline as the deposits are processed.
+use crate::{
+    crypto::{
+        aggregate_decompressed_public_keys, verify_multiple_signatures_decompressed,
+        DecompressedPublicKey,
+    },
+    map_fork,
+    phase0::{self, compute_domain, compute_epoch_at_slot, AttestationData, Validator},
+    primitives::{
+        BlsPublicKey, BlsSignature, CommitteeIndex, Domain, DomainType, Epoch, Root, Slot,
+        ValidatorIndex,
+    },
+    signing::compute_signing_root,
+    state_transition::{Context, DecompressedPubkeyCache, Result},
+    types::{BeaconState, SignedBeaconBlock},
+    Error, Fork,
+};
+use std::iter::zip;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum BatchVerificationError {
+    #[error("validator {0} is not in the state")]
+    UnknownValidator(ValidatorIndex),
+    #[error(
+        "state at slot {state_slot} can not verify the signatures of a block at slot {block_slot}"
+    )]
+    StateNotAtBlockSlot { state_slot: Slot, block_slot: Slot },
+    #[error("aggregation bits of length {found} do not match a committee of size {expected}")]
+    AggregationBitsMismatch { expected: usize, found: usize },
+    #[error("a signature set has no signers")]
+    NoSigners,
+    #[error("batch contains an invalid signature")]
+    InvalidSignature,
+}
+
+/// The keys a signature is made with, aggregated if there are several.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Signers {
+    /// Validators of the state, whose keys can be served by a `DecompressedPubkeyCache`.
+    Validators(Vec<ValidatorIndex>),
+    /// Keys that are not (yet) validator keys, e.g. withdrawal keys or sync committee members.
+    PublicKeys(Vec<BlsPublicKey>),
+}
+
+/// A signature over `signing_root` to verify against `signers`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureSet {
+    pub signers: Signers,
+    pub signing_root: Root,
+    pub signature: BlsSignature,
+}
+
+/// A batch of [`SignatureSet`]s verified together.
+///
+/// A failing batch does not identify the offending signature; callers needing that should verify
+/// the sets one at a time, e.g. by applying the block with full validation.
+#[derive(Debug, Clone, Default)]
+pub struct SignatureVerifier {
+    sets: Vec<SignatureSet>,
+}
+
+impl SignatureVerifier {
+    pub fn push(&mut self, signers: Signers, signing_root: Root, signature: &BlsSignature) {
+        self.sets.push(SignatureSet { signers, signing_root, signature: signature.clone() });
+    }
+
+    pub fn sets(&self) -> &[SignatureSet] {
+        &self.sets
+    }
+
+    pub fn len(&self) -> usize {
+        self.sets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+
+    /// Verify every set in one multi-pairing, resolving the keys of `Signers::Validators` from
+    /// `validators` through `pubkey_cache`. An empty batch is trivially valid.
+    pub fn verify(
+        &self,
+        validators: &[Validator],
+        pubkey_cache: &mut DecompressedPubkeyCache,
+    ) -> Result<()> {
+        if self.sets.is_empty() {
+            return Ok(())
+        }
+        let mut public_keys = Vec::with_capacity(self.sets.len());
+        for set in &self.sets {
+            let keys = match &set.signers {
+                Signers::Validators(indices) => indices
+                    .iter()
+                    .map(|&index| {
+                        let validator = validators
+                            .get(index)
+                            .ok_or(BatchVerificationError::UnknownValidator(index))?;
+                        pubkey_cache
+                            .get_or_decompress(index, &validator.public_key)
+                            .map(|public_key| public_key.into_owned())
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+                Signers::PublicKeys(public_keys) => public_keys
+                    .iter()
+                    .map(|public_key| {
+                        DecompressedPublicKey::try_from(public_key).map_err(Into::into)
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+            };
+            let public_key = match keys.len() {
+                0 => return Err(BatchVerificationError::NoSigners.into()),
+                1 => keys.into_iter().next().expect("has one key"),
+                _ => aggregate_decompressed_public_keys(&keys.iter().collect::<Vec<_>>())?,
+            };
+            public_keys.push(public_key);
+        }
+
+        let public_keys = public_keys.iter().collect::<Vec<_>>();
+        let msgs = self.sets.iter().map(|set| set.signing_root.as_ref()).collect::<Vec<_>>();
+        let signatures = self.sets.iter().map(|set| &set.signature).collect::<Vec<_>>();
+        verify_multiple_signatures_decompressed(&public_keys, &msgs, &signatures)
+            .map_err(|_| BatchVerificationError::InvalidSignature.into())
+    }
+}
+
+// `get_domain` over the fork data of a state, so it applies to any fork of the state.
+fn get_domain(
+    fork: &phase0::Fork,
+    genesis_validators_root: Root,
+    domain_type: DomainType,
+    epoch: Epoch,
+    context: &Context,
+) -> Result<Domain> {
+    let fork_version =
+        if epoch < fork.epoch { fork.previous_version } else { fork.current_version };
+    compute_domain(domain_type, Some(fork_version), Some(genesis_validators_root), context)
+}
+
+/// Gather the signatures `signed_block` carries, as they would be verified when applying it to
+/// `state`, which must already be advanced to the slot of the block.
+///
+/// `committee` returns the beacon committee at a slot with an index, to resolve the attesters of
+/// each attestation. The operations are not otherwise validated, so a block with invalid
+/// operations may still pass batch verification and fail later in the state transition.
+pub fn collect_block_signatures<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    signed_block: &SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >,
+    mut committee: impl FnMut(Slot, CommitteeIndex) -> Result<Vec<ValidatorIndex>>,
+    context: &Context,
+) -> Result<SignatureVerifier> {
+    let message = signed_block.message();
+    let block_slot = message.slot();
+    if state.slot() != block_slot {
+        return Err(BatchVerificationError::StateNotAtBlockSlot {
+            state_slot: state.slot(),
+            block_slot,
+        }
+        .into())
+    }
+    let fork = state.fork();
+    let genesis_validators_root = state.genesis_validators_root();
+    let domain =
+        |domain_type, epoch| get_domain(fork, genesis_validators_root, domain_type, epoch, context);
+    let epoch = compute_epoch_at_slot(block_slot, context);
+    let mut verifier = SignatureVerifier::default();
+
+    let proposer_index = message.proposer_index();
+    let signing_root = map_fork!(
+        SignedBeaconBlock,
+        signed_block,
+        inner => compute_signing_root(&inner.message, domain(DomainType::BeaconProposer, epoch)?)
+    )?;
+    verifier.push(
+        Signers::Validators(vec![proposer_index]),
+        signing_root,
+        signed_block.signature(),
+    );
+
+    let body = message.body();
+    let signing_root = compute_signing_root(&epoch, domain(DomainType::Randao, epoch)?)?;
+    verifier.push(Signers::Validators(vec![proposer_index]), signing_root, body.randao_reveal());
+
+    for proposer_slashing in body.proposer_slashings().iter() {
+        for signed_header in
+            [&proposer_slashing.signed_header_1, &proposer_slashing.signed_header_2]
+        {
+            let header = &signed_header.message;
+            let domain =
+                domain(DomainType::BeaconProposer, compute_epoch_at_slot(header.slot, context))?;
+            verifier.push(
+                Signers::Validators(vec![header.proposer_index]),
+                compute_signing_root(header, domain)?,
+                &signed_header.signature,
+            );
+        }
+    }
+
+    let push_indexed_attestation = |verifier: &mut SignatureVerifier,
+                                    attesting_indices: &[ValidatorIndex],
+                                    data: &AttestationData,
+                                    signature: &BlsSignature| {
+        let domain = domain(DomainType::BeaconAttester, data.target.epoch)?;
+        verifier.push(
+            Signers::Validators(attesting_indices.to_vec()),
+            compute_signing_root(data, domain)?,
+            signature,
+        );
+        Ok::<_, Error>(())
+    };
+    let attester_slashings = body.attester_slashings().into_iter().flatten();
+    for attester_slashing in attester_slashings {
+        for attestation in [&attester_slashing.attestation_1, &attester_slashing.attestation_2] {
+            push_indexed_attestation(
+                &mut verifier,
+                &attestation.attesting_indices,
+                &attestation.data,
+                &attestation.signature,
+            )?;
+        }
+    }
+    let electra_attester_slashings =
+        body.electra().into_iter().flat_map(|body| body.attester_slashings.iter());
+    for attester_slashing in electra_attester_slashings {
+        for attestation in [&attester_slashing.attestation_1, &attester_slashing.attestation_2] {
+            push_indexed_attestation(
+                &mut verifier,
+                &attestation.attesting_indices,
+                &attestation.data,
+                &attestation.signature,
+            )?;
+        }
+    }
+
+    for attestation in body.attestations().into_iter().flatten() {
+        let data = &attestation.data;
+        let committee = committee(data.slot, data.index)?;
+        let bits = &attestation.aggregation_bits;
+        if bits.len() != committee.len() {
+            return Err(BatchVerificationError::AggregationBitsMismatch {
+                expected: committee.len(),
+                found: bits.len(),
+            }
+            .into())
+        }
+        let attesting_indices = committee
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, index)| if bits[i] { Some(index) } else { None })
+            .collect::<Vec<_>>();
+        let domain = domain(DomainType::BeaconAttester, data.target.epoch)?;
+        verifier.push(
+            Signers::Validators(attesting_indices),
+            compute_signing_root(data, domain)?,
+            &attestation.signature,
+        );
+    }
+    // from electra, an attestation aggregates over every committee set in its `committee_bits`
+    // and its aggregation bits run over those committees back to back (EIP-7549)
+    for attestation in body.electra().into_iter().flat_map(|body| body.attestations.iter()) {
+        let data = &attestation.data;
+        let mut attesting_indices = vec![];
+        let mut offset = 0;
+        for (index, _) in attestation.committee_bits.iter().enumerate().filter(|(_, bit)| **bit) {
+            let committee = committee(data.slot, index as CommitteeIndex)?;
+            for (i, validator_index) in committee.iter().enumerate() {
+                if attestation.aggregation_bits.get(offset + i).is_some_and(|bit| *bit) {
+                    attesting_indices.push(*validator_index);
+                }
+            }
+            offset += committee.len();
+        }
+        if attestation.aggregation_bits.len() != offset {
+            return Err(BatchVerificationError::AggregationBitsMismatch {
+                expected: offset,
+                found: attestation.aggregation_bits.len(),
+            }
+            .into())
+        }
+        push_indexed_attestation(&mut verifier, &attesting_indices, data, &attestation.signature)?;
+    }
+
+    for signed_voluntary_exit in body.voluntary_exits().iter() {
+        let voluntary_exit = &signed_voluntary_exit.message;
+        // from deneb, exits are signed for the capella fork so they never expire (EIP-7044)
+        let domain = if state.version() >= Fork::Deneb {
+            compute_domain(
+                DomainType::VoluntaryExit,
+                Some(context.capella_fork_version),
+                Some(genesis_validators_root),
+                context,
+            )?
+        } else {
+            domain(DomainType::VoluntaryExit, voluntary_exit.epoch)?
+        };
+        verifier.push(
+            Signers::Validators(vec![voluntary_exit.validator_index]),
+            compute_signing_root(voluntary_exit, domain)?,
+            &signed_voluntary_exit.signature,
+        );
+    }
+
+    if let (Some(sync_aggregate), Some(sync_committee)) =
+        (body.sync_aggregate(), state.current_sync_committee())
+    {
+        let participants =
+            zip(sync_committee.public_keys.iter(), sync_aggregate.sync_committee_bits.iter())
+                .filter_map(|(public_key, bit)| if *bit { Some(public_key.clone()) } else { None })
+                .collect::<Vec<_>>();
+        let signature = &sync_aggregate.sync_committee_signature;
+        // no participants is only valid with the signature at infinity, which needs no pairing
+        if participants.is_empty() {
+            if !signature.is_infinity() {
+                return Err(BatchVerificationError::InvalidSignature.into())
+            }
+        } else {
+            let previous_slot = state.slot().checked_sub(1).ok_or(Error::Underflow)?;
+            let root =
+                state.block_roots()[(previous_slot % SLOTS_PER_HISTORICAL_ROOT as u64) as usize];
+            let domain =
+                domain(DomainType::SyncCommittee, compute_epoch_at_slot(previous_slot, context))?;
+            verifier.push(
+                Signers::PublicKeys(participants),
+                compute_signing_root(&root, domain)?,
+                signature,
+            );
+        }
+    }
+
+    if let Some(bls_to_execution_changes) = body.bls_to_execution_changes() {
+        // signed with the genesis fork version so they are valid in any fork
+        let domain = compute_domain(
+            DomainType::BlsToExecutionChange,
+            None,
+            Some(genesis_validators_root),
+            context,
+        )?;
+        for signed_change in bls_to_execution_changes.iter() {
+            let change = &signed_change.message;
+            verifier.push(
+                Signers::PublicKeys(vec![change.from_bls_public_key.clone()]),
+                compute_signing_root(change, domain)?,
+                &signed_change.signature,
+            );
+        }
+    }
+
+    Ok(verifier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::{
+            determinism,
+            phase0::{genesis_state, signed_block},
+            secret_keys,
+        },
+        signing::sign_with_domain,
+        state_transition::minimal::Executor,
+        storage::{decode_block, decode_state},
+        types::minimal,
+    };
+
+    fn executor() -> Executor {
+        Executor::new(BeaconState::Phase0(genesis_state()), Context::for_minimal())
+    }
+
+    #[test]
+    fn test_batch_matches_individual_verification() {
+        let block = SignedBeaconBlock::Phase0(signed_block());
+        let mut expected = executor();
+        expected.apply_block(&block).unwrap();
+
+        let mut executor = executor();
+        executor.process_slots(block.message().slot()).unwrap();
+        let state = executor.state.phase0().unwrap();
+        let committee =
+            |slot, index| phase0::get_beacon_committee(state, slot, index, &executor.context);
+        let verifier =
+            collect_block_signatures(executor.state.inner(), &block, committee, &executor.context)
+                .unwrap();
+        // proposer, randao, two slashed headers, two slashing attestations and an attestation
+        assert_eq!(verifier.len(), 7);
+
+        executor.apply_block_with_batch_verification(&block).unwrap();
+        assert_eq!(
+            executor.state.hash_tree_root().unwrap(),
+            expected.state.hash_tree_root().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_signature_is_identified() {
+        let mut block = signed_block();
+        // a valid signature, but not over the attestation
+        let randao_reveal = block.message.body.randao_reveal.clone();
+        block.message.body.attestations[0].signature = randao_reveal;
+        let domain = phase0::get_domain(
+            &genesis_state(),
+            DomainType::BeaconProposer,
+            None,
+            &Context::for_minimal(),
+        )
+        .unwrap();
+        let proposer_key = &secret_keys()[block.message.proposer_index];
+        block.signature = sign_with_domain(&block.message, proposer_key, domain).unwrap();
+        let block = SignedBeaconBlock::Phase0(block);
+
+        let mut executor = executor();
+        executor.process_slots(block.message().slot()).unwrap();
+        assert!(matches!(
+            executor.verify_block_signatures(&block),
+            Err(Error::BatchVerification(BatchVerificationError::InvalidSignature))
+        ));
+
+        let expected = self::executor().apply_block(&block).unwrap_err();
+        let err = executor.apply_block_with_batch_verification(&block).unwrap_err();
+        assert!(matches!(err, Error::InvalidBlock(_)));
+        assert_eq!(err.to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn test_batch_verification_across_forks() {
+        let context = determinism::context();
+        for (fork, bundle) in determinism::starter_bundles() {
+            let state = decode_state(&bundle.pre_state).unwrap();
+            let mut executor = Executor::new(state, context.clone());
+            for encoding in &bundle.blocks {
+                let block: minimal::SignedBeaconBlock = decode_block(encoding).unwrap();
+                executor.process_slots(block.message().slot()).unwrap();
+                executor.verify_block_signatures(&block).unwrap();
+                executor.apply_block_with_batch_verification(&block).unwrap();
+            }
+            let root = executor.state.hash_tree_root().unwrap();
+            assert_eq!(root, bundle.expected_post_root, "{fork}");
+        }
+    }
+}
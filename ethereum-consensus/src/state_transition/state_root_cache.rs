@@ -0,0 +1,800 @@
+use crate::{
+    altair, bellatrix, capella, deneb, electra,
+    phase0::{self, merkleize_field_roots, Validator},
+    primitives::{Gwei, ParticipationFlags, Root},
+    ssz::prelude::*,
+    state_transition::Result,
+    types::BeaconState,
+};
+use sha2::{Digest, Sha256};
+use std::{mem::size_of, sync::OnceLock};
+
+type Chunk = [u8; 32];
+
+fn hash_pair(left: &Chunk, right: &Chunk) -> Chunk {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+// The root of a tree of `depth` levels over zeroed chunks.
+fn zero_hash(depth: usize) -> Chunk {
+    static ZERO_HASHES: OnceLock<Vec<Chunk>> = OnceLock::new();
+    ZERO_HASHES.get_or_init(|| {
+        let mut zero_hashes = vec![Chunk::default()];
+        for depth in 0..64 {
+            let zero_hash = zero_hashes[depth];
+            zero_hashes.push(hash_pair(&zero_hash, &zero_hash));
+        }
+        zero_hashes
+    })[depth]
+}
+
+// The depth of the smallest tree with room for `chunk_count` chunks.
+fn depth_for(chunk_count: usize) -> usize {
+    chunk_count.next_power_of_two().trailing_zeros() as usize
+}
+
+fn mix_in_length(root: Chunk, length: usize) -> Root {
+    let mut length_chunk = Chunk::default();
+    length_chunk[..8].copy_from_slice(&(length as u64).to_le_bytes());
+    to_root(hash_pair(&root, &length_chunk))
+}
+
+fn to_root(chunk: Chunk) -> Root {
+    Root::try_from(chunk.as_ref()).expect("correct length")
+}
+
+fn to_chunk<T: AsRef<[u8]>>(element: &T) -> Chunk {
+    element.as_ref().try_into().expect("elements are 32 bytes")
+}
+
+// A basic type packed into the chunks of a list or vector.
+trait Packed: Copy {
+    const SIZE: usize;
+
+    fn write_le(&self, out: &mut [u8]);
+}
+
+impl Packed for u64 {
+    const SIZE: usize = 8;
+
+    fn write_le(&self, out: &mut [u8]) {
+        out.copy_from_slice(&self.to_le_bytes())
+    }
+}
+
+impl Packed for u8 {
+    const SIZE: usize = 1;
+
+    fn write_le(&self, out: &mut [u8]) {
+        out[0] = *self
+    }
+}
+
+fn packed_chunk<T: Packed>(values: &[T], index: usize) -> Chunk {
+    let per_chunk = 32 / T::SIZE;
+    let mut chunk = Chunk::default();
+    let values = &values[index * per_chunk..values.len().min((index + 1) * per_chunk)];
+    for (value, out) in values.iter().zip(chunk.chunks_exact_mut(T::SIZE)) {
+        value.write_le(out);
+    }
+    chunk
+}
+
+// The merkle tree over the chunks of a list or vector, keeping every node so that only the paths
+// from changed chunks to the root are hashed again. Nodes past the end of a layer are the roots of
+// empty subtrees and are not stored.
+#[derive(Debug, Clone, Default)]
+struct ChunkTree {
+    // `layers[0]` holds the chunks and each following layer the parents of the one before it, up
+    // to the root
+    layers: Vec<Vec<Chunk>>,
+}
+
+impl ChunkTree {
+    fn len(&self) -> usize {
+        self.layers.first().map_or(0, |chunks| chunks.len())
+    }
+
+    fn memory_estimate(&self) -> usize {
+        self.layers.iter().map(|layer| layer.len() * size_of::<Chunk>()).sum()
+    }
+
+    // Update the tree to `chunk_count` chunks below a root at `depth`, where `chunk(i)` is the
+    // `i`th chunk or `None` if it is known to be unchanged, and return the root.
+    fn update(
+        &mut self,
+        depth: usize,
+        chunk_count: usize,
+        mut chunk: impl FnMut(usize) -> Result<Option<Chunk>>,
+    ) -> Result<Chunk> {
+        if self.layers.len() != depth + 1 || chunk_count < self.len() {
+            self.layers = vec![Vec::new(); depth + 1];
+        }
+        let chunks = &mut self.layers[0];
+        let mut dirty = Vec::new();
+        for i in 0..chunk_count {
+            match chunk(i)? {
+                Some(chunk) if i == chunks.len() => chunks.push(chunk),
+                Some(chunk) if chunks[i] != chunk => chunks[i] = chunk,
+                _ => continue,
+            }
+            dirty.push(i);
+        }
+        debug_assert_eq!(chunks.len(), chunk_count, "chunks past the end are never unchanged");
+        for level in 0..depth {
+            let (lower, upper) = self.layers.split_at_mut(level + 1);
+            let (children, parents) = (&lower[level], &mut upper[0]);
+            let zero_hash = zero_hash(level);
+            parents.resize(children.len().div_ceil(2), Chunk::default());
+            for i in dirty.iter_mut() {
+                *i /= 2;
+            }
+            dirty.dedup();
+            for &i in &dirty {
+                let right = children.get(2 * i + 1).unwrap_or(&zero_hash);
+                parents[i] = hash_pair(&children[2 * i], right);
+            }
+        }
+        Ok(self.layers[depth].first().copied().unwrap_or_else(|| zero_hash(depth)))
+    }
+
+    fn vector_root<T: AsRef<[u8]>>(&mut self, elements: &[T]) -> Result<Root> {
+        let root = self.update(depth_for(elements.len()), elements.len(), |i| {
+            Ok(Some(to_chunk(&elements[i])))
+        })?;
+        Ok(to_root(root))
+    }
+
+    fn list_root<T: AsRef<[u8]>>(&mut self, elements: &[T], limit: usize) -> Result<Root> {
+        let root =
+            self.update(depth_for(limit), elements.len(), |i| Ok(Some(to_chunk(&elements[i]))))?;
+        Ok(mix_in_length(root, elements.len()))
+    }
+
+    fn packed_vector_root<T: Packed>(&mut self, values: &[T]) -> Result<Root> {
+        let chunk_count = (values.len() * T::SIZE).div_ceil(32);
+        let root = self
+            .update(depth_for(chunk_count), chunk_count, |i| Ok(Some(packed_chunk(values, i))))?;
+        Ok(to_root(root))
+    }
+
+    fn packed_list_root<T: Packed>(&mut self, values: &[T], limit: usize) -> Result<Root> {
+        let depth = depth_for((limit * T::SIZE).div_ceil(32));
+        let chunk_count = (values.len() * T::SIZE).div_ceil(32);
+        let root = self.update(depth, chunk_count, |i| Ok(Some(packed_chunk(values, i))))?;
+        Ok(mix_in_length(root, values.len()))
+    }
+}
+
+// The tree over the roots of the validators, along with a copy of the validators the roots were
+// computed from so that only the roots of changed validators are computed again.
+#[derive(Debug, Clone, Default)]
+struct ValidatorRoots {
+    validators: Vec<Validator>,
+    tree: ChunkTree,
+}
+
+impl ValidatorRoots {
+    fn memory_estimate(&self) -> usize {
+        self.validators.len() * size_of::<Validator>() + self.tree.memory_estimate()
+    }
+
+    fn list_root(&mut self, validators: &[Validator], limit: usize) -> Result<Root> {
+        let depth = depth_for(limit);
+        if self.tree.layers.len() != depth + 1 || validators.len() < self.validators.len() {
+            *self = Self::default();
+        }
+        let snapshot = &mut self.validators;
+        let root = self.tree.update(depth, validators.len(), |i| {
+            let validator = &validators[i];
+            if snapshot.get(i) == Some(validator) {
+                return Ok(None)
+            }
+            let root = validator.hash_tree_root()?;
+            if i < snapshot.len() {
+                snapshot[i] = validator.clone();
+            } else {
+                snapshot.push(validator.clone());
+            }
+            Ok(Some(to_chunk(&root)))
+        })?;
+        Ok(mix_in_length(root, validators.len()))
+    }
+}
+
+/// A `BeaconState` whose root a `StateRootCache` can compute.
+pub trait CachedFieldRoots {
+    /// The roots of the fields of the state in order, computed from scratch.
+    fn compute_field_roots(&self) -> Result<Vec<Root>>;
+
+    /// The roots of the fields of the state in order, reusing the parts of the largest fields
+    /// that are unchanged since `cache` last saw them.
+    fn cached_field_roots(&self, cache: &mut StateRootCache) -> Result<Vec<Root>>;
+}
+
+/// The merkle trees of the largest fields of a `BeaconState` kept between computations of its
+/// root, e.g. across the slots and blocks an `Executor` processes, so that only the parts of the
+/// state that changed in between are hashed again.
+///
+/// `ssz_rs` does not track changes to a value, so the cache finds them by comparing each field
+/// against what it saw last time; this is far cheaper than hashing, but means the cache holds a
+/// copy of the validators. The cache is never stale: it can be used with any state, although it
+/// only saves work for states close to the one it saw last.
+#[derive(Debug, Clone)]
+pub struct StateRootCache {
+    enabled: bool,
+    block_roots: ChunkTree,
+    state_roots: ChunkTree,
+    historical_roots: ChunkTree,
+    validators: ValidatorRoots,
+    balances: ChunkTree,
+    randao_mixes: ChunkTree,
+    slashings: ChunkTree,
+    previous_epoch_participation: ChunkTree,
+    current_epoch_participation: ChunkTree,
+    inactivity_scores: ChunkTree,
+}
+
+impl Default for StateRootCache {
+    fn default() -> Self {
+        Self::new(true)
+    }
+}
+
+impl StateRootCache {
+    /// A cache that holds nothing if not `enabled`, computing every root from scratch.
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            block_roots: Default::default(),
+            state_roots: Default::default(),
+            historical_roots: Default::default(),
+            validators: Default::default(),
+            balances: Default::default(),
+            randao_mixes: Default::default(),
+            slashings: Default::default(),
+            previous_epoch_participation: Default::default(),
+            current_epoch_participation: Default::default(),
+            inactivity_scores: Default::default(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Approximate bytes held by the cache.
+    pub fn memory_estimate(&self) -> usize {
+        self.validators.memory_estimate() +
+            [
+                &self.block_roots,
+                &self.state_roots,
+                &self.historical_roots,
+                &self.balances,
+                &self.randao_mixes,
+                &self.slashings,
+                &self.previous_epoch_participation,
+                &self.current_epoch_participation,
+                &self.inactivity_scores,
+            ]
+            .iter()
+            .map(|tree| tree.memory_estimate())
+            .sum::<usize>()
+    }
+
+    /// Drop everything held by the cache.
+    pub fn clear(&mut self) {
+        *self = Self::new(self.enabled);
+    }
+
+    /// The roots of the fields of `state`, as `compute_state_field_roots` of its fork computes
+    /// them.
+    pub fn field_roots<S: CachedFieldRoots>(&mut self, state: &S) -> Result<Vec<Root>> {
+        if self.enabled {
+            state.cached_field_roots(self)
+        } else {
+            state.compute_field_roots()
+        }
+    }
+
+    /// The root of `state`, equal to its `hash_tree_root`.
+    pub fn state_root<S: CachedFieldRoots>(&mut self, state: &S) -> Result<Root> {
+        merkleize_field_roots(&self.field_roots(state)?)
+    }
+
+    /// The root of `state` from whichever fork, equal to its `hash_tree_root`.
+    pub fn hash_tree_root<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    >(
+        &mut self,
+        state: &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    ) -> Result<Root> {
+        match state {
+            BeaconState::Phase0(state) => self.state_root(state),
+            BeaconState::Altair(state) => self.state_root(state),
+            BeaconState::Bellatrix(state) => self.state_root(state),
+            BeaconState::Capella(state) => self.state_root(state),
+            BeaconState::Deneb(state) => self.state_root(state),
+            BeaconState::Electra(state) => self.state_root(state),
+        }
+    }
+}
+
+// The roots of the fields from `genesis_time` to `slashings` of `$state`, which every fork shares.
+macro_rules! shared_field_roots {
+    ($cache:ident, $state:ident, $historical_roots_limit:expr, $validator_registry_limit:expr) => {
+        vec![
+            $state.genesis_time.hash_tree_root()?,
+            $state.genesis_validators_root.hash_tree_root()?,
+            $state.slot.hash_tree_root()?,
+            $state.fork.hash_tree_root()?,
+            $state.latest_block_header.hash_tree_root()?,
+            $cache.block_roots.vector_root(&$state.block_roots)?,
+            $cache.state_roots.vector_root(&$state.state_roots)?,
+            $cache.historical_roots.list_root(&$state.historical_roots, $historical_roots_limit)?,
+            $state.eth1_data.hash_tree_root()?,
+            $state.eth1_data_votes.hash_tree_root()?,
+            $state.eth1_deposit_index.hash_tree_root()?,
+            $cache.validators.list_root(&$state.validators, $validator_registry_limit)?,
+            $cache
+                .balances
+                .packed_list_root::<Gwei>(&$state.balances, $validator_registry_limit)?,
+            $cache.randao_mixes.vector_root(&$state.randao_mixes)?,
+            $cache.slashings.packed_vector_root::<Gwei>(&$state.slashings)?,
+        ]
+    };
+}
+
+// The roots of the fields from `previous_epoch_participation` to `next_sync_committee` of
+// `$state`, which every fork from altair shares.
+macro_rules! altair_field_roots {
+    ($cache:ident, $state:ident, $validator_registry_limit:expr) => {
+        [
+            $cache.previous_epoch_participation.packed_list_root::<ParticipationFlags>(
+                &$state.previous_epoch_participation,
+                $validator_registry_limit,
+            )?,
+            $cache.current_epoch_participation.packed_list_root::<ParticipationFlags>(
+                &$state.current_epoch_participation,
+                $validator_registry_limit,
+            )?,
+            $state.justification_bits.hash_tree_root()?,
+            $state.previous_justified_checkpoint.hash_tree_root()?,
+            $state.current_justified_checkpoint.hash_tree_root()?,
+            $state.finalized_checkpoint.hash_tree_root()?,
+            $cache
+                .inactivity_scores
+                .packed_list_root::<u64>(&$state.inactivity_scores, $validator_registry_limit)?,
+            $state.current_sync_committee.hash_tree_root()?,
+            $state.next_sync_committee.hash_tree_root()?,
+        ]
+    };
+}
+
+impl<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+    > CachedFieldRoots
+    for phase0::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >
+{
+    fn compute_field_roots(&self) -> Result<Vec<Root>> {
+        Ok(phase0::compute_state_field_roots(self)?)
+    }
+
+    fn cached_field_roots(&self, cache: &mut StateRootCache) -> Result<Vec<Root>> {
+        let mut roots =
+            shared_field_roots!(cache, self, HISTORICAL_ROOTS_LIMIT, VALIDATOR_REGISTRY_LIMIT);
+        roots.extend([
+            self.previous_epoch_attestations.hash_tree_root()?,
+            self.current_epoch_attestations.hash_tree_root()?,
+            self.justification_bits.hash_tree_root()?,
+            self.previous_justified_checkpoint.hash_tree_root()?,
+            self.current_justified_checkpoint.hash_tree_root()?,
+            self.finalized_checkpoint.hash_tree_root()?,
+        ]);
+        Ok(roots)
+    }
+}
+
+impl<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+    > CachedFieldRoots
+    for altair::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >
+{
+    fn compute_field_roots(&self) -> Result<Vec<Root>> {
+        Ok(altair::compute_state_field_roots(self)?)
+    }
+
+    fn cached_field_roots(&self, cache: &mut StateRootCache) -> Result<Vec<Root>> {
+        let mut roots =
+            shared_field_roots!(cache, self, HISTORICAL_ROOTS_LIMIT, VALIDATOR_REGISTRY_LIMIT);
+        roots.extend(altair_field_roots!(cache, self, VALIDATOR_REGISTRY_LIMIT));
+        Ok(roots)
+    }
+}
+
+impl<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+    > CachedFieldRoots
+    for bellatrix::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >
+{
+    fn compute_field_roots(&self) -> Result<Vec<Root>> {
+        Ok(bellatrix::compute_state_field_roots(self)?)
+    }
+
+    fn cached_field_roots(&self, cache: &mut StateRootCache) -> Result<Vec<Root>> {
+        let mut roots =
+            shared_field_roots!(cache, self, HISTORICAL_ROOTS_LIMIT, VALIDATOR_REGISTRY_LIMIT);
+        roots.extend(altair_field_roots!(cache, self, VALIDATOR_REGISTRY_LIMIT));
+        roots.push(self.latest_execution_payload_header.hash_tree_root()?);
+        Ok(roots)
+    }
+}
+
+impl<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+    > CachedFieldRoots
+    for capella::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >
+{
+    fn compute_field_roots(&self) -> Result<Vec<Root>> {
+        Ok(capella::compute_state_field_roots(self)?)
+    }
+
+    fn cached_field_roots(&self, cache: &mut StateRootCache) -> Result<Vec<Root>> {
+        let mut roots =
+            shared_field_roots!(cache, self, HISTORICAL_ROOTS_LIMIT, VALIDATOR_REGISTRY_LIMIT);
+        roots.extend(altair_field_roots!(cache, self, VALIDATOR_REGISTRY_LIMIT));
+        roots.extend([
+            self.latest_execution_payload_header.hash_tree_root()?,
+            self.next_withdrawal_index.hash_tree_root()?,
+            self.next_withdrawal_validator_index.hash_tree_root()?,
+            self.historical_summaries.hash_tree_root()?,
+        ]);
+        Ok(roots)
+    }
+}
+
+impl<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+    > CachedFieldRoots
+    for deneb::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >
+{
+    fn compute_field_roots(&self) -> Result<Vec<Root>> {
+        Ok(deneb::compute_state_field_roots(self)?)
+    }
+
+    fn cached_field_roots(&self, cache: &mut StateRootCache) -> Result<Vec<Root>> {
+        let mut roots =
+            shared_field_roots!(cache, self, HISTORICAL_ROOTS_LIMIT, VALIDATOR_REGISTRY_LIMIT);
+        roots.extend(altair_field_roots!(cache, self, VALIDATOR_REGISTRY_LIMIT));
+        roots.extend([
+            self.latest_execution_payload_header.hash_tree_root()?,
+            self.next_withdrawal_index.hash_tree_root()?,
+            self.next_withdrawal_validator_index.hash_tree_root()?,
+            self.historical_summaries.hash_tree_root()?,
+        ]);
+        Ok(roots)
+    }
+}
+
+impl<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    > CachedFieldRoots
+    for electra::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >
+{
+    fn compute_field_roots(&self) -> Result<Vec<Root>> {
+        Ok(electra::compute_state_field_roots(self)?)
+    }
+
+    fn cached_field_roots(&self, cache: &mut StateRootCache) -> Result<Vec<Root>> {
+        let mut roots =
+            shared_field_roots!(cache, self, HISTORICAL_ROOTS_LIMIT, VALIDATOR_REGISTRY_LIMIT);
+        roots.extend(altair_field_roots!(cache, self, VALIDATOR_REGISTRY_LIMIT));
+        roots.extend([
+            self.latest_execution_payload_header.hash_tree_root()?,
+            self.next_withdrawal_index.hash_tree_root()?,
+            self.next_withdrawal_validator_index.hash_tree_root()?,
+            self.historical_summaries.hash_tree_root()?,
+            self.deposit_requests_start_index.hash_tree_root()?,
+            self.deposit_balance_to_consume.hash_tree_root()?,
+            self.exit_balance_to_consume.hash_tree_root()?,
+            self.earliest_exit_epoch.hash_tree_root()?,
+            self.consolidation_balance_to_consume.hash_tree_root()?,
+            self.earliest_consolidation_epoch.hash_tree_root()?,
+            self.pending_deposits.hash_tree_root()?,
+            self.pending_partial_withdrawals.hash_tree_root()?,
+            self.pending_consolidations.hash_tree_root()?,
+        ]);
+        Ok(roots)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{Bytes32, FAR_FUTURE_EPOCH};
+
+    const VALIDATOR_COUNT: usize = 37;
+    const BALANCE: Gwei = 32_000_000_000;
+
+    fn validator(i: usize) -> Validator {
+        Validator {
+            activation_epoch: i as u64,
+            effective_balance: BALANCE,
+            exit_epoch: FAR_FUTURE_EPOCH,
+            withdrawable_epoch: FAR_FUTURE_EPOCH,
+            ..Default::default()
+        }
+    }
+
+    fn assert_cached_root<S: CachedFieldRoots + HashTreeRoot>(
+        cache: &mut StateRootCache,
+        state: &S,
+    ) {
+        assert_eq!(cache.state_root(state).unwrap(), state.hash_tree_root().unwrap());
+    }
+
+    #[test]
+    fn test_cached_root_matches_phase0() {
+        let mut state = phase0::minimal::BeaconState::default();
+        let mut cache = StateRootCache::default();
+        assert_cached_root(&mut cache, &state);
+        for i in 0..VALIDATOR_COUNT {
+            state.validators.push(validator(i));
+            state.balances.push(BALANCE);
+        }
+        assert_cached_root(&mut cache, &state);
+
+        state.validators[3].slashed = true;
+        state.validators[VALIDATOR_COUNT - 1].effective_balance -= 1;
+        assert_cached_root(&mut cache, &state);
+        state.balances[7] += 1;
+        state.balances[VALIDATOR_COUNT - 1] = 0;
+        assert_cached_root(&mut cache, &state);
+        state.randao_mixes[2] = Bytes32::try_from([0xaa; 32].as_ref()).unwrap();
+        assert_cached_root(&mut cache, &state);
+        state.slashings[1] = BALANCE;
+        state.state_roots[5] = Root::try_from([0xbb; 32].as_ref()).unwrap();
+        state.historical_roots.push(Root::try_from([0xcc; 32].as_ref()).unwrap());
+        assert_cached_root(&mut cache, &state);
+        // growing the registry past a power of two
+        for i in VALIDATOR_COUNT..2 * VALIDATOR_COUNT {
+            state.validators.push(validator(i));
+            state.balances.push(BALANCE);
+        }
+        assert_cached_root(&mut cache, &state);
+        assert!(cache.memory_estimate() > 0);
+
+        // a smaller state rebuilds the trees rather than reusing them
+        assert_cached_root(&mut cache, &phase0::minimal::BeaconState::default());
+        assert_cached_root(&mut cache, &state);
+    }
+
+    #[test]
+    fn test_cached_root_matches_deneb() {
+        let mut state = deneb::minimal::BeaconState::default();
+        let mut cache = StateRootCache::default();
+        for i in 0..VALIDATOR_COUNT {
+            state.validators.push(validator(i));
+            state.balances.push(BALANCE);
+            state.previous_epoch_participation.push(0);
+            state.current_epoch_participation.push(0);
+            state.inactivity_scores.push(0);
+        }
+        assert_cached_root(&mut cache, &state);
+
+        state.validators[0].exit_epoch = 9;
+        state.balances[33] -= 1;
+        state.randao_mixes[0] = Bytes32::try_from([0xaa; 32].as_ref()).unwrap();
+        assert_cached_root(&mut cache, &state);
+        state.previous_epoch_participation[35] = 0b111;
+        state.current_epoch_participation[1] = 0b1;
+        state.inactivity_scores[36] = 4;
+        assert_cached_root(&mut cache, &state);
+        state.block_roots[1] = Root::try_from([0xdd; 32].as_ref()).unwrap();
+        state.slot += 1;
+        assert_cached_root(&mut cache, &state);
+
+        let polymorphic = BeaconState::Deneb(state.clone());
+        let expected = polymorphic.hash_tree_root().unwrap();
+        assert_eq!(cache.hash_tree_root(&polymorphic).unwrap(), expected);
+        assert_eq!(
+            cache.field_roots(&state).unwrap(),
+            deneb::compute_state_field_roots(&state).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_cached_root_matches_electra() {
+        let mut state = electra::minimal::BeaconState::default();
+        let mut cache = StateRootCache::default();
+        for i in 0..VALIDATOR_COUNT {
+            state.validators.push(validator(i));
+            state.balances.push(BALANCE);
+            state.previous_epoch_participation.push(0);
+            state.current_epoch_participation.push(0);
+            state.inactivity_scores.push(0);
+        }
+        assert_cached_root(&mut cache, &state);
+
+        state.balances[2] += 1;
+        state.earliest_exit_epoch = 5;
+        state
+            .pending_deposits
+            .push(electra::PendingDeposit { amount: BALANCE, ..Default::default() });
+        assert_cached_root(&mut cache, &state);
+        state
+            .pending_consolidations
+            .push(electra::PendingConsolidation { source_index: 1, target_index: 2 });
+        state.deposit_requests_start_index = 7;
+        assert_cached_root(&mut cache, &state);
+
+        let polymorphic = BeaconState::Electra(state.clone());
+        let expected = polymorphic.hash_tree_root().unwrap();
+        assert_eq!(cache.hash_tree_root(&polymorphic).unwrap(), expected);
+        assert_eq!(
+            cache.field_roots(&state).unwrap(),
+            electra::compute_state_field_roots(&state).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_disabled_cache() {
+        let mut state = deneb::minimal::BeaconState::default();
+        for i in 0..VALIDATOR_COUNT {
+            state.validators.push(validator(i));
+            state.balances.push(BALANCE);
+        }
+        let mut cache = StateRootCache::new(false);
+        assert_cached_root(&mut cache, &state);
+        assert_eq!(cache.memory_estimate(), 0);
+    }
+}
@@ -0,0 +1,450 @@
+//! A self-describing encoding of blocks and states for databases: a one byte fork tag followed
+//! by the `ssz_snappy` encoding of the object used on the wire by req/resp, i.e. the length of
+//! the SSZ encoding as an unsigned LEB128 varint and then the SSZ encoding in the snappy frame
+//! format.
+//!
+//! Decoding only needs the tag, not a `Context`. Tags of forks this version does not support are
+//! reported with the encoding intact, so older software can pass newer data through (or leave it
+//! in place) rather than destroying it.
+use crate::{
+    altair, bellatrix, capella, deneb, electra, phase0,
+    ssz::prelude::*,
+    types::{BeaconState, SignedBeaconBlock},
+    Fork,
+};
+use std::io::{Read, Write};
+use thiserror::Error;
+
+// The longest LEB128 encoding of a `u64`.
+const MAX_VARINT_LEN: usize = 10;
+
+#[derive(Debug, Error)]
+pub enum StorageError {
+    #[error("encoding is empty")]
+    Empty,
+    #[error("fork tag {tag} is not supported")]
+    UnsupportedFork { tag: u8, bytes: Vec<u8> },
+    #[error("malformed length prefix")]
+    MalformedLength,
+    #[error("length prefix of {expected} bytes does not match the {found} bytes decompressed")]
+    LengthMismatch { expected: u64, found: usize },
+    #[error("{0}")]
+    Snappy(#[from] std::io::Error),
+    #[error("{0}")]
+    Serialize(#[from] SerializeError),
+    #[error("{0}")]
+    Deserialize(#[from] DeserializeError),
+}
+
+/// The tag of `fork` in encodings, stable across versions.
+pub fn fork_tag(fork: Fork) -> u8 {
+    match fork {
+        Fork::Phase0 => 0,
+        Fork::Altair => 1,
+        Fork::Bellatrix => 2,
+        Fork::Capella => 3,
+        Fork::Deneb => 4,
+        Fork::Electra => 5,
+    }
+}
+
+fn encode<T: Serialize>(fork: Fork, value: &T) -> Result<Vec<u8>, StorageError> {
+    let encoding = serialize(value)?;
+    let mut bytes = vec![fork_tag(fork)];
+    let mut length = encoding.len() as u64;
+    while length >= 0x80 {
+        bytes.push((length as u8) | 0x80);
+        length >>= 7;
+    }
+    bytes.push(length as u8);
+    let mut encoder = snap::write::FrameEncoder::new(&mut bytes);
+    encoder.write_all(&encoding)?;
+    encoder.flush()?;
+    drop(encoder);
+    Ok(bytes)
+}
+
+// Split `bytes` into the fork of its tag and the decompressed SSZ encoding.
+fn decode(bytes: &[u8]) -> Result<(Fork, Vec<u8>), StorageError> {
+    let (&tag, payload) = bytes.split_first().ok_or(StorageError::Empty)?;
+    let fork = match tag {
+        0 => Fork::Phase0,
+        1 => Fork::Altair,
+        2 => Fork::Bellatrix,
+        3 => Fork::Capella,
+        4 => Fork::Deneb,
+        5 => Fork::Electra,
+        _ => return Err(StorageError::UnsupportedFork { tag, bytes: bytes.to_vec() }),
+    };
+
+    let mut length = 0u64;
+    let mut prefix_len = 0;
+    loop {
+        let &byte = payload.get(prefix_len).ok_or(StorageError::MalformedLength)?;
+        if prefix_len == MAX_VARINT_LEN - 1 && byte > 1 {
+            return Err(StorageError::MalformedLength)
+        }
+        length |= ((byte & 0x7f) as u64) << (7 * prefix_len);
+        prefix_len += 1;
+        if byte & 0x80 == 0 {
+            break
+        }
+    }
+
+    // read at most one byte past the prefixed length, so a lying prefix cannot make the
+    // decompression allocate more than it claims
+    let mut encoding = vec![];
+    snap::read::FrameDecoder::new(&payload[prefix_len..])
+        .take(length.saturating_add(1))
+        .read_to_end(&mut encoding)?;
+    if encoding.len() as u64 != length {
+        return Err(StorageError::LengthMismatch { expected: length, found: encoding.len() })
+    }
+    Ok((fork, encoding))
+}
+
+pub fn encode_block<
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    block: &SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >,
+) -> Result<Vec<u8>, StorageError> {
+    match block {
+        SignedBeaconBlock::Phase0(inner) => encode(Fork::Phase0, inner),
+        SignedBeaconBlock::Altair(inner) => encode(Fork::Altair, inner),
+        SignedBeaconBlock::Bellatrix(inner) => encode(Fork::Bellatrix, inner),
+        SignedBeaconBlock::Capella(inner) => encode(Fork::Capella, inner),
+        SignedBeaconBlock::Deneb(inner) => encode(Fork::Deneb, inner),
+        SignedBeaconBlock::Electra(inner) => encode(Fork::Electra, inner),
+    }
+}
+
+pub fn decode_block<
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+>(
+    bytes: &[u8],
+) -> Result<
+    SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >,
+    StorageError,
+> {
+    let (fork, encoding) = decode(bytes)?;
+    let block = match fork {
+        Fork::Phase0 => {
+            SignedBeaconBlock::Phase0(phase0::SignedBeaconBlock::deserialize(&encoding)?)
+        }
+        Fork::Altair => {
+            SignedBeaconBlock::Altair(altair::SignedBeaconBlock::deserialize(&encoding)?)
+        }
+        Fork::Bellatrix => {
+            SignedBeaconBlock::Bellatrix(bellatrix::SignedBeaconBlock::deserialize(&encoding)?)
+        }
+        Fork::Capella => {
+            SignedBeaconBlock::Capella(capella::SignedBeaconBlock::deserialize(&encoding)?)
+        }
+        Fork::Deneb => SignedBeaconBlock::Deneb(deneb::SignedBeaconBlock::deserialize(&encoding)?),
+        Fork::Electra => {
+            SignedBeaconBlock::Electra(electra::SignedBeaconBlock::deserialize(&encoding)?)
+        }
+    };
+    Ok(block)
+}
+
+pub fn encode_state<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+) -> Result<Vec<u8>, StorageError> {
+    match state {
+        BeaconState::Phase0(inner) => encode(Fork::Phase0, inner),
+        BeaconState::Altair(inner) => encode(Fork::Altair, inner),
+        BeaconState::Bellatrix(inner) => encode(Fork::Bellatrix, inner),
+        BeaconState::Capella(inner) => encode(Fork::Capella, inner),
+        BeaconState::Deneb(inner) => encode(Fork::Deneb, inner),
+        BeaconState::Electra(inner) => encode(Fork::Electra, inner),
+    }
+}
+
+pub fn decode_state<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    bytes: &[u8],
+) -> Result<
+    BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    StorageError,
+> {
+    let (fork, encoding) = decode(bytes)?;
+    let state = match fork {
+        Fork::Phase0 => BeaconState::Phase0(phase0::BeaconState::deserialize(&encoding)?),
+        Fork::Altair => BeaconState::Altair(altair::BeaconState::deserialize(&encoding)?),
+        Fork::Bellatrix => BeaconState::Bellatrix(bellatrix::BeaconState::deserialize(&encoding)?),
+        Fork::Capella => BeaconState::Capella(capella::BeaconState::deserialize(&encoding)?),
+        Fork::Deneb => BeaconState::Deneb(deneb::BeaconState::deserialize(&encoding)?),
+        Fork::Electra => BeaconState::Electra(electra::BeaconState::deserialize(&encoding)?),
+    };
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fixtures::phase0::{genesis_state, signed_block},
+        state_transition::Context,
+        types::minimal,
+    };
+
+    fn blocks() -> Vec<minimal::SignedBeaconBlock> {
+        let mut deneb_block = deneb::minimal::SignedBeaconBlock::default();
+        deneb_block.message.slot = 7;
+        deneb_block.message.body.blob_kzg_commitments.push(Default::default());
+        let mut electra_block = electra::minimal::SignedBeaconBlock::default();
+        electra_block.message.slot = 9;
+        electra_block.message.body.execution_requests.deposits.push(Default::default());
+        vec![
+            SignedBeaconBlock::Phase0(signed_block()),
+            SignedBeaconBlock::Altair(Default::default()),
+            SignedBeaconBlock::Bellatrix(Default::default()),
+            SignedBeaconBlock::Capella(Default::default()),
+            SignedBeaconBlock::Deneb(deneb_block),
+            SignedBeaconBlock::Electra(electra_block),
+        ]
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for block in blocks() {
+            let bytes = encode_block(&block).unwrap();
+            assert_eq!(bytes[0], fork_tag(block.version()));
+            let recovered: minimal::SignedBeaconBlock = decode_block(&bytes).unwrap();
+            assert_eq!(recovered, block);
+        }
+
+        let genesis_state = genesis_state();
+        let context = Context::for_minimal();
+        let altair_state = altair::upgrade_to_altair(&genesis_state, &context).unwrap();
+        let bellatrix_state = bellatrix::upgrade_to_bellatrix(&altair_state, &context);
+        let capella_state = capella::upgrade_to_capella(&bellatrix_state, &context);
+        let deneb_state = deneb::upgrade_to_deneb(&capella_state, &context);
+        let electra_state = electra::upgrade_to_electra(&deneb_state, &context).unwrap();
+        let states: Vec<minimal::BeaconState> = vec![
+            BeaconState::Phase0(genesis_state),
+            BeaconState::Altair(altair_state),
+            BeaconState::Bellatrix(bellatrix_state),
+            BeaconState::Capella(capella_state),
+            BeaconState::Deneb(deneb_state),
+            BeaconState::Electra(electra_state),
+        ];
+        for state in states {
+            let bytes = encode_state(&state).unwrap();
+            assert_eq!(bytes[0], fork_tag(state.version()));
+            // the registry of a genesis state compresses well
+            assert!(bytes.len() < serialize(&state).unwrap().len());
+            let recovered: minimal::BeaconState = decode_state(&bytes).unwrap();
+            assert_eq!(recovered, state);
+        }
+    }
+
+    #[test]
+    fn test_corrupted_tag() {
+        let block = SignedBeaconBlock::Phase0(signed_block());
+        let bytes = encode_block(&block).unwrap();
+
+        // data from newer forks (or garbage) is handed back untouched
+        for tag in [fork_tag(Fork::Electra) + 1, u8::MAX] {
+            let mut unsupported = bytes.clone();
+            unsupported[0] = tag;
+            let result: Result<minimal::SignedBeaconBlock, _> = decode_block(&unsupported);
+            let err = result.unwrap_err();
+            assert!(matches!(
+                err,
+                StorageError::UnsupportedFork { tag: t, bytes } if t == tag && bytes == unsupported
+            ));
+        }
+
+        // a payload under the tag of another fork does not decode
+        let mut mislabeled = bytes.clone();
+        mislabeled[0] = fork_tag(Fork::Deneb);
+        let result: Result<minimal::SignedBeaconBlock, _> = decode_block(&mislabeled);
+        assert!(matches!(result, Err(StorageError::Deserialize(_))));
+
+        let result: Result<minimal::SignedBeaconBlock, _> = decode_block(&[]);
+        assert!(matches!(result, Err(StorageError::Empty)));
+        let result: Result<minimal::SignedBeaconBlock, _> = decode_block(&bytes[..bytes.len() / 2]);
+        assert!(matches!(result, Err(StorageError::Snappy(_))));
+    }
+
+    #[test]
+    fn test_length_prefix() {
+        let block = SignedBeaconBlock::Phase0(signed_block());
+        let bytes = encode_block(&block).unwrap();
+        let ssz = serialize(&block).unwrap();
+        let varint = |mut value: usize| {
+            let mut bytes = vec![];
+            while value >= 0x80 {
+                bytes.push((value as u8) | 0x80);
+                value >>= 7;
+            }
+            bytes.push(value as u8);
+            bytes
+        };
+        // the prefix is the length of the SSZ encoding, followed by the snappy stream identifier
+        let prefix = varint(ssz.len());
+        assert_eq!(&bytes[1..=prefix.len()], prefix);
+        let stream = &bytes[1 + prefix.len()..];
+        assert_eq!(&stream[..10], b"\xff\x06\x00\x00sNaPpY");
+
+        // a prefix that disagrees with the payload is rejected either way
+        for length in [ssz.len() - 1, ssz.len() + 1] {
+            let mut wrong = vec![bytes[0]];
+            wrong.extend(varint(length));
+            wrong.extend_from_slice(stream);
+            let result: Result<minimal::SignedBeaconBlock, _> = decode_block(&wrong);
+            assert!(matches!(result, Err(StorageError::LengthMismatch { .. })));
+        }
+        let result: Result<minimal::SignedBeaconBlock, _> = decode_block(&[0, 0xff]);
+        assert!(matches!(result, Err(StorageError::MalformedLength)));
+        let mut overlong = vec![0];
+        overlong.extend([0xff; MAX_VARINT_LEN]);
+        let result: Result<minimal::SignedBeaconBlock, _> = decode_block(&overlong);
+        assert!(matches!(result, Err(StorageError::MalformedLength)));
+    }
+}
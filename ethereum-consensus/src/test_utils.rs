@@ -0,0 +1,81 @@
+//! Utilities for testing code that handles blobs, e.g. data availability checks.
+//!
+//! Blobs are generated from a seed so tests are reproducible, and every 32-byte chunk of a
+//! generated blob is a canonical field element so the KZG backend accepts it.
+use crate::{
+    deneb::{
+        blob_sidecar::Blob,
+        polynomial_commitments::{
+            blob_to_kzg_commitment, compute_blob_kzg_proof, Error, KzgCommitment, KzgProof,
+            KzgSettings, BYTES_PER_FIELD_ELEMENT,
+        },
+    },
+    interop::reduce,
+};
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+/// Blobs with the commitment and proof for each, in the same order.
+pub type BlobBundle<const BYTES_PER_BLOB: usize> =
+    (Vec<Blob<BYTES_PER_BLOB>>, Vec<KzgCommitment>, Vec<KzgProof>);
+
+/// A blob of pseudo-random canonical field elements, determined by `seed`.
+pub fn random_valid_blob<const BYTES_PER_BLOB: usize>(seed: u64) -> Blob<BYTES_PER_BLOB> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut bytes = vec![0u8; BYTES_PER_BLOB];
+    for element in bytes.chunks_exact_mut(BYTES_PER_FIELD_ELEMENT) {
+        rng.fill_bytes(element);
+        reduce(element);
+    }
+    Blob::try_from(bytes.as_slice()).expect("correct size")
+}
+
+/// `blob_count` blobs with their commitments and proofs, made with the KZG backend. The `i`th
+/// blob is the one [`random_valid_blob`] makes from `seed + i`.
+pub fn random_blob_bundle<const BYTES_PER_BLOB: usize>(
+    seed: u64,
+    blob_count: usize,
+    kzg_settings: &KzgSettings,
+) -> Result<BlobBundle<BYTES_PER_BLOB>, Error> {
+    let mut blobs = Vec::with_capacity(blob_count);
+    let mut commitments = Vec::with_capacity(blob_count);
+    let mut proofs = Vec::with_capacity(blob_count);
+    for i in 0..blob_count as u64 {
+        let blob = random_valid_blob(seed.wrapping_add(i));
+        let commitment = blob_to_kzg_commitment(&blob, kzg_settings)?;
+        proofs.push(compute_blob_kzg_proof(&blob, &commitment, kzg_settings)?);
+        commitments.push(commitment);
+        blobs.push(blob);
+    }
+    Ok((blobs, commitments, proofs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        deneb::{minimal, polynomial_commitments::verify_blob_kzg_proof_batch},
+        interop::BLS_MODULUS,
+        state_transition::Context,
+    };
+
+    #[test]
+    fn test_random_valid_blob() {
+        let blob = random_valid_blob::<{ minimal::BYTES_PER_BLOB }>(7);
+        assert_eq!(blob, random_valid_blob(7));
+        assert_ne!(blob, random_valid_blob(8));
+        let bytes: &[u8] = blob.as_ref();
+        for element in bytes.chunks_exact(BYTES_PER_FIELD_ELEMENT) {
+            assert!(element < &BLS_MODULUS[..]);
+        }
+    }
+
+    #[test]
+    fn test_random_blob_bundle() {
+        let context = Context::for_minimal();
+        let (blobs, commitments, proofs) =
+            random_blob_bundle::<{ minimal::BYTES_PER_BLOB }>(11, 3, &context.kzg_settings)
+                .unwrap();
+        assert_eq!(blobs[2], random_valid_blob(13));
+        verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs, &context.kzg_settings).unwrap();
+    }
+}
@@ -4,6 +4,8 @@ use crate::{
     bellatrix::beacon_block as bellatrix,
     capella::{beacon_block as capella, SignedBlsToExecutionChange},
     deneb::{beacon_block as deneb, polynomial_commitments::KzgCommitment},
+    electra::{beacon_block as electra, ExecutionRequests},
+    error::WrongFork,
     phase0::{
         beacon_block as phase0, Attestation, AttesterSlashing, Deposit, Eth1Data, ProposerSlashing,
         SignedVoluntaryExit,
@@ -31,6 +33,13 @@ pub enum BeaconBlockBody<
     const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
     const MAX_BLS_TO_EXECUTION_CHANGES: usize,
     const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
 > {
     Phase0(
         phase0::BeaconBlockBody<
@@ -103,6 +112,28 @@ pub enum BeaconBlockBody<
             MAX_BLOB_COMMITMENTS_PER_BLOCK,
         >,
     ),
+    Electra(
+        electra::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    ),
 }
 impl<
         const MAX_PROPOSER_SLASHINGS: usize,
@@ -119,6 +150,13 @@ impl<
         const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     BeaconBlockBody<
         MAX_PROPOSER_SLASHINGS,
@@ -135,6 +173,13 @@ impl<
         MAX_WITHDRAWALS_PER_PAYLOAD,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     pub fn phase0(
@@ -171,6 +216,60 @@ impl<
             _ => None,
         }
     }
+    pub fn as_phase0(
+        &self,
+    ) -> Result<
+        &phase0::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Phase0(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Phase0, found: other.version() }),
+        }
+    }
+    pub fn as_phase0_mut(
+        &mut self,
+    ) -> Result<
+        &mut phase0::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Phase0(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Phase0, found: other.version() }),
+        }
+    }
+    pub fn into_phase0(
+        self,
+    ) -> Result<
+        phase0::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Phase0(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Phase0, found: other.version() }),
+        }
+    }
     pub fn altair(
         &self,
     ) -> Option<
@@ -207,6 +306,63 @@ impl<
             _ => None,
         }
     }
+    pub fn as_altair(
+        &self,
+    ) -> Result<
+        &altair::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Altair(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Altair, found: other.version() }),
+        }
+    }
+    pub fn as_altair_mut(
+        &mut self,
+    ) -> Result<
+        &mut altair::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Altair(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Altair, found: other.version() }),
+        }
+    }
+    pub fn into_altair(
+        self,
+    ) -> Result<
+        altair::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Altair(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Altair, found: other.version() }),
+        }
+    }
     pub fn bellatrix(
         &self,
     ) -> Option<
@@ -251,6 +407,75 @@ impl<
             _ => None,
         }
     }
+    pub fn as_bellatrix(
+        &self,
+    ) -> Result<
+        &bellatrix::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Bellatrix(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Bellatrix, found: other.version() }),
+        }
+    }
+    pub fn as_bellatrix_mut(
+        &mut self,
+    ) -> Result<
+        &mut bellatrix::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Bellatrix(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Bellatrix, found: other.version() }),
+        }
+    }
+    pub fn into_bellatrix(
+        self,
+    ) -> Result<
+        bellatrix::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Bellatrix(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Bellatrix, found: other.version() }),
+        }
+    }
     pub fn capella(
         &self,
     ) -> Option<
@@ -299,6 +524,81 @@ impl<
             _ => None,
         }
     }
+    pub fn as_capella(
+        &self,
+    ) -> Result<
+        &capella::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Capella(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Capella, found: other.version() }),
+        }
+    }
+    pub fn as_capella_mut(
+        &mut self,
+    ) -> Result<
+        &mut capella::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Capella(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Capella, found: other.version() }),
+        }
+    }
+    pub fn into_capella(
+        self,
+    ) -> Result<
+        capella::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Capella(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Capella, found: other.version() }),
+        }
+    }
     pub fn deneb(
         &self,
     ) -> Option<
@@ -349,6 +649,232 @@ impl<
             _ => None,
         }
     }
+    pub fn as_deneb(
+        &self,
+    ) -> Result<
+        &deneb::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Deneb(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Deneb, found: other.version() }),
+        }
+    }
+    pub fn as_deneb_mut(
+        &mut self,
+    ) -> Result<
+        &mut deneb::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Deneb(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Deneb, found: other.version() }),
+        }
+    }
+    pub fn into_deneb(
+        self,
+    ) -> Result<
+        deneb::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Deneb(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Deneb, found: other.version() }),
+        }
+    }
+    pub fn electra(
+        &self,
+    ) -> Option<
+        &electra::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        match self {
+            Self::Electra(inner) => Some(inner),
+            _ => None,
+        }
+    }
+    pub fn electra_mut(
+        &mut self,
+    ) -> Option<
+        &mut electra::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        match self {
+            Self::Electra(inner) => Some(inner),
+            _ => None,
+        }
+    }
+    pub fn as_electra(
+        &self,
+    ) -> Result<
+        &electra::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Electra(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Electra, found: other.version() }),
+        }
+    }
+    pub fn as_electra_mut(
+        &mut self,
+    ) -> Result<
+        &mut electra::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Electra(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Electra, found: other.version() }),
+        }
+    }
+    pub fn into_electra(
+        self,
+    ) -> Result<
+        electra::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Electra(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Electra, found: other.version() }),
+        }
+    }
     pub fn version(&self) -> Version {
         match self {
             Self::Phase0(_) => Version::Phase0,
@@ -356,6 +882,7 @@ impl<
             Self::Bellatrix(_) => Version::Bellatrix,
             Self::Capella(_) => Version::Capella,
             Self::Deneb(_) => Version::Deneb,
+            Self::Electra(_) => Version::Electra,
         }
     }
     pub fn randao_reveal(&self) -> &BlsSignature {
@@ -365,6 +892,7 @@ impl<
             Self::Bellatrix(inner) => &inner.randao_reveal,
             Self::Capella(inner) => &inner.randao_reveal,
             Self::Deneb(inner) => &inner.randao_reveal,
+            Self::Electra(inner) => &inner.randao_reveal,
         }
     }
     pub fn randao_reveal_mut(&mut self) -> &mut BlsSignature {
@@ -374,6 +902,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.randao_reveal,
             Self::Capella(inner) => &mut inner.randao_reveal,
             Self::Deneb(inner) => &mut inner.randao_reveal,
+            Self::Electra(inner) => &mut inner.randao_reveal,
         }
     }
     pub fn eth1_data(&self) -> &Eth1Data {
@@ -383,6 +912,7 @@ impl<
             Self::Bellatrix(inner) => &inner.eth1_data,
             Self::Capella(inner) => &inner.eth1_data,
             Self::Deneb(inner) => &inner.eth1_data,
+            Self::Electra(inner) => &inner.eth1_data,
         }
     }
     pub fn eth1_data_mut(&mut self) -> &mut Eth1Data {
@@ -392,6 +922,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.eth1_data,
             Self::Capella(inner) => &mut inner.eth1_data,
             Self::Deneb(inner) => &mut inner.eth1_data,
+            Self::Electra(inner) => &mut inner.eth1_data,
         }
     }
     pub fn graffiti(&self) -> &Bytes32 {
@@ -401,6 +932,7 @@ impl<
             Self::Bellatrix(inner) => &inner.graffiti,
             Self::Capella(inner) => &inner.graffiti,
             Self::Deneb(inner) => &inner.graffiti,
+            Self::Electra(inner) => &inner.graffiti,
         }
     }
     pub fn graffiti_mut(&mut self) -> &mut Bytes32 {
@@ -410,6 +942,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.graffiti,
             Self::Capella(inner) => &mut inner.graffiti,
             Self::Deneb(inner) => &mut inner.graffiti,
+            Self::Electra(inner) => &mut inner.graffiti,
         }
     }
     pub fn proposer_slashings(&self) -> &List<ProposerSlashing, MAX_PROPOSER_SLASHINGS> {
@@ -419,6 +952,7 @@ impl<
             Self::Bellatrix(inner) => &inner.proposer_slashings,
             Self::Capella(inner) => &inner.proposer_slashings,
             Self::Deneb(inner) => &inner.proposer_slashings,
+            Self::Electra(inner) => &inner.proposer_slashings,
         }
     }
     pub fn proposer_slashings_mut(
@@ -430,50 +964,56 @@ impl<
             Self::Bellatrix(inner) => &mut inner.proposer_slashings,
             Self::Capella(inner) => &mut inner.proposer_slashings,
             Self::Deneb(inner) => &mut inner.proposer_slashings,
+            Self::Electra(inner) => &mut inner.proposer_slashings,
         }
     }
     pub fn attester_slashings(
         &self,
-    ) -> &List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS> {
+    ) -> Option<&List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS>> {
         match self {
-            Self::Phase0(inner) => &inner.attester_slashings,
-            Self::Altair(inner) => &inner.attester_slashings,
-            Self::Bellatrix(inner) => &inner.attester_slashings,
-            Self::Capella(inner) => &inner.attester_slashings,
-            Self::Deneb(inner) => &inner.attester_slashings,
+            Self::Phase0(inner) => Some(&inner.attester_slashings),
+            Self::Altair(inner) => Some(&inner.attester_slashings),
+            Self::Bellatrix(inner) => Some(&inner.attester_slashings),
+            Self::Capella(inner) => Some(&inner.attester_slashings),
+            Self::Deneb(inner) => Some(&inner.attester_slashings),
+            Self::Electra(_) => None,
         }
     }
     pub fn attester_slashings_mut(
         &mut self,
-    ) -> &mut List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS> {
+    ) -> Option<&mut List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS>>
+    {
         match self {
-            Self::Phase0(inner) => &mut inner.attester_slashings,
-            Self::Altair(inner) => &mut inner.attester_slashings,
-            Self::Bellatrix(inner) => &mut inner.attester_slashings,
-            Self::Capella(inner) => &mut inner.attester_slashings,
-            Self::Deneb(inner) => &mut inner.attester_slashings,
+            Self::Phase0(inner) => Some(&mut inner.attester_slashings),
+            Self::Altair(inner) => Some(&mut inner.attester_slashings),
+            Self::Bellatrix(inner) => Some(&mut inner.attester_slashings),
+            Self::Capella(inner) => Some(&mut inner.attester_slashings),
+            Self::Deneb(inner) => Some(&mut inner.attester_slashings),
+            Self::Electra(_) => None,
         }
     }
     pub fn attestations(
         &self,
-    ) -> &List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS> {
+    ) -> Option<&List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS>> {
         match self {
-            Self::Phase0(inner) => &inner.attestations,
-            Self::Altair(inner) => &inner.attestations,
-            Self::Bellatrix(inner) => &inner.attestations,
-            Self::Capella(inner) => &inner.attestations,
-            Self::Deneb(inner) => &inner.attestations,
+            Self::Phase0(inner) => Some(&inner.attestations),
+            Self::Altair(inner) => Some(&inner.attestations),
+            Self::Bellatrix(inner) => Some(&inner.attestations),
+            Self::Capella(inner) => Some(&inner.attestations),
+            Self::Deneb(inner) => Some(&inner.attestations),
+            Self::Electra(_) => None,
         }
     }
     pub fn attestations_mut(
         &mut self,
-    ) -> &mut List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS> {
+    ) -> Option<&mut List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS>> {
         match self {
-            Self::Phase0(inner) => &mut inner.attestations,
-            Self::Altair(inner) => &mut inner.attestations,
-            Self::Bellatrix(inner) => &mut inner.attestations,
-            Self::Capella(inner) => &mut inner.attestations,
-            Self::Deneb(inner) => &mut inner.attestations,
+            Self::Phase0(inner) => Some(&mut inner.attestations),
+            Self::Altair(inner) => Some(&mut inner.attestations),
+            Self::Bellatrix(inner) => Some(&mut inner.attestations),
+            Self::Capella(inner) => Some(&mut inner.attestations),
+            Self::Deneb(inner) => Some(&mut inner.attestations),
+            Self::Electra(_) => None,
         }
     }
     pub fn deposits(&self) -> &List<Deposit, MAX_DEPOSITS> {
@@ -483,6 +1023,7 @@ impl<
             Self::Bellatrix(inner) => &inner.deposits,
             Self::Capella(inner) => &inner.deposits,
             Self::Deneb(inner) => &inner.deposits,
+            Self::Electra(inner) => &inner.deposits,
         }
     }
     pub fn deposits_mut(&mut self) -> &mut List<Deposit, MAX_DEPOSITS> {
@@ -492,6 +1033,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.deposits,
             Self::Capella(inner) => &mut inner.deposits,
             Self::Deneb(inner) => &mut inner.deposits,
+            Self::Electra(inner) => &mut inner.deposits,
         }
     }
     pub fn voluntary_exits(&self) -> &List<SignedVoluntaryExit, MAX_VOLUNTARY_EXITS> {
@@ -501,6 +1043,7 @@ impl<
             Self::Bellatrix(inner) => &inner.voluntary_exits,
             Self::Capella(inner) => &inner.voluntary_exits,
             Self::Deneb(inner) => &inner.voluntary_exits,
+            Self::Electra(inner) => &inner.voluntary_exits,
         }
     }
     pub fn voluntary_exits_mut(&mut self) -> &mut List<SignedVoluntaryExit, MAX_VOLUNTARY_EXITS> {
@@ -510,6 +1053,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.voluntary_exits,
             Self::Capella(inner) => &mut inner.voluntary_exits,
             Self::Deneb(inner) => &mut inner.voluntary_exits,
+            Self::Electra(inner) => &mut inner.voluntary_exits,
         }
     }
     pub fn sync_aggregate(&self) -> Option<&SyncAggregate<SYNC_COMMITTEE_SIZE>> {
@@ -519,6 +1063,7 @@ impl<
             Self::Bellatrix(inner) => Some(&inner.sync_aggregate),
             Self::Capella(inner) => Some(&inner.sync_aggregate),
             Self::Deneb(inner) => Some(&inner.sync_aggregate),
+            Self::Electra(inner) => Some(&inner.sync_aggregate),
         }
     }
     pub fn sync_aggregate_mut(&mut self) -> Option<&mut SyncAggregate<SYNC_COMMITTEE_SIZE>> {
@@ -528,6 +1073,7 @@ impl<
             Self::Bellatrix(inner) => Some(&mut inner.sync_aggregate),
             Self::Capella(inner) => Some(&mut inner.sync_aggregate),
             Self::Deneb(inner) => Some(&mut inner.sync_aggregate),
+            Self::Electra(inner) => Some(&mut inner.sync_aggregate),
         }
     }
     pub fn execution_payload(
@@ -547,6 +1093,7 @@ impl<
             Self::Bellatrix(inner) => Some(From::from(&inner.execution_payload)),
             Self::Capella(inner) => Some(From::from(&inner.execution_payload)),
             Self::Deneb(inner) => Some(From::from(&inner.execution_payload)),
+            Self::Electra(inner) => Some(From::from(&inner.execution_payload)),
         }
     }
     pub fn execution_payload_mut(
@@ -566,6 +1113,7 @@ impl<
             Self::Bellatrix(inner) => Some(From::from(&mut inner.execution_payload)),
             Self::Capella(inner) => Some(From::from(&mut inner.execution_payload)),
             Self::Deneb(inner) => Some(From::from(&mut inner.execution_payload)),
+            Self::Electra(inner) => Some(From::from(&mut inner.execution_payload)),
         }
     }
     pub fn bls_to_execution_changes(
@@ -577,6 +1125,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(inner) => Some(&inner.bls_to_execution_changes),
             Self::Deneb(inner) => Some(&inner.bls_to_execution_changes),
+            Self::Electra(inner) => Some(&inner.bls_to_execution_changes),
         }
     }
     pub fn bls_to_execution_changes_mut(
@@ -588,6 +1137,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(inner) => Some(&mut inner.bls_to_execution_changes),
             Self::Deneb(inner) => Some(&mut inner.bls_to_execution_changes),
+            Self::Electra(inner) => Some(&mut inner.bls_to_execution_changes),
         }
     }
     pub fn blob_kzg_commitments(
@@ -598,18 +1148,56 @@ impl<
             Self::Altair(_) => None,
             Self::Bellatrix(_) => None,
             Self::Capella(_) => None,
-            Self::Deneb(inner) => Some(&inner.blob_kzg_commitments),
+            Self::Deneb(inner) => Some(&inner.blob_kzg_commitments),
+            Self::Electra(inner) => Some(&inner.blob_kzg_commitments),
+        }
+    }
+    pub fn blob_kzg_commitments_mut(
+        &mut self,
+    ) -> Option<&mut List<KzgCommitment, MAX_BLOB_COMMITMENTS_PER_BLOCK>> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(inner) => Some(&mut inner.blob_kzg_commitments),
+            Self::Electra(inner) => Some(&mut inner.blob_kzg_commitments),
+        }
+    }
+    pub fn execution_requests(
+        &self,
+    ) -> Option<
+        &ExecutionRequests<
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.execution_requests),
         }
     }
-    pub fn blob_kzg_commitments_mut(
+    pub fn execution_requests_mut(
         &mut self,
-    ) -> Option<&mut List<KzgCommitment, MAX_BLOB_COMMITMENTS_PER_BLOCK>> {
+    ) -> Option<
+        &mut ExecutionRequests<
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
         match self {
             Self::Phase0(_) => None,
             Self::Altair(_) => None,
             Self::Bellatrix(_) => None,
             Self::Capella(_) => None,
-            Self::Deneb(inner) => Some(&mut inner.blob_kzg_commitments),
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.execution_requests),
         }
     }
 }
@@ -629,6 +1217,13 @@ impl<
         const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     > serde::Deserialize<'de>
     for BeaconBlockBody<
         MAX_PROPOSER_SLASHINGS,
@@ -645,6 +1240,13 @@ impl<
         MAX_WITHDRAWALS_PER_PAYLOAD,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -652,6 +1254,9 @@ impl<
         D: serde::Deserializer<'de>,
     {
         let value = serde_json::Value::deserialize(deserializer)?;
+        if let Ok(inner) = <_ as serde::Deserialize>::deserialize(&value) {
+            return Ok(Self::Electra(inner));
+        }
         if let Ok(inner) = <_ as serde::Deserialize>::deserialize(&value) {
             return Ok(Self::Deneb(inner));
         }
@@ -688,6 +1293,13 @@ pub enum BeaconBlockBodyRef<
     const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
     const MAX_BLS_TO_EXECUTION_CHANGES: usize,
     const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
 > {
     Phase0(
         &'a phase0::BeaconBlockBody<
@@ -760,6 +1372,28 @@ pub enum BeaconBlockBodyRef<
             MAX_BLOB_COMMITMENTS_PER_BLOCK,
         >,
     ),
+    Electra(
+        &'a electra::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    ),
 }
 impl<
         'a,
@@ -777,6 +1411,13 @@ impl<
         const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     BeaconBlockBodyRef<
         'a,
@@ -794,6 +1435,13 @@ impl<
         MAX_WITHDRAWALS_PER_PAYLOAD,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     pub fn phase0(
@@ -902,6 +1550,35 @@ impl<
             _ => None,
         }
     }
+    pub fn electra(
+        &self,
+    ) -> Option<
+        &electra::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        match self {
+            Self::Electra(inner) => Some(inner),
+            _ => None,
+        }
+    }
     pub fn version(&self) -> Version {
         match self {
             Self::Phase0(_) => Version::Phase0,
@@ -909,6 +1586,7 @@ impl<
             Self::Bellatrix(_) => Version::Bellatrix,
             Self::Capella(_) => Version::Capella,
             Self::Deneb(_) => Version::Deneb,
+            Self::Electra(_) => Version::Electra,
         }
     }
     pub fn randao_reveal(&self) -> &BlsSignature {
@@ -918,6 +1596,7 @@ impl<
             Self::Bellatrix(inner) => &inner.randao_reveal,
             Self::Capella(inner) => &inner.randao_reveal,
             Self::Deneb(inner) => &inner.randao_reveal,
+            Self::Electra(inner) => &inner.randao_reveal,
         }
     }
     pub fn eth1_data(&self) -> &Eth1Data {
@@ -927,6 +1606,7 @@ impl<
             Self::Bellatrix(inner) => &inner.eth1_data,
             Self::Capella(inner) => &inner.eth1_data,
             Self::Deneb(inner) => &inner.eth1_data,
+            Self::Electra(inner) => &inner.eth1_data,
         }
     }
     pub fn graffiti(&self) -> &Bytes32 {
@@ -936,6 +1616,7 @@ impl<
             Self::Bellatrix(inner) => &inner.graffiti,
             Self::Capella(inner) => &inner.graffiti,
             Self::Deneb(inner) => &inner.graffiti,
+            Self::Electra(inner) => &inner.graffiti,
         }
     }
     pub fn proposer_slashings(&self) -> &List<ProposerSlashing, MAX_PROPOSER_SLASHINGS> {
@@ -945,28 +1626,31 @@ impl<
             Self::Bellatrix(inner) => &inner.proposer_slashings,
             Self::Capella(inner) => &inner.proposer_slashings,
             Self::Deneb(inner) => &inner.proposer_slashings,
+            Self::Electra(inner) => &inner.proposer_slashings,
         }
     }
     pub fn attester_slashings(
         &self,
-    ) -> &List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS> {
+    ) -> Option<&List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS>> {
         match self {
-            Self::Phase0(inner) => &inner.attester_slashings,
-            Self::Altair(inner) => &inner.attester_slashings,
-            Self::Bellatrix(inner) => &inner.attester_slashings,
-            Self::Capella(inner) => &inner.attester_slashings,
-            Self::Deneb(inner) => &inner.attester_slashings,
+            Self::Phase0(inner) => Some(&inner.attester_slashings),
+            Self::Altair(inner) => Some(&inner.attester_slashings),
+            Self::Bellatrix(inner) => Some(&inner.attester_slashings),
+            Self::Capella(inner) => Some(&inner.attester_slashings),
+            Self::Deneb(inner) => Some(&inner.attester_slashings),
+            Self::Electra(_) => None,
         }
     }
     pub fn attestations(
         &self,
-    ) -> &List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS> {
+    ) -> Option<&List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS>> {
         match self {
-            Self::Phase0(inner) => &inner.attestations,
-            Self::Altair(inner) => &inner.attestations,
-            Self::Bellatrix(inner) => &inner.attestations,
-            Self::Capella(inner) => &inner.attestations,
-            Self::Deneb(inner) => &inner.attestations,
+            Self::Phase0(inner) => Some(&inner.attestations),
+            Self::Altair(inner) => Some(&inner.attestations),
+            Self::Bellatrix(inner) => Some(&inner.attestations),
+            Self::Capella(inner) => Some(&inner.attestations),
+            Self::Deneb(inner) => Some(&inner.attestations),
+            Self::Electra(_) => None,
         }
     }
     pub fn deposits(&self) -> &List<Deposit, MAX_DEPOSITS> {
@@ -976,6 +1660,7 @@ impl<
             Self::Bellatrix(inner) => &inner.deposits,
             Self::Capella(inner) => &inner.deposits,
             Self::Deneb(inner) => &inner.deposits,
+            Self::Electra(inner) => &inner.deposits,
         }
     }
     pub fn voluntary_exits(&self) -> &List<SignedVoluntaryExit, MAX_VOLUNTARY_EXITS> {
@@ -985,6 +1670,7 @@ impl<
             Self::Bellatrix(inner) => &inner.voluntary_exits,
             Self::Capella(inner) => &inner.voluntary_exits,
             Self::Deneb(inner) => &inner.voluntary_exits,
+            Self::Electra(inner) => &inner.voluntary_exits,
         }
     }
     pub fn sync_aggregate(&self) -> Option<&SyncAggregate<SYNC_COMMITTEE_SIZE>> {
@@ -994,6 +1680,7 @@ impl<
             Self::Bellatrix(inner) => Some(&inner.sync_aggregate),
             Self::Capella(inner) => Some(&inner.sync_aggregate),
             Self::Deneb(inner) => Some(&inner.sync_aggregate),
+            Self::Electra(inner) => Some(&inner.sync_aggregate),
         }
     }
     pub fn execution_payload(
@@ -1013,6 +1700,7 @@ impl<
             Self::Bellatrix(inner) => Some(From::from(&inner.execution_payload)),
             Self::Capella(inner) => Some(From::from(&inner.execution_payload)),
             Self::Deneb(inner) => Some(From::from(&inner.execution_payload)),
+            Self::Electra(inner) => Some(From::from(&inner.execution_payload)),
         }
     }
     pub fn bls_to_execution_changes(
@@ -1024,6 +1712,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(inner) => Some(&inner.bls_to_execution_changes),
             Self::Deneb(inner) => Some(&inner.bls_to_execution_changes),
+            Self::Electra(inner) => Some(&inner.bls_to_execution_changes),
         }
     }
     pub fn blob_kzg_commitments(
@@ -1035,6 +1724,25 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(_) => None,
             Self::Deneb(inner) => Some(&inner.blob_kzg_commitments),
+            Self::Electra(inner) => Some(&inner.blob_kzg_commitments),
+        }
+    }
+    pub fn execution_requests(
+        &self,
+    ) -> Option<
+        &ExecutionRequests<
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.execution_requests),
         }
     }
 }
@@ -1054,6 +1762,13 @@ impl<
         const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     From<
         &'a phase0::BeaconBlockBody<
@@ -1081,6 +1796,13 @@ impl<
         MAX_WITHDRAWALS_PER_PAYLOAD,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     fn from(
@@ -1112,6 +1834,13 @@ impl<
         const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     From<
         &'a altair::BeaconBlockBody<
@@ -1140,6 +1869,13 @@ impl<
         MAX_WITHDRAWALS_PER_PAYLOAD,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     fn from(
@@ -1172,6 +1908,13 @@ impl<
         const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     From<
         &'a bellatrix::BeaconBlockBody<
@@ -1204,6 +1947,13 @@ impl<
         MAX_WITHDRAWALS_PER_PAYLOAD,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     fn from(
@@ -1240,6 +1990,13 @@ impl<
         const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     From<
         &'a capella::BeaconBlockBody<
@@ -1274,6 +2031,13 @@ impl<
         MAX_WITHDRAWALS_PER_PAYLOAD,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     fn from(
@@ -1312,6 +2076,13 @@ impl<
         const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     From<
         &'a deneb::BeaconBlockBody<
@@ -1347,6 +2118,13 @@ impl<
         MAX_WITHDRAWALS_PER_PAYLOAD,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     fn from(
@@ -1370,6 +2148,102 @@ impl<
         Self::Deneb(value)
     }
 }
+impl<
+        'a,
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+    >
+    From<
+        &'a electra::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    >
+    for BeaconBlockBodyRef<
+        'a,
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >
+{
+    fn from(
+        value: &'a electra::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    ) -> Self {
+        Self::Electra(value)
+    }
+}
 #[derive(Debug, PartialEq, Eq, HashTreeRoot)]
 #[ssz(transparent)]
 pub enum BeaconBlockBodyRefMut<
@@ -1388,6 +2262,13 @@ pub enum BeaconBlockBodyRefMut<
     const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
     const MAX_BLS_TO_EXECUTION_CHANGES: usize,
     const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
 > {
     Phase0(
         &'a mut phase0::BeaconBlockBody<
@@ -1460,6 +2341,28 @@ pub enum BeaconBlockBodyRefMut<
             MAX_BLOB_COMMITMENTS_PER_BLOCK,
         >,
     ),
+    Electra(
+        &'a mut electra::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    ),
 }
 impl<
         'a,
@@ -1477,6 +2380,13 @@ impl<
         const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     BeaconBlockBodyRefMut<
         'a,
@@ -1494,6 +2404,13 @@ impl<
         MAX_WITHDRAWALS_PER_PAYLOAD,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     pub fn phase0(
@@ -1708,6 +2625,64 @@ impl<
             _ => None,
         }
     }
+    pub fn electra(
+        &self,
+    ) -> Option<
+        &electra::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        match self {
+            Self::Electra(inner) => Some(inner),
+            _ => None,
+        }
+    }
+    pub fn electra_mut(
+        &mut self,
+    ) -> Option<
+        &mut electra::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        match self {
+            Self::Electra(inner) => Some(inner),
+            _ => None,
+        }
+    }
     pub fn version(&self) -> Version {
         match self {
             Self::Phase0(_) => Version::Phase0,
@@ -1715,6 +2690,7 @@ impl<
             Self::Bellatrix(_) => Version::Bellatrix,
             Self::Capella(_) => Version::Capella,
             Self::Deneb(_) => Version::Deneb,
+            Self::Electra(_) => Version::Electra,
         }
     }
     pub fn randao_reveal(&self) -> &BlsSignature {
@@ -1724,6 +2700,7 @@ impl<
             Self::Bellatrix(inner) => &inner.randao_reveal,
             Self::Capella(inner) => &inner.randao_reveal,
             Self::Deneb(inner) => &inner.randao_reveal,
+            Self::Electra(inner) => &inner.randao_reveal,
         }
     }
     pub fn randao_reveal_mut(&mut self) -> &mut BlsSignature {
@@ -1733,6 +2710,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.randao_reveal,
             Self::Capella(inner) => &mut inner.randao_reveal,
             Self::Deneb(inner) => &mut inner.randao_reveal,
+            Self::Electra(inner) => &mut inner.randao_reveal,
         }
     }
     pub fn eth1_data(&self) -> &Eth1Data {
@@ -1742,6 +2720,7 @@ impl<
             Self::Bellatrix(inner) => &inner.eth1_data,
             Self::Capella(inner) => &inner.eth1_data,
             Self::Deneb(inner) => &inner.eth1_data,
+            Self::Electra(inner) => &inner.eth1_data,
         }
     }
     pub fn eth1_data_mut(&mut self) -> &mut Eth1Data {
@@ -1751,6 +2730,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.eth1_data,
             Self::Capella(inner) => &mut inner.eth1_data,
             Self::Deneb(inner) => &mut inner.eth1_data,
+            Self::Electra(inner) => &mut inner.eth1_data,
         }
     }
     pub fn graffiti(&self) -> &Bytes32 {
@@ -1760,6 +2740,7 @@ impl<
             Self::Bellatrix(inner) => &inner.graffiti,
             Self::Capella(inner) => &inner.graffiti,
             Self::Deneb(inner) => &inner.graffiti,
+            Self::Electra(inner) => &inner.graffiti,
         }
     }
     pub fn graffiti_mut(&mut self) -> &mut Bytes32 {
@@ -1769,6 +2750,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.graffiti,
             Self::Capella(inner) => &mut inner.graffiti,
             Self::Deneb(inner) => &mut inner.graffiti,
+            Self::Electra(inner) => &mut inner.graffiti,
         }
     }
     pub fn proposer_slashings(&self) -> &List<ProposerSlashing, MAX_PROPOSER_SLASHINGS> {
@@ -1778,6 +2760,7 @@ impl<
             Self::Bellatrix(inner) => &inner.proposer_slashings,
             Self::Capella(inner) => &inner.proposer_slashings,
             Self::Deneb(inner) => &inner.proposer_slashings,
+            Self::Electra(inner) => &inner.proposer_slashings,
         }
     }
     pub fn proposer_slashings_mut(
@@ -1789,50 +2772,56 @@ impl<
             Self::Bellatrix(inner) => &mut inner.proposer_slashings,
             Self::Capella(inner) => &mut inner.proposer_slashings,
             Self::Deneb(inner) => &mut inner.proposer_slashings,
+            Self::Electra(inner) => &mut inner.proposer_slashings,
         }
     }
     pub fn attester_slashings(
         &self,
-    ) -> &List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS> {
+    ) -> Option<&List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS>> {
         match self {
-            Self::Phase0(inner) => &inner.attester_slashings,
-            Self::Altair(inner) => &inner.attester_slashings,
-            Self::Bellatrix(inner) => &inner.attester_slashings,
-            Self::Capella(inner) => &inner.attester_slashings,
-            Self::Deneb(inner) => &inner.attester_slashings,
+            Self::Phase0(inner) => Some(&inner.attester_slashings),
+            Self::Altair(inner) => Some(&inner.attester_slashings),
+            Self::Bellatrix(inner) => Some(&inner.attester_slashings),
+            Self::Capella(inner) => Some(&inner.attester_slashings),
+            Self::Deneb(inner) => Some(&inner.attester_slashings),
+            Self::Electra(_) => None,
         }
     }
     pub fn attester_slashings_mut(
         &mut self,
-    ) -> &mut List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS> {
+    ) -> Option<&mut List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS>>
+    {
         match self {
-            Self::Phase0(inner) => &mut inner.attester_slashings,
-            Self::Altair(inner) => &mut inner.attester_slashings,
-            Self::Bellatrix(inner) => &mut inner.attester_slashings,
-            Self::Capella(inner) => &mut inner.attester_slashings,
-            Self::Deneb(inner) => &mut inner.attester_slashings,
+            Self::Phase0(inner) => Some(&mut inner.attester_slashings),
+            Self::Altair(inner) => Some(&mut inner.attester_slashings),
+            Self::Bellatrix(inner) => Some(&mut inner.attester_slashings),
+            Self::Capella(inner) => Some(&mut inner.attester_slashings),
+            Self::Deneb(inner) => Some(&mut inner.attester_slashings),
+            Self::Electra(_) => None,
         }
     }
     pub fn attestations(
         &self,
-    ) -> &List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS> {
+    ) -> Option<&List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS>> {
         match self {
-            Self::Phase0(inner) => &inner.attestations,
-            Self::Altair(inner) => &inner.attestations,
-            Self::Bellatrix(inner) => &inner.attestations,
-            Self::Capella(inner) => &inner.attestations,
-            Self::Deneb(inner) => &inner.attestations,
+            Self::Phase0(inner) => Some(&inner.attestations),
+            Self::Altair(inner) => Some(&inner.attestations),
+            Self::Bellatrix(inner) => Some(&inner.attestations),
+            Self::Capella(inner) => Some(&inner.attestations),
+            Self::Deneb(inner) => Some(&inner.attestations),
+            Self::Electra(_) => None,
         }
     }
     pub fn attestations_mut(
         &mut self,
-    ) -> &mut List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS> {
+    ) -> Option<&mut List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS>> {
         match self {
-            Self::Phase0(inner) => &mut inner.attestations,
-            Self::Altair(inner) => &mut inner.attestations,
-            Self::Bellatrix(inner) => &mut inner.attestations,
-            Self::Capella(inner) => &mut inner.attestations,
-            Self::Deneb(inner) => &mut inner.attestations,
+            Self::Phase0(inner) => Some(&mut inner.attestations),
+            Self::Altair(inner) => Some(&mut inner.attestations),
+            Self::Bellatrix(inner) => Some(&mut inner.attestations),
+            Self::Capella(inner) => Some(&mut inner.attestations),
+            Self::Deneb(inner) => Some(&mut inner.attestations),
+            Self::Electra(_) => None,
         }
     }
     pub fn deposits(&self) -> &List<Deposit, MAX_DEPOSITS> {
@@ -1842,6 +2831,7 @@ impl<
             Self::Bellatrix(inner) => &inner.deposits,
             Self::Capella(inner) => &inner.deposits,
             Self::Deneb(inner) => &inner.deposits,
+            Self::Electra(inner) => &inner.deposits,
         }
     }
     pub fn deposits_mut(&mut self) -> &mut List<Deposit, MAX_DEPOSITS> {
@@ -1851,6 +2841,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.deposits,
             Self::Capella(inner) => &mut inner.deposits,
             Self::Deneb(inner) => &mut inner.deposits,
+            Self::Electra(inner) => &mut inner.deposits,
         }
     }
     pub fn voluntary_exits(&self) -> &List<SignedVoluntaryExit, MAX_VOLUNTARY_EXITS> {
@@ -1860,6 +2851,7 @@ impl<
             Self::Bellatrix(inner) => &inner.voluntary_exits,
             Self::Capella(inner) => &inner.voluntary_exits,
             Self::Deneb(inner) => &inner.voluntary_exits,
+            Self::Electra(inner) => &inner.voluntary_exits,
         }
     }
     pub fn voluntary_exits_mut(&mut self) -> &mut List<SignedVoluntaryExit, MAX_VOLUNTARY_EXITS> {
@@ -1869,6 +2861,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.voluntary_exits,
             Self::Capella(inner) => &mut inner.voluntary_exits,
             Self::Deneb(inner) => &mut inner.voluntary_exits,
+            Self::Electra(inner) => &mut inner.voluntary_exits,
         }
     }
     pub fn sync_aggregate(&self) -> Option<&SyncAggregate<SYNC_COMMITTEE_SIZE>> {
@@ -1878,6 +2871,7 @@ impl<
             Self::Bellatrix(inner) => Some(&inner.sync_aggregate),
             Self::Capella(inner) => Some(&inner.sync_aggregate),
             Self::Deneb(inner) => Some(&inner.sync_aggregate),
+            Self::Electra(inner) => Some(&inner.sync_aggregate),
         }
     }
     pub fn sync_aggregate_mut(&mut self) -> Option<&mut SyncAggregate<SYNC_COMMITTEE_SIZE>> {
@@ -1887,6 +2881,7 @@ impl<
             Self::Bellatrix(inner) => Some(&mut inner.sync_aggregate),
             Self::Capella(inner) => Some(&mut inner.sync_aggregate),
             Self::Deneb(inner) => Some(&mut inner.sync_aggregate),
+            Self::Electra(inner) => Some(&mut inner.sync_aggregate),
         }
     }
     pub fn execution_payload(
@@ -1906,6 +2901,7 @@ impl<
             Self::Bellatrix(inner) => Some(From::from(&inner.execution_payload)),
             Self::Capella(inner) => Some(From::from(&inner.execution_payload)),
             Self::Deneb(inner) => Some(From::from(&inner.execution_payload)),
+            Self::Electra(inner) => Some(From::from(&inner.execution_payload)),
         }
     }
     pub fn execution_payload_mut(
@@ -1925,6 +2921,7 @@ impl<
             Self::Bellatrix(inner) => Some(From::from(&mut inner.execution_payload)),
             Self::Capella(inner) => Some(From::from(&mut inner.execution_payload)),
             Self::Deneb(inner) => Some(From::from(&mut inner.execution_payload)),
+            Self::Electra(inner) => Some(From::from(&mut inner.execution_payload)),
         }
     }
     pub fn bls_to_execution_changes(
@@ -1936,6 +2933,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(inner) => Some(&inner.bls_to_execution_changes),
             Self::Deneb(inner) => Some(&inner.bls_to_execution_changes),
+            Self::Electra(inner) => Some(&inner.bls_to_execution_changes),
         }
     }
     pub fn bls_to_execution_changes_mut(
@@ -1947,6 +2945,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(inner) => Some(&mut inner.bls_to_execution_changes),
             Self::Deneb(inner) => Some(&mut inner.bls_to_execution_changes),
+            Self::Electra(inner) => Some(&mut inner.bls_to_execution_changes),
         }
     }
     pub fn blob_kzg_commitments(
@@ -1958,6 +2957,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(_) => None,
             Self::Deneb(inner) => Some(&inner.blob_kzg_commitments),
+            Self::Electra(inner) => Some(&inner.blob_kzg_commitments),
         }
     }
     pub fn blob_kzg_commitments_mut(
@@ -1969,6 +2969,43 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(_) => None,
             Self::Deneb(inner) => Some(&mut inner.blob_kzg_commitments),
+            Self::Electra(inner) => Some(&mut inner.blob_kzg_commitments),
+        }
+    }
+    pub fn execution_requests(
+        &self,
+    ) -> Option<
+        &ExecutionRequests<
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.execution_requests),
+        }
+    }
+    pub fn execution_requests_mut(
+        &mut self,
+    ) -> Option<
+        &mut ExecutionRequests<
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.execution_requests),
         }
     }
 }
@@ -1988,6 +3025,13 @@ impl<
         const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     From<
         &'a mut phase0::BeaconBlockBody<
@@ -2015,6 +3059,13 @@ impl<
         MAX_WITHDRAWALS_PER_PAYLOAD,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     fn from(
@@ -2046,6 +3097,13 @@ impl<
         const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     From<
         &'a mut altair::BeaconBlockBody<
@@ -2074,6 +3132,13 @@ impl<
         MAX_WITHDRAWALS_PER_PAYLOAD,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     fn from(
@@ -2106,6 +3171,13 @@ impl<
         const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     From<
         &'a mut bellatrix::BeaconBlockBody<
@@ -2138,6 +3210,13 @@ impl<
         MAX_WITHDRAWALS_PER_PAYLOAD,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     fn from(
@@ -2174,6 +3253,13 @@ impl<
         const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     From<
         &'a mut capella::BeaconBlockBody<
@@ -2208,6 +3294,13 @@ impl<
         MAX_WITHDRAWALS_PER_PAYLOAD,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     fn from(
@@ -2246,6 +3339,13 @@ impl<
         const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     From<
         &'a mut deneb::BeaconBlockBody<
@@ -2281,6 +3381,13 @@ impl<
         MAX_WITHDRAWALS_PER_PAYLOAD,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     fn from(
@@ -2304,3 +3411,99 @@ impl<
         Self::Deneb(value)
     }
 }
+impl<
+        'a,
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+    >
+    From<
+        &'a mut electra::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    >
+    for BeaconBlockBodyRefMut<
+        'a,
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >
+{
+    fn from(
+        value: &'a mut electra::BeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    ) -> Self {
+        Self::Electra(value)
+    }
+}
@@ -4,6 +4,10 @@ use crate::{
     bellatrix::beacon_state as bellatrix,
     capella::beacon_state::{self as capella, HistoricalSummary},
     deneb::beacon_state as deneb,
+    electra::beacon_state::{
+        self as electra, PendingConsolidation, PendingDeposit, PendingPartialWithdrawal,
+    },
+    error::WrongFork,
     phase0::{
         beacon_block::BeaconBlockHeader,
         beacon_state::{self as phase0, Fork},
@@ -11,7 +15,9 @@ use crate::{
         validator::Validator,
         JUSTIFICATION_BITS_LENGTH,
     },
-    primitives::{Bytes32, Gwei, ParticipationFlags, Root, Slot, ValidatorIndex, WithdrawalIndex},
+    primitives::{
+        Bytes32, Epoch, Gwei, ParticipationFlags, Root, Slot, ValidatorIndex, WithdrawalIndex,
+    },
     ssz::prelude::*,
     types::execution_payload_header::{ExecutionPayloadHeaderRef, ExecutionPayloadHeaderRefMut},
     Fork as Version,
@@ -31,6 +37,9 @@ pub enum BeaconState<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
 > {
     Phase0(
         phase0::BeaconState<
@@ -98,6 +107,23 @@ pub enum BeaconState<
             MAX_EXTRA_DATA_BYTES,
         >,
     ),
+    Electra(
+        electra::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    ),
 }
 impl<
         const SLOTS_PER_HISTORICAL_ROOT: usize,
@@ -111,6 +137,9 @@ impl<
         const SYNC_COMMITTEE_SIZE: usize,
         const BYTES_PER_LOGS_BLOOM: usize,
         const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
     >
     BeaconState<
         SLOTS_PER_HISTORICAL_ROOT,
@@ -124,6 +153,9 @@ impl<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
     >
 {
     pub fn phase0(
@@ -164,6 +196,66 @@ impl<
             _ => None,
         }
     }
+    pub fn as_phase0(
+        &self,
+    ) -> Result<
+        &phase0::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Phase0(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Phase0, found: other.version() }),
+        }
+    }
+    pub fn as_phase0_mut(
+        &mut self,
+    ) -> Result<
+        &mut phase0::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Phase0(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Phase0, found: other.version() }),
+        }
+    }
+    pub fn into_phase0(
+        self,
+    ) -> Result<
+        phase0::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Phase0(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Phase0, found: other.version() }),
+        }
+    }
     pub fn altair(
         &self,
     ) -> Option<
@@ -202,6 +294,66 @@ impl<
             _ => None,
         }
     }
+    pub fn as_altair(
+        &self,
+    ) -> Result<
+        &altair::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Altair(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Altair, found: other.version() }),
+        }
+    }
+    pub fn as_altair_mut(
+        &mut self,
+    ) -> Result<
+        &mut altair::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Altair(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Altair, found: other.version() }),
+        }
+    }
+    pub fn into_altair(
+        self,
+    ) -> Result<
+        altair::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Altair(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Altair, found: other.version() }),
+        }
+    }
     pub fn bellatrix(
         &self,
     ) -> Option<
@@ -244,6 +396,72 @@ impl<
             _ => None,
         }
     }
+    pub fn as_bellatrix(
+        &self,
+    ) -> Result<
+        &bellatrix::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Bellatrix(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Bellatrix, found: other.version() }),
+        }
+    }
+    pub fn as_bellatrix_mut(
+        &mut self,
+    ) -> Result<
+        &mut bellatrix::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Bellatrix(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Bellatrix, found: other.version() }),
+        }
+    }
+    pub fn into_bellatrix(
+        self,
+    ) -> Result<
+        bellatrix::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Bellatrix(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Bellatrix, found: other.version() }),
+        }
+    }
     pub fn capella(
         &self,
     ) -> Option<
@@ -286,6 +504,72 @@ impl<
             _ => None,
         }
     }
+    pub fn as_capella(
+        &self,
+    ) -> Result<
+        &capella::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Capella(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Capella, found: other.version() }),
+        }
+    }
+    pub fn as_capella_mut(
+        &mut self,
+    ) -> Result<
+        &mut capella::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Capella(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Capella, found: other.version() }),
+        }
+    }
+    pub fn into_capella(
+        self,
+    ) -> Result<
+        capella::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Capella(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Capella, found: other.version() }),
+        }
+    }
     pub fn deneb(
         &self,
     ) -> Option<
@@ -328,6 +612,195 @@ impl<
             _ => None,
         }
     }
+    pub fn as_deneb(
+        &self,
+    ) -> Result<
+        &deneb::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Deneb(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Deneb, found: other.version() }),
+        }
+    }
+    pub fn as_deneb_mut(
+        &mut self,
+    ) -> Result<
+        &mut deneb::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Deneb(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Deneb, found: other.version() }),
+        }
+    }
+    pub fn into_deneb(
+        self,
+    ) -> Result<
+        deneb::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Deneb(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Deneb, found: other.version() }),
+        }
+    }
+    pub fn electra(
+        &self,
+    ) -> Option<
+        &electra::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    > {
+        match self {
+            Self::Electra(inner) => Some(inner),
+            _ => None,
+        }
+    }
+    pub fn electra_mut(
+        &mut self,
+    ) -> Option<
+        &mut electra::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    > {
+        match self {
+            Self::Electra(inner) => Some(inner),
+            _ => None,
+        }
+    }
+    pub fn as_electra(
+        &self,
+    ) -> Result<
+        &electra::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Electra(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Electra, found: other.version() }),
+        }
+    }
+    pub fn as_electra_mut(
+        &mut self,
+    ) -> Result<
+        &mut electra::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Electra(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Electra, found: other.version() }),
+        }
+    }
+    pub fn into_electra(
+        self,
+    ) -> Result<
+        electra::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Electra(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Electra, found: other.version() }),
+        }
+    }
     pub fn version(&self) -> Version {
         match self {
             Self::Phase0(_) => Version::Phase0,
@@ -335,6 +808,7 @@ impl<
             Self::Bellatrix(_) => Version::Bellatrix,
             Self::Capella(_) => Version::Capella,
             Self::Deneb(_) => Version::Deneb,
+            Self::Electra(_) => Version::Electra,
         }
     }
     pub fn genesis_time(&self) -> u64 {
@@ -344,6 +818,7 @@ impl<
             Self::Bellatrix(inner) => inner.genesis_time,
             Self::Capella(inner) => inner.genesis_time,
             Self::Deneb(inner) => inner.genesis_time,
+            Self::Electra(inner) => inner.genesis_time,
         }
     }
     pub fn genesis_time_mut(&mut self) -> &mut u64 {
@@ -353,6 +828,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.genesis_time,
             Self::Capella(inner) => &mut inner.genesis_time,
             Self::Deneb(inner) => &mut inner.genesis_time,
+            Self::Electra(inner) => &mut inner.genesis_time,
         }
     }
     pub fn genesis_validators_root(&self) -> Root {
@@ -362,6 +838,7 @@ impl<
             Self::Bellatrix(inner) => inner.genesis_validators_root,
             Self::Capella(inner) => inner.genesis_validators_root,
             Self::Deneb(inner) => inner.genesis_validators_root,
+            Self::Electra(inner) => inner.genesis_validators_root,
         }
     }
     pub fn genesis_validators_root_mut(&mut self) -> &mut Root {
@@ -371,6 +848,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.genesis_validators_root,
             Self::Capella(inner) => &mut inner.genesis_validators_root,
             Self::Deneb(inner) => &mut inner.genesis_validators_root,
+            Self::Electra(inner) => &mut inner.genesis_validators_root,
         }
     }
     pub fn slot(&self) -> Slot {
@@ -380,6 +858,7 @@ impl<
             Self::Bellatrix(inner) => inner.slot,
             Self::Capella(inner) => inner.slot,
             Self::Deneb(inner) => inner.slot,
+            Self::Electra(inner) => inner.slot,
         }
     }
     pub fn slot_mut(&mut self) -> &mut Slot {
@@ -389,6 +868,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.slot,
             Self::Capella(inner) => &mut inner.slot,
             Self::Deneb(inner) => &mut inner.slot,
+            Self::Electra(inner) => &mut inner.slot,
         }
     }
     pub fn fork(&self) -> &Fork {
@@ -398,6 +878,7 @@ impl<
             Self::Bellatrix(inner) => &inner.fork,
             Self::Capella(inner) => &inner.fork,
             Self::Deneb(inner) => &inner.fork,
+            Self::Electra(inner) => &inner.fork,
         }
     }
     pub fn fork_mut(&mut self) -> &mut Fork {
@@ -407,6 +888,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.fork,
             Self::Capella(inner) => &mut inner.fork,
             Self::Deneb(inner) => &mut inner.fork,
+            Self::Electra(inner) => &mut inner.fork,
         }
     }
     pub fn latest_block_header(&self) -> &BeaconBlockHeader {
@@ -416,6 +898,7 @@ impl<
             Self::Bellatrix(inner) => &inner.latest_block_header,
             Self::Capella(inner) => &inner.latest_block_header,
             Self::Deneb(inner) => &inner.latest_block_header,
+            Self::Electra(inner) => &inner.latest_block_header,
         }
     }
     pub fn latest_block_header_mut(&mut self) -> &mut BeaconBlockHeader {
@@ -425,6 +908,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.latest_block_header,
             Self::Capella(inner) => &mut inner.latest_block_header,
             Self::Deneb(inner) => &mut inner.latest_block_header,
+            Self::Electra(inner) => &mut inner.latest_block_header,
         }
     }
     pub fn block_roots(&self) -> &Vector<Root, SLOTS_PER_HISTORICAL_ROOT> {
@@ -434,6 +918,7 @@ impl<
             Self::Bellatrix(inner) => &inner.block_roots,
             Self::Capella(inner) => &inner.block_roots,
             Self::Deneb(inner) => &inner.block_roots,
+            Self::Electra(inner) => &inner.block_roots,
         }
     }
     pub fn block_roots_mut(&mut self) -> &mut Vector<Root, SLOTS_PER_HISTORICAL_ROOT> {
@@ -443,6 +928,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.block_roots,
             Self::Capella(inner) => &mut inner.block_roots,
             Self::Deneb(inner) => &mut inner.block_roots,
+            Self::Electra(inner) => &mut inner.block_roots,
         }
     }
     pub fn state_roots(&self) -> &Vector<Root, SLOTS_PER_HISTORICAL_ROOT> {
@@ -452,6 +938,7 @@ impl<
             Self::Bellatrix(inner) => &inner.state_roots,
             Self::Capella(inner) => &inner.state_roots,
             Self::Deneb(inner) => &inner.state_roots,
+            Self::Electra(inner) => &inner.state_roots,
         }
     }
     pub fn state_roots_mut(&mut self) -> &mut Vector<Root, SLOTS_PER_HISTORICAL_ROOT> {
@@ -461,6 +948,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.state_roots,
             Self::Capella(inner) => &mut inner.state_roots,
             Self::Deneb(inner) => &mut inner.state_roots,
+            Self::Electra(inner) => &mut inner.state_roots,
         }
     }
     pub fn historical_roots(&self) -> &List<Root, HISTORICAL_ROOTS_LIMIT> {
@@ -470,6 +958,7 @@ impl<
             Self::Bellatrix(inner) => &inner.historical_roots,
             Self::Capella(inner) => &inner.historical_roots,
             Self::Deneb(inner) => &inner.historical_roots,
+            Self::Electra(inner) => &inner.historical_roots,
         }
     }
     pub fn historical_roots_mut(&mut self) -> &mut List<Root, HISTORICAL_ROOTS_LIMIT> {
@@ -479,6 +968,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.historical_roots,
             Self::Capella(inner) => &mut inner.historical_roots,
             Self::Deneb(inner) => &mut inner.historical_roots,
+            Self::Electra(inner) => &mut inner.historical_roots,
         }
     }
     pub fn eth1_data(&self) -> &Eth1Data {
@@ -488,6 +978,7 @@ impl<
             Self::Bellatrix(inner) => &inner.eth1_data,
             Self::Capella(inner) => &inner.eth1_data,
             Self::Deneb(inner) => &inner.eth1_data,
+            Self::Electra(inner) => &inner.eth1_data,
         }
     }
     pub fn eth1_data_mut(&mut self) -> &mut Eth1Data {
@@ -497,6 +988,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.eth1_data,
             Self::Capella(inner) => &mut inner.eth1_data,
             Self::Deneb(inner) => &mut inner.eth1_data,
+            Self::Electra(inner) => &mut inner.eth1_data,
         }
     }
     pub fn eth1_data_votes(&self) -> &List<Eth1Data, ETH1_DATA_VOTES_BOUND> {
@@ -506,6 +998,7 @@ impl<
             Self::Bellatrix(inner) => &inner.eth1_data_votes,
             Self::Capella(inner) => &inner.eth1_data_votes,
             Self::Deneb(inner) => &inner.eth1_data_votes,
+            Self::Electra(inner) => &inner.eth1_data_votes,
         }
     }
     pub fn eth1_data_votes_mut(&mut self) -> &mut List<Eth1Data, ETH1_DATA_VOTES_BOUND> {
@@ -515,6 +1008,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.eth1_data_votes,
             Self::Capella(inner) => &mut inner.eth1_data_votes,
             Self::Deneb(inner) => &mut inner.eth1_data_votes,
+            Self::Electra(inner) => &mut inner.eth1_data_votes,
         }
     }
     pub fn eth1_deposit_index(&self) -> u64 {
@@ -524,6 +1018,7 @@ impl<
             Self::Bellatrix(inner) => inner.eth1_deposit_index,
             Self::Capella(inner) => inner.eth1_deposit_index,
             Self::Deneb(inner) => inner.eth1_deposit_index,
+            Self::Electra(inner) => inner.eth1_deposit_index,
         }
     }
     pub fn eth1_deposit_index_mut(&mut self) -> &mut u64 {
@@ -533,6 +1028,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.eth1_deposit_index,
             Self::Capella(inner) => &mut inner.eth1_deposit_index,
             Self::Deneb(inner) => &mut inner.eth1_deposit_index,
+            Self::Electra(inner) => &mut inner.eth1_deposit_index,
         }
     }
     pub fn validators(&self) -> &List<Validator, VALIDATOR_REGISTRY_LIMIT> {
@@ -542,6 +1038,7 @@ impl<
             Self::Bellatrix(inner) => &inner.validators,
             Self::Capella(inner) => &inner.validators,
             Self::Deneb(inner) => &inner.validators,
+            Self::Electra(inner) => &inner.validators,
         }
     }
     pub fn validators_mut(&mut self) -> &mut List<Validator, VALIDATOR_REGISTRY_LIMIT> {
@@ -551,6 +1048,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.validators,
             Self::Capella(inner) => &mut inner.validators,
             Self::Deneb(inner) => &mut inner.validators,
+            Self::Electra(inner) => &mut inner.validators,
         }
     }
     pub fn balances(&self) -> &List<Gwei, VALIDATOR_REGISTRY_LIMIT> {
@@ -560,6 +1058,7 @@ impl<
             Self::Bellatrix(inner) => &inner.balances,
             Self::Capella(inner) => &inner.balances,
             Self::Deneb(inner) => &inner.balances,
+            Self::Electra(inner) => &inner.balances,
         }
     }
     pub fn balances_mut(&mut self) -> &mut List<Gwei, VALIDATOR_REGISTRY_LIMIT> {
@@ -569,6 +1068,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.balances,
             Self::Capella(inner) => &mut inner.balances,
             Self::Deneb(inner) => &mut inner.balances,
+            Self::Electra(inner) => &mut inner.balances,
         }
     }
     pub fn randao_mixes(&self) -> &Vector<Bytes32, EPOCHS_PER_HISTORICAL_VECTOR> {
@@ -578,6 +1078,7 @@ impl<
             Self::Bellatrix(inner) => &inner.randao_mixes,
             Self::Capella(inner) => &inner.randao_mixes,
             Self::Deneb(inner) => &inner.randao_mixes,
+            Self::Electra(inner) => &inner.randao_mixes,
         }
     }
     pub fn randao_mixes_mut(&mut self) -> &mut Vector<Bytes32, EPOCHS_PER_HISTORICAL_VECTOR> {
@@ -587,6 +1088,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.randao_mixes,
             Self::Capella(inner) => &mut inner.randao_mixes,
             Self::Deneb(inner) => &mut inner.randao_mixes,
+            Self::Electra(inner) => &mut inner.randao_mixes,
         }
     }
     pub fn slashings(&self) -> &Vector<Gwei, EPOCHS_PER_SLASHINGS_VECTOR> {
@@ -596,6 +1098,7 @@ impl<
             Self::Bellatrix(inner) => &inner.slashings,
             Self::Capella(inner) => &inner.slashings,
             Self::Deneb(inner) => &inner.slashings,
+            Self::Electra(inner) => &inner.slashings,
         }
     }
     pub fn slashings_mut(&mut self) -> &mut Vector<Gwei, EPOCHS_PER_SLASHINGS_VECTOR> {
@@ -605,6 +1108,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.slashings,
             Self::Capella(inner) => &mut inner.slashings,
             Self::Deneb(inner) => &mut inner.slashings,
+            Self::Electra(inner) => &mut inner.slashings,
         }
     }
     pub fn previous_epoch_attestations(
@@ -617,6 +1121,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(_) => None,
             Self::Deneb(_) => None,
+            Self::Electra(_) => None,
         }
     }
     pub fn previous_epoch_attestations_mut(
@@ -630,6 +1135,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(_) => None,
             Self::Deneb(_) => None,
+            Self::Electra(_) => None,
         }
     }
     pub fn current_epoch_attestations(
@@ -642,6 +1148,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(_) => None,
             Self::Deneb(_) => None,
+            Self::Electra(_) => None,
         }
     }
     pub fn current_epoch_attestations_mut(
@@ -655,6 +1162,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(_) => None,
             Self::Deneb(_) => None,
+            Self::Electra(_) => None,
         }
     }
     pub fn justification_bits(&self) -> &Bitvector<JUSTIFICATION_BITS_LENGTH> {
@@ -664,6 +1172,7 @@ impl<
             Self::Bellatrix(inner) => &inner.justification_bits,
             Self::Capella(inner) => &inner.justification_bits,
             Self::Deneb(inner) => &inner.justification_bits,
+            Self::Electra(inner) => &inner.justification_bits,
         }
     }
     pub fn justification_bits_mut(&mut self) -> &mut Bitvector<JUSTIFICATION_BITS_LENGTH> {
@@ -673,6 +1182,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.justification_bits,
             Self::Capella(inner) => &mut inner.justification_bits,
             Self::Deneb(inner) => &mut inner.justification_bits,
+            Self::Electra(inner) => &mut inner.justification_bits,
         }
     }
     pub fn previous_justified_checkpoint(&self) -> &Checkpoint {
@@ -682,6 +1192,7 @@ impl<
             Self::Bellatrix(inner) => &inner.previous_justified_checkpoint,
             Self::Capella(inner) => &inner.previous_justified_checkpoint,
             Self::Deneb(inner) => &inner.previous_justified_checkpoint,
+            Self::Electra(inner) => &inner.previous_justified_checkpoint,
         }
     }
     pub fn previous_justified_checkpoint_mut(&mut self) -> &mut Checkpoint {
@@ -691,6 +1202,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.previous_justified_checkpoint,
             Self::Capella(inner) => &mut inner.previous_justified_checkpoint,
             Self::Deneb(inner) => &mut inner.previous_justified_checkpoint,
+            Self::Electra(inner) => &mut inner.previous_justified_checkpoint,
         }
     }
     pub fn current_justified_checkpoint(&self) -> &Checkpoint {
@@ -700,6 +1212,7 @@ impl<
             Self::Bellatrix(inner) => &inner.current_justified_checkpoint,
             Self::Capella(inner) => &inner.current_justified_checkpoint,
             Self::Deneb(inner) => &inner.current_justified_checkpoint,
+            Self::Electra(inner) => &inner.current_justified_checkpoint,
         }
     }
     pub fn current_justified_checkpoint_mut(&mut self) -> &mut Checkpoint {
@@ -709,6 +1222,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.current_justified_checkpoint,
             Self::Capella(inner) => &mut inner.current_justified_checkpoint,
             Self::Deneb(inner) => &mut inner.current_justified_checkpoint,
+            Self::Electra(inner) => &mut inner.current_justified_checkpoint,
         }
     }
     pub fn finalized_checkpoint(&self) -> &Checkpoint {
@@ -718,6 +1232,7 @@ impl<
             Self::Bellatrix(inner) => &inner.finalized_checkpoint,
             Self::Capella(inner) => &inner.finalized_checkpoint,
             Self::Deneb(inner) => &inner.finalized_checkpoint,
+            Self::Electra(inner) => &inner.finalized_checkpoint,
         }
     }
     pub fn finalized_checkpoint_mut(&mut self) -> &mut Checkpoint {
@@ -727,6 +1242,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.finalized_checkpoint,
             Self::Capella(inner) => &mut inner.finalized_checkpoint,
             Self::Deneb(inner) => &mut inner.finalized_checkpoint,
+            Self::Electra(inner) => &mut inner.finalized_checkpoint,
         }
     }
     pub fn previous_epoch_participation(
@@ -738,6 +1254,7 @@ impl<
             Self::Bellatrix(inner) => Some(&inner.previous_epoch_participation),
             Self::Capella(inner) => Some(&inner.previous_epoch_participation),
             Self::Deneb(inner) => Some(&inner.previous_epoch_participation),
+            Self::Electra(inner) => Some(&inner.previous_epoch_participation),
         }
     }
     pub fn previous_epoch_participation_mut(
@@ -749,6 +1266,7 @@ impl<
             Self::Bellatrix(inner) => Some(&mut inner.previous_epoch_participation),
             Self::Capella(inner) => Some(&mut inner.previous_epoch_participation),
             Self::Deneb(inner) => Some(&mut inner.previous_epoch_participation),
+            Self::Electra(inner) => Some(&mut inner.previous_epoch_participation),
         }
     }
     pub fn current_epoch_participation(
@@ -760,6 +1278,7 @@ impl<
             Self::Bellatrix(inner) => Some(&inner.current_epoch_participation),
             Self::Capella(inner) => Some(&inner.current_epoch_participation),
             Self::Deneb(inner) => Some(&inner.current_epoch_participation),
+            Self::Electra(inner) => Some(&inner.current_epoch_participation),
         }
     }
     pub fn current_epoch_participation_mut(
@@ -771,6 +1290,7 @@ impl<
             Self::Bellatrix(inner) => Some(&mut inner.current_epoch_participation),
             Self::Capella(inner) => Some(&mut inner.current_epoch_participation),
             Self::Deneb(inner) => Some(&mut inner.current_epoch_participation),
+            Self::Electra(inner) => Some(&mut inner.current_epoch_participation),
         }
     }
     pub fn inactivity_scores(&self) -> Option<&List<u64, VALIDATOR_REGISTRY_LIMIT>> {
@@ -780,6 +1300,7 @@ impl<
             Self::Bellatrix(inner) => Some(&inner.inactivity_scores),
             Self::Capella(inner) => Some(&inner.inactivity_scores),
             Self::Deneb(inner) => Some(&inner.inactivity_scores),
+            Self::Electra(inner) => Some(&inner.inactivity_scores),
         }
     }
     pub fn inactivity_scores_mut(&mut self) -> Option<&mut List<u64, VALIDATOR_REGISTRY_LIMIT>> {
@@ -789,122 +1310,325 @@ impl<
             Self::Bellatrix(inner) => Some(&mut inner.inactivity_scores),
             Self::Capella(inner) => Some(&mut inner.inactivity_scores),
             Self::Deneb(inner) => Some(&mut inner.inactivity_scores),
+            Self::Electra(inner) => Some(&mut inner.inactivity_scores),
+        }
+    }
+    pub fn current_sync_committee(&self) -> Option<&SyncCommittee<SYNC_COMMITTEE_SIZE>> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(inner) => Some(&inner.current_sync_committee),
+            Self::Bellatrix(inner) => Some(&inner.current_sync_committee),
+            Self::Capella(inner) => Some(&inner.current_sync_committee),
+            Self::Deneb(inner) => Some(&inner.current_sync_committee),
+            Self::Electra(inner) => Some(&inner.current_sync_committee),
+        }
+    }
+    pub fn current_sync_committee_mut(
+        &mut self,
+    ) -> Option<&mut SyncCommittee<SYNC_COMMITTEE_SIZE>> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(inner) => Some(&mut inner.current_sync_committee),
+            Self::Bellatrix(inner) => Some(&mut inner.current_sync_committee),
+            Self::Capella(inner) => Some(&mut inner.current_sync_committee),
+            Self::Deneb(inner) => Some(&mut inner.current_sync_committee),
+            Self::Electra(inner) => Some(&mut inner.current_sync_committee),
+        }
+    }
+    pub fn next_sync_committee(&self) -> Option<&SyncCommittee<SYNC_COMMITTEE_SIZE>> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(inner) => Some(&inner.next_sync_committee),
+            Self::Bellatrix(inner) => Some(&inner.next_sync_committee),
+            Self::Capella(inner) => Some(&inner.next_sync_committee),
+            Self::Deneb(inner) => Some(&inner.next_sync_committee),
+            Self::Electra(inner) => Some(&inner.next_sync_committee),
+        }
+    }
+    pub fn next_sync_committee_mut(&mut self) -> Option<&mut SyncCommittee<SYNC_COMMITTEE_SIZE>> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(inner) => Some(&mut inner.next_sync_committee),
+            Self::Bellatrix(inner) => Some(&mut inner.next_sync_committee),
+            Self::Capella(inner) => Some(&mut inner.next_sync_committee),
+            Self::Deneb(inner) => Some(&mut inner.next_sync_committee),
+            Self::Electra(inner) => Some(&mut inner.next_sync_committee),
+        }
+    }
+    pub fn latest_execution_payload_header(
+        &self,
+    ) -> Option<ExecutionPayloadHeaderRef<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(inner) => Some(From::from(&inner.latest_execution_payload_header)),
+            Self::Capella(inner) => Some(From::from(&inner.latest_execution_payload_header)),
+            Self::Deneb(inner) => Some(From::from(&inner.latest_execution_payload_header)),
+            Self::Electra(inner) => Some(From::from(&inner.latest_execution_payload_header)),
+        }
+    }
+    pub fn latest_execution_payload_header_mut(
+        &mut self,
+    ) -> Option<ExecutionPayloadHeaderRefMut<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(inner) => Some(From::from(&mut inner.latest_execution_payload_header)),
+            Self::Capella(inner) => Some(From::from(&mut inner.latest_execution_payload_header)),
+            Self::Deneb(inner) => Some(From::from(&mut inner.latest_execution_payload_header)),
+            Self::Electra(inner) => Some(From::from(&mut inner.latest_execution_payload_header)),
+        }
+    }
+    pub fn next_withdrawal_index(&self) -> Option<WithdrawalIndex> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(inner) => Some(inner.next_withdrawal_index),
+            Self::Deneb(inner) => Some(inner.next_withdrawal_index),
+            Self::Electra(inner) => Some(inner.next_withdrawal_index),
+        }
+    }
+    pub fn next_withdrawal_index_mut(&mut self) -> Option<&mut WithdrawalIndex> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(inner) => Some(&mut inner.next_withdrawal_index),
+            Self::Deneb(inner) => Some(&mut inner.next_withdrawal_index),
+            Self::Electra(inner) => Some(&mut inner.next_withdrawal_index),
+        }
+    }
+    pub fn next_withdrawal_validator_index(&self) -> Option<ValidatorIndex> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(inner) => Some(inner.next_withdrawal_validator_index),
+            Self::Deneb(inner) => Some(inner.next_withdrawal_validator_index),
+            Self::Electra(inner) => Some(inner.next_withdrawal_validator_index),
+        }
+    }
+    pub fn next_withdrawal_validator_index_mut(&mut self) -> Option<&mut ValidatorIndex> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(inner) => Some(&mut inner.next_withdrawal_validator_index),
+            Self::Deneb(inner) => Some(&mut inner.next_withdrawal_validator_index),
+            Self::Electra(inner) => Some(&mut inner.next_withdrawal_validator_index),
+        }
+    }
+    pub fn historical_summaries(&self) -> Option<&List<HistoricalSummary, HISTORICAL_ROOTS_LIMIT>> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(inner) => Some(&inner.historical_summaries),
+            Self::Deneb(inner) => Some(&inner.historical_summaries),
+            Self::Electra(inner) => Some(&inner.historical_summaries),
+        }
+    }
+    pub fn historical_summaries_mut(
+        &mut self,
+    ) -> Option<&mut List<HistoricalSummary, HISTORICAL_ROOTS_LIMIT>> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(inner) => Some(&mut inner.historical_summaries),
+            Self::Deneb(inner) => Some(&mut inner.historical_summaries),
+            Self::Electra(inner) => Some(&mut inner.historical_summaries),
+        }
+    }
+    pub fn deposit_requests_start_index(&self) -> Option<u64> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(inner.deposit_requests_start_index),
+        }
+    }
+    pub fn deposit_requests_start_index_mut(&mut self) -> Option<&mut u64> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.deposit_requests_start_index),
+        }
+    }
+    pub fn deposit_balance_to_consume(&self) -> Option<&Gwei> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.deposit_balance_to_consume),
+        }
+    }
+    pub fn deposit_balance_to_consume_mut(&mut self) -> Option<&mut Gwei> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.deposit_balance_to_consume),
+        }
+    }
+    pub fn exit_balance_to_consume(&self) -> Option<&Gwei> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.exit_balance_to_consume),
+        }
+    }
+    pub fn exit_balance_to_consume_mut(&mut self) -> Option<&mut Gwei> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.exit_balance_to_consume),
         }
     }
-    pub fn current_sync_committee(&self) -> Option<&SyncCommittee<SYNC_COMMITTEE_SIZE>> {
+    pub fn earliest_exit_epoch(&self) -> Option<&Epoch> {
         match self {
             Self::Phase0(_) => None,
-            Self::Altair(inner) => Some(&inner.current_sync_committee),
-            Self::Bellatrix(inner) => Some(&inner.current_sync_committee),
-            Self::Capella(inner) => Some(&inner.current_sync_committee),
-            Self::Deneb(inner) => Some(&inner.current_sync_committee),
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.earliest_exit_epoch),
         }
     }
-    pub fn current_sync_committee_mut(
-        &mut self,
-    ) -> Option<&mut SyncCommittee<SYNC_COMMITTEE_SIZE>> {
+    pub fn earliest_exit_epoch_mut(&mut self) -> Option<&mut Epoch> {
         match self {
             Self::Phase0(_) => None,
-            Self::Altair(inner) => Some(&mut inner.current_sync_committee),
-            Self::Bellatrix(inner) => Some(&mut inner.current_sync_committee),
-            Self::Capella(inner) => Some(&mut inner.current_sync_committee),
-            Self::Deneb(inner) => Some(&mut inner.current_sync_committee),
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.earliest_exit_epoch),
         }
     }
-    pub fn next_sync_committee(&self) -> Option<&SyncCommittee<SYNC_COMMITTEE_SIZE>> {
+    pub fn consolidation_balance_to_consume(&self) -> Option<&Gwei> {
         match self {
             Self::Phase0(_) => None,
-            Self::Altair(inner) => Some(&inner.next_sync_committee),
-            Self::Bellatrix(inner) => Some(&inner.next_sync_committee),
-            Self::Capella(inner) => Some(&inner.next_sync_committee),
-            Self::Deneb(inner) => Some(&inner.next_sync_committee),
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.consolidation_balance_to_consume),
         }
     }
-    pub fn next_sync_committee_mut(&mut self) -> Option<&mut SyncCommittee<SYNC_COMMITTEE_SIZE>> {
+    pub fn consolidation_balance_to_consume_mut(&mut self) -> Option<&mut Gwei> {
         match self {
             Self::Phase0(_) => None,
-            Self::Altair(inner) => Some(&mut inner.next_sync_committee),
-            Self::Bellatrix(inner) => Some(&mut inner.next_sync_committee),
-            Self::Capella(inner) => Some(&mut inner.next_sync_committee),
-            Self::Deneb(inner) => Some(&mut inner.next_sync_committee),
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.consolidation_balance_to_consume),
         }
     }
-    pub fn latest_execution_payload_header(
-        &self,
-    ) -> Option<ExecutionPayloadHeaderRef<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>> {
+    pub fn earliest_consolidation_epoch(&self) -> Option<&Epoch> {
         match self {
             Self::Phase0(_) => None,
             Self::Altair(_) => None,
-            Self::Bellatrix(inner) => Some(From::from(&inner.latest_execution_payload_header)),
-            Self::Capella(inner) => Some(From::from(&inner.latest_execution_payload_header)),
-            Self::Deneb(inner) => Some(From::from(&inner.latest_execution_payload_header)),
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.earliest_consolidation_epoch),
         }
     }
-    pub fn latest_execution_payload_header_mut(
-        &mut self,
-    ) -> Option<ExecutionPayloadHeaderRefMut<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>> {
+    pub fn earliest_consolidation_epoch_mut(&mut self) -> Option<&mut Epoch> {
         match self {
             Self::Phase0(_) => None,
             Self::Altair(_) => None,
-            Self::Bellatrix(inner) => Some(From::from(&mut inner.latest_execution_payload_header)),
-            Self::Capella(inner) => Some(From::from(&mut inner.latest_execution_payload_header)),
-            Self::Deneb(inner) => Some(From::from(&mut inner.latest_execution_payload_header)),
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.earliest_consolidation_epoch),
         }
     }
-    pub fn next_withdrawal_index(&self) -> Option<WithdrawalIndex> {
+    pub fn pending_deposits(&self) -> Option<&List<PendingDeposit, PENDING_DEPOSITS_LIMIT>> {
         match self {
             Self::Phase0(_) => None,
             Self::Altair(_) => None,
             Self::Bellatrix(_) => None,
-            Self::Capella(inner) => Some(inner.next_withdrawal_index),
-            Self::Deneb(inner) => Some(inner.next_withdrawal_index),
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.pending_deposits),
         }
     }
-    pub fn next_withdrawal_index_mut(&mut self) -> Option<&mut WithdrawalIndex> {
+    pub fn pending_deposits_mut(
+        &mut self,
+    ) -> Option<&mut List<PendingDeposit, PENDING_DEPOSITS_LIMIT>> {
         match self {
             Self::Phase0(_) => None,
             Self::Altair(_) => None,
             Self::Bellatrix(_) => None,
-            Self::Capella(inner) => Some(&mut inner.next_withdrawal_index),
-            Self::Deneb(inner) => Some(&mut inner.next_withdrawal_index),
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.pending_deposits),
         }
     }
-    pub fn next_withdrawal_validator_index(&self) -> Option<ValidatorIndex> {
+    pub fn pending_partial_withdrawals(
+        &self,
+    ) -> Option<&List<PendingPartialWithdrawal, PENDING_PARTIAL_WITHDRAWALS_LIMIT>> {
         match self {
             Self::Phase0(_) => None,
             Self::Altair(_) => None,
             Self::Bellatrix(_) => None,
-            Self::Capella(inner) => Some(inner.next_withdrawal_validator_index),
-            Self::Deneb(inner) => Some(inner.next_withdrawal_validator_index),
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.pending_partial_withdrawals),
         }
     }
-    pub fn next_withdrawal_validator_index_mut(&mut self) -> Option<&mut ValidatorIndex> {
+    pub fn pending_partial_withdrawals_mut(
+        &mut self,
+    ) -> Option<&mut List<PendingPartialWithdrawal, PENDING_PARTIAL_WITHDRAWALS_LIMIT>> {
         match self {
             Self::Phase0(_) => None,
             Self::Altair(_) => None,
             Self::Bellatrix(_) => None,
-            Self::Capella(inner) => Some(&mut inner.next_withdrawal_validator_index),
-            Self::Deneb(inner) => Some(&mut inner.next_withdrawal_validator_index),
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.pending_partial_withdrawals),
         }
     }
-    pub fn historical_summaries(&self) -> Option<&List<HistoricalSummary, HISTORICAL_ROOTS_LIMIT>> {
+    pub fn pending_consolidations(
+        &self,
+    ) -> Option<&List<PendingConsolidation, PENDING_CONSOLIDATIONS_LIMIT>> {
         match self {
             Self::Phase0(_) => None,
             Self::Altair(_) => None,
             Self::Bellatrix(_) => None,
-            Self::Capella(inner) => Some(&inner.historical_summaries),
-            Self::Deneb(inner) => Some(&inner.historical_summaries),
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.pending_consolidations),
         }
     }
-    pub fn historical_summaries_mut(
+    pub fn pending_consolidations_mut(
         &mut self,
-    ) -> Option<&mut List<HistoricalSummary, HISTORICAL_ROOTS_LIMIT>> {
+    ) -> Option<&mut List<PendingConsolidation, PENDING_CONSOLIDATIONS_LIMIT>> {
         match self {
             Self::Phase0(_) => None,
             Self::Altair(_) => None,
             Self::Bellatrix(_) => None,
-            Self::Capella(inner) => Some(&mut inner.historical_summaries),
-            Self::Deneb(inner) => Some(&mut inner.historical_summaries),
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.pending_consolidations),
         }
     }
 }
@@ -921,6 +1645,9 @@ impl<
         const SYNC_COMMITTEE_SIZE: usize,
         const BYTES_PER_LOGS_BLOOM: usize,
         const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
     > serde::Deserialize<'de>
     for BeaconState<
         SLOTS_PER_HISTORICAL_ROOT,
@@ -934,6 +1661,9 @@ impl<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
     >
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -941,6 +1671,9 @@ impl<
         D: serde::Deserializer<'de>,
     {
         let value = serde_json::Value::deserialize(deserializer)?;
+        if let Ok(inner) = <_ as serde::Deserialize>::deserialize(&value) {
+            return Ok(Self::Electra(inner));
+        }
         if let Ok(inner) = <_ as serde::Deserialize>::deserialize(&value) {
             return Ok(Self::Deneb(inner));
         }
@@ -974,6 +1707,9 @@ pub enum BeaconStateRef<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
 > {
     Phase0(
         &'a phase0::BeaconState<
@@ -1041,6 +1777,23 @@ pub enum BeaconStateRef<
             MAX_EXTRA_DATA_BYTES,
         >,
     ),
+    Electra(
+        &'a electra::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    ),
 }
 impl<
         'a,
@@ -1055,6 +1808,9 @@ impl<
         const SYNC_COMMITTEE_SIZE: usize,
         const BYTES_PER_LOGS_BLOOM: usize,
         const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
     >
     BeaconStateRef<
         'a,
@@ -1069,6 +1825,9 @@ impl<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
     >
 {
     pub fn phase0(
@@ -1172,6 +1931,30 @@ impl<
             _ => None,
         }
     }
+    pub fn electra(
+        &self,
+    ) -> Option<
+        &electra::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    > {
+        match self {
+            Self::Electra(inner) => Some(inner),
+            _ => None,
+        }
+    }
     pub fn version(&self) -> Version {
         match self {
             Self::Phase0(_) => Version::Phase0,
@@ -1179,6 +1962,7 @@ impl<
             Self::Bellatrix(_) => Version::Bellatrix,
             Self::Capella(_) => Version::Capella,
             Self::Deneb(_) => Version::Deneb,
+            Self::Electra(_) => Version::Electra,
         }
     }
     pub fn genesis_time(&self) -> u64 {
@@ -1188,6 +1972,7 @@ impl<
             Self::Bellatrix(inner) => inner.genesis_time,
             Self::Capella(inner) => inner.genesis_time,
             Self::Deneb(inner) => inner.genesis_time,
+            Self::Electra(inner) => inner.genesis_time,
         }
     }
     pub fn genesis_validators_root(&self) -> Root {
@@ -1197,6 +1982,7 @@ impl<
             Self::Bellatrix(inner) => inner.genesis_validators_root,
             Self::Capella(inner) => inner.genesis_validators_root,
             Self::Deneb(inner) => inner.genesis_validators_root,
+            Self::Electra(inner) => inner.genesis_validators_root,
         }
     }
     pub fn slot(&self) -> Slot {
@@ -1206,6 +1992,7 @@ impl<
             Self::Bellatrix(inner) => inner.slot,
             Self::Capella(inner) => inner.slot,
             Self::Deneb(inner) => inner.slot,
+            Self::Electra(inner) => inner.slot,
         }
     }
     pub fn fork(&self) -> &Fork {
@@ -1215,6 +2002,7 @@ impl<
             Self::Bellatrix(inner) => &inner.fork,
             Self::Capella(inner) => &inner.fork,
             Self::Deneb(inner) => &inner.fork,
+            Self::Electra(inner) => &inner.fork,
         }
     }
     pub fn latest_block_header(&self) -> &BeaconBlockHeader {
@@ -1224,6 +2012,7 @@ impl<
             Self::Bellatrix(inner) => &inner.latest_block_header,
             Self::Capella(inner) => &inner.latest_block_header,
             Self::Deneb(inner) => &inner.latest_block_header,
+            Self::Electra(inner) => &inner.latest_block_header,
         }
     }
     pub fn block_roots(&self) -> &Vector<Root, SLOTS_PER_HISTORICAL_ROOT> {
@@ -1233,6 +2022,7 @@ impl<
             Self::Bellatrix(inner) => &inner.block_roots,
             Self::Capella(inner) => &inner.block_roots,
             Self::Deneb(inner) => &inner.block_roots,
+            Self::Electra(inner) => &inner.block_roots,
         }
     }
     pub fn state_roots(&self) -> &Vector<Root, SLOTS_PER_HISTORICAL_ROOT> {
@@ -1242,6 +2032,7 @@ impl<
             Self::Bellatrix(inner) => &inner.state_roots,
             Self::Capella(inner) => &inner.state_roots,
             Self::Deneb(inner) => &inner.state_roots,
+            Self::Electra(inner) => &inner.state_roots,
         }
     }
     pub fn historical_roots(&self) -> &List<Root, HISTORICAL_ROOTS_LIMIT> {
@@ -1251,6 +2042,7 @@ impl<
             Self::Bellatrix(inner) => &inner.historical_roots,
             Self::Capella(inner) => &inner.historical_roots,
             Self::Deneb(inner) => &inner.historical_roots,
+            Self::Electra(inner) => &inner.historical_roots,
         }
     }
     pub fn eth1_data(&self) -> &Eth1Data {
@@ -1260,6 +2052,7 @@ impl<
             Self::Bellatrix(inner) => &inner.eth1_data,
             Self::Capella(inner) => &inner.eth1_data,
             Self::Deneb(inner) => &inner.eth1_data,
+            Self::Electra(inner) => &inner.eth1_data,
         }
     }
     pub fn eth1_data_votes(&self) -> &List<Eth1Data, ETH1_DATA_VOTES_BOUND> {
@@ -1269,6 +2062,7 @@ impl<
             Self::Bellatrix(inner) => &inner.eth1_data_votes,
             Self::Capella(inner) => &inner.eth1_data_votes,
             Self::Deneb(inner) => &inner.eth1_data_votes,
+            Self::Electra(inner) => &inner.eth1_data_votes,
         }
     }
     pub fn eth1_deposit_index(&self) -> u64 {
@@ -1278,6 +2072,7 @@ impl<
             Self::Bellatrix(inner) => inner.eth1_deposit_index,
             Self::Capella(inner) => inner.eth1_deposit_index,
             Self::Deneb(inner) => inner.eth1_deposit_index,
+            Self::Electra(inner) => inner.eth1_deposit_index,
         }
     }
     pub fn validators(&self) -> &List<Validator, VALIDATOR_REGISTRY_LIMIT> {
@@ -1287,6 +2082,7 @@ impl<
             Self::Bellatrix(inner) => &inner.validators,
             Self::Capella(inner) => &inner.validators,
             Self::Deneb(inner) => &inner.validators,
+            Self::Electra(inner) => &inner.validators,
         }
     }
     pub fn balances(&self) -> &List<Gwei, VALIDATOR_REGISTRY_LIMIT> {
@@ -1296,6 +2092,7 @@ impl<
             Self::Bellatrix(inner) => &inner.balances,
             Self::Capella(inner) => &inner.balances,
             Self::Deneb(inner) => &inner.balances,
+            Self::Electra(inner) => &inner.balances,
         }
     }
     pub fn randao_mixes(&self) -> &Vector<Bytes32, EPOCHS_PER_HISTORICAL_VECTOR> {
@@ -1305,6 +2102,7 @@ impl<
             Self::Bellatrix(inner) => &inner.randao_mixes,
             Self::Capella(inner) => &inner.randao_mixes,
             Self::Deneb(inner) => &inner.randao_mixes,
+            Self::Electra(inner) => &inner.randao_mixes,
         }
     }
     pub fn slashings(&self) -> &Vector<Gwei, EPOCHS_PER_SLASHINGS_VECTOR> {
@@ -1314,6 +2112,7 @@ impl<
             Self::Bellatrix(inner) => &inner.slashings,
             Self::Capella(inner) => &inner.slashings,
             Self::Deneb(inner) => &inner.slashings,
+            Self::Electra(inner) => &inner.slashings,
         }
     }
     pub fn previous_epoch_attestations(
@@ -1326,6 +2125,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(_) => None,
             Self::Deneb(_) => None,
+            Self::Electra(_) => None,
         }
     }
     pub fn current_epoch_attestations(
@@ -1338,6 +2138,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(_) => None,
             Self::Deneb(_) => None,
+            Self::Electra(_) => None,
         }
     }
     pub fn justification_bits(&self) -> &Bitvector<JUSTIFICATION_BITS_LENGTH> {
@@ -1347,6 +2148,7 @@ impl<
             Self::Bellatrix(inner) => &inner.justification_bits,
             Self::Capella(inner) => &inner.justification_bits,
             Self::Deneb(inner) => &inner.justification_bits,
+            Self::Electra(inner) => &inner.justification_bits,
         }
     }
     pub fn previous_justified_checkpoint(&self) -> &Checkpoint {
@@ -1356,6 +2158,7 @@ impl<
             Self::Bellatrix(inner) => &inner.previous_justified_checkpoint,
             Self::Capella(inner) => &inner.previous_justified_checkpoint,
             Self::Deneb(inner) => &inner.previous_justified_checkpoint,
+            Self::Electra(inner) => &inner.previous_justified_checkpoint,
         }
     }
     pub fn current_justified_checkpoint(&self) -> &Checkpoint {
@@ -1365,6 +2168,7 @@ impl<
             Self::Bellatrix(inner) => &inner.current_justified_checkpoint,
             Self::Capella(inner) => &inner.current_justified_checkpoint,
             Self::Deneb(inner) => &inner.current_justified_checkpoint,
+            Self::Electra(inner) => &inner.current_justified_checkpoint,
         }
     }
     pub fn finalized_checkpoint(&self) -> &Checkpoint {
@@ -1374,6 +2178,7 @@ impl<
             Self::Bellatrix(inner) => &inner.finalized_checkpoint,
             Self::Capella(inner) => &inner.finalized_checkpoint,
             Self::Deneb(inner) => &inner.finalized_checkpoint,
+            Self::Electra(inner) => &inner.finalized_checkpoint,
         }
     }
     pub fn previous_epoch_participation(
@@ -1385,6 +2190,7 @@ impl<
             Self::Bellatrix(inner) => Some(&inner.previous_epoch_participation),
             Self::Capella(inner) => Some(&inner.previous_epoch_participation),
             Self::Deneb(inner) => Some(&inner.previous_epoch_participation),
+            Self::Electra(inner) => Some(&inner.previous_epoch_participation),
         }
     }
     pub fn current_epoch_participation(
@@ -1396,6 +2202,7 @@ impl<
             Self::Bellatrix(inner) => Some(&inner.current_epoch_participation),
             Self::Capella(inner) => Some(&inner.current_epoch_participation),
             Self::Deneb(inner) => Some(&inner.current_epoch_participation),
+            Self::Electra(inner) => Some(&inner.current_epoch_participation),
         }
     }
     pub fn inactivity_scores(&self) -> Option<&List<u64, VALIDATOR_REGISTRY_LIMIT>> {
@@ -1405,6 +2212,7 @@ impl<
             Self::Bellatrix(inner) => Some(&inner.inactivity_scores),
             Self::Capella(inner) => Some(&inner.inactivity_scores),
             Self::Deneb(inner) => Some(&inner.inactivity_scores),
+            Self::Electra(inner) => Some(&inner.inactivity_scores),
         }
     }
     pub fn current_sync_committee(&self) -> Option<&SyncCommittee<SYNC_COMMITTEE_SIZE>> {
@@ -1414,6 +2222,7 @@ impl<
             Self::Bellatrix(inner) => Some(&inner.current_sync_committee),
             Self::Capella(inner) => Some(&inner.current_sync_committee),
             Self::Deneb(inner) => Some(&inner.current_sync_committee),
+            Self::Electra(inner) => Some(&inner.current_sync_committee),
         }
     }
     pub fn next_sync_committee(&self) -> Option<&SyncCommittee<SYNC_COMMITTEE_SIZE>> {
@@ -1423,6 +2232,7 @@ impl<
             Self::Bellatrix(inner) => Some(&inner.next_sync_committee),
             Self::Capella(inner) => Some(&inner.next_sync_committee),
             Self::Deneb(inner) => Some(&inner.next_sync_committee),
+            Self::Electra(inner) => Some(&inner.next_sync_committee),
         }
     }
     pub fn latest_execution_payload_header(
@@ -1434,6 +2244,7 @@ impl<
             Self::Bellatrix(inner) => Some(From::from(&inner.latest_execution_payload_header)),
             Self::Capella(inner) => Some(From::from(&inner.latest_execution_payload_header)),
             Self::Deneb(inner) => Some(From::from(&inner.latest_execution_payload_header)),
+            Self::Electra(inner) => Some(From::from(&inner.latest_execution_payload_header)),
         }
     }
     pub fn next_withdrawal_index(&self) -> Option<WithdrawalIndex> {
@@ -1443,6 +2254,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(inner) => Some(inner.next_withdrawal_index),
             Self::Deneb(inner) => Some(inner.next_withdrawal_index),
+            Self::Electra(inner) => Some(inner.next_withdrawal_index),
         }
     }
     pub fn next_withdrawal_validator_index(&self) -> Option<ValidatorIndex> {
@@ -1452,6 +2264,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(inner) => Some(inner.next_withdrawal_validator_index),
             Self::Deneb(inner) => Some(inner.next_withdrawal_validator_index),
+            Self::Electra(inner) => Some(inner.next_withdrawal_validator_index),
         }
     }
     pub fn historical_summaries(&self) -> Option<&List<HistoricalSummary, HISTORICAL_ROOTS_LIMIT>> {
@@ -1461,6 +2274,101 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(inner) => Some(&inner.historical_summaries),
             Self::Deneb(inner) => Some(&inner.historical_summaries),
+            Self::Electra(inner) => Some(&inner.historical_summaries),
+        }
+    }
+    pub fn deposit_requests_start_index(&self) -> Option<u64> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(inner.deposit_requests_start_index),
+        }
+    }
+    pub fn deposit_balance_to_consume(&self) -> Option<&Gwei> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.deposit_balance_to_consume),
+        }
+    }
+    pub fn exit_balance_to_consume(&self) -> Option<&Gwei> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.exit_balance_to_consume),
+        }
+    }
+    pub fn earliest_exit_epoch(&self) -> Option<&Epoch> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.earliest_exit_epoch),
+        }
+    }
+    pub fn consolidation_balance_to_consume(&self) -> Option<&Gwei> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.consolidation_balance_to_consume),
+        }
+    }
+    pub fn earliest_consolidation_epoch(&self) -> Option<&Epoch> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.earliest_consolidation_epoch),
+        }
+    }
+    pub fn pending_deposits(&self) -> Option<&List<PendingDeposit, PENDING_DEPOSITS_LIMIT>> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.pending_deposits),
+        }
+    }
+    pub fn pending_partial_withdrawals(
+        &self,
+    ) -> Option<&List<PendingPartialWithdrawal, PENDING_PARTIAL_WITHDRAWALS_LIMIT>> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.pending_partial_withdrawals),
+        }
+    }
+    pub fn pending_consolidations(
+        &self,
+    ) -> Option<&List<PendingConsolidation, PENDING_CONSOLIDATIONS_LIMIT>> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.pending_consolidations),
         }
     }
 }
@@ -1477,6 +2385,9 @@ impl<
         const SYNC_COMMITTEE_SIZE: usize,
         const BYTES_PER_LOGS_BLOOM: usize,
         const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
     >
     From<
         &'a phase0::BeaconState<
@@ -1503,6 +2414,9 @@ impl<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
     >
 {
     fn from(
@@ -1533,6 +2447,9 @@ impl<
         const SYNC_COMMITTEE_SIZE: usize,
         const BYTES_PER_LOGS_BLOOM: usize,
         const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
     >
     From<
         &'a altair::BeaconState<
@@ -1559,6 +2476,9 @@ impl<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
     >
 {
     fn from(
@@ -1589,6 +2509,9 @@ impl<
         const SYNC_COMMITTEE_SIZE: usize,
         const BYTES_PER_LOGS_BLOOM: usize,
         const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
     >
     From<
         &'a bellatrix::BeaconState<
@@ -1617,6 +2540,9 @@ impl<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
     >
 {
     fn from(
@@ -1649,6 +2575,9 @@ impl<
         const SYNC_COMMITTEE_SIZE: usize,
         const BYTES_PER_LOGS_BLOOM: usize,
         const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
     >
     From<
         &'a capella::BeaconState<
@@ -1677,6 +2606,9 @@ impl<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
     >
 {
     fn from(
@@ -1709,6 +2641,9 @@ impl<
         const SYNC_COMMITTEE_SIZE: usize,
         const BYTES_PER_LOGS_BLOOM: usize,
         const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
     >
     From<
         &'a deneb::BeaconState<
@@ -1737,10 +2672,82 @@ impl<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >
+{
+    fn from(
+        value: &'a deneb::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+    ) -> Self {
+        Self::Deneb(value)
+    }
+}
+impl<
+        'a,
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    >
+    From<
+        &'a electra::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    >
+    for BeaconStateRef<
+        'a,
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
     >
 {
     fn from(
-        value: &'a deneb::BeaconState<
+        value: &'a electra::BeaconState<
             SLOTS_PER_HISTORICAL_ROOT,
             HISTORICAL_ROOTS_LIMIT,
             ETH1_DATA_VOTES_BOUND,
@@ -1751,9 +2758,12 @@ impl<
             SYNC_COMMITTEE_SIZE,
             BYTES_PER_LOGS_BLOOM,
             MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
         >,
     ) -> Self {
-        Self::Deneb(value)
+        Self::Electra(value)
     }
 }
 #[derive(Debug, PartialEq, Eq, HashTreeRoot)]
@@ -1771,6 +2781,9 @@ pub enum BeaconStateRefMut<
     const SYNC_COMMITTEE_SIZE: usize,
     const BYTES_PER_LOGS_BLOOM: usize,
     const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
 > {
     Phase0(
         &'a mut phase0::BeaconState<
@@ -1838,6 +2851,23 @@ pub enum BeaconStateRefMut<
             MAX_EXTRA_DATA_BYTES,
         >,
     ),
+    Electra(
+        &'a mut electra::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    ),
 }
 impl<
         'a,
@@ -1852,6 +2882,9 @@ impl<
         const SYNC_COMMITTEE_SIZE: usize,
         const BYTES_PER_LOGS_BLOOM: usize,
         const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
     >
     BeaconStateRefMut<
         'a,
@@ -1866,6 +2899,9 @@ impl<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
     >
 {
     pub fn phase0(
@@ -2070,6 +3106,54 @@ impl<
             _ => None,
         }
     }
+    pub fn electra(
+        &self,
+    ) -> Option<
+        &electra::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    > {
+        match self {
+            Self::Electra(inner) => Some(inner),
+            _ => None,
+        }
+    }
+    pub fn electra_mut(
+        &mut self,
+    ) -> Option<
+        &mut electra::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    > {
+        match self {
+            Self::Electra(inner) => Some(inner),
+            _ => None,
+        }
+    }
     pub fn version(&self) -> Version {
         match self {
             Self::Phase0(_) => Version::Phase0,
@@ -2077,6 +3161,7 @@ impl<
             Self::Bellatrix(_) => Version::Bellatrix,
             Self::Capella(_) => Version::Capella,
             Self::Deneb(_) => Version::Deneb,
+            Self::Electra(_) => Version::Electra,
         }
     }
     pub fn genesis_time(&self) -> u64 {
@@ -2086,6 +3171,7 @@ impl<
             Self::Bellatrix(inner) => inner.genesis_time,
             Self::Capella(inner) => inner.genesis_time,
             Self::Deneb(inner) => inner.genesis_time,
+            Self::Electra(inner) => inner.genesis_time,
         }
     }
     pub fn genesis_time_mut(&mut self) -> &mut u64 {
@@ -2095,6 +3181,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.genesis_time,
             Self::Capella(inner) => &mut inner.genesis_time,
             Self::Deneb(inner) => &mut inner.genesis_time,
+            Self::Electra(inner) => &mut inner.genesis_time,
         }
     }
     pub fn genesis_validators_root(&self) -> Root {
@@ -2104,6 +3191,7 @@ impl<
             Self::Bellatrix(inner) => inner.genesis_validators_root,
             Self::Capella(inner) => inner.genesis_validators_root,
             Self::Deneb(inner) => inner.genesis_validators_root,
+            Self::Electra(inner) => inner.genesis_validators_root,
         }
     }
     pub fn genesis_validators_root_mut(&mut self) -> &mut Root {
@@ -2113,6 +3201,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.genesis_validators_root,
             Self::Capella(inner) => &mut inner.genesis_validators_root,
             Self::Deneb(inner) => &mut inner.genesis_validators_root,
+            Self::Electra(inner) => &mut inner.genesis_validators_root,
         }
     }
     pub fn slot(&self) -> Slot {
@@ -2122,6 +3211,7 @@ impl<
             Self::Bellatrix(inner) => inner.slot,
             Self::Capella(inner) => inner.slot,
             Self::Deneb(inner) => inner.slot,
+            Self::Electra(inner) => inner.slot,
         }
     }
     pub fn slot_mut(&mut self) -> &mut Slot {
@@ -2131,6 +3221,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.slot,
             Self::Capella(inner) => &mut inner.slot,
             Self::Deneb(inner) => &mut inner.slot,
+            Self::Electra(inner) => &mut inner.slot,
         }
     }
     pub fn fork(&self) -> &Fork {
@@ -2140,6 +3231,7 @@ impl<
             Self::Bellatrix(inner) => &inner.fork,
             Self::Capella(inner) => &inner.fork,
             Self::Deneb(inner) => &inner.fork,
+            Self::Electra(inner) => &inner.fork,
         }
     }
     pub fn fork_mut(&mut self) -> &mut Fork {
@@ -2149,6 +3241,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.fork,
             Self::Capella(inner) => &mut inner.fork,
             Self::Deneb(inner) => &mut inner.fork,
+            Self::Electra(inner) => &mut inner.fork,
         }
     }
     pub fn latest_block_header(&self) -> &BeaconBlockHeader {
@@ -2158,6 +3251,7 @@ impl<
             Self::Bellatrix(inner) => &inner.latest_block_header,
             Self::Capella(inner) => &inner.latest_block_header,
             Self::Deneb(inner) => &inner.latest_block_header,
+            Self::Electra(inner) => &inner.latest_block_header,
         }
     }
     pub fn latest_block_header_mut(&mut self) -> &mut BeaconBlockHeader {
@@ -2167,6 +3261,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.latest_block_header,
             Self::Capella(inner) => &mut inner.latest_block_header,
             Self::Deneb(inner) => &mut inner.latest_block_header,
+            Self::Electra(inner) => &mut inner.latest_block_header,
         }
     }
     pub fn block_roots(&self) -> &Vector<Root, SLOTS_PER_HISTORICAL_ROOT> {
@@ -2176,6 +3271,7 @@ impl<
             Self::Bellatrix(inner) => &inner.block_roots,
             Self::Capella(inner) => &inner.block_roots,
             Self::Deneb(inner) => &inner.block_roots,
+            Self::Electra(inner) => &inner.block_roots,
         }
     }
     pub fn block_roots_mut(&mut self) -> &mut Vector<Root, SLOTS_PER_HISTORICAL_ROOT> {
@@ -2185,6 +3281,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.block_roots,
             Self::Capella(inner) => &mut inner.block_roots,
             Self::Deneb(inner) => &mut inner.block_roots,
+            Self::Electra(inner) => &mut inner.block_roots,
         }
     }
     pub fn state_roots(&self) -> &Vector<Root, SLOTS_PER_HISTORICAL_ROOT> {
@@ -2194,6 +3291,7 @@ impl<
             Self::Bellatrix(inner) => &inner.state_roots,
             Self::Capella(inner) => &inner.state_roots,
             Self::Deneb(inner) => &inner.state_roots,
+            Self::Electra(inner) => &inner.state_roots,
         }
     }
     pub fn state_roots_mut(&mut self) -> &mut Vector<Root, SLOTS_PER_HISTORICAL_ROOT> {
@@ -2203,6 +3301,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.state_roots,
             Self::Capella(inner) => &mut inner.state_roots,
             Self::Deneb(inner) => &mut inner.state_roots,
+            Self::Electra(inner) => &mut inner.state_roots,
         }
     }
     pub fn historical_roots(&self) -> &List<Root, HISTORICAL_ROOTS_LIMIT> {
@@ -2212,6 +3311,7 @@ impl<
             Self::Bellatrix(inner) => &inner.historical_roots,
             Self::Capella(inner) => &inner.historical_roots,
             Self::Deneb(inner) => &inner.historical_roots,
+            Self::Electra(inner) => &inner.historical_roots,
         }
     }
     pub fn historical_roots_mut(&mut self) -> &mut List<Root, HISTORICAL_ROOTS_LIMIT> {
@@ -2221,6 +3321,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.historical_roots,
             Self::Capella(inner) => &mut inner.historical_roots,
             Self::Deneb(inner) => &mut inner.historical_roots,
+            Self::Electra(inner) => &mut inner.historical_roots,
         }
     }
     pub fn eth1_data(&self) -> &Eth1Data {
@@ -2230,6 +3331,7 @@ impl<
             Self::Bellatrix(inner) => &inner.eth1_data,
             Self::Capella(inner) => &inner.eth1_data,
             Self::Deneb(inner) => &inner.eth1_data,
+            Self::Electra(inner) => &inner.eth1_data,
         }
     }
     pub fn eth1_data_mut(&mut self) -> &mut Eth1Data {
@@ -2239,6 +3341,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.eth1_data,
             Self::Capella(inner) => &mut inner.eth1_data,
             Self::Deneb(inner) => &mut inner.eth1_data,
+            Self::Electra(inner) => &mut inner.eth1_data,
         }
     }
     pub fn eth1_data_votes(&self) -> &List<Eth1Data, ETH1_DATA_VOTES_BOUND> {
@@ -2248,6 +3351,7 @@ impl<
             Self::Bellatrix(inner) => &inner.eth1_data_votes,
             Self::Capella(inner) => &inner.eth1_data_votes,
             Self::Deneb(inner) => &inner.eth1_data_votes,
+            Self::Electra(inner) => &inner.eth1_data_votes,
         }
     }
     pub fn eth1_data_votes_mut(&mut self) -> &mut List<Eth1Data, ETH1_DATA_VOTES_BOUND> {
@@ -2257,6 +3361,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.eth1_data_votes,
             Self::Capella(inner) => &mut inner.eth1_data_votes,
             Self::Deneb(inner) => &mut inner.eth1_data_votes,
+            Self::Electra(inner) => &mut inner.eth1_data_votes,
         }
     }
     pub fn eth1_deposit_index(&self) -> u64 {
@@ -2266,6 +3371,7 @@ impl<
             Self::Bellatrix(inner) => inner.eth1_deposit_index,
             Self::Capella(inner) => inner.eth1_deposit_index,
             Self::Deneb(inner) => inner.eth1_deposit_index,
+            Self::Electra(inner) => inner.eth1_deposit_index,
         }
     }
     pub fn eth1_deposit_index_mut(&mut self) -> &mut u64 {
@@ -2275,6 +3381,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.eth1_deposit_index,
             Self::Capella(inner) => &mut inner.eth1_deposit_index,
             Self::Deneb(inner) => &mut inner.eth1_deposit_index,
+            Self::Electra(inner) => &mut inner.eth1_deposit_index,
         }
     }
     pub fn validators(&self) -> &List<Validator, VALIDATOR_REGISTRY_LIMIT> {
@@ -2284,6 +3391,7 @@ impl<
             Self::Bellatrix(inner) => &inner.validators,
             Self::Capella(inner) => &inner.validators,
             Self::Deneb(inner) => &inner.validators,
+            Self::Electra(inner) => &inner.validators,
         }
     }
     pub fn validators_mut(&mut self) -> &mut List<Validator, VALIDATOR_REGISTRY_LIMIT> {
@@ -2293,6 +3401,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.validators,
             Self::Capella(inner) => &mut inner.validators,
             Self::Deneb(inner) => &mut inner.validators,
+            Self::Electra(inner) => &mut inner.validators,
         }
     }
     pub fn balances(&self) -> &List<Gwei, VALIDATOR_REGISTRY_LIMIT> {
@@ -2302,6 +3411,7 @@ impl<
             Self::Bellatrix(inner) => &inner.balances,
             Self::Capella(inner) => &inner.balances,
             Self::Deneb(inner) => &inner.balances,
+            Self::Electra(inner) => &inner.balances,
         }
     }
     pub fn balances_mut(&mut self) -> &mut List<Gwei, VALIDATOR_REGISTRY_LIMIT> {
@@ -2311,6 +3421,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.balances,
             Self::Capella(inner) => &mut inner.balances,
             Self::Deneb(inner) => &mut inner.balances,
+            Self::Electra(inner) => &mut inner.balances,
         }
     }
     pub fn randao_mixes(&self) -> &Vector<Bytes32, EPOCHS_PER_HISTORICAL_VECTOR> {
@@ -2320,6 +3431,7 @@ impl<
             Self::Bellatrix(inner) => &inner.randao_mixes,
             Self::Capella(inner) => &inner.randao_mixes,
             Self::Deneb(inner) => &inner.randao_mixes,
+            Self::Electra(inner) => &inner.randao_mixes,
         }
     }
     pub fn randao_mixes_mut(&mut self) -> &mut Vector<Bytes32, EPOCHS_PER_HISTORICAL_VECTOR> {
@@ -2329,6 +3441,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.randao_mixes,
             Self::Capella(inner) => &mut inner.randao_mixes,
             Self::Deneb(inner) => &mut inner.randao_mixes,
+            Self::Electra(inner) => &mut inner.randao_mixes,
         }
     }
     pub fn slashings(&self) -> &Vector<Gwei, EPOCHS_PER_SLASHINGS_VECTOR> {
@@ -2338,6 +3451,7 @@ impl<
             Self::Bellatrix(inner) => &inner.slashings,
             Self::Capella(inner) => &inner.slashings,
             Self::Deneb(inner) => &inner.slashings,
+            Self::Electra(inner) => &inner.slashings,
         }
     }
     pub fn slashings_mut(&mut self) -> &mut Vector<Gwei, EPOCHS_PER_SLASHINGS_VECTOR> {
@@ -2347,6 +3461,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.slashings,
             Self::Capella(inner) => &mut inner.slashings,
             Self::Deneb(inner) => &mut inner.slashings,
+            Self::Electra(inner) => &mut inner.slashings,
         }
     }
     pub fn previous_epoch_attestations(
@@ -2359,6 +3474,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(_) => None,
             Self::Deneb(_) => None,
+            Self::Electra(_) => None,
         }
     }
     pub fn previous_epoch_attestations_mut(
@@ -2372,6 +3488,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(_) => None,
             Self::Deneb(_) => None,
+            Self::Electra(_) => None,
         }
     }
     pub fn current_epoch_attestations(
@@ -2384,6 +3501,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(_) => None,
             Self::Deneb(_) => None,
+            Self::Electra(_) => None,
         }
     }
     pub fn current_epoch_attestations_mut(
@@ -2397,6 +3515,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(_) => None,
             Self::Deneb(_) => None,
+            Self::Electra(_) => None,
         }
     }
     pub fn justification_bits(&self) -> &Bitvector<JUSTIFICATION_BITS_LENGTH> {
@@ -2406,6 +3525,7 @@ impl<
             Self::Bellatrix(inner) => &inner.justification_bits,
             Self::Capella(inner) => &inner.justification_bits,
             Self::Deneb(inner) => &inner.justification_bits,
+            Self::Electra(inner) => &inner.justification_bits,
         }
     }
     pub fn justification_bits_mut(&mut self) -> &mut Bitvector<JUSTIFICATION_BITS_LENGTH> {
@@ -2415,6 +3535,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.justification_bits,
             Self::Capella(inner) => &mut inner.justification_bits,
             Self::Deneb(inner) => &mut inner.justification_bits,
+            Self::Electra(inner) => &mut inner.justification_bits,
         }
     }
     pub fn previous_justified_checkpoint(&self) -> &Checkpoint {
@@ -2424,6 +3545,7 @@ impl<
             Self::Bellatrix(inner) => &inner.previous_justified_checkpoint,
             Self::Capella(inner) => &inner.previous_justified_checkpoint,
             Self::Deneb(inner) => &inner.previous_justified_checkpoint,
+            Self::Electra(inner) => &inner.previous_justified_checkpoint,
         }
     }
     pub fn previous_justified_checkpoint_mut(&mut self) -> &mut Checkpoint {
@@ -2433,6 +3555,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.previous_justified_checkpoint,
             Self::Capella(inner) => &mut inner.previous_justified_checkpoint,
             Self::Deneb(inner) => &mut inner.previous_justified_checkpoint,
+            Self::Electra(inner) => &mut inner.previous_justified_checkpoint,
         }
     }
     pub fn current_justified_checkpoint(&self) -> &Checkpoint {
@@ -2442,6 +3565,7 @@ impl<
             Self::Bellatrix(inner) => &inner.current_justified_checkpoint,
             Self::Capella(inner) => &inner.current_justified_checkpoint,
             Self::Deneb(inner) => &inner.current_justified_checkpoint,
+            Self::Electra(inner) => &inner.current_justified_checkpoint,
         }
     }
     pub fn current_justified_checkpoint_mut(&mut self) -> &mut Checkpoint {
@@ -2451,6 +3575,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.current_justified_checkpoint,
             Self::Capella(inner) => &mut inner.current_justified_checkpoint,
             Self::Deneb(inner) => &mut inner.current_justified_checkpoint,
+            Self::Electra(inner) => &mut inner.current_justified_checkpoint,
         }
     }
     pub fn finalized_checkpoint(&self) -> &Checkpoint {
@@ -2460,6 +3585,7 @@ impl<
             Self::Bellatrix(inner) => &inner.finalized_checkpoint,
             Self::Capella(inner) => &inner.finalized_checkpoint,
             Self::Deneb(inner) => &inner.finalized_checkpoint,
+            Self::Electra(inner) => &inner.finalized_checkpoint,
         }
     }
     pub fn finalized_checkpoint_mut(&mut self) -> &mut Checkpoint {
@@ -2469,6 +3595,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.finalized_checkpoint,
             Self::Capella(inner) => &mut inner.finalized_checkpoint,
             Self::Deneb(inner) => &mut inner.finalized_checkpoint,
+            Self::Electra(inner) => &mut inner.finalized_checkpoint,
         }
     }
     pub fn previous_epoch_participation(
@@ -2480,6 +3607,7 @@ impl<
             Self::Bellatrix(inner) => Some(&inner.previous_epoch_participation),
             Self::Capella(inner) => Some(&inner.previous_epoch_participation),
             Self::Deneb(inner) => Some(&inner.previous_epoch_participation),
+            Self::Electra(inner) => Some(&inner.previous_epoch_participation),
         }
     }
     pub fn previous_epoch_participation_mut(
@@ -2491,6 +3619,7 @@ impl<
             Self::Bellatrix(inner) => Some(&mut inner.previous_epoch_participation),
             Self::Capella(inner) => Some(&mut inner.previous_epoch_participation),
             Self::Deneb(inner) => Some(&mut inner.previous_epoch_participation),
+            Self::Electra(inner) => Some(&mut inner.previous_epoch_participation),
         }
     }
     pub fn current_epoch_participation(
@@ -2502,6 +3631,7 @@ impl<
             Self::Bellatrix(inner) => Some(&inner.current_epoch_participation),
             Self::Capella(inner) => Some(&inner.current_epoch_participation),
             Self::Deneb(inner) => Some(&inner.current_epoch_participation),
+            Self::Electra(inner) => Some(&inner.current_epoch_participation),
         }
     }
     pub fn current_epoch_participation_mut(
@@ -2513,6 +3643,7 @@ impl<
             Self::Bellatrix(inner) => Some(&mut inner.current_epoch_participation),
             Self::Capella(inner) => Some(&mut inner.current_epoch_participation),
             Self::Deneb(inner) => Some(&mut inner.current_epoch_participation),
+            Self::Electra(inner) => Some(&mut inner.current_epoch_participation),
         }
     }
     pub fn inactivity_scores(&self) -> Option<&List<u64, VALIDATOR_REGISTRY_LIMIT>> {
@@ -2522,6 +3653,7 @@ impl<
             Self::Bellatrix(inner) => Some(&inner.inactivity_scores),
             Self::Capella(inner) => Some(&inner.inactivity_scores),
             Self::Deneb(inner) => Some(&inner.inactivity_scores),
+            Self::Electra(inner) => Some(&inner.inactivity_scores),
         }
     }
     pub fn inactivity_scores_mut(&mut self) -> Option<&mut List<u64, VALIDATOR_REGISTRY_LIMIT>> {
@@ -2531,6 +3663,7 @@ impl<
             Self::Bellatrix(inner) => Some(&mut inner.inactivity_scores),
             Self::Capella(inner) => Some(&mut inner.inactivity_scores),
             Self::Deneb(inner) => Some(&mut inner.inactivity_scores),
+            Self::Electra(inner) => Some(&mut inner.inactivity_scores),
         }
     }
     pub fn current_sync_committee(&self) -> Option<&SyncCommittee<SYNC_COMMITTEE_SIZE>> {
@@ -2540,6 +3673,7 @@ impl<
             Self::Bellatrix(inner) => Some(&inner.current_sync_committee),
             Self::Capella(inner) => Some(&inner.current_sync_committee),
             Self::Deneb(inner) => Some(&inner.current_sync_committee),
+            Self::Electra(inner) => Some(&inner.current_sync_committee),
         }
     }
     pub fn current_sync_committee_mut(
@@ -2551,6 +3685,7 @@ impl<
             Self::Bellatrix(inner) => Some(&mut inner.current_sync_committee),
             Self::Capella(inner) => Some(&mut inner.current_sync_committee),
             Self::Deneb(inner) => Some(&mut inner.current_sync_committee),
+            Self::Electra(inner) => Some(&mut inner.current_sync_committee),
         }
     }
     pub fn next_sync_committee(&self) -> Option<&SyncCommittee<SYNC_COMMITTEE_SIZE>> {
@@ -2560,6 +3695,7 @@ impl<
             Self::Bellatrix(inner) => Some(&inner.next_sync_committee),
             Self::Capella(inner) => Some(&inner.next_sync_committee),
             Self::Deneb(inner) => Some(&inner.next_sync_committee),
+            Self::Electra(inner) => Some(&inner.next_sync_committee),
         }
     }
     pub fn next_sync_committee_mut(&mut self) -> Option<&mut SyncCommittee<SYNC_COMMITTEE_SIZE>> {
@@ -2569,6 +3705,7 @@ impl<
             Self::Bellatrix(inner) => Some(&mut inner.next_sync_committee),
             Self::Capella(inner) => Some(&mut inner.next_sync_committee),
             Self::Deneb(inner) => Some(&mut inner.next_sync_committee),
+            Self::Electra(inner) => Some(&mut inner.next_sync_committee),
         }
     }
     pub fn latest_execution_payload_header(
@@ -2580,6 +3717,7 @@ impl<
             Self::Bellatrix(inner) => Some(From::from(&inner.latest_execution_payload_header)),
             Self::Capella(inner) => Some(From::from(&inner.latest_execution_payload_header)),
             Self::Deneb(inner) => Some(From::from(&inner.latest_execution_payload_header)),
+            Self::Electra(inner) => Some(From::from(&inner.latest_execution_payload_header)),
         }
     }
     pub fn latest_execution_payload_header_mut(
@@ -2591,6 +3729,7 @@ impl<
             Self::Bellatrix(inner) => Some(From::from(&mut inner.latest_execution_payload_header)),
             Self::Capella(inner) => Some(From::from(&mut inner.latest_execution_payload_header)),
             Self::Deneb(inner) => Some(From::from(&mut inner.latest_execution_payload_header)),
+            Self::Electra(inner) => Some(From::from(&mut inner.latest_execution_payload_header)),
         }
     }
     pub fn next_withdrawal_index(&self) -> Option<WithdrawalIndex> {
@@ -2600,6 +3739,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(inner) => Some(inner.next_withdrawal_index),
             Self::Deneb(inner) => Some(inner.next_withdrawal_index),
+            Self::Electra(inner) => Some(inner.next_withdrawal_index),
         }
     }
     pub fn next_withdrawal_index_mut(&mut self) -> Option<&mut WithdrawalIndex> {
@@ -2609,6 +3749,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(inner) => Some(&mut inner.next_withdrawal_index),
             Self::Deneb(inner) => Some(&mut inner.next_withdrawal_index),
+            Self::Electra(inner) => Some(&mut inner.next_withdrawal_index),
         }
     }
     pub fn next_withdrawal_validator_index(&self) -> Option<ValidatorIndex> {
@@ -2618,6 +3759,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(inner) => Some(inner.next_withdrawal_validator_index),
             Self::Deneb(inner) => Some(inner.next_withdrawal_validator_index),
+            Self::Electra(inner) => Some(inner.next_withdrawal_validator_index),
         }
     }
     pub fn next_withdrawal_validator_index_mut(&mut self) -> Option<&mut ValidatorIndex> {
@@ -2627,6 +3769,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(inner) => Some(&mut inner.next_withdrawal_validator_index),
             Self::Deneb(inner) => Some(&mut inner.next_withdrawal_validator_index),
+            Self::Electra(inner) => Some(&mut inner.next_withdrawal_validator_index),
         }
     }
     pub fn historical_summaries(&self) -> Option<&List<HistoricalSummary, HISTORICAL_ROOTS_LIMIT>> {
@@ -2636,6 +3779,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(inner) => Some(&inner.historical_summaries),
             Self::Deneb(inner) => Some(&inner.historical_summaries),
+            Self::Electra(inner) => Some(&inner.historical_summaries),
         }
     }
     pub fn historical_summaries_mut(
@@ -2647,6 +3791,197 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(inner) => Some(&mut inner.historical_summaries),
             Self::Deneb(inner) => Some(&mut inner.historical_summaries),
+            Self::Electra(inner) => Some(&mut inner.historical_summaries),
+        }
+    }
+    pub fn deposit_requests_start_index(&self) -> Option<u64> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(inner.deposit_requests_start_index),
+        }
+    }
+    pub fn deposit_requests_start_index_mut(&mut self) -> Option<&mut u64> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.deposit_requests_start_index),
+        }
+    }
+    pub fn deposit_balance_to_consume(&self) -> Option<&Gwei> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.deposit_balance_to_consume),
+        }
+    }
+    pub fn deposit_balance_to_consume_mut(&mut self) -> Option<&mut Gwei> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.deposit_balance_to_consume),
+        }
+    }
+    pub fn exit_balance_to_consume(&self) -> Option<&Gwei> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.exit_balance_to_consume),
+        }
+    }
+    pub fn exit_balance_to_consume_mut(&mut self) -> Option<&mut Gwei> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.exit_balance_to_consume),
+        }
+    }
+    pub fn earliest_exit_epoch(&self) -> Option<&Epoch> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.earliest_exit_epoch),
+        }
+    }
+    pub fn earliest_exit_epoch_mut(&mut self) -> Option<&mut Epoch> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.earliest_exit_epoch),
+        }
+    }
+    pub fn consolidation_balance_to_consume(&self) -> Option<&Gwei> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.consolidation_balance_to_consume),
+        }
+    }
+    pub fn consolidation_balance_to_consume_mut(&mut self) -> Option<&mut Gwei> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.consolidation_balance_to_consume),
+        }
+    }
+    pub fn earliest_consolidation_epoch(&self) -> Option<&Epoch> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.earliest_consolidation_epoch),
+        }
+    }
+    pub fn earliest_consolidation_epoch_mut(&mut self) -> Option<&mut Epoch> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.earliest_consolidation_epoch),
+        }
+    }
+    pub fn pending_deposits(&self) -> Option<&List<PendingDeposit, PENDING_DEPOSITS_LIMIT>> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.pending_deposits),
+        }
+    }
+    pub fn pending_deposits_mut(
+        &mut self,
+    ) -> Option<&mut List<PendingDeposit, PENDING_DEPOSITS_LIMIT>> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.pending_deposits),
+        }
+    }
+    pub fn pending_partial_withdrawals(
+        &self,
+    ) -> Option<&List<PendingPartialWithdrawal, PENDING_PARTIAL_WITHDRAWALS_LIMIT>> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.pending_partial_withdrawals),
+        }
+    }
+    pub fn pending_partial_withdrawals_mut(
+        &mut self,
+    ) -> Option<&mut List<PendingPartialWithdrawal, PENDING_PARTIAL_WITHDRAWALS_LIMIT>> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.pending_partial_withdrawals),
+        }
+    }
+    pub fn pending_consolidations(
+        &self,
+    ) -> Option<&List<PendingConsolidation, PENDING_CONSOLIDATIONS_LIMIT>> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.pending_consolidations),
+        }
+    }
+    pub fn pending_consolidations_mut(
+        &mut self,
+    ) -> Option<&mut List<PendingConsolidation, PENDING_CONSOLIDATIONS_LIMIT>> {
+        match self {
+            Self::Phase0(_) => None,
+            Self::Altair(_) => None,
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.pending_consolidations),
         }
     }
 }
@@ -2663,6 +3998,9 @@ impl<
         const SYNC_COMMITTEE_SIZE: usize,
         const BYTES_PER_LOGS_BLOOM: usize,
         const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
     >
     From<
         &'a mut phase0::BeaconState<
@@ -2689,6 +4027,9 @@ impl<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
     >
 {
     fn from(
@@ -2719,6 +4060,9 @@ impl<
         const SYNC_COMMITTEE_SIZE: usize,
         const BYTES_PER_LOGS_BLOOM: usize,
         const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
     >
     From<
         &'a mut altair::BeaconState<
@@ -2745,6 +4089,9 @@ impl<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
     >
 {
     fn from(
@@ -2775,6 +4122,9 @@ impl<
         const SYNC_COMMITTEE_SIZE: usize,
         const BYTES_PER_LOGS_BLOOM: usize,
         const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
     >
     From<
         &'a mut bellatrix::BeaconState<
@@ -2803,6 +4153,9 @@ impl<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
     >
 {
     fn from(
@@ -2835,6 +4188,9 @@ impl<
         const SYNC_COMMITTEE_SIZE: usize,
         const BYTES_PER_LOGS_BLOOM: usize,
         const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
     >
     From<
         &'a mut capella::BeaconState<
@@ -2863,6 +4219,9 @@ impl<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
     >
 {
     fn from(
@@ -2895,6 +4254,9 @@ impl<
         const SYNC_COMMITTEE_SIZE: usize,
         const BYTES_PER_LOGS_BLOOM: usize,
         const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
     >
     From<
         &'a mut deneb::BeaconState<
@@ -2923,6 +4285,9 @@ impl<
         SYNC_COMMITTEE_SIZE,
         BYTES_PER_LOGS_BLOOM,
         MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
     >
 {
     fn from(
@@ -2942,3 +4307,75 @@ impl<
         Self::Deneb(value)
     }
 }
+impl<
+        'a,
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    >
+    From<
+        &'a mut electra::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    >
+    for BeaconStateRefMut<
+        'a,
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >
+{
+    fn from(
+        value: &'a mut electra::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    ) -> Self {
+        Self::Electra(value)
+    }
+}
@@ -4,6 +4,8 @@ use crate::{
     bellatrix::blinded_beacon_block as bellatrix,
     capella::{blinded_beacon_block as capella, SignedBlsToExecutionChange},
     deneb::{blinded_beacon_block as deneb, polynomial_commitments::KzgCommitment},
+    electra::{blinded_beacon_block as electra, ExecutionRequests},
+    error::WrongFork,
     phase0::{
         Attestation, AttesterSlashing, Deposit, Eth1Data, ProposerSlashing, SignedVoluntaryExit,
     },
@@ -27,6 +29,13 @@ pub enum BlindedBeaconBlockBody<
     const MAX_EXTRA_DATA_BYTES: usize,
     const MAX_BLS_TO_EXECUTION_CHANGES: usize,
     const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
 > {
     Bellatrix(
         bellatrix::BlindedBeaconBlockBody<
@@ -70,6 +79,25 @@ pub enum BlindedBeaconBlockBody<
             MAX_BLOB_COMMITMENTS_PER_BLOCK,
         >,
     ),
+    Electra(
+        electra::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    ),
 }
 impl<
         const MAX_PROPOSER_SLASHINGS: usize,
@@ -83,6 +111,13 @@ impl<
         const MAX_EXTRA_DATA_BYTES: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     BlindedBeaconBlockBody<
         MAX_PROPOSER_SLASHINGS,
@@ -96,6 +131,13 @@ impl<
         MAX_EXTRA_DATA_BYTES,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     pub fn bellatrix(
@@ -138,6 +180,69 @@ impl<
             _ => None,
         }
     }
+    pub fn as_bellatrix(
+        &self,
+    ) -> Result<
+        &bellatrix::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Bellatrix(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Bellatrix, found: other.version() }),
+        }
+    }
+    pub fn as_bellatrix_mut(
+        &mut self,
+    ) -> Result<
+        &mut bellatrix::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Bellatrix(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Bellatrix, found: other.version() }),
+        }
+    }
+    pub fn into_bellatrix(
+        self,
+    ) -> Result<
+        bellatrix::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Bellatrix(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Bellatrix, found: other.version() }),
+        }
+    }
     pub fn capella(
         &self,
     ) -> Option<
@@ -180,6 +285,72 @@ impl<
             _ => None,
         }
     }
+    pub fn as_capella(
+        &self,
+    ) -> Result<
+        &capella::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Capella(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Capella, found: other.version() }),
+        }
+    }
+    pub fn as_capella_mut(
+        &mut self,
+    ) -> Result<
+        &mut capella::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Capella(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Capella, found: other.version() }),
+        }
+    }
+    pub fn into_capella(
+        self,
+    ) -> Result<
+        capella::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Capella(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Capella, found: other.version() }),
+        }
+    }
     pub fn deneb(
         &self,
     ) -> Option<
@@ -224,11 +395,214 @@ impl<
             _ => None,
         }
     }
+    pub fn as_deneb(
+        &self,
+    ) -> Result<
+        &deneb::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Deneb(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Deneb, found: other.version() }),
+        }
+    }
+    pub fn as_deneb_mut(
+        &mut self,
+    ) -> Result<
+        &mut deneb::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Deneb(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Deneb, found: other.version() }),
+        }
+    }
+    pub fn into_deneb(
+        self,
+    ) -> Result<
+        deneb::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Deneb(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Deneb, found: other.version() }),
+        }
+    }
+    pub fn electra(
+        &self,
+    ) -> Option<
+        &electra::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        match self {
+            Self::Electra(inner) => Some(inner),
+            _ => None,
+        }
+    }
+    pub fn electra_mut(
+        &mut self,
+    ) -> Option<
+        &mut electra::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        match self {
+            Self::Electra(inner) => Some(inner),
+            _ => None,
+        }
+    }
+    pub fn as_electra(
+        &self,
+    ) -> Result<
+        &electra::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Electra(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Electra, found: other.version() }),
+        }
+    }
+    pub fn as_electra_mut(
+        &mut self,
+    ) -> Result<
+        &mut electra::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Electra(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Electra, found: other.version() }),
+        }
+    }
+    pub fn into_electra(
+        self,
+    ) -> Result<
+        electra::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+        WrongFork,
+    > {
+        match self {
+            Self::Electra(inner) => Ok(inner),
+            other => Err(WrongFork { expected: Version::Electra, found: other.version() }),
+        }
+    }
     pub fn version(&self) -> Version {
         match self {
             Self::Bellatrix(_) => Version::Bellatrix,
             Self::Capella(_) => Version::Capella,
             Self::Deneb(_) => Version::Deneb,
+            Self::Electra(_) => Version::Electra,
         }
     }
     pub fn randao_reveal(&self) -> &BlsSignature {
@@ -236,6 +610,7 @@ impl<
             Self::Bellatrix(inner) => &inner.randao_reveal,
             Self::Capella(inner) => &inner.randao_reveal,
             Self::Deneb(inner) => &inner.randao_reveal,
+            Self::Electra(inner) => &inner.randao_reveal,
         }
     }
     pub fn randao_reveal_mut(&mut self) -> &mut BlsSignature {
@@ -243,6 +618,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.randao_reveal,
             Self::Capella(inner) => &mut inner.randao_reveal,
             Self::Deneb(inner) => &mut inner.randao_reveal,
+            Self::Electra(inner) => &mut inner.randao_reveal,
         }
     }
     pub fn eth1_data(&self) -> &Eth1Data {
@@ -250,6 +626,7 @@ impl<
             Self::Bellatrix(inner) => &inner.eth1_data,
             Self::Capella(inner) => &inner.eth1_data,
             Self::Deneb(inner) => &inner.eth1_data,
+            Self::Electra(inner) => &inner.eth1_data,
         }
     }
     pub fn eth1_data_mut(&mut self) -> &mut Eth1Data {
@@ -257,6 +634,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.eth1_data,
             Self::Capella(inner) => &mut inner.eth1_data,
             Self::Deneb(inner) => &mut inner.eth1_data,
+            Self::Electra(inner) => &mut inner.eth1_data,
         }
     }
     pub fn graffiti(&self) -> &Bytes32 {
@@ -264,6 +642,7 @@ impl<
             Self::Bellatrix(inner) => &inner.graffiti,
             Self::Capella(inner) => &inner.graffiti,
             Self::Deneb(inner) => &inner.graffiti,
+            Self::Electra(inner) => &inner.graffiti,
         }
     }
     pub fn graffiti_mut(&mut self) -> &mut Bytes32 {
@@ -271,6 +650,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.graffiti,
             Self::Capella(inner) => &mut inner.graffiti,
             Self::Deneb(inner) => &mut inner.graffiti,
+            Self::Electra(inner) => &mut inner.graffiti,
         }
     }
     pub fn proposer_slashings(&self) -> &List<ProposerSlashing, MAX_PROPOSER_SLASHINGS> {
@@ -278,6 +658,7 @@ impl<
             Self::Bellatrix(inner) => &inner.proposer_slashings,
             Self::Capella(inner) => &inner.proposer_slashings,
             Self::Deneb(inner) => &inner.proposer_slashings,
+            Self::Electra(inner) => &inner.proposer_slashings,
         }
     }
     pub fn proposer_slashings_mut(
@@ -287,42 +668,48 @@ impl<
             Self::Bellatrix(inner) => &mut inner.proposer_slashings,
             Self::Capella(inner) => &mut inner.proposer_slashings,
             Self::Deneb(inner) => &mut inner.proposer_slashings,
+            Self::Electra(inner) => &mut inner.proposer_slashings,
         }
     }
     pub fn attester_slashings(
         &self,
-    ) -> &List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS> {
+    ) -> Option<&List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS>> {
         match self {
-            Self::Bellatrix(inner) => &inner.attester_slashings,
-            Self::Capella(inner) => &inner.attester_slashings,
-            Self::Deneb(inner) => &inner.attester_slashings,
+            Self::Bellatrix(inner) => Some(&inner.attester_slashings),
+            Self::Capella(inner) => Some(&inner.attester_slashings),
+            Self::Deneb(inner) => Some(&inner.attester_slashings),
+            Self::Electra(_) => None,
         }
     }
     pub fn attester_slashings_mut(
         &mut self,
-    ) -> &mut List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS> {
+    ) -> Option<&mut List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS>>
+    {
         match self {
-            Self::Bellatrix(inner) => &mut inner.attester_slashings,
-            Self::Capella(inner) => &mut inner.attester_slashings,
-            Self::Deneb(inner) => &mut inner.attester_slashings,
+            Self::Bellatrix(inner) => Some(&mut inner.attester_slashings),
+            Self::Capella(inner) => Some(&mut inner.attester_slashings),
+            Self::Deneb(inner) => Some(&mut inner.attester_slashings),
+            Self::Electra(_) => None,
         }
     }
     pub fn attestations(
         &self,
-    ) -> &List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS> {
+    ) -> Option<&List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS>> {
         match self {
-            Self::Bellatrix(inner) => &inner.attestations,
-            Self::Capella(inner) => &inner.attestations,
-            Self::Deneb(inner) => &inner.attestations,
+            Self::Bellatrix(inner) => Some(&inner.attestations),
+            Self::Capella(inner) => Some(&inner.attestations),
+            Self::Deneb(inner) => Some(&inner.attestations),
+            Self::Electra(_) => None,
         }
     }
     pub fn attestations_mut(
         &mut self,
-    ) -> &mut List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS> {
+    ) -> Option<&mut List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS>> {
         match self {
-            Self::Bellatrix(inner) => &mut inner.attestations,
-            Self::Capella(inner) => &mut inner.attestations,
-            Self::Deneb(inner) => &mut inner.attestations,
+            Self::Bellatrix(inner) => Some(&mut inner.attestations),
+            Self::Capella(inner) => Some(&mut inner.attestations),
+            Self::Deneb(inner) => Some(&mut inner.attestations),
+            Self::Electra(_) => None,
         }
     }
     pub fn deposits(&self) -> &List<Deposit, MAX_DEPOSITS> {
@@ -330,6 +717,7 @@ impl<
             Self::Bellatrix(inner) => &inner.deposits,
             Self::Capella(inner) => &inner.deposits,
             Self::Deneb(inner) => &inner.deposits,
+            Self::Electra(inner) => &inner.deposits,
         }
     }
     pub fn deposits_mut(&mut self) -> &mut List<Deposit, MAX_DEPOSITS> {
@@ -337,6 +725,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.deposits,
             Self::Capella(inner) => &mut inner.deposits,
             Self::Deneb(inner) => &mut inner.deposits,
+            Self::Electra(inner) => &mut inner.deposits,
         }
     }
     pub fn voluntary_exits(&self) -> &List<SignedVoluntaryExit, MAX_VOLUNTARY_EXITS> {
@@ -344,6 +733,7 @@ impl<
             Self::Bellatrix(inner) => &inner.voluntary_exits,
             Self::Capella(inner) => &inner.voluntary_exits,
             Self::Deneb(inner) => &inner.voluntary_exits,
+            Self::Electra(inner) => &inner.voluntary_exits,
         }
     }
     pub fn voluntary_exits_mut(&mut self) -> &mut List<SignedVoluntaryExit, MAX_VOLUNTARY_EXITS> {
@@ -351,6 +741,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.voluntary_exits,
             Self::Capella(inner) => &mut inner.voluntary_exits,
             Self::Deneb(inner) => &mut inner.voluntary_exits,
+            Self::Electra(inner) => &mut inner.voluntary_exits,
         }
     }
     pub fn sync_aggregate(&self) -> &SyncAggregate<SYNC_COMMITTEE_SIZE> {
@@ -358,6 +749,7 @@ impl<
             Self::Bellatrix(inner) => &inner.sync_aggregate,
             Self::Capella(inner) => &inner.sync_aggregate,
             Self::Deneb(inner) => &inner.sync_aggregate,
+            Self::Electra(inner) => &inner.sync_aggregate,
         }
     }
     pub fn sync_aggregate_mut(&mut self) -> &mut SyncAggregate<SYNC_COMMITTEE_SIZE> {
@@ -365,6 +757,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.sync_aggregate,
             Self::Capella(inner) => &mut inner.sync_aggregate,
             Self::Deneb(inner) => &mut inner.sync_aggregate,
+            Self::Electra(inner) => &mut inner.sync_aggregate,
         }
     }
     pub fn execution_payload_header(
@@ -374,6 +767,7 @@ impl<
             Self::Bellatrix(inner) => From::from(&inner.execution_payload_header),
             Self::Capella(inner) => From::from(&inner.execution_payload_header),
             Self::Deneb(inner) => From::from(&inner.execution_payload_header),
+            Self::Electra(inner) => From::from(&inner.execution_payload_header),
         }
     }
     pub fn execution_payload_header_mut(
@@ -383,6 +777,7 @@ impl<
             Self::Bellatrix(inner) => From::from(&mut inner.execution_payload_header),
             Self::Capella(inner) => From::from(&mut inner.execution_payload_header),
             Self::Deneb(inner) => From::from(&mut inner.execution_payload_header),
+            Self::Electra(inner) => From::from(&mut inner.execution_payload_header),
         }
     }
     pub fn bls_to_execution_changes(
@@ -392,6 +787,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(inner) => Some(&inner.bls_to_execution_changes),
             Self::Deneb(inner) => Some(&inner.bls_to_execution_changes),
+            Self::Electra(inner) => Some(&inner.bls_to_execution_changes),
         }
     }
     pub fn bls_to_execution_changes_mut(
@@ -401,6 +797,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(inner) => Some(&mut inner.bls_to_execution_changes),
             Self::Deneb(inner) => Some(&mut inner.bls_to_execution_changes),
+            Self::Electra(inner) => Some(&mut inner.bls_to_execution_changes),
         }
     }
     pub fn blob_kzg_commitments(
@@ -410,6 +807,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(_) => None,
             Self::Deneb(inner) => Some(&inner.blob_kzg_commitments),
+            Self::Electra(inner) => Some(&inner.blob_kzg_commitments),
         }
     }
     pub fn blob_kzg_commitments_mut(
@@ -419,6 +817,39 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(_) => None,
             Self::Deneb(inner) => Some(&mut inner.blob_kzg_commitments),
+            Self::Electra(inner) => Some(&mut inner.blob_kzg_commitments),
+        }
+    }
+    pub fn execution_requests(
+        &self,
+    ) -> Option<
+        &ExecutionRequests<
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        match self {
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.execution_requests),
+        }
+    }
+    pub fn execution_requests_mut(
+        &mut self,
+    ) -> Option<
+        &mut ExecutionRequests<
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        match self {
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.execution_requests),
         }
     }
 }
@@ -435,6 +866,13 @@ impl<
         const MAX_EXTRA_DATA_BYTES: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     > serde::Deserialize<'de>
     for BlindedBeaconBlockBody<
         MAX_PROPOSER_SLASHINGS,
@@ -448,6 +886,13 @@ impl<
         MAX_EXTRA_DATA_BYTES,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -455,6 +900,9 @@ impl<
         D: serde::Deserializer<'de>,
     {
         let value = serde_json::Value::deserialize(deserializer)?;
+        if let Ok(inner) = <_ as serde::Deserialize>::deserialize(&value) {
+            return Ok(Self::Electra(inner));
+        }
         if let Ok(inner) = <_ as serde::Deserialize>::deserialize(&value) {
             return Ok(Self::Deneb(inner));
         }
@@ -482,6 +930,13 @@ pub enum BlindedBeaconBlockBodyRef<
     const MAX_EXTRA_DATA_BYTES: usize,
     const MAX_BLS_TO_EXECUTION_CHANGES: usize,
     const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
 > {
     Bellatrix(
         &'a bellatrix::BlindedBeaconBlockBody<
@@ -525,6 +980,25 @@ pub enum BlindedBeaconBlockBodyRef<
             MAX_BLOB_COMMITMENTS_PER_BLOCK,
         >,
     ),
+    Electra(
+        &'a electra::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    ),
 }
 impl<
         'a,
@@ -539,6 +1013,13 @@ impl<
         const MAX_EXTRA_DATA_BYTES: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     BlindedBeaconBlockBodyRef<
         'a,
@@ -553,6 +1034,13 @@ impl<
         MAX_EXTRA_DATA_BYTES,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     pub fn bellatrix(
@@ -618,11 +1106,38 @@ impl<
             _ => None,
         }
     }
+    pub fn electra(
+        &self,
+    ) -> Option<
+        &electra::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        match self {
+            Self::Electra(inner) => Some(inner),
+            _ => None,
+        }
+    }
     pub fn version(&self) -> Version {
         match self {
             Self::Bellatrix(_) => Version::Bellatrix,
             Self::Capella(_) => Version::Capella,
             Self::Deneb(_) => Version::Deneb,
+            Self::Electra(_) => Version::Electra,
         }
     }
     pub fn randao_reveal(&self) -> &BlsSignature {
@@ -630,6 +1145,7 @@ impl<
             Self::Bellatrix(inner) => &inner.randao_reveal,
             Self::Capella(inner) => &inner.randao_reveal,
             Self::Deneb(inner) => &inner.randao_reveal,
+            Self::Electra(inner) => &inner.randao_reveal,
         }
     }
     pub fn eth1_data(&self) -> &Eth1Data {
@@ -637,6 +1153,7 @@ impl<
             Self::Bellatrix(inner) => &inner.eth1_data,
             Self::Capella(inner) => &inner.eth1_data,
             Self::Deneb(inner) => &inner.eth1_data,
+            Self::Electra(inner) => &inner.eth1_data,
         }
     }
     pub fn graffiti(&self) -> &Bytes32 {
@@ -644,6 +1161,7 @@ impl<
             Self::Bellatrix(inner) => &inner.graffiti,
             Self::Capella(inner) => &inner.graffiti,
             Self::Deneb(inner) => &inner.graffiti,
+            Self::Electra(inner) => &inner.graffiti,
         }
     }
     pub fn proposer_slashings(&self) -> &List<ProposerSlashing, MAX_PROPOSER_SLASHINGS> {
@@ -651,24 +1169,27 @@ impl<
             Self::Bellatrix(inner) => &inner.proposer_slashings,
             Self::Capella(inner) => &inner.proposer_slashings,
             Self::Deneb(inner) => &inner.proposer_slashings,
+            Self::Electra(inner) => &inner.proposer_slashings,
         }
     }
     pub fn attester_slashings(
         &self,
-    ) -> &List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS> {
+    ) -> Option<&List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS>> {
         match self {
-            Self::Bellatrix(inner) => &inner.attester_slashings,
-            Self::Capella(inner) => &inner.attester_slashings,
-            Self::Deneb(inner) => &inner.attester_slashings,
+            Self::Bellatrix(inner) => Some(&inner.attester_slashings),
+            Self::Capella(inner) => Some(&inner.attester_slashings),
+            Self::Deneb(inner) => Some(&inner.attester_slashings),
+            Self::Electra(_) => None,
         }
     }
     pub fn attestations(
         &self,
-    ) -> &List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS> {
+    ) -> Option<&List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS>> {
         match self {
-            Self::Bellatrix(inner) => &inner.attestations,
-            Self::Capella(inner) => &inner.attestations,
-            Self::Deneb(inner) => &inner.attestations,
+            Self::Bellatrix(inner) => Some(&inner.attestations),
+            Self::Capella(inner) => Some(&inner.attestations),
+            Self::Deneb(inner) => Some(&inner.attestations),
+            Self::Electra(_) => None,
         }
     }
     pub fn deposits(&self) -> &List<Deposit, MAX_DEPOSITS> {
@@ -676,6 +1197,7 @@ impl<
             Self::Bellatrix(inner) => &inner.deposits,
             Self::Capella(inner) => &inner.deposits,
             Self::Deneb(inner) => &inner.deposits,
+            Self::Electra(inner) => &inner.deposits,
         }
     }
     pub fn voluntary_exits(&self) -> &List<SignedVoluntaryExit, MAX_VOLUNTARY_EXITS> {
@@ -683,6 +1205,7 @@ impl<
             Self::Bellatrix(inner) => &inner.voluntary_exits,
             Self::Capella(inner) => &inner.voluntary_exits,
             Self::Deneb(inner) => &inner.voluntary_exits,
+            Self::Electra(inner) => &inner.voluntary_exits,
         }
     }
     pub fn sync_aggregate(&self) -> &SyncAggregate<SYNC_COMMITTEE_SIZE> {
@@ -690,6 +1213,7 @@ impl<
             Self::Bellatrix(inner) => &inner.sync_aggregate,
             Self::Capella(inner) => &inner.sync_aggregate,
             Self::Deneb(inner) => &inner.sync_aggregate,
+            Self::Electra(inner) => &inner.sync_aggregate,
         }
     }
     pub fn execution_payload_header(
@@ -699,6 +1223,7 @@ impl<
             Self::Bellatrix(inner) => From::from(&inner.execution_payload_header),
             Self::Capella(inner) => From::from(&inner.execution_payload_header),
             Self::Deneb(inner) => From::from(&inner.execution_payload_header),
+            Self::Electra(inner) => From::from(&inner.execution_payload_header),
         }
     }
     pub fn bls_to_execution_changes(
@@ -708,6 +1233,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(inner) => Some(&inner.bls_to_execution_changes),
             Self::Deneb(inner) => Some(&inner.bls_to_execution_changes),
+            Self::Electra(inner) => Some(&inner.bls_to_execution_changes),
         }
     }
     pub fn blob_kzg_commitments(
@@ -717,6 +1243,23 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(_) => None,
             Self::Deneb(inner) => Some(&inner.blob_kzg_commitments),
+            Self::Electra(inner) => Some(&inner.blob_kzg_commitments),
+        }
+    }
+    pub fn execution_requests(
+        &self,
+    ) -> Option<
+        &ExecutionRequests<
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        match self {
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.execution_requests),
         }
     }
 }
@@ -733,6 +1276,13 @@ impl<
         const MAX_EXTRA_DATA_BYTES: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     From<
         &'a bellatrix::BlindedBeaconBlockBody<
@@ -760,6 +1310,13 @@ impl<
         MAX_EXTRA_DATA_BYTES,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     fn from(
@@ -791,6 +1348,13 @@ impl<
         const MAX_EXTRA_DATA_BYTES: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     From<
         &'a capella::BlindedBeaconBlockBody<
@@ -819,6 +1383,13 @@ impl<
         MAX_EXTRA_DATA_BYTES,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     fn from(
@@ -851,6 +1422,13 @@ impl<
         const MAX_EXTRA_DATA_BYTES: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     From<
         &'a deneb::BlindedBeaconBlockBody<
@@ -880,6 +1458,13 @@ impl<
         MAX_EXTRA_DATA_BYTES,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     fn from(
@@ -900,6 +1485,90 @@ impl<
         Self::Deneb(value)
     }
 }
+impl<
+        'a,
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+    >
+    From<
+        &'a electra::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    >
+    for BlindedBeaconBlockBodyRef<
+        'a,
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >
+{
+    fn from(
+        value: &'a electra::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    ) -> Self {
+        Self::Electra(value)
+    }
+}
 #[derive(Debug, PartialEq, Eq, HashTreeRoot)]
 #[ssz(transparent)]
 pub enum BlindedBeaconBlockBodyRefMut<
@@ -915,6 +1584,13 @@ pub enum BlindedBeaconBlockBodyRefMut<
     const MAX_EXTRA_DATA_BYTES: usize,
     const MAX_BLS_TO_EXECUTION_CHANGES: usize,
     const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+    const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+    const MAX_ATTESTATIONS_ELECTRA: usize,
+    const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
 > {
     Bellatrix(
         &'a mut bellatrix::BlindedBeaconBlockBody<
@@ -958,6 +1634,25 @@ pub enum BlindedBeaconBlockBodyRefMut<
             MAX_BLOB_COMMITMENTS_PER_BLOCK,
         >,
     ),
+    Electra(
+        &'a mut electra::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    ),
 }
 impl<
         'a,
@@ -972,6 +1667,13 @@ impl<
         const MAX_EXTRA_DATA_BYTES: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     BlindedBeaconBlockBodyRefMut<
         'a,
@@ -986,6 +1688,13 @@ impl<
         MAX_EXTRA_DATA_BYTES,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     pub fn bellatrix(
@@ -1114,11 +1823,64 @@ impl<
             _ => None,
         }
     }
+    pub fn electra(
+        &self,
+    ) -> Option<
+        &electra::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        match self {
+            Self::Electra(inner) => Some(inner),
+            _ => None,
+        }
+    }
+    pub fn electra_mut(
+        &mut self,
+    ) -> Option<
+        &mut electra::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        match self {
+            Self::Electra(inner) => Some(inner),
+            _ => None,
+        }
+    }
     pub fn version(&self) -> Version {
         match self {
             Self::Bellatrix(_) => Version::Bellatrix,
             Self::Capella(_) => Version::Capella,
             Self::Deneb(_) => Version::Deneb,
+            Self::Electra(_) => Version::Electra,
         }
     }
     pub fn randao_reveal(&self) -> &BlsSignature {
@@ -1126,6 +1888,7 @@ impl<
             Self::Bellatrix(inner) => &inner.randao_reveal,
             Self::Capella(inner) => &inner.randao_reveal,
             Self::Deneb(inner) => &inner.randao_reveal,
+            Self::Electra(inner) => &inner.randao_reveal,
         }
     }
     pub fn randao_reveal_mut(&mut self) -> &mut BlsSignature {
@@ -1133,6 +1896,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.randao_reveal,
             Self::Capella(inner) => &mut inner.randao_reveal,
             Self::Deneb(inner) => &mut inner.randao_reveal,
+            Self::Electra(inner) => &mut inner.randao_reveal,
         }
     }
     pub fn eth1_data(&self) -> &Eth1Data {
@@ -1140,6 +1904,7 @@ impl<
             Self::Bellatrix(inner) => &inner.eth1_data,
             Self::Capella(inner) => &inner.eth1_data,
             Self::Deneb(inner) => &inner.eth1_data,
+            Self::Electra(inner) => &inner.eth1_data,
         }
     }
     pub fn eth1_data_mut(&mut self) -> &mut Eth1Data {
@@ -1147,6 +1912,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.eth1_data,
             Self::Capella(inner) => &mut inner.eth1_data,
             Self::Deneb(inner) => &mut inner.eth1_data,
+            Self::Electra(inner) => &mut inner.eth1_data,
         }
     }
     pub fn graffiti(&self) -> &Bytes32 {
@@ -1154,6 +1920,7 @@ impl<
             Self::Bellatrix(inner) => &inner.graffiti,
             Self::Capella(inner) => &inner.graffiti,
             Self::Deneb(inner) => &inner.graffiti,
+            Self::Electra(inner) => &inner.graffiti,
         }
     }
     pub fn graffiti_mut(&mut self) -> &mut Bytes32 {
@@ -1161,6 +1928,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.graffiti,
             Self::Capella(inner) => &mut inner.graffiti,
             Self::Deneb(inner) => &mut inner.graffiti,
+            Self::Electra(inner) => &mut inner.graffiti,
         }
     }
     pub fn proposer_slashings(&self) -> &List<ProposerSlashing, MAX_PROPOSER_SLASHINGS> {
@@ -1168,6 +1936,7 @@ impl<
             Self::Bellatrix(inner) => &inner.proposer_slashings,
             Self::Capella(inner) => &inner.proposer_slashings,
             Self::Deneb(inner) => &inner.proposer_slashings,
+            Self::Electra(inner) => &inner.proposer_slashings,
         }
     }
     pub fn proposer_slashings_mut(
@@ -1177,42 +1946,48 @@ impl<
             Self::Bellatrix(inner) => &mut inner.proposer_slashings,
             Self::Capella(inner) => &mut inner.proposer_slashings,
             Self::Deneb(inner) => &mut inner.proposer_slashings,
+            Self::Electra(inner) => &mut inner.proposer_slashings,
         }
     }
     pub fn attester_slashings(
         &self,
-    ) -> &List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS> {
+    ) -> Option<&List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS>> {
         match self {
-            Self::Bellatrix(inner) => &inner.attester_slashings,
-            Self::Capella(inner) => &inner.attester_slashings,
-            Self::Deneb(inner) => &inner.attester_slashings,
+            Self::Bellatrix(inner) => Some(&inner.attester_slashings),
+            Self::Capella(inner) => Some(&inner.attester_slashings),
+            Self::Deneb(inner) => Some(&inner.attester_slashings),
+            Self::Electra(_) => None,
         }
     }
     pub fn attester_slashings_mut(
         &mut self,
-    ) -> &mut List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS> {
+    ) -> Option<&mut List<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTER_SLASHINGS>>
+    {
         match self {
-            Self::Bellatrix(inner) => &mut inner.attester_slashings,
-            Self::Capella(inner) => &mut inner.attester_slashings,
-            Self::Deneb(inner) => &mut inner.attester_slashings,
+            Self::Bellatrix(inner) => Some(&mut inner.attester_slashings),
+            Self::Capella(inner) => Some(&mut inner.attester_slashings),
+            Self::Deneb(inner) => Some(&mut inner.attester_slashings),
+            Self::Electra(_) => None,
         }
     }
     pub fn attestations(
         &self,
-    ) -> &List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS> {
+    ) -> Option<&List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS>> {
         match self {
-            Self::Bellatrix(inner) => &inner.attestations,
-            Self::Capella(inner) => &inner.attestations,
-            Self::Deneb(inner) => &inner.attestations,
+            Self::Bellatrix(inner) => Some(&inner.attestations),
+            Self::Capella(inner) => Some(&inner.attestations),
+            Self::Deneb(inner) => Some(&inner.attestations),
+            Self::Electra(_) => None,
         }
     }
     pub fn attestations_mut(
         &mut self,
-    ) -> &mut List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS> {
+    ) -> Option<&mut List<Attestation<MAX_VALIDATORS_PER_COMMITTEE>, MAX_ATTESTATIONS>> {
         match self {
-            Self::Bellatrix(inner) => &mut inner.attestations,
-            Self::Capella(inner) => &mut inner.attestations,
-            Self::Deneb(inner) => &mut inner.attestations,
+            Self::Bellatrix(inner) => Some(&mut inner.attestations),
+            Self::Capella(inner) => Some(&mut inner.attestations),
+            Self::Deneb(inner) => Some(&mut inner.attestations),
+            Self::Electra(_) => None,
         }
     }
     pub fn deposits(&self) -> &List<Deposit, MAX_DEPOSITS> {
@@ -1220,6 +1995,7 @@ impl<
             Self::Bellatrix(inner) => &inner.deposits,
             Self::Capella(inner) => &inner.deposits,
             Self::Deneb(inner) => &inner.deposits,
+            Self::Electra(inner) => &inner.deposits,
         }
     }
     pub fn deposits_mut(&mut self) -> &mut List<Deposit, MAX_DEPOSITS> {
@@ -1227,6 +2003,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.deposits,
             Self::Capella(inner) => &mut inner.deposits,
             Self::Deneb(inner) => &mut inner.deposits,
+            Self::Electra(inner) => &mut inner.deposits,
         }
     }
     pub fn voluntary_exits(&self) -> &List<SignedVoluntaryExit, MAX_VOLUNTARY_EXITS> {
@@ -1234,6 +2011,7 @@ impl<
             Self::Bellatrix(inner) => &inner.voluntary_exits,
             Self::Capella(inner) => &inner.voluntary_exits,
             Self::Deneb(inner) => &inner.voluntary_exits,
+            Self::Electra(inner) => &inner.voluntary_exits,
         }
     }
     pub fn voluntary_exits_mut(&mut self) -> &mut List<SignedVoluntaryExit, MAX_VOLUNTARY_EXITS> {
@@ -1241,6 +2019,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.voluntary_exits,
             Self::Capella(inner) => &mut inner.voluntary_exits,
             Self::Deneb(inner) => &mut inner.voluntary_exits,
+            Self::Electra(inner) => &mut inner.voluntary_exits,
         }
     }
     pub fn sync_aggregate(&self) -> &SyncAggregate<SYNC_COMMITTEE_SIZE> {
@@ -1248,6 +2027,7 @@ impl<
             Self::Bellatrix(inner) => &inner.sync_aggregate,
             Self::Capella(inner) => &inner.sync_aggregate,
             Self::Deneb(inner) => &inner.sync_aggregate,
+            Self::Electra(inner) => &inner.sync_aggregate,
         }
     }
     pub fn sync_aggregate_mut(&mut self) -> &mut SyncAggregate<SYNC_COMMITTEE_SIZE> {
@@ -1255,6 +2035,7 @@ impl<
             Self::Bellatrix(inner) => &mut inner.sync_aggregate,
             Self::Capella(inner) => &mut inner.sync_aggregate,
             Self::Deneb(inner) => &mut inner.sync_aggregate,
+            Self::Electra(inner) => &mut inner.sync_aggregate,
         }
     }
     pub fn execution_payload_header(
@@ -1264,6 +2045,7 @@ impl<
             Self::Bellatrix(inner) => From::from(&inner.execution_payload_header),
             Self::Capella(inner) => From::from(&inner.execution_payload_header),
             Self::Deneb(inner) => From::from(&inner.execution_payload_header),
+            Self::Electra(inner) => From::from(&inner.execution_payload_header),
         }
     }
     pub fn execution_payload_header_mut(
@@ -1273,6 +2055,7 @@ impl<
             Self::Bellatrix(inner) => From::from(&mut inner.execution_payload_header),
             Self::Capella(inner) => From::from(&mut inner.execution_payload_header),
             Self::Deneb(inner) => From::from(&mut inner.execution_payload_header),
+            Self::Electra(inner) => From::from(&mut inner.execution_payload_header),
         }
     }
     pub fn bls_to_execution_changes(
@@ -1282,6 +2065,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(inner) => Some(&inner.bls_to_execution_changes),
             Self::Deneb(inner) => Some(&inner.bls_to_execution_changes),
+            Self::Electra(inner) => Some(&inner.bls_to_execution_changes),
         }
     }
     pub fn bls_to_execution_changes_mut(
@@ -1291,6 +2075,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(inner) => Some(&mut inner.bls_to_execution_changes),
             Self::Deneb(inner) => Some(&mut inner.bls_to_execution_changes),
+            Self::Electra(inner) => Some(&mut inner.bls_to_execution_changes),
         }
     }
     pub fn blob_kzg_commitments(
@@ -1300,6 +2085,7 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(_) => None,
             Self::Deneb(inner) => Some(&inner.blob_kzg_commitments),
+            Self::Electra(inner) => Some(&inner.blob_kzg_commitments),
         }
     }
     pub fn blob_kzg_commitments_mut(
@@ -1309,6 +2095,39 @@ impl<
             Self::Bellatrix(_) => None,
             Self::Capella(_) => None,
             Self::Deneb(inner) => Some(&mut inner.blob_kzg_commitments),
+            Self::Electra(inner) => Some(&mut inner.blob_kzg_commitments),
+        }
+    }
+    pub fn execution_requests(
+        &self,
+    ) -> Option<
+        &ExecutionRequests<
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        match self {
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&inner.execution_requests),
+        }
+    }
+    pub fn execution_requests_mut(
+        &mut self,
+    ) -> Option<
+        &mut ExecutionRequests<
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    > {
+        match self {
+            Self::Bellatrix(_) => None,
+            Self::Capella(_) => None,
+            Self::Deneb(_) => None,
+            Self::Electra(inner) => Some(&mut inner.execution_requests),
         }
     }
 }
@@ -1325,6 +2144,13 @@ impl<
         const MAX_EXTRA_DATA_BYTES: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     From<
         &'a mut bellatrix::BlindedBeaconBlockBody<
@@ -1352,6 +2178,13 @@ impl<
         MAX_EXTRA_DATA_BYTES,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     fn from(
@@ -1383,6 +2216,13 @@ impl<
         const MAX_EXTRA_DATA_BYTES: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     From<
         &'a mut capella::BlindedBeaconBlockBody<
@@ -1411,6 +2251,13 @@ impl<
         MAX_EXTRA_DATA_BYTES,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     fn from(
@@ -1443,6 +2290,13 @@ impl<
         const MAX_EXTRA_DATA_BYTES: usize,
         const MAX_BLS_TO_EXECUTION_CHANGES: usize,
         const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
     >
     From<
         &'a mut deneb::BlindedBeaconBlockBody<
@@ -1472,6 +2326,13 @@ impl<
         MAX_EXTRA_DATA_BYTES,
         MAX_BLS_TO_EXECUTION_CHANGES,
         MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
     >
 {
     fn from(
@@ -1492,3 +2353,87 @@ impl<
         Self::Deneb(value)
     }
 }
+impl<
+        'a,
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+    >
+    From<
+        &'a mut electra::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    >
+    for BlindedBeaconBlockBodyRefMut<
+        'a,
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >
+{
+    fn from(
+        value: &'a mut electra::BlindedBeaconBlockBody<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+        >,
+    ) -> Self {
+        Self::Electra(value)
+    }
+}
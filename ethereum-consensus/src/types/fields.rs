@@ -0,0 +1,1229 @@
+//! Accessors for the fields of states and blocks regardless of fork, implemented for the types of
+//! every fork and for the polymorphic types of this module.
+//!
+//! Fields present in every fork have plain accessors and the others return `None` in the forks
+//! without them. Lists are returned as slices, so that code written against these traits does not
+//! depend on the preset either. Fields whose types change shape across forks (like attestations)
+//! are left to the fork types.
+use crate::{
+    altair, bellatrix,
+    capella::{self, HistoricalSummary, SignedBlsToExecutionChange, Withdrawal},
+    deneb::{self, polynomial_commitments::KzgCommitment},
+    electra::{
+        self, ConsolidationRequest, DepositRequest, PendingConsolidation, PendingDeposit,
+        PendingPartialWithdrawal, WithdrawalRequest,
+    },
+    phase0::{
+        self, BeaconBlockHeader, Checkpoint, Deposit, Eth1Data, Fork, ProposerSlashing,
+        SignedVoluntaryExit, Validator, JUSTIFICATION_BITS_LENGTH,
+    },
+    primitives::{
+        BlsSignature, Bytes32, Epoch, ExecutionAddress, Gwei, Hash32, ParticipationFlags, Root,
+        Slot, ValidatorIndex, WithdrawalIndex,
+    },
+    ssz::prelude::*,
+    types, Fork as Version,
+};
+
+pub trait BeaconStateFields {
+    fn version(&self) -> Version;
+    fn genesis_time(&self) -> u64;
+    fn genesis_validators_root(&self) -> Root;
+    fn slot(&self) -> Slot;
+    fn fork(&self) -> &Fork;
+    fn latest_block_header(&self) -> &BeaconBlockHeader;
+    fn block_roots(&self) -> &[Root];
+    fn state_roots(&self) -> &[Root];
+    fn historical_roots(&self) -> &[Root];
+    fn eth1_data(&self) -> &Eth1Data;
+    fn eth1_data_votes(&self) -> &[Eth1Data];
+    fn eth1_deposit_index(&self) -> u64;
+    fn validators(&self) -> &[Validator];
+    fn balances(&self) -> &[Gwei];
+    fn randao_mixes(&self) -> &[Bytes32];
+    fn slashings(&self) -> &[Gwei];
+    fn justification_bits(&self) -> &Bitvector<JUSTIFICATION_BITS_LENGTH>;
+    fn previous_justified_checkpoint(&self) -> &Checkpoint;
+    fn current_justified_checkpoint(&self) -> &Checkpoint;
+    fn finalized_checkpoint(&self) -> &Checkpoint;
+
+    fn previous_epoch_participation(&self) -> Option<&[ParticipationFlags]> {
+        None
+    }
+    fn current_epoch_participation(&self) -> Option<&[ParticipationFlags]> {
+        None
+    }
+    fn inactivity_scores(&self) -> Option<&[u64]> {
+        None
+    }
+    fn latest_execution_payload_header(&self) -> Option<&dyn ExecutionPayloadFields> {
+        None
+    }
+    fn next_withdrawal_index(&self) -> Option<WithdrawalIndex> {
+        None
+    }
+    fn next_withdrawal_validator_index(&self) -> Option<ValidatorIndex> {
+        None
+    }
+    fn historical_summaries(&self) -> Option<&[HistoricalSummary]> {
+        None
+    }
+    fn deposit_requests_start_index(&self) -> Option<u64> {
+        None
+    }
+    fn deposit_balance_to_consume(&self) -> Option<Gwei> {
+        None
+    }
+    fn exit_balance_to_consume(&self) -> Option<Gwei> {
+        None
+    }
+    fn earliest_exit_epoch(&self) -> Option<Epoch> {
+        None
+    }
+    fn consolidation_balance_to_consume(&self) -> Option<Gwei> {
+        None
+    }
+    fn earliest_consolidation_epoch(&self) -> Option<Epoch> {
+        None
+    }
+    fn pending_deposits(&self) -> Option<&[PendingDeposit]> {
+        None
+    }
+    fn pending_partial_withdrawals(&self) -> Option<&[PendingPartialWithdrawal]> {
+        None
+    }
+    fn pending_consolidations(&self) -> Option<&[PendingConsolidation]> {
+        None
+    }
+}
+
+/// Mutable access to the fields of [`BeaconStateFields`] that hold values. Lists can be changed
+/// in place but not resized.
+pub trait BeaconStateFieldsMut: BeaconStateFields {
+    fn genesis_time_mut(&mut self) -> &mut u64;
+    fn genesis_validators_root_mut(&mut self) -> &mut Root;
+    fn slot_mut(&mut self) -> &mut Slot;
+    fn fork_mut(&mut self) -> &mut Fork;
+    fn latest_block_header_mut(&mut self) -> &mut BeaconBlockHeader;
+    fn block_roots_mut(&mut self) -> &mut [Root];
+    fn state_roots_mut(&mut self) -> &mut [Root];
+    fn eth1_data_mut(&mut self) -> &mut Eth1Data;
+    fn eth1_deposit_index_mut(&mut self) -> &mut u64;
+    fn validators_mut(&mut self) -> &mut [Validator];
+    fn balances_mut(&mut self) -> &mut [Gwei];
+    fn randao_mixes_mut(&mut self) -> &mut [Bytes32];
+    fn slashings_mut(&mut self) -> &mut [Gwei];
+    fn justification_bits_mut(&mut self) -> &mut Bitvector<JUSTIFICATION_BITS_LENGTH>;
+    fn previous_justified_checkpoint_mut(&mut self) -> &mut Checkpoint;
+    fn current_justified_checkpoint_mut(&mut self) -> &mut Checkpoint;
+    fn finalized_checkpoint_mut(&mut self) -> &mut Checkpoint;
+
+    fn previous_epoch_participation_mut(&mut self) -> Option<&mut [ParticipationFlags]> {
+        None
+    }
+    fn current_epoch_participation_mut(&mut self) -> Option<&mut [ParticipationFlags]> {
+        None
+    }
+    fn inactivity_scores_mut(&mut self) -> Option<&mut [u64]> {
+        None
+    }
+    fn next_withdrawal_index_mut(&mut self) -> Option<&mut WithdrawalIndex> {
+        None
+    }
+    fn next_withdrawal_validator_index_mut(&mut self) -> Option<&mut ValidatorIndex> {
+        None
+    }
+    fn deposit_balance_to_consume_mut(&mut self) -> Option<&mut Gwei> {
+        None
+    }
+    fn exit_balance_to_consume_mut(&mut self) -> Option<&mut Gwei> {
+        None
+    }
+    fn earliest_exit_epoch_mut(&mut self) -> Option<&mut Epoch> {
+        None
+    }
+    fn consolidation_balance_to_consume_mut(&mut self) -> Option<&mut Gwei> {
+        None
+    }
+    fn earliest_consolidation_epoch_mut(&mut self) -> Option<&mut Epoch> {
+        None
+    }
+}
+
+/// The fields shared by execution payloads and execution payload headers.
+pub trait ExecutionPayloadFields {
+    fn parent_hash(&self) -> &Hash32;
+    fn fee_recipient(&self) -> &ExecutionAddress;
+    fn state_root(&self) -> &Bytes32;
+    fn receipts_root(&self) -> &Bytes32;
+    fn logs_bloom(&self) -> &[u8];
+    fn prev_randao(&self) -> &Bytes32;
+    fn block_number(&self) -> u64;
+    fn gas_limit(&self) -> u64;
+    fn gas_used(&self) -> u64;
+    fn timestamp(&self) -> u64;
+    fn extra_data(&self) -> &[u8];
+    fn base_fee_per_gas(&self) -> &U256;
+    fn block_hash(&self) -> &Hash32;
+
+    /// The withdrawals of a payload; headers only commit to them.
+    fn withdrawals(&self) -> Option<&[Withdrawal]> {
+        None
+    }
+    fn blob_gas_used(&self) -> Option<u64> {
+        None
+    }
+    fn excess_blob_gas(&self) -> Option<u64> {
+        None
+    }
+}
+
+pub trait BeaconBlockBodyFields {
+    fn randao_reveal(&self) -> &BlsSignature;
+    fn eth1_data(&self) -> &Eth1Data;
+    fn graffiti(&self) -> &Bytes32;
+    fn proposer_slashings(&self) -> &[ProposerSlashing];
+    fn deposits(&self) -> &[Deposit];
+    fn voluntary_exits(&self) -> &[SignedVoluntaryExit];
+
+    fn execution_payload(&self) -> Option<&dyn ExecutionPayloadFields> {
+        None
+    }
+    fn bls_to_execution_changes(&self) -> Option<&[SignedBlsToExecutionChange]> {
+        None
+    }
+    fn blob_kzg_commitments(&self) -> Option<&[KzgCommitment]> {
+        None
+    }
+    fn deposit_requests(&self) -> Option<&[DepositRequest]> {
+        None
+    }
+    fn withdrawal_requests(&self) -> Option<&[WithdrawalRequest]> {
+        None
+    }
+    fn consolidation_requests(&self) -> Option<&[ConsolidationRequest]> {
+        None
+    }
+}
+
+pub trait BeaconBlockFields {
+    fn version(&self) -> Version;
+    fn slot(&self) -> Slot;
+    fn proposer_index(&self) -> ValidatorIndex;
+    fn parent_root(&self) -> Root;
+    fn state_root(&self) -> Root;
+    fn body(&self) -> &dyn BeaconBlockBodyFields;
+}
+
+pub trait SignedBeaconBlockFields {
+    fn message(&self) -> &dyn BeaconBlockFields;
+    fn signature(&self) -> &BlsSignature;
+}
+
+// The accessors of the state fields introduced in the fork named by the argument.
+macro_rules! state_fields {
+    (altair) => {
+        fn previous_epoch_participation(&self) -> Option<&[ParticipationFlags]> {
+            Some(&self.previous_epoch_participation)
+        }
+        fn current_epoch_participation(&self) -> Option<&[ParticipationFlags]> {
+            Some(&self.current_epoch_participation)
+        }
+        fn inactivity_scores(&self) -> Option<&[u64]> {
+            Some(&self.inactivity_scores)
+        }
+    };
+    (bellatrix) => {
+        fn latest_execution_payload_header(&self) -> Option<&dyn ExecutionPayloadFields> {
+            Some(&self.latest_execution_payload_header)
+        }
+    };
+    (capella) => {
+        fn next_withdrawal_index(&self) -> Option<WithdrawalIndex> {
+            Some(self.next_withdrawal_index)
+        }
+        fn next_withdrawal_validator_index(&self) -> Option<ValidatorIndex> {
+            Some(self.next_withdrawal_validator_index)
+        }
+        fn historical_summaries(&self) -> Option<&[HistoricalSummary]> {
+            Some(&self.historical_summaries)
+        }
+    };
+    (electra) => {
+        fn deposit_requests_start_index(&self) -> Option<u64> {
+            Some(self.deposit_requests_start_index)
+        }
+        fn deposit_balance_to_consume(&self) -> Option<Gwei> {
+            Some(self.deposit_balance_to_consume)
+        }
+        fn exit_balance_to_consume(&self) -> Option<Gwei> {
+            Some(self.exit_balance_to_consume)
+        }
+        fn earliest_exit_epoch(&self) -> Option<Epoch> {
+            Some(self.earliest_exit_epoch)
+        }
+        fn consolidation_balance_to_consume(&self) -> Option<Gwei> {
+            Some(self.consolidation_balance_to_consume)
+        }
+        fn earliest_consolidation_epoch(&self) -> Option<Epoch> {
+            Some(self.earliest_consolidation_epoch)
+        }
+        fn pending_deposits(&self) -> Option<&[PendingDeposit]> {
+            Some(&self.pending_deposits)
+        }
+        fn pending_partial_withdrawals(&self) -> Option<&[PendingPartialWithdrawal]> {
+            Some(&self.pending_partial_withdrawals)
+        }
+        fn pending_consolidations(&self) -> Option<&[PendingConsolidation]> {
+            Some(&self.pending_consolidations)
+        }
+    };
+}
+
+macro_rules! state_fields_mut {
+    (altair) => {
+        fn previous_epoch_participation_mut(&mut self) -> Option<&mut [ParticipationFlags]> {
+            Some(&mut self.previous_epoch_participation)
+        }
+        fn current_epoch_participation_mut(&mut self) -> Option<&mut [ParticipationFlags]> {
+            Some(&mut self.current_epoch_participation)
+        }
+        fn inactivity_scores_mut(&mut self) -> Option<&mut [u64]> {
+            Some(&mut self.inactivity_scores)
+        }
+    };
+    (bellatrix) => {};
+    (capella) => {
+        fn next_withdrawal_index_mut(&mut self) -> Option<&mut WithdrawalIndex> {
+            Some(&mut self.next_withdrawal_index)
+        }
+        fn next_withdrawal_validator_index_mut(&mut self) -> Option<&mut ValidatorIndex> {
+            Some(&mut self.next_withdrawal_validator_index)
+        }
+    };
+    (electra) => {
+        fn deposit_balance_to_consume_mut(&mut self) -> Option<&mut Gwei> {
+            Some(&mut self.deposit_balance_to_consume)
+        }
+        fn exit_balance_to_consume_mut(&mut self) -> Option<&mut Gwei> {
+            Some(&mut self.exit_balance_to_consume)
+        }
+        fn earliest_exit_epoch_mut(&mut self) -> Option<&mut Epoch> {
+            Some(&mut self.earliest_exit_epoch)
+        }
+        fn consolidation_balance_to_consume_mut(&mut self) -> Option<&mut Gwei> {
+            Some(&mut self.consolidation_balance_to_consume)
+        }
+        fn earliest_consolidation_epoch_mut(&mut self) -> Option<&mut Epoch> {
+            Some(&mut self.earliest_consolidation_epoch)
+        }
+    };
+}
+
+// Implement the state traits for the state of a fork, given its version and the forks that
+// introduced its other fields.
+macro_rules! impl_beacon_state_fields {
+    (
+        $fork:ident::BeaconState<$($param:ident),+ $(,)?>,
+        $version:ident $(, $since:ident)* $(,)?
+    ) => {
+        impl<$(const $param: usize),+> BeaconStateFields for $fork::BeaconState<$($param),+> {
+            fn version(&self) -> Version {
+                Version::$version
+            }
+            fn genesis_time(&self) -> u64 {
+                self.genesis_time
+            }
+            fn genesis_validators_root(&self) -> Root {
+                self.genesis_validators_root
+            }
+            fn slot(&self) -> Slot {
+                self.slot
+            }
+            fn fork(&self) -> &Fork {
+                &self.fork
+            }
+            fn latest_block_header(&self) -> &BeaconBlockHeader {
+                &self.latest_block_header
+            }
+            fn block_roots(&self) -> &[Root] {
+                &self.block_roots
+            }
+            fn state_roots(&self) -> &[Root] {
+                &self.state_roots
+            }
+            fn historical_roots(&self) -> &[Root] {
+                &self.historical_roots
+            }
+            fn eth1_data(&self) -> &Eth1Data {
+                &self.eth1_data
+            }
+            fn eth1_data_votes(&self) -> &[Eth1Data] {
+                &self.eth1_data_votes
+            }
+            fn eth1_deposit_index(&self) -> u64 {
+                self.eth1_deposit_index
+            }
+            fn validators(&self) -> &[Validator] {
+                &self.validators
+            }
+            fn balances(&self) -> &[Gwei] {
+                &self.balances
+            }
+            fn randao_mixes(&self) -> &[Bytes32] {
+                &self.randao_mixes
+            }
+            fn slashings(&self) -> &[Gwei] {
+                &self.slashings
+            }
+            fn justification_bits(&self) -> &Bitvector<JUSTIFICATION_BITS_LENGTH> {
+                &self.justification_bits
+            }
+            fn previous_justified_checkpoint(&self) -> &Checkpoint {
+                &self.previous_justified_checkpoint
+            }
+            fn current_justified_checkpoint(&self) -> &Checkpoint {
+                &self.current_justified_checkpoint
+            }
+            fn finalized_checkpoint(&self) -> &Checkpoint {
+                &self.finalized_checkpoint
+            }
+            $(state_fields!($since);)*
+        }
+
+        impl<$(const $param: usize),+> BeaconStateFieldsMut for $fork::BeaconState<$($param),+> {
+            fn genesis_time_mut(&mut self) -> &mut u64 {
+                &mut self.genesis_time
+            }
+            fn genesis_validators_root_mut(&mut self) -> &mut Root {
+                &mut self.genesis_validators_root
+            }
+            fn slot_mut(&mut self) -> &mut Slot {
+                &mut self.slot
+            }
+            fn fork_mut(&mut self) -> &mut Fork {
+                &mut self.fork
+            }
+            fn latest_block_header_mut(&mut self) -> &mut BeaconBlockHeader {
+                &mut self.latest_block_header
+            }
+            fn block_roots_mut(&mut self) -> &mut [Root] {
+                &mut self.block_roots
+            }
+            fn state_roots_mut(&mut self) -> &mut [Root] {
+                &mut self.state_roots
+            }
+            fn eth1_data_mut(&mut self) -> &mut Eth1Data {
+                &mut self.eth1_data
+            }
+            fn eth1_deposit_index_mut(&mut self) -> &mut u64 {
+                &mut self.eth1_deposit_index
+            }
+            fn validators_mut(&mut self) -> &mut [Validator] {
+                &mut self.validators
+            }
+            fn balances_mut(&mut self) -> &mut [Gwei] {
+                &mut self.balances
+            }
+            fn randao_mixes_mut(&mut self) -> &mut [Bytes32] {
+                &mut self.randao_mixes
+            }
+            fn slashings_mut(&mut self) -> &mut [Gwei] {
+                &mut self.slashings
+            }
+            fn justification_bits_mut(&mut self) -> &mut Bitvector<JUSTIFICATION_BITS_LENGTH> {
+                &mut self.justification_bits
+            }
+            fn previous_justified_checkpoint_mut(&mut self) -> &mut Checkpoint {
+                &mut self.previous_justified_checkpoint
+            }
+            fn current_justified_checkpoint_mut(&mut self) -> &mut Checkpoint {
+                &mut self.current_justified_checkpoint
+            }
+            fn finalized_checkpoint_mut(&mut self) -> &mut Checkpoint {
+                &mut self.finalized_checkpoint
+            }
+            $(state_fields_mut!($since);)*
+        }
+    };
+}
+
+// The accessors of the block body fields introduced in the fork named by the argument.
+macro_rules! body_fields {
+    (bellatrix) => {
+        fn execution_payload(&self) -> Option<&dyn ExecutionPayloadFields> {
+            Some(&self.execution_payload)
+        }
+    };
+    (capella) => {
+        fn bls_to_execution_changes(&self) -> Option<&[SignedBlsToExecutionChange]> {
+            Some(&self.bls_to_execution_changes)
+        }
+    };
+    (deneb) => {
+        fn blob_kzg_commitments(&self) -> Option<&[KzgCommitment]> {
+            Some(&self.blob_kzg_commitments)
+        }
+    };
+    (electra) => {
+        fn deposit_requests(&self) -> Option<&[DepositRequest]> {
+            Some(&self.execution_requests.deposits)
+        }
+        fn withdrawal_requests(&self) -> Option<&[WithdrawalRequest]> {
+            Some(&self.execution_requests.withdrawals)
+        }
+        fn consolidation_requests(&self) -> Option<&[ConsolidationRequest]> {
+            Some(&self.execution_requests.consolidations)
+        }
+    };
+}
+
+// Implement the block traits for the block body, block and signed block of a fork, which share
+// their parameters.
+macro_rules! impl_beacon_block_fields {
+    (
+        $fork:ident::BeaconBlock<$($param:ident),+ $(,)?>,
+        $version:ident $(, $since:ident)* $(,)?
+    ) => {
+        impl<$(const $param: usize),+> BeaconBlockBodyFields
+            for $fork::BeaconBlockBody<$($param),+>
+        {
+            fn randao_reveal(&self) -> &BlsSignature {
+                &self.randao_reveal
+            }
+            fn eth1_data(&self) -> &Eth1Data {
+                &self.eth1_data
+            }
+            fn graffiti(&self) -> &Bytes32 {
+                &self.graffiti
+            }
+            fn proposer_slashings(&self) -> &[ProposerSlashing] {
+                &self.proposer_slashings
+            }
+            fn deposits(&self) -> &[Deposit] {
+                &self.deposits
+            }
+            fn voluntary_exits(&self) -> &[SignedVoluntaryExit] {
+                &self.voluntary_exits
+            }
+            $(body_fields!($since);)*
+        }
+
+        impl<$(const $param: usize),+> BeaconBlockFields for $fork::BeaconBlock<$($param),+> {
+            fn version(&self) -> Version {
+                Version::$version
+            }
+            fn slot(&self) -> Slot {
+                self.slot
+            }
+            fn proposer_index(&self) -> ValidatorIndex {
+                self.proposer_index
+            }
+            fn parent_root(&self) -> Root {
+                self.parent_root
+            }
+            fn state_root(&self) -> Root {
+                self.state_root
+            }
+            fn body(&self) -> &dyn BeaconBlockBodyFields {
+                &self.body
+            }
+        }
+
+        impl<$(const $param: usize),+> SignedBeaconBlockFields
+            for $fork::SignedBeaconBlock<$($param),+>
+        {
+            fn message(&self) -> &dyn BeaconBlockFields {
+                &self.message
+            }
+            fn signature(&self) -> &BlsSignature {
+                &self.signature
+            }
+        }
+    };
+}
+
+// The accessors of the execution payload fields introduced in the fork named by the argument;
+// `capella` only applies to payloads.
+macro_rules! payload_fields {
+    (capella) => {
+        fn withdrawals(&self) -> Option<&[Withdrawal]> {
+            Some(&self.withdrawals)
+        }
+    };
+    (deneb) => {
+        fn blob_gas_used(&self) -> Option<u64> {
+            Some(self.blob_gas_used)
+        }
+        fn excess_blob_gas(&self) -> Option<u64> {
+            Some(self.excess_blob_gas)
+        }
+    };
+}
+
+macro_rules! impl_execution_payload_fields {
+    ($fork:ident::$name:ident<$($param:ident),+ $(,)?> $(, $since:ident)* $(,)?) => {
+        impl<$(const $param: usize),+> ExecutionPayloadFields for $fork::$name<$($param),+> {
+            fn parent_hash(&self) -> &Hash32 {
+                &self.parent_hash
+            }
+            fn fee_recipient(&self) -> &ExecutionAddress {
+                &self.fee_recipient
+            }
+            fn state_root(&self) -> &Bytes32 {
+                &self.state_root
+            }
+            fn receipts_root(&self) -> &Bytes32 {
+                &self.receipts_root
+            }
+            fn logs_bloom(&self) -> &[u8] {
+                self.logs_bloom.as_ref()
+            }
+            fn prev_randao(&self) -> &Bytes32 {
+                &self.prev_randao
+            }
+            fn block_number(&self) -> u64 {
+                self.block_number
+            }
+            fn gas_limit(&self) -> u64 {
+                self.gas_limit
+            }
+            fn gas_used(&self) -> u64 {
+                self.gas_used
+            }
+            fn timestamp(&self) -> u64 {
+                self.timestamp
+            }
+            fn extra_data(&self) -> &[u8] {
+                self.extra_data.as_ref()
+            }
+            fn base_fee_per_gas(&self) -> &U256 {
+                &self.base_fee_per_gas
+            }
+            fn block_hash(&self) -> &Hash32 {
+                &self.block_hash
+            }
+            $(payload_fields!($since);)*
+        }
+    };
+}
+
+impl_beacon_state_fields!(
+    phase0::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >, Phase0
+);
+
+impl_beacon_state_fields!(
+    altair::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >, Altair, altair
+);
+
+impl_beacon_state_fields!(
+    bellatrix::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >, Bellatrix, altair, bellatrix
+);
+
+impl_beacon_state_fields!(
+    capella::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >, Capella, altair, bellatrix, capella
+);
+
+impl_beacon_state_fields!(
+    deneb::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >, Deneb, altair, bellatrix, capella
+);
+
+impl_beacon_state_fields!(
+    electra::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >, Electra, altair, bellatrix, capella, electra
+);
+
+impl_beacon_block_fields!(
+    phase0::BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+    >, Phase0
+);
+
+impl_beacon_block_fields!(
+    altair::BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+    >, Altair
+);
+
+impl_beacon_block_fields!(
+    bellatrix::BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >, Bellatrix, bellatrix
+);
+
+impl_beacon_block_fields!(
+    capella::BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+    >, Capella, bellatrix, capella
+);
+
+impl_beacon_block_fields!(
+    deneb::BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    >, Deneb, bellatrix, capella, deneb
+);
+
+impl_beacon_block_fields!(
+    electra::BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >, Electra, bellatrix, capella, deneb, electra
+);
+
+impl_execution_payload_fields!(
+    bellatrix::ExecutionPayload<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >
+);
+
+impl_execution_payload_fields!(
+    capella::ExecutionPayload<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+    >, capella
+);
+
+impl_execution_payload_fields!(
+    deneb::ExecutionPayload<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+    >, capella, deneb
+);
+
+impl_execution_payload_fields!(
+    bellatrix::ExecutionPayloadHeader<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >
+);
+
+impl_execution_payload_fields!(
+    capella::ExecutionPayloadHeader<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >
+);
+
+impl_execution_payload_fields!(
+    deneb::ExecutionPayloadHeader<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >, deneb
+);
+
+// Implement trait methods by calling them on whichever fork variant a polymorphic value holds.
+macro_rules! delegate {
+    ($ty:ident { $(fn $name:ident(&self) -> $ret:ty;)+ }) => {
+        $(
+            fn $name(&self) -> $ret {
+                crate::map_fork!($ty, self, inner => inner.$name())
+            }
+        )+
+    };
+    ($ty:ident { $(fn $name:ident(&mut self) -> $ret:ty;)+ }) => {
+        $(
+            fn $name(&mut self) -> $ret {
+                crate::map_fork!($ty, self, inner => inner.$name())
+            }
+        )+
+    };
+}
+
+impl<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    > BeaconStateFields
+    for types::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >
+{
+    delegate!(BeaconState {
+        fn version(&self) -> Version;
+        fn genesis_time(&self) -> u64;
+        fn genesis_validators_root(&self) -> Root;
+        fn slot(&self) -> Slot;
+        fn fork(&self) -> &Fork;
+        fn latest_block_header(&self) -> &BeaconBlockHeader;
+        fn block_roots(&self) -> &[Root];
+        fn state_roots(&self) -> &[Root];
+        fn historical_roots(&self) -> &[Root];
+        fn eth1_data(&self) -> &Eth1Data;
+        fn eth1_data_votes(&self) -> &[Eth1Data];
+        fn eth1_deposit_index(&self) -> u64;
+        fn validators(&self) -> &[Validator];
+        fn balances(&self) -> &[Gwei];
+        fn randao_mixes(&self) -> &[Bytes32];
+        fn slashings(&self) -> &[Gwei];
+        fn justification_bits(&self) -> &Bitvector<JUSTIFICATION_BITS_LENGTH>;
+        fn previous_justified_checkpoint(&self) -> &Checkpoint;
+        fn current_justified_checkpoint(&self) -> &Checkpoint;
+        fn finalized_checkpoint(&self) -> &Checkpoint;
+        fn previous_epoch_participation(&self) -> Option<&[ParticipationFlags]>;
+        fn current_epoch_participation(&self) -> Option<&[ParticipationFlags]>;
+        fn inactivity_scores(&self) -> Option<&[u64]>;
+        fn latest_execution_payload_header(&self) -> Option<&dyn ExecutionPayloadFields>;
+        fn next_withdrawal_index(&self) -> Option<WithdrawalIndex>;
+        fn next_withdrawal_validator_index(&self) -> Option<ValidatorIndex>;
+        fn historical_summaries(&self) -> Option<&[HistoricalSummary]>;
+        fn deposit_requests_start_index(&self) -> Option<u64>;
+        fn deposit_balance_to_consume(&self) -> Option<Gwei>;
+        fn exit_balance_to_consume(&self) -> Option<Gwei>;
+        fn earliest_exit_epoch(&self) -> Option<Epoch>;
+        fn consolidation_balance_to_consume(&self) -> Option<Gwei>;
+        fn earliest_consolidation_epoch(&self) -> Option<Epoch>;
+        fn pending_deposits(&self) -> Option<&[PendingDeposit]>;
+        fn pending_partial_withdrawals(&self) -> Option<&[PendingPartialWithdrawal]>;
+        fn pending_consolidations(&self) -> Option<&[PendingConsolidation]>;
+    });
+}
+
+impl<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const PENDING_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
+    > BeaconStateFieldsMut
+    for types::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >
+{
+    delegate!(BeaconState {
+        fn genesis_time_mut(&mut self) -> &mut u64;
+        fn genesis_validators_root_mut(&mut self) -> &mut Root;
+        fn slot_mut(&mut self) -> &mut Slot;
+        fn fork_mut(&mut self) -> &mut Fork;
+        fn latest_block_header_mut(&mut self) -> &mut BeaconBlockHeader;
+        fn block_roots_mut(&mut self) -> &mut [Root];
+        fn state_roots_mut(&mut self) -> &mut [Root];
+        fn eth1_data_mut(&mut self) -> &mut Eth1Data;
+        fn eth1_deposit_index_mut(&mut self) -> &mut u64;
+        fn validators_mut(&mut self) -> &mut [Validator];
+        fn balances_mut(&mut self) -> &mut [Gwei];
+        fn randao_mixes_mut(&mut self) -> &mut [Bytes32];
+        fn slashings_mut(&mut self) -> &mut [Gwei];
+        fn justification_bits_mut(&mut self) -> &mut Bitvector<JUSTIFICATION_BITS_LENGTH>;
+        fn previous_justified_checkpoint_mut(&mut self) -> &mut Checkpoint;
+        fn current_justified_checkpoint_mut(&mut self) -> &mut Checkpoint;
+        fn finalized_checkpoint_mut(&mut self) -> &mut Checkpoint;
+        fn previous_epoch_participation_mut(&mut self) -> Option<&mut [ParticipationFlags]>;
+        fn current_epoch_participation_mut(&mut self) -> Option<&mut [ParticipationFlags]>;
+        fn inactivity_scores_mut(&mut self) -> Option<&mut [u64]>;
+        fn next_withdrawal_index_mut(&mut self) -> Option<&mut WithdrawalIndex>;
+        fn next_withdrawal_validator_index_mut(&mut self) -> Option<&mut ValidatorIndex>;
+        fn deposit_balance_to_consume_mut(&mut self) -> Option<&mut Gwei>;
+        fn exit_balance_to_consume_mut(&mut self) -> Option<&mut Gwei>;
+        fn earliest_exit_epoch_mut(&mut self) -> Option<&mut Epoch>;
+        fn consolidation_balance_to_consume_mut(&mut self) -> Option<&mut Gwei>;
+        fn earliest_consolidation_epoch_mut(&mut self) -> Option<&mut Epoch>;
+    });
+}
+
+impl<
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+    > BeaconBlockFields
+    for types::BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >
+{
+    delegate!(BeaconBlock {
+        fn version(&self) -> Version;
+        fn slot(&self) -> Slot;
+        fn proposer_index(&self) -> ValidatorIndex;
+        fn parent_root(&self) -> Root;
+        fn state_root(&self) -> Root;
+        fn body(&self) -> &dyn BeaconBlockBodyFields;
+    });
+}
+
+impl<
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS_ELECTRA: usize,
+        const MAX_ATTESTATIONS_ELECTRA: usize,
+        const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD: usize,
+    > SignedBeaconBlockFields
+    for types::SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS_ELECTRA,
+        MAX_ATTESTATIONS_ELECTRA,
+        MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD,
+    >
+{
+    delegate!(SignedBeaconBlock {
+        fn message(&self) -> &dyn BeaconBlockFields;
+        fn signature(&self) -> &BlsSignature;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_state<S: BeaconStateFieldsMut>(mut state: S, version: Version) {
+        assert_eq!(state.version(), version);
+        *state.slot_mut() = 33;
+        state.finalized_checkpoint_mut().epoch = 2;
+        state.balances_mut()[0] += 1;
+        assert_eq!(state.slot(), 33);
+        assert_eq!(state.finalized_checkpoint().epoch, 2);
+        assert_eq!(state.validators().len(), 1);
+        assert_eq!(state.balances(), &[33]);
+        assert!(!state.block_roots().is_empty());
+
+        assert_eq!(state.inactivity_scores().is_some(), version >= Version::Altair);
+        assert_eq!(state.current_epoch_participation().is_some(), version >= Version::Altair);
+        let header = state.latest_execution_payload_header();
+        assert_eq!(header.is_some(), version >= Version::Bellatrix);
+        if let Some(header) = header {
+            assert_eq!(header.block_hash(), &Hash32::default());
+            assert!(header.withdrawals().is_none());
+            assert_eq!(header.blob_gas_used().is_some(), version >= Version::Deneb);
+        }
+        assert_eq!(state.historical_summaries().is_some(), version >= Version::Capella);
+        if let Some(index) = state.next_withdrawal_index_mut() {
+            *index = 5;
+        }
+        assert_eq!(state.next_withdrawal_index(), (version >= Version::Capella).then_some(5));
+        assert_eq!(state.pending_deposits().is_some(), version == Version::Electra);
+        if let Some(epoch) = state.earliest_exit_epoch_mut() {
+            *epoch = 9;
+        }
+        assert_eq!(state.earliest_exit_epoch(), (version == Version::Electra).then_some(9));
+    }
+
+    fn check_block<B: SignedBeaconBlockFields>(block: &B, version: Version) {
+        let message = block.message();
+        assert_eq!(message.version(), version);
+        assert_eq!(message.slot(), 7);
+        let body = message.body();
+        assert_eq!(body.proposer_slashings().len(), 1);
+        let payload = body.execution_payload();
+        assert_eq!(payload.is_some(), version >= Version::Bellatrix);
+        if let Some(payload) = payload {
+            assert_eq!(payload.block_hash(), &Hash32::try_from([7u8; 32].as_ref()).unwrap());
+            assert_eq!(payload.withdrawals().is_some(), version >= Version::Capella);
+            assert_eq!(payload.excess_blob_gas().is_some(), version >= Version::Deneb);
+        }
+        assert_eq!(body.bls_to_execution_changes().is_some(), version >= Version::Capella);
+        assert_eq!(body.blob_kzg_commitments().is_some(), version >= Version::Deneb);
+        assert_eq!(body.consolidation_requests().is_some(), version == Version::Electra);
+    }
+
+    // Check the fields of a state and a block of each fork in `$preset`, both as the types of the
+    // forks and as the polymorphic types.
+    macro_rules! check_forks {
+        ($preset:ident, polymorphic: [$($fork:ident => $version:ident),+]) => {
+            $(
+                let (state, block) = fork_values!($fork::$preset);
+                check_state(state.clone(), Version::$version);
+                check_state(types::$preset::BeaconState::$version(state), Version::$version);
+                check_block(&block, Version::$version);
+                check_block(&types::$preset::SignedBeaconBlock::$version(block), Version::$version);
+            )+
+        };
+    }
+
+    macro_rules! fork_values {
+        ($fork:ident::$preset:ident) => {{
+            let mut state = $fork::$preset::BeaconState::default();
+            state.validators.push(Validator::default());
+            state.balances.push(32);
+            let mut block = $fork::$preset::SignedBeaconBlock::default();
+            block.message.slot = 7;
+            block.message.body.proposer_slashings.push(Default::default());
+            set_block_hash!($fork, block);
+            (state, block)
+        }};
+    }
+
+    macro_rules! set_block_hash {
+        (phase0, $block:ident) => {};
+        (altair, $block:ident) => {};
+        ($fork:ident, $block:ident) => {
+            $block.message.body.execution_payload.block_hash =
+                Hash32::try_from([7u8; 32].as_ref()).unwrap();
+        };
+    }
+
+    #[test]
+    fn test_fields_of_every_fork() {
+        check_forks!(
+            minimal,
+            polymorphic: [
+                phase0 => Phase0,
+                altair => Altair,
+                bellatrix => Bellatrix,
+                capella => Capella,
+                deneb => Deneb,
+                electra => Electra
+            ]
+        );
+        check_forks!(
+            mainnet,
+            polymorphic: [
+                phase0 => Phase0,
+                altair => Altair,
+                bellatrix => Bellatrix,
+                capella => Capella,
+                deneb => Deneb,
+                electra => Electra
+            ]
+        );
+    }
+}
@@ -0,0 +1,88 @@
+//! WARNING: This file was derived by the `spec-gen` utility. DO NOT EDIT MANUALLY.
+
+/// Evaluate the same expression against whichever fork variant a polymorphic value holds.
+///
+/// The first argument names the type in this module, the second is the value to match on and
+/// the third binds the inner value for the body, e.g.
+/// `map_fork!(BeaconState, &state, inner => inner.validators.len())`.
+#[macro_export]
+macro_rules! map_fork {
+    (BeaconBlockBody, $value:expr, $inner:ident => $body:expr) => {
+        match $value {
+            $crate::types::BeaconBlockBody::Phase0($inner) => $body,
+            $crate::types::BeaconBlockBody::Altair($inner) => $body,
+            $crate::types::BeaconBlockBody::Bellatrix($inner) => $body,
+            $crate::types::BeaconBlockBody::Capella($inner) => $body,
+            $crate::types::BeaconBlockBody::Deneb($inner) => $body,
+            $crate::types::BeaconBlockBody::Electra($inner) => $body,
+        }
+    };
+    (BeaconBlock, $value:expr, $inner:ident => $body:expr) => {
+        match $value {
+            $crate::types::BeaconBlock::Phase0($inner) => $body,
+            $crate::types::BeaconBlock::Altair($inner) => $body,
+            $crate::types::BeaconBlock::Bellatrix($inner) => $body,
+            $crate::types::BeaconBlock::Capella($inner) => $body,
+            $crate::types::BeaconBlock::Deneb($inner) => $body,
+            $crate::types::BeaconBlock::Electra($inner) => $body,
+        }
+    };
+    (SignedBeaconBlock, $value:expr, $inner:ident => $body:expr) => {
+        match $value {
+            $crate::types::SignedBeaconBlock::Phase0($inner) => $body,
+            $crate::types::SignedBeaconBlock::Altair($inner) => $body,
+            $crate::types::SignedBeaconBlock::Bellatrix($inner) => $body,
+            $crate::types::SignedBeaconBlock::Capella($inner) => $body,
+            $crate::types::SignedBeaconBlock::Deneb($inner) => $body,
+            $crate::types::SignedBeaconBlock::Electra($inner) => $body,
+        }
+    };
+    (ExecutionPayload, $value:expr, $inner:ident => $body:expr) => {
+        match $value {
+            $crate::types::ExecutionPayload::Bellatrix($inner) => $body,
+            $crate::types::ExecutionPayload::Capella($inner) => $body,
+            $crate::types::ExecutionPayload::Deneb($inner) => $body,
+        }
+    };
+    (ExecutionPayloadHeader, $value:expr, $inner:ident => $body:expr) => {
+        match $value {
+            $crate::types::ExecutionPayloadHeader::Bellatrix($inner) => $body,
+            $crate::types::ExecutionPayloadHeader::Capella($inner) => $body,
+            $crate::types::ExecutionPayloadHeader::Deneb($inner) => $body,
+        }
+    };
+    (BlindedBeaconBlockBody, $value:expr, $inner:ident => $body:expr) => {
+        match $value {
+            $crate::types::BlindedBeaconBlockBody::Bellatrix($inner) => $body,
+            $crate::types::BlindedBeaconBlockBody::Capella($inner) => $body,
+            $crate::types::BlindedBeaconBlockBody::Deneb($inner) => $body,
+            $crate::types::BlindedBeaconBlockBody::Electra($inner) => $body,
+        }
+    };
+    (BlindedBeaconBlock, $value:expr, $inner:ident => $body:expr) => {
+        match $value {
+            $crate::types::BlindedBeaconBlock::Bellatrix($inner) => $body,
+            $crate::types::BlindedBeaconBlock::Capella($inner) => $body,
+            $crate::types::BlindedBeaconBlock::Deneb($inner) => $body,
+            $crate::types::BlindedBeaconBlock::Electra($inner) => $body,
+        }
+    };
+    (SignedBlindedBeaconBlock, $value:expr, $inner:ident => $body:expr) => {
+        match $value {
+            $crate::types::SignedBlindedBeaconBlock::Bellatrix($inner) => $body,
+            $crate::types::SignedBlindedBeaconBlock::Capella($inner) => $body,
+            $crate::types::SignedBlindedBeaconBlock::Deneb($inner) => $body,
+            $crate::types::SignedBlindedBeaconBlock::Electra($inner) => $body,
+        }
+    };
+    (BeaconState, $value:expr, $inner:ident => $body:expr) => {
+        match $value {
+            $crate::types::BeaconState::Phase0($inner) => $body,
+            $crate::types::BeaconState::Altair($inner) => $body,
+            $crate::types::BeaconState::Bellatrix($inner) => $body,
+            $crate::types::BeaconState::Capella($inner) => $body,
+            $crate::types::BeaconState::Deneb($inner) => $body,
+            $crate::types::BeaconState::Electra($inner) => $body,
+        }
+    };
+}
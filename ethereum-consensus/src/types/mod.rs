@@ -11,6 +11,8 @@ mod blinded_beacon_block;
 mod blinded_beacon_block_body;
 mod execution_payload;
 mod execution_payload_header;
+mod fields;
+mod map_fork;
 mod presets;
 mod signed_beacon_block;
 mod signed_blinded_beacon_block;
@@ -22,7 +24,119 @@ pub use blinded_beacon_block::*;
 pub use blinded_beacon_block_body::*;
 pub use execution_payload::*;
 pub use execution_payload_header::*;
+pub use fields::*;
 pub use signed_beacon_block::*;
 pub use signed_blinded_beacon_block::*;
 
-pub use presets::{mainnet, minimal};
+pub use presets::{gnosis, mainnet, minimal};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::WrongFork, Fork};
+
+    #[test]
+    fn test_downcasts_on_wrong_fork() {
+        let mut state = minimal::BeaconState::Altair(Default::default());
+        let wrong_fork = |expected| WrongFork { expected, found: Fork::Altair };
+        assert!(state.as_altair().is_ok());
+        assert!(state.as_altair_mut().is_ok());
+        assert_eq!(state.as_phase0().unwrap_err(), wrong_fork(Fork::Phase0));
+        assert_eq!(state.as_phase0_mut().unwrap_err(), wrong_fork(Fork::Phase0));
+        assert_eq!(state.as_bellatrix().unwrap_err(), wrong_fork(Fork::Bellatrix));
+        assert_eq!(state.as_bellatrix_mut().unwrap_err(), wrong_fork(Fork::Bellatrix));
+        assert_eq!(state.as_capella().unwrap_err(), wrong_fork(Fork::Capella));
+        assert_eq!(state.as_capella_mut().unwrap_err(), wrong_fork(Fork::Capella));
+        assert_eq!(state.as_deneb().unwrap_err(), wrong_fork(Fork::Deneb));
+        assert_eq!(state.as_deneb_mut().unwrap_err(), wrong_fork(Fork::Deneb));
+        assert_eq!(state.as_electra().unwrap_err(), wrong_fork(Fork::Electra));
+        assert_eq!(state.as_electra_mut().unwrap_err(), wrong_fork(Fork::Electra));
+        assert_eq!(state.clone().into_phase0().unwrap_err(), wrong_fork(Fork::Phase0));
+        assert_eq!(state.clone().into_bellatrix().unwrap_err(), wrong_fork(Fork::Bellatrix));
+        assert_eq!(state.clone().into_capella().unwrap_err(), wrong_fork(Fork::Capella));
+        assert_eq!(state.clone().into_deneb().unwrap_err(), wrong_fork(Fork::Deneb));
+        assert_eq!(state.clone().into_electra().unwrap_err(), wrong_fork(Fork::Electra));
+        assert!(state.into_altair().is_ok());
+
+        let mut block = minimal::SignedBeaconBlock::Deneb(Default::default());
+        let wrong_fork = |expected| WrongFork { expected, found: Fork::Deneb };
+        assert_eq!(block.as_phase0().unwrap_err(), wrong_fork(Fork::Phase0));
+        assert_eq!(block.as_phase0_mut().unwrap_err(), wrong_fork(Fork::Phase0));
+        assert_eq!(block.as_altair().unwrap_err(), wrong_fork(Fork::Altair));
+        assert_eq!(block.as_altair_mut().unwrap_err(), wrong_fork(Fork::Altair));
+        assert_eq!(block.as_bellatrix().unwrap_err(), wrong_fork(Fork::Bellatrix));
+        assert_eq!(block.as_bellatrix_mut().unwrap_err(), wrong_fork(Fork::Bellatrix));
+        assert_eq!(block.as_capella().unwrap_err(), wrong_fork(Fork::Capella));
+        assert_eq!(block.as_capella_mut().unwrap_err(), wrong_fork(Fork::Capella));
+        assert_eq!(block.as_electra().unwrap_err(), wrong_fork(Fork::Electra));
+        assert_eq!(block.as_electra_mut().unwrap_err(), wrong_fork(Fork::Electra));
+        assert_eq!(block.clone().into_phase0().unwrap_err(), wrong_fork(Fork::Phase0));
+        assert_eq!(block.clone().into_altair().unwrap_err(), wrong_fork(Fork::Altair));
+        assert_eq!(block.clone().into_bellatrix().unwrap_err(), wrong_fork(Fork::Bellatrix));
+        assert_eq!(block.clone().into_capella().unwrap_err(), wrong_fork(Fork::Capella));
+        assert_eq!(block.clone().into_electra().unwrap_err(), wrong_fork(Fork::Electra));
+        assert!(block.as_deneb().is_ok());
+        assert!(block.into_deneb().is_ok());
+
+        let mut block = minimal::SignedBeaconBlock::Electra(Default::default());
+        let wrong_fork = |expected| WrongFork { expected, found: Fork::Electra };
+        assert_eq!(block.as_deneb().unwrap_err(), wrong_fork(Fork::Deneb));
+        assert_eq!(block.as_deneb_mut().unwrap_err(), wrong_fork(Fork::Deneb));
+        assert_eq!(block.clone().into_deneb().unwrap_err(), wrong_fork(Fork::Deneb));
+        assert!(block.as_electra().is_ok());
+        assert!(block.as_electra_mut().is_ok());
+        assert!(block.into_electra().is_ok());
+
+        let mut block = minimal::SignedBlindedBeaconBlock::Electra(Default::default());
+        let wrong_fork = |expected| WrongFork { expected, found: Fork::Electra };
+        assert_eq!(block.as_bellatrix().unwrap_err(), wrong_fork(Fork::Bellatrix));
+        assert_eq!(block.as_capella_mut().unwrap_err(), wrong_fork(Fork::Capella));
+        assert_eq!(block.clone().into_deneb().unwrap_err(), wrong_fork(Fork::Deneb));
+        assert!(block.as_electra().is_ok());
+        assert!(block.as_electra_mut().is_ok());
+        assert!(block.message().body().execution_requests().is_some());
+        assert!(block.into_electra().is_ok());
+
+        let body = minimal::BlindedBeaconBlockBody::Capella(Default::default());
+        let wrong_fork = |expected| WrongFork { expected, found: Fork::Capella };
+        assert_eq!(body.as_deneb().unwrap_err(), wrong_fork(Fork::Deneb));
+        assert_eq!(body.clone().into_bellatrix().unwrap_err(), wrong_fork(Fork::Bellatrix));
+        assert!(body.blob_kzg_commitments().is_none());
+        assert!(body.bls_to_execution_changes().is_some());
+        assert!(body.into_capella().is_ok());
+
+        let error =
+            minimal::ExecutionPayload::Capella(Default::default()).into_deneb().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected an object from fork deneb but found one from fork capella"
+        );
+    }
+
+    #[test]
+    fn test_map_fork() {
+        let state = minimal::BeaconState::Capella(Default::default());
+        let slot = crate::map_fork!(BeaconState, &state, inner => inner.slot + 1);
+        assert_eq!(slot, 1);
+        let payload = minimal::ExecutionPayload::Deneb(Default::default());
+        let count = crate::map_fork!(ExecutionPayload, &payload, inner => inner.transactions.len());
+        assert_eq!(count, 0);
+        let block = minimal::BeaconBlock::Electra(Default::default());
+        let count = crate::map_fork!(BeaconBlock, &block, inner => inner.body.attestations.len());
+        assert_eq!(count, 0);
+        let block = minimal::SignedBlindedBeaconBlock::Bellatrix(Default::default());
+        let gas_limit = crate::map_fork!(
+            SignedBlindedBeaconBlock,
+            &block,
+            inner => inner.message.body.execution_payload_header.gas_limit
+        );
+        assert_eq!(gas_limit, 0);
+        let block = minimal::BlindedBeaconBlock::Electra(Default::default());
+        let count = crate::map_fork!(
+            BlindedBeaconBlock,
+            &block,
+            inner => inner.body.execution_requests.deposits.len()
+        );
+        assert_eq!(count, 0);
+    }
+}
@@ -0,0 +1,155 @@
+//! Validator predicates usable across forks, along with bulk variants for callers scanning the
+//! registry repeatedly (e.g. once per epoch of a historical range).
+//!
+//! The bulk variants take the registry as a slice (e.g. `state.validators()` or
+//! `&state.validators`) so they apply to the `BeaconState` of any fork.
+pub use crate::phase0::{is_active_validator, is_slashable_validator};
+use crate::{
+    electra,
+    phase0::{self, Validator},
+    primitives::{Epoch, Gwei, ValidatorIndex, FAR_FUTURE_EPOCH},
+    state_transition::Context,
+    Fork,
+};
+
+/// `is_eligible_for_activation_queue` as defined at `fork`.
+///
+/// Prior to `electra`, only validators with exactly `MAX_EFFECTIVE_BALANCE` are eligible;
+/// `electra` admits any validator with at least `MIN_ACTIVATION_BALANCE`.
+pub fn is_eligible_for_activation_queue(
+    validator: &Validator,
+    fork: Fork,
+    context: &Context,
+) -> bool {
+    match fork {
+        Fork::Phase0 | Fork::Altair | Fork::Bellatrix | Fork::Capella | Fork::Deneb => {
+            phase0::is_eligible_for_activation_queue(validator, context)
+        }
+        Fork::Electra => electra::is_eligible_for_activation_queue(validator, context),
+    }
+}
+
+/// `is_eligible_for_activation` given the epoch of the state's finalized checkpoint.
+pub fn is_eligible_for_activation_at(validator: &Validator, finalized_epoch: Epoch) -> bool {
+    validator.activation_eligibility_epoch <= finalized_epoch &&
+        validator.activation_epoch == FAR_FUTURE_EPOCH
+}
+
+/// Write the indices of the validators active at `epoch` into `indices`, replacing its contents.
+/// Reusing `indices` across calls avoids the allocation made by `get_active_validator_indices`.
+pub fn active_validator_indices(
+    validators: &[Validator],
+    epoch: Epoch,
+    indices: &mut Vec<ValidatorIndex>,
+) {
+    indices.clear();
+    indices.extend(
+        validators
+            .iter()
+            .enumerate()
+            .filter_map(|(i, validator)| is_active_validator(validator, epoch).then_some(i)),
+    );
+}
+
+/// Effective balance of the validators active at some epoch, split by their status.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ActiveBalances {
+    /// Active, unslashed validators with no exit scheduled.
+    pub ongoing: Gwei,
+    /// Active, unslashed validators with an exit scheduled.
+    pub exiting: Gwei,
+    /// Active validators that have been slashed.
+    pub slashed: Gwei,
+}
+
+impl ActiveBalances {
+    pub fn total(&self) -> Gwei {
+        self.ongoing + self.exiting + self.slashed
+    }
+}
+
+/// Sum the effective balance of the validators active at `epoch` by status.
+pub fn active_balance_by_status(validators: &[Validator], epoch: Epoch) -> ActiveBalances {
+    let mut balances = ActiveBalances::default();
+    for validator in validators.iter().filter(|validator| is_active_validator(validator, epoch)) {
+        let balance = if validator.slashed {
+            &mut balances.slashed
+        } else if validator.exit_epoch == FAR_FUTURE_EPOCH {
+            &mut balances.ongoing
+        } else {
+            &mut balances.exiting
+        };
+        *balance += validator.effective_balance;
+    }
+    balances
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> Vec<Validator> {
+        // a spread of activation and exit epochs, some slashed
+        (0..97u64)
+            .map(|i| Validator {
+                activation_epoch: i % 7,
+                exit_epoch: if i % 3 == 0 { FAR_FUTURE_EPOCH } else { 4 + i % 5 },
+                slashed: i % 11 == 0,
+                effective_balance: (16 + i % 17) * 10u64.pow(9),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_electra_activation_queue_boundary() {
+        let context = Context::for_mainnet();
+        let mut validator =
+            Validator { activation_eligibility_epoch: FAR_FUTURE_EPOCH, ..Default::default() };
+
+        validator.effective_balance = context.min_activation_balance;
+        assert!(is_eligible_for_activation_queue(&validator, Fork::Electra, &context));
+        assert_eq!(
+            is_eligible_for_activation_queue(&validator, Fork::Deneb, &context),
+            context.min_activation_balance == context.max_effective_balance
+        );
+
+        validator.effective_balance = context.min_activation_balance - 1;
+        assert!(!is_eligible_for_activation_queue(&validator, Fork::Electra, &context));
+        assert!(!is_eligible_for_activation_queue(&validator, Fork::Deneb, &context));
+
+        validator.effective_balance = context.max_effective_balance_electra;
+        assert!(is_eligible_for_activation_queue(&validator, Fork::Electra, &context));
+
+        validator.activation_eligibility_epoch = 0;
+        validator.effective_balance = context.min_activation_balance;
+        assert!(!is_eligible_for_activation_queue(&validator, Fork::Electra, &context));
+    }
+
+    #[test]
+    fn test_bulk_matches_scalar() {
+        let validators = registry();
+        let mut indices = vec![usize::MAX; 3];
+        for epoch in 0..12 {
+            active_validator_indices(&validators, epoch, &mut indices);
+            let expected = validators
+                .iter()
+                .enumerate()
+                .filter(|(_, validator)| is_active_validator(validator, epoch))
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>();
+            assert_eq!(indices, expected);
+
+            let balances = active_balance_by_status(&validators, epoch);
+            let expected_total =
+                expected.iter().map(|&i| validators[i].effective_balance).sum::<Gwei>();
+            assert_eq!(balances.total(), expected_total);
+            let expected_slashed = expected
+                .iter()
+                .filter(|&&i| validators[i].slashed)
+                .map(|&i| validators[i].effective_balance)
+                .sum::<Gwei>();
+            assert_eq!(balances.slashed, expected_slashed);
+        }
+    }
+}
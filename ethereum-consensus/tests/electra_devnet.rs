@@ -0,0 +1,122 @@
+//! A smoke test of a small devnet across the upgrade to Electra: blocks with operations, blobs and
+//! execution requests are made by the chain generator, applied with full validation and imported
+//! into a fork choice store, which must follow the chain as it finalizes.
+use ethereum_consensus::{
+    deneb::verify_blob_sidecar_inclusion_proof,
+    electra::ConsolidationRequest,
+    fixtures::chain::{self, BlockOperations, ChainGenerator},
+    fork_choice::minimal::Store,
+    primitives::{ExecutionAddress, Slot, COMPOUNDING_WITHDRAWAL_PREFIX, FAR_FUTURE_EPOCH},
+    ssz::prelude::*,
+    types::SignedBeaconBlock,
+    Fork,
+};
+
+const VALIDATOR_COUNT: usize = 64;
+const EPOCHS: u64 = 6;
+
+// the validator changing to execution credentials and then to compounding ones
+const COMPOUNDING_VALIDATOR: usize = 1;
+const EXITING_VALIDATOR: usize = 2;
+
+const BLS_TO_EXECUTION_CHANGE_SLOT: Slot = 2;
+const DENEB_BLOBS_SLOT: Slot = 3;
+const DEPOSIT_REQUEST_SLOT: Slot = 9;
+const CONSOLIDATION_REQUEST_SLOT: Slot = 10;
+const ELECTRA_BLOBS_SLOT: Slot = 12;
+const VOLUNTARY_EXIT_SLOT: Slot = 25;
+
+#[test]
+fn test_electra_devnet() {
+    let context = chain::context();
+    let genesis_state = chain::genesis_state(VALIDATOR_COUNT, &context);
+    let anchor_block = chain::anchor_block(&genesis_state);
+    let mut store = Store::new(genesis_state.clone(), anchor_block, context.clone()).unwrap();
+    let mut generator = ChainGenerator::new(genesis_state, context.clone());
+    let address = ExecutionAddress::try_from([0x42u8; 20].as_ref()).unwrap();
+
+    for slot in 1..=EPOCHS * context.slots_per_epoch {
+        let mut operations = BlockOperations::default();
+        match slot {
+            BLS_TO_EXECUTION_CHANGE_SLOT => operations.bls_to_execution_changes.push(
+                generator.signed_bls_to_execution_change(COMPOUNDING_VALIDATOR, address.clone()),
+            ),
+            DENEB_BLOBS_SLOT => operations.blob_count = 2,
+            DEPOSIT_REQUEST_SLOT => {
+                // the next interop validator joins, as the first deposit after the genesis ones
+                let request = generator.deposit_request(
+                    VALIDATOR_COUNT,
+                    context.min_activation_balance,
+                    VALIDATOR_COUNT as u64,
+                );
+                operations.execution_requests.deposits.push(request);
+            }
+            CONSOLIDATION_REQUEST_SLOT => {
+                // a consolidation into the source itself switches it to compounding credentials
+                let public_key = generator.executor.state.inner().validators()
+                    [COMPOUNDING_VALIDATOR]
+                    .public_key
+                    .clone();
+                operations.execution_requests.consolidations.push(ConsolidationRequest {
+                    source_address: address.clone(),
+                    source_public_key: public_key.clone(),
+                    target_public_key: public_key,
+                });
+            }
+            ELECTRA_BLOBS_SLOT => operations.blob_count = 3,
+            VOLUNTARY_EXIT_SLOT => {
+                operations.voluntary_exits.push(generator.signed_voluntary_exit(EXITING_VALIDATOR))
+            }
+            _ => {}
+        }
+        let block = generator.next_block(operations);
+
+        let sidecars = chain::blob_sidecars(&block);
+        for sidecar in &sidecars {
+            verify_blob_sidecar_inclusion_proof(sidecar, &context).unwrap();
+        }
+        let blobs = sidecars.iter().map(|sidecar| sidecar.blob.clone()).collect::<Vec<_>>();
+        let proofs = sidecars.iter().map(|sidecar| sidecar.kzg_proof.clone()).collect::<Vec<_>>();
+
+        store.on_tick(store.genesis_time + slot * context.seconds_per_slot);
+        if sidecars.is_empty() {
+            store.on_block(&block).unwrap();
+        } else {
+            store.on_block_with_blobs(&block, &blobs, &proofs).unwrap();
+        }
+        let head = match &block {
+            SignedBeaconBlock::Deneb(block) => {
+                for attestation in block.message.body.attestations.iter() {
+                    store.on_attestation(attestation, true).unwrap();
+                }
+                block.message.hash_tree_root().unwrap()
+            }
+            SignedBeaconBlock::Electra(block) => {
+                // the votes of the last slot before the upgrade are for a Deneb checkpoint state,
+                // which has no committees in the Electra sense to index them with
+                for attestation in block.message.body.attestations.iter() {
+                    if attestation.data.target.epoch >= context.electra_fork_epoch {
+                        store.on_electra_attestation(attestation, true).unwrap();
+                    }
+                }
+                block.message.hash_tree_root().unwrap()
+            }
+            block => panic!("unexpected block from {:?}", block.version()),
+        };
+        assert_eq!(store.get_head().unwrap(), head);
+    }
+
+    let state = generator.executor.state.inner();
+    assert_eq!(state.version(), Fork::Electra);
+    let finalized_checkpoint = state.finalized_checkpoint();
+    assert!(finalized_checkpoint.epoch >= EPOCHS - 3);
+    assert!(store.finalized_checkpoint.epoch >= finalized_checkpoint.epoch);
+
+    let validators = state.validators();
+    assert_eq!(validators.len(), VALIDATOR_COUNT + 1);
+    assert_eq!(
+        validators[COMPOUNDING_VALIDATOR].withdrawal_credentials[0],
+        COMPOUNDING_WITHDRAWAL_PREFIX
+    );
+    assert_ne!(validators[EXITING_VALIDATOR].exit_epoch, FAR_FUTURE_EPOCH);
+}
@@ -96,11 +96,15 @@ impl Fork {
             Self::Electra => &[
                 "beacon_block",
                 "beacon_state",
+                "blinded_beacon_block",
                 "block_processing",
                 "constants",
-                "execution_payload",
+                "epoch_processing",
+                "execution_engine",
+                "execution_requests",
                 "genesis",
                 "helpers",
+                "light_client",
                 "operations",
             ],
         }
@@ -138,6 +142,7 @@ impl Fork {
                     use std::collections::HashSet;
                     use crate::ssz::prelude::*;
                     use crate::crypto::{hash, fast_aggregate_verify};
+                    use crate::rewards::phase0_proposer_reward;
 
                     pub use crate::altair::fork::upgrade_to_altair;
                 };
@@ -151,6 +156,7 @@ impl Fork {
                     use crate::ssz::prelude::*;
                     use integer_sqrt::IntegerSquareRoot;
                     use crate::crypto::{hash, fast_aggregate_verify, eth_aggregate_public_keys, eth_fast_aggregate_verify};
+                    use crate::rewards::{base_reward_per_increment, flag_reward, phase0_proposer_reward, proposer_attestation_reward, proposer_sync_reward};
 
                     pub use crate::bellatrix::fork::upgrade_to_bellatrix;
                 };
@@ -164,6 +170,7 @@ impl Fork {
                     use crate::ssz::prelude::*;
                     use integer_sqrt::IntegerSquareRoot;
                     use crate::crypto::{hash, fast_aggregate_verify, eth_aggregate_public_keys, eth_fast_aggregate_verify};
+                    use crate::rewards::{base_reward_per_increment, flag_reward, phase0_proposer_reward, proposer_attestation_reward, proposer_sync_reward};
 
                     pub use crate::capella::fork::upgrade_to_capella;
                 };
@@ -175,8 +182,8 @@ impl Fork {
                     use std::collections::{HashSet, HashMap};
                     use std::iter::zip;
                     use crate::ssz::prelude::*;
-                    use integer_sqrt::IntegerSquareRoot;
                     use crate::crypto::{hash, fast_aggregate_verify, eth_aggregate_public_keys, eth_fast_aggregate_verify};
+                    use crate::rewards::{base_reward_per_increment, flag_reward, phase0_proposer_reward, proposer_sync_reward};
 
                     pub use crate::deneb::fork::upgrade_to_deneb;
                 };
@@ -190,6 +197,7 @@ impl Fork {
                     use crate::ssz::prelude::*;
                     use integer_sqrt::IntegerSquareRoot;
                     use crate::crypto::{hash, fast_aggregate_verify, eth_aggregate_public_keys, eth_fast_aggregate_verify};
+                    use crate::rewards::{base_reward_per_increment, flag_reward, phase0_proposer_reward, proposer_sync_reward};
 
                     pub use crate::electra::fork::upgrade_to_electra;
                 };
@@ -115,8 +115,19 @@ impl Type {
         }
     }
 
+    // Fields whose type changes shape at the given `Fork`, e.g. attestations in Electra.
+    // The accessors keep the type of the earlier forks and return `None` from the change
+    // onwards, so the new shape is only reached through the fork-specific variant.
+    fn field_type_changes(&self) -> Option<(Fork, Vec<&str>)> {
+        match self {
+            Self::BeaconBlockBody | Self::BlindedBeaconBlockBody => {
+                Some((Fork::Electra, vec!["attester_slashings", "attestations"]))
+            }
+            _ => None,
+        }
+    }
+
     fn imports(&self) -> syn::Item {
-        // TODO: electra
         match self {
             Self::BeaconBlockBody => parse_quote! {
                 use crate::{
@@ -124,6 +135,7 @@ impl Type {
                     bellatrix::beacon_block as bellatrix,
                     capella::beacon_block as capella,
                     deneb::{beacon_block as deneb, polynomial_commitments::KzgCommitment},
+                    electra::{beacon_block as electra, ExecutionRequests},
                     phase0::{
                         beacon_block as phase0, Attestation, AttesterSlashing, Deposit, Eth1Data, ProposerSlashing,
                         SignedVoluntaryExit,
@@ -133,6 +145,7 @@ impl Type {
                     primitives::{BlsSignature, Bytes32},
                     ssz::prelude::*,
                     Fork as Version,
+                    error::WrongFork,
                     types::execution_payload::{ExecutionPayloadRef, ExecutionPayloadRefMut},
                 };
             },
@@ -141,6 +154,7 @@ impl Type {
                     bellatrix::blinded_beacon_block as bellatrix,
                     capella::blinded_beacon_block as capella,
                     deneb::{blinded_beacon_block as deneb, polynomial_commitments::KzgCommitment},
+                    electra::{blinded_beacon_block as electra, ExecutionRequests},
                     phase0::{
                         Attestation, AttesterSlashing, Deposit, Eth1Data, ProposerSlashing,
                         SignedVoluntaryExit,
@@ -150,6 +164,7 @@ impl Type {
                     primitives::{BlsSignature, Bytes32},
                     ssz::prelude::*,
                     Fork as Version,
+                    error::WrongFork,
                     types::execution_payload_header::{ExecutionPayloadHeaderRef, ExecutionPayloadHeaderRefMut},
                 };
             },
@@ -159,10 +174,12 @@ impl Type {
                     bellatrix::beacon_block as bellatrix,
                     capella::beacon_block as capella,
                     deneb::beacon_block as deneb,
+                    electra::beacon_block as electra,
                     phase0::beacon_block as phase0,
                     primitives::{Slot, ValidatorIndex, Root},
                     ssz::prelude::*,
                     Fork as Version,
+                    error::WrongFork,
                     types::beacon_block_body::{BeaconBlockBodyRef, BeaconBlockBodyRefMut},
                 };
             },
@@ -171,9 +188,11 @@ impl Type {
                     bellatrix::blinded_beacon_block as bellatrix,
                     capella::blinded_beacon_block as capella,
                     deneb::blinded_beacon_block as deneb,
+                    electra::blinded_beacon_block as electra,
                     primitives::{Slot, ValidatorIndex, Root},
                     ssz::prelude::*,
                     Fork as Version,
+                    error::WrongFork,
                     types::blinded_beacon_block_body::{BlindedBeaconBlockBodyRef, BlindedBeaconBlockBodyRefMut},
                 };
             },
@@ -183,10 +202,12 @@ impl Type {
                     bellatrix::beacon_block as bellatrix,
                     capella::beacon_block as capella,
                     deneb::beacon_block as deneb,
+                    electra::beacon_block as electra,
                     phase0::beacon_block as phase0,
                     primitives::BlsSignature,
                     ssz::prelude::*,
                     Fork as Version,
+                    error::WrongFork,
                     types::beacon_block::{BeaconBlockRef, BeaconBlockRefMut},
                 };
             },
@@ -195,9 +216,11 @@ impl Type {
                     bellatrix::blinded_beacon_block as bellatrix,
                     capella::blinded_beacon_block as capella,
                     deneb::blinded_beacon_block as deneb,
+                    electra::blinded_beacon_block as electra,
                     primitives::BlsSignature,
                     ssz::prelude::*,
                     Fork as Version,
+                    error::WrongFork,
                     types::blinded_beacon_block::{BlindedBeaconBlockRef, BlindedBeaconBlockRefMut},
                 };
             },
@@ -209,6 +232,7 @@ impl Type {
                     primitives::{Hash32, ExecutionAddress, Bytes32},
                     ssz::prelude::*,
                     Fork as Version,
+                    error::WrongFork,
                 };
             },
             Self::ExecutionPayloadHeader => parse_quote! {
@@ -219,6 +243,7 @@ impl Type {
                     primitives::{Hash32, Root, ExecutionAddress, Bytes32},
                     ssz::prelude::*,
                     Fork as Version,
+                    error::WrongFork,
                 };
             },
             Self::BeaconState => parse_quote! {
@@ -228,11 +253,13 @@ impl Type {
                     bellatrix::beacon_state as bellatrix,
                     capella::beacon_state::{HistoricalSummary, self as capella},
                     deneb::beacon_state as deneb,
+                    electra::beacon_state::{self as electra, PendingConsolidation, PendingDeposit, PendingPartialWithdrawal},
                     phase0::beacon_state::{self as phase0, Fork},
                     phase0::{JUSTIFICATION_BITS_LENGTH, beacon_block::BeaconBlockHeader, validator::Validator, operations::{PendingAttestation, Checkpoint, Eth1Data}},
-                    primitives::{Root, ValidatorIndex, WithdrawalIndex, ParticipationFlags, Slot, Gwei, Bytes32},
+                    primitives::{Root, ValidatorIndex, WithdrawalIndex, ParticipationFlags, Slot, Epoch, Gwei, Bytes32},
                     ssz::prelude::*,
                     Fork as Version,
+                    error::WrongFork,
                     types::execution_payload_header::{ExecutionPayloadHeaderRef, ExecutionPayloadHeaderRefMut},
                 };
             },
@@ -308,7 +335,12 @@ fn derive_merge_generics(variants: &[Variant]) -> Vec<Ident> {
     all_generics
 }
 
-fn derive_merge_type(fork_sequence: &[Fork], defns: Vec<TypeDefn>) -> MergeType {
+fn derive_merge_type(
+    target_type: &Type,
+    fork_sequence: &[Fork],
+    defns: Vec<TypeDefn>,
+) -> MergeType {
+    let type_changes = target_type.field_type_changes();
     let mut variants = vec![];
     let mut fields: Vec<FieldDefn> = vec![];
     let mut seen_fields = HashSet::new();
@@ -323,7 +355,12 @@ fn derive_merge_type(fork_sequence: &[Fork], defns: Vec<TypeDefn>) -> MergeType
             let ident = field.ident.clone().unwrap();
             let mut field_defn =
                 FieldDefn { fork: *fork, ident: ident.clone(), type_def: field.ty.clone() };
-            if seen_fields.contains(&ident) {
+            let type_changed = type_changes.as_ref().is_some_and(|(change_fork, fields)| {
+                fork >= change_fork && fields.contains(&ident.to_string().as_str())
+            });
+            if seen_fields.contains(&ident) && type_changed {
+                continue
+            } else if seen_fields.contains(&ident) {
                 let target = fields.iter().position(|field| field.ident == ident).unwrap();
                 let field_to_update = &mut fields[target];
                 field_defn.fork = field_to_update.fork;
@@ -388,14 +425,12 @@ fn derive_method_set(
     ref_type: &Option<RefType>,
 ) -> Vec<ImplItemMethod> {
     let ident = &field_defn.ident;
-    let deletion_fork = target_type.field_deletions().and_then(|(fork, fields)| {
-        let identifier = ident.to_string();
-        if fields.contains(&identifier.as_str()) {
-            Some(fork)
-        } else {
-            None
-        }
-    });
+    let identifier = ident.to_string();
+    let deletion_fork = target_type
+        .field_deletions()
+        .into_iter()
+        .chain(target_type.field_type_changes())
+        .find_map(|(fork, fields)| fields.contains(&identifier.as_str()).then_some(fork));
     let field_defined_in_all_forks = field_defn.fork == fork_sequence[0];
     let is_optional = deletion_fork.is_some() || !field_defined_in_all_forks;
     let is_polymorphic = target_type.polymorphic_fields().contains(&ident.to_string().as_ref());
@@ -589,10 +624,37 @@ fn derive_fields_impl(
                     }
                 }
             };
+            let as_ident = as_syn_ident(format!("as_{fork_name}"));
+            let as_ref = parse_quote! {
+                pub fn #as_ident(&self) -> Result<&#fork_name::#type_name #arguments, WrongFork> {
+                    match self {
+                        Self::#fork(inner) => Ok(inner),
+                        other => Err(WrongFork { expected: Version::#fork, found: other.version() }),
+                    }
+                }
+            };
+            let as_mut_ident = as_syn_ident(format!("as_{fork_name}_mut"));
+            let as_mut = parse_quote! {
+                pub fn #as_mut_ident(&mut self) -> Result<&mut #fork_name::#type_name #arguments, WrongFork> {
+                    match self {
+                        Self::#fork(inner) => Ok(inner),
+                        other => Err(WrongFork { expected: Version::#fork, found: other.version() }),
+                    }
+                }
+            };
+            let into_ident = as_syn_ident(format!("into_{fork_name}"));
+            let into = parse_quote! {
+                pub fn #into_ident(self) -> Result<#fork_name::#type_name #arguments, WrongFork> {
+                    match self {
+                        Self::#fork(inner) => Ok(inner),
+                        other => Err(WrongFork { expected: Version::#fork, found: other.version() }),
+                    }
+                }
+            };
             match ref_type {
                 Some(RefType::Immutable) => vec![immut_ref],
                 Some(RefType::Mutable) => vec![immut_ref, mut_ref],
-                None => vec![immut_ref, mut_ref],
+                None => vec![immut_ref, mut_ref, as_ref, as_mut, into],
             }
         })
         .collect::<Vec<ImplItemMethod>>();
@@ -820,8 +882,44 @@ fn render(target_type: &Type, items: Vec<Item>) {
     fs::write(target_path, output).unwrap();
 }
 
+// Render `map_fork!` with an arm for each type over exactly the forks it has variants for, so
+// the macro cannot drift from the types themselves.
+fn render_map_fork(merge_types: &[(&Type, MergeType)]) {
+    let arms = merge_types
+        .iter()
+        .map(|(target_type, merge_type)| {
+            let type_name = target_type.name();
+            let variants = merge_type
+                .supported_forks()
+                .iter()
+                .map(|fork| {
+                    format!("            $crate::types::{type_name}::{fork:?}($inner) => $body,\n")
+                })
+                .collect::<String>();
+            format!(
+                "    ({type_name}, $value:expr, $inner:ident => $body:expr) => {{\n        match $value {{\n{variants}        }}\n    }};\n"
+            )
+        })
+        .collect::<String>();
+    let output = format!(
+        r#"//! WARNING: This file was derived by the `spec-gen` utility. DO NOT EDIT MANUALLY.
+
+/// Evaluate the same expression against whichever fork variant a polymorphic value holds.
+///
+/// The first argument names the type in this module, the second is the value to match on and
+/// the third binds the inner value for the body, e.g.
+/// `map_fork!(BeaconState, &state, inner => inner.validators.len())`.
+#[macro_export]
+macro_rules! map_fork {{
+{arms}}}
+"#
+    );
+    fs::write(format!("{SOURCE_ROOT}/types/map_fork.rs"), output).unwrap();
+}
+
 pub fn run() {
-    let fork_sequence = &[Fork::Phase0, Fork::Altair, Fork::Bellatrix, Fork::Capella, Fork::Deneb];
+    let fork_sequence =
+        &[Fork::Phase0, Fork::Altair, Fork::Bellatrix, Fork::Capella, Fork::Deneb, Fork::Electra];
     let types = [
         Type::BeaconBlockBody,
         Type::BeaconBlock,
@@ -834,12 +932,15 @@ pub fn run() {
         Type::BeaconState,
     ];
 
+    let mut merge_types = vec![];
     for target_type in &types {
         let defns = load_type_defns(target_type, fork_sequence);
-        let merge_type = derive_merge_type(fork_sequence, defns);
+        let merge_type = derive_merge_type(target_type, fork_sequence, defns);
         let output = as_syn(target_type, &merge_type);
-        render(target_type, output)
+        render(target_type, output);
+        merge_types.push((target_type, merge_type));
     }
+    render_map_fork(&merge_types);
 }
 
 #[cfg(test)]
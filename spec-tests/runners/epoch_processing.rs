@@ -1,7 +1,8 @@
 use crate::{
     runners::{gen_exec, gen_match_for, gen_match_for_all},
     test_case::TestCase,
-    test_utils::{load_snappy_ssz, Error},
+    test_meta::Fork,
+    test_utils::{load_snappy_ssz, state_mismatch, Error},
 };
 use ethereum_consensus::{state_transition::Context, Error as SpecError};
 
@@ -15,7 +16,7 @@ fn load_test<S: ssz_rs::Deserialize>(test_case_path: &str) -> (S, Option<S>) {
     (pre, post)
 }
 
-fn run_test<S: Eq, F>(
+fn run_test<S: Eq + serde::Serialize, F>(
     mut pre: S,
     post: Option<S>,
     context: &Context,
@@ -28,7 +29,7 @@ where
     if let Some(post) = post {
         assert!(result.is_ok());
         if pre != post {
-            Err(Error::InvalidState)
+            Err(state_mismatch(&pre, &post))
         } else {
             Ok(())
         }
@@ -39,7 +40,73 @@ where
     }
 }
 
+// Electra reorders epoch processing around the new pending deposit and consolidation queues, so
+// its handlers are dispatched separately rather than alongside the earlier forks.
+fn dispatch_electra(test: &TestCase) -> Result<(), Error> {
+    let handler = test.meta.handler.0.as_str();
+    gen_match_for! {
+        test,
+        (mainnet, electra),
+        (minimal, electra)
+        {
+            gen_exec! {
+                test,
+                load_test,
+                |(pre, post): (spec::BeaconState, Option<spec::BeaconState>), context| {
+                    run_test(pre, post, context, |state, context| match handler {
+                        "effective_balance_updates" => {
+                            spec::process_effective_balance_updates(state, context);
+                            Ok(())
+                        }
+                        "eth1_data_reset" => {
+                            spec::process_eth1_data_reset(state, context);
+                            Ok(())
+                        }
+                        "historical_summaries_update" => {
+                            spec::process_historical_summaries_update(state, context)
+                        }
+                        "inactivity_updates" => spec::process_inactivity_updates(state, context),
+                        "justification_and_finalization" => {
+                            spec::process_justification_and_finalization(state, context)
+                        }
+                        "participation_flag_updates" => {
+                            spec::process_participation_flag_updates(state)
+                        }
+                        "pending_deposits" => {
+                            spec::process_pending_deposits(state, context)
+                        }
+                        "pending_consolidations" => {
+                            spec::process_pending_consolidations(state, context);
+                            Ok(())
+                        }
+                        "randao_mixes_reset" => {
+                            spec::process_randao_mixes_reset(state, context);
+                            Ok(())
+                        }
+                        "registry_updates" => spec::process_registry_updates(state, context),
+                        "rewards_and_penalties" => {
+                            spec::process_rewards_and_penalties(state, context)
+                        }
+                        "slashings" => spec::process_slashings(state, context),
+                        "slashings_reset" => {
+                            spec::process_slashings_reset(state, context);
+                            Ok(())
+                        }
+                        "sync_committee_updates" => {
+                            spec::process_sync_committee_updates(state, context)
+                        }
+                        handler => unreachable!("no tests for {handler}"),
+                    })
+                }
+            }
+        }
+    }
+}
+
 pub fn dispatch(test: &TestCase) -> Result<(), Error> {
+    if matches!(test.meta.fork, Fork::Electra) {
+        return dispatch_electra(test)
+    }
     match test.meta.handler.0.as_str() {
         "effective_balance_updates" => {
             gen_match_for_all! {
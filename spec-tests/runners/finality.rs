@@ -1,6 +1,6 @@
 use crate::{
     runners::{
-        gen_exec, gen_match_for_all,
+        gen_exec, gen_match_for,
         utils::{load_blocks_test, run_blocks_test},
     },
     test_case::TestCase,
@@ -11,11 +11,28 @@ use ethereum_consensus::state_transition::Validation;
 pub fn dispatch(test: &TestCase) -> Result<(), Error> {
     match test.meta.handler.0.as_str() {
         "finality" => {
-            gen_match_for_all! {
+            gen_match_for! {
                 test,
-                load_blocks_test,
-                |(pre, post, blocks): (spec::BeaconState, Option<spec::BeaconState>, Vec<spec::SignedBeaconBlock>), context| {
-                    run_blocks_test(pre, post, blocks, context, |state, signed_block, context| { spec::state_transition(state, signed_block, Validation::Enabled, context) })
+                (mainnet, phase0),
+                (mainnet, altair),
+                (mainnet, bellatrix),
+                (mainnet, capella),
+                (mainnet, deneb),
+                (mainnet, electra),
+                (minimal, phase0),
+                (minimal, altair),
+                (minimal, bellatrix),
+                (minimal, capella),
+                (minimal, deneb),
+                (minimal, electra)
+                {
+                    gen_exec! {
+                        test,
+                        load_blocks_test,
+                        |(pre, post, blocks): (spec::BeaconState, Option<spec::BeaconState>, Vec<spec::SignedBeaconBlock>), context| {
+                            run_blocks_test(pre, post, blocks, context, |state, signed_block, context| { spec::state_transition(state, signed_block, Validation::Enabled, context) })
+                        }
+                    }
                 }
             }
         }
@@ -80,6 +80,18 @@ pub fn dispatch(test: &TestCase) -> Result<(), Error> {
                         }
                     }
                 }
+                Fork::Electra => {
+                    use ethereum_consensus::{
+                        deneb::mainnet as pre_spec, electra::mainnet as spec,
+                    };
+                    gen_exec! {
+                        test,
+                        load_test,
+                        |(pre, expected): (pre_spec::BeaconState, spec::BeaconState), context| {
+                            run_test(pre, expected, context, |state, context| spec::upgrade_to_electra(state, context).unwrap())
+                        }
+                    }
+                }
                 fork => unreachable!("no tests for (Mainnet, {fork:?})"),
             },
             Config::Minimal => match test.meta.fork {
@@ -131,6 +143,18 @@ pub fn dispatch(test: &TestCase) -> Result<(), Error> {
                         }
                     }
                 }
+                Fork::Electra => {
+                    use ethereum_consensus::{
+                        deneb::minimal as pre_spec, electra::minimal as spec,
+                    };
+                    gen_exec! {
+                        test,
+                        load_test,
+                        |(pre, expected): (pre_spec::BeaconState, spec::BeaconState), context| {
+                            run_test(pre, expected, context, |state, context| spec::upgrade_to_electra(state, context).unwrap())
+                        }
+                    }
+                }
                 fork => unreachable!("no tests for (Minimal, {fork:?})"),
             },
             config => unreachable!("no tests for {config:?}"),
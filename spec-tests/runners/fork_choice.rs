@@ -0,0 +1,230 @@
+use crate::{
+    test_case::TestCase,
+    test_meta::{Config, Fork},
+    test_utils::{load_snappy_ssz, load_yaml, Error},
+};
+use ethereum_consensus::{
+    bellatrix::fork_choice::PowBlock,
+    deneb::polynomial_commitments::KzgProof,
+    phase0::{BeaconBlockHeader, Checkpoint},
+    primitives::{Epoch, Root, Slot},
+    state_transition::Context,
+    types::{BeaconState, SignedBeaconBlock},
+    Error as SpecError,
+};
+use serde::Deserialize;
+use ssz_rs::prelude::*;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Step {
+    Tick {
+        tick: u64,
+    },
+    Block {
+        block: String,
+        blobs: Option<String>,
+        #[serde(default)]
+        proofs: Vec<KzgProof>,
+        #[serde(default = "default_valid")]
+        valid: bool,
+    },
+    Attestation {
+        attestation: String,
+        #[serde(default = "default_valid")]
+        valid: bool,
+    },
+    AttesterSlashing {
+        attester_slashing: String,
+        #[serde(default = "default_valid")]
+        valid: bool,
+    },
+    PowBlock {
+        pow_block: String,
+    },
+    Checks {
+        checks: Checks,
+    },
+}
+
+fn default_valid() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct Head {
+    slot: Slot,
+    root: Root,
+}
+
+#[derive(Deserialize)]
+struct CheckpointData {
+    epoch: Epoch,
+    root: Root,
+}
+
+impl From<CheckpointData> for Checkpoint {
+    fn from(CheckpointData { epoch, root }: CheckpointData) -> Self {
+        Self { epoch, root }
+    }
+}
+
+#[derive(Deserialize)]
+struct Checks {
+    head: Option<Head>,
+    time: Option<u64>,
+    genesis_time: Option<u64>,
+    justified_checkpoint: Option<CheckpointData>,
+    finalized_checkpoint: Option<CheckpointData>,
+    proposer_boost_root: Option<Root>,
+}
+
+fn check<T: PartialEq + std::fmt::Debug>(
+    name: &'static str,
+    expected: Option<T>,
+    found: T,
+) -> Result<(), Error> {
+    match expected {
+        Some(expected) if expected != found => Err(Error::CheckMismatch {
+            check: name,
+            expected: format!("{expected:?}"),
+            found: format!("{found:?}"),
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn check_validity(result: Result<(), SpecError>, valid: bool) -> Result<(), Error> {
+    match result {
+        Ok(()) if !valid => Err(Error::Expected),
+        Err(err) if valid => Err(err.into()),
+        _ => Ok(()),
+    }
+}
+
+// Schedule every fork up to `fork` at genesis and leave later forks unscheduled, so that the
+// states of the test are never upgraded.
+pub(crate) fn context_for_fork(context: &Context, fork: Fork) -> Context {
+    let mut context = context.clone();
+    let fork_epoch = |target: Fork| if fork as usize >= target as usize { 0 } else { Epoch::MAX };
+    context.altair_fork_epoch = fork_epoch(Fork::Altair);
+    context.bellatrix_fork_epoch = fork_epoch(Fork::Bellatrix);
+    context.capella_fork_epoch = fork_epoch(Fork::Capella);
+    context.deneb_fork_epoch = fork_epoch(Fork::Deneb);
+    context.electra_fork_epoch = fork_epoch(Fork::Electra);
+    context
+}
+
+macro_rules! run_test {
+    ($test:expr, $config:ident, $fork:ident, $variant:ident) => {{
+        use ethereum_consensus::{
+            deneb::$config::{Blob, MAX_BLOB_COMMITMENTS_PER_BLOCK},
+            fork_choice::$config::Store,
+            phase0::$config::{Attestation, AttesterSlashing},
+            $fork::$config as spec,
+        };
+
+        let path = &$test.data_path;
+        let step_path = |name: &str| format!("{path}/{name}.ssz_snappy");
+        let context = context_for_fork($test.context(), $test.meta.fork);
+
+        let anchor_state: spec::BeaconState = load_snappy_ssz(&step_path("anchor_state")).unwrap();
+        let anchor_block: spec::BeaconBlock = load_snappy_ssz(&step_path("anchor_block")).unwrap();
+        let anchor_header = BeaconBlockHeader {
+            slot: anchor_block.slot,
+            proposer_index: anchor_block.proposer_index,
+            parent_root: anchor_block.parent_root,
+            state_root: anchor_block.state_root,
+            body_root: anchor_block.body.hash_tree_root().map_err(SpecError::from)?,
+        };
+        let mut store = Store::new(BeaconState::$variant(anchor_state), anchor_header, context)?;
+
+        let steps: Vec<Step> = load_yaml(&format!("{path}/steps.yaml"));
+        for step in steps {
+            match step {
+                Step::Tick { tick } => store.on_tick(tick),
+                Step::Block { block, blobs, proofs, valid } => {
+                    let block: spec::SignedBeaconBlock =
+                        load_snappy_ssz(&step_path(&block)).unwrap();
+                    let block = SignedBeaconBlock::$variant(block);
+                    let mut apply = || -> Result<(), SpecError> {
+                        match &blobs {
+                            Some(blobs) => {
+                                let blobs: List<Blob, MAX_BLOB_COMMITMENTS_PER_BLOCK> =
+                                    load_snappy_ssz(&step_path(blobs)).unwrap();
+                                store.on_block_with_blobs(&block, &blobs, &proofs)?;
+                            }
+                            None => store.on_block(&block)?,
+                        }
+                        // a block also delivers the attestations and attester slashings it carries
+                        let message = block.message();
+                        let body = message.body();
+                        for attestation in body.attestations().into_iter().flatten() {
+                            store.on_attestation(attestation, true)?;
+                        }
+                        for attester_slashing in body.attester_slashings().into_iter().flatten() {
+                            store.on_attester_slashing(attester_slashing)?;
+                        }
+                        Ok(())
+                    };
+                    check_validity(apply(), valid)?;
+                }
+                Step::Attestation { attestation, valid } => {
+                    let attestation: Attestation =
+                        load_snappy_ssz(&step_path(&attestation)).unwrap();
+                    check_validity(store.on_attestation(&attestation, false), valid)?;
+                }
+                Step::AttesterSlashing { attester_slashing, valid } => {
+                    let attester_slashing: AttesterSlashing =
+                        load_snappy_ssz(&step_path(&attester_slashing)).unwrap();
+                    check_validity(store.on_attester_slashing(&attester_slashing), valid)?;
+                }
+                Step::PowBlock { pow_block } => {
+                    let pow_block: PowBlock = load_snappy_ssz(&step_path(&pow_block)).unwrap();
+                    store.pow_blocks.insert(pow_block.block_hash.clone(), pow_block);
+                }
+                Step::Checks { checks } => {
+                    if let Some(head) = checks.head {
+                        let root = store.get_head()?;
+                        let slot = store.block(&root)?.slot;
+                        check("head", Some((head.slot, head.root)), (slot, root))?;
+                    }
+                    check("time", checks.time, store.time)?;
+                    check("genesis_time", checks.genesis_time, store.genesis_time)?;
+                    check(
+                        "justified_checkpoint",
+                        checks.justified_checkpoint.map(Checkpoint::from),
+                        store.justified_checkpoint.clone(),
+                    )?;
+                    check(
+                        "finalized_checkpoint",
+                        checks.finalized_checkpoint.map(Checkpoint::from),
+                        store.finalized_checkpoint.clone(),
+                    )?;
+                    check(
+                        "proposer_boost_root",
+                        checks.proposer_boost_root,
+                        store.proposer_boost_root,
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }};
+}
+
+pub fn dispatch(test: &TestCase) -> Result<(), Error> {
+    match (test.meta.config, test.meta.fork) {
+        (Config::Mainnet, Fork::Phase0) => run_test!(test, mainnet, phase0, Phase0),
+        (Config::Mainnet, Fork::Altair) => run_test!(test, mainnet, altair, Altair),
+        (Config::Mainnet, Fork::Bellatrix) => run_test!(test, mainnet, bellatrix, Bellatrix),
+        (Config::Mainnet, Fork::Capella) => run_test!(test, mainnet, capella, Capella),
+        (Config::Mainnet, Fork::Deneb) => run_test!(test, mainnet, deneb, Deneb),
+        (Config::Minimal, Fork::Phase0) => run_test!(test, minimal, phase0, Phase0),
+        (Config::Minimal, Fork::Altair) => run_test!(test, minimal, altair, Altair),
+        (Config::Minimal, Fork::Bellatrix) => run_test!(test, minimal, bellatrix, Bellatrix),
+        (Config::Minimal, Fork::Capella) => run_test!(test, minimal, capella, Capella),
+        (Config::Minimal, Fork::Deneb) => run_test!(test, minimal, deneb, Deneb),
+        pair => unreachable!("no tests for {pair:?}"),
+    }
+}
@@ -287,6 +287,62 @@ pub fn dispatch(test: &TestCase) -> Result<(), Error> {
                         }
                     }
                 }
+                (minimal, electra) => {
+                    gen_exec! {
+                        test,
+                        load_initialization_test,
+                        |(eth1, deposits, execution_payload_header, expected): (
+                            Eth1,
+                            Vec<spec::Deposit>,
+                            Option<spec::ExecutionPayloadHeader>,
+                            spec::BeaconState
+                        ),
+                         context| {
+                            let state = spec::initialize_beacon_state_from_eth1::<
+                                { spec::SLOTS_PER_HISTORICAL_ROOT },
+                                { spec::HISTORICAL_ROOTS_LIMIT },
+                                { spec::ETH1_DATA_VOTES_BOUND },
+                                { spec::VALIDATOR_REGISTRY_LIMIT },
+                                { spec::EPOCHS_PER_HISTORICAL_VECTOR },
+                                { spec::EPOCHS_PER_SLASHINGS_VECTOR },
+                                { spec::MAX_VALIDATORS_PER_COMMITTEE },
+                                { spec::SYNC_COMMITTEE_SIZE },
+                                { spec::PENDING_DEPOSITS_LIMIT },
+                                { spec::PENDING_PARTIAL_WITHDRAWALS_LIMIT },
+                                { spec::PENDING_CONSOLIDATIONS_LIMIT },
+                                { spec::MAX_PROPOSER_SLASHINGS },
+                                { spec::MAX_VALIDATORS_PER_SLOT },
+                                { spec::MAX_COMMITTEES_PER_SLOT },
+                                { spec::MAX_ATTESTER_SLASHINGS_ELECTRA },
+                                { spec::MAX_ATTESTATIONS_ELECTRA },
+                                { spec::MAX_DEPOSITS },
+                                { spec::MAX_VOLUNTARY_EXITS },
+                                { spec::BYTES_PER_LOGS_BLOOM },
+                                { spec::MAX_EXTRA_DATA_BYTES },
+                                { spec::MAX_BYTES_PER_TRANSACTION },
+                                { spec::MAX_TRANSACTIONS_PER_PAYLOAD },
+                                { spec::MAX_WITHDRAWALS_PER_PAYLOAD },
+                                { spec::MAX_BLS_TO_EXECUTION_CHANGES },
+                                { spec::MAX_BLOB_COMMITMENTS_PER_BLOCK },
+                                { spec::MAX_DEPOSIT_REQUESTS_PER_PAYLOAD },
+                                { spec::MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD },
+                                { spec::MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD },
+                            >(
+                                eth1.eth1_block_hash,
+                                eth1.eth1_timestamp,
+                                &deposits,
+                                execution_payload_header.as_ref(),
+                                context,
+                            )
+                            .unwrap();
+                            if expected == state {
+                                Ok(())
+                            } else {
+                                Err(Error::InvalidState)
+                            }
+                        }
+                    }
+                }
             }
         }
         "validity" => {
@@ -296,7 +352,8 @@ pub fn dispatch(test: &TestCase) -> Result<(), Error> {
                 (minimal, altair),
                 (minimal, bellatrix),
                 (minimal, capella),
-                (minimal, deneb)
+                (minimal, deneb),
+                (minimal, electra)
                 {
                     gen_exec! {
                         test,
@@ -1,31 +1,52 @@
 use crate::{
     test_case::TestCase,
+    test_meta::Config,
     test_utils::{load_yaml, Error},
 };
 use ethereum_consensus::deneb::{
-    mainnet::Blob,
+    blob_sidecar::Blob,
+    mainnet, minimal,
     polynomial_commitments::{
-        blob_to_kzg_commitment, compute_blob_kzg_proof, compute_kzg_proof, verify_blob_kzg_proof,
-        verify_blob_kzg_proof_batch, verify_kzg_proof, FieldElement, KzgCommitment, KzgProof,
-        KzgSettings, ProofAndEvaluation,
+        blob_to_kzg_commitment, compute_blob_kzg_proof, compute_kzg_proof, mainnet_kzg_settings,
+        verify_blob_kzg_proof, verify_blob_kzg_proof_batch, verify_kzg_proof, FieldElement,
+        KzgCommitment, KzgProof, KzgSettings, ProofAndEvaluation,
     },
 };
 
 pub fn dispatch(test: &TestCase) -> Result<(), Error> {
-    let kzg_settings = &test.context().kzg_settings;
+    // the `general` vectors are generated with the mainnet preset
+    match test.meta.config {
+        Config::Mainnet | Config::General => {
+            dispatch_for_preset::<{ mainnet::BYTES_PER_BLOB }>(test)
+        }
+        Config::Minimal => dispatch_for_preset::<{ minimal::BYTES_PER_BLOB }>(test),
+    }
+}
+
+fn dispatch_for_preset<const BYTES_PER_BLOB: usize>(test: &TestCase) -> Result<(), Error> {
+    // every preset shares the trusted setup of mainnet, which is loaded once for all the tests
+    let kzg_settings = mainnet_kzg_settings();
 
     match test.meta.handler.0.as_str() {
-        "blob_to_kzg_commitment" => run_blob_to_kzg_commitment_test(test, kzg_settings),
-        "compute_kzg_proof" => run_compute_kzg_proof_test(test, kzg_settings),
+        "blob_to_kzg_commitment" => {
+            run_blob_to_kzg_commitment_test::<BYTES_PER_BLOB>(test, kzg_settings)
+        }
+        "compute_kzg_proof" => run_compute_kzg_proof_test::<BYTES_PER_BLOB>(test, kzg_settings),
         "verify_kzg_proof" => run_verify_kzg_proof_test(test, kzg_settings),
-        "compute_blob_kzg_proof" => run_compute_blob_kzg_proof_test(test, kzg_settings),
-        "verify_blob_kzg_proof" => run_verify_blob_kzg_proof_test(test, kzg_settings),
-        "verify_blob_kzg_proof_batch" => run_verify_blob_kzg_proof_batch_test(test, kzg_settings),
+        "compute_blob_kzg_proof" => {
+            run_compute_blob_kzg_proof_test::<BYTES_PER_BLOB>(test, kzg_settings)
+        }
+        "verify_blob_kzg_proof" => {
+            run_verify_blob_kzg_proof_test::<BYTES_PER_BLOB>(test, kzg_settings)
+        }
+        "verify_blob_kzg_proof_batch" => {
+            run_verify_blob_kzg_proof_batch_test::<BYTES_PER_BLOB>(test, kzg_settings)
+        }
         handler => unreachable!("no tests for {handler}"),
     }
 }
 
-fn run_blob_to_kzg_commitment_test(
+fn run_blob_to_kzg_commitment_test<const BYTES_PER_BLOB: usize>(
     test: &TestCase,
     kzg_settings: &KzgSettings,
 ) -> Result<(), Error> {
@@ -39,7 +60,7 @@ fn run_blob_to_kzg_commitment_test(
     let output: Option<KzgCommitment> = serde_yaml::from_value(output_yaml.clone()).unwrap();
 
     // Check the deserialization of input(s)
-    let blob: Blob = match serde_yaml::from_value(blob_yaml.clone()) {
+    let blob: Blob<BYTES_PER_BLOB> = match serde_yaml::from_value(blob_yaml.clone()) {
         Ok(blob) => blob,
         Err(_) => {
             assert!(output.is_none());
@@ -61,7 +82,10 @@ fn run_blob_to_kzg_commitment_test(
     }
 }
 
-fn run_compute_kzg_proof_test(test: &TestCase, kzg_settings: &KzgSettings) -> Result<(), Error> {
+fn run_compute_kzg_proof_test<const BYTES_PER_BLOB: usize>(
+    test: &TestCase,
+    kzg_settings: &KzgSettings,
+) -> Result<(), Error> {
     let path = &test.data_path;
     let path = path.to_string() + "/data.yaml";
     let test_data: serde_yaml::Value = load_yaml(&path);
@@ -72,7 +96,7 @@ fn run_compute_kzg_proof_test(test: &TestCase, kzg_settings: &KzgSettings) -> Re
     let output: Option<(KzgProof, FieldElement)> =
         serde_yaml::from_value(output_yaml.clone()).unwrap();
 
-    let blob: Blob = match serde_yaml::from_value(blob_yaml.clone()) {
+    let blob: Blob<BYTES_PER_BLOB> = match serde_yaml::from_value(blob_yaml.clone()) {
         Ok(blob) => blob,
         Err(_) => {
             assert!(output.is_none());
@@ -160,7 +184,7 @@ fn run_verify_kzg_proof_test(test: &TestCase, kzg_settings: &KzgSettings) -> Res
     }
 }
 
-fn run_compute_blob_kzg_proof_test(
+fn run_compute_blob_kzg_proof_test<const BYTES_PER_BLOB: usize>(
     test: &TestCase,
     kzg_settings: &KzgSettings,
 ) -> Result<(), Error> {
@@ -173,7 +197,7 @@ fn run_compute_blob_kzg_proof_test(
     let output_yaml = test_data.get("output").unwrap();
     let output: Option<KzgProof> = serde_yaml::from_value(output_yaml.clone()).unwrap();
 
-    let blob: Blob = match serde_yaml::from_value(blob_yaml.clone()) {
+    let blob: Blob<BYTES_PER_BLOB> = match serde_yaml::from_value(blob_yaml.clone()) {
         Ok(blob) => blob,
         Err(_) => {
             assert!(output.is_none());
@@ -200,7 +224,7 @@ fn run_compute_blob_kzg_proof_test(
     }
 }
 
-fn run_verify_blob_kzg_proof_test(
+fn run_verify_blob_kzg_proof_test<const BYTES_PER_BLOB: usize>(
     test: &TestCase,
     kzg_settings: &KzgSettings,
 ) -> Result<(), Error> {
@@ -214,7 +238,7 @@ fn run_verify_blob_kzg_proof_test(
     let output_yaml = test_data.get("output").unwrap();
     let output: Option<bool> = serde_yaml::from_value(output_yaml.clone()).unwrap();
 
-    let blob: Blob = match serde_yaml::from_value(blob_yaml.clone()) {
+    let blob: Blob<BYTES_PER_BLOB> = match serde_yaml::from_value(blob_yaml.clone()) {
         Ok(blob) => blob,
         Err(_) => {
             assert!(output.is_none());
@@ -253,7 +277,7 @@ fn run_verify_blob_kzg_proof_test(
     }
 }
 
-fn run_verify_blob_kzg_proof_batch_test(
+fn run_verify_blob_kzg_proof_batch_test<const BYTES_PER_BLOB: usize>(
     test: &TestCase,
     kzg_settings: &KzgSettings,
 ) -> Result<(), Error> {
@@ -267,7 +291,7 @@ fn run_verify_blob_kzg_proof_batch_test(
     let output_yaml = test_data.get("output").unwrap();
     let output: Option<bool> = serde_yaml::from_value(output_yaml.clone()).unwrap();
 
-    let blobs: Vec<Blob> = match serde_yaml::from_value(blobs_yaml.clone()) {
+    let blobs: Vec<Blob<BYTES_PER_BLOB>> = match serde_yaml::from_value(blobs_yaml.clone()) {
         Ok(blobs) => blobs,
         Err(_) => {
             assert!(output.is_none());
@@ -1,22 +1,29 @@
 use crate::{
-    runners::gen_match_for,
+    runners::{fork_choice::context_for_fork, gen_match_for},
     test_case::TestCase,
-    test_meta::TestMeta,
+    test_meta::{Config, TestMeta},
     test_utils::{load_snappy_ssz, load_yaml, Error},
     Fork,
 };
-use ethereum_consensus::Error as SpecError;
+use ethereum_consensus::{
+    light_client::{LightClientHeader, Tracker},
+    primitives::{ForkDigest, Root, Slot},
+    serde as eth_serde,
+    state_transition::Context,
+    Error as SpecError, Fork as ConsensusFork,
+};
 use serde::Deserialize;
 use ssz_rs::{
     prelude::*,
     proofs::{get_subtree_index, is_valid_merkle_branch_for_generalized_index, log_2},
 };
+use std::path::Path;
 
 #[derive(Debug, Deserialize)]
 pub struct Proof {
-    leaf: Node,
-    leaf_index: GeneralizedIndex,
-    branch: Vec<Node>,
+    pub leaf: Node,
+    pub leaf_index: GeneralizedIndex,
+    pub branch: Vec<Node>,
 }
 
 pub fn load_test<O: ssz_rs::Deserialize>(test_case_path: &str) -> (O, Proof) {
@@ -68,6 +75,262 @@ pub fn run_test<O: SimpleSerialize>(object: O, path: Path, proof: &Proof) -> Res
     .map_err(|err| SpecError::from(err).into())
 }
 
+#[derive(Deserialize)]
+struct SyncMeta {
+    genesis_validators_root: Root,
+    trusted_block_root: Root,
+    #[serde(deserialize_with = "eth_serde::as_hex::deserialize")]
+    bootstrap_fork_digest: ForkDigest,
+}
+
+#[derive(Deserialize)]
+struct HeaderCheck {
+    slot: Slot,
+    beacon_root: Root,
+    execution_root: Option<Root>,
+}
+
+#[derive(Deserialize)]
+struct SyncChecks {
+    finalized_header: HeaderCheck,
+    optimistic_header: HeaderCheck,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SyncStep {
+    ForceUpdate {
+        current_slot: Slot,
+        checks: SyncChecks,
+    },
+    ProcessUpdate {
+        #[serde(deserialize_with = "eth_serde::as_hex::deserialize")]
+        update_fork_digest: ForkDigest,
+        update: String,
+        current_slot: Slot,
+        checks: SyncChecks,
+    },
+    UpgradeStore {
+        checks: SyncChecks,
+    },
+}
+
+#[derive(Deserialize)]
+struct UpdateRankingMeta {
+    updates_count: usize,
+}
+
+// Find the fork an object was produced at from the fork digest it is tagged with.
+fn fork_at_digest(
+    digest: ForkDigest,
+    genesis_validators_root: Root,
+    context: &Context,
+) -> Result<ConsensusFork, Error> {
+    let forks = [
+        ConsensusFork::Phase0,
+        ConsensusFork::Altair,
+        ConsensusFork::Bellatrix,
+        ConsensusFork::Capella,
+        ConsensusFork::Deneb,
+        ConsensusFork::Electra,
+    ];
+    for fork in forks {
+        if context.fork_digest(fork, genesis_validators_root)? == digest {
+            return Ok(fork)
+        }
+    }
+    unreachable!("no fork has the digest {digest:?}")
+}
+
+fn check_header(
+    name: &'static str,
+    expected: &HeaderCheck,
+    slot: Slot,
+    beacon_root: Root,
+    execution_root: Option<Root>,
+) -> Result<(), Error> {
+    let expected_execution_root = expected.execution_root.or(execution_root);
+    let expected = (expected.slot, expected.beacon_root, expected_execution_root);
+    let found = (slot, beacon_root, execution_root);
+    if expected != found {
+        return Err(Error::CheckMismatch {
+            check: name,
+            expected: format!("{expected:?}"),
+            found: format!("{found:?}"),
+        })
+    }
+    Ok(())
+}
+
+// Decode the object at `$path` with the type of the fork it was produced at and upgrade it to the
+// type of `$fork`, the fork of the test.
+macro_rules! load_upgraded {
+    ($path:expr, $data_fork:expr, $config:ident, altair, $kind:ident) => {
+        paste::paste! {
+            load_snappy_ssz::<
+                ethereum_consensus::altair::$config::[<LightClient $kind>],
+            >(&$path)
+            .unwrap()
+        }
+    };
+    ($path:expr, $data_fork:expr, $config:ident, bellatrix, $kind:ident) => {
+        load_upgraded!($path, $data_fork, $config, altair, $kind)
+    };
+    ($path:expr, $data_fork:expr, $config:ident, capella, $kind:ident) => {
+        match $data_fork {
+            ConsensusFork::Capella => paste::paste! {
+                load_snappy_ssz::<
+                    ethereum_consensus::capella::$config::[<LightClient $kind>],
+                >(&$path)
+                .unwrap()
+            },
+            _ => paste::paste! {
+                ethereum_consensus::capella::light_client::[<upgrade_lc_ $kind:snake _to_capella>](
+                    &load_upgraded!($path, $data_fork, $config, altair, $kind),
+                )
+            },
+        }
+    };
+    ($path:expr, $data_fork:expr, $config:ident, deneb, $kind:ident) => {
+        match $data_fork {
+            ConsensusFork::Deneb => paste::paste! {
+                load_snappy_ssz::<
+                    ethereum_consensus::deneb::$config::[<LightClient $kind>],
+                >(&$path)
+                .unwrap()
+            },
+            _ => paste::paste! {
+                ethereum_consensus::deneb::light_client::[<upgrade_lc_ $kind:snake _to_deneb>](
+                    &load_upgraded!($path, $data_fork, $config, capella, $kind),
+                )
+            },
+        }
+    };
+}
+
+// The `Tracker` only ranks updates, so it follows the headers of the store for as long as the
+// store takes them from updates.
+fn check_tracker<H: LightClientHeader>(
+    tracker: &Tracker<H>,
+    finalized_header: &H,
+    optimistic_header: &H,
+) -> Result<(), Error> {
+    for (check, expected, found) in [
+        ("tracker_finalized", finalized_header, tracker.finalized()),
+        ("tracker_head", optimistic_header, tracker.current_head()),
+    ] {
+        if expected.beacon() != found.beacon() {
+            return Err(Error::CheckMismatch {
+                check,
+                expected: format!("{:?}", expected.beacon()),
+                found: format!("{:?}", found.beacon()),
+            })
+        }
+    }
+    Ok(())
+}
+
+macro_rules! execution_root {
+    (altair, $header:expr, $context:expr) => {
+        None
+    };
+    (bellatrix, $header:expr, $context:expr) => {
+        None
+    };
+    ($fork:ident, $header:expr, $context:expr) => {
+        Some(spec::get_lc_execution_root($header, $context)?)
+    };
+}
+
+macro_rules! run_sync {
+    ($test:expr, $config:ident, $fork:ident) => {{
+        use ethereum_consensus::$fork::$config as spec;
+
+        let path = &$test.data_path;
+        // tests across a fork transition ship the config scheduling the fork
+        let config_path = format!("{path}/config.yaml");
+        let context = if Path::new(&config_path).exists() {
+            Context::try_from_file(&config_path)?
+        } else {
+            context_for_fork($test.context(), $test.meta.fork)
+        };
+
+        let meta: SyncMeta = load_yaml(&format!("{path}/meta.yaml"));
+        let genesis_validators_root = meta.genesis_validators_root;
+        let bootstrap_fork =
+            fork_at_digest(meta.bootstrap_fork_digest, genesis_validators_root, &context)?;
+        let bootstrap_path = format!("{path}/bootstrap.ssz_snappy");
+        let bootstrap: spec::LightClientBootstrap =
+            load_upgraded!(bootstrap_path, bootstrap_fork, $config, $fork, Bootstrap);
+        let mut store =
+            spec::initialize_light_client_store(meta.trusted_block_root, &bootstrap, &context)?;
+        let mut tracker = Some(Tracker::new(bootstrap.header.clone(), &context));
+
+        let steps: Vec<SyncStep> = load_yaml(&format!("{path}/steps.yaml"));
+        for step in steps {
+            let checks = match step {
+                SyncStep::ForceUpdate { current_slot, checks } => {
+                    spec::process_light_client_store_force_update(
+                        &mut store,
+                        current_slot,
+                        &context,
+                    )?;
+                    // the tracker has no update timeout to force, so stop following the store
+                    tracker = None;
+                    checks
+                }
+                SyncStep::ProcessUpdate { update_fork_digest, update, current_slot, checks } => {
+                    let update_fork =
+                        fork_at_digest(update_fork_digest, genesis_validators_root, &context)?;
+                    let update_path = format!("{path}/{update}.ssz_snappy");
+                    let update: spec::LightClientUpdate =
+                        load_upgraded!(update_path, update_fork, $config, $fork, Update);
+                    spec::process_light_client_update(
+                        &mut store,
+                        &update,
+                        current_slot,
+                        genesis_validators_root,
+                        &context,
+                    )?;
+                    if let Some(tracker) = tracker.as_mut() {
+                        tracker.process(&update, current_slot).map_err(|err| {
+                            Error::CheckMismatch {
+                                check: "tracker_update",
+                                expected: "update accepted".to_string(),
+                                found: err.to_string(),
+                            }
+                        })?;
+                    }
+                    checks
+                }
+                // every object is upgraded to the fork of the test as it is loaded so the store
+                // is already in its final form
+                SyncStep::UpgradeStore { checks } => checks,
+            };
+            let header = &store.finalized_header;
+            check_header(
+                "finalized_header",
+                &checks.finalized_header,
+                header.beacon.slot,
+                header.beacon.hash_tree_root().map_err(SpecError::from)?,
+                execution_root!($fork, header, &context),
+            )?;
+            let header = &store.optimistic_header;
+            check_header(
+                "optimistic_header",
+                &checks.optimistic_header,
+                header.beacon.slot,
+                header.beacon.hash_tree_root().map_err(SpecError::from)?,
+                execution_root!($fork, header, &context),
+            )?;
+            if let Some(tracker) = tracker.as_ref() {
+                check_tracker(tracker, &store.finalized_header, &store.optimistic_header)?;
+            }
+        }
+        Ok(())
+    }};
+}
+
 pub fn dispatch(test: &TestCase) -> Result<(), Error> {
     match test.meta.handler.0.as_str() {
         "single_merkle_proof" => {
@@ -94,6 +357,54 @@ pub fn dispatch(test: &TestCase) -> Result<(), Error> {
                 }
             }
         }
+        "sync" => match (test.meta.config, test.meta.fork) {
+            (Config::Mainnet, Fork::Altair) => run_sync!(test, mainnet, altair),
+            (Config::Mainnet, Fork::Bellatrix) => run_sync!(test, mainnet, bellatrix),
+            (Config::Mainnet, Fork::Capella) => run_sync!(test, mainnet, capella),
+            (Config::Mainnet, Fork::Deneb) => run_sync!(test, mainnet, deneb),
+            (Config::Minimal, Fork::Altair) => run_sync!(test, minimal, altair),
+            (Config::Minimal, Fork::Bellatrix) => run_sync!(test, minimal, bellatrix),
+            (Config::Minimal, Fork::Capella) => run_sync!(test, minimal, capella),
+            (Config::Minimal, Fork::Deneb) => run_sync!(test, minimal, deneb),
+            pair => unreachable!("no tests for {pair:?}"),
+        },
+        "update_ranking" => {
+            gen_match_for! {
+                test,
+                (mainnet, altair),
+                (mainnet, bellatrix),
+                (mainnet, capella),
+                (mainnet, deneb),
+                (minimal, altair),
+                (minimal, bellatrix),
+                (minimal, capella),
+                (minimal, deneb)
+                {
+                    let path = &test.data_path;
+                    let context = context_for_fork(test.context(), test.meta.fork);
+                    let meta: UpdateRankingMeta = load_yaml(&format!("{path}/meta.yaml"));
+                    let updates = (0..meta.updates_count)
+                        .map(|i| {
+                            load_snappy_ssz::<spec::LightClientUpdate>(&format!(
+                                "{path}/updates_{i}.ssz_snappy"
+                            ))
+                            .unwrap()
+                        })
+                        .collect::<Vec<_>>();
+                    // updates are sorted from best to worst
+                    for (i, pair) in updates.windows(2).enumerate() {
+                        if spec::is_better_update(&pair[1], &pair[0], &context) {
+                            return Err(Error::CheckMismatch {
+                                check: "update_ranking",
+                                expected: format!("update {i} ranked first"),
+                                found: format!("update {} ranked first", i + 1),
+                            })
+                        }
+                    }
+                    Ok(())
+                }
+            }
+        }
         handler => unreachable!("no tests for {handler}"),
     }
 }
@@ -6,6 +6,7 @@ use crate::{
     test_case::TestCase,
     test_utils::Error,
 };
+use ethereum_consensus::Fork;
 
 pub fn dispatch(test: &TestCase) -> Result<(), Error> {
     match test.meta.handler.0.as_str() {
@@ -18,9 +19,17 @@ pub fn dispatch(test: &TestCase) -> Result<(), Error> {
                     gen_exec! {
                         test,
                         load_test,
-                        |(object, proof): (spec::BeaconBlockBody, Proof), _| {
+                        |(object, proof): (spec::BeaconBlockBody, Proof), context| {
                             // NOTE: `0` index is hard-coded in test generator
-                            let path = &["blob_kzg_commitments".into(), 0.into()];
+                            let index = 0;
+                            let g_index =
+                                spec::blob_kzg_commitment_gindex(index, Fork::Deneb, context)?;
+                            assert_eq!(g_index, proof.leaf_index);
+                            let branch =
+                                spec::compute_kzg_commitment_inclusion_proof(&object, index)?;
+                            assert_eq!(branch, proof.branch);
+
+                            let path = &["blob_kzg_commitments".into(), index.into()];
                             run_test(object, path, &proof)
                         }
                     }
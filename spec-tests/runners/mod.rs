@@ -2,6 +2,7 @@ pub mod bls;
 pub mod epoch_processing;
 pub mod finality;
 pub mod fork;
+pub mod fork_choice;
 pub mod genesis;
 pub mod kzg;
 pub mod light_client;
@@ -55,6 +55,9 @@ make_load_test!(voluntary_exit);
 make_load_test!(sync_aggregate);
 make_load_test!(withdrawals, execution_payload);
 make_load_test!(bls_to_execution_change, address_change);
+make_load_test!(deposit_request);
+make_load_test!(withdrawal_request);
+make_load_test!(consolidation_request);
 
 #[derive(Deserialize)]
 struct ExecutionValidity {
@@ -107,20 +110,54 @@ where
 pub fn dispatch(test: &TestCase) -> Result<(), Error> {
     match test.meta.handler.0.as_str() {
         "attestation" => {
-            gen_match_for_all! {
+            gen_match_for! {
                 test,
-                load_attestation_test,
-                |(pre, post, operation): (spec::BeaconState, Option<spec::BeaconState>, spec::Attestation), context| {
-                    run_test(pre, post, operation, context, |state, operation, context| { spec::process_attestation(state, operation, context)} )
+                (mainnet, phase0),
+                (mainnet, altair),
+                (mainnet, bellatrix),
+                (mainnet, capella),
+                (mainnet, deneb),
+                (mainnet, electra),
+                (minimal, phase0),
+                (minimal, altair),
+                (minimal, bellatrix),
+                (minimal, capella),
+                (minimal, deneb),
+                (minimal, electra)
+                {
+                    gen_exec! {
+                        test,
+                        load_attestation_test,
+                        |(pre, post, operation): (spec::BeaconState, Option<spec::BeaconState>, spec::Attestation), context| {
+                            run_test(pre, post, operation, context, |state, operation, context| { spec::process_attestation(state, operation, context)} )
+                        }
+                    }
                 }
             }
         }
         "attester_slashing" => {
-            gen_match_for_all! {
+            gen_match_for! {
                 test,
-                load_attester_slashing_test,
-                |(pre, post, operation): (spec::BeaconState, Option<spec::BeaconState>, spec::AttesterSlashing), context| {
-                    run_test(pre, post, operation, context, spec::process_attester_slashing)
+                (mainnet, phase0),
+                (mainnet, altair),
+                (mainnet, bellatrix),
+                (mainnet, capella),
+                (mainnet, deneb),
+                (mainnet, electra),
+                (minimal, phase0),
+                (minimal, altair),
+                (minimal, bellatrix),
+                (minimal, capella),
+                (minimal, deneb),
+                (minimal, electra)
+                {
+                    gen_exec! {
+                        test,
+                        load_attester_slashing_test,
+                        |(pre, post, operation): (spec::BeaconState, Option<spec::BeaconState>, spec::AttesterSlashing), context| {
+                            run_test(pre, post, operation, context, spec::process_attester_slashing)
+                        }
+                    }
                 }
             }
         }
@@ -209,8 +246,10 @@ pub fn dispatch(test: &TestCase) -> Result<(), Error> {
                 test,
                 (mainnet, capella),
                 (mainnet, deneb),
+                (mainnet, electra),
                 (minimal, capella),
-                (minimal, deneb)
+                (minimal, deneb),
+                (minimal, electra)
                 {
                     gen_exec! {
                         test,
@@ -240,6 +279,57 @@ pub fn dispatch(test: &TestCase) -> Result<(), Error> {
                 }
             }
         }
+        "deposit_request" => {
+            gen_match_for! {
+                test,
+                (mainnet, electra),
+                (minimal, electra)
+                {
+                    gen_exec! {
+                        test,
+                        load_deposit_request_test,
+                        |(pre, post, operation): (spec::BeaconState, Option<spec::BeaconState>, spec::DepositRequest), context| {
+                            run_test(pre, post, operation, context, |state, operation, _| {
+                                spec::process_deposit_request(state, operation);
+                                Ok(())
+                            })
+                        }
+                    }
+                }
+            }
+        }
+        "withdrawal_request" => {
+            gen_match_for! {
+                test,
+                (mainnet, electra),
+                (minimal, electra)
+                {
+                    gen_exec! {
+                        test,
+                        load_withdrawal_request_test,
+                        |(pre, post, operation): (spec::BeaconState, Option<spec::BeaconState>, spec::WithdrawalRequest), context| {
+                            run_test(pre, post, operation, context, spec::process_withdrawal_request)
+                        }
+                    }
+                }
+            }
+        }
+        "consolidation_request" => {
+            gen_match_for! {
+                test,
+                (mainnet, electra),
+                (minimal, electra)
+                {
+                    gen_exec! {
+                        test,
+                        load_consolidation_request_test,
+                        |(pre, post, operation): (spec::BeaconState, Option<spec::BeaconState>, spec::ConsolidationRequest), context| {
+                            run_test(pre, post, operation, context, spec::process_consolidation_request)
+                        }
+                    }
+                }
+            }
+        }
         handler => unreachable!("no tests for {handler}"),
     }
 }
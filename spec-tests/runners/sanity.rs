@@ -1,10 +1,10 @@
 use crate::{
     runners::{
-        gen_exec, gen_match_for_all,
+        gen_exec, gen_match_for,
         utils::{load_blocks_test, run_blocks_test},
     },
     test_case::TestCase,
-    test_utils::{load_snappy_ssz, load_yaml, Error},
+    test_utils::{load_snappy_ssz, load_yaml, state_mismatch, Error},
 };
 use ethereum_consensus::state_transition::Validation;
 
@@ -24,25 +24,59 @@ fn load_test<S: ssz_rs::Deserialize>(test_case_path: &str) -> (S, S, u64) {
 pub fn dispatch(test: &TestCase) -> Result<(), Error> {
     match test.meta.handler.0.as_str() {
         "blocks" => {
-            gen_match_for_all! {
+            gen_match_for! {
                 test,
-                load_blocks_test,
-                |(pre, post, blocks): (spec::BeaconState, Option<spec::BeaconState>, Vec<spec::SignedBeaconBlock>), context| {
-                    run_blocks_test(pre, post, blocks, context, |state, signed_block, context| { spec::state_transition(state, signed_block, Validation::Enabled, context) })
+                (mainnet, phase0),
+                (mainnet, altair),
+                (mainnet, bellatrix),
+                (mainnet, capella),
+                (mainnet, deneb),
+                (mainnet, electra),
+                (minimal, phase0),
+                (minimal, altair),
+                (minimal, bellatrix),
+                (minimal, capella),
+                (minimal, deneb),
+                (minimal, electra)
+                {
+                    gen_exec! {
+                        test,
+                        load_blocks_test,
+                        |(pre, post, blocks): (spec::BeaconState, Option<spec::BeaconState>, Vec<spec::SignedBeaconBlock>), context| {
+                            run_blocks_test(pre, post, blocks, context, |state, signed_block, context| { spec::state_transition(state, signed_block, Validation::Enabled, context) })
+                        }
+                    }
                 }
             }
         }
         "slots" => {
-            gen_match_for_all! {
+            gen_match_for! {
                 test,
-                load_test,
-                |(mut pre, post, slots): (spec::BeaconState, spec::BeaconState, u64), context| {
-                    let target_slot = pre.slot + slots;
-                    spec::process_slots(&mut pre, target_slot, context)?;
-                    if pre != post {
-                        Err(Error::InvalidState)
-                    } else {
-                        Ok(())
+                (mainnet, phase0),
+                (mainnet, altair),
+                (mainnet, bellatrix),
+                (mainnet, capella),
+                (mainnet, deneb),
+                (mainnet, electra),
+                (minimal, phase0),
+                (minimal, altair),
+                (minimal, bellatrix),
+                (minimal, capella),
+                (minimal, deneb),
+                (minimal, electra)
+                {
+                    gen_exec! {
+                        test,
+                        load_test,
+                        |(mut pre, post, slots): (spec::BeaconState, spec::BeaconState, u64), context| {
+                            let target_slot = pre.slot + slots;
+                            spec::process_slots(&mut pre, target_slot, context)?;
+                            if pre != post {
+                                Err(state_mismatch(&pre, &post))
+                            } else {
+                                Ok(())
+                            }
+                        }
                     }
                 }
             }
@@ -1,6 +1,7 @@
 use crate::{
     runners::{gen_exec, gen_match_for, gen_match_for_all},
     test_case::TestCase,
+    test_meta::Fork,
     test_utils::{load_snappy_ssz_bytes, load_yaml, Error},
 };
 use ethereum_consensus::{primitives::Root, state_transition::Context};
@@ -35,6 +36,33 @@ fn run_test<T: ssz_rs::SimpleSerialize>(
     Ok(())
 }
 
+// Electra changes the bounds of the attestation and slashing containers, so only those are run
+// for Electra so far.
+macro_rules! gen_electra {
+    ($test_case:expr, $($handler:ident),*) => {
+        let result = match $test_case.meta.handler.0.as_str() {
+            $(
+                stringify!($handler) => gen_match_for! {
+                    $test_case,
+                    (mainnet, electra),
+                    (minimal, electra)
+                    {
+                        gen_exec! {
+                            $test_case, load_test, run_test::<spec::$handler>
+                        }
+                    }
+                },
+            )*
+            _ => Err(Error::InternalContinue),
+        };
+        match result {
+            Ok(()) => return Ok(()),
+            Err(Error::InternalContinue) => {},
+            Err(err) => return Err(err)
+        }
+    };
+}
+
 macro_rules! gen_deneb_and_later {
     ($test_case:expr, $($handler:ident),*) => {
         let result = match $test_case.meta.handler.0.as_str() {
@@ -165,6 +193,16 @@ macro_rules! gen_match {
 }
 
 pub fn dispatch(test: &TestCase) -> Result<(), Error> {
+    if matches!(test.meta.fork, Fork::Electra) {
+        gen_electra! {
+            test,
+            Attestation,
+            AttesterSlashing,
+            IndexedAttestation,
+            BeaconBlockBody
+        }
+    }
+
     gen_deneb_and_later! {
         test,
         BlobSidecar,
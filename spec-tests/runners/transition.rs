@@ -63,6 +63,7 @@ fn set_fork_epochs(meta: &Meta, context: &mut Context) {
     context.bellatrix_fork_epoch = Epoch::MAX;
     context.capella_fork_epoch = Epoch::MAX;
     context.deneb_fork_epoch = Epoch::MAX;
+    context.electra_fork_epoch = Epoch::MAX;
 
     match meta.post_fork.as_ref() {
         "altair" => {
@@ -83,6 +84,13 @@ fn set_fork_epochs(meta: &Meta, context: &mut Context) {
             context.capella_fork_epoch = 0;
             context.deneb_fork_epoch = meta.fork_epoch;
         }
+        "electra" => {
+            context.altair_fork_epoch = 0;
+            context.bellatrix_fork_epoch = 0;
+            context.capella_fork_epoch = 0;
+            context.deneb_fork_epoch = 0;
+            context.electra_fork_epoch = meta.fork_epoch;
+        }
         _ => todo!(),
     }
 }
@@ -207,6 +215,35 @@ pub fn dispatch(test: &TestCase) -> Result<(), Error> {
                         }
                     }
                 }
+                Fork::Electra => {
+                    use ethereum_consensus::{
+                        deneb::mainnet as pre_spec, electra::mainnet as spec,
+                    };
+                    gen_exec! {
+                        test,
+                        load_test,
+                        | (pre, expected, pre_blocks, post_blocks, meta): (pre_spec::BeaconState, spec::BeaconState, Vec<pre_spec::SignedBeaconBlock>, Vec<spec::SignedBeaconBlock>, Meta), context: &Context| {
+                            assert_eq!(meta.post_fork, "electra");
+                            let mut context = context.clone();
+                            set_fork_epochs(&meta, &mut context);
+                            let mut executor = state_transition::mainnet::Executor::new(BeaconState::Deneb(pre), context);
+                            for block in pre_blocks.into_iter() {
+                                let block = SignedBeaconBlock::Deneb(block);
+                                executor.apply_block(&block)?;
+                            }
+                            for block in post_blocks.into_iter() {
+                                let block = SignedBeaconBlock::Electra(block);
+                                executor.apply_block(&block)?;
+                            }
+                            let post = executor.state.electra().unwrap();
+                            if post != &expected {
+                                Err(Error::InvalidState)
+                            } else {
+                                Ok(())
+                            }
+                        }
+                    }
+                }
                 _ => todo!(),
             },
             Config::Minimal => match test.meta.fork {
@@ -326,6 +363,35 @@ pub fn dispatch(test: &TestCase) -> Result<(), Error> {
                         }
                     }
                 }
+                Fork::Electra => {
+                    use ethereum_consensus::{
+                        deneb::minimal as pre_spec, electra::minimal as spec,
+                    };
+                    gen_exec! {
+                        test,
+                        load_test,
+                        | (pre, expected, pre_blocks, post_blocks, meta): (pre_spec::BeaconState, spec::BeaconState, Vec<pre_spec::SignedBeaconBlock>, Vec<spec::SignedBeaconBlock>, Meta), context: &Context| {
+                            assert_eq!(meta.post_fork, "electra");
+                            let mut context = context.clone();
+                            set_fork_epochs(&meta, &mut context);
+                            let mut executor = state_transition::minimal::Executor::new(BeaconState::Deneb(pre), context);
+                            for block in pre_blocks.into_iter() {
+                                let block = SignedBeaconBlock::Deneb(block);
+                                executor.apply_block(&block)?;
+                            }
+                            for block in post_blocks.into_iter() {
+                                let block = SignedBeaconBlock::Electra(block);
+                                executor.apply_block(&block)?;
+                            }
+                            let post = executor.state.electra().unwrap();
+                            if post != &expected {
+                                Err(Error::InvalidState)
+                            } else {
+                                Ok(())
+                            }
+                        }
+                    }
+                }
                 _ => todo!(),
             },
             config => unreachable!("no tests for {config:?}"),
@@ -1,6 +1,6 @@
-use crate::test_utils::{load_snappy_ssz, load_yaml, Error};
+use crate::test_utils::{load_snappy_ssz, load_yaml, state_mismatch, Error};
 use ethereum_consensus::{state_transition::Context, Error as SpecError};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize)]
 struct BlocksMeta {
@@ -30,7 +30,7 @@ pub(crate) fn load_blocks_test<S: ssz_rs::Deserialize, B: ssz_rs::Deserialize>(
     (pre, post, blocks)
 }
 
-pub(crate) fn run_blocks_test<S: Eq, B, F>(
+pub(crate) fn run_blocks_test<S: Eq + Serialize, B, F>(
     mut pre: S,
     post: Option<S>,
     mut blocks: Vec<B>,
@@ -49,7 +49,7 @@ where
         if pre == post {
             Ok(())
         } else {
-            Err(Error::InvalidState)
+            Err(state_mismatch(&pre, &post))
         }
     } else if result.is_err() {
         Ok(())
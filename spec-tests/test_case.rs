@@ -1,7 +1,7 @@
 use crate::{
     runners::{
-        bls, epoch_processing, finality, fork, genesis, kzg, light_client, merkle_proof,
-        operations, random, rewards, sanity, shuffling, ssz_static, transition,
+        bls, epoch_processing, finality, fork, fork_choice, genesis, kzg, light_client,
+        merkle_proof, operations, random, rewards, sanity, shuffling, ssz_static, transition,
     },
     test_meta::TestMeta,
     Config, Context,
@@ -38,7 +38,7 @@ impl TestCase {
             Bls => bls::dispatch(self),
             EpochProcessing => epoch_processing::dispatch(self),
             Finality => finality::dispatch(self),
-            ForkChoice => todo!(),
+            ForkChoice => fork_choice::dispatch(self),
             Fork => fork::dispatch(self),
             Genesis => genesis::dispatch(self),
             Operations => operations::dispatch(self),
@@ -33,8 +33,12 @@ pub enum Fork {
     Bellatrix,
     Capella,
     Deneb,
+    Electra,
+    Fulu,
     Eip6110,
     Whisk,
+    // any other feature under development in the spec
+    Feature,
 }
 
 impl From<&str> for Fork {
@@ -45,8 +49,11 @@ impl From<&str> for Fork {
             "bellatrix" => Self::Bellatrix,
             "capella" => Self::Capella,
             "deneb" => Self::Deneb,
+            "electra" => Self::Electra,
+            "fulu" => Self::Fulu,
             "eip6110" => Self::Eip6110,
             "whisk" => Self::Whisk,
+            fork if fork.starts_with("eip") => Self::Feature,
             fork => panic!("unsupported fork: {fork:?}"),
         }
     }
@@ -83,7 +90,7 @@ pub enum Runner {
 
 impl Runner {
     pub fn should_ignore(&self) -> bool {
-        matches!(self, Self::ForkChoice | Self::Sync)
+        matches!(self, Self::Sync)
     }
 
     // Do not collect these tests.
@@ -204,9 +211,45 @@ impl TestMeta {
     // If ignored, a test could be implemented in the future but is currently not.
     pub fn should_ignore(&self) -> bool {
         let ignored_runner = self.runner.should_ignore();
-        let ignored_handler =
-            matches!(self.runner, Runner::LightClient) && self.handler.0 != "single_merkle_proof";
-        ignored_runner || ignored_handler
+        let ignored_handler = match self.runner {
+            // proposer reorgs are not covered by the fork choice store
+            Runner::ForkChoice => matches!(
+                self.handler.0.as_str(),
+                "get_proposer_head" | "should_override_forkchoice_update"
+            ),
+            _ => false,
+        };
+        // only epoch processing, genesis, the upgrade, whole blocks, the attestation, slashing and
+        // withdrawal operations, the attestation and slashing containers and the execution layer
+        // requests are covered for electra so far
+        let electra_operations = matches!(self.runner, Runner::Operations) &&
+            matches!(
+                self.handler.0.as_str(),
+                "attestation" |
+                    "attester_slashing" |
+                    "withdrawals" |
+                    "deposit_request" |
+                    "withdrawal_request" |
+                    "consolidation_request"
+            );
+        let electra_containers = matches!(self.runner, Runner::SszStatic) &&
+            matches!(
+                self.handler.0.as_str(),
+                "Attestation" | "AttesterSlashing" | "IndexedAttestation" | "BeaconBlockBody"
+            );
+        let ignored_fork = matches!(self.fork, Fork::Electra) &&
+            !matches!(
+                self.runner,
+                Runner::EpochProcessing |
+                    Runner::Genesis |
+                    Runner::Fork |
+                    Runner::Sanity |
+                    Runner::Transition |
+                    Runner::Finality
+            ) &&
+            !electra_operations &&
+            !electra_containers;
+        ignored_runner || ignored_handler || ignored_fork
     }
 
     // Skip collecting this test if `true`.
@@ -214,7 +257,8 @@ impl TestMeta {
     // as it is not currently supported and there is no intention to support in this repo.
     pub fn should_skip(&self) -> bool {
         let skipped_runner = self.runner.should_skip();
-        let skipped_forks = matches!(self.fork, Fork::Eip6110 | Fork::Whisk);
+        let skipped_forks =
+            matches!(self.fork, Fork::Fulu | Fork::Eip6110 | Fork::Whisk | Fork::Feature);
         skipped_runner | skipped_forks
     }
 }
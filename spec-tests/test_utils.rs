@@ -1,16 +1,32 @@
 use ethereum_consensus::Error as SpecError;
-use serde::Deserialize;
-use std::{fs::File, io::Read, path::Path};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
 use thiserror::Error;
 
+// Number of differing field paths to report when a state does not match the expected state
+const MAX_REPORTED_DIFFERENCES: usize = 16;
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error(transparent)]
     Spec(#[from] SpecError),
     #[error("state did not match expected")]
     InvalidState,
+    #[error(
+        "state did not match expected; first differing fields: {}; {dump}",
+        .differences.join(", ")
+    )]
+    StateMismatch { differences: Vec<String>, dump: String },
     #[error("test was expected to error but did not")]
     Expected,
+    #[error("check {check} expected {expected} but found {found}")]
+    CheckMismatch { check: &'static str, expected: String, found: String },
     #[error("internal error; you should not see this")]
     InternalContinue,
 }
@@ -47,3 +63,76 @@ pub fn load_snappy_ssz<T: ssz_rs::Deserialize>(path: &str) -> Option<T> {
 
     result.ok()
 }
+
+fn collect_differences(
+    path: &str,
+    expected: &Value,
+    actual: &Value,
+    differences: &mut Vec<String>,
+) {
+    if differences.len() >= MAX_REPORTED_DIFFERENCES {
+        return
+    }
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, expected_value) in expected {
+                let path = if path.is_empty() { key.clone() } else { format!("{path}.{key}") };
+                match actual.get(key) {
+                    Some(actual_value) => {
+                        collect_differences(&path, expected_value, actual_value, differences)
+                    }
+                    None => differences.push(path),
+                }
+            }
+            for key in actual.keys().filter(|key| !expected.contains_key(*key)) {
+                differences.push(format!("{path}.{key}"));
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) => {
+            for (i, (expected_value, actual_value)) in expected.iter().zip(actual).enumerate() {
+                collect_differences(
+                    &format!("{path}[{i}]"),
+                    expected_value,
+                    actual_value,
+                    differences,
+                );
+            }
+            if expected.len() != actual.len() {
+                differences.push(format!("{path}.len()"));
+            }
+        }
+        (expected, actual) => {
+            if expected != actual {
+                differences.push(path.to_string());
+            }
+        }
+    }
+    differences.truncate(MAX_REPORTED_DIFFERENCES);
+}
+
+fn dump_states(expected: &Value, actual: &Value) -> std::io::Result<PathBuf> {
+    static DUMP_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    let id = DUMP_COUNT.fetch_add(1, Ordering::Relaxed);
+    let dir = std::env::temp_dir().join(format!("spec-tests-{}-{id}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    for (name, state) in [("expected.json", expected), ("actual.json", actual)] {
+        let file = File::create(dir.join(name))?;
+        serde_json::to_writer_pretty(file, state)?;
+    }
+    Ok(dir)
+}
+
+/// Builds the error for a post-state mismatch, naming the first differing field paths
+/// (e.g. `balances[3]`) and writing both states to a temporary directory for inspection.
+pub fn state_mismatch<S: Serialize>(actual: &S, expected: &S) -> Error {
+    let expected = serde_json::to_value(expected).expect("can serialize state");
+    let actual = serde_json::to_value(actual).expect("can serialize state");
+    let mut differences = vec![];
+    collect_differences("", &expected, &actual, &mut differences);
+    let dump = match dump_states(&expected, &actual) {
+        Ok(dir) => format!("states written to {}", dir.display()),
+        Err(err) => format!("could not write states: {err}"),
+    };
+    Error::StateMismatch { differences, dump }
+}